@@ -0,0 +1,7 @@
+-- API key usage tracking + prefix lookup.
+-- Keys are stored as SHA-256(key) in key_hash; the prefix ("atl_" + 8 hex)
+-- narrows the lookup before the hash comparison.
+
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS last_used_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_prefix_hash ON api_keys (prefix, key_hash);
@@ -0,0 +1,10 @@
+-- Per-user CLI config for `atlas configure sync`.
+-- One row per Clerk user; `version` increments on every write so clients
+-- can detect a concurrent push. Secrets never reach this table.
+
+CREATE TABLE IF NOT EXISTS user_configs (
+    user_id    TEXT PRIMARY KEY,
+    config     JSONB NOT NULL,
+    version    INTEGER NOT NULL DEFAULT 1,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,6 @@
+-- API key scopes, checked per route group by apiKeyAuth (src/lib/scopes.ts).
+-- Keys created before scopes existed keep full access.
+
+ALTER TABLE api_keys
+    ADD COLUMN IF NOT EXISTS scopes TEXT[] NOT NULL
+    DEFAULT ARRAY['read', 'trade-analytics', 'config-sync'];
@@ -3,13 +3,23 @@ import { corsMiddleware } from "./middleware/cors.ts";
 import { errorHandler } from "./middleware/error.ts";
 import { clerkAuth } from "./middleware/auth.ts";
 import { apiKeyAuth } from "./middleware/apikey.ts";
-import { health } from "./routes/health.ts";
+import { withCache } from "./middleware/cache.ts";
+import { health, readiness } from "./routes/health.ts";
+import { metrics } from "./routes/metrics.ts";
+import { ws, wsHandler } from "./routes/ws.ts";
 import { keys } from "./routes/keys.ts";
 import { rpc } from "./routes/atlas-os/rpc.ts";
-import { dex } from "./routes/atlas-os/market/dex/index.ts";
+import { dex, DEX_CACHE_RULES } from "./routes/atlas-os/market/dex/index.ts";
 import { compute } from "./routes/atlas-os/compute/index.ts";
 import { zerox } from "./routes/atlas-os/0x/index.ts";
+import { morpho } from "./routes/atlas-os/morpho/index.ts";
+import { hl, HL_CACHE_RULES } from "./routes/atlas-os/hl/index.ts";
 import { me } from "./routes/atlas-os/me.ts";
+import { config } from "./routes/atlas-os/config.ts";
+import { keys as keySelf } from "./routes/atlas-os/keys.ts";
+import { connectDb, db } from "./lib/db.ts";
+import { cached, coingeckoCheck, postgresCheck } from "./lib/readiness.ts";
+import { apiKeys } from "./lib/apikeys.ts";
 
 const app = new Hono();
 
@@ -18,34 +28,64 @@ app.use("*", corsMiddleware);
 app.use("*", errorHandler);
 
 // ── Public ────────────────────────────────────────────
-app.route("/health", health);
+app.route("/healthz", health);
+app.route("/health", health); // pre-split path, still probed by older CLIs
+app.route(
+    "/readyz",
+    readiness({
+        postgres: postgresCheck(db),
+        // At most one upstream ping a minute, however often /readyz is probed
+        coingecko: cached(coingeckoCheck(process.env["COINGECKO_API_KEY"]), 60_000),
+    })
+);
+app.route("/metrics", metrics);
 
 // ── Dashboard management (Clerk JWT) ─────────────────
 app.use("/keys/*", clerkAuth);
 app.route("/keys", keys);
 
 // ── Atlas OS — CLI / SDK (Atlas API key) ─────────────
+// apiKeyAuth also checks the key's scopes per route group (lib/scopes.ts)
+const requireApiKey = apiKeyAuth(apiKeys);
 const atlasOs = new Hono();
-atlasOs.use("/rpc/*", apiKeyAuth);
+// /rpc/v2/:apiKey/:chain carries the key in the path and validates it inline
+atlasOs.use("/rpc/*", (ctx, next) =>
+    ctx.req.path.includes("/rpc/v2/") ? next() : requireApiKey(ctx, next)
+);
 atlasOs.route("/rpc", rpc);
-atlasOs.use("/dex/*", apiKeyAuth);
-atlasOs.route("/dex", dex);
-atlasOs.use("/compute/*", apiKeyAuth);
+atlasOs.use("/dex/*", requireApiKey);
+atlasOs.route("/dex", withCache(dex, "/dex", DEX_CACHE_RULES));
+atlasOs.use("/compute/*", requireApiKey);
 atlasOs.route("/compute", compute);
-atlasOs.use("/0x/*", apiKeyAuth);
+atlasOs.use("/0x/*", requireApiKey);
 atlasOs.route("/0x", zerox);
-atlasOs.use("/me", apiKeyAuth);
+atlasOs.use("/morpho/*", requireApiKey);
+atlasOs.route("/morpho", morpho);
+atlasOs.use("/hl/*", requireApiKey);
+atlasOs.route("/hl", withCache(hl, "/hl", HL_CACHE_RULES));
+atlasOs.use("/me", requireApiKey);
 atlasOs.route("/me", me);
+atlasOs.use("/config", requireApiKey);
+atlasOs.route("/config", config);
+atlasOs.use("/keys/*", requireApiKey);
+atlasOs.route("/keys", keySelf);
 
 app.route("/atlas-os", atlasOs);
 
+// ── Streaming (Atlas API key) ────────────────────────
+app.use("/ws/*", requireApiKey);
+app.route("/ws", ws);
+
 // 404 fallback
 app.notFound((ctx) => ctx.json({ error: "Not found" }, 404));
 
+await connectDb();
+
 const port = parseInt(process.env["PORT"] ?? "3001", 10);
 console.log(`[atlas-backend] Starting on http://localhost:${port}`);
 
 export default {
     port,
     fetch: app.fetch,
+    websocket: wsHandler,
 };
@@ -0,0 +1,43 @@
+import { db } from "./db.ts";
+import { redis } from "./redis.ts";
+import { hashKey, keyPrefix } from "./keygen.ts";
+import { rateLimitFromEnv, tokenBucket } from "./ratelimit.ts";
+import type { ApiKeyRecord, ApiKeyStore } from "../middleware/apikey.ts";
+
+/** Only bump last_used_at once per key per window — not on every request. */
+const LAST_USED_WINDOW_SECS = 60;
+
+/**
+ * Look up a raw `atl_xxx` key by prefix + SHA-256 hash.
+ * Returns null for unknown keys. Records last_used_at on success.
+ */
+export async function resolveApiKey(rawKey: string): Promise<ApiKeyRecord | null> {
+    const { rows } = await db.query<ApiKeyRecord>(
+        "SELECT id, user_id, scopes FROM api_keys WHERE prefix = $1 AND key_hash = $2",
+        [keyPrefix(rawKey), hashKey(rawKey)]
+    );
+    const row = rows[0];
+    if (!row) return null;
+
+    void touchLastUsed(row.id);
+    return row;
+}
+
+async function touchLastUsed(keyId: string): Promise<void> {
+    try {
+        const fresh = await redis.set(
+            `atlas:keys:used:${keyId}`, "1", "EX", LAST_USED_WINDOW_SECS, "NX"
+        );
+        if (fresh) {
+            await db.query("UPDATE api_keys SET last_used_at = now() WHERE id = $1", [keyId]);
+        }
+    } catch (err) {
+        console.error("[apikey] last_used_at update failed:", (err as Error).message);
+    }
+}
+
+/** Per-key rate limit, shared by every backend instance through Redis. */
+export const takeToken = tokenBucket(redis, rateLimitFromEnv());
+
+/** Keys in Postgres, rate limited in Redis. */
+export const apiKeys: ApiKeyStore = { resolve: resolveApiKey, takeToken };
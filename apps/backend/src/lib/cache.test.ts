@@ -0,0 +1,77 @@
+import { describe, expect, test } from "bun:test";
+import { ResponseCache, type CachedResponse } from "./cache.ts";
+
+const ok = (body: string): CachedResponse => ({ status: 200, contentType: "application/json", body });
+
+/** Mock upstream: counts calls, resolves after `delayMs`. */
+function mockUpstream(responses: (CachedResponse | Error)[], delayMs = 5) {
+    let calls = 0;
+    const fn = async () => {
+        const next = responses[Math.min(calls, responses.length - 1)]!;
+        calls++;
+        await Bun.sleep(delayMs);
+        if (next instanceof Error) throw next;
+        return next;
+    };
+    return { fn, calls: () => calls };
+}
+
+describe("ResponseCache", () => {
+    test("concurrent identical requests make one upstream call", async () => {
+        const cache = new ResponseCache();
+        const upstream = mockUpstream([ok("trending-v1")]);
+        const policy = { ttlMs: 30_000 };
+
+        const results = await Promise.all(
+            Array.from({ length: 10 }, () => cache.fetch("/trending", policy, upstream.fn)),
+        );
+
+        expect(upstream.calls()).toBe(1);
+        expect(results.every((r) => r.value.body === "trending-v1")).toBe(true);
+
+        const again = await cache.fetch("/trending", policy, upstream.fn);
+        expect(again.state).toBe("hit");
+        expect(upstream.calls()).toBe(1);
+    });
+
+    test("serves stale within the SWR window and refreshes in the background", async () => {
+        let now = 0;
+        const cache = new ResponseCache(100, () => now);
+        const upstream = mockUpstream([ok("v1"), ok("v2")], 1);
+        const policy = { ttlMs: 1_000, staleMs: 10_000 };
+
+        expect((await cache.fetch("/pools/eth", policy, upstream.fn)).state).toBe("miss");
+        now = 2_000;
+        const stale = await cache.fetch("/pools/eth", policy, upstream.fn);
+        expect(stale.state).toBe("stale");
+        expect(stale.value.body).toBe("v1");
+
+        await Bun.sleep(10);
+        const fresh = await cache.fetch("/pools/eth", policy, upstream.fn);
+        expect(fresh.state).toBe("hit");
+        expect(fresh.value.body).toBe("v2");
+        expect(upstream.calls()).toBe(2);
+    });
+
+    test("upstream 429 after expiry serves the last good body", async () => {
+        let now = 0;
+        const cache = new ResponseCache(100, () => now);
+        const limited: CachedResponse = { status: 429, contentType: "application/json", body: "{}" };
+        const upstream = mockUpstream([ok("v1"), limited], 1);
+        const policy = { ttlMs: 1_000 };
+
+        await cache.fetch("/coins/bitcoin", policy, upstream.fn);
+        now = 60_000;
+        const res = await cache.fetch("/coins/bitcoin", policy, upstream.fn);
+        expect(res.state).toBe("stale");
+        expect(res.value.body).toBe("v1");
+        expect(cache.stats().upstream_errors).toBe(1);
+    });
+
+    test("errors propagate when there is nothing cached", async () => {
+        const cache = new ResponseCache();
+        const upstream = mockUpstream([new Error("boom")]);
+        await expect(cache.fetch("/global", { ttlMs: 1_000 }, upstream.fn)).rejects.toThrow("boom");
+        expect(cache.stats().entries).toBe(0);
+    });
+});
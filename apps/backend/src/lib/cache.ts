@@ -0,0 +1,139 @@
+/**
+ * In-memory response cache for upstream market data (CoinGecko et al.).
+ *
+ * - TTL per entry; identical concurrent misses share one upstream call.
+ * - Stale-while-revalidate: within `staleMs` after expiry the stale body is
+ *   served immediately and refreshed in the background.
+ * - An upstream failure (throw, 429 or 5xx) serves the last good body if one
+ *   exists, however old, instead of erroring.
+ *
+ * Only 2xx responses are stored. Process-local by design — each backend
+ * instance keeps its own copy.
+ */
+
+export interface CachedResponse {
+    status: number;
+    contentType: string;
+    body: string;
+}
+
+export type CacheState = "hit" | "miss" | "stale";
+
+export interface CachePolicy {
+    ttlMs: number;
+    /** Window after expiry in which stale data is served while refreshing. */
+    staleMs?: number;
+}
+
+interface Entry {
+    value: CachedResponse;
+    storedAt: number;
+}
+
+export interface CacheStats {
+    hits: number;
+    misses: number;
+    stale: number;
+    upstream_calls: number;
+    upstream_errors: number;
+    entries: number;
+}
+
+const DEFAULT_MAX_ENTRIES = 5_000;
+
+export class ResponseCache {
+    private entries = new Map<string, Entry>();
+    private inflight = new Map<string, Promise<CachedResponse>>();
+    private counters = { hits: 0, misses: 0, stale: 0, upstream_calls: 0, upstream_errors: 0 };
+
+    constructor(
+        private readonly maxEntries = DEFAULT_MAX_ENTRIES,
+        private readonly now: () => number = Date.now,
+    ) {}
+
+    async fetch(
+        key: string,
+        policy: CachePolicy,
+        upstream: () => Promise<CachedResponse>,
+    ): Promise<{ value: CachedResponse; state: CacheState }> {
+        const entry = this.entries.get(key);
+        const age = entry ? this.now() - entry.storedAt : Infinity;
+
+        if (entry && age < policy.ttlMs) {
+            this.counters.hits++;
+            return { value: entry.value, state: "hit" };
+        }
+
+        if (entry && age < policy.ttlMs + (policy.staleMs ?? 0)) {
+            this.counters.stale++;
+            // Errors are already counted; the stale body stays in place
+            this.refresh(key, upstream).catch(() => {});
+            return { value: entry.value, state: "stale" };
+        }
+
+        try {
+            const value = await this.refresh(key, upstream);
+            this.counters.misses++;
+            return { value, state: "miss" };
+        } catch (err) {
+            if (entry) {
+                this.counters.stale++;
+                return { value: entry.value, state: "stale" };
+            }
+            throw err;
+        }
+    }
+
+    stats(): CacheStats {
+        return { ...this.counters, entries: this.entries.size };
+    }
+
+    clear(): void {
+        this.entries.clear();
+    }
+
+    /** One upstream call per key at a time; stores the result on success. */
+    private refresh(key: string, upstream: () => Promise<CachedResponse>): Promise<CachedResponse> {
+        const pending = this.inflight.get(key);
+        if (pending) return pending;
+
+        const call = (async () => {
+            this.counters.upstream_calls++;
+            try {
+                const value = await upstream();
+                if (value.status === 429 || value.status >= 500) {
+                    throw new UpstreamStatusError(value);
+                }
+                if (value.status >= 200 && value.status < 300) this.store(key, value);
+                return value;
+            } catch (err) {
+                this.counters.upstream_errors++;
+                throw err;
+            } finally {
+                this.inflight.delete(key);
+            }
+        })();
+        this.inflight.set(key, call);
+        return call;
+    }
+
+    private store(key: string, value: CachedResponse): void {
+        // Re-insert so Map order tracks recency; evict the oldest past the cap
+        this.entries.delete(key);
+        this.entries.set(key, { value, storedAt: this.now() });
+        if (this.entries.size > this.maxEntries) {
+            const oldest = this.entries.keys().next().value;
+            if (oldest !== undefined) this.entries.delete(oldest);
+        }
+    }
+}
+
+/** Upstream answered, but with a status we would rather hide behind stale data. */
+export class UpstreamStatusError extends Error {
+    constructor(readonly response: CachedResponse) {
+        super(`Upstream returned ${response.status}`);
+    }
+}
+
+/** Shared instance used by the cache middleware and /metrics. */
+export const responseCache = new ResponseCache();
@@ -0,0 +1,26 @@
+import { describe, expect, test } from "bun:test";
+import { mapWithLimit } from "./concurrency.ts";
+
+describe("mapWithLimit", () => {
+    test("never runs more than `limit` calls at once and keeps order", async () => {
+        let inFlight = 0;
+        let peak = 0;
+        const results = await mapWithLimit([5, 1, 4, 2, 3, 1, 2], 3, async (ms, i) => {
+            inFlight++;
+            peak = Math.max(peak, inFlight);
+            await Bun.sleep(ms);
+            inFlight--;
+            return i;
+        });
+
+        expect(peak).toBe(3);
+        expect(results).toEqual([0, 1, 2, 3, 4, 5, 6]);
+    });
+
+    test("empty input makes no calls", async () => {
+        let calls = 0;
+        const results = await mapWithLimit([], 4, async () => calls++);
+        expect(results).toEqual([]);
+        expect(calls).toBe(0);
+    });
+});
@@ -0,0 +1,20 @@
+/**
+ * Map over `items` with at most `limit` calls of `fn` in flight. Results
+ * keep the input order; the first rejection rejects the whole call.
+ */
+export async function mapWithLimit<T, R>(
+    items: readonly T[],
+    limit: number,
+    fn: (item: T, index: number) => Promise<R>,
+): Promise<R[]> {
+    const results = new Array<R>(items.length);
+    let next = 0;
+    const worker = async () => {
+        while (next < items.length) {
+            const i = next++;
+            results[i] = await fn(items[i]!, i);
+        }
+    };
+    await Promise.all(Array.from({ length: Math.min(Math.max(1, limit), items.length) }, worker));
+    return results;
+}
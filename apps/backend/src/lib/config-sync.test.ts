@@ -0,0 +1,26 @@
+import { describe, expect, test } from "bun:test";
+import { isConfigObject, secretPaths } from "./config-sync.ts";
+
+describe("secretPaths", () => {
+    test("finds set secrets and ignores cleared ones", () => {
+        const config = {
+            system: { api_key: "atl_12345678abcdef", verbose: false },
+            notify: { secret: null, webhook: "https://example.com/hook" },
+        };
+        expect(secretPaths(config)).toEqual(["system.api_key", "notify.webhook"]);
+    });
+
+    test("a stripped config has none", () => {
+        expect(secretPaths({ system: { verbose: true }, trading: { confirm: true } })).toEqual([]);
+        expect(secretPaths({})).toEqual([]);
+    });
+});
+
+describe("isConfigObject", () => {
+    test("only accepts plain objects", () => {
+        expect(isConfigObject({ system: {} })).toBe(true);
+        expect(isConfigObject([])).toBe(false);
+        expect(isConfigObject(null)).toBe(false);
+        expect(isConfigObject("{}")).toBe(false);
+    });
+});
@@ -0,0 +1,27 @@
+/** Largest config body accepted by PUT /atlas-os/config, in bytes. */
+export const MAX_CONFIG_BYTES = 256 * 1024;
+
+/**
+ * Dotted paths the CLI treats as secrets. Pushes carrying a non-null value
+ * at any of them are rejected rather than stored.
+ */
+export const SECRET_PATHS = ["system.api_key", "notify.secret", "notify.webhook", "security.ens_rpc_url"];
+
+/** Secret paths that hold a value in `config`. */
+export function secretPaths(config: unknown): string[] {
+    return SECRET_PATHS.filter((path) => {
+        const value = path
+            .split(".")
+            .reduce<unknown>(
+                (node, part) =>
+                    node && typeof node === "object" ? (node as Record<string, unknown>)[part] : undefined,
+                config
+            );
+        return value !== undefined && value !== null;
+    });
+}
+
+/** Is `value` a plain JSON object (not an array or null)? */
+export function isConfigObject(value: unknown): value is Record<string, unknown> {
+    return typeof value === "object" && value !== null && !Array.isArray(value);
+}
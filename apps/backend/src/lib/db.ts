@@ -1,4 +1,5 @@
 import pg from "pg";
+import { retryWithBackoff } from "./readiness.ts";
 const { Pool } = pg;
 
 if (!process.env["DATABASE_URL"]) {
@@ -16,3 +17,27 @@ export const db = new Pool({
 db.on("error", (err: Error) => {
     console.error("[db] Unexpected pool error:", err.message);
 });
+
+/**
+ * Wait for Postgres at startup: `SELECT 1` with backoff (0.5s doubling to
+ * 10s) for DB_CONNECT_ATTEMPTS tries (default 10). Gives up without
+ * throwing — the server still starts and /readyz reports the database as
+ * failing until it comes back.
+ */
+export async function connectDb(): Promise<boolean> {
+    const attempts = Math.max(parseInt(process.env["DB_CONNECT_ATTEMPTS"] ?? "10", 10) || 10, 1);
+    try {
+        await retryWithBackoff(() => db.query("SELECT 1"), {
+            attempts,
+            minMs: 500,
+            maxMs: 10_000,
+            onRetry: (err, attempt, waitMs) =>
+                console.warn(`[db] Connect attempt ${attempt}/${attempts} failed: ${err.message}; retrying in ${waitMs}ms`),
+        });
+        console.log("[db] Connected");
+        return true;
+    } catch (err) {
+        console.error(`[db] Still unreachable after ${attempts} attempts: ${(err as Error).message}`);
+        return false;
+    }
+}
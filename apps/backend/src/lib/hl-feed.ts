@@ -0,0 +1,104 @@
+/**
+ * Single upstream Hyperliquid `allMids` subscription shared by every
+ * /ws/prices client.
+ *
+ * The upstream socket opens with the first listener and closes after the
+ * last one leaves. Drops are retried with exponential backoff (1s → 30s),
+ * and an application-level ping keeps HL from idling the connection out.
+ */
+
+const HL_WS_URL = process.env["HL_WS_URL"] ?? "wss://api.hyperliquid.xyz/ws";
+const PING_INTERVAL_MS = 30_000;
+const BACKOFF_MIN_MS = 1_000;
+const BACKOFF_MAX_MS = 30_000;
+
+export type Mids = Record<string, string>;
+type Listener = (mids: Mids) => void;
+
+class HlMidsFeed {
+    private socket: WebSocket | null = null;
+    private listeners = new Set<Listener>();
+    private latest: Mids = {};
+    private backoffMs = BACKOFF_MIN_MS;
+    private pingTimer: ReturnType<typeof setInterval> | null = null;
+    private reconnectTimer: ReturnType<typeof setTimeout> | null = null;
+
+    /** Register a listener; it immediately gets the last snapshot if there is one. */
+    subscribe(listener: Listener): () => void {
+        this.listeners.add(listener);
+        if (this.listeners.size === 1) this.connect();
+        if (Object.keys(this.latest).length > 0) listener(this.latest);
+
+        return () => {
+            this.listeners.delete(listener);
+            if (this.listeners.size === 0) this.disconnect();
+        };
+    }
+
+    stats() {
+        return {
+            clients: this.listeners.size,
+            upstream_connected: this.socket?.readyState === WebSocket.OPEN,
+            coins: Object.keys(this.latest).length,
+        };
+    }
+
+    private connect(): void {
+        if (this.socket || this.reconnectTimer) return;
+        const ws = new WebSocket(HL_WS_URL);
+        this.socket = ws;
+
+        ws.onopen = () => {
+            this.backoffMs = BACKOFF_MIN_MS;
+            ws.send(JSON.stringify({ method: "subscribe", subscription: { type: "allMids" } }));
+            this.pingTimer = setInterval(
+                () => ws.send(JSON.stringify({ method: "ping" })),
+                PING_INTERVAL_MS
+            );
+            console.log("[hl-feed] Upstream connected");
+        };
+
+        ws.onmessage = (evt) => {
+            let msg: { channel?: string; data?: { mids?: Mids } };
+            try {
+                msg = JSON.parse(String(evt.data));
+            } catch {
+                return;
+            }
+            if (msg.channel !== "allMids" || !msg.data?.mids) return;
+            this.latest = msg.data.mids;
+            for (const listener of this.listeners) listener(this.latest);
+        };
+
+        ws.onerror = () => console.error("[hl-feed] Upstream socket error");
+
+        ws.onclose = () => {
+            this.clearPing();
+            if (this.socket === ws) this.socket = null;
+            if (this.listeners.size === 0) return;
+            console.warn(`[hl-feed] Upstream closed, reconnecting in ${this.backoffMs}ms`);
+            this.reconnectTimer = setTimeout(() => {
+                this.reconnectTimer = null;
+                if (this.listeners.size > 0) this.connect();
+            }, this.backoffMs);
+            this.backoffMs = Math.min(this.backoffMs * 2, BACKOFF_MAX_MS);
+        };
+    }
+
+    private disconnect(): void {
+        if (this.reconnectTimer) clearTimeout(this.reconnectTimer);
+        this.reconnectTimer = null;
+        this.clearPing();
+        const ws = this.socket;
+        this.socket = null;
+        ws?.close();
+        this.latest = {};
+    }
+
+    private clearPing(): void {
+        if (this.pingTimer) clearInterval(this.pingTimer);
+        this.pingTimer = null;
+    }
+}
+
+export const hlMids = new HlMidsFeed();
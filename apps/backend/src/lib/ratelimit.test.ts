@@ -0,0 +1,50 @@
+import { describe, expect, test } from "bun:test";
+import { rateLimitFromEnv, tokenBucket, type ScriptRunner } from "./ratelimit.ts";
+
+/** Redis double: records each EVAL and answers with `reply` (or throws it). */
+function runner(reply: [number, number] | Error) {
+    const calls: (string | number)[][] = [];
+    const client: ScriptRunner = {
+        eval: async (_script, _numKeys, ...args) => {
+            calls.push(args);
+            if (reply instanceof Error) throw reply;
+            return reply;
+        },
+    };
+    return { client, calls };
+}
+
+describe("tokenBucket", () => {
+    test("passes the key, bucket size, refill rate and clock to the script", async () => {
+        const { client, calls } = runner([1, 0]);
+        const take = tokenBucket(client, { rpm: 60, burst: 5 }, () => 1_000);
+        expect(await take("key-1")).toEqual({ allowed: true, retryAfterSecs: 0 });
+        expect(calls).toEqual([["atlas:ratelimit:key-1", 5, 0.001, 1_000]]);
+    });
+
+    test("denies with the wait rounded up to whole seconds", async () => {
+        const { client } = runner([0, 1_200]);
+        const take = tokenBucket(client, { rpm: 60, burst: 5 });
+        expect(await take("key-1")).toEqual({ allowed: false, retryAfterSecs: 2 });
+    });
+
+    test("fails open when Redis is unavailable", async () => {
+        const { client } = runner(new Error("connect ECONNREFUSED"));
+        const take = tokenBucket(client, { rpm: 60, burst: 5 });
+        expect(await take("key-1")).toEqual({ allowed: true, retryAfterSecs: 0 });
+    });
+});
+
+describe("rateLimitFromEnv", () => {
+    test("defaults to 600 rpm with a burst of the rpm", () => {
+        expect(rateLimitFromEnv({})).toEqual({ rpm: 600, burst: 600 });
+        expect(rateLimitFromEnv({ ATLAS_RATE_LIMIT_RPM: "120" })).toEqual({ rpm: 120, burst: 120 });
+    });
+
+    test("ignores invalid values", () => {
+        expect(rateLimitFromEnv({ ATLAS_RATE_LIMIT_RPM: "lots", ATLAS_RATE_LIMIT_BURST: "0" })).toEqual({
+            rpm: 600,
+            burst: 600,
+        });
+    });
+});
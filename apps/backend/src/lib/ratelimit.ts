@@ -0,0 +1,85 @@
+/**
+ * Per-API-key token bucket, stored in Redis so every backend instance
+ * shares the same budget.
+ *
+ *   ATLAS_RATE_LIMIT_RPM    — sustained requests per minute (default 600)
+ *   ATLAS_RATE_LIMIT_BURST  — bucket size (default = RPM)
+ *
+ * If Redis is unavailable the request is allowed — auth still applies,
+ * rate limiting fails open.
+ */
+
+// Refill by elapsed time, take one token if available.
+// Returns { allowed (0|1), ms until the next token }.
+const TOKEN_BUCKET = `
+local capacity = tonumber(ARGV[1])
+local refill = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+local state = redis.call("HMGET", KEYS[1], "tokens", "ts")
+local tokens = tonumber(state[1]) or capacity
+local ts = tonumber(state[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * refill)
+local allowed = 0
+local wait = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    wait = math.ceil((1 - tokens) / refill)
+end
+redis.call("HSET", KEYS[1], "tokens", tostring(tokens), "ts", now)
+redis.call("PEXPIRE", KEYS[1], math.ceil(capacity / refill) + 1000)
+return { allowed, wait }
+`;
+
+export interface RateLimitResult {
+    allowed: boolean;
+    /** Whole seconds until a request would be accepted (0 when allowed). */
+    retryAfterSecs: number;
+}
+
+export type TakeToken = (keyId: string) => Promise<RateLimitResult>;
+
+export interface RateLimitOptions {
+    /** Sustained requests per minute. */
+    rpm: number;
+    /** Bucket size. */
+    burst: number;
+}
+
+/** The one Redis method the bucket needs. */
+export interface ScriptRunner {
+    eval(script: string, numKeys: number, ...args: (string | number)[]): Promise<unknown>;
+}
+
+/** ATLAS_RATE_LIMIT_RPM / ATLAS_RATE_LIMIT_BURST, with their defaults. */
+export function rateLimitFromEnv(env: Record<string, string | undefined> = process.env): RateLimitOptions {
+    const rpm = Math.max(parseInt(env["ATLAS_RATE_LIMIT_RPM"] ?? "600", 10) || 600, 1);
+    const burst = Math.max(parseInt(env["ATLAS_RATE_LIMIT_BURST"] ?? `${rpm}`, 10) || rpm, 1);
+    return { rpm, burst };
+}
+
+/** Take one token from `keyId`'s bucket in `client`; allows the request if Redis fails. */
+export function tokenBucket(
+    client: ScriptRunner,
+    { rpm, burst }: RateLimitOptions,
+    clock: () => number = Date.now,
+): TakeToken {
+    const refillPerMs = rpm / 60_000;
+    return async (keyId) => {
+        try {
+            const [allowed, waitMs] = (await client.eval(
+                TOKEN_BUCKET,
+                1,
+                `atlas:ratelimit:${keyId}`,
+                burst,
+                refillPerMs,
+                clock()
+            )) as [number, number];
+            return { allowed: allowed === 1, retryAfterSecs: Math.ceil(waitMs / 1000) };
+        } catch (err) {
+            console.error("[ratelimit] Redis error, allowing request:", (err as Error).message);
+            return { allowed: true, retryAfterSecs: 0 };
+        }
+    };
+}
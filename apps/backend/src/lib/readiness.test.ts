@@ -0,0 +1,112 @@
+import { describe, expect, test } from "bun:test";
+import pg from "pg";
+import { readiness } from "../routes/health.ts";
+import {
+    cached,
+    coingeckoCheck,
+    postgresCheck,
+    retryWithBackoff,
+    type DependencyStatus,
+    type Fetch,
+} from "./readiness.ts";
+
+const skipped = async (): Promise<DependencyStatus> => ({ status: "skipped", latency_ms: null });
+
+async function readyz(checks: Parameters<typeof readiness>[0]) {
+    const res = await readiness(checks).request("/");
+    return { status: res.status, body: (await res.json()) as any };
+}
+
+describe("/readyz", () => {
+    test("200 with per-dependency latency when everything answers", async () => {
+        const pool = { query: async () => ({ rows: [{ "?column?": 1 }] }) };
+        const { status, body } = await readyz({ postgres: postgresCheck(pool), coingecko: skipped });
+        expect(status).toBe(200);
+        expect(body.status).toBe("ready");
+        expect(body.checks.postgres.status).toBe("ok");
+        expect(typeof body.checks.postgres.latency_ms).toBe("number");
+        expect(body.checks.coingecko.status).toBe("skipped");
+    });
+
+    test("503 when the database is down", async () => {
+        // Nothing listens on port 1: connections are refused
+        const pool = new pg.Pool({
+            connectionString: "postgres://atlas@127.0.0.1:1/atlas",
+            connectionTimeoutMillis: 1_000,
+        });
+        pool.on("error", () => {});
+        try {
+            const { status, body } = await readyz({ postgres: postgresCheck(pool), coingecko: skipped });
+            expect(status).toBe(503);
+            expect(body.status).toBe("not_ready");
+            expect(body.checks.postgres.status).toBe("fail");
+            expect(body.checks.postgres.error).toBeTruthy();
+        } finally {
+            await pool.end();
+        }
+    });
+
+    test("503 when the database hangs past the timeout", async () => {
+        const pool = { query: () => new Promise(() => {}) };
+        const { status, body } = await readyz({ postgres: postgresCheck(pool, 20), coingecko: skipped });
+        expect(status).toBe(503);
+        expect(body.checks.postgres.error).toBe("SELECT 1 timed out after 20ms");
+    });
+});
+
+describe("coingeckoCheck", () => {
+    test("a rejected key fails, at most one ping per TTL", async () => {
+        let calls = 0;
+        const fetchFn: Fetch = async () => {
+            calls++;
+            return new Response("{}", { status: 401 });
+        };
+        let now = 0;
+        const check = cached(coingeckoCheck("bad-key", fetchFn), 60_000, () => now);
+
+        const results = await Promise.all([check(), check(), check()]);
+        expect(calls).toBe(1);
+        expect(results.every((r) => r.status === "fail" && r.error === "ping returned 401")).toBe(true);
+
+        now = 59_999;
+        await check();
+        expect(calls).toBe(1);
+        now = 60_000;
+        await check();
+        expect(calls).toBe(2);
+    });
+
+    test("skipped without a key", async () => {
+        const result = await coingeckoCheck(undefined)();
+        expect(result.status).toBe("skipped");
+        expect(result.latency_ms).toBeNull();
+    });
+});
+
+describe("retryWithBackoff", () => {
+    test("doubles the wait up to the cap, then succeeds", async () => {
+        const waits: number[] = [];
+        let calls = 0;
+        const value = await retryWithBackoff(
+            async () => {
+                if (++calls < 5) throw new Error("ECONNREFUSED");
+                return "up";
+            },
+            { attempts: 10, minMs: 500, maxMs: 2_000, sleep: async (ms) => waits.push(ms) },
+        );
+        expect(value).toBe("up");
+        expect(waits).toEqual([500, 1_000, 2_000, 2_000]);
+    });
+
+    test("rethrows the last error once attempts run out", async () => {
+        let calls = 0;
+        const attempt = retryWithBackoff(
+            async () => {
+                throw new Error(`attempt ${++calls}`);
+            },
+            { attempts: 3, minMs: 1, maxMs: 1, sleep: async () => {} },
+        );
+        await expect(attempt).rejects.toThrow("attempt 3");
+        expect(calls).toBe(3);
+    });
+});
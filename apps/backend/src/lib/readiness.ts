@@ -0,0 +1,146 @@
+/**
+ * Dependency checks behind GET /readyz, and the startup retry for Postgres.
+ *
+ * A check never throws: it resolves to a DependencyStatus with its own
+ * latency, and the report is ready only while no check has failed. Checks
+ * against a metered upstream (CoinGecko) go through `cached`, so load
+ * balancer probes don't turn into upstream traffic.
+ */
+
+export type CheckStatus = "ok" | "fail" | "skipped";
+
+export interface DependencyStatus {
+    status: CheckStatus;
+    /** Round trip of the check; null when it was skipped. */
+    latency_ms: number | null;
+    error?: string;
+    /** When a cached result was produced. */
+    checked_at?: string;
+}
+
+export type DependencyCheck = () => Promise<DependencyStatus>;
+
+export interface ReadinessReport {
+    status: "ready" | "not_ready";
+    checks: Record<string, DependencyStatus>;
+    timestamp: string;
+}
+
+/** The one pool method the Postgres check needs. */
+export interface Queryable {
+    query(sql: string): Promise<unknown>;
+}
+
+export type Fetch = (url: string, init?: RequestInit) => Promise<Response>;
+
+const COINGECKO_PING = "https://pro-api.coingecko.com/api/v3/ping";
+
+/** Reject with "<what> timed out after <ms>ms" if `promise` takes longer. */
+export async function withTimeout<T>(promise: Promise<T>, ms: number, what: string): Promise<T> {
+    let timer: ReturnType<typeof setTimeout> | undefined;
+    const timeout = new Promise<never>((_, reject) => {
+        timer = setTimeout(() => reject(new Error(`${what} timed out after ${ms}ms`)), ms);
+    });
+    try {
+        return await Promise.race([promise, timeout]);
+    } finally {
+        clearTimeout(timer);
+    }
+}
+
+/** Time `fn`; a throw becomes a failed status carrying its message. */
+async function timed(fn: () => Promise<unknown>): Promise<DependencyStatus> {
+    const start = performance.now();
+    const latency = () => Math.round(performance.now() - start);
+    try {
+        await fn();
+        return { status: "ok", latency_ms: latency() };
+    } catch (err) {
+        return { status: "fail", latency_ms: latency(), error: (err as Error).message };
+    }
+}
+
+/** `SELECT 1` against `pool`, failed after `timeoutMs`. */
+export function postgresCheck(pool: Queryable, timeoutMs = 2_000): DependencyCheck {
+    return () => timed(() => withTimeout(pool.query("SELECT 1"), timeoutMs, "SELECT 1"));
+}
+
+/**
+ * CoinGecko Pro `/ping` with `apiKey`; a rejected key fails the check.
+ * Skipped when no key is configured. Wrap it in `cached`.
+ */
+export function coingeckoCheck(
+    apiKey: string | undefined,
+    fetchFn: Fetch = fetch,
+    timeoutMs = 5_000,
+): DependencyCheck {
+    if (!apiKey) {
+        return async () => ({ status: "skipped", latency_ms: null, error: "COINGECKO_API_KEY not set" });
+    }
+    return () =>
+        timed(async () => {
+            const res = await fetchFn(COINGECKO_PING, {
+                headers: { "x-cg-pro-api-key": apiKey },
+                signal: AbortSignal.timeout(timeoutMs),
+            });
+            if (!res.ok) throw new Error(`ping returned ${res.status}`);
+        });
+}
+
+/**
+ * Reuse `check`'s last result, success or failure, for `ttlMs`.
+ * Concurrent callers after expiry share one call in flight.
+ */
+export function cached(check: DependencyCheck, ttlMs: number, clock: () => number = Date.now): DependencyCheck {
+    let last: { at: number; result: DependencyStatus } | null = null;
+    let inFlight: Promise<DependencyStatus> | null = null;
+    return async () => {
+        if (last && clock() - last.at < ttlMs) return last.result;
+        inFlight ??= check()
+            .then((result) => {
+                const at = clock();
+                last = { at, result: { ...result, checked_at: new Date(at).toISOString() } };
+                return last.result;
+            })
+            .finally(() => {
+                inFlight = null;
+            });
+        return inFlight;
+    };
+}
+
+/** Run every check concurrently; `not_ready` if any failed. */
+export async function runChecks(checks: Record<string, DependencyCheck>): Promise<ReadinessReport> {
+    const names = Object.keys(checks);
+    const results = await Promise.all(names.map((name) => checks[name]!()));
+    return {
+        status: results.some((r) => r.status === "fail") ? "not_ready" : "ready",
+        checks: Object.fromEntries(names.map((name, i) => [name, results[i]!])),
+        timestamp: new Date().toISOString(),
+    };
+}
+
+export interface RetryOptions {
+    attempts: number;
+    /** Wait before the second attempt; doubles up to `maxMs`. */
+    minMs: number;
+    maxMs: number;
+    onRetry?: (err: Error, attempt: number, waitMs: number) => void;
+    sleep?: (ms: number) => Promise<unknown>;
+}
+
+/** Call `fn` until it resolves, up to `attempts` times; rethrows the last error. */
+export async function retryWithBackoff<T>(fn: () => Promise<T>, opts: RetryOptions): Promise<T> {
+    const sleep = opts.sleep ?? ((ms: number) => Bun.sleep(ms));
+    let waitMs = opts.minMs;
+    for (let attempt = 1; ; attempt++) {
+        try {
+            return await fn();
+        } catch (err) {
+            if (attempt >= opts.attempts) throw err;
+            opts.onRetry?.(err as Error, attempt, waitMs);
+            await sleep(waitMs);
+            waitMs = Math.min(waitMs * 2, opts.maxMs);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+import { describe, expect, test } from "bun:test";
+import { Hono } from "hono";
+import { SCOPES, parseScopes, requireScope, requiredScope, type Scope } from "./scopes.ts";
+
+/** One request per route group, with the scope it needs (null: any key). */
+const ROUTES: [method: string, path: string, scope: Scope | null][] = [
+    ["GET", "/atlas-os/dex/trending", "read"],
+    ["GET", "/atlas-os/hl/all-mids", "read"],
+    ["GET", "/atlas-os/hl/candles", "read"],
+    ["GET", "/atlas-os/morpho/markets", "read"],
+    ["GET", "/atlas-os/morpho/positions/0xabc", "read"],
+    ["GET", "/atlas-os/0x/swap/allowance-holder/price", "read"],
+    ["GET", "/atlas-os/0x/gasless/quote", "read"],
+    ["GET", "/atlas-os/0x/gasless/status/0x123", "read"],
+    ["POST", "/atlas-os/0x/gasless/submit", "trade-analytics"],
+    ["POST", "/atlas-os/rpc/ethereum", "read"],
+    ["GET", "/ws/prices", "read"],
+    ["GET", "/atlas-os/compute/usage", "trade-analytics"],
+    ["POST", "/atlas-os/compute/usage", "trade-analytics"],
+    ["GET", "/atlas-os/config", "config-sync"],
+    ["PUT", "/atlas-os/config", "config-sync"],
+    ["GET", "/atlas-os/me", null],
+    ["GET", "/atlas-os/keys/self", null],
+];
+
+/** Stand-in for apiKeyAuth: a key with `granted` scopes, then the real check. */
+function app(granted: string[]) {
+    const app = new Hono<{ Variables: { apiKeyScopes: string[] } }>();
+    app.use("*", (ctx, next) => {
+        ctx.set("apiKeyScopes", granted);
+        return requireScope(ctx, next);
+    });
+    app.all("*", (ctx) => ctx.json({ ok: true }));
+    return app;
+}
+
+const KEYS: string[][] = [[], ["read"], ["trade-analytics"], ["config-sync"], [...SCOPES]];
+
+describe("requireScope", () => {
+    for (const granted of KEYS) {
+        for (const [method, path, scope] of ROUTES) {
+            const allowed = scope === null || granted.includes(scope);
+            test(`${method} ${path} with [${granted.join(", ")}] → ${allowed ? 200 : 403}`, async () => {
+                const res = await app(granted).request(path, { method });
+                expect(res.status).toBe(allowed ? 200 : 403);
+                if (!allowed) {
+                    const body = (await res.json()) as any;
+                    expect(body.code).toBe("SCOPE_MISSING");
+                    expect(body.scope).toBe(scope);
+                    expect(body.granted).toEqual(granted);
+                }
+            });
+        }
+    }
+});
+
+describe("requiredScope", () => {
+    test("prefixes must match whole path segments", () => {
+        expect(requiredScope("GET", "/atlas-os/configs")).toBeNull();
+        expect(requiredScope("GET", "/atlas-os/hlx/all-mids")).toBeNull();
+        expect(requiredScope("post", "/atlas-os/0x/gasless/submit")).toBe("trade-analytics");
+    });
+});
+
+describe("parseScopes", () => {
+    test("omitted grants every scope", () => {
+        expect(parseScopes(undefined)).toEqual({ scopes: [...SCOPES] });
+    });
+
+    test("normalises order and duplicates", () => {
+        expect(parseScopes(["config-sync", "read", "read"])).toEqual({ scopes: ["read", "config-sync"] });
+    });
+
+    test("rejects empty, non-array and unknown scopes", () => {
+        expect("error" in parseScopes([])).toBe(true);
+        expect("error" in parseScopes("read")).toBe(true);
+        const unknown = parseScopes(["read", "admin"]);
+        expect("error" in unknown && unknown.error).toContain("admin");
+    });
+});
@@ -0,0 +1,84 @@
+import type { Context, Next } from "hono";
+
+/**
+ * API key scopes. A key only reaches the route groups its scopes cover:
+ *   read            — market data: /atlas-os/{rpc,dex,hl,morpho,0x}, /ws
+ *   trade-analytics — /atlas-os/compute, and relaying signed 0x gasless trades
+ *   config-sync     — /atlas-os/config (`atlas configure sync`)
+ * /atlas-os/me and /atlas-os/keys/self answer any valid key.
+ */
+export const SCOPES = ["read", "trade-analytics", "config-sync"] as const;
+export type Scope = (typeof SCOPES)[number];
+
+/** Scopes of keys created without a `scopes` list, and of keys that predate scopes. */
+export const DEFAULT_SCOPES: readonly Scope[] = SCOPES;
+
+interface ScopeRule {
+    pattern: RegExp;
+    /** Only requests with one of these methods; any method when omitted. */
+    methods?: string[];
+    scope: Scope;
+}
+
+/** First match wins, so narrower rules come before their group's rule. */
+const SCOPE_RULES: ScopeRule[] = [
+    { pattern: /^\/atlas-os\/config(\/|$)/, scope: "config-sync" },
+    { pattern: /^\/atlas-os\/compute(\/|$)/, scope: "trade-analytics" },
+    { pattern: /^\/atlas-os\/0x\/gasless\/submit(\/|$)/, methods: ["POST"], scope: "trade-analytics" },
+    { pattern: /^\/atlas-os\/(rpc|dex|hl|morpho|0x)(\/|$)/, scope: "read" },
+    { pattern: /^\/ws(\/|$)/, scope: "read" },
+];
+
+/** The scope a request needs, or null for routes open to any valid key. */
+export function requiredScope(method: string, path: string): Scope | null {
+    const rule = SCOPE_RULES.find(
+        (r) => r.pattern.test(path) && (!r.methods || r.methods.includes(method.toUpperCase()))
+    );
+    return rule?.scope ?? null;
+}
+
+export function isScope(value: unknown): value is Scope {
+    return typeof value === "string" && (SCOPES as readonly string[]).includes(value);
+}
+
+/**
+ * Validate the `scopes` of a create-key request: omitted means
+ * DEFAULT_SCOPES, otherwise a non-empty list of known scopes (deduplicated,
+ * in SCOPES order). Returns an error message for anything else.
+ */
+export function parseScopes(input: unknown): { scopes: Scope[] } | { error: string } {
+    if (input === undefined) return { scopes: [...DEFAULT_SCOPES] };
+    if (!Array.isArray(input) || input.length === 0) {
+        return { error: `scopes must be a non-empty array of: ${SCOPES.join(", ")}` };
+    }
+    const unknown = input.filter((s) => !isScope(s));
+    if (unknown.length > 0) {
+        return { error: `Unknown scope(s): ${unknown.map(String).join(", ")}. Valid: ${SCOPES.join(", ")}` };
+    }
+    return { scopes: SCOPES.filter((s) => input.includes(s)) };
+}
+
+/** 403 body naming the scope the key lacks. */
+export function scopeMissing(ctx: Context, scope: Scope, granted: readonly string[]) {
+    return ctx.json(
+        {
+            error: `This API key lacks the '${scope}' scope`,
+            code: "SCOPE_MISSING",
+            scope,
+            granted,
+            hint: `Create a key with the '${scope}' scope in the dashboard, then run: atlas configure system api-key <key>`,
+        },
+        403
+    );
+}
+
+/**
+ * Middleware: 403 unless the key's scopes (`apiKeyScopes`, set by
+ * apiKeyAuth) cover the route.
+ */
+export async function requireScope(ctx: Context, next: Next) {
+    const scope = requiredScope(ctx.req.method, ctx.req.path);
+    const granted: readonly string[] = ctx.get("apiKeyScopes") ?? [];
+    if (scope && !granted.includes(scope)) return scopeMissing(ctx, scope, granted);
+    await next();
+}
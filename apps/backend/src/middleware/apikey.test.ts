@@ -0,0 +1,94 @@
+import { describe, expect, test } from "bun:test";
+import { Hono } from "hono";
+import { apiKeyAuth, type ApiKeyRecord, type ApiKeyStore } from "./apikey.ts";
+
+const KEY = "atl_live0000000000";
+const OTHER = "atl_live1111111111";
+
+/** In-memory stand-in for lib/apikeys.ts: revoking deletes the row, each key gets `burst` tokens. */
+function store(burst = 10) {
+    const rows = new Map<string, ApiKeyRecord>([
+        [KEY, { id: "key-1", user_id: "user-1", scopes: ["read"] }],
+        [OTHER, { id: "key-2", user_id: "user-1", scopes: ["read"] }],
+    ]);
+    const taken = new Map<string, number>();
+    const keys: ApiKeyStore = {
+        resolve: async (raw) => rows.get(raw) ?? null,
+        takeToken: async (id) => {
+            const n = (taken.get(id) ?? 0) + 1;
+            taken.set(id, n);
+            return n <= burst ? { allowed: true, retryAfterSecs: 0 } : { allowed: false, retryAfterSecs: 7 };
+        },
+    };
+    return { keys, revoke: (raw: string) => rows.delete(raw), taken: (id: string) => taken.get(id) ?? 0 };
+}
+
+function app(keys: ApiKeyStore) {
+    const app = new Hono();
+    app.use("*", apiKeyAuth(keys));
+    app.all("*", (ctx) => ctx.json({ ok: true }));
+    return app;
+}
+
+async function call(keys: ApiKeyStore, headers: Record<string, string> = {}, path = "/atlas-os/hl/all-mids") {
+    const res = await app(keys).request(path, { headers });
+    return { status: res.status, headers: res.headers, body: (await res.json()) as any };
+}
+
+describe("apiKeyAuth", () => {
+    test("accepts a key from any of the three headers", async () => {
+        const { keys } = store();
+        for (const headers of [
+            { Authorization: `Bearer ${KEY}` },
+            { "X-API-Key": KEY },
+            { "X-Atlas-Api-Key": KEY },
+        ]) {
+            expect((await call(keys, headers)).status).toBe(200);
+        }
+    });
+
+    test("401 API_KEY_MISSING without a key or with a non-atl_ key", async () => {
+        const { keys, taken } = store();
+        for (const headers of [{}, { Authorization: "Bearer sk_live_123" }]) {
+            const { status, body } = await call(keys, headers);
+            expect(status).toBe(401);
+            expect(body.code).toBe("API_KEY_MISSING");
+        }
+        expect(taken("key-1")).toBe(0);
+    });
+
+    test("401 API_KEY_INVALID once the key is revoked", async () => {
+        const { keys, revoke, taken } = store();
+        expect((await call(keys, { "X-API-Key": KEY })).status).toBe(200);
+
+        revoke(KEY);
+        const { status, body } = await call(keys, { "X-API-Key": KEY });
+        expect(status).toBe(401);
+        expect(body.code).toBe("API_KEY_INVALID");
+        // A rejected key doesn't spend the rate limit
+        expect(taken("key-1")).toBe(1);
+    });
+
+    test("429 with Retry-After once the key's budget is spent", async () => {
+        const { keys } = store(3);
+        for (let i = 0; i < 3; i++) {
+            expect((await call(keys, { "X-API-Key": KEY })).status).toBe(200);
+        }
+
+        const { status, headers, body } = await call(keys, { "X-API-Key": KEY });
+        expect(status).toBe(429);
+        expect(headers.get("Retry-After")).toBe("7");
+        expect(body.code).toBe("RATE_LIMITED");
+        expect(body.retry_after).toBe(7);
+
+        // The budget is per key
+        expect((await call(keys, { "X-API-Key": OTHER })).status).toBe(200);
+    });
+
+    test("403 when the key's scopes don't cover the route", async () => {
+        const { keys } = store();
+        const { status, body } = await call(keys, { "X-API-Key": KEY }, "/atlas-os/config");
+        expect(status).toBe(403);
+        expect(body.code).toBe("SCOPE_MISSING");
+    });
+});
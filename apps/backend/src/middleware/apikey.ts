@@ -1,35 +1,74 @@
-import { db } from "../lib/db.ts";
-import { hashKey } from "../lib/keygen.ts";
+import type { TakeToken } from "../lib/ratelimit.ts";
+import { requireScope } from "../lib/scopes.ts";
 import type { Context, Next } from "hono";
 
+export interface ApiKeyRecord {
+    id: string;
+    user_id: string;
+    scopes: string[];
+}
+
+/** Where apiKeyAuth looks keys up and takes rate limit tokens (lib/apikeys.ts). */
+export interface ApiKeyStore {
+    /** The key's record, or null for unknown and revoked keys. */
+    resolve(rawKey: string): Promise<ApiKeyRecord | null>;
+    takeToken: TakeToken;
+}
+
 /**
- * Atlas API Key middleware.
- * Verifies the `atl_xxx` key from Authorization header or X-API-Key header.
- * Used for machine-to-machine auth (CLI → RPC proxy).
- * Does NOT use Clerk — checks against the api_keys table via key_hash.
+ * Read the raw key from `Authorization: Bearer`, `X-API-Key` or
+ * `X-Atlas-Api-Key`, in that order.
  */
-export async function apiKeyAuth(ctx: Context, next: Next) {
-    const header =
-        ctx.req.header("X-API-Key") ??
-        ctx.req.header("Authorization")?.replace(/^Bearer\s+/i, "");
-
-    if (!header?.startsWith("atl_")) {
-        return ctx.json({ error: "Missing or invalid API key" }, 401);
-    }
+export function presentedKey(ctx: Context): string | undefined {
+    const bearer = ctx.req.header("Authorization")?.replace(/^Bearer\s+/i, "");
+    return bearer || ctx.req.header("X-API-Key") || ctx.req.header("X-Atlas-Api-Key");
+}
 
-    const keyHash = hashKey(header);
+/** 401 body shared by every API-key protected route. */
+export function unauthorized(ctx: Context, code: "API_KEY_MISSING" | "API_KEY_INVALID") {
+    const error = code === "API_KEY_MISSING" ? "Missing or malformed API key" : "Invalid API key";
+    return ctx.json(
+        {
+            error,
+            code,
+            hint: "Create a key in the dashboard, then run: atlas configure system api-key <key>",
+        },
+        401
+    );
+}
 
-    const { rows } = await db.query<{ id: string; user_id: string }>(
-        "SELECT id, user_id FROM api_keys WHERE key_hash = $1",
-        [keyHash]
+/** 429 body + Retry-After, shared by every API-key protected route. */
+export function rateLimited(ctx: Context, retryAfterSecs: number) {
+    ctx.header("Retry-After", String(retryAfterSecs));
+    return ctx.json(
+        { error: "Rate limit exceeded", code: "RATE_LIMITED", retry_after: retryAfterSecs },
+        429
     );
+}
+
+/**
+ * Atlas API Key middleware.
+ * Verifies the `atl_xxx` key (see presentedKey for accepted headers) against
+ * `keys`, applies the per-key rate limit, sets userId / apiKeyId /
+ * apiKeyScopes on the context, and answers 403 when the key's scopes don't
+ * cover the route (see lib/scopes.ts).
+ * Used for machine-to-machine auth (CLI → RPC proxy).
+ * Does NOT use Clerk — checks against the api_keys table via prefix + key_hash.
+ */
+export function apiKeyAuth(keys: ApiKeyStore) {
+    return async (ctx: Context, next: Next) => {
+        const key = presentedKey(ctx);
+        if (!key?.startsWith("atl_")) return unauthorized(ctx, "API_KEY_MISSING");
+
+        const row = await keys.resolve(key);
+        if (!row) return unauthorized(ctx, "API_KEY_INVALID");
 
-    if (rows.length === 0) {
-        return ctx.json({ error: "Invalid API key" }, 401);
-    }
+        const limit = await keys.takeToken(row.id);
+        if (!limit.allowed) return rateLimited(ctx, limit.retryAfterSecs);
 
-    const row = rows[0]!;
-    ctx.set("userId", row.user_id);
-    ctx.set("apiKeyId", row.id);
-    await next();
+        ctx.set("userId", row.user_id);
+        ctx.set("apiKeyId", row.id);
+        ctx.set("apiKeyScopes", row.scopes);
+        return requireScope(ctx, next);
+    };
 }
@@ -0,0 +1,61 @@
+import { Hono } from "hono";
+import { responseCache, UpstreamStatusError, type CachePolicy } from "../lib/cache.ts";
+
+export interface CacheRule extends CachePolicy {
+    /** Matched against the path relative to the mount point, e.g. "/pools/eth". */
+    pattern: RegExp;
+}
+
+/**
+ * Wrap a route group with the response cache.
+ *
+ * GET requests matching a rule are answered from cache (keyed by path +
+ * sorted query) and tagged `X-Atlas-Cache: hit|miss|stale`. Everything else
+ * passes straight through. The inner app is invoked with its own Request, so
+ * background refreshes do not depend on the client connection.
+ *
+ *   atlasOs.route("/dex", withCache(dex, "/dex", DEX_CACHE_RULES));
+ */
+export function withCache(inner: Hono, mount: string, rules: CacheRule[]): Hono {
+    const outer = new Hono();
+
+    outer.all("*", async (ctx) => {
+        const url = new URL(ctx.req.url);
+        const at = url.pathname.indexOf(mount);
+        const path = at >= 0 ? url.pathname.slice(at + mount.length) || "/" : url.pathname;
+        const innerUrl = new URL(path + url.search, url.origin);
+
+        const rule = ctx.req.method === "GET" ? rules.find((r) => r.pattern.test(path)) : undefined;
+        if (!rule) return inner.fetch(new Request(innerUrl, ctx.req.raw));
+
+        url.searchParams.sort();
+        const key = `${mount}${path}?${url.searchParams.toString()}`;
+
+        try {
+            const { value, state } = await responseCache.fetch(key, rule, async () => {
+                const res = await inner.fetch(new Request(innerUrl));
+                return {
+                    status: res.status,
+                    contentType: res.headers.get("Content-Type") ?? "application/json",
+                    body: await res.text(),
+                };
+            });
+            return new Response(value.body, {
+                status: value.status,
+                headers: { "Content-Type": value.contentType, "X-Atlas-Cache": state },
+            });
+        } catch (err) {
+            // No stale copy to fall back on — pass the upstream error through
+            if (err instanceof UpstreamStatusError) {
+                const { status, contentType, body } = err.response;
+                return new Response(body, {
+                    status,
+                    headers: { "Content-Type": contentType, "X-Atlas-Cache": "miss" },
+                });
+            }
+            throw err;
+        }
+    });
+
+    return outer;
+}
@@ -4,5 +4,6 @@ import { cors } from "hono/cors";
 export const corsMiddleware = cors({
     origin: "*",
     allowMethods: ["GET", "POST", "DELETE", "PUT", "PATCH", "OPTIONS"],
-    allowHeaders: ["Content-Type", "Authorization", "X-API-Key"],
+    allowHeaders: ["Content-Type", "Authorization", "X-API-Key", "X-Atlas-Api-Key"],
+    exposeHeaders: ["Retry-After"],
 });
@@ -0,0 +1,165 @@
+/**
+ * Enrichment for 0x price/quote responses.
+ *
+ * 0x returns raw base-unit amounts and a gas price in wei. To show a quote in
+ * human terms the CLI also needs each token's decimals and USD prices for
+ * both tokens and the chain's native coin. This adds them under an `atlas`
+ * key:
+ *
+ *   atlas: {
+ *     nativeSymbol: "ETH", nativeUsdPrice: "3500.12" | null,
+ *     sellToken: { address, symbol, decimals, usdPrice },
+ *     buyToken:  { address, symbol, decimals, usdPrice },
+ *   }
+ *
+ * Best-effort: a failed lookup leaves that field null and never fails the
+ * quote. Responses with `liquidityAvailable: false` are passed through as is.
+ * Decimals are cached for the process lifetime, prices for PRICE_TTL_MS.
+ */
+
+const NATIVE_PLACEHOLDER = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+const PRICE_TTL_MS = 30_000;
+
+/** 0x chainId → Alchemy network slug and native coin symbol. */
+const NETWORKS: Record<string, { slug: string; native: string }> = {
+    "1": { slug: "eth-mainnet", native: "ETH" },
+    "10": { slug: "opt-mainnet", native: "ETH" },
+    "56": { slug: "bnb-mainnet", native: "BNB" },
+    "137": { slug: "polygon-mainnet", native: "POL" },
+    "8453": { slug: "base-mainnet", native: "ETH" },
+    "42161": { slug: "arb-mainnet", native: "ETH" },
+    "43114": { slug: "avax-mainnet", native: "AVAX" },
+};
+
+export interface TokenInfo {
+    address: string;
+    symbol: string | null;
+    decimals: number | null;
+    usdPrice: string | null;
+}
+
+export interface QuoteEnrichment {
+    nativeSymbol: string | null;
+    nativeUsdPrice: string | null;
+    sellToken: TokenInfo;
+    buyToken: TokenInfo;
+}
+
+const decimalsCache = new Map<string, number>();
+const priceCache = new Map<string, { value: string | null; at: number }>();
+
+function alchemyKey(): string | null {
+    return process.env["ALCHEMY_API_KEY"] ?? null;
+}
+
+/** ERC20 `decimals()` via eth_call; 18 for the native placeholder. */
+async function tokenDecimals(slug: string, address: string): Promise<number | null> {
+    const lower = address.toLowerCase();
+    if (lower === NATIVE_PLACEHOLDER) return 18;
+    const key = `${slug}:${lower}`;
+    const cached = decimalsCache.get(key);
+    if (cached !== undefined) return cached;
+
+    const apiKey = alchemyKey();
+    if (!apiKey) return null;
+    try {
+        const res = await fetch(`https://${slug}.g.alchemy.com/v2/${apiKey}`, {
+            method: "POST",
+            headers: { "content-type": "application/json" },
+            body: JSON.stringify({
+                jsonrpc: "2.0",
+                id: 1,
+                method: "eth_call",
+                params: [{ to: lower, data: "0x313ce567" }, "latest"],
+            }),
+        });
+        const json = (await res.json()) as { result?: string };
+        if (!json.result || json.result === "0x") return null;
+        const decimals = Number.parseInt(json.result, 16);
+        if (!Number.isFinite(decimals) || decimals > 77) return null;
+        decimalsCache.set(key, decimals);
+        return decimals;
+    } catch {
+        return null;
+    }
+}
+
+async function cachedPrice(key: string, lookup: () => Promise<string | null>): Promise<string | null> {
+    const hit = priceCache.get(key);
+    if (hit && Date.now() - hit.at < PRICE_TTL_MS) return hit.value;
+    const value = await lookup().catch(() => null);
+    priceCache.set(key, { value, at: Date.now() });
+    return value;
+}
+
+type AlchemyPrice = { prices?: { currency: string; value: string }[] };
+
+function usdOf(entry: AlchemyPrice | undefined): string | null {
+    return entry?.prices?.find((p) => p.currency.toLowerCase() === "usd")?.value ?? null;
+}
+
+/** USD price of a native coin by symbol (Alchemy Prices API). */
+function nativePrice(symbol: string): Promise<string | null> {
+    return cachedPrice(`symbol:${symbol}`, async () => {
+        const apiKey = alchemyKey();
+        if (!apiKey) return null;
+        const res = await fetch(
+            `https://api.g.alchemy.com/prices/v1/${apiKey}/tokens/by-symbol?symbols=${symbol}`,
+        );
+        const json = (await res.json()) as { data?: AlchemyPrice[] };
+        return usdOf(json.data?.[0]);
+    });
+}
+
+/** USD price of a token by contract address (Alchemy Prices API). */
+function tokenPrice(slug: string, address: string, nativeSymbol: string): Promise<string | null> {
+    const lower = address.toLowerCase();
+    if (lower === NATIVE_PLACEHOLDER) return nativePrice(nativeSymbol);
+    return cachedPrice(`${slug}:${lower}`, async () => {
+        const apiKey = alchemyKey();
+        if (!apiKey) return null;
+        const res = await fetch(`https://api.g.alchemy.com/prices/v1/${apiKey}/tokens/by-address`, {
+            method: "POST",
+            headers: { "content-type": "application/json" },
+            body: JSON.stringify({ addresses: [{ network: slug, address: lower }] }),
+        });
+        const json = (await res.json()) as { data?: AlchemyPrice[] };
+        return usdOf(json.data?.[0]);
+    });
+}
+
+type RouteToken = { address: string; symbol: string };
+
+/** Add the `atlas` block to a successful 0x price/quote body. */
+export async function enrichQuote(
+    body: Record<string, unknown>,
+    chainId: string | undefined,
+): Promise<Record<string, unknown>> {
+    if (body["liquidityAvailable"] !== true) return body;
+    const sell = typeof body["sellToken"] === "string" ? (body["sellToken"] as string) : null;
+    const buy = typeof body["buyToken"] === "string" ? (body["buyToken"] as string) : null;
+    const network = chainId ? NETWORKS[chainId] : undefined;
+    if (!sell || !buy || !network) return body;
+
+    const routeTokens = ((body["route"] as { tokens?: RouteToken[] } | undefined)?.tokens ?? []);
+    const symbolOf = (address: string) =>
+        address.toLowerCase() === NATIVE_PLACEHOLDER
+            ? network.native
+            : routeTokens.find((t) => t.address.toLowerCase() === address.toLowerCase())?.symbol ?? null;
+
+    const [sellDecimals, buyDecimals, sellUsd, buyUsd, nativeUsd] = await Promise.all([
+        tokenDecimals(network.slug, sell),
+        tokenDecimals(network.slug, buy),
+        tokenPrice(network.slug, sell, network.native),
+        tokenPrice(network.slug, buy, network.native),
+        nativePrice(network.native),
+    ]);
+
+    const atlas: QuoteEnrichment = {
+        nativeSymbol: network.native,
+        nativeUsdPrice: nativeUsd,
+        sellToken: { address: sell, symbol: symbolOf(sell), decimals: sellDecimals, usdPrice: sellUsd },
+        buyToken: { address: buy, symbol: symbolOf(buy), decimals: buyDecimals, usdPrice: buyUsd },
+    };
+    return { ...body, atlas };
+}
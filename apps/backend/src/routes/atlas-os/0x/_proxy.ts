@@ -83,19 +83,33 @@ export function buildUpstreamUrl(
 
 /**
  * Execute a proxied 0x swap request and return the raw Response.
+ * A `body` is sent as a JSON POST (gasless submit); otherwise it is a GET.
  */
-export async function proxySwap(upstreamUrl: URL): Promise<Response> {
+export async function proxySwap(upstreamUrl: URL, body?: unknown): Promise<Response> {
     const apiKey = process.env["ZERO_EX_API_KEY"];
     if (!apiKey) throw new Error("ZERO_EX_API_KEY not configured");
 
+    const headers: Record<string, string> = {
+        "0x-api-key": apiKey,
+        "0x-version": ZERO_EX_VERSION,
+    };
+    if (body === undefined) {
+        return fetch(upstreamUrl.toString(), { headers });
+    }
     return fetch(upstreamUrl.toString(), {
-        headers: {
-            "0x-api-key": apiKey,
-            "0x-version": ZERO_EX_VERSION,
-        },
+        method: "POST",
+        headers: { ...headers, "Content-Type": "application/json" },
+        body: JSON.stringify(body),
     });
 }
 
+/** Plain upstream URL (no fee injection), for status/submit/chains calls. */
+export function upstreamUrl(path: string, query: Record<string, string> = {}): URL {
+    const url = new URL(`${ZERO_EX_BASE}${path}`);
+    for (const [key, val] of Object.entries(query)) url.searchParams.set(key, val);
+    return url;
+}
+
 /** Validate required query params; returns first missing name or null. */
 export function missingParam(
     params: Partial<Record<SwapParam, string>>,
@@ -0,0 +1,23 @@
+import { Hono } from "hono";
+import { proxySwap, upstreamUrl } from "../_proxy.ts";
+
+const chains = new Hono();
+
+/**
+ * GET /atlas-os/0x/gasless/chains
+ *
+ * Chains where gasless swaps are available: { chains: [{ chainId, chainName }] }.
+ * A subset of /swap/chains.
+ */
+chains.get("/", async (ctx) => {
+    try {
+        const res = await proxySwap(upstreamUrl("/gasless/chains"));
+        const body = await res.json();
+        return ctx.json(body, res.status as 200 | 500);
+    } catch (err) {
+        const msg = err instanceof Error ? err.message : "Upstream error";
+        return ctx.json({ error: msg }, 503);
+    }
+});
+
+export { chains };
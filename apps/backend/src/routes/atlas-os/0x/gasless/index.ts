@@ -0,0 +1,28 @@
+import { Hono } from "hono";
+import { price } from "./price.ts";
+import { quote } from "./quote.ts";
+import { submit } from "./submit.ts";
+import { status } from "./status.ts";
+import { chains } from "./chains.ts";
+
+/**
+ * /atlas-os/0x/gasless/*
+ *
+ * The taker signs EIP-712 payloads and 0x's relayer submits the trade, so
+ * the wallet needs no native gas token. Fees are taken in the sell token.
+ *
+ * Routes:
+ *   GET  /chains              → getGaslessChains
+ *   GET  /price               → getPrice (Gasless) — indicative, taker optional
+ *   GET  /quote               → getQuote (Gasless) — approval + trade EIP-712 to sign
+ *   POST /submit              → submitTrade — signed approval/trade → tradeHash
+ *   GET  /status/:tradeHash   → getStatus — pending/submitted/succeeded/confirmed/failed
+ */
+const gasless = new Hono();
+gasless.route("/chains", chains);
+gasless.route("/price", price);
+gasless.route("/quote", quote);
+gasless.route("/submit", submit);
+gasless.route("/status", status);
+
+export { gasless };
@@ -0,0 +1,36 @@
+import { Hono } from "hono";
+import { buildUpstreamUrl, proxySwap, missingParam, SWAP_PARAMS } from "../_proxy.ts";
+import { enrichQuote } from "../_enrich.ts";
+
+const price = new Hono();
+
+/**
+ * GET /atlas-os/0x/gasless/price
+ *
+ * Indicative gasless price. Required: chainId, buyToken, sellToken, sellAmount
+ *
+ * Platform fee is auto-injected if ZERO_EX_FEE_RECIPIENT is set. Successful
+ * responses gain an `atlas` block with token decimals and USD prices.
+ */
+price.get("/", async (ctx) => {
+    const required = ["chainId", "buyToken", "sellToken", "sellAmount"] as const;
+    const params = Object.fromEntries(
+        SWAP_PARAMS.map((p) => [p, ctx.req.query(p)]).filter(([, v]) => v !== undefined),
+    ) as Record<string, string>;
+
+    const missing = missingParam(params as never, required);
+    if (missing) return ctx.json({ error: `'${missing}' is required` }, 400);
+
+    try {
+        const url = buildUpstreamUrl("/gasless/price", params as never);
+        const res = await proxySwap(url);
+        const body = (await res.json()) as Record<string, unknown>;
+        const status = res.status as 200 | 400 | 403 | 422 | 500;
+        return ctx.json(res.ok ? await enrichQuote(body, params["chainId"]) : body, status);
+    } catch (err) {
+        const msg = err instanceof Error ? err.message : "Upstream error";
+        return ctx.json({ error: msg }, 503);
+    }
+});
+
+export { price };
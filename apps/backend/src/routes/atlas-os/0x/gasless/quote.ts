@@ -0,0 +1,38 @@
+import { Hono } from "hono";
+import { buildUpstreamUrl, proxySwap, missingParam, SWAP_PARAMS } from "../_proxy.ts";
+import { enrichQuote } from "../_enrich.ts";
+
+const quote = new Hono();
+
+/**
+ * GET /atlas-os/0x/gasless/quote
+ *
+ * Firm gasless quote. The response carries `trade.eip712` (and `approval.eip712`
+ * when the sell token supports gasless approvals) for the taker to sign.
+ * Required: chainId, buyToken, sellToken, sellAmount, taker
+ *
+ * Platform fee is auto-injected if ZERO_EX_FEE_RECIPIENT is set. Successful
+ * responses gain an `atlas` block with token decimals and USD prices.
+ */
+quote.get("/", async (ctx) => {
+    const required = ["chainId", "buyToken", "sellToken", "sellAmount", "taker"] as const;
+    const params = Object.fromEntries(
+        SWAP_PARAMS.map((p) => [p, ctx.req.query(p)]).filter(([, v]) => v !== undefined),
+    ) as Record<string, string>;
+
+    const missing = missingParam(params as never, required);
+    if (missing) return ctx.json({ error: `'${missing}' is required` }, 400);
+
+    try {
+        const url = buildUpstreamUrl("/gasless/quote", params as never);
+        const res = await proxySwap(url);
+        const body = (await res.json()) as Record<string, unknown>;
+        const status = res.status as 200 | 400 | 403 | 422 | 500;
+        return ctx.json(res.ok ? await enrichQuote(body, params["chainId"]) : body, status);
+    } catch (err) {
+        const msg = err instanceof Error ? err.message : "Upstream error";
+        return ctx.json({ error: msg }, 503);
+    }
+});
+
+export { quote };
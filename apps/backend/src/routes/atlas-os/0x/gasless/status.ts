@@ -0,0 +1,29 @@
+import { Hono } from "hono";
+import { proxySwap, upstreamUrl } from "../_proxy.ts";
+
+const status = new Hono();
+
+/**
+ * GET /atlas-os/0x/gasless/status/:tradeHash?chainId=
+ *
+ * Relay status of a submitted gasless trade: pending, submitted, succeeded,
+ * confirmed or failed (with `reason`). `transactions` lists the on-chain
+ * hashes once the relayer has sent it.
+ */
+status.get("/:tradeHash", async (ctx) => {
+    const chainId = ctx.req.query("chainId");
+    if (!chainId) return ctx.json({ error: "'chainId' is required" }, 400);
+    const tradeHash = ctx.req.param("tradeHash");
+
+    try {
+        const url = upstreamUrl(`/gasless/status/${encodeURIComponent(tradeHash)}`, { chainId });
+        const res = await proxySwap(url);
+        const body = await res.json();
+        return ctx.json(body, res.status as 200 | 400 | 404 | 500);
+    } catch (err) {
+        const msg = err instanceof Error ? err.message : "Upstream error";
+        return ctx.json({ error: msg }, 503);
+    }
+});
+
+export { status };
@@ -0,0 +1,36 @@
+import { Hono } from "hono";
+import { proxySwap, upstreamUrl } from "../_proxy.ts";
+
+const submit = new Hono();
+
+/**
+ * POST /atlas-os/0x/gasless/submit
+ *
+ * Forward a signed gasless trade to the 0x relayer.
+ * Body: { chainId, trade: { type, eip712, signature }, approval?: { … } }
+ * Returns { tradeHash, type, zid }.
+ *
+ * The body is passed through untouched: fees were fixed when the quote was
+ * built, and changing the signed payload would invalidate the signature.
+ */
+submit.post("/", async (ctx) => {
+    let body: Record<string, unknown>;
+    try {
+        body = await ctx.req.json();
+    } catch {
+        return ctx.json({ error: "Body must be JSON" }, 400);
+    }
+    if (body["chainId"] === undefined) return ctx.json({ error: "'chainId' is required" }, 400);
+    if (!body["trade"]) return ctx.json({ error: "'trade' is required" }, 400);
+
+    try {
+        const res = await proxySwap(upstreamUrl("/gasless/submit"), body);
+        const out = await res.json();
+        return ctx.json(out, res.status as 200 | 400 | 403 | 422 | 500);
+    } catch (err) {
+        const msg = err instanceof Error ? err.message : "Upstream error";
+        return ctx.json({ error: msg }, 503);
+    }
+});
+
+export { submit };
@@ -1,5 +1,6 @@
 import { Hono } from "hono";
 import { swap } from "./swap/index.ts";
+import { gasless } from "./gasless/index.ts";
 
 /**
  * /atlas-os/0x — 0x Protocol Proxy
@@ -8,8 +9,10 @@ import { swap } from "./swap/index.ts";
  *
  * Routes:
  *   GET /swap/allowance-holder/price  → getPrice (Allowance Holder)
+ *   /gasless/*                        → Gasless API (tx relay)
  */
 const zerox = new Hono();
 zerox.route("/swap", swap);
+zerox.route("/gasless", gasless);
 
 export { zerox };
@@ -1,5 +1,6 @@
 import { Hono } from "hono";
 import { buildUpstreamUrl, proxySwap, missingParam, SWAP_PARAMS } from "../../_proxy.ts";
+import { enrichQuote } from "../../_enrich.ts";
 
 const quote = new Hono();
 
@@ -9,7 +10,8 @@ const quote = new Hono();
  * Firm quote using Permit2. Response includes transaction calldata + permit2 EIP-712.
  * Required: chainId, buyToken, sellToken, sellAmount, taker
  *
- * Platform fee is auto-injected if ZERO_EX_FEE_RECIPIENT is set.
+ * Platform fee is auto-injected if ZERO_EX_FEE_RECIPIENT is set. Successful
+ * responses gain an `atlas` block with token decimals and USD prices.
  */
 quote.get("/", async (ctx) => {
     const required = ["chainId", "buyToken", "sellToken", "sellAmount", "taker"] as const;
@@ -23,7 +25,9 @@ quote.get("/", async (ctx) => {
     try {
         const url = buildUpstreamUrl("/swap/permit2/quote", params as never);
         const res = await proxySwap(url);
-        return ctx.json(await res.json(), res.status as 200 | 400 | 403 | 422 | 500);
+        const body = (await res.json()) as Record<string, unknown>;
+        const status = res.status as 200 | 400 | 403 | 422 | 500;
+        return ctx.json(res.ok ? await enrichQuote(body, params["chainId"]) : body, status);
     } catch (err) {
         const msg = err instanceof Error ? err.message : "Upstream error";
         return ctx.json({ error: msg }, 503);
@@ -0,0 +1,103 @@
+import { Hono } from "hono";
+import { db } from "../../lib/db.ts";
+import { isConfigObject, MAX_CONFIG_BYTES, secretPaths } from "../../lib/config-sync.ts";
+
+const config = new Hono<{ Variables: { userId: string; apiKeyId: string } }>();
+
+interface ConfigRow {
+    config: Record<string, unknown>;
+    version: number;
+    updated_at: string;
+}
+
+/**
+ * GET /atlas-os/config
+ * The config last pushed by `atlas configure sync push` for this user.
+ * Auth: Atlas API key (atl_xxx) via apiKeyAuth middleware.
+ *
+ * Response: { config: object, version: number, updated_at: string }
+ * 404 { code: "CONFIG_NOT_FOUND" } until the first push.
+ */
+config.get("/", async (ctx) => {
+    const { rows } = await db.query<ConfigRow>(
+        "SELECT config, version, updated_at FROM user_configs WHERE user_id = $1",
+        [ctx.get("userId")]
+    );
+    const row = rows[0];
+    if (!row) {
+        return ctx.json({ error: "No config has been pushed yet", code: "CONFIG_NOT_FOUND" }, 404);
+    }
+    return ctx.json(row);
+});
+
+/**
+ * PUT /atlas-os/config
+ * Replace the stored config.
+ *
+ * Body: { config: object, base_version?: number }
+ *   base_version — the version the client last synced; when it no longer
+ *   matches, the write is refused with 409 so another machine's push is
+ *   not silently overwritten. Omit to overwrite unconditionally.
+ *
+ * Response: { version: number, updated_at: string }
+ * 400 { code: "SECRET_IN_CONFIG", paths } if the payload carries a secret.
+ */
+config.put("/", async (ctx) => {
+    const raw = await ctx.req.text();
+    if (raw.length > MAX_CONFIG_BYTES) {
+        return ctx.json({ error: `Config is larger than ${MAX_CONFIG_BYTES} bytes`, code: "CONFIG_TOO_LARGE" }, 413);
+    }
+    let body: { config?: unknown; base_version?: unknown };
+    try {
+        body = JSON.parse(raw);
+    } catch {
+        return ctx.json({ error: "Body is not valid JSON", code: "INVALID_BODY" }, 400);
+    }
+    if (!isConfigObject(body.config)) {
+        return ctx.json({ error: "`config` must be a JSON object", code: "INVALID_BODY" }, 400);
+    }
+    const baseVersion = body.base_version;
+    if (baseVersion !== undefined && !Number.isInteger(baseVersion)) {
+        return ctx.json({ error: "`base_version` must be an integer", code: "INVALID_BODY" }, 400);
+    }
+    const secrets = secretPaths(body.config);
+    if (secrets.length > 0) {
+        return ctx.json(
+            { error: "Secrets must not be synced", code: "SECRET_IN_CONFIG", paths: secrets },
+            400
+        );
+    }
+
+    const userId = ctx.get("userId");
+    // base_version 0 means "nothing synced yet": only succeeds for a first push
+    const { rows } = await db.query<{ version: number; updated_at: string }>(
+        `INSERT INTO user_configs (user_id, config, version, updated_at)
+         VALUES ($1, $2, 1, now())
+         ON CONFLICT (user_id) DO UPDATE
+            SET config = EXCLUDED.config,
+                version = user_configs.version + 1,
+                updated_at = now()
+          WHERE $3::int IS NULL OR user_configs.version = $3::int
+         RETURNING version, updated_at`,
+        [userId, JSON.stringify(body.config), baseVersion ?? null]
+    );
+    const row = rows[0];
+    if (!row) {
+        const current = await db.query<{ version: number }>(
+            "SELECT version FROM user_configs WHERE user_id = $1",
+            [userId]
+        );
+        return ctx.json(
+            {
+                error: "The remote config changed since your last sync",
+                code: "VERSION_CONFLICT",
+                version: current.rows[0]?.version ?? 0,
+                hint: "Run: atlas configure sync pull --merge",
+            },
+            409
+        );
+    }
+    return ctx.json(row);
+});
+
+export { config };
@@ -0,0 +1,116 @@
+/**
+ * Shared client and request validation for the Hyperliquid `/info` proxy.
+ *
+ * Only the read-only market data the CLI needs is forwarded, and every
+ * parameter is checked first so the proxy cannot be used to hit arbitrary
+ * `/info` types or pull unbounded history:
+ *   - coins must be listed in the perp or spot universe (cached UNIVERSE_TTL_MS)
+ *   - candle windows span at most MAX_CANDLES intervals
+ *   - funding windows span at most MAX_FUNDING_SPAN_MS
+ *
+ * Mainnet only; HL_API_URL overrides the upstream for local testing.
+ */
+
+const HL_API_URL = process.env["HL_API_URL"] ?? "https://api.hyperliquid.xyz";
+const UNIVERSE_TTL_MS = 5 * 60_000;
+
+/** Hyperliquid returns at most 500 candles per `candleSnapshot`. */
+export const MAX_CANDLES = 500;
+export const MAX_FUNDING_SPAN_MS = 30 * 24 * 60 * 60_000;
+
+export const INTERVAL_MS: Record<string, number> = {
+    "1m": 60_000,
+    "3m": 3 * 60_000,
+    "5m": 5 * 60_000,
+    "15m": 15 * 60_000,
+    "30m": 30 * 60_000,
+    "1h": 60 * 60_000,
+    "2h": 2 * 60 * 60_000,
+    "4h": 4 * 60 * 60_000,
+    "8h": 8 * 60 * 60_000,
+    "12h": 12 * 60 * 60_000,
+    "1d": 24 * 60 * 60_000,
+    "3d": 3 * 24 * 60 * 60_000,
+    "1w": 7 * 24 * 60 * 60_000,
+    "1M": 30 * 24 * 60 * 60_000,
+};
+
+/** `/info` bodies are JSON objects or arrays, passed through as is. */
+export type InfoBody = Record<string, unknown> | unknown[];
+
+export class UpstreamError extends Error {}
+
+/** A request parameter the proxy refuses to forward. */
+export class ValidationError extends Error {}
+
+/** POST one request to Hyperliquid `/info` and return the parsed body. */
+export async function info<T = InfoBody>(body: Record<string, unknown>): Promise<T> {
+    const res = await fetch(`${HL_API_URL}/info`, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify(body),
+    });
+    if (!res.ok) throw new UpstreamError(`Hyperliquid API error ${res.status}`);
+    return (await res.json()) as T;
+}
+
+let universe: { coins: Set<string>; at: number } | null = null;
+let universeLoad: Promise<Set<string>> | null = null;
+
+/** Perp names plus spot pair names (`PURR/USDC`, `@107`). */
+export async function knownCoins(): Promise<Set<string>> {
+    if (universe && Date.now() - universe.at < UNIVERSE_TTL_MS) return universe.coins;
+    universeLoad ??= (async () => {
+        try {
+            const [perps, spot] = await Promise.all([
+                info<{ universe: { name: string }[] }>({ type: "meta" }),
+                info<{ universe: { name: string }[] }>({ type: "spotMeta" }),
+            ]);
+            const coins = new Set([...perps.universe, ...spot.universe].map((u) => u.name));
+            universe = { coins, at: Date.now() };
+            return coins;
+        } catch (err) {
+            // Keep serving the last universe rather than rejecting every coin
+            if (universe) return universe.coins;
+            throw err;
+        } finally {
+            universeLoad = null;
+        }
+    })();
+    return universeLoad;
+}
+
+/** The `coin` parameter, if it is a listed market. */
+export async function requireCoin(raw: string | undefined): Promise<string> {
+    if (!raw) throw new ValidationError("Missing coin");
+    if (!(await knownCoins()).has(raw)) throw new ValidationError(`Unknown coin: "${raw}"`);
+    return raw;
+}
+
+/** A non-negative integer millisecond timestamp. */
+export function parseTime(name: string, raw: string | undefined): number {
+    if (raw === undefined || !/^\d{1,15}$/.test(raw)) {
+        throw new ValidationError(`${name} must be a millisecond timestamp`);
+    }
+    return Number(raw);
+}
+
+/**
+ * `[start, end]` for a request; `end` defaults to now. Rejects windows that
+ * run backwards, end in the future or exceed `maxSpanMs`.
+ */
+export function timeRange(
+    startRaw: string | undefined,
+    endRaw: string | undefined,
+    maxSpanMs: number,
+    now = Date.now(),
+): [number, number] {
+    const start = parseTime("startTime", startRaw);
+    const end = endRaw === undefined ? now : parseTime("endTime", endRaw);
+    if (end < start) throw new ValidationError("endTime is before startTime");
+    if (end > now + 60_000) throw new ValidationError("endTime is in the future");
+    if (end - start > maxSpanMs) {
+        throw new ValidationError(`Time range too long (max ${Math.floor(maxSpanMs / 60_000)} minutes)`);
+    }
+    return [start, end];
+}
@@ -0,0 +1,61 @@
+import { Hono } from "hono";
+import {
+    info,
+    requireCoin,
+    timeRange,
+    INTERVAL_MS,
+    MAX_CANDLES,
+    MAX_FUNDING_SPAN_MS,
+    UpstreamError,
+    ValidationError,
+} from "./_info.ts";
+
+/**
+ * Time-series routes
+ * GET /atlas-os/hl/candles?coin=BTC&interval=1h&startTime=&endTime=
+ *     — at most MAX_CANDLES intervals per request
+ * GET /atlas-os/hl/funding-history?coin=BTC&startTime=&endTime=
+ *     — at most 30 days per request
+ *
+ * `endTime` defaults to now. Bodies are Hyperliquid's `/info` responses.
+ */
+const history = new Hono();
+
+history.get("/candles", async (ctx) => {
+    try {
+        const coin = await requireCoin(ctx.req.query("coin"));
+        const interval = ctx.req.query("interval") ?? "";
+        const step = INTERVAL_MS[interval];
+        if (step === undefined) throw new ValidationError(`Unsupported interval: "${interval}"`);
+        const [startTime, endTime] = timeRange(
+            ctx.req.query("startTime"),
+            ctx.req.query("endTime"),
+            step * MAX_CANDLES,
+        );
+        return ctx.json(
+            await info({ type: "candleSnapshot", req: { coin, interval, startTime, endTime } }),
+        );
+    } catch (err) {
+        if (err instanceof ValidationError) return ctx.json({ error: err.message }, 400);
+        if (err instanceof UpstreamError) return ctx.json({ error: err.message }, 502);
+        throw err;
+    }
+});
+
+history.get("/funding-history", async (ctx) => {
+    try {
+        const coin = await requireCoin(ctx.req.query("coin"));
+        const [startTime, endTime] = timeRange(
+            ctx.req.query("startTime"),
+            ctx.req.query("endTime"),
+            MAX_FUNDING_SPAN_MS,
+        );
+        return ctx.json(await info({ type: "fundingHistory", coin, startTime, endTime }));
+    } catch (err) {
+        if (err instanceof ValidationError) return ctx.json({ error: err.message }, 400);
+        if (err instanceof UpstreamError) return ctx.json({ error: err.message }, 502);
+        throw err;
+    }
+});
+
+export { history };
@@ -0,0 +1,40 @@
+import { Hono } from "hono";
+import { market } from "./market.ts";
+import { history } from "./history.ts";
+import type { CacheRule } from "../../../middleware/cache.ts";
+
+/**
+ * /atlas-os/hl — Hyperliquid market data proxy, for CLIs that cannot reach
+ * api.hyperliquid.xyz directly (`data_via_backend`). Read-only: nothing
+ * here signs or forwards exchange actions.
+ *
+ * Auth: Atlas API key (atl_xxx) via apiKeyAuth middleware (set upstream in index.ts)
+ * Cache: HL_CACHE_RULES via withCache (set upstream in index.ts)
+ *
+ * Route map:
+ *   GET /atlas-os/hl/all-mids                                     → market.ts
+ *   GET /atlas-os/hl/meta-and-asset-ctxs                          → market.ts
+ *   GET /atlas-os/hl/l2-book?coin=                                → market.ts
+ *   GET /atlas-os/hl/candles?coin=&interval=&startTime=&endTime=  → history.ts
+ *   GET /atlas-os/hl/funding-history?coin=&startTime=&endTime=    → history.ts
+ */
+const hl = new Hono();
+
+hl.route("/", market);
+hl.route("/", history);
+
+const SEC = 1_000;
+
+/**
+ * Response cache TTLs (see middleware/cache.ts). Snapshots are short-lived;
+ * candle and funding requests carry their own time window in the key.
+ */
+export const HL_CACHE_RULES: CacheRule[] = [
+    { pattern: /^\/all-mids/, ttlMs: 2 * SEC, staleMs: 10 * SEC },
+    { pattern: /^\/meta-and-asset-ctxs/, ttlMs: 5 * SEC, staleMs: 30 * SEC },
+    { pattern: /^\/l2-book/, ttlMs: 1 * SEC },
+    { pattern: /^\/candles/, ttlMs: 15 * SEC },
+    { pattern: /^\/funding-history/, ttlMs: 60 * SEC },
+];
+
+export { hl };
@@ -0,0 +1,43 @@
+import { Hono } from "hono";
+import { info, requireCoin, UpstreamError, ValidationError } from "./_info.ts";
+
+/**
+ * Market snapshot routes
+ * GET /atlas-os/hl/all-mids              — mid price of every coin
+ * GET /atlas-os/hl/meta-and-asset-ctxs   — perp universe + funding, OI, volume
+ * GET /atlas-os/hl/l2-book?coin=BTC      — order book snapshot
+ *
+ * Bodies are Hyperliquid's `/info` responses, unchanged.
+ */
+const market = new Hono();
+
+market.get("/all-mids", async (ctx) => {
+    try {
+        return ctx.json(await info({ type: "allMids" }));
+    } catch (err) {
+        if (err instanceof UpstreamError) return ctx.json({ error: err.message }, 502);
+        throw err;
+    }
+});
+
+market.get("/meta-and-asset-ctxs", async (ctx) => {
+    try {
+        return ctx.json(await info({ type: "metaAndAssetCtxs" }));
+    } catch (err) {
+        if (err instanceof UpstreamError) return ctx.json({ error: err.message }, 502);
+        throw err;
+    }
+});
+
+market.get("/l2-book", async (ctx) => {
+    try {
+        const coin = await requireCoin(ctx.req.query("coin"));
+        return ctx.json(await info({ type: "l2Book", coin }));
+    } catch (err) {
+        if (err instanceof ValidationError) return ctx.json({ error: err.message }, 400);
+        if (err instanceof UpstreamError) return ctx.json({ error: err.message }, 502);
+        throw err;
+    }
+});
+
+export { market };
@@ -0,0 +1,30 @@
+import { Hono } from "hono";
+import { db } from "../../lib/db.ts";
+
+const keys = new Hono<{ Variables: { userId: string; apiKeyId: string; apiKeyScopes: string[] } }>();
+
+/**
+ * GET /atlas-os/keys/self
+ * The key used for this request and its scopes. Answers any valid key, so
+ * the CLI can check a key's scopes before it needs them.
+ * Auth: Atlas API key (atl_xxx) via apiKeyAuth middleware.
+ *
+ * Response: { id, name, prefix, scopes: string[], created_at, last_used_at }
+ */
+keys.get("/self", async (ctx) => {
+    const { rows } = await db.query<{
+        id: string;
+        name: string;
+        prefix: string;
+        created_at: string;
+        last_used_at: string | null;
+    }>(
+        "SELECT id, name, prefix, created_at, last_used_at FROM api_keys WHERE id = $1",
+        [ctx.get("apiKeyId")]
+    );
+    const key = rows[0];
+    if (!key) return ctx.json({ error: "Key not found" }, 404);
+    return ctx.json({ ...key, scopes: ctx.get("apiKeyScopes") });
+});
+
+export { keys };
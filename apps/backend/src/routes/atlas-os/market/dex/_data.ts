@@ -25,12 +25,16 @@ export interface DemoPool {
         symbol: string;
         name: string;
         image_url: string | null;
+        /** CoinGecko coin id; null for tokens CoinGecko does not list. */
+        coingecko_coin_id: string | null;
     };
     quote_token: {
         address: string;
         symbol: string;
         name: string;
         image_url: string | null;
+        /** CoinGecko coin id; null for tokens CoinGecko does not list. */
+        coingecko_coin_id: string | null;
     };
     base_token_price_usd: string;
     base_token_price_native_currency: string;
@@ -61,12 +65,14 @@ export const DEMO_POOLS: DemoPool[] = [
             symbol: "WETH",
             name: "Wrapped Ether",
             image_url: "https://assets.coingecko.com/coins/images/2518/small/weth.png",
+            coingecko_coin_id: "weth",
         },
         quote_token: {
             address: "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
             symbol: "USDC",
             name: "USD Coin",
             image_url: "https://assets.coingecko.com/coins/images/6319/small/usdc.png",
+            coingecko_coin_id: "usd-coin",
         },
         base_token_price_usd: "3241.58",
         base_token_price_native_currency: "1.0",
@@ -95,12 +101,14 @@ export const DEMO_POOLS: DemoPool[] = [
             symbol: "WBTC",
             name: "Wrapped Bitcoin",
             image_url: "https://assets.coingecko.com/coins/images/7598/small/wrapped_bitcoin_wbtc.png",
+            coingecko_coin_id: "wrapped-bitcoin",
         },
         quote_token: {
             address: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
             symbol: "WETH",
             name: "Wrapped Ether",
             image_url: "https://assets.coingecko.com/coins/images/2518/small/weth.png",
+            coingecko_coin_id: "weth",
         },
         base_token_price_usd: "97541.22",
         base_token_price_native_currency: "30.09",
@@ -135,6 +143,7 @@ export const DEMO_POOLS: DemoPool[] = [
             symbol: "WETH",
             name: "Wrapped Ether",
             image_url: "https://assets.coingecko.com/coins/images/2518/small/weth.png",
+            coingecko_coin_id: "weth",
         },
         base_token_price_usd: "1.8841",
         base_token_price_native_currency: "0.000581",
@@ -163,12 +172,14 @@ export const DEMO_POOLS: DemoPool[] = [
             symbol: "SOL",
             name: "Solana",
             image_url: "https://assets.coingecko.com/coins/images/4128/small/solana.png",
+            coingecko_coin_id: "solana",
         },
         quote_token: {
             address: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
             symbol: "USDC",
             name: "USD Coin",
             image_url: "https://assets.coingecko.com/coins/images/6319/small/usdc.png",
+            coingecko_coin_id: "usd-coin",
         },
         base_token_price_usd: "174.32",
         base_token_price_native_currency: "1.0",
@@ -203,6 +214,7 @@ export const DEMO_POOLS: DemoPool[] = [
             symbol: "WETH",
             name: "Wrapped Ether",
             image_url: "https://assets.coingecko.com/coins/images/2518/small/weth.png",
+            coingecko_coin_id: "weth",
         },
         base_token_price_usd: "0.008241",
         base_token_price_native_currency: "0.00000254",
@@ -222,6 +234,14 @@ export const DEMO_POOLS: DemoPool[] = [
     },
 ];
 
+const EVM_ADDRESS = /^0x[0-9a-fA-F]{40}$/;
+const BASE58_ADDRESS = /^[1-9A-HJ-NP-Za-km-z]{32,44}$/;
+
+/** Whether `address` is a well-formed contract address on `network`. */
+export function isAddress(network: string, address: string): boolean {
+    return network === "solana" ? BASE58_ADDRESS.test(address) : EVM_ADDRESS.test(address);
+}
+
 /** Compact pool summary for list endpoints */
 export function poolSummary(pool: DemoPool) {
     return {
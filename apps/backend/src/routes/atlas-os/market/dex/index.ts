@@ -2,13 +2,17 @@ import { Hono } from "hono";
 import { networks } from "./networks.ts";
 import { trending } from "./trending.ts";
 import { pools } from "./pools.ts";
+import { poolHistory } from "./pool-history.ts";
 import { tokens } from "./tokens.ts";
 import { search } from "./search.ts";
+import { screen } from "./screen.ts";
+import type { CacheRule } from "../../../../middleware/cache.ts";
 
 /**
  * /atlas-os/dex — DEX Market Data (Demo)
  *
  * Auth: Atlas API key (atl_xxx) via apiKeyAuth middleware (set upstream in index.ts)
+ * Cache: DEX_CACHE_RULES via withCache (set upstream in index.ts)
  *
  * Route map:
  *   GET /atlas-os/dex/networks                     → networks.ts
@@ -16,15 +20,40 @@ import { search } from "./search.ts";
  *   GET /atlas-os/dex/trending/:network            → trending.ts
  *   GET /atlas-os/dex/pools/:network               → pools.ts
  *   GET /atlas-os/dex/pools/:network/:address      → pools.ts
+ *   GET /atlas-os/dex/pools/:network/:address/ohlcv/:timeframe → pool-history.ts
+ *   GET /atlas-os/dex/pools/:network/:address/trades           → pool-history.ts
  *   GET /atlas-os/dex/tokens/:network/:address     → tokens.ts
  *   GET /atlas-os/dex/search?q=&network=           → search.ts
+ *   GET /atlas-os/dex/screen?pools=net:addr,...    → screen.ts
  */
 const dex = new Hono();
 
 dex.route("/networks", networks);
 dex.route("/trending", trending);
 dex.route("/pools", pools);
+dex.route("/pools", poolHistory);
 dex.route("/tokens", tokens);
 dex.route("/search", search);
+dex.route("/screen", screen);
+
+const SEC = 1_000;
+const MIN = 60 * SEC;
+
+/**
+ * Response cache TTLs (see middleware/cache.ts). Trending and pools are
+ * polled hardest, so they also serve stale data while refreshing and when
+ * the upstream rate-limits. Rules are tried in order, so the short-lived
+ * candle and trade rules come before the general pools rule.
+ */
+export const DEX_CACHE_RULES: CacheRule[] = [
+    { pattern: /^\/networks/, ttlMs: 60 * MIN },
+    { pattern: /^\/trending/, ttlMs: 60 * SEC, staleMs: 5 * MIN },
+    { pattern: /^\/pools\/[^/]+\/[^/]+\/ohlcv\//, ttlMs: 60 * SEC, staleMs: 2 * MIN },
+    { pattern: /^\/pools\/[^/]+\/[^/]+\/trades/, ttlMs: 15 * SEC },
+    { pattern: /^\/pools/, ttlMs: 30 * SEC, staleMs: 2 * MIN },
+    { pattern: /^\/tokens/, ttlMs: 5 * MIN },
+    { pattern: /^\/search/, ttlMs: 60 * SEC },
+    { pattern: /^\/screen/, ttlMs: 30 * SEC },
+];
 
 export { dex };
@@ -0,0 +1,174 @@
+import { Hono, type Context } from "hono";
+import { DEMO_NETWORKS, DEMO_POOLS, isAddress, type DemoPool } from "./_data.ts";
+
+/**
+ * Pool analytics routes (GeckoTerminal response shapes)
+ * GET /atlas-os/dex/pools/:network/:address/ohlcv/:timeframe?aggregate=&limit=
+ *     — candles, newest first, never older than the pool
+ * GET /atlas-os/dex/pools/:network/:address/trades?trade_volume_in_usd_greater_than=
+ *     — latest trades, newest first
+ *
+ * Demo data is synthesized deterministically from the pool's current price
+ * and 24h volume, so repeated requests within one candle agree.
+ */
+const poolHistory = new Hono();
+
+const SEC = 1_000;
+const AGGREGATES: Record<string, { unitMs: number; allowed: number[] }> = {
+    day: { unitMs: 86_400 * SEC, allowed: [1] },
+    hour: { unitMs: 3_600 * SEC, allowed: [1, 4, 12] },
+    minute: { unitMs: 60 * SEC, allowed: [1, 5, 15] },
+};
+const MAX_OHLCV_LIMIT = 1000;
+const DEFAULT_OHLCV_LIMIT = 100;
+const MAX_TRADES = 300;
+
+/** Validate `:network/:address`; returns the pool or an error response. */
+function findPool(ctx: Context): DemoPool | Response {
+    const network = ctx.req.param("network")!.toLowerCase();
+    const address = ctx.req.param("address")!;
+
+    if (!DEMO_NETWORKS.some((n) => n.id === network)) {
+        const ids = DEMO_NETWORKS.map((n) => n.id).join(", ");
+        return ctx.json({ error: `Unsupported network: "${network}". Use one of: ${ids}` }, 400);
+    }
+    if (!isAddress(network, address)) {
+        return ctx.json({ error: `Invalid pool address for ${network}: "${address}"` }, 400);
+    }
+
+    const pool = DEMO_POOLS.find(
+        (p) => p.network === network && p.address.toLowerCase() === address.toLowerCase()
+    );
+    if (!pool) {
+        return ctx.json(
+            {
+                error: `Pool not found: ${address} on ${network}.`,
+                hint: "Only demo pools are available. GET /atlas-os/dex/pools/:network for the full list.",
+            },
+            404
+        );
+    }
+    return pool;
+}
+
+/** Deterministic value in [0, 1) for a pool and a point in time. */
+function noise(pool: DemoPool, key: number): number {
+    let h = 2166136261;
+    for (const ch of `${pool.address}:${key}`) {
+        h = Math.imul(h ^ ch.charCodeAt(0), 16777619);
+    }
+    return (h >>> 0) / 2 ** 32;
+}
+
+const fmt = (n: number) => Number(n.toPrecision(8));
+
+poolHistory.get("/:network/:address/ohlcv/:timeframe", (ctx) => {
+    const pool = findPool(ctx);
+    if (pool instanceof Response) return pool;
+
+    const timeframe = ctx.req.param("timeframe").toLowerCase();
+    const spec = AGGREGATES[timeframe];
+    if (!spec) {
+        return ctx.json({ error: `Invalid timeframe: "${timeframe}". Use: day, hour, minute` }, 400);
+    }
+    const aggregate = Number(ctx.req.query("aggregate") ?? "1");
+    if (!spec.allowed.includes(aggregate)) {
+        return ctx.json(
+            { error: `Invalid aggregate for ${timeframe}: "${aggregate}". Use: ${spec.allowed.join(", ")}` },
+            400
+        );
+    }
+    const limit = Number(ctx.req.query("limit") ?? DEFAULT_OHLCV_LIMIT);
+    if (!Number.isInteger(limit) || limit < 1 || limit > MAX_OHLCV_LIMIT) {
+        return ctx.json({ error: `'limit' must be an integer 1-${MAX_OHLCV_LIMIT}` }, 400);
+    }
+
+    const periodMs = spec.unitMs * aggregate;
+    const createdMs = Date.parse(pool.pool_created_at);
+    const volumePerMs = Number(pool.volume_usd.h24) / (86_400 * SEC);
+
+    // Walk back from the current candle and the current price; stop at the
+    // candle the pool was created in
+    const ohlcvList: number[][] = [];
+    let close = Number(pool.base_token_price_usd);
+    for (let i = 0, t = Math.floor(Date.now() / periodMs) * periodMs; i < limit; i++, t -= periodMs) {
+        if (t + periodMs <= createdMs) break;
+        const drift = (noise(pool, t) - 0.5) * 0.02 * Math.sqrt(aggregate);
+        const open = close / (1 + drift);
+        const wick = noise(pool, t + 1) * 0.005;
+        ohlcvList.push([
+            t / SEC,
+            fmt(open),
+            fmt(Math.max(open, close) * (1 + wick)),
+            fmt(Math.min(open, close) * (1 - wick)),
+            fmt(close),
+            Number((volumePerMs * periodMs * (0.5 + noise(pool, t + 2))).toFixed(2)),
+        ]);
+        close = open;
+    }
+
+    const token = (t: DemoPool["base_token"]) => ({ address: t.address, name: t.name, symbol: t.symbol });
+    return ctx.json({
+        data: {
+            id: `${pool.id}_${timeframe}_${aggregate}`,
+            type: "ohlcv_request_response",
+            attributes: { ohlcv_list: ohlcvList },
+        },
+        meta: { base: token(pool.base_token), quote: token(pool.quote_token) },
+        _demo: true,
+    });
+});
+
+poolHistory.get("/:network/:address/trades", (ctx) => {
+    const pool = findPool(ctx);
+    if (pool instanceof Response) return pool;
+
+    const minRaw = ctx.req.query("trade_volume_in_usd_greater_than");
+    const minUsd = minRaw === undefined ? 0 : Number(minRaw);
+    if (!Number.isFinite(minUsd) || minUsd < 0) {
+        return ctx.json({ error: "'trade_volume_in_usd_greater_than' must be a non-negative number" }, 400);
+    }
+
+    const basePx = Number(pool.base_token_price_usd);
+    const quotePx = Number(pool.quote_token_price_usd);
+    const h24 = pool.transactions.h24;
+    const tradeCount = h24.buys + h24.sells;
+    const avgUsd = tradeCount > 0 ? Number(pool.volume_usd.h24) / tradeCount : 0;
+    const spacingMs = tradeCount > 0 ? (86_400 * SEC) / tradeCount : 0;
+    const createdMs = Date.parse(pool.pool_created_at);
+
+    const trades = [];
+    let t = Math.floor(Date.now() / SEC) * SEC;
+    for (let i = 0; i < MAX_TRADES && tradeCount > 0 && t >= createdMs; i++) {
+        const r = noise(pool, t);
+        // Heavy-tailed sizes: most trades small, a few large
+        const usd = avgUsd * 0.1 * Math.exp(4 * noise(pool, t + 1));
+        const kind = r < h24.buys / tradeCount ? "buy" : "sell";
+        if (usd >= minUsd) {
+            const baseAmt = usd / basePx;
+            const quoteAmt = usd / quotePx;
+            const [from, to] = kind === "buy" ? [quoteAmt, baseAmt] : [baseAmt, quoteAmt];
+            const [fromPx, toPx] = kind === "buy" ? [quotePx, basePx] : [basePx, quotePx];
+            const txHash = `0x${Math.floor(r * 2 ** 52).toString(16).padStart(64, "0")}`;
+            trades.push({
+                id: `${pool.network}_${t / SEC}_${txHash}_${i}`,
+                type: "trade",
+                attributes: {
+                    tx_hash: txHash,
+                    from_token_amount: String(fmt(from)),
+                    to_token_amount: String(fmt(to)),
+                    price_from_in_usd: String(fromPx),
+                    price_to_in_usd: String(toPx),
+                    block_timestamp: new Date(t).toISOString().replace(/\.\d{3}Z$/, "Z"),
+                    kind,
+                    volume_in_usd: usd.toFixed(2),
+                },
+            });
+        }
+        t -= Math.max(SEC, Math.round(spacingMs * 2 * noise(pool, t + 2)));
+    }
+
+    return ctx.json({ data: trades, _demo: true });
+});
+
+export { poolHistory };
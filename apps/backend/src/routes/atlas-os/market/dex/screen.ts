@@ -0,0 +1,134 @@
+import { Hono } from "hono";
+import { responseCache, type CachePolicy } from "../../../../lib/cache.ts";
+import { mapWithLimit } from "../../../../lib/concurrency.ts";
+import { DEMO_NETWORKS, DEMO_POOLS, isAddress, type DemoPool } from "./_data.ts";
+
+/**
+ * Batched lookups for `atlas market dex new --screen`
+ * GET /atlas-os/dex/screen?pools=<network>:<address>,...
+ *
+ * For each pool returns its detail and its base token's info in
+ * GeckoTerminal shapes (`{ data: { attributes } }`), `null` where a lookup
+ * found nothing. Lookups run a few at a time and are cached individually, so
+ * overlapping screens of the same new pools share them.
+ */
+const screen = new Hono();
+
+const MAX_POOLS = 20;
+const CONCURRENCY = 4;
+const POOL_POLICY: CachePolicy = { ttlMs: 30_000 };
+const TOKEN_POLICY: CachePolicy = { ttlMs: 5 * 60_000 };
+
+screen.get("/", async (ctx) => {
+    const raw = ctx.req.query("pools")?.trim();
+    if (!raw) {
+        return ctx.json({ error: "Query param 'pools' is required (network:address,...)" }, 400);
+    }
+    const entries = raw.split(",").map((e) => e.trim()).filter(Boolean);
+    if (entries.length > MAX_POOLS) {
+        return ctx.json({ error: `At most ${MAX_POOLS} pools per request` }, 400);
+    }
+
+    const pools: { network: string; address: string }[] = [];
+    for (const entry of entries) {
+        const [network = "", address = ""] = entry.split(":");
+        const net = network.toLowerCase();
+        if (!DEMO_NETWORKS.some((n) => n.id === net)) {
+            return ctx.json({ error: `Unsupported network: "${network}"` }, 400);
+        }
+        if (!isAddress(net, address)) {
+            return ctx.json({ error: `Invalid pool address for ${net}: "${address}"` }, 400);
+        }
+        pools.push({ network: net, address });
+    }
+
+    const data = await mapWithLimit(pools, CONCURRENCY, async ({ network, address }) => {
+        const poolKey = `/dex/screen/pool/${network}/${address.toLowerCase()}`;
+        const pool = await cachedLookup(poolKey, POOL_POLICY, () => poolDetail(network, address));
+
+        const tokenAddress = pool?.data.attributes.base_token_address;
+        const tokenKey = `/dex/screen/token/${network}/${tokenAddress?.toLowerCase()}`;
+        const baseToken = tokenAddress
+            ? await cachedLookup(tokenKey, TOKEN_POLICY, () => tokenInfo(network, tokenAddress))
+            : null;
+        return {
+            network,
+            address,
+            pool,
+            base_token: baseToken,
+            ...(pool ? {} : { error: `Pool not found: ${address} on ${network}` }),
+        };
+    });
+
+    return ctx.json({ data, _demo: true });
+});
+
+/** Run `lookup` through the shared response cache; `null` results are not cached. */
+async function cachedLookup<T>(
+    key: string,
+    policy: CachePolicy,
+    lookup: () => Promise<T | null>,
+): Promise<T | null> {
+    const { value } = await responseCache.fetch(key, policy, async () => {
+        const result = await lookup();
+        return {
+            status: result ? 200 : 404,
+            contentType: "application/json",
+            body: JSON.stringify(result),
+        };
+    });
+    return JSON.parse(value.body) as T | null;
+}
+
+function findDemoPool(network: string, address: string): DemoPool | undefined {
+    return DEMO_POOLS.find(
+        (p) => p.network === network && p.address.toLowerCase() === address.toLowerCase()
+    );
+}
+
+async function poolDetail(network: string, address: string) {
+    const pool = findDemoPool(network, address);
+    if (!pool) return null;
+    return {
+        data: {
+            id: pool.id,
+            type: "pool",
+            attributes: {
+                name: pool.name,
+                address: pool.address,
+                base_token_address: pool.base_token.address,
+                base_token_price_usd: pool.base_token_price_usd,
+                quote_token_price_usd: pool.quote_token_price_usd,
+                fdv_usd: pool.fdv_usd,
+                market_cap_usd: pool.market_cap_usd,
+                reserve_in_usd: pool.reserve_in_usd,
+                pool_created_at: pool.pool_created_at,
+                price_change_percentage: pool.price_change_percentage,
+                transactions: pool.transactions,
+                volume_usd: pool.volume_usd,
+            },
+        },
+    };
+}
+
+async function tokenInfo(network: string, address: string) {
+    const token = DEMO_POOLS.filter((p) => p.network === network)
+        .flatMap((p) => [p.base_token, p.quote_token])
+        .find((t) => t.address.toLowerCase() === address.toLowerCase());
+    if (!token) return null;
+    return {
+        data: {
+            id: `${network}_${token.address}`,
+            type: "token",
+            attributes: {
+                address: token.address,
+                name: token.name,
+                symbol: token.symbol,
+                image_url: token.image_url,
+                coingecko_coin_id: token.coingecko_coin_id,
+            },
+        },
+    };
+}
+
+export { screen };
@@ -43,6 +43,7 @@ tokens.get("/:network/:address", (ctx) => {
             symbol: token.symbol,
             name: token.name,
             image_url: token.image_url,
+            coingecko_coin_id: token.coingecko_coin_id,
             price_usd: priceUsd,
             price_change_percentage: priceChange,
             top_pools: containing.slice(0, 5).map(poolSummary),
@@ -0,0 +1,174 @@
+/**
+ * Shared client for the Morpho Blue GraphQL API.
+ *
+ * Upstream responses are flattened into the shapes the CLI decodes:
+ *   MorphoMarket   — one lending market with rates and utilization
+ *   MorphoPosition — one user position with collateral, debt, health factor
+ *
+ * Rates, utilization and LLTV are fractions (0.05 = 5%). Token amounts are
+ * decimal strings already scaled by the token's decimals.
+ *
+ * Reference: https://docs.morpho.org/morpho/tutorials/get-data
+ */
+
+const MORPHO_API = "https://blue-api.morpho.org/graphql";
+
+export const CHAIN_IDS: Record<string, number> = {
+    ethereum: 1,
+    base: 8453,
+    arbitrum: 42161,
+};
+
+export interface MorphoMarket {
+    id: string;
+    chain: string;
+    name: string;
+    collateral_asset: string;
+    loan_asset: string;
+    supply_apy: number | null;
+    borrow_apy: number | null;
+    utilization: number | null;
+    lltv: number | null;
+    total_supply_usd: number | null;
+    total_borrow_usd: number | null;
+    liquidity_usd: number | null;
+}
+
+export interface MorphoPosition {
+    market_id: string;
+    market: string;
+    collateral_asset: string;
+    loan_asset: string;
+    collateral: string;
+    collateral_usd: number | null;
+    borrowed: string;
+    borrowed_usd: number | null;
+    supplied: string;
+    supplied_usd: number | null;
+    health_factor: number | null;
+}
+
+export class UpstreamError extends Error {}
+
+export const MARKET_FIELDS = `
+    uniqueKey
+    lltv
+    loanAsset { symbol decimals }
+    collateralAsset { symbol decimals }
+    state {
+        supplyApy borrowApy utilization
+        supplyAssetsUsd borrowAssetsUsd liquidityAssetsUsd
+    }
+`;
+
+interface RawAsset {
+    symbol: string;
+    decimals: number;
+}
+
+export interface RawMarket {
+    uniqueKey: string;
+    lltv: string;
+    loanAsset: RawAsset;
+    collateralAsset: RawAsset | null;
+    state: {
+        supplyApy: number | null;
+        borrowApy: number | null;
+        utilization: number | null;
+        supplyAssetsUsd: number | null;
+        borrowAssetsUsd: number | null;
+        liquidityAssetsUsd: number | null;
+    } | null;
+}
+
+export interface RawPosition {
+    market: RawMarket;
+    healthFactor: number | null;
+    state: {
+        collateral: string | null;
+        collateralUsd: number | null;
+        borrowAssets: string | null;
+        borrowAssetsUsd: number | null;
+        supplyAssets: string | null;
+        supplyAssetsUsd: number | null;
+    } | null;
+}
+
+/** Resolve a chain name to its id; null if Morpho data is not served for it. */
+export function chainId(chain: string): number | null {
+    return CHAIN_IDS[chain.toLowerCase()] ?? null;
+}
+
+/**
+ * Run a GraphQL query. Returns null when Morpho reports NOT_FOUND (unknown
+ * market or an address that never interacted), throws on anything else.
+ */
+export async function query<T>(gql: string, variables: Record<string, unknown>): Promise<T | null> {
+    const res = await fetch(MORPHO_API, {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ query: gql, variables }),
+    });
+    if (!res.ok) throw new UpstreamError(`Morpho API error ${res.status}`);
+
+    const body = (await res.json()) as {
+        data?: T;
+        errors?: { message: string; status?: string }[];
+    };
+    if (body.errors?.length) {
+        if (body.errors.every((e) => e.status === "NOT_FOUND")) return null;
+        throw new UpstreamError(body.errors.map((e) => e.message).join("; "));
+    }
+    return body.data ?? null;
+}
+
+/** Scale a raw integer token amount by `decimals` without losing precision. */
+export function scaleUnits(raw: string | null, decimals: number): string {
+    if (!raw || raw === "0") return "0";
+    const negative = raw.startsWith("-");
+    const digits = (negative ? raw.slice(1) : raw).padStart(decimals + 1, "0");
+    const whole = digits.slice(0, digits.length - decimals);
+    const frac = digits.slice(digits.length - decimals).replace(/0+$/, "");
+    return `${negative ? "-" : ""}${whole}${frac ? `.${frac}` : ""}`;
+}
+
+export function marketName(m: RawMarket): string {
+    const collateral = m.collateralAsset?.symbol ?? "idle";
+    return `${collateral}/${m.loanAsset.symbol}`;
+}
+
+export function toMarket(m: RawMarket, chain: string): MorphoMarket {
+    return {
+        id: m.uniqueKey,
+        chain,
+        name: marketName(m),
+        collateral_asset: m.collateralAsset?.symbol ?? "",
+        loan_asset: m.loanAsset.symbol,
+        supply_apy: m.state?.supplyApy ?? null,
+        borrow_apy: m.state?.borrowApy ?? null,
+        utilization: m.state?.utilization ?? null,
+        // LLTV is a WAD (1e18 = 100%)
+        lltv: m.lltv ? Number(BigInt(m.lltv) / 10n ** 12n) / 1e6 : null,
+        total_supply_usd: m.state?.supplyAssetsUsd ?? null,
+        total_borrow_usd: m.state?.borrowAssetsUsd ?? null,
+        liquidity_usd: m.state?.liquidityAssetsUsd ?? null,
+    };
+}
+
+export function toPosition(p: RawPosition): MorphoPosition {
+    const m = p.market;
+    const collateralDecimals = m.collateralAsset?.decimals ?? 18;
+    return {
+        market_id: m.uniqueKey,
+        market: marketName(m),
+        collateral_asset: m.collateralAsset?.symbol ?? "",
+        loan_asset: m.loanAsset.symbol,
+        collateral: scaleUnits(p.state?.collateral ?? null, collateralDecimals),
+        collateral_usd: p.state?.collateralUsd ?? null,
+        borrowed: scaleUnits(p.state?.borrowAssets ?? null, m.loanAsset.decimals),
+        borrowed_usd: p.state?.borrowAssetsUsd ?? null,
+        supplied: scaleUnits(p.state?.supplyAssets ?? null, m.loanAsset.decimals),
+        supplied_usd: p.state?.supplyAssetsUsd ?? null,
+        health_factor: p.healthFactor,
+    };
+}
@@ -0,0 +1,20 @@
+import { Hono } from "hono";
+import { markets } from "./markets.ts";
+import { positions } from "./positions.ts";
+
+/**
+ * /atlas-os/morpho — Morpho Blue lending data
+ *
+ * Auth: Atlas API key (atl_xxx) via apiKeyAuth middleware (set upstream in index.ts)
+ *
+ * Route map:
+ *   GET /atlas-os/morpho/markets?chain=&limit=        → markets.ts
+ *   GET /atlas-os/morpho/markets/:id?chain=           → markets.ts
+ *   GET /atlas-os/morpho/positions/:address?chain=    → positions.ts
+ */
+const morpho = new Hono();
+
+morpho.route("/markets", markets);
+morpho.route("/positions", positions);
+
+export { morpho };
@@ -0,0 +1,60 @@
+import { Hono } from "hono";
+import { chainId, query, toMarket, MARKET_FIELDS, UpstreamError, type RawMarket } from "./_api.ts";
+
+/**
+ * Market routes
+ * GET /atlas-os/morpho/markets?chain=ethereum&limit=50  — whitelisted markets by supply
+ * GET /atlas-os/morpho/markets/:id?chain=ethereum       — single market by unique key
+ */
+const markets = new Hono();
+
+const MAX_LIMIT = 200;
+
+markets.get("/", async (ctx) => {
+    const chain = (ctx.req.query("chain") ?? "ethereum").toLowerCase();
+    const id = chainId(chain);
+    if (id === null) return ctx.json({ error: `Unsupported chain: "${chain}"` }, 400);
+    const limit = Math.min(parseInt(ctx.req.query("limit") ?? "50", 10) || 50, MAX_LIMIT);
+
+    try {
+        const data = await query<{ markets: { items: RawMarket[] } }>(
+            `query($chainId: Int!, $first: Int!) {
+                markets(
+                    first: $first
+                    orderBy: SupplyAssetsUsd
+                    orderDirection: Desc
+                    where: { chainId_in: [$chainId], whitelisted: true }
+                ) { items { ${MARKET_FIELDS} } }
+            }`,
+            { chainId: id, first: limit },
+        );
+        const items = (data?.markets.items ?? []).map((m) => toMarket(m, chain));
+        return ctx.json({ data: items, meta: { chain, total: items.length } });
+    } catch (err) {
+        if (err instanceof UpstreamError) return ctx.json({ error: err.message }, 502);
+        throw err;
+    }
+});
+
+markets.get("/:id", async (ctx) => {
+    const chain = (ctx.req.query("chain") ?? "ethereum").toLowerCase();
+    const id = chainId(chain);
+    if (id === null) return ctx.json({ error: `Unsupported chain: "${chain}"` }, 400);
+    const key = ctx.req.param("id").toLowerCase();
+
+    try {
+        const data = await query<{ marketByUniqueKey: RawMarket }>(
+            `query($key: String!, $chainId: Int!) {
+                marketByUniqueKey(uniqueKey: $key, chainId: $chainId) { ${MARKET_FIELDS} }
+            }`,
+            { key, chainId: id },
+        );
+        if (!data) return ctx.json({ error: `Market not found: ${key} on ${chain}` }, 404);
+        return ctx.json({ data: toMarket(data.marketByUniqueKey, chain) });
+    } catch (err) {
+        if (err instanceof UpstreamError) return ctx.json({ error: err.message }, 502);
+        throw err;
+    }
+});
+
+export { markets };
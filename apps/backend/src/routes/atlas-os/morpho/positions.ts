@@ -0,0 +1,56 @@
+import { Hono } from "hono";
+import {
+    chainId,
+    query,
+    toPosition,
+    MARKET_FIELDS,
+    UpstreamError,
+    type RawPosition,
+} from "./_api.ts";
+
+/**
+ * Position routes
+ * GET /atlas-os/morpho/positions/:address?chain=ethereum
+ *
+ * An address Morpho has never seen returns an empty list, not a 404 —
+ * "no positions" is a valid answer.
+ */
+const positions = new Hono();
+
+positions.get("/:address", async (ctx) => {
+    const chain = (ctx.req.query("chain") ?? "ethereum").toLowerCase();
+    const id = chainId(chain);
+    if (id === null) return ctx.json({ error: `Unsupported chain: "${chain}"` }, 400);
+    const address = ctx.req.param("address");
+    if (!/^0x[0-9a-fA-F]{40}$/.test(address)) {
+        return ctx.json({ error: `Invalid address: ${address}` }, 400);
+    }
+
+    try {
+        const data = await query<{ userByAddress: { marketPositions: RawPosition[] } }>(
+            `query($address: String!, $chainId: Int!) {
+                userByAddress(address: $address, chainId: $chainId) {
+                    marketPositions {
+                        healthFactor
+                        market { ${MARKET_FIELDS} }
+                        state {
+                            collateral collateralUsd
+                            borrowAssets borrowAssetsUsd
+                            supplyAssets supplyAssetsUsd
+                        }
+                    }
+                }
+            }`,
+            { address, chainId: id },
+        );
+        const items = (data?.userByAddress.marketPositions ?? [])
+            .map(toPosition)
+            .filter((p) => p.collateral !== "0" || p.borrowed !== "0" || p.supplied !== "0");
+        return ctx.json({ data: items, meta: { chain, address, total: items.length } });
+    } catch (err) {
+        if (err instanceof UpstreamError) return ctx.json({ error: err.message }, 502);
+        throw err;
+    }
+});
+
+export { positions };
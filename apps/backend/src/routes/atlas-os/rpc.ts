@@ -377,25 +377,21 @@ rpc.post("/:chain", async (ctx) => {
 // This route does NOT go through apiKeyAuth middleware — it validates
 // the key inline from the path param.
 
-import { db } from "../../lib/db.ts";
-import { hashKey } from "../../lib/keygen.ts";
+import { unauthorized, rateLimited } from "../../middleware/apikey.ts";
+import { resolveApiKey, takeToken } from "../../lib/apikeys.ts";
+import { scopeMissing } from "../../lib/scopes.ts";
 
 rpc.post("/v2/:apiKey/:chain", async (ctx) => {
     // ── Validate API key from path ──
     const apiKey = ctx.req.param("apiKey");
-    if (!apiKey?.startsWith("atl_")) {
-        return ctx.json({ error: "Invalid API key format" }, 401);
-    }
+    if (!apiKey?.startsWith("atl_")) return unauthorized(ctx, "API_KEY_MISSING");
 
-    const keyHash = hashKey(apiKey);
-    const { rows } = await db.query<{ id: string; user_id: string }>(
-        "SELECT id, user_id FROM api_keys WHERE key_hash = $1",
-        [keyHash]
-    );
+    const key = await resolveApiKey(apiKey);
+    if (!key) return unauthorized(ctx, "API_KEY_INVALID");
 
-    if (rows.length === 0) {
-        return ctx.json({ error: "Invalid API key" }, 401);
-    }
+    const limit = await takeToken(key.id);
+    if (!limit.allowed) return rateLimited(ctx, limit.retryAfterSecs);
+    if (!key.scopes.includes("read")) return scopeMissing(ctx, "read", key.scopes);
 
     // ── Resolve chain ──
     const alias = ctx.req.param("chain").toLowerCase();
@@ -1,5 +1,10 @@
 import { Hono } from "hono";
+import { runChecks, type DependencyCheck } from "../lib/readiness.ts";
 
+/**
+ * GET /healthz
+ * Liveness: the process is up and serving. Checks nothing else.
+ */
 const health = new Hono();
 
 health.get("/", (ctx) => {
@@ -11,4 +16,18 @@ health.get("/", (ctx) => {
     });
 });
 
-export { health };
+/**
+ * GET /readyz
+ * Readiness: runs `checks` and reports each dependency's status and
+ * latency. 200 when none failed, 503 otherwise.
+ */
+function readiness(checks: Record<string, DependencyCheck>): Hono {
+    const ready = new Hono();
+    ready.get("/", async (ctx) => {
+        const report = await runChecks(checks);
+        return ctx.json(report, report.status === "ready" ? 200 : 503);
+    });
+    return ready;
+}
+
+export { health, readiness };
@@ -2,12 +2,20 @@ import { Hono } from "hono";
 import { db } from "../lib/db.ts";
 import { redis } from "../lib/redis.ts";
 import { generateApiKey, keyPrefix, hashKey } from "../lib/keygen.ts";
+import { parseScopes } from "../lib/scopes.ts";
 
 const keys = new Hono<{ Variables: { userId: string } }>();
 
 const CACHE_TTL = 60;
 const cacheKey = (userId: string) => `atlas:keys:${userId}`;
 
+/** Body of POST /keys. */
+export interface CreateApiKeyRequest {
+    name?: string;
+    /** Defaults to every scope; see lib/scopes.ts. */
+    scopes?: unknown;
+}
+
 /**
  * GET /api/keys
  * List API keys for the authenticated user. Redis-cached 60s.
@@ -25,9 +33,11 @@ keys.get("/", async (ctx) => {
         user_id: string;
         name: string;
         prefix: string;
+        scopes: string[];
         created_at: string;
+        last_used_at: string | null;
     }>(
-        "SELECT id, user_id, name, prefix, created_at FROM api_keys WHERE user_id = $1 ORDER BY created_at DESC",
+        "SELECT id, user_id, name, prefix, scopes, created_at, last_used_at FROM api_keys WHERE user_id = $1 ORDER BY created_at DESC",
         [userId]
     );
 
@@ -38,16 +48,21 @@ keys.get("/", async (ctx) => {
 /**
  * POST /api/keys
  * Create a new API key for the authenticated user.
- * Body: { name: string }
+ * Body: { name: string, scopes?: ("read" | "trade-analytics" | "config-sync")[] }
+ * Omitting `scopes` grants all of them; ["read"] makes a read-only key.
  */
 keys.post("/", async (ctx) => {
     const userId = ctx.get("userId");
-    const body = await ctx.req.json<{ name?: string }>();
+    const body = await ctx.req.json<CreateApiKeyRequest>();
     const { name } = body;
 
     if (!name?.trim()) {
         return ctx.json({ error: "name is required" }, 400);
     }
+    const parsed = parseScopes(body.scopes);
+    if ("error" in parsed) {
+        return ctx.json({ error: parsed.error, code: "INVALID_SCOPES" }, 400);
+    }
 
     const rawKey = generateApiKey();
     const prefix = keyPrefix(rawKey);
@@ -58,12 +73,13 @@ keys.post("/", async (ctx) => {
         user_id: string;
         name: string;
         prefix: string;
+        scopes: string[];
         created_at: string;
     }>(
-        `INSERT INTO api_keys (user_id, name, prefix, key_hash)
-     VALUES ($1, $2, $3, $4)
-     RETURNING id, user_id, name, prefix, created_at`,
-        [userId, name.trim(), prefix, keyHash]
+        `INSERT INTO api_keys (user_id, name, prefix, key_hash, scopes)
+     VALUES ($1, $2, $3, $4, $5)
+     RETURNING id, user_id, name, prefix, scopes, created_at`,
+        [userId, name.trim(), prefix, keyHash, parsed.scopes]
     );
 
     const record = result.rows[0];
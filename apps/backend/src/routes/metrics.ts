@@ -0,0 +1,25 @@
+import { Hono } from "hono";
+import { responseCache } from "../lib/cache.ts";
+import { hlMids } from "../lib/hl-feed.ts";
+
+/**
+ * GET /metrics
+ * Process-local counters. Public — contains no user data.
+ */
+const metrics = new Hono();
+
+metrics.get("/", (ctx) => {
+    const cache = responseCache.stats();
+    const lookups = cache.hits + cache.misses + cache.stale;
+    return ctx.json({
+        cache: {
+            ...cache,
+            hit_ratio: lookups ? Number(((cache.hits + cache.stale) / lookups).toFixed(4)) : null,
+        },
+        price_stream: hlMids.stats(),
+        uptime_secs: Math.round(process.uptime()),
+        timestamp: new Date().toISOString(),
+    });
+});
+
+export { metrics };
@@ -0,0 +1,80 @@
+import { Hono } from "hono";
+import { createBunWebSocket } from "hono/bun";
+import type { ServerWebSocket } from "bun";
+import { hlMids, type Mids } from "../lib/hl-feed.ts";
+
+const { upgradeWebSocket, websocket } = createBunWebSocket<ServerWebSocket>();
+
+/** A client this far behind is disconnected instead of buffered further. */
+const SLOW_CONSUMER_BYTES = 1024 * 1024;
+
+/**
+ * GET /ws/prices — WebSocket fan-out of Hyperliquid mid prices.
+ * Auth: Atlas API key (set upstream in index.ts).
+ *
+ * Client → server:
+ *   {"op":"subscribe","coins":["BTC","ETH"]}   narrow to coins ([] = all)
+ *   {"op":"ping"}                              → {"type":"pong"}
+ *
+ * Server → client:
+ *   {"type":"mids","mids":{"BTC":"97000.5",...},"ts":1700000000000}
+ *   {"type":"subscribed","coins":[...]}
+ *   {"type":"error","message":"..."}
+ *
+ * Protocol-level pings are sent by Bun (see `wsHandler`).
+ */
+const ws = new Hono();
+
+ws.get(
+    "/prices",
+    upgradeWebSocket(() => {
+        let coins: Set<string> | null = null;
+        let unsubscribe: (() => void) | null = null;
+
+        return {
+            onOpen(_evt, socket) {
+                unsubscribe = hlMids.subscribe((mids: Mids) => {
+                    const raw = socket.raw as ServerWebSocket | undefined;
+                    if (raw && raw.getBufferedAmount() > SLOW_CONSUMER_BYTES) {
+                        unsubscribe?.();
+                        unsubscribe = null;
+                        socket.close(1013, "Slow consumer");
+                        return;
+                    }
+                    const data = coins
+                        ? Object.fromEntries(Object.entries(mids).filter(([c]) => coins!.has(c)))
+                        : mids;
+                    if (Object.keys(data).length === 0) return;
+                    socket.send(JSON.stringify({ type: "mids", mids: data, ts: Date.now() }));
+                });
+            },
+            onMessage(evt, socket) {
+                let msg: { op?: string; coins?: unknown };
+                try {
+                    msg = JSON.parse(String(evt.data));
+                } catch {
+                    socket.send(JSON.stringify({ type: "error", message: "Invalid JSON" }));
+                    return;
+                }
+                if (msg.op === "ping") {
+                    socket.send(JSON.stringify({ type: "pong" }));
+                } else if (msg.op === "subscribe" && Array.isArray(msg.coins)) {
+                    const list = msg.coins.map((c) => String(c).toUpperCase());
+                    coins = list.length > 0 ? new Set(list) : null;
+                    socket.send(JSON.stringify({ type: "subscribed", coins: list }));
+                } else {
+                    socket.send(JSON.stringify({ type: "error", message: "Unknown op" }));
+                }
+            },
+            onClose() {
+                unsubscribe?.();
+                unsubscribe = null;
+            },
+        };
+    })
+);
+
+/** Bun websocket handler for `export default` — idle clients are pinged, dead ones dropped. */
+const wsHandler = { ...websocket, idleTimeout: 60, sendPings: true };
+
+export { ws, wsHandler };
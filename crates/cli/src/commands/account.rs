@@ -1,13 +1,20 @@
 use anyhow::Result;
+use atlas_core::ens;
 use atlas_core::output::{render, OutputFormat};
-use atlas_core::output::{LeverageOutput, MarginOutput, TransferOutput};
+use atlas_core::output::{
+    LeverageAuditOutput, LeverageAuditRow, LeverageOutput, MarginOutput, TransferOutput,
+};
+use atlas_core::prompt::{confirm_action, confirm_attended};
+use atlas_core::workspace::load_config;
 use rust_decimal::prelude::*;
 
+use super::helpers::parse_address;
+
 /// `atlas leverage <coin> <value> [--cross]`
 pub async fn set_leverage(coin: &str, value: u32, cross: bool, fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
 
     perp.set_leverage(&coin_upper, value, cross)
         .await
@@ -22,11 +29,80 @@ pub async fn set_leverage(coin: &str, value: u32, cross: bool, fmt: OutputFormat
     Ok(())
 }
 
+/// `atlas hl perp leverage-audit [--apply-default] [--yes]`
+///
+/// Compares every open position's leverage with
+/// `modules.hyperliquid.default_leverage`. With `apply_default`, sets the
+/// mismatched coins to the default in their current margin mode; a coin
+/// that fails is reported on its row and the rest still go ahead.
+pub async fn leverage_audit(apply_default: bool, yes: bool, fmt: OutputFormat) -> Result<()> {
+    let config = load_config()?;
+    let default = config.modules.hyperliquid.config.default_leverage;
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+
+    let positions = perp.positions().await.map_err(|e| anyhow::anyhow!("{e}"))?;
+    let mut rows: Vec<LeverageAuditRow> = positions
+        .iter()
+        .filter(|p| !p.size.is_zero())
+        .map(|p| {
+            let mismatch = p.leverage.is_some_and(|l| l != default);
+            LeverageAuditRow {
+                coin: p.symbol.clone(),
+                leverage: p.leverage,
+                mode: p.margin_mode.clone(),
+                default_leverage: default,
+                mismatch,
+                action: if mismatch { "flagged" } else { "none" }.into(),
+                error: None,
+            }
+        })
+        .collect();
+
+    if apply_default && rows.iter().any(|r| r.mismatch) {
+        let summary: Vec<(&str, String)> = rows
+            .iter()
+            .filter(|r| r.mismatch)
+            .map(|r| {
+                let current = r.leverage.unwrap_or_default();
+                let mode = r.mode.as_deref().unwrap_or("isolated");
+                (
+                    r.coin.as_str(),
+                    format!("{current}x -> {default}x ({mode})"),
+                )
+            })
+            .collect();
+        let skip = yes || !config.trading.confirm;
+        if !confirm_action("ALIGN LEVERAGE — CONFIRM", &summary, skip, fmt)? {
+            return Ok(());
+        }
+
+        for row in rows.iter_mut().filter(|r| r.mismatch) {
+            let cross = row.mode.as_deref() == Some("cross");
+            match perp.set_leverage(&row.coin, default, cross).await {
+                Ok(()) => row.action = "set".into(),
+                Err(e) => {
+                    row.action = "failed".into();
+                    row.error = Some(e.to_string());
+                }
+            }
+        }
+    }
+
+    render(
+        fmt,
+        &LeverageAuditOutput {
+            default_leverage: default,
+            rows,
+        },
+    )
+}
+
 /// `atlas margin <coin> <amount>`
 pub async fn update_margin(coin: &str, amount: f64, fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
 
     let dec_amount =
         Decimal::from_f64(amount).ok_or_else(|| anyhow::anyhow!("Invalid amount: {amount}"))?;
@@ -44,22 +120,82 @@ pub async fn update_margin(coin: &str, amount: f64, fmt: OutputFormat) -> Result
     Ok(())
 }
 
-/// `atlas transfer <amount> <destination>`
-pub async fn transfer_usdc(amount: &str, destination: &str, fmt: OutputFormat) -> Result<()> {
-    let orch = crate::factory::from_active_profile().await?;
-    let perp = orch.perp(None)?;
-
+/// `atlas transfer <amount> <destination> [--yes]`
+///
+/// A `.eth` destination is resolved before the prompt, so the confirmation
+/// shows the address that will be paid; a failed lookup aborts.
+pub async fn transfer_usdc(
+    amount: &str,
+    destination: &str,
+    yes: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let config = load_config()?;
     let dec_amount: Decimal = amount
         .parse()
         .map_err(|_| anyhow::anyhow!("Invalid amount: {amount}"))?;
 
-    perp.transfer(dec_amount, destination)
+    let (name, address) = if ens::is_name(destination) {
+        let resolved = ens::resolve(&ens::rpc_url(&config)?, destination).await?;
+        (
+            Some(ens::normalize(destination)?),
+            resolved.to_checksum(None),
+        )
+    } else {
+        (None, parse_address(destination)?)
+    };
+    let label = config
+        .security
+        .whitelist
+        .check(&address)?
+        .map(str::to_string);
+
+    let mut rows = vec![
+        ("Amount", format!("{dec_amount} USDC")),
+        (
+            "Destination",
+            match &name {
+                Some(name) => format!("{name} → {address}"),
+                None => address.clone(),
+            },
+        ),
+    ];
+    if let Some(label) = &label {
+        rows.push(("Whitelisted", label.clone()));
+    }
+    rows.push((
+        "Profile",
+        atlas_core::AuthManager::active_profile(&config).to_string(),
+    ));
+    let proceed = confirm_attended(
+        "USDC TRANSFER — CONFIRM",
+        &rows,
+        yes || !config.trading.confirm,
+        fmt,
+    )?;
+    if !proceed {
+        return Ok(());
+    }
+    // Not skippable with --yes: only the configured threshold turns it off
+    atlas_core::approval::require_transfer_approval(
+        &config.security,
+        dec_amount.to_f64().unwrap_or(f64::MAX),
+        &format!("Transfer of {dec_amount} USDC to {address}"),
+    )
+    .await?;
+
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+
+    perp.transfer(dec_amount, &address)
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))?;
 
     let output = TransferOutput {
         amount: amount.to_string(),
-        destination: destination.to_string(),
+        destination: address,
+        name,
+        label,
     };
     render(fmt, &output)?;
     Ok(())
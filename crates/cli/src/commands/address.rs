@@ -0,0 +1,114 @@
+//! `atlas address` — the token address book: human-readable names for
+//! contract addresses, per chain, used wherever a command takes a token.
+
+use anyhow::Result;
+use atlas_core::assets::{AssetMap, TokenEntry};
+use atlas_core::config::AppConfig;
+use atlas_core::db::{AtlasDb, DbAddressEntry};
+use atlas_core::error::AtlasError;
+use atlas_core::output::{
+    render, AddressBookOutput, AddressBookRow, AddressChangeOutput, OutputFormat,
+};
+
+use super::zero_x::parse_chain;
+
+/// Built-in tokens and CoinGecko ids plus the user's address book.
+pub(crate) fn asset_map(config: &AppConfig) -> Result<AssetMap> {
+    let entries = AtlasDb::open()?.address_book()?;
+    Ok(AssetMap::from_config(&config.market).with_address_book(&entries))
+}
+
+/// `atlas address add <NAME> <0x…> [--chain ethereum] [--decimals N]`
+pub fn add(
+    name: &str,
+    address: &str,
+    chain: &str,
+    decimals: Option<u32>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let chain = parse_chain(chain)?;
+    let config = atlas_core::workspace::load_config()?;
+    let address = asset_map(&config)?.check_new_entry(&chain, name, address)?;
+    let entry = DbAddressEntry {
+        chain: chain.to_string(),
+        name: name.trim().to_string(),
+        address,
+        decimals,
+        added_ms: chrono::Utc::now().timestamp_millis(),
+    };
+    AtlasDb::open()?.add_address(&entry)?;
+
+    render(
+        fmt,
+        &AddressChangeOutput {
+            action: "added".into(),
+            entry: AddressBookRow {
+                chain: entry.chain,
+                name: entry.name,
+                address: entry.address,
+                decimals: entry.decimals,
+                source: "user".into(),
+            },
+        },
+    )?;
+    Ok(())
+}
+
+/// `atlas address list [--chain base]`
+pub fn list(chain: Option<&str>, fmt: OutputFormat) -> Result<()> {
+    let chain = chain.map(parse_chain).transpose()?;
+    let config = atlas_core::workspace::load_config()?;
+    let assets = asset_map(&config)?;
+    let tokens: Vec<AddressBookRow> = assets
+        .tokens(chain.as_ref())
+        .into_iter()
+        .map(book_row)
+        .collect();
+    let total = tokens.len();
+    render(
+        fmt,
+        &AddressBookOutput {
+            chain: chain.map(|c| c.to_string()),
+            tokens,
+            total,
+        },
+    )?;
+    Ok(())
+}
+
+/// `atlas address remove <NAME> [--chain ethereum]` — user entries only.
+pub fn remove(name: &str, chain: &str, fmt: OutputFormat) -> Result<()> {
+    let chain = parse_chain(chain)?;
+    let config = atlas_core::workspace::load_config()?;
+    let assets = asset_map(&config)?;
+    let entry = assets
+        .token(&chain, name)
+        .ok_or_else(|| assets.unknown_token(&chain, name))?;
+    if entry.builtin {
+        return Err(AtlasError::InvalidConfig(format!(
+            "{} on {chain} is built in and can't be removed",
+            entry.name
+        ))
+        .into());
+    }
+    AtlasDb::open()?.remove_address(&entry.chain, &entry.name)?;
+
+    render(
+        fmt,
+        &AddressChangeOutput {
+            action: "removed".into(),
+            entry: book_row(entry),
+        },
+    )?;
+    Ok(())
+}
+
+fn book_row(t: &TokenEntry) -> AddressBookRow {
+    AddressBookRow {
+        chain: t.chain.clone(),
+        name: t.name.clone(),
+        address: t.address.clone(),
+        decimals: t.decimals,
+        source: if t.builtin { "built-in" } else { "user" }.into(),
+    }
+}
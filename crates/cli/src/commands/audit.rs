@@ -0,0 +1,73 @@
+//! `atlas audit` — the local record of every action atlas signed for an
+//! exchange. Read-only: entries can't be edited or removed.
+
+use anyhow::{Context, Result};
+use atlas_core::db::{AtlasDb, AuditFilter, DbAuditEntry};
+use atlas_core::output::{render, AuditEntryOutput, AuditListOutput, AuditRow, OutputFormat};
+
+use super::helpers::{format_ms, parse_date_range};
+
+/// `atlas audit list [--type TYPE] [--from DATE] [--to DATE] [--limit N]`
+pub fn list(
+    action_type: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+    limit: usize,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let range = parse_date_range(from, to)?;
+    let db = AtlasDb::open()?;
+    let filter = AuditFilter {
+        action_type: action_type.map(str::to_lowercase),
+        from_ms: range.from_ms,
+        to_ms: range.to_ms,
+        limit: Some(limit),
+    };
+
+    let entries: Vec<AuditRow> = db
+        .query_audit_entries(&filter)?
+        .iter()
+        .map(audit_row)
+        .collect();
+    let total = entries.len();
+    render(fmt, &AuditListOutput { entries, total })?;
+    Ok(())
+}
+
+/// `atlas audit show <id>`
+pub fn show(id: i64, fmt: OutputFormat) -> Result<()> {
+    let db = AtlasDb::open()?;
+    let entry = db
+        .audit_entry(id)?
+        .with_context(|| format!("No audit entry #{id}. List them with `atlas audit list`."))?;
+
+    render(
+        fmt,
+        &AuditEntryOutput {
+            entry: audit_row(&entry),
+            request: parse_or_text(&entry.request),
+            response: entry.response.as_deref().map(parse_or_text),
+            completed: entry.completed_ms.map(format_ms),
+        },
+    )?;
+    Ok(())
+}
+
+fn audit_row(e: &DbAuditEntry) -> AuditRow {
+    AuditRow {
+        id: e.id,
+        time: format_ms(e.created_ms),
+        protocol: e.protocol.clone(),
+        action_type: e.action_type.clone(),
+        coin: e.coin.clone(),
+        status: e.status.clone(),
+        nonce: e.nonce,
+        signer: e.signer.clone(),
+        params_hash: e.params_hash.clone(),
+    }
+}
+
+/// Stored bodies are JSON except error texts, which stay strings.
+fn parse_or_text(raw: &str) -> serde_json::Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| serde_json::Value::String(raw.to_string()))
+}
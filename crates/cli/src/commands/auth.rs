@@ -1,5 +1,8 @@
 use anyhow::Result;
-use atlas_core::output::OutputFormat;
+use atlas_core::db::AtlasDb;
+use atlas_core::output::{print_confirmation, render, OutputFormat};
+use atlas_core::output::{ProfileRemoveOutput, ProfileRenameOutput};
+use atlas_core::prompt::confirm_attended;
 use atlas_core::AuthManager;
 
 /// `atlas profile generate <name>`
@@ -75,7 +78,7 @@ pub fn import_wallet(name: &str, fmt: OutputFormat) -> Result<()> {
             println!("{}", serde_json::to_string(&json)?);
         }
     } else {
-        println!("✓ Imported profile '{profile_name}' → {address}");
+        print_confirmation(&format!("Imported profile '{profile_name}' → {address}"));
     }
     Ok(())
 }
@@ -88,13 +91,27 @@ pub fn switch_profile(name: &str, fmt: OutputFormat) -> Result<()> {
         let json = serde_json::json!({"ok": true, "data": {"profile": name}});
         println!("{}", serde_json::to_string(&json)?);
     } else {
-        println!("✓ Active profile switched to '{name}'");
+        print_confirmation(&format!("Active profile switched to '{name}'"));
     }
     Ok(())
 }
 
-/// `atlas profile export <name>`
-pub fn export_wallet(name: &str, fmt: OutputFormat) -> Result<()> {
+/// `atlas profile export <name> [--yes]`
+pub fn export_wallet(name: &str, yes: bool, fmt: OutputFormat) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let proceed = confirm_attended(
+        "EXPORT PRIVATE KEY — CONFIRM",
+        &[
+            ("Profile", name.to_string()),
+            ("Warning", "private key will be printed".to_string()),
+        ],
+        yes || !config.trading.confirm,
+        fmt,
+    )?;
+    if !proceed {
+        return Ok(());
+    }
+
     let (profile_name, address, private_key) = AuthManager::export_wallet(name)?;
 
     if fmt != OutputFormat::Table {
@@ -134,6 +151,80 @@ pub fn export_wallet(name: &str, fmt: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+/// `atlas profile remove <name> [--switch-to <name>] [--purge-history] [--yes]`
+///
+/// Erases key material, so the prompt can only be skipped with `--yes`,
+/// not `trading.confirm`.
+pub fn remove_profile(
+    name: &str,
+    switch_to: Option<&str>,
+    purge_history: bool,
+    yes: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let mut details = vec![
+        ("Profile", name.to_string()),
+        (
+            "Warning",
+            "the private key is erased from the keyring".to_string(),
+        ),
+    ];
+    if let Some(next) = switch_to {
+        details.push(("Switch to", next.to_string()));
+    }
+    if purge_history {
+        details.push(("Cache", "purge snapshots and cached responses".to_string()));
+    }
+    let proceed = confirm_attended("REMOVE PROFILE — CONFIRM", &details, yes, fmt)?;
+    if !proceed {
+        return Ok(());
+    }
+
+    let removed = AuthManager::remove_profile(name, switch_to)?;
+    let purged_rows = if purge_history {
+        Some(AtlasDb::open()?.purge_account(&removed.name, &removed.address)?)
+    } else {
+        None
+    };
+
+    render(
+        fmt,
+        &ProfileRemoveOutput {
+            name: removed.name,
+            address: removed.address,
+            key_deleted: removed.key_deleted,
+            switched_to: removed.switched_to,
+            purged_rows,
+        },
+    )?;
+    Ok(())
+}
+
+/// `atlas profile rename <old> <new>`
+pub fn rename_profile(old: &str, new: &str, fmt: OutputFormat) -> Result<()> {
+    let renamed = AuthManager::rename_profile(old, new)?;
+    let mut warnings = renamed.warnings;
+    // Status snapshots are keyed by profile name; losing them is not worth
+    // failing a rename that already took effect.
+    if let Err(e) =
+        AtlasDb::open().and_then(|db| db.rename_status_snapshots(&renamed.old, &renamed.new))
+    {
+        warnings.push(format!("Status snapshots still filed under '{old}': {e:#}"));
+    }
+
+    render(
+        fmt,
+        &ProfileRenameOutput {
+            old: renamed.old,
+            new: renamed.new,
+            address: renamed.address,
+            active: renamed.was_active,
+            warnings,
+        },
+    )?;
+    Ok(())
+}
+
 /// `atlas profile list`
 pub fn list_profiles(fmt: OutputFormat) -> Result<()> {
     let store = AuthManager::load_store_pub()?;
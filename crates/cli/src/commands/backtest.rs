@@ -0,0 +1,56 @@
+//! `atlas market hyperliquid backtest` — replay a built-in strategy over
+//! historical candles. Analysis only; no orders are placed.
+
+use anyhow::Result;
+use atlas_core::backtest::{self, Bar, Strategy};
+use atlas_core::output::{render, BacktestOutput, OutputFormat};
+use rust_decimal::prelude::*;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    ticker: &str,
+    timeframe: &str,
+    strategy: &str,
+    period: usize,
+    slow: usize,
+    lookback: usize,
+    fee_bps: f64,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let strategy = Strategy::parse(strategy, period, slow)?;
+    if !(0.0..1_000.0).contains(&fee_bps) {
+        anyhow::bail!("Invalid --fee-bps {fee_bps}: must be between 0 and 1000");
+    }
+
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
+    let t = ticker.to_uppercase();
+    let candles = perp
+        .candles(&t, timeframe, lookback)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    let bars: Vec<Bar> = candles
+        .iter()
+        .map(|c| Bar {
+            time_ms: c.open_time_ms,
+            open: c.open.to_f64().unwrap_or(0.0),
+            high: c.high.to_f64().unwrap_or(0.0),
+            low: c.low.to_f64().unwrap_or(0.0),
+            close: c.close.to_f64().unwrap_or(0.0),
+        })
+        .collect();
+    let report = backtest::run(&bars, &strategy, fee_bps)?;
+
+    let output = BacktestOutput {
+        ticker: t,
+        timeframe: timeframe.to_string(),
+        strategy: strategy.name().to_string(),
+        candles: bars.len(),
+        fee_bps,
+        trade_count: report.trades.len(),
+        report,
+    };
+    render(fmt, &output)?;
+    Ok(())
+}
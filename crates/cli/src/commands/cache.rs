@@ -0,0 +1,50 @@
+//! `atlas cache` — inspect and prune local caches.
+
+use anyhow::Result;
+use atlas_core::db::AtlasDb;
+use atlas_core::output::{render, CandleCacheOutput, CandleSeriesRow, OutputFormat};
+use atlas_core::parse::parse_age_ms;
+
+use super::helpers::parse_date_to_ms;
+
+/// `atlas cache candles [--coin C] [--interval I] [--prune-before 90d]`
+///
+/// `prune_before` is an age (`90d`) or a date (`2026-01-01`).
+pub fn candles(
+    coin: Option<&str>,
+    interval: Option<&str>,
+    prune_before: Option<&str>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let db = AtlasDb::open()?;
+
+    let pruned = match prune_before {
+        Some(spec) => {
+            let cutoff_ms = match parse_age_ms(spec) {
+                Ok(age) => chrono::Utc::now().timestamp_millis() - age as i64,
+                Err(_) => parse_date_to_ms(spec).map_err(|_| {
+                    anyhow::anyhow!(
+                        "Invalid --prune-before '{spec}'. Use an age (90d) or a date (2026-01-01)"
+                    )
+                })?,
+            };
+            Some(db.prune_candles(coin, interval, cutoff_ms)?)
+        }
+        None => None,
+    };
+
+    let series = db
+        .candle_series(coin, interval)?
+        .into_iter()
+        .map(|s| CandleSeriesRow {
+            source: s.source,
+            coin: s.coin,
+            interval: s.interval,
+            candles: s.candles,
+            oldest_ms: s.oldest_ms as u64,
+            newest_ms: s.newest_ms as u64,
+        })
+        .collect();
+
+    render(fmt, &CandleCacheOutput { series, pruned })
+}
@@ -1,28 +1,53 @@
 //! `atlas market` CoinGecko-powered commands.
 //!
 //! These call the Atlas backend API which proxies to CoinGecko.
-//! Requires `atlas-server` to be running.
+//! The top-level commands keep their last response in the local DB and,
+//! with `--cached-ok`, fall back to it when the backend is unreachable.
 
 use anyhow::Result;
-use atlas_core::output::OutputFormat;
+use atlas_core::db::AtlasDb;
+use atlas_core::dex;
+use atlas_core::output::{
+    render, DexOhlcvOutput, DexTradesOutput, MarketOverviewOutput, OutputFormat,
+};
 use atlas_core::BackendClient;
+use atlas_core::{overview, pool_screen};
+use rust_decimal::prelude::*;
 
-/// Helper: ensure backend is reachable, return client.
-async fn backend() -> Result<BackendClient> {
-    let client = BackendClient::from_config()?;
-    if !client.health().await? {
-        anyhow::bail!(
-            "Atlas backend not reachable. Start it with: atlas-server\n\
-             Or set api_key: atlas configure system api-key <key>"
+use super::helpers::format_ms;
+
+/// Backend client for the active config. Failures surface per request as
+/// typed errors (`BACKEND_UNREACHABLE`, `API_KEY_REJECTED`, ...).
+pub(crate) fn backend() -> Result<BackendClient> {
+    BackendClient::from_config()
+}
+
+/// GET through the local response cache. With `cached_ok`, an unreachable
+/// or rate-limited backend is answered with the last stored response and a
+/// stale-data warning on stderr.
+async fn get_cached(
+    client: &BackendClient,
+    path: &str,
+    query: &[(&str, &str)],
+    cached_ok: bool,
+) -> Result<serde_json::Value> {
+    let db = AtlasDb::open().ok();
+    let resp = client
+        .get_cached(db.as_ref(), path, query, cached_ok)
+        .await?;
+    if let Some(fetched_ms) = resp.stale_since_ms {
+        eprintln!(
+            "⚠ Atlas backend unreachable — stale data from {} UTC",
+            format_ms(fetched_ms)
         );
     }
-    Ok(client)
+    Ok(resp.data)
 }
 
 /// `atlas market global` — global crypto market stats (CoinGecko).
-pub async fn global(fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
-    let data = client.get("/api/coingecko/global", &[]).await?;
+pub async fn global(cached_ok: bool, fmt: OutputFormat) -> Result<()> {
+    let client = backend()?;
+    let data = get_cached(&client, "/api/coingecko/global", &[], cached_ok).await?;
 
     match fmt {
         OutputFormat::Json => println!("{}", serde_json::to_string(&data)?),
@@ -77,10 +102,56 @@ pub async fn global(fmt: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+/// `atlas market overview` — the `market.watchlist` coins with global stats.
+///
+/// One batched `coins/markets` request for the whole watchlist, alongside
+/// the global stats; a failed global request only drops the header.
+pub async fn overview(
+    sort: &str,
+    limit: Option<usize>,
+    cached_ok: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let ids = config.market.watchlist;
+    if ids.is_empty() {
+        anyhow::bail!(
+            "market.watchlist is empty. Set it with: \
+             atlas configure set market.watchlist bitcoin,ethereum,solana"
+        );
+    }
+    let client = backend()?;
+    let (ids_s, per_page) = (ids.join(","), ids.len().to_string());
+    let query = [
+        ("vs_currency", "usd"),
+        ("ids", ids_s.as_str()),
+        ("price_change_percentage", "1h,24h,7d"),
+        ("per_page", per_page.as_str()),
+    ];
+    let (markets, global) = tokio::join!(
+        get_cached(&client, "/api/coingecko/coins/markets", &query, cached_ok),
+        get_cached(&client, "/api/coingecko/global", &[], cached_ok),
+    );
+
+    let mut coins = overview::parse_markets(&markets?);
+    let missing = overview::missing_ids(&ids, &coins);
+    overview::sort_rows(&mut coins, sort);
+    if let Some(limit) = limit {
+        coins.truncate(limit);
+    }
+    let output = MarketOverviewOutput {
+        global: global.ok().and_then(|g| overview::parse_global(&g)),
+        sort: sort.to_string(),
+        coins,
+        missing,
+    };
+    render(fmt, &output)
+}
+
 /// `atlas market trending` — trending coins (CoinGecko).
-pub async fn trending(fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
-    let data = client.get("/api/coingecko/trending", &[]).await?;
+pub async fn trending(cached_ok: bool, fmt: OutputFormat) -> Result<()> {
+    let client = backend()?;
+    let data = get_cached(&client, "/api/coingecko/trending", &[], cached_ok).await?;
 
     match fmt {
         OutputFormat::Json => println!("{}", serde_json::to_string(&data)?),
@@ -139,10 +210,10 @@ pub async fn trending(fmt: OutputFormat) -> Result<()> {
 }
 
 /// `atlas market coin <id>` — detailed coin info (CoinGecko).
-pub async fn coin(id: &str, fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
+pub async fn coin(id: &str, cached_ok: bool, fmt: OutputFormat) -> Result<()> {
+    let client = backend()?;
     let path = format!("/api/coingecko/coins/{}", id.to_lowercase());
-    let data = client.get(&path, &[]).await?;
+    let data = get_cached(&client, &path, &[], cached_ok).await?;
 
     match fmt {
         OutputFormat::Json => println!("{}", serde_json::to_string(&data)?),
@@ -246,9 +317,9 @@ pub async fn coin(id: &str, fmt: OutputFormat) -> Result<()> {
 }
 
 /// `atlas market movers [--limit 10]` — top gainers & losers (CoinGecko).
-pub async fn movers(limit: usize, fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
-    let data = client.get("/api/coingecko/top-movers", &[]).await?;
+pub async fn movers(limit: usize, cached_ok: bool, fmt: OutputFormat) -> Result<()> {
+    let client = backend()?;
+    let data = get_cached(&client, "/api/coingecko/top-movers", &[], cached_ok).await?;
 
     match fmt {
         OutputFormat::Json => println!("{}", serde_json::to_string(&data)?),
@@ -309,7 +380,7 @@ pub async fn movers(limit: usize, fmt: OutputFormat) -> Result<()> {
 
 /// `atlas market dex trending` — trending onchain pools.
 pub async fn dex_trending(network: Option<&str>, limit: usize, fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
+    let client = backend()?;
     let path = match network {
         Some(net) => format!("/api/coingecko/onchain/trending-pools/{}", net),
         None => "/api/coingecko/onchain/trending-pools".to_string(),
@@ -331,13 +402,21 @@ pub async fn dex_trending(network: Option<&str>, limit: usize, fmt: OutputFormat
 }
 
 /// `atlas market dex new` — newly created pools.
-pub async fn dex_new(network: Option<&str>, limit: usize, fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
+pub async fn dex_new(
+    network: Option<&str>,
+    limit: usize,
+    screen: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let client = backend()?;
     let path = match network {
         Some(net) => format!("/api/coingecko/onchain/new-pools/{}", net),
         None => "/api/coingecko/onchain/new-pools".to_string(),
     };
-    let data = client.get(&path, &[]).await?;
+    let mut data = client.get(&path, &[]).await?;
+    if screen {
+        screen_pools(&client, &mut data, limit).await?;
+    }
 
     match fmt {
         OutputFormat::Json => println!("{}", serde_json::to_string(&data)?),
@@ -353,9 +432,72 @@ pub async fn dex_new(network: Option<&str>, limit: usize, fmt: OutputFormat) ->
     Ok(())
 }
 
+/// Grade the first `limit` pools of a pool list (`--screen`), adding
+/// `risk_grade`, `risk_score` and `risk_flags` to each. The backend fetches
+/// every pool's detail and base token info in one batched request.
+async fn screen_pools(
+    client: &BackendClient,
+    data: &mut serde_json::Value,
+    limit: usize,
+) -> Result<()> {
+    let Some(pools) = data.get_mut("data").and_then(|d| d.as_array_mut()) else {
+        return Ok(());
+    };
+    pools.truncate(limit);
+    // GeckoTerminal pool ids are `<network>_<address>`
+    let keys: Vec<String> = pools
+        .iter()
+        .filter_map(|p| p.get("id").and_then(|v| v.as_str()))
+        .filter_map(|id| id.split_once('_'))
+        .map(|(net, addr)| format!("{net}:{addr}"))
+        .collect();
+    if keys.is_empty() {
+        return Ok(());
+    }
+    let screened = client
+        .get(
+            "/api/coingecko/onchain/screen",
+            &[("pools", &keys.join(","))],
+        )
+        .await?;
+    let results = screened
+        .get("data")
+        .and_then(|d| d.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    for pool in pools.iter_mut() {
+        let id = pool.get("id").and_then(|v| v.as_str()).unwrap_or_default();
+        let result = results.iter().find(|r| {
+            let key = format!(
+                "{}_{}",
+                r.get("network")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default(),
+                r.get("address")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+            );
+            key.eq_ignore_ascii_case(id)
+        });
+        // The list entry already carries pool attributes if the detail is missing
+        let detail = result.and_then(|r| r.get("pool")).filter(|p| !p.is_null());
+        let list_entry = serde_json::json!({ "data": pool.clone() });
+        let token = result
+            .and_then(|r| r.get("base_token"))
+            .filter(|t| !t.is_null());
+        let risk = pool_screen::screen_pool(detail.unwrap_or(&list_entry), token, now_ms);
+        pool["risk_grade"] = risk.grade.into();
+        pool["risk_score"] = risk.score.into();
+        pool["risk_flags"] = risk.flags.into();
+    }
+    Ok(())
+}
+
 /// `atlas market dex pools <network>` — top pools on a network.
 pub async fn dex_top_pools(network: &str, limit: usize, fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
+    let client = backend()?;
     let path = format!("/api/coingecko/onchain/pools/{}", network);
     let data = client.get(&path, &[]).await?;
 
@@ -372,7 +514,7 @@ pub async fn dex_top_pools(network: &str, limit: usize, fmt: OutputFormat) -> Re
 
 /// `atlas market dex pool <network> <address>` — pool details.
 pub async fn dex_pool_detail(network: &str, address: &str, fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
+    let client = backend()?;
     let path = format!("/api/coingecko/onchain/pools/{}/{}", network, address);
     let data = client.get(&path, &[]).await?;
 
@@ -459,9 +601,79 @@ pub async fn dex_pool_detail(network: &str, address: &str, fmt: OutputFormat) ->
     Ok(())
 }
 
+/// `atlas market dex ohlcv <network> <address>` — pool candles.
+pub async fn dex_ohlcv(
+    network: &str,
+    address: &str,
+    timeframe: &str,
+    aggregate: u32,
+    limit: usize,
+    fmt: OutputFormat,
+) -> Result<()> {
+    dex::validate_ohlcv(timeframe, aggregate, limit).map_err(anyhow::Error::msg)?;
+    let client = backend()?;
+    let path = format!(
+        "/api/coingecko/onchain/pools/{}/{}/ohlcv/{}",
+        network, address, timeframe
+    );
+    let (aggregate_s, limit_s) = (aggregate.to_string(), limit.to_string());
+    let data = client
+        .get(&path, &[("aggregate", &aggregate_s), ("limit", &limit_s)])
+        .await?;
+
+    let (base, quote) = dex::ohlcv_pair(&data);
+    let output = DexOhlcvOutput {
+        network: network.to_string(),
+        pool: address.to_string(),
+        timeframe: timeframe.to_string(),
+        aggregate,
+        base,
+        quote,
+        limit,
+        candles: dex::parse_ohlcv(&data),
+    };
+    render(fmt, &output)
+}
+
+/// `atlas market dex trades <network> <address> [--min-usd N]` — latest pool trades.
+pub async fn dex_trades(
+    network: &str,
+    address: &str,
+    min_usd: Option<f64>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let min_usd = min_usd
+        .map(|v| {
+            Decimal::from_f64(v)
+                .filter(|d| !d.is_sign_negative())
+                .ok_or_else(|| anyhow::anyhow!("--min-usd must be a non-negative number"))
+        })
+        .transpose()?;
+    let client = backend()?;
+    let path = format!(
+        "/api/coingecko/onchain/pools/{}/{}/trades",
+        network, address
+    );
+    let min_s = min_usd.map(|d| d.to_string());
+    let query: Vec<(&str, &str)> = min_s
+        .as_deref()
+        .map(|m| ("trade_volume_in_usd_greater_than", m))
+        .into_iter()
+        .collect();
+    let data = client.get(&path, &query).await?;
+
+    let output = DexTradesOutput {
+        network: network.to_string(),
+        pool: address.to_string(),
+        min_usd: min_s,
+        trades: dex::parse_trades(&data, min_usd),
+    };
+    render(fmt, &output)
+}
+
 /// `atlas market dex token <network> <address>` — token info.
 pub async fn dex_token_info(network: &str, address: &str, fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
+    let client = backend()?;
     let path = format!("/api/coingecko/onchain/tokens/{}/{}/info", network, address);
     let data = client.get(&path, &[]).await?;
 
@@ -512,7 +724,7 @@ pub async fn dex_token_info(network: &str, address: &str, fmt: OutputFormat) ->
 
 /// `atlas market dex networks` — list supported networks.
 pub async fn dex_networks(fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
+    let client = backend()?;
     let data = client.get("/api/coingecko/onchain/networks", &[]).await?;
 
     match fmt {
@@ -541,7 +753,7 @@ pub async fn dex_networks(fmt: OutputFormat) -> Result<()> {
 
 /// `atlas market dex dexes <network>` — list DEXes on a network.
 pub async fn dex_dexes(network: &str, fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
+    let client = backend()?;
     let path = format!("/api/coingecko/onchain/dexes/{}", network);
     let data = client.get(&path, &[]).await?;
 
@@ -571,7 +783,7 @@ pub async fn dex_dexes(network: &str, fmt: OutputFormat) -> Result<()> {
 
 /// `atlas market dex search <query>` — search onchain tokens/pools.
 pub async fn dex_search(query: &str, fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
+    let client = backend()?;
     let data = client
         .get("/api/coingecko/onchain/search", &[("query", query)])
         .await?;
@@ -663,11 +875,16 @@ pub async fn dex_search(query: &str, fmt: OutputFormat) -> Result<()> {
 
 fn print_pools_table(data: Option<&serde_json::Value>, limit: usize) {
     if let Some(pools) = data.and_then(|d| d.as_array()) {
-        println!(
+        let screened = pools.iter().any(|p| p.get("risk_grade").is_some());
+        print!(
             "{:<30} {:<10} {:>14} {:>12} {:>10}",
             "POOL", "DEX", "VOLUME 24h", "LIQUIDITY", "24h CHG"
         );
-        println!("{}", "─".repeat(80));
+        if screened {
+            print!("  {:<5} RISK FLAGS", "GRADE");
+        }
+        println!();
+        println!("{}", "─".repeat(if screened { 110 } else { 80 }));
         for pool in pools.iter().take(limit) {
             let name = pool
                 .get("attributes")
@@ -699,7 +916,7 @@ fn print_pools_table(data: Option<&serde_json::Value>, limit: usize) {
                 .and_then(|v| v.as_str())
                 .and_then(|s| s.parse::<f64>().ok());
 
-            println!(
+            print!(
                 "{:<30} {:<10} {:>14} {:>12} {:>+10.2}%",
                 &name[..name.len().min(29)],
                 &dex[..dex.len().min(9)],
@@ -709,6 +926,24 @@ fn print_pools_table(data: Option<&serde_json::Value>, limit: usize) {
                     .unwrap_or("—".into()),
                 chg.unwrap_or(0.0)
             );
+            if screened {
+                let grade = pool
+                    .get("risk_grade")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("—");
+                let flags: Vec<&str> = pool
+                    .get("risk_flags")
+                    .and_then(|v| v.as_array())
+                    .map(|a| a.iter().filter_map(|f| f.as_str()).collect())
+                    .unwrap_or_default();
+                let flags = if flags.is_empty() {
+                    "—".to_string()
+                } else {
+                    flags.join("; ")
+                };
+                print!("  {:<5} {}", grade, flags);
+            }
+            println!();
         }
     } else {
         println!("No pools found.");
@@ -716,9 +951,9 @@ fn print_pools_table(data: Option<&serde_json::Value>, limit: usize) {
 }
 
 /// `atlas market defi` — global DeFi stats (CoinGecko).
-pub async fn defi(fmt: OutputFormat) -> Result<()> {
-    let client = backend().await?;
-    let data = client.get("/api/coingecko/global/defi", &[]).await?;
+pub async fn defi(cached_ok: bool, fmt: OutputFormat) -> Result<()> {
+    let client = backend()?;
+    let data = get_cached(&client, "/api/coingecko/global/defi", &[], cached_ok).await?;
 
     match fmt {
         OutputFormat::Json => println!("{}", serde_json::to_string(&data)?),
@@ -0,0 +1,181 @@
+//! `atlas market compare` — one coin's price from Hyperliquid, CoinGecko
+//! and a 0x quote side by side, as a sanity check across sources.
+
+use std::future::Future;
+
+use anyhow::Result;
+use atlas_core::assets::{from_base_units, AssetMap};
+use atlas_core::date_range::now_ms;
+use atlas_core::orchestrator::MODULE_TIMEOUT;
+use atlas_core::output::{render, OutputFormat, PriceCompareOutput, SourcePrice};
+use atlas_core::symbols::SymbolResolver;
+use atlas_core::types::{Chain, Protocol};
+use atlas_core::Orchestrator;
+use rust_decimal::prelude::*;
+
+/// `atlas market compare <coin> [--chain base] [--size-usd 1000] [--threshold-bps 50]`
+pub async fn run(
+    coin: &str,
+    chain: Option<&str>,
+    size_usd: f64,
+    threshold_bps: Option<f64>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let assets = super::address::asset_map(&config)?;
+    let chain = super::zero_x::parse_chain(
+        chain.unwrap_or(config.modules.zero_x.config.default_chain.as_str()),
+    )?;
+    let size_usd = Decimal::from_f64(size_usd)
+        .filter(|s| *s > Decimal::ZERO)
+        .ok_or_else(|| anyhow::anyhow!("Invalid --size-usd: {size_usd}"))?;
+    let threshold_bps = threshold_bps.unwrap_or(config.market.compare_threshold_bps);
+    let threshold = Decimal::from_f64(threshold_bps)
+        .filter(|t| !t.is_sign_negative())
+        .ok_or_else(|| anyhow::anyhow!("Invalid --threshold-bps: {threshold_bps}"))?;
+
+    let orch = crate::factory::readonly().await?;
+    // Name the coin the way Hyperliquid does; the mapping tables use it too.
+    // A coin Hyperliquid doesn't list still gets the other two sources.
+    let symbol = orch
+        .perp(Some(&Protocol::Hyperliquid.to_string()))
+        .ok()
+        .and_then(|hl| hl.resolve_symbol(coin).ok())
+        .unwrap_or_else(|| {
+            let resolver =
+                SymbolResolver::default().with_aliases(&config.modules.hyperliquid.config.aliases);
+            resolver.alias(coin).unwrap_or(coin).trim().to_uppercase()
+        });
+
+    let (hl_price, cg_price, zx_price) = tokio::join!(
+        timed("Hyperliquid", hyperliquid_price(&orch, &symbol)),
+        timed("CoinGecko", coingecko_price(&assets, &symbol)),
+        timed(
+            "0x",
+            zero_x_price(&orch, &assets, &chain, &symbol, size_usd)
+        ),
+    );
+
+    let output = PriceCompareOutput::new(
+        &symbol,
+        &chain.to_string(),
+        size_usd,
+        threshold,
+        vec![
+            ("hyperliquid".into(), hl_price),
+            ("coingecko".into(), cg_price),
+            ("0x".into(), zx_price),
+        ],
+    );
+    render(fmt, &output)?;
+    Ok(())
+}
+
+/// Bound a source to [`MODULE_TIMEOUT`]; any failure becomes its message.
+async fn timed(
+    source: &str,
+    fut: impl Future<Output = Result<SourcePrice>>,
+) -> Result<SourcePrice, String> {
+    match tokio::time::timeout(MODULE_TIMEOUT, fut).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err(format!(
+            "{source} did not respond within {}s",
+            MODULE_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+/// Hyperliquid mid, stamped with the time it was read.
+async fn hyperliquid_price(orch: &Orchestrator, symbol: &str) -> Result<SourcePrice> {
+    let hl = orch
+        .perp(Some(&Protocol::Hyperliquid.to_string()))
+        .map_err(|_| anyhow::anyhow!("Hyperliquid module is disabled"))?;
+    let ticker = hl.ticker(symbol).await?;
+    Ok(SourcePrice {
+        price: ticker.mid_price,
+        timestamp_ms: now_ms() as u64,
+    })
+}
+
+/// CoinGecko USD spot price via the Atlas backend.
+async fn coingecko_price(assets: &AssetMap, symbol: &str) -> Result<SourcePrice> {
+    let id = assets.coingecko_id(symbol).ok_or_else(|| {
+        anyhow::anyhow!("no CoinGecko id for {symbol}; set market.coingecko_ids.{symbol}")
+    })?;
+    let client = super::coingecko::backend()?;
+    let data = client
+        .get(&format!("/api/coingecko/coins/{id}"), &[])
+        .await?;
+    let market = data.get("market_data");
+    let price = market
+        .and_then(|m| m.get("current_price"))
+        .and_then(|p| p.get("usd"))
+        .and_then(|v| v.as_f64())
+        .and_then(Decimal::from_f64)
+        .ok_or_else(|| anyhow::anyhow!("no USD price for '{id}'"))?;
+    let timestamp_ms = market
+        .and_then(|m| m.get("last_updated"))
+        .or_else(|| data.get("last_updated"))
+        .and_then(|v| v.as_str())
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.timestamp_millis().max(0) as u64)
+        .unwrap_or_else(now_ms);
+    Ok(SourcePrice {
+        price,
+        timestamp_ms,
+    })
+}
+
+/// USD price implied by a 0x quote selling `size_usd` USDC for the coin.
+async fn zero_x_price(
+    orch: &Orchestrator,
+    assets: &AssetMap,
+    chain: &Chain,
+    symbol: &str,
+    size_usd: Decimal,
+) -> Result<SourcePrice> {
+    let usdc = assets
+        .token(chain, "USDC")
+        .ok_or_else(|| anyhow::anyhow!("no USDC token known on {chain}"))?;
+    let token = assets.market_token(chain, symbol).ok_or_else(|| {
+        anyhow::anyhow!(
+            "no token for {symbol} on {chain}; add it with `atlas address add {symbol} <0x…> --chain {chain} --decimals <N>`"
+        )
+    })?;
+    let (Some(usdc_decimals), Some(decimals)) = (usdc.decimals, token.decimals) else {
+        anyhow::bail!(
+            "decimals unknown for {} on {chain}; re-add it with --decimals",
+            token.name
+        );
+    };
+    let zerox = orch
+        .swap(Some(&Protocol::ZeroX.to_string()))
+        .ok()
+        .and_then(|s| s.as_any().downcast_ref::<atlas_zero_x::ZeroXModule>())
+        .ok_or_else(|| anyhow::anyhow!("0x module is disabled"))?;
+
+    // Base units: the amount with the token's decimals as its scale
+    let mut sell_amount = size_usd;
+    sell_amount.rescale(usdc_decimals);
+    let sell_amount = sell_amount.mantissa().to_string();
+    let resp = zerox
+        .price(
+            chain,
+            &usdc.address,
+            &token.address,
+            &sell_amount,
+            None,
+            None,
+        )
+        .await?;
+    let bought = resp
+        .buy_amount
+        .as_deref()
+        .and_then(|a| from_base_units(a, decimals))
+        .filter(|a| *a > Decimal::ZERO)
+        .ok_or_else(|| anyhow::anyhow!("quote has no usable buy amount"))?;
+    Ok(SourcePrice {
+        price: (size_usd / bought).round_dp(8),
+        timestamp_ms: now_ms() as u64,
+    })
+}
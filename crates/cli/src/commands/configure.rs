@@ -1,12 +1,39 @@
+use std::time::Duration;
+
 use anyhow::Result;
-use atlas_core::config::SizeMode;
-use atlas_core::output::ConfigOutput;
-use atlas_core::output::{render, OutputFormat};
+use atlas_core::approval;
+use atlas_core::backend::required_scopes;
+use atlas_core::config::{
+    redact_secret, AppConfig, CoinPreset, HyperliquidConfig, SizeMode, WhitelistEntry, SECRET_KEYS,
+};
+use atlas_core::config_sync::{self, RemoteConfig, SyncState};
+use atlas_core::constants::ATLAS_BACKEND_URL;
+use atlas_core::db::AtlasDb;
+use atlas_core::engine::BuilderFee;
+use atlas_core::error::AtlasError;
+use atlas_core::output::{print_confirmation, render, render_context, OutputFormat};
+use atlas_core::output::{
+    ConfigFieldChange, ConfigOutput, ConfigSyncOutput, EnvListOutput, EnvRow, NotifyConfigOutput,
+    PresetRow, PresetsOutput, SecurityConfigOutput, WhitelistOutput,
+};
+use atlas_core::symbols::SymbolResolver;
+use atlas_core::{AuthManager, BackendClient};
 
-/// `atlas configure show` — display current config (non-interactive).
-pub fn run(fmt: OutputFormat) -> Result<()> {
+/// `atlas configure show [--reveal]` — display current config (non-interactive).
+/// Secrets are redacted unless `reveal`.
+pub fn run(reveal: bool, fmt: OutputFormat) -> Result<()> {
     let config = atlas_core::workspace::load_config()?;
     let hl = &config.modules.hyperliquid.config;
+    let api_key = config.system.api_key.as_deref().map(|k| {
+        if reveal {
+            k.to_string()
+        } else {
+            redact_secret(k)
+        }
+    });
+
+    // Effective fee, after env overrides and the acknowledgement gate
+    let builder = BuilderFee::from_config(hl);
 
     // JSON gets clean machine-readable values; table gets human-friendly text
     if fmt != OutputFormat::Table {
@@ -16,7 +43,22 @@ pub fn run(fmt: OutputFormat) -> Result<()> {
             "leverage": hl.default_leverage,
             "slippage": hl.default_slippage,
             "network": hl.network,
+            "env": config.system.active_env,
+            "confirm": config.trading.confirm,
+            "max_notional": config.trading.max_notional,
+            "max_leverage": config.trading.max_leverage,
+            "daily_loss_limit": config.trading.daily_loss_limit,
+            "max_slippage": config.trading.max_slippage,
+            "price_band": config.trading.price_band,
+            "spread_warn_multiple": config.trading.spread_warn_multiple,
             "lots": hl.lots.assets,
+            "api_key": api_key,
+            "builder_fee_bps": builder.as_ref().map_or(0, |b| b.f),
+            "builder_address": builder.as_ref().map(|b| &b.b),
+            "rate_limit": hl.rate_limit,
+            "tui": config.tui,
+            "security": config.security,
+            "presets": hl.presets,
         });
         let envelope = serde_json::json!({"ok": true, "data": data});
         let s = if matches!(fmt, OutputFormat::JsonPretty) {
@@ -42,7 +84,15 @@ pub fn run(fmt: OutputFormat) -> Result<()> {
         } else {
             "Mainnet".into()
         },
+        confirm: config.trading.confirm,
         lots: hl.lots.assets.clone(),
+        api_key,
+        builder_fee_bps: builder.as_ref().map_or(0, |b| b.f),
+        builder_address: builder.map(|b| b.b),
+        rate_limit: hl.rate_limit.clone(),
+        tui: config.tui.clone(),
+        output: config.output,
+        presets: preset_rows(hl),
     };
 
     render(OutputFormat::Table, &output)?;
@@ -53,6 +103,803 @@ pub fn run(fmt: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+/// `atlas configure get <dotted.key> [--reveal]`
+pub fn get(key: &str, reveal: bool, fmt: OutputFormat) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let value = config.get_path(key).map_err(|e| anyhow::anyhow!("{e}"))?;
+    print_key_value(key, &display_value(key, value, reveal), fmt)
+}
+
+/// `atlas configure set <dotted.key> <value>` — typed by the current value;
+/// see [`AppConfig::set_path`].
+pub fn set(key: &str, value: &str, fmt: OutputFormat) -> Result<()> {
+    let mut config = atlas_core::workspace::load_config()?;
+    let stored = config
+        .set_path(key, value)
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    atlas_core::workspace::save_config(&config)?;
+    if fmt == OutputFormat::Table && render_context().quiet {
+        return Ok(());
+    }
+    print_key_value(key, &display_value(key, stored, false), fmt)
+}
+
+/// Redact secret keys (and any object containing one) unless `reveal`.
+fn display_value(key: &str, value: serde_json::Value, reveal: bool) -> serde_json::Value {
+    if reveal {
+        return value;
+    }
+    let mut wrapped = value;
+    for secret in SECRET_KEYS {
+        let slot = if *secret == key {
+            Some(&mut wrapped)
+        } else if let Some(rest) = secret.strip_prefix(&format!("{key}.")) {
+            rest.split('.')
+                .try_fold(&mut wrapped, |v, part| v.get_mut(part))
+        } else {
+            None
+        };
+        if let Some(v) = slot {
+            if let Some(s) = v.as_str() {
+                *v = serde_json::Value::String(redact_secret(s));
+            }
+        }
+    }
+    wrapped
+}
+
+fn print_key_value(key: &str, value: &serde_json::Value, fmt: OutputFormat) -> Result<()> {
+    match fmt {
+        OutputFormat::Json | OutputFormat::JsonPretty => {
+            let envelope = serde_json::json!({"ok": true, "data": {"key": key, "value": value}});
+            let s = if matches!(fmt, OutputFormat::JsonPretty) {
+                serde_json::to_string_pretty(&envelope)?
+            } else {
+                serde_json::to_string(&envelope)?
+            };
+            println!("{s}");
+        }
+        OutputFormat::Table => match value {
+            serde_json::Value::String(s) => println!("{key} = {s}"),
+            serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
+                println!("{key} = {}", serde_json::to_string_pretty(value)?)
+            }
+            v => println!("{key} = {v}"),
+        },
+    }
+    Ok(())
+}
+
+/// Valid keys for `configure get/set`, for `--help`-style listing.
+pub fn keys(fmt: OutputFormat) -> Result<()> {
+    let paths = AppConfig::default().key_paths();
+    match fmt {
+        OutputFormat::Json | OutputFormat::JsonPretty => {
+            println!("{}", serde_json::json!({"ok": true, "data": paths}));
+        }
+        OutputFormat::Table => {
+            for p in paths {
+                println!("{p}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `atlas configure env list` — every environment and the network it uses.
+pub fn env_list(fmt: OutputFormat) -> Result<()> {
+    let active = atlas_core::workspace::load_config()?.system.active_env;
+    let envs = atlas_core::workspace::load_envs()?
+        .into_iter()
+        .map(|(name, config)| {
+            let active = active.as_deref() == Some(name.as_str());
+            // `--profile` applies to the env in use, not the others
+            let profile = if active {
+                AuthManager::active_profile(&config).to_string()
+            } else {
+                config.system.active_profile.clone()
+            };
+            EnvRow {
+                active,
+                network: config.modules.hyperliquid.config.network,
+                profile,
+                name,
+            }
+        })
+        .collect();
+    render(fmt, &EnvListOutput { active, envs })
+}
+
+/// `atlas configure env use <name>` — switch the environment every command
+/// (engine, streams, TUI) loads its settings from.
+pub fn env_use(name: &str, fmt: OutputFormat) -> Result<()> {
+    let envs = atlas_core::workspace::load_envs()?;
+    let Some((_, target)) = envs.iter().find(|(n, _)| n == name) else {
+        let names: Vec<&str> = envs.iter().map(|(n, _)| n.as_str()).collect();
+        anyhow::bail!(
+            "Unknown environment '{name}'. Available: {}",
+            names.join(", ")
+        );
+    };
+    let network = target.modules.hyperliquid.config.network.clone();
+
+    let mut config = atlas_core::workspace::load_config()?;
+    config.system.active_env = Some(name.to_string());
+    atlas_core::workspace::save_config(&config)?;
+
+    if fmt == OutputFormat::Table {
+        print_confirmation(&format!("env = {name} (network: {network})"));
+    } else {
+        println!(
+            "{}",
+            serde_json::json!({"ok": true, "data": {"key": "env", "value": name, "network": network}})
+        );
+    }
+    Ok(())
+}
+
+/// `on`/`true`/`1` or `off`/`false`/`0`; the error names `key`.
+fn parse_on_off(key: &str, value: &str) -> Result<bool> {
+    match value.to_lowercase().as_str() {
+        "on" | "true" | "1" => Ok(true),
+        "off" | "false" | "0" => Ok(false),
+        _ => anyhow::bail!("Invalid value for {key}: {value} (on/off, true/false or 1/0)"),
+    }
+}
+
+/// `atlas configure system verbose <on|off>`
+pub fn verbose(enabled: &str, fmt: OutputFormat) -> Result<()> {
+    let val = parse_on_off("verbose", enabled)?;
+    let mut config = atlas_core::workspace::load_config()?;
+    config.system.verbose = val;
+    atlas_core::workspace::save_config(&config)?;
+    if fmt == OutputFormat::Table {
+        print_confirmation(&format!("verbose = {val}"));
+    } else {
+        println!(
+            "{}",
+            serde_json::json!({"ok": true, "data": {"key": "verbose", "value": val}})
+        );
+    }
+    Ok(())
+}
+
+/// `atlas configure trading confirm <on|off>`
+pub fn trading_confirm(enabled: &str, fmt: OutputFormat) -> Result<()> {
+    let val = parse_on_off("confirm", enabled)?;
+    let mut config = atlas_core::workspace::load_config()?;
+    config.trading.confirm = val;
+    atlas_core::workspace::save_config(&config)?;
+    if fmt == OutputFormat::Table {
+        print_confirmation(&format!("confirm = {}", if val { "on" } else { "off" }));
+    } else {
+        println!(
+            "{}",
+            serde_json::json!({"ok": true, "data": {"key": "confirm", "value": val}})
+        );
+    }
+    Ok(())
+}
+
+/// `atlas configure trading <max-notional|max-leverage|daily-loss-limit|max-slippage|price-band|spread-warn> <value|off>`
+///
+/// `max_slippage` and `price_band` are fractions (0.1 = 10%);
+/// `spread_warn_multiple` is a multiple of the median spread, above 1.
+pub fn trading_limit(key: &str, value: &str, fmt: OutputFormat) -> Result<()> {
+    let parsed = match value.to_lowercase().as_str() {
+        "off" | "none" | "0" => None,
+        v => {
+            let n: f64 = v
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid value for {key}: {value}"))?;
+            if n < 0.0 {
+                anyhow::bail!("Invalid value for {key}: must be positive");
+            }
+            if matches!(key, "max_slippage" | "price_band") && n >= 1.0 {
+                anyhow::bail!("Invalid value for {key}: a fraction below 1 (0.1 = 10%)");
+            }
+            if key == "spread_warn_multiple" && n <= 1.0 {
+                anyhow::bail!("Invalid value for {key}: a multiple above 1 (3 = 3x the median)");
+            }
+            Some(n)
+        }
+    };
+
+    let mut config = atlas_core::workspace::load_config()?;
+    match key {
+        "max_notional" => config.trading.max_notional = parsed,
+        "max_leverage" => config.trading.max_leverage = parsed,
+        "daily_loss_limit" => config.trading.daily_loss_limit = parsed,
+        "max_slippage" => config.trading.max_slippage = parsed,
+        "price_band" => config.trading.price_band = parsed,
+        "spread_warn_multiple" => config.trading.spread_warn_multiple = parsed,
+        _ => anyhow::bail!("Unknown trading limit: {key}"),
+    }
+    atlas_core::workspace::save_config(&config)?;
+
+    if fmt == OutputFormat::Table {
+        match parsed {
+            Some(v) => print_confirmation(&format!("{key} = {v}")),
+            None => print_confirmation(&format!("{key} = off")),
+        }
+    } else {
+        println!(
+            "{}",
+            serde_json::json!({"ok": true, "data": {"key": key, "value": parsed}})
+        );
+    }
+    Ok(())
+}
+
+/// `atlas configure trading preset set <COIN> [--leverage N] [--slippage F] [--default-size S]`
+///
+/// Fields not given keep their current preset value.
+pub fn preset_set(
+    coin: &str,
+    leverage: Option<u32>,
+    slippage: Option<f64>,
+    default_size: Option<&str>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    if leverage.is_none() && slippage.is_none() && default_size.is_none() {
+        anyhow::bail!("Nothing to set. Pass --leverage, --slippage or --default-size.");
+    }
+    if leverage == Some(0) {
+        anyhow::bail!("Invalid --leverage: must be at least 1");
+    }
+    if let Some(slip) = slippage {
+        if !slip.is_finite() || slip <= 0.0 || slip >= 1.0 {
+            anyhow::bail!("Invalid --slippage: a fraction between 0 and 1 (0.003 = 0.3%)");
+        }
+    }
+
+    let mut config = atlas_core::workspace::load_config()?;
+    let hl = &mut config.modules.hyperliquid.config;
+    let coin = preset_key(hl, coin);
+    if let Some(size) = default_size {
+        // Stored as typed so `buy`/`sell` parse it with their own grammar
+        atlas_core::parse::parse_size_for(size, &coin)?;
+    }
+    let key = hl
+        .presets
+        .keys()
+        .find(|k| k.eq_ignore_ascii_case(&coin))
+        .cloned()
+        .unwrap_or(coin);
+    let preset = hl.presets.entry(key).or_default();
+    if leverage.is_some() {
+        preset.leverage = leverage;
+    }
+    if slippage.is_some() {
+        preset.slippage = slippage;
+    }
+    if let Some(size) = default_size {
+        preset.default_size = Some(size.trim().to_string());
+    }
+    atlas_core::workspace::save_config(&config)?;
+    preset_list(fmt)
+}
+
+/// `atlas configure trading preset remove <COIN>`
+pub fn preset_remove(coin: &str, fmt: OutputFormat) -> Result<()> {
+    let mut config = atlas_core::workspace::load_config()?;
+    let hl = &mut config.modules.hyperliquid.config;
+    let wanted = preset_key(hl, coin);
+    let key = hl
+        .presets
+        .keys()
+        .find(|k| k.eq_ignore_ascii_case(&wanted))
+        .cloned();
+    let Some(key) = key else {
+        anyhow::bail!("No preset for {coin}. See: atlas configure trading preset list");
+    };
+    hl.presets.remove(&key);
+    atlas_core::workspace::save_config(&config)?;
+    preset_list(fmt)
+}
+
+/// `atlas configure trading preset list`
+pub fn preset_list(fmt: OutputFormat) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    render(
+        fmt,
+        &PresetsOutput {
+            presets: preset_rows(&config.modules.hyperliquid.config),
+        },
+    )
+}
+
+/// Presets key on the exchange name, so `XBT` and `btc` share `BTC`'s.
+fn preset_key(hl: &HyperliquidConfig, coin: &str) -> String {
+    let resolver = SymbolResolver::default().with_aliases(&hl.aliases);
+    match resolver.alias(coin) {
+        Some(target) => target.to_string(),
+        None => coin.trim().to_uppercase(),
+    }
+}
+
+fn preset_rows(hl: &HyperliquidConfig) -> Vec<PresetRow> {
+    hl.presets
+        .iter()
+        .filter(|(_, preset)| !preset.is_empty())
+        .map(|(coin, preset)| {
+            let CoinPreset {
+                leverage,
+                slippage,
+                default_size,
+            } = preset.clone();
+            PresetRow {
+                coin: coin.clone(),
+                leverage,
+                slippage,
+                default_size,
+            }
+        })
+        .collect()
+}
+
+/// `atlas configure notify set [--webhook <URL|off>] [--exec <CMD|off>] [--secret <KEY|off>] [--liq-warn-pct <N>]`
+pub fn notify_set(
+    webhook: Option<&str>,
+    exec: Option<&str>,
+    secret: Option<&str>,
+    liq_warn_pct: Option<f64>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    if webhook.is_none() && exec.is_none() && secret.is_none() && liq_warn_pct.is_none() {
+        anyhow::bail!("Nothing to set. Pass --webhook, --exec, --secret or --liq-warn-pct.");
+    }
+    // `off` clears a setting
+    let clearable =
+        |v: &str| (!matches!(v.to_lowercase().as_str(), "off" | "none")).then(|| v.to_string());
+
+    let mut config = atlas_core::workspace::load_config()?;
+    let notify = &mut config.notify;
+    if let Some(url) = webhook {
+        let url = clearable(url);
+        if let Some(u) = &url {
+            if !u.starts_with("https://") && !u.starts_with("http://") {
+                anyhow::bail!("Invalid --webhook '{u}': use an http(s) URL");
+            }
+        }
+        notify.webhook = url;
+    }
+    if let Some(cmd) = exec {
+        notify.exec = clearable(cmd);
+    }
+    if let Some(key) = secret {
+        notify.secret = clearable(key);
+    }
+    if let Some(pct) = liq_warn_pct {
+        if !pct.is_finite() || pct <= 0.0 || pct >= 100.0 {
+            anyhow::bail!("--liq-warn-pct must be between 0 and 100");
+        }
+        notify.liq_warn_pct = pct;
+    }
+    atlas_core::workspace::save_config(&config)?;
+    notify_show(false, fmt)
+}
+
+/// `atlas configure notify show [--reveal]`
+pub fn notify_show(reveal: bool, fmt: OutputFormat) -> Result<()> {
+    let notify = atlas_core::workspace::load_config()?.notify;
+    let secret = notify.secret.as_deref().map(|s| {
+        if reveal {
+            s.to_string()
+        } else {
+            redact_secret(s)
+        }
+    });
+    render(
+        fmt,
+        &NotifyConfigOutput {
+            webhook: notify.webhook,
+            exec: notify.exec,
+            secret,
+            liq_warn_pct: notify.liq_warn_pct,
+            max_retries: notify.max_retries,
+            backoff_ms: notify.backoff_ms,
+        },
+    )
+}
+
+/// `atlas configure security show`
+pub fn security_show(fmt: OutputFormat) -> Result<()> {
+    let security = atlas_core::workspace::load_config()?.security;
+    render(
+        fmt,
+        &SecurityConfigOutput {
+            transfer_confirm_above: security.transfer_confirm_above,
+            method: security.transfer_confirm_method.to_string(),
+            approval_timeout_secs: security.approval_timeout_secs,
+            approval_file: approval::approval_file(&security)?.display().to_string(),
+            totp_enrolled: approval::load_totp_secret()?.is_some(),
+            whitelist_enabled: security.whitelist.enabled,
+            whitelisted: security.whitelist.entries.len(),
+        },
+    )
+}
+
+/// `atlas configure security totp enroll`
+///
+/// Replacing an enrolled secret needs a code from the current one, so a
+/// shell user can't swap in a secret they know.
+pub async fn totp_enroll(fmt: OutputFormat) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let timeout = Duration::from_secs(config.security.approval_timeout_secs);
+    if let Some(current) = approval::load_totp_secret()? {
+        eprintln!("A TOTP secret is already enrolled. Enter a code from it to replace it.");
+        approval::prompt_totp(&current, timeout).await?;
+    }
+
+    let secret = approval::generate_secret();
+    let encoded = approval::base32_encode(&secret);
+    eprintln!("Add this secret to your authenticator app:");
+    eprintln!("  {encoded}");
+    eprintln!("  {}", approval::otpauth_uri(&encoded, "transfers"));
+    eprintln!("Then enter the code it shows to finish enrolling.");
+    approval::prompt_totp(&secret, timeout).await?;
+    approval::store_totp_secret(&encoded)?;
+
+    if fmt == OutputFormat::Table && !render_context().quiet {
+        print_confirmation("TOTP enrolled — secret stored in the OS keyring");
+    }
+    security_show(fmt)
+}
+
+/// `atlas configure security totp remove` — needs a current code.
+pub async fn totp_remove(fmt: OutputFormat) -> Result<()> {
+    let security = atlas_core::workspace::load_config()?.security;
+    let Some(current) = approval::load_totp_secret()? else {
+        anyhow::bail!("No TOTP secret is enrolled");
+    };
+    approval::prompt_totp(
+        &current,
+        Duration::from_secs(security.approval_timeout_secs),
+    )
+    .await?;
+    approval::delete_totp_secret()?;
+    security_show(fmt)
+}
+
+/// `atlas configure security whitelist list`
+pub fn whitelist_list(fmt: OutputFormat) -> Result<()> {
+    let whitelist = atlas_core::workspace::load_config()?.security.whitelist;
+    render(
+        fmt,
+        &WhitelistOutput {
+            enabled: whitelist.enabled,
+            entries: whitelist.entries,
+        },
+    )
+}
+
+/// `atlas configure security whitelist add <address> --label <name>`
+pub fn whitelist_add(address: &str, label: &str, fmt: OutputFormat) -> Result<()> {
+    let address = super::helpers::parse_address(address)?;
+    let label = label.trim();
+    if label.is_empty() {
+        anyhow::bail!("--label must not be empty");
+    }
+    let mut config = atlas_core::workspace::load_config()?;
+    let entries = &mut config.security.whitelist.entries;
+    if let Some(other) = entries
+        .iter()
+        .find(|e| e.label.eq_ignore_ascii_case(label) && !e.address.eq_ignore_ascii_case(&address))
+    {
+        anyhow::bail!("Label '{label}' is already used for {}", other.address);
+    }
+    match entries
+        .iter_mut()
+        .find(|e| e.address.eq_ignore_ascii_case(&address))
+    {
+        Some(entry) => entry.label = label.to_string(),
+        None => entries.push(WhitelistEntry {
+            address,
+            label: label.to_string(),
+        }),
+    }
+    atlas_core::workspace::save_config(&config)?;
+    whitelist_list(fmt)
+}
+
+/// `atlas configure security whitelist remove <address|label>`
+pub fn whitelist_remove(target: &str, fmt: OutputFormat) -> Result<()> {
+    let mut config = atlas_core::workspace::load_config()?;
+    let entries = &mut config.security.whitelist.entries;
+    let before = entries.len();
+    entries.retain(|e| {
+        !e.address.eq_ignore_ascii_case(target.trim())
+            && !e.label.eq_ignore_ascii_case(target.trim())
+    });
+    if entries.len() == before {
+        anyhow::bail!(
+            "'{target}' is not whitelisted. List: atlas configure security whitelist list"
+        );
+    }
+    atlas_core::workspace::save_config(&config)?;
+    whitelist_list(fmt)
+}
+
+/// `atlas configure security whitelist enable|disable`
+pub fn whitelist_enable(enabled: bool, fmt: OutputFormat) -> Result<()> {
+    let mut config = atlas_core::workspace::load_config()?;
+    config.security.whitelist.enabled = enabled;
+    atlas_core::workspace::save_config(&config)?;
+    whitelist_list(fmt)
+}
+
+/// `atlas configure system api-key <key>` — save the key, then ask the
+/// backend for its scopes and warn about any the enabled modules need.
+/// The key is kept whatever the check finds.
+pub async fn api_key(key: &str, fmt: OutputFormat) -> Result<()> {
+    let mut config = atlas_core::workspace::load_config()?;
+    config.system.api_key = Some(key.to_string());
+    atlas_core::workspace::save_config(&config)?;
+    let shown = redact_secret(key);
+
+    let client = BackendClient::new(ATLAS_BACKEND_URL).with_api_key(Some(key.to_string()));
+    let mut warnings = Vec::new();
+    let (scopes, missing) = match client.key_self().await {
+        Ok(info) => {
+            // A machine that synced before will sync again with this key
+            let syncs_config = AtlasDb::open()
+                .and_then(|db| SyncState::load(&db))
+                .is_ok_and(|state| state.is_some());
+            let missing = info.missing_scopes(&required_scopes(&config, syncs_config));
+            for (scope, modules) in &missing {
+                warnings.push(format!(
+                    "API key lacks the '{scope}' scope needed by {} — create a key with it in the Atlas dashboard → Settings → API Keys",
+                    modules.join(", ")
+                ));
+            }
+            (Some(info.scopes), missing)
+        }
+        Err(AtlasError::ApiKeyRejected(_)) => {
+            warnings.push("Backend rejected this key — check it was copied in full".into());
+            (None, Default::default())
+        }
+        Err(e) => {
+            warnings.push(format!("Scopes not checked: {e}"));
+            (None, Default::default())
+        }
+    };
+
+    if fmt == OutputFormat::Table {
+        match &scopes {
+            Some(scopes) => print_confirmation(&format!(
+                "api_key = {shown} (scopes: {})",
+                scopes.join(", ")
+            )),
+            None => print_confirmation(&format!("api_key = {shown}")),
+        }
+        for w in &warnings {
+            eprintln!("⚠ {w}");
+        }
+    } else {
+        println!(
+            "{}",
+            serde_json::json!({"ok": true, "data": {
+                "key": "api_key",
+                "value": shown,
+                "scopes": scopes,
+                "missing_scopes": missing,
+                "warnings": warnings,
+            }})
+        );
+    }
+    Ok(())
+}
+
+/// Backend client for `configure sync`, authenticated with the config's API key.
+fn sync_client(config: &AppConfig) -> Result<BackendClient> {
+    if config.system.api_key.is_none() {
+        return Err(AtlasError::ApiKeyMissing.into());
+    }
+    Ok(BackendClient::new(ATLAS_BACKEND_URL).with_api_key(config.system.api_key.clone()))
+}
+
+/// `atlas configure sync status` — compare the local settings with the
+/// remote copy, without changing either.
+pub async fn sync_status(fmt: OutputFormat) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let remote = config_sync::fetch(&sync_client(&config)?).await?;
+    let state = SyncState::load(&AtlasDb::open()?)?;
+    let local = config_sync::shareable(&config);
+
+    let changes = match &remote {
+        Some(r) => {
+            let mut shared = r.config.clone();
+            config_sync::strip(&mut shared);
+            config_sync::diff(&local, &shared)
+        }
+        None => Vec::new(),
+    };
+    render(
+        fmt,
+        &ConfigSyncOutput {
+            action: "status".into(),
+            remote_version: remote.as_ref().map(|r| r.version),
+            remote_updated_at: remote.as_ref().map(|r| r.updated_at.clone()),
+            local_modified: state.as_ref().map_or(true, |s| s.modified(&local)),
+            remote_changed: remote_changed(remote.as_ref(), state.as_ref()),
+            changes,
+            conflicts: Vec::new(),
+            applied: false,
+        },
+    )
+}
+
+/// `atlas configure sync push` — upload the shareable settings. Refused if
+/// another machine pushed since this one last synced, unless `force`.
+pub async fn sync_push(force: bool, yes: bool, fmt: OutputFormat) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let client = sync_client(&config)?;
+    let db = AtlasDb::open()?;
+    let remote = config_sync::fetch(&client).await?;
+    let state = SyncState::load(&db)?;
+    let local = config_sync::shareable(&config);
+
+    if remote_changed(remote.as_ref(), state.as_ref()) && !force {
+        anyhow::bail!(
+            "The remote config changed since this machine last synced. \
+             Run `atlas configure sync pull --merge` first, or push with --force to overwrite it."
+        );
+    }
+    let changes = match &remote {
+        Some(r) => config_sync::diff(&local, &r.config),
+        None => config_sync::diff(&local, &serde_json::json!({})),
+    };
+    let base_version = remote.as_ref().map_or(0, |r| r.version);
+    if changes.is_empty() {
+        SyncState::new(base_version, local).save(&db)?;
+        return render(
+            fmt,
+            &sync_result("push", remote.as_ref(), false, Vec::new()),
+        );
+    }
+
+    preview_changes(&changes, true, fmt);
+    let summary = [
+        ("Fields", changes.len().to_string()),
+        (
+            "Replaces",
+            remote
+                .as_ref()
+                .map_or("nothing".into(), |r| format!("v{}", r.version)),
+        ),
+    ];
+    if !confirm_action("Push config", &summary, yes, fmt)? {
+        return Ok(());
+    }
+    let Some(version) = config_sync::push(&client, &local, base_version).await? else {
+        anyhow::bail!(
+            "Another machine pushed while this push was running; run `atlas configure sync status`"
+        );
+    };
+    SyncState::new(version, local).save(&db)?;
+    render(
+        fmt,
+        &ConfigSyncOutput {
+            action: "push".into(),
+            remote_version: Some(version),
+            remote_updated_at: None,
+            local_modified: false,
+            remote_changed: false,
+            changes,
+            conflicts: Vec::new(),
+            applied: true,
+        },
+    )
+}
+
+/// `atlas configure sync pull` — replace the shareable settings with the
+/// remote copy. Settings edited here since the last sync are protected:
+/// `merge` keeps them and takes the remote value only for untouched fields,
+/// `force` overwrites them; without either the pull is refused.
+pub async fn sync_pull(force: bool, merge: bool, yes: bool, fmt: OutputFormat) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let db = AtlasDb::open()?;
+    let Some(remote) = config_sync::fetch(&sync_client(&config)?).await? else {
+        anyhow::bail!("No config has been pushed yet. Run `atlas configure sync push` on the machine to copy from");
+    };
+    let state = SyncState::load(&db)?;
+    let local = config_sync::shareable(&config);
+    let mut shared = remote.config.clone();
+    config_sync::strip(&mut shared);
+
+    let local_modified = state.as_ref().map_or(true, |s| s.modified(&local));
+    let (next, conflicts) = if merge {
+        let merged = config_sync::merge(state.as_ref().map(|s| &s.snapshot), &local, &shared);
+        (merged.value, merged.conflicts)
+    } else if local_modified && !force && !config_sync::diff(&local, &shared).is_empty() {
+        anyhow::bail!(
+            "Local settings changed since the last sync and would be overwritten. \
+             Use --merge to keep local edits, or --force to take the remote copy."
+        );
+    } else {
+        (shared.clone(), Vec::new())
+    };
+
+    // Local edits kept by --merge still set this machine apart from the remote
+    let still_modified = !config_sync::diff(&next, &shared).is_empty();
+    let changes = config_sync::diff(&local, &next);
+    if changes.is_empty() {
+        SyncState::new(remote.version, shared).save(&db)?;
+        let mut output = sync_result("pull", Some(&remote), still_modified, Vec::new());
+        output.conflicts = conflicts;
+        return render(fmt, &output);
+    }
+
+    preview_changes(&changes, false, fmt);
+    let summary = [
+        ("Fields", changes.len().to_string()),
+        (
+            "From",
+            format!("v{} ({})", remote.version, remote.updated_at),
+        ),
+    ];
+    if !confirm_action("Pull config", &summary, yes, fmt)? {
+        return Ok(());
+    }
+    let updated = config_sync::apply(&config, &next).map_err(|e| {
+        AtlasError::InvalidConfig(format!(
+            "Remote config does not fit this version of atlas: {e}"
+        ))
+    })?;
+    atlas_core::workspace::save_config(&updated)?;
+    SyncState::new(remote.version, shared).save(&db)?;
+
+    let mut output = sync_result("pull", Some(&remote), still_modified, changes);
+    output.conflicts = conflicts;
+    output.applied = true;
+    render(fmt, &output)
+}
+
+/// Has the remote moved past the version this machine last synced?
+fn remote_changed(remote: Option<&RemoteConfig>, state: Option<&SyncState>) -> bool {
+    match (remote, state) {
+        (Some(r), Some(s)) => r.version != s.version,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+fn sync_result(
+    action: &str,
+    remote: Option<&RemoteConfig>,
+    local_modified: bool,
+    changes: Vec<ConfigFieldChange>,
+) -> ConfigSyncOutput {
+    ConfigSyncOutput {
+        action: action.into(),
+        remote_version: remote.map(|r| r.version),
+        remote_updated_at: remote.map(|r| r.updated_at.clone()),
+        local_modified,
+        remote_changed: false,
+        changes,
+        conflicts: Vec::new(),
+        applied: false,
+    }
+}
+
+/// List the fields about to change before asking for confirmation: remote
+/// values replaced by local ones on push, the other way round on pull.
+fn preview_changes(changes: &[ConfigFieldChange], push: bool, fmt: OutputFormat) {
+    if fmt != OutputFormat::Table {
+        return;
+    }
+    let show = |v: &Option<serde_json::Value>| v.as_ref().map_or("-".into(), |v| v.to_string());
+    for c in changes {
+        let (from, to) = if push {
+            (&c.remote, &c.local)
+        } else {
+            (&c.local, &c.remote)
+        };
+        eprintln!("  {}: {} -> {}", c.key, show(from), show(to));
+    }
+}
+
 fn size_mode_hint(mode: &SizeMode) -> &'static str {
     match mode {
         SizeMode::Usdc => "USDC margin",
@@ -0,0 +1,94 @@
+//! `atlas market hyperliquid correlate` — pairwise return correlation and
+//! beta to the first symbol, over one timeframe.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use atlas_core::batch::{map_concurrent, MAX_CONCURRENT_REQUESTS};
+use atlas_core::output::{render, CorrelationOutput, CorrelationPair, OutputFormat};
+use atlas_core::stats;
+use rust_decimal::prelude::*;
+
+/// Minimum aligned candles (→ returns) for a meaningful correlation.
+const MIN_ALIGNED_CANDLES: usize = 10;
+
+pub async fn run(
+    coins: &[String],
+    timeframe: &str,
+    lookback: usize,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let mut symbols: Vec<String> = Vec::new();
+    for c in coins {
+        let c = c.to_uppercase();
+        if !symbols.contains(&c) {
+            symbols.push(c);
+        }
+    }
+    if symbols.len() < 2 {
+        anyhow::bail!("correlate needs at least two distinct coins, e.g. BTC ETH SOL");
+    }
+
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
+    // One extra candle so `lookback` returns are available
+    let results = map_concurrent(symbols.iter(), MAX_CONCURRENT_REQUESTS, |sym| {
+        let perp = perp.as_ref();
+        async move { perp.candles(sym, timeframe, lookback + 1).await }
+    })
+    .await;
+
+    let mut series = Vec::with_capacity(symbols.len());
+    for (sym, result) in symbols.iter().zip(results) {
+        let candles = result.map_err(|e| anyhow::anyhow!("{sym}: {e}"))?;
+        series.push(
+            candles
+                .iter()
+                .map(|c| (c.open_time_ms, c.close.to_f64().unwrap_or(0.0)))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    // Newer listings have shorter histories; pair candles by open time
+    let (times, closes) = stats::align_by_time(&series);
+    if times.len() < MIN_ALIGNED_CANDLES {
+        anyhow::bail!(
+            "Only {} overlapping {timeframe} candles across {} — need at least {MIN_ALIGNED_CANDLES}",
+            times.len(),
+            symbols.join(", ")
+        );
+    }
+
+    let mut returns = Vec::with_capacity(closes.len());
+    for (sym, col) in symbols.iter().zip(&closes) {
+        let r = stats::log_returns(col)
+            .ok_or_else(|| anyhow::anyhow!("{sym}: candle data contains non-positive prices"))?;
+        returns.push(r);
+    }
+
+    let round = |x: f64| (x * 10_000.0).round() / 10_000.0;
+    let mut pairs = Vec::new();
+    for (i, a) in symbols.iter().enumerate() {
+        for (j, b) in symbols.iter().enumerate().skip(i + 1) {
+            pairs.push(CorrelationPair {
+                a: a.clone(),
+                b: b.clone(),
+                corr: stats::correlation(&returns[i], &returns[j]).map(round),
+            });
+        }
+    }
+    let betas: BTreeMap<String, Option<f64>> = symbols
+        .iter()
+        .zip(&returns)
+        .map(|(sym, r)| (sym.clone(), stats::beta(r, &returns[0]).map(round)))
+        .collect();
+
+    let output = CorrelationOutput {
+        samples: returns[0].len(),
+        symbols,
+        timeframe: timeframe.to_string(),
+        pairs,
+        betas,
+    };
+    render(fmt, &output)
+}
@@ -0,0 +1,159 @@
+//! `atlas market dex watch` — a local watchlist of onchain pools. Each
+//! check records the pool's price, 24h volume and liquidity so the next
+//! one can show what changed.
+
+use anyhow::Result;
+use atlas_core::db::{AtlasDb, DbWatchedPool};
+use atlas_core::dex::{self, PoolCheck};
+use atlas_core::output::{
+    render, DexWatchChangeOutput, DexWatchCheckOutput, DexWatchEntryRow, DexWatchListOutput,
+    OutputFormat,
+};
+
+use super::coingecko::backend;
+
+/// `atlas market dex watch add <network> <pool> [--label NAME]`
+pub fn add(network: &str, pool: &str, label: Option<&str>, fmt: OutputFormat) -> Result<()> {
+    let network = network.trim().to_lowercase();
+    let pool = pool.trim();
+    let label = label.map(str::trim).filter(|l| !l.is_empty());
+    if network.is_empty() || pool.is_empty() {
+        anyhow::bail!("Network and pool address are required");
+    }
+
+    let db = AtlasDb::open()?;
+    let watched = db.watched_pools()?;
+    if watched
+        .iter()
+        .any(|w| w.network == network && w.pool.eq_ignore_ascii_case(pool))
+    {
+        anyhow::bail!("Already watching {pool} on {network}");
+    }
+    if let Some(w) = label.and_then(|l| {
+        watched.iter().find(|w| {
+            w.label
+                .as_deref()
+                .is_some_and(|o| o.eq_ignore_ascii_case(l))
+        })
+    }) {
+        anyhow::bail!(
+            "Label {} is already used for {} on {}",
+            label.unwrap_or_default(),
+            w.pool,
+            w.network
+        );
+    }
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    db.add_watched_pool(&network, pool, label, now_ms)?;
+
+    render(
+        fmt,
+        &DexWatchChangeOutput {
+            action: "added".into(),
+            entry: DexWatchEntryRow {
+                network,
+                pool: pool.to_string(),
+                label: label.map(str::to_string),
+                added_ms: now_ms,
+                last_checked_ms: None,
+                missing_since_ms: None,
+            },
+        },
+    )
+}
+
+/// `atlas market dex watch remove <pool|label>`
+pub fn remove(pool: &str, fmt: OutputFormat) -> Result<()> {
+    let entry = AtlasDb::open()?
+        .remove_watched_pool(pool.trim())?
+        .ok_or_else(|| anyhow::anyhow!("Not watching a pool or label called {pool}"))?;
+    render(
+        fmt,
+        &DexWatchChangeOutput {
+            action: "removed".into(),
+            entry: entry_row(entry),
+        },
+    )
+}
+
+/// `atlas market dex watch list`
+pub fn list(fmt: OutputFormat) -> Result<()> {
+    let pools = AtlasDb::open()?
+        .watched_pools()?
+        .into_iter()
+        .map(entry_row)
+        .collect();
+    render(fmt, &DexWatchListOutput { pools })
+}
+
+/// `atlas market dex watch check [--threshold PCT]` — fetch every watched
+/// pool at once, compare with the previous check and record the new stats.
+/// A pool the API no longer knows is flagged, not an error.
+pub async fn check(threshold: Option<f64>, fmt: OutputFormat) -> Result<()> {
+    if threshold.is_some_and(|t| !t.is_finite() || t < 0.0) {
+        anyhow::bail!("--threshold must be a non-negative percentage");
+    }
+    let db = AtlasDb::open()?;
+    let watched = db.watched_pools()?;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+
+    let checks = if watched.is_empty() {
+        Vec::new()
+    } else {
+        let client = backend()?;
+        let client = &client;
+        futures::future::join_all(watched.iter().map(|w| async move {
+            let path = format!("/api/coingecko/onchain/pools/{}/{}", w.network, w.pool);
+            match client.get_optional(&path, &[]).await {
+                Ok(Some(data)) => match dex::parse_pool_stats(&data) {
+                    Some(stats) => PoolCheck::Found(stats),
+                    None => PoolCheck::Failed("no price in the pool data".into()),
+                },
+                Ok(None) => PoolCheck::Missing,
+                Err(e) => PoolCheck::Failed(e.to_string()),
+            }
+        }))
+        .await
+    };
+
+    let mut pools = Vec::with_capacity(watched.len());
+    for (w, check) in watched.iter().zip(&checks) {
+        pools.push(dex::watch_row(w, check, now_ms));
+        match check {
+            PoolCheck::Found(s) => db.record_pool_check(
+                &w.network,
+                &w.pool,
+                &s.price_usd.normalize().to_string(),
+                &s.volume_24h_usd.normalize().to_string(),
+                &s.liquidity_usd.normalize().to_string(),
+                now_ms,
+            )?,
+            PoolCheck::Missing => db.mark_pool_missing(&w.network, &w.pool, now_ms)?,
+            PoolCheck::Failed(_) => {}
+        }
+    }
+    if let Some(t) = threshold {
+        pools.retain(|r| dex::exceeds_threshold(r, t));
+    }
+
+    render(
+        fmt,
+        &DexWatchCheckOutput {
+            checked_ms: now_ms,
+            threshold_pct: threshold,
+            checked: watched.len(),
+            pools,
+        },
+    )
+}
+
+fn entry_row(w: DbWatchedPool) -> DexWatchEntryRow {
+    DexWatchEntryRow {
+        network: w.network,
+        pool: w.pool,
+        label: w.label,
+        added_ms: w.added_ms,
+        last_checked_ms: w.last_checked_ms,
+        missing_since_ms: w.missing_since_ms,
+    }
+}
@@ -1,19 +1,66 @@
 //! `atlas doctor` — system health checks.
+//!
+//! `--fix` applies the repairs that can't lose data (config migration,
+//! workspace re-init, cache clearing, database schema migrations, index
+//! rebuild, corrupt nonce reset)
+//! and marks each check it touched with `fixed`. Deleting an orphaned keyring entry is offered, but
+//! only with an interactive confirmation. Exits non-zero if any check is
+//! still failing afterwards; warnings don't change the exit code.
+
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use atlas_core::output::{render, OutputFormat};
+use atlas_core::auth::AuthManager;
+use atlas_core::backend::Readiness;
+use atlas_core::config::{AppConfig, RateLimitConfig};
+use atlas_core::db::{AtlasDb, SCHEMA_VERSION};
+use atlas_core::error::AtlasError;
+use atlas_core::fmt::{Cell, Color};
+use atlas_core::output::{render, render_context, OutputFormat};
 use atlas_core::output::{DoctorCheck, DoctorOutput};
+use atlas_hl::http::{ExchangeHttp, HttpFailure, RetryPolicy};
+use atlas_hl::json_cache::JsonCache;
+use atlas_hl::meta::{self, MetaCache};
+use atlas_hl::nonce;
+
+/// Local clock drift beyond this is reported. Hyperliquid validates nonces
+/// (millisecond timestamps) against its own clock, so a drifting machine
+/// starts seeing signed actions rejected.
+const MAX_CLOCK_SKEW_MS: i64 = 5_000;
 
 /// `atlas doctor [--fix]` — system health checks.
 pub async fn run(fix: bool, fmt: OutputFormat) -> Result<()> {
-    // ── Check 1: Profile ────────────────────────────────────────────
+    let output = checks(fix, fmt).await;
+    let any_failed = output.checks.iter().any(|c| c.status == "fail");
+
+    if fmt != OutputFormat::Table {
+        render(fmt, &output)?;
+    } else {
+        print_table(&output, fix);
+    }
+
+    if any_failed {
+        std::io::stdout().flush()?;
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Run every check without rendering; `atlas init` shows them too.
+pub async fn checks(fix: bool, fmt: OutputFormat) -> DoctorOutput {
+    // ── Check 1: Config schema ──────────────────────────────────────
+    // First, so every later check reads a migrated config
+    let config_check = config_check(fix);
+
+    // ── Check 2: Workspace permissions ──────────────────────────────
+    let workspace_check = workspace_check(fix);
+
+    // ── Check 3: Profile ────────────────────────────────────────────
     let config_result = atlas_core::workspace::load_config();
-    let profile_check = match (
-        &config_result,
-        atlas_core::auth::AuthManager::load_store_pub(),
-    ) {
+    let profile_check = match (&config_result, AuthManager::load_store_pub()) {
         (Ok(cfg), Ok(store)) if !store.wallets.is_empty() => {
-            let active = &cfg.system.active_profile;
+            let active = AuthManager::active_profile(cfg);
             if store.exists(active) {
                 DoctorCheck::ok("profile", active)
             } else {
@@ -31,124 +78,710 @@ pub async fn run(fix: bool, fmt: OutputFormat) -> Result<()> {
         ),
     };
 
-    // ── Check 2: Keyring ────────────────────────────────────────────
-    let wallets_path = atlas_core::workspace::resolve("keystore/wallets.json")?;
-    let keyring_check = if wallets_path.exists() {
-        DoctorCheck::ok_bare("keyring")
-    } else {
-        DoctorCheck::fail(
-            "keyring",
-            "Run: atlas profile generate main — initializes keystore",
-        )
+    // ── Check 4: Environment ────────────────────────────────────────
+    let env_check = match &config_result {
+        Ok(cfg) => match &cfg.system.active_env {
+            Some(env) => DoctorCheck::ok("environment", env),
+            None => DoctorCheck::fail(
+                "environment",
+                "Run: atlas configure env use mainnet — selects a config environment",
+            ),
+        },
+        Err(_) => DoctorCheck::fail(
+            "environment",
+            "atlas.json unreadable — run: atlas doctor --fix",
+        ),
     };
 
-    // ── Check 3: API Key ────────────────────────────────────────────
-    let api_key_check = match atlas_core::workspace::load_config() {
-        Ok(config) if config.system.api_key.is_some() => DoctorCheck::ok_bare("api_key"),
-        _ => DoctorCheck::fail(
-            "api_key",
-            "Run: atlas configure system api-key <key> — get key from apps/frontend → Settings",
-        ),
+    // ── Check 5: Keyring ────────────────────────────────────────────
+    let keyring_check = keyring_check(config_result.as_ref().ok(), fix, fmt);
+
+    // ── Check 6: API Key ────────────────────────────────────────────
+    let api_key_check = api_key_check(config_result.as_ref().ok()).await;
+
+    // ── Check 7: Endpoint latency ──────────────────────────────────
+    let testnet = config_result
+        .as_ref()
+        .map(|c| c.modules.hyperliquid.config.network == "testnet")
+        .unwrap_or(false);
+    let hl_base = if testnet {
+        atlas_core::constants::HL_TESTNET_RPC
+    } else {
+        atlas_core::constants::HL_MAINNET_RPC
     };
+    let info_check = latency_check(
+        "hl_info",
+        probe_hl(
+            &format!("{hl_base}/info"),
+            serde_json::json!({"type": "allMids"}),
+        )
+        .await,
+        "Hyperliquid info API unreachable — check network connectivity",
+    );
+    let exchange_check = latency_check(
+        "hl_exchange",
+        probe_hl(&format!("{hl_base}/exchange"), serde_json::json!({})).await,
+        "Hyperliquid exchange API unreachable — check network connectivity",
+    );
+    let backend_checks = backend_checks(probe_backend().await);
 
-    // ── Check 4: Backend/Network latency ────────────────────────────
-    let (backend_check, hl_check) = match check_api_latency().await {
-        Ok(ms) => {
-            let mut backend = DoctorCheck::ok("backend", format!("{ms}ms"));
-            backend.latency_ms = Some(ms);
-
-            // ── Check 5: Hyperliquid module ──────────────────────────
-            let hl = match atlas_core::workspace::load_config() {
-                Ok(cfg) if cfg.modules.hyperliquid.enabled => {
-                    let net = cfg.modules.hyperliquid.config.network.clone();
-                    let mut check = DoctorCheck::ok("hyperliquid", &net);
-                    check.network = Some(net);
-                    check
-                }
-                _ => DoctorCheck::fail(
-                    "hyperliquid",
-                    "Run: atlas configure module enable hyperliquid && atlas configure module set hl network mainnet",
-                ),
+    // ── Check 8: Clock skew ─────────────────────────────────────────
+    let clock_check = clock_check(hl_base).await;
+
+    // ── Check 9: Hyperliquid module ─────────────────────────────────
+    let hl_check = match &config_result {
+        Ok(cfg) if cfg.modules.hyperliquid.enabled => {
+            let net = cfg.modules.hyperliquid.config.network.clone();
+            let value = match &cfg.system.active_env {
+                Some(env) if *env != net => format!("{net} (env: {env})"),
+                _ => net.clone(),
             };
-            (backend, hl)
-        }
-        Err(_) => {
-            let backend = DoctorCheck::fail(
-                "backend",
-                "Hyperliquid API unreachable — check network connectivity",
-            );
-            let hl = DoctorCheck::fail(
-                "hyperliquid",
-                "Cannot connect to Hyperliquid — check network connectivity",
-            );
-            (backend, hl)
+            let mut check = DoctorCheck::ok("hyperliquid", value);
+            check.network = Some(net);
+            check
         }
+        _ => DoctorCheck::fail(
+            "hyperliquid",
+            "Run: atlas configure module enable hyperliquid && atlas configure module set hl network mainnet",
+        ),
     };
 
-    let checks = vec![
+    // ── Check 10: Info rate limit ───────────────────────────────────
+    let rate_check = rate_limit_check(
+        &config_result
+            .as_ref()
+            .map(|c| c.modules.hyperliquid.config.rate_limit.clone())
+            .unwrap_or_default(),
+    );
+
+    // ── Check 11: Metadata cache ────────────────────────────────────
+    let meta_ttl = config_result
+        .as_ref()
+        .map(|c| c.modules.hyperliquid.config.meta_ttl_secs)
+        .unwrap_or(600);
+    let cache_check = cache_check(testnet, Duration::from_secs(meta_ttl), fix);
+
+    // ── Check 12: Database schema ───────────────────────────────────
+    // Opening the database migrates it, so the later checks only open it
+    // once the schema is current
+    let schema_check = schema_check(fix);
+    let db_ready = schema_check.status == "ok";
+
+    // ── Check 13: Local database ────────────────────────────────────
+    let db_check = database_check(fix, db_ready);
+
+    // ── Check 14: Nonce store ───────────────────────────────────────
+    let nonce_check = nonce_check(fix, db_ready);
+
+    let mut checks = vec![
+        config_check,
+        workspace_check,
         profile_check,
+        env_check,
         keyring_check,
         api_key_check,
-        backend_check,
-        hl_check,
+        info_check,
+        exchange_check,
     ];
+    checks.extend(backend_checks);
+    checks.extend([
+        clock_check,
+        hl_check,
+        rate_check,
+        cache_check,
+        schema_check,
+        db_check,
+        nonce_check,
+    ]);
 
-    let all_ok = checks.iter().all(|c| c.status == "ok");
-    let output = DoctorOutput { checks };
-
-    if fmt != OutputFormat::Table {
-        render(fmt, &output)?;
-        return Ok(());
-    }
-
-    // ── Table mode — human-friendly ──────────────────────────────────
-    println!("┌─────────────────────────────────────────────┐");
-    println!("│  ATLAS DOCTOR                               │");
-    println!("├─────────────────────────────────────────────┤");
+    DoctorOutput { checks }
+}
 
+/// Table mode — human-friendly.
+pub fn print_table(output: &DoctorOutput, fix: bool) {
+    let ctx = render_context();
+    let mut t = ctx.panel("ATLAS DOCTOR").max_width(48);
     for check in &output.checks {
-        let icon = if check.status == "ok" { "✓" } else { "✗" };
-        let label = format!("{:<14}", check.name);
-        if check.status == "ok" {
-            let val = check.value.as_deref().unwrap_or("");
-            let display = if val.is_empty() {
-                icon.to_string()
+        let line = if check.status == "ok" {
+            let mark = if check.fixed {
+                format!("{} fixed", ctx.ok())
             } else {
-                format!("{icon} ({val})")
+                ctx.ok().to_string()
+            };
+            let text = match check.value.as_deref().filter(|v| !v.is_empty()) {
+                Some(val) => format!("{mark} ({val})"),
+                None => mark,
             };
-            println!("│  {label}: {:<27}│", display);
+            Cell::new(text).color(Color::Green)
+        } else if check.status == "warn" {
+            let value = check.value.as_deref().unwrap_or("");
+            Cell::new(format!("{} ({value})", ctx.warn())).color(Color::Yellow)
+        } else {
+            let hint = check.fix.as_deref().unwrap_or("");
+            Cell::new(format!("{} {} {hint}", ctx.fail(), ctx.arrow())).color(Color::Red)
+        };
+        t.kv(&check.name, line);
+    }
+
+    let issues: Vec<&DoctorCheck> = output.checks.iter().filter(|c| c.status != "ok").collect();
+    let any_failed = issues.iter().any(|c| c.status == "fail");
+    let repaired: Vec<&str> = output
+        .checks
+        .iter()
+        .filter(|c| c.fixed)
+        .map(|c| c.name.as_str())
+        .collect();
+
+    t.separator();
+    if fix {
+        let msg = if repaired.is_empty() {
+            "nothing to repair".to_string()
         } else {
-            let fix = check
-                .fix
-                .as_deref()
-                .unwrap_or("")
-                .chars()
-                .take(26)
-                .collect::<String>();
-            println!("│  {label}: {icon} → {:<26}│", fix);
+            format!("{} Repaired: {}", ctx.ok(), repaired.join(", "))
+        };
+        t.kv("--fix", msg);
+    }
+    let summary = if !any_failed {
+        format!("{} All systems operational.", ctx.ok())
+    } else if fix {
+        format!("Issues remain {} see below.", ctx.dash())
+    } else {
+        "Issues found. Run with --fix to repair.".to_string()
+    };
+    t.kv("result", summary);
+    print!("{}", t.render());
+
+    // The box truncates fix hints; print them in full
+    for check in issues {
+        println!(
+            "  {} {}: {}",
+            if check.status == "warn" {
+                ctx.warn()
+            } else {
+                ctx.fail()
+            },
+            check.name,
+            ctx.text(check.fix.as_deref().unwrap_or(""))
+        );
+    }
+}
+
+/// Warn when the `/info` throttle lets more through than Hyperliquid's
+/// documented limit; requests beyond it come back 429.
+fn rate_limit_check(rate: &RateLimitConfig) -> DoctorCheck {
+    let limit = atlas_core::constants::HL_INFO_REQUESTS_PER_SEC;
+    let fix = format!(
+        "Above Hyperliquid's documented {limit} req/s, expect 429s — run: atlas configure module set hl rate-limit 8"
+    );
+    if rate.requests_per_sec <= 0.0 {
+        return DoctorCheck::warn("hl_rate_limit", "off", fix);
+    }
+    let value = format!("{} req/s, burst {}", rate.requests_per_sec, rate.burst);
+    if rate.exceeds_documented_limit() {
+        DoctorCheck::warn("hl_rate_limit", value, fix)
+    } else {
+        DoctorCheck::ok("hl_rate_limit", value)
+    }
+}
+
+fn config_check(fix: bool) -> DoctorCheck {
+    let issues = match atlas_core::workspace::config_schema_issues() {
+        Ok(issues) => issues,
+        Err(e) => return DoctorCheck::fail("config", format!("{e:#}")),
+    };
+    if issues.is_empty() {
+        return DoctorCheck::ok("config", "current");
+    }
+    let summary = issues.join("; ");
+    if !fix {
+        return DoctorCheck::fail(
+            "config",
+            format!("atlas.json is outdated ({summary}) — run: atlas doctor --fix"),
+        );
+    }
+    match atlas_core::workspace::repair_config()
+        .and_then(|_| atlas_core::workspace::config_schema_issues())
+    {
+        Ok(left) if left.is_empty() => DoctorCheck::ok("config", "migrated").fixed(),
+        Ok(left) => DoctorCheck::fail(
+            "config",
+            format!(
+                "Migration incomplete ({}) — check ~/.atlas-os/atlas.json by hand",
+                left.join("; ")
+            ),
+        )
+        .fixed(),
+        Err(e) => DoctorCheck::fail("config", format!("Migration failed: {e:#}")),
+    }
+}
+
+fn workspace_check(fix: bool) -> DoctorCheck {
+    let mut unwritable = match atlas_core::workspace::unwritable_dirs() {
+        Ok(dirs) => dirs,
+        Err(e) => return DoctorCheck::fail("workspace", format!("{e:#}")),
+    };
+    let mut fixed = false;
+    // Re-init recreates missing directories; it can't fix permissions
+    if fix && !unwritable.is_empty() && atlas_core::init_workspace().is_ok() {
+        let before = unwritable.len();
+        unwritable = atlas_core::workspace::unwritable_dirs().unwrap_or(unwritable);
+        fixed = unwritable.len() < before;
+    }
+    let check = if unwritable.is_empty() {
+        DoctorCheck::ok("workspace", "writable")
+    } else {
+        let dirs: Vec<String> = unwritable.iter().map(|d| d.display().to_string()).collect();
+        DoctorCheck::fail(
+            "workspace",
+            format!(
+                "Not writable: {} — run: chmod -R u+w ~/.atlas-os",
+                dirs.join(", ")
+            ),
+        )
+    };
+    if fixed {
+        check.fixed()
+    } else {
+        check
+    }
+}
+
+fn keyring_check(config: Option<&AppConfig>, fix: bool, fmt: OutputFormat) -> DoctorCheck {
+    let mut fixed = false;
+    let wallets_exists = atlas_core::workspace::resolve("keystore/wallets.json")
+        .map(|p| p.exists())
+        .unwrap_or(false);
+    if !wallets_exists {
+        if !(fix && atlas_core::init_workspace().is_ok()) {
+            return DoctorCheck::fail("keyring", "Run: atlas doctor --fix — initializes keystore");
+        }
+        fixed = true;
+    }
+    let store = match AuthManager::load_store_pub() {
+        Ok(store) => store,
+        Err(e) => {
+            return DoctorCheck::fail(
+                "keyring",
+                format!("{e:#} — repair or remove ~/.atlas-os/keystore/wallets.json"),
+            )
+        }
+    };
+
+    // Profiles whose private key is gone from the OS keyring
+    let mut missing = Vec::new();
+    for wallet in &store.wallets {
+        match AuthManager::has_key(&wallet.name) {
+            Ok(true) => {}
+            Ok(false) => missing.push(wallet.name.clone()),
+            Err(e) => return DoctorCheck::fail("keyring", format!("{e:#}")),
         }
     }
 
+    // Keys left behind by profiles that no longer exist. The OS keyring
+    // can't be listed, so check every profile name the config refers to.
+    let mut referenced: Vec<String> = config
+        .map(|c| AuthManager::active_profile(c).to_string())
+        .into_iter()
+        .chain(
+            atlas_core::workspace::load_envs()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(_, c)| c.system.active_profile),
+        )
+        .filter(|name| !store.exists(name))
+        .collect();
+    referenced.sort();
+    referenced.dedup();
+    let mut orphans: Vec<String> = referenced
+        .into_iter()
+        .filter(|name| AuthManager::has_key(name).unwrap_or(false))
+        .collect();
+
+    let interactive = fmt == OutputFormat::Table && std::io::stdin().is_terminal();
+    if fix && interactive && !orphans.is_empty() {
+        orphans.retain(|name| {
+            let question = format!(
+                "Delete orphaned keyring key '{name}'? This permanently erases that private key"
+            );
+            let delete = atlas_core::prompt::confirm(&question, false).unwrap_or(false);
+            if delete && AuthManager::delete_key(name).is_ok() {
+                fixed = true;
+                return false;
+            }
+            true
+        });
+    }
+
+    let check = if !missing.is_empty() {
+        DoctorCheck::fail(
+            "keyring",
+            format!(
+                "No keyring key for profile(s) {} — re-import: atlas profile import <name> --key <hex>",
+                missing.join(", ")
+            ),
+        )
+    } else if !orphans.is_empty() {
+        DoctorCheck::fail(
+            "keyring",
+            format!(
+                "Orphaned keyring key(s) for deleted profile(s) {} — run atlas doctor --fix in a terminal to delete, or re-import to keep",
+                orphans.join(", ")
+            ),
+        )
+    } else {
+        DoctorCheck::ok("keyring", format!("{} profiles", store.wallets.len()))
+    };
+    if fixed {
+        check.fixed()
+    } else {
+        check
+    }
+}
+
+async fn api_key_check(config: Option<&AppConfig>) -> DoctorCheck {
+    if config.and_then(|c| c.system.api_key.as_ref()).is_none() {
+        return DoctorCheck::fail(
+            "api_key",
+            "Run: atlas configure system api-key <key> — get a key from the Atlas dashboard → Settings → API Keys",
+        );
+    }
+    let backend = match atlas_core::BackendClient::from_config() {
+        Ok(backend) => backend,
+        Err(e) => return DoctorCheck::fail("api_key", format!("{e:#}")),
+    };
+    // Cheapest authenticated endpoint: who does this key belong to
+    match backend.get("/atlas-os/me", &[]).await {
+        Ok(me) => {
+            let value = me
+                .pointer("/api_key/name")
+                .and_then(|n| n.as_str())
+                .map(|n| format!("valid ({n})"))
+                .unwrap_or_else(|| "valid".into());
+            DoctorCheck::ok("api_key", value)
+        }
+        Err(AtlasError::ApiKeyRejected(_)) => DoctorCheck::fail(
+            "api_key",
+            "Backend rejected the API key — create a new one in the Atlas dashboard → Settings → API Keys, then: atlas configure system api-key <key>",
+        ),
+        // The backend check reports reachability; don't fail the key for it
+        Err(_) => DoctorCheck::ok("api_key", "set (unverified: backend unreachable)"),
+    }
+}
+
+async fn clock_check(hl_base: &str) -> DoctorCheck {
+    let body = serde_json::json!({"type": "allMids"});
+    let skew = match probe_http() {
+        Ok(http) => http.clock_skew_ms(&format!("{hl_base}/info"), &body).await,
+        Err(e) => Err(e),
+    };
+    match skew {
+        Ok(skew) if skew.abs() <= MAX_CLOCK_SKEW_MS => {
+            DoctorCheck::ok("clock", format!("{:+.1}s", skew as f64 / 1000.0))
+        }
+        Ok(skew) => DoctorCheck::fail(
+            "clock",
+            format!(
+                "Local clock is {:+.1}s off Hyperliquid — sync it (e.g. sudo timedatectl set-ntp true)",
+                skew as f64 / 1000.0
+            ),
+        ),
+        Err(_) => DoctorCheck::fail(
+            "clock",
+            "Could not read Hyperliquid server time — check network connectivity",
+        ),
+    }
+}
+
+fn cache_check(testnet: bool, ttl: Duration, fix: bool) -> DoctorCheck {
+    let Some(path) = MetaCache::path(testnet) else {
+        return DoctorCheck::ok("meta_cache", "empty");
+    };
+    // Corrupt caches are cleared for both networks; the report is for the active one
+    let mut cleared = false;
     if fix {
-        println!("├─────────────────────────────────────────────┤");
-        println!("│  --fix: Re-initializing workspace...        │");
-        atlas_core::init_workspace()?;
-        println!("│  ✓ Workspace re-initialized.                │");
-    } else if !all_ok {
-        println!("├─────────────────────────────────────────────┤");
-        println!("│  Issues found. Run with --fix to repair.    │");
+        for other in [false, true]
+            .into_iter()
+            .filter_map(MetaCache::path)
+            .filter(|p| meta::is_corrupt(p))
+        {
+            if std::fs::remove_file(&other).is_ok() && other == path {
+                cleared = true;
+            }
+        }
+    }
+    if cleared {
+        return DoctorCheck::ok("meta_cache", "cleared corrupt cache").fixed();
+    }
+    if meta::is_corrupt(&path) {
+        return DoctorCheck::fail(
+            "meta_cache",
+            "Corrupt metadata cache — run: atlas doctor --fix",
+        );
+    }
+    match MetaCache::load(&path) {
+        Some(cache) if !cache.is_fresh(ttl) => {
+            if fix && std::fs::remove_file(&path).is_ok() {
+                return DoctorCheck::ok("meta_cache", "cleared stale cache").fixed();
+            }
+            // Refetched on next use, so stale isn't a failure
+            DoctorCheck::ok(
+                "meta_cache",
+                format!(
+                    "stale: {}s old, ttl {}s",
+                    cache.age().as_secs(),
+                    ttl.as_secs()
+                ),
+            )
+        }
+        Some(cache) => DoctorCheck::ok(
+            "meta_cache",
+            format!(
+                "{} markets, {}s old",
+                cache.perps.len(),
+                cache.age().as_secs()
+            ),
+        ),
+        None => DoctorCheck::ok("meta_cache", "empty"),
+    }
+}
+
+/// Schema version of the local database against this build. Pending
+/// migrations are applied on the next command that uses the database, so
+/// they only warn; a database from a newer atlas fails.
+fn schema_check(fix: bool) -> DoctorCheck {
+    let path = match AtlasDb::path() {
+        Ok(path) => path,
+        Err(e) => return DoctorCheck::fail("db_schema", format!("{e:#}")),
+    };
+    if !path.exists() {
+        return DoctorCheck::ok("db_schema", "not created yet");
+    }
+    let status = match AtlasDb::schema_status_at(&path) {
+        Ok(status) => status,
+        Err(e) => return DoctorCheck::fail("db_schema", format!("{e:#}")),
+    };
+    let mut check = if status.is_newer() {
+        DoctorCheck::fail(
+            "db_schema",
+            format!(
+                "Database schema v{} is newer than this atlas (v{SCHEMA_VERSION}) — upgrade atlas",
+                status.current
+            ),
+        )
+    } else if status.pending.is_empty() {
+        DoctorCheck::ok("db_schema", format!("v{}", status.current))
+    } else if fix {
+        match AtlasDb::open() {
+            Ok(_) => DoctorCheck::ok(
+                "db_schema",
+                format!(
+                    "v{SCHEMA_VERSION}, applied {} migration(s)",
+                    status.pending.len()
+                ),
+            )
+            .fixed(),
+            Err(e) => DoctorCheck::fail(
+                "db_schema",
+                format!(
+                    "Migration failed: {e:#} — move {} aside and re-run: atlas hl sync --full",
+                    path.display()
+                ),
+            ),
+        }
+    } else {
+        DoctorCheck::warn(
+            "db_schema",
+            format!(
+                "v{}, {} migration(s) pending",
+                status.current,
+                status.pending.len()
+            ),
+            "Applied by the next command that uses the database, or run: atlas doctor --fix",
+        )
+    };
+    if check.fixed {
+        check.schema_version = Some(SCHEMA_VERSION);
     } else {
-        println!("├─────────────────────────────────────────────┤");
-        println!("│  ✓ All systems operational.                 │");
+        check.schema_version = Some(status.current);
+        check.pending_migrations = status
+            .pending
+            .iter()
+            .map(|(version, name)| format!("v{version} {name}"))
+            .collect();
     }
+    check
+}
 
-    println!("└─────────────────────────────────────────────┘");
-    Ok(())
+fn database_check(fix: bool, db_ready: bool) -> DoctorCheck {
+    let path = match AtlasDb::path() {
+        Ok(path) => path,
+        Err(e) => return DoctorCheck::fail("database", format!("{e:#}")),
+    };
+    // Don't create it just to check it
+    if !path.exists() {
+        return DoctorCheck::ok("database", "not created yet");
+    }
+    if !db_ready {
+        return DoctorCheck::ok("database", "not checked until the schema is current");
+    }
+    let db = match AtlasDb::open() {
+        Ok(db) => db,
+        Err(e) => {
+            return DoctorCheck::fail(
+                "database",
+                format!(
+                    "{e:#} — move {} aside and re-run: atlas hl sync --full",
+                    path.display()
+                ),
+            )
+        }
+    };
+    let problems = match db.integrity_check() {
+        Ok(problems) if problems.is_empty() => return DoctorCheck::ok("database", "integrity ok"),
+        Ok(problems) => problems,
+        Err(e) => return DoctorCheck::fail("database", format!("Integrity check failed: {e:#}")),
+    };
+    if fix && db.rebuild_indexes().is_ok() && db.integrity_check().is_ok_and(|p| p.is_empty()) {
+        return DoctorCheck::ok("database", "indexes rebuilt").fixed();
+    }
+    let hint = if fix {
+        format!(
+            "rebuilding indexes didn't help — move {} aside and re-run: atlas hl sync --full",
+            path.display()
+        )
+    } else {
+        "run: atlas doctor --fix to rebuild indexes".to_string()
+    };
+    DoctorCheck::fail(
+        "database",
+        format!(
+            "{} integrity problem(s), first: {} — {hint}",
+            problems.len(),
+            problems[0]
+        ),
+    )
+}
+
+/// Last nonces persisted per wallet. One far in the future (or garbage)
+/// would make every later order fail with a nonce error; `--fix` forgets
+/// it so the sequence restarts from the clock.
+fn nonce_check(fix: bool, db_ready: bool) -> DoctorCheck {
+    // A missing or unopenable database is the database check's to report
+    if !AtlasDb::path().is_ok_and(|p| p.exists()) {
+        return DoctorCheck::ok("nonces", "none yet");
+    }
+    if !db_ready {
+        return DoctorCheck::ok("nonces", "not checked until the schema is current");
+    }
+    let Ok(db) = AtlasDb::open() else {
+        return DoctorCheck::fail("nonces", "Database unavailable — see the database check");
+    };
+    let records = match db.nonces() {
+        Ok(records) => records,
+        Err(e) => return DoctorCheck::fail("nonces", format!("Nonce store unreadable: {e:#}")),
+    };
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let corrupt: Vec<&str> = records
+        .iter()
+        .filter(|r| nonce::is_corrupt(r.last_nonce, now_ms))
+        .map(|r| r.address.as_str())
+        .collect();
+    if corrupt.is_empty() {
+        return match records.len() {
+            0 => DoctorCheck::ok("nonces", "none yet"),
+            n => DoctorCheck::ok("nonces", format!("{n} wallet(s)")),
+        };
+    }
+    if fix && corrupt.iter().all(|a| db.clear_nonce(a).is_ok()) {
+        return DoctorCheck::ok(
+            "nonces",
+            format!("reset {} corrupt nonce(s)", corrupt.len()),
+        )
+        .fixed();
+    }
+    DoctorCheck::fail(
+        "nonces",
+        format!(
+            "Corrupt last nonce for {} — run: atlas doctor --fix",
+            corrupt.join(", ")
+        ),
+    )
+}
+
+fn latency_check(name: &str, probe: Result<u64>, fix: &str) -> DoctorCheck {
+    match probe {
+        Ok(ms) => {
+            let mut check = DoctorCheck::ok(name, format!("{ms}ms"));
+            check.latency_ms = Some(ms);
+            check
+        }
+        Err(_) => DoctorCheck::fail(name, fix),
+    }
+}
+
+/// One attempt, short timeout — doctor reports problems, it doesn't ride them out.
+fn probe_http() -> Result<ExchangeHttp, AtlasError> {
+    ExchangeHttp::new(RetryPolicy {
+        max_retries: 0,
+        timeout: Duration::from_secs(5),
+        backoff: Duration::ZERO,
+    })
+}
+
+/// Round-trip time to a Hyperliquid endpoint. Any HTTP response — including
+/// a 4xx for an unsigned `/exchange` probe — counts as reachable.
+async fn probe_hl(url: &str, body: serde_json::Value) -> Result<u64> {
+    let http = probe_http()?;
+    let start = Instant::now();
+    match http.post_once(url, &body).await {
+        Ok(_) | Err(HttpFailure::Rejected { .. }) => Ok(start.elapsed().as_millis() as u64),
+        Err(e) => Err(e.into_error(url).into()),
+    }
+}
+
+/// `backend` for reachability, then one `backend_<dependency>` check per
+/// entry in the backend's `/readyz` report. A failing dependency fails its
+/// check, though there is nothing to repair on this machine.
+fn backend_checks(probe: Result<(u64, Readiness)>) -> Vec<DoctorCheck> {
+    let (ms, report) = match probe {
+        Ok(probe) => probe,
+        Err(_) => {
+            return vec![DoctorCheck::fail(
+                "backend",
+                "Atlas backend unreachable — check network connectivity",
+            )]
+        }
+    };
+    let mut checks = vec![latency_check("backend", Ok(ms), "")];
+    for (name, dep) in &report.checks {
+        let name = format!("backend_{name}");
+        let check = match dep.status.as_str() {
+            "ok" => latency_check(&name, Ok(dep.latency_ms.unwrap_or(0)), ""),
+            "skipped" => DoctorCheck::ok(
+                &name,
+                match &dep.error {
+                    Some(why) => format!("skipped: {why}"),
+                    None => "skipped".into(),
+                },
+            ),
+            _ => DoctorCheck::fail(
+                &name,
+                format!(
+                    "Atlas backend dependency down ({}) — not fixable locally; retry later",
+                    dep.error.as_deref().unwrap_or(&dep.status)
+                ),
+            ),
+        };
+        checks.push(check);
+    }
+    checks
 }
 
-async fn check_api_latency() -> Result<u64> {
-    let start = std::time::Instant::now();
-    let client = hypersdk::hypercore::mainnet();
-    let _ = client.all_mids(None).await?;
-    Ok(start.elapsed().as_millis() as u64)
+async fn probe_backend() -> Result<(u64, Readiness)> {
+    let backend = atlas_core::BackendClient::new(atlas_core::constants::ATLAS_BACKEND_URL)
+        .with_retries(0, Duration::ZERO);
+    let start = Instant::now();
+    let report = backend.readiness().await?;
+    Ok((start.elapsed().as_millis() as u64, report))
 }
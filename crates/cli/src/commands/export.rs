@@ -4,13 +4,16 @@ use std::collections::HashMap;
 use std::io::Write;
 
 use anyhow::{Context, Result};
+use atlas_core::date_range::DateRange;
 use atlas_core::db::AtlasDb;
-use atlas_core::db::FillFilter;
+use atlas_core::db::{FillFilter, SwapFilter};
 use atlas_core::output::ExportOutput;
 use atlas_core::output::{render, OutputFormat};
+use atlas_core::statement::{self, StatementRequest};
 use rust_decimal::Decimal;
 
-use super::helpers::{format_ms, normalize_protocol, parse_date_to_ms};
+use super::helpers::{format_ms, normalize_protocol, parse_date_range, time_zone};
+use super::history::{includes_swaps, swap_pnl_summary, swap_trade_row};
 
 /// Generate an export file path.
 fn export_path(kind: &str, ext: &str) -> Result<std::path::PathBuf> {
@@ -23,19 +26,19 @@ fn export_path(kind: &str, ext: &str) -> Result<std::path::PathBuf> {
     Ok(data_dir.join(format!("export-{kind}-{ts}.{ext}")))
 }
 
-/// `atlas export trades [--csv|--json] [--coin COIN] [--from DATE] [--to DATE]`
+/// `atlas export trades [--csv|--json] [--coin COIN] [--from DATE] [--to DATE] [--notes]`
 pub fn run_export_trades(
     protocol: Option<&str>,
     use_json: bool,
     coin: Option<&str>,
     from: Option<&str>,
     to: Option<&str>,
+    with_notes: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
     let db = AtlasDb::open()?;
 
-    let from_ms = from.map(parse_date_to_ms).transpose()?;
-    let to_ms = to.map(parse_date_to_ms).transpose()?;
+    let DateRange { from_ms, to_ms } = parse_date_range(from, to)?;
 
     let filter = FillFilter {
         protocol: protocol.map(normalize_protocol),
@@ -46,37 +49,69 @@ pub fn run_export_trades(
     };
 
     let fills = db.query_fills(&filter)?;
+    let notes = if with_notes {
+        let hashes: Vec<String> = fills.iter().map(|f| f.hash.clone()).collect();
+        db.journal_notes_for_fills(&hashes)?
+    } else {
+        HashMap::new()
+    };
+    let note_for = |hash: &str| notes.get(hash).map(|n| n.join("; ")).unwrap_or_default();
+
+    // (time, row), merged with executed 0x swaps and sorted newest first
+    let mut trades: Vec<(i64, TradeRow)> = fills
+        .iter()
+        .map(|f| {
+            (
+                f.time_ms,
+                TradeRow {
+                    protocol: f.protocol.clone(),
+                    coin: f.coin.clone(),
+                    side: f.side.clone(),
+                    size: f.sz.clone(),
+                    price: f.px.clone(),
+                    pnl: f.closed_pnl.clone(),
+                    fee: f.fee.clone(),
+                    time: format_ms(f.time_ms),
+                    hash: f.hash.clone(),
+                    notes: with_notes.then(|| note_for(&f.hash)),
+                },
+            )
+        })
+        .collect();
+    if includes_swaps(protocol) {
+        let swaps = db.query_swaps(&SwapFilter {
+            status: Some("executed".into()),
+            token: coin.map(str::to_string),
+            from_ms,
+            to_ms,
+            ..Default::default()
+        })?;
+        for swap in &swaps {
+            let row = swap_trade_row(swap);
+            trades.push((
+                swap.time_ms,
+                TradeRow {
+                    protocol: row.protocol,
+                    coin: row.coin,
+                    side: row.side,
+                    size: row.size,
+                    price: row.price,
+                    pnl: row.pnl,
+                    fee: row.fee,
+                    time: row.time,
+                    hash: row.hash,
+                    notes: with_notes.then(String::new),
+                },
+            ));
+        }
+        trades.sort_by(|a, b| b.0.cmp(&a.0));
+    }
+    let rows: Vec<TradeRow> = trades.into_iter().map(|(_, row)| row).collect();
 
     if use_json {
         // Export as JSON
         let path = export_path("trades", "json")?;
 
-        #[derive(serde::Serialize)]
-        struct TradeRow {
-            coin: String,
-            side: String,
-            size: String,
-            price: String,
-            pnl: String,
-            fee: String,
-            time: String,
-            hash: String,
-        }
-
-        let rows: Vec<TradeRow> = fills
-            .iter()
-            .map(|f| TradeRow {
-                coin: f.coin.clone(),
-                side: f.side.clone(),
-                size: f.sz.clone(),
-                price: f.px.clone(),
-                pnl: f.closed_pnl.clone(),
-                fee: f.fee.clone(),
-                time: format_ms(f.time_ms),
-                hash: f.hash.clone(),
-            })
-            .collect();
-
         let json = serde_json::to_string_pretty(&rows)?;
         std::fs::write(&path, &json)
             .with_context(|| format!("Failed to write {}", path.display()))?;
@@ -93,25 +128,27 @@ pub fn run_export_trades(
         let mut file = std::fs::File::create(&path)
             .with_context(|| format!("Failed to create {}", path.display()))?;
 
-        writeln!(file, "coin,side,size,price,pnl,fee,time,hash")?;
-        for f in &fills {
-            writeln!(
+        let header = "protocol,coin,side,size,price,pnl,fee,time,hash";
+        if with_notes {
+            writeln!(file, "{header},notes")?;
+        } else {
+            writeln!(file, "{header}")?;
+        }
+        for r in &rows {
+            write!(
                 file,
-                "{},{},{},{},{},{},{},{}",
-                f.coin,
-                f.side,
-                f.sz,
-                f.px,
-                f.closed_pnl,
-                f.fee,
-                format_ms(f.time_ms),
-                f.hash,
+                "{},{},{},{},{},{},{},{},{}",
+                r.protocol, r.coin, r.side, r.size, r.price, r.pnl, r.fee, r.time, r.hash,
             )?;
+            if let Some(notes) = &r.notes {
+                write!(file, ",{}", csv_field(notes))?;
+            }
+            writeln!(file)?;
         }
 
         let output = ExportOutput {
             path: path.display().to_string(),
-            rows: fills.len(),
+            rows: rows.len(),
             format: "csv".to_string(),
         };
         render(fmt, &output)?;
@@ -120,6 +157,21 @@ pub fn run_export_trades(
     Ok(())
 }
 
+#[derive(serde::Serialize)]
+struct TradeRow {
+    protocol: String,
+    coin: String,
+    side: String,
+    size: String,
+    price: String,
+    pnl: String,
+    fee: String,
+    time: String,
+    hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<String>,
+}
+
 /// `atlas export pnl [--protocol hl] [--csv|--json] [--from DATE] [--to DATE]`
 pub fn run_export_pnl(
     protocol: Option<&str>,
@@ -130,8 +182,7 @@ pub fn run_export_pnl(
 ) -> Result<()> {
     let db = AtlasDb::open()?;
 
-    let from_ms = from.map(parse_date_to_ms).transpose()?;
-    let to_ms = to.map(parse_date_to_ms).transpose()?;
+    let DateRange { from_ms, to_ms } = parse_date_range(from, to)?;
 
     let filter = FillFilter {
         protocol: protocol.map(normalize_protocol),
@@ -143,55 +194,69 @@ pub fn run_export_pnl(
 
     let fills = db.query_fills(&filter)?;
 
-    // Aggregate by coin
-    let mut by_coin: HashMap<String, (Decimal, Decimal, usize)> = HashMap::new();
+    // Aggregate by protocol and coin
+    let mut by_coin: HashMap<(String, String), (Decimal, Decimal, usize)> = HashMap::new();
     for fill in &fills {
         let pnl: Decimal = fill.closed_pnl.parse().unwrap_or(Decimal::ZERO);
         let fee: Decimal = fill.fee.parse().unwrap_or(Decimal::ZERO);
         let entry = by_coin
-            .entry(fill.coin.clone())
+            .entry((fill.protocol.clone(), fill.coin.clone()))
             .or_insert((Decimal::ZERO, Decimal::ZERO, 0));
         entry.0 += pnl;
         entry.1 += fee;
         entry.2 += 1;
     }
 
-    let mut rows: Vec<(String, Decimal, Decimal, usize)> = by_coin
+    let mut rows: Vec<PnlRow> = by_coin
         .into_iter()
-        .map(|(c, (pnl, fees, trades))| (c, pnl, fees, trades))
+        .map(|((protocol, coin), (pnl, fees, trades))| PnlRow {
+            protocol,
+            coin,
+            pnl_type: "realized".into(),
+            pnl: pnl.to_string(),
+            fees: fees.to_string(),
+            net_pnl: (pnl - fees).to_string(),
+            trades,
+        })
         .collect();
-    rows.sort_by(|a, b| a.0.cmp(&b.0));
-
-    if use_json {
-        let path = export_path("pnl", "json")?;
+    rows.sort_by(|a, b| (&a.protocol, &a.coin).cmp(&(&b.protocol, &b.coin)));
 
-        #[derive(serde::Serialize)]
-        struct PnlRow {
-            coin: String,
-            pnl: String,
-            fees: String,
-            net_pnl: String,
-            trades: usize,
+    // Tokens bought through 0x swaps, at cached prices. Unpriced tokens
+    // leave pnl and net_pnl empty.
+    if includes_swaps(protocol) {
+        let filter = SwapFilter {
+            from_ms,
+            to_ms,
+            ..Default::default()
+        };
+        let positions = swap_pnl_summary(&db, &filter)?.map_or_else(Vec::new, |s| s.positions);
+        for p in positions {
+            let net = p.unrealized_pnl.as_deref().and_then(|pnl| {
+                let pnl: Decimal = pnl.parse().ok()?;
+                let gas: Decimal = p.gas_usd.parse().ok()?;
+                Some((pnl - gas).to_string())
+            });
+            rows.push(PnlRow {
+                protocol: "0x".into(),
+                coin: format!("{}:{}", p.chain, p.symbol.unwrap_or(p.token)),
+                pnl_type: "unrealized".into(),
+                pnl: p.unrealized_pnl.unwrap_or_default(),
+                fees: p.gas_usd,
+                net_pnl: net.unwrap_or_default(),
+                trades: p.swaps,
+            });
         }
+    }
 
-        let export_rows: Vec<PnlRow> = rows
-            .iter()
-            .map(|(c, pnl, fees, trades)| PnlRow {
-                coin: c.clone(),
-                pnl: pnl.to_string(),
-                fees: fees.to_string(),
-                net_pnl: (*pnl - *fees).to_string(),
-                trades: *trades,
-            })
-            .collect();
-
-        let json = serde_json::to_string_pretty(&export_rows)?;
+    if use_json {
+        let path = export_path("pnl", "json")?;
+        let json = serde_json::to_string_pretty(&rows)?;
         std::fs::write(&path, &json)
             .with_context(|| format!("Failed to write {}", path.display()))?;
 
         let output = ExportOutput {
             path: path.display().to_string(),
-            rows: export_rows.len(),
+            rows: rows.len(),
             format: "json".to_string(),
         };
         render(fmt, &output)?;
@@ -200,9 +265,13 @@ pub fn run_export_pnl(
         let mut file = std::fs::File::create(&path)
             .with_context(|| format!("Failed to create {}", path.display()))?;
 
-        writeln!(file, "coin,pnl,fees,net_pnl,trades")?;
-        for (c, pnl, fees, trades) in &rows {
-            writeln!(file, "{},{},{},{},{}", c, pnl, fees, *pnl - *fees, trades)?;
+        writeln!(file, "protocol,coin,pnl_type,pnl,fees,net_pnl,trades")?;
+        for r in &rows {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{}",
+                r.protocol, r.coin, r.pnl_type, r.pnl, r.fees, r.net_pnl, r.trades
+            )?;
         }
 
         let output = ExportOutput {
@@ -215,3 +284,83 @@ pub fn run_export_pnl(
 
     Ok(())
 }
+
+#[derive(serde::Serialize)]
+struct PnlRow {
+    protocol: String,
+    coin: String,
+    /// `realized` for fills, `unrealized` for tokens bought through swaps.
+    pnl_type: String,
+    pnl: String,
+    fees: String,
+    net_pnl: String,
+    trades: usize,
+}
+
+/// `atlas export statement --month YYYY-MM [--protocol all] [--out FILE] [--format csv|json]`
+pub fn run_export_statement(
+    month: &str,
+    protocol: &str,
+    out: Option<&str>,
+    use_json: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let network = if config.modules.hyperliquid.config.network == "testnet" {
+        "Testnet"
+    } else {
+        "Mainnet"
+    };
+    let DateRange { from_ms, to_ms } = atlas_core::date_range::parse_month(month, time_zone()?)?;
+    let protocol = normalize_protocol(protocol);
+
+    let db = AtlasDb::open()?;
+    let statement = statement::build(
+        &db,
+        &StatementRequest {
+            month,
+            from_ms: from_ms.unwrap_or_default(),
+            to_ms: to_ms.unwrap_or_default(),
+            protocol: (protocol != "all").then_some(protocol.as_str()),
+            profile: atlas_core::AuthManager::active_profile(&config),
+            network,
+            now_ms: chrono::Utc::now().timestamp_millis(),
+        },
+    )?;
+
+    let ext = if use_json { "json" } else { "csv" };
+    let path = match out {
+        Some(out) => std::path::PathBuf::from(out),
+        None => export_path("statement", ext)?,
+    };
+    let rows = if use_json {
+        let json = serde_json::to_string_pretty(&statement)?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        statement.entries.len()
+    } else {
+        let file = std::fs::File::create(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut writer = std::io::BufWriter::new(file);
+        let rows = statement.write_csv(&mut writer)?;
+        writer.flush()?;
+        rows
+    };
+
+    let output = ExportOutput {
+        path: path.display().to_string(),
+        rows,
+        format: ext.to_string(),
+    };
+    render(fmt, &output)?;
+    Ok(())
+}
+
+/// Quote a free-text CSV field when it contains a delimiter, quote or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
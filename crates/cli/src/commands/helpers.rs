@@ -1,6 +1,17 @@
 //! Shared helpers for CLI commands.
 
+use std::sync::{Arc, OnceLock};
+
 use anyhow::Result;
+use atlas_core::date_range::{self, DateRange, TimeZone};
+use atlas_core::db::AtlasDb;
+use atlas_core::fmt::format_decimal;
+use atlas_core::output::{OrderRow, PositionRow};
+use atlas_core::spread_profile;
+use atlas_core::traits::PerpModule;
+use atlas_core::types::{Order, OrderType, Position, Ticker};
+use atlas_core::Orchestrator;
+use rust_decimal::Decimal;
 
 /// Normalize protocol name aliases.
 pub fn normalize_protocol(p: &str) -> String {
@@ -11,19 +22,57 @@ pub fn normalize_protocol(p: &str) -> String {
     }
 }
 
-/// Parse an ISO date string to millisecond timestamp.
-/// Accepts "2025-01-01" or "2025-01-01T00:00:00".
-pub fn parse_date_to_ms(s: &str) -> Result<i64> {
-    use chrono::NaiveDateTime;
-
-    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
-        return Ok(dt.and_utc().timestamp_millis());
+/// Record the tickers' spreads for `spread-profile` and the market order
+/// liquidity check. Best effort: a database problem never fails the
+/// command that happened to fetch them.
+pub fn record_spreads(tickers: &[Ticker]) {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let recorded =
+        AtlasDb::open().and_then(|db| spread_profile::record(&db, "hyperliquid", tickers, now_ms));
+    if let Err(e) = recorded {
+        tracing::debug!(error = %e, "spread samples not recorded");
     }
-    if let Ok(d) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
-        let dt = NaiveDateTime::new(d, chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap());
-        return Ok(dt.and_utc().timestamp_millis());
+}
+
+/// Set by the global `--tz` flag: the zone `--from`/`--to` dates are read in.
+static TIME_ZONE: OnceLock<String> = OnceLock::new();
+
+/// Read dates in every command of this process in `tz` (see
+/// [`TimeZone::parse`]) instead of local time.
+pub fn set_time_zone(tz: Option<String>) {
+    if let Some(tz) = tz {
+        let _ = TIME_ZONE.set(tz);
     }
-    anyhow::bail!("Invalid date format: {s}. Use YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS")
+}
+
+/// The zone set by `--tz`, local time by default.
+pub fn time_zone() -> Result<TimeZone> {
+    Ok(match TIME_ZONE.get() {
+        Some(tz) => TimeZone::parse(tz)?,
+        None => TimeZone::Local,
+    })
+}
+
+/// Parse a date or time to a millisecond timestamp: the start of a day
+/// for dates and named days. See [`atlas_core::date_range`] for the forms.
+pub fn parse_date_to_ms(s: &str) -> Result<i64> {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    Ok(date_range::parse_point(s, time_zone()?, now_ms)?)
+}
+
+/// Parse `--from`/`--to` into an inclusive range; `--to` a date covers
+/// that whole day. Fails unless `--from` is before `--to`.
+pub fn parse_date_range(from: Option<&str>, to: Option<&str>) -> Result<DateRange> {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    Ok(date_range::parse_range(from, to, time_zone()?, now_ms)?)
+}
+
+/// A 0x address in checksummed form.
+pub fn parse_address(s: &str) -> Result<String> {
+    let address: alloy::primitives::Address = s.trim().parse().map_err(|_| {
+        anyhow::anyhow!("Invalid address '{s}': expected 0x followed by 40 hex digits")
+    })?;
+    Ok(address.to_checksum(None))
 }
 
 /// Format a millisecond timestamp to human-readable UTC string.
@@ -32,3 +81,63 @@ pub fn format_ms(ms: i64) -> String {
         .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string())
         .unwrap_or_else(|| "N/A".to_string())
 }
+
+/// Perp modules selected by `--protocol`: `all`, one by name, or the default.
+pub fn select_perps(
+    orch: &Orchestrator,
+    protocol: Option<&str>,
+) -> Result<Vec<Arc<dyn PerpModule>>> {
+    match protocol.map(normalize_protocol).as_deref() {
+        Some("all") => Ok(orch.all_perps()),
+        name => Ok(vec![Arc::clone(orch.perp(name)?)]),
+    }
+}
+
+/// Display row for an open order; stop orders read as `trigger` with
+/// their trigger price.
+pub fn order_row(o: &Order) -> OrderRow {
+    let trigger_type = match o.order_type {
+        OrderType::StopMarket => Some("market"),
+        OrderType::StopLimit => Some("limit"),
+        OrderType::Market | OrderType::Limit => None,
+    };
+    OrderRow {
+        coin: o.symbol.clone(),
+        side: format!("{:?}", o.side),
+        size: o.size.to_string(),
+        price: o.price.map(|p| p.to_string()).unwrap_or_else(|| "—".into()),
+        oid: o.order_id.parse().unwrap_or(0),
+        order_type: if trigger_type.is_some() {
+            "trigger".into()
+        } else {
+            "limit".into()
+        },
+        trigger_price: o.trigger_price.map(|p| p.to_string()),
+        trigger_type: trigger_type.map(Into::into),
+    }
+}
+
+/// Display row for a position, tagged with its protocol. Figures are
+/// formatted the same way whichever module reported them: sizes and prices
+/// without trailing zeros, USD amounts and percentages to two places.
+pub fn position_row(p: &Position) -> PositionRow {
+    let plain = |d: Decimal| d.normalize().to_string();
+    PositionRow {
+        coin: p.symbol.clone(),
+        side: if p.is_long() {
+            "long".into()
+        } else {
+            "short".into()
+        },
+        size: plain(p.size),
+        entry_price: p.entry_price.map(plain),
+        mark_price: p.mark_price.map(plain),
+        unrealized_pnl: p.unrealized_pnl.map(|u| format_decimal(u, 2)),
+        notional: p.notional().map(|n| format_decimal(n, 2)),
+        pnl_pct: p.pnl_pct().map(|pct| format_decimal(pct, 2)),
+        liquidation_price: p.liquidation_price.map(plain),
+        leverage: p.leverage,
+        margin_mode: p.margin_mode.clone(),
+        protocol: p.protocol.to_string(),
+    }
+}
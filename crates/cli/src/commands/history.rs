@@ -2,32 +2,38 @@
 
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use atlas_core::date_range::DateRange;
 use atlas_core::db::AtlasDb;
-use atlas_core::db::{FillFilter, OrderFilter};
+use atlas_core::db::{DbSwap, DbTransfer, FillFilter, OrderFilter, SwapFilter, TransferFilter};
+use atlas_core::orchestrator::{fan_out, Gathered, MODULE_TIMEOUT};
+use atlas_core::order_sync::normalize_status;
 use atlas_core::output::{render, OutputFormat};
 use atlas_core::output::{
-    OrderHistoryOutput, OrderHistoryRow, PnlByCoinRow, PnlSummaryOutput, SyncOutput,
-    TradeHistoryOutput, TradeHistoryRow,
+    ImportErrorRow, ImportOutput, OrderHistoryOutput, OrderHistoryRow, PnlByCoinRow,
+    PnlSummaryOutput, SwapHistoryOutput, SwapHistoryRow, SwapPnlRow, SwapPnlSummary, SyncOutput,
+    TradeHistoryOutput, TradeHistoryRow, TransferHistoryOutput, TransferRow,
 };
+use atlas_core::trade_import;
 use atlas_core::Engine;
 use rust_decimal::Decimal;
 
-use super::helpers::{format_ms, normalize_protocol, parse_date_to_ms};
+use super::helpers::{format_ms, normalize_protocol, parse_date_range, select_perps, time_zone};
+use super::trade::fills_output;
 
-/// `atlas history trades [--protocol hl] [--coin COIN] [--from DATE] [--to DATE] [--limit N]`
+/// `atlas history trades [--protocol hl] [--coin COIN] [--from DATE] [--to DATE] [--limit N] [--with-notes]`
 pub fn run_trades(
     protocol: Option<&str>,
     coin: Option<&str>,
     from: Option<&str>,
     to: Option<&str>,
     limit: usize,
+    with_notes: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
     let db = AtlasDb::open()?;
 
-    let from_ms = from.map(parse_date_to_ms).transpose()?;
-    let to_ms = to.map(parse_date_to_ms).transpose()?;
+    let DateRange { from_ms, to_ms } = parse_date_range(from, to)?;
 
     let filter = FillFilter {
         protocol: protocol.map(normalize_protocol),
@@ -38,41 +44,83 @@ pub fn run_trades(
     };
 
     let fills = db.query_fills(&filter)?;
+    let hashes: Vec<String> = fills.iter().map(|f| f.hash.clone()).collect();
+    let mut notes = db.journal_notes_for_fills(&hashes)?;
 
-    let trades: Vec<TradeHistoryRow> = fills
+    let mut trades: Vec<TradeHistoryRow> = fills
         .iter()
-        .map(|f| TradeHistoryRow {
-            protocol: f.protocol.clone(),
-            coin: f.coin.clone(),
-            side: f.side.clone(),
-            size: f.sz.clone(),
-            price: f.px.clone(),
-            pnl: f.closed_pnl.clone(),
-            fee: f.fee.clone(),
-            time: format_ms(f.time_ms),
+        .map(|f| {
+            let fill_notes = notes.remove(&f.hash).unwrap_or_default();
+            TradeHistoryRow {
+                protocol: f.protocol.clone(),
+                coin: f.coin.clone(),
+                side: f.side.clone(),
+                size: f.sz.clone(),
+                price: f.px.clone(),
+                pnl: f.closed_pnl.clone(),
+                fee: f.fee.clone(),
+                time: format_ms(f.time_ms),
+                hash: f.hash.clone(),
+                has_note: !fill_notes.is_empty(),
+                notes: if with_notes { fill_notes } else { Vec::new() },
+            }
         })
         .collect();
 
+    if includes_swaps(protocol) {
+        let swaps = db.query_swaps(&SwapFilter {
+            status: Some("executed".into()),
+            token: coin.map(str::to_string),
+            from_ms,
+            to_ms,
+            limit: Some(limit),
+            ..Default::default()
+        })?;
+        // Merge newest first; both lists already are.
+        let mut times: Vec<i64> = fills.iter().map(|f| f.time_ms).collect();
+        for swap in &swaps {
+            let at = times.partition_point(|&t| t >= swap.time_ms);
+            times.insert(at, swap.time_ms);
+            trades.insert(at, swap_trade_row(swap));
+        }
+        trades.truncate(limit);
+    }
+
     let total = trades.len();
-    let output = TradeHistoryOutput { trades, total };
+    let output = TradeHistoryOutput {
+        trades,
+        total,
+        with_notes,
+    };
     render(fmt, &output)?;
     Ok(())
 }
 
-/// `atlas history orders [--protocol hl] [--coin COIN] [--status STATUS] [--limit N]`
+/// How long an open order's last confirmed status stays fresh for `--stale`.
+const STALE_ORDER_MS: i64 = 3_600_000;
+
+/// `atlas history orders [--protocol hl] [--coin COIN] [--status STATUS] [--stale] [--from DATE] [--to DATE] [--limit N]`
+#[allow(clippy::too_many_arguments)]
 pub fn run_orders(
     protocol: Option<&str>,
     coin: Option<&str>,
     status: Option<&str>,
+    stale: bool,
+    from: Option<&str>,
+    to: Option<&str>,
     limit: usize,
     fmt: OutputFormat,
 ) -> Result<()> {
     let db = AtlasDb::open()?;
 
+    let DateRange { from_ms, to_ms } = parse_date_range(from, to)?;
     let filter = OrderFilter {
         protocol: protocol.map(normalize_protocol),
         coin: coin.map(|c| c.to_uppercase()),
-        status: status.map(|s| s.to_lowercase()),
+        status: status.map(normalize_status),
+        from_ms,
+        to_ms,
+        stale_before_ms: stale.then(|| chrono::Utc::now().timestamp_millis() - STALE_ORDER_MS),
         limit: Some(limit),
     };
 
@@ -89,6 +137,9 @@ pub fn run_orders(
             status: o.status.clone(),
             order_type: o.order_type.clone(),
             time: format_ms(o.timestamp_ms),
+            filled: o.filled_sz.clone(),
+            avg_price: o.avg_px.clone(),
+            status_updated: o.status_updated_at.map(format_ms),
         })
         .collect();
 
@@ -111,8 +162,7 @@ pub fn run_pnl(
 ) -> Result<()> {
     let db = AtlasDb::open()?;
 
-    let from_ms = from.map(parse_date_to_ms).transpose()?;
-    let to_ms = to.map(parse_date_to_ms).transpose()?;
+    let DateRange { from_ms, to_ms } = parse_date_range(from, to)?;
 
     let filter = FillFilter {
         protocol: protocol.map(normalize_protocol),
@@ -180,22 +230,307 @@ pub fn run_pnl(
         loss_count,
         win_rate,
         by_coin: coin_rows,
+        net_deposits: if includes_transfers(protocol, coin) {
+            let transfers = db.query_transfers(&TransferFilter {
+                from_ms,
+                to_ms,
+                ..Default::default()
+            })?;
+            Some(net_deposits(&transfers).to_string())
+        } else {
+            None
+        },
+        swaps: if includes_swaps(protocol) {
+            swap_pnl_summary(
+                &db,
+                &SwapFilter {
+                    token: coin.map(str::to_string),
+                    from_ms,
+                    to_ms,
+                    ..Default::default()
+                },
+            )?
+        } else {
+            None
+        },
+    };
+
+    render(fmt, &output)?;
+    Ok(())
+}
+
+/// `atlas history swaps [--status executed|quoted] [--chain CHAIN] [--coin TOKEN] [--from DATE] [--to DATE] [--limit N]`
+pub fn run_swaps(
+    status: Option<&str>,
+    chain: Option<&str>,
+    coin: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+    limit: usize,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let db = AtlasDb::open()?;
+
+    let status = status.map(str::to_lowercase);
+    if let Some(s) = status.as_deref() {
+        if s != "executed" && s != "quoted" {
+            anyhow::bail!("Invalid status '{s}': expected executed or quoted");
+        }
+    }
+    let range = parse_date_range(from, to)?;
+    let filter = SwapFilter {
+        status,
+        chain: chain.map(str::to_lowercase),
+        token: coin.map(str::to_string),
+        from_ms: range.from_ms,
+        to_ms: range.to_ms,
+        limit: Some(limit),
+    };
+
+    let swaps: Vec<SwapHistoryRow> = db
+        .query_swaps(&filter)?
+        .iter()
+        .map(|s| SwapHistoryRow {
+            status: s.status.clone(),
+            chain: s.chain.clone(),
+            sell_token: s.sell_token.clone(),
+            sell_symbol: s.sell_symbol.clone(),
+            buy_token: s.buy_token.clone(),
+            buy_symbol: s.buy_symbol.clone(),
+            sell_amount: token_amount(s.sell_tokens(), &s.sell_amount),
+            buy_amount: token_amount(s.buy_tokens(), &s.buy_amount),
+            price: s.price.clone(),
+            sell_value_usd: s.sell_value_usd.clone(),
+            gas_cost_usd: s.gas_cost_usd.clone(),
+            tx_hash: s.tx_hash.clone(),
+            time: format_ms(s.time_ms),
+        })
+        .collect();
+
+    let total = swaps.len();
+    render(fmt, &SwapHistoryOutput { swaps, total })?;
+    Ok(())
+}
+
+/// `atlas history transfers [--type deposit|withdraw|internal] [--from DATE] [--to DATE] [--limit N]`
+pub fn run_transfers(
+    kind: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+    limit: usize,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let db = AtlasDb::open()?;
+
+    let range = parse_date_range(from, to)?;
+    let filter = TransferFilter {
+        kind: kind.map(str::to_string),
+        from_ms: range.from_ms,
+        to_ms: range.to_ms,
+        limit: Some(limit),
     };
+    let transfers = db.query_transfers(&filter)?;
+
+    let rows: Vec<TransferRow> = transfers
+        .iter()
+        .map(|t| TransferRow {
+            kind: t.kind.clone(),
+            ledger_type: t.ledger_type.clone(),
+            direction: t.direction.clone(),
+            token: t.token.clone(),
+            amount: t.amount.clone(),
+            usd_value: t.usd_value.clone(),
+            fee: t.fee.clone(),
+            counterparty: t.counterparty.clone(),
+            hash: t.hash.clone(),
+            time: format_ms(t.time_ms),
+        })
+        .collect();
 
+    let output = TransferHistoryOutput {
+        total: rows.len(),
+        transfers: rows,
+        net_deposits: net_deposits(&transfers).to_string(),
+    };
     render(fmt, &output)?;
     Ok(())
 }
 
+/// USD moved into the account minus USD moved out.
+fn net_deposits(transfers: &[DbTransfer]) -> Decimal {
+    transfers
+        .iter()
+        .map(DbTransfer::net_usd)
+        .sum::<Decimal>()
+        .normalize()
+}
+
+/// Whether a PnL summary can be squared with the Hyperliquid ledger:
+/// transfers are account-wide, so not per coin or for other protocols.
+fn includes_transfers(protocol: Option<&str>, coin: Option<&str>) -> bool {
+    coin.is_none() && protocol.map_or(true, |p| normalize_protocol(p) == "hyperliquid")
+}
+
+/// Whether a `--protocol` filter takes in recorded 0x swaps.
+pub(crate) fn includes_swaps(protocol: Option<&str>) -> bool {
+    protocol.map_or(true, |p| normalize_protocol(p) == "0x")
+}
+
+/// Token units when decimals are known, else the raw base units.
+fn token_amount(tokens: Option<Decimal>, raw: &str) -> String {
+    tokens.map_or_else(|| raw.to_string(), |d| d.normalize().to_string())
+}
+
+/// An executed swap as a trade: `USDC→WETH`, sized in the sell token and
+/// priced in buy tokens per sell token. The fee is the gas cost in USD.
+pub(crate) fn swap_trade_row(swap: &DbSwap) -> TradeHistoryRow {
+    TradeHistoryRow {
+        protocol: "0x".into(),
+        coin: swap.pair(),
+        side: "swap".into(),
+        size: token_amount(swap.sell_tokens(), &swap.sell_amount),
+        price: swap.price.clone().unwrap_or_default(),
+        pnl: "0".into(),
+        fee: swap.gas_cost_usd.clone().unwrap_or_else(|| "0".into()),
+        time: format_ms(swap.time_ms),
+        hash: swap.tx_hash.clone().unwrap_or_default(),
+        has_note: false,
+        notes: Vec::new(),
+    }
+}
+
+/// Unrealized PnL of tokens bought through executed swaps; `None` when no
+/// swap matches.
+pub(crate) fn swap_pnl_summary(
+    db: &AtlasDb,
+    filter: &SwapFilter,
+) -> Result<Option<SwapPnlSummary>> {
+    let (positions, skipped_swaps) = db.unrealized_swap_pnl(filter)?;
+    if positions.is_empty() && skipped_swaps == 0 {
+        return Ok(None);
+    }
+
+    let (mut cost, mut value, mut unpriced) = (Decimal::ZERO, Decimal::ZERO, 0usize);
+    let usd = |d: Decimal| d.round_dp(2).to_string();
+    let rows = positions
+        .iter()
+        .map(|p| {
+            match p.value_usd() {
+                Some(v) => {
+                    cost += p.cost_usd;
+                    value += v;
+                }
+                None => unpriced += 1,
+            }
+            SwapPnlRow {
+                chain: p.chain.clone(),
+                token: p.token.clone(),
+                symbol: p.symbol.clone(),
+                amount: p.amount.normalize().to_string(),
+                cost_usd: usd(p.cost_usd),
+                gas_usd: usd(p.gas_usd),
+                price_usd: p.price_usd.map(|d| d.normalize().to_string()),
+                value_usd: p.value_usd().map(usd),
+                unrealized_pnl: p.unrealized_pnl().map(usd),
+                priced_at: p.priced_ms.map(format_ms),
+                swaps: p.swaps,
+            }
+        })
+        .collect();
+
+    Ok(Some(SwapPnlSummary {
+        positions: rows,
+        cost_usd: usd(cost),
+        value_usd: usd(value),
+        unrealized_pnl: usd(value - cost),
+        unpriced,
+        skipped_swaps,
+    }))
+}
+
+/// `atlas history import <FILE> [--protocol custom] [--format generic|hyperliquid-ui] [--dry-run]`
+///
+/// Valid rows are cached as fills under `protocol` even when others fail;
+/// `--dry-run` only reports what would be imported.
+pub fn run_import(
+    file: &str,
+    protocol: &str,
+    format: &str,
+    dry_run: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let protocol = normalize_protocol(protocol);
+    let label_ok = |b: u8| b.is_ascii_alphanumeric() || b == b'-' || b == b'_';
+    if protocol.is_empty() || !protocol.bytes().all(label_ok) {
+        anyhow::bail!("Invalid --protocol '{protocol}': use letters, digits, '-' or '_'");
+    }
+    let text = std::fs::read_to_string(file).with_context(|| format!("Failed to read {file}"))?;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let parsed = trade_import::parse_trades(&text, format, &protocol, time_zone()?, now_ms)?;
+
+    let db = AtlasDb::open()?;
+    let imported = if dry_run {
+        let hashes: Vec<String> = parsed.fills.iter().map(|f| f.hash.clone()).collect();
+        parsed.fills.len() - db.existing_fill_hashes(&hashes)?.len()
+    } else {
+        db.insert_fills(&parsed.fills)?
+    };
+
+    render(
+        fmt,
+        &ImportOutput {
+            file: file.to_string(),
+            protocol,
+            format: format.to_string(),
+            dry_run,
+            imported,
+            skipped: parsed.fills.len() - imported + parsed.duplicates,
+            failed: parsed.errors.len(),
+            errors: parsed
+                .errors
+                .into_iter()
+                .map(|e| ImportErrorRow {
+                    line: e.line,
+                    error: e.message,
+                })
+                .collect(),
+        },
+    )?;
+    Ok(())
+}
+
+/// `atlas history fills [--protocol <name|all>] [--limit N]` — recent fills
+/// straight from the exchanges, newest first. Modules are queried
+/// concurrently; one that fails becomes a warning.
+pub async fn run_fills(protocol: Option<&str>, limit: usize, fmt: OutputFormat) -> Result<()> {
+    let orch = crate::factory::from_active_profile().await?;
+    let perps = select_perps(&orch, protocol)?;
+    let results = fan_out(&perps, MODULE_TIMEOUT, |m| async move { m.fills().await }).await;
+    let mut gathered = Gathered::from_results(results);
+
+    gathered
+        .items
+        .sort_by(|a, b| b.timestamp_ms.cmp(&a.timestamp_ms));
+    gathered.items.truncate(limit);
+
+    render(fmt, &fills_output(&gathered.items, gathered.warnings))?;
+    Ok(())
+}
+
 /// `atlas history sync [--full]`
-pub async fn run_sync(_full: bool, fmt: OutputFormat) -> Result<()> {
+pub async fn run_sync(full: bool, fmt: OutputFormat) -> Result<()> {
     let engine = Engine::from_active_profile().await?;
     let db = AtlasDb::open()?;
 
-    let (fills, orders) = engine.sync_all(&db).await?;
+    let stats = engine.sync_all(&db, full).await?;
 
     let output = SyncOutput {
-        fills_synced: fills,
-        orders_synced: orders,
+        fills_synced: stats.fills,
+        orders_synced: stats.orders.inserted,
+        orders_updated: stats.orders.updated,
+        transfers_synced: stats.transfers,
+        funding_synced: stats.funding,
+        full,
         status: "complete".to_string(),
     };
 
@@ -0,0 +1,353 @@
+//! `atlas init` — first-run setup wizard.
+//!
+//! Walks through the steps in [`atlas_core::setup`] that the config still
+//! lacks: network, wallet profile, modules, trading defaults and the
+//! backend API key. Any step can be skipped and comes up again on the next
+//! run. Answers are saved as each step completes, so an interrupted wizard
+//! resumes where it stopped. Ends with the `atlas doctor` checks.
+//!
+//! `--non-interactive --from <answers.json>` applies the same steps from a
+//! file for scripted installs; see [`SetupAnswers`].
+
+use std::io::IsTerminal;
+
+use anyhow::{Context, Result};
+use atlas_core::output::{print_confirmation, render, InitOutput, OutputFormat};
+use atlas_core::prompt::{confirm, prompt_default, prompt_secret};
+use atlas_core::setup::{self, ProfileAction, ProfileAnswer, SetupAnswers, SetupStep};
+use atlas_core::workspace::{load_config, load_envs, save_config};
+use atlas_core::AuthManager;
+
+use super::doctor;
+
+/// Shown once setup is done.
+const NEXT_COMMANDS: &[(&str, &str)] = &[
+    ("atlas status", "balances, positions and open orders"),
+    ("atlas market hl top", "most active Hyperliquid markets"),
+    (
+        "atlas hl sync",
+        "cache fills and orders for history and PnL",
+    ),
+    ("atlas configure show", "everything set so far"),
+    ("atlas init", "answer any step you skipped"),
+];
+
+/// `atlas init [--non-interactive --from <answers.json>]`
+pub async fn run(non_interactive: bool, from: Option<&str>, fmt: OutputFormat) -> Result<()> {
+    if non_interactive {
+        let path = from.context("--non-interactive needs --from <answers.json>")?;
+        return run_answers(path, fmt).await;
+    }
+    let interactive = fmt == OutputFormat::Table
+        && std::io::stdin().is_terminal()
+        && std::io::stdout().is_terminal();
+    if !interactive {
+        anyhow::bail!(
+            "atlas init needs an interactive terminal; \
+             use --non-interactive --from <answers.json> in scripts"
+        );
+    }
+
+    let pending = setup::pending_steps(&load_config()?, profile_ready()?);
+    if pending.is_empty() {
+        println!("Atlas is already set up.");
+    } else {
+        println!("Atlas setup: {} step(s) to go.", pending.len());
+        println!("Answer n to skip a step; run `atlas init` again to come back to it.");
+    }
+    for (i, step) in pending.iter().enumerate() {
+        println!();
+        println!("── {}/{} {} ──", i + 1, pending.len(), step.title());
+        println!("{}", explain(*step));
+        if !confirm(&format!("Set up {}?", step.title().to_lowercase()), true)? {
+            println!("Skipped.");
+            continue;
+        }
+        match step {
+            SetupStep::Network => network_step()?,
+            SetupStep::Profile => profile_step(fmt)?,
+            SetupStep::Modules => modules_step()?,
+            SetupStep::Trading => trading_step()?,
+            SetupStep::ApiKey => api_key_step()?,
+        }
+    }
+
+    println!();
+    let checks = doctor::checks(false, fmt).await;
+    doctor::print_table(&checks, false);
+    println!();
+    println!("Try these commands:");
+    for (command, what) in NEXT_COMMANDS {
+        println!("  {command:<22} {what}");
+    }
+    Ok(())
+}
+
+fn explain(step: SetupStep) -> &'static str {
+    match step {
+        SetupStep::Network => {
+            "Mainnet trades real funds. Testnet uses free test USDC from the \
+             Hyperliquid faucet and is the place to try things out.\n\
+             Each network keeps its own settings; the next steps apply to the one picked here."
+        }
+        SetupStep::Profile => {
+            "A profile is a wallet whose private key lives in the OS keyring, never in a file.\n\
+             Create a new one, import a key you already have, or switch to an existing profile."
+        }
+        SetupStep::Modules => {
+            "Modules are the protocols Atlas talks to. Disabled modules' commands are refused."
+        }
+        SetupStep::Trading => {
+            "Defaults for Hyperliquid orders; per-coin presets and flags on each order override them."
+        }
+        SetupStep::ApiKey => {
+            "The Atlas backend proxies 0x swaps, EVM RPC and market data; without a key \
+             those calls fail.\n\
+             Get one from the Atlas dashboard under Settings."
+        }
+    }
+}
+
+/// Ask until the answer parses; an empty answer takes `default`.
+fn ask<T>(
+    label: &str,
+    default: &str,
+    parse: impl Fn(&str) -> atlas_core::error::AtlasResult<T>,
+) -> Result<T> {
+    loop {
+        match parse(&prompt_default(label, default)?) {
+            Ok(value) => return Ok(value),
+            Err(e) => println!("  {e}"),
+        }
+    }
+}
+
+/// Save `config` with `step` recorded as answered.
+fn finish(mut config: atlas_core::config::AppConfig, step: SetupStep) -> Result<()> {
+    setup::mark_done(&mut config, step);
+    save_config(&config)
+}
+
+/// The active profile exists and its key is in the keyring.
+fn profile_ready() -> Result<bool> {
+    let config = load_config()?;
+    let name = &config.system.active_profile;
+    Ok(AuthManager::load_store_pub()?.exists(name) && AuthManager::has_key(name)?)
+}
+
+// ── Steps ───────────────────────────────────────────────────────────
+
+fn network_step() -> Result<()> {
+    let current = load_config()?.modules.hyperliquid.config.network;
+    let network = ask("Network (mainnet/testnet)", &current, setup::parse_network)?;
+    switch_network(network)?;
+    print_confirmation(&format!("network = {network}"));
+    Ok(())
+}
+
+/// Switch to the environment named after `network`, or set the network
+/// directly on configs without one, and record the step.
+fn switch_network(network: &str) -> Result<()> {
+    let has_env = load_envs()?.iter().any(|(name, _)| name == network);
+    let mut config = load_config()?;
+    if has_env {
+        config.system.active_env = Some(network.to_string());
+    } else {
+        config.modules.hyperliquid.config.network = network.to_string();
+    }
+    save_config(&config)?;
+    // Reload so the step is recorded in the environment switched to
+    finish(load_config()?, SetupStep::Network)
+}
+
+fn profile_step(fmt: OutputFormat) -> Result<()> {
+    let store = AuthManager::load_store_pub()?;
+    if !store.wallets.is_empty() {
+        let names: Vec<&str> = store.wallets.iter().map(|w| w.name.as_str()).collect();
+        println!("Existing profiles: {}", names.join(", "));
+    }
+    let default_action = if store.wallets.is_empty() {
+        "generate"
+    } else {
+        "use"
+    };
+    let action = ask(
+        "New wallet, import a key, or use an existing profile? (generate/import/use)",
+        default_action,
+        |s| {
+            serde_json::from_value::<ProfileAction>(s.trim().to_lowercase().into()).map_err(|_| {
+                atlas_core::error::AtlasError::InvalidConfig(format!(
+                    "'{s}' must be generate, import or use"
+                ))
+            })
+        },
+    )?;
+
+    let active = load_config()?.system.active_profile;
+    let name = loop {
+        let default = match action {
+            ProfileAction::Use => active.clone(),
+            _ if store.exists("main") => String::new(),
+            _ => "main".to_string(),
+        };
+        let name = prompt_default("Profile name", &default)?.trim().to_string();
+        let exists = store.exists(&name);
+        match action {
+            _ if name.is_empty() => println!("  A profile name is required."),
+            ProfileAction::Use if !exists => println!("  No profile named '{name}'."),
+            ProfileAction::Generate | ProfileAction::Import if exists => {
+                println!("  Profile '{name}' already exists; pick another name or choose use.")
+            }
+            _ => break name,
+        }
+    };
+
+    match action {
+        // Prints the new key once, with the backup warning
+        ProfileAction::Generate => super::auth::generate_wallet(&name, fmt)?,
+        ProfileAction::Import => {
+            let key = prompt_secret("Private key (hex, with or without 0x)")?;
+            if key.is_empty() {
+                anyhow::bail!("No key provided");
+            }
+            let (_, address) = AuthManager::import_wallet(&name, &key)?;
+            print_confirmation(&format!("Imported profile '{name}' → {address}"));
+        }
+        ProfileAction::Use => {}
+    }
+    AuthManager::switch_profile(&name)?;
+    print_confirmation(&format!("Active profile: {name}"));
+    finish(load_config()?, SetupStep::Profile)
+}
+
+fn modules_step() -> Result<()> {
+    let mut config = load_config()?;
+    let descriptions = [
+        ("hyperliquid", "perps and spot on Hyperliquid"),
+        ("zero_x", "token swaps via the 0x aggregator"),
+        ("morpho", "Morpho lending markets"),
+        ("paper", "simulated trading, no real funds"),
+    ];
+    for ((name, enabled), (_, what)) in setup::module_states(&config).into_iter().zip(descriptions)
+    {
+        let on = confirm(&format!("Enable {name} ({what})?"), enabled)?;
+        setup::set_module(&mut config, name, on)?;
+    }
+    finish(config, SetupStep::Modules)?;
+    print_confirmation("modules saved");
+    Ok(())
+}
+
+fn trading_step() -> Result<()> {
+    let mut config = load_config()?;
+    let hl = config.modules.hyperliquid.config.clone();
+
+    println!(
+        "Leverage multiplies both gains and losses; liquidation comes closer as it rises (1–{}).",
+        setup::MAX_LEVERAGE
+    );
+    let leverage = ask("Default leverage", &hl.default_leverage.to_string(), |s| {
+        setup::parse_leverage(s, &config)
+    })?;
+    println!("Slippage caps how far from the mid a market order may fill, e.g. 0.05 or 5%.");
+    let slippage = ask("Default slippage", &hl.default_slippage.to_string(), |s| {
+        setup::parse_slippage(s, &config)
+    })?;
+    println!("Size mode decides what a bare number means: USDC margin, asset units or lots.");
+    let size_mode = ask(
+        "Size mode (usdc/units/lots)",
+        &hl.default_size_mode.to_string(),
+        setup::parse_size_mode,
+    )?;
+
+    let hl = &mut config.modules.hyperliquid.config;
+    hl.default_leverage = leverage;
+    hl.default_slippage = slippage;
+    hl.default_size_mode = size_mode;
+    finish(config, SetupStep::Trading)?;
+    print_confirmation(&format!(
+        "trading defaults: {leverage}x, {:.2}% slippage, size in {size_mode}",
+        slippage * 100.0
+    ));
+    Ok(())
+}
+
+fn api_key_step() -> Result<()> {
+    let key = prompt_secret("Backend API key")?;
+    if key.is_empty() {
+        println!("Skipped.");
+        return Ok(());
+    }
+    let mut config = load_config()?;
+    config.system.api_key = Some(key);
+    finish(config, SetupStep::ApiKey)?;
+    print_confirmation("api_key saved");
+    Ok(())
+}
+
+// ── Answers file ────────────────────────────────────────────────────
+
+async fn run_answers(path: &str, fmt: OutputFormat) -> Result<()> {
+    let raw = std::fs::read_to_string(path).with_context(|| format!("Failed to read {path}"))?;
+    let answers = SetupAnswers::parse(&raw, &load_config()?)?;
+    let mut applied = Vec::new();
+
+    if let Some(network) = &answers.network {
+        switch_network(setup::parse_network(network)?)?;
+        applied.push(SetupStep::Network);
+    }
+    if let Some(profile) = &answers.profile {
+        apply_profile(profile)?;
+        applied.push(SetupStep::Profile);
+    }
+    // After the network switch: the rest is stored in that environment
+    let mut config = load_config()?;
+    applied.extend(answers.apply(&mut config)?);
+    save_config(&config)?;
+
+    let store = AuthManager::load_store_pub()?;
+    let profile = config.system.active_profile.clone();
+    let output = InitOutput {
+        address: store
+            .wallets
+            .iter()
+            .find(|w| w.name == profile)
+            .map(|w| w.address.clone()),
+        network: config.modules.hyperliquid.config.network.clone(),
+        applied: applied.iter().map(|s| s.as_str().to_string()).collect(),
+        pending: setup::pending_steps(&config, profile_ready()?)
+            .into_iter()
+            .map(|s| s.as_str().to_string())
+            .collect(),
+        doctor: doctor::checks(false, fmt).await,
+        profile,
+    };
+    render(fmt, &output)
+}
+
+/// Create, import or switch to the answered profile. An existing profile
+/// of that name is kept as is, so re-running the same file is harmless.
+fn apply_profile(answer: &ProfileAnswer) -> Result<()> {
+    let name = answer.name.trim();
+    let exists = AuthManager::load_store_pub()?.exists(name);
+    match answer.action {
+        // The key is not printed; `atlas profile export` shows it later
+        ProfileAction::Generate if !exists => {
+            AuthManager::create_new_wallet(name)?;
+        }
+        ProfileAction::Import if !exists => {
+            let key = match (&answer.key_env, &answer.key_file) {
+                (Some(var), _) => std::env::var(var)
+                    .with_context(|| format!("profile.key_env: ${var} is not set"))?,
+                (None, Some(file)) => std::fs::read_to_string(file).with_context(|| {
+                    format!("profile.key_file: failed to read {}", file.display())
+                })?,
+                (None, None) => unreachable!("checked by SetupAnswers::parse"),
+            };
+            AuthManager::import_wallet(name, key.trim())?;
+        }
+        _ => {}
+    }
+    AuthManager::switch_profile(name)?;
+    finish(load_config()?, SetupStep::Profile)
+}
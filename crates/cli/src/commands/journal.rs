@@ -0,0 +1,95 @@
+//! `atlas journal` — notes and tags attached to fills in the local DB.
+
+use anyhow::{Context, Result};
+use atlas_core::date_range::now_ms;
+use atlas_core::db::{AtlasDb, DbJournalEntry, JournalFilter, JournalTarget};
+use atlas_core::output::{render, JournalAddOutput, JournalOutput, JournalRow, OutputFormat};
+
+use super::helpers::{format_ms, parse_date_range, parse_date_to_ms};
+
+/// `atlas journal add <fill-hash|oid> --note TEXT [--tags a,b]`
+/// or `atlas journal add --coin COIN --at TIME --note TEXT`
+pub fn add(
+    target: Option<&str>,
+    note: &str,
+    tags: &[String],
+    coin: Option<&str>,
+    at: Option<&str>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    if note.trim().is_empty() {
+        anyhow::bail!("Note is empty");
+    }
+    let target = match (target, coin, at) {
+        (Some(t), _, _) => parse_target(t)?,
+        (None, Some(coin), Some(at)) => JournalTarget::CoinTime {
+            coin: coin.to_uppercase(),
+            time_ms: parse_date_to_ms(at)?,
+        },
+        _ => anyhow::bail!("Give a fill hash or order id, or --coin with --at"),
+    };
+
+    let db = AtlasDb::open()?;
+    let id = db.add_journal_entry(&target, note.trim(), tags, now_ms())?;
+    let entry = db
+        .journal_entry(id)?
+        .context("Journal entry not found after insert")?;
+
+    render(
+        fmt,
+        &JournalAddOutput {
+            entry: journal_row(&entry),
+        },
+    )?;
+    Ok(())
+}
+
+/// `atlas journal list [--coin] [--tag] [--from] [--to] [--search] [--limit]`
+pub fn list(
+    coin: Option<&str>,
+    tag: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+    search: Option<&str>,
+    limit: usize,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let range = parse_date_range(from, to)?;
+    let db = AtlasDb::open()?;
+    let filter = JournalFilter {
+        coin: coin.map(str::to_uppercase),
+        tag: tag.map(str::to_string),
+        from_ms: range.from_ms,
+        to_ms: range.to_ms,
+        search: search.map(str::to_string),
+        limit: Some(limit),
+    };
+
+    let entries: Vec<JournalRow> = db.query_journal(&filter)?.iter().map(journal_row).collect();
+    let total = entries.len();
+    render(fmt, &JournalOutput { entries, total })?;
+    Ok(())
+}
+
+/// `0x…` is a fill hash, a plain number an order id.
+fn parse_target(raw: &str) -> Result<JournalTarget> {
+    if raw.starts_with("0x") {
+        return Ok(JournalTarget::Fill(raw.to_string()));
+    }
+    raw.parse::<i64>()
+        .map(JournalTarget::Order)
+        .map_err(|_| anyhow::anyhow!("Expected a fill hash (0x…) or numeric order id, got '{raw}'"))
+}
+
+fn journal_row(e: &DbJournalEntry) -> JournalRow {
+    JournalRow {
+        id: e.id,
+        coin: e.coin.clone(),
+        time: format_ms(e.time_ms),
+        status: if e.is_linked() { "linked" } else { "pending" }.to_string(),
+        fill_hash: e.fill_hash.clone(),
+        oid: e.oid,
+        note: e.note.clone(),
+        tags: e.tags.clone(),
+    }
+}
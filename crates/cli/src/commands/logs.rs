@@ -0,0 +1,84 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use atlas_core::logs::{self, LogFilter, LogLevel, LOG_FILE};
+use atlas_core::output::{render_context, OutputFormat};
+
+/// How often `--follow` checks the log file for new lines.
+const FOLLOW_POLL: Duration = Duration::from_millis(500);
+
+/// `atlas logs [--tail N] [--follow] [--level L] [--grep TEXT]`
+///
+/// Table mode prints one readable line per record; JSON modes pass the
+/// stored records through unchanged (one object per line).
+pub async fn run(
+    tail: usize,
+    follow: bool,
+    level: Option<LogLevel>,
+    grep: Option<String>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let filter = LogFilter { level, grep };
+    let dir = logs::logs_dir()?;
+
+    let lines = logs::tail(&dir, &filter, tail)
+        .with_context(|| format!("Failed to read logs in {}", dir.display()))?;
+    if lines.is_empty() && !follow && fmt == OutputFormat::Table {
+        println!("No log entries in {}", dir.display());
+        return Ok(());
+    }
+    for line in &lines {
+        print_line(line, fmt);
+    }
+
+    if follow {
+        follow_log(&dir.join(LOG_FILE), &filter, fmt).await?;
+    }
+    Ok(())
+}
+
+fn print_line(line: &str, fmt: OutputFormat) {
+    match fmt {
+        OutputFormat::Json => println!("{line}"),
+        OutputFormat::JsonPretty => match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(v) => println!("{}", serde_json::to_string_pretty(&v).unwrap_or_default()),
+            Err(_) => println!("{line}"),
+        },
+        OutputFormat::Table => println!("{}", render_context().text(&logs::format_line(line))),
+    }
+}
+
+/// Poll `atlas.log` for appended lines until interrupted. A shrinking file
+/// means it was rotated, so reading restarts from the top of the new one.
+async fn follow_log(path: &std::path::Path, filter: &LogFilter, fmt: OutputFormat) -> Result<()> {
+    let mut offset = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let mut partial = String::new();
+    loop {
+        tokio::time::sleep(FOLLOW_POLL).await;
+        let Ok(mut file) = std::fs::File::open(path) else {
+            continue;
+        };
+        let len = file.metadata()?.len();
+        if len < offset {
+            offset = 0;
+            partial.clear();
+        }
+        if len == offset {
+            continue;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut chunk = String::new();
+        file.read_to_string(&mut chunk)?;
+        offset = len;
+
+        partial.push_str(&chunk);
+        while let Some(end) = partial.find('\n') {
+            let line: String = partial.drain(..=end).collect();
+            let line = line.trim_end();
+            if !line.is_empty() && filter.matches(line) {
+                print_line(line, fmt);
+            }
+        }
+    }
+}
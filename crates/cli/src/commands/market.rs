@@ -1,9 +1,20 @@
 use anyhow::Result;
-use atlas_core::fmt::format_timestamp_ms;
+use atlas_core::batch::tickers_for;
+use atlas_core::cross::cross_rate;
+use atlas_core::db::AtlasDb;
+use atlas_core::error::{AtlasError, AtlasResult};
+use atlas_core::fmt::{format_timestamp_ms, MAX_SPOT_PRICE_DECIMALS};
+use atlas_core::market_rank::{self, TopSort};
 use atlas_core::output::OutputFormat;
 use atlas_core::output::*;
+use atlas_core::spread_profile;
+use atlas_core::symbols::{fuzzy_score, resolve_spot, SymbolResolver};
+use atlas_core::traits::PerpModule;
+use atlas_core::types::{AssetContext, SpotMarket, Ticker};
 use rust_decimal::prelude::*;
 
+use super::helpers::{format_ms, record_spreads};
+
 /// Render a PriceOutput (table or JSON).
 fn render_prices(output: &PriceOutput, fmt: OutputFormat) {
     match fmt {
@@ -16,10 +27,17 @@ fn render_prices(output: &PriceOutput, fmt: OutputFormat) {
             println!("{}", serde_json::to_string_pretty(&envelope).unwrap());
         }
         OutputFormat::Table => {
-            println!("{:<12} {:>15}", "COIN", "MID PRICE");
-            println!("{}", "─".repeat(28));
+            let nf = render_context().numbers;
+            println!("{:<14} {:>15}", "COIN", "MID PRICE");
+            println!("{}", "─".repeat(30));
             for p in &output.prices {
-                println!("{:<12} {:>15}", p.coin, p.mid_price);
+                let name = p.pair.as_deref().unwrap_or(&p.coin);
+                let max_dp = p.pair.as_ref().map(|_| MAX_SPOT_PRICE_DECIMALS);
+                let price = nf.price_str(&p.mid_price, max_dp);
+                match &p.error {
+                    Some(err) => println!("{:<14} {:>15}  ({err})", name, price),
+                    None => println!("{:<14} {:>15}", name, price),
+                }
             }
         }
     }
@@ -38,16 +56,42 @@ fn render_markets(output: &MarketsOutput, fmt: OutputFormat) {
         }
         OutputFormat::Table => {
             println!("Market type: {}\n", output.market_type.to_uppercase());
-            println!(
-                "{:<15} {:>6} {:>10} {:>12}",
-                "NAME", "INDEX", "MAX LEV", "SZ DECIMALS"
-            );
-            println!("{}", "─".repeat(45));
-            for m in &output.markets {
+            if output.market_type == "spot" {
+                let nf = render_context().numbers;
                 println!(
-                    "{:<15} {:>6} {:>10}x {:>12}",
-                    m.name, m.index, m.max_leverage, m.sz_decimals
+                    "{:<15} {:>6} {:<10} {:>8} {:>8} {:>15}",
+                    "PAIR", "INDEX", "KEY", "SZ DEC", "WEI DEC", "MID PRICE"
                 );
+                println!("{}", "─".repeat(67));
+                for m in &output.markets {
+                    println!(
+                        "{:<15} {:>6} {:<10} {:>8} {:>8} {:>15}",
+                        m.name,
+                        m.index,
+                        m.key.as_deref().unwrap_or("—"),
+                        m.sz_decimals,
+                        m.wei_decimals.map(|d| d.to_string()).unwrap_or_default(),
+                        m.mid_price.as_deref().map_or("—".into(), |px| nf.price_str(
+                            px,
+                            Some(
+                                MAX_SPOT_PRICE_DECIMALS.saturating_sub(m.sz_decimals.max(0) as u32)
+                            )
+                        )),
+                    );
+                }
+            } else {
+                println!(
+                    "{:<15} {:>6} {:>10} {:>12}",
+                    "NAME", "INDEX", "MAX LEV", "SZ DECIMALS"
+                );
+                println!("{}", "─".repeat(45));
+                for m in &output.markets {
+                    let max_lev = m.max_leverage.map(|l| format!("{l}x")).unwrap_or_default();
+                    println!(
+                        "{:<15} {:>6} {:>11} {:>12}",
+                        m.name, m.index, max_lev, m.sz_decimals
+                    );
+                }
             }
             println!("\nTotal: {} markets", output.markets.len());
         }
@@ -110,33 +154,122 @@ fn render_funding(output: &FundingOutput, fmt: OutputFormat) {
     }
 }
 
-/// `atlas price <COINS...>` or `atlas price --all`
-pub async fn price(coins: &[String], all: bool, fmt: OutputFormat) -> Result<()> {
+/// `atlas price <COINS...> [--in QUOTE]` or `atlas price --all [--in QUOTE]`;
+/// `--spot` prices spot pairs instead.
+pub async fn price(
+    coins: &[String],
+    all: bool,
+    spot: bool,
+    quote: Option<&str>,
+    fmt: OutputFormat,
+) -> Result<()> {
     let orch = crate::factory::readonly().await?;
     let perp = orch.perp(None)?;
 
-    let tickers = if all || coins.is_empty() {
+    if spot {
+        let markets = perp
+            .spot_markets()
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        let prices = if all || coins.is_empty() {
+            markets
+                .iter()
+                .filter(|m| m.mid_price.is_some())
+                .map(spot_price_row)
+                .collect()
+        } else {
+            coins
+                .iter()
+                .map(|coin| match resolve_spot(&markets, coin) {
+                    Ok(m) if m.mid_price.is_some() => spot_price_row(m),
+                    Ok(m) => PriceRow {
+                        error: Some(format!("no mid price for {}", m.pair)),
+                        ..spot_price_row(m)
+                    },
+                    Err(e) => PriceRow {
+                        coin: coin.clone(),
+                        mid_price: "—".into(),
+                        protocol: "hyperliquid".into(),
+                        pair: None,
+                        key: None,
+                        error: Some(e.to_string()),
+                    },
+                })
+                .collect()
+        };
+        render_prices(&PriceOutput { prices }, fmt);
+        return Ok(());
+    }
+
+    let quote = match quote {
+        Some(q) => Some(quote_mid(perp.as_ref(), q).await?),
+        None => None,
+    };
+
+    let tickers: Vec<(String, AtlasResult<Ticker>)> = if all || coins.is_empty() {
         perp.all_tickers()
             .await
             .map_err(|e| anyhow::anyhow!("{e}"))?
+            .into_iter()
+            .map(|t| (t.symbol.clone(), Ok(t)))
+            .collect()
     } else {
-        let mut result = Vec::new();
-        for c in coins {
-            let t = perp
-                .ticker(&c.to_uppercase())
-                .await
-                .map_err(|e| anyhow::anyhow!("{e}"))?;
-            result.push(t);
-        }
-        result
+        // One bulk fetch for every coin; unknown coins become error rows
+        tickers_for(perp.as_ref(), coins).await
     };
+    let fetched: Vec<Ticker> = tickers
+        .iter()
+        .filter_map(|(_, t)| t.as_ref().ok().cloned())
+        .collect();
+    record_spreads(&fetched);
+
+    if let Some((quote, quote_usd)) = quote {
+        let prices = tickers
+            .into_iter()
+            .map(|(coin, res)| match res {
+                Ok(t) => CrossPriceRow {
+                    symbol: t.symbol,
+                    price: cross_rate(t.mid_price, quote_usd).map(|p| p.to_string()),
+                    base_usd: Some(t.mid_price.to_string()),
+                    quote_usd: quote_usd.to_string(),
+                    error: None,
+                },
+                Err(e) => CrossPriceRow {
+                    symbol: coin,
+                    price: None,
+                    base_usd: None,
+                    quote_usd: quote_usd.to_string(),
+                    error: Some(e.to_string()),
+                },
+            })
+            .collect();
+        let output = CrossPriceOutput {
+            quote,
+            quote_usd: quote_usd.to_string(),
+            prices,
+        };
+        return render(fmt, &output);
+    }
 
     let prices: Vec<PriceRow> = tickers
-        .iter()
-        .map(|t| PriceRow {
-            coin: t.symbol.clone(),
-            mid_price: t.mid_price.to_string(),
-            protocol: "hyperliquid".into(),
+        .into_iter()
+        .map(|(coin, res)| match res {
+            Ok(t) => PriceRow {
+                coin: t.symbol,
+                mid_price: t.mid_price.to_string(),
+                protocol: "hyperliquid".into(),
+                pair: None,
+                key: None,
+                error: None,
+            },
+            Err(e) => PriceRow {
+                coin,
+                mid_price: "—".into(),
+                protocol: "hyperliquid".into(),
+                pair: None,
+                key: None,
+                error: Some(e.to_string()),
+            },
         })
         .collect();
 
@@ -144,22 +277,75 @@ pub async fn price(coins: &[String], all: bool, fmt: OutputFormat) -> Result<()>
     Ok(())
 }
 
+fn spot_price_row(m: &SpotMarket) -> PriceRow {
+    PriceRow {
+        coin: m.base.clone(),
+        mid_price: m
+            .mid_price
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "—".into()),
+        protocol: "hyperliquid".into(),
+        pair: Some(m.pair.clone()),
+        key: Some(m.key.clone()),
+        error: None,
+    }
+}
+
+/// Symbol and USD mid of the `--in` quote asset.
+async fn quote_mid(perp: &dyn PerpModule, quote: &str) -> Result<(String, Decimal)> {
+    let found = tickers_for(perp, &[quote.to_string()])
+        .await
+        .into_iter()
+        .next()
+        .map(|(_, res)| res)
+        .unwrap_or_else(|| Err(AtlasError::asset_not_found(quote)));
+    let ticker = found.map_err(|e| AtlasError::InvalidTicker(format!("--in {quote}: {e}")))?;
+    if ticker.mid_price <= Decimal::ZERO {
+        return Err(AtlasError::InvalidTicker(format!(
+            "--in {}: no mid price to quote in",
+            ticker.symbol
+        ))
+        .into());
+    }
+    Ok((ticker.symbol, ticker.mid_price))
+}
+
 /// `atlas markets` or `atlas markets --spot`
 pub async fn markets(spot: bool, fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::readonly().await?;
     let perp = orch.perp(None)?;
 
-    let market_list = perp.markets().await.map_err(|e| anyhow::anyhow!("{e}"))?;
-
-    let rows: Vec<MarketRow> = market_list
-        .iter()
-        .map(|m| MarketRow {
-            name: m.symbol.clone(),
-            index: 0, // universal Market doesn't have index
-            max_leverage: m.max_leverage.unwrap_or(1) as u64,
-            sz_decimals: m.sz_decimals.unwrap_or(0) as i64,
-        })
-        .collect();
+    let rows: Vec<MarketRow> = if spot {
+        perp.spot_markets()
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?
+            .into_iter()
+            .map(|m| MarketRow {
+                name: m.pair,
+                index: m.index,
+                max_leverage: None,
+                sz_decimals: m.sz_decimals as i64,
+                key: Some(m.key),
+                wei_decimals: Some(m.wei_decimals),
+                mid_price: m.mid_price.map(|p| p.to_string()),
+            })
+            .collect()
+    } else {
+        perp.markets()
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?
+            .iter()
+            .map(|m| MarketRow {
+                name: m.symbol.clone(),
+                index: 0, // universal Market doesn't have index
+                max_leverage: Some(m.max_leverage.unwrap_or(1) as u64),
+                sz_decimals: m.sz_decimals.unwrap_or(0) as i64,
+                key: None,
+                wei_decimals: None,
+                mid_price: None,
+            })
+            .collect()
+    };
 
     let market_type = if spot { "spot" } else { "perp" };
     render_markets(
@@ -176,7 +362,7 @@ pub async fn markets(spot: bool, fmt: OutputFormat) -> Result<()> {
 pub async fn candles(coin: &str, interval: &str, limit: usize, fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::readonly().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
 
     let candle_data = perp
         .candles(&coin_upper, interval, limit)
@@ -211,7 +397,7 @@ pub async fn candles(coin: &str, interval: &str, limit: usize, fmt: OutputFormat
 pub async fn funding(coin: &str, fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::readonly().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
 
     let rates = perp
         .funding(&coin_upper)
@@ -313,313 +499,340 @@ pub async fn orderbook(ticker: &str, depth: usize, fmt: OutputFormat) -> Result<
 pub async fn info(coin: &str, fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::readonly().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
 
-    let ticker = perp
-        .ticker(&coin_upper)
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
-    let markets = perp.markets().await.map_err(|e| anyhow::anyhow!("{e}"))?;
-    let market = markets.iter().find(|m| m.symbol == coin_upper);
+    let (ticker, contexts, markets, candles) = tokio::join!(
+        perp.ticker(&coin_upper),
+        perp.asset_contexts(),
+        perp.markets(),
+        perp.candles(&coin_upper, "1h", 24),
+    );
+    let ticker = ticker.map_err(|e| anyhow::anyhow!("{e}"))?;
+    record_spreads(std::slice::from_ref(&ticker));
+    let ctx = contexts
+        .unwrap_or_default()
+        .into_iter()
+        .find(|c| c.symbol == coin_upper);
+    let market = markets
+        .unwrap_or_default()
+        .into_iter()
+        .find(|m| m.base == coin_upper);
+    let candles = candles.unwrap_or_default();
+
+    let bps = |v: Decimal, of: Decimal| (v / of * Decimal::from(10000)).round_dp(2);
+    let spread = ticker.best_bid.zip(ticker.best_ask).map(|(b, a)| a - b);
+    let spread_bps = spread
+        .filter(|_| ticker.mid_price > Decimal::ZERO)
+        .map(|s| bps(s, ticker.mid_price));
+    let now_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let c = ctx.as_ref();
+    let to_s = |v: Option<Decimal>| v.map(|v| v.normalize().to_string());
+
+    let output = MarketInfoOutput {
+        symbol: coin_upper,
+        mid_price: ticker.mid_price.to_string(),
+        best_bid: to_s(ticker.best_bid),
+        best_ask: to_s(ticker.best_ask),
+        spread: to_s(spread),
+        spread_bps: to_s(spread_bps),
+        volume_24h: to_s(ticker.volume_24h.map(|v| v.round_dp(0))),
+        volume_24h_base: to_s(c.and_then(|c| c.volume_24h_base)),
+        change_24h_pct: to_s(ticker.change_24h_pct.map(|v| v.round_dp(2))),
+        high_24h: to_s(candles.iter().map(|k| k.high).max()),
+        low_24h: to_s(candles.iter().map(|k| k.low).min()),
+        open_interest: to_s(
+            c.and_then(|c| c.open_interest)
+                .or(market.as_ref().and_then(|m| m.open_interest)),
+        ),
+        open_interest_usd: to_s(c.and_then(|c| c.open_interest_usd()).map(|v| v.round_dp(0))),
+        mark_price: to_s(
+            c.and_then(|c| c.mark_price)
+                .or(market.as_ref().and_then(|m| m.mark_price)),
+        ),
+        index_price: to_s(market.as_ref().and_then(|m| m.index_price)),
+        oracle_price: to_s(c.and_then(|c| c.oracle_price)),
+        premium: to_s(c.and_then(|c| c.premium)),
+        premium_bps: to_s(
+            c.and_then(|c| c.premium)
+                .map(|p| (p * Decimal::from(10000)).round_dp(2)),
+        ),
+        funding_rate: to_s(c.and_then(|c| c.funding_rate)),
+        funding_apr_pct: to_s(c.and_then(|c| c.funding_apr_pct()).map(|v| v.round_dp(2))),
+        next_funding_secs: c.map(|c| c.next_funding_secs(now_secs)),
+        max_leverage: market.as_ref().and_then(|m| m.max_leverage),
+    };
+    render(fmt, &output)?;
+    Ok(())
+}
 
-    match fmt {
-        OutputFormat::Json | OutputFormat::JsonPretty => {
-            let json = serde_json::json!({
-                "symbol": coin_upper,
-                "mid_price": ticker.mid_price.to_string(),
-                "best_bid": ticker.best_bid.map(|b| b.to_string()),
-                "best_ask": ticker.best_ask.map(|a| a.to_string()),
-                "spread": ticker.best_bid.and_then(|b| ticker.best_ask.map(|a| (a - b).to_string())),
-                "spread_bps": ticker.best_bid.and_then(|b| ticker.best_ask.map(|a| {
-                    if ticker.mid_price > Decimal::ZERO {
-                        ((a - b) / ticker.mid_price * Decimal::from(10000)).round_dp(2).to_string()
-                    } else { "0".to_string() }
-                })),
-                "volume_24h": ticker.volume_24h.map(|v| v.to_string()),
-                "change_24h_pct": ticker.change_24h_pct.map(|c| c.to_string()),
-                "open_interest": market.and_then(|m| m.open_interest.map(|o| o.to_string())),
-                "mark_price": market.and_then(|m| m.mark_price.map(|p| p.to_string())),
-                "index_price": market.and_then(|m| m.index_price.map(|p| p.to_string())),
-                "max_leverage": market.and_then(|m| m.max_leverage),
-            });
-            let s = if matches!(fmt, OutputFormat::JsonPretty) {
-                serde_json::to_string_pretty(&json)?
-            } else {
-                serde_json::to_string(&json)?
-            };
-            println!("{s}");
-        }
-        OutputFormat::Table => {
-            let spread = ticker.best_bid.and_then(|b| ticker.best_ask.map(|a| a - b));
-            let spread_bps = spread.map(|s| {
-                if ticker.mid_price > Decimal::ZERO {
-                    (s / ticker.mid_price * Decimal::from(10000)).round_dp(2)
-                } else {
-                    Decimal::ZERO
-                }
-            });
+/// `atlas market hl spread-profile <COIN>` — median spread per UTC hour
+/// from the samples other commands recorded.
+pub fn spread_profile(coin: &str, fmt: OutputFormat) -> Result<()> {
+    let coin = coin.to_uppercase();
+    let db = AtlasDb::open()?;
+    let from_ms = chrono::Utc::now().timestamp_millis() - spread_profile::RETENTION_MS;
+    let rows = db.query_spread_samples("hyperliquid", &coin, from_ms)?;
+
+    let (Some(first), Some(last), Some(median_bps)) =
+        (rows.first(), rows.last(), spread_profile::median_bps(&rows))
+    else {
+        anyhow::bail!(
+            "No spread samples for {coin} yet. They are recorded whenever \
+             `atlas market hl price`, `info`, `summary` or a market order fetches the coin."
+        );
+    };
 
-            println!("┌─────────────────────────────────────────────────┐");
-            println!(
-                "│  {} — Market Info{:>width$}│",
-                coin_upper,
-                "",
-                width = 47 - coin_upper.len() - 15
-            );
-            println!("├─────────────────────────────────────────────────┤");
-            println!("│  Mid Price     : ${:<29}│", ticker.mid_price);
-            println!(
-                "│  Best Bid      : ${:<29}│",
-                ticker.best_bid.map(|b| b.to_string()).unwrap_or("—".into())
-            );
-            println!(
-                "│  Best Ask      : ${:<29}│",
-                ticker.best_ask.map(|a| a.to_string()).unwrap_or("—".into())
-            );
-            println!(
-                "│  Spread        : ${:<22} ({} bps)│",
-                spread.map(|s| s.to_string()).unwrap_or("—".into()),
-                spread_bps.map(|s| s.to_string()).unwrap_or("—".into())
-            );
-            println!("├─────────────────────────────────────────────────┤");
-            println!(
-                "│  24h Volume    : ${:<29}│",
-                ticker
-                    .volume_24h
-                    .map(|v| format!("{:.0}", v))
-                    .unwrap_or("—".into())
-            );
-            println!(
-                "│  24h Change    : {:<30}│",
-                ticker
-                    .change_24h_pct
-                    .map(|c| format!("{:+.2}%", c))
-                    .unwrap_or("—".into())
-            );
+    let output = SpreadProfileOutput {
+        coin: coin.clone(),
+        samples: rows.len(),
+        median_bps,
+        from: format_ms(first.time_ms),
+        to: format_ms(last.time_ms),
+        retention_days: spread_profile::RETENTION_MS / 86_400_000,
+        hours: spread_profile::hourly_profile(&rows),
+    };
+    render(fmt, &output)
+}
 
-            if let Some(m) = market {
-                println!("├─────────────────────────────────────────────────┤");
-                println!(
-                    "│  Mark Price    : ${:<29}│",
-                    m.mark_price.map(|p| p.to_string()).unwrap_or("—".into())
-                );
-                println!(
-                    "│  Index Price   : ${:<29}│",
-                    m.index_price.map(|p| p.to_string()).unwrap_or("—".into())
-                );
-                println!(
-                    "│  Open Interest : ${:<29}│",
-                    m.open_interest
-                        .map(|o| format!("{:.0}", o))
-                        .unwrap_or("—".into())
-                );
-                println!(
-                    "│  Max Leverage  : {:<30}│",
-                    m.max_leverage
-                        .map(|l| format!("{}x", l))
-                        .unwrap_or("—".into())
-                );
-            }
-            println!("└─────────────────────────────────────────────────┘");
-        }
-    }
+/// `atlas market hl top [--sort volume|change|oi|funding|spread] [--min-volume USD] [--limit 20] [--reverse]`
+///
+/// Ranking and tiebreaks are in [`atlas_core::market_rank`].
+pub async fn top(
+    sort: TopSort,
+    min_volume: Option<f64>,
+    limit: usize,
+    reverse: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
 
-    Ok(())
+    let ctxs = perp
+        .asset_contexts()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let min_volume = min_volume.and_then(Decimal::from_f64);
+    let markets = market_rank::rank(&ctxs, sort, reverse, min_volume)
+        .into_iter()
+        .take(limit)
+        .map(market_rank::top_row)
+        .collect();
+
+    let out = TopMarketsOutput {
+        sort: sort.to_string(),
+        reverse,
+        min_volume: min_volume.map(|v| v.normalize().to_string()),
+        markets,
+    };
+    render(fmt, &out)
 }
 
-/// `atlas market top [--sort volume|change|oi] [--limit 20] [--reverse]`
-pub async fn top(sort_by: &str, limit: usize, reverse: bool, fmt: OutputFormat) -> Result<()> {
+/// `atlas market funding-screen [--min-abs 0.0005] [--min-oi 100000] [--sort rate|apr] [--limit 20] [--reverse]`
+///
+/// Ranks every perp by its current predicted funding, split into positive
+/// and negative sides, most extreme first. Markets below `min_oi_usd` open
+/// interest are dropped so illiquid listings don't crowd the top.
+pub async fn funding_screen(
+    min_abs: f64,
+    min_oi_usd: f64,
+    sort_by: &str,
+    limit: usize,
+    reverse: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
     let orch = crate::factory::readonly().await?;
     let perp = orch.perp(None)?;
 
-    let mut tickers = perp
-        .all_tickers()
+    let ctxs = perp
+        .asset_contexts()
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))?;
 
-    // Sort
-    match sort_by {
-        "volume" | "vol" => {
-            tickers.sort_by(|a, b| {
-                let va = a.volume_24h.unwrap_or(Decimal::ZERO);
-                let vb = b.volume_24h.unwrap_or(Decimal::ZERO);
-                vb.cmp(&va)
-            });
-        }
-        "change" | "chg" | "gainers" => {
-            tickers.sort_by(|a, b| {
-                let ca = a.change_24h_pct.unwrap_or(Decimal::ZERO);
-                let cb = b.change_24h_pct.unwrap_or(Decimal::ZERO);
-                cb.cmp(&ca)
-            });
-        }
-        "losers" => {
-            tickers.sort_by(|a, b| {
-                let ca = a.change_24h_pct.unwrap_or(Decimal::ZERO);
-                let cb = b.change_24h_pct.unwrap_or(Decimal::ZERO);
-                ca.cmp(&cb)
-            });
-        }
-        "price" => {
-            tickers.sort_by(|a, b| b.mid_price.cmp(&a.mid_price));
-        }
-        _ => {
-            tickers.sort_by(|a, b| {
-                let va = a.volume_24h.unwrap_or(Decimal::ZERO);
-                let vb = b.volume_24h.unwrap_or(Decimal::ZERO);
-                vb.cmp(&va)
-            });
-        }
-    }
-
-    if reverse {
-        tickers.reverse();
-    }
+    let min_abs = Decimal::from_f64(min_abs.abs()).unwrap_or(Decimal::ZERO);
+    let min_oi = Decimal::from_f64(min_oi_usd).unwrap_or(Decimal::ZERO);
+    let mut screened: Vec<(&AssetContext, Decimal, Decimal)> = ctxs
+        .iter()
+        .filter_map(|c| {
+            let rate = c.funding_rate?;
+            let apr = c.funding_apr_pct()?;
+            let oi = c.open_interest_usd()?;
+            (rate.abs() >= min_abs && oi >= min_oi).then_some((c, rate, apr))
+        })
+        .collect();
 
-    tickers.truncate(limit);
+    let key = |rate: Decimal, apr: Decimal| if sort_by == "apr" { apr } else { rate };
+    screened.sort_by(|(_, ra, aa), (_, rb, ab)| key(*rb, *ab).abs().cmp(&key(*ra, *aa).abs()));
 
-    match fmt {
-        OutputFormat::Json | OutputFormat::JsonPretty => {
-            let rows: Vec<serde_json::Value> = tickers
-                .iter()
-                .map(|t| {
-                    serde_json::json!({
-                        "symbol": t.symbol,
-                        "mid_price": t.mid_price.to_string(),
-                        "volume_24h": t.volume_24h.map(|v| v.to_string()),
-                        "change_24h_pct": t.change_24h_pct.map(|c| c.to_string()),
-                        "best_bid": t.best_bid.map(|b| b.to_string()),
-                        "best_ask": t.best_ask.map(|a| a.to_string()),
-                    })
-                })
-                .collect();
-            let json = serde_json::json!({"ok": true, "data": { "markets": rows }});
-            let s = if matches!(fmt, OutputFormat::JsonPretty) {
-                serde_json::to_string_pretty(&json)?
-            } else {
-                serde_json::to_string(&json)?
-            };
-            println!("{s}");
+    let side = |positive: bool| -> Vec<FundingScreenRow> {
+        let mut rows: Vec<_> = screened
+            .iter()
+            .filter(|(_, rate, _)| {
+                if positive {
+                    *rate > Decimal::ZERO
+                } else {
+                    *rate < Decimal::ZERO
+                }
+            })
+            .collect();
+        if reverse {
+            rows.reverse();
         }
-        OutputFormat::Table => {
-            let title = match sort_by {
-                "gainers" | "change" | "chg" => "Top Gainers",
-                "losers" => "Top Losers",
-                "price" => "By Price",
-                _ => "By Volume",
-            };
-            println!("📊 {} (top {})\n", title, limit);
-            println!(
-                "{:<12} {:>14} {:>16} {:>10}",
-                "COIN", "PRICE", "24h VOLUME", "24h CHG"
-            );
-            println!("{}", "─".repeat(55));
-            for t in &tickers {
-                let vol = t
+        rows.into_iter()
+            .take(limit)
+            .map(|(c, rate, apr)| FundingScreenRow {
+                coin: c.symbol.clone(),
+                rate: rate.normalize().to_string(),
+                apr_pct: apr.round_dp(2).to_string(),
+                open_interest_usd: c
+                    .open_interest_usd()
+                    .map(|v| v.round_dp(0).to_string())
+                    .unwrap_or_else(|| "—".into()),
+                volume_24h: c
                     .volume_24h
-                    .map(|v| {
-                        if v >= Decimal::from(1_000_000) {
-                            format!("${:.1}M", v.to_f64().unwrap_or(0.0) / 1_000_000.0)
-                        } else if v >= Decimal::from(1_000) {
-                            format!("${:.1}K", v.to_f64().unwrap_or(0.0) / 1_000.0)
-                        } else {
-                            format!("${:.0}", v)
-                        }
-                    })
-                    .unwrap_or("—".into());
-                let chg = t
-                    .change_24h_pct
-                    .map(|c| format!("{:+.2}%", c))
-                    .unwrap_or("—".into());
-                println!(
-                    "{:<12} {:>14} {:>16} {:>10}",
-                    t.symbol, t.mid_price, vol, chg
-                );
-            }
-        }
-    }
+                    .map(|v| v.round_dp(0).to_string())
+                    .unwrap_or_else(|| "—".into()),
+                mark_price: c
+                    .mark_price
+                    .map(|p| p.normalize().to_string())
+                    .unwrap_or_else(|| "—".into()),
+            })
+            .collect()
+    };
 
-    Ok(())
+    let out = FundingScreenOutput {
+        sort: sort_by.to_string(),
+        min_abs_rate: min_abs.normalize().to_string(),
+        min_oi_usd: min_oi.normalize().to_string(),
+        matched: screened.len(),
+        positive: side(true),
+        negative: side(false),
+    };
+    render(fmt, &out)
 }
 
-/// `atlas market spread <COINS...>` — bid-ask spreads.
-pub async fn spread(coins: &[String], fmt: OutputFormat) -> Result<()> {
+/// `atlas market spread <COINS...> [--in QUOTE]` — bid-ask spreads, in USD
+/// or in units of a quote asset.
+pub async fn spread(coins: &[String], quote: Option<&str>, fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::readonly().await?;
     let perp = orch.perp(None)?;
 
-    let tickers = if coins.is_empty() {
+    let quote = match quote {
+        Some(q) => Some(quote_mid(perp.as_ref(), q).await?),
+        None => None,
+    };
+    // USD amounts in quote units; unchanged without --in
+    let in_quote = |usd: Decimal| match &quote {
+        Some((_, quote_usd)) => cross_rate(usd, *quote_usd).unwrap_or_default(),
+        None => usd,
+    };
+
+    // (symbol, ticker or error note) — one round-trip regardless of coin count
+    let rows: Vec<(String, std::result::Result<Ticker, String>)> = if coins.is_empty() {
         perp.all_tickers()
             .await
             .map_err(|e| anyhow::anyhow!("{e}"))?
+            .into_iter()
+            .filter(|t| t.best_bid.is_some() && t.best_ask.is_some())
+            .map(|t| (t.symbol.clone(), Ok(t)))
+            .collect()
     } else {
-        let mut result = Vec::new();
-        for c in coins {
-            let t = perp
-                .ticker(&c.to_uppercase())
-                .await
-                .map_err(|e| anyhow::anyhow!("{e}"))?;
-            result.push(t);
-        }
-        result
+        tickers_for(perp.as_ref(), coins)
+            .await
+            .into_iter()
+            .map(|(coin, res)| {
+                let res = res.map_err(|e| e.to_string()).and_then(|t| {
+                    if t.best_bid.is_some() && t.best_ask.is_some() {
+                        Ok(t)
+                    } else {
+                        Err("no bid/ask data".to_string())
+                    }
+                });
+                (coin, res)
+            })
+            .collect()
     };
 
-    // Filter to only those with bid/ask data
-    let with_spread: Vec<_> = tickers
-        .iter()
-        .filter(|t| t.best_bid.is_some() && t.best_ask.is_some())
-        .collect();
+    let spread_of = |t: &Ticker| -> (Decimal, Decimal, Decimal, Decimal) {
+        let bid = t.best_bid.unwrap_or_default();
+        let ask = t.best_ask.unwrap_or_default();
+        let spread_abs = ask - bid;
+        let spread_bps = if t.mid_price > Decimal::ZERO {
+            (spread_abs / t.mid_price * Decimal::from(10000)).round_dp(2)
+        } else {
+            Decimal::ZERO
+        };
+        (bid, ask, spread_abs, spread_bps)
+    };
 
     match fmt {
         OutputFormat::Json | OutputFormat::JsonPretty => {
-            let rows: Vec<serde_json::Value> = with_spread
+            let json_rows: Vec<serde_json::Value> = rows
                 .iter()
-                .filter_map(|t| {
-                    let bid = t.best_bid?;
-                    let ask = t.best_ask?;
-                    let spread_abs = ask - bid;
-                    let spread_bps = if t.mid_price > Decimal::ZERO {
-                        (spread_abs / t.mid_price * Decimal::from(10000)).round_dp(2)
-                    } else {
-                        Decimal::ZERO
-                    };
-                    Some(serde_json::json!({
-                        "symbol": t.symbol,
-                        "bid": bid.to_string(),
-                        "ask": ask.to_string(),
-                        "spread": spread_abs.to_string(),
-                        "spread_bps": spread_bps.to_string(),
-                        "mid": t.mid_price.to_string(),
-                    }))
+                .map(|(symbol, res)| match res {
+                    Ok(t) => {
+                        let (bid, ask, spread_abs, spread_bps) = spread_of(t);
+                        let mut row = serde_json::json!({
+                            "symbol": t.symbol,
+                            "bid": bid.to_string(),
+                            "ask": ask.to_string(),
+                            "spread": spread_abs.to_string(),
+                            "spread_bps": spread_bps.to_string(),
+                            "mid": t.mid_price.to_string(),
+                        });
+                        if let Some((quote, quote_usd)) = &quote {
+                            row["quote"] = quote.as_str().into();
+                            row["quote_mid"] = quote_usd.to_string().into();
+                            row["bid_in_quote"] = in_quote(bid).to_string().into();
+                            row["ask_in_quote"] = in_quote(ask).to_string().into();
+                            row["spread_in_quote"] = in_quote(spread_abs).to_string().into();
+                        }
+                        row
+                    }
+                    Err(err) => serde_json::json!({"symbol": symbol, "error": err}),
                 })
                 .collect();
             let s = if matches!(fmt, OutputFormat::JsonPretty) {
-                serde_json::to_string_pretty(&rows)?
+                serde_json::to_string_pretty(&json_rows)?
             } else {
-                serde_json::to_string(&rows)?
+                serde_json::to_string(&json_rows)?
             };
             println!("{s}");
         }
         OutputFormat::Table => {
+            let unit = match &quote {
+                Some((quote, _)) => format!(" ({quote})"),
+                None => String::new(),
+            };
             println!(
                 "{:<12} {:>14} {:>14} {:>12} {:>8}",
-                "COIN", "BID", "ASK", "SPREAD", "BPS"
+                "COIN",
+                format!("BID{unit}"),
+                format!("ASK{unit}"),
+                format!("SPREAD{unit}"),
+                "BPS"
             );
             println!("{}", "─".repeat(63));
-            for t in &with_spread {
-                let (Some(bid), Some(ask)) = (t.best_bid, t.best_ask) else {
-                    continue;
-                };
-                let (bid, ask) = (bid, ask);
-                let spread_abs = ask - bid;
-                let spread_bps = if t.mid_price > Decimal::ZERO {
-                    (spread_abs / t.mid_price * Decimal::from(10000)).round_dp(2)
-                } else {
-                    Decimal::ZERO
-                };
-                println!(
-                    "{:<12} {:>14} {:>14} {:>12} {:>8}",
-                    t.symbol, bid, ask, spread_abs, spread_bps
-                );
+            for (symbol, res) in &rows {
+                match res {
+                    Ok(t) => {
+                        let (bid, ask, spread_abs, spread_bps) = spread_of(t);
+                        println!(
+                            "{:<12} {:>14} {:>14} {:>12} {:>8}",
+                            t.symbol,
+                            in_quote(bid),
+                            in_quote(ask),
+                            in_quote(spread_abs),
+                            spread_bps
+                        );
+                    }
+                    Err(err) => println!("{:<12} {err}", symbol),
+                }
+            }
+            if let Some((quote, quote_usd)) = &quote {
+                println!("{quote} = ${quote_usd}");
             }
         }
     }
@@ -634,11 +847,23 @@ pub async fn search(query: &str, fmt: OutputFormat) -> Result<()> {
 
     let all_markets = perp.markets().await.map_err(|e| anyhow::anyhow!("{e}"))?;
 
-    let q = query.to_uppercase();
-    let matches: Vec<_> = all_markets
+    // Same scorer as coin resolution: exact, prefix, substring, then typos.
+    let config = atlas_core::workspace::load_config()?;
+    let resolver =
+        SymbolResolver::default().with_aliases(&config.modules.hyperliquid.config.aliases);
+    let q = resolver.alias(query).unwrap_or(query);
+    let mut scored: Vec<_> = all_markets
         .iter()
-        .filter(|m| m.symbol.contains(&q) || m.base.to_uppercase().contains(&q))
+        .filter_map(|m| {
+            let score = [m.symbol.as_str(), m.base.as_str()]
+                .into_iter()
+                .filter_map(|name| fuzzy_score(q, name))
+                .min()?;
+            Some((score, m))
+        })
         .collect();
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.symbol.cmp(&b.1.symbol)));
+    let matches: Vec<_> = scored.into_iter().map(|(_, m)| m).collect();
 
     if matches.is_empty() {
         println!("No markets matching '{query}'");
@@ -698,125 +923,93 @@ pub async fn search(query: &str, fmt: OutputFormat) -> Result<()> {
     Ok(())
 }
 
-/// `atlas market summary` — quick market dashboard.
-pub async fn summary(fmt: OutputFormat) -> Result<()> {
-    let orch = crate::factory::readonly().await?;
-    let perp = orch.perp(None)?;
-
-    let tickers = perp
-        .all_tickers()
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
-
-    let total = tickers.len();
-    let total_volume: Decimal = tickers.iter().filter_map(|t| t.volume_24h).sum();
-
-    let gainers = tickers
-        .iter()
-        .filter(|t| t.change_24h_pct.map(|c| c > Decimal::ZERO).unwrap_or(false))
-        .count();
-    let losers = tickers
-        .iter()
-        .filter(|t| t.change_24h_pct.map(|c| c < Decimal::ZERO).unwrap_or(false))
-        .count();
-
-    let mut sorted_by_change = tickers.clone();
-    sorted_by_change.sort_by(|a, b| {
-        let ca = a.change_24h_pct.unwrap_or(Decimal::ZERO);
-        let cb = b.change_24h_pct.unwrap_or(Decimal::ZERO);
-        cb.cmp(&ca)
-    });
-
-    let top3_gainers: Vec<_> = sorted_by_change.iter().take(3).collect();
-    let top3_losers: Vec<_> = sorted_by_change.iter().rev().take(3).collect();
-
-    let mut sorted_by_vol = tickers.clone();
-    sorted_by_vol.sort_by(|a, b| {
-        let va = a.volume_24h.unwrap_or(Decimal::ZERO);
-        let vb = b.volume_24h.unwrap_or(Decimal::ZERO);
-        vb.cmp(&va)
-    });
-    let top3_volume: Vec<_> = sorted_by_vol.iter().take(3).collect();
+/// `atlas market summary [--sections gainers,losers,volume,funding,oi] [--limit 3] [--watchlist]`
+///
+/// One `metaAndAssetCtxs` fetch; with `--watchlist` the positions are read
+/// alongside it and the dashboard covers only the `hl.watchlist` coins.
+pub async fn summary(
+    sections: &[String],
+    limit: usize,
+    watchlist: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    use atlas_core::market_summary::{self, DEFAULT_SECTIONS, OI_CHANGE_WINDOW_MS};
+
+    let mut sections = market_summary::parse_sections(sections).map_err(anyhow::Error::msg)?;
+    if sections.is_empty() && !watchlist {
+        sections = DEFAULT_SECTIONS.to_vec();
+    }
+    let config = atlas_core::workspace::load_config()?;
+    let watched = config.modules.hyperliquid.config.watchlist;
+    if watchlist && watched.is_empty() {
+        return Err(AtlasError::InvalidConfig(
+            "hl.watchlist is empty. Set it with: atlas configure set hl.watchlist BTC,ETH,SOL"
+                .into(),
+        )
+        .into());
+    }
 
-    match fmt {
-        OutputFormat::Json | OutputFormat::JsonPretty => {
-            let json = serde_json::json!({
-                "total_markets": total,
-                "total_volume_24h": total_volume.to_string(),
-                "gainers": gainers,
-                "losers": losers,
-                "top_gainers": top3_gainers.iter().map(|t| serde_json::json!({
-                    "symbol": t.symbol, "change": t.change_24h_pct.map(|c| c.to_string())
-                })).collect::<Vec<_>>(),
-                "top_losers": top3_losers.iter().map(|t| serde_json::json!({
-                    "symbol": t.symbol, "change": t.change_24h_pct.map(|c| c.to_string())
-                })).collect::<Vec<_>>(),
-                "top_volume": top3_volume.iter().map(|t| serde_json::json!({
-                    "symbol": t.symbol, "volume": t.volume_24h.map(|v| v.to_string())
-                })).collect::<Vec<_>>(),
-            });
-            let s = if matches!(fmt, OutputFormat::JsonPretty) {
-                serde_json::to_string_pretty(&json)?
-            } else {
-                serde_json::to_string(&json)?
-            };
-            println!("{s}");
+    // Positions need the wallet; without one the watchlist still renders
+    let (orch, no_account) = if watchlist {
+        match crate::factory::from_active_profile().await {
+            Ok(orch) => (orch, None),
+            Err(e) => (crate::factory::readonly().await?, Some(e.to_string())),
         }
-        OutputFormat::Table => {
-            let vol_str = if total_volume >= Decimal::from(1_000_000_000) {
-                format!(
-                    "${:.2}B",
-                    total_volume.to_f64().unwrap_or(0.0) / 1_000_000_000.0
-                )
-            } else if total_volume >= Decimal::from(1_000_000) {
-                format!(
-                    "${:.1}M",
-                    total_volume.to_f64().unwrap_or(0.0) / 1_000_000.0
-                )
-            } else {
-                format!("${:.0}", total_volume)
-            };
+    } else {
+        (crate::factory::readonly().await?, None)
+    };
+    let perp = orch.perp(None)?;
 
-            println!("┌─────────────────────────────────────────────────┐");
-            println!("│  📊 MARKET SUMMARY                              │");
-            println!("├─────────────────────────────────────────────────┤");
-            println!("│  Markets       : {:<30} │", total);
-            println!("│  24h Volume    : {:<30} │", vol_str);
-            println!("│  Gainers       : {:<14} Losers: {:<9} │", gainers, losers);
-            println!("├─────────────────────────────────────────────────┤");
-            println!("│  🟢 Top Gainers                                 │");
-            for t in &top3_gainers {
-                let chg = t
-                    .change_24h_pct
-                    .map(|c| format!("{:+.2}%", c))
-                    .unwrap_or("—".into());
-                println!("│    {:<12} {:>12}  ${:<18} │", t.symbol, chg, t.mid_price);
-            }
-            println!("│  🔴 Top Losers                                  │");
-            for t in &top3_losers {
-                let chg = t
-                    .change_24h_pct
-                    .map(|c| format!("{:+.2}%", c))
-                    .unwrap_or("—".into());
-                println!("│    {:<12} {:>12}  ${:<18} │", t.symbol, chg, t.mid_price);
-            }
-            println!("│  📈 Top Volume                                  │");
-            for t in &top3_volume {
-                let vol = t
-                    .volume_24h
-                    .map(|v| {
-                        if v >= Decimal::from(1_000_000) {
-                            format!("${:.1}M", v.to_f64().unwrap_or(0.0) / 1_000_000.0)
-                        } else {
-                            format!("${:.0}", v)
-                        }
-                    })
-                    .unwrap_or("—".into());
-                println!("│    {:<12} {:>12}  ${:<18} │", t.symbol, vol, t.mid_price);
-            }
-            println!("└─────────────────────────────────────────────────┘");
-        }
-    }
+    let (ctxs, positions) = if watchlist && no_account.is_none() {
+        let (ctxs, positions) = tokio::join!(perp.asset_contexts(), perp.positions());
+        (ctxs, Some(positions))
+    } else {
+        (perp.asset_contexts().await, None)
+    };
+    let ctxs = ctxs.map_err(|e| anyhow::anyhow!("{e}"))?;
+    record_spreads(&ctxs.iter().filter_map(ctx_ticker).collect::<Vec<_>>());
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let day_ago = now_ms - OI_CHANGE_WINDOW_MS;
+    let oi_ref = AtlasDb::open()
+        .and_then(|db| {
+            db.query_oi_snapshots_between(
+                "hyperliquid",
+                day_ago - market_summary::OI_REFERENCE_TOLERANCE_MS,
+                day_ago + market_summary::OI_REFERENCE_TOLERANCE_MS,
+            )
+        })
+        .map(|snapshots| market_summary::oi_reference(&snapshots, day_ago))
+        .unwrap_or_default();
+
+    let output = if watchlist {
+        let scope = market_summary::watched(&ctxs, &watched);
+        let mut output = market_summary::summarize(&scope, &sections, limit, &oi_ref);
+        let (positions, error) = match positions {
+            Some(Ok(p)) => (Some(p), None),
+            Some(Err(e)) => (None, Some(e.to_string())),
+            None => (None, no_account),
+        };
+        let mut summary =
+            market_summary::watchlist_summary(&ctxs, &watched, positions.as_deref(), &oi_ref);
+        summary.positions_error = error;
+        output.watchlist = Some(summary);
+        output
+    } else {
+        market_summary::summarize(&ctxs, &sections, limit, &oi_ref)
+    };
+    render(fmt, &output)
+}
 
-    Ok(())
+/// Ticker view of a context, for spread sampling.
+fn ctx_ticker(ctx: &AssetContext) -> Option<Ticker> {
+    Some(Ticker {
+        symbol: ctx.symbol.clone(),
+        protocol: ctx.protocol.clone(),
+        mid_price: ctx.mid_price?,
+        best_bid: ctx.impact_bid,
+        best_ask: ctx.impact_ask,
+        volume_24h: ctx.volume_24h,
+        change_24h_pct: ctx.change_24h_pct(),
+    })
 }
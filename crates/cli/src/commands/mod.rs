@@ -1,19 +1,37 @@
 pub mod account;
+pub mod address;
+pub mod audit;
 pub mod auth;
+pub mod backtest;
+pub mod cache;
 pub mod coingecko;
+pub mod compare;
 pub mod configure;
+pub mod correlate;
+pub mod dex_watch;
 pub mod doctor;
 pub mod export;
 pub mod helpers;
 pub mod history;
+pub mod init;
+pub mod journal;
+pub mod logs;
 pub mod market;
 pub mod modules;
+pub mod morpho;
+pub mod notify;
+pub mod oi;
+pub mod paper;
 pub mod risk;
+pub mod schedule;
 pub mod spot;
 pub mod status;
+pub mod strategy;
 pub mod stream;
 pub mod sub;
 pub mod ta;
 pub mod trade;
 pub mod vault;
+pub mod wallet;
+pub mod workspace;
 pub mod zero_x;
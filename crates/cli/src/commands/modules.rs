@@ -1,7 +1,22 @@
 //! `atlas module` — Module management (list, enable, disable, config).
 
+use std::future::Future;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
-use atlas_core::output::OutputFormat;
+use atlas_core::backend::BackendClient;
+use atlas_core::config::{AppConfig, HyperliquidConfig};
+use atlas_core::constants::{ATLAS_BACKEND_URL, HL_MAINNET_RPC, HL_TESTNET_RPC};
+use atlas_core::engine::{is_evm_address, BuilderFee};
+use atlas_core::fmt::truncate_address;
+use atlas_core::output::{
+    print_confirmation, render, ModuleHealth, ModuleListOutput, ModuleRow, OutputFormat,
+};
+use atlas_core::prompt::confirm_action;
+use atlas_hl::http::{ExchangeHttp, RetryPolicy};
+use atlas_hl::proxy;
+use atlas_zero_x::client::ZeroXModule;
 
 fn json_ok(fmt: OutputFormat, action: &str, module: &str, extra: Option<(&str, &str)>) {
     if fmt != OutputFormat::Table {
@@ -14,66 +29,195 @@ fn json_ok(fmt: OutputFormat, action: &str, module: &str, extra: Option<(&str, &
     }
 }
 
-/// `atlas module list`
-pub fn run(fmt: OutputFormat) -> Result<()> {
+/// Per-probe budget, so a dead endpoint can't stall the listing.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// `atlas configure module list [--no-probe]` — modules, their settings, and
+/// (unless `no_probe`) a concurrent health probe of each enabled module and
+/// the backend.
+pub async fn run(probe: bool, fmt: OutputFormat) -> Result<()> {
+    let output = module_list(probe).await?;
+    render(fmt, &output)
+}
+
+/// `atlas configure module doctor` — probe every enabled module and the
+/// backend. Exits non-zero when any of them is unreachable.
+pub async fn doctor(fmt: OutputFormat) -> Result<()> {
+    let output = module_list(true).await?;
+    render(fmt, &output)?;
+    if !output.all_healthy() {
+        std::io::stdout().flush()?;
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn module_list(probe: bool) -> Result<ModuleListOutput> {
     let config = atlas_core::workspace::load_config()?;
+    let hl = &config.modules.hyperliquid;
+    let zero_x = &config.modules.zero_x;
+    let morpho = &config.modules.morpho;
+    let paper = &config.modules.paper;
 
-    let modules = vec![
-        (
-            "hyperliquid",
-            "Perpetual Trading",
-            config.modules.hyperliquid.enabled,
-            format!("network={}", config.modules.hyperliquid.config.network,),
-        ),
-        (
-            "zero_x",
-            "DEX Aggregator (0x)",
-            config.modules.zero_x.enabled,
-            String::from("proxied via backend"),
-        ),
+    let mut modules = vec![
+        ModuleRow {
+            name: "hyperliquid".into(),
+            description: "Perpetual Trading".into(),
+            enabled: hl.enabled,
+            network: Some(hl.config.network.clone()),
+            config: if hl.config.data_via_backend {
+                format!("network={}, data via backend", hl.config.network)
+            } else {
+                format!("network={}", hl.config.network)
+            },
+            health: None,
+        },
+        ModuleRow {
+            name: "zero_x".into(),
+            description: "DEX Aggregator (0x)".into(),
+            enabled: zero_x.enabled,
+            network: Some(zero_x.config.default_chain.clone()),
+            config: "proxied via backend".into(),
+            health: None,
+        },
+        ModuleRow {
+            name: "morpho".into(),
+            description: "Lending (Morpho Blue)".into(),
+            enabled: morpho.enabled,
+            network: Some(morpho.config.default_chain.clone()),
+            config: format!("chain={}", morpho.config.default_chain),
+            health: None,
+        },
+        ModuleRow {
+            name: "paper".into(),
+            description: "Paper Trading (simulated)".into(),
+            enabled: paper.enabled,
+            network: None,
+            config: format!(
+                "active={} balance={} fee={}bps",
+                paper.config.active, paper.config.starting_balance, paper.config.fee_bps,
+            ),
+            health: None,
+        },
     ];
+    if !probe {
+        return Ok(ModuleListOutput {
+            modules,
+            backend: None,
+        });
+    }
 
-    match fmt {
-        OutputFormat::Json | OutputFormat::JsonPretty => {
-            let json_modules: Vec<serde_json::Value> = modules
-                .iter()
-                .map(|(name, desc, enabled, cfg)| {
-                    serde_json::json!({
-                        "name": name,
-                        "description": desc,
-                        "enabled": enabled,
-                        "config": cfg,
-                    })
-                })
-                .collect();
-            let envelope = serde_json::json!({"ok": true, "data": json_modules});
-            let json = if matches!(fmt, OutputFormat::JsonPretty) {
-                serde_json::to_string_pretty(&envelope)?
-            } else {
-                serde_json::to_string(&envelope)?
-            };
-            println!("{json}");
-        }
-        OutputFormat::Table => {
-            println!(
-                "╔═══════════════════════════════════════════════════════════════════════════════╗"
-            );
-            println!("║ {:<77} ║", "ATLAS OS — MODULES");
-            println!(
-                "╠═══════════════════════════════════════════════════════════════════════════════╣"
-            );
-            for (name, desc, enabled, cfg) in &modules {
-                let status = if *enabled { "✓ ON " } else { "✗ OFF" };
-                println!("║ {} │ {:<14} │ {:<52} ║", status, name, desc);
-                println!("║       │ {:<68} ║", cfg);
-            }
-            println!(
-                "╚═══════════════════════════════════════════════════════════════════════════════╝"
-            );
+    // Paper runs locally on Hyperliquid data, so it has nothing of its own to probe
+    let api_key = config.system.api_key.clone();
+    let (hl_health, zero_x_health, morpho_health, backend) = tokio::join!(
+        probe_if(hl.enabled, probe_hl(&config)),
+        probe_if(zero_x.enabled, probe_zero_x(api_key.clone())),
+        probe_if(
+            morpho.enabled,
+            probe_morpho(api_key, &morpho.config.default_chain)
+        ),
+        timed(probe_backend()),
+    );
+    modules[0].health = hl_health;
+    modules[1].health = zero_x_health;
+    modules[2].health = morpho_health;
+    Ok(ModuleListOutput {
+        modules,
+        backend: Some(backend),
+    })
+}
+
+async fn probe_if(
+    enabled: bool,
+    probe: impl Future<Output = Result<Option<String>>>,
+) -> Option<ModuleHealth> {
+    if enabled {
+        Some(timed(probe).await)
+    } else {
+        None
+    }
+}
+
+/// Run one probe under [`PROBE_TIMEOUT`], measuring its latency.
+async fn timed(probe: impl Future<Output = Result<Option<String>>>) -> ModuleHealth {
+    let start = Instant::now();
+    match tokio::time::timeout(PROBE_TIMEOUT, probe).await {
+        Ok(Ok(detail)) => ModuleHealth::ok(start.elapsed().as_millis() as u64, detail),
+        Ok(Err(e)) => ModuleHealth::unreachable(error_summary(&e)),
+        Err(_) => {
+            ModuleHealth::unreachable(format!("timed out after {}s", PROBE_TIMEOUT.as_secs()))
         }
     }
+}
 
-    Ok(())
+/// First line of the error chain, capped so it stays readable in a listing.
+fn error_summary(e: &anyhow::Error) -> String {
+    let msg = format!("{e:#}");
+    let line = msg.lines().next().unwrap_or_default();
+    match line.char_indices().nth(160) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
+/// Perp universe size, from the exchange or through the backend proxy.
+async fn probe_hl(config: &AppConfig) -> Result<Option<String>> {
+    let hl_cfg = &config.modules.hyperliquid.config;
+    let meta = if hl_cfg.data_via_backend {
+        BackendClient::new(ATLAS_BACKEND_URL)
+            .with_api_key(config.system.api_key.clone())
+            .with_retries(0, Duration::ZERO)
+            .get(&format!("{}/meta-and-asset-ctxs", proxy::PROXY_PATH), &[])
+            .await?
+    } else {
+        let base = if hl_cfg.network == "testnet" {
+            HL_TESTNET_RPC
+        } else {
+            HL_MAINNET_RPC
+        };
+        let http = ExchangeHttp::new(RetryPolicy {
+            max_retries: 0,
+            timeout: PROBE_TIMEOUT,
+            backoff: Duration::ZERO,
+        })?;
+        http.post_json(
+            &format!("{base}/info"),
+            &serde_json::json!({"type": "meta"}),
+        )
+        .await?
+    };
+    let perps = proxy::parse_universe(&meta)?;
+    Ok(Some(format!("{} perps", perps.len())))
+}
+
+/// Chains the 0x API supports, via the backend.
+async fn probe_zero_x(api_key: Option<String>) -> Result<Option<String>> {
+    let chains = ZeroXModule::new(ATLAS_BACKEND_URL.to_string())
+        .with_api_key(api_key)
+        .supported_chains()
+        .await?;
+    Ok(Some(format!("{} chains", chains.chains.len())))
+}
+
+/// One Morpho market on the default chain, via the backend.
+async fn probe_morpho(api_key: Option<String>, chain: &str) -> Result<Option<String>> {
+    BackendClient::new(ATLAS_BACKEND_URL)
+        .with_api_key(api_key)
+        .with_retries(0, Duration::ZERO)
+        .get(
+            "/atlas-os/morpho/markets",
+            &[("chain", chain), ("limit", "1")],
+        )
+        .await?;
+    Ok(None)
+}
+
+async fn probe_backend() -> Result<Option<String>> {
+    BackendClient::new(ATLAS_BACKEND_URL)
+        .with_retries(0, Duration::ZERO)
+        .get("/healthz", &[])
+        .await?;
+    Ok(None)
 }
 
 /// `atlas module enable <name>`
@@ -84,12 +228,14 @@ pub fn enable(name: &str, fmt: OutputFormat) -> Result<()> {
     match resolved {
         "hyperliquid" => config.modules.hyperliquid.enabled = true,
         "zero_x" => config.modules.zero_x.enabled = true,
+        "morpho" => config.modules.morpho.enabled = true,
+        "paper" => config.modules.paper.enabled = true,
         _ => unreachable!(),
     }
     atlas_core::workspace::save_config(&config)?;
 
     if fmt == OutputFormat::Table {
-        println!("✓ Module '{resolved}' enabled.");
+        print_confirmation(&format!("Module '{resolved}' enabled."));
     } else {
         json_ok(fmt, "enable", resolved, None);
     }
@@ -104,12 +250,14 @@ pub fn disable(name: &str, fmt: OutputFormat) -> Result<()> {
     match resolved {
         "hyperliquid" => config.modules.hyperliquid.enabled = false,
         "zero_x" => config.modules.zero_x.enabled = false,
+        "morpho" => config.modules.morpho.enabled = false,
+        "paper" => config.modules.paper.enabled = false,
         _ => unreachable!(),
     }
     atlas_core::workspace::save_config(&config)?;
 
     if fmt == OutputFormat::Table {
-        println!("✗ Module '{resolved}' disabled.");
+        print_confirmation(&format!("Module '{resolved}' disabled."));
     } else {
         json_ok(fmt, "disable", resolved, None);
     }
@@ -119,9 +267,13 @@ pub fn disable(name: &str, fmt: OutputFormat) -> Result<()> {
 /// `atlas configure module set <module> <key> <value> [<value2>]`
 ///
 /// Handles all per-module config keys per PRD:
-///   hl: network, mode, default-size-mode, default-leverage, default-slippage, lot <COIN> <size>
+///   hl: network, data-via-backend, mode, default-size-mode, default-leverage, default-slippage, lot <COIN> <size>,
+///       retries, timeout, backoff-ms, meta-ttl, rate-limit, rate-burst, alias <FROM>=<TO>,
+///       builder-fee-bps, builder-address (need `yes` or a confirmation)
 ///   0x: default-chain, default-slippage-bps
-pub fn config_set(module: &str, values: &[String], fmt: OutputFormat) -> Result<()> {
+///   morpho: default-chain
+///   paper: active, starting-balance, fee-bps, slippage-bps
+pub fn config_set(module: &str, values: &[String], yes: bool, fmt: OutputFormat) -> Result<()> {
     if values.is_empty() {
         anyhow::bail!("Usage: atlas configure module set <module> <key> <value>");
     }
@@ -142,6 +294,16 @@ pub fn config_set(module: &str, values: &[String], fmt: OutputFormat) -> Result<
                     }
                     hl.network = v.to_string();
                 }
+                "data-via-backend" | "data_via_backend" => {
+                    let v = values.get(1).ok_or_else(|| {
+                        anyhow::anyhow!("Usage: set hl data-via-backend <true|false>")
+                    })?;
+                    hl.data_via_backend = match v.to_lowercase().as_str() {
+                        "true" | "on" | "yes" | "1" => true,
+                        "false" | "off" | "no" | "0" => false,
+                        _ => anyhow::bail!("Invalid value for data-via-backend: {v} (true/false)"),
+                    };
+                }
                 "mode" => {
                     let v = values
                         .get(1)
@@ -187,9 +349,104 @@ pub fn config_set(module: &str, values: &[String], fmt: OutputFormat) -> Result<
                         .map_err(|_| anyhow::anyhow!("Invalid lot size"))?;
                     hl.lots.assets.insert(coin.to_uppercase(), size);
                 }
+                "retries" | "max-retries" => {
+                    let v = values
+                        .get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Usage: set hl retries <n>"))?;
+                    hl.http.max_retries = v
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("Invalid retry count: {v}"))?;
+                }
+                "timeout" | "timeout-secs" => {
+                    let v = values
+                        .get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Usage: set hl timeout <secs>"))?;
+                    hl.http.timeout_secs = v
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("Invalid timeout: {v}"))?;
+                }
+                "backoff-ms" | "backoff" => {
+                    let v = values
+                        .get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Usage: set hl backoff-ms <ms>"))?;
+                    hl.http.backoff_ms = v
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("Invalid backoff: {v}"))?;
+                }
+                "meta-ttl" | "meta-ttl-secs" => {
+                    let v = values
+                        .get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Usage: set hl meta-ttl <secs>"))?;
+                    hl.meta_ttl_secs =
+                        v.parse().map_err(|_| anyhow::anyhow!("Invalid TTL: {v}"))?;
+                }
+                "rate-limit" | "requests-per-sec" => {
+                    let v = values
+                        .get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Usage: set hl rate-limit <req/s>"))?;
+                    hl.rate_limit.requests_per_sec = v
+                        .parse()
+                        .ok()
+                        .filter(|r: &f64| r.is_finite() && *r >= 0.0)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid rate limit: {v} (0 disables)"))?;
+                }
+                "rate-burst" | "burst" => {
+                    let v = values
+                        .get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Usage: set hl rate-burst <n>"))?;
+                    hl.rate_limit.burst = v
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("Invalid burst: {v}"))?;
+                }
+                "alias" => {
+                    let usage =
+                        || anyhow::anyhow!("Usage: set hl alias <FROM>=<TO> (TO=off removes)");
+                    let spec = values.get(1).ok_or_else(usage)?;
+                    let (from, to) = match spec.split_once('=') {
+                        Some((from, to)) => (from, to),
+                        None => (spec.as_str(), values.get(2).ok_or_else(usage)?.as_str()),
+                    };
+                    let from = from.trim().to_uppercase();
+                    let to = to.trim();
+                    if from.is_empty() {
+                        return Err(usage());
+                    }
+                    if to.is_empty() || matches!(to.to_lowercase().as_str(), "off" | "none") {
+                        hl.aliases.remove(&from);
+                    } else {
+                        hl.aliases.insert(from, to.to_string());
+                    }
+                }
+                "builder-fee-bps" | "builder_fee_bps" | "builder-fee" => {
+                    let v = values
+                        .get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Usage: set hl builder-fee-bps <0-100>"))?;
+                    let bps: u16 =
+                        v.parse().ok().filter(|b| *b <= 100).ok_or_else(|| {
+                            anyhow::anyhow!("Invalid builder fee: {v} (0-100 bps)")
+                        })?;
+                    let address = hl.builder_address.clone();
+                    if !acknowledge_builder_fee(hl, bps, address, yes, fmt)? {
+                        return Ok(());
+                    }
+                }
+                "builder-address" | "builder_address" => {
+                    let v = values
+                        .get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Usage: set hl builder-address <0x…>"))?;
+                    if !is_evm_address(v) {
+                        anyhow::bail!("Invalid builder address: {v}");
+                    }
+                    let bps = hl.builder_fee_bps;
+                    if !acknowledge_builder_fee(hl, bps, v.to_string(), yes, fmt)? {
+                        return Ok(());
+                    }
+                }
                 _ => anyhow::bail!(
                     "Unknown key '{key}' for hyperliquid.\n\
-                    Available: network, mode, default-size-mode, leverage, slippage, lot"
+                    Available: network, data-via-backend, mode, default-size-mode, leverage, \
+                    slippage, lot, retries, timeout, backoff-ms, meta-ttl, rate-limit, rate-burst, \
+                    alias, builder-fee-bps, builder-address"
                 ),
             }
         }
@@ -216,6 +473,58 @@ pub fn config_set(module: &str, values: &[String], fmt: OutputFormat) -> Result<
                 ),
             }
         }
+        "morpho" => {
+            let morpho = &mut config.modules.morpho.config;
+            match key {
+                "default-chain" | "chain" => {
+                    let v = values.get(1).ok_or_else(|| {
+                        anyhow::anyhow!("Usage: set morpho default-chain <ethereum|base>")
+                    })?;
+                    morpho.default_chain = v.to_lowercase();
+                }
+                _ => anyhow::bail!(
+                    "Unknown key '{key}' for morpho.\n\
+                    Available: default-chain"
+                ),
+            }
+        }
+        "paper" => {
+            let paper = &mut config.modules.paper.config;
+            let number = |usage: &str| -> Result<f64> {
+                let v = values
+                    .get(1)
+                    .ok_or_else(|| anyhow::anyhow!("Usage: {usage}"))?;
+                v.parse::<f64>()
+                    .ok()
+                    .filter(|n| n.is_finite() && *n >= 0.0)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid value for {key}: {v}"))
+            };
+            match key {
+                "active" => {
+                    let v = values
+                        .get(1)
+                        .ok_or_else(|| anyhow::anyhow!("Usage: set paper active <true|false>"))?;
+                    paper.active = match v.to_lowercase().as_str() {
+                        "true" | "on" | "yes" | "1" => true,
+                        "false" | "off" | "no" | "0" => false,
+                        _ => anyhow::bail!("Invalid value for active: {v} (true/false)"),
+                    };
+                }
+                "starting-balance" | "balance" => {
+                    paper.starting_balance = number("set paper starting-balance <usdc>")?;
+                }
+                "fee-bps" | "fee" => {
+                    paper.fee_bps = number("set paper fee-bps <4.5>")?;
+                }
+                "slippage-bps" | "slippage" => {
+                    paper.slippage_bps = number("set paper slippage-bps <1>")?;
+                }
+                _ => anyhow::bail!(
+                    "Unknown key '{key}' for paper.\n\
+                    Available: active, starting-balance, fee-bps, slippage-bps"
+                ),
+            }
+        }
         _ => unreachable!(),
     }
 
@@ -223,7 +532,7 @@ pub fn config_set(module: &str, values: &[String], fmt: OutputFormat) -> Result<
 
     let display_val = values[1..].join(" ");
     if fmt == OutputFormat::Table {
-        println!("✓ {resolved}.{key} = {display_val}");
+        print_confirmation(&format!("{resolved}.{key} = {display_val}"));
     } else {
         json_ok(
             fmt,
@@ -235,10 +544,50 @@ pub fn config_set(module: &str, values: &[String], fmt: OutputFormat) -> Result<
     Ok(())
 }
 
+/// Apply a builder fee change. Anything but the default needs an explicit
+/// acknowledgement, recorded in the config so the override takes effect.
+/// Returns `false` if the user declined.
+fn acknowledge_builder_fee(
+    hl: &mut HyperliquidConfig,
+    bps: u16,
+    address: String,
+    yes: bool,
+    fmt: OutputFormat,
+) -> Result<bool> {
+    let default = BuilderFee::default();
+    let is_default = bps == default.f && address.eq_ignore_ascii_case(&default.b);
+    if !is_default {
+        let fee = if bps == 0 {
+            "disabled".to_string()
+        } else {
+            format!("{bps} bps")
+        };
+        let proceed = confirm_action(
+            "BUILDER FEE OVERRIDE — CONFIRM",
+            &[
+                ("Fee", fee),
+                ("Address", truncate_address(&address)),
+                ("Default", format!("{} bps", default.f)),
+            ],
+            yes,
+            fmt,
+        )?;
+        if !proceed {
+            return Ok(false);
+        }
+    }
+    hl.builder_fee_bps = bps;
+    hl.builder_address = address;
+    hl.builder_fee_acknowledged = !is_default;
+    Ok(true)
+}
+
 fn resolve_module(name: &str) -> Result<&'static str> {
     match name.to_lowercase().as_str() {
         "hyperliquid" | "hl" | "perp" => Ok("hyperliquid"),
         "zero_x" | "0x" | "swap" => Ok("zero_x"),
-        _ => anyhow::bail!("Unknown module: {name}. Available: hyperliquid, zero_x"),
+        "morpho" | "lending" => Ok("morpho"),
+        "paper" | "sim" => Ok("paper"),
+        _ => anyhow::bail!("Unknown module: {name}. Available: hyperliquid, zero_x, morpho, paper"),
     }
 }
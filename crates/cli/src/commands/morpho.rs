@@ -0,0 +1,192 @@
+//! `atlas morpho` — Morpho Blue lending markets and positions.
+//!
+//! Read-only. Data comes from the Atlas backend (`/atlas-os/morpho/*`),
+//! which indexes Morpho Blue; nothing here signs or sends transactions.
+
+use anyhow::Result;
+use atlas_core::output::{
+    render, MorphoMarketOutput, MorphoMarketRow, MorphoMarketsOutput, MorphoPositionRow,
+    MorphoPositionsOutput, OutputFormat,
+};
+use atlas_core::BackendClient;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Envelope<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ApiMarket {
+    id: String,
+    pub(crate) name: String,
+    collateral_asset: String,
+    loan_asset: String,
+    /// Fraction, not percent.
+    pub(crate) supply_apy: Option<f64>,
+    borrow_apy: Option<f64>,
+    utilization: Option<f64>,
+    lltv: Option<f64>,
+    total_supply_usd: Option<f64>,
+    total_borrow_usd: Option<f64>,
+    liquidity_usd: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct ApiPosition {
+    market_id: String,
+    market: String,
+    collateral_asset: String,
+    loan_asset: String,
+    collateral: String,
+    collateral_usd: Option<f64>,
+    borrowed: String,
+    borrowed_usd: Option<f64>,
+    supplied: String,
+    supplied_usd: Option<f64>,
+    health_factor: Option<f64>,
+}
+
+/// Chain name the backend understands; falls back to the module default.
+pub(crate) fn resolve_chain(chain: Option<&str>) -> Result<String> {
+    let chain = match chain {
+        Some(c) => c.to_lowercase(),
+        None => atlas_core::workspace::load_config()?
+            .modules
+            .morpho
+            .config
+            .default_chain
+            .to_lowercase(),
+    };
+    match chain.as_str() {
+        "ethereum" | "eth" | "1" => Ok("ethereum".into()),
+        "base" | "8453" => Ok("base".into()),
+        "arbitrum" | "arb" | "42161" => Ok("arbitrum".into()),
+        _ => anyhow::bail!("Unsupported chain: {chain}. Supported: ethereum, base, arbitrum"),
+    }
+}
+
+fn pct(ratio: Option<f64>) -> String {
+    ratio
+        .map(|r| format!("{:.2}", r * 100.0))
+        .unwrap_or_else(|| "—".into())
+}
+
+fn usd(v: Option<f64>) -> String {
+    v.map(|v| format!("{v:.2}")).unwrap_or_else(|| "—".into())
+}
+
+fn market_row(m: ApiMarket) -> MorphoMarketRow {
+    MorphoMarketRow {
+        id: m.id,
+        name: m.name,
+        collateral_asset: m.collateral_asset,
+        loan_asset: m.loan_asset,
+        supply_apy_pct: pct(m.supply_apy),
+        borrow_apy_pct: pct(m.borrow_apy),
+        utilization_pct: pct(m.utilization),
+        lltv_pct: pct(m.lltv),
+        total_supply_usd: usd(m.total_supply_usd),
+        total_borrow_usd: usd(m.total_borrow_usd),
+        liquidity_usd: usd(m.liquidity_usd),
+    }
+}
+
+fn position_row(p: ApiPosition) -> MorphoPositionRow {
+    MorphoPositionRow {
+        market_id: p.market_id,
+        market: p.market,
+        collateral_asset: p.collateral_asset,
+        loan_asset: p.loan_asset,
+        collateral: p.collateral,
+        collateral_usd: usd(p.collateral_usd),
+        borrowed: p.borrowed,
+        borrowed_usd: usd(p.borrowed_usd),
+        supplied: p.supplied,
+        supplied_usd: usd(p.supplied_usd),
+        health_factor: p.health_factor.map(|h| format!("{h:.2}")),
+    }
+}
+
+fn decode<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> Result<T> {
+    let env: Envelope<T> = serde_json::from_value(value)
+        .map_err(|e| anyhow::anyhow!("Unexpected Morpho response from backend: {e}"))?;
+    Ok(env.data)
+}
+
+/// `atlas morpho markets [--chain ethereum] [--limit 50]`
+pub async fn markets(chain: Option<&str>, limit: usize, fmt: OutputFormat) -> Result<()> {
+    let chain = resolve_chain(chain)?;
+    let limit = limit.to_string();
+    let client = BackendClient::from_config()?;
+    let resp = client
+        .get(
+            "/atlas-os/morpho/markets",
+            &[("chain", chain.as_str()), ("limit", limit.as_str())],
+        )
+        .await?;
+    let markets: Vec<ApiMarket> = decode(resp)?;
+
+    let output = MorphoMarketsOutput {
+        chain,
+        markets: markets.into_iter().map(market_row).collect(),
+    };
+    render(fmt, &output)
+}
+
+/// One market by unique key; `chain` must already be resolved.
+pub(crate) async fn fetch_market(id: &str, chain: &str) -> Result<ApiMarket> {
+    let client = BackendClient::from_config()?;
+    let resp = client
+        .get(
+            &format!("/atlas-os/morpho/markets/{}", id.to_lowercase()),
+            &[("chain", chain)],
+        )
+        .await?;
+    decode(resp)
+}
+
+/// `atlas morpho market <ID> [--chain ethereum]`
+pub async fn market(id: &str, chain: Option<&str>, fmt: OutputFormat) -> Result<()> {
+    let chain = resolve_chain(chain)?;
+    let market = fetch_market(id, &chain).await?;
+
+    let output = MorphoMarketOutput {
+        chain,
+        market: market_row(market),
+    };
+    render(fmt, &output)
+}
+
+/// `atlas morpho position [--address 0x...] [--chain ethereum]`
+///
+/// Defaults to the active profile's address. An address with nothing open
+/// renders an empty list; backend or network failures are errors.
+pub async fn position(address: Option<&str>, chain: Option<&str>, fmt: OutputFormat) -> Result<()> {
+    let chain = resolve_chain(chain)?;
+    let address = match address {
+        Some(a) => a.to_string(),
+        None => atlas_core::auth::AuthManager::get_active_signer()
+            .map(|s| format!("{:#x}", alloy::signers::Signer::address(&s)))
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "No active profile. Pass --address or run: atlas profile use <name>"
+                )
+            })?,
+    };
+    let client = BackendClient::from_config()?;
+    let resp = client
+        .get(
+            &format!("/atlas-os/morpho/positions/{address}"),
+            &[("chain", chain.as_str())],
+        )
+        .await?;
+    let positions: Vec<ApiPosition> = decode(resp)?;
+
+    let output = MorphoPositionsOutput {
+        address,
+        chain,
+        positions: positions.into_iter().map(position_row).collect(),
+    };
+    render(fmt, &output)
+}
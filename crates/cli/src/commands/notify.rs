@@ -0,0 +1,277 @@
+//! `atlas notify` — push fills, order cancels and liquidation warnings to
+//! the webhook / exec hook set with `atlas configure notify set`.
+
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::StreamExt;
+use hypersdk::hypercore::{
+    types::{Incoming, Subscription},
+    ws::Event,
+};
+use rust_decimal::prelude::*;
+
+use atlas_core::config::NotifyConfig;
+use atlas_core::notify::{self, Delivery, LiqWarning, LiqWatch, Notification};
+use atlas_core::output::{render, NotifyTestOutput, OutputFormat};
+use atlas_core::traits::PerpModule;
+use atlas_core::types::{Protocol, Side};
+use atlas_core::workspace::load_config;
+use atlas_core::AuthManager;
+
+fn require_target(config: &NotifyConfig) -> Result<()> {
+    if !config.has_target() {
+        anyhow::bail!(
+            "No notification target. Run: atlas configure notify set --webhook <URL> (or --exec <COMMAND>)"
+        );
+    }
+    Ok(())
+}
+
+/// `atlas notify test` — deliver a sample event to every target. Exits
+/// non-zero when any of them fails.
+pub async fn test(fmt: OutputFormat) -> Result<()> {
+    let config = load_config()?;
+    require_target(&config.notify)?;
+    let address = AuthManager::get_active_signer()
+        .ok()
+        .map(|s| alloy::signers::local::PrivateKeySigner::address(&s).to_string());
+    let sample = Notification::sample(&config.modules.hyperliquid.config.network, address);
+    let deliveries = notify::deliver(&config.notify, &sample).await;
+    let failed = deliveries.iter().any(|d| !d.ok);
+    render(
+        fmt,
+        &NotifyTestOutput {
+            event: sample.event,
+            deliveries,
+        },
+    )?;
+    if failed {
+        std::io::stdout().flush()?;
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// `atlas notify watch [--poll-secs 30]`
+///
+/// Runs until killed. Fills and cancels come from the user WebSocket;
+/// positions are polled every `poll_secs` for the liquidation check.
+/// Deliveries run in the background so a slow target never holds up the
+/// stream.
+pub async fn watch(poll_secs: u64, fmt: OutputFormat) -> Result<()> {
+    if poll_secs == 0 {
+        anyhow::bail!("--poll-secs must be at least 1");
+    }
+    let config = load_config()?;
+    require_target(&config.notify)?;
+    let targets = Arc::new(config.notify.clone());
+    let network = config.modules.hyperliquid.config.network.clone();
+    let warn_pct = Decimal::from_f64(config.notify.liq_warn_pct).unwrap_or_default();
+
+    let signer = AuthManager::get_active_signer()?;
+    let address = alloy::signers::local::PrivateKeySigner::address(&signer);
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(Some(&Protocol::Hyperliquid.to_string()))?.clone();
+
+    let core = super::stream::build_ws_client(network == "testnet");
+    let mut ws = core.websocket();
+    ws.subscribe(Subscription::UserFills { user: address });
+    ws.subscribe(Subscription::OrderUpdates { user: address });
+
+    // The fills channel opens with a snapshot of past fills; skip those
+    let started_ms = chrono::Utc::now().timestamp_millis() as u64;
+    let address = address.to_string();
+    let mut liq_watch = LiqWatch::default();
+    let mut poll = tokio::time::interval(Duration::from_secs(poll_secs));
+
+    if fmt == OutputFormat::Table {
+        let mut to = Vec::new();
+        if let Some(url) = &targets.webhook {
+            to.push(format!("webhook {url}"));
+        }
+        if targets.exec.is_some() {
+            to.push("exec hook".to_string());
+        }
+        eprintln!(
+            "🔔 Watching {address} → {} (liq warning within {}%). Ctrl+C to stop.\n",
+            to.join(", "),
+            targets.liq_warn_pct
+        );
+    }
+
+    loop {
+        tokio::select! {
+            event = ws.next() => {
+                let Some(event) = event else { break };
+                let mut events = Vec::new();
+                match event {
+                    Event::Message(Incoming::UserFills { user: _, fills }) => {
+                        for fill in fills.iter().filter(|f| f.time >= started_ms) {
+                            let data = serde_json::json!({
+                                "order_id": fill.oid,
+                                "symbol": fill.coin,
+                                "side": format!("{:?}", fill.side).to_lowercase(),
+                                "size": fill.sz,
+                                "price": fill.px,
+                                "fee": fill.fee,
+                                "timestamp": fill.time,
+                            });
+                            events.push(("fill", data));
+                        }
+                    }
+                    Event::Message(Incoming::OrderUpdates(updates)) => {
+                        for update in &updates {
+                            let status = format!("{:?}", update.status).to_lowercase();
+                            if !status.contains("cancel") {
+                                continue;
+                            }
+                            let data = serde_json::json!({
+                                "order_id": update.order.oid,
+                                "symbol": update.order.coin,
+                                "side": format!("{:?}", update.order.side).to_lowercase(),
+                                "size": update.order.sz,
+                                "price": update.order.limit_px,
+                                "status": status,
+                                "timestamp": update.order.timestamp,
+                            });
+                            events.push(("order_cancelled", data));
+                        }
+                    }
+                    _ => {}
+                }
+                for (event, data) in events {
+                    let n = Notification::new(event, &network, Some(address.clone()), data);
+                    dispatch(&targets, n, fmt);
+                }
+            }
+            _ = poll.tick() => {
+                match liquidation_warnings(perp.as_ref(), &mut liq_watch, warn_pct).await {
+                    Ok(warnings) => {
+                        for w in warnings {
+                            let data = serde_json::to_value(&w)?;
+                            let n = Notification::new(
+                                "liquidation_warning",
+                                &network,
+                                Some(address.clone()),
+                                data,
+                            );
+                            dispatch(&targets, n, fmt);
+                        }
+                    }
+                    Err(e) => tracing::warn!("notify: position check failed: {e:#}"),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Positions that just came within `warn_pct` of liquidation.
+async fn liquidation_warnings(
+    perp: &dyn PerpModule,
+    watch: &mut LiqWatch,
+    warn_pct: Decimal,
+) -> Result<Vec<LiqWarning>> {
+    let (positions, ctxs) = tokio::join!(perp.positions(), perp.asset_contexts());
+    let positions = positions.map_err(|e| anyhow::anyhow!("{e}"))?;
+    let ctxs = ctxs.map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    let open: Vec<String> = positions.iter().map(|p| p.symbol.clone()).collect();
+    watch.retain_open(&open);
+
+    let mut warnings = Vec::new();
+    for p in &positions {
+        let Some(liquidation_price) = p.liquidation_price else {
+            continue;
+        };
+        let mark = p.mark_price.or_else(|| {
+            ctxs.iter()
+                .find(|c| c.symbol == p.symbol)
+                .and_then(|c| c.mark_price)
+        });
+        let Some(mark_price) = mark else {
+            continue;
+        };
+        let Some(distance) = notify::liq_distance_pct(mark_price, liquidation_price) else {
+            continue;
+        };
+        if watch.check(&p.symbol, distance, warn_pct) {
+            warnings.push(LiqWarning {
+                symbol: p.symbol.clone(),
+                side: if p.side == Side::Buy { "long" } else { "short" }.into(),
+                size: p.size.abs(),
+                mark_price,
+                liquidation_price,
+                distance_pct: distance.round_dp(2),
+                warn_pct,
+            });
+        }
+    }
+    Ok(warnings)
+}
+
+/// Deliver in the background and report the outcome when done.
+fn dispatch(targets: &Arc<NotifyConfig>, notification: Notification, fmt: OutputFormat) {
+    let targets = Arc::clone(targets);
+    tokio::spawn(async move {
+        let deliveries = notify::deliver(&targets, &notification).await;
+        report(&notification, &deliveries, fmt);
+    });
+}
+
+/// One line per event: a `{"event":"notify",...}` object for JSON.
+fn report(n: &Notification, deliveries: &[Delivery], fmt: OutputFormat) {
+    match fmt {
+        OutputFormat::Json | OutputFormat::JsonPretty => {
+            let line = serde_json::json!({
+                "event": "notify",
+                "notification": n,
+                "deliveries": deliveries,
+            });
+            println!("{line}");
+        }
+        OutputFormat::Table => {
+            let d = &n.data;
+            let text = |key: &str| match &d[key] {
+                serde_json::Value::String(s) => s.clone(),
+                v => v.to_string(),
+            };
+            let what = match n.event.as_str() {
+                "liquidation_warning" => format!(
+                    "⚠️  LIQ {} {} mark {} is {}% from liq {}",
+                    text("symbol"),
+                    text("side"),
+                    text("mark_price"),
+                    text("distance_pct"),
+                    text("liquidation_price")
+                ),
+                "order_cancelled" => format!(
+                    "📋 CANCEL {} {} {} @ {}",
+                    text("symbol"),
+                    text("side"),
+                    text("size"),
+                    text("price")
+                ),
+                _ => format!(
+                    "📝 FILL {} {} {} @ {}",
+                    text("symbol"),
+                    text("side"),
+                    text("size"),
+                    text("price")
+                ),
+            };
+            let outcome: Vec<String> = deliveries
+                .iter()
+                .map(|r| match &r.error {
+                    None => format!("{} ok", r.target),
+                    Some(e) => format!("{} FAILED after {} attempts: {e}", r.target, r.attempts),
+                })
+                .collect();
+            println!("{what}  → {}", outcome.join(", "));
+        }
+    }
+}
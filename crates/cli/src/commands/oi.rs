@@ -0,0 +1,177 @@
+//! `atlas market hyperliquid oi` — open interest / volume history.
+//!
+//! Hyperliquid only exposes current OI, so history is built locally:
+//! `oi record` samples `metaAndAssetCtxs` into the `oi_snapshots` table and
+//! `oi <ticker>` reads a window back out of it.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use atlas_core::db::{AtlasDb, DbOiSnapshot};
+use atlas_core::output::{render, OiHistoryOutput, OiPoint, OutputFormat};
+use rust_decimal::prelude::*;
+
+use super::helpers::format_ms;
+
+const PROTOCOL: &str = "hyperliquid";
+
+/// Moves smaller than this (in %) count as flat when reading divergence.
+const FLAT_PCT: f64 = 0.5;
+
+/// Shortest allowed sampling interval — one `metaAndAssetCtxs` call per tick.
+const MIN_INTERVAL_SECS: u64 = 10;
+
+/// `atlas market oi record [--interval 300] [--retention-days 30] [--once]`
+pub async fn record(
+    interval_secs: u64,
+    retention_days: u64,
+    once: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    if interval_secs < MIN_INTERVAL_SECS {
+        anyhow::bail!("--interval must be at least {MIN_INTERVAL_SECS} seconds");
+    }
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
+    let db = AtlasDb::open()?;
+    let retention_ms = retention_days.saturating_mul(86_400_000) as i64;
+
+    if !once && fmt == OutputFormat::Table {
+        eprintln!(
+            "⏺ Recording OI snapshots every {interval_secs}s, keeping {retention_days} days (Ctrl+C to stop)...\n"
+        );
+    }
+
+    loop {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        match perp.asset_contexts().await {
+            Ok(ctxs) => {
+                let snapshots: Vec<DbOiSnapshot> = ctxs
+                    .iter()
+                    .filter_map(|c| {
+                        Some(DbOiSnapshot {
+                            protocol: PROTOCOL.to_string(),
+                            coin: c.symbol.clone(),
+                            time_ms: now_ms,
+                            open_interest: c.open_interest?.to_string(),
+                            mark_px: c.mark_price?.to_string(),
+                            volume_24h: c.volume_24h.unwrap_or_default().to_string(),
+                            funding: c.funding_rate.unwrap_or_default().to_string(),
+                        })
+                    })
+                    .collect();
+                let inserted = db.insert_oi_snapshots(&snapshots)?;
+                let pruned = db.prune_oi_snapshots(now_ms - retention_ms)?;
+                match fmt {
+                    OutputFormat::Json | OutputFormat::JsonPretty => {
+                        let line = serde_json::json!({
+                            "event": "oi_snapshot",
+                            "timestamp": now_ms,
+                            "markets": inserted,
+                            "pruned": pruned,
+                        });
+                        println!("{line}");
+                    }
+                    OutputFormat::Table => println!(
+                        "{}  recorded {inserted} markets, pruned {pruned} old rows",
+                        format_ms(now_ms)
+                    ),
+                }
+            }
+            // A failed sample leaves a gap; the next tick tries again
+            Err(e) if !once => eprintln!("⚠ {}  snapshot failed: {e}", format_ms(now_ms)),
+            Err(e) => return Err(anyhow::anyhow!("{e}")),
+        }
+
+        if once {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+    }
+}
+
+/// `atlas market oi <TICKER> [--hours 24]`
+pub fn history(ticker: &str, hours: u64, fmt: OutputFormat) -> Result<()> {
+    let coin = ticker.to_uppercase();
+    let db = AtlasDb::open()?;
+    let from_ms = chrono::Utc::now().timestamp_millis() - (hours as i64) * 3_600_000;
+    let rows = db.query_oi_snapshots(PROTOCOL, &coin, from_ms)?;
+
+    let (Some(first), Some(last)) = (rows.first(), rows.last()) else {
+        anyhow::bail!(
+            "No OI snapshots for {coin} in the last {hours}h. Start the sampler with: atlas market hyperliquid oi record"
+        );
+    };
+    if rows.len() < 2 {
+        anyhow::bail!(
+            "Only one OI snapshot for {coin} in the last {hours}h — let `oi record` run longer"
+        );
+    }
+
+    let dec = |s: &str| Decimal::from_str(s).unwrap_or_default();
+    let (oi_start, oi_end) = (dec(&first.open_interest), dec(&last.open_interest));
+    let (px_start, px_end) = (dec(&first.mark_px), dec(&last.mark_px));
+    let (vol_start, vol_end) = (dec(&first.volume_24h), dec(&last.volume_24h));
+    let oi_pct = pct_change(oi_start, oi_end);
+    let px_pct = pct_change(px_start, px_end);
+
+    let output = OiHistoryOutput {
+        ticker: coin,
+        hours,
+        samples: rows.len(),
+        from: format_ms(first.time_ms),
+        to: format_ms(last.time_ms),
+        oi_start: oi_start.normalize().to_string(),
+        oi_end: oi_end.normalize().to_string(),
+        oi_change_pct: fmt_pct(oi_pct),
+        oi_usd_end: (oi_end * px_end).round_dp(0).to_string(),
+        price_start: px_start.normalize().to_string(),
+        price_end: px_end.normalize().to_string(),
+        price_change_pct: fmt_pct(px_pct),
+        volume_24h_start: vol_start.round_dp(0).to_string(),
+        volume_24h_end: vol_end.round_dp(0).to_string(),
+        volume_24h_change: (vol_end - vol_start).round_dp(0).to_string(),
+        divergence: divergence(oi_pct, px_pct).to_string(),
+        series: rows
+            .iter()
+            .map(|r| OiPoint {
+                time: format_ms(r.time_ms),
+                open_interest: r.open_interest.clone(),
+                mark_price: r.mark_px.clone(),
+                volume_24h: r.volume_24h.clone(),
+            })
+            .collect(),
+    };
+    render(fmt, &output)
+}
+
+fn pct_change(start: Decimal, end: Decimal) -> Option<f64> {
+    if start.is_zero() {
+        return None;
+    }
+    ((end - start) / start * Decimal::ONE_HUNDRED).to_f64()
+}
+
+fn fmt_pct(p: Option<f64>) -> String {
+    p.map(|p| format!("{p:.2}")).unwrap_or_else(|| "—".into())
+}
+
+/// Classic OI-vs-price read: rising OI means new positions are opening,
+/// and the price direction says which side is the aggressor.
+fn divergence(oi_pct: Option<f64>, px_pct: Option<f64>) -> &'static str {
+    let dir = |p: Option<f64>| match p {
+        Some(p) if p >= FLAT_PCT => 1,
+        Some(p) if p <= -FLAT_PCT => -1,
+        _ => 0,
+    };
+    match (dir(oi_pct), dir(px_pct)) {
+        (1, 1) => "longs building (OI ↑, price ↑)",
+        (1, -1) => "shorts building (OI ↑, price ↓)",
+        (-1, 1) => "short covering (OI ↓, price ↑)",
+        (-1, -1) => "long unwinding (OI ↓, price ↓)",
+        (1, 0) => "positions building, price flat",
+        (-1, 0) => "positions closing, price flat",
+        (0, 1) | (0, -1) => "price moving without new OI",
+        _ => "quiet",
+    }
+}
@@ -0,0 +1,25 @@
+//! `atlas paper` — manage the simulated paper-trading account.
+
+use anyhow::Result;
+use atlas_core::output::{render, OutputFormat, PaperResetOutput};
+use rust_decimal::prelude::*;
+
+/// `atlas paper reset [--balance <usdc>]` — wipe positions, orders and
+/// fills, starting over at `balance` (default: the configured starting balance).
+pub fn reset(balance: Option<f64>, fmt: OutputFormat) -> Result<()> {
+    let config = atlas_core::workspace::load_config()?;
+    let balance = balance.unwrap_or(config.modules.paper.config.starting_balance);
+    let balance = Decimal::from_f64(balance)
+        .filter(|b| b.is_sign_positive() && !b.is_zero())
+        .ok_or_else(|| anyhow::anyhow!("Invalid balance: {balance} (must be positive)"))?;
+
+    let fills_cleared = atlas_paper::reset(balance)?;
+    render(
+        fmt,
+        &PaperResetOutput {
+            balance: balance.normalize().to_string(),
+            fills_cleared,
+        },
+    )?;
+    Ok(())
+}
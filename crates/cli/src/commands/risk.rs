@@ -1,8 +1,14 @@
 use anyhow::Result;
-use atlas_core::output::RiskCalcOutput;
+use atlas_core::db::{AtlasDb, DbFill, FillFilter};
+use atlas_core::fmt::format_decimal;
+use atlas_core::liquidation::{self, Liquidation};
 use atlas_core::output::{render, OutputFormat};
+use atlas_core::output::{LiquidationOutput, LiquidationScenario, RiskCalcOutput};
+use atlas_core::output::{RiskSuggestOutput, SizingStatsOutput};
 use atlas_core::parse;
 use atlas_core::risk::{self, RiskInput};
+use atlas_core::sizing::{self, SizingInput, SizingMethod, SizingParams, TradeStats};
+use atlas_core::types::{MarginMode, MarginState};
 use rust_decimal::prelude::*;
 
 /// `atlas risk calc <coin> <side> <entry_price> [--stop <price>] [--leverage <n>]`
@@ -18,7 +24,7 @@ pub async fn calculate(
     let config = atlas_core::workspace::load_config()?;
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
 
     // Get account value and positions from module
     let balances = perp.balances().await.map_err(|e| anyhow::anyhow!("{e}"))?;
@@ -41,6 +47,31 @@ pub async fn calculate(
 
     let output = risk::calculate_position(&config, &config.modules.hyperliquid.config.risk, &input);
 
+    // In cross mode other positions share the equity, so the isolated
+    // estimate is replaced by the account-wide model when it is available.
+    let mut est_liquidation = output.est_liquidation;
+    let mut liq_model = "isolated";
+    if let Ok(state) = perp.margin_state().await {
+        let cross = state
+            .positions
+            .iter()
+            .find(|p| p.coin == coin_upper)
+            .map_or(true, |p| p.mode == MarginMode::Cross);
+        let signed = if is_buy { output.size } else { -output.size };
+        if let (true, Some(size), Some(entry)) = (
+            cross,
+            Decimal::from_f64(signed),
+            Decimal::from_f64(entry_price),
+        ) {
+            let after = liquidation::with_added(&state, &coin_upper, size, entry);
+            est_liquidation = liquidation::liquidation(&after, &coin_upper)
+                .and_then(|l| l.price)
+                .and_then(|p| p.to_f64())
+                .unwrap_or(0.0);
+            liq_model = "cross";
+        }
+    }
+
     let current_positions = positions.len();
     let total_exposure: f64 = positions
         .iter()
@@ -73,7 +104,8 @@ pub async fn calculate(
         notional: output.notional,
         stop_loss: output.stop_loss,
         take_profit: output.take_profit,
-        est_liquidation: output.est_liquidation,
+        est_liquidation,
+        liq_model: liq_model.into(),
         risk_usd: output.risk_usd,
         risk_pct: output.risk_pct,
         margin: output.margin,
@@ -133,6 +165,7 @@ pub fn calculate_offline(
         stop_loss: output.stop_loss,
         take_profit: output.take_profit,
         est_liquidation: output.est_liquidation,
+        liq_model: "isolated".into(),
         risk_usd: output.risk_usd,
         risk_pct: output.risk_pct,
         margin: output.margin,
@@ -144,3 +177,225 @@ pub fn calculate_offline(
     render(fmt, &risk_output)?;
     Ok(())
 }
+
+/// `atlas risk suggest <coin> --side <long|short> --entry <p> --stop <p> [--method kelly|fixed-frac] [--fraction <f>] [--leverage <n>]`
+#[allow(clippy::too_many_arguments)]
+pub async fn suggest(
+    coin: &str,
+    side: &str,
+    entry_price: f64,
+    stop_loss: f64,
+    method: &str,
+    fraction: Option<f64>,
+    leverage: Option<u32>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let is_buy = parse::parse_side(side)?;
+    let method = SizingMethod::parse(method)?;
+    if let Some(f) = fraction {
+        if !(f > 0.0 && f <= 1.0) {
+            anyhow::bail!("--fraction must be in (0, 1], e.g. 0.02 for 2%");
+        }
+    }
+    let config = atlas_core::workspace::load_config()?;
+    let hl = &config.modules.hyperliquid.config;
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+    let coin_upper = perp.resolve_symbol(coin)?;
+
+    let balances = perp.balances().await.map_err(|e| anyhow::anyhow!("{e}"))?;
+    let account_value = balances
+        .first()
+        .map(|b| b.total.to_f64().unwrap_or(0.0))
+        .unwrap_or(0.0);
+
+    // The coin's own record once it is long enough, else the account's
+    let fills = AtlasDb::open()?.query_fills(&FillFilter {
+        protocol: Some("hyperliquid".into()),
+        coin: None,
+        from_ms: None,
+        to_ms: None,
+        limit: None,
+    })?;
+    let coin_fills: Vec<DbFill> = fills
+        .iter()
+        .filter(|f| f.coin == coin_upper)
+        .cloned()
+        .collect();
+    let (scope, stats) = sizing::select_stats(
+        TradeStats::from_outcomes(&sizing::trade_outcomes(&coin_fills)),
+        TradeStats::from_outcomes(&sizing::trade_outcomes(&fills)),
+        hl.risk.kelly_min_trades,
+    );
+
+    let params = SizingParams {
+        kelly_multiplier: hl.risk.kelly_multiplier,
+        kelly_cap: hl.risk.kelly_cap,
+        min_trades: hl.risk.kelly_min_trades,
+        fraction: fraction.unwrap_or_else(|| hl.risk.effective_risk_pct(&coin_upper)),
+        max_size: hl.risk.max_size(&coin_upper),
+    };
+    let leverage = leverage.unwrap_or(hl.default_leverage);
+    let input = SizingInput {
+        account_value,
+        entry: entry_price,
+        stop: stop_loss,
+        is_buy,
+        leverage,
+    };
+    let suggestion = sizing::suggest(method, &stats, &params, &input)?;
+
+    let lots = if hl.is_cfd() {
+        hl.lots.size_to_lots(&coin_upper, suggestion.size)
+    } else {
+        suggestion.size
+    };
+
+    let output = RiskSuggestOutput {
+        coin: coin_upper,
+        side: if is_buy {
+            "long".into()
+        } else {
+            "short".into()
+        },
+        entry_price,
+        stop_loss,
+        requested: method.as_str().into(),
+        method: suggestion.method.as_str().into(),
+        stats: SizingStatsOutput {
+            scope: scope.into(),
+            trades: stats.trades,
+            wins: stats.wins,
+            losses: stats.losses,
+            win_rate: stats.win_rate(),
+            avg_win: stats.avg_win,
+            avg_loss: stats.avg_loss,
+            payoff_ratio: stats.payoff_ratio(),
+        },
+        kelly: suggestion.kelly,
+        kelly_multiplier: params.kelly_multiplier,
+        kelly_cap: params.kelly_cap,
+        account_value,
+        risk_pct: suggestion.risk_fraction,
+        risk_usd: suggestion.risk_usd,
+        size: suggestion.size,
+        lots,
+        notional: suggestion.notional,
+        margin: suggestion.margin,
+        leverage,
+        warnings: suggestion.warnings,
+    };
+
+    render(fmt, &output)?;
+    Ok(())
+}
+
+/// `atlas hl risk liq <coin> [--add-size <s> [--at <price>]]`
+pub async fn liquidation(
+    coin: &str,
+    add_size: Option<f64>,
+    at: Option<f64>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+    let coin = perp.resolve_symbol(coin)?;
+    let state = perp
+        .margin_state()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    let position = state.positions.iter().find(|p| p.coin == coin);
+    if position.is_none() && add_size.is_none() {
+        anyhow::bail!("No open {coin} position. Pass --add-size to model one.");
+    }
+
+    let mark = match position {
+        Some(p) => p.mark_price,
+        None => match at {
+            Some(px) => to_decimal(px, "--at")?,
+            None => {
+                perp.ticker(&coin)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{e}"))?
+                    .mid_price
+            }
+        },
+    };
+
+    let after = match add_size {
+        Some(add) => {
+            let add = to_decimal(add, "--add-size")?;
+            let at = match at {
+                Some(px) => to_decimal(px, "--at")?,
+                None => mark,
+            };
+            let next = liquidation::with_added(&state, &coin, add, at);
+            let liq = liquidation::liquidation(&next, &coin);
+            Some(LiquidationScenario {
+                add_size: add.normalize().to_string(),
+                at_price: format_decimal(at, 4),
+                size: position_size(&next, &coin).normalize().to_string(),
+                equity: liq.as_ref().map(|l| format_decimal(l.equity, 2)),
+                maintenance_margin: liq.as_ref().map(|l| format_decimal(l.maintenance, 2)),
+                liquidation_price: liq_price(liq.as_ref()),
+                distance_pct: distance_pct(mark, liq.as_ref()),
+            })
+        }
+        None => None,
+    };
+
+    let now = liquidation::liquidation(&state, &coin);
+    let size = position_size(&state, &coin);
+    let output = LiquidationOutput {
+        coin: coin.clone(),
+        margin_mode: match position.map(|p| &p.mode) {
+            Some(MarginMode::Isolated { .. }) => "isolated".into(),
+            _ => "cross".into(),
+        },
+        side: position.map(|p| {
+            if p.size.is_sign_negative() {
+                "short".into()
+            } else {
+                "long".into()
+            }
+        }),
+        size: size.normalize().to_string(),
+        mark_price: Some(format_decimal(mark, 4)),
+        equity: now.as_ref().map(|l| format_decimal(l.equity, 2)),
+        maintenance_margin: now.as_ref().map(|l| format_decimal(l.maintenance, 2)),
+        liquidation_price: liq_price(now.as_ref()),
+        distance_pct: distance_pct(mark, now.as_ref()),
+        after,
+    };
+
+    render(fmt, &output)?;
+    Ok(())
+}
+
+fn to_decimal(v: f64, flag: &str) -> Result<Decimal> {
+    Decimal::from_f64(v).ok_or_else(|| anyhow::anyhow!("Invalid {flag}: {v}"))
+}
+
+fn position_size(state: &MarginState, coin: &str) -> Decimal {
+    state
+        .positions
+        .iter()
+        .find(|p| p.coin == coin)
+        .map_or(Decimal::ZERO, |p| p.size)
+}
+
+fn liq_price(liq: Option<&Liquidation>) -> Option<String> {
+    liq?.price.map(|p| format_decimal(p, 4))
+}
+
+fn distance_pct(mark: Decimal, liq: Option<&Liquidation>) -> Option<String> {
+    let price = liq?.price?;
+    if mark.is_zero() {
+        return None;
+    }
+    Some(format_decimal(
+        (mark - price).abs() / mark * Decimal::ONE_HUNDRED,
+        2,
+    ))
+}
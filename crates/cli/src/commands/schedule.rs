@@ -0,0 +1,247 @@
+//! `atlas schedule` — recurring atlas commands, e.g. a daily DCA buy.
+//!
+//! Jobs are stored in the local DB. `atlas schedule run` is the executor:
+//! it sleeps until the next job is due, runs the stored command in-process
+//! through the same dispatcher as the CLI, and records the outcome.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use anyhow::Result;
+use atlas_core::db::{AtlasDb, DbSchedule, DbScheduleRun};
+use atlas_core::output::{
+    render, OutputFormat, ScheduleChangeOutput, ScheduleListOutput, ScheduleRow, ScheduleRunRow,
+};
+use atlas_core::parse::parse_age_ms;
+use atlas_core::prompt::confirm_action;
+use atlas_core::schedule::{self, CatchUp, Due};
+
+use super::helpers::format_ms;
+
+/// Longest the executor sleeps before looking for new or removed jobs.
+const POLL_MS: i64 = 60_000;
+
+/// Runs one stored command line; supplied by `main` so jobs go through the
+/// CLI's own dispatcher.
+pub type Executor = fn(String, OutputFormat) -> Pin<Box<dyn Future<Output = Result<()>>>>;
+
+fn run_row(run: DbScheduleRun) -> ScheduleRunRow {
+    ScheduleRunRow {
+        schedule_id: run.schedule_id,
+        due_ms: run.due_ms,
+        started_ms: run.started_ms,
+        status: run.status,
+        missed: run.missed,
+        order_id: run.oid,
+        price: run.fill_price,
+        error: run.error,
+    }
+}
+
+fn schedule_row(job: DbSchedule, last_run: Option<DbScheduleRun>) -> ScheduleRow {
+    let done = job.is_done();
+    ScheduleRow {
+        id: job.id,
+        every: schedule::format_every(job.every_ms),
+        command: job.command,
+        at: job.at,
+        max_runs: job.max_runs,
+        runs: job.runs,
+        status: if done { "done" } else { "active" }.into(),
+        next_run_ms: (!done).then_some(job.next_run_ms),
+        last_run: last_run.map(run_row),
+    }
+}
+
+/// `atlas schedule add "<COMMAND>" --every 24h [--at 09:00] [--max-runs 30] [--yes]`
+///
+/// `command` has already been checked by the CLI parser, which only lets
+/// trading commands through. Jobs run without their confirmation prompts,
+/// so this is where the user confirms, and only `--yes` skips it, not
+/// `trading.confirm`.
+pub fn add(
+    command: &str,
+    every: &str,
+    at: Option<&str>,
+    max_runs: Option<u32>,
+    yes: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let every_ms = parse_age_ms(every)
+        .map_err(|_| anyhow::anyhow!("Invalid --every '{every}'. Use e.g. 30m, 24h or 1w."))?
+        as i64;
+    if every_ms == 0 {
+        anyhow::bail!("--every must be at least 1m");
+    }
+    if max_runs == Some(0) {
+        anyhow::bail!("--max-runs must be at least 1");
+    }
+    let at_minutes = at.map(schedule::parse_at).transpose()?;
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let next_run_ms = schedule::first_run_ms(now_ms, every_ms, at_minutes);
+
+    let proceed = confirm_action(
+        "SCHEDULE — CONFIRM",
+        &[
+            ("Command", format!("atlas {command}")),
+            ("Every", schedule::format_every(every_ms)),
+            ("First run", format!("{} UTC", format_ms(next_run_ms))),
+            (
+                "Max runs",
+                max_runs.map_or_else(|| "unlimited".into(), |n| n.to_string()),
+            ),
+            ("Prompts", "skipped when the job runs".into()),
+        ],
+        yes,
+        fmt,
+    )?;
+    if !proceed {
+        return Ok(());
+    }
+
+    let at = at.map(str::trim);
+    let job =
+        AtlasDb::open()?.add_schedule(command, every_ms, at, max_runs, next_run_ms, now_ms)?;
+    render(
+        fmt,
+        &ScheduleChangeOutput {
+            action: "added".into(),
+            schedule: schedule_row(job, None),
+        },
+    )
+}
+
+/// `atlas schedule list`
+pub fn list(fmt: OutputFormat) -> Result<()> {
+    let db = AtlasDb::open()?;
+    let schedules = db
+        .schedules()?
+        .into_iter()
+        .map(|job| {
+            let last = db.last_schedule_run(job.id)?;
+            Ok(schedule_row(job, last))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    render(fmt, &ScheduleListOutput { schedules })
+}
+
+/// `atlas schedule remove <ID>`
+pub fn remove(id: i64, fmt: OutputFormat) -> Result<()> {
+    let job = AtlasDb::open()?
+        .remove_schedule(id)?
+        .ok_or_else(|| anyhow::anyhow!("No schedule #{id}. See: atlas schedule list"))?;
+    render(
+        fmt,
+        &ScheduleChangeOutput {
+            action: "removed".into(),
+            schedule: schedule_row(job, None),
+        },
+    )
+}
+
+/// `atlas schedule run [--catch-up skip|run-once]`
+///
+/// Runs until killed. Jobs run one at a time; a failing job is recorded
+/// and the executor carries on.
+pub async fn run(catch_up: &str, exec: Executor, fmt: OutputFormat) -> Result<()> {
+    let catch_up = CatchUp::parse(catch_up)?;
+    let db = AtlasDb::open()?;
+    let up_since_ms = chrono::Utc::now().timestamp_millis();
+    atlas_core::prompt::set_unattended(true);
+
+    if fmt == OutputFormat::Table {
+        let active = db.schedules()?.iter().filter(|j| !j.is_done()).count();
+        println!("⏱  Schedule executor started: {active} active job(s). Ctrl-C to stop.");
+    }
+
+    loop {
+        let mut wait_ms = POLL_MS;
+        for job in db.schedules()?.into_iter().filter(|j| !j.is_done()) {
+            let now_ms = chrono::Utc::now().timestamp_millis();
+            let due = schedule::due(job.next_run_ms, job.every_ms, now_ms, up_since_ms, catch_up);
+            let missed = match due {
+                Due::Wait(ms) => {
+                    wait_ms = wait_ms.min(ms);
+                    continue;
+                }
+                Due::Skip { missed } => {
+                    let run = DbScheduleRun {
+                        schedule_id: job.id,
+                        due_ms: job.next_run_ms,
+                        started_ms: now_ms,
+                        finished_ms: now_ms,
+                        status: "skipped".into(),
+                        missed,
+                        oid: None,
+                        fill_price: None,
+                        error: None,
+                    };
+                    let next = schedule::next_slot_after(job.next_run_ms, job.every_ms, now_ms);
+                    db.record_schedule_run(&run, next)?;
+                    report(&job, run, next, fmt);
+                    wait_ms = 0;
+                    continue;
+                }
+                Due::Run { missed } => missed,
+            };
+
+            atlas_core::output::start_capture();
+            let result = exec(job.command.clone(), fmt).await;
+            let (oid, fill_price) = schedule::order_fill(&atlas_core::output::take_captured());
+            let finished_ms = chrono::Utc::now().timestamp_millis();
+            let run = DbScheduleRun {
+                schedule_id: job.id,
+                due_ms: job.next_run_ms,
+                started_ms: now_ms,
+                finished_ms,
+                status: if result.is_ok() { "ok" } else { "error" }.into(),
+                missed,
+                oid,
+                fill_price,
+                error: result.err().map(|e| format!("{e:#}")),
+            };
+            let next = schedule::next_slot_after(job.next_run_ms, job.every_ms, finished_ms);
+            db.record_schedule_run(&run, next)?;
+            report(&job, run, next, fmt);
+            // Other jobs may have come due while this one ran
+            wait_ms = 0;
+        }
+        if wait_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(wait_ms as u64)).await;
+        }
+    }
+}
+
+/// One line per run: an `{"event":"schedule_run",...}` object for JSON.
+fn report(job: &DbSchedule, run: DbScheduleRun, next_run_ms: i64, fmt: OutputFormat) {
+    let done = job
+        .max_runs
+        .is_some_and(|max| job.runs + u32::from(run.status != "skipped") >= max);
+    let row = run_row(run);
+    match fmt {
+        OutputFormat::Json | OutputFormat::JsonPretty => {
+            let line = serde_json::json!({
+                "event": "schedule_run",
+                "command": job.command,
+                "run": row,
+                "next_run_ms": (!done).then_some(next_run_ms),
+            });
+            println!("{line}");
+        }
+        OutputFormat::Table => {
+            let next = if done {
+                "done".to_string()
+            } else {
+                format!("next {} UTC", format_ms(next_run_ms))
+            };
+            println!(
+                "{}  #{} {}  {}  ({next})",
+                format_ms(row.started_ms),
+                job.id,
+                job.command,
+                row.summary()
+            );
+        }
+    }
+}
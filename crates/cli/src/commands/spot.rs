@@ -1,80 +1,223 @@
 use anyhow::Result;
 use atlas_core::output::{render, OutputFormat};
-use atlas_core::output::{SpotBalanceOutput, SpotBalanceRow, SpotOrderOutput, SpotTransferOutput};
+use atlas_core::output::{
+    CancelOutput, CancelSingleOutput, OrderRow, OrdersOutput, SpotBalanceOutput, SpotBalanceRow,
+    SpotOrderOutput, SpotTransferOutput,
+};
+use atlas_core::parse;
+use atlas_core::prompt::{confirm_action, confirm_attended};
+use atlas_core::types::{MarketType, Side};
+use atlas_core::workspace::load_config;
 use rust_decimal::prelude::*;
 
-/// `atlas spot buy <BASE> <SIZE> [--slippage N]`
+use super::helpers::order_row;
+
+/// `atlas hl spot buy <BASE> <SIZE> [--slippage N] [--yes]`
+///
+/// SIZE uses the perp grammar: `$250` spends 250 USDC, `0.5hype` is base
+/// units, a bare number follows `default_size_mode` (without leverage).
 pub async fn spot_buy(
     base: &str,
-    size: f64,
+    size: &str,
+    slippage: Option<f64>,
+    yes: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    spot_market(base, Side::Buy, size, slippage, yes, fmt).await
+}
+
+/// `atlas hl spot sell <BASE> <SIZE> [--slippage N] [--yes]`
+pub async fn spot_sell(
+    base: &str,
+    size: &str,
+    slippage: Option<f64>,
+    yes: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    spot_market(base, Side::Sell, size, slippage, yes, fmt).await
+}
+
+async fn spot_market(
+    base: &str,
+    side: Side,
+    size_str: &str,
     slippage: Option<f64>,
+    yes: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
+    let base_upper = base.to_uppercase();
+    let size_input = parse::parse_size_for(size_str, &base_upper)?;
+    let config = load_config()?;
+    let hl_cfg = &config.modules.hyperliquid.config;
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let base_upper = base.to_uppercase();
 
-    let size_dec =
-        Decimal::from_f64(size).ok_or_else(|| anyhow::anyhow!("Invalid size: {size}"))?;
+    let info = perp.spot_market_info(&base_upper).await?;
+    let sizing =
+        hl_cfg.resolve_spot_size(&base_upper, &size_input, info.mid_price, info.sz_decimals);
+    if sizing.size.is_zero() {
+        anyhow::bail!(
+            "{size_str} rounds to zero {base_upper} ({} decimals at ${})",
+            info.sz_decimals,
+            info.mid_price
+        );
+    }
+    let warning = sizing.warning(&base_upper);
+    let notional = sizing.notional.round_dp(2);
+
+    if fmt == OutputFormat::Table {
+        println!(
+            "📤 SPOT {side} {} {base_upper} ≈ ${notional} @ ${}",
+            sizing.size, info.mid_price
+        );
+        if let Some(w) = &warning {
+            println!("⚠ {w}");
+        }
+    }
+
+    let proceed = confirm_action(
+        &format!("SPOT {side} — CONFIRM"),
+        &[
+            ("Market", info.pair.clone()),
+            ("Size", format!("{} {base_upper}", sizing.size)),
+            ("Mid", format!("${}", info.mid_price)),
+            ("Notional", format!("${notional}")),
+        ],
+        yes || !config.trading.confirm,
+        fmt,
+    )?;
+    if !proceed {
+        return Ok(());
+    }
 
     let result = perp
-        .spot_market_order(
-            &base_upper,
-            atlas_core::types::Side::Buy,
-            size_dec,
-            slippage,
-        )
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
+        .spot_market_order(&base_upper, side.clone(), sizing.size, slippage)
+        .await?;
 
     let output = SpotOrderOutput {
-        market: format!("{}/USDC", base_upper),
-        side: "BUY".into(),
+        market: info.pair,
+        side: side.to_string(),
+        size: sizing.size.to_string(),
+        notional: notional.to_string(),
+        warning,
         oid: result.order_id.parse().unwrap_or(0),
         status: format!("{:?}", result.status).to_lowercase(),
         total_sz: result.filled_size.map(|s| s.to_string()),
         avg_px: result.avg_price.map(|p| p.to_string()),
+        audit_id: result.audit_id,
     };
     render(fmt, &output)?;
     Ok(())
 }
 
-/// `atlas spot sell <BASE> <SIZE> [--slippage N]`
-pub async fn spot_sell(
+/// `atlas hl spot order <BASE> <SIDE> <SIZE> <PRICE>` — resting GTC limit order.
+pub async fn spot_order(
     base: &str,
+    side: &str,
     size: f64,
-    slippage: Option<f64>,
+    price: f64,
     fmt: OutputFormat,
 ) -> Result<()> {
+    let side = if parse::parse_side(side)? {
+        Side::Buy
+    } else {
+        Side::Sell
+    };
+    let size_dec =
+        Decimal::from_f64(size).ok_or_else(|| anyhow::anyhow!("Invalid size: {size}"))?;
+    let price_dec =
+        Decimal::from_f64(price).ok_or_else(|| anyhow::anyhow!("Invalid price: {price}"))?;
+
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
     let base_upper = base.to_uppercase();
 
-    let size_dec =
-        Decimal::from_f64(size).ok_or_else(|| anyhow::anyhow!("Invalid size: {size}"))?;
-
     let result = perp
-        .spot_market_order(
-            &base_upper,
-            atlas_core::types::Side::Sell,
-            size_dec,
-            slippage,
-        )
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
+        .spot_limit_order(&base_upper, side.clone(), size_dec, price_dec)
+        .await?;
 
     let output = SpotOrderOutput {
         market: format!("{}/USDC", base_upper),
-        side: "SELL".into(),
+        side: side.to_string(),
+        size: size_dec.to_string(),
+        notional: (size_dec * price_dec).round_dp(2).to_string(),
+        warning: None,
         oid: result.order_id.parse().unwrap_or(0),
         status: format!("{:?}", result.status).to_lowercase(),
         total_sz: result.filled_size.map(|s| s.to_string()),
         avg_px: result.avg_price.map(|p| p.to_string()),
+        audit_id: result.audit_id,
     };
     render(fmt, &output)?;
     Ok(())
 }
 
+/// `atlas hl spot orders`
+pub async fn spot_orders(fmt: OutputFormat) -> Result<()> {
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+    let orders = perp
+        .open_orders()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    let rows: Vec<OrderRow> = orders
+        .iter()
+        .filter(|o| o.market_type == MarketType::Spot)
+        .map(order_row)
+        .collect();
+
+    render(
+        fmt,
+        &OrdersOutput {
+            market_type: "spot".into(),
+            orders: rows,
+        },
+    )?;
+    Ok(())
+}
+
+/// `atlas hl spot cancel <BASE> [--oid N]` — without `--oid`, cancels every
+/// open order on the pair.
+pub async fn spot_cancel(base: &str, oid: Option<u64>, fmt: OutputFormat) -> Result<()> {
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+    let base_upper = base.to_uppercase();
+    let pair = format!("{base_upper}/USDC");
+
+    match oid {
+        Some(id) => {
+            perp.spot_cancel_order(&base_upper, &id.to_string())
+                .await
+                .map_err(|e| anyhow::anyhow!("{e}"))?;
+            render(
+                fmt,
+                &CancelSingleOutput {
+                    coin: pair,
+                    oid: id,
+                    status: "cancelled".into(),
+                },
+            )?;
+        }
+        None => {
+            let count = perp
+                .spot_cancel_all(&base_upper)
+                .await
+                .map_err(|e| anyhow::anyhow!("{e}"))?;
+            render(
+                fmt,
+                &CancelOutput {
+                    coin: pair,
+                    cancelled: count,
+                    total: count,
+                    oids: vec![],
+                },
+            )?;
+        }
+    }
+    Ok(())
+}
+
 /// `atlas spot balance`
 pub async fn spot_balance(fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::from_active_profile().await?;
@@ -99,16 +242,15 @@ pub async fn spot_balance(fmt: OutputFormat) -> Result<()> {
     Ok(())
 }
 
-/// `atlas spot transfer <DIRECTION> <AMOUNT> [--token TOKEN]`
+/// `atlas spot transfer <DIRECTION> <AMOUNT> [--token TOKEN] [--yes]`
 pub async fn spot_transfer(
     direction: &str,
     amount: &str,
     token: Option<&str>,
+    yes: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
-    let orch = crate::factory::from_active_profile().await?;
-    let perp = orch.perp(None)?;
-
+    let config = load_config()?;
     let amount_dec: Decimal = amount
         .parse()
         .map_err(|_| anyhow::anyhow!("Invalid amount: {amount}"))?;
@@ -116,6 +258,26 @@ pub async fn spot_transfer(
     let dir = direction.to_lowercase();
     let tk = token.unwrap_or("USDC");
 
+    let proceed = confirm_attended(
+        "SPOT TRANSFER — CONFIRM",
+        &[
+            ("Direction", dir.clone()),
+            ("Amount", format!("{amount_dec} {}", tk.to_uppercase())),
+            (
+                "Profile",
+                atlas_core::AuthManager::active_profile(&config).to_string(),
+            ),
+        ],
+        yes || !config.trading.confirm,
+        fmt,
+    )?;
+    if !proceed {
+        return Ok(());
+    }
+
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+
     let _result = perp
         .internal_transfer(&dir, amount_dec, Some(tk))
         .await
@@ -1,9 +1,25 @@
+use std::collections::HashMap;
+
 use anyhow::Result;
+use atlas_core::error::AtlasResult;
+use atlas_core::orchestrator::MODULE_TIMEOUT;
 use atlas_core::output::{render, OutputFormat};
-use atlas_core::output::{BalanceRow, PositionRow, StatusOutput};
+use atlas_core::output::{BalanceRow, ProtocolSummary, StatusOutput, ValuedBalanceRow};
+use atlas_core::status_snapshot::{self, StatusSnapshot};
+use atlas_core::traits::{PerpModule, SwapModule};
+use atlas_core::types::{Balance, Position, SpotBalance};
+use rust_decimal::Decimal;
+
+use super::helpers::position_row;
+
+/// Assets valued at $1 without a price lookup.
+const STABLECOINS: [&str; 5] = ["USDC", "USDT", "USDT0", "USDE", "USDH"];
 
 /// `atlas status` — fast textual summary, no TUI.
-pub async fn run(fmt: OutputFormat) -> Result<()> {
+/// With `all`, also values every enabled module and EVM wallet in USD.
+/// Each run that reaches the account is saved as a snapshot; with `diff`
+/// the output also shows what changed since the previous one.
+pub async fn run(all: bool, diff: bool, fmt: OutputFormat) -> Result<()> {
     let config = atlas_core::workspace::load_config()?;
 
     // Determine active modules
@@ -14,6 +30,9 @@ pub async fn run(fmt: OutputFormat) -> Result<()> {
     if config.modules.zero_x.enabled {
         modules.push("zero_x".to_string());
     }
+    if config.modules.paper.enabled {
+        modules.push("paper".to_string());
+    }
 
     let network = if config.modules.hyperliquid.config.network == "testnet" {
         "Testnet".to_string()
@@ -21,72 +40,76 @@ pub async fn run(fmt: OutputFormat) -> Result<()> {
         "Mainnet".to_string()
     };
 
+    let env = config.system.active_env.clone();
+    let keep = config.system.status_snapshots;
+    let profile = atlas_core::AuthManager::active_profile(&config).to_string();
+
+    if all {
+        let (mut output, complete) = all_output(profile, env, network, modules).await;
+        record_snapshot(&mut output, diff, complete, keep);
+        return render(fmt, &output);
+    }
+
     let orch_res = crate::factory::from_active_profile().await;
     match orch_res {
         Ok(orch) => {
-            let perp = orch.perp(None).map_err(|e| anyhow::anyhow!("{e}"))?;
-            let balances = perp.balances().await.map_err(|e| anyhow::anyhow!("{e}"))?;
-            let positions = perp.positions().await.map_err(|e| anyhow::anyhow!("{e}"))?;
-            let orders = perp
-                .open_orders()
-                .await
-                .map_err(|e| anyhow::anyhow!("{e}"))?;
-            let bal = balances.first();
-
-            let balance_rows: Vec<BalanceRow> = balances
-                .iter()
-                .map(|b| BalanceRow {
-                    asset: b.asset.clone(),
-                    total: b.total.to_string(),
-                    available: b.available.to_string(),
-                    protocol: "hyperliquid".to_string(),
-                })
-                .collect();
+            // Every perp module at once; one that fails becomes a warning.
+            let results = orch
+                .fan_out_perps(
+                    MODULE_TIMEOUT,
+                    |m| async move { perp_account(m.as_ref()).await },
+                )
+                .await;
 
-            let pos_rows: Vec<PositionRow> = positions
-                .iter()
-                .map(|p| PositionRow {
-                    coin: p.symbol.clone(),
-                    side: if p.size > rust_decimal::Decimal::ZERO {
-                        "long".into()
-                    } else {
-                        "short".into()
-                    },
-                    size: p.size.to_string(),
-                    entry_price: p.entry_price.map(|e| e.to_string()),
-                    mark_price: p.mark_price.map(|m| m.to_string()),
-                    unrealized_pnl: p.unrealized_pnl.map(|u| u.to_string()),
-                    liquidation_price: p.liquidation_price.map(|l| l.to_string()),
-                    leverage: p.leverage,
-                    margin_mode: p.margin_mode.clone(),
-                    protocol: "hyperliquid".into(),
-                })
-                .collect();
+            let mut warnings = Vec::new();
+            let mut balance_rows = Vec::new();
+            let mut pos_rows = Vec::new();
+            let mut open_orders = 0;
+            // Headline account figures come from the default perp module.
+            let mut bal: Option<Balance> = None;
+            for (protocol, result) in results {
+                match result {
+                    Ok(acct) => {
+                        if orch.default_perp.as_deref() == Some(protocol.to_string().as_str()) {
+                            bal = acct.balances.first().cloned();
+                        }
+                        balance_rows.extend(acct.balances.iter().map(balance_row));
+                        pos_rows.extend(acct.positions.iter().map(position_row));
+                        open_orders += acct.open_orders;
+                    }
+                    Err(e) => warnings.push(format!("{protocol}: {e}")),
+                }
+            }
 
-            // Get address from auth manager
-            let address = atlas_core::auth::AuthManager::get_active_signer()
-                .map(|s| format!("{:#x}", alloy::signers::Signer::address(&s)))
-                .unwrap_or_else(|_| "unknown".to_string());
+            let address = active_address();
+            let complete = warnings.is_empty();
 
-            let output = StatusOutput {
-                profile: config.system.active_profile.clone(),
+            let mut output = StatusOutput {
+                profile,
                 address,
+                env: env.clone(),
                 network,
                 modules,
                 balances: balance_rows,
-                account_value: bal.map(|b| b.total.to_string()),
-                margin_used: bal.map(|b| b.locked.to_string()),
+                account_value: bal.as_ref().map(|b| b.total.to_string()),
+                margin_used: bal.as_ref().map(|b| b.locked.to_string()),
                 net_position: None,
-                withdrawable: bal.map(|b| b.available.to_string()),
+                withdrawable: bal.as_ref().map(|b| b.available.to_string()),
                 positions: pos_rows,
-                open_orders: orders.len(),
+                open_orders,
+                total_value_usd: None,
+                protocols: vec![],
+                warnings,
+                diff: None,
             };
+            record_snapshot(&mut output, diff, complete, keep);
             render(fmt, &output)?;
         }
         Err(e) => {
             let output = StatusOutput {
-                profile: config.system.active_profile.clone(),
+                profile,
                 address: "unknown".into(),
+                env,
                 network,
                 modules,
                 balances: vec![],
@@ -96,6 +119,10 @@ pub async fn run(fmt: OutputFormat) -> Result<()> {
                 withdrawable: None,
                 positions: vec![],
                 open_orders: 0,
+                total_value_usd: None,
+                protocols: vec![],
+                warnings: vec![],
+                diff: None,
             };
             render(fmt, &output)?;
             if fmt == OutputFormat::Table {
@@ -107,3 +134,303 @@ pub async fn run(fmt: OutputFormat) -> Result<()> {
 
     Ok(())
 }
+
+/// With `diff`, compare `output` with the previous snapshot of its profile
+/// and network; then save it as the latest one, unless a source failed
+/// (`!complete`) and positions would look closed next time. Snapshot
+/// trouble becomes a warning rather than failing the status.
+fn record_snapshot(output: &mut StatusOutput, diff: bool, complete: bool, keep: usize) {
+    if let Err(e) = try_record_snapshot(output, diff, complete, keep) {
+        output.warnings.push(format!("snapshot: {e:#}"));
+    }
+}
+
+fn try_record_snapshot(
+    output: &mut StatusOutput,
+    diff: bool,
+    complete: bool,
+    keep: usize,
+) -> Result<()> {
+    let db = atlas_core::db::AtlasDb::open()?;
+    let now = StatusSnapshot::from(&*output);
+    if diff {
+        output.diff = Some(
+            match db.latest_status_snapshot(&output.profile, &output.network)? {
+                Some(prev) => {
+                    let snapshot: StatusSnapshot = serde_json::from_str(&prev.snapshot)?;
+                    status_snapshot::diff(&snapshot, prev.taken_ms, &now)
+                }
+                None => Default::default(),
+            },
+        );
+    }
+    if complete {
+        db.save_status_snapshot(
+            &output.profile,
+            &output.network,
+            &serde_json::to_string(&now)?,
+            chrono::Utc::now().timestamp_millis(),
+            keep,
+        )?;
+    }
+    Ok(())
+}
+
+fn balance_row(b: &Balance) -> BalanceRow {
+    BalanceRow {
+        asset: b.asset.clone(),
+        total: b.total.to_string(),
+        available: b.available.to_string(),
+        protocol: b.protocol.to_string(),
+    }
+}
+
+/// Get address from auth manager.
+fn active_address() -> String {
+    atlas_core::auth::AuthManager::get_active_signer()
+        .map(|s| format!("{:#x}", alloy::signers::Signer::address(&s)))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// One perp module's account state.
+struct PerpAccount {
+    balances: Vec<Balance>,
+    positions: Vec<Position>,
+    open_orders: usize,
+}
+
+async fn perp_account(perp: &dyn PerpModule) -> AtlasResult<PerpAccount> {
+    let (balances, positions, orders) =
+        tokio::join!(perp.balances(), perp.positions(), perp.open_orders());
+    Ok(PerpAccount {
+        balances: balances?,
+        positions: positions?,
+        open_orders: orders?.len(),
+    })
+}
+
+/// USD price for an asset: stablecoins at par, everything else at the
+/// Hyperliquid mid of the same symbol.
+fn usd_price(asset: &str, mids: &HashMap<String, Decimal>) -> Option<Decimal> {
+    let asset = asset.to_uppercase();
+    if STABLECOINS.contains(&asset.as_str()) {
+        return Some(Decimal::ONE);
+    }
+    mids.get(&asset).copied()
+}
+
+/// Value a list of holdings; unpriced assets are listed but add nothing.
+fn valued_section(
+    protocol: &str,
+    holdings: &[(String, String, Decimal)],
+    positions: usize,
+    mids: &HashMap<String, Decimal>,
+    warnings: &mut Vec<String>,
+) -> (ProtocolSummary, Decimal) {
+    let mut total = Decimal::ZERO;
+    let balances = holdings
+        .iter()
+        .map(|(asset, chain, amount)| {
+            let price = usd_price(asset, mids);
+            let value = price.map(|p| p * amount);
+            match value {
+                Some(v) => total += v,
+                None if !amount.is_zero() => {
+                    warnings.push(format!(
+                        "{protocol}: no USD price for {asset}, left out of total"
+                    ));
+                }
+                None => {}
+            }
+            ValuedBalanceRow {
+                asset: asset.clone(),
+                chain: chain.clone(),
+                amount: amount.normalize().to_string(),
+                price_usd: price.map(|p| p.normalize().to_string()),
+                value_usd: value.map(|v| v.round_dp(2).to_string()),
+            }
+        })
+        .collect();
+    let summary = ProtocolSummary {
+        protocol: protocol.to_string(),
+        value_usd: total.round_dp(2).to_string(),
+        balances,
+        positions,
+    };
+    (summary, total)
+}
+
+/// `atlas status --all` — every enabled module plus EVM wallet balances,
+/// fetched concurrently and valued in USD. A source that fails becomes a
+/// warning instead of failing the whole view. Also returns whether every
+/// source answered.
+async fn all_output(
+    profile: String,
+    env: Option<String>,
+    network: String,
+    modules: Vec<String>,
+) -> (StatusOutput, bool) {
+    let mut warnings = Vec::new();
+    let mut complete = true;
+    let orch = match crate::factory::from_active_profile().await {
+        Ok(orch) => Some(orch),
+        Err(e) => {
+            warnings.push(format!("connection failed: {e:#}"));
+            complete = false;
+            None
+        }
+    };
+    let perp = orch.as_ref().and_then(|o| o.perp(None).ok());
+    let swap = orch.as_ref().and_then(|o| o.swap(None).ok());
+
+    let hl_fut = async {
+        match perp {
+            Some(p) => Some(perp_account(p.as_ref()).await),
+            None => None,
+        }
+    };
+    let spot_fut = async {
+        match perp {
+            Some(p) => Some(p.spot_balances().await),
+            None => None,
+        }
+    };
+    let mids_fut = async {
+        match perp {
+            Some(p) => Some(p.all_tickers().await),
+            None => None,
+        }
+    };
+    let wallet_fut = async {
+        match swap {
+            Some(s) => Some(s.wallet_balances().await),
+            None => None,
+        }
+    };
+    let (hl, spot, tickers, wallet) = tokio::join!(hl_fut, spot_fut, mids_fut, wallet_fut);
+
+    let mids: HashMap<String, Decimal> = match tickers {
+        Some(Ok(t)) => t
+            .into_iter()
+            .map(|t| (t.symbol.to_uppercase(), t.mid_price))
+            .collect(),
+        Some(Err(e)) => {
+            warnings.push(format!("prices: {e} — only stablecoins are valued"));
+            HashMap::new()
+        }
+        None => HashMap::new(),
+    };
+
+    let mut sections = Vec::new();
+    let mut total = Decimal::ZERO;
+    let mut balance_rows = Vec::new();
+    let mut pos_rows = Vec::new();
+    let (mut account_value, mut margin_used, mut withdrawable) = (None, None, None);
+    let mut open_orders = 0;
+
+    match hl {
+        Some(Ok(acct)) => {
+            let bal = acct.balances.first();
+            account_value = bal.map(|b| b.total.to_string());
+            margin_used = bal.map(|b| b.locked.to_string());
+            withdrawable = bal.map(|b| b.available.to_string());
+            open_orders = acct.open_orders;
+            pos_rows = acct.positions.iter().map(position_row).collect();
+            let holdings: Vec<_> = acct
+                .balances
+                .iter()
+                .map(|b| (b.asset.clone(), b.chain.to_string(), b.total))
+                .collect();
+            let (section, value) = valued_section(
+                "hyperliquid",
+                &holdings,
+                acct.positions.len(),
+                &mids,
+                &mut warnings,
+            );
+            total += value;
+            sections.push(section);
+            balance_rows.extend(acct.balances.iter().map(|b| BalanceRow {
+                asset: b.asset.clone(),
+                total: b.total.to_string(),
+                available: b.available.to_string(),
+                protocol: "hyperliquid".to_string(),
+            }));
+        }
+        Some(Err(e)) => {
+            warnings.push(format!("hyperliquid: {e}"));
+            complete = false;
+        }
+        None => {}
+    }
+
+    match spot {
+        Some(Ok(spot)) => {
+            let held: Vec<&SpotBalance> = spot.iter().filter(|b| !b.total.is_zero()).collect();
+            if !held.is_empty() {
+                let holdings: Vec<_> = held
+                    .iter()
+                    .map(|b| (b.token.clone(), "hyperliquid-l1".to_string(), b.total))
+                    .collect();
+                let (section, value) =
+                    valued_section("hyperliquid-spot", &holdings, 0, &mids, &mut warnings);
+                total += value;
+                sections.push(section);
+                balance_rows.extend(held.iter().map(|b| BalanceRow {
+                    asset: b.token.clone(),
+                    total: b.total.to_string(),
+                    available: b.available.to_string(),
+                    protocol: "hyperliquid-spot".to_string(),
+                }));
+            }
+        }
+        Some(Err(e)) => {
+            warnings.push(format!("hyperliquid-spot: {e}"));
+            complete = false;
+        }
+        None => {}
+    }
+
+    match wallet {
+        Some(Ok(wallet)) if !wallet.is_empty() => {
+            let holdings: Vec<_> = wallet
+                .iter()
+                .map(|b| (b.asset.clone(), b.chain.to_string(), b.total))
+                .collect();
+            let (section, value) = valued_section("evm-wallet", &holdings, 0, &mids, &mut warnings);
+            total += value;
+            sections.push(section);
+            balance_rows.extend(wallet.iter().map(|b| BalanceRow {
+                asset: b.asset.clone(),
+                total: b.total.to_string(),
+                available: b.available.to_string(),
+                protocol: format!("evm:{}", b.chain),
+            }));
+        }
+        Some(Ok(_)) | None => {}
+        Some(Err(e)) => {
+            warnings.push(format!("evm-wallet: {e}"));
+            complete = false;
+        }
+    }
+
+    let output = StatusOutput {
+        profile,
+        address: active_address(),
+        env,
+        network,
+        modules,
+        balances: balance_rows,
+        account_value,
+        margin_used,
+        net_position: None,
+        withdrawable,
+        positions: pos_rows,
+        open_orders,
+        total_value_usd: Some(total.round_dp(2).to_string()),
+        protocols: sections,
+        warnings,
+        diff: None,
+    };
+    (output, complete)
+}
@@ -0,0 +1,118 @@
+//! `atlas strategy` — read-only strategy estimates. Nothing here places orders.
+
+use anyhow::Result;
+use atlas_core::carry::{self, CarryParams, FundingInputs};
+use atlas_core::engine::BuilderFee;
+use atlas_core::fmt::format_decimal;
+use atlas_core::output::{render, CarryOutput, OutputFormat};
+use rust_decimal::prelude::*;
+
+/// `atlas strategy carry <COIN> --size-usd <N> [--horizon-days 30] [--market <ID>] [--chain <CHAIN>] [--taker-fee-bps 4.5]`
+///
+/// Short the HL perp, hold the spot asset and supply it on Morpho. The
+/// Morpho market comes from `--market` or `modules.morpho.carry_markets`.
+pub async fn carry(
+    coin: &str,
+    size_usd: f64,
+    horizon_days: u32,
+    market: Option<&str>,
+    chain: Option<&str>,
+    taker_fee_bps: f64,
+    fmt: OutputFormat,
+) -> Result<()> {
+    if !size_usd.is_finite() || size_usd <= 0.0 {
+        anyhow::bail!("--size-usd must be positive");
+    }
+    if horizon_days == 0 {
+        anyhow::bail!("--horizon-days must be at least 1");
+    }
+    if !taker_fee_bps.is_finite() || taker_fee_bps < 0.0 {
+        anyhow::bail!("--taker-fee-bps must be zero or positive");
+    }
+
+    let config = atlas_core::workspace::load_config()?;
+    if !config.modules.morpho.enabled {
+        anyhow::bail!("Morpho module is disabled. Run: atlas configure module enable morpho");
+    }
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
+    let coin = perp.resolve_symbol(coin)?;
+
+    let morpho = &config.modules.morpho.config;
+    let market_id = match market {
+        Some(id) => id.to_string(),
+        None => morpho
+            .carry_markets
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(&coin))
+            .map(|(_, id)| id.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No Morpho market mapped for {coin}. Pass --market <ID> or run: \
+                     atlas configure set modules.morpho.carry_markets.{coin} <ID>"
+                )
+            })?,
+    };
+    let chain = super::morpho::resolve_chain(chain)?;
+
+    let (ctxs, history, market) = tokio::join!(
+        perp.asset_contexts(),
+        perp.funding(&coin),
+        super::morpho::fetch_market(&market_id, &chain),
+    );
+    let ctxs = ctxs.map_err(|e| anyhow::anyhow!("{e}"))?;
+    let history = history.map_err(|e| anyhow::anyhow!("{e}"))?;
+    let market = market?;
+
+    let ctx = ctxs
+        .iter()
+        .find(|c| c.symbol == coin)
+        .ok_or_else(|| anyhow::anyhow!("No market context for {coin}"))?;
+    let current_rate = ctx
+        .funding_rate
+        .ok_or_else(|| anyhow::anyhow!("No current funding rate for {coin}"))?;
+    let rates: Vec<Decimal> = history.iter().map(|r| r.rate).collect();
+    let funding = FundingInputs {
+        current_rate,
+        trailing_avg_rate: carry::average_rate(&rates),
+        interval_hours: ctx.funding_interval_hours,
+    };
+
+    let supply_apy = market
+        .supply_apy
+        .ok_or_else(|| anyhow::anyhow!("Morpho market {market_id} reports no supply APY"))?;
+    let supply_apy_pct = Decimal::from_f64(supply_apy * 100.0).unwrap_or_default();
+    let builder_fee_bps = BuilderFee::from_config(&config.modules.hyperliquid.config)
+        .map_or(Decimal::ZERO, |fee| Decimal::from(fee.f));
+    let params = CarryParams {
+        size_usd: Decimal::from_f64(size_usd).unwrap_or_default(),
+        horizon_days,
+        taker_fee_bps: Decimal::from_f64(taker_fee_bps).unwrap_or_default(),
+        builder_fee_bps,
+    };
+    let e = carry::estimate(&funding, supply_apy_pct, &params);
+
+    let output = CarryOutput {
+        coin,
+        size_usd: format_decimal(params.size_usd, 2),
+        horizon_days,
+        chain,
+        market_id,
+        market: market.name,
+        funding_rate: current_rate.normalize().to_string(),
+        funding_interval_hours: funding.interval_hours,
+        funding_apr_pct: format_decimal(e.funding_apr_pct, 2),
+        trailing_funding_apr_pct: e.trailing_funding_apr_pct.map(|apr| format_decimal(apr, 2)),
+        supply_apy_pct: format_decimal(e.supply_apy_pct, 2),
+        taker_fee_bps: params.taker_fee_bps.normalize().to_string(),
+        builder_fee_bps: builder_fee_bps.to_string(),
+        fees_usd: format_decimal(e.fees_usd, 2),
+        fees_apr_pct: format_decimal(e.fees_apr_pct, 2),
+        net_apr_pct: format_decimal(e.net_apr_pct, 2),
+        net_usd: format_decimal(e.net_usd, 2),
+        breakeven_funding_apr_pct: format_decimal(e.breakeven_funding_apr_pct, 2),
+        breakeven_funding_rate: format_decimal(e.breakeven_funding_rate, 8),
+        trailing_below_current: e.trailing_below_current,
+    };
+    render(fmt, &output)
+}
@@ -2,19 +2,27 @@ use anyhow::Result;
 use futures::StreamExt;
 use hypersdk::hypercore::{
     self as hypercore,
-    types::{Incoming, Subscription},
+    types::{Incoming, L2Book, Subscription, Trade},
     ws::Event,
 };
 use rust_decimal::Decimal;
+use serde_json::Value;
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
+use atlas_core::date_range::now_ms;
+use atlas_core::error::AtlasError;
 use atlas_core::fmt::format_timestamp_ms;
-use atlas_core::output::OutputFormat;
+use atlas_core::metrics::{self, StreamMetrics};
+use atlas_core::output::{render, OutputFormat, StreamRecordingOutput};
+use atlas_core::recording::{self, Record, Recorder, RecordingSpec, StopReason};
 use atlas_core::workspace::load_config;
 use atlas_core::AuthManager;
 
 /// Build HL websocket client from config (no Engine needed).
-fn build_ws_client(testnet: bool) -> hypersdk::hypercore::HttpClient {
+pub(crate) fn build_ws_client(testnet: bool) -> hypersdk::hypercore::HttpClient {
     if testnet {
         hypercore::testnet()
     } else {
@@ -22,19 +30,70 @@ fn build_ws_client(testnet: bool) -> hypersdk::hypercore::HttpClient {
     }
 }
 
-/// `atlas stream prices` — live mid prices for all markets
-pub async fn stream_prices(fmt: OutputFormat) -> Result<()> {
+/// Heartbeat registry for the stream, served over HTTP with
+/// `--metrics-port`. Without it the counters are kept but never exposed.
+pub async fn start_metrics(listen: Option<&str>, stale_after: u64) -> Result<Arc<StreamMetrics>> {
+    let metrics = Arc::new(StreamMetrics::new(Duration::from_secs(stale_after)));
+    if let Some(listen) = listen {
+        let addr = metrics::serve(metrics.clone(), metrics::parse_listen_addr(listen)?).await?;
+        eprintln!("📈 Metrics on http://{addr}/metrics (health: /healthz)");
+    }
+    Ok(metrics)
+}
+
+/// Record a WebSocket event. hypersdk reconnects on its own and reports
+/// each connection, so `Connected` events after the first are reconnects.
+fn observe(metrics: &StreamMetrics, event: &Event) {
+    match event {
+        Event::Connected => metrics.record_connected(),
+        Event::Message(msg) => metrics.record_message(channel(msg)),
+        _ => {}
+    }
+}
+
+/// Metric label for a message, after the Hyperliquid subscription name.
+fn channel(msg: &Incoming) -> &'static str {
+    match msg {
+        Incoming::AllMids { .. } => "allMids",
+        Incoming::Trades(_) => "trades",
+        Incoming::L2Book(_) => "l2Book",
+        Incoming::Candle(_) => "candle",
+        Incoming::UserFills { .. } => "userFills",
+        Incoming::OrderUpdates(_) => "orderUpdates",
+        _ => "other",
+    }
+}
+
+/// `atlas stream prices [COINS...] [--via-backend]` — live mid prices
+pub async fn stream_prices(
+    coins: &[String],
+    via_backend: bool,
+    metrics: &StreamMetrics,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let coins: Vec<String> = coins.iter().map(|c| c.to_uppercase()).collect();
+    if via_backend {
+        return stream_prices_via_backend(&coins, metrics, fmt).await;
+    }
+
     let config = load_config()?;
     let testnet = config.modules.hyperliquid.config.network == "testnet";
     let core = build_ws_client(testnet);
 
-    eprintln!("🔴 Streaming all mid prices (Ctrl+C to stop)...\n");
+    eprintln!(
+        "🔴 Streaming {} (Ctrl+C to stop)...\n",
+        prices_label(&coins)
+    );
 
     let mut ws = core.websocket();
     ws.subscribe(Subscription::AllMids { dex: None });
 
     while let Some(event) = ws.next().await {
-        if let Event::Message(Incoming::AllMids { dex: _, mids }) = event {
+        observe(metrics, &event);
+        if let Event::Message(Incoming::AllMids { dex: _, mut mids }) = event {
+            if !coins.is_empty() {
+                mids.retain(|coin, _| coins.contains(coin));
+            }
             render_mids_update(&mids, fmt);
         }
     }
@@ -42,8 +101,40 @@ pub async fn stream_prices(fmt: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+/// Same output as the direct stream, but one shared upstream HL socket on
+/// the backend serves every client.
+async fn stream_prices_via_backend(
+    coins: &[String],
+    metrics: &StreamMetrics,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let client = atlas_core::BackendClient::from_config()?;
+    let mut stream = client.stream_prices(coins).await?;
+
+    eprintln!(
+        "🔴 Streaming {} via Atlas backend (Ctrl+C to stop)...\n",
+        prices_label(coins)
+    );
+
+    while let Some(update) = stream.next().await {
+        let mids = update?;
+        metrics.record_message("allMids");
+        render_mids_update(&mids, fmt);
+    }
+
+    anyhow::bail!("Backend closed the price stream")
+}
+
+fn prices_label(coins: &[String]) -> String {
+    if coins.is_empty() {
+        "all mid prices".to_string()
+    } else {
+        format!("{} mid prices", coins.join(", "))
+    }
+}
+
 /// `atlas stream trades <COIN>` — live trade feed
-pub async fn stream_trades(coin: &str, fmt: OutputFormat) -> Result<()> {
+pub async fn stream_trades(coin: &str, metrics: &StreamMetrics, fmt: OutputFormat) -> Result<()> {
     let config = load_config()?;
     let testnet = config.modules.hyperliquid.config.network == "testnet";
     let core = build_ws_client(testnet);
@@ -64,20 +155,15 @@ pub async fn stream_trades(coin: &str, fmt: OutputFormat) -> Result<()> {
     }
 
     while let Some(event) = ws.next().await {
+        observe(metrics, &event);
         if let Event::Message(Incoming::Trades(trades)) = event {
             for trade in &trades {
                 match fmt {
                     OutputFormat::Json | OutputFormat::JsonPretty => {
-                        // PRD canonical NDJSON: symbol, price, size, side, timestamp
-                        let canonical = serde_json::json!({
-                            "event": "trade",
-                            "symbol": trade.coin,
-                            "price": trade.px.to_string(),
-                            "size": trade.sz.to_string(),
-                            "side": format!("{:?}", trade.side).to_lowercase(),
-                            "timestamp": trade.time,
-                        });
-                        println!("{}", serde_json::to_string(&canonical).unwrap_or_default());
+                        println!(
+                            "{}",
+                            serde_json::to_string(&trade_event(trade)).unwrap_or_default()
+                        );
                     }
                     OutputFormat::Table => {
                         let time = format_timestamp_ms(trade.time);
@@ -98,8 +184,42 @@ pub async fn stream_trades(coin: &str, fmt: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+/// PRD canonical NDJSON for one trade: symbol, price, size, side, timestamp.
+fn trade_event(trade: &Trade) -> Value {
+    serde_json::json!({
+        "event": "trade",
+        "symbol": trade.coin,
+        "price": trade.px.to_string(),
+        "size": trade.sz.to_string(),
+        "side": format!("{:?}", trade.side).to_lowercase(),
+        "timestamp": trade.time,
+    })
+}
+
+/// NDJSON for one L2 book snapshot, every level the feed sent.
+fn book_event(book: &L2Book) -> Value {
+    let [bids, asks] = [0, 1].map(|side| {
+        book.levels[side]
+            .iter()
+            .map(|l| serde_json::json!({"price": l.px.to_string(), "size": l.sz.to_string()}))
+            .collect::<Vec<_>>()
+    });
+    serde_json::json!({
+        "event": "book",
+        "symbol": book.coin,
+        "bids": bids,
+        "asks": asks,
+        "timestamp": book.time,
+    })
+}
+
 /// `atlas stream book <COIN>` — live order book
-pub async fn stream_book(coin: &str, depth: usize, fmt: OutputFormat) -> Result<()> {
+pub async fn stream_book(
+    coin: &str,
+    depth: usize,
+    metrics: &StreamMetrics,
+    fmt: OutputFormat,
+) -> Result<()> {
     let config = load_config()?;
     let testnet = config.modules.hyperliquid.config.network == "testnet";
     let core = build_ws_client(testnet);
@@ -112,21 +232,14 @@ pub async fn stream_book(coin: &str, depth: usize, fmt: OutputFormat) -> Result<
     eprintln!("🔴 Streaming {coin} order book (Ctrl+C to stop)...\n");
 
     while let Some(event) = ws.next().await {
+        observe(metrics, &event);
         if let Event::Message(Incoming::L2Book(book)) = event {
             match fmt {
                 OutputFormat::Json | OutputFormat::JsonPretty => {
-                    let canonical = serde_json::json!({
-                        "event": "book",
-                        "symbol": book.coin,
-                        "bids": book.levels[0].iter().map(|l| {
-                            serde_json::json!({"price": l.px.to_string(), "size": l.sz.to_string()})
-                        }).collect::<Vec<_>>(),
-                        "asks": book.levels[1].iter().map(|l| {
-                            serde_json::json!({"price": l.px.to_string(), "size": l.sz.to_string()})
-                        }).collect::<Vec<_>>(),
-                        "timestamp": book.time,
-                    });
-                    println!("{}", serde_json::to_string(&canonical).unwrap_or_default());
+                    println!(
+                        "{}",
+                        serde_json::to_string(&book_event(&book)).unwrap_or_default()
+                    );
                 }
                 OutputFormat::Table => {
                     print!("\x1B[2J\x1B[H");
@@ -154,7 +267,12 @@ pub async fn stream_book(coin: &str, depth: usize, fmt: OutputFormat) -> Result<
 }
 
 /// `atlas stream candles <COIN> <INTERVAL>` — live candle updates
-pub async fn stream_candles(coin: &str, interval: &str, fmt: OutputFormat) -> Result<()> {
+pub async fn stream_candles(
+    coin: &str,
+    interval: &str,
+    metrics: &StreamMetrics,
+    fmt: OutputFormat,
+) -> Result<()> {
     let config = load_config()?;
     let testnet = config.modules.hyperliquid.config.network == "testnet";
     let core = build_ws_client(testnet);
@@ -176,6 +294,7 @@ pub async fn stream_candles(coin: &str, interval: &str, fmt: OutputFormat) -> Re
     }
 
     while let Some(event) = ws.next().await {
+        observe(metrics, &event);
         if let Event::Message(Incoming::Candle(candle)) = event {
             match fmt {
                 OutputFormat::Json | OutputFormat::JsonPretty => {
@@ -207,7 +326,7 @@ pub async fn stream_candles(coin: &str, interval: &str, fmt: OutputFormat) -> Re
 }
 
 /// `atlas stream user` — live user events (fills, orders, liquidations)
-pub async fn stream_user(fmt: OutputFormat) -> Result<()> {
+pub async fn stream_user(metrics: &StreamMetrics, fmt: OutputFormat) -> Result<()> {
     let config = load_config()?;
     let testnet = config.modules.hyperliquid.config.network == "testnet";
     let core = build_ws_client(testnet);
@@ -224,6 +343,7 @@ pub async fn stream_user(fmt: OutputFormat) -> Result<()> {
     );
 
     while let Some(event) = ws.next().await {
+        observe(metrics, &event);
         match event {
             Event::Message(Incoming::UserFills { user: _, fills }) => {
                 for fill in &fills {
@@ -295,6 +415,179 @@ pub async fn stream_user(fmt: OutputFormat) -> Result<()> {
     Ok(())
 }
 
+/// `atlas stream record <COIN> --channels book,trades --out DIR` — append
+/// the live feeds to a recording until Ctrl+C, the stream ends or the disk
+/// runs low. Each trade and each book snapshot is one record.
+pub async fn record(
+    coin: &str,
+    channels: &[String],
+    out: &str,
+    rotate_bytes: u64,
+    min_free_bytes: u64,
+    metrics: &StreamMetrics,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let mut channels = channels.to_vec();
+    channels.sort();
+    channels.dedup();
+    if channels.is_empty() {
+        channels = recording::CHANNELS.iter().map(|c| c.to_string()).collect();
+    }
+    let out = Path::new(out);
+    let config = load_config()?;
+    let network = config.modules.hyperliquid.config.network.clone();
+    let core = build_ws_client(network == "testnet");
+
+    let spec = RecordingSpec {
+        ticker: coin.to_string(),
+        channels: channels.clone(),
+        network,
+        rotate_bytes,
+        min_free_bytes,
+    };
+    let mut recorder = Recorder::create(out, &spec, recording::disk_free, now_ms())?;
+
+    let mut ws = core.websocket();
+    let book = channels.iter().any(|c| c == "book");
+    let trades = channels.iter().any(|c| c == "trades");
+    if book {
+        ws.subscribe(Subscription::L2Book {
+            coin: coin.to_string(),
+        });
+    }
+    if trades {
+        ws.subscribe(Subscription::Trades {
+            coin: coin.to_string(),
+        });
+    }
+
+    eprintln!(
+        "🔴 Recording {coin} {} to {} (Ctrl+C to stop)...\n",
+        channels.join(", "),
+        out.display()
+    );
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let mut connected = false;
+    let mut last_ms = now_ms();
+    let stopped: Result<StopReason> = loop {
+        let event = tokio::select! {
+            _ = &mut ctrl_c => break Ok(StopReason::Interrupted),
+            event = ws.next() => event,
+        };
+        let Some(event) = event else {
+            break Ok(StopReason::StreamClosed);
+        };
+        observe(metrics, &event);
+        let recv_ms = now_ms();
+        let records: Vec<Record> = match event {
+            Event::Connected => {
+                if connected {
+                    if let Err(e) = recorder.gap(last_ms, recv_ms) {
+                        break Err(e);
+                    }
+                }
+                connected = true;
+                continue;
+            }
+            Event::Message(Incoming::Trades(batch)) if trades => batch
+                .iter()
+                .map(|t| Record {
+                    recv_ms,
+                    channel: "trades".into(),
+                    event: trade_event(t),
+                })
+                .collect(),
+            Event::Message(Incoming::L2Book(snapshot)) if book => vec![Record {
+                recv_ms,
+                channel: "book".into(),
+                event: book_event(&snapshot),
+            }],
+            _ => continue,
+        };
+        last_ms = recv_ms;
+        if let Err(e) = records.iter().try_for_each(|r| recorder.write(r)) {
+            break Err(e);
+        }
+    };
+
+    let reason = match &stopped {
+        Ok(reason) => *reason,
+        Err(e)
+            if matches!(
+                e.downcast_ref::<AtlasError>(),
+                Some(AtlasError::DiskSpaceLow(_))
+            ) =>
+        {
+            StopReason::DiskSpace
+        }
+        Err(_) => StopReason::Error,
+    };
+    let manifest = recorder.finish(reason, now_ms())?;
+    if let Err(e) = stopped {
+        eprintln!(
+            "Recording stopped: {} messages kept in {}",
+            manifest.total_messages(),
+            out.display()
+        );
+        return Err(e);
+    }
+    render(
+        fmt,
+        &StreamRecordingOutput {
+            dir: out.display().to_string(),
+            ticker: manifest.ticker.clone(),
+            channels: manifest.channels.clone(),
+            network: manifest.network.clone(),
+            started: format_timestamp_ms(manifest.started_ms as u64),
+            ended: format_timestamp_ms(manifest.ended_ms.unwrap_or(manifest.started_ms) as u64),
+            stop_reason: reason.as_str().to_string(),
+            messages: manifest.messages.clone(),
+            segments: manifest.segments.len(),
+            bytes: manifest.total_bytes(),
+            gaps: manifest.gaps.clone(),
+        },
+    )
+}
+
+/// `atlas stream replay <DIR> [--speed 10x]` — re-emit a recording as the
+/// NDJSON the live commands print, paced on the receive times. The
+/// recording is checked against its manifest before anything is printed.
+pub async fn replay(dir: &str, speed: &str) -> Result<()> {
+    let dir = Path::new(dir);
+    let speed = recording::parse_speed(speed).map_err(anyhow::Error::msg)?;
+    let manifest = recording::verify(dir)?;
+    eprintln!(
+        "▶ Replaying {} {} — {} messages at {} (Ctrl+C to stop)...\n",
+        manifest.ticker,
+        manifest.channels.join(", "),
+        manifest.total_messages(),
+        speed.map_or("full speed".to_string(), |s| format!("{s}x"))
+    );
+
+    let mut gaps = manifest.gaps.iter().peekable();
+    let mut prev_ms: Option<i64> = None;
+    for record in recording::records(dir, &manifest) {
+        let record = record?;
+        while let Some(gap) = gaps.next_if(|g| g.to_ms <= record.recv_ms) {
+            eprintln!(
+                "⚠ {:.1}s without data in the recording (reconnect)",
+                (gap.to_ms - gap.from_ms) as f64 / 1000.0
+            );
+        }
+        if let (Some(speed), Some(prev)) = (speed, prev_ms) {
+            let wait_ms = (record.recv_ms - prev).max(0) as f64 / speed;
+            if wait_ms >= 1.0 {
+                tokio::time::sleep(Duration::from_secs_f64(wait_ms / 1000.0)).await;
+            }
+        }
+        prev_ms = Some(record.recv_ms);
+        println!("{}", serde_json::to_string(&record.event)?);
+    }
+    Ok(())
+}
+
 fn render_mids_update(mids: &HashMap<String, Decimal>, fmt: OutputFormat) {
     match fmt {
         OutputFormat::Json | OutputFormat::JsonPretty => {
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use atlas_core::output::OutputFormat;
+use atlas_core::output::{print_confirmation, OutputFormat};
 
 /// `atlas sub list`
 pub async fn sub_list(fmt: OutputFormat) -> Result<()> {
@@ -68,9 +68,7 @@ pub async fn agent_approve(address: &str, name: Option<&str>, fmt: OutputFormat)
             };
             println!("{s}");
         }
-        OutputFormat::Table => {
-            println!("✅ {result}");
-        }
+        OutputFormat::Table => print_confirmation(&result),
     }
     Ok(())
 }
@@ -3,26 +3,82 @@
 //! Pure-Rust TA library: RSI, MACD, Bollinger Bands, Stochastic, ADX, ATR,
 //! EMA, SMA, OBV, CCI, Williams %R, and more.
 
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use anyhow::Result;
-use atlas_core::output::OutputFormat;
+use atlas_core::batch::{map_concurrent, MAX_CONCURRENT_REQUESTS};
+use atlas_core::candle_gaps::{self, CandleGap};
+use atlas_core::indicators::{parse_indicators, IndicatorKind, IndicatorSpec, DEFAULT_BUNDLE};
+use atlas_core::output::{
+    render, render_context, OutputFormat, PatternRow, PatternScanOutput, PatternScanRow,
+    PatternsOutput, TaBundleOutput, TaReading, TrendMtfOutput, TrendTimeframeRow,
+};
+use atlas_core::patterns::{self, Ohlc};
+use atlas_core::ta_ext::{Ichimoku, SuperTrend};
+use atlas_core::timeframe::Timeframe;
+use atlas_core::traits::PerpModule;
 use rust_decimal::prelude::*;
 use ta::indicators::{
     AverageTrueRange, BollingerBands, CommodityChannelIndex, ExponentialMovingAverage,
     MovingAverageConvergenceDivergence, RelativeStrengthIndex, SimpleMovingAverage, SlowStochastic,
 };
-use ta::{Close, DataItem, High, Low, Next, Open};
+use ta::{Close, DataItem, High, Low, Next};
+
+use super::helpers::format_ms;
+
+static STRICT_GAPS: AtomicBool = AtomicBool::new(false);
+
+/// `--strict`: refuse to compute over a series with missing candles
+/// instead of warning.
+pub fn set_strict_gaps(strict: bool) {
+    STRICT_GAPS.store(strict, Ordering::Relaxed);
+}
+
+/// Gaps in a series of `timeframe` candles opened at `open_times`; an error
+/// under `--strict`.
+fn check_gaps(ticker: &str, timeframe: Timeframe, open_times: &[u64]) -> Result<Vec<CandleGap>> {
+    // Months have no fixed stride to check against
+    if timeframe == Timeframe::Month1 {
+        return Ok(Vec::new());
+    }
+    let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+    let gaps = candle_gaps::find_gaps(open_times, timeframe.duration_ms(), now_ms).gaps;
+    if !gaps.is_empty() && STRICT_GAPS.load(Ordering::Relaxed) {
+        anyhow::bail!(
+            "{} [{timeframe}]: {} (--strict)",
+            ticker.to_uppercase(),
+            candle_gaps::describe(&gaps)
+        );
+    }
+    Ok(gaps)
+}
+
+/// Candles converted for the `ta` crate, with the gaps left in them.
+struct Series {
+    items: Vec<DataItem>,
+    volumes: Vec<f64>,
+    gaps: Vec<CandleGap>,
+}
 
 /// Fetch candle data from Hyperliquid and convert to ta::DataItem.
-async fn fetch_data_items(
+async fn fetch_data_items(ticker: &str, timeframe: &str, count: usize) -> Result<Series> {
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
+    fetch_candles(perp.as_ref(), ticker, timeframe, count).await
+}
+
+/// Candles for `ticker` from an already-built perp module.
+async fn fetch_candles(
+    perp: &dyn PerpModule,
     ticker: &str,
     timeframe: &str,
     count: usize,
-) -> Result<(Vec<DataItem>, Vec<f64>)> {
-    let orch = crate::factory::readonly().await?;
-    let perp = orch.perp(None)?;
+) -> Result<Series> {
     let ticker_upper = ticker.to_uppercase();
+    let tf: Timeframe = timeframe.parse()?;
 
-    let candles = perp
+    let mut candles = perp
         .candles(&ticker_upper, timeframe, count)
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))?;
@@ -30,6 +86,9 @@ async fn fetch_data_items(
     if candles.is_empty() {
         anyhow::bail!("No candle data for {ticker_upper}");
     }
+    candle_gaps::dedup(&mut candles);
+    let open_times: Vec<u64> = candles.iter().map(|c| c.open_time_ms).collect();
+    let gaps = check_gaps(ticker, tf, &open_times)?;
 
     let mut items = Vec::with_capacity(candles.len());
     let mut volumes = Vec::with_capacity(candles.len());
@@ -56,23 +115,312 @@ async fn fetch_data_items(
         anyhow::bail!("Failed to parse candle data");
     }
 
-    Ok((items, volumes))
+    Ok(Series {
+        items,
+        volumes,
+        gaps,
+    })
 }
 
-fn print_json(val: &serde_json::Value, pretty: bool) {
+/// `val` with a `gaps` array added when the series had any.
+fn print_json(val: &serde_json::Value, gaps: &[CandleGap], pretty: bool) {
+    let mut val = val.clone();
+    if !gaps.is_empty() {
+        val["gaps"] = serde_json::json!(gaps);
+    }
     if pretty {
-        println!("{}", serde_json::to_string_pretty(val).unwrap());
+        println!("{}", serde_json::to_string_pretty(&val).unwrap());
     } else {
-        println!("{}", serde_json::to_string(val).unwrap());
+        println!("{}", serde_json::to_string(&val).unwrap());
+    }
+}
+
+/// Warning under a single-indicator table when the series had gaps.
+fn warn_gaps(gaps: &[CandleGap]) {
+    if !gaps.is_empty() {
+        println!(
+            "   {} {}; values may be skewed (--strict refuses)",
+            render_context().warn(),
+            candle_gaps::describe(gaps)
+        );
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════
+//  BATCH TA (one candle fetch, many indicators)
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Streaming state for one indicator in a batch.
+enum IndicatorState {
+    Rsi(RelativeStrengthIndex),
+    Macd(MovingAverageConvergenceDivergence),
+    Bbands(BollingerBands),
+    Atr(AverageTrueRange),
+    Ema(ExponentialMovingAverage),
+    Sma(SimpleMovingAverage),
+    Stoch(SlowStochastic, ExponentialMovingAverage),
+    Cci(CommodityChannelIndex),
+    Willr {
+        period: usize,
+        window: VecDeque<(f64, f64)>,
+    },
+    Obv {
+        value: f64,
+        prev_close: Option<f64>,
+    },
+    Ichimoku(Ichimoku),
+    Supertrend(SuperTrend),
+}
+
+impl IndicatorState {
+    fn new(spec: &IndicatorSpec) -> Result<Self> {
+        let period = spec.period().unwrap_or(0);
+        let init = |e: ta::errors::TaError| anyhow::anyhow!("{spec} init: {e}");
+        Ok(match spec.kind {
+            IndicatorKind::Rsi => Self::Rsi(RelativeStrengthIndex::new(period).map_err(init)?),
+            IndicatorKind::Macd => {
+                Self::Macd(MovingAverageConvergenceDivergence::new(12, 26, 9).map_err(init)?)
+            }
+            IndicatorKind::Bbands => {
+                Self::Bbands(BollingerBands::new(period, 2.0_f64).map_err(init)?)
+            }
+            IndicatorKind::Atr => Self::Atr(AverageTrueRange::new(period).map_err(init)?),
+            IndicatorKind::Ema => Self::Ema(ExponentialMovingAverage::new(period).map_err(init)?),
+            IndicatorKind::Sma => Self::Sma(SimpleMovingAverage::new(period).map_err(init)?),
+            IndicatorKind::Stoch => Self::Stoch(
+                SlowStochastic::new(14, 3).map_err(init)?,
+                ExponentialMovingAverage::new(3).map_err(init)?,
+            ),
+            IndicatorKind::Cci => Self::Cci(CommodityChannelIndex::new(period).map_err(init)?),
+            IndicatorKind::Willr => Self::Willr {
+                period,
+                window: VecDeque::with_capacity(period + 1),
+            },
+            IndicatorKind::Obv => Self::Obv {
+                value: 0.0,
+                prev_close: None,
+            },
+            IndicatorKind::Ichimoku => Self::Ichimoku(Ichimoku::default()),
+            IndicatorKind::Supertrend => {
+                Self::Supertrend(SuperTrend::new(period, 3.0).map_err(init)?)
+            }
+        })
+    }
+
+    /// Feed one candle; returns the value of each output line.
+    fn next(&mut self, item: &DataItem, volume: f64) -> Vec<(&'static str, f64)> {
+        match self {
+            Self::Rsi(ind) => vec![("value", ind.next(item.close()))],
+            Self::Macd(ind) => {
+                let out = ind.next(item.close());
+                vec![
+                    ("macd", out.macd),
+                    ("signal", out.signal),
+                    ("histogram", out.histogram),
+                ]
+            }
+            Self::Bbands(ind) => {
+                let out = ind.next(item.close());
+                vec![
+                    ("upper", out.upper),
+                    ("middle", out.average),
+                    ("lower", out.lower),
+                ]
+            }
+            Self::Atr(ind) => vec![("value", ind.next(item))],
+            Self::Ema(ind) => vec![("value", ind.next(item.close()))],
+            Self::Sma(ind) => vec![("value", ind.next(item.close()))],
+            Self::Stoch(k_ind, d_ema) => {
+                let k = k_ind.next(item);
+                vec![("k", k), ("d", d_ema.next(k))]
+            }
+            Self::Cci(ind) => vec![("value", ind.next(item))],
+            Self::Willr { period, window } => {
+                window.push_back((item.high(), item.low()));
+                if window.len() > *period {
+                    window.pop_front();
+                }
+                let high = window.iter().map(|w| w.0).fold(f64::MIN, f64::max);
+                let low = window.iter().map(|w| w.1).fold(f64::MAX, f64::min);
+                let wr = if (high - low).abs() > f64::EPSILON {
+                    ((high - item.close()) / (high - low)) * -100.0
+                } else {
+                    -50.0
+                };
+                vec![("value", wr)]
+            }
+            Self::Obv { value, prev_close } => {
+                let close = item.close();
+                if let Some(pc) = *prev_close {
+                    if close > pc {
+                        *value += volume;
+                    } else if close < pc {
+                        *value -= volume;
+                    }
+                }
+                *prev_close = Some(close);
+                vec![("value", *value)]
+            }
+            // Empty until the cloud has enough history
+            Self::Ichimoku(ind) => match ind.next(item) {
+                Some(out) => vec![
+                    ("tenkan", out.tenkan),
+                    ("kijun", out.kijun),
+                    ("senkou_a", out.senkou_a),
+                    ("senkou_b", out.senkou_b),
+                    ("chikou", out.chikou),
+                ],
+                None => Vec::new(),
+            },
+            Self::Supertrend(ind) => {
+                let out = ind.next(item);
+                vec![
+                    ("value", out.value),
+                    ("upper", out.upper),
+                    ("lower", out.lower),
+                ]
+            }
+        }
+    }
+}
+
+/// Latest and previous-candle values for one spec.
+pub(crate) struct IndicatorReading {
+    pub spec: IndicatorSpec,
+    pub latest: Vec<(&'static str, f64)>,
+    pub previous: Option<Vec<(&'static str, f64)>>,
+}
+
+/// One candle fetch with every requested indicator computed over it.
+pub(crate) struct TaBundle {
+    pub items: Vec<DataItem>,
+    pub readings: Vec<IndicatorReading>,
+    pub gaps: Vec<CandleGap>,
+}
+
+impl TaBundle {
+    /// Latest value of `field` for `spec`.
+    pub fn value(&self, spec: &IndicatorSpec, field: &str) -> Option<f64> {
+        self.readings
+            .iter()
+            .find(|r| r.spec == *spec)?
+            .latest
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map(|(_, v)| *v)
+    }
+
+    pub fn last_close(&self) -> f64 {
+        self.items.last().map(|i| i.close()).unwrap_or(0.0)
+    }
+}
+
+/// Compute all `specs` in a single pass over the candles.
+fn compute_bundle(series: Series, specs: &[IndicatorSpec]) -> Result<TaBundle> {
+    let Series {
+        items,
+        volumes,
+        gaps,
+    } = series;
+    let mut states = specs
+        .iter()
+        .map(IndicatorState::new)
+        .collect::<Result<Vec<_>>>()?;
+    let mut latest: Vec<Vec<(&'static str, f64)>> = vec![Vec::new(); specs.len()];
+    let mut previous: Vec<Option<Vec<(&'static str, f64)>>> = vec![None; specs.len()];
+
+    for (item, volume) in items.iter().zip(&volumes) {
+        for (i, state) in states.iter_mut().enumerate() {
+            let prev = std::mem::replace(&mut latest[i], state.next(item, *volume));
+            if !prev.is_empty() {
+                previous[i] = Some(prev);
+            }
+        }
+    }
+
+    let readings = specs
+        .iter()
+        .zip(latest.into_iter().zip(previous))
+        .map(|(spec, (latest, previous))| IndicatorReading {
+            spec: *spec,
+            latest,
+            previous,
+        })
+        .collect();
+    Ok(TaBundle {
+        items,
+        readings,
+        gaps,
+    })
+}
+
+/// Fetch enough candles for the longest lookback and compute `specs`.
+pub(crate) async fn fetch_bundle(
+    perp: &dyn PerpModule,
+    ticker: &str,
+    timeframe: &str,
+    specs: &[IndicatorSpec],
+) -> Result<TaBundle> {
+    let lookback = specs.iter().map(|s| s.lookback()).max().unwrap_or(0);
+    let series = fetch_candles(perp, ticker, timeframe, lookback + 100).await?;
+    compute_bundle(series, specs)
+}
+
+fn format_field(kind: IndicatorKind, value: f64) -> String {
+    match kind {
+        IndicatorKind::Macd | IndicatorKind::Atr => format!("{:.4}", value),
+        IndicatorKind::Obv => format!("{:.0}", value),
+        _ => format!("{:.2}", value),
+    }
+}
+
+fn format_fields(kind: IndicatorKind, fields: &[(&'static str, f64)]) -> BTreeMap<String, String> {
+    fields
+        .iter()
+        .map(|(f, v)| (f.to_string(), format_field(kind, *v)))
+        .collect()
+}
+
+/// `atlas market hl ta <ticker> [--indicators rsi,macd,ema:50]`
+pub async fn ta(
+    ticker: &str,
+    timeframe: &str,
+    indicators: Option<&str>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let specs = parse_indicators(indicators.unwrap_or(DEFAULT_BUNDLE))?;
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
+    let bundle = fetch_bundle(perp.as_ref(), ticker, timeframe, &specs).await?;
+
+    let output = TaBundleOutput {
+        ticker: ticker.to_uppercase(),
+        timeframe: timeframe.to_string(),
+        candles: bundle.items.len(),
+        last_price: format!("{:.2}", bundle.last_close()),
+        indicators: bundle
+            .readings
+            .iter()
+            .map(|r| {
+                let reading = TaReading {
+                    latest: format_fields(r.spec.kind, &r.latest),
+                    previous: r.previous.as_ref().map(|p| format_fields(r.spec.kind, p)),
+                };
+                (r.spec.to_string(), reading)
+            })
+            .collect(),
+        gaps: bundle.gaps,
+    };
+    render(fmt, &output)?;
+    Ok(())
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 //  RSI
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn rsi(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, period + 100).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, period + 100).await?;
     let mut rsi_ind =
         RelativeStrengthIndex::new(period).map_err(|e| anyhow::anyhow!("RSI init: {e}"))?;
 
@@ -97,6 +445,7 @@ pub async fn rsi(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
                     "ticker": t, "timeframe": timeframe, "period": period,
                     "rsi": format!("{:.2}", rsi_val), "signal": signal,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -104,6 +453,7 @@ pub async fn rsi(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
             println!("📊 RSI({period}) for {t} [{timeframe}]");
             println!("   Value:  {:.2}", rsi_val);
             println!("   Signal: {signal}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -114,7 +464,7 @@ pub async fn rsi(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn macd(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, 150).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, 150).await?;
     let mut macd_ind = MovingAverageConvergenceDivergence::new(12, 26, 9)
         .map_err(|e| anyhow::anyhow!("MACD init: {e}"))?;
 
@@ -144,6 +494,7 @@ pub async fn macd(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()
                     "histogram": format!("{:.4}", output.histogram),
                     "trend": trend,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -153,6 +504,7 @@ pub async fn macd(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()
             println!("   Signal:    {:.4}", output.signal);
             println!("   Histogram: {:.4}", output.histogram);
             println!("   Trend:     {trend}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -163,7 +515,11 @@ pub async fn macd(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn vwap(ticker: &str, fmt: OutputFormat) -> Result<()> {
-    let (items, volumes) = fetch_data_items(ticker, "1h", 24).await?;
+    let Series {
+        items,
+        volumes,
+        gaps,
+    } = fetch_data_items(ticker, "1h", 24).await?;
 
     let mut cum_tp_vol = 0.0f64;
     let mut cum_vol = 0.0f64;
@@ -189,6 +545,7 @@ pub async fn vwap(ticker: &str, fmt: OutputFormat) -> Result<()> {
                     "ticker": t, "vwap": format!("{:.2}", vwap_val),
                     "last_price": format!("{:.2}", last), "position": pos,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -197,6 +554,7 @@ pub async fn vwap(ticker: &str, fmt: OutputFormat) -> Result<()> {
             println!("   VWAP:       ${:.2}", vwap_val);
             println!("   Last Price: ${:.2}", last);
             println!("   Position:   {pos} VWAP");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -207,7 +565,7 @@ pub async fn vwap(ticker: &str, fmt: OutputFormat) -> Result<()> {
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn bbands(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, period + 100).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, period + 100).await?;
     let mut bb =
         BollingerBands::new(period, 2.0_f64).map_err(|e| anyhow::anyhow!("BBANDS init: {e}"))?;
 
@@ -246,6 +604,7 @@ pub async fn bbands(ticker: &str, timeframe: &str, period: usize, fmt: OutputFor
                     "width_pct": format!("{:.2}", width),
                     "position": pos, "last_price": format!("{:.2}", last),
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -257,6 +616,7 @@ pub async fn bbands(ticker: &str, timeframe: &str, period: usize, fmt: OutputFor
             println!("   Width:    {:.2}%", width);
             println!("   Position: {pos}");
             println!("   Last:     ${:.2}", last);
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -267,7 +627,7 @@ pub async fn bbands(ticker: &str, timeframe: &str, period: usize, fmt: OutputFor
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn stoch(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, 100).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, 100).await?;
 
     // %K via SlowStochastic, %D via EMA of %K
     let mut stoch_ind =
@@ -304,6 +664,7 @@ pub async fn stoch(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<(
                     "k": format!("{:.2}", k_val), "d": format!("{:.2}", d_val),
                     "signal": signal, "cross": cross,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -313,6 +674,7 @@ pub async fn stoch(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<(
             println!("   %D:     {:.2}", d_val);
             println!("   Signal: {signal}");
             println!("   Cross:  {cross}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -323,7 +685,7 @@ pub async fn stoch(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<(
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn adx(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, period + 100).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, period + 100).await?;
 
     if items.len() < period + 1 {
         anyhow::bail!("Not enough data for ADX({period})");
@@ -409,6 +771,7 @@ pub async fn adx(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
                     "ticker": t, "timeframe": timeframe, "period": period,
                     "adx": format!("{:.2}", adx_val), "strength": strength,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -416,6 +779,7 @@ pub async fn adx(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
             println!("📊 ADX({period}) for {t} [{timeframe}]");
             println!("   ADX:      {:.2}", adx_val);
             println!("   Strength: {strength}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -426,7 +790,7 @@ pub async fn adx(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn atr(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, period + 100).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, period + 100).await?;
     let mut atr_ind =
         AverageTrueRange::new(period).map_err(|e| anyhow::anyhow!("ATR init: {e}"))?;
 
@@ -460,6 +824,7 @@ pub async fn atr(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
                     "volatility": volatility,
                     "last_price": format!("{:.2}", last),
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -469,6 +834,7 @@ pub async fn atr(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
             println!("   ATR%:       {:.2}%", atr_pct);
             println!("   Volatility: {volatility}");
             println!("   Last:       ${:.2}", last);
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -479,7 +845,7 @@ pub async fn atr(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn ema(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, period + 100).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, period + 100).await?;
     let mut ema_ind =
         ExponentialMovingAverage::new(period).map_err(|e| anyhow::anyhow!("EMA init: {e}"))?;
 
@@ -500,6 +866,7 @@ pub async fn ema(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
                     "ema": format!("{:.2}", ema_val),
                     "last_price": format!("{:.2}", last), "position": pos,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -508,6 +875,7 @@ pub async fn ema(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
             println!("   EMA:  ${:.2}", ema_val);
             println!("   Last: ${:.2}", last);
             println!("   Position: {pos}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -518,7 +886,7 @@ pub async fn ema(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn sma(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, period + 100).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, period + 100).await?;
     let mut sma_ind =
         SimpleMovingAverage::new(period).map_err(|e| anyhow::anyhow!("SMA init: {e}"))?;
 
@@ -539,6 +907,7 @@ pub async fn sma(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
                     "sma": format!("{:.2}", sma_val),
                     "last_price": format!("{:.2}", last), "position": pos,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -547,6 +916,7 @@ pub async fn sma(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
             println!("   SMA:  ${:.2}", sma_val);
             println!("   Last: ${:.2}", last);
             println!("   Position: {pos}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -557,7 +927,11 @@ pub async fn sma(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn obv(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()> {
-    let (items, volumes) = fetch_data_items(ticker, timeframe, 100).await?;
+    let Series {
+        items,
+        volumes,
+        gaps,
+    } = fetch_data_items(ticker, timeframe, 100).await?;
 
     let mut obv_val = 0.0f64;
     let mut prev_close = items[0].close();
@@ -592,6 +966,7 @@ pub async fn obv(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()>
                     "ticker": t, "timeframe": timeframe,
                     "obv": format!("{:.0}", obv_val), "trend": obv_trend,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -599,6 +974,7 @@ pub async fn obv(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()>
             println!("📊 OBV for {t} [{timeframe}]");
             println!("   OBV:   {:.0}", obv_val);
             println!("   Trend: {obv_trend}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -609,7 +985,7 @@ pub async fn obv(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()>
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn cci(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, period + 100).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, period + 100).await?;
     let mut cci_ind =
         CommodityChannelIndex::new(period).map_err(|e| anyhow::anyhow!("CCI init: {e}"))?;
 
@@ -634,6 +1010,7 @@ pub async fn cci(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
                     "ticker": t, "timeframe": timeframe, "period": period,
                     "cci": format!("{:.2}", cci_val), "signal": signal,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -641,6 +1018,7 @@ pub async fn cci(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
             println!("📊 CCI({period}) for {t} [{timeframe}]");
             println!("   CCI:    {:.2}", cci_val);
             println!("   Signal: {signal}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -651,7 +1029,7 @@ pub async fn cci(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn willr(ticker: &str, timeframe: &str, period: usize, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, period + 50).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, period + 50).await?;
 
     if items.len() < period {
         anyhow::bail!("Not enough data for Williams %R({period})");
@@ -690,6 +1068,7 @@ pub async fn willr(ticker: &str, timeframe: &str, period: usize, fmt: OutputForm
                     "ticker": t, "timeframe": timeframe, "period": period,
                     "willr": format!("{:.2}", wr), "signal": signal,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -697,6 +1076,7 @@ pub async fn willr(ticker: &str, timeframe: &str, period: usize, fmt: OutputForm
             println!("📊 Williams %R({period}) for {t} [{timeframe}]");
             println!("   %R:     {:.2}", wr);
             println!("   Signal: {signal}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
@@ -707,7 +1087,7 @@ pub async fn willr(ticker: &str, timeframe: &str, period: usize, fmt: OutputForm
 // ═══════════════════════════════════════════════════════════════════════
 
 pub async fn sar(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, 100).await?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, 100).await?;
 
     if items.len() < 3 {
         anyhow::bail!("Not enough data for Parabolic SAR");
@@ -775,6 +1155,7 @@ pub async fn sar(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()>
                     "sar": format!("{:.4}", sar),
                     "last_price": format!("{:.2}", last), "signal": signal,
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -783,199 +1164,404 @@ pub async fn sar(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()>
             println!("   SAR:    ${:.4}", sar);
             println!("   Last:   ${:.2}", last);
             println!("   Signal: {signal}");
+            warn_gaps(&gaps);
         }
     }
     Ok(())
 }
 
 // ═══════════════════════════════════════════════════════════════════════
-//  CANDLESTICK PATTERNS (manual detection)
+//  ICHIMOKU CLOUD
 // ═══════════════════════════════════════════════════════════════════════
 
-pub async fn patterns(ticker: &str, timeframe: &str, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, timeframe, 10).await?;
-
-    if items.len() < 3 {
-        anyhow::bail!("Need at least 3 candles for pattern detection");
-    }
-
-    let mut detected: Vec<(&str, &str, &str)> = Vec::new();
-    let n = items.len();
-    let last = &items[n - 1];
-    let prev = &items[n - 2];
-
-    let body = (last.close() - last.open()).abs();
-    let range = last.high() - last.low();
-    let upper_shadow = last.high() - last.close().max(last.open());
-    let lower_shadow = last.close().min(last.open()) - last.low();
-
-    // Doji
-    if range > 0.0 && body / range < 0.1 {
-        detected.push(("Doji", "indecision", "neutral"));
-    }
-
-    // Hammer (bullish reversal)
-    if range > 0.0 && lower_shadow > body * 2.0 && upper_shadow < body * 0.5 {
-        detected.push(("Hammer", "bullish reversal", "bullish"));
-    }
-
-    // Shooting Star (bearish reversal)
-    if range > 0.0 && upper_shadow > body * 2.0 && lower_shadow < body * 0.5 {
-        detected.push(("Shooting Star", "bearish reversal", "bearish"));
+pub async fn ichimoku(
+    ticker: &str,
+    timeframe: &str,
+    tenkan: usize,
+    kijun: usize,
+    senkou: usize,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let mut ind =
+        Ichimoku::new(tenkan, kijun, senkou).map_err(|e| anyhow::anyhow!("ICHIMOKU init: {e}"))?;
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, ind.warmup() + 50).await?;
+
+    let mut output = None;
+    for item in &items {
+        output = ind.next(item);
     }
+    let Some(out) = output else {
+        anyhow::bail!(
+            "Not enough data for Ichimoku({tenkan},{kijun},{senkou}): need {} candles",
+            ind.warmup()
+        );
+    };
 
-    // Bullish Engulfing
-    if prev.close() < prev.open()
-        && last.close() > last.open()
-        && last.open() <= prev.close()
-        && last.close() >= prev.open()
-    {
-        detected.push(("Bullish Engulfing", "bullish reversal", "bullish"));
-    }
+    let last = items.last().map(|i| i.close()).unwrap_or(0.0);
+    let position = out.cloud_position(last);
+    let cross = if out.tenkan > out.kijun {
+        "bullish (tenkan > kijun)"
+    } else {
+        "bearish (tenkan < kijun)"
+    };
+    let chikou = if out.chikou > out.chikou_ref {
+        "bullish (above price)"
+    } else {
+        "bearish (below price)"
+    };
+    let t = ticker.to_uppercase();
 
-    // Bearish Engulfing
-    if prev.close() > prev.open()
-        && last.close() < last.open()
-        && last.open() >= prev.close()
-        && last.close() <= prev.open()
-    {
-        detected.push(("Bearish Engulfing", "bearish reversal", "bearish"));
+    match fmt {
+        OutputFormat::Json | OutputFormat::JsonPretty => {
+            print_json(
+                &serde_json::json!({
+                    "ticker": t, "timeframe": timeframe,
+                    "periods": [tenkan, kijun, senkou],
+                    "tenkan": format!("{:.2}", out.tenkan),
+                    "kijun": format!("{:.2}", out.kijun),
+                    "senkou_a": format!("{:.2}", out.senkou_a),
+                    "senkou_b": format!("{:.2}", out.senkou_b),
+                    "chikou": format!("{:.2}", out.chikou),
+                    "position": position, "cross": cross, "chikou_signal": chikou,
+                    "last_price": format!("{:.2}", last),
+                }),
+                &gaps,
+                matches!(fmt, OutputFormat::JsonPretty),
+            );
+        }
+        OutputFormat::Table => {
+            println!("📊 Ichimoku({tenkan},{kijun},{senkou}) for {t} [{timeframe}]");
+            println!("   Tenkan:   ${:.2}", out.tenkan);
+            println!("   Kijun:    ${:.2}", out.kijun);
+            println!("   Senkou A: ${:.2}", out.senkou_a);
+            println!("   Senkou B: ${:.2}", out.senkou_b);
+            println!("   Chikou:   ${:.2}", out.chikou);
+            println!("   Position: {position}");
+            println!("   Cross:    {cross}");
+            println!("   Chikou:   {chikou}");
+            println!("   Last:     ${:.2}", last);
+            warn_gaps(&gaps);
+        }
     }
+    Ok(())
+}
 
-    // Bullish Harami
-    if prev.close() < prev.open()
-        && last.close() > last.open()
-        && last.open() > prev.close()
-        && last.close() < prev.open()
-    {
-        detected.push(("Bullish Harami", "bullish reversal", "bullish"));
-    }
+// ═══════════════════════════════════════════════════════════════════════
+//  SUPERTREND
+// ═══════════════════════════════════════════════════════════════════════
 
-    // Bearish Harami
-    if prev.close() > prev.open()
-        && last.close() < last.open()
-        && last.open() < prev.close()
-        && last.close() > prev.open()
-    {
-        detected.push(("Bearish Harami", "bearish reversal", "bearish"));
+pub async fn supertrend(
+    ticker: &str,
+    timeframe: &str,
+    period: usize,
+    multiplier: f64,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let Series { items, gaps, .. } = fetch_data_items(ticker, timeframe, period + 100).await?;
+    let mut st =
+        SuperTrend::new(period, multiplier).map_err(|e| anyhow::anyhow!("SUPERTREND init: {e}"))?;
+
+    let mut outputs = Vec::with_capacity(items.len());
+    for item in &items {
+        outputs.push(st.next(item));
     }
+    let Some(out) = outputs.last().copied() else {
+        anyhow::bail!("Not enough data for SuperTrend({period})");
+    };
+    // Candles since the last flip
+    let held = outputs
+        .iter()
+        .rev()
+        .take_while(|o| o.uptrend == out.uptrend)
+        .count();
 
+    let last = items.last().map(|i| i.close()).unwrap_or(0.0);
+    let signal = if out.uptrend { "bullish" } else { "bearish" };
     let t = ticker.to_uppercase();
 
     match fmt {
         OutputFormat::Json | OutputFormat::JsonPretty => {
-            let pats: Vec<serde_json::Value> = detected.iter().map(|(name, kind, sig)| {
-                serde_json::json!({ "pattern": name, "type": kind, "signal": sig })
-            }).collect();
             print_json(
                 &serde_json::json!({
-                    "ticker": t, "timeframe": timeframe, "patterns": pats,
+                    "ticker": t, "timeframe": timeframe,
+                    "period": period, "multiplier": multiplier,
+                    "supertrend": format!("{:.4}", out.value),
+                    "upper": format!("{:.4}", out.upper),
+                    "lower": format!("{:.4}", out.lower),
+                    "signal": signal, "candles_in_trend": held,
+                    "last_price": format!("{:.2}", last),
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
         OutputFormat::Table => {
-            println!("🕯️ Candlestick Patterns for {t} [{timeframe}]\n");
-            if detected.is_empty() {
-                println!("   No patterns detected on latest candle.");
-            } else {
-                println!("{:<22} {:<22} {:<10}", "PATTERN", "TYPE", "SIGNAL");
-                println!("{}", "─".repeat(55));
-                for (name, kind, sig) in &detected {
-                    println!("{:<22} {:<22} {:<10}", name, kind, sig);
-                }
-            }
+            println!("📊 SuperTrend({period}, {multiplier}) for {t} [{timeframe}]");
+            println!("   SuperTrend: ${:.4}", out.value);
+            println!("   Signal:     {signal} ({held} candles)");
+            println!("   Last:       ${:.2}", last);
+            warn_gaps(&gaps);
         }
     }
     Ok(())
 }
 
 // ═══════════════════════════════════════════════════════════════════════
-//  TREND (multi-indicator composite)
+//  CANDLESTICK PATTERNS
 // ═══════════════════════════════════════════════════════════════════════
 
-pub async fn trend(ticker: &str, fmt: OutputFormat) -> Result<()> {
-    let (items, _) = fetch_data_items(ticker, "1h", 200).await?;
-    let t = ticker.to_uppercase();
+/// Closed candles for `ticker`, oldest first, and the gaps between them:
+/// the forming candle is left out, since a pattern on it can still
+/// disappear.
+async fn closed_ohlc(
+    perp: &dyn PerpModule,
+    ticker: &str,
+    timeframe: Timeframe,
+    count: usize,
+) -> Result<(Vec<Ohlc>, Vec<CandleGap>)> {
+    let mut candles = perp
+        .candles(&ticker.to_uppercase(), timeframe.as_str(), count + 1)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    candle_gaps::dedup(&mut candles);
+    let bars: Vec<Ohlc> = candles.iter().map(Ohlc::from).collect();
+    let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+    let bars = patterns::closed(&bars, timeframe.duration_ms(), now_ms).to_vec();
+    let open_times: Vec<u64> = bars.iter().map(|b| b.time_ms).collect();
+    let gaps = check_gaps(ticker, timeframe, &open_times)?;
+    Ok((bars, gaps))
+}
 
-    // RSI
-    let mut rsi_ind = RelativeStrengthIndex::new(14).unwrap();
-    let mut rsi_val = 50.0;
-    for item in &items {
-        rsi_val = rsi_ind.next(item.close());
+/// `atlas market hl patterns <ticker> [--lookback 20] [--patterns all]`
+pub async fn patterns(
+    ticker: &str,
+    timeframe: Timeframe,
+    lookback: usize,
+    list: &str,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let families = patterns::parse_families(list)?;
+    let lookback = lookback.max(1);
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
+    // Extra history so the oldest candles searched still have an ATR
+    let (bars, gaps) = closed_ohlc(
+        perp.as_ref(),
+        ticker,
+        timeframe,
+        lookback + patterns::ATR_PERIOD,
+    )
+    .await?;
+    if bars.len() < 2 {
+        anyhow::bail!("Need at least 2 closed candles for pattern detection");
     }
 
-    // MACD
-    let mut macd_ind = MovingAverageConvergenceDivergence::new(12, 26, 9).unwrap();
-    let mut macd_out = ta::indicators::MovingAverageConvergenceDivergenceOutput {
-        macd: 0.0,
-        signal: 0.0,
-        histogram: 0.0,
+    let rows = patterns::scan(&bars, &families, lookback)
+        .into_iter()
+        .map(|found| PatternRow {
+            time: format_ms(found.time_ms as i64),
+            candles_ago: bars.len() - found.index,
+            signal: found.direction.as_str().into(),
+            found,
+        })
+        .collect();
+    let output = PatternsOutput {
+        ticker: ticker.to_uppercase(),
+        timeframe: timeframe.to_string(),
+        lookback: lookback.min(bars.len()),
+        patterns: rows,
+        gaps,
     };
-    for item in &items {
-        macd_out = macd_ind.next(item.close());
-    }
+    render(fmt, &output)?;
+    Ok(())
+}
 
-    // SMA 20 & 50
-    let mut sma20_ind = SimpleMovingAverage::new(20).unwrap();
-    let mut sma50_ind = SimpleMovingAverage::new(50).unwrap();
-    let mut sma20_val = 0.0;
-    let mut sma50_val = 0.0;
-    for item in &items {
-        sma20_val = sma20_ind.next(item.close());
-        sma50_val = sma50_ind.next(item.close());
-    }
+/// `atlas market hl patterns-scan [--timeframe 4h] [--patterns all] [--limit 50]`
+///
+/// Checks the latest closed candle of the `limit` most traded perps,
+/// fetched concurrently. A market whose candles fail becomes a warning, as
+/// does one with missing candles.
+pub async fn patterns_scan(
+    timeframe: Timeframe,
+    list: &str,
+    limit: usize,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let families = patterns::parse_families(list)?;
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
 
-    // Bollinger Bands
-    let mut bb = BollingerBands::new(20, 2.0_f64).unwrap();
-    let mut bb_out = ta::indicators::BollingerBandsOutput {
-        average: 0.0,
-        upper: 0.0,
-        lower: 0.0,
-    };
-    for item in &items {
-        bb_out = bb.next(item.close());
+    let mut tickers = perp
+        .all_tickers()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    tickers.sort_by(|a, b| {
+        let va = a.volume_24h.unwrap_or(Decimal::ZERO);
+        let vb = b.volume_24h.unwrap_or(Decimal::ZERO);
+        vb.cmp(&va)
+    });
+    tickers.truncate(limit);
+
+    // The latest candle plus its predecessor and an ATR's worth of history
+    let count = patterns::ATR_PERIOD + 1;
+    let results = map_concurrent(tickers.iter(), MAX_CONCURRENT_REQUESTS, |t| {
+        let perp = perp.as_ref();
+        async move { closed_ohlc(perp, &t.symbol, timeframe, count).await }
+    })
+    .await;
+
+    let mut hits = Vec::new();
+    let mut warnings = Vec::new();
+    for (ticker, result) in tickers.iter().zip(results) {
+        let bars = match result {
+            Ok((bars, gaps)) if !bars.is_empty() => {
+                if !gaps.is_empty() {
+                    let gaps = candle_gaps::describe(&gaps);
+                    warnings.push(format!("{}: {gaps}", ticker.symbol));
+                }
+                bars
+            }
+            Ok(_) => {
+                warnings.push(format!("{}: no closed candles", ticker.symbol));
+                continue;
+            }
+            Err(e) => {
+                warnings.push(format!("{}: {e:#}", ticker.symbol));
+                continue;
+            }
+        };
+        for found in patterns::detect_at(&bars, bars.len() - 1, &families) {
+            hits.push(PatternScanRow {
+                coin: ticker.symbol.clone(),
+                pattern: found.pattern.into(),
+                kind: found.kind.into(),
+                direction: found.direction.as_str().into(),
+                strength: found.strength,
+                time: format_ms(found.time_ms as i64),
+                volume_24h: ticker.volume_24h.map(|v| v.round().to_string()),
+            });
+        }
     }
+    // Stable: equally strong hits stay in volume order
+    hits.sort_by(|a, b| b.strength.cmp(&a.strength));
+
+    let output = PatternScanOutput {
+        timeframe: timeframe.to_string(),
+        patterns: families.iter().map(|f| f.as_str().to_string()).collect(),
+        scanned: tickers.len(),
+        hits,
+        warnings,
+    };
+    render(fmt, &output)?;
+    Ok(())
+}
 
-    // ATR
-    let mut atr_ind = AverageTrueRange::new(14).unwrap();
-    let mut atr_val = 0.0;
-    for item in &items {
-        atr_val = atr_ind.next(item);
-    }
+// ═══════════════════════════════════════════════════════════════════════
+//  TREND (multi-indicator composite)
+// ═══════════════════════════════════════════════════════════════════════
 
-    let last = items.last().map(|i| i.close()).unwrap_or(0.0);
+/// Indicators the trend score is built from.
+fn trend_specs() -> [IndicatorSpec; 6] {
+    [
+        IndicatorSpec::new(IndicatorKind::Rsi, None),
+        IndicatorSpec::new(IndicatorKind::Macd, None),
+        IndicatorSpec::new(IndicatorKind::Sma, Some(20)),
+        IndicatorSpec::new(IndicatorKind::Sma, Some(50)),
+        IndicatorSpec::new(IndicatorKind::Bbands, None),
+        IndicatorSpec::new(IndicatorKind::Atr, None),
+    ]
+}
 
-    // Score
-    let mut score = 50i32;
-    if rsi_val > 50.0 {
-        score += ((rsi_val - 50.0) * 0.5) as i32;
-    } else {
-        score -= ((50.0 - rsi_val) * 0.5) as i32;
-    }
-    if macd_out.histogram > 0.0 {
-        score += 12;
-    } else {
-        score -= 12;
-    }
-    if macd_out.macd > macd_out.signal {
-        score += 5;
-    } else {
-        score -= 5;
-    }
-    if last > sma20_val {
-        score += 8;
-    } else {
-        score -= 8;
+/// Trend score and its inputs for one timeframe.
+struct TrendReading {
+    score: i32,
+    rsi: f64,
+    macd_histogram: f64,
+    atr: f64,
+    sma_20: f64,
+    sma_50: f64,
+    bb_upper: f64,
+    bb_lower: f64,
+    support: f64,
+    resistance: f64,
+    last: f64,
+}
+
+impl TrendReading {
+    /// Score a bundle built from [`trend_specs`]. `None` when there are
+    /// fewer candles than the slowest indicator needs.
+    fn from_bundle(bundle: &TaBundle) -> Option<Self> {
+        let [rsi_spec, macd_spec, sma20_spec, sma50_spec, bb_spec, atr_spec] = trend_specs();
+        let lookback = trend_specs().iter().map(|s| s.lookback()).max()?;
+        if bundle.items.len() < lookback {
+            return None;
+        }
+
+        let rsi = bundle.value(&rsi_spec, "value")?;
+        let macd = bundle.value(&macd_spec, "macd")?;
+        let signal = bundle.value(&macd_spec, "signal")?;
+        let macd_histogram = bundle.value(&macd_spec, "histogram")?;
+        let sma_20 = bundle.value(&sma20_spec, "value")?;
+        let sma_50 = bundle.value(&sma50_spec, "value")?;
+        let last = bundle.last_close();
+
+        let mut score = 50i32;
+        if rsi > 50.0 {
+            score += ((rsi - 50.0) * 0.5) as i32;
+        } else {
+            score -= ((50.0 - rsi) * 0.5) as i32;
+        }
+        if macd_histogram > 0.0 {
+            score += 12;
+        } else {
+            score -= 12;
+        }
+        if macd > signal {
+            score += 5;
+        } else {
+            score -= 5;
+        }
+        if last > sma_20 {
+            score += 8;
+        } else {
+            score -= 8;
+        }
+        if sma_20 > sma_50 {
+            score += 8;
+        } else {
+            score -= 8;
+        }
+
+        let items = &bundle.items;
+        let recent = items.len().saturating_sub(24);
+        Some(Self {
+            score: score.clamp(0, 100),
+            rsi,
+            macd_histogram,
+            atr: bundle.value(&atr_spec, "value")?,
+            sma_20,
+            sma_50,
+            bb_upper: bundle.value(&bb_spec, "upper")?,
+            bb_lower: bundle.value(&bb_spec, "lower")?,
+            support: items[recent..]
+                .iter()
+                .map(|i| i.low())
+                .fold(f64::MAX, f64::min),
+            resistance: items[recent..]
+                .iter()
+                .map(|i| i.high())
+                .fold(0.0f64, f64::max),
+            last,
+        })
     }
-    if sma20_val > sma50_val {
-        score += 8;
-    } else {
-        score -= 8;
+
+    fn label(&self) -> &'static str {
+        trend_label(self.score)
     }
-    let score = score.clamp(0, 100);
+}
 
-    let trend_label = if score >= 70 {
+fn trend_label(score: i32) -> &'static str {
+    if score >= 70 {
         "bullish"
     } else if score >= 55 {
         "slightly bullish"
@@ -985,34 +1571,42 @@ pub async fn trend(ticker: &str, fmt: OutputFormat) -> Result<()> {
         "slightly bearish"
     } else {
         "bearish"
-    };
+    }
+}
 
-    let recent = items.len().saturating_sub(24);
-    let support = items[recent..]
-        .iter()
-        .map(|i| i.low())
-        .fold(f64::MAX, f64::min);
-    let resistance = items[recent..]
-        .iter()
-        .map(|i| i.high())
-        .fold(0.0f64, f64::max);
+/// `atlas market hl trend <ticker> [--mtf 15m,1h,4h]`
+pub async fn trend(ticker: &str, mtf: Option<&str>, fmt: OutputFormat) -> Result<()> {
+    if let Some(list) = mtf {
+        return trend_mtf(ticker, list, fmt).await;
+    }
+
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
+    let bundle = fetch_bundle(perp.as_ref(), ticker, "1h", &trend_specs()).await?;
+    let r = TrendReading::from_bundle(&bundle)
+        .ok_or_else(|| anyhow::anyhow!("Not enough data for trend analysis"))?;
+    let t = ticker.to_uppercase();
+    let score = r.score;
+    let trend_label = r.label();
+    let gaps = bundle.gaps;
 
     match fmt {
         OutputFormat::Json | OutputFormat::JsonPretty => {
             print_json(
                 &serde_json::json!({
                     "ticker": t, "trend": trend_label, "score": score,
-                    "rsi": format!("{:.2}", rsi_val),
-                    "macd_histogram": format!("{:.4}", macd_out.histogram),
-                    "atr": format!("{:.4}", atr_val),
-                    "sma_20": format!("{:.2}", sma20_val),
-                    "sma_50": format!("{:.2}", sma50_val),
-                    "bb_upper": format!("{:.2}", bb_out.upper),
-                    "bb_lower": format!("{:.2}", bb_out.lower),
-                    "support": format!("{:.2}", support),
-                    "resistance": format!("{:.2}", resistance),
-                    "last_price": format!("{:.2}", last),
+                    "rsi": format!("{:.2}", r.rsi),
+                    "macd_histogram": format!("{:.4}", r.macd_histogram),
+                    "atr": format!("{:.4}", r.atr),
+                    "sma_20": format!("{:.2}", r.sma_20),
+                    "sma_50": format!("{:.2}", r.sma_50),
+                    "bb_upper": format!("{:.2}", r.bb_upper),
+                    "bb_lower": format!("{:.2}", r.bb_lower),
+                    "support": format!("{:.2}", r.support),
+                    "resistance": format!("{:.2}", r.resistance),
+                    "last_price": format!("{:.2}", r.last),
                 }),
+                &gaps,
                 matches!(fmt, OutputFormat::JsonPretty),
             );
         }
@@ -1021,16 +1615,120 @@ pub async fn trend(ticker: &str, fmt: OutputFormat) -> Result<()> {
             println!("   Trend:      {trend_label}");
             println!("   Score:      {score}/100");
             println!("   ─────────────────────");
-            println!("   RSI(14):    {:.2}", rsi_val);
-            println!("   MACD Hist:  {:.4}", macd_out.histogram);
-            println!("   ATR(14):    ${:.4}", atr_val);
-            println!("   SMA(20):    ${:.2}", sma20_val);
-            println!("   SMA(50):    ${:.2}", sma50_val);
-            println!("   BB:         ${:.2} — ${:.2}", bb_out.lower, bb_out.upper);
-            println!("   Support:    ${:.2}", support);
-            println!("   Resistance: ${:.2}", resistance);
-            println!("   Last:       ${:.2}", last);
+            println!("   RSI(14):    {:.2}", r.rsi);
+            println!("   MACD Hist:  {:.4}", r.macd_histogram);
+            println!("   ATR(14):    ${:.4}", r.atr);
+            println!("   SMA(20):    ${:.2}", r.sma_20);
+            println!("   SMA(50):    ${:.2}", r.sma_50);
+            println!("   BB:         ${:.2} — ${:.2}", r.bb_lower, r.bb_upper);
+            println!("   Support:    ${:.2}", r.support);
+            println!("   Resistance: ${:.2}", r.resistance);
+            println!("   Last:       ${:.2}", r.last);
+            warn_gaps(&gaps);
+        }
+    }
+    Ok(())
+}
+
+/// Trend on each timeframe concurrently, plus a confluence verdict.
+///
+/// Longer timeframes weigh more in the overall score: weight is the
+/// timeframe's rank by length (15m=1, 1h=2, 4h=3).
+async fn trend_mtf(ticker: &str, list: &str, fmt: OutputFormat) -> Result<()> {
+    let mut timeframes: Vec<Timeframe> = Vec::new();
+    for raw in list.split(',').map(str::trim).filter(|tf| !tf.is_empty()) {
+        // Dedupe on the canonical form, so `60m,1h` is one timeframe.
+        let tf: Timeframe = raw.parse()?;
+        if !timeframes.contains(&tf) {
+            timeframes.push(tf);
+        }
+    }
+    if timeframes.is_empty() {
+        anyhow::bail!("--mtf needs at least one timeframe, e.g. 15m,1h,4h");
+    }
+
+    let orch = crate::factory::readonly().await?;
+    let perp = orch.perp(None)?;
+    let specs = trend_specs();
+    let results = map_concurrent(timeframes.iter(), MAX_CONCURRENT_REQUESTS, |tf| {
+        let (perp, specs) = (perp.as_ref(), &specs);
+        async move { fetch_bundle(perp, ticker, tf.as_str(), specs).await }
+    })
+    .await;
+
+    let mut by_length = timeframes.clone();
+    by_length.sort_unstable();
+
+    let mut rows = Vec::with_capacity(timeframes.len());
+    let (mut weighted, mut weight_sum) = (0.0f64, 0.0f64);
+    let (mut bullish, mut bearish) = (0usize, 0usize);
+    for (tf, result) in timeframes.iter().zip(results) {
+        let reading = result.as_ref().ok().and_then(TrendReading::from_bundle);
+        let Some(r) = reading else {
+            rows.push(TrendTimeframeRow {
+                timeframe: tf.to_string(),
+                direction: "n/a".into(),
+                score: None,
+                indicators: BTreeMap::new(),
+                error: Some(match result {
+                    Err(e) => format!("{e:#}"),
+                    Ok(_) => "insufficient data".into(),
+                }),
+                gaps: Vec::new(),
+            });
+            continue;
+        };
+
+        let weight = (by_length.iter().position(|t| t == tf).unwrap_or(0) + 1) as f64;
+        weighted += r.score as f64 * weight;
+        weight_sum += weight;
+        if r.score >= 55 {
+            bullish += 1;
+        } else if r.score < 45 {
+            bearish += 1;
         }
+
+        rows.push(TrendTimeframeRow {
+            timeframe: tf.to_string(),
+            direction: r.label().into(),
+            score: Some(r.score),
+            indicators: BTreeMap::from([
+                ("rsi".to_string(), format!("{:.2}", r.rsi)),
+                (
+                    "macd_histogram".to_string(),
+                    format!("{:.4}", r.macd_histogram),
+                ),
+                ("sma_20".to_string(), format!("{:.2}", r.sma_20)),
+                ("sma_50".to_string(), format!("{:.2}", r.sma_50)),
+                ("atr".to_string(), format!("{:.4}", r.atr)),
+                ("last_price".to_string(), format!("{:.2}", r.last)),
+            ]),
+            error: None,
+            gaps: result.map(|b| b.gaps).unwrap_or_default(),
+        });
     }
+
+    if weight_sum == 0.0 {
+        anyhow::bail!("No timeframe had enough data for trend analysis ({list})");
+    }
+
+    let score = (weighted / weight_sum).round() as i32;
+    let n = timeframes.len();
+    let verdict = if bullish > bearish {
+        format!("bullish {bullish}/{n} timeframes")
+    } else if bearish > bullish {
+        format!("bearish {bearish}/{n} timeframes")
+    } else {
+        "mixed".to_string()
+    };
+
+    let output = TrendMtfOutput {
+        ticker: ticker.to_uppercase(),
+        timeframes: rows,
+        score,
+        direction: trend_label(score).into(),
+        verdict,
+    };
+    render(fmt, &output)?;
     Ok(())
 }
@@ -1,35 +1,169 @@
 use anyhow::Result;
-use atlas_core::config::{SizeInput, SizeMode};
-use atlas_core::fmt::order_result_to_output;
+use atlas_core::batch::{map_concurrent, MAX_CONCURRENT_REQUESTS};
+use atlas_core::config::AppConfig;
+use atlas_core::config::{HyperliquidConfig, ParamSource, SizeInput, SizeMode};
+use atlas_core::date_range::DateRange;
+use atlas_core::db::AtlasDb;
+use atlas_core::depth::{walk_book, ImpactEstimate};
+use atlas_core::engine::BuilderFee;
+use atlas_core::fmt::{format_decimal, order_result_to_output, truncate_address};
+use atlas_core::orchestrator::{fan_out, Gathered, MODULE_TIMEOUT};
 use atlas_core::output::{render, OutputFormat};
 use atlas_core::output::{
-    CancelOutput, CancelSingleOutput, FillRow, FillsOutput, OrderRow, OrdersOutput, PositionRow,
+    CancelOutput, CancelSingleOutput, CloseAllOutput, CloseAllRow, CloseAllSummary, FillRow,
+    FillsOutput, FlipOutput, FlipSide, MarketOrderPreview, OrderRow, OrdersOutput, PositionRow,
+    PositionsOutput,
 };
 use atlas_core::parse;
+use atlas_core::prompt::{confirm_action, confirm_attended};
+use atlas_core::risk::{
+    check_impact, check_market_order, check_order_risk, check_reduce_only, flip_order,
+    market_limit_price,
+};
+use atlas_core::spread_profile::{self, LiquidityWarning};
+use atlas_core::traits::PerpModule;
+use atlas_core::types::{
+    Fill, FillQuery, LimitOptions, OrderResult, Position, Side, Ticker, TriggerKind, TriggerOptions,
+};
 use atlas_core::workspace::load_config;
 use rust_decimal::prelude::*;
+use std::sync::Arc;
+
+use super::helpers::{order_row, parse_date_range, position_row, record_spreads, select_perps};
+
+/// Levels per side fetched for the impact estimate — all that
+/// Hyperliquid's `l2Book` returns.
+const IMPACT_BOOK_DEPTH: usize = 20;
+
+/// Apply `--override-risk` to the `trading` risk limits the module checks
+/// before every order that opens or adds exposure. Must run before the
+/// orchestrator is built.
+fn override_risk_limits(config: &AppConfig, override_risk: bool, fmt: OutputFormat) {
+    crate::factory::set_override_risk_limits(override_risk);
+    if override_risk && config.trading.has_risk_limits() && fmt == OutputFormat::Table {
+        println!("⚠ Risk limits overridden for this order (--override-risk)");
+    }
+}
+
+/// Apply `--force` to the order protections (`trading.max_slippage`,
+/// `trading.price_band`) that the module checks before sending a market
+/// order or close. Must run before the orchestrator is built.
+fn override_order_protections(config: &AppConfig, force: bool, fmt: OutputFormat) {
+    crate::factory::set_override_order_protections(force);
+    if force && config.trading.has_order_protections() && fmt == OutputFormat::Table {
+        println!("⚠ Order protections overridden for this order (--force)");
+    }
+}
+
+/// The order protections for a `--dry-run` preview, which never reaches
+/// the module. The band needs the latest 1m candle; without one (a fresh
+/// listing) only the slippage cap applies.
+async fn preview_order_protections(
+    perp: &Arc<dyn PerpModule>,
+    config: &AppConfig,
+    coin: &str,
+    side: &Side,
+    mid: f64,
+    slippage: f64,
+) -> atlas_core::error::AtlasResult<()> {
+    if !config.trading.has_order_protections() {
+        return Ok(());
+    }
+    let reference = if config.trading.price_band.is_some() {
+        perp.candles(coin, "1m", 1)
+            .await?
+            .last()
+            .and_then(|c| c.close.to_f64())
+            .unwrap_or(0.0)
+    } else {
+        0.0
+    };
+    check_market_order(&config.trading, coin, side, mid, slippage, reference)
+}
+
+/// The ticker's spread against the coin's recorded median, when
+/// `trading.spread_warn_multiple` is on. Only ever a warning: without
+/// enough history, or when the database can't be read, there is none.
+fn liquidity_check(config: &AppConfig, coin: &str, ticker: &Ticker) -> Option<LiquidityWarning> {
+    let multiple = config.trading.spread_warn_multiple?;
+    let current = spread_profile::ticker_spread_bps(ticker)?;
+    let from_ms = chrono::Utc::now().timestamp_millis() - spread_profile::RETENTION_MS;
+    let history = AtlasDb::open()
+        .and_then(|db| db.query_spread_samples("hyperliquid", coin, from_ms))
+        .map_err(|e| tracing::debug!(coin, error = %e, "spread history unavailable"))
+        .ok()?;
+    spread_profile::check(current, &history, multiple)
+}
+
+/// The order size: the argument, else the coin preset's `default_size`.
+fn preset_size<'a>(
+    hl_cfg: &'a HyperliquidConfig,
+    coin: &str,
+    arg: Option<&'a str>,
+) -> Result<(&'a str, ParamSource)> {
+    hl_cfg.size_for(coin, arg).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No size given and {coin} has no preset size. Pass one, or set it: \
+             atlas configure trading preset set {coin} --default-size '$500'"
+        )
+    })
+}
 
-/// `atlas order <coin> <side> <size> <price> [--reduce-only] [--tif Gtc|Ioc|Alo]`
+/// Confirmation suffix naming where a parameter came from; flags need none.
+fn source_note(source: ParamSource) -> &'static str {
+    match source {
+        ParamSource::Flag => "",
+        ParamSource::Preset => " (preset)",
+        ParamSource::Default => " (default)",
+    }
+}
+
+/// Confirmation row showing the builder fee the order will carry.
+fn builder_fee_row(config: &AppConfig) -> (&'static str, String) {
+    let fee = match BuilderFee::from_config(&config.modules.hyperliquid.config) {
+        Some(fee) => format!("{} bps → {}", fee.f, truncate_address(&fee.b)),
+        None => "off".to_string(),
+    };
+    ("Builder fee", fee)
+}
+
+/// `atlas order <coin> <side> <size> <price> [--reduce-only [--strict] | --reduce] [--tif gtc|ioc|alo] [--post-only] [--trigger <px> [--trigger-type market|limit]] [--cloid <id>] [--override-risk]`
+///
+/// A reduce-only order is checked against the open position first: it must
+/// face the other way, and a size above the position is capped at it with
+/// a warning, or refused when `strict`.
+///
+/// With `trigger`, the order is a stop that the module checks against the
+/// mid before sending: a buy stop must sit above it, a sell stop below.
+#[allow(clippy::too_many_arguments)]
 pub async fn limit_order(
     coin: &str,
     side: &str,
     size_str: &str,
     price: f64,
-    reduce_only: bool,
-    _tif: &str,
+    mut opts: LimitOptions,
+    trigger: Option<(f64, TriggerKind)>,
+    strict: bool,
+    override_risk: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
     let is_buy = parse::parse_side(side)?;
     let size_input = parse::parse_size(size_str)?;
     let config = load_config()?;
+    override_risk_limits(&config, override_risk, fmt);
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
     let hl_cfg = &config.modules.hyperliquid.config;
-    let lev = hl_cfg.default_leverage.max(1);
+    let (lev, _) = hl_cfg.leverage_for(&coin_upper, None);
 
     let price_dec =
         Decimal::from_f64(price).ok_or_else(|| anyhow::anyhow!("Invalid price: {price}"))?;
+    if let Some((trigger_px, kind)) = trigger {
+        let price = Decimal::from_f64(trigger_px)
+            .ok_or_else(|| anyhow::anyhow!("Invalid trigger price: {trigger_px}"))?;
+        opts.trigger = Some(TriggerOptions { price, kind });
+    }
 
     let size = match &size_input {
         SizeInput::Usdc(margin_usdc) => {
@@ -63,38 +197,74 @@ pub async fn limit_order(
         }
     };
 
-    let size_dec =
+    let mut size_dec =
         Decimal::from_f64(size).ok_or_else(|| anyhow::anyhow!("Invalid size: {size}"))?;
-    let uni_side = if is_buy {
-        atlas_core::types::Side::Buy
-    } else {
-        atlas_core::types::Side::Sell
-    };
+    let uni_side = if is_buy { Side::Buy } else { Side::Sell };
+
+    let mut warnings = Vec::new();
+    if opts.reduce_only {
+        let positions = perp.positions().await.map_err(|e| anyhow::anyhow!("{e}"))?;
+        let position = positions.iter().find(|p| p.symbol == coin_upper);
+        let checked = check_reduce_only(&coin_upper, position, uni_side, size_dec, strict)?;
+        size_dec = checked.size;
+        warnings.extend(checked.warning);
+    }
 
     let result = perp
-        .limit_order(&coin_upper, uni_side, size_dec, price_dec, reduce_only)
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
+        .limit_order(&coin_upper, uni_side, size_dec, price_dec, &opts)
+        .await?;
 
-    render(fmt, &order_result_to_output(&result))?;
+    let mut out = order_result_to_output(&result);
+    match &opts.trigger {
+        Some(t) => {
+            out.trigger_price = Some(t.price.to_string());
+            out.trigger_type = Some(t.kind.to_string());
+        }
+        None => out.tif = Some(opts.tif.to_string()),
+    }
+    out.cloid = opts.cloid;
+    out.warnings = warnings;
+    render(fmt, &out)?;
     Ok(())
 }
 
-/// `atlas buy <coin> <size> [--leverage 10] [--slippage 0.05]`
-pub async fn market_buy(
+/// `atlas buy|sell <coin> [size] [--leverage 10] [--slippage 0.05] [--max-impact-bps N] [--dry-run] [--yes] [--override-risk] [--force]`
+///
+/// Size, leverage and slippage come from the flags, then the coin's
+/// preset, then the module defaults; the confirmation shows which.
+///
+/// The L2 book is walked for the resolved size whenever the order is
+/// previewed, confirmed or guarded by `max_impact_bps`. Only the guard
+/// makes the estimate mandatory; otherwise a book that can't be fetched
+/// just leaves it out. `dry_run` stops after the checks and prints the
+/// preview instead of sending.
+#[allow(clippy::too_many_arguments)]
+pub async fn market_open(
+    side: Side,
     coin: &str,
-    size_str: &str,
+    size_str: Option<&str>,
     leverage: Option<u32>,
     slippage: Option<f64>,
+    max_impact_bps: Option<f64>,
+    dry_run: bool,
+    yes: bool,
+    override_risk: bool,
+    force: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
-    let size_input = parse::parse_size(size_str)?;
     let config = load_config()?;
+    override_order_protections(&config, force, fmt);
+    override_risk_limits(&config, override_risk, fmt);
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
     let hl_cfg = &config.modules.hyperliquid.config;
-    let lev = leverage.unwrap_or(hl_cfg.default_leverage).max(1);
+    let (size_str, size_src) = preset_size(hl_cfg, &coin_upper, size_str)?;
+    let size_input = parse::parse_size_for(size_str, &coin_upper)?;
+    let (lev, lev_src) = hl_cfg.leverage_for(&coin_upper, leverage);
+    let (slip, slip_src) = hl_cfg.slippage_for(&coin_upper, slippage);
+    let is_buy = side == Side::Buy;
+    let verb = side.to_string();
 
     let ticker = perp
         .ticker(&coin_upper)
@@ -102,97 +272,524 @@ pub async fn market_buy(
         .map_err(|e| anyhow::anyhow!("{e}"))?;
     let mark = ticker.mid_price.to_f64().unwrap_or(0.0);
     let (size, _) = hl_cfg.resolve_size_input(&coin_upper, &size_input, mark, Some(lev));
+    // Checked before this sample joins the history it is compared with
+    let liquidity_warning = liquidity_check(&config, &coin_upper, &ticker);
+    record_spreads(std::slice::from_ref(&ticker));
 
-    if fmt == OutputFormat::Table {
-        println!("📤 MARKET BUY {}", hl_cfg.format_size(&coin_upper, size));
+    if fmt == OutputFormat::Table && !dry_run {
+        println!("📤 MARKET {verb} {}", hl_cfg.format_size(&coin_upper, size));
     }
 
     let size_dec =
         Decimal::from_f64(size).ok_or_else(|| anyhow::anyhow!("Invalid size: {size}"))?;
 
-    let effective_slippage = slippage.or(Some(hl_cfg.default_slippage));
+    let effective_slippage = Some(slip);
 
-    let result = perp
-        .market_order(
+    // A dry run never reaches the module, which checks these on send
+    if dry_run && !override_risk {
+        check_order_risk(
+            perp.as_ref(),
+            &config.trading,
             &coin_upper,
-            atlas_core::types::Side::Buy,
+            &side,
             size_dec,
-            effective_slippage,
+            mark,
         )
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
+        .await?;
+    }
+    if dry_run && !force {
+        preview_order_protections(perp, &config, &coin_upper, &side, mark, slip).await?;
+    }
 
-    render(fmt, &order_result_to_output(&result))?;
+    let skip = yes || !config.trading.confirm;
+    let impact = if dry_run || !skip || max_impact_bps.is_some() {
+        estimate_impact(perp, &coin_upper, &side, size_dec, max_impact_bps).await?
+    } else {
+        None
+    };
+
+    let limit_price = market_limit_price(mark, is_buy, slip);
+    if dry_run {
+        let preview = MarketOrderPreview {
+            symbol: coin_upper.clone(),
+            side: verb.to_lowercase(),
+            size: size_dec.normalize().to_string(),
+            size_source: size_src,
+            mark: format!("{mark:.4}"),
+            notional: format!("{:.2}", size * mark),
+            leverage: lev,
+            leverage_source: lev_src,
+            slippage: slip,
+            slippage_source: slip_src,
+            limit_price: format!("{limit_price:.4}"),
+            estimated_avg_px: impact
+                .as_ref()
+                .and_then(|i| i.avg_px)
+                .map(|px| px.normalize().to_string()),
+            estimated_impact_bps: impact
+                .as_ref()
+                .and_then(|i| i.impact_bps)
+                .map(|bps| format_decimal(bps, 2)),
+            book_coverage_pct: impact
+                .as_ref()
+                .map(|i| i.coverage_pct.round_dp(2).normalize().to_string()),
+            liquidity_warning,
+        };
+        return render(fmt, &preview);
+    }
+
+    let mut summary = vec![
+        ("Market", coin_upper.clone()),
+        (
+            "Size",
+            format!(
+                "{}{}",
+                hl_cfg.format_size(&coin_upper, size),
+                source_note(size_src)
+            ),
+        ),
+        ("Mark", format!("${mark:.4}")),
+        (
+            "Price band",
+            format!(
+                "{} ${limit_price:.4} ({:.1}% slip){}",
+                if is_buy { "≤" } else { "≥" },
+                slip * 100.0,
+                source_note(slip_src)
+            ),
+        ),
+    ];
+    if let Some(impact) = &impact {
+        summary.push(("Est. impact", impact.describe()));
+    }
+    if let Some(w) = &liquidity_warning {
+        summary.push((
+            "Liquidity",
+            format!("⚠ {:.2} bps, {:.1}x median", w.spread_bps, w.ratio),
+        ));
+    }
+    summary.extend([
+        ("Notional", format!("${:.2}", size * mark)),
+        ("Leverage", format!("{lev}x{}", source_note(lev_src))),
+        builder_fee_row(&config),
+    ]);
+    let proceed = confirm_action(&format!("MARKET {verb} — CONFIRM"), &summary, skip, fmt)?;
+    if !proceed {
+        return Ok(());
+    }
+
+    let result = perp
+        .market_order(&coin_upper, side, size_dec, effective_slippage)
+        .await?;
+
+    let mut out = order_result_to_output(&result);
+    out.liquidity_warning = liquidity_warning;
+    render(fmt, &out)?;
     Ok(())
 }
 
-/// `atlas sell <coin> <size> [--leverage 10] [--slippage 0.05]`
-pub async fn market_sell(
+/// Walk the L2 book for a market order of `size`. With a `max_impact_bps`
+/// guard the estimate must exist and stay under it; without one, a failed
+/// book fetch only drops the estimate.
+async fn estimate_impact(
+    perp: &Arc<dyn PerpModule>,
     coin: &str,
-    size_str: &str,
-    leverage: Option<u32>,
+    side: &Side,
+    size: Decimal,
+    max_impact_bps: Option<f64>,
+) -> Result<Option<ImpactEstimate>> {
+    let book = match perp.orderbook(coin, IMPACT_BOOK_DEPTH).await {
+        Ok(book) => book,
+        Err(e) if max_impact_bps.is_none() => {
+            tracing::debug!(coin, error = %e, "order book unavailable, skipping impact estimate");
+            return Ok(None);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let estimate = walk_book(&book, side.clone(), size);
+    if let Some(max) = max_impact_bps {
+        check_impact(coin, &estimate, max)?;
+    }
+    Ok(Some(estimate))
+}
+
+/// `atlas close <coin> [--size 0.5] [--slippage 0.05] [--yes] [--force]`
+pub async fn close_position(
+    coin: &str,
+    size: Option<f64>,
     slippage: Option<f64>,
+    yes: bool,
+    force: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
-    let size_input = parse::parse_size(size_str)?;
     let config = load_config()?;
+    override_order_protections(&config, force, fmt);
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
-    let hl_cfg = &config.modules.hyperliquid.config;
-    let lev = leverage.unwrap_or(hl_cfg.default_leverage).max(1);
+    let coin_upper = perp.resolve_symbol(coin)?;
 
-    let ticker = perp
-        .ticker(&coin_upper)
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
-    let mark = ticker.mid_price.to_f64().unwrap_or(0.0);
-    let (size, _) = hl_cfg.resolve_size_input(&coin_upper, &size_input, mark, Some(lev));
+    let size_dec = size.and_then(Decimal::from_f64);
+    let (slip, _) = config
+        .modules
+        .hyperliquid
+        .config
+        .slippage_for(&coin_upper, slippage);
+    let effective_slippage = Some(slip);
 
-    if fmt == OutputFormat::Table {
-        println!("📤 MARKET SELL {}", hl_cfg.format_size(&coin_upper, size));
-    }
+    let skip = yes || !config.trading.confirm;
+    let position = perp
+        .positions()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .into_iter()
+        .find(|p| p.symbol.eq_ignore_ascii_case(&coin_upper));
 
-    let size_dec =
-        Decimal::from_f64(size).ok_or_else(|| anyhow::anyhow!("Invalid size: {size}"))?;
+    if !skip {
+        let (open, open_size, mark) = match position {
+            Some(p) => (
+                format!("{} {}", p.side, p.size.abs()),
+                p.size.abs(),
+                p.mark_price.or(p.entry_price).unwrap_or_default(),
+            ),
+            None => ("none".to_string(), Decimal::ZERO, Decimal::ZERO),
+        };
+        let close_size = size_dec
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "full".to_string());
+        let notional = size_dec.unwrap_or(open_size) * mark;
 
-    let effective_slippage = slippage.or(Some(hl_cfg.default_slippage));
+        let proceed = confirm_action(
+            "CLOSE POSITION — CONFIRM",
+            &[
+                ("Market", coin_upper.clone()),
+                ("Position", open),
+                ("Close size", close_size),
+                ("Notional", format!("${notional:.2}")),
+                ("Slippage", format!("{:.1}%", slip * 100.0)),
+                builder_fee_row(&config),
+            ],
+            skip,
+            fmt,
+        )?;
+        if !proceed {
+            return Ok(());
+        }
+    }
 
     let result = perp
-        .market_order(
-            &coin_upper,
-            atlas_core::types::Side::Sell,
-            size_dec,
-            effective_slippage,
-        )
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
+        .close_position(&coin_upper, size_dec, effective_slippage)
+        .await?;
 
     render(fmt, &order_result_to_output(&result))?;
     Ok(())
 }
 
-/// `atlas close <coin> [--size 0.5] [--slippage 0.05]`
-pub async fn close_position(
+/// `atlas hl perp close-all [--only-losing|--only-winning] [--coin-prefix K] [--slippage 0.05] [--yes] [--force]`
+///
+/// Closes every matching position with reduce-only IOC orders, a few at a
+/// time. A market that rejects — or fails its price band — doesn't stop
+/// the others; it becomes an error row. The prompt can only be skipped
+/// with `--yes`, not `trading.confirm`.
+#[allow(clippy::too_many_arguments)]
+pub async fn close_all(
+    only_losing: bool,
+    only_winning: bool,
+    coin_prefix: Option<&str>,
+    slippage: Option<f64>,
+    yes: bool,
+    force: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let config = load_config()?;
+    override_order_protections(&config, force, fmt);
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+
+    let prefix = coin_prefix.map(str::to_uppercase);
+    let positions: Vec<Position> = perp
+        .positions()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .into_iter()
+        .filter(|p| !p.size.is_zero())
+        .filter(|p| {
+            prefix
+                .as_deref()
+                .map_or(true, |pre| p.symbol.to_uppercase().starts_with(pre))
+        })
+        .filter(|p| {
+            let pnl = p.unrealized_pnl.unwrap_or_default();
+            (!only_losing || pnl < Decimal::ZERO) && (!only_winning || pnl > Decimal::ZERO)
+        })
+        .collect();
+    if positions.is_empty() {
+        return render(
+            fmt,
+            &CloseAllOutput {
+                results: Vec::new(),
+                summary: CloseAllSummary {
+                    closed: 0,
+                    failed: 0,
+                    realized_pnl: "0".into(),
+                },
+            },
+        );
+    }
+
+    let effective_slippage = slippage.or(Some(config.modules.hyperliquid.config.default_slippage));
+    let slip = effective_slippage.unwrap_or(0.0);
+
+    let lines: Vec<String> = positions
+        .iter()
+        .map(|p| {
+            let upnl = p
+                .unrealized_pnl
+                .map_or_else(|| "—".to_string(), |u| format!("${:.2}", u));
+            format!(
+                "{} {} (uPnL {upnl})",
+                side_name(p).to_uppercase(),
+                p.size.abs()
+            )
+        })
+        .collect();
+    let mut summary: Vec<(&str, String)> = positions
+        .iter()
+        .zip(&lines)
+        .map(|(p, line)| (p.symbol.as_str(), line.clone()))
+        .collect();
+    summary.push(("Slippage", format!("{:.1}%", slip * 100.0)));
+    summary.push(builder_fee_row(&config));
+    if !confirm_attended("CLOSE ALL POSITIONS — CONFIRM", &summary, yes, fmt)? {
+        return Ok(());
+    }
+
+    let results = map_concurrent(positions.iter(), MAX_CONCURRENT_REQUESTS, |p| async move {
+        perp.close_position(&p.symbol, None, effective_slippage)
+            .await
+    })
+    .await;
+
+    // Closed PnL comes from the fills; one lookup covers every order
+    let fills = if results.iter().any(|r| r.is_ok()) {
+        perp.fills().await.unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let mut total_pnl = Decimal::ZERO;
+    let rows: Vec<CloseAllRow> = positions
+        .iter()
+        .zip(results)
+        .map(|(p, result)| match result {
+            Ok(order) => {
+                let pnl = closed_pnl(p, &order, &fills);
+                total_pnl += pnl.unwrap_or_default();
+                let partial = order.filled_size.is_some_and(|f| f < p.size.abs());
+                CloseAllRow {
+                    coin: p.symbol.clone(),
+                    side: side_name(p).into(),
+                    size: p.size.abs().to_string(),
+                    status: if partial { "partial" } else { "closed" }.into(),
+                    oid: order.order_id.parse().ok(),
+                    filled: order.filled_size.map(|f| f.to_string()),
+                    avg_price: order.avg_price.map(|px| px.to_string()),
+                    realized_pnl: pnl.map(|v| v.round_dp(2).to_string()),
+                    error: None,
+                }
+            }
+            Err(e) => CloseAllRow {
+                coin: p.symbol.clone(),
+                side: side_name(p).into(),
+                size: p.size.abs().to_string(),
+                status: "error".into(),
+                oid: None,
+                filled: None,
+                avg_price: None,
+                realized_pnl: None,
+                error: Some(e.to_string()),
+            },
+        })
+        .collect();
+
+    let failed = rows.iter().filter(|r| r.error.is_some()).count();
+    render(
+        fmt,
+        &CloseAllOutput {
+            summary: CloseAllSummary {
+                closed: rows.len() - failed,
+                failed,
+                realized_pnl: total_pnl.round_dp(2).to_string(),
+            },
+            results: rows,
+        },
+    )
+}
+
+fn side_name(p: &Position) -> &'static str {
+    if p.is_long() {
+        "long"
+    } else {
+        "short"
+    }
+}
+
+/// Closed PnL of a closing order: summed from its fills, or estimated from
+/// the average fill price when the fills aren't visible yet.
+fn closed_pnl(p: &Position, order: &OrderResult, fills: &[Fill]) -> Option<Decimal> {
+    let own: Vec<&Fill> = fills
+        .iter()
+        .filter(|f| f.order_id == order.order_id)
+        .collect();
+    if !own.is_empty() {
+        return Some(own.iter().filter_map(|f| f.realized_pnl).sum());
+    }
+    let (avg, entry, filled) = (order.avg_price?, p.entry_price?, order.filled_size?);
+    let per_unit = if p.is_long() {
+        avg - entry
+    } else {
+        entry - avg
+    };
+    Some(per_unit * filled)
+}
+
+/// `atlas hl perp flip <coin> [--size 1.5] [--slippage 0.05] [--yes] [--override-risk] [--force]`
+///
+/// Reverses the open position with one IOC order of `|current| + target`,
+/// so there is no flat window between closing and reopening. `size` is the
+/// absolute size of the new position (default: the current one).
+pub async fn flip(
     coin: &str,
     size: Option<f64>,
     slippage: Option<f64>,
+    yes: bool,
+    override_risk: bool,
+    force: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
     let config = load_config()?;
+    override_order_protections(&config, force, fmt);
+    override_risk_limits(&config, override_risk, fmt);
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
 
-    let size_dec = size.and_then(Decimal::from_f64);
-    let effective_slippage = slippage.or(Some(config.modules.hyperliquid.config.default_slippage));
+    let position = perp
+        .positions()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .into_iter()
+        .find(|p| p.symbol.eq_ignore_ascii_case(&coin_upper) && !p.size.is_zero())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No open {coin_upper} position to flip. Open one with: atlas hl perp buy|sell {coin_upper} <size>"
+            )
+        })?;
+    let current = position.size.abs();
+    let target = match size {
+        Some(s) => Some(
+            Decimal::from_f64(s)
+                .filter(|t| t.is_sign_positive() && !t.is_zero())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Invalid --size {s}: must be positive (use close to go flat)")
+                })?,
+        ),
+        None => None,
+    };
+
+    // Rounded to the market's lot the way the exchange will
+    let sz_decimals = perp
+        .markets()
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .into_iter()
+        .find(|m| m.symbol.eq_ignore_ascii_case(&coin_upper))
+        .and_then(|m| m.sz_decimals)
+        .unwrap_or(8)
+        .max(0) as u32;
+    let order = flip_order(&position, target, sz_decimals)?;
+    let (side, order_size, target) = (order.side, order.size, order.target);
+    let (before_side, after_side) = if position.is_long() {
+        ("long", "short")
+    } else {
+        ("short", "long")
+    };
+    // The mid the order is priced off, as for buy/sell
+    let mark = perp
+        .ticker(&coin_upper)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .mid_price
+        .to_f64()
+        .unwrap_or(0.0);
+    let (slip, _) = config
+        .modules
+        .hyperliquid
+        .config
+        .slippage_for(&coin_upper, slippage);
+    let effective_slippage = Some(slip);
+
+    // Post-flip exposure: the new position replaces the current one
+    let target_notional = target.to_f64().unwrap_or(0.0) * mark;
+
+    let band = match side {
+        Side::Buy => format!("≤ ${:.4}", mark * (1.0 + slip)),
+        Side::Sell => format!("≥ ${:.4}", mark * (1.0 - slip)),
+    };
+    let proceed = confirm_action(
+        "FLIP POSITION — CONFIRM",
+        &[
+            ("Market", coin_upper.clone()),
+            (
+                "Position",
+                format!("{} {current}", before_side.to_uppercase()),
+            ),
+            ("After", format!("{} {target}", after_side.to_uppercase())),
+            ("Order", format!("{side} {order_size} (IOC)")),
+            ("Mark", format!("${mark:.4}")),
+            ("Price band", format!("{band} ({:.1}% slip)", slip * 100.0)),
+            ("New notional", format!("${target_notional:.2}")),
+            builder_fee_row(&config),
+        ],
+        yes || !config.trading.confirm,
+        fmt,
+    )?;
+    if !proceed {
+        return Ok(());
+    }
 
     let result = perp
-        .close_position(&coin_upper, size_dec, effective_slippage)
+        .market_order(&coin_upper, side, order_size, effective_slippage)
+        .await?;
+
+    let after = perp
+        .positions()
         .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .into_iter()
+        .find(|p| p.symbol.eq_ignore_ascii_case(&coin_upper) && !p.size.is_zero());
+    let after = match after {
+        Some(p) => FlipSide {
+            side: if p.is_long() { "long" } else { "short" }.into(),
+            size: p.size.abs().to_string(),
+        },
+        None => FlipSide {
+            side: "flat".into(),
+            size: "0".into(),
+        },
+    };
 
-    render(fmt, &order_result_to_output(&result))?;
+    render(
+        fmt,
+        &FlipOutput {
+            coin: coin_upper,
+            before: FlipSide {
+                side: before_side.into(),
+                size: current.to_string(),
+            },
+            after,
+            order: order_result_to_output(&result),
+        },
+    )?;
     Ok(())
 }
 
@@ -200,7 +797,7 @@ pub async fn close_position(
 pub async fn cancel(coin: &str, oid: Option<u64>, fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let coin_upper = coin.to_uppercase();
+    let coin_upper = perp.resolve_symbol(coin)?;
 
     match oid {
         Some(id) => {
@@ -235,6 +832,65 @@ pub async fn cancel(coin: &str, oid: Option<u64>, fmt: OutputFormat) -> Result<(
     Ok(())
 }
 
+/// `atlas hl perp modify <coin> --oid <id>|--cloid <0x…> [--price P] [--size S]`
+///
+/// The new size and price go through the risk limits like a new order.
+#[allow(clippy::too_many_arguments)]
+pub async fn modify(
+    coin: &str,
+    oid: Option<u64>,
+    cloid: Option<&str>,
+    price: Option<f64>,
+    size: Option<f64>,
+    yes: bool,
+    override_risk: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let order_id = match (oid, cloid) {
+        (Some(oid), None) => oid.to_string(),
+        (None, Some(cloid)) => cloid.to_string(),
+        _ => anyhow::bail!("Give exactly one of --oid or --cloid"),
+    };
+    let positive = |v: f64, what: &str| {
+        Decimal::from_f64(v)
+            .filter(|d| *d > Decimal::ZERO)
+            .ok_or_else(|| anyhow::anyhow!("Invalid {what}: {v}"))
+    };
+    let price_dec = price.map(|p| positive(p, "price")).transpose()?;
+    let size_dec = size.map(|s| positive(s, "size")).transpose()?;
+    if price_dec.is_none() && size_dec.is_none() {
+        anyhow::bail!("Give a new --price and/or --size");
+    }
+
+    let config = load_config()?;
+    override_risk_limits(&config, override_risk, fmt);
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+    let coin_upper = perp.resolve_symbol(coin)?;
+
+    let mut summary = vec![("Market", coin_upper.clone()), ("Order", order_id.clone())];
+    if let Some(p) = price_dec {
+        summary.push(("New price", format!("${}", p.normalize())));
+    }
+    if let Some(s) = size_dec {
+        summary.push(("New size", s.normalize().to_string()));
+    }
+    if let (Some(p), Some(s)) = (price, size) {
+        summary.push(("New notional", format!("${:.2}", p * s)));
+    }
+    let skip = yes || !config.trading.confirm;
+    if !confirm_action("MODIFY ORDER — CONFIRM", &summary, skip, fmt)? {
+        return Ok(());
+    }
+
+    let result = perp
+        .modify_order(&coin_upper, &order_id, price_dec, size_dec)
+        .await?;
+
+    render(fmt, &order_result_to_output(&result))?;
+    Ok(())
+}
+
 /// `atlas orders`
 pub async fn list_orders(fmt: OutputFormat) -> Result<()> {
     let orch = crate::factory::from_active_profile().await?;
@@ -246,41 +902,135 @@ pub async fn list_orders(fmt: OutputFormat) -> Result<()> {
 
     let rows: Vec<OrderRow> = orders
         .iter()
-        .map(|o| OrderRow {
-            coin: o.symbol.clone(),
-            side: format!("{:?}", o.side),
-            size: o.size.to_string(),
-            price: o.price.map(|p| p.to_string()).unwrap_or_else(|| "—".into()),
-            oid: o.order_id.parse().unwrap_or(0),
-        })
+        .filter(|o| o.market_type == atlas_core::types::MarketType::Perp)
+        .map(order_row)
         .collect();
 
-    render(fmt, &OrdersOutput { orders: rows })?;
+    render(
+        fmt,
+        &OrdersOutput {
+            market_type: "perp".into(),
+            orders: rows,
+        },
+    )?;
     Ok(())
 }
 
-/// `atlas fills`
-pub async fn list_fills(fmt: OutputFormat) -> Result<()> {
+/// `atlas hl perp fills [--coin <COIN>] [--from <DATE>] [--to <DATE>] [--limit 50]`
+///
+/// Without a range, the most recent fills. With `--from`/`--to`, the
+/// module pages through its history from the start of the range, so
+/// `--limit` keeps the earliest matches.
+pub async fn list_fills(
+    coin: Option<&str>,
+    from: Option<&str>,
+    to: Option<&str>,
+    limit: usize,
+    fmt: OutputFormat,
+) -> Result<()> {
+    if limit == 0 {
+        anyhow::bail!("--limit must be at least 1");
+    }
+    let DateRange { from_ms, to_ms } = parse_date_range(from, to)?;
+
     let orch = crate::factory::from_active_profile().await?;
     let perp = orch.perp(None)?;
-    let fills = perp.fills().await.map_err(|e| anyhow::anyhow!("{e}"))?;
+    let query = FillQuery {
+        coin: coin.map(|c| perp.resolve_symbol(c)).transpose()?,
+        from_ms: from_ms.map(|ms| ms.max(0) as u64),
+        to_ms: to_ms.map(|ms| ms.max(0) as u64),
+        limit,
+    };
+    let fills = perp
+        .fills_query(&query)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    render(fmt, &fills_output(&fills, vec![]))?;
+    Ok(())
+}
 
-    let rows: Vec<FillRow> = fills
+/// Fill listing with fee and closed PnL totals over `fills`.
+pub(crate) fn fills_output(fills: &[Fill], warnings: Vec<String>) -> FillsOutput {
+    let total_fee: Decimal = fills.iter().map(|f| f.fee).sum();
+    let net_closed_pnl: Decimal = fills.iter().filter_map(|f| f.realized_pnl).sum();
+    FillsOutput {
+        fills: fills.iter().map(fill_row).collect(),
+        total_fee: total_fee.normalize().to_string(),
+        net_closed_pnl: format_decimal(net_closed_pnl, 2),
+        warnings,
+    }
+}
+
+fn fill_row(f: &Fill) -> FillRow {
+    FillRow {
+        coin: f.symbol.clone(),
+        side: format!("{:?}", f.side),
+        size: f.size.to_string(),
+        price: f.price.to_string(),
+        fee: f.fee.to_string(),
+        closed_pnl: f
+            .realized_pnl
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "—".into()),
+        protocol: f.protocol.to_string(),
+        timestamp_ms: f.timestamp_ms,
+    }
+}
+
+/// `atlas positions [--protocol <name|all>]` — positions from one or every
+/// perp module, queried concurrently. A module that fails becomes a warning.
+///
+/// `coin` and `min_notional` narrow the list; `sort` (`pnl`, `size` or
+/// `notional`, largest first) reorders it. The totals cover what is listed.
+pub async fn positions(
+    protocol: Option<&str>,
+    coin: Option<&str>,
+    min_notional: Option<f64>,
+    sort: Option<&str>,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let min_notional = match min_notional {
+        Some(n) if !n.is_finite() || n < 0.0 => {
+            anyhow::bail!("--min-notional must be a non-negative number, got {n}")
+        }
+        Some(n) => Decimal::from_f64(n),
+        None => None,
+    };
+
+    let orch = crate::factory::from_active_profile().await?;
+    let perps = select_perps(&orch, protocol)?;
+    let results = fan_out(
+        &perps,
+        MODULE_TIMEOUT,
+        |m| async move { m.positions().await },
+    )
+    .await;
+    let gathered = Gathered::from_results(results);
+
+    let mut positions: Vec<&Position> = gathered
+        .items
         .iter()
-        .map(|f| FillRow {
-            coin: f.symbol.clone(),
-            side: format!("{:?}", f.side),
-            size: f.size.to_string(),
-            price: f.price.to_string(),
-            fee: f.fee.to_string(),
-            closed_pnl: f
-                .realized_pnl
-                .map(|p| p.to_string())
-                .unwrap_or_else(|| "—".into()),
-        })
+        .filter(|p| coin.map_or(true, |c| p.symbol.eq_ignore_ascii_case(c)))
+        .filter(|p| min_notional.map_or(true, |min| p.notional().is_some_and(|n| n >= min)))
         .collect();
+    // Largest first; `None` sorts below every value, so reversed it goes last.
+    match sort {
+        Some("pnl") => positions.sort_by_key(|p| std::cmp::Reverse(p.unrealized_pnl)),
+        Some("size") => positions.sort_by_key(|p| std::cmp::Reverse(p.size.abs())),
+        Some("notional") => positions.sort_by_key(|p| std::cmp::Reverse(p.notional())),
+        _ => {}
+    }
 
-    render(fmt, &FillsOutput { fills: rows })?;
+    let total_notional: Decimal = positions.iter().filter_map(|p| p.notional()).sum();
+    let total_pnl: Decimal = positions.iter().filter_map(|p| p.unrealized_pnl).sum();
+    let output = PositionsOutput {
+        positions: positions.into_iter().map(position_row).collect(),
+        total_notional: format_decimal(total_notional, 2),
+        total_unrealized_pnl: format_decimal(total_pnl, 2),
+        warnings: gathered.warnings,
+    };
+    render(fmt, &output)?;
     Ok(())
 }
 
@@ -299,25 +1049,7 @@ pub async fn list_positions(fmt: OutputFormat) -> Result<()> {
         return Ok(());
     }
 
-    let rows: Vec<PositionRow> = positions
-        .iter()
-        .map(|p| PositionRow {
-            coin: p.symbol.clone(),
-            side: if p.size > rust_decimal::Decimal::ZERO {
-                "long".into()
-            } else {
-                "short".into()
-            },
-            size: p.size.to_string(),
-            entry_price: p.entry_price.map(|e| e.to_string()),
-            mark_price: p.mark_price.map(|m| m.to_string()),
-            unrealized_pnl: p.unrealized_pnl.map(|u| u.to_string()),
-            liquidation_price: p.liquidation_price.map(|l| l.to_string()),
-            leverage: p.leverage,
-            margin_mode: p.margin_mode.clone(),
-            protocol: "hyperliquid".into(),
-        })
-        .collect();
+    let rows: Vec<PositionRow> = positions.iter().map(position_row).collect();
 
     match fmt {
         OutputFormat::Json => {
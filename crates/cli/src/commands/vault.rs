@@ -1,5 +1,74 @@
 use anyhow::Result;
-use atlas_core::output::OutputFormat;
+use atlas_core::batch::{map_concurrent, MAX_CONCURRENT_REQUESTS};
+use atlas_core::output::{render, OutputFormat, VaultListOutput};
+use atlas_core::vault_list::{filter_vaults, sort_vaults, to_rows};
+use rust_decimal::prelude::*;
+
+/// `atlas hl vault list` — public vaults ranked by `sort` (`apr`, `tvl` or
+/// `followers`), after the `min_tvl` and `search` filters.
+///
+/// The listing has no follower counts, so `followers` takes the `limit`
+/// largest matching vaults by TVL, looks each one up and ranks those.
+pub async fn vault_list(
+    sort: &str,
+    min_tvl: Option<f64>,
+    search: Option<&str>,
+    limit: usize,
+    refresh: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let min_tvl = match min_tvl {
+        Some(n) if !n.is_finite() || n < 0.0 => {
+            anyhow::bail!("--min-tvl must be a non-negative number, got {n}")
+        }
+        Some(n) => Decimal::from_f64(n),
+        None => None,
+    };
+    if limit == 0 {
+        anyhow::bail!("--limit must be at least 1");
+    }
+
+    let orch = crate::factory::from_active_profile().await?;
+    let perp = orch.perp(None)?;
+
+    let listing = perp
+        .vault_list(refresh)
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let mut vaults = filter_vaults(listing, min_tvl, search);
+    let matched = vaults.len();
+
+    if sort == "followers" {
+        sort_vaults(&mut vaults, "tvl");
+        vaults.truncate(limit);
+        let counts = map_concurrent(vaults.iter(), MAX_CONCURRENT_REQUESTS, |v| async move {
+            match perp.vault_details(&v.address).await {
+                Ok(d) => Some(d.followers),
+                Err(e) => {
+                    tracing::warn!(vault = %v.address, "follower lookup failed: {e}");
+                    None
+                }
+            }
+        })
+        .await;
+        for (v, followers) in vaults.iter_mut().zip(counts) {
+            v.followers = followers;
+        }
+    }
+    sort_vaults(&mut vaults, sort);
+    vaults.truncate(limit);
+
+    let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+    render(
+        fmt,
+        &VaultListOutput {
+            sort: sort.to_string(),
+            matched,
+            vaults: to_rows(&vaults, now_ms),
+        },
+    )?;
+    Ok(())
+}
 
 /// `atlas vault details <VAULT_ADDRESS>`
 pub async fn vault_details(vault: &str, fmt: OutputFormat) -> Result<()> {
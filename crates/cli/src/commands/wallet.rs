@@ -0,0 +1,287 @@
+//! `atlas wallet` commands — ERC-20 allowances for the loaded wallet.
+
+use alloy::primitives::U256;
+use anyhow::Result;
+use atlas_core::assets::{from_base_units, to_base_units, ResolvedToken};
+use atlas_core::engine::is_evm_address;
+use atlas_core::output::{render, AllowanceOutput, ApprovalOutput, OutputFormat};
+use atlas_core::prompt::confirm_attended;
+use atlas_core::types::Chain;
+use atlas_core::workspace::load_config;
+use atlas_core::Orchestrator;
+use atlas_zero_x::{ZeroXModule, ALLOWANCE_HOLDER, NATIVE_TOKEN};
+use rust_decimal::Decimal;
+
+use super::zero_x::parse_chain;
+
+const ALLOWANCE_HOLDER_NAME: &str = "0x AllowanceHolder";
+
+/// The 0x module, which carries the wallet signer and RPC plumbing.
+fn zero_x(orch: &Orchestrator) -> Result<&ZeroXModule> {
+    orch.swap(None)?
+        .as_any()
+        .downcast_ref::<ZeroXModule>()
+        .ok_or_else(|| anyhow::anyhow!("0x module not available"))
+}
+
+/// Spender address and display name. `0x-exchange` (or `allowance-holder`)
+/// names the 0x AllowanceHolder that swaps pull tokens through.
+fn resolve_spender(input: &str) -> Result<(String, Option<String>)> {
+    let input = input.trim();
+    match input.to_lowercase().as_str() {
+        "0x-exchange" | "allowance-holder" => Ok((
+            ALLOWANCE_HOLDER.to_string(),
+            Some(ALLOWANCE_HOLDER_NAME.to_string()),
+        )),
+        _ if is_evm_address(input) => {
+            let name = input
+                .eq_ignore_ascii_case(ALLOWANCE_HOLDER)
+                .then(|| ALLOWANCE_HOLDER_NAME.to_string());
+            Ok((input.to_string(), name))
+        }
+        _ => anyhow::bail!("Invalid spender '{input}': expected a 0x address or `0x-exchange`"),
+    }
+}
+
+/// Look up an ERC-20 in the address book; the native coin has no allowance.
+fn resolve_erc20(chain: &Chain, token: &str) -> Result<ResolvedToken> {
+    let config = load_config()?;
+    let token = super::address::asset_map(&config)?.resolve_token(chain, token)?;
+    if token.address.eq_ignore_ascii_case(NATIVE_TOKEN) {
+        anyhow::bail!(
+            "{} is the native coin; it has no allowance",
+            token.name.unwrap_or(token.address)
+        );
+    }
+    Ok(token)
+}
+
+/// Decimals from the address book, else `decimals()` on chain.
+async fn decimals(zerox: &ZeroXModule, chain: &Chain, token: &ResolvedToken) -> Result<u32> {
+    match token.decimals {
+        Some(d) => Ok(d),
+        None => Ok(zerox.token_decimals(chain, &token.address).await?),
+    }
+}
+
+/// Allowances this large are treated as unlimited: spending from a
+/// `uint256` max approval only ever shaves a little off the top.
+fn is_unlimited(value: U256) -> bool {
+    value.bit(255)
+}
+
+/// Parse `max` / `unlimited` or a token amount into base units.
+fn parse_amount(input: &str, decimals: u32) -> Result<U256> {
+    if matches!(input.to_lowercase().as_str(), "max" | "unlimited") {
+        return Ok(U256::MAX);
+    }
+    let amount: Decimal = input
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid amount '{input}': expected a number or `max`"))?;
+    let raw = to_base_units(amount, decimals).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Invalid amount '{input}': must be non-negative with at most {decimals} decimal places"
+        )
+    })?;
+    Ok(U256::from(raw))
+}
+
+/// Read the wallet's current allowance for `spender` on `token`.
+async fn read(
+    zerox: &ZeroXModule,
+    chain: &Chain,
+    token: &ResolvedToken,
+    decimals: u32,
+    spender: &(String, Option<String>),
+) -> Result<AllowanceOutput> {
+    let owner = zerox
+        .taker_address()
+        .ok_or_else(|| anyhow::anyhow!("No wallet loaded. Run: atlas profile import"))?;
+    let value = zerox
+        .allowance(chain, &token.address, None, &spender.0)
+        .await?;
+    let unlimited = is_unlimited(value);
+    let raw = value.to_string();
+    Ok(AllowanceOutput {
+        chain: chain.to_string(),
+        token: token.address.clone(),
+        token_name: token.name.clone(),
+        decimals: Some(decimals),
+        owner,
+        spender: spender.0.clone(),
+        spender_name: spender.1.clone(),
+        allowance_tokens: if unlimited {
+            None
+        } else {
+            from_base_units(&raw, decimals).map(|d| d.normalize().to_string())
+        },
+        allowance: raw,
+        unlimited,
+    })
+}
+
+/// `atlas wallet allowance <token> --spender <address|0x-exchange> [--chain ethereum]`
+pub async fn allowance(token: &str, spender: &str, chain: &str, fmt: OutputFormat) -> Result<()> {
+    let chain = parse_chain(chain)?;
+    let token = resolve_erc20(&chain, token)?;
+    let spender = resolve_spender(spender)?;
+    let orch = crate::factory::from_active_profile().await?;
+    let zerox = zero_x(&orch)?;
+    let decimals = decimals(zerox, &chain, &token).await?;
+
+    render(fmt, &read(zerox, &chain, &token, decimals, &spender).await?)?;
+    Ok(())
+}
+
+/// `atlas wallet approve <token> <amount|max> --spender ... [--chain ethereum] [--yes]`
+pub async fn approve(
+    token: &str,
+    amount: &str,
+    spender: &str,
+    chain: &str,
+    yes: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    set_allowance(token, Some(amount), spender, chain, yes, fmt).await
+}
+
+/// `atlas wallet revoke <token> --spender ... [--chain ethereum] [--yes]` —
+/// approve 0.
+pub async fn revoke(
+    token: &str,
+    spender: &str,
+    chain: &str,
+    yes: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    set_allowance(token, None, spender, chain, yes, fmt).await
+}
+
+/// Send `approve(spender, amount)`; `None` revokes.
+async fn set_allowance(
+    token: &str,
+    amount: Option<&str>,
+    spender: &str,
+    chain: &str,
+    yes: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    let config = load_config()?;
+    let chain = parse_chain(chain)?;
+    let token = resolve_erc20(&chain, token)?;
+    let spender = resolve_spender(spender)?;
+    let orch = crate::factory::from_active_profile().await?;
+    let zerox = zero_x(&orch)?;
+    let decimals = decimals(zerox, &chain, &token).await?;
+    let value = match amount {
+        Some(a) => parse_amount(a, decimals)?,
+        None => U256::ZERO,
+    };
+    let current = read(zerox, &chain, &token, decimals, &spender).await?;
+
+    let unlimited = is_unlimited(value);
+    let token_label = token.name.clone().unwrap_or_else(|| token.address.clone());
+    let new_label = if unlimited {
+        "unlimited".to_string()
+    } else {
+        let tokens = from_base_units(&value.to_string(), decimals)
+            .map_or_else(|| value.to_string(), |d| d.normalize().to_string());
+        format!("{tokens} {token_label}")
+    };
+    let current_label = match (&current.allowance_tokens, current.unlimited) {
+        (_, true) => "unlimited".to_string(),
+        (Some(t), false) => format!("{t} {token_label}"),
+        (None, false) => format!("{} base units", current.allowance),
+    };
+
+    // An unlimited approval lets the spender drain the token at any time,
+    // so it always asks, even with `trading.confirm` off.
+    if unlimited && fmt == OutputFormat::Table {
+        println!(
+            "⚠ Unlimited approval: {} can move all of your {token_label}, now and later",
+            spender.1.as_deref().unwrap_or(&spender.0)
+        );
+    }
+    let title = match amount {
+        Some(_) => "ERC-20 APPROVE — CONFIRM",
+        None => "ERC-20 REVOKE — CONFIRM",
+    };
+    let proceed = confirm_attended(
+        title,
+        &[
+            ("Chain", chain.to_string()),
+            ("Token", format!("{token_label} ({})", token.address)),
+            (
+                "Spender",
+                spender.1.clone().unwrap_or_else(|| spender.0.clone()),
+            ),
+            ("Current", current_label),
+            ("New", new_label),
+        ],
+        yes || (!unlimited && !config.trading.confirm),
+        fmt,
+    )?;
+    if !proceed {
+        return Ok(());
+    }
+
+    let tx_hash = zerox
+        .approve(&chain, &token.address, &spender.0, value)
+        .await?;
+    let after = read(zerox, &chain, &token, decimals, &spender).await?;
+
+    render(
+        fmt,
+        &ApprovalOutput {
+            action: if amount.is_some() {
+                "approved"
+            } else {
+                "revoked"
+            }
+            .into(),
+            tx_hash,
+            allowance: after,
+        },
+    )?;
+    Ok(())
+}
+
+/// For `atlas 0x swap`: a warning with the approve command to run when the
+/// wallet's allowance for `spender` is below `needed` base units. `None` when
+/// the allowance covers it, the sell token is native, or the read fails.
+pub(crate) async fn allowance_shortfall(
+    zerox: &ZeroXModule,
+    chain: &Chain,
+    token: &ResolvedToken,
+    needed: &str,
+    spender: &str,
+) -> Option<String> {
+    if token.address.eq_ignore_ascii_case(NATIVE_TOKEN) {
+        return None;
+    }
+    let needed: U256 = needed.parse().ok()?;
+    let current = zerox
+        .allowance(chain, &token.address, None, spender)
+        .await
+        .ok()?;
+    if current >= needed {
+        return None;
+    }
+    let decimals = decimals(zerox, chain, token).await.ok()?;
+    let tokens = |v: U256| {
+        from_base_units(&v.to_string(), decimals)
+            .map_or_else(|| v.to_string(), |d| d.normalize().to_string())
+    };
+    let name = token.name.as_deref().unwrap_or(&token.address);
+    let spender_arg = if spender.eq_ignore_ascii_case(ALLOWANCE_HOLDER) {
+        "0x-exchange"
+    } else {
+        spender
+    };
+    Some(format!(
+        "Allowance is {} {name}, below the {} {name} being sold. The swap approves the exact amount first; to approve ahead of time: atlas wallet approve {name} {} --spender {spender_arg} --chain {chain}",
+        tokens(current),
+        tokens(needed),
+        tokens(needed),
+    ))
+}
@@ -0,0 +1,124 @@
+//! `atlas workspace backup/restore` — move `~/.atlas-os` to another machine.
+
+use std::path::Path;
+
+use anyhow::Result;
+use atlas_core::backup::{self, OsKeyring};
+use atlas_core::output::{render, BackupOutput, OutputFormat, RestoreOutput};
+use atlas_core::prompt::confirm_attended;
+use atlas_core::workspace::root_dir;
+
+/// Read by both commands instead of prompting, for scripted backups.
+const PASSPHRASE_ENV: &str = "ATLAS_BACKUP_PASSPHRASE";
+
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Step lines go to stderr, and only in table mode, so JSON stays parseable.
+fn progress(fmt: OutputFormat) -> impl FnMut(&str) {
+    move |step: &str| {
+        if fmt == OutputFormat::Table {
+            eprintln!("  → {step}");
+        }
+    }
+}
+
+/// The backup passphrase, from `ATLAS_BACKUP_PASSPHRASE` or one prompt.
+fn passphrase(label: &str) -> Result<String> {
+    if let Ok(p) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(p);
+    }
+    eprintln!("{label} (or set {PASSPHRASE_ENV}):");
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| anyhow::anyhow!("Failed to read passphrase: {e}"))?;
+    Ok(input.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// `atlas workspace backup <path> [--include-keys] [--yes]`
+pub fn backup(path: &str, include_keys: bool, yes: bool, fmt: OutputFormat) -> Result<()> {
+    let root = root_dir()?;
+    let archive = Path::new(path);
+
+    let passphrase = if include_keys {
+        // Key export always asks, whatever `trading.confirm` says
+        let proceed = confirm_attended(
+            "BACKUP PRIVATE KEYS — CONFIRM",
+            &[
+                ("Archive", path.to_string()),
+                ("Warning", "keys are exported, passphrase-encrypted".into()),
+            ],
+            yes,
+            fmt,
+        )?;
+        if !proceed {
+            return Ok(());
+        }
+        let p = passphrase("Passphrase to encrypt the keys")?;
+        if p.chars().count() < MIN_PASSPHRASE_LEN {
+            anyhow::bail!("Passphrase must be at least {MIN_PASSPHRASE_LEN} characters");
+        }
+        Some(p)
+    } else {
+        None
+    };
+
+    let keys = passphrase
+        .as_deref()
+        .map(|p| (&OsKeyring as &dyn backup::KeyStore, p));
+    let summary = backup::create_backup(&root, archive, keys, &mut progress(fmt))?;
+
+    render(
+        fmt,
+        &BackupOutput {
+            path: archive.display().to_string(),
+            bytes: summary.bytes,
+            files: summary.manifest.files,
+            profiles_with_keys: summary.manifest.keys,
+            created_at: summary.manifest.created_at,
+        },
+    )
+}
+
+/// `atlas workspace restore <path> [--force]`
+pub fn restore(path: &str, force: bool, fmt: OutputFormat) -> Result<()> {
+    let root = root_dir()?;
+    let archive = Path::new(path);
+
+    // Check before asking for a passphrase that won't be used
+    let manifest = backup::read_manifest(archive)?;
+    if !force && backup::has_workspace(&root) {
+        anyhow::bail!(
+            "A workspace already exists at {} — pass --force to overwrite it",
+            root.display()
+        );
+    }
+    let passphrase = if manifest.keys.is_empty() {
+        None
+    } else {
+        Some(passphrase(&format!(
+            "Passphrase for the keys of {}",
+            manifest.keys.join(", ")
+        ))?)
+    };
+
+    let summary = backup::restore_backup(
+        &root,
+        archive,
+        &OsKeyring,
+        passphrase.as_deref(),
+        force,
+        &mut progress(fmt),
+    )?;
+
+    render(
+        fmt,
+        &RestoreOutput {
+            path: archive.display().to_string(),
+            created_at: summary.manifest.created_at,
+            files_restored: summary.files_restored,
+            profiles_imported: summary.profiles_imported,
+            profiles_without_key: summary.profiles_without_key,
+        },
+    )
+}
@@ -1,11 +1,23 @@
 //! `atlas zero-x` commands — 0x DEX aggregator (multi-chain swaps).
 
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
-use atlas_core::output::OutputFormat;
+use atlas_core::assets::ResolvedToken;
+use atlas_core::db::{AtlasDb, DbSwap};
+use atlas_core::error::AtlasError;
+use atlas_core::output::{render, OutputFormat, RouteFillRow, SwapQuoteOutput};
 use atlas_core::types::Chain;
+use atlas_zero_x::client::ZeroXQuoteResponse;
+use atlas_zero_x::gasless::GaslessStatus;
+use atlas_zero_x::ZeroXModule;
+use rust_decimal::Decimal;
+
+/// How often `swap --gasless` asks the relayer for the trade's status.
+const GASLESS_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 /// Parse chain string to Chain enum.
-fn parse_chain(chain: &str) -> Result<Chain> {
+pub(crate) fn parse_chain(chain: &str) -> Result<Chain> {
     match chain.to_lowercase().as_str() {
         "ethereum" | "eth" | "1" => Ok(Chain::Ethereum),
         "arbitrum" | "arb" | "42161" => Ok(Chain::Arbitrum),
@@ -14,16 +26,44 @@ fn parse_chain(chain: &str) -> Result<Chain> {
     }
 }
 
-/// `atlas zero-x quote <sell_token> <buy_token> <amount> [--chain ethereum]`
+/// Look up sell and buy arguments (names or addresses) in the address book.
+pub(crate) fn resolve_pair(
+    chain: &Chain,
+    sell: &str,
+    buy: &str,
+) -> Result<(ResolvedToken, ResolvedToken)> {
+    let config = atlas_core::workspace::load_config()?;
+    let assets = super::address::asset_map(&config)?;
+    Ok((
+        assets.resolve_token(chain, sell)?,
+        assets.resolve_token(chain, buy)?,
+    ))
+}
+
+/// `USDC (0xA0b8…eB48)` for a named token, else the address, cut to `width`.
+fn token_label(token: &ResolvedToken, width: usize) -> String {
+    let a = &token.address;
+    let label = match &token.name {
+        Some(name) => format!("{name} ({}…{})", &a[..6], &a[a.len() - 4..]),
+        None => a.clone(),
+    };
+    label.chars().take(width).collect()
+}
+
+/// `atlas zero-x quote <sell_token> <buy_token> <amount> [--chain ethereum] [--gasless] [--record]`
+#[allow(clippy::too_many_arguments)]
 pub async fn quote(
     sell_token: &str,
     buy_token: &str,
     amount: &str,
     chain: &str,
     slippage_bps: Option<u32>,
+    gasless: bool,
+    record: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
     let chain_enum = parse_chain(chain)?;
+    let (sell, buy) = resolve_pair(&chain_enum, sell_token, buy_token)?;
     // Try to load signer for taker address (better price simulation)
     let orch = match crate::factory::from_active_profile().await {
         Ok(o) => o,
@@ -38,121 +78,190 @@ pub async fn quote(
         .ok_or_else(|| anyhow::anyhow!("0x module not available"))?;
 
     let taker = zerox.taker_address();
-    let resp = zerox
-        .price(
-            &chain_enum,
-            sell_token,
-            buy_token,
-            amount,
-            taker.as_deref(),
-            slippage_bps,
-        )
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let slippage = slippage_bps.unwrap_or(zerox.default_slippage_bps);
+    let resp = if gasless {
+        zerox.ensure_gasless(&chain_enum).await?;
+        zerox
+            .gasless_price(
+                &chain_enum,
+                &sell.address,
+                &buy.address,
+                amount,
+                taker.as_deref(),
+                Some(slippage),
+            )
+            .await?
+            .quote
+    } else {
+        zerox
+            .price(
+                &chain_enum,
+                &sell.address,
+                &buy.address,
+                amount,
+                taker.as_deref(),
+                Some(slippage),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?
+    };
 
-    if !resp.liquidity_available {
-        println!("⚠️  No liquidity available for this pair on {chain}");
-        return Ok(());
-    }
+    let mut output = quote_output(&chain_enum, &sell, &buy, amount, slippage, resp)?;
+    output.gasless = gasless;
+    record_swap(&output, record.then_some("quoted"), None);
+    render(fmt, &output)?;
+    Ok(())
+}
 
-    let allowance_required = resp
-        .issues
-        .as_ref()
-        .and_then(|i| i.allowance.as_ref())
-        .is_some();
-    let allowance_spender = resp
-        .issues
-        .as_ref()
-        .and_then(|i| i.allowance.as_ref())
-        .map(|a| a.spender.as_str());
+/// Cache the USD prices a quote carries (for `history pnl`) and, with a
+/// status, save it to the swap history. Failures are logged and otherwise
+/// ignored: by the time an executed swap is recorded it has already happened.
+pub(crate) fn record_swap(quote: &SwapQuoteOutput, status: Option<&str>, tx_hash: Option<&str>) {
+    if let Err(e) = try_record_swap(quote, status, tx_hash) {
+        tracing::warn!("Failed to record 0x swap in history: {e:#}");
+    }
+}
 
-    match fmt {
-        OutputFormat::Json | OutputFormat::JsonPretty => {
-            let json = serde_json::json!({
-                "ok": true,
-                "data": {
-                    "chain": chain,
-                    "sell_token": sell_token,
-                    "buy_token": buy_token,
-                    "sell_amount": resp.sell_amount,
-                    "buy_amount": resp.buy_amount,
-                    "min_buy_amount": resp.min_buy_amount,
-                    "gas_price": resp.gas_price,
-                    "allowance_target": resp.allowance_target,
-                    "allowance_required": allowance_required,
-                    "allowance_spender": allowance_spender,
-                    "route": resp.route,
-                    "fees": resp.fees,
-                    "issues": resp.issues,
-                    "liquidity_available": resp.liquidity_available,
-                }
-            });
-            let s = if matches!(fmt, OutputFormat::JsonPretty) {
-                serde_json::to_string_pretty(&json)?
-            } else {
-                serde_json::to_string(&json)?
-            };
-            println!("{s}");
+fn try_record_swap(
+    quote: &SwapQuoteOutput,
+    status: Option<&str>,
+    tx_hash: Option<&str>,
+) -> Result<()> {
+    let db = AtlasDb::open()?;
+    let now = chrono::Utc::now().timestamp_millis();
+    for (token, price) in [
+        (&quote.sell_token, &quote.sell_usd_price),
+        (&quote.buy_token, &quote.buy_usd_price),
+    ] {
+        if let Some(price) = price {
+            db.set_token_price(&quote.chain, token, price, now)?;
         }
-        OutputFormat::Table => {
-            let sell_amt = resp.sell_amount.as_deref().unwrap_or("—");
-            let buy_amt = resp.buy_amount.as_deref().unwrap_or("—");
-            let min_buy = resp.min_buy_amount.as_deref().unwrap_or("—");
-
-            println!("┌─────────────────────────────────────────────────┐");
-            println!("│  0x SWAP QUOTE                                  │");
-            println!("├─────────────────────────────────────────────────┤");
-            println!("│  Chain         : {:<30} │", chain);
-            println!(
-                "│  Sell Token    : {:<30} │",
-                &sell_token[..sell_token.len().min(30)]
-            );
-            println!(
-                "│  Buy Token     : {:<30} │",
-                &buy_token[..buy_token.len().min(30)]
-            );
-            println!("│  Sell Amount   : {:<30} │", sell_amt);
-            println!("│  Buy Amount    : {:<30} │", buy_amt);
-            println!("│  Min Buy Amt   : {:<30} │", min_buy);
-            println!("├─────────────────────────────────────────────────┤");
-
-            // Show route
-            if let Some(route) = &resp.route {
-                let sources: Vec<String> = route
-                    .fills
-                    .iter()
-                    .map(|f| {
-                        format!(
-                            "{} ({}%)",
-                            f.source,
-                            f.proportion_bps.parse::<f64>().unwrap_or(0.0) / 100.0
-                        )
-                    })
-                    .collect();
-                println!(
-                    "│  Route         : {:<30} │",
-                    sources.join(", ").chars().take(30).collect::<String>()
-                );
-            }
+    }
 
-            // Show issues
-            if let Some(issues) = &resp.issues {
-                if let Some(allowance) = &issues.allowance {
-                    println!(
-                        "│  ⚠ Allowance   : set on {:<23} │",
-                        &allowance.spender[..allowance.spender.len().min(23)]
-                    );
-                }
-                if let Some(balance) = &issues.balance {
-                    println!("│  ⚠ Balance     : need {:<25} │", &balance.expected);
-                }
-            }
+    let Some(status) = status else {
+        return Ok(());
+    };
+    db.insert_swap(&DbSwap {
+        status: status.to_string(),
+        chain: quote.chain.clone(),
+        sell_token: quote.sell_token.clone(),
+        sell_symbol: quote
+            .sell_token_name
+            .clone()
+            .or_else(|| quote.sell_symbol.clone()),
+        sell_decimals: quote.sell_decimals,
+        sell_amount: quote.sell_amount.clone(),
+        buy_token: quote.buy_token.clone(),
+        buy_symbol: quote
+            .buy_token_name
+            .clone()
+            .or_else(|| quote.buy_symbol.clone()),
+        buy_decimals: quote.buy_decimals,
+        buy_amount: quote.buy_amount.clone(),
+        price: quote.price.clone(),
+        sell_value_usd: quote.sell_value_usd.clone(),
+        gas_cost_usd: quote.gas_cost_usd.clone(),
+        tx_hash: tx_hash.map(str::to_string),
+        time_ms: now,
+    })
+}
 
-            println!("└─────────────────────────────────────────────────┘");
-        }
+/// Flatten a 0x price response into [`SwapQuoteOutput`]. Decimals and USD
+/// prices come from the backend enrichment, with the address book as the
+/// fallback for decimals.
+pub(crate) fn quote_output(
+    chain: &Chain,
+    sell: &ResolvedToken,
+    buy: &ResolvedToken,
+    amount: &str,
+    slippage_bps: u32,
+    resp: ZeroXQuoteResponse,
+) -> Result<SwapQuoteOutput> {
+    let enrich = resp.atlas.as_ref();
+    let sell_info = enrich.map(|e| &e.sell_token);
+    let buy_info = enrich.map(|e| &e.buy_token);
+
+    let route = resp
+        .route
+        .as_ref()
+        .map(|r| {
+            let symbol = |address: &str| {
+                r.tokens
+                    .iter()
+                    .find(|t| t.address.eq_ignore_ascii_case(address))
+                    .map_or_else(|| address.to_string(), |t| t.symbol.clone())
+            };
+            r.fills
+                .iter()
+                .map(|f| RouteFillRow {
+                    source: f.source.clone(),
+                    from: symbol(&f.from),
+                    to: symbol(&f.to),
+                    proportion_pct: f
+                        .proportion_bps
+                        .parse::<Decimal>()
+                        .map(|bps| (bps / Decimal::ONE_HUNDRED).normalize().to_string())
+                        .unwrap_or_else(|_| f.proportion_bps.clone()),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // `gas` is top-level on /price; /quote only has it on the transaction
+    let gas = resp
+        .gas
+        .as_deref()
+        .and_then(|g| g.parse::<u64>().ok())
+        .or_else(|| {
+            resp.transaction
+                .as_ref()
+                .and_then(|t| t.gas.as_ref())
+                .and_then(|g| match g {
+                    serde_json::Value::String(s) => s.parse().ok(),
+                    other => other.as_u64(),
+                })
+        });
+    let network_fee_wei = resp.total_network_fee.as_ref().and_then(|v| match v {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    });
+    let allowance = resp.issues.as_ref().and_then(|i| i.allowance.as_ref());
+
+    Ok(SwapQuoteOutput {
+        chain: chain.to_string(),
+        sell_token: sell.address.clone(),
+        sell_token_name: sell.name.clone(),
+        sell_symbol: sell_info.and_then(|t| t.symbol.clone()),
+        sell_decimals: sell_info.and_then(|t| t.decimals).or(sell.decimals),
+        buy_token: buy.address.clone(),
+        buy_token_name: buy.name.clone(),
+        buy_symbol: buy_info.and_then(|t| t.symbol.clone()),
+        buy_decimals: buy_info.and_then(|t| t.decimals).or(buy.decimals),
+        sell_amount: resp
+            .sell_amount
+            .clone()
+            .unwrap_or_else(|| amount.to_string()),
+        buy_amount: resp.buy_amount.clone().unwrap_or_else(|| "0".into()),
+        min_buy_amount: resp.min_buy_amount.clone(),
+        sell_usd_price: sell_info.and_then(|t| t.usd_price.clone()),
+        buy_usd_price: buy_info.and_then(|t| t.usd_price.clone()),
+        slippage_bps: Some(slippage_bps),
+        gas,
+        gas_price_wei: resp.gas_price.clone(),
+        network_fee_wei,
+        native_symbol: enrich.and_then(|e| e.native_symbol.clone()),
+        native_usd_price: enrich.and_then(|e| e.native_usd_price.clone()),
+        route,
+        allowance_target: resp.allowance_target.clone(),
+        allowance_required: allowance.is_some(),
+        allowance_spender: allowance.map(|a| a.spender.clone()),
+        fees: resp.fees.as_ref().map(serde_json::to_value).transpose()?,
+        issues: resp.issues.as_ref().map(serde_json::to_value).transpose()?,
+        zid: resp.zid.clone(),
+        ..Default::default()
     }
-
-    Ok(())
+    .with_derived())
 }
 
 /// `atlas zero-x chains` — list supported chains.
@@ -233,17 +342,62 @@ pub async fn sources(chain: &str, fmt: OutputFormat) -> Result<()> {
     Ok(())
 }
 
-/// `atlas zero-x swap <sell_token> <buy_token> <amount> [--chain ethereum] [--yes]`
+/// Boxed summary shown before a swap executes.
+#[allow(clippy::too_many_arguments)]
+fn print_confirmation(
+    title: &str,
+    chain: &str,
+    sell: &ResolvedToken,
+    buy: &ResolvedToken,
+    sell_amt: &str,
+    buy_amt: &str,
+    min_buy: &str,
+    slippage: u32,
+    taker: &str,
+) {
+    println!("┌─────────────────────────────────────────────────┐");
+    println!("│  {:<47}│", title);
+    println!("├─────────────────────────────────────────────────┤");
+    println!("│  Chain         : {:<30} │", chain);
+    println!("│  Sell          : {:<30} │", token_label(sell, 30));
+    println!("│  Buy           : {:<30} │", token_label(buy, 30));
+    println!("│  Sell Amount   : {:<30} │", sell_amt);
+    println!("│  Buy Amount    : {:<30} │", buy_amt);
+    println!("│  Min Buy (slip): {:<30} │", min_buy);
+    println!("│  Slippage      : {:<30} │", format!("{} bps", slippage));
+    println!("│  Taker         : {:<30} │", &taker[..taker.len().min(30)]);
+    println!("└─────────────────────────────────────────────────┘");
+}
+
+/// `Execute this swap? (y/N)` on stdin.
+fn ask_execute() -> Result<bool> {
+    print!("\nExecute this swap? (y/N): ");
+    use std::io::Write;
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    if !input.trim().eq_ignore_ascii_case("y") {
+        println!("Swap cancelled.");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// `atlas zero-x swap <sell_token> <buy_token> <amount> [--chain ethereum] [--gasless] [--yes]`
+#[allow(clippy::too_many_arguments)]
 pub async fn swap(
     sell_token: &str,
     buy_token: &str,
     amount: &str,
     chain: &str,
     slippage_bps: Option<u32>,
+    gasless: bool,
+    timeout_secs: u64,
     skip_confirm: bool,
     fmt: OutputFormat,
 ) -> Result<()> {
     let chain_enum = parse_chain(chain)?;
+    let (sell, buy) = resolve_pair(&chain_enum, sell_token, buy_token)?;
     let orch = crate::factory::from_active_profile().await?;
     let swap_mod = orch.swap(None).map_err(|e| anyhow::anyhow!("{e}"))?;
 
@@ -259,12 +413,26 @@ pub async fn swap(
 
     let slippage = slippage_bps.unwrap_or(zerox.default_slippage_bps);
 
+    if gasless {
+        let request = GaslessRequest {
+            chain_enum: &chain_enum,
+            chain,
+            sell: &sell,
+            buy: &buy,
+            amount,
+            slippage,
+            taker: &taker,
+        };
+        let timeout = Duration::from_secs(timeout_secs);
+        return swap_gasless(zerox, request, timeout, skip_confirm, fmt).await;
+    }
+
     println!("⏳ Getting swap quote...");
     let price_resp = zerox
         .price(
             &chain_enum,
-            sell_token,
-            buy_token,
+            &sell.address,
+            &buy.address,
             amount,
             Some(&taker),
             Some(slippage),
@@ -272,43 +440,35 @@ pub async fn swap(
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))?;
 
-    if !price_resp.liquidity_available {
-        anyhow::bail!("No liquidity available for this pair on {chain}");
-    }
-
     let sell_amt = price_resp.sell_amount.as_deref().unwrap_or(amount);
     let buy_amt = price_resp.buy_amount.as_deref().unwrap_or("?");
     let min_buy = price_resp.min_buy_amount.as_deref().unwrap_or("?");
 
     // 2. Show quote and confirm
     if !skip_confirm {
-        println!("┌─────────────────────────────────────────────────┐");
-        println!("│  0x SWAP — CONFIRM EXECUTION                    │");
-        println!("├─────────────────────────────────────────────────┤");
-        println!("│  Chain         : {:<30} │", chain);
-        println!(
-            "│  Sell          : {:<30} │",
-            &sell_token[..sell_token.len().min(30)]
-        );
-        println!(
-            "│  Buy           : {:<30} │",
-            &buy_token[..buy_token.len().min(30)]
+        print_confirmation(
+            "0x SWAP — CONFIRM EXECUTION",
+            chain,
+            &sell,
+            &buy,
+            sell_amt,
+            buy_amt,
+            min_buy,
+            slippage,
+            &taker,
         );
-        println!("│  Sell Amount   : {:<30} │", sell_amt);
-        println!("│  Buy Amount    : {:<30} │", buy_amt);
-        println!("│  Min Buy (slip): {:<30} │", min_buy);
-        println!("│  Slippage      : {:<30} │", format!("{} bps", slippage));
-        println!("│  Taker         : {:<30} │", &taker[..taker.len().min(30)]);
-        println!("└─────────────────────────────────────────────────┘");
 
         // Show issues
+        let spender = price_resp
+            .allowance_target
+            .as_deref()
+            .unwrap_or(atlas_zero_x::ALLOWANCE_HOLDER);
+        if let Some(hint) =
+            super::wallet::allowance_shortfall(zerox, &chain_enum, &sell, sell_amt, spender).await
+        {
+            println!("  ⚠ {hint}");
+        }
         if let Some(ref issues) = price_resp.issues {
-            if let Some(ref allowance) = issues.allowance {
-                println!(
-                    "  ⚠ Token approval needed (spender: {})",
-                    &allowance.spender[..allowance.spender.len().min(42)]
-                );
-            }
             if let Some(ref balance) = issues.balance {
                 println!(
                     "  ⚠ Insufficient balance (need: {}, have: {})",
@@ -317,13 +477,7 @@ pub async fn swap(
             }
         }
 
-        print!("\nExecute this swap? (y/N): ");
-        use std::io::Write;
-        std::io::stdout().flush()?;
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-        if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Swap cancelled.");
+        if !ask_execute()? {
             return Ok(());
         }
     }
@@ -339,8 +493,8 @@ pub async fn swap(
     let quote = atlas_core::types::SwapQuote {
         protocol: atlas_core::types::Protocol::ZeroX,
         chain: chain_enum,
-        sell_token: sell_token.to_string(),
-        buy_token: buy_token.to_string(),
+        sell_token: sell.address.clone(),
+        buy_token: buy.address.clone(),
         sell_amount: sell_dec,
         buy_amount: buy_amt.parse().unwrap_or(rust_decimal::Decimal::ZERO),
         estimated_gas: None,
@@ -354,6 +508,19 @@ pub async fn swap(
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))?;
 
+    // Amounts are as quoted; the receipt is not read back
+    match quote_output(
+        &chain_enum,
+        &sell,
+        &buy,
+        amount,
+        slippage,
+        price_resp.clone(),
+    ) {
+        Ok(recorded) => record_swap(&recorded, Some("executed"), Some(&tx_hash)),
+        Err(e) => tracing::warn!("Failed to record 0x swap in history: {e:#}"),
+    }
+
     // 4. Output result
     match fmt {
         OutputFormat::Json | OutputFormat::JsonPretty => {
@@ -362,8 +529,10 @@ pub async fn swap(
                 "data": {
                     "tx_hash": tx_hash,
                     "chain": chain,
-                    "sell_token": sell_token,
-                    "buy_token": buy_token,
+                    "sell_token": sell.address,
+                    "sell_token_name": sell.name,
+                    "buy_token": buy.address,
+                    "buy_token_name": buy.name,
                     "sell_amount": sell_amt,
                     "buy_amount": buy_amt,
                     "status": "confirmed"
@@ -380,16 +549,208 @@ pub async fn swap(
             println!("✅ Swap executed successfully!");
             println!("   TX Hash: {tx_hash}");
             println!("   Chain: {chain}");
-            println!(
-                "   Sold: {sell_amt} of {}",
-                &sell_token[..sell_token.len().min(20)]
-            );
-            println!(
-                "   Bought: ~{buy_amt} of {}",
-                &buy_token[..buy_token.len().min(20)]
-            );
+            println!("   Sold: {sell_amt} of {}", token_label(&sell, 30));
+            println!("   Bought: ~{buy_amt} of {}", token_label(&buy, 30));
+        }
+    }
+
+    Ok(())
+}
+
+/// What `swap --gasless` trades, resolved by [`swap`].
+struct GaslessRequest<'a> {
+    chain_enum: &'a Chain,
+    /// Chain as the user typed it, for output.
+    chain: &'a str,
+    sell: &'a ResolvedToken,
+    buy: &'a ResolvedToken,
+    amount: &'a str,
+    slippage: u32,
+    taker: &'a str,
+}
+
+/// Gasless path of `atlas zero-x swap`: firm gasless quote → sign the
+/// approval/trade EIP-712 payloads → submit to the relayer → poll until the
+/// trade is confirmed or fails, or `timeout` passes.
+async fn swap_gasless(
+    zerox: &ZeroXModule,
+    req: GaslessRequest<'_>,
+    timeout: Duration,
+    skip_confirm: bool,
+    fmt: OutputFormat,
+) -> Result<()> {
+    zerox.ensure_gasless(req.chain_enum).await?;
+
+    if fmt == OutputFormat::Table {
+        println!("⏳ Getting gasless quote...");
+    }
+    let quote = zerox
+        .gasless_quote(
+            req.chain_enum,
+            &req.sell.address,
+            &req.buy.address,
+            req.amount,
+            req.taker,
+            Some(req.slippage),
+        )
+        .await?;
+
+    let sell_amt = quote.quote.sell_amount.as_deref().unwrap_or(req.amount);
+    let buy_amt = quote.quote.buy_amount.as_deref().unwrap_or("?");
+    let min_buy = quote.quote.min_buy_amount.as_deref().unwrap_or("?");
+
+    if !skip_confirm {
+        print_confirmation(
+            "0x GASLESS SWAP — CONFIRM EXECUTION",
+            req.chain,
+            req.sell,
+            req.buy,
+            sell_amt,
+            buy_amt,
+            min_buy,
+            req.slippage,
+            req.taker,
+        );
+        println!("  Gas is paid by the 0x relayer; its fee comes out of the sell amount.");
+        if quote.approval.is_some() {
+            println!("  Includes a gasless approval of the sell token.");
+        }
+        if !ask_execute()? {
+            return Ok(());
+        }
+    }
+
+    let submission = zerox.sign_gasless(req.chain_enum, req.amount, &quote)?;
+    let submitted = zerox.gasless_submit(req.chain_enum, &submission).await?;
+    let trade_hash = submitted.trade_hash;
+    if fmt == OutputFormat::Table {
+        println!("⏳ Submitted {trade_hash}, waiting for the relayer...");
+    }
+
+    let status = wait_for_gasless(zerox, req.chain_enum, &trade_hash, timeout, fmt).await?;
+    if !status.is_success() {
+        return Err(AtlasError::Protocol {
+            protocol: "0x".into(),
+            message: format!(
+                "Gasless trade {trade_hash} failed: {}",
+                status.reason.as_deref().unwrap_or("no reason given")
+            ),
+        }
+        .into());
+    }
+    let tx_hash = status.tx_hash().unwrap_or(&trade_hash).to_string();
+
+    // Amounts are as quoted; the receipt is not read back
+    match quote_output(
+        req.chain_enum,
+        req.sell,
+        req.buy,
+        req.amount,
+        req.slippage,
+        quote.quote.clone(),
+    ) {
+        Ok(mut recorded) => {
+            recorded.gasless = true;
+            record_swap(&recorded, Some("executed"), Some(&tx_hash))
+        }
+        Err(e) => tracing::warn!("Failed to record 0x swap in history: {e:#}"),
+    }
+
+    match fmt {
+        OutputFormat::Json | OutputFormat::JsonPretty => {
+            let json = serde_json::json!({
+                "ok": true,
+                "data": {
+                    "tx_hash": tx_hash,
+                    "trade_hash": trade_hash,
+                    "gasless": true,
+                    "chain": req.chain,
+                    "sell_token": req.sell.address,
+                    "sell_token_name": req.sell.name,
+                    "buy_token": req.buy.address,
+                    "buy_token_name": req.buy.name,
+                    "sell_amount": sell_amt,
+                    "buy_amount": buy_amt,
+                    "status": status.status,
+                }
+            });
+            let s = if matches!(fmt, OutputFormat::JsonPretty) {
+                serde_json::to_string_pretty(&json)?
+            } else {
+                serde_json::to_string(&json)?
+            };
+            println!("{s}");
+        }
+        OutputFormat::Table => {
+            println!("✅ Gasless swap {}!", status.status);
+            println!("   TX Hash: {tx_hash}");
+            println!("   Trade Hash: {trade_hash}");
+            println!("   Chain: {}", req.chain);
+            println!("   Sold: {sell_amt} of {}", token_label(req.sell, 30));
+            println!("   Bought: ~{buy_amt} of {}", token_label(req.buy, 30));
         }
     }
 
     Ok(())
 }
+
+/// Poll the relayer until the trade is `confirmed` or `failed`. Each status
+/// change is reported on stderr: a `{"event":"gasless_status",...}` line for
+/// JSON, a step line for tables, so stdout keeps only the result.
+///
+/// At the timeout a trade that already `succeeded` (mined, not yet final) is
+/// returned as is; anything earlier is a [`AtlasError::ProtocolTimeout`].
+async fn wait_for_gasless(
+    zerox: &ZeroXModule,
+    chain: &Chain,
+    trade_hash: &str,
+    timeout: Duration,
+    fmt: OutputFormat,
+) -> Result<GaslessStatus> {
+    let deadline = Instant::now() + timeout;
+    let mut last: Option<GaslessStatus> = None;
+    loop {
+        match zerox.gasless_status(chain, trade_hash).await {
+            Ok(status) => {
+                if last.as_ref().map(|l| &l.status) != Some(&status.status) {
+                    report_gasless_status(fmt, trade_hash, &status);
+                }
+                if status.is_final() {
+                    return Ok(status);
+                }
+                last = Some(status);
+            }
+            // A failed poll is not a failed trade; keep asking until the deadline
+            Err(e) => tracing::debug!(trade_hash, error = %e, "gasless status poll failed"),
+        }
+        if Instant::now() >= deadline {
+            return match last {
+                Some(status) if status.is_success() => Ok(status),
+                last => Err(AtlasError::ProtocolTimeout(format!(
+                    "Gasless trade {trade_hash} was not confirmed within {}s (last status: {}); \
+                     it may still land, so check the wallet before retrying",
+                    timeout.as_secs(),
+                    last.as_ref().map_or("unknown", |s| s.status.as_str())
+                ))
+                .into()),
+            };
+        }
+        tokio::time::sleep(GASLESS_POLL_INTERVAL).await;
+    }
+}
+
+fn report_gasless_status(fmt: OutputFormat, trade_hash: &str, status: &GaslessStatus) {
+    match fmt {
+        OutputFormat::Json | OutputFormat::JsonPretty => {
+            let event = serde_json::json!({
+                "event": "gasless_status",
+                "trade_hash": trade_hash,
+                "status": status.status,
+                "tx_hash": status.tx_hash(),
+                "timestamp": chrono::Utc::now().timestamp(),
+            });
+            eprintln!("{event}");
+        }
+        OutputFormat::Table => eprintln!("  → {}", status.status),
+    }
+}
@@ -4,14 +4,51 @@
 //! (that would create circular deps).
 
 use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tracing::info;
 
 use atlas_core::auth::AuthManager;
 use atlas_core::config::AppConfig;
+use atlas_core::traits::PerpModule;
+use atlas_core::types::Protocol;
 use atlas_core::workspace::load_config;
 use atlas_core::Orchestrator;
 
+/// Set by the global `--refresh-meta` flag: bypass the metadata cache.
+static REFRESH_META: AtomicBool = AtomicBool::new(false);
+
+/// Force every module built in this process to refetch market metadata.
+pub fn set_refresh_meta(refresh: bool) {
+    REFRESH_META.store(refresh, Ordering::Relaxed);
+}
+
+/// Set by the global `--no-cache` flag: fetch every candle from the exchange.
+static NO_CANDLE_CACHE: AtomicBool = AtomicBool::new(false);
+
+/// Bypass the local candle cache for every module built in this process.
+pub fn set_no_candle_cache(disabled: bool) {
+    NO_CANDLE_CACHE.store(disabled, Ordering::Relaxed);
+}
+
+/// Set by `--force` on trading commands: skip the order protections
+/// (`trading.max_slippage`, `trading.price_band`) the module applies.
+static OVERRIDE_ORDER_PROTECTIONS: AtomicBool = AtomicBool::new(false);
+
+/// Turn the order protections off for every module built in this process.
+pub fn set_override_order_protections(force: bool) {
+    OVERRIDE_ORDER_PROTECTIONS.store(force, Ordering::Relaxed);
+}
+
+/// Set by `--override-risk` on trading commands: skip the `trading` risk
+/// limits the module applies.
+static OVERRIDE_RISK_LIMITS: AtomicBool = AtomicBool::new(false);
+
+/// Turn the risk limits off for every module built in this process.
+pub fn set_override_risk_limits(override_risk: bool) {
+    OVERRIDE_RISK_LIMITS.store(override_risk, Ordering::Relaxed);
+}
+
 /// Build an Orchestrator from config — registers enabled modules.
 pub async fn from_config(
     config: &AppConfig,
@@ -19,21 +56,98 @@ pub async fn from_config(
 ) -> Result<Orchestrator> {
     let mut orch = Orchestrator::new();
 
+    let hl_cfg = &config.modules.hyperliquid.config;
+    let testnet = hl_cfg.network == "testnet";
+    let meta = atlas_hl::meta::MetaOptions {
+        ttl: std::time::Duration::from_secs(hl_cfg.meta_ttl_secs),
+        refresh: REFRESH_META.load(Ordering::Relaxed),
+    };
+    // Shared by every HL instance below; the DB is opened on first use
+    let candle_cache = (!NO_CANDLE_CACHE.load(Ordering::Relaxed))
+        .then(|| Arc::new(atlas_core::candle_cache::CandleCache::new()));
+
     // ── Hyperliquid (perp) ──────────────────────────────────
-    if config.modules.hyperliquid.enabled {
-        let testnet = config.modules.hyperliquid.config.network == "testnet";
-        let hl = match signer.clone() {
-            Some(s) => atlas_hl::client::HyperliquidModule::new(s, testnet).await,
-            None => atlas_hl::client::HyperliquidModule::new_readonly(testnet).await,
-        }
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
-        orch.add_perp(Arc::new(hl));
+    let mut hl_module: Option<Arc<dyn PerpModule>> = None;
+    if config.modules.hyperliquid.enabled && hl_cfg.data_via_backend {
+        // Market data only: signing never goes through the proxy, so the
+        // signer is dropped and trading commands fail with PROXY_ONLY_MODE.
+        let hl = atlas_hl::client::HyperliquidModule::readonly_via_backend(
+            hl_backend(config),
+            testnet,
+            meta.clone(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .with_aliases(&hl_cfg.aliases)
+        .with_candle_cache(candle_cache.clone());
+        let hl: Arc<dyn PerpModule> = Arc::new(hl);
+        orch.add_perp(hl.clone());
+        hl_module = Some(hl);
+        info!("Hyperliquid perp module loaded (market data via Atlas backend)");
+    } else if config.modules.hyperliquid.enabled {
+        let policy = atlas_hl::http::RetryPolicy::from(&hl_cfg.http);
+        let hl = atlas_hl::client::HyperliquidModule::with_options(
+            signer.clone(),
+            testnet,
+            meta.clone(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .with_retry_policy(policy)
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .with_rate_limit(&hl_cfg.rate_limit)
+        .with_aliases(&hl_cfg.aliases)
+        .with_candle_cache(candle_cache.clone())
+        .with_builder_fee(atlas_core::engine::BuilderFee::from_config(hl_cfg))
+        .with_order_protections(
+            (!OVERRIDE_ORDER_PROTECTIONS.load(Ordering::Relaxed)).then(|| config.trading.clone()),
+        )
+        .with_risk_limits(
+            (!OVERRIDE_RISK_LIMITS.load(Ordering::Relaxed)).then(|| config.trading.clone()),
+        );
+        let hl: Arc<dyn PerpModule> = Arc::new(hl);
+        orch.add_perp(hl.clone());
+        hl_module = Some(hl);
         info!("Hyperliquid perp module loaded");
     }
 
+    // ── Paper (simulated perp on live HL prices) ────────────
+    if config.modules.paper.enabled {
+        let data: Arc<dyn PerpModule> = match hl_module {
+            Some(hl) => hl,
+            None => {
+                let hl = if hl_cfg.data_via_backend {
+                    atlas_hl::client::HyperliquidModule::readonly_via_backend(
+                        hl_backend(config),
+                        testnet,
+                        meta,
+                    )
+                    .await
+                } else {
+                    atlas_hl::client::HyperliquidModule::with_options(None, testnet, meta).await
+                }
+                .map_err(|e| anyhow::anyhow!("{e}"))?
+                .with_rate_limit(&hl_cfg.rate_limit)
+                .with_aliases(&hl_cfg.aliases)
+                .with_candle_cache(candle_cache.clone());
+                Arc::new(hl)
+            }
+        };
+        let paper_cfg = &config.modules.paper.config;
+        let settings =
+            atlas_paper::account::PaperSettings::from_config(paper_cfg, hl_cfg.default_leverage);
+        orch.add_perp(Arc::new(atlas_paper::PaperModule::new(data, settings)));
+        if paper_cfg.active {
+            orch.default_perp = Some(Protocol::Paper.to_string());
+            info!("Paper module loaded (active: perp orders are simulated)");
+        } else {
+            info!("Paper module loaded");
+        }
+    }
+
     // ── 0x (swap) ───────────────────────────────────────────
     if config.modules.zero_x.enabled {
-        let backend_url = "https://atlas-os-backend-production.up.railway.app".to_string();
+        let backend_url = atlas_core::constants::ATLAS_BACKEND_URL.to_string();
         let default_chain = atlas_zero_x::parse_chain(&config.modules.zero_x.config.default_chain);
         let default_slippage_bps = config.modules.zero_x.config.default_slippage_bps;
         let mut zero_x = atlas_zero_x::client::ZeroXModule::new(backend_url)
@@ -52,11 +166,23 @@ pub async fn from_config(
     Ok(orch)
 }
 
-/// Load config, load active wallet signer, and build Orchestrator.
+/// Backend client for Hyperliquid market data (`data_via_backend`).
+fn hl_backend(config: &AppConfig) -> atlas_core::backend::BackendClient {
+    atlas_core::backend::BackendClient::new(atlas_core::constants::ATLAS_BACKEND_URL)
+        .with_api_key(config.system.api_key.clone())
+}
+
+/// Load config, load the active wallet signer (or `--profile`'s), and
+/// build Orchestrator.
 pub async fn from_active_profile() -> Result<Orchestrator> {
     let config = load_config()?;
-    let signer = AuthManager::load_active_signer(&config)?;
-    from_config(&config, Some(signer)).await
+    from_profile(&config, AuthManager::active_profile(&config)).await
+}
+
+/// Build an Orchestrator signing as `profile`, whatever profile is active.
+pub async fn from_profile(config: &AppConfig, profile: &str) -> Result<Orchestrator> {
+    let signer = AuthManager::load_signer(profile)?;
+    from_config(config, Some(signer)).await
 }
 
 /// Build a read-only Orchestrator (no signer needed).
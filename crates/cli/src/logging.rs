@@ -0,0 +1,78 @@
+//! Tracing setup: human-readable stderr output (`RUST_LOG`) plus the JSON
+//! log file configured under `system.log`.
+
+use std::sync::Mutex;
+
+use atlas_core::config::LogConfig;
+use atlas_core::logs::{self, FieldCollector, LogLevel, RotatingLog};
+use chrono::Utc;
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Writes every event at or above `level` to the rotating log file.
+///
+/// Dependencies (HTTP, websocket, SQLite) are only recorded from WARN up so
+/// the file stays about what Atlas itself did.
+struct FileLayer {
+    log: Mutex<RotatingLog>,
+    level: LogLevel,
+}
+
+impl<S: Subscriber> Layer<S> for FileLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        let level = LogLevel::from(meta.level());
+        let own = meta.target().starts_with("atlas");
+        if level < self.level || (!own && level < LogLevel::Warn) {
+            return;
+        }
+
+        let mut fields = FieldCollector::default();
+        event.record(&mut fields);
+        let now = Utc::now();
+        let line = logs::format_record(now, level, meta.target(), fields.fields);
+        if let Ok(mut log) = self.log.lock() {
+            // Logging must never take a command down with it.
+            let _ = log.write_line(&line, now.date_naive());
+        }
+    }
+}
+
+/// `system.log` read straight from `atlas.json`. Runs before the workspace
+/// is initialised, so it must not trigger config migrations.
+fn log_config() -> LogConfig {
+    atlas_core::workspace::resolve("atlas.json")
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .and_then(|value| serde_json::from_value(value.pointer("/system/log")?.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Build the file layer; `None` when disabled or when the logs directory
+/// cannot be created.
+fn file_layer() -> Option<FileLayer> {
+    let config = log_config();
+    if !config.enabled {
+        return None;
+    }
+    let dir = logs::logs_dir().ok()?;
+    let log = RotatingLog::open(&dir, config.retention).ok()?;
+    Some(FileLayer {
+        log: Mutex::new(log),
+        level: config.level,
+    })
+}
+
+/// Install the global subscriber. Call after the workspace exists.
+pub fn init() {
+    let stderr = tracing_subscriber::fmt::layer()
+        .with_target(false)
+        .with_filter(EnvFilter::from_default_env());
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(file_layer())
+        .init();
+}
@@ -1,12 +1,14 @@
 mod commands;
 mod factory;
+mod logging;
 mod tui;
 
 use anyhow::Result;
 use atlas_core::error::AtlasError;
 use atlas_core::output::OutputFormat;
+use atlas_core::timeframe::Timeframe;
+use atlas_core::AuthManager;
 use clap::{Parser, Subcommand, ValueEnum};
-use tracing_subscriber::EnvFilter;
 
 #[derive(Parser)]
 #[command(
@@ -19,6 +21,35 @@ struct Cli {
     #[arg(long, short = 'o', global = true, default_value = "table")]
     output: CliOutputFormat,
 
+    /// Ignore cached market metadata and refetch it.
+    #[arg(long, global = true)]
+    refresh_meta: bool,
+
+    /// Fetch all candles from the exchange instead of the local candle
+    /// cache (TA, backtest, correlate, candles).
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// ASCII-only tables: no box drawing, emoji or colors.
+    /// Also enabled by NO_COLOR, ATLAS_PLAIN or TERM=dumb.
+    #[arg(long, global = true, visible_alias = "no-emoji")]
+    plain: bool,
+
+    /// Print nothing on success for commands that only change something
+    /// (config sets, cancels, transfers). Errors are still printed.
+    #[arg(long, short = 'q', global = true)]
+    quiet: bool,
+
+    /// Time zone for --from/--to dates and days like "yesterday": local
+    /// (default), UTC or an offset such as +05:30.
+    #[arg(long, global = true, value_name = "TZ")]
+    tz: Option<String>,
+
+    /// Act as this profile for this command only, instead of the active
+    /// one. Nothing is written to the config.
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -60,7 +91,41 @@ enum Commands {
     },
 
     /// Print account summary.
-    Status,
+    Status {
+        /// Include every enabled module and EVM wallet balances, valued in USD
+        #[arg(long, default_value_t = false)]
+        all: bool,
+        /// Show what changed since the previous `atlas status` snapshot
+        #[arg(long, default_value_t = false)]
+        diff: bool,
+    },
+
+    /// Open positions across perp modules, with notional and PnL %.
+    Positions {
+        /// Perp protocol to query, or `all`. Default: the default perp module.
+        #[arg(long, alias = "proto")]
+        protocol: Option<String>,
+        /// Only positions in this coin
+        #[arg(long)]
+        coin: Option<String>,
+        /// Hide positions worth less than this many USD
+        #[arg(long)]
+        min_notional: Option<f64>,
+        /// Largest first by unrealized PnL, absolute size or notional
+        #[arg(long, value_parser = ["pnl", "size", "notional"])]
+        sort: Option<String>,
+    },
+
+    /// First-run setup: network, wallet profile, modules, trading defaults
+    /// and API key. Asks only about what is not configured yet.
+    Init {
+        /// Apply an answers file instead of prompting (for scripts)
+        #[arg(long, requires = "from")]
+        non_interactive: bool,
+        /// JSON answers file for --non-interactive
+        #[arg(long, requires = "non_interactive")]
+        from: Option<String>,
+    },
 
     /// Check system health.
     Doctor {
@@ -68,6 +133,34 @@ enum Commands {
         fix: bool,
     },
 
+    /// Show the structured log (`~/.atlas-os/logs/atlas.log`).
+    Logs {
+        /// Number of most recent matching entries to show
+        #[arg(long, default_value_t = 100)]
+        tail: usize,
+        /// Keep printing new entries as they are written
+        #[arg(long, short = 'f')]
+        follow: bool,
+        /// Minimum level: trace, debug, info, warn, error
+        #[arg(long)]
+        level: Option<atlas_core::logs::LogLevel>,
+        /// Only entries containing this text (case-insensitive)
+        #[arg(long)]
+        grep: Option<String>,
+    },
+
+    /// Back up or restore the workspace (config, profiles, local database).
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceAction,
+    },
+
+    /// Inspect and prune local caches.
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
     /// Launch interactive Terminal UI.
     Tui,
 
@@ -82,6 +175,20 @@ enum Commands {
     Stream {
         #[command(subcommand)]
         action: StreamAction,
+        /// Serve heartbeat metrics over HTTP (`/metrics`, `/healthz`). A bare
+        /// port listens on localhost; use HOST:PORT (e.g. 0.0.0.0:9100) for
+        /// container probes.
+        #[arg(long, global = true, value_name = "[HOST:]PORT")]
+        metrics_port: Option<String>,
+        /// Seconds without a message before `/healthz` returns 503.
+        #[arg(long, global = true, default_value_t = atlas_core::metrics::DEFAULT_STALE_AFTER_SECS)]
+        stale_after: u64,
+    },
+
+    /// Strategy estimates across protocols (read-only, no orders).
+    Strategy {
+        #[command(subcommand)]
+        action: StrategyAction,
     },
 
     // ── PROTOCOL MODULES (namespaced per protocol) ──────────────
@@ -99,6 +206,18 @@ enum Commands {
         action: ZeroXAction,
     },
 
+    /// Morpho Blue lending: markets, rates, positions.
+    Morpho {
+        #[command(subcommand)]
+        action: MorphoAction,
+    },
+
+    /// Paper trading: simulated perp account on live Hyperliquid prices.
+    Paper {
+        #[command(subcommand)]
+        action: PaperAction,
+    },
+
     // ── UTILITIES ───────────────────────────────────────────────
     /// Query cached history and PnL.
     History {
@@ -111,6 +230,42 @@ enum Commands {
         #[command(subcommand)]
         action: ExportAction,
     },
+
+    /// Trade journal: notes and tags on fills.
+    Journal {
+        #[command(subcommand)]
+        action: JournalAction,
+    },
+
+    /// Audit log of every signed exchange action (read-only).
+    Audit {
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+
+    /// Recurring commands (e.g. a daily DCA buy), run by `atlas schedule run`.
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleAction,
+    },
+
+    /// Webhook / exec notifications on fills, cancels and liquidation risk.
+    Notify {
+        #[command(subcommand)]
+        action: NotifyAction,
+    },
+
+    /// Token address book: names for contract addresses, per chain.
+    Address {
+        #[command(subcommand)]
+        action: AddressAction,
+    },
+
+    /// Wallet: ERC-20 allowances on EVM chains.
+    Wallet {
+        #[command(subcommand)]
+        action: WalletAction,
+    },
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -140,6 +295,77 @@ enum ProfileAction {
     Export {
         /// Profile name to export.
         name: String,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Delete a profile and its keyring key.
+    Remove {
+        /// Profile name to delete.
+        name: String,
+        /// Activate this profile instead (required to remove the active one).
+        #[arg(long)]
+        switch_to: Option<String>,
+        /// Also delete the profile's cached snapshots, backend responses and nonce.
+        #[arg(long)]
+        purge_history: bool,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Rename a profile, moving its keyring key and the active pointer.
+    Rename {
+        /// Current profile name.
+        old: String,
+        /// New profile name.
+        new: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Cached candle series; `--prune-before` deletes older candles.
+    ///
+    /// Closed candles are cached as TA commands fetch them; the forming
+    /// candle is always refetched. Intervals above 1d are not cached.
+    Candles {
+        /// Only this coin.
+        #[arg(long)]
+        coin: Option<String>,
+        /// Only this interval (e.g. 1h).
+        #[arg(long)]
+        interval: Option<Timeframe>,
+        /// Delete candles opened before this age (90d, 12h, 2w) or date
+        /// (YYYY-MM-DD).
+        #[arg(long, value_name = "AGE|DATE")]
+        prune_before: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorkspaceAction {
+    /// Write config, profiles and the local database to one archive.
+    ///
+    /// Keys stay out unless `--include-keys`: each profile's key is then
+    /// exported as a passphrase-encrypted keystore, after a confirmation.
+    /// The passphrase is prompted for, or read from ATLAS_BACKUP_PASSPHRASE.
+    Backup {
+        /// Archive to write, e.g. atlas-backup.tar.gz.
+        path: String,
+        /// Include encrypted private keys for every profile.
+        #[arg(long)]
+        include_keys: bool,
+        /// Skip the confirmation for --include-keys.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Restore a backup: files, then keys into the OS keyring.
+    Restore {
+        /// Archive written by `atlas workspace backup`.
+        path: String,
+        /// Overwrite an existing workspace (profiles or database).
+        #[arg(long)]
+        force: bool,
     },
 }
 
@@ -149,8 +375,34 @@ enum ProfileAction {
 
 #[derive(Subcommand)]
 enum ConfigureAction {
-    /// Show all current configuration.
-    Show,
+    /// Show all current configuration (secrets redacted).
+    Show {
+        /// Print secrets such as the API key in full.
+        #[arg(long)]
+        reveal: bool,
+    },
+
+    /// Read any config value by dotted key, e.g. `trading.max_notional`.
+    Get {
+        /// Dotted key path. `atlas configure keys` lists them.
+        key: String,
+        /// Print secrets such as the API key in full.
+        #[arg(long)]
+        reveal: bool,
+    },
+
+    /// Set any config value by dotted key, e.g. `modules.zero_x.default_chain base`.
+    ///
+    /// Values are parsed by the key's type: true/false/on/off for flags,
+    /// numbers for limits, `off`/`null` to clear optional values.
+    Set {
+        /// Dotted key path. `atlas configure keys` lists them.
+        key: String,
+        value: String,
+    },
+
+    /// List every key accepted by `configure get/set`.
+    Keys,
 
     /// System-level settings.
     System {
@@ -163,6 +415,223 @@ enum ConfigureAction {
         #[command(subcommand)]
         action: ModuleConfigAction,
     },
+
+    /// Cross-protocol trading safety settings and per-coin presets.
+    Trading {
+        #[command(subcommand)]
+        action: TradingConfigAction,
+    },
+
+    /// Config environments (mainnet/testnet): separate settings per network.
+    Env {
+        #[command(subcommand)]
+        action: EnvConfigAction,
+    },
+
+    /// Notification targets for `atlas notify`.
+    Notify {
+        #[command(subcommand)]
+        action: NotifyConfigAction,
+    },
+
+    /// Second approval for large transfers (TOTP or confirmation file) and
+    /// the transfer whitelist.
+    ///
+    /// The threshold and method are config keys:
+    ///   atlas configure set security.transfer_confirm_above 1000
+    ///   atlas configure set security.transfer_confirm_method totp
+    Security {
+        #[command(subcommand)]
+        action: SecurityConfigAction,
+    },
+
+    /// Share settings between machines through the Atlas backend.
+    ///
+    /// The API key, webhook secret, ENS RPC URL and machine-local keys
+    /// (active profile and environment, network, log and approval file
+    /// settings, transfer whitelist) are never uploaded or overwritten.
+    Sync {
+        #[command(subcommand)]
+        action: SyncConfigAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum SyncConfigAction {
+    /// Show which settings differ from the remote copy.
+    Status,
+    /// Upload this machine's settings.
+    Push {
+        /// Overwrite a remote copy pushed from another machine since the last sync
+        #[arg(long)]
+        force: bool,
+        /// Skip the confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Replace this machine's settings with the remote copy.
+    Pull {
+        /// Take the remote copy even over settings edited here since the last sync
+        #[arg(long, conflicts_with = "merge")]
+        force: bool,
+        /// Keep settings edited here; take remote values only for the rest
+        #[arg(long)]
+        merge: bool,
+        /// Skip the confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum SecurityConfigAction {
+    /// Show the transfer threshold, approval method and TOTP enrollment.
+    Show,
+    /// Manage the TOTP secret in the OS keyring.
+    Totp {
+        #[command(subcommand)]
+        action: TotpAction,
+    },
+    /// Known transfer destinations. Once enabled, `hl perp transfer` only
+    /// pays whitelisted addresses.
+    Whitelist {
+        #[command(subcommand)]
+        action: WhitelistAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum WhitelistAction {
+    /// Show whitelisted addresses and whether whitelist mode is on.
+    List,
+    /// Whitelist an address, or relabel one already listed.
+    Add {
+        /// Destination EVM address (0x...).
+        address: String,
+        /// Name shown in transfer confirmations and output.
+        #[arg(long)]
+        label: String,
+    },
+    /// Remove an address, by address or label.
+    Remove {
+        /// Address or label.
+        target: String,
+    },
+    /// Refuse transfers to addresses not on the whitelist.
+    Enable,
+    /// Allow transfers to any address again (labels still shown).
+    Disable,
+}
+
+#[derive(Subcommand)]
+enum TotpAction {
+    /// Create a secret for your authenticator app and confirm it with a
+    /// code. Replacing an existing secret needs a code from it.
+    Enroll,
+    /// Delete the secret. Needs a current code.
+    Remove,
+}
+
+#[derive(Subcommand)]
+enum NotifyConfigAction {
+    /// Set the webhook, exec hook, signing secret or liquidation threshold.
+    ///
+    /// Examples:
+    ///   atlas configure notify set --webhook https://example.com/atlas --secret s3cret
+    ///   atlas configure notify set --exec "notify-send atlas"
+    ///   atlas configure notify set --liq-warn-pct 5
+    ///   atlas configure notify set --webhook off
+    Set {
+        /// URL that receives each event as a JSON POST, or `off`.
+        #[arg(long)]
+        webhook: Option<String>,
+        /// Shell command run per event with the JSON on stdin, or `off`.
+        #[arg(long)]
+        exec: Option<String>,
+        /// Key for the X-Atlas-Signature HMAC-SHA256 header, or `off`.
+        #[arg(long)]
+        secret: Option<String>,
+        /// Warn when a position's mark is within this % of its liq price.
+        #[arg(long)]
+        liq_warn_pct: Option<f64>,
+    },
+    /// Show notification settings (secret redacted).
+    Show {
+        /// Print the signing secret in full.
+        #[arg(long)]
+        reveal: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum EnvConfigAction {
+    /// List environments and the network each one uses.
+    List,
+    /// Switch the active environment, e.g. `atlas configure env use testnet`.
+    Use { name: String },
+}
+
+#[derive(Subcommand)]
+enum TradingConfigAction {
+    /// Require confirmation before orders, transfers and key exports.
+    Confirm {
+        /// Enable or disable (on/off).
+        enabled: String,
+    },
+    /// Max notional (USD) per opening order, or `off`.
+    #[command(name = "max-notional")]
+    MaxNotional { value: String },
+    /// Max account leverage after an order, or `off`.
+    #[command(name = "max-leverage")]
+    MaxLeverage { value: String },
+    /// Daily realized loss (USD) that blocks new exposure, or `off`.
+    #[command(name = "daily-loss-limit")]
+    DailyLossLimit { value: String },
+    /// Max slippage a market order may use (0.1 = 10%), or `off`.
+    #[command(name = "max-slippage")]
+    MaxSlippage { value: String },
+    /// Max distance of a market order's limit price from the last 1m close
+    /// (0.15 = 15%), or `off`.
+    #[command(name = "price-band")]
+    PriceBand { value: String },
+    /// Warn on a market order whose spread is over this multiple of the
+    /// coin's median spread (3 = 3x), or `off`.
+    #[command(name = "spread-warn")]
+    SpreadWarn { value: String },
+    /// Per-coin leverage, slippage and default size.
+    Preset {
+        #[command(subcommand)]
+        action: PresetAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum PresetAction {
+    /// Set a coin's preset; fields not given are kept.
+    ///
+    /// Orders in the coin use these before the module defaults; flags on
+    /// the order still win.
+    ///
+    /// Examples:
+    ///   atlas configure trading preset set BTC --leverage 5 --slippage 0.003
+    ///   atlas configure trading preset set ETH --default-size '$500'
+    Set {
+        /// Coin symbol (aliases such as XBT resolve to the exchange name).
+        coin: String,
+        /// Leverage used to size USDC amounts.
+        #[arg(long)]
+        leverage: Option<u32>,
+        /// Market order slippage (0.003 = 0.3%).
+        #[arg(long)]
+        slippage: Option<f64>,
+        /// Size for `buy`/`sell` when none is given: $500, 0.5eth, 10lots.
+        #[arg(long)]
+        default_size: Option<String>,
+    },
+    /// List every coin preset.
+    List,
+    /// Delete a coin's preset.
+    Remove { coin: String },
 }
 
 #[derive(Subcommand)]
@@ -174,15 +643,21 @@ enum SystemConfigAction {
         /// Enable or disable (true/false).
         enabled: String,
     },
-    /// Set Atlas backend API key.
+    /// Set Atlas backend API key; warns if it lacks a scope enabled modules need.
     #[command(name = "api-key")]
     ApiKey { key: String },
 }
 
 #[derive(Subcommand)]
 enum ModuleConfigAction {
-    /// List all modules.
-    List,
+    /// List all modules, probing each enabled one (status, latency).
+    List {
+        /// Skip the health probes and only show configuration.
+        #[arg(long)]
+        no_probe: bool,
+    },
+    /// Probe every enabled module and the backend; exits 1 if any is unreachable.
+    Doctor,
     /// Enable a module.
     Enable { name: String },
     /// Disable a module.
@@ -194,11 +669,16 @@ enum ModuleConfigAction {
     ///   atlas configure module set hl leverage 5
     ///   atlas configure module set hl lot ETH 0.01
     ///   atlas configure module set 0x default-chain base
+    ///   atlas configure module set hl builder-fee-bps 0 --yes
+    ///   atlas configure module set paper active true
     Set {
-        /// Module name (hl, 0x, hyperliquid, zero_x).
+        /// Module name (hl, 0x, morpho, paper, hyperliquid, zero_x).
         module: String,
         /// Config key and value(s).
         values: Vec<String>,
+        /// Acknowledge a builder fee change without prompting.
+        #[arg(long)]
+        yes: bool,
     },
 }
 
@@ -212,6 +692,9 @@ enum MarketAction {
     /// Hyperliquid perp/spot market data.
     #[command(alias = "hl")]
     Hyperliquid {
+        /// TA: fail instead of warning when the candle series has gaps.
+        #[arg(long, global = true)]
+        strict: bool,
         #[command(subcommand)]
         action: MarketHlAction,
     },
@@ -225,18 +708,85 @@ enum MarketAction {
 
     // ── Cross-protocol / macro (via backend → CoinGecko) ──────
     /// Global crypto market stats.
-    Global,
+    Global {
+        /// Serve the last cached response if the backend is unreachable.
+        #[arg(long)]
+        cached_ok: bool,
+    },
+    /// Dashboard of the market.watchlist coins with global stats.
+    Overview {
+        /// Sort by 24h or 7d change (biggest gain first) or market cap rank.
+        #[arg(long, default_value = "mcap", value_parser = ["24h", "7d", "mcap"])]
+        sort: String,
+        /// Show at most this many coins.
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Serve the last cached response if the backend is unreachable.
+        #[arg(long)]
+        cached_ok: bool,
+    },
     /// Trending coins.
-    Trending,
+    Trending {
+        /// Serve the last cached response if the backend is unreachable.
+        #[arg(long)]
+        cached_ok: bool,
+    },
     /// Detailed coin info (e.g. bitcoin, ethereum).
-    Coin { id: String },
+    Coin {
+        id: String,
+        /// Serve the last cached response if the backend is unreachable.
+        #[arg(long)]
+        cached_ok: bool,
+    },
     /// Top gainers & losers across all crypto.
     Movers {
         #[arg(long, default_value_t = 10)]
         limit: usize,
+        /// Serve the last cached response if the backend is unreachable.
+        #[arg(long)]
+        cached_ok: bool,
     },
     /// Global DeFi market stats.
-    Defi,
+    Defi {
+        /// Serve the last cached response if the backend is unreachable.
+        #[arg(long)]
+        cached_ok: bool,
+    },
+    /// Compare a coin's price on Hyperliquid, CoinGecko and a 0x quote.
+    ///
+    /// Each source is shown with its deviation from the median; a source
+    /// that fails is listed as unavailable.
+    Compare {
+        /// Coin symbol (e.g. ETH, BTC).
+        coin: String,
+        /// Chain for the 0x quote. Default: 0x module default-chain.
+        #[arg(long)]
+        chain: Option<String>,
+        /// USDC amount the 0x quote sells for the coin.
+        #[arg(long, default_value_t = 1000.0)]
+        size_usd: f64,
+        /// Flag sources further than this from the median (bps).
+        /// Default: market.compare_threshold_bps (50).
+        #[arg(long)]
+        threshold_bps: Option<f64>,
+    },
+}
+
+/// `atlas market hyperliquid oi <action>`
+#[derive(Subcommand)]
+enum OiAction {
+    /// Sample OI / volume for every market into the local DB.
+    Record {
+        /// Seconds between samples
+        #[arg(long, default_value_t = 300)]
+        interval: u64,
+        /// Delete snapshots older than this many days
+        #[arg(long, default_value_t = 30)]
+        retention_days: u64,
+        /// Take one sample and exit (for cron)
+        #[arg(long, default_value_t = false)]
+        once: bool,
+    },
 }
 
 /// `atlas market hyperliquid <action>` — Hyperliquid-specific market data.
@@ -244,6 +794,7 @@ enum MarketAction {
 enum MarketHlAction {
     /// List available markets.
     List {
+        /// Spot pairs with decimals and current mids instead of perps
         #[arg(long, default_value_t = false)]
         spot: bool,
     },
@@ -252,6 +803,12 @@ enum MarketHlAction {
         tickers: Vec<String>,
         #[arg(long, default_value_t = false)]
         all: bool,
+        /// Price spot pairs: a base token (HYPE), a pair (HYPE/USDC) or a key (@107)
+        #[arg(long, default_value_t = false, conflicts_with = "quote")]
+        spot: bool,
+        /// Quote prices in another asset instead of USD (e.g. --in BTC)
+        #[arg(long = "in", value_name = "QUOTE")]
+        quote: Option<String>,
     },
     /// Get funding rate history.
     Funding { ticker: String },
@@ -265,34 +822,93 @@ enum MarketHlAction {
     Candles {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
         #[arg(long, default_value_t = 50)]
         limit: usize,
     },
     /// Detailed market info (price, spread, OI, volume).
     Info { coin: String },
-    /// Top markets by volume, gainers, or losers.
+    /// Top markets by volume, 24h change, open interest, funding or spread.
     Top {
+        /// volume, change, oi, funding, or spread (tightest first)
         #[arg(long, default_value = "volume")]
+        sort: atlas_core::market_rank::TopSort,
+        /// Skip markets with less 24h volume (USD)
+        #[arg(long)]
+        min_volume: Option<f64>,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Worst first; markets without the metric stay last
+        #[arg(long, default_value_t = false)]
+        reverse: bool,
+    },
+    /// Open interest / volume history from locally recorded snapshots.
+    #[command(args_conflicts_with_subcommands = true)]
+    Oi {
+        #[command(subcommand)]
+        action: Option<OiAction>,
+        /// Coin to report on (run `oi record` first to collect snapshots)
+        ticker: Option<String>,
+        /// Window to analyze
+        #[arg(long, default_value_t = 24)]
+        hours: u64,
+    },
+    /// Screen all perps for extreme funding (annualized, with OI and volume).
+    FundingScreen {
+        /// Minimum absolute hourly rate.
+        #[arg(long, default_value_t = 0.0005)]
+        min_abs: f64,
+        /// Minimum open interest in USD.
+        #[arg(long, default_value_t = 100_000.0)]
+        min_oi: f64,
+        #[arg(long, default_value = "apr", value_parser = ["rate", "apr"])]
         sort: String,
+        /// Rows per side (positive / negative).
         #[arg(long, default_value_t = 20)]
         limit: usize,
         #[arg(long, default_value_t = false)]
         reverse: bool,
     },
     /// Bid-ask spread for one or more coins.
-    Spread { coins: Vec<String> },
+    Spread {
+        coins: Vec<String>,
+        /// Express bid, ask and spread in another asset (e.g. --in BTC)
+        #[arg(long = "in", value_name = "QUOTE")]
+        quote: Option<String>,
+    },
+    /// Median spread per UTC hour from locally recorded samples.
+    SpreadProfile { coin: String },
     /// Search markets by symbol or name.
     Search { query: String },
     /// Quick market dashboard (gainers, losers, volume leaders).
-    Summary,
+    Summary {
+        /// Sections to show: gainers,losers,volume,funding,oi
+        /// (default: gainers,losers,volume; none with --watchlist)
+        #[arg(long, value_delimiter = ',', value_parser = ["gainers", "losers", "volume", "funding", "oi"])]
+        sections: Vec<String>,
+        /// Rows per section
+        #[arg(long, default_value_t = 3)]
+        limit: usize,
+        /// Only the hl.watchlist coins, with your position in each
+        #[arg(long, default_value_t = false)]
+        watchlist: bool,
+    },
 
     // ── Technical Analysis (TA-Lib) ──────────────────────────
+    /// Several indicators from one candle fetch (e.g. rsi,macd,ema:50).
+    Ta {
+        ticker: String,
+        #[arg(long, default_value = "1h")]
+        timeframe: Timeframe,
+        /// Comma-separated specs, `name` or `name:period` [default: rsi,macd,bbands,atr,ema:20,ema:50]
+        #[arg(long)]
+        indicators: Option<String>,
+    },
     /// Calculate RSI.
     Rsi {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
         #[arg(long, default_value_t = 14)]
         period: usize,
     },
@@ -300,17 +916,22 @@ enum MarketHlAction {
     Macd {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
     },
     /// Calculate VWAP.
     Vwap { ticker: String },
     /// Multi-indicator trend signal (bullish/bearish + score).
-    Trend { ticker: String },
+    Trend {
+        ticker: String,
+        /// Score each timeframe and report confluence, e.g. 15m,1h,4h
+        #[arg(long)]
+        mtf: Option<String>,
+    },
     /// Bollinger Bands.
     Bbands {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
         #[arg(long, default_value_t = 20)]
         period: usize,
     },
@@ -318,13 +939,13 @@ enum MarketHlAction {
     Stoch {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
     },
     /// Average Directional Index (trend strength).
     Adx {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
         #[arg(long, default_value_t = 14)]
         period: usize,
     },
@@ -332,7 +953,7 @@ enum MarketHlAction {
     Atr {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
         #[arg(long, default_value_t = 14)]
         period: usize,
     },
@@ -340,7 +961,7 @@ enum MarketHlAction {
     Ema {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
         #[arg(long, default_value_t = 20)]
         period: usize,
     },
@@ -348,7 +969,7 @@ enum MarketHlAction {
     Sma {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
         #[arg(long, default_value_t = 20)]
         period: usize,
     },
@@ -356,13 +977,13 @@ enum MarketHlAction {
     Obv {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
     },
     /// Commodity Channel Index.
     Cci {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
         #[arg(long, default_value_t = 20)]
         period: usize,
     },
@@ -370,7 +991,7 @@ enum MarketHlAction {
     Willr {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
         #[arg(long, default_value_t = 14)]
         period: usize,
     },
@@ -378,13 +999,84 @@ enum MarketHlAction {
     Sar {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
+    },
+    /// Ichimoku Cloud (tenkan/kijun/senkou/chikou).
+    Ichimoku {
+        ticker: String,
+        #[arg(long, default_value = "1h")]
+        timeframe: Timeframe,
+        #[arg(long, default_value_t = 9)]
+        tenkan: usize,
+        #[arg(long, default_value_t = 26)]
+        kijun: usize,
+        #[arg(long, default_value_t = 52)]
+        senkou: usize,
+    },
+    /// SuperTrend (ATR bands that flip with the trend).
+    Supertrend {
+        ticker: String,
+        #[arg(long, default_value = "1h")]
+        timeframe: Timeframe,
+        #[arg(long, default_value_t = 10)]
+        period: usize,
+        #[arg(long, default_value_t = 3.0)]
+        multiplier: f64,
+    },
+    /// Backtest a built-in strategy over historical candles (no orders placed).
+    Backtest {
+        ticker: String,
+        #[arg(long, default_value = "1h")]
+        timeframe: Timeframe,
+        /// rsi-reversion (RSI crosses 30/70) or ema-cross (--period × --slow EMAs)
+        #[arg(long, default_value = "rsi-reversion")]
+        strategy: String,
+        /// RSI period, or the fast EMA for ema-cross
+        #[arg(long, default_value_t = 14)]
+        period: usize,
+        /// Slow EMA for ema-cross
+        #[arg(long, default_value_t = 50)]
+        slow: usize,
+        /// Number of candles to replay
+        #[arg(long, default_value_t = 1000)]
+        lookback: usize,
+        /// Fee per side in basis points
+        #[arg(long, default_value_t = 4.5)]
+        fee_bps: f64,
+    },
+    /// Pairwise return correlation and beta to the first coin.
+    Correlate {
+        /// Two or more coins; betas are measured against the first
+        #[arg(required = true, num_args = 2..)]
+        coins: Vec<String>,
+        #[arg(long, default_value = "1h")]
+        timeframe: Timeframe,
+        /// Number of returns per coin
+        #[arg(long, default_value_t = 200)]
+        lookback: usize,
     },
-    /// Candlestick pattern recognition.
+    /// Candlestick patterns over recent closed candles, with strength.
     Patterns {
         ticker: String,
         #[arg(long, default_value = "1h")]
-        timeframe: String,
+        timeframe: Timeframe,
+        /// Closed candles to search, newest back
+        #[arg(long, default_value_t = 20)]
+        lookback: usize,
+        /// doji, hammer, shooting_star, engulfing, harami (comma-separated) or all
+        #[arg(long, default_value = "all")]
+        patterns: String,
+    },
+    /// Markets where a pattern completed on the latest closed candle.
+    PatternsScan {
+        #[arg(long, default_value = "4h")]
+        timeframe: Timeframe,
+        /// doji, hammer, shooting_star, engulfing, harami (comma-separated) or all
+        #[arg(long, default_value = "all")]
+        patterns: String,
+        /// Number of markets to scan, by 24h volume
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
     },
 }
 
@@ -405,6 +1097,9 @@ enum MarketDexAction {
         network: Option<String>,
         #[arg(long, default_value_t = 10)]
         limit: usize,
+        /// Grade each pool A-F on basic rug heuristics.
+        #[arg(long)]
+        screen: bool,
     },
     /// Top pools on a network.
     Pools {
@@ -420,6 +1115,30 @@ enum MarketDexAction {
         /// Pool contract address.
         address: String,
     },
+    /// Pool candles (USD prices, oldest first).
+    Ohlcv {
+        /// Network (ethereum, base, ...).
+        network: String,
+        /// Pool contract address.
+        address: String,
+        #[arg(long, default_value = "hour", value_parser = ["day", "hour", "minute"])]
+        timeframe: String,
+        /// Timeframe units per candle: day 1; hour 1, 4, 12; minute 1, 5, 15.
+        #[arg(long, default_value_t = 1)]
+        aggregate: u32,
+        #[arg(long, default_value_t = 100)]
+        limit: usize,
+    },
+    /// Latest trades in a pool (newest first).
+    Trades {
+        /// Network (ethereum, base, ...).
+        network: String,
+        /// Pool contract address.
+        address: String,
+        /// Only trades worth at least this many USD.
+        #[arg(long)]
+        min_usd: Option<f64>,
+    },
     /// Token info by address.
     Token {
         /// Network (ethereum, base, ...).
@@ -433,6 +1152,67 @@ enum MarketDexAction {
     Dexes { network: String },
     /// Search onchain tokens/pools.
     Search { query: String },
+    /// Pool watchlist with changes since the last check.
+    Watch {
+        #[command(subcommand)]
+        action: DexWatchAction,
+    },
+}
+
+/// `atlas market dex watch <action>` — pools tracked in the local DB.
+#[derive(Subcommand)]
+enum DexWatchAction {
+    /// Watch a pool, e.g. `atlas market dex watch add base 0x6921… --label toshi`.
+    Add {
+        /// Network (ethereum, base, ...).
+        network: String,
+        /// Pool contract address.
+        pool: String,
+        /// Short name to show and to remove it by.
+        #[arg(long)]
+        label: Option<String>,
+    },
+    /// Stop watching a pool.
+    Remove {
+        /// Pool address or label.
+        pool: String,
+    },
+    /// List watched pools.
+    List,
+    /// Fetch every watched pool and compare with the previous check.
+    Check {
+        /// Only pools whose price moved more than this many percent.
+        #[arg(long)]
+        threshold: Option<f64>,
+    },
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  STRATEGY — Read-only estimates across protocols
+// ═══════════════════════════════════════════════════════════════════════
+
+#[derive(Subcommand)]
+enum StrategyAction {
+    /// Net carry of short HL perp / long spot supplied on Morpho.
+    Carry {
+        /// Coin (e.g. ETH).
+        coin: String,
+        /// Notional of each leg in USD.
+        #[arg(long)]
+        size_usd: f64,
+        /// Days held; fees are amortized over them.
+        #[arg(long, default_value_t = 30)]
+        horizon_days: u32,
+        /// Morpho market unique key. Default: modules.morpho.carry_markets.<COIN>.
+        #[arg(long)]
+        market: Option<String>,
+        /// Chain (ethereum, base, arbitrum). Default: morpho default-chain.
+        #[arg(long)]
+        chain: Option<String>,
+        /// Taker fee per fill on either leg, in bps.
+        #[arg(long, default_value_t = 4.5)]
+        taker_fee_bps: f64,
+    },
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -468,6 +1248,8 @@ enum HyperliquidAction {
     },
     /// Sync data to local DB cache.
     Sync {
+        /// Walk the whole account history (fills, transfers, funding)
+        /// instead of only what is newer than the cache.
         #[arg(long)]
         full: bool,
     },
@@ -485,26 +1267,66 @@ enum HlPerpAction {
         /// Coin symbol (e.g. ETH, BTC, SOL).
         ticker: String,
         /// Size: 200 (default mode), $200 (USDC margin), 0.5eth (units), 10lots.
-        size: String,
-        /// Override leverage for size calculation.
+        /// Omit to use the coin's preset default size.
+        size: Option<String>,
+        /// Override leverage for size calculation (default: coin preset,
+        /// then the module default).
         #[arg(long)]
         leverage: Option<u32>,
-        /// Slippage tolerance (e.g. 0.05 = 5%).
+        /// Slippage tolerance (e.g. 0.05 = 5%; default: coin preset, then
+        /// the module default).
         #[arg(long)]
         slippage: Option<f64>,
+        /// Abort if walking the order book puts the average fill more than
+        /// this many bps from the mid, or the book can't fill the size.
+        #[arg(long)]
+        max_impact_bps: Option<f64>,
+        /// Print the order with its estimated book impact instead of
+        /// sending it.
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Bypass the pre-trade risk guard for this order.
+        #[arg(long)]
+        override_risk: bool,
+        /// Skip the max-slippage and price-band protections for this order.
+        #[arg(long)]
+        force: bool,
     },
     /// Market sell / short.
     Sell {
         /// Coin symbol (e.g. ETH, BTC, SOL).
         ticker: String,
         /// Size: 200 (default mode), $200 (USDC margin), 0.5eth (units), 10lots.
-        size: String,
-        /// Override leverage for size calculation.
+        /// Omit to use the coin's preset default size.
+        size: Option<String>,
+        /// Override leverage for size calculation (default: coin preset,
+        /// then the module default).
         #[arg(long)]
         leverage: Option<u32>,
-        /// Slippage tolerance (e.g. 0.05 = 5%).
+        /// Slippage tolerance (e.g. 0.05 = 5%; default: coin preset, then
+        /// the module default).
         #[arg(long)]
         slippage: Option<f64>,
+        /// Abort if walking the order book puts the average fill more than
+        /// this many bps from the mid, or the book can't fill the size.
+        #[arg(long)]
+        max_impact_bps: Option<f64>,
+        /// Print the order with its estimated book impact instead of
+        /// sending it.
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Bypass the pre-trade risk guard for this order.
+        #[arg(long)]
+        override_risk: bool,
+        /// Skip the max-slippage and price-band protections for this order.
+        #[arg(long)]
+        force: bool,
     },
     /// Close position.
     Close {
@@ -516,6 +1338,53 @@ enum HlPerpAction {
         /// Slippage tolerance.
         #[arg(long)]
         slippage: Option<f64>,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Skip the max-slippage and price-band protections for this order.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Close every open position (or those matching the filters).
+    CloseAll {
+        /// Only positions with negative unrealized PnL.
+        #[arg(long, conflicts_with = "only_winning")]
+        only_losing: bool,
+        /// Only positions with positive unrealized PnL.
+        #[arg(long)]
+        only_winning: bool,
+        /// Only coins starting with this prefix (e.g. K for kPEPE, kBONK).
+        #[arg(long)]
+        coin_prefix: Option<String>,
+        /// Slippage tolerance.
+        #[arg(long)]
+        slippage: Option<f64>,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Skip the max-slippage and price-band protections for this order.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Reverse a position (long → short or back) with one order.
+    Flip {
+        /// Coin symbol.
+        ticker: String,
+        /// Size of the new position in coin units (default: the current size).
+        #[arg(long)]
+        size: Option<f64>,
+        /// Slippage tolerance.
+        #[arg(long)]
+        slippage: Option<f64>,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Bypass the pre-trade risk guard for this order.
+        #[arg(long)]
+        override_risk: bool,
+        /// Skip the max-slippage and price-band protections for this order.
+        #[arg(long)]
+        force: bool,
     },
     /// Place limit order.
     Order {
@@ -527,9 +1396,39 @@ enum HlPerpAction {
         size: String,
         /// Limit price in USD.
         price: f64,
-        /// Close-only order (won't open new positions).
+        /// Close-only order (won't open new positions). A size above the
+        /// open position is capped at it, with a warning.
         #[arg(long, default_value_t = false)]
         reduce_only: bool,
+        /// With --reduce-only: refuse a size above the open position
+        /// instead of capping it.
+        #[arg(long, requires = "reduce_only")]
+        strict: bool,
+        /// Shorthand for --reduce-only: reduce the open position, capping
+        /// the size at it.
+        #[arg(long, conflicts_with_all = ["reduce_only", "strict"])]
+        reduce: bool,
+        /// Time in force: gtc (default), ioc (fill now or cancel) or alo (post-only).
+        #[arg(long, conflicts_with = "post_only")]
+        tif: Option<atlas_core::types::TimeInForce>,
+        /// Only add liquidity; same as --tif alo.
+        #[arg(long)]
+        post_only: bool,
+        /// Make this a stop order that rests off the book until the mark
+        /// crosses this price: above the mid to buy, below it to sell.
+        /// With --reduce-only the other side is a take-profit.
+        #[arg(long, conflicts_with_all = ["tif", "post_only"])]
+        trigger: Option<f64>,
+        /// How a triggered order executes: market (default; PRICE is the
+        /// worst fill price) or limit (rests at PRICE).
+        #[arg(long, requires = "trigger")]
+        trigger_type: Option<atlas_core::types::TriggerKind>,
+        /// Client order ID (32 hex digits or a UUID) to track the order by.
+        #[arg(long, value_parser = atlas_core::parse::parse_cloid)]
+        cloid: Option<String>,
+        /// Bypass the pre-trade risk guard for this order.
+        #[arg(long)]
+        override_risk: bool,
     },
     /// Cancel order(s). Without --oid, cancels all orders for the coin.
     Cancel {
@@ -539,12 +1438,50 @@ enum HlPerpAction {
         #[arg(long)]
         oid: Option<u64>,
     },
+    /// Amend a resting limit order's price and/or size in place.
+    Modify {
+        /// Coin symbol.
+        ticker: String,
+        /// Order ID to modify.
+        #[arg(long, conflicts_with = "cloid", required_unless_present = "cloid")]
+        oid: Option<u64>,
+        /// Client order ID (0x…) to modify instead of --oid.
+        #[arg(long)]
+        cloid: Option<String>,
+        /// New limit price in USD.
+        #[arg(long, required_unless_present = "size")]
+        price: Option<f64>,
+        /// New size in coin units.
+        #[arg(long)]
+        size: Option<f64>,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Bypass the pre-trade risk guard for the modified order.
+        #[arg(long)]
+        override_risk: bool,
+    },
     /// List open positions.
     Positions,
     /// List open orders.
     Orders,
-    /// List recent fills.
-    Fills,
+    /// List fills, newest first. With --from/--to, pages through the
+    /// account's fill history.
+    Fills {
+        /// Only fills for this coin.
+        #[arg(long)]
+        coin: Option<String>,
+        /// Start of the range: a date, datetime, unix time, age (7d) or
+        /// today/yesterday/month-to-date.
+        #[arg(long)]
+        from: Option<String>,
+        /// End of the range, inclusive: a date covers the whole day.
+        #[arg(long)]
+        to: Option<String>,
+        /// Maximum number of fills to show.
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
     /// Set leverage for a coin.
     Leverage {
         /// Coin symbol.
@@ -555,6 +1492,17 @@ enum HlPerpAction {
         #[arg(long, default_value_t = false)]
         cross: bool,
     },
+    /// Compare each position's leverage and margin mode with the configured
+    /// default leverage.
+    #[command(name = "leverage-audit")]
+    LeverageAudit {
+        /// Set mismatched coins to the default leverage, keeping their margin mode.
+        #[arg(long)]
+        apply_default: bool,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
     /// Update isolated margin for a position.
     Margin {
         /// Coin symbol.
@@ -566,8 +1514,11 @@ enum HlPerpAction {
     Transfer {
         /// Amount of USDC.
         amount: String,
-        /// Destination EVM address (0x...).
+        /// Destination EVM address (0x...) or ENS name (name.eth).
         destination: String,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
 }
 
@@ -577,21 +1528,48 @@ enum HlSpotAction {
     Buy {
         /// Token symbol (e.g. PURR, HYPE).
         base: String,
-        /// Amount to buy.
-        size: f64,
+        /// Size: $250 (USDC to spend), 0.5hype (units), or a bare number per default_size_mode.
+        size: String,
         /// Slippage tolerance.
         #[arg(long)]
         slippage: Option<f64>,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
     /// Sell spot token.
     Sell {
         /// Token symbol.
         base: String,
-        /// Amount to sell.
-        size: f64,
+        /// Size: $250 (USDC worth), 0.5hype (units), or a bare number per default_size_mode.
+        size: String,
         /// Slippage tolerance.
         #[arg(long)]
         slippage: Option<f64>,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Place a resting spot limit order on the USDC pair.
+    Order {
+        /// Token symbol (e.g. PURR, HYPE).
+        base: String,
+        /// Side: buy/sell (or b/s).
+        side: String,
+        /// Amount in base tokens.
+        size: f64,
+        /// Limit price in USDC.
+        price: f64,
+    },
+    /// List open spot orders.
+    Orders,
+    /// Cancel spot order(s). Without --oid, cancels all orders on the pair.
+    Cancel {
+        /// Token symbol.
+        base: String,
+        /// Specific order ID to cancel.
+        #[arg(long)]
+        oid: Option<u64>,
     },
     /// Show spot token balances.
     Balance,
@@ -604,11 +1582,31 @@ enum HlSpotAction {
         /// Token (default: USDC).
         #[arg(long)]
         token: Option<String>,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
 }
 
 #[derive(Subcommand)]
 enum HlVaultAction {
+    /// Public vaults, ranked (listing cached for 5 minutes).
+    List {
+        /// Rank by APR, TVL or follower count
+        #[arg(long, value_parser = atlas_core::vault_list::VAULT_SORTS, default_value = "tvl")]
+        sort: String,
+        /// Hide vaults with less than this many USD deposited
+        #[arg(long)]
+        min_tvl: Option<f64>,
+        /// Only vaults whose name contains this (case-insensitive)
+        #[arg(long)]
+        search: Option<String>,
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Refetch the listing instead of using the cache
+        #[arg(long)]
+        refresh: bool,
+    },
     /// Vault details.
     Details { vault: String },
     /// Your vault deposits.
@@ -639,9 +1637,9 @@ enum HlAgentAction {
 enum ZeroXAction {
     /// Get indicative swap price quote.
     Quote {
-        /// Sell token contract address.
+        /// Sell token: address-book name (e.g. USDC) or contract address.
         sell_token: String,
-        /// Buy token contract address.
+        /// Buy token: address-book name (e.g. WETH) or contract address.
         buy_token: String,
         /// Amount to sell (in base units / wei).
         amount: String,
@@ -651,12 +1649,18 @@ enum ZeroXAction {
         /// Max slippage in basis points (default 100 = 1%).
         #[arg(long)]
         slippage: Option<u32>,
+        /// Quote through the gasless API (relayer pays gas, fee in the sell token).
+        #[arg(long)]
+        gasless: bool,
+        /// Save the quote to history (`atlas history swaps --status quoted`)
+        #[arg(long)]
+        record: bool,
     },
     /// Execute a swap on-chain (quote → approve → sign → broadcast).
     Swap {
-        /// Sell token contract address.
+        /// Sell token: address-book name (e.g. USDC) or contract address.
         sell_token: String,
-        /// Buy token contract address.
+        /// Buy token: address-book name (e.g. WETH) or contract address.
         buy_token: String,
         /// Amount to sell (in base units / wei).
         amount: String,
@@ -666,6 +1670,12 @@ enum ZeroXAction {
         /// Max slippage in basis points (default 100 = 1%).
         #[arg(long)]
         slippage: Option<u32>,
+        /// Sign an EIP-712 order and let the 0x relayer submit it; no native gas needed.
+        #[arg(long)]
+        gasless: bool,
+        /// With --gasless: seconds to wait for the relayer to confirm the trade.
+        #[arg(long, default_value_t = 120, requires = "gasless")]
+        timeout: u64,
         /// Skip confirmation prompt.
         #[arg(long)]
         yes: bool,
@@ -679,6 +1689,46 @@ enum ZeroXAction {
     },
 }
 
+#[derive(Subcommand)]
+enum MorphoAction {
+    /// List markets with supply/borrow APY, utilization and LLTV.
+    Markets {
+        /// Chain (ethereum, base, arbitrum). Default: module default-chain.
+        #[arg(long)]
+        chain: Option<String>,
+        /// Max markets, largest supply first.
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Show one market by its unique key.
+    Market {
+        /// Market unique key (0x...).
+        id: String,
+        /// Chain (ethereum, base, arbitrum). Default: module default-chain.
+        #[arg(long)]
+        chain: Option<String>,
+    },
+    /// Show collateral, debt and health factor per market.
+    Position {
+        /// Address to inspect. Default: active profile.
+        #[arg(long)]
+        address: Option<String>,
+        /// Chain (ethereum, base, arbitrum). Default: module default-chain.
+        #[arg(long)]
+        chain: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PaperAction {
+    /// Wipe the paper account: positions, orders and fills.
+    Reset {
+        /// New USDC balance. Default: module starting-balance.
+        #[arg(long)]
+        balance: Option<f64>,
+    },
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 //  UTILITIES — Stream, Risk, History, Export
 // ═══════════════════════════════════════════════════════════════════════
@@ -686,7 +1736,14 @@ enum ZeroXAction {
 #[derive(Subcommand)]
 enum StreamAction {
     /// Stream all mid prices in real-time.
-    Prices,
+    Prices {
+        /// Only these coins (e.g. BTC ETH). Default: all markets.
+        coins: Vec<String>,
+        /// Read from the Atlas backend's shared feed instead of a direct
+        /// Hyperliquid WebSocket (requires api_key).
+        #[arg(long)]
+        via_backend: bool,
+    },
     /// Stream trades for a specific coin.
     Trades {
         /// Coin symbol (e.g. BTC, ETH).
@@ -704,11 +1761,36 @@ enum StreamAction {
     Candles {
         /// Coin symbol.
         ticker: String,
-        /// Candle interval (e.g. 1m, 5m, 1h).
-        interval: String,
+        /// Candle interval (e.g. 1m, 5m, 1h; aliases like 60m or D accepted).
+        interval: Timeframe,
     },
     /// Stream user account updates (fills, orders).
     User,
+    /// Record a coin's feeds to disk for offline analysis.
+    Record {
+        /// Coin symbol.
+        ticker: String,
+        /// Feeds to record.
+        #[arg(long, value_delimiter = ',', default_value = "book,trades", value_parser = ["book", "trades"])]
+        channels: Vec<String>,
+        /// Recording directory (created; must not hold a recording).
+        #[arg(long)]
+        out: String,
+        /// Start a new segment file past this size (e.g. 100mb, 1gb).
+        #[arg(long, default_value = "100mb", value_parser = atlas_core::recording::parse_byte_size)]
+        rotate: u64,
+        /// Stop with an error when free disk space falls below this.
+        #[arg(long, default_value = "1gb", value_parser = atlas_core::recording::parse_byte_size)]
+        min_free: u64,
+    },
+    /// Re-emit a recording as the live streams' NDJSON.
+    Replay {
+        /// Directory written by `stream record`.
+        dir: String,
+        /// Playback speed: 1x, 10x, 0.5x, or max for no pacing.
+        #[arg(long, default_value = "1x")]
+        speed: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -732,6 +1814,35 @@ enum RiskAction {
         #[arg(long)]
         leverage: Option<u32>,
     },
+    /// Position size from your closed-trade record (Kelly) or a fixed fraction.
+    Suggest {
+        coin: String,
+        /// long or short
+        #[arg(long)]
+        side: String,
+        #[arg(long)]
+        entry: f64,
+        #[arg(long)]
+        stop: f64,
+        /// Falls back to fixed-frac with fewer than risk.kelly_min_trades trades
+        #[arg(long, default_value = "kelly", value_parser = ["kelly", "fixed-frac"])]
+        method: String,
+        /// Account fraction to risk for fixed-frac (default: risk.max_risk_pct)
+        #[arg(long)]
+        fraction: Option<f64>,
+        #[arg(long)]
+        leverage: Option<u32>,
+    },
+    /// Liquidation price across all cross positions (or the isolated margin).
+    Liq {
+        coin: String,
+        /// Hypothetical trade to model: positive buys, negative sells.
+        #[arg(long, allow_negative_numbers = true)]
+        add_size: Option<f64>,
+        /// Fill price of the hypothetical trade (default: mark).
+        #[arg(long, requires = "add_size")]
+        at: Option<f64>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -748,6 +1859,9 @@ enum HistoryAction {
         to: Option<String>,
         #[arg(long, default_value_t = 50)]
         limit: usize,
+        /// Show journal notes inline instead of a marker
+        #[arg(long)]
+        with_notes: bool,
     },
     Orders {
         /// Filter by protocol (hyperliquid, 0x). Default: all.
@@ -755,8 +1869,16 @@ enum HistoryAction {
         protocol: Option<String>,
         #[arg(long)]
         coin: Option<String>,
+        /// open, filled, canceled, triggered or rejected
         #[arg(long)]
         status: Option<String>,
+        /// Only orders still open whose status no sync confirmed in the last hour
+        #[arg(long)]
+        stale: bool,
+        #[arg(long)]
+        from: Option<String>,
+        #[arg(long)]
+        to: Option<String>,
         #[arg(long, default_value_t = 50)]
         limit: usize,
     },
@@ -771,6 +1893,58 @@ enum HistoryAction {
         #[arg(long)]
         to: Option<String>,
     },
+    /// 0x swaps sent from this machine and quotes saved with `0x quote --record`.
+    Swaps {
+        /// executed or quoted. Default: both.
+        #[arg(long)]
+        status: Option<String>,
+        #[arg(long)]
+        chain: Option<String>,
+        /// Token symbol or address on either side
+        #[arg(long)]
+        coin: Option<String>,
+        #[arg(long)]
+        from: Option<String>,
+        #[arg(long)]
+        to: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Deposits, withdrawals and transfers cached by `hl sync`.
+    Transfers {
+        /// deposit, withdraw or internal. Default: all.
+        #[arg(long = "type", value_parser = atlas_core::ledger::TRANSFER_KINDS)]
+        kind: Option<String>,
+        #[arg(long)]
+        from: Option<String>,
+        #[arg(long)]
+        to: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Import trades made outside Atlas from a CSV file into the fills cache.
+    Import {
+        /// CSV file with a header row.
+        file: String,
+        /// Label to store the fills under; history and export filter on it.
+        #[arg(long, alias = "proto", default_value = "custom")]
+        protocol: String,
+        /// generic: timestamp,coin,side,size,price,fee,pnl[,tx_id].
+        /// hyperliquid-ui: the Hyperliquid web app's trade history export.
+        #[arg(long, default_value = "generic", value_parser = atlas_core::trade_import::IMPORT_FORMATS)]
+        format: String,
+        /// Validate and count without writing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Recent fills fetched live from the exchanges (not the local cache).
+    Fills {
+        /// Perp protocol to query, or `all`. Default: the default perp module.
+        #[arg(long, alias = "proto")]
+        protocol: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
 }
 
 #[derive(Subcommand)]
@@ -788,6 +1962,9 @@ enum ExportAction {
         from: Option<String>,
         #[arg(long)]
         to: Option<String>,
+        /// Add a notes column with linked journal notes
+        #[arg(long)]
+        notes: bool,
     },
     Pnl {
         #[arg(long, alias = "proto")]
@@ -801,55 +1978,242 @@ enum ExportAction {
         #[arg(long)]
         to: Option<String>,
     },
+    /// Monthly statement: opening equity, fills, funding, transfers,
+    /// closing equity and totals.
+    Statement {
+        /// Month, YYYY-MM (in --tz, local time by default)
+        #[arg(long)]
+        month: String,
+        /// all, hyperliquid (hl) or paper
+        #[arg(long, alias = "proto", default_value = "all")]
+        protocol: String,
+        /// File to write; default data/export-statement-<ts>.<format>
+        #[arg(long)]
+        out: Option<String>,
+        #[arg(long, default_value = "csv", value_parser = ["csv", "json"])]
+        format: String,
+    },
 }
 
-// ═══════════════════════════════════════════════════════════════════════
-//  ENTRYPOINT
-// ═══════════════════════════════════════════════════════════════════════
-
-#[tokio::main]
-async fn main() {
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
-        .with_target(false)
-        .init();
-
-    if let Err(e) = atlas_core::init_workspace() {
-        eprintln!("Error: {e}");
-        std::process::exit(1);
-    }
-
-    let cli = Cli::parse();
-    let fmt: OutputFormat = cli.output.into();
+#[derive(Subcommand)]
+enum ScheduleAction {
+    /// Schedule an atlas command, e.g. "hl perp buy BTC \$50".
+    Add {
+        /// Command line without `atlas`, quoted.
+        command: String,
+        /// Interval: 30m, 24h, 1w ...
+        #[arg(long)]
+        every: String,
+        /// First run at HH:MM UTC. Default: one interval from now.
+        #[arg(long)]
+        at: Option<String>,
+        /// Stop after this many runs.
+        #[arg(long)]
+        max_runs: Option<u32>,
+        /// Skip the confirmation prompt.
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// List jobs with their next run and last result.
+    List,
+    /// Delete a job and its run history.
+    Remove { id: i64 },
+    /// Run jobs as they come due, until stopped.
+    Run {
+        /// Runs missed while the executor was down: skip them, or run once.
+        #[arg(long, value_parser = ["skip", "run-once"], default_value = "skip")]
+        catch_up: String,
+    },
+}
 
-    let result = run(cli.command, fmt).await;
+#[derive(Subcommand)]
+enum NotifyAction {
+    /// Deliver events from the user stream until stopped.
+    Watch {
+        /// Seconds between position checks for liquidation warnings.
+        #[arg(long, default_value = "30")]
+        poll_secs: u64,
+    },
+    /// Send a sample event to every configured target.
+    Test,
+}
 
-    if let Err(e) = result {
-        if fmt != OutputFormat::Table {
-            // PRD-compliant structured error JSON to stdout for machine consumers
-            // Try to extract AtlasError for structured output, else wrap as UNKNOWN_ERROR
-            let json = classify_error(&e);
-            println!("{}", serde_json::to_string(&json).unwrap_or_default());
-            let exit_code = json["error"]["category"]
-                .as_str()
-                .map(|c| match c {
-                    "network" => 2,
-                    "system" => 3,
-                    _ => 1,
-                })
-                .unwrap_or(1);
-            std::process::exit(exit_code);
-        } else {
-            eprintln!("Error: {e:#}");
-            // Determine exit code from error chain
-            let exit_code = exit_code_from_error(&e);
-            std::process::exit(exit_code);
-        }
-    }
+#[derive(Subcommand)]
+enum JournalAction {
+    /// Attach a note to a fill (hash) or order (oid), or to --coin/--at
+    /// for a trade that has not been synced yet.
+    Add {
+        /// Fill hash (0x…) or order id
+        target: Option<String>,
+        #[arg(long)]
+        note: String,
+        /// Comma-separated tags, e.g. breakout,news
+        #[arg(long, value_delimiter = ',')]
+        tags: Vec<String>,
+        /// Coin of an unsynced trade (with --at)
+        #[arg(long, requires = "at", conflicts_with = "target")]
+        coin: Option<String>,
+        /// Approximate trade time, e.g. 2026-01-31T14:30 or yesterday
+        #[arg(long, requires = "coin")]
+        at: Option<String>,
+    },
+    /// List notes, newest first.
+    List {
+        #[arg(long)]
+        coin: Option<String>,
+        #[arg(long)]
+        tag: Option<String>,
+        #[arg(long)]
+        from: Option<String>,
+        #[arg(long)]
+        to: Option<String>,
+        /// Full-text search over notes and tags
+        #[arg(long)]
+        search: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
 }
 
-/// Classify an anyhow error into PRD-compliant structured JSON.
-///
+#[derive(Subcommand)]
+enum AuditAction {
+    /// List signed actions, newest first.
+    List {
+        /// order, cancel, modify, leverage, margin, transfer, agent (Hyperliquid);
+        /// swap, approve, revoke, gasless (0x)
+        #[arg(long = "type", value_parser = ["order", "cancel", "modify", "leverage", "margin", "transfer", "agent", "swap", "approve", "revoke", "gasless"])]
+        action_type: Option<String>,
+        #[arg(long)]
+        from: Option<String>,
+        #[arg(long)]
+        to: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// Show one entry with the raw request and response.
+    Show {
+        /// Entry id, e.g. the `audit_id` of an order result
+        id: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum AddressAction {
+    /// Name a token contract, e.g. `atlas address add AERO 0x9401… --chain base`.
+    Add {
+        /// Name to use in place of the address (case-insensitive).
+        name: String,
+        /// Contract address; mixed case must be a valid EIP-55 checksum.
+        address: String,
+        #[arg(long, default_value = "ethereum")]
+        chain: String,
+        /// Token decimals, needed to price it in `atlas market compare`.
+        #[arg(long)]
+        decimals: Option<u32>,
+    },
+    /// List built-in and user tokens.
+    List {
+        /// Only this chain (default: all).
+        #[arg(long)]
+        chain: Option<String>,
+    },
+    /// Remove a user-added token.
+    Remove {
+        name: String,
+        #[arg(long, default_value = "ethereum")]
+        chain: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum WalletAction {
+    /// Current ERC-20 allowance the wallet has granted a spender.
+    Allowance {
+        /// Address-book name (e.g. USDC) or contract address.
+        token: String,
+        /// Spender address, or `0x-exchange` for the 0x AllowanceHolder.
+        #[arg(long)]
+        spender: String,
+        #[arg(long, default_value = "ethereum")]
+        chain: String,
+    },
+    /// Approve a spender for an amount in token units, or `max`.
+    Approve {
+        token: String,
+        /// Token amount (e.g. 250.5) or `max` for unlimited.
+        amount: String,
+        #[arg(long)]
+        spender: String,
+        #[arg(long, default_value = "ethereum")]
+        chain: String,
+        /// Skip confirmation (required for `max` without a terminal).
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Set a spender's allowance to 0.
+    Revoke {
+        token: String,
+        #[arg(long)]
+        spender: String,
+        #[arg(long, default_value = "ethereum")]
+        chain: String,
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  ENTRYPOINT
+// ═══════════════════════════════════════════════════════════════════════
+
+#[tokio::main]
+async fn main() {
+    logging::init();
+
+    if let Err(e) = atlas_core::init_workspace() {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+
+    let cli = Cli::parse();
+    let fmt: OutputFormat = cli.output.into();
+    factory::set_refresh_meta(cli.refresh_meta);
+    factory::set_no_candle_cache(cli.no_cache);
+    commands::helpers::set_time_zone(cli.tz);
+    if let Commands::Market {
+        action: MarketAction::Hyperliquid { strict, .. },
+    } = &cli.command
+    {
+        commands::ta::set_strict_gaps(*strict);
+    }
+    let numbers = atlas_core::workspace::load_config()
+        .map(|config| atlas_core::fmt::NumberFormat::from(&config.output))
+        .unwrap_or_default();
+    atlas_core::output::set_render_context(
+        atlas_core::output::RenderContext::detect(cli.plain, cli.quiet).with_numbers(numbers),
+    );
+
+    let result = match cli.profile.as_deref().map(AuthManager::override_profile) {
+        Some(Err(e)) => Err(e),
+        _ => run(cli.command, fmt).await,
+    };
+
+    if let Err(e) = result {
+        if fmt != OutputFormat::Table {
+            // PRD-compliant structured error JSON to stdout for machine consumers
+            // Try to extract AtlasError for structured output, else wrap as UNKNOWN_ERROR
+            let json = classify_error(&e);
+            println!("{}", serde_json::to_string(&json).unwrap_or_default());
+        } else {
+            eprintln!("Error: {e:#}");
+        }
+        // Same exit code in every output mode, from the error chain
+        std::process::exit(exit_code_from_error(&e));
+    }
+}
+
+/// Classify an anyhow error into PRD-compliant structured JSON.
+///
 /// If the error chain contains an `AtlasError`, use its structured detail.
 /// Otherwise, heuristically classify from the error message.
 fn classify_error(e: &anyhow::Error) -> serde_json::Value {
@@ -858,6 +2222,11 @@ fn classify_error(e: &anyhow::Error) -> serde_json::Value {
         return atlas_err.to_json();
     }
 
+    // Exchange rejections that were flattened to a string still classify by kind
+    if let Some(rejected) = rejection_from_message(e) {
+        return rejected.to_json();
+    }
+
     // Heuristic classification from error message
     let msg = format!("{e:#}");
     let lower = msg.to_lowercase();
@@ -917,11 +2286,23 @@ fn classify_error(e: &anyhow::Error) -> serde_json::Value {
     })
 }
 
+/// Recover a structured `OrderRejected` from an error that lost its type.
+fn rejection_from_message(e: &anyhow::Error) -> Option<AtlasError> {
+    let msg = format!("{e:#}");
+    let idx = msg.find("Order rejected: ")?;
+    Some(AtlasError::order_rejected(
+        &msg[idx + "Order rejected: ".len()..],
+    ))
+}
+
 /// Determine exit code from an anyhow error chain.
 fn exit_code_from_error(e: &anyhow::Error) -> i32 {
     if let Some(atlas_err) = e.downcast_ref::<AtlasError>() {
         return atlas_err.exit_code();
     }
+    if let Some(rejected) = rejection_from_message(e) {
+        return rejected.exit_code();
+    }
 
     let msg = format!("{e:#}").to_lowercase();
     if msg.contains("timeout")
@@ -943,64 +2324,193 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
             ProfileAction::Import { name } => commands::auth::import_wallet(&name, fmt),
             ProfileAction::Use { name } => commands::auth::switch_profile(&name, fmt),
             ProfileAction::List => commands::auth::list_profiles(fmt),
-            ProfileAction::Export { name } => commands::auth::export_wallet(&name, fmt),
+            ProfileAction::Export { name, yes } => commands::auth::export_wallet(&name, yes, fmt),
+            ProfileAction::Remove {
+                name,
+                switch_to,
+                purge_history,
+                yes,
+            } => {
+                commands::auth::remove_profile(&name, switch_to.as_deref(), purge_history, yes, fmt)
+            }
+            ProfileAction::Rename { old, new } => commands::auth::rename_profile(&old, &new, fmt),
         },
 
         Commands::Configure { action } => match action {
-            ConfigureAction::Show => commands::configure::run(fmt),
+            ConfigureAction::Show { reveal } => commands::configure::run(reveal, fmt),
+            ConfigureAction::Get { key, reveal } => commands::configure::get(&key, reveal, fmt),
+            ConfigureAction::Set { key, value } => commands::configure::set(&key, &value, fmt),
+            ConfigureAction::Keys => commands::configure::keys(fmt),
+            ConfigureAction::Env { action } => match action {
+                EnvConfigAction::List => commands::configure::env_list(fmt),
+                EnvConfigAction::Use { name } => commands::configure::env_use(&name, fmt),
+            },
             ConfigureAction::System { action } => match action {
                 SystemConfigAction::Profile { name } => commands::auth::switch_profile(&name, fmt),
                 SystemConfigAction::Verbose { enabled } => {
-                    let mut config = atlas_core::workspace::load_config()?;
-                    let val = enabled.to_lowercase() == "true" || enabled == "1" || enabled == "on";
-                    config.system.verbose = val;
-                    atlas_core::workspace::save_config(&config)?;
-                    if fmt == OutputFormat::Table {
-                        println!("✓ verbose = {val}");
-                    } else {
-                        println!(
-                            "{}",
-                            serde_json::json!({"ok": true, "data": {"key": "verbose", "value": val}})
-                        );
-                    }
-                    Ok(())
-                }
-                SystemConfigAction::ApiKey { key } => {
-                    let mut config = atlas_core::workspace::load_config()?;
-                    config.system.api_key = Some(key.clone());
-                    atlas_core::workspace::save_config(&config)?;
-                    if fmt == OutputFormat::Table {
-                        println!("✓ api_key = {key}");
-                    } else {
-                        println!(
-                            "{}",
-                            serde_json::json!({"ok": true, "data": {"key": "api_key", "value": key}})
-                        );
-                    }
-                    Ok(())
+                    commands::configure::verbose(&enabled, fmt)
                 }
+                SystemConfigAction::ApiKey { key } => commands::configure::api_key(&key, fmt).await,
             },
             ConfigureAction::Module { action } => match action {
-                ModuleConfigAction::List => commands::modules::run(fmt),
+                ModuleConfigAction::List { no_probe } => {
+                    commands::modules::run(!no_probe, fmt).await
+                }
+                ModuleConfigAction::Doctor => commands::modules::doctor(fmt).await,
                 ModuleConfigAction::Enable { name } => commands::modules::enable(&name, fmt),
                 ModuleConfigAction::Disable { name } => commands::modules::disable(&name, fmt),
-                ModuleConfigAction::Set { module, values } => {
-                    commands::modules::config_set(&module, &values, fmt)
+                ModuleConfigAction::Set {
+                    module,
+                    values,
+                    yes,
+                } => commands::modules::config_set(&module, &values, yes, fmt),
+            },
+            ConfigureAction::Trading { action } => match action {
+                TradingConfigAction::Confirm { enabled } => {
+                    commands::configure::trading_confirm(&enabled, fmt)
+                }
+                TradingConfigAction::MaxNotional { value } => {
+                    commands::configure::trading_limit("max_notional", &value, fmt)
+                }
+                TradingConfigAction::MaxLeverage { value } => {
+                    commands::configure::trading_limit("max_leverage", &value, fmt)
+                }
+                TradingConfigAction::DailyLossLimit { value } => {
+                    commands::configure::trading_limit("daily_loss_limit", &value, fmt)
+                }
+                TradingConfigAction::MaxSlippage { value } => {
+                    commands::configure::trading_limit("max_slippage", &value, fmt)
+                }
+                TradingConfigAction::PriceBand { value } => {
+                    commands::configure::trading_limit("price_band", &value, fmt)
+                }
+                TradingConfigAction::SpreadWarn { value } => {
+                    commands::configure::trading_limit("spread_warn_multiple", &value, fmt)
+                }
+                TradingConfigAction::Preset { action } => match action {
+                    PresetAction::Set {
+                        coin,
+                        leverage,
+                        slippage,
+                        default_size,
+                    } => commands::configure::preset_set(
+                        &coin,
+                        leverage,
+                        slippage,
+                        default_size.as_deref(),
+                        fmt,
+                    ),
+                    PresetAction::List => commands::configure::preset_list(fmt),
+                    PresetAction::Remove { coin } => commands::configure::preset_remove(&coin, fmt),
+                },
+            },
+            ConfigureAction::Notify { action } => match action {
+                NotifyConfigAction::Set {
+                    webhook,
+                    exec,
+                    secret,
+                    liq_warn_pct,
+                } => commands::configure::notify_set(
+                    webhook.as_deref(),
+                    exec.as_deref(),
+                    secret.as_deref(),
+                    liq_warn_pct,
+                    fmt,
+                ),
+                NotifyConfigAction::Show { reveal } => {
+                    commands::configure::notify_show(reveal, fmt)
+                }
+            },
+            ConfigureAction::Security { action } => match action {
+                SecurityConfigAction::Show => commands::configure::security_show(fmt),
+                SecurityConfigAction::Totp { action } => match action {
+                    TotpAction::Enroll => commands::configure::totp_enroll(fmt).await,
+                    TotpAction::Remove => commands::configure::totp_remove(fmt).await,
+                },
+                SecurityConfigAction::Whitelist { action } => match action {
+                    WhitelistAction::List => commands::configure::whitelist_list(fmt),
+                    WhitelistAction::Add { address, label } => {
+                        commands::configure::whitelist_add(&address, &label, fmt)
+                    }
+                    WhitelistAction::Remove { target } => {
+                        commands::configure::whitelist_remove(&target, fmt)
+                    }
+                    WhitelistAction::Enable => commands::configure::whitelist_enable(true, fmt),
+                    WhitelistAction::Disable => commands::configure::whitelist_enable(false, fmt),
+                },
+            },
+            ConfigureAction::Sync { action } => match action {
+                SyncConfigAction::Status => commands::configure::sync_status(fmt).await,
+                SyncConfigAction::Push { force, yes } => {
+                    commands::configure::sync_push(force, yes, fmt).await
+                }
+                SyncConfigAction::Pull { force, merge, yes } => {
+                    commands::configure::sync_pull(force, merge, yes, fmt).await
                 }
             },
         },
 
-        Commands::Status => commands::status::run(fmt).await,
+        Commands::Status { all, diff } => commands::status::run(all, diff, fmt).await,
+        Commands::Positions {
+            protocol,
+            coin,
+            min_notional,
+            sort,
+        } => {
+            commands::trade::positions(
+                protocol.as_deref(),
+                coin.as_deref(),
+                min_notional,
+                sort.as_deref(),
+                fmt,
+            )
+            .await
+        }
+        Commands::Init {
+            non_interactive,
+            from,
+        } => commands::init::run(non_interactive, from.as_deref(), fmt).await,
         Commands::Doctor { fix } => commands::doctor::run(fix, fmt).await,
+        Commands::Logs {
+            tail,
+            follow,
+            level,
+            grep,
+        } => commands::logs::run(tail, follow, level, grep, fmt).await,
+        Commands::Workspace { action } => match action {
+            WorkspaceAction::Backup {
+                path,
+                include_keys,
+                yes,
+            } => commands::workspace::backup(&path, include_keys, yes, fmt),
+            WorkspaceAction::Restore { path, force } => {
+                commands::workspace::restore(&path, force, fmt)
+            }
+        },
+        Commands::Cache { action } => match action {
+            CacheAction::Candles {
+                coin,
+                interval,
+                prune_before,
+            } => commands::cache::candles(
+                coin.as_deref(),
+                interval.map(Timeframe::as_str),
+                prune_before.as_deref(),
+                fmt,
+            ),
+        },
         Commands::Tui => tui::run().await,
 
         // ── MARKET DATA & ANALYTICS ─────────────────────────────
         Commands::Market { action } => match action {
-            MarketAction::Hyperliquid { action } => match action {
+            MarketAction::Hyperliquid { action, .. } => match action {
                 MarketHlAction::List { spot } => commands::market::markets(spot, fmt).await,
-                MarketHlAction::Price { tickers, all } => {
-                    commands::market::price(&tickers, all, fmt).await
-                }
+                MarketHlAction::Price {
+                    tickers,
+                    all,
+                    spot,
+                    quote,
+                } => commands::market::price(&tickers, all, spot, quote.as_deref(), fmt).await,
                 MarketHlAction::Funding { ticker } => commands::market::funding(&ticker, fmt).await,
                 MarketHlAction::Orderbook { ticker, depth } => {
                     commands::market::orderbook(&ticker, depth, fmt).await
@@ -1009,87 +2519,208 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                     ticker,
                     timeframe,
                     limit,
-                } => commands::market::candles(&ticker, &timeframe, limit, fmt).await,
+                } => commands::market::candles(&ticker, timeframe.as_str(), limit, fmt).await,
                 MarketHlAction::Info { coin } => commands::market::info(&coin, fmt).await,
                 MarketHlAction::Top {
                     sort,
+                    min_volume,
                     limit,
                     reverse,
-                } => commands::market::top(&sort, limit, reverse, fmt).await,
-                MarketHlAction::Spread { coins } => commands::market::spread(&coins, fmt).await,
+                } => commands::market::top(sort, min_volume, limit, reverse, fmt).await,
+                MarketHlAction::Oi {
+                    action,
+                    ticker,
+                    hours,
+                } => {
+                    match (action, ticker) {
+                        (
+                            Some(OiAction::Record {
+                                interval,
+                                retention_days,
+                                once,
+                            }),
+                            _,
+                        ) => commands::oi::record(interval, retention_days, once, fmt).await,
+                        (None, Some(ticker)) => commands::oi::history(&ticker, hours, fmt),
+                        (None, None) => {
+                            anyhow::bail!("Usage: atlas market hyperliquid oi <TICKER> [--hours 24] | oi record")
+                        }
+                    }
+                }
+                MarketHlAction::FundingScreen {
+                    min_abs,
+                    min_oi,
+                    sort,
+                    limit,
+                    reverse,
+                } => {
+                    commands::market::funding_screen(min_abs, min_oi, &sort, limit, reverse, fmt)
+                        .await
+                }
+                MarketHlAction::Spread { coins, quote } => {
+                    commands::market::spread(&coins, quote.as_deref(), fmt).await
+                }
+                MarketHlAction::SpreadProfile { coin } => {
+                    commands::market::spread_profile(&coin, fmt)
+                }
                 MarketHlAction::Search { query } => commands::market::search(&query, fmt).await,
-                MarketHlAction::Summary => commands::market::summary(fmt).await,
+                MarketHlAction::Summary {
+                    sections,
+                    limit,
+                    watchlist,
+                } => commands::market::summary(&sections, limit, watchlist, fmt).await,
+                MarketHlAction::Ta {
+                    ticker,
+                    timeframe,
+                    indicators,
+                } => {
+                    commands::ta::ta(&ticker, timeframe.as_str(), indicators.as_deref(), fmt).await
+                }
                 MarketHlAction::Rsi {
                     ticker,
                     timeframe,
                     period,
-                } => commands::ta::rsi(&ticker, &timeframe, period, fmt).await,
+                } => commands::ta::rsi(&ticker, timeframe.as_str(), period, fmt).await,
                 MarketHlAction::Macd { ticker, timeframe } => {
-                    commands::ta::macd(&ticker, &timeframe, fmt).await
+                    commands::ta::macd(&ticker, timeframe.as_str(), fmt).await
                 }
                 MarketHlAction::Vwap { ticker } => commands::ta::vwap(&ticker, fmt).await,
-                MarketHlAction::Trend { ticker } => commands::ta::trend(&ticker, fmt).await,
+                MarketHlAction::Trend { ticker, mtf } => {
+                    commands::ta::trend(&ticker, mtf.as_deref(), fmt).await
+                }
                 MarketHlAction::Bbands {
                     ticker,
                     timeframe,
                     period,
-                } => commands::ta::bbands(&ticker, &timeframe, period, fmt).await,
+                } => commands::ta::bbands(&ticker, timeframe.as_str(), period, fmt).await,
                 MarketHlAction::Stoch { ticker, timeframe } => {
-                    commands::ta::stoch(&ticker, &timeframe, fmt).await
+                    commands::ta::stoch(&ticker, timeframe.as_str(), fmt).await
                 }
                 MarketHlAction::Adx {
                     ticker,
                     timeframe,
                     period,
-                } => commands::ta::adx(&ticker, &timeframe, period, fmt).await,
+                } => commands::ta::adx(&ticker, timeframe.as_str(), period, fmt).await,
                 MarketHlAction::Atr {
                     ticker,
                     timeframe,
                     period,
-                } => commands::ta::atr(&ticker, &timeframe, period, fmt).await,
+                } => commands::ta::atr(&ticker, timeframe.as_str(), period, fmt).await,
                 MarketHlAction::Ema {
                     ticker,
                     timeframe,
                     period,
-                } => commands::ta::ema(&ticker, &timeframe, period, fmt).await,
+                } => commands::ta::ema(&ticker, timeframe.as_str(), period, fmt).await,
                 MarketHlAction::Sma {
                     ticker,
                     timeframe,
                     period,
-                } => commands::ta::sma(&ticker, &timeframe, period, fmt).await,
+                } => commands::ta::sma(&ticker, timeframe.as_str(), period, fmt).await,
                 MarketHlAction::Obv { ticker, timeframe } => {
-                    commands::ta::obv(&ticker, &timeframe, fmt).await
+                    commands::ta::obv(&ticker, timeframe.as_str(), fmt).await
                 }
                 MarketHlAction::Cci {
                     ticker,
                     timeframe,
                     period,
-                } => commands::ta::cci(&ticker, &timeframe, period, fmt).await,
+                } => commands::ta::cci(&ticker, timeframe.as_str(), period, fmt).await,
                 MarketHlAction::Willr {
                     ticker,
                     timeframe,
                     period,
-                } => commands::ta::willr(&ticker, &timeframe, period, fmt).await,
+                } => commands::ta::willr(&ticker, timeframe.as_str(), period, fmt).await,
                 MarketHlAction::Sar { ticker, timeframe } => {
-                    commands::ta::sar(&ticker, &timeframe, fmt).await
+                    commands::ta::sar(&ticker, timeframe.as_str(), fmt).await
                 }
-                MarketHlAction::Patterns { ticker, timeframe } => {
-                    commands::ta::patterns(&ticker, &timeframe, fmt).await
+                MarketHlAction::Ichimoku {
+                    ticker,
+                    timeframe,
+                    tenkan,
+                    kijun,
+                    senkou,
+                } => {
+                    commands::ta::ichimoku(&ticker, timeframe.as_str(), tenkan, kijun, senkou, fmt)
+                        .await
+                }
+                MarketHlAction::Supertrend {
+                    ticker,
+                    timeframe,
+                    period,
+                    multiplier,
+                } => {
+                    commands::ta::supertrend(&ticker, timeframe.as_str(), period, multiplier, fmt)
+                        .await
+                }
+                MarketHlAction::Backtest {
+                    ticker,
+                    timeframe,
+                    strategy,
+                    period,
+                    slow,
+                    lookback,
+                    fee_bps,
+                } => {
+                    commands::backtest::run(
+                        &ticker,
+                        timeframe.as_str(),
+                        &strategy,
+                        period,
+                        slow,
+                        lookback,
+                        fee_bps,
+                        fmt,
+                    )
+                    .await
                 }
+                MarketHlAction::Correlate {
+                    coins,
+                    timeframe,
+                    lookback,
+                } => commands::correlate::run(&coins, timeframe.as_str(), lookback, fmt).await,
+                MarketHlAction::Patterns {
+                    ticker,
+                    timeframe,
+                    lookback,
+                    patterns,
+                } => commands::ta::patterns(&ticker, timeframe, lookback, &patterns, fmt).await,
+                MarketHlAction::PatternsScan {
+                    timeframe,
+                    patterns,
+                    limit,
+                } => commands::ta::patterns_scan(timeframe, &patterns, limit, fmt).await,
             },
             MarketAction::Dex { action } => match action {
                 MarketDexAction::Trending { network, limit } => {
                     commands::coingecko::dex_trending(network.as_deref(), limit, fmt).await
                 }
-                MarketDexAction::New { network, limit } => {
-                    commands::coingecko::dex_new(network.as_deref(), limit, fmt).await
-                }
+                MarketDexAction::New {
+                    network,
+                    limit,
+                    screen,
+                } => commands::coingecko::dex_new(network.as_deref(), limit, screen, fmt).await,
                 MarketDexAction::Pools { network, limit } => {
                     commands::coingecko::dex_top_pools(&network, limit, fmt).await
                 }
                 MarketDexAction::Pool { network, address } => {
                     commands::coingecko::dex_pool_detail(&network, &address, fmt).await
                 }
+                MarketDexAction::Ohlcv {
+                    network,
+                    address,
+                    timeframe,
+                    aggregate,
+                    limit,
+                } => {
+                    commands::coingecko::dex_ohlcv(
+                        &network, &address, &timeframe, aggregate, limit, fmt,
+                    )
+                    .await
+                }
+                MarketDexAction::Trades {
+                    network,
+                    address,
+                    min_usd,
+                } => commands::coingecko::dex_trades(&network, &address, min_usd, fmt).await,
                 MarketDexAction::Token { network, address } => {
                     commands::coingecko::dex_token_info(&network, &address, fmt).await
                 }
@@ -1100,24 +2731,103 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                 MarketDexAction::Search { query } => {
                     commands::coingecko::dex_search(&query, fmt).await
                 }
+                MarketDexAction::Watch { action } => match action {
+                    DexWatchAction::Add {
+                        network,
+                        pool,
+                        label,
+                    } => commands::dex_watch::add(&network, &pool, label.as_deref(), fmt),
+                    DexWatchAction::Remove { pool } => commands::dex_watch::remove(&pool, fmt),
+                    DexWatchAction::List => commands::dex_watch::list(fmt),
+                    DexWatchAction::Check { threshold } => {
+                        commands::dex_watch::check(threshold, fmt).await
+                    }
+                },
             },
-            MarketAction::Global => commands::coingecko::global(fmt).await,
-            MarketAction::Trending => commands::coingecko::trending(fmt).await,
-            MarketAction::Coin { id } => commands::coingecko::coin(&id, fmt).await,
-            MarketAction::Movers { limit } => commands::coingecko::movers(limit, fmt).await,
-            MarketAction::Defi => commands::coingecko::defi(fmt).await,
+            MarketAction::Global { cached_ok } => commands::coingecko::global(cached_ok, fmt).await,
+            MarketAction::Overview {
+                sort,
+                limit,
+                cached_ok,
+            } => commands::coingecko::overview(&sort, limit, cached_ok, fmt).await,
+            MarketAction::Trending { cached_ok } => {
+                commands::coingecko::trending(cached_ok, fmt).await
+            }
+            MarketAction::Coin { id, cached_ok } => {
+                commands::coingecko::coin(&id, cached_ok, fmt).await
+            }
+            MarketAction::Movers { limit, cached_ok } => {
+                commands::coingecko::movers(limit, cached_ok, fmt).await
+            }
+            MarketAction::Defi { cached_ok } => commands::coingecko::defi(cached_ok, fmt).await,
+            MarketAction::Compare {
+                coin,
+                chain,
+                size_usd,
+                threshold_bps,
+            } => {
+                commands::compare::run(&coin, chain.as_deref(), size_usd, threshold_bps, fmt).await
+            }
         },
 
-        Commands::Stream { action } => match action {
-            StreamAction::Prices => commands::stream::stream_prices(fmt).await,
-            StreamAction::Trades { ticker } => commands::stream::stream_trades(&ticker, fmt).await,
-            StreamAction::Book { ticker, depth } => {
-                commands::stream::stream_book(&ticker, depth, fmt).await
+        Commands::Stream {
+            action,
+            metrics_port,
+            stale_after,
+        } => {
+            let metrics =
+                commands::stream::start_metrics(metrics_port.as_deref(), stale_after).await?;
+            match action {
+                StreamAction::Prices { coins, via_backend } => {
+                    commands::stream::stream_prices(&coins, via_backend, &metrics, fmt).await
+                }
+                StreamAction::Trades { ticker } => {
+                    commands::stream::stream_trades(&ticker, &metrics, fmt).await
+                }
+                StreamAction::Book { ticker, depth } => {
+                    commands::stream::stream_book(&ticker, depth, &metrics, fmt).await
+                }
+                StreamAction::Candles { ticker, interval } => {
+                    commands::stream::stream_candles(&ticker, interval.as_str(), &metrics, fmt)
+                        .await
+                }
+                StreamAction::User => commands::stream::stream_user(&metrics, fmt).await,
+                StreamAction::Record {
+                    ticker,
+                    channels,
+                    out,
+                    rotate,
+                    min_free,
+                } => {
+                    commands::stream::record(
+                        &ticker, &channels, &out, rotate, min_free, &metrics, fmt,
+                    )
+                    .await
+                }
+                StreamAction::Replay { dir, speed } => commands::stream::replay(&dir, &speed).await,
             }
-            StreamAction::Candles { ticker, interval } => {
-                commands::stream::stream_candles(&ticker, &interval, fmt).await
+        }
+
+        Commands::Strategy { action } => match action {
+            StrategyAction::Carry {
+                coin,
+                size_usd,
+                horizon_days,
+                market,
+                chain,
+                taker_fee_bps,
+            } => {
+                commands::strategy::carry(
+                    &coin,
+                    size_usd,
+                    horizon_days,
+                    market.as_deref(),
+                    chain.as_deref(),
+                    taker_fee_bps,
+                    fmt,
+                )
+                .await
             }
-            StreamAction::User => commands::stream::stream_user(fmt).await,
         },
 
         // ── HYPERLIQUID ─────────────────────────────────────────
@@ -1133,34 +2843,136 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                         size,
                         leverage,
                         slippage,
-                    } => commands::trade::market_buy(&ticker, &size, leverage, slippage, fmt).await,
+                        max_impact_bps,
+                        dry_run,
+                        yes,
+                        override_risk,
+                        force,
+                    } => {
+                        commands::trade::market_open(
+                            atlas_core::types::Side::Buy,
+                            &ticker,
+                            size.as_deref(),
+                            leverage,
+                            slippage,
+                            max_impact_bps,
+                            dry_run,
+                            yes,
+                            override_risk,
+                            force,
+                            fmt,
+                        )
+                        .await
+                    }
                     HlPerpAction::Sell {
                         ticker,
                         size,
                         leverage,
                         slippage,
+                        max_impact_bps,
+                        dry_run,
+                        yes,
+                        override_risk,
+                        force,
                     } => {
-                        commands::trade::market_sell(&ticker, &size, leverage, slippage, fmt).await
+                        commands::trade::market_open(
+                            atlas_core::types::Side::Sell,
+                            &ticker,
+                            size.as_deref(),
+                            leverage,
+                            slippage,
+                            max_impact_bps,
+                            dry_run,
+                            yes,
+                            override_risk,
+                            force,
+                            fmt,
+                        )
+                        .await
                     }
                     HlPerpAction::Close {
                         ticker,
                         size,
                         slippage,
-                    } => commands::trade::close_position(&ticker, size, slippage, fmt).await,
+                        yes,
+                        force,
+                    } => {
+                        commands::trade::close_position(&ticker, size, slippage, yes, force, fmt)
+                            .await
+                    }
+                    HlPerpAction::CloseAll {
+                        only_losing,
+                        only_winning,
+                        coin_prefix,
+                        slippage,
+                        yes,
+                        force,
+                    } => {
+                        commands::trade::close_all(
+                            only_losing,
+                            only_winning,
+                            coin_prefix.as_deref(),
+                            slippage,
+                            yes,
+                            force,
+                            fmt,
+                        )
+                        .await
+                    }
+                    HlPerpAction::Flip {
+                        ticker,
+                        size,
+                        slippage,
+                        yes,
+                        override_risk,
+                        force,
+                    } => {
+                        commands::trade::flip(
+                            &ticker,
+                            size,
+                            slippage,
+                            yes,
+                            override_risk,
+                            force,
+                            fmt,
+                        )
+                        .await
+                    }
                     HlPerpAction::Order {
                         ticker,
                         side,
                         size,
                         price,
                         reduce_only,
+                        strict,
+                        reduce,
+                        tif,
+                        post_only,
+                        trigger,
+                        trigger_type,
+                        cloid,
+                        override_risk,
                     } => {
+                        let tif = if post_only {
+                            atlas_core::types::TimeInForce::Alo
+                        } else {
+                            tif.unwrap_or_default()
+                        };
+                        let opts = atlas_core::types::LimitOptions {
+                            reduce_only: reduce_only || reduce,
+                            tif,
+                            cloid,
+                            trigger: None,
+                        };
                         commands::trade::limit_order(
                             &ticker,
                             &side,
                             &size,
                             price,
-                            reduce_only,
-                            "Gtc",
+                            opts,
+                            trigger.map(|px| (px, trigger_type.unwrap_or_default())),
+                            strict,
+                            override_risk,
                             fmt,
                         )
                         .await
@@ -1168,44 +2980,119 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                     HlPerpAction::Cancel { ticker, oid } => {
                         commands::trade::cancel(&ticker, oid, fmt).await
                     }
+                    HlPerpAction::Modify {
+                        ticker,
+                        oid,
+                        cloid,
+                        price,
+                        size,
+                        yes,
+                        override_risk,
+                    } => {
+                        commands::trade::modify(
+                            &ticker,
+                            oid,
+                            cloid.as_deref(),
+                            price,
+                            size,
+                            yes,
+                            override_risk,
+                            fmt,
+                        )
+                        .await
+                    }
                     HlPerpAction::Positions => commands::trade::list_positions(fmt).await,
                     HlPerpAction::Orders => commands::trade::list_orders(fmt).await,
-                    HlPerpAction::Fills => commands::trade::list_fills(fmt).await,
+                    HlPerpAction::Fills {
+                        coin,
+                        from,
+                        to,
+                        limit,
+                    } => {
+                        commands::trade::list_fills(
+                            coin.as_deref(),
+                            from.as_deref(),
+                            to.as_deref(),
+                            limit,
+                            fmt,
+                        )
+                        .await
+                    }
                     HlPerpAction::Leverage {
                         ticker,
                         value,
                         cross,
                     } => commands::account::set_leverage(&ticker, value, cross, fmt).await,
+                    HlPerpAction::LeverageAudit { apply_default, yes } => {
+                        commands::account::leverage_audit(apply_default, yes, fmt).await
+                    }
                     HlPerpAction::Margin { ticker, amount } => {
                         commands::account::update_margin(&ticker, amount, fmt).await
                     }
                     HlPerpAction::Transfer {
                         amount,
                         destination,
-                    } => commands::account::transfer_usdc(&amount, &destination, fmt).await,
+                        yes,
+                    } => commands::account::transfer_usdc(&amount, &destination, yes, fmt).await,
                 },
                 HyperliquidAction::Spot { action } => match action {
                     HlSpotAction::Buy {
                         base,
                         size,
                         slippage,
-                    } => commands::spot::spot_buy(&base, size, slippage, fmt).await,
+                        yes,
+                    } => commands::spot::spot_buy(&base, &size, slippage, yes, fmt).await,
                     HlSpotAction::Sell {
                         base,
                         size,
                         slippage,
-                    } => commands::spot::spot_sell(&base, size, slippage, fmt).await,
+                        yes,
+                    } => commands::spot::spot_sell(&base, &size, slippage, yes, fmt).await,
+                    HlSpotAction::Order {
+                        base,
+                        side,
+                        size,
+                        price,
+                    } => commands::spot::spot_order(&base, &side, size, price, fmt).await,
+                    HlSpotAction::Orders => commands::spot::spot_orders(fmt).await,
+                    HlSpotAction::Cancel { base, oid } => {
+                        commands::spot::spot_cancel(&base, oid, fmt).await
+                    }
                     HlSpotAction::Balance => commands::spot::spot_balance(fmt).await,
                     HlSpotAction::Transfer {
                         direction,
                         amount,
                         token,
+                        yes,
                     } => {
-                        commands::spot::spot_transfer(&direction, &amount, token.as_deref(), fmt)
-                            .await
+                        commands::spot::spot_transfer(
+                            &direction,
+                            &amount,
+                            token.as_deref(),
+                            yes,
+                            fmt,
+                        )
+                        .await
                     }
                 },
                 HyperliquidAction::Vault { action } => match action {
+                    HlVaultAction::List {
+                        sort,
+                        min_tvl,
+                        search,
+                        limit,
+                        refresh,
+                    } => {
+                        commands::vault::vault_list(
+                            &sort,
+                            min_tvl,
+                            search.as_deref(),
+                            limit,
+                            refresh,
+                            fmt,
+                        )
+                        .await
+                    }
                     HlVaultAction::Details { vault } => {
                         commands::vault::vault_details(&vault, fmt).await
                     }
@@ -1238,6 +3125,23 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                     } => commands::risk::calculate_offline(
                         &coin, &side, entry, account, stop, leverage, fmt,
                     ),
+                    RiskAction::Suggest {
+                        coin,
+                        side,
+                        entry,
+                        stop,
+                        method,
+                        fraction,
+                        leverage,
+                    } => {
+                        commands::risk::suggest(
+                            &coin, &side, entry, stop, &method, fraction, leverage, fmt,
+                        )
+                        .await
+                    }
+                    RiskAction::Liq { coin, add_size, at } => {
+                        commands::risk::liquidation(&coin, add_size, at, fmt).await
+                    }
                 },
             }
         }
@@ -1255,9 +3159,20 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                     amount,
                     chain,
                     slippage,
+                    gasless,
+                    record,
                 } => {
-                    commands::zero_x::quote(&sell_token, &buy_token, &amount, &chain, slippage, fmt)
-                        .await
+                    commands::zero_x::quote(
+                        &sell_token,
+                        &buy_token,
+                        &amount,
+                        &chain,
+                        slippage,
+                        gasless,
+                        record,
+                        fmt,
+                    )
+                    .await
                 }
                 ZeroXAction::Chains => commands::zero_x::chains(fmt).await,
                 ZeroXAction::Sources { chain } => commands::zero_x::sources(&chain, fmt).await,
@@ -1267,6 +3182,8 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                     amount,
                     chain,
                     slippage,
+                    gasless,
+                    timeout,
                     yes,
                 } => {
                     commands::zero_x::swap(
@@ -1275,6 +3192,8 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                         &amount,
                         &chain,
                         slippage,
+                        gasless,
+                        timeout,
                         yes,
                         fmt,
                     )
@@ -1283,6 +3202,30 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
             }
         }
 
+        Commands::Morpho { action } => {
+            let config = atlas_core::workspace::load_config()?;
+            if !config.modules.morpho.enabled {
+                anyhow::bail!(
+                    "Morpho module is disabled. Run: atlas configure module enable morpho"
+                );
+            }
+            match action {
+                MorphoAction::Markets { chain, limit } => {
+                    commands::morpho::markets(chain.as_deref(), limit, fmt).await
+                }
+                MorphoAction::Market { id, chain } => {
+                    commands::morpho::market(&id, chain.as_deref(), fmt).await
+                }
+                MorphoAction::Position { address, chain } => {
+                    commands::morpho::position(address.as_deref(), chain.as_deref(), fmt).await
+                }
+            }
+        }
+
+        Commands::Paper { action } => match action {
+            PaperAction::Reset { balance } => commands::paper::reset(balance, fmt),
+        },
+
         // ── UTILITIES ───────────────────────────────────────────
         Commands::History { action } => match action {
             HistoryAction::Trades {
@@ -1291,23 +3234,31 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                 from,
                 to,
                 limit,
+                with_notes,
             } => commands::history::run_trades(
                 protocol.as_deref(),
                 coin.as_deref(),
                 from.as_deref(),
                 to.as_deref(),
                 limit,
+                with_notes,
                 fmt,
             ),
             HistoryAction::Orders {
                 protocol,
                 coin,
                 status,
+                stale,
+                from,
+                to,
                 limit,
             } => commands::history::run_orders(
                 protocol.as_deref(),
                 coin.as_deref(),
                 status.as_deref(),
+                stale,
+                from.as_deref(),
+                to.as_deref(),
                 limit,
                 fmt,
             ),
@@ -1323,6 +3274,43 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                 to.as_deref(),
                 fmt,
             ),
+            HistoryAction::Swaps {
+                status,
+                chain,
+                coin,
+                from,
+                to,
+                limit,
+            } => commands::history::run_swaps(
+                status.as_deref(),
+                chain.as_deref(),
+                coin.as_deref(),
+                from.as_deref(),
+                to.as_deref(),
+                limit,
+                fmt,
+            ),
+            HistoryAction::Transfers {
+                kind,
+                from,
+                to,
+                limit,
+            } => commands::history::run_transfers(
+                kind.as_deref(),
+                from.as_deref(),
+                to.as_deref(),
+                limit,
+                fmt,
+            ),
+            HistoryAction::Import {
+                file,
+                protocol,
+                format,
+                dry_run,
+            } => commands::history::run_import(&file, &protocol, &format, dry_run, fmt),
+            HistoryAction::Fills { protocol, limit } => {
+                commands::history::run_fills(protocol.as_deref(), limit, fmt).await
+            }
         },
 
         Commands::Export { action } => match action {
@@ -1333,12 +3321,14 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                 coin,
                 from,
                 to,
+                notes,
             } => commands::export::run_export_trades(
                 protocol.as_deref(),
                 json,
                 coin.as_deref(),
                 from.as_deref(),
                 to.as_deref(),
+                notes,
                 fmt,
             ),
             ExportAction::Pnl {
@@ -1354,6 +3344,187 @@ async fn run(command: Commands, fmt: OutputFormat) -> Result<()> {
                 to.as_deref(),
                 fmt,
             ),
+            ExportAction::Statement {
+                month,
+                protocol,
+                out,
+                format,
+            } => commands::export::run_export_statement(
+                &month,
+                &protocol,
+                out.as_deref(),
+                format == "json",
+                fmt,
+            ),
+        },
+
+        Commands::Schedule { action } => match action {
+            ScheduleAction::Add {
+                command,
+                every,
+                at,
+                max_runs,
+                yes,
+            } => {
+                let command = scheduled_command(&command)?;
+                commands::schedule::add(&command, &every, at.as_deref(), max_runs, yes, fmt)
+            }
+            ScheduleAction::List => commands::schedule::list(fmt),
+            ScheduleAction::Remove { id } => commands::schedule::remove(id, fmt),
+            ScheduleAction::Run { catch_up } => {
+                commands::schedule::run(&catch_up, run_scheduled, fmt).await
+            }
+        },
+
+        Commands::Notify { action } => match action {
+            NotifyAction::Watch { poll_secs } => commands::notify::watch(poll_secs, fmt).await,
+            NotifyAction::Test => commands::notify::test(fmt).await,
+        },
+
+        Commands::Journal { action } => match action {
+            JournalAction::Add {
+                target,
+                note,
+                tags,
+                coin,
+                at,
+            } => commands::journal::add(
+                target.as_deref(),
+                &note,
+                &tags,
+                coin.as_deref(),
+                at.as_deref(),
+                fmt,
+            ),
+            JournalAction::List {
+                coin,
+                tag,
+                from,
+                to,
+                search,
+                limit,
+            } => commands::journal::list(
+                coin.as_deref(),
+                tag.as_deref(),
+                from.as_deref(),
+                to.as_deref(),
+                search.as_deref(),
+                limit,
+                fmt,
+            ),
+        },
+
+        Commands::Audit { action } => match action {
+            AuditAction::List {
+                action_type,
+                from,
+                to,
+                limit,
+            } => commands::audit::list(
+                action_type.as_deref(),
+                from.as_deref(),
+                to.as_deref(),
+                limit,
+                fmt,
+            ),
+            AuditAction::Show { id } => commands::audit::show(id, fmt),
+        },
+
+        Commands::Address { action } => match action {
+            AddressAction::Add {
+                name,
+                address,
+                chain,
+                decimals,
+            } => commands::address::add(&name, &address, &chain, decimals, fmt),
+            AddressAction::List { chain } => commands::address::list(chain.as_deref(), fmt),
+            AddressAction::Remove { name, chain } => commands::address::remove(&name, &chain, fmt),
+        },
+        Commands::Wallet { action } => match action {
+            WalletAction::Allowance {
+                token,
+                spender,
+                chain,
+            } => commands::wallet::allowance(&token, &spender, &chain, fmt).await,
+            WalletAction::Approve {
+                token,
+                amount,
+                spender,
+                chain,
+                yes,
+            } => commands::wallet::approve(&token, &amount, &spender, &chain, yes, fmt).await,
+            WalletAction::Revoke {
+                token,
+                spender,
+                chain,
+                yes,
+            } => commands::wallet::revoke(&token, &spender, &chain, yes, fmt).await,
         },
     }
 }
+
+/// A command line for `atlas schedule add`, checked with the CLI parser and
+/// stored without a leading `atlas`.
+fn scheduled_command(line: &str) -> Result<String> {
+    let line = line.trim();
+    let line = line.strip_prefix("atlas ").unwrap_or(line).trim_start();
+    parse_scheduled(line)?;
+    Ok(line.to_string())
+}
+
+/// Parse a stored schedule command. Global flags in it (`-o json`, …) are
+/// ignored: the executor's own apply.
+fn parse_scheduled(line: &str) -> Result<Commands> {
+    let args = atlas_core::parse::split_command_line(line)?;
+    if args.is_empty() {
+        anyhow::bail!("Scheduled command is empty");
+    }
+    let cli =
+        Cli::try_parse_from(std::iter::once("atlas".to_string()).chain(args)).map_err(|e| {
+            let msg = e.to_string();
+            let first = msg.lines().next().unwrap_or_default();
+            anyhow::anyhow!(
+                "Invalid scheduled command `{line}`: {}",
+                first.trim_start_matches("error: ")
+            )
+        })?;
+    // Jobs run unattended, so only order commands: nothing that moves
+    // funds off the account, touches keys or closes everything at once
+    match cli.command {
+        command @ Commands::Hyperliquid {
+            action:
+                HyperliquidAction::Perp {
+                    action:
+                        HlPerpAction::Buy { .. }
+                        | HlPerpAction::Sell { .. }
+                        | HlPerpAction::Close { .. }
+                        | HlPerpAction::Flip { .. }
+                        | HlPerpAction::Order { .. }
+                        | HlPerpAction::Cancel { .. }
+                        | HlPerpAction::Modify { .. },
+                }
+                | HyperliquidAction::Spot {
+                    action:
+                        HlSpotAction::Buy { .. }
+                        | HlSpotAction::Sell { .. }
+                        | HlSpotAction::Order { .. }
+                        | HlSpotAction::Cancel { .. },
+                },
+        }
+        | command @ Commands::ZeroX {
+            action: ZeroXAction::Swap { .. },
+        } => Ok(command),
+        _ => anyhow::bail!(
+            "Only trading commands can be scheduled: hl perp buy|sell|close|flip|order|cancel|modify, \
+             hl spot buy|sell|order|cancel, zero-x swap"
+        ),
+    }
+}
+
+/// Run a stored schedule command in-process, through [`run`].
+fn run_scheduled(
+    line: String,
+    fmt: OutputFormat,
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>>>> {
+    Box::pin(async move { run(parse_scheduled(&line)?, fmt).await })
+}
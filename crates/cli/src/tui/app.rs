@@ -1,6 +1,8 @@
 use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
 use tui_input::backend::crossterm::EventHandler;
+use tui_input::Input;
 
+use super::keymap::Binding;
 use super::state::{App, SwapFocus, TradeFocus};
 
 /// Actions the TUI can perform in response to input.
@@ -19,6 +21,14 @@ pub enum Action {
     ToggleSwap,
     SubmitTrade,
     SubmitSwap,
+    OpenClosePosition,
+    SubmitClose,
+    CancelCoinOrders,
+    OpenBook,
+    OpenScreenerBook,
+    CycleScreenerSort,
+    BookDepthUp,
+    BookDepthDown,
 }
 
 /// Map a cross-term event to an Action.
@@ -68,6 +78,23 @@ pub fn handle_event(app: &mut App, event: Event) -> Action {
             }
         }
 
+        // ── Close Position Confirmation ────────────────────────────
+        if app.close_popup.visible {
+            match key.code {
+                KeyCode::Esc => {
+                    app.close_popup.visible = false;
+                    return Action::None;
+                }
+                KeyCode::Enter => {
+                    return Action::SubmitClose;
+                }
+                _ => {
+                    app.close_popup.size.handle_event(&event);
+                    return Action::None;
+                }
+            }
+        }
+
         // ── Active Swap Popup ───────────────────────────────────────
         if app.swap_popup.visible {
             match key.code {
@@ -105,57 +132,117 @@ pub fn handle_event(app: &mut App, event: Event) -> Action {
             }
         }
 
+        // ── Book coin input ────────────────────────────────────────
+        if let Some(input) = app.book_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => app.book_input = None,
+                KeyCode::Enter => {
+                    let coin = input.value().to_string();
+                    app.book_input = None;
+                    app.select_coin(&coin);
+                }
+                _ => {
+                    input.handle_event(&event);
+                }
+            }
+            return Action::None;
+        }
+
+        // ── Screener filter box (filters as you type) ──────────────
+        if let Some(input) = app.screener_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => {
+                    app.screener_input = None;
+                    app.set_screener_filter("");
+                }
+                KeyCode::Enter => app.screener_input = None,
+                _ => {
+                    input.handle_event(&event);
+                    let filter = input.value().to_string();
+                    app.set_screener_filter(&filter);
+                }
+            }
+            return Action::None;
+        }
+
         // ── Help overlay ────────────────────────────────────────────
         if app.show_help {
-            return match key.code {
-                KeyCode::Char('?') | KeyCode::Esc => Action::ToggleHelp,
-                _ => Action::None,
+            return if key.code == KeyCode::Esc || app.keymap.matches(Binding::Help, &key) {
+                Action::ToggleHelp
+            } else {
+                Action::None
             };
         }
 
-        match key.code {
-            // ── Quit ────────────────────────────────────────────
-            KeyCode::Char('q') => Action::Quit,
-            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Quit;
+        }
 
-            // ── Refresh ─────────────────────────────────────────
-            KeyCode::Char('r') => Action::Refresh,
+        // ── Tab switching (number keys) ─────────────────────────────
+        if let KeyCode::Char(c @ '1'..='6') = key.code {
+            return Action::Tab(c as usize - '1' as usize);
+        }
 
-            // ── Cancel order (on Orders tab) ────────────────────
-            KeyCode::Char('c') => {
-                if app.tab == 2 {
-                    Action::CancelOrder
-                } else {
-                    Action::None
-                }
-            }
+        // A key may be bound to several actions; the first that applies
+        // on the current tab wins
+        let bindings: Vec<Binding> = app.keymap.bindings_for(&key).collect();
+        bindings
+            .into_iter()
+            .find_map(|binding| binding_action(app, binding))
+            .unwrap_or(Action::None)
+    } else {
+        Action::None
+    }
+}
 
-            // ── Popups ──────────────────────────────────────────
-            KeyCode::Char('t') => Action::ToggleTrade,
-            KeyCode::Char('s') => Action::ToggleSwap,
+/// What `binding` does on the current tab, `None` where it doesn't apply.
+fn binding_action(app: &mut App, binding: Binding) -> Option<Action> {
+    let action = match binding {
+        // ── Close position (Positions tab) / cancel order (Orders tab)
+        Binding::Close => match app.tab {
+            1 => Action::OpenClosePosition,
+            2 => Action::CancelOrder,
+            _ => return None,
+        },
 
-            // ── Tab switching (number keys) ─────────────────────
-            KeyCode::Char('1') => Action::Tab(0),
-            KeyCode::Char('2') => Action::Tab(1),
-            KeyCode::Char('3') => Action::Tab(2),
-            KeyCode::Char('4') => Action::Tab(3),
+        // ── Cancel all orders on selected position's coin ───────
+        Binding::CancelCoin if app.tab == 1 => Action::CancelCoinOrders,
 
-            // ── Tab cycling ─────────────────────────────────────
-            KeyCode::Tab => Action::NextTab,
-            KeyCode::BackTab => Action::PrevTab, // Shift+Tab
-            KeyCode::Char('l') | KeyCode::Right => Action::NextTab,
-            KeyCode::Char('h') | KeyCode::Left => Action::PrevTab,
+        // ── Order book (Book tab / jump from Positions) ─────────
+        Binding::Book if app.tab == 1 => Action::OpenBook,
+        Binding::Search if app.tab == 4 => {
+            app.book_input = Some(Input::default().with_value(app.selected_coin.clone()));
+            Action::None
+        }
+        Binding::DepthUp if app.tab == 4 => Action::BookDepthUp,
+        Binding::DepthDown if app.tab == 4 => Action::BookDepthDown,
 
-            // ── Scroll / Selection ──────────────────────────────
-            KeyCode::Char('j') | KeyCode::Down => Action::ScrollDown,
-            KeyCode::Char('k') | KeyCode::Up => Action::ScrollUp,
+        // ── Screener tab ────────────────────────────────────────
+        Binding::Search if app.tab == 5 => {
+            app.screener_input = Some(Input::default().with_value(app.screener_filter.clone()));
+            Action::None
+        }
+        Binding::Sort if app.tab == 5 => Action::CycleScreenerSort,
+        Binding::Open if app.tab == 5 => Action::OpenScreenerBook,
 
-            // ── Help ────────────────────────────────────────────
-            KeyCode::Char('?') => Action::ToggleHelp,
+        // ── Global ──────────────────────────────────────────────
+        Binding::Quit => Action::Quit,
+        Binding::Refresh => Action::Refresh,
+        Binding::Trade => Action::ToggleTrade,
+        Binding::Swap => Action::ToggleSwap,
+        Binding::Help => Action::ToggleHelp,
+        Binding::NextTab => Action::NextTab,
+        Binding::PrevTab => Action::PrevTab,
+        Binding::Down => Action::ScrollDown,
+        Binding::Up => Action::ScrollUp,
 
-            _ => Action::None,
-        }
-    } else {
-        Action::None
-    }
+        Binding::CancelCoin
+        | Binding::Book
+        | Binding::Search
+        | Binding::DepthUp
+        | Binding::DepthDown
+        | Binding::Sort
+        | Binding::Open => return None,
+    };
+    Some(action)
 }
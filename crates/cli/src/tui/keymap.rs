@@ -0,0 +1,276 @@
+//! Key bindings for `atlas tui`: built-in defaults plus `tui.keys`
+//! overrides from the config.
+//!
+//! Keys inside popups and input boxes (Esc, Enter, Tab between fields),
+//! the tab numbers 1-6 and Ctrl+C are fixed.
+
+use std::collections::BTreeMap;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Something a key can be bound to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Binding {
+    Close,
+    CancelCoin,
+    Book,
+    Search,
+    DepthUp,
+    DepthDown,
+    Sort,
+    Open,
+    Quit,
+    Refresh,
+    Trade,
+    Swap,
+    Help,
+    NextTab,
+    PrevTab,
+    Down,
+    Up,
+}
+
+impl Binding {
+    /// Lookup order. Bindings that only apply on some tabs come before the
+    /// global ones, so a shared key (`s`: sort on the Screener, swap
+    /// elsewhere) goes to the tab-specific action where it applies.
+    pub const ALL: [Binding; 17] = [
+        Binding::Close,
+        Binding::CancelCoin,
+        Binding::Book,
+        Binding::Search,
+        Binding::DepthUp,
+        Binding::DepthDown,
+        Binding::Sort,
+        Binding::Open,
+        Binding::Quit,
+        Binding::Refresh,
+        Binding::Trade,
+        Binding::Swap,
+        Binding::Help,
+        Binding::NextTab,
+        Binding::PrevTab,
+        Binding::Down,
+        Binding::Up,
+    ];
+
+    /// Name under `tui.keys`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Binding::Close => "close",
+            Binding::CancelCoin => "cancel_coin",
+            Binding::Book => "book",
+            Binding::Search => "search",
+            Binding::DepthUp => "depth_up",
+            Binding::DepthDown => "depth_down",
+            Binding::Sort => "sort",
+            Binding::Open => "open",
+            Binding::Quit => "quit",
+            Binding::Refresh => "refresh",
+            Binding::Trade => "trade",
+            Binding::Swap => "swap",
+            Binding::Help => "help",
+            Binding::NextTab => "next_tab",
+            Binding::PrevTab => "prev_tab",
+            Binding::Down => "down",
+            Binding::Up => "up",
+        }
+    }
+
+    /// Help overlay text.
+    pub fn description(self) -> &'static str {
+        match self {
+            Binding::Close => "Close position (Positions) / cancel order (Orders)",
+            Binding::CancelCoin => "Cancel orders on position's coin",
+            Binding::Book => "Open order book for position's coin",
+            Binding::Search => "Change book coin (Book) / filter (Screener)",
+            Binding::DepthUp => "More book depth (Book tab)",
+            Binding::DepthDown => "Less book depth (Book tab)",
+            Binding::Sort => "Cycle sort column (Screener tab)",
+            Binding::Open => "Open book for coin (Screener tab)",
+            Binding::Quit => "Quit (Ctrl+C always works)",
+            Binding::Refresh => "Force refresh (REST)",
+            Binding::Trade => "Trade popup",
+            Binding::Swap => "Swap popup",
+            Binding::Help => "Toggle help",
+            Binding::NextTab => "Next tab",
+            Binding::PrevTab => "Previous tab",
+            Binding::Down => "Scroll down / select next",
+            Binding::Up => "Scroll up / select prev",
+        }
+    }
+
+    /// Shown under Navigation rather than Actions in the help overlay.
+    pub fn is_navigation(self) -> bool {
+        matches!(
+            self,
+            Binding::NextTab | Binding::PrevTab | Binding::Down | Binding::Up
+        )
+    }
+
+    fn default_keys(self) -> &'static str {
+        match self {
+            Binding::Close => "c",
+            Binding::CancelCoin => "x",
+            Binding::Book => "b",
+            Binding::Search => "/",
+            Binding::DepthUp => "+, =",
+            Binding::DepthDown => "-",
+            Binding::Sort => "s",
+            Binding::Open => "enter",
+            Binding::Quit => "q",
+            Binding::Refresh => "r",
+            Binding::Trade => "t",
+            Binding::Swap => "s",
+            Binding::Help => "?",
+            Binding::NextTab => "tab, l, right",
+            Binding::PrevTab => "backtab, h, left",
+            Binding::Down => "j, down",
+            Binding::Up => "k, up",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|b| b.name() == name)
+    }
+}
+
+/// The effective key for every [`Binding`].
+pub struct Keymap {
+    keys: Vec<(Binding, Vec<KeyCode>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).0
+    }
+}
+
+impl Keymap {
+    /// The defaults with `overrides` (action name → comma-separated keys)
+    /// applied. Unknown actions and unparseable keys leave the default in
+    /// place and come back as warnings.
+    pub fn from_config(overrides: &BTreeMap<String, String>) -> (Self, Vec<String>) {
+        let mut warnings = Vec::new();
+        for name in overrides.keys() {
+            if Binding::from_name(name).is_none() {
+                warnings.push(format!("tui.keys: unknown action '{name}'"));
+            }
+        }
+
+        let keys = Binding::ALL
+            .into_iter()
+            .map(|binding| {
+                let default = || parse_keys(binding.default_keys()).expect("valid default keys");
+                let codes = match overrides.get(binding.name()) {
+                    Some(spec) => parse_keys(spec).unwrap_or_else(|bad| {
+                        warnings.push(format!(
+                            "tui.keys.{}: unknown key '{bad}', keeping {}",
+                            binding.name(),
+                            binding.default_keys()
+                        ));
+                        default()
+                    }),
+                    None => default(),
+                };
+                (binding, codes)
+            })
+            .collect();
+        (Self { keys }, warnings)
+    }
+
+    /// Bindings `key` triggers, in [`Binding::ALL`] order.
+    pub fn bindings_for<'a>(&'a self, key: &'a KeyEvent) -> impl Iterator<Item = Binding> + 'a {
+        self.keys
+            .iter()
+            .filter(move |(_, codes)| codes.contains(&key.code))
+            .map(|(binding, _)| *binding)
+    }
+
+    pub fn matches(&self, binding: Binding, key: &KeyEvent) -> bool {
+        self.bindings_for(key).any(|b| b == binding)
+    }
+
+    /// `Tab / l / →` for the help overlay.
+    pub fn label(&self, binding: Binding) -> String {
+        self.keys
+            .iter()
+            .find(|(b, _)| *b == binding)
+            .map(|(_, codes)| {
+                codes
+                    .iter()
+                    .map(|c| key_label(*c))
+                    .collect::<Vec<_>>()
+                    .join(" / ")
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// `"tab, l, right"` → key codes. On failure, the key that didn't parse.
+fn parse_keys(spec: &str) -> Result<Vec<KeyCode>, String> {
+    let codes = spec
+        .split(',')
+        .map(str::trim)
+        .map(|k| parse_key(k).ok_or_else(|| k.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(codes)
+}
+
+/// A single character, or a named key such as `tab`, `enter`, `space`,
+/// `left` or `f5`.
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let code = match name.to_ascii_lowercase().as_str() {
+        "tab" => KeyCode::Tab,
+        "backtab" | "shift-tab" => KeyCode::BackTab,
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "comma" => KeyCode::Char(','),
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        other => {
+            let n: u8 = other.strip_prefix('f')?.parse().ok()?;
+            if !(1..=12).contains(&n) {
+                return None;
+            }
+            KeyCode::F(n)
+        }
+    };
+    Some(code)
+}
+
+fn key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".into(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Tab => "Tab".into(),
+        KeyCode::BackTab => "S-Tab".into(),
+        KeyCode::Enter => "Enter".into(),
+        KeyCode::Esc => "Esc".into(),
+        KeyCode::Backspace => "Bksp".into(),
+        KeyCode::Delete => "Del".into(),
+        KeyCode::Left => "←".into(),
+        KeyCode::Right => "→".into(),
+        KeyCode::Up => "↑".into(),
+        KeyCode::Down => "↓".into(),
+        KeyCode::Home => "Home".into(),
+        KeyCode::End => "End".into(),
+        KeyCode::PageUp => "PgUp".into(),
+        KeyCode::PageDown => "PgDn".into(),
+        KeyCode::F(n) => format!("F{n}"),
+        other => format!("{other:?}"),
+    }
+}
@@ -1,4 +1,5 @@
 mod app;
+mod keymap;
 mod state;
 mod ui;
 
@@ -13,13 +14,13 @@ use crossterm::{
 };
 use futures::StreamExt;
 use hypersdk::hypercore::{
-    types::{Incoming, Subscription},
+    types::{Incoming, Side, Subscription},
     ws::Event as WsEvent,
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 
 use app::Action;
-use state::App;
+use state::{App, TapeRow};
 
 /// `atlas tui` — launch the interactive terminal interface.
 pub async fn run() -> Result<()> {
@@ -63,7 +64,24 @@ async fn run_loop(
     let mut ws = core.websocket();
     ws.subscribe(Subscription::AllMids { dex: None });
 
+    // Coin whose L2Book/Trades channels are currently subscribed.
+    let mut book_sub: Option<String> = None;
+
     loop {
+        // ── Book subscriptions: drop the old coin before adding the new one
+        let wanted = app.book_subscription();
+        if wanted != book_sub {
+            if let Some(old) = book_sub.take() {
+                ws.unsubscribe(Subscription::L2Book { coin: old.clone() });
+                ws.unsubscribe(Subscription::Trades { coin: old });
+            }
+            if let Some(ref coin) = wanted {
+                ws.subscribe(Subscription::L2Book { coin: coin.clone() });
+                ws.subscribe(Subscription::Trades { coin: coin.clone() });
+            }
+            book_sub = wanted;
+        }
+
         // ── Draw ────────────────────────────────────────────────
         terminal.draw(|frame| ui::render(frame, app))?;
 
@@ -71,7 +89,7 @@ async fn run_loop(
         tokio::select! {
             // Poll terminal events (with timeout for ticking)
             term_event = tokio::task::spawn_blocking(|| {
-                if event::poll(Duration::from_millis(200)).unwrap_or(false) {
+                if event::poll(Duration::from_millis(state::TICK_MS)).unwrap_or(false) {
                     event::read().ok()
                 } else {
                     None
@@ -97,13 +115,23 @@ async fn run_loop(
                         Action::ToggleTrade => {
                             app.trade_popup.visible = !app.trade_popup.visible;
                             app.swap_popup.visible = false;
+                            app.close_popup.visible = false;
                         }
                         Action::ToggleSwap => {
                             app.swap_popup.visible = !app.swap_popup.visible;
                             app.trade_popup.visible = false;
+                            app.close_popup.visible = false;
                         }
                         Action::SubmitTrade => app.execute_trade().await,
                         Action::SubmitSwap => app.execute_swap().await,
+                        Action::OpenClosePosition => app.open_close_popup(),
+                        Action::SubmitClose => app.execute_close().await,
+                        Action::CancelCoinOrders => app.cancel_selected_coin_orders().await,
+                        Action::OpenBook => app.open_book_for_selected(),
+                        Action::OpenScreenerBook => app.open_book_for_screener(),
+                        Action::CycleScreenerSort => app.cycle_screener_sort(),
+                        Action::BookDepthUp => app.book_depth_up(),
+                        Action::BookDepthDown => app.book_depth_down(),
                     }
                 }
 
@@ -120,6 +148,35 @@ async fn run_loop(
                     Some(WsEvent::Message(Incoming::AllMids { dex: _, mids })) => {
                         app.on_ws_mids(mids);
                     }
+                    Some(WsEvent::Message(Incoming::L2Book(book))) => {
+                        let bids = book.levels[0].iter().map(|l| (l.px, l.sz)).collect();
+                        let asks = book.levels[1].iter().map(|l| (l.px, l.sz)).collect();
+                        app.on_ws_book(&book.coin, bids, asks);
+                    }
+                    Some(WsEvent::Message(Incoming::Trades(trades))) => {
+                        let Some(coin) = trades.first().map(|t| t.coin.clone()) else {
+                            continue;
+                        };
+                        let prints = trades
+                            .iter()
+                            .map(|t| TapeRow {
+                                time: chrono::DateTime::from_timestamp_millis(t.time as i64)
+                                    .map(|d| {
+                                        d.with_timezone(&chrono::Local)
+                                            .format("%H:%M:%S")
+                                            .to_string()
+                                    })
+                                    .unwrap_or_default(),
+                                side: match t.side {
+                                    Side::Bid => "BUY".to_string(),
+                                    Side::Ask => "SELL".to_string(),
+                                },
+                                price: t.px,
+                                size: t.sz,
+                            })
+                            .collect();
+                        app.on_ws_trades(&coin, prints);
+                    }
                     Some(WsEvent::Connected) => {
                         app.on_ws_connected();
                     }
@@ -132,6 +189,8 @@ async fn run_loop(
                         app.on_ws_disconnected();
                         ws = core.websocket();
                         ws.subscribe(Subscription::AllMids { dex: None });
+                        // Force the book channels to be re-subscribed
+                        book_sub = None;
                     }
                 }
             }
@@ -1,8 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
+use atlas_core::config::{AppConfig, Decimals, TuiConfig};
+use atlas_core::fmt::NumberFormat;
+use atlas_core::types::AssetContext;
 use rust_decimal::Decimal;
 use tui_input::Input;
 
+use super::keymap::Keymap;
+
 #[derive(Default)]
 pub enum TradeFocus {
     #[default]
@@ -54,6 +59,75 @@ pub struct SwapPopup {
     pub status: Option<String>,
 }
 
+/// Confirmation modal for closing the selected position.
+#[derive(Default)]
+pub struct ClosePopup {
+    pub visible: bool,
+    pub coin: String,
+    /// Full signed position size at the time the modal was opened.
+    pub position_size: Decimal,
+    /// Optional partial size — empty means close the full position.
+    pub size: Input,
+    pub status: Option<String>,
+}
+
+/// Smallest / largest number of book levels shown per side.
+const MIN_BOOK_DEPTH: usize = 5;
+const MAX_BOOK_DEPTH: usize = 50;
+/// Number of prints kept in the trade tape.
+const TAPE_LEN: usize = 200;
+/// Main loop poll interval; the tick counters count these.
+pub const TICK_MS: u64 = 200;
+
+/// Column the Screener tab is sorted by (`s` cycles through them).
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScreenerSort {
+    #[default]
+    Volume,
+    Change,
+    Funding,
+    OpenInterest,
+}
+
+impl ScreenerSort {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Volume => Self::Change,
+            Self::Change => Self::Funding,
+            Self::Funding => Self::OpenInterest,
+            Self::OpenInterest => Self::Volume,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Volume => "Volume",
+            Self::Change => "24h %",
+            Self::Funding => "Funding",
+            Self::OpenInterest => "OI",
+        }
+    }
+
+    /// Sort key, highest first; `None` sorts last.
+    fn key(self, ctx: &AssetContext) -> Option<Decimal> {
+        match self {
+            Self::Volume => ctx.volume_24h,
+            Self::Change => ctx.change_24h_pct(),
+            Self::Funding => ctx.funding_rate,
+            Self::OpenInterest => ctx.open_interest_usd(),
+        }
+    }
+}
+
+/// A single print on the trade tape.
+#[derive(Clone)]
+pub struct TapeRow {
+    pub time: String,
+    pub side: String,
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
 /// All data the TUI needs to render — fetched from Hyperliquid via Engine.
 pub struct App {
     /// Active tab index.
@@ -68,6 +142,11 @@ pub struct App {
     pub tick_count: u64,
     /// Ticks between auto-refreshes (200ms per tick → 50 ticks = 10s).
     pub refresh_interval: u64,
+    /// Effective key bindings (`tui.keys` over the defaults).
+    pub keymap: Keymap,
+    /// Number rules for display (`output.*`, prices overridden by
+    /// `tui.price_decimals`).
+    pub numbers: NumberFormat,
 
     // ── Account data ────────────────────────────────────────────
     pub profile_name: String,
@@ -81,6 +160,8 @@ pub struct App {
 
     // ── Positions ───────────────────────────────────────────────
     pub positions: Vec<PositionRow>,
+    /// Selected position index (for close / cancel-coin keybinds).
+    pub selected_position: usize,
 
     // ── Open orders ─────────────────────────────────────────────
     pub open_orders: Vec<OrderRow>,
@@ -94,6 +175,32 @@ pub struct App {
     /// Market token index mapping for spot names (e.g. 1 -> PURR).
     pub spot_map: HashMap<usize, String>,
 
+    // ── Screener ────────────────────────────────────────────────
+    /// Every perp's asset context, refreshed with the REST data.
+    pub screener: Vec<AssetContext>,
+    /// Indices into `screener` after filtering and sorting, in display order.
+    pub screener_view: Vec<usize>,
+    pub screener_sort: ScreenerSort,
+    /// Symbol filter applied to the view (case-insensitive substring).
+    pub screener_filter: String,
+    /// Filter box while it is open (`/` on the Screener tab).
+    pub screener_input: Option<Input>,
+    /// Selected row in `screener_view`.
+    pub selected_screener: usize,
+
+    // ── Order book / trade tape ─────────────────────────────────
+    /// Coin shown on the Book tab; picked from Positions or the Screener.
+    pub selected_coin: String,
+    /// Levels shown per side (adjusted with +/-).
+    pub book_depth: usize,
+    /// Local L2 state (price → size), updated in place from WS messages.
+    pub book_bids: BTreeMap<Decimal, Decimal>,
+    pub book_asks: BTreeMap<Decimal, Decimal>,
+    /// Most recent prints first.
+    pub tape: VecDeque<TapeRow>,
+    /// Coin input for switching the book coin (`/` on the Book tab).
+    pub book_input: Option<Input>,
+
     // ── Connection state ────────────────────────────────────────
     pub connected: bool,
     pub ws_connected: bool,
@@ -105,9 +212,15 @@ pub struct App {
     pub cancel_status: Option<String>,
     pub cancel_status_tick: u64,
 
+    // ── Notifications ───────────────────────────────────────────
+    /// Transient result of the last user action, shown in the status bar.
+    pub notification: Option<String>,
+    pub notification_tick: u64,
+
     // ── Popups ──────────────────────────────────────────────────
     pub trade_popup: TradePopup,
     pub swap_popup: SwapPopup,
+    pub close_popup: ClosePopup,
 }
 
 #[derive(Clone)]
@@ -139,20 +252,31 @@ impl App {
     /// Create a new App and attempt initial data fetch.
     pub async fn new() -> Self {
         let config = atlas_core::workspace::load_config().unwrap_or_default();
-        let profile_name = config.system.active_profile.clone();
+        let profile_name = atlas_core::AuthManager::active_profile(&config).to_string();
         let network = if config.modules.hyperliquid.config.network == "testnet" {
             "Testnet".to_string()
         } else {
             "Mainnet".to_string()
         };
 
+        let (keymap, mut warnings) = Keymap::from_config(&config.tui.keys);
+
         let mut app = Self {
             tab: 0,
-            tabs: vec!["Dashboard", "Positions", "Orders", "Markets"],
+            tabs: vec![
+                "Dashboard",
+                "Positions",
+                "Orders",
+                "Markets",
+                "Book",
+                "Screener",
+            ],
             show_help: false,
             scroll: 0,
             tick_count: 0,
             refresh_interval: 50, // ~10s at 200ms poll
+            keymap,
+            numbers: tui_numbers(&config),
 
             profile_name,
             address: String::from("—"),
@@ -164,12 +288,27 @@ impl App {
             withdrawable: String::from("—"),
 
             positions: Vec::new(),
+            selected_position: 0,
             open_orders: Vec::new(),
             selected_order: 0,
             all_mids: Vec::new(),
             live_mids: HashMap::new(),
             spot_map: HashMap::new(),
 
+            screener: Vec::new(),
+            screener_view: Vec::new(),
+            screener_sort: ScreenerSort::default(),
+            screener_filter: String::new(),
+            screener_input: None,
+            selected_screener: 0,
+
+            selected_coin: String::from("BTC"),
+            book_depth: 10,
+            book_bids: BTreeMap::new(),
+            book_asks: BTreeMap::new(),
+            tape: VecDeque::new(),
+            book_input: None,
+
             connected: false,
             ws_connected: false,
             last_error: None,
@@ -179,14 +318,59 @@ impl App {
             cancel_status: None,
             cancel_status_tick: 0,
 
+            notification: None,
+            notification_tick: 0,
+
             trade_popup: TradePopup::default(),
             swap_popup: SwapPopup::default(),
+            close_popup: ClosePopup::default(),
         };
+        app.apply_config(&config.tui, &mut warnings);
+        if !warnings.is_empty() {
+            for w in &warnings {
+                tracing::warn!("{w}");
+            }
+            app.notification = Some(format!("Config: {}", warnings.join("; ")));
+        }
 
         app.refresh().await;
         app
     }
 
+    /// Start tab and refresh cadence from `tui`; unusable values keep the
+    /// defaults and add a warning.
+    fn apply_config(&mut self, tui: &TuiConfig, warnings: &mut Vec<String>) {
+        let wanted = tui.default_tab.trim();
+        let tab = match wanted.parse::<usize>() {
+            Ok(n) => n.checked_sub(1).filter(|i| *i < self.tabs.len()),
+            Err(_) => self
+                .tabs
+                .iter()
+                .position(|t| t.eq_ignore_ascii_case(wanted)),
+        };
+        match tab {
+            Some(idx) => self.set_tab(idx),
+            None => warnings.push(format!(
+                "tui.default_tab: unknown tab '{wanted}', starting on {}",
+                self.tabs[self.tab]
+            )),
+        }
+
+        if tui.refresh_secs == 0 {
+            warnings.push(format!(
+                "tui.refresh_secs must be at least 1, using {}",
+                self.refresh_interval * TICK_MS / 1000
+            ));
+        } else {
+            self.refresh_interval = (tui.refresh_secs * 1000).div_ceil(TICK_MS);
+        }
+    }
+
+    /// A price by the display rules.
+    pub fn fmt_price(&self, px: Decimal) -> String {
+        self.numbers.price(px, None)
+    }
+
     /// Fetch all data from Hyperliquid. Non-fatal — stores error in state.
     pub async fn refresh(&mut self) {
         match self.fetch_data().await {
@@ -200,6 +384,70 @@ impl App {
                 self.last_error = Some(format!("{e:#}"));
             }
         }
+        // Market data only — works without a signer
+        if let Err(e) = self.fetch_screener().await {
+            self.last_error = Some(format!("Screener: {e:#}"));
+        }
+    }
+
+    async fn fetch_screener(&mut self) -> anyhow::Result<()> {
+        let orch = crate::factory::readonly().await?;
+        let perp = orch.perp(None)?;
+        let ctxs = perp
+            .asset_contexts()
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+        // Keep the selection on the same coin across refreshes
+        let selected = self.selected_screener_coin();
+        self.screener = ctxs;
+        self.screener.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        self.rebuild_screener_view();
+        if let Some(coin) = selected {
+            if let Some(i) = self
+                .screener_view
+                .iter()
+                .position(|&i| self.screener[i].symbol == coin)
+            {
+                self.selected_screener = i;
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-apply the filter and sort to `screener`.
+    pub fn rebuild_screener_view(&mut self) {
+        self.screener_view =
+            screener_view(&self.screener, &self.screener_filter, self.screener_sort);
+        if self.selected_screener >= self.screener_view.len() {
+            self.selected_screener = self.screener_view.len().saturating_sub(1);
+        }
+    }
+
+    pub fn cycle_screener_sort(&mut self) {
+        self.screener_sort = self.screener_sort.next();
+        self.selected_screener = 0;
+        self.rebuild_screener_view();
+    }
+
+    /// Update the filter as the user types in the filter box.
+    pub fn set_screener_filter(&mut self, filter: &str) {
+        self.screener_filter = filter.trim().to_string();
+        self.selected_screener = 0;
+        self.rebuild_screener_view();
+    }
+
+    fn selected_screener_coin(&self) -> Option<String> {
+        let i = *self.screener_view.get(self.selected_screener)?;
+        Some(self.screener[i].symbol.clone())
+    }
+
+    /// Jump to the Book tab for the coin selected in the Screener.
+    pub fn open_book_for_screener(&mut self) {
+        if let Some(coin) = self.selected_screener_coin() {
+            self.select_coin(&coin);
+            self.set_tab(4);
+        }
     }
 
     async fn fetch_data(&mut self) -> anyhow::Result<()> {
@@ -242,13 +490,13 @@ impl App {
                     size_dec: p.szi,
                     entry_px: p
                         .entry_px
-                        .map(|e| format!("{}", e))
+                        .map(|e| self.fmt_price(e))
                         .unwrap_or_else(|| "—".into()),
                     entry_px_dec: p.entry_px,
                     mark_px: String::from("—"), // updated below with all_mids
                     liq_px: p
                         .liquidation_px
-                        .map(|e| format!("{}", e))
+                        .map(|e| self.fmt_price(e))
                         .unwrap_or_else(|| "—".into()),
                     upnl: format!("{}", p.unrealized_pnl),
                     roe: format!("{}", p.return_on_equity),
@@ -258,6 +506,11 @@ impl App {
             })
             .collect();
 
+        // Clamp selected position
+        if self.selected_position >= self.positions.len() {
+            self.selected_position = self.positions.len().saturating_sub(1);
+        }
+
         // Open orders — hypersdk returns BasicOrder with Decimal fields
         let orders = client
             .open_orders(address, None)
@@ -275,7 +528,7 @@ impl App {
                     coin: o.coin.clone(),
                     side: side_str,
                     size: format!("{}", o.sz),
-                    price: format!("{}", o.limit_px),
+                    price: self.fmt_price(o.limit_px),
                     oid: o.oid,
                     order_type: "Limit".to_string(),
                 }
@@ -320,7 +573,7 @@ impl App {
         // Sort mids alphabetically for display
         let mut mids_vec: Vec<(String, String)> = resolved_mids
             .into_iter()
-            .map(|(k, v)| (k, format!("{}", v)))
+            .map(|(k, v)| (k, self.fmt_price(v)))
             .collect();
         mids_vec.sort_by(|a, b| a.0.cmp(&b.0));
         self.all_mids = mids_vec;
@@ -332,7 +585,7 @@ impl App {
     pub fn update_positions_from_mids(&mut self) {
         for pos in &mut self.positions {
             if let Some(mid) = self.live_mids.get(&pos.coin) {
-                pos.mark_px = format!("{}", mid);
+                pos.mark_px = self.numbers.price(*mid, None);
 
                 // Recalculate unrealized PnL: (mark - entry) * size
                 if let Some(entry) = pos.entry_px_dec {
@@ -378,12 +631,80 @@ impl App {
         let mut mids_vec: Vec<(String, String)> = self
             .live_mids
             .iter()
-            .map(|(k, v)| (k.clone(), format!("{}", v)))
+            .map(|(k, v)| (k.clone(), self.fmt_price(*v)))
             .collect();
         mids_vec.sort_by(|a, b| a.0.cmp(&b.0));
         self.all_mids = mids_vec;
     }
 
+    /// Coin whose `L2Book`/`Trades` channels should currently be subscribed.
+    ///
+    /// Only the Book tab needs them, so switching away drops the subscription.
+    pub fn book_subscription(&self) -> Option<String> {
+        (self.tab == 4).then(|| self.selected_coin.clone())
+    }
+
+    /// Switch the Book tab to another coin and clear the stale book/tape.
+    pub fn select_coin(&mut self, coin: &str) {
+        let coin = coin.trim().to_uppercase();
+        if coin.is_empty() || coin == self.selected_coin {
+            return;
+        }
+        self.selected_coin = coin;
+        self.book_bids.clear();
+        self.book_asks.clear();
+        self.tape.clear();
+    }
+
+    /// Apply a WebSocket `L2Book` message to the local book.
+    ///
+    /// Only levels that changed are touched: prices absent from the update are
+    /// dropped and new or resized levels are upserted.
+    pub fn on_ws_book(
+        &mut self,
+        coin: &str,
+        bids: Vec<(Decimal, Decimal)>,
+        asks: Vec<(Decimal, Decimal)>,
+    ) {
+        if !coin.eq_ignore_ascii_case(&self.selected_coin) {
+            return;
+        }
+        apply_levels(&mut self.book_bids, bids);
+        apply_levels(&mut self.book_asks, asks);
+        self.last_ws_update = chrono::Local::now().format("%H:%M:%S").to_string();
+    }
+
+    /// Push WebSocket `Trades` prints onto the tape (newest first).
+    pub fn on_ws_trades(&mut self, coin: &str, prints: Vec<TapeRow>) {
+        if !coin.eq_ignore_ascii_case(&self.selected_coin) {
+            return;
+        }
+        for row in prints {
+            self.tape.push_front(row);
+        }
+        self.tape.truncate(TAPE_LEN);
+    }
+
+    pub fn book_depth_up(&mut self) {
+        self.book_depth = (self.book_depth + 5).min(MAX_BOOK_DEPTH);
+    }
+
+    pub fn book_depth_down(&mut self) {
+        self.book_depth = self.book_depth.saturating_sub(5).max(MIN_BOOK_DEPTH);
+    }
+
+    /// Jump to the Book tab for the selected position's coin.
+    pub fn open_book_for_selected(&mut self) {
+        if let Some(coin) = self
+            .positions
+            .get(self.selected_position)
+            .map(|p| p.coin.clone())
+        {
+            self.select_coin(&coin);
+        }
+        self.set_tab(4);
+    }
+
     /// Handle WebSocket connected event.
     pub fn on_ws_connected(&mut self) {
         self.ws_connected = true;
@@ -430,6 +751,112 @@ impl App {
         Ok(())
     }
 
+    /// Show a transient notification in the status bar.
+    pub fn notify(&mut self, msg: impl Into<String>) {
+        self.notification = Some(msg.into());
+        self.notification_tick = self.tick_count;
+    }
+
+    /// Open the close-position confirmation modal for the selected row.
+    pub fn open_close_popup(&mut self) {
+        let Some(pos) = self.positions.get(self.selected_position) else {
+            self.notify("No position selected");
+            return;
+        };
+
+        self.close_popup = ClosePopup {
+            visible: true,
+            coin: pos.coin.clone(),
+            position_size: pos.size_dec,
+            size: Input::default(),
+            status: None,
+        };
+        self.trade_popup.visible = false;
+        self.swap_popup.visible = false;
+    }
+
+    /// Close the position from the confirmation modal (full or partial).
+    pub async fn execute_close(&mut self) {
+        let coin = self.close_popup.coin.clone();
+        let size_str = self.close_popup.size.value().trim().to_string();
+
+        let size = if size_str.is_empty() {
+            None
+        } else {
+            match Decimal::from_str_exact(&size_str) {
+                Ok(s) if s > Decimal::ZERO && s <= self.close_popup.position_size.abs() => Some(s),
+                Ok(_) => {
+                    self.close_popup.status = Some(format!(
+                        "Size must be between 0 and {}",
+                        self.close_popup.position_size.abs()
+                    ));
+                    return;
+                }
+                Err(_) => {
+                    self.close_popup.status = Some("Invalid size format".into());
+                    return;
+                }
+            }
+        };
+
+        self.close_popup.status = Some("Closing...".into());
+        match self.do_close(&coin, size).await {
+            Ok(()) => {
+                self.close_popup.visible = false;
+                let what = size
+                    .map(|s| format!("{s}"))
+                    .unwrap_or_else(|| "full position".into());
+                self.notify(format!("Closed {what} on {coin}"));
+                self.refresh().await;
+            }
+            Err(e) => {
+                self.close_popup.status = Some(format!("Error: {e}"));
+                self.notify(format!("Close {coin} failed: {e:#}"));
+            }
+        }
+    }
+
+    async fn do_close(&self, coin: &str, size: Option<Decimal>) -> anyhow::Result<()> {
+        let orch = crate::factory::from_active_profile().await?;
+        let perp = orch.perp(None).map_err(|e| anyhow::anyhow!("{e}"))?;
+        perp.close_position(coin, size, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+        Ok(())
+    }
+
+    /// Cancel all open orders on the selected position's coin.
+    pub async fn cancel_selected_coin_orders(&mut self) {
+        let Some(coin) = self
+            .positions
+            .get(self.selected_position)
+            .map(|p| p.coin.clone())
+        else {
+            self.notify("No position selected");
+            return;
+        };
+
+        match self.do_cancel_all(&coin).await {
+            Ok(0) => self.notify(format!("No open orders on {coin}")),
+            Ok(n) => {
+                self.notify(format!("Cancelled {n} order(s) on {coin}"));
+                self.open_orders.retain(|o| o.coin != coin);
+                if self.selected_order >= self.open_orders.len() {
+                    self.selected_order = self.open_orders.len().saturating_sub(1);
+                }
+            }
+            Err(e) => self.notify(format!("Cancel {coin} orders failed: {e:#}")),
+        }
+    }
+
+    async fn do_cancel_all(&self, coin: &str) -> anyhow::Result<u32> {
+        let orch = crate::factory::from_active_profile().await?;
+        let perp = orch.perp(None).map_err(|e| anyhow::anyhow!("{e}"))?;
+        perp.cancel_all(coin)
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))
+    }
+
     pub fn set_tab(&mut self, idx: usize) {
         if idx < self.tabs.len() {
             self.tab = idx;
@@ -452,20 +879,37 @@ impl App {
     }
 
     pub fn scroll_up(&mut self) {
-        if self.tab == 2 {
+        if self.tab == 1 {
+            // Positions tab — move selection
+            self.selected_position = self.selected_position.saturating_sub(1);
+        } else if self.tab == 2 {
             // Orders tab — move selection
             self.selected_order = self.selected_order.saturating_sub(1);
+        } else if self.tab == 5 {
+            // Screener tab — move selection
+            self.selected_screener = self.selected_screener.saturating_sub(1);
         } else {
             self.scroll = self.scroll.saturating_sub(1);
         }
     }
 
     pub fn scroll_down(&mut self) {
-        if self.tab == 2 {
+        if self.tab == 1 {
+            // Positions tab — move selection
+            if !self.positions.is_empty() {
+                self.selected_position = (self.selected_position + 1).min(self.positions.len() - 1);
+            }
+        } else if self.tab == 2 {
             // Orders tab — move selection
             if !self.open_orders.is_empty() {
                 self.selected_order = (self.selected_order + 1).min(self.open_orders.len() - 1);
             }
+        } else if self.tab == 5 {
+            // Screener tab — move selection
+            if !self.screener_view.is_empty() {
+                self.selected_screener =
+                    (self.selected_screener + 1).min(self.screener_view.len() - 1);
+            }
         } else {
             self.scroll = self.scroll.saturating_add(1);
         }
@@ -483,6 +927,12 @@ impl App {
         {
             self.cancel_status = None;
         }
+        // Clear notifications after ~5 seconds (25 ticks at 200ms)
+        if self.notification.is_some()
+            && self.tick_count.saturating_sub(self.notification_tick) > 25
+        {
+            self.notification = None;
+        }
     }
 
     /// Check if it's time for auto-refresh (full REST refresh for account data).
@@ -539,7 +989,7 @@ impl App {
     ) -> anyhow::Result<()> {
         let orch = crate::factory::from_active_profile().await?;
         let perp = orch.perp(None).map_err(|e| anyhow::anyhow!("{e}"))?;
-        perp.limit_order(coin, side, size, price, false)
+        perp.limit_order(coin, side, size, price, &Default::default())
             .await
             .map_err(|e| anyhow::anyhow!("{e}"))?;
         Ok(())
@@ -569,6 +1019,9 @@ impl App {
         buy_token: &str,
         amount: &str,
     ) -> anyhow::Result<String> {
+        let chain = atlas_core::types::Chain::Arbitrum;
+        // Token fields take address-book names as well as addresses
+        let (sell, buy) = crate::commands::zero_x::resolve_pair(&chain, sell_token, buy_token)?;
         let orch = crate::factory::from_active_profile().await?;
         let swap_mod = orch.swap(None).map_err(|e| anyhow::anyhow!("{e}"))?;
 
@@ -580,9 +1033,9 @@ impl App {
         let taker = zerox.taker_address().unwrap_or_default();
         let price_resp = zerox
             .price(
-                &atlas_core::types::Chain::Arbitrum,
-                sell_token,
-                buy_token,
+                &chain,
+                &sell.address,
+                &buy.address,
                 amount,
                 Some(&taker),
                 Some(50),
@@ -590,19 +1043,23 @@ impl App {
             .await
             .map_err(|e| anyhow::anyhow!("{e}"))?;
 
-        if !price_resp.liquidity_available {
-            anyhow::bail!("No liquidity");
-        }
-
         let sell_dec: rust_decimal::Decimal = amount
             .parse()
             .map_err(|_| anyhow::anyhow!("Invalid amount: {amount}"))?;
+        let recorded = crate::commands::zero_x::quote_output(
+            &chain,
+            &sell,
+            &buy,
+            amount,
+            50,
+            price_resp.clone(),
+        );
 
         let quote = atlas_core::types::SwapQuote {
             protocol: atlas_core::types::Protocol::ZeroX,
-            chain: atlas_core::types::Chain::Arbitrum,
-            sell_token: sell_token.to_string(),
-            buy_token: buy_token.to_string(),
+            chain,
+            sell_token: sell.address,
+            buy_token: buy.address,
             sell_amount: sell_dec,
             buy_amount: price_resp
                 .buy_amount
@@ -619,6 +1076,127 @@ impl App {
             .swap(&quote)
             .await
             .map_err(|e| anyhow::anyhow!("{e}"))?;
+        if let Ok(recorded) = recorded {
+            crate::commands::zero_x::record_swap(&recorded, Some("executed"), Some(&tx_hash));
+        }
         Ok(tx_hash)
     }
 }
+
+/// Descending order with `None` after every value.
+/// Indices of the markets whose symbol contains `filter` (any case), sorted
+/// by `sort`.
+///
+/// The sort is stable over the alphabetical base order, so ties (and
+/// markets missing the sorted field, which go last) stay alphabetical.
+fn screener_view(screener: &[AssetContext], filter: &str, sort: ScreenerSort) -> Vec<usize> {
+    let filter = filter.to_uppercase();
+    let mut view: Vec<usize> = (0..screener.len())
+        .filter(|&i| screener[i].symbol.to_uppercase().contains(&filter))
+        .collect();
+    view.sort_by(|&a, &b| desc_none_last(sort.key(&screener[a]), sort.key(&screener[b])));
+    view
+}
+
+fn desc_none_last(a: Option<Decimal>, b: Option<Decimal>) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    match (a, b) {
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Reconcile one side of the local book with an incoming level set.
+fn apply_levels(side: &mut BTreeMap<Decimal, Decimal>, levels: Vec<(Decimal, Decimal)>) {
+    let incoming: HashMap<Decimal, Decimal> = levels.into_iter().collect();
+    side.retain(|px, _| incoming.contains_key(px));
+    for (px, sz) in incoming {
+        if sz.is_zero() {
+            side.remove(&px);
+        } else if side.get(&px) != Some(&sz) {
+            side.insert(px, sz);
+        }
+    }
+}
+
+/// `output.*`, with `tui.price_decimals` taking precedence for prices.
+fn tui_numbers(config: &AppConfig) -> NumberFormat {
+    let mut numbers = NumberFormat::from(&config.output);
+    if let Some(dp) = config.tui.price_decimals {
+        numbers.price = Decimals::Fixed(dp);
+    }
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atlas_core::types::Protocol;
+    use std::cmp::Ordering;
+
+    fn market(symbol: &str, volume: Option<i64>, funding: Option<&str>) -> AssetContext {
+        AssetContext {
+            symbol: symbol.into(),
+            protocol: Protocol::Hyperliquid,
+            mark_price: None,
+            mid_price: None,
+            oracle_price: None,
+            funding_rate: funding.map(|f| f.parse().unwrap()),
+            funding_interval_hours: 1,
+            premium: None,
+            open_interest: None,
+            volume_24h: volume.map(Decimal::from),
+            volume_24h_base: None,
+            prev_day_price: None,
+            impact_bid: None,
+            impact_ask: None,
+        }
+    }
+
+    fn symbols(screener: &[AssetContext], view: &[usize]) -> Vec<String> {
+        view.iter().map(|&i| screener[i].symbol.clone()).collect()
+    }
+
+    #[test]
+    fn test_desc_none_last() {
+        let (one, two) = (Some(Decimal::ONE), Some(Decimal::TWO));
+        assert_eq!(desc_none_last(two, one), Ordering::Less);
+        assert_eq!(desc_none_last(one, two), Ordering::Greater);
+        assert_eq!(desc_none_last(one, one), Ordering::Equal);
+        assert_eq!(desc_none_last(one, None), Ordering::Less);
+        assert_eq!(desc_none_last(None, one), Ordering::Greater);
+        assert_eq!(desc_none_last(None, None), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_screener_view_sorts_filters_and_keeps_ties_alphabetical() {
+        // Alphabetical, as fetch_screener leaves it
+        let screener = vec![
+            market("ARB", Some(50), None),
+            market("BTC", Some(900), Some("0.0001")),
+            market("DOGE", None, Some("-0.0002")),
+            market("ETH", Some(500), Some("0.0001")),
+            market("SOL", Some(500), None),
+            market("kPEPE", None, None),
+        ];
+
+        let view = screener_view(&screener, "", ScreenerSort::Volume);
+        assert_eq!(
+            symbols(&screener, &view),
+            ["BTC", "ETH", "SOL", "ARB", "DOGE", "kPEPE"]
+        );
+
+        let view = screener_view(&screener, "", ScreenerSort::Funding);
+        assert_eq!(
+            symbols(&screener, &view),
+            ["BTC", "ETH", "DOGE", "ARB", "SOL", "kPEPE"]
+        );
+
+        // Case-insensitive substring match, still sorted
+        let view = screener_view(&screener, "e", ScreenerSort::Volume);
+        assert_eq!(symbols(&screener, &view), ["ETH", "DOGE", "kPEPE"]);
+        assert!(screener_view(&screener, "XYZ", ScreenerSort::Volume).is_empty());
+    }
+}
@@ -8,7 +8,8 @@ use ratatui::{
 
 use atlas_core::fmt::{self, Sign};
 
-use super::state::App;
+use super::keymap::Binding;
+use super::state::{App, ScreenerSort, TICK_MS};
 
 // ─── Color palette ──────────────────────────────────────────────────
 
@@ -45,6 +46,8 @@ pub fn render(frame: &mut Frame, app: &App) {
         1 => render_positions(frame, app, root[2]),
         2 => render_orders(frame, app, root[2]),
         3 => render_markets(frame, app, root[2]),
+        4 => render_book(frame, app, root[2]),
+        5 => render_screener(frame, app, root[2]),
         _ => {}
     }
 
@@ -52,7 +55,7 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     // Help overlay on top
     if app.show_help {
-        render_help(frame, area);
+        render_help(frame, app, area);
     }
 
     // Trade & Swap modals
@@ -63,6 +66,10 @@ pub fn render(frame: &mut Frame, app: &App) {
     if app.swap_popup.visible {
         render_swap_popup(frame, app, area);
     }
+
+    if app.close_popup.visible {
+        render_close_popup(frame, app, area);
+    }
 }
 
 // ─── Header ─────────────────────────────────────────────────────────
@@ -170,12 +177,12 @@ fn render_account_card(frame: &mut Frame, app: &App, area: Rect) {
         .border_style(Style::default().fg(DIM));
 
     // Calculate total uPnL from positions
-    let total_upnl: f64 = app
+    let total_upnl: rust_decimal::Decimal = app
         .positions
         .iter()
-        .filter_map(|p| p.upnl.parse::<f64>().ok())
+        .filter_map(|p| p.upnl.parse::<rust_decimal::Decimal>().ok())
         .sum();
-    let upnl_str = format!("{:.2}", total_upnl);
+    let upnl_str = app.numbers.usd(total_upnl);
     let upnl_color = color_for_value(&upnl_str);
 
     let pnl_color = color_for_value(&app.total_ntl_pos);
@@ -184,44 +191,35 @@ fn render_account_card(frame: &mut Frame, app: &App, area: Rect) {
         Line::from(vec![
             Span::styled(" Account Value  ", Style::default().fg(DIM)),
             Span::styled(
-                fmt::format_usd(&app.account_value),
+                usd(app, &app.account_value),
                 Style::default().fg(WHITE).bold(),
             ),
         ]),
         Line::from(vec![
             Span::styled(" Unrealized PnL ", Style::default().fg(DIM)),
             Span::styled(
-                fmt::format_usd_full(&upnl_str),
+                format!("${upnl_str}"),
                 Style::default().fg(upnl_color).bold(),
             ),
         ]),
         Line::from(vec![
             Span::styled(" Margin Used    ", Style::default().fg(DIM)),
             Span::styled(
-                fmt::format_usd(&app.total_margin_used),
+                usd(app, &app.total_margin_used),
                 Style::default().fg(YELLOW),
             ),
         ]),
         Line::from(vec![
             Span::styled(" Net Position   ", Style::default().fg(DIM)),
-            Span::styled(
-                fmt::format_usd(&app.total_ntl_pos),
-                Style::default().fg(pnl_color),
-            ),
+            Span::styled(usd(app, &app.total_ntl_pos), Style::default().fg(pnl_color)),
         ]),
         Line::from(vec![
             Span::styled(" Raw USD        ", Style::default().fg(DIM)),
-            Span::styled(
-                fmt::format_usd(&app.total_raw_usd),
-                Style::default().fg(WHITE),
-            ),
+            Span::styled(usd(app, &app.total_raw_usd), Style::default().fg(WHITE)),
         ]),
         Line::from(vec![
             Span::styled(" Withdrawable   ", Style::default().fg(DIM)),
-            Span::styled(
-                fmt::format_usd(&app.withdrawable),
-                Style::default().fg(GREEN),
-            ),
+            Span::styled(usd(app, &app.withdrawable), Style::default().fg(GREEN)),
         ]),
         Line::raw(""),
         Line::from(vec![
@@ -276,10 +274,11 @@ fn render_quick_positions(frame: &mut Frame, app: &App, area: Rect) {
             let side_color = if p.size.starts_with('-') { RED } else { GREEN };
             Row::new(vec![
                 Cell::from(p.coin.clone()).style(Style::default().fg(WHITE).bold()),
-                Cell::from(p.size.clone()).style(Style::default().fg(side_color)),
-                Cell::from(fmt::truncate_number(&p.entry_px)),
-                Cell::from(fmt::truncate_number(&p.mark_px)).style(Style::default().fg(YELLOW)),
-                Cell::from(fmt::truncate_number(&p.upnl))
+                Cell::from(app.numbers.size_str(&p.size, None))
+                    .style(Style::default().fg(side_color)),
+                Cell::from(p.entry_px.clone()),
+                Cell::from(p.mark_px.clone()).style(Style::default().fg(YELLOW)),
+                Cell::from(app.numbers.usd_str(&p.upnl))
                     .style(Style::default().fg(pnl_color).bold()),
                 Cell::from(fmt::format_pct(&p.roe)).style(Style::default().fg(roe_color)),
             ])
@@ -330,7 +329,7 @@ fn render_top_markets(frame: &mut Frame, app: &App, area: Rect) {
         .map(|(coin, mid)| {
             Row::new(vec![
                 Cell::from(coin.clone()).style(Style::default().fg(WHITE)),
-                Cell::from(fmt::truncate_number(mid)).style(Style::default().fg(GREEN)),
+                Cell::from(mid.clone()).style(Style::default().fg(GREEN)),
             ])
         })
         .collect();
@@ -346,7 +345,10 @@ fn render_top_markets(frame: &mut Frame, app: &App, area: Rect) {
 
 fn render_positions(frame: &mut Frame, app: &App, area: Rect) {
     let block = Block::default()
-        .title(" Positions — Live PnL ")
+        .title(format!(
+            " Positions ({}) — Live PnL — c: close  x: cancel coin orders ",
+            app.positions.len()
+        ))
         .title_style(Style::default().fg(GREEN).bold())
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
@@ -363,7 +365,7 @@ fn render_positions(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     let header = Row::new(vec![
-        "Coin", "Size", "Entry", "Mark", "Liq", "uPnL", "ROE%", "Lev", "Margin",
+        "", "Coin", "Size", "Entry", "Mark", "Liq", "uPnL", "ROE%", "Lev", "Margin",
     ])
     .style(Style::default().fg(ACCENT).bold())
     .bottom_margin(0);
@@ -371,29 +373,41 @@ fn render_positions(frame: &mut Frame, app: &App, area: Rect) {
     let rows: Vec<Row> = app
         .positions
         .iter()
-        .map(|p| {
+        .enumerate()
+        .map(|(i, p)| {
+            let is_selected = i == app.selected_position;
             let pnl_color = color_for_value(&p.upnl);
             let roe_color = color_for_value(&p.roe);
             let side_color = if p.size.starts_with('-') { RED } else { GREEN };
+            let indicator = if is_selected { "►" } else { " " };
+            let row_style = if is_selected {
+                Style::default().fg(pnl_color).bg(BG_SELECTED)
+            } else {
+                Style::default().fg(pnl_color)
+            };
 
             Row::new(vec![
+                Cell::from(indicator).style(Style::default().fg(ACCENT).bold()),
                 Cell::from(p.coin.clone()).style(Style::default().fg(WHITE).bold()),
-                Cell::from(p.size.clone()).style(Style::default().fg(side_color)),
-                Cell::from(fmt::truncate_number(&p.entry_px)),
-                Cell::from(fmt::truncate_number(&p.mark_px)).style(Style::default().fg(YELLOW)),
-                Cell::from(fmt::truncate_number(&p.liq_px)).style(Style::default().fg(RED)),
-                Cell::from(fmt::truncate_number(&p.upnl))
+                Cell::from(app.numbers.size_str(&p.size, None))
+                    .style(Style::default().fg(side_color)),
+                Cell::from(p.entry_px.clone()),
+                Cell::from(p.mark_px.clone()).style(Style::default().fg(YELLOW)),
+                Cell::from(p.liq_px.clone()).style(Style::default().fg(RED)),
+                Cell::from(app.numbers.usd_str(&p.upnl))
                     .style(Style::default().fg(pnl_color).bold()),
                 Cell::from(fmt::format_pct(&p.roe)).style(Style::default().fg(roe_color).bold()),
                 Cell::from(p.leverage.clone()).style(Style::default().fg(YELLOW)),
-                Cell::from(fmt::truncate_number(&p.margin_used)),
+                Cell::from(app.numbers.usd_str(&p.margin_used)),
             ])
+            .style(row_style)
         })
         .collect();
 
     let table = Table::new(
         rows,
         [
+            Constraint::Length(2),  // Indicator
             Constraint::Length(8),  // Coin
             Constraint::Length(12), // Size
             Constraint::Length(12), // Entry
@@ -461,8 +475,8 @@ fn render_orders(frame: &mut Frame, app: &App, area: Rect) {
                 Cell::from(indicator).style(Style::default().fg(ACCENT).bold()),
                 Cell::from(o.coin.clone()).style(Style::default().fg(WHITE).bold()),
                 Cell::from(o.side.clone()).style(Style::default().fg(side_color).bold()),
-                Cell::from(o.size.clone()),
-                Cell::from(fmt::truncate_number(&o.price)),
+                Cell::from(app.numbers.size_str(&o.size, None)),
+                Cell::from(o.price.clone()),
                 Cell::from(o.order_type.clone()).style(Style::default().fg(DIM)),
                 Cell::from(format!("{}", o.oid)).style(Style::default().fg(DIM)),
             ])
@@ -519,7 +533,7 @@ fn render_markets(frame: &mut Frame, app: &App, area: Rect) {
             Row::new(vec![
                 Cell::from(format!("{}", i + 1)).style(Style::default().fg(DIM)),
                 Cell::from(coin.clone()).style(Style::default().fg(WHITE).bold()),
-                Cell::from(fmt::truncate_number(mid)).style(Style::default().fg(GREEN)),
+                Cell::from(mid.clone()).style(Style::default().fg(GREEN)),
             ])
         })
         .collect();
@@ -538,6 +552,310 @@ fn render_markets(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(table, area);
 }
 
+// ─── Tab 5: Order book + trade tape (live from WebSocket) ───────────
+
+/// Width (in cells) of the depth bar at the largest visible level.
+const BOOK_BAR_WIDTH: usize = 14;
+
+fn render_book(frame: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(35), // Bids
+            Constraint::Percentage(35), // Asks
+            Constraint::Percentage(30), // Tape
+        ])
+        .split(area);
+
+    // Best levels first: bids descending, asks ascending
+    let bids: Vec<_> = app.book_bids.iter().rev().take(app.book_depth).collect();
+    let asks: Vec<_> = app.book_asks.iter().take(app.book_depth).collect();
+    let max_sz = bids
+        .iter()
+        .chain(asks.iter())
+        .map(|(_, sz)| **sz)
+        .max()
+        .unwrap_or_default();
+
+    let spread = match (bids.first(), asks.first()) {
+        (Some((bid, _)), Some((ask, _))) => format!("  spread {}", *ask - *bid),
+        _ => String::new(),
+    };
+    let title = match app.book_input {
+        Some(ref input) => format!(" Coin: {}▏ ", input.value()),
+        None => format!(
+            " {} Bids — depth {}{} ",
+            app.selected_coin, app.book_depth, spread
+        ),
+    };
+
+    render_book_side(frame, &title, &bids, max_sz, GREEN, true, chunks[0]);
+    render_book_side(
+        frame,
+        " Asks — +/-: depth  /: coin ",
+        &asks,
+        max_sz,
+        RED,
+        false,
+        chunks[1],
+    );
+    render_tape(frame, app, chunks[2]);
+}
+
+fn render_book_side(
+    frame: &mut Frame,
+    title: &str,
+    levels: &[(&rust_decimal::Decimal, &rust_decimal::Decimal)],
+    max_sz: rust_decimal::Decimal,
+    color: Color,
+    is_bid: bool,
+    area: Rect,
+) {
+    let block = Block::default()
+        .title(title.to_string())
+        .title_style(Style::default().fg(color).bold())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(DIM));
+
+    if levels.is_empty() {
+        let p = Paragraph::new("\n  Waiting for book...")
+            .style(Style::default().fg(DIM))
+            .block(block);
+        frame.render_widget(p, area);
+        return;
+    }
+
+    let rows: Vec<Row> = levels
+        .iter()
+        .map(|(px, sz)| {
+            let bar = depth_bar(**sz, max_sz);
+            let cells = if is_bid {
+                vec![
+                    Cell::from(format!("{bar:>BOOK_BAR_WIDTH$}")).style(Style::default().fg(color)),
+                    Cell::from(fmt::truncate_number(&sz.to_string())),
+                    Cell::from(px.to_string()).style(Style::default().fg(color).bold()),
+                ]
+            } else {
+                vec![
+                    Cell::from(px.to_string()).style(Style::default().fg(color).bold()),
+                    Cell::from(fmt::truncate_number(&sz.to_string())),
+                    Cell::from(bar).style(Style::default().fg(color)),
+                ]
+            };
+            Row::new(cells)
+        })
+        .collect();
+
+    let widths = if is_bid {
+        [
+            Constraint::Length(BOOK_BAR_WIDTH as u16),
+            Constraint::Length(12),
+            Constraint::Min(10),
+        ]
+    } else {
+        [
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Min(BOOK_BAR_WIDTH as u16),
+        ]
+    };
+    let header = if is_bid {
+        Row::new(vec!["", "Size", "Bid"])
+    } else {
+        Row::new(vec!["Ask", "Size", ""])
+    }
+    .style(Style::default().fg(ACCENT).bold());
+
+    let table = Table::new(rows, widths).header(header).block(block);
+    frame.render_widget(table, area);
+}
+
+fn render_tape(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(format!(" {} Trades ", app.selected_coin))
+        .title_style(Style::default().fg(YELLOW).bold())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(DIM));
+
+    if app.tape.is_empty() {
+        let p = Paragraph::new("\n  Waiting for trades...")
+            .style(Style::default().fg(DIM))
+            .block(block);
+        frame.render_widget(p, area);
+        return;
+    }
+
+    let max_rows = (area.height as usize).saturating_sub(3);
+    let rows: Vec<Row> = app
+        .tape
+        .iter()
+        .take(max_rows)
+        .map(|t| {
+            let color = if t.side == "BUY" { GREEN } else { RED };
+            Row::new(vec![
+                Cell::from(t.time.clone()).style(Style::default().fg(DIM)),
+                Cell::from(app.numbers.price(t.price, None))
+                    .style(Style::default().fg(color).bold()),
+                Cell::from(app.numbers.size(t.size, None)).style(Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let header = Row::new(vec!["Time", "Price", "Size"]).style(Style::default().fg(ACCENT).bold());
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(9),
+            Constraint::Length(12),
+            Constraint::Min(8),
+        ],
+    )
+    .header(header)
+    .block(block);
+
+    frame.render_widget(table, area);
+}
+
+/// Horizontal bar proportional to `sz / max_sz`.
+fn depth_bar(sz: rust_decimal::Decimal, max_sz: rust_decimal::Decimal) -> String {
+    use rust_decimal::prelude::ToPrimitive;
+
+    if max_sz.is_zero() {
+        return String::new();
+    }
+    let ratio = (sz / max_sz).to_f64().unwrap_or(0.0);
+    let len = ((ratio * BOOK_BAR_WIDTH as f64).round() as usize).clamp(1, BOOK_BAR_WIDTH);
+    "█".repeat(len)
+}
+
+// ─── Tab 6: Screener (all perps from asset contexts) ────────────────
+
+fn render_screener(frame: &mut Frame, app: &App, area: Rect) {
+    let title = match app.screener_input {
+        Some(ref input) => format!(" Filter: {}▏ ", input.value()),
+        None if !app.screener_filter.is_empty() => format!(
+            " Screener ({}/{}) — \"{}\" — sort: {} ",
+            app.screener_view.len(),
+            app.screener.len(),
+            app.screener_filter,
+            app.screener_sort.label()
+        ),
+        None => format!(
+            " Screener ({}) — sort: {} — s: sort  /: filter  Enter: book ",
+            app.screener.len(),
+            app.screener_sort.label()
+        ),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(Style::default().fg(YELLOW).bold())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(DIM));
+
+    if app.screener_view.is_empty() {
+        let msg = if app.screener.is_empty() {
+            "\n  Loading..."
+        } else {
+            "\n  No markets match the filter."
+        };
+        let p = Paragraph::new(msg)
+            .style(Style::default().fg(DIM))
+            .block(block);
+        frame.render_widget(p, area);
+        return;
+    }
+
+    let sorted = |col: ScreenerSort, name: &'static str| {
+        if app.screener_sort == col {
+            Cell::from(format!("{name} ▼")).style(Style::default().fg(YELLOW).bold())
+        } else {
+            Cell::from(name)
+        }
+    };
+    let header = Row::new(vec![
+        Cell::from(""),
+        Cell::from("Coin"),
+        Cell::from("Mark"),
+        sorted(ScreenerSort::Change, "24h %"),
+        sorted(ScreenerSort::Volume, "Volume"),
+        sorted(ScreenerSort::Funding, "Funding/h"),
+        sorted(ScreenerSort::OpenInterest, "OI"),
+    ])
+    .style(Style::default().fg(ACCENT).bold());
+
+    // Only build rows for the visible window, scrolled to keep the
+    // selection in view
+    let visible = (area.height as usize).saturating_sub(3).max(1);
+    let offset = app.selected_screener.saturating_sub(visible - 1);
+    let dash = || "—".to_string();
+    let rows: Vec<Row> = app
+        .screener_view
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, &idx)| {
+            let c = &app.screener[idx];
+            let is_selected = i == app.selected_screener;
+            let pct = |v: Option<rust_decimal::Decimal>, dp: usize| {
+                let color = match v.map(|v| v.round_dp(dp as u32)) {
+                    Some(v) if v.is_zero() => DIM,
+                    Some(v) if v.is_sign_negative() => RED,
+                    Some(_) => GREEN,
+                    None => DIM,
+                };
+                Cell::from(v.map_or_else(dash, |v| format!("{v:.dp$}%")))
+                    .style(Style::default().fg(color))
+            };
+            let usd = |v: Option<rust_decimal::Decimal>| {
+                v.map_or_else(dash, |v| fmt::format_usd(&v.to_string()))
+            };
+            Row::new(vec![
+                Cell::from(if is_selected { "►" } else { " " })
+                    .style(Style::default().fg(ACCENT).bold()),
+                Cell::from(c.symbol.clone()).style(Style::default().fg(WHITE).bold()),
+                Cell::from(
+                    c.mark_price
+                        .map_or_else(dash, |p| app.numbers.price(p, None)),
+                ),
+                pct(c.change_24h_pct(), 2),
+                Cell::from(usd(c.volume_24h)),
+                pct(
+                    c.funding_rate
+                        .map(|f| f * rust_decimal::Decimal::ONE_HUNDRED),
+                    4,
+                ),
+                Cell::from(usd(c.open_interest_usd())),
+            ])
+            .style(if is_selected {
+                Style::default().bg(BG_SELECTED)
+            } else {
+                Style::default()
+            })
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(2),  // Indicator
+            Constraint::Length(10), // Coin
+            Constraint::Length(12), // Mark
+            Constraint::Length(10), // 24h %
+            Constraint::Length(12), // Volume
+            Constraint::Length(12), // Funding
+            Constraint::Min(12),    // OI
+        ],
+    )
+    .header(header)
+    .block(block);
+
+    frame.render_widget(table, area);
+}
+
 // ─── Status bar ─────────────────────────────────────────────────────
 
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
@@ -562,10 +880,20 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         Span::raw("")
     };
 
+    let notification_span = if let Some(ref msg) = app.notification {
+        Span::styled(
+            format!("  │  {}", fmt::truncate_str(msg, 40)),
+            Style::default().fg(YELLOW).bold(),
+        )
+    } else {
+        Span::raw("")
+    };
+
     let line = Line::from(vec![
         Span::styled(" ", Style::default()),
         conn,
         ws,
+        notification_span,
         error_span,
         Span::styled(
             format!(
@@ -575,7 +903,7 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
             Style::default().fg(DIM),
         ),
         Span::styled(
-            "?",
+            app.keymap.label(Binding::Help),
             Style::default()
                 .fg(Color::Black)
                 .bg(ACCENT)
@@ -583,7 +911,7 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         ),
         Span::styled(" Help ", Style::default().fg(DIM)),
         Span::styled(
-            "q",
+            app.keymap.label(Binding::Quit),
             Style::default()
                 .fg(Color::Black)
                 .bg(RED)
@@ -598,48 +926,59 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
 
 // ─── Help overlay ───────────────────────────────────────────────────
 
-fn render_help(frame: &mut Frame, area: Rect) {
-    let popup = centered_rect(55, 70, area);
+fn render_help(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(55, 80, area);
 
     frame.render_widget(Clear, popup);
 
-    let help_text = vec![
-        Line::from(""),
-        Line::from(Span::styled(
-            "  Navigation",
-            Style::default().fg(ACCENT).bold(),
-        )),
-        Line::from(""),
-        Line::from("  1-4           Switch tab"),
-        Line::from("  Tab / l / →   Next tab"),
-        Line::from("  S-Tab / h / ← Previous tab"),
-        Line::from("  j / ↓         Scroll down / select next"),
-        Line::from("  k / ↑         Scroll up / select prev"),
-        Line::from(""),
+    let section = |title: &'static str| {
         Line::from(Span::styled(
-            "  Actions",
+            format!("  {title}"),
             Style::default().fg(ACCENT).bold(),
-        )),
-        Line::from(""),
-        Line::from("  r             Force refresh (REST)"),
-        Line::from("  c             Cancel selected order (Orders tab)"),
-        Line::from("  ?             Toggle help"),
-        Line::from("  q / Ctrl+C    Quit"),
+        ))
+    };
+    let binding_line = |binding: Binding| {
+        Line::from(format!(
+            "  {:<14}{}",
+            app.keymap.label(binding),
+            binding.description()
+        ))
+    };
+
+    let mut help_text = vec![Line::from(""), section("Navigation"), Line::from("")];
+    help_text.push(Line::from(format!("  {:<14}Switch tab", "1-6")));
+    help_text.extend(
+        Binding::ALL
+            .into_iter()
+            .filter(|b| b.is_navigation())
+            .map(binding_line),
+    );
+    help_text.extend([Line::from(""), section("Actions"), Line::from("")]);
+    help_text.extend(
+        Binding::ALL
+            .into_iter()
+            .filter(|b| !b.is_navigation())
+            .map(binding_line),
+    );
+    help_text.extend([
         Line::from(""),
-        Line::from(Span::styled(
-            "  Live Data",
-            Style::default().fg(ACCENT).bold(),
-        )),
+        section("Live Data"),
         Line::from(""),
         Line::from("  Prices update via WebSocket (AllMids)"),
         Line::from("  Positions PnL recalculated on each tick"),
-        Line::from("  Account data refreshes via REST every ~10s"),
+        Line::from(format!(
+            "  Account data refreshes via REST every ~{}s",
+            app.refresh_interval * TICK_MS / 1000
+        )),
         Line::from(""),
         Line::from(Span::styled(
-            "  Press ? or Esc to close",
+            format!(
+                "  Press {} or Esc to close",
+                app.keymap.label(Binding::Help)
+            ),
             Style::default().fg(DIM),
         )),
-    ];
+    ]);
 
     let help = Paragraph::new(help_text)
         .block(
@@ -814,8 +1153,86 @@ fn render_swap_popup(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(Paragraph::new(Line::from(vec![status_txt])), layout[3]);
 }
 
+fn render_close_popup(frame: &mut Frame, app: &App, area: Rect) {
+    let popup = centered_rect(40, 30, area);
+    frame.render_widget(Clear, popup);
+
+    let cp = &app.close_popup;
+    let block = Block::default()
+        .title(format!(" Close {} ", cp.coin))
+        .title_style(Style::default().fg(RED).bold())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(RED))
+        .style(Style::default().bg(BG_HEADER));
+
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Position
+            Constraint::Length(2), // Size input
+            Constraint::Length(2), // Status / Hint
+        ])
+        .margin(1)
+        .split(inner);
+
+    let side_color = if cp.position_size.is_sign_negative() {
+        RED
+    } else {
+        GREEN
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!("{:<8}", "Position"), Style::default().fg(DIM)),
+            Span::styled(
+                format!("{}", cp.position_size),
+                Style::default().fg(side_color).bold(),
+            ),
+        ])),
+        layout[0],
+    );
+
+    let size_display = if cp.size.value().is_empty() {
+        "full".to_string()
+    } else {
+        cp.size.value().to_string()
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!("{:<8}", "Size"), Style::default().fg(DIM)),
+            Span::styled(
+                format!("{size_display:^12}"),
+                Style::default().fg(Color::Black).bg(ACCENT),
+            ),
+        ])),
+        layout[1],
+    );
+
+    let status_txt = if let Some(ref s) = cp.status {
+        Span::styled(format!(" {s} "), Style::default().fg(YELLOW))
+    } else {
+        Span::styled(
+            " Esc: Abort | Enter: Confirm close ",
+            Style::default().fg(DIM),
+        )
+    };
+    frame.render_widget(Paragraph::new(Line::from(vec![status_txt])), layout[2]);
+}
+
 // ─── Helpers ────────────────────────────────────────────────────────
 
+/// A USD amount by the `output.*` rules; placeholders pass through.
+fn usd(app: &App, s: &str) -> String {
+    if s.trim().parse::<rust_decimal::Decimal>().is_ok() {
+        format!("${}", app.numbers.usd_str(s))
+    } else {
+        s.to_string()
+    }
+}
+
 /// Determine color based on numeric string sign.
 fn color_for_value(s: &str) -> Color {
     match fmt::sign_of(s) {
@@ -0,0 +1,350 @@
+//! Second approval for large transfers (`security.transfer_confirm_above`).
+//!
+//! Two methods, picked with `security.transfer_confirm_method`:
+//! - **totp** — a 6-digit RFC 6238 code (HMAC-SHA1, 30 s step) from any
+//!   authenticator app. The secret lives in the OS keyring, never in
+//!   `atlas.json`.
+//! - **file** — the command prints a nonce and waits until a file holding
+//!   that nonce appears, so a second device or process can approve.
+//!
+//! Wrong codes and timeouts fail with [`AtlasError::Auth`]. `--yes` and
+//! `trading.confirm = false` don't apply here.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use keyring::Entry;
+use rand::Rng;
+use sha1::Sha1;
+use tracing::info;
+
+use crate::config::{ApprovalMethod, SecurityConfig};
+use crate::error::{AtlasError, AtlasResult};
+
+/// Keyring service and user for the TOTP secret. One secret covers every
+/// profile, like the threshold it protects.
+const KEYRING_SERVICE: &str = "atlas_os_totp";
+const KEYRING_USER: &str = "transfer_approval";
+
+/// RFC 6238 defaults understood by every authenticator app.
+const STEP_SECS: u64 = 30;
+const DIGITS: u32 = 6;
+const SECRET_BYTES: usize = 20;
+
+/// RFC 4648 base32 alphabet, used for secrets and nonces.
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// How often the file method checks for the approval file.
+const FILE_POLL: Duration = Duration::from_millis(500);
+
+// ═══════════════════════════════════════════════════════════════════════
+//  TOTP
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Unpadded base32 of `bytes`.
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+/// Decode base32, ignoring case, spaces and `=` padding. `None` on any
+/// other character.
+pub fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in text.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32
+            .iter()
+            .position(|&b| b as char == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// The code for the 30 s step containing `unix_secs`.
+pub fn totp_code(secret: &[u8], unix_secs: u64) -> u32 {
+    let counter = unix_secs / STEP_SECS;
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+    // RFC 4226 dynamic truncation
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    binary % 10u32.pow(DIGITS)
+}
+
+/// Does `code` match the current step or one step either side? The
+/// window absorbs clock drift and a code typed just as it rolled over.
+pub fn verify_totp(secret: &[u8], code: &str, unix_secs: u64) -> bool {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let Ok(entered) = code.parse::<u32>() else {
+        return false;
+    };
+    [
+        unix_secs.saturating_sub(STEP_SECS),
+        unix_secs,
+        unix_secs + STEP_SECS,
+    ]
+    .iter()
+    .any(|&t| totp_code(secret, t) == entered)
+}
+
+/// A fresh random 160-bit secret.
+pub fn generate_secret() -> Vec<u8> {
+    let mut secret = vec![0u8; SECRET_BYTES];
+    rand::thread_rng().fill(&mut secret[..]);
+    secret
+}
+
+/// `otpauth://` URI for authenticator apps that import by link or QR code.
+pub fn otpauth_uri(secret_b32: &str, account: &str) -> String {
+    format!(
+        "otpauth://totp/Atlas:{account}?secret={secret_b32}&issuer=Atlas&digits={DIGITS}&period={STEP_SECS}"
+    )
+}
+
+fn keyring_entry() -> Result<Entry> {
+    Entry::new(KEYRING_SERVICE, KEYRING_USER).context("Failed to access keyring entry")
+}
+
+/// Store a base32 TOTP secret in the OS keyring, replacing any previous one.
+pub fn store_totp_secret(secret_b32: &str) -> Result<()> {
+    keyring_entry()?
+        .set_password(secret_b32)
+        .context("Failed to store TOTP secret in OS keyring")?;
+    info!("TOTP secret enrolled");
+    Ok(())
+}
+
+/// The enrolled TOTP secret, or `None` if nothing is enrolled.
+pub fn load_totp_secret() -> AtlasResult<Option<Vec<u8>>> {
+    let entry = keyring_entry().map_err(|e| AtlasError::KeyringError(format!("{e:#}")))?;
+    match entry.get_password() {
+        Ok(secret) => base32_decode(&secret).map(Some).ok_or_else(|| {
+            AtlasError::KeyringError("Corrupted TOTP secret in keyring — invalid base32".into())
+        }),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AtlasError::KeyringError(format!(
+            "OS keyring unavailable: {e}"
+        ))),
+    }
+}
+
+/// Remove the enrolled TOTP secret. Returns whether one existed.
+pub fn delete_totp_secret() -> Result<bool> {
+    match keyring_entry()?.delete_credential() {
+        Ok(()) => {
+            info!("TOTP secret removed");
+            Ok(true)
+        }
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e).context("Failed to delete TOTP secret from OS keyring"),
+    }
+}
+
+/// Ask for a code on stderr, read it from stdin and check it against
+/// `secret`. Stdin need not be a terminal, so a code can be piped in.
+pub async fn prompt_totp(secret: &[u8], timeout: Duration) -> Result<()> {
+    eprint!("TOTP code: ");
+    io::stderr().flush()?;
+    let read = tokio::task::spawn_blocking(|| {
+        let mut line = String::new();
+        io::stdin().read_line(&mut line).map(|n| (n, line))
+    });
+    let (n, code) = match tokio::time::timeout(timeout, read).await {
+        Ok(joined) => joined.context("TOTP prompt failed")??,
+        Err(_) => {
+            return Err(AtlasError::Auth(format!(
+                "Timed out after {}s waiting for a TOTP code",
+                timeout.as_secs()
+            ))
+            .into())
+        }
+    };
+    if n == 0 || code.trim().is_empty() {
+        return Err(AtlasError::Auth("No TOTP code entered".into()).into());
+    }
+    let now = chrono::Utc::now().timestamp().max(0) as u64;
+    if !verify_totp(secret, &code, now) {
+        return Err(AtlasError::Auth("Wrong TOTP code".into()).into());
+    }
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  CONFIRMATION FILE
+// ═══════════════════════════════════════════════════════════════════════
+
+/// A 10-character base32 nonce (50 random bits) for one approval.
+pub fn new_nonce() -> String {
+    let mut rng = rand::thread_rng();
+    (0..10)
+        .map(|_| BASE32[rng.gen_range(0..BASE32.len())] as char)
+        .collect()
+}
+
+/// `security.approval_file`, or `~/.atlas-os/approvals/transfer.approve`.
+pub fn approval_file(security: &SecurityConfig) -> Result<PathBuf> {
+    match &security.approval_file {
+        Some(path) => Ok(PathBuf::from(path)),
+        None => crate::workspace::resolve("approvals/transfer.approve"),
+    }
+}
+
+/// Does the file at `path` exist and contain `nonce`?
+pub fn file_approves(path: &Path, nonce: &str) -> bool {
+    std::fs::read_to_string(path)
+        .map(|text| text.split_whitespace().any(|word| word == nonce))
+        .unwrap_or(false)
+}
+
+/// Poll until `path` holds `nonce`, then delete it so it can't approve
+/// anything else.
+pub async fn wait_for_file(path: &Path, nonce: &str, timeout: Duration) -> AtlasResult<()> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if file_approves(path, nonce) {
+            let _ = std::fs::remove_file(path);
+            return Ok(());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(AtlasError::Auth(format!(
+                "Transfer approval timed out after {}s: no file with nonce {nonce} at {}",
+                timeout.as_secs(),
+                path.display()
+            )));
+        }
+        tokio::time::sleep(FILE_POLL).await;
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  GATE
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Require a second approval when a transfer of `amount` USDC is above
+/// `security.transfer_confirm_above`; returns immediately otherwise.
+/// Prompts go to stderr so JSON output stays clean.
+pub async fn require_transfer_approval(
+    security: &SecurityConfig,
+    amount: f64,
+    summary: &str,
+) -> Result<()> {
+    if !security.needs_approval(amount) {
+        return Ok(());
+    }
+    let threshold = security.transfer_confirm_above.unwrap_or_default();
+    let timeout = Duration::from_secs(security.approval_timeout_secs);
+    eprintln!("{summary} is above security.transfer_confirm_above ({threshold} USDC).");
+
+    match security.transfer_confirm_method {
+        ApprovalMethod::Totp => {
+            let secret = load_totp_secret()?.ok_or_else(|| {
+                AtlasError::Auth(
+                    "Transfer needs a TOTP code but none is enrolled. \
+                     Run: atlas configure security totp enroll"
+                        .into(),
+                )
+            })?;
+            prompt_totp(&secret, timeout).await
+        }
+        ApprovalMethod::File => {
+            let path = approval_file(security)?;
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create {}", dir.display()))?;
+            }
+            let nonce = new_nonce();
+            eprintln!("Approval nonce: {nonce}");
+            eprintln!(
+                "Waiting up to {}s for {} to contain it, e.g. from another device:",
+                timeout.as_secs(),
+                path.display()
+            );
+            eprintln!("  echo {nonce} > {}", path.display());
+            Ok(wait_for_file(&path, &nonce, timeout).await?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 appendix B SHA-1 secret.
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_totp_rfc6238_vectors() {
+        // The RFC lists 8-digit codes; ours are their last 6 digits
+        assert_eq!(totp_code(RFC_SECRET, 59), 287_082);
+        assert_eq!(totp_code(RFC_SECRET, 1_111_111_109), 81_804);
+        assert_eq!(totp_code(RFC_SECRET, 1_234_567_890), 5_924);
+        assert_eq!(totp_code(RFC_SECRET, 2_000_000_000), 279_037);
+    }
+
+    #[test]
+    fn test_verify_totp_window_and_format() {
+        let now = 1_111_111_109;
+        assert!(verify_totp(RFC_SECRET, "081804", now));
+        assert!(verify_totp(RFC_SECRET, " 081804\n", now + 30));
+        assert!(!verify_totp(RFC_SECRET, "081804", now + 90));
+        assert!(!verify_totp(RFC_SECRET, "81804", now));
+        assert!(!verify_totp(RFC_SECRET, "08180a", now));
+    }
+
+    #[test]
+    fn test_base32_round_trip() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(base32_decode("mzxw 6ytb oi==").unwrap(), b"foobar");
+        assert!(base32_decode("MZXW1").is_none());
+        let secret = generate_secret();
+        assert_eq!(base32_decode(&base32_encode(&secret)).unwrap(), secret);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_file_matches_nonce() {
+        let path =
+            std::env::temp_dir().join(format!("atlas-approval-{}.approve", std::process::id()));
+        let nonce = new_nonce();
+        std::fs::write(&path, "SOMETHINGELSE\n").unwrap();
+        let err = wait_for_file(&path, &nonce, Duration::ZERO)
+            .await
+            .unwrap_err();
+        assert_eq!(err.detail().code, "AUTH_ERROR");
+
+        std::fs::write(&path, format!("{nonce}\n")).unwrap();
+        wait_for_file(&path, &nonce, Duration::ZERO).await.unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,598 @@
+//! Asset identifiers across market data sources, and the token address book.
+//!
+//! Hyperliquid, CoinGecko and 0x name the same asset differently: `BTC` is
+//! `bitcoin` on CoinGecko and a wrapped-token contract on each EVM chain.
+//! [`AssetMap`] holds both lookups: symbol → CoinGecko id (built-ins plus
+//! the user's `market.coingecko_ids`), and the per-chain address book of
+//! token names (built-in top tokens plus entries from `atlas address add`),
+//! which also lets 0x commands take `USDC` instead of a contract address.
+
+use std::collections::HashMap;
+
+use alloy::primitives::Address;
+use rust_decimal::Decimal;
+
+use crate::config::MarketConfig;
+use crate::db::DbAddressEntry;
+use crate::engine::is_evm_address;
+use crate::error::AtlasError;
+use crate::symbols::{suggest, MAX_SUGGESTIONS};
+use crate::types::Chain;
+
+/// Exchange symbol → CoinGecko coin id.
+pub const BUILTIN_COINGECKO_IDS: &[(&str, &str)] = &[
+    ("BTC", "bitcoin"),
+    ("ETH", "ethereum"),
+    ("SOL", "solana"),
+    ("HYPE", "hyperliquid"),
+    ("ARB", "arbitrum"),
+    ("OP", "optimism"),
+    ("AVAX", "avalanche-2"),
+    ("DOGE", "dogecoin"),
+    ("LINK", "chainlink"),
+    ("UNI", "uniswap"),
+    ("AAVE", "aave"),
+    ("LDO", "lido-dao"),
+    ("USDC", "usd-coin"),
+];
+
+/// Placeholder address 0x uses for a chain's native coin.
+pub const NATIVE_TOKEN_ADDRESS: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+
+/// Built-in address book: `(chain, name, address, decimals)`.
+pub const BUILTIN_TOKENS: &[(&str, &str, &str, u32)] = &[
+    ("ethereum", "ETH", NATIVE_TOKEN_ADDRESS, 18),
+    (
+        "ethereum",
+        "WETH",
+        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+        18,
+    ),
+    (
+        "ethereum",
+        "USDC",
+        "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+        6,
+    ),
+    (
+        "ethereum",
+        "USDT",
+        "0xdAC17F958D2ee523a2206206994597C13D831ec7",
+        6,
+    ),
+    (
+        "ethereum",
+        "DAI",
+        "0x6B175474E89094C44Da98b954EedeAC495271d0F",
+        18,
+    ),
+    (
+        "ethereum",
+        "WBTC",
+        "0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599",
+        8,
+    ),
+    (
+        "ethereum",
+        "LINK",
+        "0x514910771AF9Ca656af840dff83E8264EcF986CA",
+        18,
+    ),
+    (
+        "ethereum",
+        "UNI",
+        "0x1f9840a85d5aF5bf1D1762F925BDADdC4201F984",
+        18,
+    ),
+    (
+        "ethereum",
+        "AAVE",
+        "0x7Fc66500c84A76Ad7e9c93437bFc5Ac33E2DDaE9",
+        18,
+    ),
+    (
+        "ethereum",
+        "LDO",
+        "0x5A98FcBEA516Cf06857215779Fd812CA3beF1B32",
+        18,
+    ),
+    ("arbitrum", "ETH", NATIVE_TOKEN_ADDRESS, 18),
+    (
+        "arbitrum",
+        "WETH",
+        "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1",
+        18,
+    ),
+    (
+        "arbitrum",
+        "USDC",
+        "0xaf88d065e77c8cC2239327C5EDb3A432268e5831",
+        6,
+    ),
+    (
+        "arbitrum",
+        "USDT",
+        "0xFd086bC7CD5C481DCC9C85ebE478A1C0b69FCbb9",
+        6,
+    ),
+    (
+        "arbitrum",
+        "DAI",
+        "0xDA10009cBd5D07dd0CeCc66161FC93D7c9000da1",
+        18,
+    ),
+    (
+        "arbitrum",
+        "WBTC",
+        "0x2f2a2543B76A4166549F7aaB2e75Bef0aefC5B0f",
+        8,
+    ),
+    (
+        "arbitrum",
+        "ARB",
+        "0x912CE59144191C1204E64559FE8253a0e49E6548",
+        18,
+    ),
+    (
+        "arbitrum",
+        "LINK",
+        "0xf97f4df75117a78c1A5a0DBb814Af92458539FB4",
+        18,
+    ),
+    ("base", "ETH", NATIVE_TOKEN_ADDRESS, 18),
+    (
+        "base",
+        "WETH",
+        "0x4200000000000000000000000000000000000006",
+        18,
+    ),
+    (
+        "base",
+        "USDC",
+        "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913",
+        6,
+    ),
+    (
+        "base",
+        "DAI",
+        "0x50c5725949A6F0c72E6C4a641F24049A917DB0Cb",
+        18,
+    ),
+    (
+        "base",
+        "cbBTC",
+        "0xcbB7C0000aB88B473b1f5aFd9ef808440eed33Bf",
+        8,
+    ),
+];
+
+/// Address book names that stand in for an exchange symbol when pricing it
+/// on a chain: `(symbol, chain, token)`. Other symbols use their own name.
+pub const MARKET_STAND_INS: &[(&str, &str, &str)] = &[
+    ("BTC", "ethereum", "WBTC"),
+    ("BTC", "arbitrum", "WBTC"),
+    ("BTC", "base", "cbBTC"),
+];
+
+/// One address book entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenEntry {
+    pub chain: String,
+    pub name: String,
+    /// EIP-55 checksummed.
+    pub address: String,
+    /// Unknown for user entries added without `--decimals`.
+    pub decimals: Option<u32>,
+    pub builtin: bool,
+}
+
+/// A token argument after address book lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedToken {
+    /// Address book name; `None` for a raw address the book doesn't know.
+    pub name: Option<String>,
+    pub address: String,
+    /// Decimals from the book, when known.
+    pub decimals: Option<u32>,
+}
+
+/// Symbol lookups for CoinGecko and the per-chain address book.
+#[derive(Debug, Clone)]
+pub struct AssetMap {
+    /// Uppercased symbol → CoinGecko id.
+    coingecko_ids: HashMap<String, String>,
+    tokens: Vec<TokenEntry>,
+}
+
+impl Default for AssetMap {
+    fn default() -> Self {
+        Self {
+            coingecko_ids: BUILTIN_COINGECKO_IDS
+                .iter()
+                .map(|(symbol, id)| (symbol.to_string(), id.to_string()))
+                .collect(),
+            tokens: BUILTIN_TOKENS
+                .iter()
+                .map(|(chain, name, address, decimals)| TokenEntry {
+                    chain: chain.to_string(),
+                    name: name.to_string(),
+                    address: address.to_string(),
+                    decimals: Some(*decimals),
+                    builtin: true,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl AssetMap {
+    /// Built-in tables plus the CoinGecko id overrides in `market`.
+    pub fn from_config(market: &MarketConfig) -> Self {
+        let mut map = Self::default();
+        for (symbol, id) in &market.coingecko_ids {
+            map.coingecko_ids
+                .insert(symbol.trim().to_uppercase(), id.trim().to_lowercase());
+        }
+        map
+    }
+
+    /// Add the user's address book entries from the local DB.
+    pub fn with_address_book(mut self, entries: &[DbAddressEntry]) -> Self {
+        self.tokens.extend(entries.iter().map(|e| TokenEntry {
+            chain: e.chain.clone(),
+            name: e.name.clone(),
+            address: e.address.clone(),
+            decimals: e.decimals,
+            builtin: false,
+        }));
+        self
+    }
+
+    /// CoinGecko id for an exchange symbol.
+    pub fn coingecko_id(&self, symbol: &str) -> Option<&str> {
+        self.coingecko_ids
+            .get(&symbol.trim().to_uppercase())
+            .map(String::as_str)
+    }
+
+    /// Address book entry by name (case-insensitive) on `chain`.
+    pub fn token(&self, chain: &Chain, name: &str) -> Option<&TokenEntry> {
+        let chain = chain.to_string();
+        let name = name.trim();
+        self.tokens
+            .iter()
+            .find(|t| t.chain == chain && t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The token to price an exchange symbol with on `chain`.
+    pub fn market_token(&self, chain: &Chain, symbol: &str) -> Option<&TokenEntry> {
+        let chain_name = chain.to_string();
+        let symbol = symbol.trim();
+        let name = MARKET_STAND_INS
+            .iter()
+            .find(|(s, c, _)| s.eq_ignore_ascii_case(symbol) && *c == chain_name)
+            .map_or(symbol, |(_, _, token)| *token);
+        self.token(chain, name)
+    }
+
+    /// Every entry, optionally on one chain only, by chain then name.
+    pub fn tokens(&self, chain: Option<&Chain>) -> Vec<&TokenEntry> {
+        let chain = chain.map(|c| c.to_string());
+        let mut tokens: Vec<&TokenEntry> = self
+            .tokens
+            .iter()
+            .filter(|t| match &chain {
+                Some(c) => t.chain == *c,
+                None => true,
+            })
+            .collect();
+        tokens.sort_by(|a, b| {
+            a.chain
+                .cmp(&b.chain)
+                .then_with(|| a.name.to_uppercase().cmp(&b.name.to_uppercase()))
+        });
+        tokens
+    }
+
+    /// Resolve a token argument: a `0x` address is used as given (named if
+    /// the book knows it); anything else must be a name on `chain`.
+    pub fn resolve_token(&self, chain: &Chain, input: &str) -> Result<ResolvedToken, AtlasError> {
+        let input = input.trim();
+        if input.starts_with("0x") || input.starts_with("0X") {
+            if !is_evm_address(input) {
+                return Err(AtlasError::Other(format!(
+                    "Invalid token address '{input}': expected 0x followed by 40 hex digits"
+                )));
+            }
+            let chain = chain.to_string();
+            let known = self
+                .tokens
+                .iter()
+                .find(|t| t.chain == chain && t.address.eq_ignore_ascii_case(input));
+            return Ok(ResolvedToken {
+                name: known.map(|t| t.name.clone()),
+                address: input.to_string(),
+                decimals: known.and_then(|t| t.decimals),
+            });
+        }
+        match self.token(chain, input) {
+            Some(t) => Ok(ResolvedToken {
+                name: Some(t.name.clone()),
+                address: t.address.clone(),
+                decimals: t.decimals,
+            }),
+            None => Err(self.unknown_token(chain, input)),
+        }
+    }
+
+    /// Not-in-the-book error for `name`, with the nearest names on `chain`.
+    pub fn unknown_token(&self, chain: &Chain, name: &str) -> AtlasError {
+        let names: Vec<&str> = self
+            .tokens(Some(chain))
+            .into_iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        let mut other_chains: Vec<String> = self
+            .tokens
+            .iter()
+            .filter(|t| t.name.eq_ignore_ascii_case(name))
+            .map(|t| t.chain.clone())
+            .collect();
+        other_chains.sort();
+        other_chains.dedup();
+        AtlasError::UnknownToken {
+            name: name.to_string(),
+            chain: chain.to_string(),
+            suggestions: suggest(name, &names, MAX_SUGGESTIONS),
+            other_chains,
+        }
+    }
+
+    /// Validate a new address book entry and return its checksummed
+    /// address. Both the name and the address must be new on the chain.
+    pub fn check_new_entry(
+        &self,
+        chain: &Chain,
+        name: &str,
+        address: &str,
+    ) -> Result<String, AtlasError> {
+        let name = name.trim();
+        let valid_name = !name.is_empty()
+            && name.len() <= 20
+            && !name.to_lowercase().starts_with("0x")
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        if !valid_name {
+            return Err(AtlasError::InvalidConfig(format!(
+                "Invalid token name '{name}': use up to 20 letters, digits, '.', '-' or '_'"
+            )));
+        }
+        let address = checksum_address(address).map_err(AtlasError::InvalidConfig)?;
+        if let Some(existing) = self.token(chain, name) {
+            return Err(AtlasError::InvalidConfig(format!(
+                "{} is already in the address book on {chain} ({})",
+                existing.name, existing.address
+            )));
+        }
+        let chain_name = chain.to_string();
+        if let Some(existing) = self
+            .tokens
+            .iter()
+            .find(|t| t.chain == chain_name && t.address.eq_ignore_ascii_case(&address))
+        {
+            return Err(AtlasError::InvalidConfig(format!(
+                "{address} is already in the address book on {chain} as {}",
+                existing.name
+            )));
+        }
+        Ok(address)
+    }
+}
+
+/// EIP-55 checksummed form of `input`. An all-lowercase or all-uppercase
+/// address carries no checksum; a mixed-case one must match it.
+pub fn checksum_address(input: &str) -> Result<String, String> {
+    let input = input.trim();
+    if !is_evm_address(input) {
+        return Err(format!(
+            "Invalid address '{input}': expected 0x followed by 40 hex digits"
+        ));
+    }
+    let parsed: Address = input
+        .parse()
+        .map_err(|e| format!("Invalid address '{input}': {e}"))?;
+    let checksummed = parsed.to_checksum(None);
+    let hex = &input[2..];
+    let mixed_case =
+        hex.chars().any(|c| c.is_ascii_lowercase()) && hex.chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case && input != checksummed {
+        return Err(format!(
+            "Checksum mismatch for '{input}' (expected {checksummed}); check for a typo"
+        ));
+    }
+    Ok(checksummed)
+}
+
+/// A base-unit amount (`"1500000"`) as a token amount (`1.5` at 6
+/// decimals). `None` if it isn't an integer or doesn't fit a `Decimal`.
+pub fn from_base_units(raw: &str, decimals: u32) -> Option<Decimal> {
+    let raw: i128 = raw.trim().parse().ok()?;
+    Decimal::try_from_i128_with_scale(raw, decimals).ok()
+}
+
+/// A token amount (`1.5`) in base units (`1500000` at 6 decimals). `None`
+/// if it is negative, has more fractional digits than the token, or is too
+/// large for a `Decimal` once scaled.
+pub fn to_base_units(amount: Decimal, decimals: u32) -> Option<u128> {
+    use rust_decimal::prelude::ToPrimitive;
+
+    if amount.is_sign_negative() {
+        return None;
+    }
+    let unit = Decimal::try_from_i128_with_scale(10i128.checked_pow(decimals)?, 0).ok()?;
+    let scaled = amount.checked_mul(unit)?;
+    if !scaled.fract().is_zero() {
+        return None;
+    }
+    scaled.to_u128()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AERO: &str = "0x940181a94A35A4569E4529A3CDfB74e38FD98631";
+
+    #[test]
+    fn test_builtin_lookups_ignore_case() {
+        let map = AssetMap::default();
+        assert_eq!(map.coingecko_id("btc"), Some("bitcoin"));
+        assert_eq!(map.coingecko_id("PURR"), None);
+        assert_eq!(map.token(&Chain::Base, "usdc").unwrap().decimals, Some(6));
+        assert_eq!(map.token(&Chain::Base, "CBBTC").unwrap().name, "cbBTC");
+        assert!(map.token(&Chain::Base, "ARB").is_none());
+        // Every built-in address is stored checksummed
+        for (chain, name, address, _) in BUILTIN_TOKENS {
+            assert_eq!(
+                checksum_address(address).as_deref(),
+                Ok(*address),
+                "{chain}:{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_coingecko_overrides_win() {
+        let mut market = MarketConfig::default();
+        market.coingecko_ids.insert("purr".into(), "Purr-2".into());
+        market.coingecko_ids.insert("ETH".into(), "weth".into());
+        let map = AssetMap::from_config(&market);
+        assert_eq!(map.coingecko_id("PURR"), Some("purr-2"));
+        assert_eq!(map.coingecko_id("ETH"), Some("weth"));
+    }
+
+    #[test]
+    fn test_market_token_uses_stand_ins() {
+        let map = AssetMap::default();
+        assert_eq!(
+            map.market_token(&Chain::Arbitrum, "btc").unwrap().name,
+            "WBTC"
+        );
+        assert_eq!(map.market_token(&Chain::Base, "BTC").unwrap().name, "cbBTC");
+        assert_eq!(map.market_token(&Chain::Base, "ETH").unwrap().name, "ETH");
+        assert!(map.market_token(&Chain::Base, "SOL").is_none());
+    }
+
+    #[test]
+    fn test_resolve_token_by_name_or_address() {
+        let map = AssetMap::default().with_address_book(&[DbAddressEntry {
+            chain: "base".into(),
+            name: "AERO".into(),
+            address: AERO.into(),
+            decimals: None,
+            added_ms: 0,
+        }]);
+        let usdc = map.resolve_token(&Chain::Ethereum, "usdc").unwrap();
+        assert_eq!(usdc.name.as_deref(), Some("USDC"));
+        assert_eq!(usdc.address, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        assert_eq!(usdc.decimals, Some(6));
+        let aero = map.resolve_token(&Chain::Base, "aero").unwrap();
+        assert_eq!(aero.address, AERO);
+        assert_eq!(aero.decimals, None);
+        assert!(map.resolve_token(&Chain::Ethereum, "AERO").is_err());
+
+        // A raw address passes through, named when the book knows it
+        let raw = map
+            .resolve_token(
+                &Chain::Ethereum,
+                "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            )
+            .unwrap();
+        assert_eq!(raw.name.as_deref(), Some("USDC"));
+        assert_eq!(raw.decimals, Some(6));
+        assert_eq!(raw.address, "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        let unknown = map
+            .resolve_token(
+                &Chain::Ethereum,
+                "0x1111111111111111111111111111111111111111",
+            )
+            .unwrap();
+        assert_eq!(unknown.name, None);
+        assert!(map.resolve_token(&Chain::Ethereum, "0x1234").is_err());
+    }
+
+    #[test]
+    fn test_unknown_name_suggests_per_chain() {
+        let map = AssetMap::default();
+        match map.resolve_token(&Chain::Base, "USD") {
+            Err(AtlasError::UnknownToken {
+                chain, suggestions, ..
+            }) => {
+                assert_eq!(chain, "base");
+                assert_eq!(suggestions, vec!["USDC".to_string()]);
+            }
+            other => panic!("expected UnknownToken, got {other:?}"),
+        }
+        // USDT isn't built in on Base, but the error says where it is
+        match map.resolve_token(&Chain::Base, "usdt") {
+            Err(AtlasError::UnknownToken { other_chains, .. }) => {
+                assert_eq!(other_chains, vec!["arbitrum", "ethereum"]);
+            }
+            other => panic!("expected UnknownToken, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_from_base_units() {
+        assert_eq!(from_base_units("1500000", 6), Some(Decimal::new(15, 1)));
+        assert_eq!(
+            from_base_units("285714285714285714", 18)
+                .unwrap()
+                .to_string(),
+            "0.285714285714285714"
+        );
+        assert_eq!(from_base_units("1.5", 6), None);
+        assert_eq!(from_base_units("1", 40), None);
+    }
+
+    #[test]
+    fn test_to_base_units() {
+        assert_eq!(to_base_units(Decimal::new(15, 1), 6), Some(1_500_000));
+        assert_eq!(
+            to_base_units(Decimal::new(25, 2), 18),
+            Some(250_000_000_000_000_000)
+        );
+        assert_eq!(to_base_units(Decimal::ZERO, 6), Some(0));
+        // 7 fractional digits can't be expressed in USDC base units
+        assert_eq!(to_base_units(Decimal::new(1_234_567, 7), 6), None);
+        assert_eq!(to_base_units(Decimal::new(-1, 0), 6), None);
+        assert_eq!(to_base_units(Decimal::from(1_000_000_000_000u64), 18), None);
+    }
+
+    #[test]
+    fn test_check_new_entry() {
+        let map = AssetMap::default();
+        // Lowercase input is stored checksummed
+        assert_eq!(
+            map.check_new_entry(&Chain::Base, "AERO", &AERO.to_lowercase())
+                .unwrap(),
+            AERO
+        );
+        // A mixed-case typo fails the checksum
+        let typo = "0x940181a94A35A4569E4529A3CDfB74e38FD98632";
+        assert!(map.check_new_entry(&Chain::Base, "AERO", typo).is_err());
+        // Duplicate name or address on the same chain
+        let usdc = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913";
+        assert!(map.check_new_entry(&Chain::Base, "usdc", AERO).is_err());
+        assert!(map.check_new_entry(&Chain::Base, "USDbC", usdc).is_err());
+        // ... but the same name on another chain is fine
+        assert!(map.check_new_entry(&Chain::Ethereum, "AERO", AERO).is_ok());
+        for bad in ["", "0xAERO", "AE RO", "AVERYLONGTOKENNAMEXYZ"] {
+            assert!(
+                map.check_new_entry(&Chain::Base, bad, AERO).is_err(),
+                "{bad}"
+            );
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! Audit log of every action atlas signs for an exchange (`atlas audit`).
+//!
+//! [`AuditTrail::begin`] writes a `pending` entry before the signed request
+//! is sent; if that write fails the caller must not send it. The outcome is
+//! recorded with [`AuditTrail::complete`] once there is one — by then the
+//! action is out, so a failure there is logged rather than returned. The
+//! table refuses deletes and rewrites of a recorded request, and there is
+//! no command that removes entries.
+//!
+//! For actions hypersdk signs and sends itself (spot orders, transfers,
+//! agent approvals) the entry holds the action fields and nonce it was
+//! given; the signature never reaches atlas.
+
+use std::sync::{Mutex, OnceLock};
+
+use alloy::primitives::{hex, keccak256};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::db::{AtlasDb, AuditFilter, DbAuditEntry};
+use crate::error::{AtlasError, AtlasResult};
+
+/// Replaces the value of any key that looks like a secret.
+pub const REDACTED: &str = "[redacted]";
+
+/// Key fragments (lowercase, `_`/`-` removed) whose values are redacted.
+const SECRET_KEYS: &[&str] = &[
+    "privatekey",
+    "secret",
+    "apikey",
+    "password",
+    "mnemonic",
+    "seed",
+];
+
+/// A signed action about to be sent.
+#[derive(Debug, Clone)]
+pub struct SignedAction<'a> {
+    pub protocol: &'a str,
+    /// `order`, `cancel`, `modify`, `leverage`, `margin`, `transfer` or
+    /// `agent` on Hyperliquid; `swap`, `approve`, `revoke` or `gasless` on
+    /// 0x.
+    pub action_type: &'a str,
+    pub coin: Option<&'a str>,
+    pub nonce: Option<u64>,
+    /// Address of the signing key.
+    pub signer: String,
+    /// The request body; redacted before it is stored.
+    pub request: &'a Value,
+}
+
+/// How a sent action ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditOutcome {
+    /// The exchange's raw answer; `ok` or `rejected` by its content.
+    Response(String),
+    /// No usable answer: transport failure, or an SDK call that failed.
+    Error(String),
+}
+
+impl AuditOutcome {
+    /// `ok`, `rejected` or `error`. A response is rejected when it says
+    /// `status: err` or any per-order status carries an `error`.
+    pub fn status(&self) -> &'static str {
+        let body = match self {
+            AuditOutcome::Error(_) => return "error",
+            AuditOutcome::Response(body) => body,
+        };
+        let Ok(json) = serde_json::from_str::<Value>(body) else {
+            return "ok";
+        };
+        let statuses = json
+            .pointer("/response/data/statuses")
+            .and_then(Value::as_array);
+        let order_error = statuses.is_some_and(|s| s.iter().any(|s| s.get("error").is_some()));
+        if json.get("status").and_then(Value::as_str) == Some("err") || order_error {
+            "rejected"
+        } else {
+            "ok"
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            AuditOutcome::Response(text) | AuditOutcome::Error(text) => text,
+        }
+    }
+}
+
+/// `request` with the value of every secret-looking key replaced by
+/// [`REDACTED`], at any depth.
+pub fn redact(request: &Value) -> Value {
+    match request {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let folded = key.to_ascii_lowercase().replace(['_', '-'], "");
+                    let value = if SECRET_KEYS.iter().any(|k| folded.contains(k)) {
+                        Value::String(REDACTED.into())
+                    } else {
+                        redact(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        other => other.clone(),
+    }
+}
+
+/// keccak256 of the request's `action` (the whole request without one),
+/// `0x` hex. Identical parameters hash the same whatever the nonce.
+pub fn params_hash(request: &Value) -> String {
+    let action = request.get("action").unwrap_or(request);
+    let bytes = serde_json::to_vec(action).unwrap_or_default();
+    format!("0x{}", hex::encode(keccak256(bytes)))
+}
+
+/// Where signed actions are recorded. Opens its database on first use.
+pub struct AuditTrail {
+    open: fn() -> anyhow::Result<AtlasDb>,
+    db: OnceLock<Result<Mutex<AtlasDb>, String>>,
+}
+
+impl AuditTrail {
+    /// The workspace database, `~/.atlas-os/data/atlas.db`.
+    pub fn workspace() -> Self {
+        Self {
+            open: AtlasDb::open,
+            db: OnceLock::new(),
+        }
+    }
+
+    /// A private in-memory database, for modules driven by tests.
+    pub fn in_memory() -> Self {
+        Self {
+            open: AtlasDb::open_in_memory,
+            db: OnceLock::new(),
+        }
+    }
+
+    fn with_db<T>(&self, f: impl FnOnce(&AtlasDb) -> anyhow::Result<T>) -> AtlasResult<T> {
+        let db = self
+            .db
+            .get_or_init(|| (self.open)().map(Mutex::new).map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(|e| AtlasError::AuditLog(format!("cannot open the database: {e}")))?;
+        let db = db
+            .lock()
+            .map_err(|_| AtlasError::AuditLog("audit log lock poisoned".into()))?;
+        f(&db).map_err(|e| AtlasError::AuditLog(e.to_string()))
+    }
+
+    /// Write a `pending` entry for `action` and return its id. On error
+    /// the action must not be sent.
+    pub fn begin(&self, action: &SignedAction<'_>) -> AtlasResult<i64> {
+        let request = redact(action.request);
+        let entry = DbAuditEntry {
+            id: 0,
+            created_ms: chrono::Utc::now().timestamp_millis(),
+            protocol: action.protocol.to_string(),
+            action_type: action.action_type.to_string(),
+            coin: action.coin.map(str::to_string),
+            params_hash: params_hash(&request),
+            nonce: action.nonce.map(|n| n as i64),
+            signer: action.signer.clone(),
+            request: request.to_string(),
+            status: "pending".into(),
+            response: None,
+            completed_ms: None,
+        };
+        self.with_db(|db| db.insert_audit_entry(&entry))
+    }
+
+    /// Record how entry `id` ended. Logged, not returned, on failure: the
+    /// action has already been sent and the entry stays `pending`.
+    pub fn complete(&self, id: i64, outcome: &AuditOutcome) {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let result = self.with_db(|db| {
+            db.complete_audit_entry(id, outcome.status(), Some(outcome.text()), now_ms)
+        });
+        if let Err(e) = result {
+            warn!(
+                audit_id = id,
+                "Failed to record the outcome of a signed action: {e}"
+            );
+        }
+    }
+
+    /// Entries matching `filter`, newest first.
+    pub fn entries(&self, filter: &AuditFilter) -> AtlasResult<Vec<DbAuditEntry>> {
+        self.with_db(|db| db.query_audit_entries(filter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn action(request: &Value) -> SignedAction<'_> {
+        SignedAction {
+            protocol: "hyperliquid",
+            action_type: "order",
+            coin: Some("ETH"),
+            nonce: Some(1_700_000_000_000),
+            signer: "0x1111".into(),
+            request,
+        }
+    }
+
+    #[test]
+    fn test_redact_secret_keys_at_any_depth() {
+        let request = json!({
+            "action": {"type": "order", "orders": [{"a": 4, "private_key": "0xdead"}]},
+            "signature": {"r": "0x01", "s": "0x02", "v": 27},
+            "meta": {"apiKey": "atl_123", "Client-Secret": "x"}
+        });
+        let redacted = redact(&request);
+        assert_eq!(redacted["action"]["orders"][0]["private_key"], REDACTED);
+        assert_eq!(redacted["action"]["orders"][0]["a"], 4);
+        assert_eq!(redacted["meta"]["apiKey"], REDACTED);
+        assert_eq!(redacted["meta"]["Client-Secret"], REDACTED);
+        // Signatures are public once sent and identify the request
+        assert_eq!(redacted["signature"], request["signature"]);
+    }
+
+    #[test]
+    fn test_params_hash_ignores_nonce_and_signature() {
+        let a = json!({"action": {"type": "cancel", "cancels": [{"a": 1, "o": 7}]}, "nonce": 1});
+        let b = json!({"action": {"type": "cancel", "cancels": [{"a": 1, "o": 7}]}, "nonce": 2});
+        let c = json!({"action": {"type": "cancel", "cancels": [{"a": 1, "o": 8}]}, "nonce": 1});
+        assert_eq!(params_hash(&a), params_hash(&b));
+        assert_ne!(params_hash(&a), params_hash(&c));
+        assert!(params_hash(&a).starts_with("0x"));
+        assert_eq!(params_hash(&a).len(), 66);
+    }
+
+    #[test]
+    fn test_outcome_status() {
+        let ok = r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"resting":{"oid":1}}]}}}"#;
+        let rejected = r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"error":"Insufficient margin"}]}}}"#;
+        assert_eq!(AuditOutcome::Response(ok.into()).status(), "ok");
+        assert_eq!(AuditOutcome::Response(rejected.into()).status(), "rejected");
+        assert_eq!(
+            AuditOutcome::Response(r#"{"status":"err","response":"bad nonce"}"#.into()).status(),
+            "rejected"
+        );
+        assert_eq!(AuditOutcome::Error("timed out".into()).status(), "error");
+    }
+
+    #[test]
+    fn test_trail_records_then_completes() {
+        let trail = AuditTrail::in_memory();
+        let request = json!({"action": {"type": "order"}, "nonce": 1, "apiKey": "atl_1"});
+        let id = trail.begin(&action(&request)).unwrap();
+        trail.complete(id, &AuditOutcome::Response(r#"{"status":"ok"}"#.into()));
+
+        let entries = trail.entries(&AuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!((entry.id, entry.status.as_str()), (id, "ok"));
+        assert_eq!(entry.nonce, Some(1_700_000_000_000));
+        assert!(!entry.request.contains("atl_1"));
+        assert_eq!(entry.response.as_deref(), Some(r#"{"status":"ok"}"#));
+    }
+
+    #[test]
+    fn test_unavailable_database_fails_closed() {
+        let trail = AuditTrail {
+            open: || anyhow::bail!("disk full"),
+            db: OnceLock::new(),
+        };
+        let err = trail.begin(&action(&json!({}))).unwrap_err();
+        assert!(matches!(err, AtlasError::AuditLog(_)), "{err:?}");
+        assert!(err.to_string().contains("disk full"));
+    }
+}
@@ -1,8 +1,9 @@
 use std::fs;
+use std::sync::OnceLock;
 
 use alloy::primitives::Address;
 use alloy::signers::local::PrivateKeySigner;
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use keyring::Entry;
 use tracing::info;
 
@@ -11,6 +12,97 @@ use crate::profile::{WalletProfile, WalletStore};
 /// Keyring service name — all Atlas private keys are stored under this.
 const KEYRING_SERVICE: &str = "atlas_os";
 
+/// Set by the global `--profile` flag: the profile this process acts as
+/// instead of `system.active_profile`. Never written to the config.
+static PROFILE_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Where profile private keys are kept: [`OsKeyring`], or an in-memory
+/// store in tests.
+pub trait KeyStore {
+    /// The key for `profile`, `None` when there is no entry.
+    fn get(&self, profile: &str) -> Result<Option<String>>;
+    fn set(&self, profile: &str, hex_key: &str) -> Result<()>;
+    fn delete(&self, profile: &str) -> Result<()>;
+}
+
+/// Where profiles are recorded: `wallets.json` and the active-profile
+/// pointer in the config. [`WorkspaceFiles`] outside tests.
+pub trait ProfileFiles {
+    fn load_store(&self) -> Result<WalletStore>;
+    fn save_store(&self, store: &WalletStore) -> Result<()>;
+    fn active_profile(&self) -> Result<String>;
+    fn set_active_profile(&self, name: &str) -> Result<()>;
+}
+
+/// The OS keyring, under [`KEYRING_SERVICE`].
+pub struct OsKeyring;
+
+impl KeyStore for OsKeyring {
+    fn get(&self, profile: &str) -> Result<Option<String>> {
+        let entry =
+            Entry::new(KEYRING_SERVICE, profile).context("Failed to access keyring entry")?;
+        match entry.get_password() {
+            Ok(key) => Ok(Some(key)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e).context("OS keyring unavailable"),
+        }
+    }
+
+    fn set(&self, profile: &str, hex_key: &str) -> Result<()> {
+        AuthManager::store_key(profile, hex_key)
+    }
+
+    fn delete(&self, profile: &str) -> Result<()> {
+        AuthManager::delete_key(profile)
+    }
+}
+
+/// `keystore/wallets.json` and `atlas.json` in the workspace.
+pub struct WorkspaceFiles;
+
+impl ProfileFiles for WorkspaceFiles {
+    fn load_store(&self) -> Result<WalletStore> {
+        AuthManager::load_store()
+    }
+
+    fn save_store(&self, store: &WalletStore) -> Result<()> {
+        AuthManager::save_store(store)
+    }
+
+    fn active_profile(&self) -> Result<String> {
+        Ok(crate::workspace::load_config()?.system.active_profile)
+    }
+
+    fn set_active_profile(&self, name: &str) -> Result<()> {
+        let mut config = crate::workspace::load_config()?;
+        config.system.active_profile = name.to_string();
+        crate::workspace::save_config(&config)
+    }
+}
+
+/// A profile removed by [`AuthManager::remove_profile`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemovedProfile {
+    pub name: String,
+    pub address: String,
+    /// False when the keyring had no key for it.
+    pub key_deleted: bool,
+    /// Profile made active instead, with `--switch-to`.
+    pub switched_to: Option<String>,
+}
+
+/// A profile renamed by [`AuthManager::rename_profile`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenamedProfile {
+    pub old: String,
+    pub new: String,
+    pub address: String,
+    /// The active-profile pointer followed the rename.
+    pub was_active: bool,
+    /// Cleanup that failed after the rename took effect.
+    pub warnings: Vec<String>,
+}
+
 /// Manages wallet profiles and their secrets.
 ///
 /// Public metadata lives in `$HOME/.atlas-os/keystore/wallets.json`.
@@ -47,7 +139,7 @@ impl AuthManager {
     // ── Keyring helpers ─────────────────────────────────────────────
 
     /// Store a hex-encoded private key in the OS keyring.
-    fn store_key(profile_name: &str, hex_key: &str) -> Result<()> {
+    pub(crate) fn store_key(profile_name: &str, hex_key: &str) -> Result<()> {
         let entry =
             Entry::new(KEYRING_SERVICE, profile_name).context("Failed to create keyring entry")?;
         entry
@@ -57,7 +149,7 @@ impl AuthManager {
     }
 
     /// Retrieve a hex-encoded private key from the OS keyring.
-    fn retrieve_key(profile_name: &str) -> Result<String> {
+    pub(crate) fn retrieve_key(profile_name: &str) -> Result<String> {
         let entry =
             Entry::new(KEYRING_SERVICE, profile_name).context("Failed to access keyring entry")?;
         let key = entry
@@ -66,6 +158,29 @@ impl AuthManager {
         Ok(key)
     }
 
+    /// Whether the OS keyring holds a key for `profile_name`. Errors only
+    /// when the keyring itself can't be reached.
+    pub fn has_key(profile_name: &str) -> Result<bool> {
+        let entry =
+            Entry::new(KEYRING_SERVICE, profile_name).context("Failed to access keyring entry")?;
+        match entry.get_password() {
+            Ok(_) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(e).context("OS keyring unavailable"),
+        }
+    }
+
+    /// Permanently delete the keyring entry for `profile_name`.
+    pub fn delete_key(profile_name: &str) -> Result<()> {
+        let entry =
+            Entry::new(KEYRING_SERVICE, profile_name).context("Failed to access keyring entry")?;
+        entry
+            .delete_credential()
+            .context("Failed to delete key from OS keyring")?;
+        info!(profile = profile_name, "keyring entry deleted");
+        Ok(())
+    }
+
     // ── Public API ──────────────────────────────────────────────────
 
     /// Generate a brand-new random EVM wallet and store it.
@@ -139,16 +254,227 @@ impl AuthManager {
         Ok(())
     }
 
+    /// Delete a profile: its keyring key, then its `wallets.json` entry.
+    /// The active profile is refused unless `switch_to` names another one
+    /// to activate. When a file write fails after the key was deleted,
+    /// the key is written back and the error says whether that worked.
+    pub fn remove_profile(name: &str, switch_to: Option<&str>) -> Result<RemovedProfile> {
+        Self::remove_profile_with(&OsKeyring, &WorkspaceFiles, name, switch_to)
+    }
+
+    pub fn remove_profile_with(
+        keys: &dyn KeyStore,
+        files: &dyn ProfileFiles,
+        name: &str,
+        switch_to: Option<&str>,
+    ) -> Result<RemovedProfile> {
+        let store = files.load_store()?;
+        let Some(profile) = store.find(name).cloned() else {
+            bail!("Profile '{name}' does not exist");
+        };
+        if let Some(next) = switch_to {
+            if next == name {
+                bail!("--switch-to must name a different profile than '{name}'");
+            }
+            if !store.exists(next) {
+                bail!("Profile '{next}' does not exist");
+            }
+        }
+        let active = files.active_profile()?;
+        if active == name && switch_to.is_none() {
+            bail!(
+                "'{name}' is the active profile. Switch first (atlas profile use <name>) \
+                 or pass --switch-to <name>"
+            );
+        }
+
+        // Kept so a failed file write can put the key back.
+        let secret = keys.get(name)?;
+        if secret.is_some() {
+            keys.delete(name)?;
+        }
+        let restore_key = |step: &str, err: anyhow::Error| {
+            let Some(secret) = &secret else {
+                return anyhow!("{step} failed, nothing was removed: {err:#}");
+            };
+            match keys.set(name, secret) {
+                Ok(()) => anyhow!("{step} failed; the keyring key was restored and '{name}' is unchanged: {err:#}"),
+                Err(e) => anyhow!(
+                    "{step} failed after the keyring key of '{name}' was deleted, and restoring it \
+                     failed too ({e:#}). wallets.json still lists '{name}'; re-import its key with \
+                     atlas profile import {name}: {err:#}"
+                ),
+            }
+        };
+
+        if let Some(next) = switch_to {
+            files
+                .set_active_profile(next)
+                .map_err(|e| restore_key("Switching the active profile", e))?;
+        }
+        let mut next_store = store.clone();
+        next_store.remove(name);
+        if let Err(e) = files.save_store(&next_store) {
+            if switch_to.is_some() {
+                // Best effort: the error below names what is left to fix.
+                let _ = files.set_active_profile(&active);
+            }
+            return Err(restore_key("Writing wallets.json", e));
+        }
+
+        info!(profile = name, "profile removed");
+        Ok(RemovedProfile {
+            name: name.to_string(),
+            address: profile.address,
+            key_deleted: secret.is_some(),
+            switched_to: switch_to.map(str::to_string),
+        })
+    }
+
+    /// Rename a profile: copy its key to the new keyring entry, rewrite
+    /// `wallets.json` and move the active pointer, undoing the earlier
+    /// steps when a later one fails. The old keyring entry is deleted
+    /// last; failing that only leaves a warning.
+    pub fn rename_profile(old: &str, new: &str) -> Result<RenamedProfile> {
+        Self::rename_profile_with(&OsKeyring, &WorkspaceFiles, old, new)
+    }
+
+    pub fn rename_profile_with(
+        keys: &dyn KeyStore,
+        files: &dyn ProfileFiles,
+        old: &str,
+        new: &str,
+    ) -> Result<RenamedProfile> {
+        let new = new.trim();
+        if new.is_empty() {
+            bail!("New profile name is empty");
+        }
+        let store = files.load_store()?;
+        let Some(profile) = store.find(old).cloned() else {
+            bail!("Profile '{old}' does not exist");
+        };
+        if store.exists(new) {
+            bail!("Profile '{new}' already exists");
+        }
+        let was_active = files.active_profile()? == old;
+
+        let secret = keys.get(old)?;
+        if let Some(secret) = &secret {
+            if keys.get(new)?.is_some() {
+                bail!(
+                    "The keyring already holds a key named '{new}'; remove it or pick another name"
+                );
+            }
+            keys.set(new, secret)?;
+        }
+        let undo_key = || {
+            if secret.is_some() {
+                let _ = keys.delete(new);
+            }
+        };
+
+        let mut next_store = store.clone();
+        if let Some(w) = next_store.wallets.iter_mut().find(|w| w.name == old) {
+            w.name = new.to_string();
+        }
+        if let Err(e) = files.save_store(&next_store) {
+            undo_key();
+            return Err(e.context(format!("Rename failed, '{old}' is unchanged")));
+        }
+        if was_active {
+            if let Err(e) = files.set_active_profile(new) {
+                let restored = files.save_store(&store);
+                undo_key();
+                return Err(match restored {
+                    Ok(()) => e.context(format!("Rename failed, '{old}' is unchanged")),
+                    Err(re) => e.context(format!(
+                        "Rename failed and wallets.json could not be restored ({re:#}): \
+                         it lists '{new}' while the active profile is still '{old}'"
+                    )),
+                });
+            }
+        }
+
+        let mut warnings = Vec::new();
+        if secret.is_some() {
+            if let Err(e) = keys.delete(old) {
+                warnings.push(format!(
+                    "The old keyring entry '{old}' could not be deleted: {e:#}"
+                ));
+            }
+        }
+
+        info!(old, new, "profile renamed");
+        Ok(RenamedProfile {
+            old: old.to_string(),
+            new: new.to_string(),
+            address: profile.address,
+            was_active,
+            warnings,
+        })
+    }
+
+    /// Act as `name` for the rest of this process (`--profile`), without
+    /// touching the configured active profile. Fails when no such profile
+    /// exists, listing the ones that do.
+    pub fn override_profile(name: &str) -> Result<()> {
+        Self::override_profile_with(&WorkspaceFiles, name)
+    }
+
+    pub fn override_profile_with(files: &dyn ProfileFiles, name: &str) -> Result<()> {
+        let name = Self::check_profile_with(files, name)?;
+        let _ = PROFILE_OVERRIDE.set(name);
+        Ok(())
+    }
+
+    /// `name` if `wallets.json` lists it, else an error naming the
+    /// profiles that exist.
+    pub fn check_profile_with(files: &dyn ProfileFiles, name: &str) -> Result<String> {
+        let store = files.load_store()?;
+        if store.exists(name) {
+            return Ok(name.to_string());
+        }
+        let names: Vec<&str> = store.wallets.iter().map(|w| w.name.as_str()).collect();
+        if names.is_empty() {
+            bail!(
+                "Profile '{name}' does not exist. No profiles yet: atlas profile generate <name>"
+            );
+        }
+        bail!(
+            "Profile '{name}' does not exist. Available: {}",
+            names.join(", ")
+        )
+    }
+
+    /// The profile this process acts as: `--profile` when given, else the
+    /// configured active profile.
+    pub fn active_profile(config: &crate::config::AppConfig) -> &str {
+        PROFILE_OVERRIDE
+            .get()
+            .map_or(config.system.active_profile.as_str(), String::as_str)
+    }
+
     /// Get a `PrivateKeySigner` for the currently active profile.
     pub fn get_active_signer() -> Result<PrivateKeySigner> {
         let config = crate::workspace::load_config()?;
         Self::load_active_signer(&config)
     }
 
-    /// Load the signer for the active profile from the given config.
+    /// Load the signer for the active profile (or `--profile`) from the
+    /// given config.
     pub fn load_active_signer(config: &crate::config::AppConfig) -> Result<PrivateKeySigner> {
-        let profile_name = &config.system.active_profile;
-        let hex_key = Self::retrieve_key(profile_name)?;
+        Self::load_signer(Self::active_profile(config))
+    }
+
+    /// Load the signer for `profile` from the OS keyring.
+    pub fn load_signer(profile: &str) -> Result<PrivateKeySigner> {
+        Self::load_signer_with(&OsKeyring, profile)
+    }
+
+    pub fn load_signer_with(keys: &dyn KeyStore, profile: &str) -> Result<PrivateKeySigner> {
+        let hex_key = keys
+            .get(profile)?
+            .with_context(|| format!("No keyring entry found for profile '{profile}'"))?;
         let signer: PrivateKeySigner = hex_key
             .parse()
             .context("Corrupted key in keyring — invalid hex")?;
@@ -176,3 +502,236 @@ impl AuthManager {
         Ok((name.to_string(), address_str, hex_key))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashMap;
+
+    use crate::profile::WalletProfile;
+
+    #[derive(Default)]
+    struct MemoryKeys {
+        keys: RefCell<HashMap<String, String>>,
+        fail_set: Cell<bool>,
+        fail_delete: Cell<bool>,
+    }
+
+    impl KeyStore for MemoryKeys {
+        fn get(&self, profile: &str) -> Result<Option<String>> {
+            Ok(self.keys.borrow().get(profile).cloned())
+        }
+
+        fn set(&self, profile: &str, hex_key: &str) -> Result<()> {
+            if self.fail_set.get() {
+                bail!("keyring locked");
+            }
+            self.keys
+                .borrow_mut()
+                .insert(profile.into(), hex_key.into());
+            Ok(())
+        }
+
+        fn delete(&self, profile: &str) -> Result<()> {
+            if self.fail_delete.get() {
+                bail!("keyring locked");
+            }
+            self.keys.borrow_mut().remove(profile);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryFiles {
+        store: RefCell<WalletStore>,
+        active: RefCell<String>,
+        fail_save_store: Cell<bool>,
+        fail_set_active: Cell<bool>,
+    }
+
+    impl ProfileFiles for MemoryFiles {
+        fn load_store(&self) -> Result<WalletStore> {
+            Ok(self.store.borrow().clone())
+        }
+
+        fn save_store(&self, store: &WalletStore) -> Result<()> {
+            if self.fail_save_store.get() {
+                bail!("disk full");
+            }
+            *self.store.borrow_mut() = store.clone();
+            Ok(())
+        }
+
+        fn active_profile(&self) -> Result<String> {
+            Ok(self.active.borrow().clone())
+        }
+
+        fn set_active_profile(&self, name: &str) -> Result<()> {
+            if self.fail_set_active.get() {
+                bail!("atlas.json is read-only");
+            }
+            *self.active.borrow_mut() = name.into();
+            Ok(())
+        }
+    }
+
+    /// Profiles `main` (active) and `bot`, each with a key.
+    fn setup() -> (MemoryKeys, MemoryFiles) {
+        let keys = MemoryKeys::default();
+        let files = MemoryFiles::default();
+        for (i, name) in ["main", "bot"].into_iter().enumerate() {
+            keys.set(name, &format!("key-{name}")).unwrap();
+            files.store.borrow_mut().add(WalletProfile {
+                name: name.into(),
+                address: format!("0x{:040}", i + 1),
+                created_at: "2026-01-01T00:00:00Z".into(),
+            });
+        }
+        *files.active.borrow_mut() = "main".into();
+        (keys, files)
+    }
+
+    #[test]
+    fn test_remove_deletes_key_and_entry() {
+        let (keys, files) = setup();
+        let removed = AuthManager::remove_profile_with(&keys, &files, "bot", None).unwrap();
+        assert!(removed.key_deleted);
+        assert_eq!(removed.switched_to, None);
+        assert!(!files.store.borrow().exists("bot"));
+        assert!(keys.get("bot").unwrap().is_none());
+        assert_eq!(*files.active.borrow(), "main");
+    }
+
+    #[test]
+    fn test_remove_active_needs_switch_to() {
+        let (keys, files) = setup();
+        let err = AuthManager::remove_profile_with(&keys, &files, "main", None).unwrap_err();
+        assert!(err.to_string().contains("active profile"), "{err}");
+        assert!(keys.get("main").unwrap().is_some());
+
+        assert!(AuthManager::remove_profile_with(&keys, &files, "main", Some("main")).is_err());
+        assert!(AuthManager::remove_profile_with(&keys, &files, "main", Some("ghost")).is_err());
+
+        let removed = AuthManager::remove_profile_with(&keys, &files, "main", Some("bot")).unwrap();
+        assert_eq!(removed.switched_to.as_deref(), Some("bot"));
+        assert_eq!(*files.active.borrow(), "bot");
+        assert!(!files.store.borrow().exists("main"));
+    }
+
+    #[test]
+    fn test_remove_restores_key_when_store_write_fails() {
+        let (keys, files) = setup();
+        files.fail_save_store.set(true);
+        let err = AuthManager::remove_profile_with(&keys, &files, "main", Some("bot"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("keyring key was restored"), "{err}");
+        assert_eq!(keys.get("main").unwrap().as_deref(), Some("key-main"));
+        assert!(files.store.borrow().exists("main"));
+        assert_eq!(*files.active.borrow(), "main");
+    }
+
+    #[test]
+    fn test_remove_reports_lost_key_when_restore_fails() {
+        let (keys, files) = setup();
+        files.fail_save_store.set(true);
+        keys.fail_set.set(true);
+        let err = AuthManager::remove_profile_with(&keys, &files, "bot", None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("restoring it failed too"), "{err}");
+        assert!(err.contains("atlas profile import bot"), "{err}");
+        assert!(files.store.borrow().exists("bot"));
+    }
+
+    #[test]
+    fn test_remove_leaves_everything_when_key_delete_fails() {
+        let (keys, files) = setup();
+        keys.fail_delete.set(true);
+        assert!(AuthManager::remove_profile_with(&keys, &files, "bot", None).is_err());
+        assert!(files.store.borrow().exists("bot"));
+        assert!(keys.get("bot").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_rename_moves_key_entry_and_active_pointer() {
+        let (keys, files) = setup();
+        let renamed = AuthManager::rename_profile_with(&keys, &files, "main", "trading").unwrap();
+        assert!(renamed.was_active);
+        assert!(renamed.warnings.is_empty());
+        assert_eq!(renamed.address, format!("0x{:040}", 1));
+        assert_eq!(keys.get("trading").unwrap().as_deref(), Some("key-main"));
+        assert!(keys.get("main").unwrap().is_none());
+        assert!(files.store.borrow().exists("trading"));
+        assert!(!files.store.borrow().exists("main"));
+        assert_eq!(*files.active.borrow(), "trading");
+
+        assert!(AuthManager::rename_profile_with(&keys, &files, "trading", "bot").is_err());
+        assert!(AuthManager::rename_profile_with(&keys, &files, "ghost", "x").is_err());
+    }
+
+    #[test]
+    fn test_rename_rolls_back_when_config_write_fails() {
+        let (keys, files) = setup();
+        files.fail_set_active.set(true);
+        let err = AuthManager::rename_profile_with(&keys, &files, "main", "trading").unwrap_err();
+        assert!(
+            format!("{err:#}").contains("'main' is unchanged"),
+            "{err:#}"
+        );
+        assert!(files.store.borrow().exists("main"));
+        assert!(!files.store.borrow().exists("trading"));
+        assert!(keys.get("trading").unwrap().is_none());
+        assert_eq!(keys.get("main").unwrap().as_deref(), Some("key-main"));
+    }
+
+    #[test]
+    fn test_rename_warns_when_old_key_survives() {
+        let (keys, files) = setup();
+        keys.fail_delete.set(true);
+        let renamed = AuthManager::rename_profile_with(&keys, &files, "bot", "sniper").unwrap();
+        assert!(!renamed.was_active);
+        assert_eq!(renamed.warnings.len(), 1);
+        assert!(files.store.borrow().exists("sniper"));
+        assert_eq!(keys.get("sniper").unwrap().as_deref(), Some("key-bot"));
+    }
+
+    #[test]
+    fn test_profile_override_leaves_active_untouched() {
+        let (keys, files) = setup();
+        let signer = PrivateKeySigner::random();
+        keys.set("bot", &hex::encode(signer.credential().to_bytes()))
+            .unwrap();
+        // Any write to the profile files would fail the test
+        files.fail_save_store.set(true);
+        files.fail_set_active.set(true);
+
+        let name = AuthManager::check_profile_with(&files, "bot").unwrap();
+        let loaded = AuthManager::load_signer_with(&keys, &name).unwrap();
+        assert_eq!(loaded.address(), signer.address());
+        assert_eq!(*files.active.borrow(), "main");
+
+        let err = AuthManager::check_profile_with(&files, "b0t").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Profile 'b0t' does not exist. Available: main, bot"
+        );
+        assert!(AuthManager::load_signer_with(&keys, "ghost").is_err());
+    }
+
+    /// The only test that sets the process-wide override.
+    #[test]
+    fn test_active_profile_follows_override() {
+        let (_, files) = setup();
+        let mut config = crate::config::AppConfig::default();
+        config.system.active_profile = "main".into();
+        assert!(AuthManager::override_profile_with(&files, "ghost").is_err());
+        assert_eq!(AuthManager::active_profile(&config), "main");
+
+        AuthManager::override_profile_with(&files, "bot").unwrap();
+        assert_eq!(AuthManager::active_profile(&config), "bot");
+        assert_eq!(config.system.active_profile, "main");
+        assert_eq!(*files.active.borrow(), "main");
+    }
+}
@@ -1,12 +1,127 @@
 //! Atlas Backend API client — used by CLI to access backend services
 //! (CoinGecko, Alchemy, etc.) through the Atlas API gateway.
 
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
 use anyhow::{Context, Result};
+use futures::stream::BoxStream;
+use futures::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::config::AppConfig;
+use crate::db::AtlasDb;
+use crate::error::{AtlasError, AtlasResult};
+
+/// Retries of an idempotent GET after a connect error, timeout, 5xx or 429.
+const DEFAULT_RETRIES: u32 = 2;
+/// Wait before the first retry; doubles with each further one.
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(300);
+/// Longest `Retry-After` worth waiting for instead of failing fast.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(5);
 
 /// Lightweight client for calling the Atlas backend API.
+///
+/// GETs fail with a typed [`AtlasError`]: `ApiKeyRejected` (401/403),
+/// `ApiKeyScopeMissing` (403 naming a scope the key lacks),
+/// `RateLimited` (429), `BackendUnreachable` (connect errors, timeouts,
+/// 5xx) or `Protocol` for anything else the backend answers.
 pub struct BackendClient {
     http: reqwest::Client,
     base_url: String,
+    api_key: Option<String>,
+    max_retries: u32,
+    backoff: Duration,
+}
+
+/// A backend response, fresh or replayed from the local cache.
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub data: serde_json::Value,
+    /// When the replayed copy was fetched; `None` for a fresh response.
+    pub stale_since_ms: Option<i64>,
+}
+
+/// `GET /readyz`: the backend's report on its own dependencies.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Readiness {
+    /// `ready` or `not_ready`.
+    pub status: String,
+    /// By dependency name (`postgres`, `coingecko`).
+    pub checks: BTreeMap<String, DependencyHealth>,
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.status == "ready"
+    }
+}
+
+/// One dependency in a [`Readiness`] report.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DependencyHealth {
+    /// `ok`, `fail` or `skipped`.
+    pub status: String,
+    #[serde(default)]
+    pub latency_ms: Option<u64>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// `GET /atlas-os/keys/self`: the API key the client authenticates with.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KeyInfo {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
+    /// `read`, `trade-analytics` and/or `config-sync`.
+    pub scopes: Vec<String>,
+}
+
+impl KeyInfo {
+    /// Scopes in `needed` this key lacks, with the modules that need each.
+    pub fn missing_scopes(
+        &self,
+        needed: &BTreeMap<&'static str, Vec<&'static str>>,
+    ) -> BTreeMap<&'static str, Vec<&'static str>> {
+        needed
+            .iter()
+            .filter(|(scope, _)| !self.scopes.iter().any(|s| s == *scope))
+            .map(|(scope, modules)| (*scope, modules.clone()))
+            .collect()
+    }
+}
+
+/// Backend scopes this machine uses, each with the features that need it:
+/// `read` for data fetched through the backend (0x quotes, Morpho,
+/// Hyperliquid with `data_via_backend`), `trade-analytics` for 0x gasless
+/// trades, which the backend relays, and `config-sync` once the config has
+/// been synced (`syncs_config`).
+pub fn required_scopes(
+    config: &AppConfig,
+    syncs_config: bool,
+) -> BTreeMap<&'static str, Vec<&'static str>> {
+    let modules = &config.modules;
+    let mut needed: BTreeMap<&'static str, Vec<&'static str>> = BTreeMap::new();
+    let mut need = |scope, module| needed.entry(scope).or_default().push(module);
+    if modules.hyperliquid.enabled && modules.hyperliquid.config.data_via_backend {
+        need("read", "hyperliquid");
+    }
+    if modules.zero_x.enabled {
+        need("read", "zero_x");
+        need("trade-analytics", "zero_x");
+    }
+    if modules.morpho.enabled {
+        need("read", "morpho");
+    }
+    if syncs_config {
+        need("config-sync", "config sync");
+    }
+    needed
 }
 
 impl BackendClient {
@@ -20,45 +135,657 @@ impl BackendClient {
         Self {
             http,
             base_url: api_url.trim_end_matches('/').to_string(),
+            api_key: None,
+            max_retries: DEFAULT_RETRIES,
+            backoff: DEFAULT_BACKOFF,
         }
     }
 
+    /// Atlas API key sent as `Authorization: Bearer` on every request.
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key;
+        self
+    }
+
+    /// How often a failed GET is retried, and the wait before the first retry.
+    pub fn with_retries(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.backoff = backoff;
+        self
+    }
+
     /// Create from the active config.
     pub fn from_config() -> Result<Self> {
-        let _config = crate::workspace::load_config()?;
-        Ok(Self::new("https://api.atlas-os.ai"))
+        let config = crate::workspace::load_config()?;
+        Ok(Self::new("https://api.atlas-os.ai").with_api_key(config.system.api_key))
     }
 
     /// GET a JSON endpoint from the backend.
-    pub async fn get(&self, path: &str, query: &[(&str, &str)]) -> Result<serde_json::Value> {
+    pub async fn get(&self, path: &str, query: &[(&str, &str)]) -> AtlasResult<serde_json::Value> {
+        let resp = self.send_get(path, query).await?;
+        self.json_body(resp).await
+    }
+
+    /// Like [`get`](Self::get), but `None` when the backend answers 404.
+    pub async fn get_optional(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> AtlasResult<Option<serde_json::Value>> {
+        let resp = self.send_get(path, query).await?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        self.json_body(resp).await.map(Some)
+    }
+
+    /// PUT a JSON body, or `None` when the backend answers 409 Conflict.
+    /// Sent once: unlike GETs, writes are never retried.
+    pub async fn put(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> AtlasResult<Option<serde_json::Value>> {
         let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .get(&url)
-            .query(query)
-            .send()
-            .await
-            .with_context(|| {
-                format!("Failed to reach Atlas backend at {url}. Is atlas-server running?")
-            })?;
+        let mut req = self.http.put(&url).json(body);
+        if let Some(ref key) = self.api_key {
+            req = req.header("Authorization", format!("Bearer {key}"));
+        }
+        let resp = req.send().await.map_err(|e| {
+            AtlasError::BackendUnreachable(format!(
+                "Failed to reach Atlas backend at {url}: {}",
+                if e.is_timeout() {
+                    "timed out"
+                } else {
+                    "connection failed"
+                }
+            ))
+        })?;
+        match resp.status() {
+            reqwest::StatusCode::CONFLICT => Ok(None),
+            reqwest::StatusCode::TOO_MANY_REQUESTS => Err(AtlasError::RateLimited(format!(
+                "Atlas backend rate limited {path} (retry after {}s)",
+                retry_after(&resp).unwrap_or(self.backoff).as_secs().max(1)
+            ))),
+            _ => self.json_body(resp).await.map(Some),
+        }
+    }
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let text = resp.text().await.unwrap_or_default();
-            anyhow::bail!("Backend error {status}: {text}");
+    /// Like [`get`](Self::get), keeping the last successful response per
+    /// endpoint in `cache`. With `cached_ok`, an unreachable or rate-limited
+    /// backend is answered from that copy instead of failing.
+    pub async fn get_cached(
+        &self,
+        cache: Option<&AtlasDb>,
+        path: &str,
+        query: &[(&str, &str)],
+        cached_ok: bool,
+    ) -> AtlasResult<CachedResponse> {
+        let key = cache_key(path, query);
+        match self.get(path, query).await {
+            Ok(data) => {
+                if let Some(db) = cache {
+                    let now_ms = chrono::Utc::now().timestamp_millis();
+                    if let Err(e) = db.save_backend_response(&key, &data.to_string(), now_ms) {
+                        tracing::warn!("Failed to cache backend response for {key}: {e}");
+                    }
+                }
+                Ok(CachedResponse {
+                    data,
+                    stale_since_ms: None,
+                })
+            }
+            Err(e @ (AtlasError::BackendUnreachable(_) | AtlasError::RateLimited(_)))
+                if cached_ok =>
+            {
+                let cached = cache.and_then(|db| db.backend_response(&key).ok().flatten());
+                let Some((body, fetched_ms)) = cached else {
+                    return Err(e);
+                };
+                let Ok(data) = serde_json::from_str(&body) else {
+                    return Err(e);
+                };
+                tracing::debug!("Serving cached {key} after: {e}");
+                Ok(CachedResponse {
+                    data,
+                    stale_since_ms: Some(fetched_ms),
+                })
+            }
+            Err(e) => Err(e),
         }
+    }
 
-        resp.json::<serde_json::Value>()
-            .await
-            .context("Failed to parse backend response")
+    /// Send a GET, retrying connect errors, timeouts, 5xx and 429 with
+    /// doubling backoff. Other answers are returned for [`json_body`](Self::json_body).
+    async fn send_get(&self, path: &str, query: &[(&str, &str)]) -> AtlasResult<reqwest::Response> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            let mut req = self.http.get(&url).query(query);
+            if let Some(ref key) = self.api_key {
+                req = req.header("Authorization", format!("Bearer {key}"));
+            }
+            let last = attempt >= self.max_retries;
+            let wait = match req.send().await {
+                Ok(resp) if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                    let wait = retry_after(&resp).unwrap_or(delay);
+                    if last || wait > MAX_RETRY_AFTER {
+                        return Err(AtlasError::RateLimited(format!(
+                            "Atlas backend rate limited {path} (retry after {}s)",
+                            wait.as_secs().max(1)
+                        )));
+                    }
+                    wait
+                }
+                Ok(resp) if resp.status().is_server_error() && !last => delay,
+                Ok(resp) => return Ok(resp),
+                Err(e) if last => {
+                    return Err(AtlasError::BackendUnreachable(format!(
+                        "Failed to reach Atlas backend at {url}: {}",
+                        if e.is_timeout() {
+                            "timed out"
+                        } else {
+                            "connection failed"
+                        }
+                    )));
+                }
+                Err(_) => delay,
+            };
+            tracing::debug!("Retrying GET {url} in {wait:?} (attempt {})", attempt + 1);
+            tokio::time::sleep(wait).await;
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+
+    /// A successful response's JSON body; anything else is a typed error.
+    async fn json_body(&self, resp: reqwest::Response) -> AtlasResult<serde_json::Value> {
+        let status = resp.status();
+        if status.is_success() {
+            return resp
+                .json::<serde_json::Value>()
+                .await
+                .map_err(|e| protocol_error(format!("Failed to parse backend response: {e}")));
+        }
+
+        let text = resp.text().await.unwrap_or_default();
+        Err(self.status_error(status, &text))
+    }
+
+    fn status_error(&self, status: reqwest::StatusCode, text: &str) -> AtlasError {
+        match status.as_u16() {
+            401 | 403 if self.api_key.is_none() => AtlasError::ApiKeyMissing,
+            403 => match serde_json::from_str::<serde_json::Value>(text) {
+                Ok(body) if body["code"] == "SCOPE_MISSING" => AtlasError::ApiKeyScopeMissing(
+                    body["scope"].as_str().unwrap_or("unknown").to_string(),
+                ),
+                _ => AtlasError::ApiKeyRejected(format!("Backend error {status}: {text}")),
+            },
+            401 | 403 => AtlasError::ApiKeyRejected(format!("Backend error {status}: {text}")),
+            500..=599 => AtlasError::BackendUnreachable(format!("Backend error {status}: {text}")),
+            _ => protocol_error(format!("Backend error {status}: {text}")),
+        }
+    }
+
+    /// The key this client authenticates with, and its scopes.
+    pub async fn key_self(&self) -> AtlasResult<KeyInfo> {
+        let body = self.get("/atlas-os/keys/self", &[]).await?;
+        serde_json::from_value(body)
+            .map_err(|e| protocol_error(format!("Failed to parse API key info: {e}")))
+    }
+
+    /// The backend's dependency report. Sent once: a 503 that carries a
+    /// report is the answer, not a failure to retry. Anything else that
+    /// isn't a 200 report is an error, as for [`get`](Self::get).
+    pub async fn readiness(&self) -> AtlasResult<Readiness> {
+        let url = format!("{}/readyz", self.base_url);
+        let resp = self.http.get(&url).send().await.map_err(|e| {
+            AtlasError::BackendUnreachable(format!(
+                "Failed to reach Atlas backend at {url}: {}",
+                if e.is_timeout() {
+                    "timed out"
+                } else {
+                    "connection failed"
+                }
+            ))
+        })?;
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        let report = serde_json::from_str::<Readiness>(&text);
+        match report {
+            Ok(report)
+                if status.is_success() || status == reqwest::StatusCode::SERVICE_UNAVAILABLE =>
+            {
+                Ok(report)
+            }
+            Err(e) if status.is_success() => Err(protocol_error(format!(
+                "Failed to parse backend readiness report: {e}"
+            ))),
+            _ => Err(self.status_error(status, &text)),
+        }
     }
 
     /// Check if the backend is reachable.
     pub async fn health(&self) -> Result<bool> {
-        let url = format!("{}/api/health", self.base_url);
+        let url = format!("{}/healthz", self.base_url);
         match self.http.get(&url).send().await {
             Ok(r) => Ok(r.status().is_success()),
             Err(_) => Ok(false),
         }
     }
+
+    /// Mid prices from the backend's shared Hyperliquid feed (`/ws/prices`),
+    /// one map per upstream tick. `coins` narrows the feed; empty = all.
+    ///
+    /// The stream ends when the backend closes the socket — including when
+    /// it drops this client for falling too far behind.
+    pub async fn stream_prices(
+        &self,
+        coins: &[String],
+    ) -> Result<BoxStream<'static, Result<HashMap<String, Decimal>>>> {
+        let url = format!("{}/ws/prices", ws_base_url(&self.base_url));
+        let mut request = url
+            .as_str()
+            .into_client_request()
+            .context("Invalid backend WebSocket URL")?;
+        if let Some(ref key) = self.api_key {
+            request.headers_mut().insert(
+                "Authorization",
+                HeaderValue::from_str(&format!("Bearer {key}"))?,
+            );
+        }
+        let (mut socket, _) = tokio_tungstenite::connect_async(request)
+            .await
+            .with_context(|| {
+                format!("Failed to reach Atlas backend at {url}. Is atlas-server running?")
+            })?;
+
+        if !coins.is_empty() {
+            let sub = serde_json::json!({"op": "subscribe", "coins": coins});
+            socket
+                .send(Message::text(sub.to_string()))
+                .await
+                .context("Failed to subscribe to backend price stream")?;
+        }
+
+        // Pings are answered by tungstenite while the stream is polled
+        let stream = socket.filter_map(|msg| async move {
+            match msg {
+                Ok(Message::Text(text)) => parse_price_message(&text),
+                Ok(_) => None,
+                Err(e) => Some(Err(anyhow::anyhow!("Backend price stream error: {e}"))),
+            }
+        });
+        Ok(stream.boxed())
+    }
+}
+
+fn protocol_error(message: String) -> AtlasError {
+    AtlasError::Protocol {
+        protocol: "atlas-backend".into(),
+        message,
+    }
+}
+
+/// `Retry-After` given in seconds.
+fn retry_after(resp: &reqwest::Response) -> Option<Duration> {
+    let secs = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(Duration::from_secs(secs))
+}
+
+/// Cache key for a GET: the path plus its query in request order.
+fn cache_key(path: &str, query: &[(&str, &str)]) -> String {
+    if query.is_empty() {
+        return path.to_string();
+    }
+    let query: Vec<String> = query.iter().map(|(k, v)| format!("{k}={v}")).collect();
+    format!("{path}?{}", query.join("&"))
+}
+
+/// `https://host` → `wss://host`, `http://host` → `ws://host`.
+fn ws_base_url(base_url: &str) -> String {
+    if let Some(rest) = base_url.strip_prefix("https://") {
+        format!("wss://{rest}")
+    } else if let Some(rest) = base_url.strip_prefix("http://") {
+        format!("ws://{rest}")
+    } else {
+        base_url.to_string()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum PriceMessage {
+    Mids {
+        mids: HashMap<String, Decimal>,
+    },
+    Error {
+        message: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// `None` for control frames (`pong`, `subscribed`) and unknown message types.
+fn parse_price_message(text: &str) -> Option<Result<HashMap<String, Decimal>>> {
+    match serde_json::from_str::<PriceMessage>(text) {
+        Ok(PriceMessage::Mids { mids }) => Some(Ok(mids)),
+        Ok(PriceMessage::Error { message }) => {
+            Some(Err(anyhow::anyhow!("Backend price stream: {message}")))
+        }
+        Ok(PriceMessage::Other) => None,
+        Err(e) => Some(Err(anyhow::anyhow!("Unexpected price stream message: {e}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Backend stub: `respond(n)` answers the n-th connection, `None` drops it.
+    async fn mock_server(
+        respond: fn(usize) -> Option<(u16, &'static str)>,
+    ) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut sock, _)) = listener.accept().await else {
+                    return;
+                };
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let mut buf = vec![0u8; 4096];
+                let _ = sock.read(&mut buf).await;
+                if let Some((status, body)) = respond(n) {
+                    let resp = format!(
+                        "HTTP/1.1 {status} X\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = sock.write_all(resp.as_bytes()).await;
+                }
+            }
+        });
+        (format!("http://{addr}"), hits)
+    }
+
+    fn client(url: &str) -> BackendClient {
+        BackendClient::new(url)
+            .with_api_key(Some("key".into()))
+            .with_retries(2, Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn test_put_is_sent_once_and_reports_conflicts() {
+        let (url, hits) = mock_server(|n| match n {
+            0 => Some((200, r#"{"version":2}"#)),
+            1 => Some((409, r#"{"code":"VERSION_CONFLICT"}"#)),
+            _ => Some((503, "down")),
+        })
+        .await;
+        let c = client(&url);
+        let body = serde_json::json!({"config": {}});
+        assert_eq!(
+            c.put("/atlas-os/config", &body).await.unwrap(),
+            Some(serde_json::json!({"version": 2}))
+        );
+        assert_eq!(c.put("/atlas-os/config", &body).await.unwrap(), None);
+        assert!(matches!(
+            c.put("/atlas-os/config", &body).await,
+            Err(AtlasError::BackendUnreachable(_))
+        ));
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_key_is_not_retried() {
+        let (url, hits) = mock_server(|_| Some((401, r#"{"error":"invalid key"}"#))).await;
+        let err = client(&url).get("/api/x", &[]).await.unwrap_err();
+        assert!(matches!(err, AtlasError::ApiKeyRejected(_)), "{err:?}");
+        assert_eq!(err.detail().code, "API_KEY_REJECTED");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        let anonymous = client(&url).with_api_key(None);
+        let err = anonymous.get("/api/x", &[]).await.unwrap_err();
+        assert!(matches!(err, AtlasError::ApiKeyMissing), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_key_self_and_missing_scope() {
+        let (url, _) = mock_server(|n| match n {
+            0 => Some((
+                200,
+                r#"{"id":"k1","name":"laptop","prefix":"atl_1234abcd","scopes":["read"],"created_at":"2026-10-01T00:00:00Z","last_used_at":null}"#,
+            )),
+            _ => Some((
+                403,
+                r#"{"error":"This API key lacks the 'config-sync' scope","code":"SCOPE_MISSING","scope":"config-sync","granted":["read"]}"#,
+            )),
+        })
+        .await;
+        let c = client(&url);
+
+        let key = c.key_self().await.unwrap();
+        assert_eq!(key.name, "laptop");
+        assert_eq!(key.scopes, vec!["read"]);
+
+        let err = c.get("/atlas-os/config", &[]).await.unwrap_err();
+        assert!(
+            matches!(&err, AtlasError::ApiKeyScopeMissing(s) if s == "config-sync"),
+            "{err:?}"
+        );
+        assert_eq!(err.detail().code, "API_KEY_SCOPE_MISSING");
+    }
+
+    #[test]
+    fn test_required_scopes_follow_enabled_modules() {
+        let mut config = AppConfig::default();
+        // Hyperliquid alone talks to the exchange directly
+        assert!(required_scopes(&config, false).is_empty());
+        assert_eq!(
+            required_scopes(&config, true)["config-sync"],
+            vec!["config sync"]
+        );
+
+        config.modules.hyperliquid.config.data_via_backend = true;
+        config.modules.zero_x.enabled = true;
+        let needed = required_scopes(&config, false);
+        assert_eq!(needed["read"], vec!["hyperliquid", "zero_x"]);
+        assert_eq!(needed["trade-analytics"], vec!["zero_x"]);
+        assert!(!needed.contains_key("config-sync"));
+
+        let read_only = KeyInfo {
+            id: "k1".into(),
+            name: "ci".into(),
+            prefix: "atl_1234abcd".into(),
+            scopes: vec!["read".into()],
+        };
+        let missing = read_only.missing_scopes(&needed);
+        assert_eq!(missing.keys().collect::<Vec<_>>(), vec![&"trade-analytics"]);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_report_on_503_is_not_an_error() {
+        let (url, hits) = mock_server(|n| match n {
+            0 => Some((
+                503,
+                r#"{"status":"not_ready","checks":{"postgres":{"status":"fail","latency_ms":2000,"error":"SELECT 1 timed out after 2000ms"},"coingecko":{"status":"skipped","latency_ms":null}},"timestamp":"2026-10-16T00:00:00Z"}"#,
+            )),
+            1 => Some((200, r#"{"status":"ready","checks":{"postgres":{"status":"ok","latency_ms":3}}}"#)),
+            _ => Some((503, "Service Unavailable")),
+        })
+        .await;
+        let c = client(&url);
+
+        let report = c.readiness().await.unwrap();
+        assert!(!report.is_ready());
+        let postgres = &report.checks["postgres"];
+        assert_eq!(postgres.status, "fail");
+        assert_eq!(postgres.latency_ms, Some(2000));
+        assert_eq!(
+            postgres.error.as_deref(),
+            Some("SELECT 1 timed out after 2000ms")
+        );
+        assert_eq!(report.checks["coingecko"].latency_ms, None);
+
+        assert!(c.readiness().await.unwrap().is_ready());
+
+        // A 503 without a report (a proxy in front) is the backend being down
+        let err = c.readiness().await.unwrap_err();
+        assert!(matches!(err, AtlasError::BackendUnreachable(_)), "{err:?}");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_retries_then_fails() {
+        let (url, hits) = mock_server(|_| Some((429, "slow down"))).await;
+        let err = client(&url).get("/api/x", &[]).await.unwrap_err();
+        assert!(matches!(err, AtlasError::RateLimited(_)), "{err:?}");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_server_error_retried_until_success() {
+        let (url, hits) = mock_server(|n| match n {
+            0 => Some((503, "")),
+            _ => Some((200, r#"{"ok":1}"#)),
+        })
+        .await;
+        let v = client(&url).get("/api/x", &[]).await.unwrap();
+        assert_eq!(v["ok"], 1);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_connectivity_failures_are_unreachable() {
+        let (url, hits) = mock_server(|_| None).await;
+        let err = client(&url).get("/api/x", &[]).await.unwrap_err();
+        assert!(matches!(err, AtlasError::BackendUnreachable(_)), "{err:?}");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let (url, _) = mock_server(|_| Some((502, "bad gateway"))).await;
+        let err = client(&url).get("/api/x", &[]).await.unwrap_err();
+        assert!(matches!(err, AtlasError::BackendUnreachable(_)), "{err:?}");
+
+        // Nothing listening at all
+        let port = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let err = client(&format!("http://127.0.0.1:{port}"))
+            .get("/api/x", &[])
+            .await
+            .unwrap_err();
+        let json = err.to_json();
+        assert_eq!(json["error"]["code"], "BACKEND_UNREACHABLE");
+        assert!(json["error"]["hints"][0]
+            .as_str()
+            .unwrap()
+            .contains("atlas doctor"));
+    }
+
+    #[tokio::test]
+    async fn test_other_statuses() {
+        let (url, hits) = mock_server(|n| match n {
+            0 => Some((404, "")),
+            1 => Some((400, "bad id")),
+            _ => Some((200, "not json")),
+        })
+        .await;
+        let c = client(&url);
+        assert!(c.get_optional("/api/x", &[]).await.unwrap().is_none());
+        let err = c.get("/api/x", &[]).await.unwrap_err();
+        assert!(err.to_string().contains("Backend error 400"), "{err}");
+        assert!(matches!(
+            c.get("/api/x", &[]).await.unwrap_err(),
+            AtlasError::Protocol { .. }
+        ));
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cached_fallback_when_unreachable() {
+        let (url, _) = mock_server(|n| match n {
+            0 => Some((200, r#"{"data":{"btc":1}}"#)),
+            1..=3 => Some((503, "")),
+            _ => Some((401, "")),
+        })
+        .await;
+        let db = AtlasDb::open_in_memory().unwrap();
+        let c = client(&url);
+        let query = [("vs", "usd")];
+
+        let fresh = c.get_cached(Some(&db), "/g", &query, false).await.unwrap();
+        assert!(fresh.stale_since_ms.is_none());
+        assert!(db.backend_response("/g?vs=usd").unwrap().is_some());
+
+        let stale = c.get_cached(Some(&db), "/g", &query, true).await.unwrap();
+        assert_eq!(stale.data, fresh.data);
+        assert!(stale.stale_since_ms.is_some());
+
+        // A rejected key is never papered over with cached data
+        let err = c
+            .get_cached(Some(&db), "/g", &query, true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AtlasError::ApiKeyRejected(_)), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_without_cache_fails() {
+        let (url, _) = mock_server(|_| None).await;
+        let db = AtlasDb::open_in_memory().unwrap();
+        let c = client(&url);
+        // Nothing cached yet for this endpoint
+        let err = c.get_cached(Some(&db), "/g", &[], true).await.unwrap_err();
+        assert!(matches!(err, AtlasError::BackendUnreachable(_)), "{err:?}");
+
+        db.save_backend_response("/g", r#"{"v":1}"#, 1_700_000_000_000)
+            .unwrap();
+        let err = c.get_cached(Some(&db), "/g", &[], false).await.unwrap_err();
+        assert!(matches!(err, AtlasError::BackendUnreachable(_)), "{err:?}");
+        let cached = c.get_cached(Some(&db), "/g", &[], true).await.unwrap();
+        assert_eq!(cached.stale_since_ms, Some(1_700_000_000_000));
+    }
+
+    #[test]
+    fn test_ws_base_url() {
+        assert_eq!(
+            ws_base_url("https://api.atlas-os.ai"),
+            "wss://api.atlas-os.ai"
+        );
+        assert_eq!(ws_base_url("http://localhost:3001"), "ws://localhost:3001");
+    }
+
+    #[test]
+    fn test_parse_price_message() {
+        let mids = parse_price_message(r#"{"type":"mids","mids":{"BTC":"97000.5"},"ts":1}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(mids["BTC"], Decimal::new(970005, 1));
+
+        assert!(parse_price_message(r#"{"type":"pong"}"#).is_none());
+        assert!(parse_price_message(r#"{"type":"subscribed","coins":["BTC"]}"#).is_none());
+        assert!(
+            parse_price_message(r#"{"type":"error","message":"Unknown op"}"#)
+                .unwrap()
+                .is_err()
+        );
+        assert!(parse_price_message("not json").unwrap().is_err());
+    }
 }
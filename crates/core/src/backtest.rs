@@ -0,0 +1,478 @@
+//! Candle-replay backtester for simple indicator strategies.
+//!
+//! Analysis only — nothing here places orders. Signals are read on each
+//! candle's close and filled at the next candle's open, paying `fee_bps`
+//! per side. Equity starts at 1.0 and is fully allocated to every trade,
+//! without leverage. A position still open after the last candle is closed
+//! at that candle's close.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use ta::indicators::{ExponentialMovingAverage, RelativeStrengthIndex};
+use ta::Next;
+
+/// One OHLC candle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    pub time_ms: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Long,
+    Short,
+}
+
+impl Direction {
+    fn sign(self) -> f64 {
+        match self {
+            Direction::Long => 1.0,
+            Direction::Short => -1.0,
+        }
+    }
+}
+
+/// Built-in strategies. Both are stop-and-reverse: once the first signal
+/// fires the simulation is always long or short.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// Long when RSI crosses up through `lower`, short when it crosses
+    /// down through `upper`.
+    RsiReversion {
+        period: usize,
+        lower: f64,
+        upper: f64,
+    },
+    /// Long when the fast EMA crosses above the slow EMA, short below.
+    EmaCross { fast: usize, slow: usize },
+}
+
+impl Strategy {
+    pub const NAMES: [&'static str; 2] = ["rsi-reversion", "ema-cross"];
+
+    /// Build a strategy from its CLI name. `period` is the RSI period or
+    /// the fast EMA; `slow` is only used by `ema-cross`.
+    pub fn parse(name: &str, period: usize, slow: usize) -> Result<Self> {
+        if period == 0 {
+            bail!("--period must be at least 1");
+        }
+        match name.to_lowercase().as_str() {
+            "rsi-reversion" | "rsi" => Ok(Strategy::RsiReversion {
+                period,
+                lower: 30.0,
+                upper: 70.0,
+            }),
+            "ema-cross" | "ema" => {
+                if slow <= period {
+                    bail!("--slow ({slow}) must be greater than --period ({period})");
+                }
+                Ok(Strategy::EmaCross { fast: period, slow })
+            }
+            _ => bail!("Unknown strategy '{name}'. Use: {}", Self::NAMES.join(", ")),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::RsiReversion { .. } => "rsi-reversion",
+            Strategy::EmaCross { .. } => "ema-cross",
+        }
+    }
+
+    /// Candles before the first signal can be trusted.
+    pub fn warmup(&self) -> usize {
+        match self {
+            Strategy::RsiReversion { period, .. } => *period + 1,
+            Strategy::EmaCross { slow, .. } => *slow,
+        }
+    }
+
+    /// Target direction after each candle's close; `None` = hold.
+    pub fn signals(&self, bars: &[Bar]) -> Result<Vec<Option<Direction>>> {
+        let warmup = self.warmup();
+        let mut out = Vec::with_capacity(bars.len());
+        match *self {
+            Strategy::RsiReversion {
+                period,
+                lower,
+                upper,
+            } => {
+                let mut rsi = RelativeStrengthIndex::new(period)
+                    .map_err(|e| anyhow::anyhow!("RSI init: {e}"))?;
+                let mut prev: Option<f64> = None;
+                for (i, bar) in bars.iter().enumerate() {
+                    let value = rsi.next(bar.close);
+                    let signal = match prev {
+                        Some(p) if i >= warmup && p < lower && value >= lower => {
+                            Some(Direction::Long)
+                        }
+                        Some(p) if i >= warmup && p > upper && value <= upper => {
+                            Some(Direction::Short)
+                        }
+                        _ => None,
+                    };
+                    out.push(signal);
+                    prev = Some(value);
+                }
+            }
+            Strategy::EmaCross { fast, slow } => {
+                let mut fast_ema = ExponentialMovingAverage::new(fast)
+                    .map_err(|e| anyhow::anyhow!("EMA init: {e}"))?;
+                let mut slow_ema = ExponentialMovingAverage::new(slow)
+                    .map_err(|e| anyhow::anyhow!("EMA init: {e}"))?;
+                let mut prev_above: Option<bool> = None;
+                for (i, bar) in bars.iter().enumerate() {
+                    let above = fast_ema.next(bar.close) > slow_ema.next(bar.close);
+                    let signal = match prev_above {
+                        Some(was) if i >= warmup && was != above => Some(if above {
+                            Direction::Long
+                        } else {
+                            Direction::Short
+                        }),
+                        _ => None,
+                    };
+                    out.push(signal);
+                    prev_above = Some(above);
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// How a simulated trade ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitReason {
+    /// An opposite signal.
+    Signal,
+    /// Still open at the last candle.
+    EndOfData,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimTrade {
+    pub direction: Direction,
+    pub entry_time_ms: u64,
+    pub entry_price: f64,
+    pub exit_time_ms: u64,
+    pub exit_price: f64,
+    /// Net of entry and exit fees, in percent.
+    pub return_pct: f64,
+    pub exit_reason: ExitReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EquityPoint {
+    pub time_ms: u64,
+    pub equity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BacktestReport {
+    pub trades: Vec<SimTrade>,
+    /// Final equity vs. starting equity, in percent.
+    pub total_return_pct: f64,
+    /// Percent of trades that made money. 0 when there were no trades.
+    pub win_rate_pct: f64,
+    /// Sum of winning returns / sum of losing returns. `None` with no losers.
+    pub profit_factor: Option<f64>,
+    /// Largest peak-to-trough equity drop, in percent.
+    pub max_drawdown_pct: f64,
+    /// Mark-to-market equity at each candle close.
+    pub equity_curve: Vec<EquityPoint>,
+}
+
+struct OpenPosition {
+    direction: Direction,
+    entry_time_ms: u64,
+    entry_price: f64,
+    /// Equity before the entry fee, so a trade's return includes both fees.
+    equity_before_entry: f64,
+    /// Equity after paying the entry fee.
+    equity_at_entry: f64,
+}
+
+impl OpenPosition {
+    fn value_at(&self, price: f64) -> f64 {
+        self.equity_at_entry * (1.0 + self.direction.sign() * (price / self.entry_price - 1.0))
+    }
+}
+
+/// Run `strategy` over `bars`.
+pub fn run(bars: &[Bar], strategy: &Strategy, fee_bps: f64) -> Result<BacktestReport> {
+    if bars.len() <= strategy.warmup() {
+        bail!(
+            "Need more than {} candles for {} (got {})",
+            strategy.warmup(),
+            strategy.name(),
+            bars.len()
+        );
+    }
+    let signals = strategy.signals(bars)?;
+    Ok(simulate(bars, &signals, fee_bps))
+}
+
+/// Replay `signals` (one per bar) over `bars`. A signal on bar `i` fills at
+/// the open of bar `i + 1`; a signal on the last bar is ignored.
+pub fn simulate(bars: &[Bar], signals: &[Option<Direction>], fee_bps: f64) -> BacktestReport {
+    let fee = fee_bps / 10_000.0;
+    let mut equity = 1.0;
+    let mut position: Option<OpenPosition> = None;
+    let mut pending: Option<Direction> = None;
+    let mut trades = Vec::new();
+    let mut equity_curve = Vec::with_capacity(bars.len());
+
+    for (i, bar) in bars.iter().enumerate() {
+        // Fill yesterday's signal at this open
+        if let Some(target) = pending.take() {
+            if position.as_ref().map(|p| p.direction) != Some(target) {
+                if let Some(pos) = position.take() {
+                    equity = pos.value_at(bar.open) * (1.0 - fee);
+                    trades.push(close_trade(
+                        &pos,
+                        bar.time_ms,
+                        bar.open,
+                        equity,
+                        ExitReason::Signal,
+                    ));
+                }
+                let equity_before_entry = equity;
+                equity *= 1.0 - fee;
+                position = Some(OpenPosition {
+                    direction: target,
+                    entry_time_ms: bar.time_ms,
+                    entry_price: bar.open,
+                    equity_before_entry,
+                    equity_at_entry: equity,
+                });
+            }
+        }
+
+        let marked = position.as_ref().map_or(equity, |p| p.value_at(bar.close));
+        equity_curve.push(EquityPoint {
+            time_ms: bar.time_ms,
+            equity: marked,
+        });
+
+        if i + 1 < bars.len() {
+            pending = signals.get(i).copied().flatten();
+        }
+    }
+
+    if let (Some(pos), Some(last)) = (position.take(), bars.last()) {
+        equity = pos.value_at(last.close) * (1.0 - fee);
+        trades.push(close_trade(
+            &pos,
+            last.time_ms,
+            last.close,
+            equity,
+            ExitReason::EndOfData,
+        ));
+        if let Some(point) = equity_curve.last_mut() {
+            point.equity = equity;
+        }
+    }
+
+    let wins = trades.iter().filter(|t| t.return_pct > 0.0).count();
+    let gross_profit: f64 = trades.iter().map(|t| t.return_pct.max(0.0)).sum();
+    let gross_loss: f64 = trades.iter().map(|t| (-t.return_pct).max(0.0)).sum();
+
+    BacktestReport {
+        win_rate_pct: if trades.is_empty() {
+            0.0
+        } else {
+            wins as f64 / trades.len() as f64 * 100.0
+        },
+        profit_factor: (gross_loss > 0.0).then(|| gross_profit / gross_loss),
+        max_drawdown_pct: max_drawdown(&equity_curve),
+        total_return_pct: (equity - 1.0) * 100.0,
+        trades,
+        equity_curve,
+    }
+}
+
+fn close_trade(
+    pos: &OpenPosition,
+    exit_time_ms: u64,
+    exit_price: f64,
+    equity_after: f64,
+    exit_reason: ExitReason,
+) -> SimTrade {
+    SimTrade {
+        direction: pos.direction,
+        entry_time_ms: pos.entry_time_ms,
+        entry_price: pos.entry_price,
+        exit_time_ms,
+        exit_price,
+        return_pct: (equity_after / pos.equity_before_entry - 1.0) * 100.0,
+        exit_reason,
+    }
+}
+
+fn max_drawdown(curve: &[EquityPoint]) -> f64 {
+    let mut peak = f64::MIN;
+    let mut worst = 0.0f64;
+    for point in curve {
+        peak = peak.max(point.equity);
+        if peak > 0.0 {
+            worst = worst.max((peak - point.equity) / peak);
+        }
+    }
+    worst * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(t: u64, open: f64, close: f64) -> Bar {
+        Bar {
+            time_ms: t,
+            open,
+            high: open.max(close),
+            low: open.min(close),
+            close,
+        }
+    }
+
+    /// Bars whose close follows `closes`, each opening at the previous close.
+    fn series(closes: &[f64]) -> Vec<Bar> {
+        let mut prev = closes[0];
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| {
+                let b = bar(i as u64, prev, c);
+                prev = c;
+                b
+            })
+            .collect()
+    }
+
+    fn close_to(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_simulate_fills_at_next_open() {
+        let bars = vec![
+            bar(0, 100.0, 100.0),
+            bar(1, 110.0, 115.0),
+            bar(2, 120.0, 120.0),
+            bar(3, 90.0, 95.0),
+            bar(4, 100.0, 80.0),
+        ];
+        let signals = [
+            Some(Direction::Long),
+            None,
+            Some(Direction::Short),
+            None,
+            None,
+        ];
+        let r = simulate(&bars, &signals, 0.0);
+
+        assert_eq!(r.trades.len(), 2);
+        let long = &r.trades[0];
+        assert_eq!(long.direction, Direction::Long);
+        assert_eq!((long.entry_time_ms, long.entry_price), (1, 110.0));
+        assert_eq!((long.exit_time_ms, long.exit_price), (3, 90.0));
+        assert_eq!(long.exit_reason, ExitReason::Signal);
+        assert!(close_to(long.return_pct, (90.0 / 110.0 - 1.0) * 100.0));
+
+        let short = &r.trades[1];
+        assert_eq!(short.direction, Direction::Short);
+        assert_eq!(short.exit_reason, ExitReason::EndOfData);
+        assert!(close_to(short.return_pct, 100.0 / 9.0));
+
+        assert!(close_to(r.total_return_pct, -100.0 / 11.0));
+        assert!(close_to(r.win_rate_pct, 50.0));
+        assert!(close_to(
+            r.profit_factor.unwrap(),
+            (100.0 / 9.0) / (100.0 * 2.0 / 11.0)
+        ));
+        // Peak 12/11 at bar 2, trough (9/11)·(17/18) at bar 3
+        assert!(close_to(r.max_drawdown_pct, 693.0 / 2376.0 * 100.0));
+        assert_eq!(r.equity_curve.len(), 5);
+        assert!(close_to(r.equity_curve[4].equity, 10.0 / 11.0));
+    }
+
+    #[test]
+    fn test_simulate_charges_fee_per_side() {
+        let bars = series(&[100.0, 100.0, 100.0]);
+        let r = simulate(&bars, &[Some(Direction::Long), None, None], 10.0);
+        assert_eq!(r.trades.len(), 1);
+        assert!(close_to(
+            r.trades[0].return_pct,
+            (0.999 * 0.999 - 1.0) * 100.0
+        ));
+        assert_eq!(r.profit_factor, Some(0.0));
+    }
+
+    #[test]
+    fn test_signal_on_last_bar_is_ignored() {
+        let bars = series(&[100.0, 101.0]);
+        let r = simulate(&bars, &[None, Some(Direction::Long)], 0.0);
+        assert!(r.trades.is_empty());
+        assert_eq!(r.total_return_pct, 0.0);
+        assert!(r.profit_factor.is_none());
+    }
+
+    #[test]
+    fn test_ema_cross_goes_long_on_recovery() {
+        let mut closes: Vec<f64> = (0..40).map(|i| 100.0 - i as f64).collect();
+        closes.extend((1..=80).map(|i| 61.0 + i as f64));
+        let strategy = Strategy::parse("ema-cross", 5, 10).unwrap();
+        let r = run(&series(&closes), &strategy, 0.0).unwrap();
+
+        assert_eq!(r.trades.len(), 1);
+        assert_eq!(r.trades[0].direction, Direction::Long);
+        assert_eq!(r.trades[0].exit_reason, ExitReason::EndOfData);
+        assert!(r.trades[0].entry_time_ms > 40);
+        assert!(r.total_return_pct > 0.0);
+    }
+
+    #[test]
+    fn test_rsi_reversion_signals() {
+        // Down 20, up 20, down 20
+        let mut closes: Vec<f64> = (0..20).map(|i| 100.0 - i as f64).collect();
+        closes.extend((1..=20).map(|i| 81.0 + i as f64));
+        closes.extend((1..=20).map(|i| 101.0 - i as f64));
+        let strategy = Strategy::parse("rsi-reversion", 14, 0).unwrap();
+        let signals = strategy.signals(&series(&closes)).unwrap();
+
+        let fired: Vec<(usize, Direction)> = signals
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.map(|d| (i, d)))
+            .collect();
+        assert_eq!(fired.len(), 2);
+        assert_eq!(fired[0].1, Direction::Long);
+        assert!((20..40).contains(&fired[0].0));
+        assert_eq!(fired[1].1, Direction::Short);
+        assert!((40..60).contains(&fired[1].0));
+    }
+
+    #[test]
+    fn test_strategy_parse() {
+        assert_eq!(
+            Strategy::parse("EMA-CROSS", 12, 26).unwrap(),
+            Strategy::EmaCross { fast: 12, slow: 26 }
+        );
+        assert!(Strategy::parse("ema-cross", 26, 12).is_err());
+        assert!(Strategy::parse("rsi-reversion", 0, 0).is_err());
+        let err = Strategy::parse("macd", 14, 50).unwrap_err().to_string();
+        assert!(err.contains("rsi-reversion"));
+    }
+
+    #[test]
+    fn test_run_needs_enough_candles() {
+        let strategy = Strategy::parse("ema-cross", 5, 50).unwrap();
+        assert!(run(&series(&[100.0; 20]), &strategy, 0.0).is_err());
+    }
+}
@@ -0,0 +1,654 @@
+//! Workspace backup and restore — `atlas workspace backup/restore`.
+//!
+//! A backup is one gzipped tar:
+//!
+//! ```text
+//! manifest.json            format version, contents, profiles with keys
+//! atlas.json
+//! keystore/wallets.json    profile names and addresses (no secrets)
+//! data/atlas.db            consistent snapshot of the SQLite cache
+//! data/*                   other workspace data (address book, ...)
+//! keys/<profile>.json      encrypted Web3 keystores, only with --include-keys
+//! ```
+//!
+//! Private keys only ever leave the keyring as scrypt/AES keystores under
+//! the backup passphrase. Logs and the market metadata cache are not kept.
+
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::profile::WalletStore;
+
+/// Archive layout version; restore refuses anything else.
+pub const BACKUP_FORMAT: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const CONFIG: &str = "atlas.json";
+const WALLETS: &str = "keystore/wallets.json";
+const DATABASE: &str = "data/atlas.db";
+
+/// Files in `data/` that are rebuilt on demand and not worth carrying over.
+fn is_disposable(name: &str) -> bool {
+    name.starts_with("hl-meta-")
+        || name.starts_with("atlas.db")
+        || name.starts_with("export-")
+        || name.starts_with('.')
+}
+
+/// Where profile private keys are kept: the OS keyring in the CLI, a map in
+/// tests.
+pub trait KeyStore {
+    /// Hex-encoded private key for `profile`.
+    fn get_key(&self, profile: &str) -> Result<String>;
+    fn set_key(&self, profile: &str, hex_key: &str) -> Result<()>;
+}
+
+/// The OS keyring, as used by [`crate::AuthManager`].
+pub struct OsKeyring;
+
+impl KeyStore for OsKeyring {
+    fn get_key(&self, profile: &str) -> Result<String> {
+        crate::AuthManager::retrieve_key(profile)
+    }
+
+    fn set_key(&self, profile: &str, hex_key: &str) -> Result<()> {
+        crate::AuthManager::store_key(profile, hex_key)
+    }
+}
+
+/// `manifest.json`, the first entry of every backup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format: u32,
+    /// CLI version that wrote the backup.
+    pub atlas_version: String,
+    pub created_at: String,
+    /// Archive paths, manifest excluded.
+    pub files: Vec<String>,
+    /// Profiles whose encrypted key is in `keys/`.
+    pub keys: Vec<String>,
+}
+
+/// What `create_backup` wrote.
+#[derive(Debug, Clone)]
+pub struct BackupSummary {
+    pub manifest: BackupManifest,
+    pub bytes: u64,
+}
+
+/// What `restore_backup` put back.
+#[derive(Debug, Clone)]
+pub struct RestoreSummary {
+    pub manifest: BackupManifest,
+    pub files_restored: Vec<String>,
+    pub profiles_imported: Vec<String>,
+    /// Profiles restored without a key; they need `atlas profile import`.
+    pub profiles_without_key: Vec<String>,
+}
+
+/// Whether `root` already holds a workspace worth protecting: any profile
+/// or a local database. A freshly initialized workspace does not count.
+pub fn has_workspace(root: &Path) -> bool {
+    let has_profiles = fs::read_to_string(root.join(WALLETS))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<WalletStore>(&raw).ok())
+        .is_some_and(|store| !store.wallets.is_empty());
+    has_profiles || root.join(DATABASE).exists()
+}
+
+/// Write a backup of the workspace at `root` to `archive`.
+///
+/// With `keys`, each profile's private key is exported as an encrypted
+/// keystore under the passphrase. `progress` receives one line per step.
+pub fn create_backup(
+    root: &Path,
+    archive: &Path,
+    keys: Option<(&dyn KeyStore, &str)>,
+    progress: &mut dyn FnMut(&str),
+) -> Result<BackupSummary> {
+    let staging = Staging::new()?;
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+
+    for rel in [CONFIG, WALLETS] {
+        let path = root.join(rel);
+        if !path.exists() {
+            bail!("{} not found — nothing to back up", path.display());
+        }
+        files.push((rel.to_string(), path));
+    }
+
+    let db = root.join(DATABASE);
+    if db.exists() {
+        progress("Snapshotting database");
+        let snapshot = staging.path().join("atlas.db");
+        snapshot_db(&db, &snapshot)?;
+        files.push((DATABASE.to_string(), snapshot));
+    }
+
+    let mut data_files: Vec<(String, PathBuf)> = match fs::read_dir(root.join("data")) {
+        Ok(dir) => dir
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+            .filter_map(|e| {
+                let name = e.file_name().to_str()?.to_string();
+                (!is_disposable(&name)).then(|| (format!("data/{name}"), e.path()))
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    data_files.sort();
+    files.extend(data_files);
+
+    let mut exported = Vec::new();
+    if let Some((store, passphrase)) = keys {
+        let wallets = read_wallets(&root.join(WALLETS))?;
+        let keys_dir = staging.path().join("keys");
+        fs::create_dir_all(&keys_dir)?;
+        for profile in &wallets.wallets {
+            check_profile_name(&profile.name)?;
+            progress(&format!("Encrypting key for profile '{}'", profile.name));
+            let hex_key = store
+                .get_key(&profile.name)
+                .with_context(|| format!("No key for profile '{}'", profile.name))?;
+            let bytes = hex::decode(hex_key.trim().trim_start_matches("0x"))
+                .with_context(|| format!("Corrupted key for profile '{}'", profile.name))?;
+            let file = format!("{}.json", profile.name);
+            PrivateKeySigner::encrypt_keystore(
+                &keys_dir,
+                &mut rand::thread_rng(),
+                bytes,
+                passphrase,
+                Some(&file),
+            )
+            .with_context(|| format!("Failed to encrypt key for '{}'", profile.name))?;
+            files.push((format!("keys/{file}"), keys_dir.join(&file)));
+            exported.push(profile.name.clone());
+        }
+    }
+
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT,
+        atlas_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        files: files.iter().map(|(rel, _)| rel.clone()).collect(),
+        keys: exported,
+    };
+
+    progress(&format!("Writing {}", archive.display()));
+    if let Some(parent) = archive.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let out =
+        File::create(archive).with_context(|| format!("Failed to create {}", archive.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        out.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    let mut tar = tar::Builder::new(GzEncoder::new(out, Compression::default()));
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o600);
+    header.set_cksum();
+    tar.append_data(&mut header, MANIFEST, manifest_json.as_slice())?;
+    for (rel, path) in &files {
+        let mut file =
+            File::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        tar.append_file(rel, &mut file)?;
+    }
+    tar.into_inner()?.finish()?.sync_all()?;
+
+    let bytes = fs::metadata(archive)?.len();
+    Ok(BackupSummary { manifest, bytes })
+}
+
+/// Read only the manifest of a backup, e.g. to ask for a passphrase first.
+pub fn read_manifest(archive: &Path) -> Result<BackupManifest> {
+    let mut tar = open_archive(archive)?;
+    let mut entry = tar.entries()?.next().context("Backup archive is empty")??;
+    if entry.path()?.to_str() != Some(MANIFEST) {
+        bail!("Not an Atlas backup: {} has no manifest", archive.display());
+    }
+    let manifest: BackupManifest =
+        serde_json::from_reader(&mut entry).context("Invalid backup manifest")?;
+    if manifest.format != BACKUP_FORMAT {
+        bail!(
+            "Unsupported backup format {} (this version reads format {BACKUP_FORMAT})",
+            manifest.format
+        );
+    }
+    Ok(manifest)
+}
+
+/// Restore a backup into the workspace at `root`.
+///
+/// Refuses to overwrite an existing workspace (see [`has_workspace`]) unless
+/// `force`. Everything is checked — config, keystores, passphrase — before
+/// the first file is written. `passphrase` is needed when the backup holds
+/// keys.
+pub fn restore_backup(
+    root: &Path,
+    archive: &Path,
+    keys: &dyn KeyStore,
+    passphrase: Option<&str>,
+    force: bool,
+    progress: &mut dyn FnMut(&str),
+) -> Result<RestoreSummary> {
+    let manifest = read_manifest(archive)?;
+    if !force && has_workspace(root) {
+        bail!(
+            "A workspace already exists at {} — pass --force to overwrite it",
+            root.display()
+        );
+    }
+
+    progress(&format!("Unpacking {}", archive.display()));
+    let staging = Staging::new()?;
+    let mut unpacked = Vec::new();
+    for entry in open_archive(archive)?.entries()? {
+        let mut entry = entry?;
+        let rel = entry.path()?.to_str().map(str::to_string);
+        let Some(rel) = rel.filter(|r| is_allowed_entry(r)) else {
+            bail!("Unexpected entry in backup: {}", entry.path()?.display());
+        };
+        // Links could point the restore (and its `fs::copy`) outside the
+        // workspace; a backup only ever holds regular files
+        if !entry.header().entry_type().is_file() {
+            bail!("Backup entry {rel} is not a regular file");
+        }
+        if rel == MANIFEST {
+            continue;
+        }
+        let dest = staging.path().join(&rel);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        entry.unpack(&dest)?;
+        unpacked.push(rel);
+    }
+    for required in [CONFIG, WALLETS] {
+        if !unpacked.iter().any(|r| r == required) {
+            bail!("Backup is missing {required}");
+        }
+    }
+
+    progress("Validating config");
+    let raw = fs::read_to_string(staging.path().join(CONFIG))?;
+    let mut value: serde_json::Value =
+        serde_json::from_str(&raw).context("atlas.json in the backup is not valid JSON")?;
+    config::migrate_envs(&mut value);
+    config::resolve_env(&value, None)
+        .context("atlas.json in the backup does not match the config schema")?;
+    let wallets = read_wallets(&staging.path().join(WALLETS))?;
+
+    // Decrypt every key before touching the workspace, so a wrong
+    // passphrase leaves it as it was
+    let mut decrypted = Vec::new();
+    if !manifest.keys.is_empty() {
+        let passphrase = passphrase.context("This backup contains keys; a passphrase is needed")?;
+        for name in &manifest.keys {
+            check_profile_name(name)?;
+            progress(&format!("Decrypting key for profile '{name}'"));
+            let path = staging.path().join(format!("keys/{name}.json"));
+            let signer = PrivateKeySigner::decrypt_keystore(&path, passphrase).map_err(|e| {
+                anyhow::anyhow!("Cannot decrypt key for '{name}': {e} (wrong passphrase?)")
+            })?;
+            let expected = wallets.find(name).map(|w| w.address.to_lowercase());
+            let actual = signer.address().to_string().to_lowercase();
+            if expected.as_deref() != Some(actual.as_str()) {
+                bail!("Key for profile '{name}' does not match its address in wallets.json");
+            }
+            decrypted.push((name.clone(), hex::encode(signer.credential().to_bytes())));
+        }
+    }
+
+    progress(&format!("Restoring files into {}", root.display()));
+    let mut files_restored = Vec::new();
+    for rel in unpacked.iter().filter(|r| !r.starts_with("keys/")) {
+        let dest = root.join(rel);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        if rel == DATABASE {
+            // Stale WAL files would be replayed over the restored database
+            for suffix in ["-wal", "-shm"] {
+                let _ = fs::remove_file(root.join(format!("{DATABASE}{suffix}")));
+            }
+        }
+        fs::copy(staging.path().join(rel), &dest)
+            .with_context(|| format!("Failed to write {}", dest.display()))?;
+        files_restored.push(rel.clone());
+    }
+
+    let mut profiles_imported = Vec::new();
+    for (name, hex_key) in &decrypted {
+        progress(&format!(
+            "Importing key for profile '{name}' into the keyring"
+        ));
+        keys.set_key(name, hex_key)?;
+        profiles_imported.push(name.clone());
+    }
+    let profiles_without_key = wallets
+        .wallets
+        .iter()
+        .map(|w| w.name.clone())
+        .filter(|name| !profiles_imported.contains(name))
+        .collect();
+
+    Ok(RestoreSummary {
+        manifest,
+        files_restored,
+        profiles_imported,
+        profiles_without_key,
+    })
+}
+
+fn open_archive(archive: &Path) -> Result<tar::Archive<GzDecoder<File>>> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    Ok(tar::Archive::new(GzDecoder::new(file)))
+}
+
+fn read_wallets(path: &Path) -> Result<WalletStore> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&raw).context("Failed to parse wallets.json")
+}
+
+/// Only the layout in the module docs: no absolute paths, no `..`, nothing
+/// outside `data/` and `keys/` besides the fixed files.
+fn is_allowed_entry(rel: &str) -> bool {
+    let path = Path::new(rel);
+    if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return false;
+    }
+    match rel {
+        MANIFEST | CONFIG | WALLETS => true,
+        _ => match rel.split_once('/') {
+            Some(("data", name)) => !name.is_empty() && !name.contains('/'),
+            Some(("keys", name)) => name.ends_with(".json") && !name.contains('/'),
+            _ => false,
+        },
+    }
+}
+
+/// Profile names become file names in `keys/`.
+fn check_profile_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        bail!("Profile name '{name}' can't be backed up: use letters, digits, '-', '_' or '.'");
+    }
+    Ok(())
+}
+
+/// Copy a live SQLite database, WAL included, into a single file.
+fn snapshot_db(db: &Path, dest: &Path) -> Result<()> {
+    let conn =
+        rusqlite::Connection::open_with_flags(db, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .with_context(|| format!("Failed to open {}", db.display()))?;
+    let dest = dest.to_str().context("Non UTF-8 temp path")?;
+    conn.execute("VACUUM INTO ?1", [dest])
+        .context("Failed to snapshot the database")?;
+    Ok(())
+}
+
+/// Scratch directory under the system temp dir, removed on drop.
+struct Staging(PathBuf);
+
+impl Staging {
+    fn new() -> Result<Self> {
+        let dir = std::env::temp_dir().join(format!("atlas-backup-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        Ok(Self(dir))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::profile::WalletProfile;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MemKeyring(RefCell<HashMap<String, String>>);
+
+    impl KeyStore for MemKeyring {
+        fn get_key(&self, profile: &str) -> Result<String> {
+            self.0
+                .borrow()
+                .get(profile)
+                .cloned()
+                .context("no keyring entry")
+        }
+
+        fn set_key(&self, profile: &str, hex_key: &str) -> Result<()> {
+            self.0
+                .borrow_mut()
+                .insert(profile.to_string(), hex_key.to_string());
+            Ok(())
+        }
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("atlas-backup-test-{name}-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("data")).unwrap();
+        fs::create_dir_all(dir.join("keystore")).unwrap();
+        dir
+    }
+
+    /// A workspace with one profile, a database row and an address book.
+    fn seed_workspace(root: &Path, keyring: &MemKeyring) -> PrivateKeySigner {
+        let signer = PrivateKeySigner::random();
+        let config = AppConfig::default().to_json_string().unwrap();
+        fs::write(root.join(CONFIG), config).unwrap();
+        let store = WalletStore {
+            wallets: vec![WalletProfile {
+                name: "main".into(),
+                address: signer.address().to_string(),
+                created_at: "2026-01-01T00:00:00Z".into(),
+            }],
+        };
+        fs::write(root.join(WALLETS), serde_json::to_string(&store).unwrap()).unwrap();
+        let conn = rusqlite::Connection::open(root.join(DATABASE)).unwrap();
+        conn.pragma_update(None, "journal_mode", "WAL").unwrap();
+        conn.execute_batch("CREATE TABLE notes (body TEXT); INSERT INTO notes VALUES ('kept');")
+            .unwrap();
+        fs::write(root.join("data/addresses.json"), "{}").unwrap();
+        fs::write(root.join("data/hl-meta-mainnet.json"), "{}").unwrap();
+        keyring
+            .set_key("main", &hex::encode(signer.credential().to_bytes()))
+            .unwrap();
+        signer
+    }
+
+    #[test]
+    fn test_backup_restore_round_trip_with_keys() {
+        let (src, dst) = (temp_root("src"), temp_root("dst"));
+        let archive = src.join("out/backup.tar.gz");
+        let src_keys = MemKeyring::default();
+        let signer = seed_workspace(&src, &src_keys);
+
+        let mut steps = Vec::new();
+        let backup = create_backup(
+            &src,
+            &archive,
+            Some((&src_keys, "correct horse")),
+            &mut |s| steps.push(s.to_string()),
+        )
+        .unwrap();
+        assert!(backup.bytes > 0);
+        assert_eq!(backup.manifest.keys, ["main"]);
+        assert!(backup.manifest.files.contains(&DATABASE.to_string()));
+        assert!(backup
+            .manifest
+            .files
+            .contains(&"data/addresses.json".to_string()));
+        assert!(!backup.manifest.files.iter().any(|f| f.contains("hl-meta")));
+        assert!(steps.iter().any(|s| s.contains("Encrypting key")));
+        assert_eq!(read_manifest(&archive).unwrap().keys, ["main"]);
+
+        // Wrong passphrase fails before anything is written
+        let dst_keys = MemKeyring::default();
+        let err = restore_backup(&dst, &archive, &dst_keys, Some("wrong"), false, &mut |_| {})
+            .unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+        assert!(!dst.join(CONFIG).exists());
+
+        let summary = restore_backup(
+            &dst,
+            &archive,
+            &dst_keys,
+            Some("correct horse"),
+            false,
+            &mut |_| {},
+        )
+        .unwrap();
+        assert_eq!(summary.profiles_imported, ["main"]);
+        assert!(summary.profiles_without_key.is_empty());
+        assert!(summary.files_restored.contains(&WALLETS.to_string()));
+        assert_eq!(
+            fs::read_to_string(dst.join(CONFIG)).unwrap(),
+            fs::read_to_string(src.join(CONFIG)).unwrap()
+        );
+        let conn = rusqlite::Connection::open(dst.join(DATABASE)).unwrap();
+        let body: String = conn
+            .query_row("SELECT body FROM notes", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(body, "kept");
+        let restored: PrivateKeySigner = dst_keys.get_key("main").unwrap().parse().unwrap();
+        assert_eq!(restored.address(), signer.address());
+
+        // The restored workspace is now protected
+        let err = restore_backup(
+            &dst,
+            &archive,
+            &dst_keys,
+            Some("correct horse"),
+            false,
+            &mut |_| {},
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        restore_backup(
+            &dst,
+            &archive,
+            &dst_keys,
+            Some("correct horse"),
+            true,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        fs::remove_dir_all(&src).unwrap();
+        fs::remove_dir_all(&dst).unwrap();
+    }
+
+    #[test]
+    fn test_backup_without_keys() {
+        let (src, dst) = (temp_root("nokeys-src"), temp_root("nokeys-dst"));
+        let archive = src.join("backup.tar.gz");
+        let src_keys = MemKeyring::default();
+        seed_workspace(&src, &src_keys);
+
+        let backup = create_backup(&src, &archive, None, &mut |_| {}).unwrap();
+        assert!(backup.manifest.keys.is_empty());
+        assert!(!backup.manifest.files.iter().any(|f| f.starts_with("keys/")));
+
+        let dst_keys = MemKeyring::default();
+        let summary = restore_backup(&dst, &archive, &dst_keys, None, false, &mut |_| {}).unwrap();
+        assert!(summary.profiles_imported.is_empty());
+        assert_eq!(summary.profiles_without_key, ["main"]);
+        assert!(dst_keys.0.borrow().is_empty());
+
+        fs::remove_dir_all(&src).unwrap();
+        fs::remove_dir_all(&dst).unwrap();
+    }
+
+    #[test]
+    fn test_restore_rejects_symlink_entry() {
+        let (src, dst) = (temp_root("link-src"), temp_root("link-dst"));
+        let archive = src.join("backup.tar.gz");
+        let src_keys = MemKeyring::default();
+        seed_workspace(&src, &src_keys);
+        let manifest = create_backup(&src, &archive, None, &mut |_| {})
+            .unwrap()
+            .manifest;
+
+        // Same manifest and config, plus an allowed path that is a symlink
+        let out = File::create(&archive).unwrap();
+        let mut tar = tar::Builder::new(GzEncoder::new(out, Compression::default()));
+        let manifest_json = serde_json::to_vec(&manifest).unwrap();
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest_json.len() as u64);
+        header.set_cksum();
+        tar.append_data(&mut header, MANIFEST, manifest_json.as_slice())
+            .unwrap();
+        tar.append_path_with_name(src.join(CONFIG), CONFIG).unwrap();
+        let mut link = tar::Header::new_gnu();
+        link.set_entry_type(tar::EntryType::Symlink);
+        link.set_link_name("/etc/passwd").unwrap();
+        link.set_size(0);
+        link.set_cksum();
+        tar.append_data(&mut link, "data/addresses.json", std::io::empty())
+            .unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+
+        let err = restore_backup(
+            &dst,
+            &archive,
+            &MemKeyring::default(),
+            None,
+            false,
+            &mut |_| {},
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not a regular file"), "{err}");
+        assert!(!dst.join(CONFIG).exists());
+        assert!(!dst.join("data/addresses.json").exists());
+
+        fs::remove_dir_all(&src).unwrap();
+        fs::remove_dir_all(&dst).unwrap();
+    }
+
+    #[test]
+    fn test_archive_entries_are_confined() {
+        assert!(is_allowed_entry("atlas.json"));
+        assert!(is_allowed_entry("data/addresses.json"));
+        assert!(is_allowed_entry("keys/main.json"));
+        assert!(!is_allowed_entry("../atlas.json"));
+        assert!(!is_allowed_entry("/etc/passwd"));
+        assert!(!is_allowed_entry("data/../../x"));
+        assert!(!is_allowed_entry("keys/main.txt"));
+        assert!(!is_allowed_entry("logs/atlas.log"));
+        assert!(check_profile_name("trading-1").is_ok());
+        assert!(check_profile_name("../evil").is_err());
+    }
+}
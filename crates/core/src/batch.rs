@@ -0,0 +1,307 @@
+//! Concurrent multi-coin fetches.
+//!
+//! Commands that take a list of coins should not await one round-trip per
+//! coin. Prefer one bulk call; when per-coin calls are unavoidable, run them
+//! with bounded concurrency so a long list doesn't trip exchange rate limits.
+
+use std::future::Future;
+
+use futures::stream::{self, StreamExt};
+
+use crate::error::{AtlasError, AtlasResult};
+use crate::traits::PerpModule;
+use crate::types::Ticker;
+
+/// Max in-flight per-coin requests.
+pub const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Run `f` over `items` with at most `limit` futures in flight.
+/// Results come back in input order.
+pub async fn map_concurrent<T, R, F, Fut>(
+    items: impl IntoIterator<Item = T>,
+    limit: usize,
+    f: F,
+) -> Vec<R>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = R>,
+{
+    stream::iter(items)
+        .map(f)
+        .buffered(limit.max(1))
+        .collect()
+        .await
+}
+
+/// Tickers for `symbols`, one result per symbol in input order.
+///
+/// Uses a single `all_tickers` call. If that fails, falls back to per-coin
+/// `ticker` calls run concurrently. An unknown coin yields an error entry
+/// instead of failing the whole batch.
+pub async fn tickers_for(
+    perp: &dyn PerpModule,
+    symbols: &[String],
+) -> Vec<(String, AtlasResult<Ticker>)> {
+    let symbols: Vec<String> = symbols.iter().map(|s| s.to_uppercase()).collect();
+
+    match perp.all_tickers().await {
+        Ok(all) => symbols
+            .into_iter()
+            .map(|sym| {
+                let found = perp.resolve_symbol(&sym).and_then(|name| {
+                    all.iter()
+                        .find(|t| t.symbol == name)
+                        .cloned()
+                        .ok_or_else(|| AtlasError::asset_not_found(name))
+                });
+                (sym, found)
+            })
+            .collect(),
+        Err(_) => {
+            map_concurrent(symbols, MAX_CONCURRENT_REQUESTS, |sym| async move {
+                let res = perp.ticker(&sym).await;
+                (sym, res)
+            })
+            .await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+    use async_trait::async_trait;
+    use rust_decimal::Decimal;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    /// Perp module quoting `coins`. `all_tickers` fails unless `bulk`; each
+    /// `ticker` call takes 50ms and is counted while in flight.
+    struct MockPerp {
+        coins: Vec<&'static str>,
+        bulk: bool,
+        ticker_calls: AtomicUsize,
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl MockPerp {
+        fn new(coins: Vec<&'static str>, bulk: bool) -> Self {
+            Self {
+                coins,
+                bulk,
+                ticker_calls: AtomicUsize::new(0),
+                in_flight: AtomicUsize::new(0),
+                peak: AtomicUsize::new(0),
+            }
+        }
+
+        fn quote(&self, symbol: &str) -> AtlasResult<Ticker> {
+            let i = self
+                .coins
+                .iter()
+                .position(|c| *c == symbol)
+                .ok_or_else(|| AtlasError::asset_not_found(symbol))?;
+            Ok(Ticker {
+                symbol: symbol.into(),
+                protocol: Protocol::Hyperliquid,
+                mid_price: Decimal::from(i as u64 + 1),
+                best_bid: None,
+                best_ask: None,
+                volume_24h: None,
+                change_24h_pct: None,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl PerpModule for MockPerp {
+        fn protocol(&self) -> Protocol {
+            Protocol::Hyperliquid
+        }
+
+        async fn ticker(&self, symbol: &str) -> AtlasResult<Ticker> {
+            self.ticker_calls.fetch_add(1, Ordering::SeqCst);
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.quote(symbol)
+        }
+
+        async fn all_tickers(&self) -> AtlasResult<Vec<Ticker>> {
+            if !self.bulk {
+                return Err(AtlasError::Network("all_tickers unavailable".into()));
+            }
+            self.coins.iter().map(|c| self.quote(c)).collect()
+        }
+
+        async fn markets(&self) -> AtlasResult<Vec<Market>> {
+            unimplemented!()
+        }
+        async fn candles(&self, _: &str, _: &str, _: usize) -> AtlasResult<Vec<Candle>> {
+            unimplemented!()
+        }
+        async fn funding(&self, _: &str) -> AtlasResult<Vec<FundingRate>> {
+            unimplemented!()
+        }
+        async fn orderbook(&self, _: &str, _: usize) -> AtlasResult<OrderBook> {
+            unimplemented!()
+        }
+        async fn market_order(
+            &self,
+            _: &str,
+            _: Side,
+            _: Decimal,
+            _: Option<f64>,
+        ) -> AtlasResult<OrderResult> {
+            unimplemented!()
+        }
+        async fn limit_order(
+            &self,
+            _: &str,
+            _: Side,
+            _: Decimal,
+            _: Decimal,
+            _: &LimitOptions,
+        ) -> AtlasResult<OrderResult> {
+            unimplemented!()
+        }
+        async fn close_position(
+            &self,
+            _: &str,
+            _: Option<Decimal>,
+            _: Option<f64>,
+        ) -> AtlasResult<OrderResult> {
+            unimplemented!()
+        }
+        async fn cancel_order(&self, _: &str, _: &str) -> AtlasResult<()> {
+            unimplemented!()
+        }
+        async fn cancel_all(&self, _: &str) -> AtlasResult<u32> {
+            unimplemented!()
+        }
+        async fn open_orders(&self) -> AtlasResult<Vec<Order>> {
+            unimplemented!()
+        }
+        async fn positions(&self) -> AtlasResult<Vec<Position>> {
+            unimplemented!()
+        }
+        async fn fills(&self) -> AtlasResult<Vec<Fill>> {
+            unimplemented!()
+        }
+        async fn balances(&self) -> AtlasResult<Vec<Balance>> {
+            unimplemented!()
+        }
+        async fn set_leverage(&self, _: &str, _: u32, _: bool) -> AtlasResult<()> {
+            unimplemented!()
+        }
+        async fn update_margin(&self, _: &str, _: Decimal) -> AtlasResult<()> {
+            unimplemented!()
+        }
+        async fn transfer(&self, _: Decimal, _: &str) -> AtlasResult<String> {
+            unimplemented!()
+        }
+    }
+
+    fn symbols(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// (symbol, mid) per entry, `None` for error entries.
+    fn mids(out: &[(String, AtlasResult<Ticker>)]) -> Vec<(&str, Option<Decimal>)> {
+        out.iter()
+            .map(|(s, t)| (s.as_str(), t.as_ref().ok().map(|t| t.mid_price)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_tickers_for_uses_one_bulk_call() {
+        let perp = MockPerp::new(vec!["BTC", "ETH", "SOL"], true);
+        let out = tickers_for(&perp, &symbols(&["sol", "NOPE", "btc"])).await;
+        assert_eq!(
+            mids(&out),
+            vec![
+                ("SOL", Some(Decimal::from(3))),
+                ("NOPE", None),
+                ("BTC", Some(Decimal::ONE)),
+            ]
+        );
+        assert_eq!(perp.ticker_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_tickers_for_falls_back_to_concurrent_calls() {
+        let coins = vec!["BTC", "ETH", "SOL", "ARB", "OP", "DOGE", "AVAX", "LINK"];
+        let perp = MockPerp::new(coins.clone(), false);
+        // Reverse order, with an unknown coin in the middle
+        let mut wanted: Vec<&str> = coins.iter().rev().copied().collect();
+        wanted.insert(4, "nope");
+
+        let start = Instant::now();
+        let out = tickers_for(&perp, &symbols(&wanted)).await;
+        let elapsed = start.elapsed();
+
+        let names: Vec<&str> = out.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["LINK", "AVAX", "DOGE", "OP", "NOPE", "ARB", "SOL", "ETH", "BTC"]
+        );
+        assert_eq!(out[0].1.as_ref().unwrap().mid_price, Decimal::from(8));
+        assert!(out[4].1.is_err());
+        assert_eq!(out[8].1.as_ref().unwrap().mid_price, Decimal::ONE);
+
+        // 9 calls, at most 8 wide: two 50ms waves, not nine
+        assert_eq!(perp.ticker_calls.load(Ordering::SeqCst), 9);
+        assert_eq!(perp.peak.load(Ordering::SeqCst), MAX_CONCURRENT_REQUESTS);
+        assert!(elapsed < Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn test_map_concurrent_runs_in_parallel() {
+        // 8 simulated 50ms requests should take ~one round-trip, not eight
+        let start = Instant::now();
+        let out = map_concurrent(0..8, MAX_CONCURRENT_REQUESTS, |i| async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            i * 2
+        })
+        .await;
+        assert!(start.elapsed() < Duration::from_millis(200));
+        assert_eq!(out, vec![0, 2, 4, 6, 8, 10, 12, 14]);
+    }
+
+    #[tokio::test]
+    async fn test_map_concurrent_respects_limit() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let (in_flight, peak) = (&in_flight, &peak);
+        let start = Instant::now();
+        map_concurrent(0..12, 4, |_| async move {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        })
+        .await;
+        assert_eq!(peak.load(Ordering::SeqCst), 4);
+        // 12 calls at 4 wide = 3 waves
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[tokio::test]
+    async fn test_map_concurrent_keeps_partial_failures() {
+        let out: Vec<Result<u32, String>> =
+            map_concurrent(["BTC", "NOPE", "ETH"], 8, |c| async move {
+                if c == "NOPE" {
+                    Err(format!("unknown {c}"))
+                } else {
+                    Ok(1)
+                }
+            })
+            .await;
+        assert!(out[0].is_ok());
+        assert!(out[1].is_err());
+        assert!(out[2].is_ok());
+    }
+}
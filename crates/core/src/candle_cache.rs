@@ -0,0 +1,373 @@
+//! Closed candles cached in the local DB, so TA commands re-run with new
+//! parameters only fetch what changed since the last run.
+//!
+//! A candle never changes once its interval has ended. Per series the cache
+//! keeps the contiguous span of open times it has fetched (`candle_coverage`);
+//! a request fetches only the part of its window outside that span — at most
+//! one range before it and one after. The still-forming latest candle is
+//! always fetched and never stored.
+//!
+//! Coverage records what was asked for, not what came back: if the exchange
+//! dropped candles from a response, the stored series has holes. Those are
+//! found with [`crate::candle_gaps`] on every read and refetched window by
+//! window; candles the exchange really doesn't have stay missing.
+
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::Result;
+use tracing::{debug, warn};
+
+use crate::candle_gaps;
+use crate::db::AtlasDb;
+use crate::error::AtlasResult;
+use crate::timeframe::Timeframe;
+use crate::types::Candle;
+
+/// Longest cached interval. Up to a day, candles open at multiples of the
+/// interval since the epoch; longer ones follow exchange calendars.
+const MAX_CACHED_INTERVAL_MS: u64 = 86_400_000;
+
+/// Candle cache on the local database, opened on first use.
+#[derive(Default)]
+pub struct CandleCache {
+    db: OnceLock<Option<Mutex<AtlasDb>>>,
+}
+
+impl CandleCache {
+    /// Cache on `~/.atlas-os/data/atlas.db`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache on an already-open database.
+    pub fn with_db(db: AtlasDb) -> Self {
+        Self {
+            db: OnceLock::from(Some(Mutex::new(db))),
+        }
+    }
+
+    /// Whether candles of `tf` are cached; longer intervals always go to
+    /// the exchange.
+    pub fn is_cacheable(tf: Timeframe) -> bool {
+        tf.duration_ms() <= MAX_CACHED_INTERVAL_MS
+    }
+
+    fn db<T>(&self, f: impl FnOnce(&AtlasDb) -> Result<T>) -> Result<T> {
+        let db = self.db.get_or_init(|| match AtlasDb::open() {
+            Ok(db) => Some(Mutex::new(db)),
+            Err(e) => {
+                warn!("Candle cache unavailable: {e}");
+                None
+            }
+        });
+        let db = db
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("candle cache unavailable"))?
+            .lock()
+            .map_err(|_| anyhow::anyhow!("candle cache lock poisoned"))?;
+        f(&db)
+    }
+
+    /// The `limit` candle slots ending with the one open at `now_ms`, oldest
+    /// first. Slots the exchange has no candle for (before a listing) are
+    /// skipped, so fewer may come back.
+    ///
+    /// `fetch(start_ms, end_ms)` returns every candle opened in that range
+    /// and is called only for what the cache lacks plus the forming candle,
+    /// and once per gap in the stored series. If the database fails, the
+    /// whole window is fetched instead.
+    pub async fn candles<F, Fut>(
+        &self,
+        source: &str,
+        coin: &str,
+        tf: Timeframe,
+        limit: usize,
+        now_ms: u64,
+        mut fetch: F,
+    ) -> AtlasResult<Vec<Candle>>
+    where
+        F: FnMut(u64, u64) -> Fut,
+        Fut: Future<Output = AtlasResult<Vec<Candle>>>,
+    {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let interval = tf.as_str();
+        let iv = tf.duration_ms();
+        let forming = now_ms / iv * iv;
+        let first = forming.saturating_sub((limit as u64 - 1) * iv);
+
+        let coverage = match self.db(|db| db.candle_coverage(source, coin, interval)) {
+            Ok(coverage) => coverage,
+            Err(e) => {
+                debug!("Candle cache skipped: {e}");
+                return fetch(first, now_ms).await;
+            }
+        };
+        // A span that neither overlaps nor touches the window is replaced
+        let span = coverage.filter(|&(from, to)| from <= forming && to + iv >= first);
+
+        let mut ranges = Vec::with_capacity(2);
+        if let Some((from, _)) = span.filter(|&(from, _)| first < from) {
+            ranges.push((first, from - iv));
+        }
+        let tail_start = span.map_or(first, |(_, to)| (to + iv).max(first));
+        ranges.push((tail_start, now_ms));
+
+        let mut fetched = Vec::new();
+        for (start, end) in ranges {
+            fetched.extend(fetch(start, end).await?);
+        }
+        let (closed, latest): (Vec<Candle>, Vec<Candle>) = fetched
+            .into_iter()
+            .filter(|c| c.open_time_ms >= first)
+            .partition(|c| c.open_time_ms < forming);
+        debug!(
+            coin,
+            interval,
+            fetched = closed.len(),
+            "Candle cache refreshed"
+        );
+
+        let cached = self.db(|db| {
+            if forming >= first + iv {
+                let from = span.map_or(first, |(from, _)| from.min(first));
+                db.upsert_candles(source, coin, interval, &closed)?;
+                db.set_candle_coverage(source, coin, interval, iv, from, forming - iv)?;
+            }
+            db.query_candles(source, coin, interval, first, forming.saturating_sub(1))
+        });
+        let mut candles = match cached {
+            Ok(candles) => candles,
+            Err(e) => {
+                warn!("Failed to update candle cache: {e}");
+                return fetch(first, now_ms).await;
+            }
+        };
+
+        let open_times: Vec<u64> = candles.iter().map(|c| c.open_time_ms).collect();
+        let gaps = candle_gaps::find_gaps(&open_times, iv, forming).gaps;
+        if !gaps.is_empty() {
+            let mut repaired = Vec::new();
+            for gap in &gaps {
+                repaired.extend(fetch(gap.from_ms, gap.to_ms).await?);
+            }
+            repaired.retain(|c| c.open_time_ms >= first && c.open_time_ms < forming);
+            debug!(
+                coin,
+                interval,
+                gaps = gaps.len(),
+                repaired = repaired.len(),
+                "Candle gaps refetched"
+            );
+            if !repaired.is_empty() {
+                let requeried = self.db(|db| {
+                    db.upsert_candles(source, coin, interval, &repaired)?;
+                    db.query_candles(source, coin, interval, first, forming - 1)
+                });
+                match requeried {
+                    Ok(requeried) => candles = requeried,
+                    Err(e) => {
+                        warn!("Failed to store refetched candles: {e}");
+                        candles.extend(repaired);
+                        candle_gaps::dedup(&mut candles);
+                    }
+                }
+            }
+        }
+
+        candles.extend(latest.into_iter().filter(|c| c.open_time_ms == forming));
+        Ok(candles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::cell::RefCell;
+
+    const HOUR: u64 = 3_600_000;
+
+    fn candle(open_time_ms: u64, close: i64) -> Candle {
+        Candle {
+            open_time_ms,
+            open: Decimal::ONE,
+            high: Decimal::from(close),
+            low: Decimal::ONE,
+            close: Decimal::from(close),
+            volume: Decimal::ONE,
+            trades: None,
+        }
+    }
+
+    /// Mock exchange: hourly candles from `listed`, the forming one's close
+    /// set by `tick` so each call sees a different live price.
+    struct MockExchange {
+        listed: u64,
+        tick: RefCell<i64>,
+        calls: RefCell<Vec<(u64, u64)>>,
+        /// Open times left out of the next response that spans them.
+        dropped: RefCell<Vec<u64>>,
+        /// Open times the exchange never has.
+        missing: Vec<u64>,
+    }
+
+    impl MockExchange {
+        fn new(listed: u64) -> Self {
+            Self {
+                listed,
+                tick: RefCell::new(100),
+                calls: RefCell::new(Vec::new()),
+                dropped: RefCell::new(Vec::new()),
+                missing: Vec::new(),
+            }
+        }
+
+        async fn range(&self, start: u64, end: u64, now: u64) -> AtlasResult<Vec<Candle>> {
+            self.calls.borrow_mut().push((start, end));
+            let forming = now / HOUR * HOUR;
+            let from = start.max(self.listed).div_ceil(HOUR) * HOUR;
+            let mut dropped = self.dropped.borrow_mut();
+            Ok((from..=end.min(forming))
+                .step_by(HOUR as usize)
+                .filter(|t| !self.missing.contains(t))
+                .filter(|t| match dropped.iter().position(|d| d == t) {
+                    Some(i) => {
+                        dropped.remove(i);
+                        false
+                    }
+                    None => true,
+                })
+                .map(|t| {
+                    let close = if t == forming {
+                        *self.tick.borrow()
+                    } else {
+                        (t / HOUR) as i64
+                    };
+                    candle(t, close)
+                })
+                .collect())
+        }
+    }
+
+    fn cache() -> CandleCache {
+        CandleCache::with_db(AtlasDb::open_in_memory().unwrap())
+    }
+
+    async fn load(cache: &CandleCache, mock: &MockExchange, limit: usize, now: u64) -> Vec<Candle> {
+        cache
+            .candles("hyperliquid", "ETH", Timeframe::H1, limit, now, |s, e| {
+                mock.range(s, e, now)
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_second_call_fetches_only_forming_candle() {
+        let cache = cache();
+        let mock = MockExchange::new(0);
+        let now = 1_000 * HOUR + 1_234;
+
+        let first = load(&cache, &mock, 200, now).await;
+        assert_eq!(first.len(), 200);
+        assert_eq!(first.last().unwrap().close, Decimal::from(100));
+        assert_eq!(mock.calls.borrow().len(), 1);
+
+        // Same TA call again: no history is requested, only the live candle
+        mock.calls.borrow_mut().clear();
+        *mock.tick.borrow_mut() = 101;
+        let second = load(&cache, &mock, 200, now + 5_000).await;
+        assert_eq!(*mock.calls.borrow(), [(1_000 * HOUR, now + 5_000)]);
+        assert_eq!(second.len(), 200);
+        let key = |c: &Candle| (c.open_time_ms, c.close);
+        assert!(first[..199]
+            .iter()
+            .map(key)
+            .eq(second[..199].iter().map(key)));
+        // The forming candle was refetched, not served stale from the cache
+        assert_eq!(second.last().unwrap().close, Decimal::from(101));
+    }
+
+    #[tokio::test]
+    async fn test_forming_candle_never_cached() {
+        let cache = cache();
+        let mock = MockExchange::new(0);
+        let now = 1_000 * HOUR + 1_234;
+        load(&cache, &mock, 10, now).await;
+
+        // An hour later the previously forming candle has closed: it is
+        // fetched with its final values instead of the snapshot seen live.
+        mock.calls.borrow_mut().clear();
+        let later = load(&cache, &mock, 10, now + HOUR).await;
+        assert_eq!(*mock.calls.borrow(), [(1_000 * HOUR, now + HOUR)]);
+        assert_eq!(later[8].open_time_ms, 1_000 * HOUR);
+        assert_eq!(later[8].close, Decimal::from(1_000));
+    }
+
+    #[tokio::test]
+    async fn test_fetches_only_missing_ranges() {
+        let cache = cache();
+        let mock = MockExchange::new(0);
+        let now = 1_000 * HOUR;
+        load(&cache, &mock, 50, now).await;
+
+        // A deeper lookback fetches only the older part plus the live candle
+        mock.calls.borrow_mut().clear();
+        let deeper = load(&cache, &mock, 120, now).await;
+        assert_eq!(deeper.len(), 120);
+        assert_eq!(
+            *mock.calls.borrow(),
+            [(881 * HOUR, 950 * HOUR), (1_000 * HOUR, now)]
+        );
+        assert!(deeper
+            .windows(2)
+            .all(|w| w[1].open_time_ms - w[0].open_time_ms == HOUR));
+    }
+
+    #[tokio::test]
+    async fn test_history_before_listing_not_refetched() {
+        let cache = cache();
+        let now = 1_000 * HOUR;
+        let mock = MockExchange::new(now - 29 * HOUR);
+        assert_eq!(load(&cache, &mock, 100, now).await.len(), 30);
+
+        mock.calls.borrow_mut().clear();
+        assert_eq!(load(&cache, &mock, 100, now).await.len(), 30);
+        assert_eq!(*mock.calls.borrow(), [(now, now)]);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_candles_refetched() {
+        let cache = cache();
+        let mut mock = MockExchange::new(0);
+        mock.missing = vec![960 * HOUR];
+        *mock.dropped.borrow_mut() = vec![970 * HOUR, 971 * HOUR, 990 * HOUR];
+        let now = 1_000 * HOUR + 1_234;
+
+        let candles = load(&cache, &mock, 50, now).await;
+        // Each hole is asked for once; the one the exchange never had stays
+        assert_eq!(
+            *mock.calls.borrow(),
+            [
+                (951 * HOUR, now),
+                (960 * HOUR, 960 * HOUR),
+                (970 * HOUR, 971 * HOUR),
+                (990 * HOUR, 990 * HOUR)
+            ]
+        );
+        assert_eq!(candles.len(), 49);
+        assert!(candles.iter().all(|c| c.open_time_ms != 960 * HOUR));
+        assert!(candles.iter().any(|c| c.open_time_ms == 990 * HOUR));
+
+        // Repaired candles were stored: only the real hole is retried
+        mock.calls.borrow_mut().clear();
+        assert_eq!(load(&cache, &mock, 50, now).await.len(), 49);
+        assert_eq!(
+            *mock.calls.borrow(),
+            [(1_000 * HOUR, now), (960 * HOUR, 960 * HOUR)]
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Gaps in a candle series.
+//!
+//! Candles of a fixed interval open exactly one stride apart, so a missing
+//! candle shows up as a jump between consecutive open times. Everything is
+//! plain millisecond arithmetic on epoch timestamps: a local-time DST switch
+//! does not move candle boundaries, and a day is always 86 400 000 ms.
+//!
+//! The phase comes from the series itself, so weekly candles opening on a
+//! Monday check out even though the epoch fell on a Thursday. Intervals with
+//! no fixed stride (`1M`) cannot be checked this way.
+
+use serde::Serialize;
+
+use crate::types::Candle;
+
+/// A run of consecutive missing candles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CandleGap {
+    /// Open time of the first missing candle.
+    pub from_ms: u64,
+    /// Open time of the last missing candle.
+    pub to_ms: u64,
+    /// Number of missing candles, `from_ms..=to_ms` by the stride.
+    pub missing: u64,
+}
+
+/// What [`find_gaps`] found in a series.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GapReport {
+    /// Missing ranges, oldest first.
+    pub gaps: Vec<CandleGap>,
+    /// Open times that appear more than once, counting each extra copy.
+    pub duplicates: usize,
+}
+
+impl GapReport {
+    /// Total missing candles over all gaps.
+    pub fn missing(&self) -> u64 {
+        self.gaps.iter().map(|g| g.missing).sum()
+    }
+}
+
+/// Gaps in `open_times` (any order, duplicates allowed) for candles of
+/// `interval_ms`, as of `now_ms`.
+///
+/// History before the first candle is never a gap — the market may not have
+/// been listed. After the last candle, every slot that has closed by `now_ms`
+/// is expected; the forming one (open, not yet ended) is not, since
+/// exchanges may or may not include it.
+pub fn find_gaps(open_times: &[u64], interval_ms: u64, now_ms: u64) -> GapReport {
+    let mut times = open_times.to_vec();
+    times.sort_unstable();
+    let before = times.len();
+    times.dedup();
+    let mut report = GapReport {
+        gaps: Vec::new(),
+        duplicates: before - times.len(),
+    };
+    if interval_ms == 0 {
+        return report;
+    }
+
+    let mut push = |from_ms: u64, missing: u64| {
+        if missing > 0 {
+            report.gaps.push(CandleGap {
+                from_ms,
+                to_ms: from_ms + (missing - 1) * interval_ms,
+                missing,
+            });
+        }
+    };
+    for pair in times.windows(2) {
+        // An off-stride pair still misses every whole slot in between
+        push(pair[0] + interval_ms, (pair[1] - pair[0] - 1) / interval_ms);
+    }
+    if let Some(&last) = times.last() {
+        // Slots after `last` that ended by `now_ms`
+        let closed = (now_ms.saturating_sub(last) / interval_ms).saturating_sub(1);
+        push(last + interval_ms, closed);
+    }
+    report
+}
+
+/// Sorts `candles` by open time and drops repeated open times, keeping the
+/// copy that came last (the freshest fetch). Returns how many were dropped.
+pub fn dedup(candles: &mut Vec<Candle>) -> usize {
+    let before = candles.len();
+    candles.reverse();
+    // Stable, so the last copy of each open time now comes first
+    candles.sort_by_key(|c| c.open_time_ms);
+    candles.dedup_by_key(|c| c.open_time_ms);
+    before - candles.len()
+}
+
+/// One-line description for warnings, e.g. `3 missing candles in 2 gaps,
+/// first at 2026-03-08 02:00 UTC`.
+pub fn describe(gaps: &[CandleGap]) -> String {
+    let missing: u64 = gaps.iter().map(|g| g.missing).sum();
+    let Some(first) = gaps.first() else {
+        return "no missing candles".to_string();
+    };
+    let at = chrono::DateTime::from_timestamp_millis(first.from_ms as i64)
+        .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| first.from_ms.to_string());
+    format!(
+        "{missing} missing candle{} in {} gap{}, first at {at}",
+        if missing == 1 { "" } else { "s" },
+        gaps.len(),
+        if gaps.len() == 1 { "" } else { "s" },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    const HOUR: u64 = 3_600_000;
+    const DAY: u64 = 24 * HOUR;
+
+    fn hours(hs: &[u64]) -> Vec<u64> {
+        hs.iter().map(|h| h * HOUR).collect()
+    }
+
+    #[test]
+    fn test_contiguous_series_across_dst_has_no_gaps() {
+        // 2026-03-06 .. 2026-03-10 UTC midnights: US clocks jump on 03-08
+        // and Europe's on 03-29, neither shifts UTC day boundaries.
+        let start = 1_772_755_200_000; // 2026-03-06T00:00:00Z
+        let days: Vec<u64> = (0..5).map(|i| start + i * DAY).collect();
+        let now = start + 4 * DAY + 3 * HOUR; // forming 03-10 candle
+        assert_eq!(find_gaps(&days, DAY, now), GapReport::default());
+    }
+
+    #[test]
+    fn test_internal_gap() {
+        let report = find_gaps(&hours(&[10, 11, 14, 15]), HOUR, 15 * HOUR + 1);
+        assert_eq!(
+            report.gaps,
+            [CandleGap {
+                from_ms: 12 * HOUR,
+                to_ms: 13 * HOUR,
+                missing: 2
+            }]
+        );
+        assert_eq!(report.missing(), 2);
+    }
+
+    #[test]
+    fn test_duplicates_counted_not_gaps() {
+        let report = find_gaps(&hours(&[12, 10, 11, 11, 12, 11]), HOUR, 12 * HOUR);
+        assert!(report.gaps.is_empty());
+        assert_eq!(report.duplicates, 3);
+    }
+
+    #[test]
+    fn test_forming_candle_never_required() {
+        // Latest closed candle present, forming one absent or present: fine
+        let now = 13 * HOUR + 59 * 60_000;
+        assert!(find_gaps(&hours(&[10, 11, 12]), HOUR, now).gaps.is_empty());
+        assert!(find_gaps(&hours(&[10, 11, 12, 13]), HOUR, now)
+            .gaps
+            .is_empty());
+        // Exactly at the boundary the 13:00 candle has just closed
+        let report = find_gaps(&hours(&[10, 11, 12]), HOUR, 14 * HOUR);
+        assert_eq!(
+            report.gaps,
+            [CandleGap {
+                from_ms: 13 * HOUR,
+                to_ms: 13 * HOUR,
+                missing: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn test_phase_follows_series() {
+        // Weekly candles opening on Mondays, four days off the epoch phase
+        let week = 7 * DAY;
+        let monday = 4 * DAY + 2_900 * week;
+        let times = [monday, monday + week, monday + 3 * week];
+        let report = find_gaps(&times, week, monday + 3 * week + DAY);
+        assert_eq!(report.gaps.len(), 1);
+        assert_eq!(report.gaps[0].from_ms, monday + 2 * week);
+        assert_eq!(report.gaps[0].missing, 1);
+    }
+
+    #[test]
+    fn test_dedup_keeps_last_copy() {
+        let candle = |t: u64, close: i64| Candle {
+            open_time_ms: t,
+            open: Decimal::ONE,
+            high: Decimal::from(close),
+            low: Decimal::ONE,
+            close: Decimal::from(close),
+            volume: Decimal::ONE,
+            trades: None,
+        };
+        let mut candles = vec![candle(2, 1), candle(1, 1), candle(2, 2)];
+        assert_eq!(dedup(&mut candles), 1);
+        let got: Vec<_> = candles.iter().map(|c| (c.open_time_ms, c.close)).collect();
+        assert_eq!(got, [(1, Decimal::ONE), (2, Decimal::from(2))]);
+    }
+
+    #[test]
+    fn test_describe() {
+        let gaps = find_gaps(&hours(&[0, 2, 5]), HOUR, 5 * HOUR).gaps;
+        assert_eq!(
+            describe(&gaps),
+            "3 missing candles in 2 gaps, first at 1970-01-01 01:00 UTC"
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Delta-neutral carry estimate (`atlas strategy carry`): short the perp,
+//! hold the same amount of the spot asset and supply it on Morpho.
+//!
+//! The position earns funding on the short (when funding is positive) plus
+//! the supply APY, and pays taker fees on four fills — perp open and close,
+//! spot buy and sell — plus the builder fee on the two perp fills. Fees are
+//! spread over the holding horizon to compare them with the yearly rates.
+//! Nothing here places orders.
+
+use rust_decimal::Decimal;
+
+const DAYS_PER_YEAR: u32 = 365;
+const BPS: u32 = 10_000;
+
+/// What the estimate is for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarryParams {
+    pub size_usd: Decimal,
+    /// Days the position is held; the fees are amortized over them.
+    pub horizon_days: u32,
+    /// Taker fee on every fill of either leg, in bps.
+    pub taker_fee_bps: Decimal,
+    /// Builder fee on each perp fill, in bps.
+    pub builder_fee_bps: Decimal,
+}
+
+/// Current and trailing funding of the perp.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundingInputs {
+    /// Predicted rate for the next interval, as a fraction.
+    pub current_rate: Decimal,
+    /// Mean rate per interval over the trailing window, if known.
+    pub trailing_avg_rate: Option<Decimal>,
+    /// Hours between payments.
+    pub interval_hours: u32,
+}
+
+/// Yearly figures are percentages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarryEstimate {
+    pub funding_apr_pct: Decimal,
+    pub trailing_funding_apr_pct: Option<Decimal>,
+    pub supply_apy_pct: Decimal,
+    /// Open and close of both legs, in USD.
+    pub fees_usd: Decimal,
+    /// `fees_usd` spread over the horizon, as a yearly rate.
+    pub fees_apr_pct: Decimal,
+    /// Funding + supply − fees.
+    pub net_apr_pct: Decimal,
+    /// What `net_apr_pct` earns on the size over the horizon.
+    pub net_usd: Decimal,
+    /// Funding APR at which the carry nets zero.
+    pub breakeven_funding_apr_pct: Decimal,
+    /// The same, as a rate per funding interval.
+    pub breakeven_funding_rate: Decimal,
+    /// The trailing average is under half the current rate, so the
+    /// current rate is likely a spike.
+    pub trailing_below_current: bool,
+}
+
+/// Estimate the carry. `supply_apy_pct` is the Morpho supply APY in percent.
+pub fn estimate(
+    funding: &FundingInputs,
+    supply_apy_pct: Decimal,
+    params: &CarryParams,
+) -> CarryEstimate {
+    let hundred = Decimal::ONE_HUNDRED;
+    let periods_per_year = Decimal::from(24 * DAYS_PER_YEAR / funding.interval_hours.max(1));
+    let apr = |rate: Decimal| rate * periods_per_year * hundred;
+    let funding_apr_pct = apr(funding.current_rate);
+    let trailing_funding_apr_pct = funding.trailing_avg_rate.map(apr);
+
+    let fee_bps = Decimal::from(4) * params.taker_fee_bps + Decimal::TWO * params.builder_fee_bps;
+    let fees_usd = params.size_usd * fee_bps / Decimal::from(BPS);
+    let horizon = Decimal::from(params.horizon_days.max(1));
+    let years = horizon / Decimal::from(DAYS_PER_YEAR);
+    let fees_apr_pct = fee_bps / Decimal::from(BPS) / years * hundred;
+
+    let net_apr_pct = funding_apr_pct + supply_apy_pct - fees_apr_pct;
+    let breakeven_funding_apr_pct = fees_apr_pct - supply_apy_pct;
+    CarryEstimate {
+        funding_apr_pct,
+        trailing_funding_apr_pct,
+        supply_apy_pct,
+        fees_usd,
+        fees_apr_pct,
+        net_apr_pct,
+        net_usd: params.size_usd * net_apr_pct / hundred * years,
+        breakeven_funding_apr_pct,
+        breakeven_funding_rate: breakeven_funding_apr_pct / hundred / periods_per_year,
+        trailing_below_current: funding.current_rate > Decimal::ZERO
+            && funding
+                .trailing_avg_rate
+                .is_some_and(|avg| avg * Decimal::TWO < funding.current_rate),
+    }
+}
+
+/// Mean of the rates, `None` when there are none.
+pub fn average_rate(rates: &[Decimal]) -> Option<Decimal> {
+    if rates.is_empty() {
+        return None;
+    }
+    Some(rates.iter().sum::<Decimal>() / Decimal::from(rates.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn params(horizon_days: u32) -> CarryParams {
+        CarryParams {
+            size_usd: d("10000"),
+            horizon_days,
+            taker_fee_bps: d("4.5"),
+            builder_fee_bps: d("1"),
+        }
+    }
+
+    #[test]
+    fn test_estimate_breakdown() {
+        // 0.00125% per hour = 10.95% APR
+        let funding = FundingInputs {
+            current_rate: d("0.0000125"),
+            trailing_avg_rate: Some(d("0.00001")),
+            interval_hours: 1,
+        };
+        let e = estimate(&funding, d("3.5"), &params(73));
+
+        assert_eq!(e.funding_apr_pct, d("10.95"));
+        assert_eq!(e.trailing_funding_apr_pct, Some(d("8.76")));
+        // 4 × 4.5 + 2 × 1 = 20 bps of $10K
+        assert_eq!(e.fees_usd, d("20"));
+        // 0.2% over 73 days = 1% a year
+        assert_eq!(e.fees_apr_pct, d("1"));
+        assert_eq!(e.net_apr_pct, d("13.45"));
+        // 13.45% of $10K for a fifth of a year
+        assert_eq!(e.net_usd, d("269"));
+        assert_eq!(e.breakeven_funding_apr_pct, d("-2.5"));
+        assert_eq!(
+            (e.breakeven_funding_rate * Decimal::from(8760)).round_dp(12),
+            d("-0.025")
+        );
+        assert!(!e.trailing_below_current);
+    }
+
+    #[test]
+    fn test_trailing_below_current_warns() {
+        let mut funding = FundingInputs {
+            current_rate: d("0.0001"),
+            trailing_avg_rate: Some(d("0.00004")),
+            interval_hours: 8,
+        };
+        let e = estimate(&funding, Decimal::ZERO, &params(30));
+        assert!(e.trailing_below_current);
+        // 3 payments a day
+        assert_eq!(e.funding_apr_pct, d("10.95"));
+
+        funding.trailing_avg_rate = Some(d("0.00005"));
+        assert!(!estimate(&funding, Decimal::ZERO, &params(30)).trailing_below_current);
+
+        // Negative current funding never warns: the short pays either way
+        funding.current_rate = d("-0.0001");
+        funding.trailing_avg_rate = Some(d("-0.0005"));
+        let e = estimate(&funding, Decimal::ZERO, &params(30));
+        assert!(!e.trailing_below_current);
+        assert!(e.net_apr_pct < Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_short_horizon_costs_more() {
+        let funding = FundingInputs {
+            current_rate: d("0.00001"),
+            trailing_avg_rate: None,
+            interval_hours: 1,
+        };
+        let week = estimate(&funding, d("2"), &params(7));
+        let year = estimate(&funding, d("2"), &params(365));
+        assert_eq!(week.fees_usd, year.fees_usd);
+        assert!(week.fees_apr_pct > year.fees_apr_pct);
+        assert_eq!(year.fees_apr_pct, d("0.2"));
+        assert_eq!(year.breakeven_funding_apr_pct, year.fees_apr_pct - d("2"));
+    }
+
+    #[test]
+    fn test_average_rate() {
+        assert_eq!(average_rate(&[]), None);
+        assert_eq!(
+            average_rate(&[d("0.00001"), d("0.00003"), d("-0.00001")]),
+            Some(d("0.00001"))
+        );
+    }
+}
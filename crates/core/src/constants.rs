@@ -16,3 +16,10 @@ pub const PROTOCOL_HYPERLIQUID: &str = "hyperliquid";
 /// Default RPC endpoints.
 pub const HL_MAINNET_RPC: &str = "https://api.hyperliquid.xyz";
 pub const HL_TESTNET_RPC: &str = "https://api.hyperliquid-testnet.xyz";
+
+/// Hyperliquid's documented REST limit: 1200 request weight per minute per
+/// IP. Light `/info` requests weigh 2, so this is 10 of them per second.
+pub const HL_INFO_REQUESTS_PER_SEC: f64 = 10.0;
+
+/// Atlas backend gateway (0x proxy, market data, health).
+pub const ATLAS_BACKEND_URL: &str = "https://atlas-os-backend-production.up.railway.app";
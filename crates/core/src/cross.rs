@@ -0,0 +1,55 @@
+//! Cross rates between assets quoted in USD (`atlas market hl price --in`).
+//!
+//! Hyperliquid only lists USD(C) markets, so ETH in BTC terms is the ratio
+//! of the two USD mids. Everything stays in `Decimal`; results are rounded
+//! to [`CROSS_SIG_FIGS`] significant figures, more than any mid carries.
+
+use rust_decimal::Decimal;
+
+/// Significant figures kept in a cross rate.
+pub const CROSS_SIG_FIGS: u32 = 10;
+
+/// Value of `usd` in units of an asset whose USD price is `quote_usd`:
+/// the cross rate when `usd` is another asset's mid, or a spread or
+/// notional expressed in the quote asset. `None` if `quote_usd` is not
+/// positive.
+pub fn cross_rate(usd: Decimal, quote_usd: Decimal) -> Option<Decimal> {
+    if quote_usd <= Decimal::ZERO {
+        return None;
+    }
+    let rate = usd.checked_div(quote_usd)?;
+    Some(rate.round_sf(CROSS_SIG_FIGS).unwrap_or(rate).normalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_cross_rate() {
+        assert_eq!(cross_rate(d("3000"), d("60000")), Some(d("0.05")));
+        assert_eq!(cross_rate(d("60000"), d("3000")), Some(d("20")));
+        // Same asset on both sides
+        assert_eq!(cross_rate(d("123.45"), d("123.45")), Some(Decimal::ONE));
+    }
+
+    #[test]
+    fn test_cross_rate_rounds_to_significant_figures() {
+        assert_eq!(cross_rate(d("1"), d("3")), Some(d("0.3333333333")));
+        assert_eq!(
+            cross_rate(d("3412.7"), d("97345.5")),
+            Some(d("0.03505760410"))
+        );
+    }
+
+    #[test]
+    fn test_cross_rate_needs_positive_quote() {
+        assert_eq!(cross_rate(d("3000"), Decimal::ZERO), None);
+        assert_eq!(cross_rate(d("3000"), d("-1")), None);
+        assert_eq!(cross_rate(Decimal::ZERO, d("60000")), Some(Decimal::ZERO));
+    }
+}
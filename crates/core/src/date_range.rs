@@ -0,0 +1,442 @@
+//! `--from` / `--to` parsing shared by every command that filters by time.
+//!
+//! A value is one of:
+//! - a date, `2026-01-31`: the whole day, midnight to midnight
+//! - a datetime, `2026-01-31T14:30[:00]` (or with a space), optionally
+//!   with an offset: `…+02:00`, `…Z`
+//! - unix seconds or milliseconds, `1767225600` / `1767225600000`
+//! - an age, `30m`, `24h`, `7d`, `1w`: that long before now
+//! - `now`, `today`, `yesterday`, `week-to-date`, `month-to-date`,
+//!   `year-to-date` (`wtd`, `mtd`, `ytd`)
+//!
+//! Values without an offset are wall-clock time in the chosen zone: local
+//! time unless `--tz` says otherwise. Days and periods cover their whole
+//! span: as `--from` they start at its first millisecond, as `--to` they
+//! end at its last, so `--from yesterday --to yesterday` is all of
+//! yesterday.
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone as _,
+};
+
+use crate::error::{AtlasError, AtlasResult};
+
+/// Examples of every accepted form, for error hints and help text.
+pub const ACCEPTED_FORMATS: &str = "2026-01-31, 2026-01-31T14:30:00, 2026-01-31T14:30:00+02:00, \
+     1767225600 (unix s or ms), 30m, 24h, 7d, 1w, now, today, yesterday, week-to-date, \
+     month-to-date, year-to-date";
+
+/// The current time, unix ms.
+pub fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+const DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+];
+
+const OFFSET_DATETIME_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%.f%:z", "%Y-%m-%d %H:%M%:z"];
+
+/// Unix timestamps at or above this are milliseconds (as seconds it would
+/// be the year 5138).
+const MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// The zone that dates and relative days are read in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeZone {
+    /// The system's local time, DST included.
+    #[default]
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl TimeZone {
+    pub const UTC: Self = Self::Fixed(match FixedOffset::east_opt(0) {
+        Some(utc) => utc,
+        None => unreachable!(),
+    });
+
+    /// Parse `--tz`: `local`, `UTC` (or `Z`, `GMT`), or a fixed offset such
+    /// as `+05:30`, `-0800`, `+2` or `UTC+2`. Named zones are not supported.
+    pub fn parse(s: &str) -> AtlasResult<Self> {
+        let lower = s.trim().to_ascii_lowercase();
+        match lower.as_str() {
+            "local" => return Ok(Self::Local),
+            "utc" | "z" | "gmt" => return Ok(Self::UTC),
+            _ => {}
+        }
+        let offset = lower
+            .strip_prefix("utc")
+            .or_else(|| lower.strip_prefix("gmt"))
+            .unwrap_or(&lower);
+        parse_offset(offset).map(Self::Fixed).ok_or_else(|| {
+            AtlasError::InvalidDate(format!(
+                "--tz '{s}' is not a time zone. Use local, UTC or an offset like +05:30"
+            ))
+        })
+    }
+
+    /// `naive` wall-clock time in this zone, as unix milliseconds. A local
+    /// time skipped by a DST change is read with the offset before it.
+    fn to_ms(self, naive: NaiveDateTime) -> i64 {
+        let offset = match self {
+            Self::Fixed(offset) => offset,
+            Self::Local => Local
+                .offset_from_local_datetime(&naive)
+                .earliest()
+                .unwrap_or_else(|| Local.offset_from_utc_datetime(&naive)),
+        };
+        (naive - Duration::seconds(offset.local_minus_utc() as i64))
+            .and_utc()
+            .timestamp_millis()
+    }
+
+    /// The calendar date at `ms` in this zone.
+    fn date_at(self, ms: i64) -> NaiveDate {
+        let utc = DateTime::from_timestamp_millis(ms).unwrap_or_default();
+        match self {
+            Self::Fixed(offset) => utc.with_timezone(&offset).date_naive(),
+            Self::Local => utc.with_timezone(&Local).date_naive(),
+        }
+    }
+
+    fn day_start(self, date: NaiveDate) -> i64 {
+        self.to_ms(date.and_time(NaiveTime::MIN))
+    }
+
+    /// The whole of `date`: first to last millisecond.
+    fn day(self, date: NaiveDate) -> (i64, i64) {
+        let next = date.succ_opt().unwrap_or(date);
+        (self.day_start(date), self.day_start(next) - 1)
+    }
+}
+
+/// `+05:30`, `-0800`, `+2`: hours up to 14, minutes under 60.
+fn parse_offset(s: &str) -> Option<FixedOffset> {
+    let (sign, body) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let (h, m) = match body.split_once(':') {
+        Some(parts) => parts,
+        None if body.len() > 2 => body.split_at(body.len() - 2),
+        None => (body, "0"),
+    };
+    if h.is_empty() || !(h.bytes().chain(m.bytes())).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (h, m): (i32, i32) = (h.parse().ok()?, m.parse().ok()?);
+    if h > 14 || m > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (h * 3600 + m * 60))
+}
+
+/// A validated time filter. A missing bound is open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DateRange {
+    /// Inclusive, unix ms.
+    pub from_ms: Option<i64>,
+    /// Inclusive, unix ms.
+    pub to_ms: Option<i64>,
+}
+
+/// Parse `--from` / `--to` at `now_ms`. Fails when either value does not
+/// parse or when the range is empty (`from` not before `to`).
+pub fn parse_range(
+    from: Option<&str>,
+    to: Option<&str>,
+    tz: TimeZone,
+    now_ms: i64,
+) -> AtlasResult<DateRange> {
+    let bound = |flag: &str, value: &str| {
+        parse_span(value, tz, now_ms).ok_or_else(|| {
+            AtlasError::InvalidDate(format!("{flag} '{value}' is not a date or time"))
+        })
+    };
+    let range = DateRange {
+        from_ms: from
+            .map(|s| bound("--from", s).map(|span| span.0))
+            .transpose()?,
+        to_ms: to
+            .map(|s| bound("--to", s).map(|span| span.1))
+            .transpose()?,
+    };
+    if let (Some(start), Some(end)) = (range.from_ms, range.to_ms) {
+        if start >= end {
+            return Err(AtlasError::InvalidDate(format!(
+                "--from '{}' must be before --to '{}'",
+                from.unwrap_or_default(),
+                to.unwrap_or_default()
+            )));
+        }
+    }
+    Ok(range)
+}
+
+/// Parse a single point in time (the start of a day or period).
+pub fn parse_point(s: &str, tz: TimeZone, now_ms: i64) -> AtlasResult<i64> {
+    parse_span(s, tz, now_ms)
+        .map(|span| span.0)
+        .ok_or_else(|| AtlasError::InvalidDate(format!("'{s}' is not a date or time")))
+}
+
+/// Parse a calendar month, `2026-01`, into its first and last millisecond
+/// in `tz`.
+pub fn parse_month(s: &str, tz: TimeZone) -> AtlasResult<DateRange> {
+    let first = NaiveDate::parse_from_str(&format!("{}-01", s.trim()), "%Y-%m-%d")
+        .ok()
+        .filter(|_| s.trim().len() == 7)
+        .ok_or_else(|| {
+            AtlasError::InvalidDate(format!("--month '{s}' is not a month. Use YYYY-MM"))
+        })?;
+    let next = first
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(first);
+    Ok(DateRange {
+        from_ms: Some(tz.day_start(first)),
+        to_ms: Some(tz.day_start(next) - 1),
+    })
+}
+
+/// The first and last millisecond `s` stands for; equal for an instant.
+fn parse_span(s: &str, tz: TimeZone, now_ms: i64) -> Option<(i64, i64)> {
+    let s = s.trim();
+    let lower = s.to_ascii_lowercase();
+    let today = tz.date_at(now_ms);
+    let instant = |ms: i64| Some((ms, ms));
+
+    match lower.as_str() {
+        "" => return None,
+        "now" => return instant(now_ms),
+        "today" => return Some(tz.day(today)),
+        "yesterday" => return Some(tz.day(today.pred_opt()?)),
+        "week-to-date" | "wtd" => {
+            let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            return Some((tz.day_start(monday), now_ms));
+        }
+        "month-to-date" | "mtd" => return Some((tz.day_start(today.with_day(1)?), now_ms)),
+        "year-to-date" | "ytd" => return Some((tz.day_start(today.with_ordinal(1)?), now_ms)),
+        _ => {}
+    }
+
+    if lower.bytes().all(|b| b.is_ascii_digit()) {
+        let n: i64 = lower.parse().ok()?;
+        return instant(if n >= MILLIS_THRESHOLD { n } else { n * 1000 });
+    }
+    if lower.ends_with(['m', 'h', 'd', 'w'])
+        && lower[..lower.len() - 1].bytes().all(|b| b.is_ascii_digit())
+    {
+        let age = crate::parse::parse_age_ms(&lower).ok()?;
+        return instant(now_ms.checked_sub(i64::try_from(age).ok()?)?);
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Some(tz.day(date));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return instant(dt.timestamp_millis());
+    }
+    for format in OFFSET_DATETIME_FORMATS {
+        if let Ok(dt) = DateTime::parse_from_str(s, format) {
+            return instant(dt.timestamp_millis());
+        }
+    }
+    for format in DATETIME_FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(s, format) {
+            return instant(tz.to_ms(naive));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 3_600_000;
+    const DAY: i64 = 24 * HOUR;
+    /// 2026-01-15 (Thursday) 10:30:00 UTC
+    const NOW: i64 = 1_768_473_000_000;
+    /// 2026-01-15 00:00:00 UTC
+    const TODAY_UTC: i64 = 1_768_435_200_000;
+
+    fn tz(s: &str) -> TimeZone {
+        TimeZone::parse(s).unwrap()
+    }
+
+    fn point(s: &str) -> i64 {
+        parse_point(s, TimeZone::UTC, NOW).unwrap()
+    }
+
+    fn range(from: Option<&str>, to: Option<&str>, zone: &str) -> AtlasResult<DateRange> {
+        parse_range(from, to, tz(zone), NOW)
+    }
+
+    #[test]
+    fn test_time_zone_parse() {
+        assert_eq!(tz("local"), TimeZone::Local);
+        assert_eq!(tz("UTC"), TimeZone::UTC);
+        assert_eq!(tz("z"), TimeZone::UTC);
+        let offset = |secs| TimeZone::Fixed(FixedOffset::east_opt(secs).unwrap());
+        assert_eq!(tz("+05:30"), offset(5 * 3600 + 1800));
+        assert_eq!(tz("-0800"), offset(-8 * 3600));
+        assert_eq!(tz("+2"), offset(2 * 3600));
+        assert_eq!(tz("UTC+2"), offset(2 * 3600));
+        assert_eq!(tz("gmt-03:00"), offset(-3 * 3600));
+        for bad in ["Europe/Berlin", "+15", "+05:60", "5", "+", "utc+", "+5:3x"] {
+            assert!(TimeZone::parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_dates_cover_the_whole_day() {
+        let r = range(Some("2026-01-01"), Some("2026-01-31"), "UTC").unwrap();
+        assert_eq!(r.from_ms, Some(1_767_225_600_000));
+        assert_eq!(r.to_ms, Some(1_767_225_600_000 + 31 * DAY - 1));
+
+        // Midnight in UTC+2 is 22:00 the day before in UTC
+        let r = range(Some("2026-01-01"), None, "+02:00").unwrap();
+        assert_eq!(r.from_ms, Some(1_767_225_600_000 - 2 * HOUR));
+        assert_eq!(r.to_ms, None);
+    }
+
+    #[test]
+    fn test_datetimes() {
+        let base = TODAY_UTC + 14 * HOUR + 30 * 60_000;
+        assert_eq!(point("2026-01-15T14:30:00"), base);
+        assert_eq!(point("2026-01-15T14:30"), base);
+        assert_eq!(point("2026-01-15 14:30:00"), base);
+        assert_eq!(point("2026-01-15 14:30"), base);
+        assert_eq!(point("2026-01-15T14:30:00.250"), base + 250);
+        // Offsets win over --tz
+        assert_eq!(point("2026-01-15T14:30:00Z"), base);
+        assert_eq!(point("2026-01-15T16:30:00+02:00"), base);
+        assert_eq!(point("2026-01-15 09:30:00-05:00"), base);
+        assert_eq!(
+            parse_point("2026-01-15T16:30:00+02:00", tz("-08:00"), NOW).unwrap(),
+            base
+        );
+        // Without one, --tz applies
+        assert_eq!(
+            parse_point("2026-01-15T16:30:00", tz("+02:00"), NOW).unwrap(),
+            base
+        );
+    }
+
+    #[test]
+    fn test_unix_timestamps() {
+        assert_eq!(point("1767225600"), 1_767_225_600_000);
+        assert_eq!(point("1767225600000"), 1_767_225_600_000);
+        assert_eq!(point("0"), 0);
+    }
+
+    #[test]
+    fn test_ages_count_back_from_now() {
+        assert_eq!(point("30m"), NOW - 30 * 60_000);
+        assert_eq!(point("24h"), NOW - DAY);
+        assert_eq!(point("7d"), NOW - 7 * DAY);
+        assert_eq!(point("1w"), NOW - 7 * DAY);
+        assert_eq!(point("7D"), NOW - 7 * DAY);
+        let r = range(Some("7d"), Some("1d"), "UTC").unwrap();
+        assert_eq!((r.from_ms, r.to_ms), (Some(NOW - 7 * DAY), Some(NOW - DAY)));
+    }
+
+    #[test]
+    fn test_named_periods() {
+        assert_eq!(point("now"), NOW);
+        let r = range(Some("today"), Some("today"), "UTC").unwrap();
+        assert_eq!(
+            (r.from_ms, r.to_ms),
+            (Some(TODAY_UTC), Some(TODAY_UTC + DAY - 1))
+        );
+        let r = range(Some("yesterday"), Some("Yesterday"), "UTC").unwrap();
+        assert_eq!(
+            (r.from_ms, r.to_ms),
+            (Some(TODAY_UTC - DAY), Some(TODAY_UTC - 1))
+        );
+        // Thursday: the week started on Monday the 12th
+        let r = range(Some("week-to-date"), Some("now"), "UTC").unwrap();
+        assert_eq!(r.from_ms, Some(TODAY_UTC - 3 * DAY));
+        let r = range(Some("month-to-date"), Some("mtd"), "UTC").unwrap();
+        assert_eq!(
+            (r.from_ms, r.to_ms),
+            (Some(TODAY_UTC - 14 * DAY), Some(NOW))
+        );
+        assert_eq!(point("ytd"), 1_767_225_600_000);
+    }
+
+    #[test]
+    fn test_relative_days_follow_the_zone() {
+        // 10:30 UTC is already the 16th in UTC+14
+        let r = range(Some("today"), None, "+14:00").unwrap();
+        assert_eq!(r.from_ms, Some(TODAY_UTC + DAY - 14 * HOUR));
+        // and still the 14th in UTC-12
+        let r = range(Some("yesterday"), None, "-12:00").unwrap();
+        assert_eq!(r.from_ms, Some(TODAY_UTC - 2 * DAY + 12 * HOUR));
+        let r = range(Some("month-to-date"), None, "+14:00").unwrap();
+        assert_eq!(r.from_ms, Some(TODAY_UTC - 14 * DAY - 14 * HOUR));
+    }
+
+    #[test]
+    fn test_local_zone_resolves() {
+        // Whatever the machine's zone, a local date is within a day of UTC
+        let local = parse_point("2026-01-15", TimeZone::Local, NOW).unwrap();
+        assert!((local - TODAY_UTC).abs() <= 14 * HOUR);
+    }
+
+    #[test]
+    fn test_range_must_not_be_empty() {
+        let err = range(Some("2026-02-01"), Some("2026-01-01"), "UTC").unwrap_err();
+        assert!(err.to_string().contains("must be before --to"));
+        assert!(range(Some("now"), Some("now"), "UTC").is_err());
+        // A single day as both bounds is fine
+        assert!(range(Some("2026-01-01"), Some("2026-01-01"), "UTC").is_ok());
+        assert_eq!(range(None, None, "UTC").unwrap(), DateRange::default());
+    }
+
+    #[test]
+    fn test_months() {
+        let jan = parse_month("2026-01", TimeZone::UTC).unwrap();
+        assert_eq!(jan.from_ms, Some(1_767_225_600_000));
+        assert_eq!(jan.to_ms, Some(1_767_225_600_000 + 31 * DAY - 1));
+        // December rolls into the next year
+        let dec = parse_month("2025-12", TimeZone::UTC).unwrap();
+        assert_eq!(dec.to_ms, Some(1_767_225_600_000 - 1));
+        let feb = parse_month("2026-02", tz("+02:00")).unwrap();
+        assert_eq!(feb.from_ms, Some(1_767_225_600_000 + 31 * DAY - 2 * HOUR));
+        for bad in ["2026-13", "2026-1", "2026-01-01", "jan", ""] {
+            assert!(parse_month(bad, TimeZone::UTC).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_rejects_garbage() {
+        for bad in [
+            "",
+            "last tuesday",
+            "2026-13-01",
+            "2026-02-30",
+            "01/02/2026",
+            "7x",
+            "d",
+            "-7d",
+            "2026-01-15T25:00",
+            "99999999999999999999",
+        ] {
+            let err = parse_point(bad, TimeZone::UTC, NOW).unwrap_err();
+            assert!(matches!(err, AtlasError::InvalidDate(_)), "{bad}");
+        }
+        let err = range(Some("soon"), None, "UTC").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid date: --from 'soon' is not a date or time"
+        );
+        assert_eq!(err.detail().code, "VALIDATION_ERROR");
+    }
+}
@@ -1,10 +1,17 @@
-// Local SQLite database for caching trades, orders, and sync state.
+// Local SQLite database for caching trades, orders, sync state, and
+// market snapshots (open interest history).
 //
 // All Decimal values are stored as TEXT and parsed back with `rust_decimal` on read.
-// Uses WAL mode for concurrent read safety.
+// Uses WAL mode for concurrent read safety. Schema changes are numbered
+// migrations (`MIGRATIONS`), applied when the database is opened.
+
+use std::collections::{BTreeMap, HashSet};
 
 use anyhow::{Context, Result};
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, Transaction, TransactionBehavior};
+use rust_decimal::Decimal;
+
+use crate::types::Candle;
 
 /// A cached fill row read from the database.
 #[derive(Debug, Clone)]
@@ -21,6 +28,14 @@ pub struct DbFill {
     pub closed_pnl: String,
 }
 
+/// The last exchange nonce signed for a wallet.
+#[derive(Debug, Clone)]
+pub struct DbNonce {
+    pub address: String,
+    pub last_nonce: i64,
+    pub updated_ms: i64,
+}
+
 /// A cached order row read from the database.
 #[derive(Debug, Clone)]
 pub struct DbOrder {
@@ -33,54 +48,341 @@ pub struct DbOrder {
     pub timestamp_ms: i64,
     pub status: String,
     pub order_type: String,
+    /// Size filled so far.
+    pub filled_sz: String,
+    /// Volume-weighted price of the cached fills, if any.
+    pub avg_px: Option<String>,
+    /// When `status` was last confirmed against the exchange (ms).
+    pub status_updated_at: Option<i64>,
 }
 
-/// Local SQLite database handle.
-pub struct AtlasDb {
-    conn: Connection,
+/// One market's open interest / volume sample, from `atlas market hl oi record`.
+#[derive(Debug, Clone)]
+pub struct DbOiSnapshot {
+    pub protocol: String,
+    pub coin: String,
+    pub time_ms: i64,
+    /// Open interest in base units.
+    pub open_interest: String,
+    pub mark_px: String,
+    /// Rolling 24h notional volume at sample time.
+    pub volume_24h: String,
+    pub funding: String,
 }
 
-impl AtlasDb {
-    /// Open (or create) the database at `~/.atlas-os/data/atlas.db`.
-    /// Enables WAL mode and creates tables if they don't exist.
-    pub fn open() -> Result<Self> {
-        let db_path = crate::workspace::root_dir()?.join("data/atlas.db");
+/// One market's impact spread, sampled by price/info/summary calls and
+/// market orders for `atlas market hl spread-profile`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbSpreadSample {
+    pub protocol: String,
+    pub coin: String,
+    /// Start of the minute the sample fell in.
+    pub time_ms: i64,
+    /// Impact ask minus impact bid, in bps of the mid.
+    pub spread_bps: String,
+}
 
-        // Ensure parent directory exists
-        if let Some(parent) = db_path.parent() {
-            std::fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create DB directory: {}", parent.display()))?;
+/// One signed exchange action (`atlas audit`), written before it is sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbAuditEntry {
+    /// Assigned on insert; ignored by [`AtlasDb::insert_audit_entry`].
+    pub id: i64,
+    pub created_ms: i64,
+    pub protocol: String,
+    /// `order`, `cancel`, `modify`, `leverage`, `margin`, `transfer` or
+    /// `agent` on Hyperliquid; `swap`, `approve`, `revoke` or `gasless` on
+    /// 0x.
+    pub action_type: String,
+    pub coin: Option<String>,
+    /// keccak256 of the action JSON, `0x` hex.
+    pub params_hash: String,
+    pub nonce: Option<i64>,
+    /// Address of the signing key.
+    pub signer: String,
+    /// Request JSON, secrets redacted.
+    pub request: String,
+    /// `pending` until the outcome is recorded, then `ok`, `rejected`
+    /// (the exchange answered with an error) or `error` (no usable answer).
+    pub status: String,
+    pub response: Option<String>,
+    pub completed_ms: Option<i64>,
+}
+
+/// One cached candle series (`atlas cache candles`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbCandleSeries {
+    pub source: String,
+    pub coin: String,
+    pub interval: String,
+    pub candles: usize,
+    pub oldest_ms: i64,
+    pub newest_ms: i64,
+}
+
+/// A cached Hyperliquid ledger entry: a deposit, withdrawal or transfer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbTransfer {
+    /// Ledger entry hash; the dedup key.
+    pub hash: String,
+    pub time_ms: i64,
+    /// `deposit`, `withdraw` (bridge) or `internal` (everything that stays
+    /// on Hyperliquid).
+    pub kind: String,
+    /// The ledger's own entry type, e.g. `accountClassTransfer`.
+    pub ledger_type: String,
+    /// `in` or `out` of the account, or `none` for spot↔perp moves.
+    pub direction: String,
+    pub token: String,
+    /// Token units, unsigned.
+    pub amount: String,
+    /// USD value when the ledger gives one (always for USDC).
+    pub usd_value: Option<String>,
+    pub fee: String,
+    /// The other address or vault, or `spot→perp` / `perp→spot`.
+    pub counterparty: Option<String>,
+}
+
+impl DbTransfer {
+    /// USD added to (positive) or taken from (negative) the account; zero
+    /// for spot↔perp moves and entries without a USD value.
+    pub fn net_usd(&self) -> Decimal {
+        let usd = self
+            .usd_value
+            .as_deref()
+            .and_then(|v| v.parse::<Decimal>().ok())
+            .unwrap_or_default();
+        match self.direction.as_str() {
+            "in" => usd,
+            "out" => -usd,
+            _ => Decimal::ZERO,
         }
+    }
+}
 
-        let conn = Connection::open(&db_path)
-            .with_context(|| format!("Failed to open database: {}", db_path.display()))?;
+/// A cached Hyperliquid funding payment on one position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbFunding {
+    pub coin: String,
+    pub time_ms: i64,
+    /// USDC received (positive) or paid (negative).
+    pub usdc: String,
+    /// Signed position size the payment was charged on.
+    pub szi: String,
+    pub funding_rate: String,
+}
 
-        // Enable WAL mode for concurrent access
-        conn.pragma_update(None, "journal_mode", "WAL")?;
-        conn.pragma_update(None, "synchronous", "NORMAL")?;
+/// A trade journal note read from the database.
+#[derive(Debug, Clone)]
+pub struct DbJournalEntry {
+    pub id: i64,
+    /// Fill the note belongs to; `None` until the fill has been synced.
+    pub fill_hash: Option<String>,
+    pub oid: Option<i64>,
+    pub coin: Option<String>,
+    /// Fill time once linked, otherwise the time the user gave.
+    pub time_ms: i64,
+    pub note: String,
+    pub tags: Vec<String>,
+    pub created_ms: i64,
+}
 
-        let db = Self { conn };
-        db.init_tables()?;
-        Ok(db)
+impl DbJournalEntry {
+    /// Linked to a cached fill (as opposed to waiting for the next sync).
+    pub fn is_linked(&self) -> bool {
+        self.fill_hash.is_some()
     }
+}
 
-    /// Open an in-memory database (for testing).
-    #[cfg(test)]
-    pub fn open_in_memory() -> Result<Self> {
-        let conn = Connection::open_in_memory()?;
-        let db = Self { conn };
-        db.init_tables()?;
-        Ok(db)
+/// A user-added token in the address book.
+#[derive(Debug, Clone)]
+pub struct DbAddressEntry {
+    pub chain: String,
+    pub name: String,
+    /// EIP-55 checksummed.
+    pub address: String,
+    pub decimals: Option<u32>,
+    pub added_ms: i64,
+}
+
+/// A pool on the DEX watchlist with the stats seen at its last check.
+#[derive(Debug, Clone)]
+pub struct DbWatchedPool {
+    pub network: String,
+    pub pool: String,
+    pub label: Option<String>,
+    pub added_ms: i64,
+    pub last_price_usd: Option<String>,
+    pub last_volume_usd: Option<String>,
+    pub last_liquidity_usd: Option<String>,
+    /// When the stats above were recorded; `None` before the first check.
+    pub last_checked_ms: Option<i64>,
+    /// First check that could not find the pool, cleared when it reappears.
+    pub missing_since_ms: Option<i64>,
+}
+
+/// A saved `atlas status` snapshot (`status_snapshot::StatusSnapshot` as JSON).
+#[derive(Debug, Clone)]
+pub struct DbStatusSnapshot {
+    pub taken_ms: i64,
+    pub snapshot: String,
+}
+
+/// A recurring command from `atlas schedule add`.
+#[derive(Debug, Clone)]
+pub struct DbSchedule {
+    pub id: i64,
+    /// The atlas command line, without `atlas`.
+    pub command: String,
+    pub every_ms: i64,
+    /// `HH:MM` (UTC) the first run was anchored to.
+    pub at: Option<String>,
+    pub max_runs: Option<u32>,
+    /// Executions so far; skipped runs don't count.
+    pub runs: u32,
+    pub next_run_ms: i64,
+    pub created_ms: i64,
+}
+
+impl DbSchedule {
+    /// All `max_runs` executions are done.
+    pub fn is_done(&self) -> bool {
+        self.max_runs.is_some_and(|max| self.runs >= max)
     }
+}
 
-    /// Create all tables and indices if they don't exist.
-    fn init_tables(&self) -> Result<()> {
-        self.conn
-            .execute_batch(
-                "
+/// One execution (or skipped slot) of a schedule.
+#[derive(Debug, Clone)]
+pub struct DbScheduleRun {
+    pub schedule_id: i64,
+    pub due_ms: i64,
+    pub started_ms: i64,
+    pub finished_ms: i64,
+    /// `ok`, `error` or `skipped`.
+    pub status: String,
+    /// Slots that passed unrun before this one.
+    pub missed: u32,
+    pub oid: Option<String>,
+    pub fill_price: Option<String>,
+    pub error: Option<String>,
+}
+
+/// A 0x swap sent from this machine, or a quote recorded with
+/// `atlas 0x quote --record`.
+#[derive(Debug, Clone)]
+pub struct DbSwap {
+    /// `executed` or `quoted`.
+    pub status: String,
+    pub chain: String,
+    pub sell_token: String,
+    pub sell_symbol: Option<String>,
+    pub sell_decimals: Option<u32>,
+    /// Base units.
+    pub sell_amount: String,
+    pub buy_token: String,
+    pub buy_symbol: Option<String>,
+    pub buy_decimals: Option<u32>,
+    /// Base units, as quoted; not read back from the receipt.
+    pub buy_amount: String,
+    /// Buy tokens per sell token.
+    pub price: Option<String>,
+    /// USD value sold, at quote-time prices.
+    pub sell_value_usd: Option<String>,
+    pub gas_cost_usd: Option<String>,
+    pub tx_hash: Option<String>,
+    pub time_ms: i64,
+}
+
+impl DbSwap {
+    /// `USDC→WETH`, falling back to addresses for unnamed tokens.
+    pub fn pair(&self) -> String {
+        format!(
+            "{}→{}",
+            self.sell_symbol.as_deref().unwrap_or(&self.sell_token),
+            self.buy_symbol.as_deref().unwrap_or(&self.buy_token)
+        )
+    }
+
+    /// Sell amount in token units, when decimals are known.
+    pub fn sell_tokens(&self) -> Option<Decimal> {
+        let raw: i128 = self.sell_amount.parse().ok()?;
+        Decimal::try_from_i128_with_scale(raw, self.sell_decimals?).ok()
+    }
+
+    /// Buy amount in token units, when decimals are known.
+    pub fn buy_tokens(&self) -> Option<Decimal> {
+        let raw: i128 = self.buy_amount.parse().ok()?;
+        Decimal::try_from_i128_with_scale(raw, self.buy_decimals?).ok()
+    }
+}
+
+/// Tokens bought through executed swaps, with what they cost and the
+/// latest cached price. PnL from this is unrealized: later sales of the
+/// token are not netted out.
+#[derive(Debug, Clone)]
+pub struct DbSwapPosition {
+    pub chain: String,
+    pub token: String,
+    pub symbol: Option<String>,
+    /// Token units bought.
+    pub amount: Decimal,
+    /// USD sold to buy them, at swap-time prices.
+    pub cost_usd: Decimal,
+    /// Gas paid for those swaps, where it was known.
+    pub gas_usd: Decimal,
+    pub price_usd: Option<Decimal>,
+    /// When `price_usd` was cached.
+    pub priced_ms: Option<i64>,
+    pub swaps: usize,
+}
+
+impl DbSwapPosition {
+    pub fn value_usd(&self) -> Option<Decimal> {
+        self.price_usd.map(|p| p * self.amount)
+    }
+
+    pub fn unrealized_pnl(&self) -> Option<Decimal> {
+        self.value_usd().map(|v| v - self.cost_usd)
+    }
+}
+
+/// What a new journal note is attached to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JournalTarget {
+    /// A cached fill, by hash.
+    Fill(String),
+    /// An order id; linked to the order's first fill.
+    Order(i64),
+    /// A coin and approximate time, for trades not synced yet.
+    CoinTime { coin: String, time_ms: i64 },
+}
+
+/// How far from the given time a coin+time note may match a fill.
+pub const JOURNAL_MATCH_WINDOW_MS: i64 = 10 * 60 * 1000;
+
+// ─── Schema migrations ──────────────────────────────────────────────
+
+/// One step of the schema history. Steps run in order, each in its own
+/// transaction together with its `schema_version` row, so a failure leaves
+/// the database at the last complete version.
+struct Migration {
+    version: u32,
+    name: &'static str,
+    /// `(table, column, definition)` added with `ALTER TABLE` before `sql`
+    /// runs, unless the column is already there: databases from before
+    /// versioning may have it.
+    add_columns: &'static [(&'static str, &'static str, &'static str)],
+    sql: &'static str,
+}
+
+/// The schema history. Append only: never edit a step that has shipped.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "fills, orders and sync state",
+        add_columns: &[],
+        sql: "
             CREATE TABLE IF NOT EXISTS fills (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
-                protocol TEXT NOT NULL DEFAULT 'hyperliquid',
                 coin TEXT NOT NULL,
                 px TEXT NOT NULL,
                 sz TEXT NOT NULL,
@@ -93,11 +395,9 @@ impl AtlasDb {
             );
             CREATE INDEX IF NOT EXISTS idx_fills_coin ON fills(coin);
             CREATE INDEX IF NOT EXISTS idx_fills_time ON fills(time_ms);
-            CREATE INDEX IF NOT EXISTS idx_fills_protocol ON fills(protocol);
 
             CREATE TABLE IF NOT EXISTS orders (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
-                protocol TEXT NOT NULL DEFAULT 'hyperliquid',
                 coin TEXT NOT NULL,
                 side TEXT NOT NULL,
                 limit_px TEXT NOT NULL,
@@ -109,45 +409,534 @@ impl AtlasDb {
             );
             CREATE INDEX IF NOT EXISTS idx_orders_coin ON orders(coin);
             CREATE INDEX IF NOT EXISTS idx_orders_time ON orders(timestamp_ms);
-            CREATE INDEX IF NOT EXISTS idx_orders_protocol ON orders(protocol);
 
             CREATE TABLE IF NOT EXISTS sync_state (
                 key TEXT PRIMARY KEY,
                 value TEXT NOT NULL,
                 updated_ms INTEGER NOT NULL
             );
-            ",
-            )
-            .context("Failed to initialize database tables")?;
+        ",
+    },
+    Migration {
+        version: 2,
+        name: "protocol column on fills and orders",
+        add_columns: &[
+            ("fills", "protocol", "TEXT NOT NULL DEFAULT 'hyperliquid'"),
+            ("orders", "protocol", "TEXT NOT NULL DEFAULT 'hyperliquid'"),
+        ],
+        sql: "
+            CREATE INDEX IF NOT EXISTS idx_fills_protocol ON fills(protocol);
+            CREATE INDEX IF NOT EXISTS idx_orders_protocol ON orders(protocol);
+        ",
+    },
+    Migration {
+        version: 3,
+        name: "open interest snapshots",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS oi_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                protocol TEXT NOT NULL DEFAULT 'hyperliquid',
+                coin TEXT NOT NULL,
+                time_ms INTEGER NOT NULL,
+                open_interest TEXT NOT NULL,
+                mark_px TEXT NOT NULL,
+                volume_24h TEXT NOT NULL,
+                funding TEXT NOT NULL DEFAULT '0',
+                UNIQUE(protocol, coin, time_ms)
+            );
+            CREATE INDEX IF NOT EXISTS idx_oi_coin_time ON oi_snapshots(coin, time_ms);
+        ",
+    },
+    Migration {
+        version: 4,
+        name: "trade journal",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS journal (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                fill_hash TEXT,
+                oid INTEGER,
+                coin TEXT,
+                time_ms INTEGER NOT NULL,
+                note TEXT NOT NULL,
+                tags TEXT NOT NULL DEFAULT '',
+                created_ms INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_journal_fill ON journal(fill_hash);
+            CREATE INDEX IF NOT EXISTS idx_journal_time ON journal(time_ms);
 
-        // Migration: add protocol column to existing DBs
-        self.migrate_add_protocol()?;
+            CREATE VIRTUAL TABLE IF NOT EXISTS journal_fts
+                USING fts5(note, tags, content='journal', content_rowid='id');
+            CREATE TRIGGER IF NOT EXISTS journal_fts_insert AFTER INSERT ON journal BEGIN
+                INSERT INTO journal_fts(rowid, note, tags) VALUES (new.id, new.note, new.tags);
+            END;
+            CREATE TRIGGER IF NOT EXISTS journal_fts_delete AFTER DELETE ON journal BEGIN
+                INSERT INTO journal_fts(journal_fts, rowid, note, tags)
+                    VALUES ('delete', old.id, old.note, old.tags);
+            END;
+            CREATE TRIGGER IF NOT EXISTS journal_fts_update AFTER UPDATE OF note, tags ON journal BEGIN
+                INSERT INTO journal_fts(journal_fts, rowid, note, tags)
+                    VALUES ('delete', old.id, old.note, old.tags);
+                INSERT INTO journal_fts(rowid, note, tags) VALUES (new.id, new.note, new.tags);
+            END;
+        ",
+    },
+    Migration {
+        version: 5,
+        name: "paper account",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS paper_account (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                state TEXT NOT NULL,
+                updated_ms INTEGER NOT NULL
+            );
+        ",
+    },
+    Migration {
+        version: 6,
+        name: "address book",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS address_book (
+                chain TEXT NOT NULL,
+                name TEXT NOT NULL COLLATE NOCASE,
+                address TEXT NOT NULL,
+                decimals INTEGER,
+                added_ms INTEGER NOT NULL,
+                UNIQUE(chain, name)
+            );
+        ",
+    },
+    Migration {
+        version: 7,
+        name: "swaps and token prices",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS swaps (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                status TEXT NOT NULL,
+                chain TEXT NOT NULL,
+                sell_token TEXT NOT NULL,
+                sell_symbol TEXT,
+                sell_decimals INTEGER,
+                sell_amount TEXT NOT NULL,
+                buy_token TEXT NOT NULL,
+                buy_symbol TEXT,
+                buy_decimals INTEGER,
+                buy_amount TEXT NOT NULL,
+                price TEXT,
+                sell_value_usd TEXT,
+                gas_cost_usd TEXT,
+                tx_hash TEXT UNIQUE,
+                time_ms INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_swaps_time ON swaps(time_ms);
 
-        Ok(())
+            CREATE TABLE IF NOT EXISTS token_prices (
+                chain TEXT NOT NULL,
+                address TEXT NOT NULL COLLATE NOCASE,
+                usd_price TEXT NOT NULL,
+                time_ms INTEGER NOT NULL,
+                PRIMARY KEY (chain, address)
+            );
+        ",
+    },
+    Migration {
+        version: 8,
+        name: "candle cache",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS candles (
+                source TEXT NOT NULL,
+                coin TEXT NOT NULL,
+                interval TEXT NOT NULL,
+                open_time_ms INTEGER NOT NULL,
+                open TEXT NOT NULL,
+                high TEXT NOT NULL,
+                low TEXT NOT NULL,
+                close TEXT NOT NULL,
+                volume TEXT NOT NULL,
+                trades INTEGER,
+                PRIMARY KEY (source, coin, interval, open_time_ms)
+            ) WITHOUT ROWID;
+
+            CREATE TABLE IF NOT EXISTS candle_coverage (
+                source TEXT NOT NULL,
+                coin TEXT NOT NULL,
+                interval TEXT NOT NULL,
+                interval_ms INTEGER NOT NULL,
+                from_ms INTEGER NOT NULL,
+                to_ms INTEGER NOT NULL,
+                PRIMARY KEY (source, coin, interval)
+            );
+        ",
+    },
+    Migration {
+        version: 9,
+        name: "order nonces",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS nonces (
+                address TEXT PRIMARY KEY COLLATE NOCASE,
+                last_nonce INTEGER NOT NULL,
+                updated_ms INTEGER NOT NULL
+            );
+        ",
+    },
+    Migration {
+        version: 10,
+        name: "order lifecycle columns",
+        add_columns: &[
+            ("orders", "filled_sz", "TEXT NOT NULL DEFAULT '0'"),
+            ("orders", "avg_px", "TEXT"),
+            ("orders", "status_updated_at", "INTEGER"),
+        ],
+        sql: "",
+    },
+    Migration {
+        version: 11,
+        name: "dex pool watchlist",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS dex_watchlist (
+                network TEXT NOT NULL,
+                pool TEXT NOT NULL COLLATE NOCASE,
+                label TEXT COLLATE NOCASE UNIQUE,
+                added_ms INTEGER NOT NULL,
+                last_price_usd TEXT,
+                last_volume_usd TEXT,
+                last_liquidity_usd TEXT,
+                last_checked_ms INTEGER,
+                missing_since_ms INTEGER,
+                PRIMARY KEY (network, pool)
+            );
+        ",
+    },
+    Migration {
+        version: 12,
+        name: "status snapshots",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS status_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                profile TEXT NOT NULL,
+                network TEXT NOT NULL,
+                taken_ms INTEGER NOT NULL,
+                snapshot TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_status_snapshots_key
+                ON status_snapshots(profile, network, id);
+        ",
+    },
+    Migration {
+        version: 13,
+        name: "schedules",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS schedules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                command TEXT NOT NULL,
+                every_ms INTEGER NOT NULL,
+                at TEXT,
+                max_runs INTEGER,
+                runs INTEGER NOT NULL DEFAULT 0,
+                next_run_ms INTEGER NOT NULL,
+                created_ms INTEGER NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS schedule_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                schedule_id INTEGER NOT NULL,
+                due_ms INTEGER NOT NULL,
+                started_ms INTEGER NOT NULL,
+                finished_ms INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                missed INTEGER NOT NULL DEFAULT 0,
+                oid TEXT,
+                fill_price TEXT,
+                error TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_schedule_runs_schedule
+                ON schedule_runs(schedule_id, id);
+        ",
+    },
+    Migration {
+        version: 14,
+        name: "transfer ledger",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS transfers (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                hash TEXT UNIQUE NOT NULL,
+                time_ms INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                ledger_type TEXT NOT NULL,
+                direction TEXT NOT NULL,
+                token TEXT NOT NULL,
+                amount TEXT NOT NULL,
+                usd_value TEXT,
+                fee TEXT NOT NULL DEFAULT '0',
+                counterparty TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_transfers_time ON transfers(time_ms);
+        ",
+    },
+    Migration {
+        version: 15,
+        name: "backend response cache",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS backend_cache (
+                key TEXT PRIMARY KEY,
+                body TEXT NOT NULL,
+                fetched_ms INTEGER NOT NULL
+            );
+        ",
+    },
+    Migration {
+        version: 16,
+        name: "funding payments",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS funding_payments (
+                coin TEXT NOT NULL,
+                time_ms INTEGER NOT NULL,
+                usdc TEXT NOT NULL,
+                szi TEXT NOT NULL,
+                funding_rate TEXT NOT NULL,
+                PRIMARY KEY (coin, time_ms)
+            );
+            CREATE INDEX IF NOT EXISTS idx_funding_time ON funding_payments(time_ms);
+        ",
+    },
+    Migration {
+        version: 17,
+        name: "spread samples",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS spread_samples (
+                protocol TEXT NOT NULL,
+                coin TEXT NOT NULL,
+                time_ms INTEGER NOT NULL,
+                spread_bps TEXT NOT NULL,
+                PRIMARY KEY (protocol, coin, time_ms)
+            );
+            CREATE INDEX IF NOT EXISTS idx_spread_time ON spread_samples(time_ms);
+        ",
+    },
+    Migration {
+        version: 18,
+        name: "audit log",
+        add_columns: &[],
+        sql: "
+            CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_ms INTEGER NOT NULL,
+                protocol TEXT NOT NULL,
+                action_type TEXT NOT NULL,
+                coin TEXT,
+                params_hash TEXT NOT NULL,
+                nonce INTEGER,
+                signer TEXT NOT NULL,
+                request TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                response TEXT,
+                completed_ms INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_audit_created ON audit_log(created_ms);
+            CREATE TRIGGER IF NOT EXISTS audit_log_no_delete BEFORE DELETE ON audit_log
+            BEGIN
+                SELECT RAISE(ABORT, 'audit_log is append-only');
+            END;
+            CREATE TRIGGER IF NOT EXISTS audit_log_request_fixed
+            BEFORE UPDATE OF id, created_ms, protocol, action_type, coin, params_hash, nonce,
+                signer, request ON audit_log
+            BEGIN
+                SELECT RAISE(ABORT, 'audit_log requests cannot be changed');
+            END;
+            CREATE TRIGGER IF NOT EXISTS audit_log_complete_once BEFORE UPDATE ON audit_log
+            WHEN OLD.status != 'pending'
+            BEGIN
+                SELECT RAISE(ABORT, 'audit_log entry already completed');
+            END;
+        ",
+    },
+];
+
+/// Schema version this build writes: the last migration.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Where a database file stands against [`SCHEMA_VERSION`].
+#[derive(Debug, Clone)]
+pub struct SchemaStatus {
+    /// Last applied migration; 0 for an empty or pre-versioning database.
+    pub current: u32,
+    /// `(version, name)` of each migration not applied yet.
+    pub pending: Vec<(u32, &'static str)>,
+}
+
+impl SchemaStatus {
+    /// Written by a newer atlas than this one.
+    pub fn is_newer(&self) -> bool {
+        self.current > SCHEMA_VERSION
     }
+}
 
-    /// Migration: add `protocol` column if missing (for DBs created before multi-protocol).
-    fn migrate_add_protocol(&self) -> Result<()> {
-        // Check if fills has protocol column
-        let has_protocol: bool = self
-            .conn
-            .prepare("SELECT protocol FROM fills LIMIT 0")
-            .is_ok();
+/// Last applied migration, without creating anything.
+fn schema_version(conn: &Connection) -> Result<u32> {
+    let versioned: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !versioned {
+        return Ok(0);
+    }
+    let version: Option<u32> =
+        conn.query_row("SELECT MAX(version) FROM schema_version", [], |row| {
+            row.get(0)
+        })?;
+    Ok(version.unwrap_or(0))
+}
 
-        if !has_protocol {
-            self.conn
-                .execute_batch(
-                    "
-                ALTER TABLE fills ADD COLUMN protocol TEXT NOT NULL DEFAULT 'hyperliquid';
-                ALTER TABLE orders ADD COLUMN protocol TEXT NOT NULL DEFAULT 'hyperliquid';
-                CREATE INDEX IF NOT EXISTS idx_fills_protocol ON fills(protocol);
-                CREATE INDEX IF NOT EXISTS idx_orders_protocol ON orders(protocol);
-                ",
+fn schema_status(conn: &Connection) -> Result<SchemaStatus> {
+    let current = schema_version(conn)?;
+    Ok(SchemaStatus {
+        current,
+        pending: MIGRATIONS
+            .iter()
+            .filter(|m| m.version > current)
+            .map(|m| (m.version, m.name))
+            .collect(),
+    })
+}
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> bool {
+    conn.prepare(&format!("SELECT {column} FROM {table} LIMIT 0"))
+        .is_ok()
+}
+
+/// Apply every migration above the current version, up to `target`.
+/// Refuses a database newer than this build. Returns the versions applied.
+fn migrate_to(conn: &Connection, target: u32) -> Result<Vec<u32>> {
+    let current = schema_version(conn)?;
+    if current > SCHEMA_VERSION {
+        anyhow::bail!(
+            "Database schema v{current} is newer than this atlas supports (v{SCHEMA_VERSION}). \
+             Upgrade atlas to use this database."
+        );
+    }
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_ms INTEGER NOT NULL
+        );",
+    )?;
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|m| m.version > current && m.version <= target)
+    {
+        // Immediate: another atlas process may be migrating the same file
+        let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+        if schema_version(&tx)? >= migration.version {
+            continue;
+        }
+        migration
+            .add_columns
+            .iter()
+            .filter(|(table, column, _)| !has_column(&tx, table, column))
+            .try_for_each(|(table, column, definition)| {
+                tx.execute_batch(&format!(
+                    "ALTER TABLE {table} ADD COLUMN {column} {definition};"
+                ))
+            })
+            .and_then(|_| tx.execute_batch(migration.sql))
+            .and_then(|_| {
+                tx.execute(
+                    "INSERT INTO schema_version (version, name, applied_ms) VALUES (?1, ?2, ?3)",
+                    params![
+                        migration.version,
+                        migration.name,
+                        chrono::Utc::now().timestamp_millis()
+                    ],
+                )
+            })
+            .and_then(|_| tx.commit())
+            .with_context(|| {
+                format!(
+                    "Failed to migrate database to v{} ({})",
+                    migration.version, migration.name
                 )
-                .context("Failed to migrate: add protocol column")?;
+            })?;
+        tracing::info!(
+            version = migration.version,
+            name = migration.name,
+            "applied database migration"
+        );
+        applied.push(migration.version);
+    }
+    Ok(applied)
+}
+
+/// Local SQLite database handle.
+pub struct AtlasDb {
+    conn: Connection,
+}
+
+impl AtlasDb {
+    /// Open (or create) the database at `~/.atlas-os/data/atlas.db`.
+    /// Enables WAL mode and applies pending schema migrations.
+    pub fn open() -> Result<Self> {
+        Self::open_at(&Self::path()?)
+    }
+
+    /// Open (or create) the database at `db_path`.
+    pub fn open_at(db_path: &std::path::Path) -> Result<Self> {
+        // Ensure parent directory exists
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create DB directory: {}", parent.display()))?;
         }
 
-        Ok(())
+        let conn = Connection::open(db_path)
+            .with_context(|| format!("Failed to open database: {}", db_path.display()))?;
+
+        // Other atlas processes hold the write lock only briefly: wait for it
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+
+        // Enable WAL mode for concurrent access
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+
+        migrate_to(&conn, SCHEMA_VERSION)?;
+        Ok(Self { conn })
+    }
+
+    /// Location of the database file.
+    pub fn path() -> Result<std::path::PathBuf> {
+        crate::workspace::resolve("data/atlas.db")
+    }
+
+    /// Schema version of the existing file at `db_path` and the migrations
+    /// that opening it would apply. Nothing is migrated or created.
+    pub fn schema_status_at(db_path: &std::path::Path) -> Result<SchemaStatus> {
+        let conn =
+            Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE)
+                .with_context(|| format!("Failed to open database: {}", db_path.display()))?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        schema_status(&conn)
+    }
+
+    /// Open an in-memory database (for testing).
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory()?;
+        migrate_to(&conn, SCHEMA_VERSION)?;
+        Ok(Self { conn })
     }
 
     // ─── Fills ──────────────────────────────────────────────────────
@@ -185,6 +974,20 @@ impl AtlasDb {
         Ok(inserted)
     }
 
+    /// Which of `hashes` are already cached as fills.
+    pub fn existing_fill_hashes(&self, hashes: &[String]) -> Result<HashSet<String>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT 1 FROM fills WHERE hash = ?1")?;
+        let mut existing = HashSet::new();
+        for hash in hashes {
+            if stmt.exists(params![hash])? {
+                existing.insert(hash.clone());
+            }
+        }
+        Ok(existing)
+    }
+
     /// Query fills with optional filters.
     pub fn query_fills(&self, filter: &FillFilter) -> Result<Vec<DbFill>> {
         let mut sql = String::from(
@@ -248,33 +1051,66 @@ impl AtlasDb {
         Ok(result)
     }
 
-    // ─── Orders ─────────────────────────────────────────────────────
+    /// Time of the oldest cached fill, optionally for one protocol.
+    pub fn first_fill_time(&self, protocol: Option<&str>) -> Result<Option<i64>> {
+        let result: Option<i64> = match protocol {
+            Some(p) => self.conn.query_row(
+                "SELECT MIN(time_ms) FROM fills WHERE protocol = ?1",
+                params![p],
+                |row| row.get(0),
+            )?,
+            None => self
+                .conn
+                .query_row("SELECT MIN(time_ms) FROM fills", [], |row| row.get(0))?,
+        };
+        Ok(result)
+    }
 
-    /// Insert orders into the database (upsert by oid).
+    /// Realized PnL net of fees for all fills at or after `from_ms`.
+    pub fn realized_pnl_since(&self, from_ms: i64) -> Result<f64> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT closed_pnl, fee FROM fills WHERE time_ms >= ?1")?;
+        let rows = stmt.query_map(params![from_ms], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut total = 0.0;
+        for row in rows {
+            let (pnl, fee) = row?;
+            total += pnl.parse::<f64>().unwrap_or(0.0) - fee.parse::<f64>().unwrap_or(0.0);
+        }
+        Ok(total)
+    }
+
+    // ─── Transfers ──────────────────────────────────────────────────
+
+    /// Insert ledger entries (skips hashes already cached).
     /// Returns the number of newly inserted rows.
-    pub fn insert_orders(&self, orders: &[DbOrder]) -> Result<usize> {
+    pub fn insert_transfers(&self, transfers: &[DbTransfer]) -> Result<usize> {
         let mut inserted = 0usize;
         let tx = self.conn.unchecked_transaction()?;
 
         {
             let mut stmt = tx.prepare_cached(
-                "INSERT OR REPLACE INTO orders (protocol, coin, side, limit_px, sz, oid, timestamp_ms, status, order_type)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+                "INSERT OR IGNORE INTO transfers (hash, time_ms, kind, ledger_type, direction, token,
+                    amount, usd_value, fee, counterparty)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             )?;
 
-            for order in orders {
-                let rows = stmt.execute(params![
-                    order.protocol,
-                    order.coin,
-                    order.side,
-                    order.limit_px,
-                    order.sz,
-                    order.oid,
-                    order.timestamp_ms,
-                    order.status,
-                    order.order_type,
+            for t in transfers {
+                inserted += stmt.execute(params![
+                    t.hash,
+                    t.time_ms,
+                    t.kind,
+                    t.ledger_type,
+                    t.direction,
+                    t.token,
+                    t.amount,
+                    t.usd_value,
+                    t.fee,
+                    t.counterparty,
                 ])?;
-                inserted += rows;
             }
         }
 
@@ -282,27 +1118,1541 @@ impl AtlasDb {
         Ok(inserted)
     }
 
-    /// Query orders with optional filters.
-    pub fn query_orders(&self, filter: &OrderFilter) -> Result<Vec<DbOrder>> {
+    /// Query cached ledger entries, newest first.
+    pub fn query_transfers(&self, filter: &TransferFilter) -> Result<Vec<DbTransfer>> {
         let mut sql = String::from(
-            "SELECT protocol, coin, side, limit_px, sz, oid, timestamp_ms, status, order_type FROM orders WHERE 1=1"
+            "SELECT hash, time_ms, kind, ledger_type, direction, token, amount, usd_value, fee,
+                counterparty
+             FROM transfers WHERE 1=1",
         );
         let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
 
-        if let Some(ref protocol) = filter.protocol {
-            sql.push_str(" AND protocol = ?");
-            bind_values.push(Box::new(protocol.clone()));
-        }
-        if let Some(ref coin) = filter.coin {
-            sql.push_str(" AND coin = ?");
-            bind_values.push(Box::new(coin.clone()));
+        if let Some(ref kind) = filter.kind {
+            sql.push_str(" AND kind = ?");
+            bind_values.push(Box::new(kind.clone()));
         }
-        if let Some(ref status) = filter.status {
-            sql.push_str(" AND status = ?");
-            bind_values.push(Box::new(status.clone()));
+        if let Some(from) = filter.from_ms {
+            sql.push_str(" AND time_ms >= ?");
+            bind_values.push(Box::new(from));
         }
+        if let Some(to) = filter.to_ms {
+            sql.push_str(" AND time_ms <= ?");
+            bind_values.push(Box::new(to));
+        }
+
+        sql.push_str(" ORDER BY time_ms DESC, id DESC");
+
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            bind_values.iter().map(|b| b.as_ref()).collect();
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_refs.as_slice(), |row| {
+            Ok(DbTransfer {
+                hash: row.get(0)?,
+                time_ms: row.get(1)?,
+                kind: row.get(2)?,
+                ledger_type: row.get(3)?,
+                direction: row.get(4)?,
+                token: row.get(5)?,
+                amount: row.get(6)?,
+                usd_value: row.get(7)?,
+                fee: row.get(8)?,
+                counterparty: row.get(9)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read transfers")
+    }
+
+    /// Time of the newest cached ledger entry.
+    pub fn last_transfer_time(&self) -> Result<Option<i64>> {
+        let mut stmt = self.conn.prepare("SELECT MAX(time_ms) FROM transfers")?;
+        let result: Option<i64> = stmt.query_row([], |row| row.get(0))?;
+        Ok(result)
+    }
+
+    // ─── Funding ────────────────────────────────────────────────────
+
+    /// Insert funding payments (skips coin+time pairs already cached).
+    /// Returns the number of newly inserted rows.
+    pub fn insert_funding(&self, payments: &[DbFunding]) -> Result<usize> {
+        let mut inserted = 0usize;
+        let tx = self.conn.unchecked_transaction()?;
+
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR IGNORE INTO funding_payments (coin, time_ms, usdc, szi, funding_rate)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for f in payments {
+                inserted +=
+                    stmt.execute(params![f.coin, f.time_ms, f.usdc, f.szi, f.funding_rate])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Cached funding payments in `[from_ms, to_ms]` (either open), oldest
+    /// first.
+    pub fn query_funding(
+        &self,
+        from_ms: Option<i64>,
+        to_ms: Option<i64>,
+    ) -> Result<Vec<DbFunding>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT coin, time_ms, usdc, szi, funding_rate FROM funding_payments
+             WHERE time_ms >= ?1 AND time_ms <= ?2
+             ORDER BY time_ms, coin",
+        )?;
+        let rows = stmt.query_map(
+            params![from_ms.unwrap_or(i64::MIN), to_ms.unwrap_or(i64::MAX)],
+            |row| {
+                Ok(DbFunding {
+                    coin: row.get(0)?,
+                    time_ms: row.get(1)?,
+                    usdc: row.get(2)?,
+                    szi: row.get(3)?,
+                    funding_rate: row.get(4)?,
+                })
+            },
+        )?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read funding payments")
+    }
+
+    /// Time of the newest cached funding payment.
+    pub fn last_funding_time(&self) -> Result<Option<i64>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT MAX(time_ms) FROM funding_payments")?;
+        let result: Option<i64> = stmt.query_row([], |row| row.get(0))?;
+        Ok(result)
+    }
+
+    // ─── Orders ─────────────────────────────────────────────────────
+
+    /// Insert orders into the database (upsert by oid).
+    /// Returns the number of newly inserted rows.
+    pub fn insert_orders(&self, orders: &[DbOrder]) -> Result<usize> {
+        let mut inserted = 0usize;
+        let tx = self.conn.unchecked_transaction()?;
+
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR REPLACE INTO orders (protocol, coin, side, limit_px, sz, oid, timestamp_ms, status, order_type, filled_sz, avg_px, status_updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)"
+            )?;
+
+            for order in orders {
+                let rows = stmt.execute(params![
+                    order.protocol,
+                    order.coin,
+                    order.side,
+                    order.limit_px,
+                    order.sz,
+                    order.oid,
+                    order.timestamp_ms,
+                    order.status,
+                    order.order_type,
+                    order.filled_sz,
+                    order.avg_px,
+                    order.status_updated_at,
+                ])?;
+                inserted += rows;
+            }
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Record orders as just seen on the exchange: unknown oids are
+    /// inserted, cached ones get their status, filled size, average price
+    /// and confirmation time updated in place.
+    /// Returns `(inserted, changed)` — changed counts cached orders whose
+    /// status or filled size moved.
+    pub fn update_orders(&self, orders: &[DbOrder]) -> Result<(usize, usize)> {
+        let (mut inserted, mut changed) = (0usize, 0usize);
+        let tx = self.conn.unchecked_transaction()?;
+
+        {
+            let mut select =
+                tx.prepare_cached("SELECT status, filled_sz FROM orders WHERE oid = ?1")?;
+            let mut update = tx.prepare_cached(
+                "UPDATE orders SET status = ?2, filled_sz = ?3, avg_px = ?4, status_updated_at = ?5
+                 WHERE oid = ?1",
+            )?;
+            let mut insert = tx.prepare_cached(
+                "INSERT INTO orders (protocol, coin, side, limit_px, sz, oid, timestamp_ms, status, order_type, filled_sz, avg_px, status_updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            )?;
+
+            for order in orders {
+                let cached = match select.query_row(params![order.oid], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                }) {
+                    Ok(row) => Some(row),
+                    Err(rusqlite::Error::QueryReturnedNoRows) => None,
+                    Err(e) => return Err(e.into()),
+                };
+                match cached {
+                    Some((status, filled_sz)) => {
+                        update.execute(params![
+                            order.oid,
+                            order.status,
+                            order.filled_sz,
+                            order.avg_px,
+                            order.status_updated_at,
+                        ])?;
+                        if status != order.status || filled_sz != order.filled_sz {
+                            changed += 1;
+                        }
+                    }
+                    None => {
+                        insert.execute(params![
+                            order.protocol,
+                            order.coin,
+                            order.side,
+                            order.limit_px,
+                            order.sz,
+                            order.oid,
+                            order.timestamp_ms,
+                            order.status,
+                            order.order_type,
+                            order.filled_sz,
+                            order.avg_px,
+                            order.status_updated_at,
+                        ])?;
+                        inserted += 1;
+                    }
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok((inserted, changed))
+    }
+
+    /// Volume-weighted price of the cached fills of order `oid`
+    /// (`None` without fills).
+    pub fn avg_fill_px(&self, oid: i64) -> Result<Option<Decimal>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT px, sz FROM fills WHERE oid = ?1")?;
+        let rows = stmt.query_map(params![oid], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let (mut notional, mut size) = (Decimal::ZERO, Decimal::ZERO);
+        for row in rows {
+            let (px, sz) = row?;
+            let (Ok(px), Ok(sz)) = (px.parse::<Decimal>(), sz.parse::<Decimal>()) else {
+                continue;
+            };
+            notional += px * sz;
+            size += sz;
+        }
+        Ok((!size.is_zero()).then(|| (notional / size).normalize()))
+    }
+
+    /// Query orders with optional filters.
+    pub fn query_orders(&self, filter: &OrderFilter) -> Result<Vec<DbOrder>> {
+        let mut sql = String::from(
+            "SELECT protocol, coin, side, limit_px, sz, oid, timestamp_ms, status, order_type, filled_sz, avg_px, status_updated_at FROM orders WHERE 1=1"
+        );
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        if let Some(ref protocol) = filter.protocol {
+            sql.push_str(" AND protocol = ?");
+            bind_values.push(Box::new(protocol.clone()));
+        }
+        if let Some(ref coin) = filter.coin {
+            sql.push_str(" AND coin = ?");
+            bind_values.push(Box::new(coin.clone()));
+        }
+        if let Some(ref status) = filter.status {
+            sql.push_str(" AND status = ?");
+            bind_values.push(Box::new(status.clone()));
+        }
+        if let Some(from) = filter.from_ms {
+            sql.push_str(" AND timestamp_ms >= ?");
+            bind_values.push(Box::new(from));
+        }
+        if let Some(to) = filter.to_ms {
+            sql.push_str(" AND timestamp_ms <= ?");
+            bind_values.push(Box::new(to));
+        }
+        if let Some(before_ms) = filter.stale_before_ms {
+            let open = OPEN_ORDER_STATUSES.map(|s| format!("'{s}'")).join(", ");
+            sql.push_str(&format!(
+                " AND status IN ({open}) AND (status_updated_at IS NULL OR status_updated_at < ?)"
+            ));
+            bind_values.push(Box::new(before_ms));
+        }
+
+        sql.push_str(" ORDER BY timestamp_ms DESC");
+
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            bind_values.iter().map(|b| b.as_ref()).collect();
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_refs.as_slice(), |row| {
+            Ok(DbOrder {
+                protocol: row.get(0)?,
+                coin: row.get(1)?,
+                side: row.get(2)?,
+                limit_px: row.get(3)?,
+                sz: row.get(4)?,
+                oid: row.get(5)?,
+                timestamp_ms: row.get(6)?,
+                status: row.get(7)?,
+                order_type: row.get(8)?,
+                filled_sz: row.get(9)?,
+                avg_px: row.get(10)?,
+                status_updated_at: row.get(11)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    // ─── OI Snapshots ───────────────────────────────────────────────
+
+    /// Insert OI snapshots (one row per protocol/coin/time; repeats are ignored).
+    /// Returns the number of newly inserted rows.
+    pub fn insert_oi_snapshots(&self, snapshots: &[DbOiSnapshot]) -> Result<usize> {
+        let mut inserted = 0usize;
+        let tx = self.conn.unchecked_transaction()?;
+
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR IGNORE INTO oi_snapshots (protocol, coin, time_ms, open_interest, mark_px, volume_24h, funding)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+
+            for snap in snapshots {
+                inserted += stmt.execute(params![
+                    snap.protocol,
+                    snap.coin,
+                    snap.time_ms,
+                    snap.open_interest,
+                    snap.mark_px,
+                    snap.volume_24h,
+                    snap.funding,
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Snapshots for one coin at or after `from_ms`, oldest first.
+    pub fn query_oi_snapshots(
+        &self,
+        protocol: &str,
+        coin: &str,
+        from_ms: i64,
+    ) -> Result<Vec<DbOiSnapshot>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT protocol, coin, time_ms, open_interest, mark_px, volume_24h, funding
+             FROM oi_snapshots WHERE protocol = ?1 AND coin = ?2 AND time_ms >= ?3
+             ORDER BY time_ms ASC",
+        )?;
+        let rows = stmt.query_map(params![protocol, coin, from_ms], |row| {
+            Ok(DbOiSnapshot {
+                protocol: row.get(0)?,
+                coin: row.get(1)?,
+                time_ms: row.get(2)?,
+                open_interest: row.get(3)?,
+                mark_px: row.get(4)?,
+                volume_24h: row.get(5)?,
+                funding: row.get(6)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Snapshots of every coin between `from_ms` and `to_ms` (inclusive),
+    /// oldest first.
+    pub fn query_oi_snapshots_between(
+        &self,
+        protocol: &str,
+        from_ms: i64,
+        to_ms: i64,
+    ) -> Result<Vec<DbOiSnapshot>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT protocol, coin, time_ms, open_interest, mark_px, volume_24h, funding
+             FROM oi_snapshots WHERE protocol = ?1 AND time_ms BETWEEN ?2 AND ?3
+             ORDER BY time_ms ASC, coin ASC",
+        )?;
+        let rows = stmt.query_map(params![protocol, from_ms, to_ms], |row| {
+            Ok(DbOiSnapshot {
+                protocol: row.get(0)?,
+                coin: row.get(1)?,
+                time_ms: row.get(2)?,
+                open_interest: row.get(3)?,
+                mark_px: row.get(4)?,
+                volume_24h: row.get(5)?,
+                funding: row.get(6)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Delete snapshots older than `before_ms`. Returns rows removed.
+    pub fn prune_oi_snapshots(&self, before_ms: i64) -> Result<usize> {
+        let removed = self.conn.execute(
+            "DELETE FROM oi_snapshots WHERE time_ms < ?1",
+            params![before_ms],
+        )?;
+        Ok(removed)
+    }
+
+    // ─── Spread Samples ─────────────────────────────────────────────
+
+    /// Insert spread samples; a coin already sampled in the same minute
+    /// keeps its first sample. Returns the number of newly inserted rows.
+    pub fn insert_spread_samples(&self, samples: &[DbSpreadSample]) -> Result<usize> {
+        let mut inserted = 0usize;
+        let tx = self.conn.unchecked_transaction()?;
+
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR IGNORE INTO spread_samples (protocol, coin, time_ms, spread_bps)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+
+            for sample in samples {
+                inserted += stmt.execute(params![
+                    sample.protocol,
+                    sample.coin,
+                    sample.time_ms,
+                    sample.spread_bps,
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Samples for one coin at or after `from_ms`, oldest first.
+    pub fn query_spread_samples(
+        &self,
+        protocol: &str,
+        coin: &str,
+        from_ms: i64,
+    ) -> Result<Vec<DbSpreadSample>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT protocol, coin, time_ms, spread_bps
+             FROM spread_samples WHERE protocol = ?1 AND coin = ?2 AND time_ms >= ?3
+             ORDER BY time_ms ASC",
+        )?;
+        let rows = stmt.query_map(params![protocol, coin, from_ms], |row| {
+            Ok(DbSpreadSample {
+                protocol: row.get(0)?,
+                coin: row.get(1)?,
+                time_ms: row.get(2)?,
+                spread_bps: row.get(3)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Delete spread samples older than `before_ms`. Returns rows removed.
+    pub fn prune_spread_samples(&self, before_ms: i64) -> Result<usize> {
+        let removed = self.conn.execute(
+            "DELETE FROM spread_samples WHERE time_ms < ?1",
+            params![before_ms],
+        )?;
+        Ok(removed)
+    }
+
+    // ─── Candle Cache ───────────────────────────────────────────────
+
+    /// Insert or replace closed candles of one series. Returns rows written.
+    pub fn upsert_candles(
+        &self,
+        source: &str,
+        coin: &str,
+        interval: &str,
+        candles: &[Candle],
+    ) -> Result<usize> {
+        let mut written = 0usize;
+        let tx = self.conn.unchecked_transaction()?;
+
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR REPLACE INTO candles
+                 (source, coin, interval, open_time_ms, open, high, low, close, volume, trades)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+
+            for c in candles {
+                written += stmt.execute(params![
+                    source,
+                    coin,
+                    interval,
+                    c.open_time_ms as i64,
+                    c.open.to_string(),
+                    c.high.to_string(),
+                    c.low.to_string(),
+                    c.close.to_string(),
+                    c.volume.to_string(),
+                    c.trades.map(|n| n as i64),
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(written)
+    }
+
+    /// Cached candles with open times in `[from_ms, to_ms]`, oldest first.
+    pub fn query_candles(
+        &self,
+        source: &str,
+        coin: &str,
+        interval: &str,
+        from_ms: u64,
+        to_ms: u64,
+    ) -> Result<Vec<Candle>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT open_time_ms, open, high, low, close, volume, trades FROM candles
+             WHERE source = ?1 AND coin = ?2 AND interval = ?3
+               AND open_time_ms >= ?4 AND open_time_ms <= ?5
+             ORDER BY open_time_ms ASC",
+        )?;
+        let rows = stmt.query_map(
+            params![source, coin, interval, from_ms as i64, to_ms as i64],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    [
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                        row.get::<_, String>(5)?,
+                    ],
+                    row.get::<_, Option<i64>>(6)?,
+                ))
+            },
+        )?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let (open_time_ms, prices, trades) = row?;
+            let [open, high, low, close, volume] = prices.map(|p| p.parse::<Decimal>());
+            results.push(Candle {
+                open_time_ms: open_time_ms as u64,
+                open: open?,
+                high: high?,
+                low: low?,
+                close: close?,
+                volume: volume?,
+                trades: trades.map(|n| n as u64),
+            });
+        }
+        Ok(results)
+    }
+
+    /// The contiguous span of open times `(from_ms, to_ms)` already fetched
+    /// for a series. Candles missing inside it do not exist on the exchange.
+    pub fn candle_coverage(
+        &self,
+        source: &str,
+        coin: &str,
+        interval: &str,
+    ) -> Result<Option<(u64, u64)>> {
+        let result = self.conn.query_row(
+            "SELECT from_ms, to_ms FROM candle_coverage
+             WHERE source = ?1 AND coin = ?2 AND interval = ?3",
+            params![source, coin, interval],
+            |row| Ok((row.get::<_, i64>(0)? as u64, row.get::<_, i64>(1)? as u64)),
+        );
+        match result {
+            Ok(span) => Ok(Some(span)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Record the fetched span of a series, replacing the previous one.
+    pub fn set_candle_coverage(
+        &self,
+        source: &str,
+        coin: &str,
+        interval: &str,
+        interval_ms: u64,
+        from_ms: u64,
+        to_ms: u64,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO candle_coverage
+             (source, coin, interval, interval_ms, from_ms, to_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                source,
+                coin,
+                interval,
+                interval_ms as i64,
+                from_ms as i64,
+                to_ms as i64
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Delete cached candles that opened before `before_ms`, optionally for
+    /// one coin (any case) or interval. Coverage shrinks to match, so the
+    /// pruned range is refetched if a later request needs it again.
+    /// Returns candles removed.
+    pub fn prune_candles(
+        &self,
+        coin: Option<&str>,
+        interval: Option<&str>,
+        before_ms: i64,
+    ) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let removed = tx.execute(
+            "DELETE FROM candles WHERE open_time_ms < ?1
+               AND (?2 IS NULL OR coin = ?2 COLLATE NOCASE)
+               AND (?3 IS NULL OR interval = ?3)",
+            params![before_ms, coin, interval],
+        )?;
+        tx.execute(
+            "DELETE FROM candle_coverage WHERE to_ms < ?1
+               AND (?2 IS NULL OR coin = ?2 COLLATE NOCASE)
+               AND (?3 IS NULL OR interval = ?3)",
+            params![before_ms, coin, interval],
+        )?;
+        // First open time at or after the cutoff
+        tx.execute(
+            "UPDATE candle_coverage
+             SET from_ms = MAX(from_ms, (?1 + interval_ms - 1) / interval_ms * interval_ms)
+             WHERE (?2 IS NULL OR coin = ?2 COLLATE NOCASE)
+               AND (?3 IS NULL OR interval = ?3)",
+            params![before_ms.max(0), coin, interval],
+        )?;
+        tx.execute("DELETE FROM candle_coverage WHERE from_ms > to_ms", [])?;
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    /// Cached series, optionally for one coin (any case) or interval.
+    pub fn candle_series(
+        &self,
+        coin: Option<&str>,
+        interval: Option<&str>,
+    ) -> Result<Vec<DbCandleSeries>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT source, coin, interval, COUNT(*), MIN(open_time_ms), MAX(open_time_ms)
+             FROM candles
+             WHERE (?1 IS NULL OR coin = ?1 COLLATE NOCASE) AND (?2 IS NULL OR interval = ?2)
+             GROUP BY source, coin, interval
+             ORDER BY coin, source, interval",
+        )?;
+        let rows = stmt.query_map(params![coin, interval], |row| {
+            Ok(DbCandleSeries {
+                source: row.get(0)?,
+                coin: row.get(1)?,
+                interval: row.get(2)?,
+                candles: row.get::<_, i64>(3)? as usize,
+                oldest_ms: row.get(4)?,
+                newest_ms: row.get(5)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    // ─── Sync State ─────────────────────────────────────────────────
+
+    /// Get a sync state value by key.
+    pub fn get_sync_state(&self, key: &str) -> Result<Option<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT value FROM sync_state WHERE key = ?1")?;
+        let result = stmt.query_row(params![key], |row| row.get::<_, String>(0));
+        match result {
+            Ok(val) => Ok(Some(val)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Set a sync state value.
+    pub fn set_sync_state(&self, key: &str, value: &str) -> Result<()> {
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO sync_state (key, value, updated_ms) VALUES (?1, ?2, ?3)",
+            params![key, value, now_ms],
+        )?;
+        Ok(())
+    }
+
+    // ─── Nonces ─────────────────────────────────────────────────────
+
+    /// Reserve the next exchange nonce for `address`: `max(now_ms, last + 1)`.
+    ///
+    /// Runs under SQLite's write lock (`BEGIN IMMEDIATE`), so atlas processes
+    /// signing for the same wallet at the same moment never share a nonce.
+    pub fn next_nonce(&self, address: &str, now_ms: u64) -> Result<u64> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let last = match tx.query_row(
+            "SELECT last_nonce FROM nonces WHERE address = ?1",
+            params![address],
+            // Garbage in the row restarts the sequence from the clock
+            |row| Ok(row.get::<_, i64>(0).ok()),
+        ) {
+            Ok(last) => last.and_then(|last| u64::try_from(last).ok()),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(e.into()),
+        };
+        let nonce = last.map_or(now_ms, |last| now_ms.max(last + 1));
+        tx.execute(
+            "INSERT OR REPLACE INTO nonces (address, last_nonce, updated_ms) VALUES (?1, ?2, ?3)",
+            params![address, nonce as i64, now_ms as i64],
+        )?;
+        tx.commit()?;
+        Ok(nonce)
+    }
+
+    /// Last nonce used by each wallet. A value that isn't an integer reads
+    /// as `-1`, so it can be found and cleared.
+    pub fn nonces(&self) -> Result<Vec<DbNonce>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT address, last_nonce, updated_ms FROM nonces ORDER BY address")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(DbNonce {
+                    address: row.get(0)?,
+                    last_nonce: row.get(1).unwrap_or(-1),
+                    updated_ms: row.get(2).unwrap_or(0),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    /// Forget the last nonce of `address`; the next one starts from the clock.
+    pub fn clear_nonce(&self, address: &str) -> Result<bool> {
+        let n = self
+            .conn
+            .execute("DELETE FROM nonces WHERE address = ?1", params![address])?;
+        Ok(n > 0)
+    }
+
+    // ─── Paper Trading ──────────────────────────────────────────────
+
+    /// The saved paper account (JSON), if one was ever written.
+    pub fn paper_account(&self) -> Result<Option<String>> {
+        let result =
+            self.conn
+                .query_row("SELECT state FROM paper_account WHERE id = 1", [], |row| {
+                    row.get::<_, String>(0)
+                });
+        match result {
+            Ok(state) => Ok(Some(state)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Replace the saved paper account, together with the fills it produced
+    /// since the last save, in one transaction.
+    pub fn save_paper_account(&self, state: &str, new_fills: &[DbFill]) -> Result<()> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO paper_account (id, state, updated_ms) VALUES (1, ?1, ?2)",
+            params![state, now_ms],
+        )?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR IGNORE INTO fills (protocol, coin, px, sz, side, time_ms, fee, hash, oid, closed_pnl)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            for fill in new_fills {
+                stmt.execute(params![
+                    fill.protocol,
+                    fill.coin,
+                    fill.px,
+                    fill.sz,
+                    fill.side,
+                    fill.time_ms,
+                    fill.fee,
+                    fill.hash,
+                    fill.oid,
+                    fill.closed_pnl,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Delete every cached fill of one protocol. Returns rows removed.
+    pub fn delete_fills(&self, protocol: &str) -> Result<usize> {
+        let removed = self
+            .conn
+            .execute("DELETE FROM fills WHERE protocol = ?1", params![protocol])?;
+        Ok(removed)
+    }
+
+    // ─── Address Book ───────────────────────────────────────────────
+
+    /// User-added tokens, by chain then name.
+    pub fn address_book(&self) -> Result<Vec<DbAddressEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT chain, name, address, decimals, added_ms FROM address_book
+             ORDER BY chain, name",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(DbAddressEntry {
+                chain: row.get(0)?,
+                name: row.get(1)?,
+                address: row.get(2)?,
+                decimals: row.get(3)?,
+                added_ms: row.get(4)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read address book")
+    }
+
+    /// Add a token. Callers validate the entry first; a name already taken
+    /// on the chain is still rejected by the table.
+    pub fn add_address(&self, entry: &DbAddressEntry) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO address_book (chain, name, address, decimals, added_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    entry.chain,
+                    entry.name,
+                    entry.address,
+                    entry.decimals,
+                    entry.added_ms
+                ],
+            )
+            .with_context(|| format!("Failed to add {} on {}", entry.name, entry.chain))?;
+        Ok(())
+    }
+
+    /// Remove a token by name (case-insensitive). Returns whether it existed.
+    pub fn remove_address(&self, chain: &str, name: &str) -> Result<bool> {
+        let removed = self.conn.execute(
+            "DELETE FROM address_book WHERE chain = ?1 AND name = ?2",
+            params![chain, name],
+        )?;
+        Ok(removed > 0)
+    }
+
+    // ─── DEX Watchlist ──────────────────────────────────────────────
+
+    /// Watched pools, in the order they were added.
+    pub fn watched_pools(&self) -> Result<Vec<DbWatchedPool>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT network, pool, label, added_ms, last_price_usd, last_volume_usd,
+                    last_liquidity_usd, last_checked_ms, missing_since_ms
+             FROM dex_watchlist ORDER BY added_ms, network, pool",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(DbWatchedPool {
+                network: row.get(0)?,
+                pool: row.get(1)?,
+                label: row.get(2)?,
+                added_ms: row.get(3)?,
+                last_price_usd: row.get(4)?,
+                last_volume_usd: row.get(5)?,
+                last_liquidity_usd: row.get(6)?,
+                last_checked_ms: row.get(7)?,
+                missing_since_ms: row.get(8)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read DEX watchlist")
+    }
+
+    /// Watch a pool. A pool already watched on the network, or a label
+    /// already in use, is rejected by the table.
+    pub fn add_watched_pool(
+        &self,
+        network: &str,
+        pool: &str,
+        label: Option<&str>,
+        now_ms: i64,
+    ) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO dex_watchlist (network, pool, label, added_ms) VALUES (?1, ?2, ?3, ?4)",
+                params![network, pool, label, now_ms],
+            )
+            .with_context(|| format!("Failed to watch {pool} on {network}"))?;
+        Ok(())
+    }
+
+    /// Stop watching a pool, given by address or label (case-insensitive).
+    /// Returns the removed entry.
+    pub fn remove_watched_pool(&self, pool_or_label: &str) -> Result<Option<DbWatchedPool>> {
+        let Some(entry) = self
+            .watched_pools()?
+            .into_iter()
+            .find(|w| watch_matches(w, pool_or_label))
+        else {
+            return Ok(None);
+        };
+        self.conn.execute(
+            "DELETE FROM dex_watchlist WHERE network = ?1 AND pool = ?2",
+            params![entry.network, entry.pool],
+        )?;
+        Ok(Some(entry))
+    }
+
+    /// Record the stats seen by a check; the pool is no longer missing.
+    pub fn record_pool_check(
+        &self,
+        network: &str,
+        pool: &str,
+        price_usd: &str,
+        volume_usd: &str,
+        liquidity_usd: &str,
+        now_ms: i64,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE dex_watchlist SET last_price_usd = ?3, last_volume_usd = ?4,
+                 last_liquidity_usd = ?5, last_checked_ms = ?6, missing_since_ms = NULL
+             WHERE network = ?1 AND pool = ?2",
+            params![network, pool, price_usd, volume_usd, liquidity_usd, now_ms],
+        )?;
+        Ok(())
+    }
+
+    /// Flag a pool the API no longer returns. The last stats are kept so a
+    /// pool that comes back is compared with what it was before.
+    pub fn mark_pool_missing(&self, network: &str, pool: &str, now_ms: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE dex_watchlist SET missing_since_ms = COALESCE(missing_since_ms, ?3)
+             WHERE network = ?1 AND pool = ?2",
+            params![network, pool, now_ms],
+        )?;
+        Ok(())
+    }
+
+    // ─── Status Snapshots ───────────────────────────────────────────
+
+    /// The most recent snapshot for a profile and network.
+    pub fn latest_status_snapshot(
+        &self,
+        profile: &str,
+        network: &str,
+    ) -> Result<Option<DbStatusSnapshot>> {
+        let result = self.conn.query_row(
+            "SELECT taken_ms, snapshot FROM status_snapshots
+             WHERE profile = ?1 AND network = ?2 ORDER BY id DESC LIMIT 1",
+            params![profile, network],
+            |row| {
+                Ok(DbStatusSnapshot {
+                    taken_ms: row.get(0)?,
+                    snapshot: row.get(1)?,
+                })
+            },
+        );
+        match result {
+            Ok(snapshot) => Ok(Some(snapshot)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Newest snapshot for the profile and network taken at or before
+    /// `at_ms`.
+    pub fn status_snapshot_before(
+        &self,
+        profile: &str,
+        network: &str,
+        at_ms: i64,
+    ) -> Result<Option<DbStatusSnapshot>> {
+        let result = self.conn.query_row(
+            "SELECT taken_ms, snapshot FROM status_snapshots
+             WHERE profile = ?1 AND network = ?2 AND taken_ms <= ?3
+             ORDER BY taken_ms DESC, id DESC LIMIT 1",
+            params![profile, network, at_ms],
+            |row| {
+                Ok(DbStatusSnapshot {
+                    taken_ms: row.get(0)?,
+                    snapshot: row.get(1)?,
+                })
+            },
+        );
+        match result {
+            Ok(snapshot) => Ok(Some(snapshot)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Save a snapshot, keeping only the newest `keep` (at least one) for
+    /// the profile and network.
+    pub fn save_status_snapshot(
+        &self,
+        profile: &str,
+        network: &str,
+        snapshot: &str,
+        taken_ms: i64,
+        keep: usize,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO status_snapshots (profile, network, taken_ms, snapshot)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![profile, network, taken_ms, snapshot],
+        )?;
+        self.conn.execute(
+            "DELETE FROM status_snapshots WHERE profile = ?1 AND network = ?2 AND id NOT IN (
+                 SELECT id FROM status_snapshots WHERE profile = ?1 AND network = ?2
+                 ORDER BY id DESC LIMIT ?3
+             )",
+            params![profile, network, keep.max(1) as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Move a renamed profile's snapshots to its new name.
+    pub fn rename_status_snapshots(&self, old: &str, new: &str) -> Result<usize> {
+        Ok(self.conn.execute(
+            "UPDATE status_snapshots SET profile = ?2 WHERE profile = ?1",
+            params![old, new],
+        )?)
+    }
+
+    /// Forget what is cached for one account: the profile's status
+    /// snapshots, backend responses fetched for its address and its last
+    /// nonce. Returns the rows deleted.
+    pub fn purge_account(&self, profile: &str, address: &str) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut deleted = tx.execute(
+            "DELETE FROM status_snapshots WHERE profile = ?1",
+            params![profile],
+        )?;
+        deleted += tx.execute(
+            "DELETE FROM backend_cache WHERE instr(lower(key), lower(?1)) > 0",
+            params![address],
+        )?;
+        deleted += tx.execute("DELETE FROM nonces WHERE address = ?1", params![address])?;
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    // ─── Backend Cache ──────────────────────────────────────────────
+
+    /// Last successful backend response for `key` and when it was fetched.
+    pub fn backend_response(&self, key: &str) -> Result<Option<(String, i64)>> {
+        let result = self.conn.query_row(
+            "SELECT body, fetched_ms FROM backend_cache WHERE key = ?1",
+            params![key],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+        );
+        match result {
+            Ok(entry) => Ok(Some(entry)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Store a backend response, replacing the previous one for `key`.
+    pub fn save_backend_response(&self, key: &str, body: &str, fetched_ms: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO backend_cache (key, body, fetched_ms) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET body = ?2, fetched_ms = ?3",
+            params![key, body, fetched_ms],
+        )?;
+        Ok(())
+    }
+
+    // ─── Schedules ──────────────────────────────────────────────────
+
+    /// Scheduled jobs, oldest first.
+    pub fn schedules(&self) -> Result<Vec<DbSchedule>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, command, every_ms, at, max_runs, runs, next_run_ms, created_ms
+             FROM schedules ORDER BY id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(DbSchedule {
+                id: row.get(0)?,
+                command: row.get(1)?,
+                every_ms: row.get(2)?,
+                at: row.get(3)?,
+                max_runs: row.get(4)?,
+                runs: row.get(5)?,
+                next_run_ms: row.get(6)?,
+                created_ms: row.get(7)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read schedules")
+    }
+
+    /// Add a job; returns it with its new id.
+    pub fn add_schedule(
+        &self,
+        command: &str,
+        every_ms: i64,
+        at: Option<&str>,
+        max_runs: Option<u32>,
+        next_run_ms: i64,
+        now_ms: i64,
+    ) -> Result<DbSchedule> {
+        self.conn
+            .execute(
+                "INSERT INTO schedules (command, every_ms, at, max_runs, next_run_ms, created_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![command, every_ms, at, max_runs, next_run_ms, now_ms],
+            )
+            .context("Failed to save schedule")?;
+        Ok(DbSchedule {
+            id: self.conn.last_insert_rowid(),
+            command: command.to_string(),
+            every_ms,
+            at: at.map(str::to_string),
+            max_runs,
+            runs: 0,
+            next_run_ms,
+            created_ms: now_ms,
+        })
+    }
+
+    /// Delete a job and its run history. Returns the removed job.
+    pub fn remove_schedule(&self, id: i64) -> Result<Option<DbSchedule>> {
+        let Some(job) = self.schedules()?.into_iter().find(|s| s.id == id) else {
+            return Ok(None);
+        };
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM schedule_runs WHERE schedule_id = ?1",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM schedules WHERE id = ?1", params![id])?;
+        tx.commit()?;
+        Ok(Some(job))
+    }
+
+    /// Record a run and move the job to `next_run_ms`. Runs other than
+    /// `skipped` count towards `max_runs`.
+    pub fn record_schedule_run(&self, run: &DbScheduleRun, next_run_ms: i64) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO schedule_runs (schedule_id, due_ms, started_ms, finished_ms, status,
+                 missed, oid, fill_price, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                run.schedule_id,
+                run.due_ms,
+                run.started_ms,
+                run.finished_ms,
+                run.status,
+                run.missed,
+                run.oid,
+                run.fill_price,
+                run.error
+            ],
+        )?;
+        tx.execute(
+            "UPDATE schedules SET next_run_ms = ?2, runs = runs + ?3 WHERE id = ?1",
+            params![
+                run.schedule_id,
+                next_run_ms,
+                i64::from(run.status != "skipped")
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// The most recent run of a job.
+    pub fn last_schedule_run(&self, schedule_id: i64) -> Result<Option<DbScheduleRun>> {
+        let result = self.conn.query_row(
+            "SELECT schedule_id, due_ms, started_ms, finished_ms, status, missed, oid,
+                    fill_price, error
+             FROM schedule_runs WHERE schedule_id = ?1 ORDER BY id DESC LIMIT 1",
+            params![schedule_id],
+            |row| {
+                Ok(DbScheduleRun {
+                    schedule_id: row.get(0)?,
+                    due_ms: row.get(1)?,
+                    started_ms: row.get(2)?,
+                    finished_ms: row.get(3)?,
+                    status: row.get(4)?,
+                    missed: row.get(5)?,
+                    oid: row.get(6)?,
+                    fill_price: row.get(7)?,
+                    error: row.get(8)?,
+                })
+            },
+        );
+        match result {
+            Ok(run) => Ok(Some(run)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // ─── 0x Swaps ───────────────────────────────────────────────────
+
+    /// Record a swap or quote.
+    pub fn insert_swap(&self, swap: &DbSwap) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO swaps (status, chain, sell_token, sell_symbol, sell_decimals, sell_amount,
+                    buy_token, buy_symbol, buy_decimals, buy_amount, price, sell_value_usd,
+                    gas_cost_usd, tx_hash, time_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                params![
+                    swap.status,
+                    swap.chain,
+                    swap.sell_token,
+                    swap.sell_symbol,
+                    swap.sell_decimals,
+                    swap.sell_amount,
+                    swap.buy_token,
+                    swap.buy_symbol,
+                    swap.buy_decimals,
+                    swap.buy_amount,
+                    swap.price,
+                    swap.sell_value_usd,
+                    swap.gas_cost_usd,
+                    swap.tx_hash,
+                    swap.time_ms,
+                ],
+            )
+            .context("Failed to record swap")?;
+        Ok(())
+    }
+
+    /// Query swaps, newest first.
+    pub fn query_swaps(&self, filter: &SwapFilter) -> Result<Vec<DbSwap>> {
+        let mut sql = String::from(
+            "SELECT status, chain, sell_token, sell_symbol, sell_decimals, sell_amount, buy_token,
+                buy_symbol, buy_decimals, buy_amount, price, sell_value_usd, gas_cost_usd, tx_hash,
+                time_ms
+             FROM swaps WHERE 1=1",
+        );
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        if let Some(ref status) = filter.status {
+            sql.push_str(" AND status = ?");
+            bind_values.push(Box::new(status.clone()));
+        }
+        if let Some(ref chain) = filter.chain {
+            sql.push_str(" AND chain = ?");
+            bind_values.push(Box::new(chain.clone()));
+        }
+        if let Some(ref token) = filter.token {
+            sql.push_str(
+                " AND (sell_symbol = ? COLLATE NOCASE OR buy_symbol = ? COLLATE NOCASE
+                   OR sell_token = ? COLLATE NOCASE OR buy_token = ? COLLATE NOCASE)",
+            );
+            for _ in 0..4 {
+                bind_values.push(Box::new(token.clone()));
+            }
+        }
+        if let Some(from) = filter.from_ms {
+            sql.push_str(" AND time_ms >= ?");
+            bind_values.push(Box::new(from));
+        }
+        if let Some(to) = filter.to_ms {
+            sql.push_str(" AND time_ms <= ?");
+            bind_values.push(Box::new(to));
+        }
+
+        sql.push_str(" ORDER BY time_ms DESC, id DESC");
+
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            bind_values.iter().map(|b| b.as_ref()).collect();
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_refs.as_slice(), |row| {
+            Ok(DbSwap {
+                status: row.get(0)?,
+                chain: row.get(1)?,
+                sell_token: row.get(2)?,
+                sell_symbol: row.get(3)?,
+                sell_decimals: row.get(4)?,
+                sell_amount: row.get(5)?,
+                buy_token: row.get(6)?,
+                buy_symbol: row.get(7)?,
+                buy_decimals: row.get(8)?,
+                buy_amount: row.get(9)?,
+                price: row.get(10)?,
+                sell_value_usd: row.get(11)?,
+                gas_cost_usd: row.get(12)?,
+                tx_hash: row.get(13)?,
+                time_ms: row.get(14)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read swaps")
+    }
+
+    /// Cache a token's USD price, replacing an older one.
+    pub fn set_token_price(
+        &self,
+        chain: &str,
+        address: &str,
+        usd_price: &str,
+        time_ms: i64,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO token_prices (chain, address, usd_price, time_ms) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(chain, address) DO UPDATE SET usd_price = ?3, time_ms = ?4
+             WHERE ?4 >= token_prices.time_ms",
+            params![chain, address, usd_price, time_ms],
+        )?;
+        Ok(())
+    }
 
-        sql.push_str(" ORDER BY timestamp_ms DESC");
+    /// Latest cached USD price of a token and when it was seen.
+    pub fn token_price(&self, chain: &str, address: &str) -> Result<Option<(String, i64)>> {
+        let result = self.conn.query_row(
+            "SELECT usd_price, time_ms FROM token_prices WHERE chain = ?1 AND address = ?2",
+            params![chain, address],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+        );
+        match result {
+            Ok(price) => Ok(Some(price)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Tokens bought by executed swaps matching `filter` (its status is
+    /// ignored), valued at cached prices. Also returns how many swaps were
+    /// left out for lacking a USD cost or token decimals.
+    pub fn unrealized_swap_pnl(&self, filter: &SwapFilter) -> Result<(Vec<DbSwapPosition>, usize)> {
+        let swaps = self.query_swaps(&SwapFilter {
+            status: Some("executed".into()),
+            limit: None,
+            ..filter.clone()
+        })?;
+
+        let mut skipped = 0usize;
+        let mut positions: BTreeMap<(String, String), DbSwapPosition> = BTreeMap::new();
+        for swap in &swaps {
+            let cost = swap
+                .sell_value_usd
+                .as_deref()
+                .and_then(|v| v.parse::<Decimal>().ok());
+            let (Some(amount), Some(cost)) = (swap.buy_tokens(), cost) else {
+                skipped += 1;
+                continue;
+            };
+            let key = (swap.chain.clone(), swap.buy_token.to_lowercase());
+            let position = positions.entry(key).or_insert_with(|| DbSwapPosition {
+                chain: swap.chain.clone(),
+                token: swap.buy_token.clone(),
+                symbol: swap.buy_symbol.clone(),
+                amount: Decimal::ZERO,
+                cost_usd: Decimal::ZERO,
+                gas_usd: Decimal::ZERO,
+                price_usd: None,
+                priced_ms: None,
+                swaps: 0,
+            });
+            position.amount += amount;
+            position.cost_usd += cost;
+            position.gas_usd += swap
+                .gas_cost_usd
+                .as_deref()
+                .and_then(|g| g.parse::<Decimal>().ok())
+                .unwrap_or_default();
+            position.swaps += 1;
+        }
+
+        let mut out = Vec::with_capacity(positions.len());
+        for mut position in positions.into_values() {
+            if let Some((price, time_ms)) = self.token_price(&position.chain, &position.token)? {
+                position.price_usd = price.parse().ok();
+                position.priced_ms = Some(time_ms);
+            }
+            out.push(position);
+        }
+        Ok((out, skipped))
+    }
+
+    // ─── Journal ────────────────────────────────────────────────────
+
+    /// Store a note and link it to its fill right away when the fill is
+    /// already cached. Returns the new entry's id.
+    ///
+    /// Fill hashes must refer to a cached fill; order ids and coin+time
+    /// targets may point at trades that only show up after the next sync.
+    pub fn add_journal_entry(
+        &self,
+        target: &JournalTarget,
+        note: &str,
+        tags: &[String],
+        now_ms: i64,
+    ) -> Result<i64> {
+        let (fill_hash, oid, coin, time_ms) = match target {
+            JournalTarget::Fill(hash) => {
+                let fill = self.fill_by_hash(hash)?.with_context(|| {
+                    format!("No cached fill with hash {hash}. Run `atlas history sync` first.")
+                })?;
+                (
+                    Some(fill.hash),
+                    Some(fill.oid),
+                    Some(fill.coin),
+                    fill.time_ms,
+                )
+            }
+            JournalTarget::Order(oid) => {
+                let order = self
+                    .conn
+                    .query_row(
+                        "SELECT coin, timestamp_ms FROM orders WHERE oid = ?1",
+                        params![oid],
+                        |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+                    )
+                    .ok();
+                let (coin, time_ms) = match order {
+                    Some((coin, time)) => (Some(coin), time),
+                    None => (None, now_ms),
+                };
+                (None, Some(*oid), coin, time_ms)
+            }
+            JournalTarget::CoinTime { coin, time_ms } => {
+                (None, None, Some(coin.to_uppercase()), *time_ms)
+            }
+        };
+
+        self.conn.execute(
+            "INSERT INTO journal (fill_hash, oid, coin, time_ms, note, tags, created_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                fill_hash,
+                oid,
+                coin,
+                time_ms,
+                note,
+                encode_tags(tags),
+                now_ms
+            ],
+        )?;
+        let id = self.conn.last_insert_rowid();
+        self.reconcile_journal()?;
+        Ok(id)
+    }
+
+    /// Link pending notes to fills that have since been synced: by order id
+    /// (first fill of the order), or by coin to the nearest fill within
+    /// [`JOURNAL_MATCH_WINDOW_MS`]. Returns how many notes were linked.
+    pub fn reconcile_journal(&self) -> Result<usize> {
+        let pending: Vec<(i64, Option<i64>, Option<String>, i64)> = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT id, oid, coin, time_ms FROM journal WHERE fill_hash IS NULL")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        let mut linked = 0;
+        for (id, oid, coin, time_ms) in pending {
+            let fill = match (oid, coin) {
+                (Some(oid), _) => self.first_fill_of_order(oid)?,
+                (None, Some(coin)) => self.nearest_fill(&coin, time_ms)?,
+                (None, None) => None,
+            };
+            if let Some(fill) = fill {
+                self.conn.execute(
+                    "UPDATE journal SET fill_hash = ?1, oid = ?2, coin = ?3, time_ms = ?4 WHERE id = ?5",
+                    params![fill.hash, fill.oid, fill.coin, fill.time_ms, id],
+                )?;
+                linked += 1;
+            }
+        }
+        Ok(linked)
+    }
+
+    /// Query journal notes, newest first.
+    pub fn query_journal(&self, filter: &JournalFilter) -> Result<Vec<DbJournalEntry>> {
+        let mut sql = String::from(
+            "SELECT id, fill_hash, oid, coin, time_ms, note, tags, created_ms FROM journal WHERE 1=1",
+        );
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        if let Some(ref coin) = filter.coin {
+            sql.push_str(" AND coin = ?");
+            bind_values.push(Box::new(coin.to_uppercase()));
+        }
+        if let Some(ref tag) = filter.tag {
+            sql.push_str(" AND tags LIKE ?");
+            bind_values.push(Box::new(format!("%,{},%", tag.trim().to_lowercase())));
+        }
+        if let Some(from) = filter.from_ms {
+            sql.push_str(" AND time_ms >= ?");
+            bind_values.push(Box::new(from));
+        }
+        if let Some(to) = filter.to_ms {
+            sql.push_str(" AND time_ms <= ?");
+            bind_values.push(Box::new(to));
+        }
+        if let Some(query) = filter.search.as_deref().map(fts_query) {
+            if !query.is_empty() {
+                sql.push_str(
+                    " AND id IN (SELECT rowid FROM journal_fts WHERE journal_fts MATCH ?)",
+                );
+                bind_values.push(Box::new(query));
+            }
+        }
+
+        sql.push_str(" ORDER BY time_ms DESC, id DESC");
 
         if let Some(limit) = filter.limit {
             sql.push_str(&format!(" LIMIT {}", limit));
@@ -312,19 +2662,7 @@ impl AtlasDb {
             bind_values.iter().map(|b| b.as_ref()).collect();
 
         let mut stmt = self.conn.prepare(&sql)?;
-        let rows = stmt.query_map(params_refs.as_slice(), |row| {
-            Ok(DbOrder {
-                protocol: row.get(0)?,
-                coin: row.get(1)?,
-                side: row.get(2)?,
-                limit_px: row.get(3)?,
-                sz: row.get(4)?,
-                oid: row.get(5)?,
-                timestamp_ms: row.get(6)?,
-                status: row.get(7)?,
-                order_type: row.get(8)?,
-            })
-        })?;
+        let rows = stmt.query_map(params_refs.as_slice(), journal_from_row)?;
 
         let mut results = Vec::new();
         for row in rows {
@@ -333,36 +2671,220 @@ impl AtlasDb {
         Ok(results)
     }
 
-    // ─── Sync State ─────────────────────────────────────────────────
+    /// A single journal entry by id.
+    pub fn journal_entry(&self, id: i64) -> Result<Option<DbJournalEntry>> {
+        let result = self.conn.query_row(
+            "SELECT id, fill_hash, oid, coin, time_ms, note, tags, created_ms FROM journal WHERE id = ?1",
+            params![id],
+            journal_from_row,
+        );
+        match result {
+            Ok(entry) => Ok(Some(entry)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
 
-    /// Get a sync state value by key.
-    pub fn get_sync_state(&self, key: &str) -> Result<Option<String>> {
+    /// Notes linked to each of the given fills, oldest note first.
+    pub fn journal_notes_for_fills(
+        &self,
+        hashes: &[String],
+    ) -> Result<std::collections::HashMap<String, Vec<String>>> {
+        let mut notes: std::collections::HashMap<String, Vec<String>> = Default::default();
         let mut stmt = self
             .conn
-            .prepare("SELECT value FROM sync_state WHERE key = ?1")?;
-        let result = stmt.query_row(params![key], |row| row.get::<_, String>(0));
+            .prepare_cached("SELECT note FROM journal WHERE fill_hash = ?1 ORDER BY id")?;
+        for hash in hashes {
+            let rows = stmt
+                .query_map(params![hash], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            if !rows.is_empty() {
+                notes.insert(hash.clone(), rows);
+            }
+        }
+        Ok(notes)
+    }
+
+    fn fill_by_hash(&self, hash: &str) -> Result<Option<DbFill>> {
+        self.query_one_fill("WHERE hash = ?1", params![hash])
+    }
+
+    fn first_fill_of_order(&self, oid: i64) -> Result<Option<DbFill>> {
+        self.query_one_fill("WHERE oid = ?1 ORDER BY time_ms, id", params![oid])
+    }
+
+    fn nearest_fill(&self, coin: &str, time_ms: i64) -> Result<Option<DbFill>> {
+        self.query_one_fill(
+            "WHERE coin = ?1 AND ABS(time_ms - ?2) <= ?3 ORDER BY ABS(time_ms - ?2), id",
+            params![coin, time_ms, JOURNAL_MATCH_WINDOW_MS],
+        )
+    }
+
+    fn query_one_fill(
+        &self,
+        clause: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Option<DbFill>> {
+        let sql = format!(
+            "SELECT protocol, coin, px, sz, side, time_ms, fee, hash, oid, closed_pnl FROM fills {clause} LIMIT 1"
+        );
+        let result = self.conn.query_row(&sql, params, |row| {
+            Ok(DbFill {
+                protocol: row.get(0)?,
+                coin: row.get(1)?,
+                px: row.get(2)?,
+                sz: row.get(3)?,
+                side: row.get(4)?,
+                time_ms: row.get(5)?,
+                fee: row.get(6)?,
+                hash: row.get(7)?,
+                oid: row.get(8)?,
+                closed_pnl: row.get(9)?,
+            })
+        });
         match result {
-            Ok(val) => Ok(Some(val)),
+            Ok(fill) => Ok(Some(fill)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e.into()),
         }
     }
 
-    /// Set a sync state value.
-    pub fn set_sync_state(&self, key: &str, value: &str) -> Result<()> {
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as i64;
+    // ─── Audit Log ──────────────────────────────────────────────────
 
+    /// Append a `pending` entry; `id`, `status`, `response` and
+    /// `completed_ms` of `entry` are ignored. Returns the new id.
+    pub fn insert_audit_entry(&self, entry: &DbAuditEntry) -> Result<i64> {
         self.conn.execute(
-            "INSERT OR REPLACE INTO sync_state (key, value, updated_ms) VALUES (?1, ?2, ?3)",
-            params![key, value, now_ms],
+            "INSERT INTO audit_log (created_ms, protocol, action_type, coin, params_hash, nonce,
+                signer, request)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                entry.created_ms,
+                entry.protocol,
+                entry.action_type,
+                entry.coin,
+                entry.params_hash,
+                entry.nonce,
+                entry.signer,
+                entry.request,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Record the outcome of a `pending` entry. Fails for an unknown or
+    /// already completed id: an outcome is written once.
+    pub fn complete_audit_entry(
+        &self,
+        id: i64,
+        status: &str,
+        response: Option<&str>,
+        completed_ms: i64,
+    ) -> Result<()> {
+        let updated = self.conn.execute(
+            "UPDATE audit_log SET status = ?2, response = ?3, completed_ms = ?4
+             WHERE id = ?1 AND status = 'pending'",
+            params![id, status, response, completed_ms],
         )?;
+        if updated == 0 {
+            anyhow::bail!("Audit entry {id} is missing or already completed");
+        }
+        Ok(())
+    }
+
+    /// Audit entries, newest first.
+    pub fn query_audit_entries(&self, filter: &AuditFilter) -> Result<Vec<DbAuditEntry>> {
+        let mut sql = format!("SELECT {AUDIT_COLUMNS} FROM audit_log WHERE 1=1");
+        let mut bind_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        if let Some(ref action_type) = filter.action_type {
+            sql.push_str(" AND action_type = ?");
+            bind_values.push(Box::new(action_type.clone()));
+        }
+        if let Some(from) = filter.from_ms {
+            sql.push_str(" AND created_ms >= ?");
+            bind_values.push(Box::new(from));
+        }
+        if let Some(to) = filter.to_ms {
+            sql.push_str(" AND created_ms <= ?");
+            bind_values.push(Box::new(to));
+        }
+
+        sql.push_str(" ORDER BY id DESC");
+
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            bind_values.iter().map(|b| b.as_ref()).collect();
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_refs.as_slice(), audit_entry_from_row)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read audit log")
+    }
+
+    /// One audit entry by id.
+    pub fn audit_entry(&self, id: i64) -> Result<Option<DbAuditEntry>> {
+        let result = self.conn.query_row(
+            &format!("SELECT {AUDIT_COLUMNS} FROM audit_log WHERE id = ?1"),
+            params![id],
+            audit_entry_from_row,
+        );
+        match result {
+            Ok(entry) => Ok(Some(entry)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // ─── Maintenance ────────────────────────────────────────────────
+
+    /// `PRAGMA integrity_check`. Empty when healthy, otherwise SQLite's
+    /// description of each problem.
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows.into_iter().filter(|r| r != "ok").collect())
+    }
+
+    /// Rebuild every index from table data (`REINDEX`).
+    pub fn rebuild_indexes(&self) -> Result<()> {
+        self.conn.execute_batch("REINDEX;")?;
         Ok(())
     }
 }
 
+const AUDIT_COLUMNS: &str = "id, created_ms, protocol, action_type, coin, params_hash, nonce, \
+     signer, request, status, response, completed_ms";
+
+fn audit_entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<DbAuditEntry> {
+    Ok(DbAuditEntry {
+        id: row.get(0)?,
+        created_ms: row.get(1)?,
+        protocol: row.get(2)?,
+        action_type: row.get(3)?,
+        coin: row.get(4)?,
+        params_hash: row.get(5)?,
+        nonce: row.get(6)?,
+        signer: row.get(7)?,
+        request: row.get(8)?,
+        status: row.get(9)?,
+        response: row.get(10)?,
+        completed_ms: row.get(11)?,
+    })
+}
+
+fn watch_matches(w: &DbWatchedPool, pool_or_label: &str) -> bool {
+    w.pool.eq_ignore_ascii_case(pool_or_label)
+        || w.label
+            .as_deref()
+            .is_some_and(|l| l.eq_ignore_ascii_case(pool_or_label))
+}
+
 // Database filter types for querying cached data.
 
 /// Filter for querying cached fills from the local database.
@@ -380,6 +2902,94 @@ pub struct FillFilter {
     pub limit: Option<usize>,
 }
 
+/// Filter for querying cached ledger entries.
+#[derive(Debug, Clone, Default)]
+pub struct TransferFilter {
+    /// `deposit`, `withdraw` or `internal`. None = all.
+    pub kind: Option<String>,
+    /// Start time (inclusive) in milliseconds since epoch.
+    pub from_ms: Option<i64>,
+    /// End time (inclusive) in milliseconds since epoch.
+    pub to_ms: Option<i64>,
+    /// Maximum number of results to return.
+    pub limit: Option<usize>,
+}
+
+/// Filter for querying the audit log.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    /// `order`, `cancel`, `transfer`, ... None = all.
+    pub action_type: Option<String>,
+    /// Start time (inclusive) in milliseconds since epoch.
+    pub from_ms: Option<i64>,
+    /// End time (inclusive) in milliseconds since epoch.
+    pub to_ms: Option<i64>,
+    /// Maximum number of results to return.
+    pub limit: Option<usize>,
+}
+
+/// Filter for querying journal notes.
+#[derive(Debug, Clone, Default)]
+pub struct JournalFilter {
+    /// Filter by coin symbol (e.g. "ETH").
+    pub coin: Option<String>,
+    /// Only notes carrying this tag (case-insensitive, exact).
+    pub tag: Option<String>,
+    /// Start time (inclusive) in milliseconds since epoch.
+    pub from_ms: Option<i64>,
+    /// End time (inclusive) in milliseconds since epoch.
+    pub to_ms: Option<i64>,
+    /// Full-text search over note and tags; every word must match.
+    pub search: Option<String>,
+    /// Maximum number of results to return.
+    pub limit: Option<usize>,
+}
+
+/// Tags are stored as `,tag1,tag2,` so a single `LIKE '%,tag,%'` matches
+/// whole tags only.
+fn encode_tags(tags: &[String]) -> String {
+    let mut clean: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase().replace(',', "");
+        if !tag.is_empty() && !clean.contains(&tag) {
+            clean.push(tag);
+        }
+    }
+    if clean.is_empty() {
+        String::new()
+    } else {
+        format!(",{},", clean.join(","))
+    }
+}
+
+fn decode_tags(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Quote each word so user input is never parsed as FTS5 query syntax.
+fn fts_query(text: &str) -> String {
+    text.split_whitespace()
+        .map(|w| format!("\"{}\"", w.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn journal_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<DbJournalEntry> {
+    Ok(DbJournalEntry {
+        id: row.get(0)?,
+        fill_hash: row.get(1)?,
+        oid: row.get(2)?,
+        coin: row.get(3)?,
+        time_ms: row.get(4)?,
+        note: row.get(5)?,
+        tags: decode_tags(&row.get::<_, String>(6)?),
+        created_ms: row.get(7)?,
+    })
+}
+
 /// Filter for querying cached orders from the local database.
 #[derive(Debug, Clone, Default)]
 pub struct OrderFilter {
@@ -389,6 +2999,33 @@ pub struct OrderFilter {
     pub coin: Option<String>,
     /// Filter by order status (e.g. "open", "filled", "canceled").
     pub status: Option<String>,
+    /// Placed at or after (ms).
+    pub from_ms: Option<i64>,
+    /// Placed at or before (ms).
+    pub to_ms: Option<i64>,
+    /// Only orders that may still be open and whose status was last
+    /// confirmed before this time (ms), or never.
+    pub stale_before_ms: Option<i64>,
+    /// Maximum number of results to return.
+    pub limit: Option<usize>,
+}
+
+/// Order statuses that can still change: resting or triggered orders, and
+/// `historical` rows synced before statuses were tracked.
+pub const OPEN_ORDER_STATUSES: [&str; 3] = ["open", "triggered", "historical"];
+
+/// Filter for querying recorded 0x swaps.
+#[derive(Debug, Clone, Default)]
+pub struct SwapFilter {
+    /// `executed` or `quoted`. None = both.
+    pub status: Option<String>,
+    pub chain: Option<String>,
+    /// Symbol or address on either side of the swap (case-insensitive).
+    pub token: Option<String>,
+    /// Start time (inclusive) in milliseconds since epoch.
+    pub from_ms: Option<i64>,
+    /// End time (inclusive) in milliseconds since epoch.
+    pub to_ms: Option<i64>,
     /// Maximum number of results to return.
     pub limit: Option<usize>,
 }
@@ -397,12 +3034,147 @@ pub struct OrderFilter {
 mod tests {
     use super::*;
 
+    /// A temp database path for one test, emptied first: a file left by an
+    /// earlier run of the same pid would change what the test sees.
+    fn temp_db(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("atlas-{name}-{}.db", std::process::id()));
+        remove_db(&path);
+        path
+    }
+
+    fn remove_db(path: &std::path::Path) {
+        for ext in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{ext}", path.display()));
+        }
+    }
+
     #[test]
     fn test_open_in_memory() {
         let db = AtlasDb::open_in_memory().unwrap();
         assert!(db.last_fill_time().unwrap().is_none());
     }
 
+    #[test]
+    fn test_next_nonce_is_monotonic_per_address() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert_eq!(db.next_nonce("0xabc", 1_000).unwrap(), 1_000);
+        // Same millisecond, or a clock that stepped back: still increasing
+        assert_eq!(db.next_nonce("0xabc", 1_000).unwrap(), 1_001);
+        assert_eq!(db.next_nonce("0xABC", 900).unwrap(), 1_002);
+        assert_eq!(db.next_nonce("0xabc", 5_000).unwrap(), 5_000);
+        // Wallets don't share a sequence
+        assert_eq!(db.next_nonce("0xdef", 1_000).unwrap(), 1_000);
+
+        assert!(db.clear_nonce("0xabc").unwrap());
+        assert_eq!(db.next_nonce("0xabc", 2_000).unwrap(), 2_000);
+        assert_eq!(db.nonces().unwrap().len(), 2);
+
+        // Garbage written by something else still lists, as -1
+        db.conn
+            .execute(
+                "UPDATE nonces SET last_nonce = 'x' WHERE address = '0xdef'",
+                [],
+            )
+            .unwrap();
+        let garbled = db.nonces().unwrap();
+        assert_eq!(garbled[1].address, "0xdef");
+        assert_eq!(garbled[1].last_nonce, -1);
+        assert_eq!(db.next_nonce("0xdef", 3_000).unwrap(), 3_000);
+    }
+
+    #[test]
+    fn test_next_nonce_across_connections() {
+        // Each connection stands in for a separate atlas process
+        let path = temp_db("nonce");
+        AtlasDb::open_at(&path).unwrap();
+        let per_task = 50;
+        let batches: Vec<Vec<u64>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..16)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let db = AtlasDb::open_at(&path).unwrap();
+                        (0..per_task)
+                            .map(|_| db.next_nonce("0xabc", 1_000).unwrap())
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        for batch in &batches {
+            assert!(batch.windows(2).all(|w| w[0] < w[1]));
+        }
+        let mut all: Vec<u64> = batches.concat();
+        all.sort_unstable();
+        all.dedup();
+        assert_eq!(all.len(), 16 * per_task);
+        assert_eq!(
+            all,
+            (1_000..1_000 + 16 * per_task as u64).collect::<Vec<_>>()
+        );
+
+        remove_db(&path);
+    }
+
+    #[test]
+    fn test_paper_account_roundtrip_and_reset() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert!(db.paper_account().unwrap().is_none());
+
+        let fill = DbFill {
+            protocol: "paper".to_string(),
+            coin: "ETH".into(),
+            px: "3500".into(),
+            sz: "0.1".into(),
+            side: "Buy".into(),
+            time_ms: 1_000,
+            fee: "0.1575".into(),
+            hash: "paper-1".into(),
+            oid: 1,
+            closed_pnl: "0".into(),
+        };
+        let fills = [fill];
+        db.save_paper_account(r#"{"balance":"9999.84"}"#, &fills)
+            .unwrap();
+        db.save_paper_account(r#"{"balance":"42"}"#, &fills)
+            .unwrap();
+        assert_eq!(
+            db.paper_account().unwrap().as_deref(),
+            Some(r#"{"balance":"42"}"#)
+        );
+        // Same hash saved twice is stored once
+        assert_eq!(db.delete_fills("paper").unwrap(), 1);
+        assert_eq!(db.delete_fills("paper").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_realized_pnl_since() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let fill = |hash: &str, time_ms: i64, pnl: &str, fee: &str| DbFill {
+            protocol: "hyperliquid".to_string(),
+            coin: "ETH".into(),
+            px: "3500".into(),
+            sz: "1".into(),
+            side: "Sell".into(),
+            time_ms,
+            fee: fee.into(),
+            hash: hash.into(),
+            oid: 1,
+            closed_pnl: pnl.into(),
+        };
+        db.insert_fills(&[
+            fill("0x1", 1_000, "-300", "1"), // before window
+            fill("0x2", 2_000, "-200", "2"),
+            fill("0x3", 3_000, "50", "1"),
+        ])
+        .unwrap();
+
+        let pnl = db.realized_pnl_since(2_000).unwrap();
+        assert!((pnl - (-153.0)).abs() < 1e-9);
+        assert_eq!(db.realized_pnl_since(10_000).unwrap(), 0.0);
+    }
+
     #[test]
     fn test_insert_and_query_fills() {
         let db = AtlasDb::open_in_memory().unwrap();
@@ -532,8 +3304,94 @@ mod tests {
             },
         ];
 
-        db.insert_fills(&fills).unwrap();
-        assert_eq!(db.last_fill_time().unwrap(), Some(2000));
+        db.insert_fills(&fills).unwrap();
+        assert_eq!(db.last_fill_time().unwrap(), Some(2000));
+        assert_eq!(db.first_fill_time(None).unwrap(), Some(1000));
+        assert_eq!(db.first_fill_time(Some("hyperliquid")).unwrap(), Some(1000));
+        assert!(db.first_fill_time(Some("paper")).unwrap().is_none());
+    }
+
+    fn transfer(hash: &str, time_ms: i64, kind: &str, direction: &str, usd: &str) -> DbTransfer {
+        DbTransfer {
+            hash: hash.into(),
+            time_ms,
+            kind: kind.into(),
+            ledger_type: kind.into(),
+            direction: direction.into(),
+            token: "USDC".into(),
+            amount: usd.into(),
+            usd_value: Some(usd.into()),
+            fee: "0".into(),
+            counterparty: None,
+        }
+    }
+
+    #[test]
+    fn test_transfers_dedup_and_filter() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert!(db.last_transfer_time().unwrap().is_none());
+
+        let batch = vec![
+            transfer("t1", 1000, "deposit", "in", "500"),
+            transfer("t2", 2000, "internal", "none", "100"),
+            transfer("t3", 3000, "withdraw", "out", "200"),
+        ];
+        assert_eq!(db.insert_transfers(&batch).unwrap(), 3);
+        // A re-sync overlapping the last page
+        assert_eq!(db.insert_transfers(&batch[2..]).unwrap(), 0);
+        assert_eq!(db.last_transfer_time().unwrap(), Some(3000));
+
+        let all = db.query_transfers(&TransferFilter::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].hash, "t3");
+        let net: Decimal = all.iter().map(DbTransfer::net_usd).sum();
+        assert_eq!(net, Decimal::from(300));
+
+        let deposits = db
+            .query_transfers(&TransferFilter {
+                kind: Some("deposit".into()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(deposits.len(), 1);
+        let ranged = db
+            .query_transfers(&TransferFilter {
+                from_ms: Some(1500),
+                to_ms: Some(3000),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(ranged.len(), 1);
+        assert_eq!(ranged[0].hash, "t3");
+    }
+
+    #[test]
+    fn test_funding_dedup_and_range() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert!(db.last_funding_time().unwrap().is_none());
+
+        let payment = |coin: &str, time_ms, usdc: &str| DbFunding {
+            coin: coin.into(),
+            time_ms,
+            usdc: usdc.into(),
+            szi: "1.5".into(),
+            funding_rate: "0.0000125".into(),
+        };
+        let batch = vec![
+            payment("ETH", 3000, "-0.12"),
+            payment("BTC", 1000, "0.5"),
+            payment("ETH", 1000, "-0.1"),
+        ];
+        assert_eq!(db.insert_funding(&batch).unwrap(), 3);
+        assert_eq!(db.insert_funding(&batch[..1]).unwrap(), 0);
+        assert_eq!(db.last_funding_time().unwrap(), Some(3000));
+
+        let all = db.query_funding(None, None).unwrap();
+        let keys: Vec<_> = all.iter().map(|f| (f.coin.as_str(), f.time_ms)).collect();
+        assert_eq!(keys, [("BTC", 1000), ("ETH", 1000), ("ETH", 3000)]);
+        assert_eq!(db.query_funding(Some(2000), None).unwrap().len(), 1);
+        assert_eq!(db.query_funding(None, Some(999)).unwrap().len(), 0);
     }
 
     #[test]
@@ -551,6 +3409,9 @@ mod tests {
                 timestamp_ms: 1700000000000,
                 status: "filled".into(),
                 order_type: "Limit".into(),
+                filled_sz: "0".into(),
+                avg_px: None,
+                status_updated_at: None,
             },
             DbOrder {
                 protocol: "hyperliquid".to_string(),
@@ -562,6 +3423,9 @@ mod tests {
                 timestamp_ms: 1700000001000,
                 status: "open".into(),
                 order_type: "Limit".into(),
+                filled_sz: "0".into(),
+                avg_px: None,
+                status_updated_at: None,
             },
         ];
 
@@ -590,6 +3454,17 @@ mod tests {
             .unwrap();
         assert_eq!(filled.len(), 1);
         assert_eq!(filled[0].coin, "ETH");
+
+        // Query by time placed
+        let later = db
+            .query_orders(&OrderFilter {
+                from_ms: Some(1700000000500),
+                to_ms: Some(1700000001000),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(later.len(), 1);
+        assert_eq!(later[0].oid, 201);
     }
 
     #[test]
@@ -606,6 +3481,9 @@ mod tests {
             timestamp_ms: 1700000000000,
             status: "open".into(),
             order_type: "Limit".into(),
+            filled_sz: "0".into(),
+            avg_px: None,
+            status_updated_at: None,
         };
 
         db.insert_orders(&[order]).unwrap();
@@ -621,6 +3499,9 @@ mod tests {
             timestamp_ms: 1700000000000,
             status: "filled".into(),
             order_type: "Limit".into(),
+            filled_sz: "0".into(),
+            avg_px: None,
+            status_updated_at: None,
         };
 
         db.insert_orders(&[updated]).unwrap();
@@ -630,6 +3511,364 @@ mod tests {
         assert_eq!(all[0].status, "filled");
     }
 
+    #[test]
+    fn test_update_orders_and_stale_filter() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let order = |oid: i64, status: &str, confirmed: Option<i64>| DbOrder {
+            protocol: "hyperliquid".to_string(),
+            coin: "ETH".into(),
+            side: "Buy".into(),
+            limit_px: "3500".into(),
+            sz: "1".into(),
+            oid,
+            timestamp_ms: 1700000000000 + oid,
+            status: status.into(),
+            order_type: "Limit".into(),
+            filled_sz: "0".into(),
+            avg_px: None,
+            status_updated_at: confirmed,
+        };
+        db.insert_orders(&[
+            order(1, "open", Some(1_000)),
+            order(2, "open", Some(5_000)),
+            order(3, "historical", None),
+            order(4, "filled", Some(1_000)),
+        ])
+        .unwrap();
+
+        let stale = |before_ms| {
+            db.query_orders(&OrderFilter {
+                stale_before_ms: Some(before_ms),
+                ..Default::default()
+            })
+            .unwrap()
+            .iter()
+            .map(|o| o.oid)
+            .collect::<Vec<_>>()
+        };
+        // Terminal orders are never stale; unconfirmed legacy rows always are
+        assert_eq!(stale(2_000), [3, 1]);
+        assert_eq!(stale(9_000), [3, 2, 1]);
+
+        let filled = DbOrder {
+            status: "filled".into(),
+            filled_sz: "1".into(),
+            avg_px: Some("3499.5".into()),
+            status_updated_at: Some(9_000),
+            ..order(1, "", None)
+        };
+        let still_open = order(2, "open", Some(9_000));
+        let (inserted, changed) = db
+            .update_orders(&[filled, still_open, order(5, "open", Some(9_000))])
+            .unwrap();
+        assert_eq!((inserted, changed), (1, 1));
+
+        let one = &db.query_orders(&OrderFilter::default()).unwrap()[4];
+        assert_eq!(one.oid, 1);
+        assert_eq!(one.status, "filled");
+        assert_eq!(one.filled_sz, "1");
+        assert_eq!(one.avg_px.as_deref(), Some("3499.5"));
+        assert_eq!(stale(9_000), [3]);
+    }
+
+    #[test]
+    fn test_order_lifecycle_migration() {
+        let path = temp_db("orders-migrate");
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE orders (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    protocol TEXT NOT NULL DEFAULT 'hyperliquid',
+                    coin TEXT NOT NULL,
+                    side TEXT NOT NULL,
+                    limit_px TEXT NOT NULL,
+                    sz TEXT NOT NULL,
+                    oid INTEGER UNIQUE NOT NULL,
+                    timestamp_ms INTEGER NOT NULL,
+                    status TEXT NOT NULL,
+                    order_type TEXT NOT NULL DEFAULT ''
+                );
+                INSERT INTO orders (coin, side, limit_px, sz, oid, timestamp_ms, status)
+                    VALUES ('ETH', 'Buy', '3500', '1', 7, 1700000000000, 'historical');",
+            )
+            .unwrap();
+        }
+
+        let db = AtlasDb::open_at(&path).unwrap();
+        let orders = db.query_orders(&OrderFilter::default()).unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].filled_sz, "0");
+        assert_eq!(orders[0].status_updated_at, None);
+        drop(db);
+
+        remove_db(&path);
+    }
+
+    /// A database as schema v1 left it: fills, orders and sync state, no
+    /// protocol or order lifecycle columns.
+    const V1_FIXTURE: &str = "
+        CREATE TABLE schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_ms INTEGER NOT NULL
+        );
+        INSERT INTO schema_version VALUES (1, 'fills, orders and sync state', 1700000000000);
+        CREATE TABLE fills (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            coin TEXT NOT NULL,
+            px TEXT NOT NULL,
+            sz TEXT NOT NULL,
+            side TEXT NOT NULL,
+            time_ms INTEGER NOT NULL,
+            fee TEXT NOT NULL,
+            hash TEXT UNIQUE NOT NULL,
+            oid INTEGER NOT NULL,
+            closed_pnl TEXT NOT NULL DEFAULT '0'
+        );
+        CREATE TABLE orders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            coin TEXT NOT NULL,
+            side TEXT NOT NULL,
+            limit_px TEXT NOT NULL,
+            sz TEXT NOT NULL,
+            oid INTEGER UNIQUE NOT NULL,
+            timestamp_ms INTEGER NOT NULL,
+            status TEXT NOT NULL,
+            order_type TEXT NOT NULL DEFAULT ''
+        );
+        CREATE TABLE sync_state (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_ms INTEGER NOT NULL
+        );
+        INSERT INTO fills (coin, px, sz, side, time_ms, fee, hash, oid, closed_pnl) VALUES
+            ('ETH', '3500', '1', 'B', 1700000000000, '0.35', '0xf1', 7, '0'),
+            ('ETH', '3600', '1', 'A', 1700000060000, '0.36', '0xf2', 8, '100');
+        INSERT INTO orders (coin, side, limit_px, sz, oid, timestamp_ms, status, order_type)
+            VALUES ('ETH', 'Buy', '3500', '1', 7, 1700000000000, 'filled', 'limit');
+        INSERT INTO sync_state VALUES ('fills', '1700000060000', 1700000060000);
+    ";
+
+    #[test]
+    fn test_migrates_v1_fixture_to_head() {
+        let path = temp_db("schema-v1");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(V1_FIXTURE)
+            .unwrap();
+
+        let status = AtlasDb::schema_status_at(&path).unwrap();
+        assert_eq!(status.current, 1);
+        assert_eq!(status.pending.len() as u32, SCHEMA_VERSION - 1);
+        assert_eq!(
+            status.pending[0],
+            (2, "protocol column on fills and orders")
+        );
+
+        let db = AtlasDb::open_at(&path).unwrap();
+        let fills = db.query_fills(&FillFilter::default()).unwrap();
+        assert_eq!(fills.len(), 2);
+        assert!(fills.iter().all(|f| f.protocol == "hyperliquid"));
+        assert!(fills
+            .iter()
+            .any(|f| f.hash == "0xf2" && f.closed_pnl == "100"));
+        let orders = db.query_orders(&OrderFilter::default()).unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].status, "filled");
+        assert_eq!(orders[0].filled_sz, "0");
+        assert_eq!(
+            db.get_sync_state("fills").unwrap().as_deref(),
+            Some("1700000060000")
+        );
+        // Tables from later migrations are usable
+        assert_eq!(db.next_nonce("0xabc", 5_000).unwrap(), 5_000);
+        drop(db);
+
+        let status = AtlasDb::schema_status_at(&path).unwrap();
+        assert_eq!(status.current, SCHEMA_VERSION);
+        assert!(status.pending.is_empty());
+
+        remove_db(&path);
+    }
+
+    #[test]
+    fn test_migrate_stops_at_target_and_refuses_newer() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert_eq!(migrate_to(&conn, 3).unwrap(), [1, 2, 3]);
+        assert!(has_column(&conn, "fills", "protocol"));
+        assert!(!has_column(&conn, "orders", "filled_sz"));
+        assert_eq!(schema_status(&conn).unwrap().current, 3);
+
+        let rest = migrate_to(&conn, SCHEMA_VERSION).unwrap();
+        assert_eq!(rest.first(), Some(&4));
+        assert!(migrate_to(&conn, SCHEMA_VERSION).unwrap().is_empty());
+
+        conn.execute(
+            "INSERT INTO schema_version VALUES (?1, 'from the future', 0)",
+            params![SCHEMA_VERSION + 1],
+        )
+        .unwrap();
+        let err = migrate_to(&conn, SCHEMA_VERSION).unwrap_err().to_string();
+        assert!(err.contains("newer than this atlas"), "{err}");
+        assert!(err.contains("Upgrade atlas"), "{err}");
+    }
+
+    #[test]
+    fn test_oi_snapshots_insert_query_prune() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let snap = |coin: &str, time_ms: i64, oi: &str| DbOiSnapshot {
+            protocol: "hyperliquid".to_string(),
+            coin: coin.into(),
+            time_ms,
+            open_interest: oi.into(),
+            mark_px: "3500".into(),
+            volume_24h: "1000000".into(),
+            funding: "0.0000125".into(),
+        };
+        let inserted = db
+            .insert_oi_snapshots(&[
+                snap("ETH", 1_000, "100"),
+                snap("ETH", 2_000, "110"),
+                snap("ETH", 2_000, "999"), // same sample again
+                snap("BTC", 2_000, "50"),
+                snap("ETH", 3_000, "120"),
+            ])
+            .unwrap();
+        assert_eq!(inserted, 4);
+
+        let eth = db.query_oi_snapshots("hyperliquid", "ETH", 2_000).unwrap();
+        assert_eq!(eth.len(), 2);
+        assert_eq!(eth[0].open_interest, "110");
+        assert_eq!(eth[1].time_ms, 3_000);
+        let window = db
+            .query_oi_snapshots_between("hyperliquid", 2_000, 2_999)
+            .unwrap();
+        assert_eq!(
+            window.iter().map(|s| s.coin.as_str()).collect::<Vec<_>>(),
+            ["BTC", "ETH"]
+        );
+
+        assert_eq!(db.prune_oi_snapshots(2_500).unwrap(), 3);
+        let eth = db.query_oi_snapshots("hyperliquid", "ETH", 0).unwrap();
+        assert_eq!(eth.len(), 1);
+        assert!(db
+            .query_oi_snapshots("hyperliquid", "BTC", 0)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_spread_samples_insert_query_prune() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let sample = |coin: &str, time_ms: i64, bps: &str| DbSpreadSample {
+            protocol: "hyperliquid".to_string(),
+            coin: coin.into(),
+            time_ms,
+            spread_bps: bps.into(),
+        };
+        let inserted = db
+            .insert_spread_samples(&[
+                sample("ETH", 60_000, "1.5"),
+                sample("ETH", 120_000, "2.0"),
+                sample("ETH", 120_000, "9.9"), // same minute again
+                sample("BTC", 120_000, "0.8"),
+                sample("ETH", 180_000, "2.5"),
+            ])
+            .unwrap();
+        assert_eq!(inserted, 4);
+
+        let eth = db
+            .query_spread_samples("hyperliquid", "ETH", 120_000)
+            .unwrap();
+        assert_eq!(eth.len(), 2);
+        assert_eq!(eth[0].spread_bps, "2.0");
+        assert_eq!(eth[1].time_ms, 180_000);
+        assert!(db
+            .query_spread_samples("paper", "ETH", 0)
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(db.prune_spread_samples(150_000).unwrap(), 3);
+        assert_eq!(
+            db.query_spread_samples("hyperliquid", "ETH", 0).unwrap(),
+            [sample("ETH", 180_000, "2.5")]
+        );
+    }
+
+    #[test]
+    fn test_audit_log_is_append_only() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let entry = |action_type: &str, created_ms: i64| DbAuditEntry {
+            id: 0,
+            created_ms,
+            protocol: "hyperliquid".into(),
+            action_type: action_type.into(),
+            coin: Some("ETH".into()),
+            params_hash: "0xabc".into(),
+            nonce: Some(created_ms),
+            signer: "0x1111".into(),
+            request: r#"{"action":{}}"#.into(),
+            status: "ok".into(), // ignored on insert
+            response: None,
+            completed_ms: None,
+        };
+        let order = db.insert_audit_entry(&entry("order", 1_000)).unwrap();
+        let transfer = db.insert_audit_entry(&entry("transfer", 2_000)).unwrap();
+        assert_eq!(db.audit_entry(order).unwrap().unwrap().status, "pending");
+
+        db.complete_audit_entry(order, "ok", Some(r#"{"status":"ok"}"#), 1_500)
+            .unwrap();
+        let done = db.audit_entry(order).unwrap().unwrap();
+        assert_eq!(
+            (done.status.as_str(), done.completed_ms),
+            ("ok", Some(1_500))
+        );
+        // An outcome is written once
+        assert!(db
+            .complete_audit_entry(order, "error", None, 1_600)
+            .is_err());
+        assert!(db.complete_audit_entry(99, "ok", None, 1_600).is_err());
+
+        // Nothing deletes or rewrites entries, even bypassing the methods
+        assert!(db.conn.execute("DELETE FROM audit_log", []).is_err());
+        assert!(db
+            .conn
+            .execute(
+                "UPDATE audit_log SET request = '{}' WHERE id = ?1",
+                params![transfer]
+            )
+            .is_err());
+        assert!(db
+            .conn
+            .execute(
+                "UPDATE audit_log SET status = 'ok' WHERE id = ?1",
+                params![order]
+            )
+            .is_err());
+
+        let all = db.query_audit_entries(&AuditFilter::default()).unwrap();
+        assert_eq!(
+            all.iter().map(|e| e.id).collect::<Vec<_>>(),
+            [transfer, order]
+        );
+        let transfers = db
+            .query_audit_entries(&AuditFilter {
+                action_type: Some("transfer".into()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(transfers.len(), 1);
+        let early = db
+            .query_audit_entries(&AuditFilter {
+                to_ms: Some(1_999),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(early[0].id, order);
+        assert_eq!(db.audit_entry(42).unwrap(), None);
+    }
+
     #[test]
     fn test_sync_state() {
         let db = AtlasDb::open_in_memory().unwrap();
@@ -687,10 +3926,541 @@ mod tests {
             protocol: None,
             coin: Some("BTC".to_string()),
             status: Some("filled".to_string()),
+            from_ms: None,
+            to_ms: None,
+            stale_before_ms: None,
             limit: Some(100),
         };
         assert_eq!(f.coin.as_deref(), Some("BTC"));
         assert_eq!(f.status.as_deref(), Some("filled"));
         assert_eq!(f.limit, Some(100));
     }
+
+    #[test]
+    fn test_integrity_check_and_reindex() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert!(db.integrity_check().unwrap().is_empty());
+        db.rebuild_indexes().unwrap();
+        assert!(db.integrity_check().unwrap().is_empty());
+    }
+
+    fn journal_fill(hash: &str, coin: &str, oid: i64, time_ms: i64) -> DbFill {
+        DbFill {
+            protocol: "hyperliquid".into(),
+            coin: coin.into(),
+            px: "100".into(),
+            sz: "1".into(),
+            side: "Buy".into(),
+            time_ms,
+            fee: "0.1".into(),
+            hash: hash.into(),
+            oid,
+            closed_pnl: "0".into(),
+        }
+    }
+
+    #[test]
+    fn test_journal_attach_to_cached_fill() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        db.insert_fills(&[journal_fill("0xaa", "ETH", 7, 1_000)])
+            .unwrap();
+
+        let tags = vec!["Breakout".to_string(), "news".into(), "breakout".into()];
+        let id = db
+            .add_journal_entry(&JournalTarget::Fill("0xaa".into()), "range break", &tags, 5)
+            .unwrap();
+        assert_eq!(db.journal_entry(id).unwrap().unwrap().note, "range break");
+        assert!(db.journal_entry(id + 1).unwrap().is_none());
+        let entries = db.query_journal(&JournalFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].is_linked());
+        assert_eq!(entries[0].coin.as_deref(), Some("ETH"));
+        assert_eq!(entries[0].oid, Some(7));
+        assert_eq!(entries[0].tags, vec!["breakout", "news"]);
+
+        let err = db
+            .add_journal_entry(&JournalTarget::Fill("0xmissing".into()), "x", &[], 5)
+            .unwrap_err();
+        assert!(err.to_string().contains("history sync"));
+    }
+
+    #[test]
+    fn test_journal_pending_notes_reconcile_on_sync() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let by_time = JournalTarget::CoinTime {
+            coin: "btc".into(),
+            time_ms: 1_000_000,
+        };
+        db.add_journal_entry(&by_time, "fomc fade", &[], 1).unwrap();
+        db.add_journal_entry(&JournalTarget::Order(42), "scaled in", &[], 1)
+            .unwrap();
+        assert!(db
+            .query_journal(&JournalFilter::default())
+            .unwrap()
+            .iter()
+            .all(|e| !e.is_linked()));
+
+        // Outside the window, wrong coin, then the real matches.
+        db.insert_fills(&[
+            journal_fill("0x01", "BTC", 1, 1_000_000 + JOURNAL_MATCH_WINDOW_MS + 1),
+            journal_fill("0x02", "ETH", 2, 1_000_000),
+            journal_fill("0x03", "BTC", 3, 1_000_000 - 30_000),
+            journal_fill("0x04", "SOL", 42, 2_000_000),
+            journal_fill("0x05", "SOL", 42, 2_000_500),
+        ])
+        .unwrap();
+        assert_eq!(db.reconcile_journal().unwrap(), 2);
+
+        let notes = db
+            .journal_notes_for_fills(&["0x03".into(), "0x04".into(), "0x05".into()])
+            .unwrap();
+        assert_eq!(notes["0x03"], vec!["fomc fade"]);
+        assert_eq!(notes["0x04"], vec!["scaled in"]);
+        assert!(!notes.contains_key("0x05"));
+        assert_eq!(db.reconcile_journal().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_journal_filters_and_search() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        db.insert_fills(&[
+            journal_fill("0x01", "BTC", 1, 1_000),
+            journal_fill("0x02", "ETH", 2, 2_000),
+        ])
+        .unwrap();
+        let tag = |t: &str| vec![t.to_string()];
+        db.add_journal_entry(
+            &JournalTarget::Fill("0x01".into()),
+            "Breakout above range high",
+            &tag("breakout"),
+            0,
+        )
+        .unwrap();
+        db.add_journal_entry(
+            &JournalTarget::Fill("0x02".into()),
+            "news driven \"pump\"",
+            &tag("news"),
+            0,
+        )
+        .unwrap();
+
+        let by = |filter: JournalFilter| {
+            db.query_journal(&filter)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.coin.unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(by(JournalFilter::default()), vec!["ETH", "BTC"]);
+        assert_eq!(
+            by(JournalFilter {
+                coin: Some("eth".into()),
+                ..Default::default()
+            }),
+            vec!["ETH"]
+        );
+        assert_eq!(
+            by(JournalFilter {
+                tag: Some("BREAKOUT".into()),
+                ..Default::default()
+            }),
+            vec!["BTC"]
+        );
+        assert!(by(JournalFilter {
+            tag: Some("break".into()),
+            ..Default::default()
+        })
+        .is_empty());
+        assert_eq!(
+            by(JournalFilter {
+                from_ms: Some(1_500),
+                ..Default::default()
+            }),
+            vec!["ETH"]
+        );
+        assert_eq!(
+            by(JournalFilter {
+                search: Some("range".into()),
+                ..Default::default()
+            }),
+            vec!["BTC"]
+        );
+        assert_eq!(
+            by(JournalFilter {
+                search: Some("\"pump\" news".into()),
+                ..Default::default()
+            }),
+            vec!["ETH"]
+        );
+        assert_eq!(
+            by(JournalFilter {
+                search: Some("  ".into()),
+                ..Default::default()
+            })
+            .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_address_book_roundtrip() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let entry = |chain: &str, name: &str, decimals| DbAddressEntry {
+            chain: chain.into(),
+            name: name.into(),
+            address: "0x940181a94A35A4569E4529A3CDfB74e38FD98631".into(),
+            decimals,
+            added_ms: 1,
+        };
+        db.add_address(&entry("base", "AERO", Some(18))).unwrap();
+        db.add_address(&entry("arbitrum", "aero", None)).unwrap();
+        // Names are unique per chain, ignoring case
+        assert!(db.add_address(&entry("base", "Aero", None)).is_err());
+
+        let book = db.address_book().unwrap();
+        assert_eq!(book.len(), 2);
+        assert_eq!(
+            (book[0].chain.as_str(), book[0].decimals),
+            ("arbitrum", None)
+        );
+        assert_eq!(
+            (book[1].name.as_str(), book[1].decimals),
+            ("AERO", Some(18))
+        );
+
+        assert!(db.remove_address("base", "aero").unwrap());
+        assert!(!db.remove_address("base", "aero").unwrap());
+        assert_eq!(db.address_book().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_dex_watchlist_roundtrip() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let weth_usdc = "0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640";
+        db.add_watched_pool("eth", weth_usdc, Some("weth"), 1)
+            .unwrap();
+        db.add_watched_pool(
+            "base",
+            "0x6921b130d297cc43754afba22e5eac0fbf8db75b",
+            None,
+            2,
+        )
+        .unwrap();
+        // Same pool twice, or a label already in use
+        assert!(db
+            .add_watched_pool("eth", &weth_usdc.to_uppercase(), None, 3)
+            .is_err());
+        assert!(db
+            .add_watched_pool("arb", "0x1234", Some("WETH"), 3)
+            .is_err());
+
+        db.record_pool_check("eth", weth_usdc, "3241.58", "536545444.9", "1000", 10)
+            .unwrap();
+        db.mark_pool_missing("eth", weth_usdc, 20).unwrap();
+        db.mark_pool_missing("eth", weth_usdc, 30).unwrap();
+        let pools = db.watched_pools().unwrap();
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[0].label.as_deref(), Some("weth"));
+        assert_eq!(pools[0].last_price_usd.as_deref(), Some("3241.58"));
+        assert_eq!(pools[0].last_checked_ms, Some(10));
+        assert_eq!(pools[0].missing_since_ms, Some(20));
+        assert_eq!(pools[1].last_checked_ms, None);
+
+        // Back again: no longer missing
+        db.record_pool_check("eth", weth_usdc, "3300", "1", "1", 40)
+            .unwrap();
+        assert_eq!(db.watched_pools().unwrap()[0].missing_since_ms, None);
+
+        let removed = db.remove_watched_pool("WETH").unwrap().unwrap();
+        assert_eq!(removed.pool, weth_usdc);
+        assert!(db.remove_watched_pool("weth").unwrap().is_none());
+        assert_eq!(db.watched_pools().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_schedule_runs_recorded() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let job = db
+            .add_schedule(
+                "hl perp buy BTC $50",
+                3_600_000,
+                Some("09:00"),
+                Some(2),
+                1_000,
+                0,
+            )
+            .unwrap();
+        db.add_schedule("hl perp buy ETH $20", 60_000, None, None, 2_000, 0)
+            .unwrap();
+        assert!(db.last_schedule_run(job.id).unwrap().is_none());
+
+        let run = |status: &str, missed: u32| DbScheduleRun {
+            schedule_id: job.id,
+            due_ms: 1_000,
+            started_ms: 1_500,
+            finished_ms: 1_600,
+            status: status.into(),
+            missed,
+            oid: (status == "ok").then(|| "77".into()),
+            fill_price: (status == "ok").then(|| "67000.5".into()),
+            error: None,
+        };
+        db.record_schedule_run(&run("skipped", 3), 5_000).unwrap();
+        db.record_schedule_run(&run("ok", 0), 9_000).unwrap();
+
+        let stored = &db.schedules().unwrap()[0];
+        assert_eq!((stored.runs, stored.next_run_ms), (1, 9_000));
+        assert_eq!(stored.at.as_deref(), Some("09:00"));
+        assert!(!stored.is_done());
+        let last = db.last_schedule_run(job.id).unwrap().unwrap();
+        assert_eq!(last.status, "ok");
+        assert_eq!(last.oid.as_deref(), Some("77"));
+        assert_eq!(last.fill_price.as_deref(), Some("67000.5"));
+
+        db.record_schedule_run(&run("error", 0), 13_000).unwrap();
+        assert!(db.schedules().unwrap()[0].is_done());
+
+        assert_eq!(db.remove_schedule(job.id).unwrap().unwrap().runs, 2);
+        assert!(db.remove_schedule(job.id).unwrap().is_none());
+        assert!(db.last_schedule_run(job.id).unwrap().is_none());
+        assert_eq!(db.schedules().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_purge_and_rename_account_rows() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let addr = "0xAbC0000000000000000000000000000000000001";
+        db.save_status_snapshot("bot", "Mainnet", "s1", 1, 5)
+            .unwrap();
+        db.save_status_snapshot("main", "Mainnet", "s2", 2, 5)
+            .unwrap();
+        db.save_backend_response(
+            &format!("/atlas-os/hl/fills?user={}", addr.to_lowercase()),
+            "[]",
+            1,
+        )
+        .unwrap();
+        db.save_backend_response("/atlas-os/hl/meta", "{}", 1)
+            .unwrap();
+        db.next_nonce(addr, 42).unwrap();
+
+        assert_eq!(db.rename_status_snapshots("bot", "sniper").unwrap(), 1);
+        assert!(db
+            .latest_status_snapshot("sniper", "Mainnet")
+            .unwrap()
+            .is_some());
+
+        assert_eq!(db.purge_account("sniper", addr).unwrap(), 3);
+        assert!(db
+            .latest_status_snapshot("sniper", "Mainnet")
+            .unwrap()
+            .is_none());
+        assert!(db
+            .latest_status_snapshot("main", "Mainnet")
+            .unwrap()
+            .is_some());
+        assert!(db.backend_response("/atlas-os/hl/meta").unwrap().is_some());
+        assert_eq!(db.purge_account("sniper", addr).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_status_snapshots_keyed_and_pruned() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert!(db
+            .latest_status_snapshot("main", "Mainnet")
+            .unwrap()
+            .is_none());
+        for t in 1..=4 {
+            db.save_status_snapshot("main", "Mainnet", &format!("m{t}"), t, 2)
+                .unwrap();
+        }
+        db.save_status_snapshot("main", "Testnet", "t1", 5, 2)
+            .unwrap();
+
+        let latest = db
+            .latest_status_snapshot("main", "Mainnet")
+            .unwrap()
+            .unwrap();
+        assert_eq!((latest.taken_ms, latest.snapshot.as_str()), (4, "m4"));
+        let kept: i64 = db
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM status_snapshots WHERE network = 'Mainnet'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(kept, 2);
+        assert_eq!(
+            db.latest_status_snapshot("main", "Testnet")
+                .unwrap()
+                .unwrap()
+                .snapshot,
+            "t1"
+        );
+        assert!(db
+            .latest_status_snapshot("alt", "Mainnet")
+            .unwrap()
+            .is_none());
+
+        let before = db
+            .status_snapshot_before("main", "Mainnet", 3)
+            .unwrap()
+            .unwrap();
+        assert_eq!(before.snapshot, "m3");
+        // m1 and m2 were pruned
+        assert!(db
+            .status_snapshot_before("main", "Mainnet", 2)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_swaps_and_unrealized_pnl() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let swap =
+            |status: &str, sell_usd: Option<&str>, buy: &str, hash: Option<&str>, t| DbSwap {
+                status: status.into(),
+                chain: "base".into(),
+                sell_token: "0x8335".into(),
+                sell_symbol: Some("USDC".into()),
+                sell_decimals: Some(6),
+                sell_amount: "1000000000".into(),
+                buy_token: "0x4200".into(),
+                buy_symbol: Some("WETH".into()),
+                buy_decimals: Some(18),
+                buy_amount: buy.into(),
+                price: None,
+                sell_value_usd: sell_usd.map(str::to_string),
+                gas_cost_usd: Some("0.01".into()),
+                tx_hash: hash.map(str::to_string),
+                time_ms: t,
+            };
+        db.insert_swap(&swap(
+            "executed",
+            Some("1000"),
+            "250000000000000000",
+            Some("0xa"),
+            1,
+        ))
+        .unwrap();
+        db.insert_swap(&swap(
+            "executed",
+            Some("1000"),
+            "300000000000000000",
+            Some("0xb"),
+            2,
+        ))
+        .unwrap();
+        db.insert_swap(&swap(
+            "executed",
+            None,
+            "300000000000000000",
+            Some("0xc"),
+            3,
+        ))
+        .unwrap();
+        db.insert_swap(&swap("quoted", Some("1000"), "280000000000000000", None, 4))
+            .unwrap();
+        // A tx hash is recorded once
+        assert!(db
+            .insert_swap(&swap("executed", Some("1"), "1", Some("0xa"), 5))
+            .is_err());
+
+        let all = db.query_swaps(&SwapFilter::default()).unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[0].status, "quoted");
+        assert_eq!(all[0].pair(), "USDC→WETH");
+        assert_eq!(
+            all[3].buy_tokens().unwrap().to_string(),
+            "0.250000000000000000"
+        );
+        let quoted = SwapFilter {
+            status: Some("quoted".into()),
+            ..Default::default()
+        };
+        assert_eq!(db.query_swaps(&quoted).unwrap().len(), 1);
+        let by_token = SwapFilter {
+            token: Some("weth".into()),
+            limit: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(db.query_swaps(&by_token).unwrap().len(), 2);
+
+        // Unpriced: cost known, no cached price yet
+        let (positions, skipped) = db.unrealized_swap_pnl(&SwapFilter::default()).unwrap();
+        assert_eq!(skipped, 1);
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].amount, Decimal::new(55, 2));
+        assert_eq!(positions[0].cost_usd, Decimal::from(2000));
+        assert_eq!(positions[0].gas_usd, Decimal::new(2, 2));
+        assert_eq!(positions[0].unrealized_pnl(), None);
+
+        // A newer price replaces an older one, never the other way round
+        db.set_token_price("base", "0x4200", "4000", 10).unwrap();
+        db.set_token_price("base", "0x4200", "1", 5).unwrap();
+        assert_eq!(
+            db.token_price("base", "0x4200").unwrap(),
+            Some(("4000".into(), 10))
+        );
+        let (positions, _) = db.unrealized_swap_pnl(&SwapFilter::default()).unwrap();
+        assert_eq!(positions[0].value_usd(), Some(Decimal::from(2200)));
+        assert_eq!(positions[0].unrealized_pnl(), Some(Decimal::from(200)));
+        assert_eq!(positions[0].priced_ms, Some(10));
+    }
+
+    #[test]
+    fn test_candle_cache_roundtrip_and_prune() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let candle = |t: u64, close: i64| Candle {
+            open_time_ms: t,
+            open: Decimal::ONE,
+            high: Decimal::from(close),
+            low: Decimal::ONE,
+            close: Decimal::from(close),
+            volume: Decimal::new(15, 1),
+            trades: Some(3),
+        };
+        let hour = 3_600_000u64;
+        let series: Vec<Candle> = (0..5).map(|i| candle(i * hour, 10 + i as i64)).collect();
+        assert_eq!(
+            db.upsert_candles("hyperliquid", "ETH", "1h", &series)
+                .unwrap(),
+            5
+        );
+        // Upserts replace rather than duplicate
+        db.upsert_candles("hyperliquid", "ETH", "1h", &[candle(4 * hour, 99)])
+            .unwrap();
+        db.set_candle_coverage("hyperliquid", "ETH", "1h", hour, 0, 4 * hour)
+            .unwrap();
+
+        let rows = db
+            .query_candles("hyperliquid", "ETH", "1h", hour, 4 * hour)
+            .unwrap();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[3].close, Decimal::from(99));
+        assert_eq!(rows[0].volume, Decimal::new(15, 1));
+        assert_eq!(rows[0].trades, Some(3));
+
+        // Cutoff mid-candle: coverage moves to the next open time
+        let removed = db
+            .prune_candles(Some("eth"), None, (2 * hour + 1) as i64)
+            .unwrap();
+        assert_eq!(removed, 3);
+        assert_eq!(
+            db.candle_coverage("hyperliquid", "ETH", "1h").unwrap(),
+            Some((3 * hour, 4 * hour))
+        );
+        let series = db.candle_series(None, Some("1h")).unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].candles, 2);
+        assert_eq!(series[0].oldest_ms, (3 * hour) as i64);
+
+        // Pruning past the end drops the coverage too
+        db.prune_candles(None, None, (10 * hour) as i64).unwrap();
+        assert_eq!(
+            db.candle_coverage("hyperliquid", "ETH", "1h").unwrap(),
+            None
+        );
+    }
 }
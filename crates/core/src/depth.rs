@@ -0,0 +1,217 @@
+//! Order book depth analytics: what a market order of a given size would
+//! actually pay, walked level by level through the visible book.
+//!
+//! Only resting size is counted. Hidden liquidity and orders that arrive
+//! before the fill can make the real price better or worse, so this is an
+//! estimate of the visible-book cost, not a quote.
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::types::{BookLevel, OrderBook, Side};
+
+const BPS: u32 = 10_000;
+
+/// Expected cost of taking `size` from one side of the book.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImpactEstimate {
+    /// Size the order asks for, in asset units.
+    pub size: Decimal,
+    /// Part of `size` the visible levels can fill.
+    pub fillable: Decimal,
+    /// Share of `size` covered by the visible book, 0–100.
+    pub coverage_pct: Decimal,
+    /// Mid between the best bid and ask; `None` when either side is empty.
+    pub mid: Option<Decimal>,
+    /// Size-weighted average fill price; `None` unless the book covers the
+    /// whole order.
+    pub avg_px: Option<Decimal>,
+    /// Price of the last level the order reaches.
+    pub worst_px: Option<Decimal>,
+    /// Distance of `avg_px` from `mid` against the taker, in bps.
+    pub impact_bps: Option<Decimal>,
+}
+
+impl ImpactEstimate {
+    /// Whether the visible book holds the whole order.
+    pub fn is_covered(&self) -> bool {
+        self.fillable >= self.size
+    }
+
+    /// `12.4 bps @ 3501.2`, or the coverage when the book runs out.
+    pub fn describe(&self) -> String {
+        match (self.avg_px, self.impact_bps) {
+            (Some(avg), Some(bps)) => format!("{} bps @ {}", bps.round_dp(1), avg.normalize()),
+            _ => format!(
+                "insufficient visible liquidity ({}% covered)",
+                self.coverage_pct.round_dp(1).normalize()
+            ),
+        }
+    }
+}
+
+/// Walk `book` for a market order of `size` on `side`: a buy takes the
+/// asks from the best price up, a sell the bids from the best price down.
+///
+/// Levels are taken in price order whatever order the book lists them in.
+/// When the visible levels run out before `size` is filled, the average
+/// price and impact are left out rather than computed over a partial fill.
+pub fn walk_book(book: &OrderBook, side: Side, size: Decimal) -> ImpactEstimate {
+    let mut levels: Vec<&BookLevel> = match side {
+        Side::Buy => book.asks.iter(),
+        Side::Sell => book.bids.iter(),
+    }
+    .filter(|l| l.size > Decimal::ZERO)
+    .collect();
+    match side {
+        Side::Buy => levels.sort_by(|a, b| a.price.cmp(&b.price)),
+        Side::Sell => levels.sort_by(|a, b| b.price.cmp(&a.price)),
+    }
+
+    let mut remaining = size.max(Decimal::ZERO);
+    let mut cost = Decimal::ZERO;
+    let mut worst_px = None;
+    for level in levels {
+        if remaining.is_zero() {
+            break;
+        }
+        let take = level.size.min(remaining);
+        cost += take * level.price;
+        remaining -= take;
+        worst_px = Some(level.price);
+    }
+
+    let fillable = size.max(Decimal::ZERO) - remaining;
+    let coverage_pct = if size > Decimal::ZERO {
+        fillable / size * Decimal::ONE_HUNDRED
+    } else {
+        Decimal::ONE_HUNDRED
+    };
+    let mid = mid_price(book);
+    let avg_px = (remaining.is_zero() && fillable > Decimal::ZERO).then(|| cost / fillable);
+    let impact_bps = match (avg_px, mid) {
+        (Some(avg), Some(mid)) if !mid.is_zero() => {
+            let adverse = match side {
+                Side::Buy => avg - mid,
+                Side::Sell => mid - avg,
+            };
+            Some(adverse / mid * Decimal::from(BPS))
+        }
+        _ => None,
+    };
+
+    ImpactEstimate {
+        size,
+        fillable,
+        coverage_pct,
+        mid,
+        avg_px,
+        worst_px,
+        impact_bps,
+    }
+}
+
+/// Mid between the best bid and best ask.
+pub fn mid_price(book: &OrderBook) -> Option<Decimal> {
+    let best_bid = book.bids.iter().map(|l| l.price).max()?;
+    let best_ask = book.asks.iter().map(|l| l.price).min()?;
+    Some((best_bid + best_ask) / Decimal::TWO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Protocol;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn level(price: &str, size: &str) -> BookLevel {
+        BookLevel {
+            price: d(price),
+            size: d(size),
+            count: None,
+        }
+    }
+
+    /// Mid 100: asks 100.5 × 1, 101 × 2, 102 × 3; bids mirror them.
+    fn book() -> OrderBook {
+        OrderBook {
+            symbol: "ETH".into(),
+            protocol: Protocol::Hyperliquid,
+            bids: vec![level("99.5", "1"), level("99", "2"), level("98", "3")],
+            asks: vec![level("100.5", "1"), level("101", "2"), level("102", "3")],
+            timestamp_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_walk_within_top_level() {
+        let est = walk_book(&book(), Side::Buy, d("0.5"));
+        assert!(est.is_covered());
+        assert_eq!(est.mid, Some(d("100")));
+        assert_eq!(est.avg_px, Some(d("100.5")));
+        assert_eq!(est.worst_px, Some(d("100.5")));
+        assert_eq!(est.impact_bps, Some(d("50")));
+    }
+
+    #[test]
+    fn test_walk_across_levels() {
+        // 1 @ 100.5 + 2 @ 101 + 1 @ 102 = 404.5 over 4
+        let est = walk_book(&book(), Side::Buy, d("4"));
+        assert_eq!(est.avg_px, Some(d("101.125")));
+        assert_eq!(est.worst_px, Some(d("102")));
+        assert_eq!(est.impact_bps, Some(d("112.5")));
+        assert_eq!(est.describe(), "112.5 bps @ 101.125");
+
+        // Sells walk the bids down and read impact the other way
+        let est = walk_book(&book(), Side::Sell, d("4"));
+        assert_eq!(est.avg_px, Some(d("98.875")));
+        assert_eq!(est.impact_bps, Some(d("112.5")));
+    }
+
+    #[test]
+    fn test_walk_sorts_unordered_levels() {
+        let mut shuffled = book();
+        shuffled.asks.reverse();
+        shuffled.bids.swap(0, 2);
+        assert_eq!(
+            walk_book(&shuffled, Side::Buy, d("4")),
+            walk_book(&book(), Side::Buy, d("4"))
+        );
+        assert_eq!(
+            walk_book(&shuffled, Side::Sell, d("4")),
+            walk_book(&book(), Side::Sell, d("4"))
+        );
+    }
+
+    #[test]
+    fn test_walk_beyond_visible_depth() {
+        let est = walk_book(&book(), Side::Buy, d("8"));
+        assert!(!est.is_covered());
+        assert_eq!(est.fillable, d("6"));
+        assert_eq!(est.coverage_pct, d("75"));
+        assert_eq!(est.avg_px, None);
+        assert_eq!(est.impact_bps, None);
+        assert_eq!(est.worst_px, Some(d("102")));
+        assert_eq!(
+            est.describe(),
+            "insufficient visible liquidity (75% covered)"
+        );
+    }
+
+    #[test]
+    fn test_walk_empty_side() {
+        let mut one_sided = book();
+        one_sided.asks.clear();
+        let est = walk_book(&one_sided, Side::Buy, d("1"));
+        assert_eq!(est.mid, None);
+        assert_eq!(est.coverage_pct, Decimal::ZERO);
+        assert_eq!(est.avg_px, None);
+        // The other side still walks, but without a mid there is no impact
+        let est = walk_book(&one_sided, Side::Sell, d("1"));
+        assert_eq!(est.avg_px, Some(d("99.5")));
+        assert_eq!(est.impact_bps, None);
+    }
+}
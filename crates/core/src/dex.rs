@@ -0,0 +1,426 @@
+//! Pool analytics from GeckoTerminal (`atlas market dex ohlcv|trades|watch`).
+//!
+//! The backend passes GeckoTerminal's JSON:API responses through unchanged;
+//! this module turns them into typed rows. Numbers arrive as JSON numbers
+//! or strings (sometimes in scientific notation for dust-priced tokens) and
+//! are kept as `Decimal`. A pool with no trades yet has an empty list, and
+//! a young one simply has fewer candles than requested — neither is an
+//! error.
+
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::db::DbWatchedPool;
+use crate::fmt::format_timestamp_ms;
+use crate::output::{DexCandleRow, DexTradeRow, DexWatchRow};
+
+/// Candle timeframes GeckoTerminal serves.
+pub const OHLCV_TIMEFRAMES: [&str; 3] = ["day", "hour", "minute"];
+
+/// Most candles one OHLCV request returns.
+pub const MAX_OHLCV_LIMIT: usize = 1000;
+
+/// Aggregations allowed for `timeframe`: `hour` with `4` is a 4h candle.
+pub fn ohlcv_aggregates(timeframe: &str) -> Option<&'static [u32]> {
+    match timeframe {
+        "day" => Some(&[1]),
+        "hour" => Some(&[1, 4, 12]),
+        "minute" => Some(&[1, 5, 15]),
+        _ => None,
+    }
+}
+
+/// Check OHLCV parameters before asking the backend.
+pub fn validate_ohlcv(timeframe: &str, aggregate: u32, limit: usize) -> Result<(), String> {
+    let allowed = ohlcv_aggregates(timeframe).ok_or_else(|| {
+        format!(
+            "Invalid timeframe '{timeframe}'. Use: {}",
+            OHLCV_TIMEFRAMES.join(", ")
+        )
+    })?;
+    if !allowed.contains(&aggregate) {
+        let allowed: Vec<String> = allowed.iter().map(u32::to_string).collect();
+        return Err(format!(
+            "Invalid --aggregate {aggregate} for {timeframe} candles. Use: {}",
+            allowed.join(", ")
+        ));
+    }
+    if limit == 0 || limit > MAX_OHLCV_LIMIT {
+        return Err(format!("--limit must be 1-{MAX_OHLCV_LIMIT}"));
+    }
+    Ok(())
+}
+
+/// Candles of an OHLCV response, oldest first. Malformed entries are
+/// skipped.
+pub fn parse_ohlcv(resp: &Value) -> Vec<DexCandleRow> {
+    let list = resp
+        .pointer("/data/attributes/ohlcv_list")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let mut candles: Vec<DexCandleRow> = list
+        .iter()
+        .filter_map(|entry| {
+            let v = entry.as_array()?;
+            let ts = v.first()?.as_u64()?;
+            let field = |i: usize| v.get(i).and_then(decimal).map(|d| d.to_string());
+            Some(DexCandleRow {
+                timestamp_ms: ts * 1000,
+                time: format_timestamp_ms(ts * 1000),
+                open: field(1)?,
+                high: field(2)?,
+                low: field(3)?,
+                close: field(4)?,
+                volume_usd: field(5).unwrap_or_else(|| "0".into()),
+            })
+        })
+        .collect();
+    candles.sort_by_key(|c| c.timestamp_ms);
+    candles.dedup_by_key(|c| c.timestamp_ms);
+    candles
+}
+
+/// Base and quote token symbols from an OHLCV response's `meta`.
+pub fn ohlcv_pair(resp: &Value) -> (Option<String>, Option<String>) {
+    let symbol = |side: &str| {
+        resp.pointer(&format!("/meta/{side}/symbol"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    (symbol("base"), symbol("quote"))
+}
+
+/// Trades of a pool trades response, newest first, keeping those worth at
+/// least `min_usd`.
+///
+/// GeckoTerminal reports each trade as a swap from one token to the other;
+/// for a buy the pool's base token is the `to` side, for a sell the `from`
+/// side.
+pub fn parse_trades(resp: &Value, min_usd: Option<Decimal>) -> Vec<DexTradeRow> {
+    let list = resp
+        .get("data")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let mut trades: Vec<DexTradeRow> = list
+        .iter()
+        .filter_map(|t| {
+            let a = t.get("attributes")?;
+            let num = |key: &str| a.get(key).and_then(decimal);
+            let side = a.get("kind").and_then(Value::as_str)?.to_ascii_lowercase();
+            let (base, quote, price) = if side == "buy" {
+                ("to_token_amount", "from_token_amount", "price_to_in_usd")
+            } else {
+                ("from_token_amount", "to_token_amount", "price_from_in_usd")
+            };
+            let volume_usd = num("volume_in_usd")?;
+            if min_usd.is_some_and(|min| volume_usd < min) {
+                return None;
+            }
+            let time = a.get("block_timestamp").and_then(Value::as_str)?;
+            let timestamp_ms = chrono::DateTime::parse_from_rfc3339(time)
+                .ok()?
+                .timestamp_millis()
+                .max(0) as u64;
+            Some(DexTradeRow {
+                timestamp_ms,
+                time: format_timestamp_ms(timestamp_ms),
+                side,
+                base_amount: num(base)?.to_string(),
+                quote_amount: num(quote)?.to_string(),
+                price_usd: num(price).map(|p| p.to_string()),
+                volume_usd: volume_usd.to_string(),
+                tx_hash: a
+                    .get("tx_hash")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        })
+        .collect();
+    trades.sort_by_key(|t| std::cmp::Reverse(t.timestamp_ms));
+    trades
+}
+
+/// Headline stats of a pool, from a pool detail response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolStats {
+    pub name: Option<String>,
+    pub price_usd: Decimal,
+    pub volume_24h_usd: Decimal,
+    pub liquidity_usd: Decimal,
+}
+
+/// Stats of a pool detail response (`data.attributes`), `None` if the
+/// pool has no price yet.
+pub fn parse_pool_stats(resp: &Value) -> Option<PoolStats> {
+    let a = resp.pointer("/data/attributes")?;
+    let num = |ptr: &str| a.pointer(ptr).and_then(decimal);
+    Some(PoolStats {
+        name: a.get("name").and_then(Value::as_str).map(str::to_string),
+        price_usd: num("/base_token_price_usd")?,
+        volume_24h_usd: num("/volume_usd/h24").unwrap_or_default(),
+        liquidity_usd: num("/reserve_in_usd").unwrap_or_default(),
+    })
+}
+
+/// Percent change from `prev` to `now`, to two decimals. `None` without a
+/// usable previous value.
+pub fn pct_change(prev: Option<&str>, now: Decimal) -> Option<f64> {
+    let prev = Decimal::from_str(prev?).ok().filter(|p| !p.is_zero())?;
+    let pct = ((now - prev) / prev * Decimal::ONE_HUNDRED).round_dp(2);
+    pct.to_string().parse().ok()
+}
+
+/// What a watchlist check found for one pool.
+#[derive(Debug, Clone)]
+pub enum PoolCheck {
+    Found(PoolStats),
+    /// The API no longer knows the pool (rugged, migrated or delisted).
+    Missing,
+    /// The request failed; the pool may still exist.
+    Failed(String),
+}
+
+/// Watchlist row for a pool checked at `now_ms`: current stats and the
+/// change since the stats recorded at its previous check.
+pub fn watch_row(entry: &DbWatchedPool, check: &PoolCheck, now_ms: i64) -> DexWatchRow {
+    let mut row = DexWatchRow {
+        network: entry.network.clone(),
+        pool: entry.pool.clone(),
+        label: entry.label.clone(),
+        name: None,
+        status: String::new(),
+        price_usd: None,
+        price_change_pct: None,
+        volume_24h_usd: None,
+        volume_change_pct: None,
+        liquidity_usd: None,
+        liquidity_change_pct: None,
+        previous_check_ms: entry.last_checked_ms,
+        missing_since_ms: None,
+        error: None,
+    };
+    match check {
+        PoolCheck::Found(stats) => {
+            row.status = if entry.last_checked_ms.is_some() {
+                "ok"
+            } else {
+                "new"
+            }
+            .into();
+            row.name = stats.name.clone();
+            row.price_usd = Some(stats.price_usd.normalize().to_string());
+            row.volume_24h_usd = Some(stats.volume_24h_usd.normalize().to_string());
+            row.liquidity_usd = Some(stats.liquidity_usd.normalize().to_string());
+            row.price_change_pct = pct_change(entry.last_price_usd.as_deref(), stats.price_usd);
+            row.volume_change_pct =
+                pct_change(entry.last_volume_usd.as_deref(), stats.volume_24h_usd);
+            row.liquidity_change_pct =
+                pct_change(entry.last_liquidity_usd.as_deref(), stats.liquidity_usd);
+        }
+        PoolCheck::Missing => {
+            row.status = "missing".into();
+            row.missing_since_ms = Some(entry.missing_since_ms.unwrap_or(now_ms));
+        }
+        PoolCheck::Failed(e) => {
+            row.status = "error".into();
+            row.error = Some(e.clone());
+        }
+    }
+    row
+}
+
+/// Whether a checked pool belongs in `watch check --threshold` output: its
+/// price moved more than `threshold_pct` either way. Missing pools and
+/// failed checks are always shown.
+pub fn exceeds_threshold(row: &DexWatchRow, threshold_pct: f64) -> bool {
+    match row.status.as_str() {
+        "missing" | "error" => true,
+        _ => row
+            .price_change_pct
+            .is_some_and(|pct| pct.abs() > threshold_pct),
+    }
+}
+
+/// A number given as JSON number or string, plain or scientific.
+fn decimal(v: &Value) -> Option<Decimal> {
+    let s = match v {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.trim().to_string(),
+        _ => return None,
+    };
+    Decimal::from_str(&s)
+        .or_else(|_| Decimal::from_scientific(&s))
+        .ok()
+        .map(|d| d.normalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_ohlcv() {
+        assert!(validate_ohlcv("hour", 4, 100).is_ok());
+        assert!(validate_ohlcv("minute", 15, 1000).is_ok());
+        assert!(validate_ohlcv("week", 1, 100)
+            .unwrap_err()
+            .contains("day, hour, minute"));
+        assert!(validate_ohlcv("day", 4, 100)
+            .unwrap_err()
+            .contains("Use: 1"));
+        assert!(validate_ohlcv("hour", 1, 0).is_err());
+        assert!(validate_ohlcv("hour", 1, MAX_OHLCV_LIMIT + 1).is_err());
+    }
+
+    #[test]
+    fn test_parse_ohlcv_sorts_oldest_first() {
+        let resp = json!({
+            "data": {
+                "id": "bc786a99",
+                "type": "ohlcv_request_response",
+                "attributes": {
+                    "ohlcv_list": [
+                        [1712538000, 3454.61, 3660.86, 3417.92, 3660.86, 306823.27],
+                        [1712534400, "3400.1", "3460", "3390.5", "3454.61", "120400.5"],
+                        ["garbage"],
+                        [1712530800, 2.5e-7, 3.1e-7, 2.4e-7, 3.0e-7, 12]
+                    ]
+                }
+            },
+            "meta": {
+                "base": {"address": "0xc02a", "name": "Wrapped Ether", "symbol": "WETH"},
+                "quote": {"address": "0xa0b8", "name": "USD Coin", "symbol": "USDC"}
+            }
+        });
+        let candles = parse_ohlcv(&resp);
+        assert_eq!(candles.len(), 3);
+        assert_eq!(candles[0].timestamp_ms, 1_712_530_800_000);
+        assert_eq!(candles[0].open, "0.00000025");
+        assert_eq!(candles[1].close, "3454.61");
+        assert_eq!(candles[2].high, "3660.86");
+        assert_eq!(candles[2].volume_usd, "306823.27");
+        assert_eq!(
+            ohlcv_pair(&resp),
+            (Some("WETH".into()), Some("USDC".into()))
+        );
+    }
+
+    #[test]
+    fn test_parse_ohlcv_empty_pool() {
+        let resp = json!({"data": {"attributes": {"ohlcv_list": []}}, "meta": {}});
+        assert!(parse_ohlcv(&resp).is_empty());
+        assert!(parse_ohlcv(&json!({})).is_empty());
+        assert_eq!(ohlcv_pair(&json!({})), (None, None));
+    }
+
+    fn trade(kind: &str, usd: &str, at: &str) -> Value {
+        json!({
+            "id": "eth_19612255_0xabc_158",
+            "type": "trade",
+            "attributes": {
+                "block_number": 19612255,
+                "tx_hash": "0xabc",
+                "from_token_amount": if kind == "buy" { "3241.58" } else { "1.0" },
+                "to_token_amount": if kind == "buy" { "1.0" } else { "3241.58" },
+                "price_from_in_usd": if kind == "buy" { "1.0" } else { "3241.58" },
+                "price_to_in_usd": if kind == "buy" { "3241.58" } else { "1.0" },
+                "block_timestamp": at,
+                "kind": kind,
+                "volume_in_usd": usd
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_trades_orients_base_and_quote() {
+        let resp = json!({"data": [
+            trade("sell", "3241.58", "2024-04-09T08:03:59Z"),
+            trade("buy", "3241.58", "2024-04-09T08:05:11Z"),
+        ]});
+        let trades = parse_trades(&resp, None);
+        assert_eq!(trades.len(), 2);
+        // Newest first; base token amount and price on both sides
+        assert_eq!(trades[0].side, "buy");
+        assert_eq!(trades[0].time, "2024-04-09 08:05:11");
+        for t in &trades {
+            assert_eq!(t.base_amount, "1");
+            assert_eq!(t.quote_amount, "3241.58");
+            assert_eq!(t.price_usd.as_deref(), Some("3241.58"));
+        }
+    }
+
+    #[test]
+    fn test_parse_trades_min_usd() {
+        let resp = json!({"data": [
+            trade("buy", "50", "2024-04-09T08:00:00Z"),
+            trade("sell", "5000", "2024-04-09T08:01:00Z"),
+        ]});
+        let big = parse_trades(&resp, Some(Decimal::from(1000)));
+        assert_eq!(big.len(), 1);
+        assert_eq!(big[0].volume_usd, "5000");
+        assert!(parse_trades(&json!({"data": []}), None).is_empty());
+    }
+
+    fn watched(last_price: Option<&str>) -> DbWatchedPool {
+        DbWatchedPool {
+            network: "base".into(),
+            pool: "0x6921b130d297cc43754afba22e5eac0fbf8db75b".into(),
+            label: Some("toshi".into()),
+            added_ms: 1,
+            last_price_usd: last_price.map(str::to_string),
+            last_volume_usd: last_price.map(|_| "1000000".to_string()),
+            last_liquidity_usd: last_price.map(|_| "0".to_string()),
+            last_checked_ms: last_price.map(|_| 1_000),
+            missing_since_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_watch_row_changes_since_last_check() {
+        let resp = json!({"data": {"attributes": {
+            "name": "TOSHI / WETH",
+            "base_token_price_usd": "0.009",
+            "volume_usd": {"h24": "1250000"},
+            "reserve_in_usd": "3182041.44"
+        }}});
+        let stats = parse_pool_stats(&resp).unwrap();
+        assert!(parse_pool_stats(&json!({"data": {"attributes": {}}})).is_none());
+
+        let row = watch_row(
+            &watched(Some("0.0075")),
+            &PoolCheck::Found(stats.clone()),
+            2_000,
+        );
+        assert_eq!(row.status, "ok");
+        assert_eq!(row.price_change_pct, Some(20.0));
+        assert_eq!(row.volume_change_pct, Some(25.0));
+        // No meaningful change from zero liquidity
+        assert_eq!(row.liquidity_change_pct, None);
+        assert!(exceeds_threshold(&row, 10.0));
+        assert!(!exceeds_threshold(&row, 20.0));
+
+        let first = watch_row(&watched(None), &PoolCheck::Found(stats), 2_000);
+        assert_eq!(first.status, "new");
+        assert_eq!(first.price_change_pct, None);
+        assert!(!exceeds_threshold(&first, 0.0));
+    }
+
+    #[test]
+    fn test_watch_row_flags_missing_pools() {
+        let row = watch_row(&watched(Some("0.0075")), &PoolCheck::Missing, 2_000);
+        assert_eq!(row.status, "missing");
+        assert_eq!(row.missing_since_ms, Some(2_000));
+        assert_eq!(row.price_usd, None);
+        assert!(exceeds_threshold(&row, 50.0));
+
+        let failed = watch_row(&watched(None), &PoolCheck::Failed("timeout".into()), 2_000);
+        assert_eq!(failed.error.as_deref(), Some("timeout"));
+        assert!(exceeds_threshold(&failed, 50.0));
+    }
+}
@@ -11,6 +11,16 @@ use tracing::info;
 
 use crate::auth::AuthManager;
 use crate::config::AppConfig;
+use crate::order_sync::{self, OrderSyncStats};
+
+/// Rows a sync added (or, for orders, updated) per kind.
+#[derive(Debug, Default)]
+pub struct SyncStats {
+    pub fills: usize,
+    pub orders: OrderSyncStats,
+    pub transfers: usize,
+    pub funding: usize,
+}
 
 /// Minimal engine for DB sync operations.
 pub struct Engine {
@@ -20,10 +30,20 @@ pub struct Engine {
 }
 
 impl Engine {
-    /// Create from the active wallet profile.
+    /// Create from the active wallet profile (or `--profile`).
     pub async fn from_active_profile() -> Result<Self> {
         let config = crate::workspace::load_config()?;
-        let signer = AuthManager::get_active_signer()?;
+        let profile = AuthManager::active_profile(&config).to_string();
+        Self::from_profile_with(config, &profile)
+    }
+
+    /// Create for `profile`, whatever profile is active.
+    pub async fn from_profile(profile: &str) -> Result<Self> {
+        Self::from_profile_with(crate::workspace::load_config()?, profile)
+    }
+
+    fn from_profile_with(config: AppConfig, profile: &str) -> Result<Self> {
+        let signer = AuthManager::load_signer(profile)?;
         let address = signer.address();
         let testnet = config.modules.hyperliquid.config.network == "testnet";
 
@@ -79,54 +99,199 @@ impl Engine {
             .collect();
 
         let inserted = db.insert_fills(&db_fills)?;
-        info!(fetched = api_fills.len(), inserted, "fills sync complete");
+        let linked = db.reconcile_journal()?;
+        if linked > 0 {
+            info!(linked, "journal notes linked to synced fills");
+        }
+        info!(
+            event = "sync",
+            kind = "fills",
+            fetched = api_fills.len(),
+            inserted,
+            status = "ok",
+            "fills sync complete"
+        );
         Ok(inserted)
     }
 
-    /// Sync historical orders from the API into the local database.
-    pub async fn sync_orders(&self, db: &crate::db::AtlasDb) -> Result<usize> {
-        use crate::db::DbOrder;
+    /// Sync every fill Hyperliquid still serves (its most recent 10000),
+    /// paging `userFillsByTime` forward from the account's start.
+    pub async fn sync_fills_full(&self, db: &crate::db::AtlasDb) -> Result<usize> {
+        info!("syncing full fill history from API");
+
+        let user = format!("{:?}", self.address);
+        let inserted = crate::ledger::sync_fills(db, 0, |start_ms| {
+            let body = serde_json::json!({
+                "type": "userFillsByTime",
+                "user": user,
+                "startTime": start_ms,
+            });
+            async move {
+                self.post_info(body)
+                    .await
+                    .context("Failed to fetch fills from API")
+            }
+        })
+        .await?;
+        let linked = db.reconcile_journal()?;
+        if linked > 0 {
+            info!(linked, "journal notes linked to synced fills");
+        }
+        info!(
+            event = "sync",
+            kind = "fills",
+            inserted,
+            full = true,
+            status = "ok",
+            "fills sync complete"
+        );
+        Ok(inserted)
+    }
 
+    /// Sync orders from the API into the local database: new orders are
+    /// cached, cached ones still open get their current status.
+    pub async fn sync_orders(&self, db: &crate::db::AtlasDb) -> Result<OrderSyncStats> {
         info!("syncing orders from API");
 
-        let api_orders = self
-            .client
-            .historical_orders(self.address)
+        let user = format!("{:?}", self.address);
+        let historical = self
+            .post_info(serde_json::json!({"type": "historicalOrders", "user": user}))
             .await
             .context("Failed to fetch historical orders from API")?;
 
-        let db_orders: Vec<DbOrder> = api_orders
-            .iter()
-            .map(|o| {
-                let side = match o.side {
-                    Side::Bid => "Buy",
-                    Side::Ask => "Sell",
-                };
-                let order_type = format!("{:?}", o.order_type);
-                DbOrder {
-                    protocol: "hyperliquid".to_string(),
-                    coin: o.coin.clone(),
-                    side: side.to_string(),
-                    limit_px: o.limit_px.to_string(),
-                    sz: o.sz.to_string(),
-                    oid: o.oid as i64,
-                    timestamp_ms: o.timestamp as i64,
-                    status: "historical".to_string(),
-                    order_type,
-                }
-            })
-            .collect();
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let stats = order_sync::reconcile_orders(db, &historical, now_ms, |oid| {
+            let body = serde_json::json!({"type": "orderStatus", "user": user, "oid": oid});
+            async move {
+                self.post_info(body)
+                    .await
+                    .with_context(|| format!("Failed to fetch status of order {oid}"))
+            }
+        })
+        .await?;
+        info!(
+            event = "sync",
+            kind = "orders",
+            fetched = stats.fetched,
+            inserted = stats.inserted,
+            updated = stats.updated,
+            status = "ok",
+            "orders sync complete"
+        );
+        Ok(stats)
+    }
+
+    /// Sync deposits, withdrawals and transfers from the non-funding
+    /// ledger, starting at the newest cached entry (or the beginning when
+    /// `full`).
+    pub async fn sync_transfers(&self, db: &crate::db::AtlasDb, full: bool) -> Result<usize> {
+        info!("syncing transfers from API");
+
+        let user = format!("{:?}", self.address);
+        let start_ms = if full {
+            0
+        } else {
+            db.last_transfer_time()?.unwrap_or(0)
+        };
+        let inserted = crate::ledger::sync_transfers(db, &user, start_ms, |start_ms| {
+            let body = serde_json::json!({
+                "type": "userNonFundingLedgerUpdates",
+                "user": user,
+                "startTime": start_ms,
+            });
+            async move {
+                self.post_info(body)
+                    .await
+                    .context("Failed to fetch ledger updates from API")
+            }
+        })
+        .await?;
+        info!(
+            event = "sync",
+            kind = "transfers",
+            inserted,
+            status = "ok",
+            "transfers sync complete"
+        );
+        Ok(inserted)
+    }
+
+    /// Sync funding payments, starting at the newest cached one (or the
+    /// beginning when `full`).
+    pub async fn sync_funding(&self, db: &crate::db::AtlasDb, full: bool) -> Result<usize> {
+        info!("syncing funding payments from API");
 
-        let inserted = db.insert_orders(&db_orders)?;
-        info!(fetched = api_orders.len(), inserted, "orders sync complete");
+        let user = format!("{:?}", self.address);
+        let start_ms = if full {
+            0
+        } else {
+            db.last_funding_time()?.unwrap_or(0)
+        };
+        let inserted = crate::ledger::sync_funding(db, start_ms, |start_ms| {
+            let body = serde_json::json!({
+                "type": "userFunding",
+                "user": user,
+                "startTime": start_ms,
+            });
+            async move {
+                self.post_info(body)
+                    .await
+                    .context("Failed to fetch funding payments from API")
+            }
+        })
+        .await?;
+        info!(
+            event = "sync",
+            kind = "funding",
+            inserted,
+            status = "ok",
+            "funding sync complete"
+        );
         Ok(inserted)
     }
 
-    /// Sync all data (fills + orders) from the API into the local database.
-    pub async fn sync_all(&self, db: &crate::db::AtlasDb) -> Result<(usize, usize)> {
-        let fills = self.sync_fills(db).await?;
-        let orders = self.sync_orders(db).await?;
-        Ok((fills, orders))
+    /// Sync all data (fills, orders, transfers, funding) from the API into
+    /// the local database. `full` re-walks the whole account history
+    /// instead of only what is newer than the cache, and records that the
+    /// cache is complete (see [`crate::ledger::FULL_SYNC_STATE`]).
+    pub async fn sync_all(&self, db: &crate::db::AtlasDb, full: bool) -> Result<SyncStats> {
+        let fills = if full {
+            self.sync_fills_full(db).await?
+        } else {
+            self.sync_fills(db).await?
+        };
+        let stats = SyncStats {
+            fills,
+            orders: self.sync_orders(db).await?,
+            transfers: self.sync_transfers(db, full).await?,
+            funding: self.sync_funding(db, full).await?,
+        };
+        if full {
+            let now_ms = chrono::Utc::now().timestamp_millis();
+            db.set_sync_state(crate::ledger::FULL_SYNC_STATE, &now_ms.to_string())?;
+        }
+        Ok(stats)
+    }
+
+    /// Raw `/info` request against the configured network.
+    async fn post_info(&self, body: serde_json::Value) -> Result<serde_json::Value> {
+        let base = if self.config.modules.hyperliquid.config.network == "testnet" {
+            crate::constants::HL_TESTNET_RPC
+        } else {
+            crate::constants::HL_MAINNET_RPC
+        };
+        let resp = reqwest::Client::new()
+            .post(format!("{base}/info"))
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(resp.json().await?)
+    }
+
+    /// Builder fee for orders placed under this engine's config.
+    pub fn builder_fee(&self) -> Option<BuilderFee> {
+        BuilderFee::from_config(&self.config.modules.hyperliquid.config)
     }
 }
 // Builder fee constants for protocol revenue injection.
@@ -135,20 +300,30 @@ impl Engine {
 // ║  BUILDER FEE — MANDATORY REVENUE LAYER                        ║
 // ║                                                                ║
 // ║  Every order submitted through Atlas MUST include the builder  ║
-// ║  parameter pointing to BUILDER_ADDRESS. This is the sole      ║
-// ║  monetization mechanism of the protocol. Do NOT remove or      ║
-// ║  bypass this. Any order path that skips builder fee injection  ║
-// ║  is a critical bug.                                            ║
+// ║  parameter resolved by `BuilderFee::from_config`. This is the  ║
+// ║  sole monetization mechanism of the protocol. Any order path   ║
+// ║  that skips builder fee injection is a critical bug. The only  ║
+// ║  sanctioned way to change or disable it is an acknowledged     ║
+// ║  config / environment override.                                ║
 // ╚══════════════════════════════════════════════════════════════════╝
 
 use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::HyperliquidConfig;
 
 /// The address that receives builder fees on Hyperliquid.
-/// Replace with the actual revenue wallet before production deployment.
-pub const BUILDER_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+pub const BUILDER_ADDRESS: &str = crate::constants::BUILDER_ADDRESS_EVM;
+
+/// Default builder fee in basis points.
+pub const BUILDER_FEE_BPS: u16 = crate::constants::BUILDER_FEE_BPS;
 
-/// Builder fee in basis points (10 bps = 0.1%).
-pub const BUILDER_FEE_BPS: u16 = 10;
+/// Overrides `builder_fee_bps` from the environment.
+pub const ENV_BUILDER_FEE_BPS: &str = "ATLAS_BUILDER_FEE_BPS";
+/// Overrides `builder_address` from the environment.
+pub const ENV_BUILDER_ADDRESS: &str = "ATLAS_BUILDER_ADDRESS";
+/// `1` acknowledges an environment override without touching the config.
+pub const ENV_BUILDER_FEE_ACK: &str = "ATLAS_BUILDER_FEE_ACK";
 
 /// Builder fee payload injected into the exchange action JSON.
 ///
@@ -159,14 +334,14 @@ pub const BUILDER_FEE_BPS: u16 = 10;
 ///     "type": "order",
 ///     "orders": [...],
 ///     "grouping": "na",
-///     "builder": { "b": "0xADDRESS", "f": 10 }
+///     "builder": { "b": "0xADDRESS", "f": 1 }
 ///   }
 /// }
 /// ```
 ///
 /// The builder field is NOT part of the signed data (excluded from the
 /// RMP hash), so it can be injected after signing.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BuilderFee {
     /// Builder address (hex with 0x prefix).
     pub b: String,
@@ -183,6 +358,68 @@ impl Default for BuilderFee {
     }
 }
 
+impl BuilderFee {
+    /// The fee to attach to orders, or `None` when disabled (0 bps).
+    ///
+    /// `ATLAS_BUILDER_FEE_BPS` / `ATLAS_BUILDER_ADDRESS` take precedence
+    /// over the config. Anything but the default only applies once
+    /// acknowledged (`builder_fee_acknowledged`, or `ATLAS_BUILDER_FEE_ACK=1`);
+    /// otherwise the default fee is used and a warning logged.
+    pub fn from_config(cfg: &HyperliquidConfig) -> Option<Self> {
+        let env = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+        let bps = match env(ENV_BUILDER_FEE_BPS) {
+            Some(raw) => raw.trim().parse().unwrap_or_else(|_| {
+                warn!(value = %raw, "ignoring invalid {ENV_BUILDER_FEE_BPS}");
+                cfg.builder_fee_bps
+            }),
+            None => cfg.builder_fee_bps,
+        };
+        let address = env(ENV_BUILDER_ADDRESS).unwrap_or_else(|| cfg.builder_address.clone());
+        let acknowledged = cfg.builder_fee_acknowledged
+            || env(ENV_BUILDER_FEE_ACK).is_some_and(|v| matches!(v.trim(), "1" | "true" | "yes"));
+        Self::resolve(bps, address.trim(), acknowledged)
+    }
+
+    fn resolve(bps: u16, address: &str, acknowledged: bool) -> Option<Self> {
+        let default = Self::default();
+        if bps == default.f && address.eq_ignore_ascii_case(&default.b) {
+            return Some(default);
+        }
+        if !acknowledged {
+            warn!(
+                bps,
+                address, "builder fee override not acknowledged; using the default fee"
+            );
+            return Some(default);
+        }
+        if bps == 0 {
+            return None;
+        }
+        if !is_evm_address(address) {
+            warn!(address, "invalid builder address; using the default fee");
+            return Some(default);
+        }
+        Some(Self {
+            b: address.to_string(),
+            f: bps,
+        })
+    }
+
+    /// Attach `fee` to a signed `/exchange` request body; `None` leaves
+    /// the request without a builder.
+    pub fn inject(fee: Option<&Self>, request: &mut serde_json::Value) -> serde_json::Result<()> {
+        if let (Some(fee), Some(action)) = (fee, request.get_mut("action")) {
+            action["builder"] = serde_json::to_value(fee)?;
+        }
+        Ok(())
+    }
+}
+
+/// `0x` followed by 40 hex digits.
+pub fn is_evm_address(s: &str) -> bool {
+    s.len() == 42 && s.starts_with("0x") && s[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,4 +452,79 @@ mod tests {
         assert!(json.contains("\"b\""));
         assert!(json.contains("\"f\""));
     }
+
+    const SELF_HOSTED: &str = "0x1111111111111111111111111111111111111111";
+
+    #[test]
+    fn test_overrides_need_acknowledgement() {
+        assert_eq!(
+            BuilderFee::resolve(BUILDER_FEE_BPS, BUILDER_ADDRESS, false),
+            Some(BuilderFee::default())
+        );
+        assert_eq!(
+            BuilderFee::resolve(0, BUILDER_ADDRESS, false),
+            Some(BuilderFee::default())
+        );
+        assert_eq!(
+            BuilderFee::resolve(5, SELF_HOSTED, false),
+            Some(BuilderFee::default())
+        );
+
+        assert_eq!(BuilderFee::resolve(0, BUILDER_ADDRESS, true), None);
+        assert_eq!(
+            BuilderFee::resolve(5, SELF_HOSTED, true),
+            Some(BuilderFee {
+                b: SELF_HOSTED.into(),
+                f: 5
+            })
+        );
+        // A malformed address never reaches the exchange.
+        assert_eq!(
+            BuilderFee::resolve(5, "0x1234", true),
+            Some(BuilderFee::default())
+        );
+    }
+
+    #[test]
+    fn test_from_config_uses_configured_values() {
+        let cfg = HyperliquidConfig {
+            builder_fee_bps: 3,
+            builder_address: SELF_HOSTED.into(),
+            builder_fee_acknowledged: true,
+            ..HyperliquidConfig::default()
+        };
+        assert_eq!(
+            BuilderFee::from_config(&cfg),
+            Some(BuilderFee {
+                b: SELF_HOSTED.into(),
+                f: 3
+            })
+        );
+        assert_eq!(
+            BuilderFee::from_config(&HyperliquidConfig::default()),
+            Some(BuilderFee::default())
+        );
+    }
+
+    #[test]
+    fn test_injected_json_matches_configured_fee() {
+        let request = || serde_json::json!({"action": {"type": "order", "orders": []}, "nonce": 1});
+
+        let fee = BuilderFee::resolve(7, SELF_HOSTED, true);
+        let mut body = request();
+        BuilderFee::inject(fee.as_ref(), &mut body).unwrap();
+        assert_eq!(
+            body["action"]["builder"],
+            serde_json::json!({"b": SELF_HOSTED, "f": 7})
+        );
+
+        // Disabled: no builder key at all.
+        let mut body = request();
+        BuilderFee::inject(
+            BuilderFee::resolve(0, BUILDER_ADDRESS, true).as_ref(),
+            &mut body,
+        )
+        .unwrap();
+        assert!(body["action"].get("builder").is_none());
+    }
 }
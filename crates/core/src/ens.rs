@@ -0,0 +1,174 @@
+//! ENS names (`vitalik.eth`) resolved to addresses for transfers.
+//!
+//! Two `eth_call`s against Ethereum mainnet: the registry's
+//! `resolver(node)`, then that resolver's `addr(node)`. Names are taken as
+//! lowercase ASCII; anything that would need full UTS-46 normalization is
+//! refused rather than guessed at, since a wrong guess hashes to a
+//! different name. A failed lookup is always an error — the caller must
+//! never fall back to the literal string.
+
+use alloy::primitives::{address, keccak256, Address, Bytes, B256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::types::TransactionRequest;
+
+use crate::config::AppConfig;
+use crate::constants::ATLAS_BACKEND_URL;
+use crate::error::{AtlasError, AtlasResult};
+
+/// ENS registry, the same address on mainnet since 2020.
+pub const ENS_REGISTRY: Address = address!("00000000000C2E074eC69A0dFb2997BA6C7d2e1e");
+
+/// `resolver(bytes32)` on the registry.
+const RESOLVER_SELECTOR: [u8; 4] = [0x01, 0x78, 0xb8, 0xbf];
+/// `addr(bytes32)` on a resolver.
+const ADDR_SELECTOR: [u8; 4] = [0x3b, 0x3b, 0x57, 0xde];
+
+/// Whether `destination` is meant as an ENS name rather than an address.
+pub fn is_name(destination: &str) -> bool {
+    destination.trim().to_lowercase().ends_with(".eth")
+}
+
+/// `name` lowercased and checked: dot-separated, non-empty labels of ASCII
+/// letters, digits, `-` and `_`.
+pub fn normalize(name: &str) -> AtlasResult<String> {
+    let name = name.trim().to_lowercase();
+    let valid = name.split('.').all(|label| {
+        !label.is_empty()
+            && label
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    });
+    if !valid {
+        return Err(AtlasError::EnsResolution(format!(
+            "'{name}' is not a supported ENS name (ASCII letters, digits, '-' and '_' only)"
+        )));
+    }
+    Ok(name)
+}
+
+/// EIP-137 namehash of an already normalized name.
+pub fn namehash(name: &str) -> B256 {
+    name.rsplit('.')
+        .filter(|label| !label.is_empty())
+        .fold(B256::ZERO, |node, label| {
+            let mut buf = [0u8; 64];
+            buf[..32].copy_from_slice(node.as_slice());
+            buf[32..].copy_from_slice(keccak256(label.as_bytes()).as_slice());
+            keccak256(buf)
+        })
+}
+
+/// RPC used for lookups: `security.ens_rpc_url`, else the Atlas backend
+/// proxy for Ethereum (the same one the 0x module uses).
+pub fn rpc_url(config: &AppConfig) -> AtlasResult<String> {
+    if let Some(url) = &config.security.ens_rpc_url {
+        return Ok(url.clone());
+    }
+    match &config.system.api_key {
+        Some(key) => Ok(format!(
+            "{ATLAS_BACKEND_URL}/atlas-os/rpc/v2/{key}/ethereum"
+        )),
+        None => Err(AtlasError::EnsResolution(
+            "no Ethereum RPC: set security.ens_rpc_url or an Atlas API key".into(),
+        )),
+    }
+}
+
+/// The address `name` resolves to on Ethereum mainnet.
+pub async fn resolve(rpc_url: &str, name: &str) -> AtlasResult<Address> {
+    let name = normalize(name)?;
+    let url = rpc_url
+        .parse()
+        .map_err(|e| AtlasError::EnsResolution(format!("invalid RPC URL: {e}")))?;
+    let provider = ProviderBuilder::new().connect_http(url);
+    let node = namehash(&name);
+
+    let resolver = call_address(&provider, ENS_REGISTRY, RESOLVER_SELECTOR, node)
+        .await?
+        .ok_or_else(|| AtlasError::EnsResolution(format!("{name} has no resolver")))?;
+    call_address(&provider, resolver, ADDR_SELECTOR, node)
+        .await?
+        .ok_or_else(|| AtlasError::EnsResolution(format!("{name} has no address set")))
+}
+
+/// `to.<selector>(node)` decoded as an address; `None` for the zero address.
+async fn call_address(
+    provider: &impl Provider,
+    to: Address,
+    selector: [u8; 4],
+    node: B256,
+) -> AtlasResult<Option<Address>> {
+    let mut data = selector.to_vec();
+    data.extend_from_slice(node.as_slice());
+    let tx = TransactionRequest::default()
+        .to(to)
+        .input(Bytes::from(data).into());
+    let out = provider
+        .call(tx)
+        .await
+        .map_err(|e| AtlasError::EnsResolution(format!("eth_call to {to}: {e}")))?;
+    decode_address(&out)
+}
+
+/// An ABI-encoded `address` return value.
+fn decode_address(out: &[u8]) -> AtlasResult<Option<Address>> {
+    if out.len() < 32 {
+        return Err(AtlasError::EnsResolution(format!(
+            "unexpected {}-byte eth_call result",
+            out.len()
+        )));
+    }
+    let address = Address::from_slice(&out[12..32]);
+    Ok((!address.is_zero()).then_some(address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::b256;
+
+    #[test]
+    fn test_namehash_eip137_vectors() {
+        assert_eq!(namehash(""), B256::ZERO);
+        assert_eq!(
+            namehash("eth"),
+            b256!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            b256!("de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+        );
+    }
+
+    #[test]
+    fn test_name_detection_and_normalization() {
+        assert!(is_name("Vitalik.ETH"));
+        assert!(!is_name("0xab5801a7d398351b8be11c439e05c5b3259aec9b"));
+        assert_eq!(normalize(" Vitalik.ETH ").unwrap(), "vitalik.eth");
+        assert!(normalize("vitalik..eth").is_err());
+        assert!(normalize("vitalïk.eth").is_err());
+    }
+
+    #[test]
+    fn test_decode_address() {
+        let mut word = [0u8; 32];
+        assert_eq!(decode_address(&word).unwrap(), None);
+        word[31] = 1;
+        assert_eq!(
+            decode_address(&word).unwrap(),
+            Some(address!("0000000000000000000000000000000000000001"))
+        );
+        assert!(decode_address(&[0u8; 4]).is_err());
+    }
+
+    #[test]
+    fn test_rpc_url_prefers_config() {
+        let mut config = AppConfig::default();
+        config.system.api_key = None;
+        assert!(rpc_url(&config).is_err());
+        config.system.api_key = Some("k".into());
+        assert!(rpc_url(&config).unwrap().ends_with("/rpc/v2/k/ethereum"));
+        config.security.ens_rpc_url = Some("https://eth.example".into());
+        assert_eq!(rpc_url(&config).unwrap(), "https://eth.example");
+    }
+}
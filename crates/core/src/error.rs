@@ -30,6 +30,7 @@ use thiserror::Error;
 /// - `1`: user error (auth, config, validation)
 /// - `2`: network error
 /// - `3`: system error
+/// - `4`: insufficient margin (see [`AtlasError::exit_code`])
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ErrorCategory {
@@ -66,6 +67,100 @@ pub struct ErrorDetail {
     pub hints: Vec<String>,
 }
 
+/// Why the exchange rejected an order, parsed from its error text.
+///
+/// Lets agents branch on `error.code` instead of matching message strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeRejectionKind {
+    InsufficientMargin,
+    PostOnlyWouldCross,
+    ReduceOnlyViolation,
+    PriceOutOfBand,
+    MinSize,
+    NoImmediateMatch,
+    AlreadyFilled,
+    Unknown,
+}
+
+impl ExchangeRejectionKind {
+    /// Classify a raw exchange rejection message.
+    pub fn from_message(msg: &str) -> Self {
+        let lower = msg.to_lowercase();
+        if lower.contains("insufficient margin") || lower.contains("insufficient balance") {
+            Self::InsufficientMargin
+        } else if lower.contains("post only") || lower.contains("post-only") {
+            Self::PostOnlyWouldCross
+        } else if lower.contains("reduce only") || lower.contains("reduce-only") {
+            Self::ReduceOnlyViolation
+        } else if lower.contains("minimum value") || lower.contains("min size") {
+            Self::MinSize
+        } else if lower.contains("away from the reference price")
+            || lower.contains("price too far")
+            || lower.contains("invalid price")
+        {
+            Self::PriceOutOfBand
+        } else if lower.contains("could not immediately match") {
+            Self::NoImmediateMatch
+        } else if lower.contains("canceled or filled")
+            || lower.contains("canceled, or filled")
+            || lower.contains("already filled")
+        {
+            Self::AlreadyFilled
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Stable error code for this rejection.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::InsufficientMargin => "INSUFFICIENT_MARGIN",
+            Self::PostOnlyWouldCross => "POST_ONLY_WOULD_CROSS",
+            Self::ReduceOnlyViolation => "REDUCE_ONLY_VIOLATION",
+            Self::PriceOutOfBand => "PRICE_OUT_OF_BAND",
+            Self::MinSize => "MIN_SIZE",
+            Self::NoImmediateMatch => "NO_IMMEDIATE_MATCH",
+            Self::AlreadyFilled => "ORDER_ALREADY_FILLED",
+            Self::Unknown => "ORDER_REJECTED",
+        }
+    }
+
+    fn hints(self) -> Vec<String> {
+        let hints: &[&str] = match self {
+            Self::InsufficientMargin => &[
+                "Reduce position size",
+                "Add margin with: atlas hl perp margin add <coin> <amount>",
+            ],
+            Self::PostOnlyWouldCross => &[
+                "Move the limit price away from the touch",
+                "Or drop post-only to allow taking liquidity",
+            ],
+            Self::ReduceOnlyViolation => &[
+                "Reduce-only orders must shrink an open position",
+                "Check open positions: atlas hl perp positions --output json",
+            ],
+            Self::PriceOutOfBand => &[
+                "Limit price is too far from the mark — move it closer",
+                "Check the mark: atlas market hyperliquid info <coin> --output json",
+            ],
+            Self::MinSize => {
+                &["Increase the order size — Hyperliquid requires $10 minimum notional"]
+            }
+            Self::NoImmediateMatch => &[
+                "No liquidity within the price band — increase --slippage",
+                "Or place a resting limit order instead",
+            ],
+            Self::AlreadyFilled => &[
+                "The order is no longer resting — it filled or was cancelled",
+                "Check fills: atlas hl perp fills --output json",
+            ],
+            Self::Unknown => &["Check order parameters and account state"],
+        };
+        hints.iter().map(|h| h.to_string()).collect()
+    }
+}
+
 /// Top-level error type for all Atlas OS operations.
 ///
 /// Each variant maps to a specific error code, category, and recovery info.
@@ -82,6 +177,15 @@ pub enum AtlasError {
     #[error("API key missing")]
     ApiKeyMissing,
 
+    /// The Atlas backend answered 401/403 to the configured API key.
+    #[error("API key rejected: {0}")]
+    ApiKeyRejected(String),
+
+    /// The Atlas backend answered 403 `SCOPE_MISSING`: the key is valid but
+    /// lacks the scope (`read`, `trade-analytics`, `config-sync`) the route needs.
+    #[error("API key lacks the '{0}' scope")]
+    ApiKeyScopeMissing(String),
+
     #[error("Authentication error: {0}")]
     Auth(String),
 
@@ -98,6 +202,11 @@ pub enum AtlasError {
     #[error("Configuration error: {0}")]
     Config(String),
 
+    /// Signing or account access while Hyperliquid data is routed through
+    /// the Atlas backend (`data_via_backend`).
+    #[error("Proxy-only mode: {0}")]
+    ProxyOnly(String),
+
     // ── Execution ────────────────────────────────────────────────────
     #[error("Slippage exceeded: {0}")]
     SlippageExceeded(String),
@@ -108,8 +217,11 @@ pub enum AtlasError {
     #[error("Position not found: {0}")]
     PositionNotFound(String),
 
-    #[error("Order rejected: {0}")]
-    OrderRejected(String),
+    #[error("Order rejected: {message}")]
+    OrderRejected {
+        kind: ExchangeRejectionKind,
+        message: String,
+    },
 
     #[error("Insufficient balance: {0}")]
     InsufficientBalance(String),
@@ -117,6 +229,35 @@ pub enum AtlasError {
     #[error("Protocol error ({protocol}): {message}")]
     Protocol { protocol: String, message: String },
 
+    #[error("No liquidity: {0}")]
+    NoLiquidity(String),
+
+    #[error("Risk limit '{limit}' exceeded: {value:.2} > {max:.2}")]
+    RiskBlocked { limit: String, value: f64, max: f64 },
+
+    #[error("Slippage {:.2}% exceeds max_slippage {:.2}%", .slippage * 100.0, .max * 100.0)]
+    SlippageTooHigh { slippage: f64, max: f64 },
+
+    #[error(
+        "{coin} limit price {limit_price} is {:.2}% from the last 1m close {reference} (price band {:.2}%)",
+        .deviation * 100.0,
+        .max * 100.0
+    )]
+    PriceBandExceeded {
+        coin: String,
+        limit_price: f64,
+        reference: f64,
+        deviation: f64,
+        max: f64,
+    },
+
+    #[error("{coin} market order would move the book {impact_bps:.1} bps (max {max_bps} bps)")]
+    ImpactTooHigh {
+        coin: String,
+        impact_bps: f64,
+        max_bps: f64,
+    },
+
     // ── Network ─────────────────────────────────────────────────────
     #[error("Backend unreachable: {0}")]
     BackendUnreachable(String),
@@ -134,19 +275,75 @@ pub enum AtlasError {
     #[error("Invalid size: {0}")]
     InvalidSize(String),
 
+    /// A trigger price on the wrong side of the market for the order.
+    #[error("Invalid trigger: {0}")]
+    InvalidTrigger(String),
+
     #[error("Invalid ticker: {0}")]
     InvalidTicker(String),
 
+    #[error("Invalid indicator: {0}")]
+    InvalidIndicator(String),
+
+    #[error("Invalid timeframe: {0}")]
+    InvalidTimeframe(String),
+
     #[error("Unsupported chain: {0}")]
     UnsupportedChain(String),
 
-    #[error("Asset not found: {0}")]
-    AssetNotFound(String),
+    /// A `--from` / `--to` / `--tz` value that does not parse, or an empty range.
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    #[error("Asset not found: {symbol}{}", did_you_mean(.suggestions))]
+    AssetNotFound {
+        symbol: String,
+        /// Nearest listed names, best first.
+        suggestions: Vec<String>,
+    },
+
+    #[error("Unknown token on {chain}: {name}{}", did_you_mean(.suggestions))]
+    UnknownToken {
+        name: String,
+        chain: String,
+        /// Nearest address book names on `chain`, best first.
+        suggestions: Vec<String>,
+        /// Other chains where the address book has this name.
+        other_chains: Vec<String>,
+    },
+
+    #[error("Confirmation required: {0}")]
+    ConfirmationRequired(String),
+
+    /// A transfer to an address outside the enabled whitelist.
+    #[error("Destination not whitelisted: {0}")]
+    DestinationNotWhitelisted(String),
+
+    /// A `.eth` destination that could not be resolved to an address.
+    #[error("ENS resolution failed: {0}")]
+    EnsResolution(String),
+
+    /// The local cache does not reach back far enough for a report.
+    #[error("History not synced: {0}")]
+    HistoryNotSynced(String),
 
     // ── System ───────────────────────────────────────────────────────
     #[error("Database error: {0}")]
     Database(String),
 
+    /// A signed action could not be recorded in the audit log, so it was
+    /// not sent.
+    #[error("Audit log unavailable: {0}")]
+    AuditLog(String),
+
+    /// Free space fell below the recording's minimum.
+    #[error("Disk space low: {0}")]
+    DiskSpaceLow(String),
+
+    /// A stream recording no longer matches its manifest.
+    #[error("Recording invalid: {0}")]
+    RecordingInvalid(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 
@@ -155,6 +352,23 @@ pub enum AtlasError {
 }
 
 impl AtlasError {
+    /// Not-found error with no suggestions.
+    pub fn asset_not_found(symbol: impl Into<String>) -> Self {
+        AtlasError::AssetNotFound {
+            symbol: symbol.into(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Build an `OrderRejected` from raw exchange text, classifying the cause.
+    pub fn order_rejected(message: impl Into<String>) -> Self {
+        let message = message.into();
+        AtlasError::OrderRejected {
+            kind: ExchangeRejectionKind::from_message(&message),
+            message,
+        }
+    }
+
     /// Get the structured error detail for JSON output.
     pub fn detail(&self) -> ErrorDetail {
         match self {
@@ -180,7 +394,27 @@ impl AtlasError {
                 recoverable: true,
                 hints: vec![
                     "Run: atlas configure system api-key <key>".into(),
-                    "Get key from apps/frontend → Settings → API Keys".into(),
+                    "Get a key from the Atlas dashboard → Settings → API Keys".into(),
+                ],
+            },
+            AtlasError::ApiKeyRejected(msg) => ErrorDetail {
+                code: "API_KEY_REJECTED".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Auth,
+                recoverable: true,
+                hints: vec![
+                    "The key may be revoked or mistyped — create a new one in the Atlas dashboard → Settings → API Keys".into(),
+                    "Then: atlas configure system api-key <key>".into(),
+                ],
+            },
+            AtlasError::ApiKeyScopeMissing(scope) => ErrorDetail {
+                code: "API_KEY_SCOPE_MISSING".into(),
+                message: self.to_string(),
+                category: ErrorCategory::Auth,
+                recoverable: true,
+                hints: vec![
+                    format!("Create a key with the '{scope}' scope in the Atlas dashboard → Settings → API Keys"),
+                    "Then: atlas configure system api-key <key>".into(),
                 ],
             },
             AtlasError::Auth(msg) => ErrorDetail {
@@ -220,6 +454,16 @@ impl AtlasError {
                 recoverable: true,
                 hints: vec![],
             },
+            AtlasError::ProxyOnly(msg) => ErrorDetail {
+                code: "PROXY_ONLY_MODE".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Config,
+                recoverable: true,
+                hints: vec![
+                    "Orders and account queries always go to Hyperliquid directly".into(),
+                    "Run: atlas configure module set hyperliquid data_via_backend false".into(),
+                ],
+            },
 
             // Execution
             AtlasError::SlippageExceeded(msg) => ErrorDetail {
@@ -249,13 +493,24 @@ impl AtlasError {
                 recoverable: false,
                 hints: vec!["Check open positions: atlas hl perp positions --output json".into()],
             },
-            AtlasError::OrderRejected(msg) => ErrorDetail {
-                code: "ORDER_REJECTED".into(),
-                message: msg.clone(),
-                category: ErrorCategory::Execution,
-                recoverable: true,
-                hints: vec!["Check order parameters and account state".into()],
-            },
+            AtlasError::OrderRejected { kind, message } => {
+                let mut hints = kind.hints();
+                if *kind == ExchangeRejectionKind::PostOnlyWouldCross {
+                    if let Some(bbo) = post_only_bbo(message) {
+                        hints.insert(
+                            0,
+                            format!("Best opposite price was {bbo}: rest a buy below it or a sell above it"),
+                        );
+                    }
+                }
+                ErrorDetail {
+                    code: kind.code().into(),
+                    message: message.clone(),
+                    category: ErrorCategory::Execution,
+                    recoverable: true,
+                    hints,
+                }
+            }
             AtlasError::InsufficientBalance(msg) => ErrorDetail {
                 code: "INSUFFICIENT_BALANCE".into(),
                 message: msg.clone(),
@@ -270,6 +525,65 @@ impl AtlasError {
                 recoverable: true,
                 hints: vec![],
             },
+            AtlasError::NoLiquidity(msg) => ErrorDetail {
+                code: "NO_LIQUIDITY".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Execution,
+                recoverable: true,
+                hints: vec![
+                    "Try a smaller amount or another chain (--chain)".into(),
+                    "Check both tokens exist on this chain: atlas address list --chain <chain>"
+                        .into(),
+                ],
+            },
+
+            AtlasError::RiskBlocked { limit, .. } => ErrorDetail {
+                code: "RISK_BLOCKED".into(),
+                message: self.to_string(),
+                category: ErrorCategory::Execution,
+                recoverable: true,
+                hints: vec![
+                    "Reduce the order size".into(),
+                    format!(
+                        "Adjust the limit: atlas configure trading {} <value>",
+                        limit.replace('_', "-")
+                    ),
+                    "Bypass once with --override-risk".into(),
+                ],
+            },
+            AtlasError::SlippageTooHigh { .. } => ErrorDetail {
+                code: "SLIPPAGE_TOO_HIGH".into(),
+                message: self.to_string(),
+                category: ErrorCategory::Execution,
+                recoverable: true,
+                hints: vec![
+                    "Lower --slippage".into(),
+                    "Raise the cap: atlas configure trading max-slippage <value>".into(),
+                    "Bypass once with --force".into(),
+                ],
+            },
+            AtlasError::PriceBandExceeded { .. } => ErrorDetail {
+                code: "PRICE_BAND_EXCEEDED".into(),
+                message: self.to_string(),
+                category: ErrorCategory::Execution,
+                recoverable: true,
+                hints: vec![
+                    "The mid price may come from a degraded feed — retry in a few seconds".into(),
+                    "Widen the band: atlas configure trading price-band <value>".into(),
+                    "Bypass once with --force".into(),
+                ],
+            },
+            AtlasError::ImpactTooHigh { .. } => ErrorDetail {
+                code: "IMPACT_TOO_HIGH".into(),
+                message: self.to_string(),
+                category: ErrorCategory::Execution,
+                recoverable: true,
+                hints: vec![
+                    "Reduce the order size or split it up".into(),
+                    "Use a limit order: atlas hl perp order <coin> <side> <size> <price>".into(),
+                    "Raise or drop --max-impact-bps".into(),
+                ],
+            },
 
             // Network
             AtlasError::BackendUnreachable(msg) => ErrorDetail {
@@ -278,7 +592,9 @@ impl AtlasError {
                 category: ErrorCategory::Network,
                 recoverable: true,
                 hints: vec![
-                    "Check network connectivity".into(),
+                    "Run: atlas doctor --output json — checks backend reachability and the API key"
+                        .into(),
+                    "Market commands can serve the last response with --cached-ok".into(),
                     "Retry in a few seconds".into(),
                 ],
             },
@@ -312,6 +628,16 @@ impl AtlasError {
                 recoverable: true,
                 hints: vec!["Size must be a positive number. Use: 200, 0.5eth, 10lots".into()],
             },
+            AtlasError::InvalidTrigger(msg) => ErrorDetail {
+                code: "INVALID_TRIGGER".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Validation,
+                recoverable: true,
+                hints: vec![
+                    "A buy stop triggers above the mid, a sell stop below it".into(),
+                    "Check the mid: atlas market hyperliquid price <coin>".into(),
+                ],
+            },
             AtlasError::InvalidTicker(msg) => ErrorDetail {
                 code: "INVALID_TICKER".into(),
                 message: msg.clone(),
@@ -321,6 +647,26 @@ impl AtlasError {
                     "List available markets: atlas market hyperliquid list --output json".into(),
                 ],
             },
+            AtlasError::InvalidIndicator(msg) => ErrorDetail {
+                code: "INVALID_INDICATOR".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Validation,
+                recoverable: true,
+                hints: vec![format!(
+                    "Supported: {}",
+                    crate::indicators::supported_list()
+                )],
+            },
+            AtlasError::InvalidTimeframe(msg) => ErrorDetail {
+                code: "INVALID_TIMEFRAME".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Validation,
+                recoverable: true,
+                hints: vec![format!(
+                    "Valid timeframes: {}",
+                    crate::timeframe::supported_list()
+                )],
+            },
             AtlasError::UnsupportedChain(msg) => ErrorDetail {
                 code: "UNSUPPORTED_CHAIN".into(),
                 message: msg.clone(),
@@ -328,15 +674,103 @@ impl AtlasError {
                 recoverable: true,
                 hints: vec!["Check supported chains: atlas 0x chains --output json".into()],
             },
-            AtlasError::AssetNotFound(msg) => ErrorDetail {
-                code: "ASSET_NOT_FOUND".into(),
+            AtlasError::InvalidDate(msg) => ErrorDetail {
+                code: "VALIDATION_ERROR".into(),
                 message: msg.clone(),
                 category: ErrorCategory::Validation,
                 recoverable: true,
                 hints: vec![
+                    format!("Accepted: {}", crate::date_range::ACCEPTED_FORMATS),
+                    "Dates and relative days use local time; override with --tz UTC or --tz +05:30"
+                        .into(),
+                ],
+            },
+            AtlasError::AssetNotFound { suggestions, .. } => {
+                let mut hints = Vec::new();
+                if !suggestions.is_empty() {
+                    hints.push(format!("Did you mean {}?", suggestions.join(", ")));
+                }
+                hints.push(
                     "Check available assets: atlas market hyperliquid list --output json".into(),
+                );
+                ErrorDetail {
+                    code: "ASSET_NOT_FOUND".into(),
+                    message: self.to_string(),
+                    category: ErrorCategory::Validation,
+                    recoverable: true,
+                    hints,
+                }
+            }
+            AtlasError::UnknownToken {
+                name,
+                chain,
+                suggestions,
+                other_chains,
+            } => {
+                let mut hints = Vec::new();
+                if !suggestions.is_empty() {
+                    hints.push(format!("Did you mean {}?", suggestions.join(", ")));
+                }
+                if !other_chains.is_empty() {
+                    hints.push(format!(
+                        "{name} is in the address book on {}",
+                        other_chains.join(", ")
+                    ));
+                }
+                hints.push(format!(
+                    "List known tokens: atlas address list --chain {chain}"
+                ));
+                hints.push(format!(
+                    "Or add it: atlas address add {name} <0x…> --chain {chain}"
+                ));
+                ErrorDetail {
+                    code: "UNKNOWN_TOKEN".into(),
+                    message: self.to_string(),
+                    category: ErrorCategory::Validation,
+                    recoverable: true,
+                    hints,
+                }
+            }
+
+            AtlasError::ConfirmationRequired(msg) => ErrorDetail {
+                code: "CONFIRMATION_REQUIRED".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Validation,
+                recoverable: true,
+                hints: vec![
+                    "Re-run with --yes to skip the confirmation".into(),
+                    "Or disable prompts: atlas configure trading confirm off".into(),
+                ],
+            },
+            AtlasError::DestinationNotWhitelisted(address) => ErrorDetail {
+                code: "DESTINATION_NOT_WHITELISTED".into(),
+                message: self.to_string(),
+                category: ErrorCategory::Validation,
+                recoverable: true,
+                hints: vec![
+                    format!(
+                        "Whitelist it: atlas configure security whitelist add {address} --label <name>"
+                    ),
+                    "List whitelisted addresses: atlas configure security whitelist list".into(),
                 ],
             },
+            AtlasError::EnsResolution(msg) => ErrorDetail {
+                code: "ENS_RESOLUTION_FAILED".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Validation,
+                recoverable: true,
+                hints: vec![
+                    "Check the name, or pass the 0x address instead".into(),
+                    "Set the Ethereum RPC used for lookups: atlas configure set security.ens_rpc_url <url>".into(),
+                ],
+            },
+            AtlasError::HistoryNotSynced(msg) => ErrorDetail {
+                code: "HISTORY_NOT_SYNCED".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Validation,
+                recoverable: true,
+                hints: vec!["Run: atlas hl sync --full".into()],
+            },
 
             // System
             AtlasError::Database(msg) => ErrorDetail {
@@ -346,6 +780,36 @@ impl AtlasError {
                 recoverable: false,
                 hints: vec!["Run: atlas doctor --fix".into()],
             },
+            AtlasError::AuditLog(msg) => ErrorDetail {
+                code: "AUDIT_LOG_FAILED".into(),
+                message: msg.clone(),
+                category: ErrorCategory::System,
+                recoverable: false,
+                hints: vec![
+                    "Nothing was sent to the exchange".into(),
+                    "Check free disk space and permissions on ~/.atlas-os/data, then: atlas doctor --fix".into(),
+                ],
+            },
+            AtlasError::DiskSpaceLow(msg) => ErrorDetail {
+                code: "DISK_SPACE_LOW".into(),
+                message: msg.clone(),
+                category: ErrorCategory::System,
+                recoverable: true,
+                hints: vec![
+                    "Free up space, or record elsewhere with --out".into(),
+                    "Lower the threshold with --min-free".into(),
+                ],
+            },
+            AtlasError::RecordingInvalid(msg) => ErrorDetail {
+                code: "RECORDING_INVALID".into(),
+                message: msg.clone(),
+                category: ErrorCategory::Validation,
+                recoverable: false,
+                hints: vec![
+                    "Segments were edited, truncated or removed after recording".into(),
+                    "A recorder killed without Ctrl+C leaves its last segment ahead of manifest.json".into(),
+                ],
+            },
             AtlasError::Internal(msg) => ErrorDetail {
                 code: "INTERNAL_ERROR".into(),
                 message: msg.clone(),
@@ -364,21 +828,93 @@ impl AtlasError {
     }
 
     /// PRD exit code: 0 success, 1 user error, 2 network, 3 system.
+    ///
+    /// Margin shortfalls exit with `4` so scripts can tell "fund the account"
+    /// apart from "fix the command".
     pub fn exit_code(&self) -> i32 {
-        self.detail().category.exit_code()
+        match self {
+            AtlasError::InsufficientMargin(_)
+            | AtlasError::OrderRejected {
+                kind: ExchangeRejectionKind::InsufficientMargin,
+                ..
+            } => 4,
+            _ => self.detail().category.exit_code(),
+        }
     }
 
     /// Serialize this error as the PRD-compliant JSON error envelope.
     pub fn to_json(&self) -> serde_json::Value {
-        serde_json::json!({
+        let mut json = serde_json::json!({
             "ok": false,
             "error": self.detail(),
-        })
+        });
+        // Risk refusals carry the exact limit and computed value for agents
+        if let AtlasError::RiskBlocked { limit, value, max } = self {
+            json["error"]["limit"] = serde_json::json!(limit);
+            json["error"]["value"] = serde_json::json!(value);
+            json["error"]["max"] = serde_json::json!(max);
+        }
+        if let AtlasError::SlippageTooHigh { slippage, max } = self {
+            json["error"]["slippage"] = serde_json::json!(slippage);
+            json["error"]["max"] = serde_json::json!(max);
+        }
+        if let AtlasError::PriceBandExceeded {
+            limit_price,
+            reference,
+            deviation,
+            max,
+            ..
+        } = self
+        {
+            json["error"]["limit_price"] = serde_json::json!(limit_price);
+            json["error"]["reference"] = serde_json::json!(reference);
+            json["error"]["deviation"] = serde_json::json!(deviation);
+            json["error"]["max"] = serde_json::json!(max);
+        }
+        if let AtlasError::ImpactTooHigh {
+            impact_bps,
+            max_bps,
+            ..
+        } = self
+        {
+            json["error"]["impact_bps"] = serde_json::json!(impact_bps);
+            json["error"]["max"] = serde_json::json!(max_bps);
+        }
+        if let AtlasError::OrderRejected { kind, message } = self {
+            json["error"]["rejection"] = serde_json::json!(kind);
+            if let Some(bbo) = post_only_bbo(message) {
+                json["error"]["bbo"] = serde_json::json!(bbo);
+            }
+        }
+        if let AtlasError::AssetNotFound { suggestions, .. }
+        | AtlasError::UnknownToken { suggestions, .. } = self
+        {
+            json["error"]["suggestions"] = serde_json::json!(suggestions);
+        }
+        json
     }
 }
 
 pub type AtlasResult<T> = Result<T, AtlasError>;
 
+/// The touch price from a post-only rejection: `…, bbo was 3000.1. asset=1`.
+fn post_only_bbo(message: &str) -> Option<&str> {
+    let rest = &message[message.find("bbo was ")? + "bbo was ".len()..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rest.len());
+    Some(rest[..end].trim_end_matches('.')).filter(|px| !px.is_empty())
+}
+
+/// ` (did you mean ETH, ETHFI?)`, or nothing without suggestions.
+fn did_you_mean(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(" (did you mean {}?)", suggestions.join(", "))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -433,6 +969,222 @@ mod tests {
         assert!(detail.message.contains("hyperliquid"));
     }
 
+    #[test]
+    fn test_confirmation_required_detail() {
+        let err = AtlasError::ConfirmationRequired("hl perp buy ETH".into());
+        let json = err.to_json();
+        assert_eq!(json["error"]["code"], "CONFIRMATION_REQUIRED");
+        assert_eq!(json["error"]["category"], "validation");
+        assert_eq!(err.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_risk_blocked_json_has_limit() {
+        let err = AtlasError::RiskBlocked {
+            limit: "max_notional".into(),
+            value: 75000.0,
+            max: 50000.0,
+        };
+        let json = err.to_json();
+        assert_eq!(json["error"]["code"], "RISK_BLOCKED");
+        assert_eq!(json["error"]["limit"], "max_notional");
+        assert_eq!(json["error"]["value"], 75000.0);
+        assert_eq!(json["error"]["max"], 50000.0);
+        assert!(err.to_string().contains("75000.00 > 50000.00"));
+    }
+
+    #[test]
+    fn test_order_protection_json_has_values() {
+        let err = AtlasError::SlippageTooHigh {
+            slippage: 0.2,
+            max: 0.1,
+        };
+        let json = err.to_json();
+        assert_eq!(json["error"]["code"], "SLIPPAGE_TOO_HIGH");
+        assert_eq!(json["error"]["slippage"], 0.2);
+        assert!(err
+            .to_string()
+            .contains("20.00% exceeds max_slippage 10.00%"));
+
+        let err = AtlasError::PriceBandExceeded {
+            coin: "ETH".into(),
+            limit_price: 3600.0,
+            reference: 3000.0,
+            deviation: 0.2,
+            max: 0.15,
+        };
+        let json = err.to_json();
+        assert_eq!(json["error"]["code"], "PRICE_BAND_EXCEEDED");
+        assert_eq!(json["error"]["limit_price"], 3600.0);
+        assert_eq!(json["error"]["reference"], 3000.0);
+        assert_eq!(json["error"]["max"], 0.15);
+    }
+
+    #[test]
+    fn test_rejection_kind_from_exchange_text() {
+        use ExchangeRejectionKind::*;
+        let cases = [
+            (
+                "Insufficient margin to place order. asset=0",
+                InsufficientMargin,
+            ),
+            (
+                "Post only order would have immediately matched, bbo was 3000.1. asset=1",
+                PostOnlyWouldCross,
+            ),
+            (
+                "Reduce only order would increase position. asset=0",
+                ReduceOnlyViolation,
+            ),
+            (
+                "Order price cannot be more than 80% away from the reference price",
+                PriceOutOfBand,
+            ),
+            ("Order must have minimum value of $10. asset=4", MinSize),
+            (
+                "Order could not immediately match against any resting orders. asset=0",
+                NoImmediateMatch,
+            ),
+            ("Cannot modify canceled or filled order", AlreadyFilled),
+            (
+                "Order was never placed, already canceled, or filled. asset=0",
+                AlreadyFilled,
+            ),
+            ("Something new", Unknown),
+        ];
+        for (msg, kind) in cases {
+            assert_eq!(ExchangeRejectionKind::from_message(msg), kind, "{msg}");
+        }
+    }
+
+    #[test]
+    fn test_order_rejected_json_shape_per_kind() {
+        let cases = [
+            (
+                "Insufficient margin to place order.",
+                "INSUFFICIENT_MARGIN",
+                "insufficient_margin",
+            ),
+            (
+                "Post only order would have immediately matched",
+                "POST_ONLY_WOULD_CROSS",
+                "post_only_would_cross",
+            ),
+            (
+                "Reduce only order would increase position.",
+                "REDUCE_ONLY_VIOLATION",
+                "reduce_only_violation",
+            ),
+            (
+                "Order price cannot be more than 80% away from the reference price",
+                "PRICE_OUT_OF_BAND",
+                "price_out_of_band",
+            ),
+            (
+                "Order must have minimum value of $10.",
+                "MIN_SIZE",
+                "min_size",
+            ),
+            (
+                "Order could not immediately match against any resting orders.",
+                "NO_IMMEDIATE_MATCH",
+                "no_immediate_match",
+            ),
+            (
+                "Cannot modify canceled or filled order",
+                "ORDER_ALREADY_FILLED",
+                "already_filled",
+            ),
+            ("Something new", "ORDER_REJECTED", "unknown"),
+        ];
+        for (msg, code, rejection) in cases {
+            let json = AtlasError::order_rejected(msg).to_json();
+            assert_eq!(json["ok"], false);
+            assert_eq!(json["error"]["code"], code);
+            assert_eq!(json["error"]["rejection"], rejection);
+            assert_eq!(json["error"]["message"], msg);
+            assert_eq!(json["error"]["category"], "execution");
+            assert_eq!(json["error"]["recoverable"], true);
+            assert!(!json["error"]["hints"].as_array().unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_post_only_rejection_carries_bbo() {
+        let err = AtlasError::order_rejected(
+            "Post only order would have immediately matched, bbo was 3000.1. asset=1",
+        );
+        let detail = err.detail();
+        assert_eq!(detail.code, "POST_ONLY_WOULD_CROSS");
+        assert!(detail.hints[0].contains("Best opposite price was 3000.1"));
+        assert_eq!(err.to_json()["error"]["bbo"], "3000.1");
+
+        // No price in the message: generic hints only.
+        let err = AtlasError::order_rejected("Post only order would have immediately matched");
+        assert!(err.detail().hints[0].starts_with("Move the limit price"));
+        assert!(err.to_json()["error"].get("bbo").is_none());
+    }
+
+    #[test]
+    fn test_margin_rejection_exit_code() {
+        assert_eq!(
+            AtlasError::order_rejected("Insufficient margin to place order.").exit_code(),
+            4
+        );
+        assert_eq!(
+            AtlasError::InsufficientMargin("short".into()).exit_code(),
+            4
+        );
+        assert_eq!(
+            AtlasError::order_rejected("Order must have minimum value of $10.").exit_code(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_unknown_token_hints() {
+        let err = AtlasError::UnknownToken {
+            name: "USDT".into(),
+            chain: "base".into(),
+            suggestions: vec!["USDC".into()],
+            other_chains: vec!["ethereum".into()],
+        };
+        assert_eq!(
+            err.to_string(),
+            "Unknown token on base: USDT (did you mean USDC?)"
+        );
+        let json = err.to_json();
+        assert_eq!(json["error"]["code"], "UNKNOWN_TOKEN");
+        assert_eq!(json["error"]["suggestions"][0], "USDC");
+        let hints = err.detail().hints;
+        assert_eq!(hints[1], "USDT is in the address book on ethereum");
+        assert!(hints[3].contains("atlas address add USDT"));
+    }
+
+    #[test]
+    fn test_no_liquidity_is_recoverable() {
+        let err = AtlasError::NoLiquidity("USDC -> WETH on base".into());
+        let json = err.to_json();
+        assert_eq!(json["error"]["code"], "NO_LIQUIDITY");
+        assert_eq!(json["error"]["category"], "execution");
+        assert_eq!(json["error"]["recoverable"], true);
+    }
+
+    #[test]
+    fn test_invalid_date_is_validation_error() {
+        let json = AtlasError::InvalidDate("--from 'last tuesday'".into()).to_json();
+        assert_eq!(json["error"]["code"], "VALIDATION_ERROR");
+        assert_eq!(json["error"]["category"], "validation");
+        assert!(json["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("last tuesday"));
+        assert!(json["error"]["hints"][0]
+            .as_str()
+            .unwrap()
+            .contains("month-to-date"));
+    }
+
     #[test]
     fn test_all_categories_have_exit_codes() {
         // Auth/Config/Validation → 1
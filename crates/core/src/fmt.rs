@@ -1,5 +1,9 @@
 //! Formatting utilities shared across CLI, TUI, and core.
 
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::config::{Decimals, OutputConfig};
+
 /// Format a millisecond timestamp to human-readable UTC string.
 /// Uses the Howard Hinnant algorithm — no chrono dependency.
 pub fn format_timestamp_ms(ms: u64) -> String {
@@ -25,6 +29,18 @@ pub fn format_timestamp_ms(ms: u64) -> String {
     format!("{y:04}-{m:02}-{d:02} {hours:02}:{minutes:02}:{seconds:02}")
 }
 
+/// Compact countdown: `45s`, `42m 05s`, `3h 07m`.
+pub fn format_countdown(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{h}h {m:02}m")
+    } else if m > 0 {
+        format!("{m}m {s:02}s")
+    } else {
+        format!("{s}s")
+    }
+}
+
 /// Convert universal OrderResult to CLI OrderResultOutput.
 pub fn order_result_to_output(r: &crate::types::OrderResult) -> crate::output::OrderResultOutput {
     crate::output::OrderResultOutput {
@@ -40,9 +56,16 @@ pub fn order_result_to_output(r: &crate::types::OrderResult) -> crate::output::O
         avg_px: r.avg_price.map(|p| p.to_string()),
         filled: r.filled_size.map(|s| s.to_string()),
         fee: r.fee.map(|f| f.to_string()),
-        builder_fee_bps: crate::constants::BUILDER_FEE_BPS as u32,
+        builder_fee_bps: r.builder_fee_bps.map_or(0, u32::from),
         protocol: format!("{}", r.protocol),
         timestamp: r.timestamp,
+        tif: None,
+        trigger_price: None,
+        trigger_type: None,
+        cloid: None,
+        warnings: Vec::new(),
+        liquidity_warning: None,
+        audit_id: r.audit_id,
     }
 }
 
@@ -97,6 +120,18 @@ pub fn format_usd_full(s: &str) -> String {
     }
 }
 
+/// A decimal with exactly `dp` places, so figures from different modules
+/// line up (`12.5` and `12.500000` both become `12.50`). A value that
+/// rounds to zero prints without a sign.
+pub fn format_decimal(d: rust_decimal::Decimal, dp: u32) -> String {
+    let mut d = d.round_dp(dp);
+    if d.is_zero() {
+        d = rust_decimal::Decimal::ZERO;
+    }
+    d.rescale(dp);
+    d.to_string()
+}
+
 /// Format a decimal ratio as percentage (e.g. 0.05 → "5.00%").
 pub fn format_pct(s: &str) -> String {
     if s == "—" || s == "-" {
@@ -165,10 +200,593 @@ pub fn sign_of(s: &str) -> Sign {
     }
 }
 
+// ─── Numbers ────────────────────────────────────────────────────────
+
+/// Significant figures Hyperliquid allows in a price.
+const PRICE_SIG_FIGS: u32 = 5;
+
+/// Most decimals a perp price can have; a market's tick allows
+/// `6 - szDecimals`.
+pub const MAX_PERP_PRICE_DECIMALS: u32 = 6;
+
+/// The same for spot: `8 - szDecimals`.
+pub const MAX_SPOT_PRICE_DECIMALS: u32 = 8;
+
+/// Precision rules for numbers in table output, from `output.*` in
+/// atlas.json. Values are rounded half to even, never truncated; JSON
+/// output keeps full precision and never goes through this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub price: Decimals,
+    pub size: Decimals,
+    pub usd: u32,
+    pub pct: u32,
+    /// Group thousands with `,`.
+    pub thousands: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::from(&OutputConfig::default())
+    }
+}
+
+impl From<&OutputConfig> for NumberFormat {
+    fn from(config: &OutputConfig) -> Self {
+        Self {
+            price: config.price_decimals,
+            size: config.size_decimals,
+            usd: config.usd_decimals,
+            pct: config.pct_decimals,
+            thousands: config.thousands_separator,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// A price. `auto` keeps 5 significant figures, but no more than
+    /// `max_decimals` places (the market's tick, [`MAX_PERP_PRICE_DECIMALS`]
+    /// when unknown) and never drops integer digits.
+    pub fn price(&self, px: Decimal, max_decimals: Option<u32>) -> String {
+        let dp = match self.price {
+            Decimals::Fixed(dp) => dp,
+            Decimals::Auto => {
+                auto_price_decimals(px).min(max_decimals.unwrap_or(MAX_PERP_PRICE_DECIMALS))
+            }
+        };
+        self.fixed(px, dp)
+    }
+
+    /// A size. `auto` rounds to the asset's `sz_decimals`, or shows the
+    /// size as given when they are unknown.
+    pub fn size(&self, sz: Decimal, sz_decimals: Option<u32>) -> String {
+        match (self.size, sz_decimals) {
+            (Decimals::Fixed(dp), _) | (Decimals::Auto, Some(dp)) => self.fixed(sz, dp),
+            (Decimals::Auto, None) => self.group(sz.normalize().to_string()),
+        }
+    }
+
+    /// A USD value, without the `$`.
+    pub fn usd(&self, value: Decimal) -> String {
+        self.fixed(value, self.usd)
+    }
+
+    /// A value already in percent (`12.5` for 12.5%), without the `%`.
+    pub fn pct(&self, value: Decimal) -> String {
+        self.fixed(value, self.pct)
+    }
+
+    /// [`price`](Self::price) for a numeric string. Anything that doesn't
+    /// parse (a `—` placeholder) comes back unchanged, as with the other
+    /// `*_str` helpers.
+    pub fn price_str(&self, s: &str, max_decimals: Option<u32>) -> String {
+        parse_or_keep(s, |d| self.price(d, max_decimals))
+    }
+
+    pub fn size_str(&self, s: &str, sz_decimals: Option<u32>) -> String {
+        parse_or_keep(s, |d| self.size(d, sz_decimals))
+    }
+
+    pub fn usd_str(&self, s: &str) -> String {
+        parse_or_keep(s, |d| self.usd(d))
+    }
+
+    pub fn pct_str(&self, s: &str) -> String {
+        parse_or_keep(s, |d| self.pct(d))
+    }
+
+    fn fixed(&self, value: Decimal, dp: u32) -> String {
+        let mut d = value.round_dp_with_strategy(dp, RoundingStrategy::MidpointNearestEven);
+        if d.is_zero() {
+            d = Decimal::ZERO;
+        }
+        d.rescale(dp);
+        self.group(d.to_string())
+    }
+
+    fn group(&self, s: String) -> String {
+        if self.thousands {
+            group_thousands(&s)
+        } else {
+            s
+        }
+    }
+}
+
+fn parse_or_keep(s: &str, f: impl Fn(Decimal) -> String) -> String {
+    match s.trim().parse::<Decimal>() {
+        Ok(d) => f(d),
+        Err(_) => s.to_string(),
+    }
+}
+
+/// Places needed for 5 significant figures: none above 99,999, more the
+/// further a fraction sits below 1.
+fn auto_price_decimals(px: Decimal) -> u32 {
+    let abs = px.abs().normalize();
+    if abs.is_zero() {
+        return 0;
+    }
+    if abs >= Decimal::ONE {
+        let int_digits = abs.trunc().to_string().len() as u32;
+        return PRICE_SIG_FIGS.saturating_sub(int_digits);
+    }
+    let digits = abs.mantissa().to_string().len() as u32;
+    let leading_zeros = abs.scale().saturating_sub(digits);
+    leading_zeros + PRICE_SIG_FIGS
+}
+
+/// `-1234567.5` → `-1,234,567.5`.
+fn group_thousands(s: &str) -> String {
+    let (sign, rest) = match s.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", s),
+    };
+    let (int, frac) = match rest.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (rest, None),
+    };
+    let mut out = String::with_capacity(s.len() + int.len() / 3);
+    out.push_str(sign);
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    if let Some(frac) = frac {
+        out.push('.');
+        out.push_str(frac);
+    }
+    out
+}
+
+// ─── Tables ─────────────────────────────────────────────────────────
+
+/// Horizontal alignment of a table column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// ANSI color for a table cell. Only emitted when the table has color on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Green,
+    Red,
+    Yellow,
+    Dim,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Green => "32",
+            Color::Red => "31",
+            Color::Yellow => "33",
+            Color::Dim => "2",
+        }
+    }
+}
+
+/// One table cell: text plus an optional color.
+#[derive(Debug, Clone, Default)]
+pub struct Cell {
+    text: String,
+    color: Option<Color>,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Green when positive, red when negative — for PnL and changes.
+    pub fn signed(text: impl Into<String>) -> Self {
+        let cell = Self::new(text);
+        match sign_of(&cell.text) {
+            Sign::Positive => cell.color(Color::Green),
+            Sign::Negative => cell.color(Color::Red),
+            Sign::Zero => cell,
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&String> for Cell {
+    fn from(text: &String) -> Self {
+        Self::new(text.clone())
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Column {
+    header: String,
+    align: Align,
+    max_width: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum Row {
+    Cells(Vec<Cell>),
+    Separator,
+}
+
+/// Box-drawn table sized to its content.
+///
+/// Widths are measured in terminal columns (so `✓`, `→` and CJK symbols
+/// line up), columns with a `max_width` are cut with `…`, and the header
+/// row is skipped when every header is empty. With [`ascii`](Self::ascii)
+/// the borders become `+-|` and cell text goes through [`to_ascii`].
+///
+/// ```text
+/// ┌─────────────────────────┐
+/// │ POSITIONS               │
+/// ├───────┬─────────┬───────┤
+/// │ Coin  │    Size │  uPnL │
+/// ├───────┼─────────┼───────┤
+/// │ BTC   │     0.1 │ 12.50 │
+/// │ kPEPE │ 1000000 │  -3.2 │
+/// └───────┴─────────┴───────┘
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    title: Option<String>,
+    columns: Vec<Column>,
+    rows: Vec<Row>,
+    color: bool,
+    ascii: bool,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Label / value panel with a title, e.g. an account summary.
+    pub fn panel(title: impl Into<String>) -> Self {
+        Self::new()
+            .title(title)
+            .column("", Align::Left)
+            .column("", Align::Left)
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn column(mut self, header: impl Into<String>, align: Align) -> Self {
+        self.columns.push(Column {
+            header: header.into(),
+            align,
+            max_width: None,
+        });
+        self
+    }
+
+    /// Cap the most recently added column; longer cells end in `…`.
+    pub fn max_width(mut self, width: usize) -> Self {
+        if let Some(col) = self.columns.last_mut() {
+            col.max_width = Some(width.max(1));
+        }
+        self
+    }
+
+    /// Emit ANSI colors for colored cells.
+    pub fn color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+
+    /// Draw with ASCII only, for dumb terminals and CI logs.
+    pub fn ascii(mut self, enabled: bool) -> Self {
+        self.ascii = enabled;
+        self
+    }
+
+    pub fn row<I, C>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Cell>,
+    {
+        self.rows
+            .push(Row::Cells(cells.into_iter().map(Into::into).collect()));
+    }
+
+    /// One `label: value` row of a [`panel`](Self::panel).
+    pub fn kv(&mut self, label: &str, value: impl Into<Cell>) {
+        self.rows
+            .push(Row::Cells(vec![Cell::new(label), value.into()]));
+    }
+
+    /// Horizontal rule between row groups.
+    pub fn separator(&mut self) {
+        self.rows.push(Row::Separator);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.rows.iter().any(|r| matches!(r, Row::Cells(_)))
+    }
+
+    /// The whole table, one line per row, ending in a newline.
+    pub fn render(&self) -> String {
+        let n = self.columns.len();
+        if n == 0 {
+            return String::new();
+        }
+        let show_header = self.columns.iter().any(|c| !c.header.is_empty());
+        let text = |s: &str| {
+            if self.ascii {
+                to_ascii(s)
+            } else {
+                s.to_string()
+            }
+        };
+        let (h, v, ellipsis) = if self.ascii {
+            ("-", "|", "...")
+        } else {
+            ("─", "│", "…")
+        };
+        let corner = |fancy: &'static str| if self.ascii { "+" } else { fancy };
+        let title = self.title.as_deref().map(text);
+
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .map(|c| {
+                if show_header {
+                    display_width(&text(&c.header))
+                } else {
+                    0
+                }
+            })
+            .collect();
+        for row in &self.rows {
+            if let Row::Cells(cells) = row {
+                for (w, cell) in widths.iter_mut().zip(cells) {
+                    *w = (*w).max(display_width(&text(&cell.text)));
+                }
+            }
+        }
+        for (w, col) in widths.iter_mut().zip(&self.columns) {
+            if let Some(max) = col.max_width {
+                *w = (*w).min(max);
+            }
+            *w = (*w).max(1);
+        }
+        let inner = widths.iter().sum::<usize>() + 3 * (n - 1);
+        if let Some(title) = &title {
+            let needed = display_width(title);
+            if needed > inner {
+                widths[n - 1] += needed - inner;
+            }
+        }
+        let inner = widths.iter().sum::<usize>() + 3 * (n - 1);
+
+        let rule = |left: &'static str, mid: &'static str, right: &'static str| {
+            let segs: Vec<String> = widths.iter().map(|w| h.repeat(w + 2)).collect();
+            format!(
+                "{}{}{}\n",
+                corner(left),
+                segs.join(corner(mid)),
+                corner(right)
+            )
+        };
+        let line = |cells: &[Cell], header: bool| {
+            let parts: Vec<String> = self
+                .columns
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (col, &w))| {
+                    let empty = Cell::default();
+                    let cell = cells.get(i).unwrap_or(&empty);
+                    let shown = truncate_with(&text(&cell.text), w, ellipsis);
+                    let shown = pad(&shown, w, col.align);
+                    match cell.color {
+                        Some(c) if self.color && !header => {
+                            format!("\x1b[{}m{shown}\x1b[0m", c.code())
+                        }
+                        _ => shown,
+                    }
+                })
+                .collect();
+            format!("{v} {} {v}\n", parts.join(&format!(" {v} ")))
+        };
+
+        let has_body = show_header || !self.rows.is_empty();
+        let mut out = String::new();
+        match &title {
+            Some(title) => {
+                out.push_str(&format!(
+                    "{}{}{}\n",
+                    corner("┌"),
+                    h.repeat(inner + 2),
+                    corner("┐")
+                ));
+                out.push_str(&format!("{v} {} {v}\n", pad(title, inner, Align::Left)));
+                if has_body {
+                    out.push_str(&rule("├", "┬", "┤"));
+                }
+            }
+            None => out.push_str(&rule("┌", "┬", "┐")),
+        }
+        if show_header {
+            let headers: Vec<Cell> = self.columns.iter().map(|c| Cell::new(&c.header)).collect();
+            out.push_str(&line(&headers, true));
+            if !self.rows.is_empty() {
+                out.push_str(&rule("├", "┼", "┤"));
+            }
+        }
+        for row in &self.rows {
+            match row {
+                Row::Cells(cells) => out.push_str(&line(cells, false)),
+                Row::Separator => out.push_str(&rule("├", "┼", "┤")),
+            }
+        }
+        if title.is_some() && !has_body {
+            out.push_str(&format!(
+                "{}{}{}\n",
+                corner("└"),
+                h.repeat(inner + 2),
+                corner("┘")
+            ));
+        } else {
+            out.push_str(&rule("└", "┴", "┘"));
+        }
+        out
+    }
+}
+
+/// Whether tables printed to stdout should carry ANSI colors.
+pub fn color_enabled() -> bool {
+    std::io::IsTerminal::is_terminal(&std::io::stdout())
+}
+
+/// Terminal columns taken by `s` (wide CJK / emoji count as two).
+pub fn display_width(s: &str) -> usize {
+    unicode_width::UnicodeWidthStr::width(s)
+}
+
+/// Cut `s` to at most `max` terminal columns, ending in `…` if cut.
+pub fn truncate_width(s: &str, max: usize) -> String {
+    truncate_with(s, max, "…")
+}
+
+fn truncate_with(s: &str, max: usize, ellipsis: &str) -> String {
+    if display_width(s) <= max {
+        return s.to_string();
+    }
+    let budget = max.saturating_sub(display_width(ellipsis));
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in s.chars() {
+        let w = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        out.push(ch);
+        used += w;
+    }
+    out.push_str(ellipsis);
+    out
+}
+
+/// ASCII stand-in for `s`, for `--plain` output.
+///
+/// Box drawing, dashes, arrows and status marks map to their nearest ASCII
+/// (`─` → `-`, `→` → `->`, `✓` → `OK`); emoji are dropped along with the
+/// space after them, and any other non-ASCII character becomes `?`.
+pub fn to_ascii(s: &str) -> String {
+    if s.is_ascii() {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let mapped = match ch {
+            c if c.is_ascii() => {
+                out.push(c);
+                continue;
+            }
+            '─' | '━' | '═' | '—' | '–' | '−' => "-",
+            '│' | '┃' | '║' => "|",
+            '\u{2500}'..='\u{257F}' => "+",
+            '…' => "...",
+            '→' => "->",
+            '←' => "<-",
+            '↑' => "^",
+            '↓' => "v",
+            '≥' => ">=",
+            '≤' => "<=",
+            '≈' => "~",
+            '×' => "x",
+            '•' | '·' => "*",
+            '✓' | '✔' | '✅' => "OK",
+            '✗' | '✘' | '❌' => "X",
+            '⚠' => "!",
+            '\u{2600}'..='\u{27BF}' | '\u{1F000}'..='\u{1FAFF}' | '\u{FE0F}' | '\u{200D}' => {
+                // Pictographs: drop them and the space that followed
+                if chars.peek() == Some(&' ') && (out.is_empty() || out.ends_with(' ')) {
+                    chars.next();
+                }
+                ""
+            }
+            _ => "?",
+        };
+        out.push_str(mapped);
+    }
+    out
+}
+
+/// Pad `s` to `width` terminal columns.
+fn pad(s: &str, width: usize, align: Align) -> String {
+    let fill = width.saturating_sub(display_width(s));
+    match align {
+        Align::Left => format!("{s}{}", " ".repeat(fill)),
+        Align::Right => format!("{}{s}", " ".repeat(fill)),
+        Align::Center => {
+            let left = fill / 2;
+            format!("{}{s}{}", " ".repeat(left), " ".repeat(fill - left))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(45), "45s");
+        assert_eq!(format_countdown(42 * 60 + 5), "42m 05s");
+        assert_eq!(format_countdown(3 * 3600 + 7 * 60 + 59), "3h 07m");
+        assert_eq!(format_countdown(3600), "1h 00m");
+    }
+
     #[test]
     fn test_truncate_number_large() {
         assert_eq!(truncate_number("1234567.89"), "1234568");
@@ -235,6 +853,16 @@ mod tests {
         assert_eq!(format_usd_full("—"), "—");
     }
 
+    #[test]
+    fn test_format_decimal() {
+        let d = |s: &str| s.parse::<rust_decimal::Decimal>().unwrap();
+        assert_eq!(format_decimal(d("12.5"), 2), "12.50");
+        assert_eq!(format_decimal(d("12.500000"), 2), "12.50");
+        assert_eq!(format_decimal(d("-3.14159"), 2), "-3.14");
+        assert_eq!(format_decimal(d("-0.001"), 2), "0.00");
+        assert_eq!(format_decimal(d("7"), 0), "7");
+    }
+
     #[test]
     fn test_format_pct_positive() {
         assert_eq!(format_pct("0.05"), "5.00%");
@@ -309,4 +937,214 @@ mod tests {
         assert_eq!(sign_of("0.00"), Sign::Zero);
         assert_eq!(sign_of("—"), Sign::Zero);
     }
+
+    #[test]
+    fn test_table_snapshot() {
+        let mut t = Table::new()
+            .title("POSITIONS")
+            .column("Coin", Align::Left)
+            .column("Size", Align::Right)
+            .column("uPnL", Align::Right);
+        t.row(["BTC", "0.1", "12.50"]);
+        t.row(["kPEPE", "1000000", "-3.2"]);
+        assert_eq!(
+            t.render(),
+            "\
+┌─────────────────────────┐
+│ POSITIONS               │
+├───────┬─────────┬───────┤
+│ Coin  │    Size │  uPnL │
+├───────┼─────────┼───────┤
+│ BTC   │     0.1 │ 12.50 │
+│ kPEPE │ 1000000 │  -3.2 │
+└───────┴─────────┴───────┘
+"
+        );
+    }
+
+    #[test]
+    fn test_table_panel_fits_long_values() {
+        let mut p = Table::panel("ACCOUNT SUMMARY");
+        p.kv("Profile", "main");
+        p.kv("Address", "0xe8Ecb4D59690d1E1748217e1b56B73D51A8Bc94C");
+        assert_eq!(
+            p.render(),
+            "\
+┌──────────────────────────────────────────────────────┐
+│ ACCOUNT SUMMARY                                      │
+├─────────┬────────────────────────────────────────────┤
+│ Profile │ main                                       │
+│ Address │ 0xe8Ecb4D59690d1E1748217e1b56B73D51A8Bc94C │
+└─────────┴────────────────────────────────────────────┘
+"
+        );
+    }
+
+    #[test]
+    fn test_table_unicode_width_and_truncation() {
+        let mut t = Table::new()
+            .column("Status", Align::Center)
+            .column("Name", Align::Left)
+            .max_width(8);
+        t.row(["✓", "币安币安币安"]);
+        t.row(["→ ok", "0xe8Ecb4D59690d1E1748217e1b56B73D51A8Bc94C"]);
+        let out = t.render();
+        assert_eq!(
+            out,
+            "\
+┌────────┬──────────┐
+│ Status │ Name     │
+├────────┼──────────┤
+│   ✓    │ 币安币…  │
+│  → ok  │ 0xe8Ecb… │
+└────────┴──────────┘
+"
+        );
+        // Every line spans the same number of terminal columns
+        let widths: Vec<usize> = out.lines().map(display_width).collect();
+        assert!(widths.iter().all(|w| *w == widths[0]), "{widths:?}");
+    }
+
+    #[test]
+    fn test_table_color_is_opt_in() {
+        let build = |color: bool| {
+            let mut t = Table::new().column("PnL", Align::Right).color(color);
+            t.row([Cell::signed("-1.5")]);
+            t.row([Cell::signed("2")]);
+            t.render()
+        };
+        assert!(!build(false).contains('\x1b'));
+        let colored = build(true);
+        assert!(colored.contains("\x1b[31m-1.5\x1b[0m"));
+        assert!(colored.contains("\x1b[32m   2\x1b[0m"));
+    }
+
+    #[test]
+    fn test_table_title_only() {
+        let t = Table::new().title("EMPTY").column("", Align::Left);
+        assert!(t.is_empty());
+        assert_eq!(t.render(), "┌───────┐\n│ EMPTY │\n└───────┘\n");
+    }
+
+    #[test]
+    fn test_table_ascii_mode() {
+        let mut t = Table::new()
+            .title("FOLLOWERS — top")
+            .column("User", Align::Left)
+            .max_width(8)
+            .column("✓", Align::Center)
+            .ascii(true);
+        t.row(["0xe8Ecb4D59690d1E1748217e1b56B73D51A8Bc94C", "✓"]);
+        t.row(["币安", "—"]);
+        assert_eq!(
+            t.render(),
+            "\
++-----------------+
+| FOLLOWERS - top |
++----------+------+
+| User     |  OK  |
++----------+------+
+| 0xe8E... |  OK  |
+| ??       |  -   |
++----------+------+
+"
+        );
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        assert_eq!(to_ascii("plain"), "plain");
+        assert_eq!(to_ascii("✓ Sync done — 3 → 4"), "OK Sync done - 3 -> 4");
+        assert_eq!(to_ascii("📊 TA for BTC"), "TA for BTC");
+        assert_eq!(to_ascii("⚠ |rate| ≥ 0.01…"), "! |rate| >= 0.01...");
+        assert_eq!(to_ascii("╔═╗"), "+-+");
+        assert!(to_ascii("币安 ❌ 🏦").is_ascii());
+    }
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_number_format_auto_price() {
+        let nf = NumberFormat::default();
+        // Five significant figures, integer digits kept
+        assert_eq!(nf.price(d("105234.5"), None), "105234");
+        assert_eq!(nf.price(d("105235.5"), None), "105236");
+        assert_eq!(nf.price(d("3456.789"), None), "3456.8");
+        assert_eq!(nf.price(d("0.123456"), None), "0.12346");
+        assert_eq!(nf.price(d("-2.5"), None), "-2.5000");
+        assert_eq!(nf.price(Decimal::ZERO, None), "0");
+        // Capped at the tick: 6 places by default, fewer when known
+        assert_eq!(nf.price(d("0.0000123456"), None), "0.000012");
+        assert_eq!(nf.price(d("1.23456"), Some(4)), "1.2346");
+        assert_eq!(nf.price(d("0.123456"), Some(4)), "0.1235");
+        assert_eq!(nf.price(d("3456.789"), Some(0)), "3457");
+    }
+
+    #[test]
+    fn test_number_format_rounds_half_to_even() {
+        let nf = NumberFormat::default();
+        assert_eq!(nf.price(d("3456.75"), None), "3456.8");
+        assert_eq!(nf.price(d("3456.85"), None), "3456.8");
+        assert_eq!(nf.usd(d("0.125")), "0.12");
+        assert_eq!(nf.usd(d("0.135")), "0.14");
+        assert_eq!(nf.size(d("1.2345"), Some(3)), "1.234");
+        assert_eq!(nf.size(d("1.2355"), Some(3)), "1.236");
+        // Not truncated
+        assert_eq!(nf.pct(d("5.2689")), "5.27");
+        // A value that rounds to zero has no sign
+        assert_eq!(nf.usd(d("-0.004")), "0.00");
+    }
+
+    #[test]
+    fn test_number_format_fixed_and_auto_rules() {
+        let nf = NumberFormat {
+            price: Decimals::Fixed(2),
+            size: Decimals::Fixed(2),
+            usd: 0,
+            pct: 1,
+            thousands: false,
+        };
+        // Fixed overrides the tick and szDecimals
+        assert_eq!(nf.price(d("3456.785"), Some(1)), "3456.78");
+        assert_eq!(nf.price(d("0.000123"), None), "0.00");
+        assert_eq!(nf.size(d("0.015"), Some(4)), "0.02");
+        assert_eq!(nf.usd(d("2.5")), "2");
+        assert_eq!(nf.usd(d("3.5")), "4");
+        assert_eq!(nf.pct(d("12.25")), "12.2");
+
+        let auto = NumberFormat::default();
+        assert_eq!(auto.size(d("1.2300"), None), "1.23");
+        assert_eq!(auto.size(d("1.23"), Some(4)), "1.2300");
+        assert_eq!(auto.usd(d("12.5")), "12.50");
+        assert_eq!(auto.pct(d("-5.263157")), "-5.26");
+    }
+
+    #[test]
+    fn test_number_format_thousands_separator() {
+        let nf = NumberFormat {
+            price: Decimals::Fixed(2),
+            thousands: true,
+            ..NumberFormat::default()
+        };
+        assert_eq!(nf.price(d("1234567.891"), None), "1,234,567.89");
+        assert_eq!(nf.usd(d("-1234.5")), "-1,234.50");
+        assert_eq!(nf.usd(d("999.5")), "999.50");
+        assert_eq!(nf.size(d("12345.678"), None), "12,345.678");
+        assert_eq!(nf.usd(d("100000")), "100,000.00");
+        // Off by default
+        assert_eq!(NumberFormat::default().usd(d("1234.5")), "1234.50");
+    }
+
+    #[test]
+    fn test_number_format_strings() {
+        let nf = NumberFormat::default();
+        assert_eq!(nf.usd_str("12.346"), "12.35");
+        assert_eq!(nf.price_str("3456.789", None), "3456.8");
+        assert_eq!(nf.size_str("0.50", None), "0.5");
+        assert_eq!(nf.pct_str("1.005"), "1.00");
+        assert_eq!(nf.usd_str("—"), "—");
+        assert_eq!(nf.price_str("n/a", None), "n/a");
+    }
 }
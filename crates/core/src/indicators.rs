@@ -0,0 +1,227 @@
+//! Indicator specs for batch TA (`atlas market hl ta --indicators ...`).
+//!
+//! A spec is `name` or `name:period`, e.g. `rsi`, `rsi:7`, `ema:50`.
+//! Parsing lives here so every TA command validates the same way.
+
+use std::fmt;
+
+use crate::error::{AtlasError, AtlasResult};
+
+/// Bundle computed when `--indicators` is omitted.
+pub const DEFAULT_BUNDLE: &str = "rsi,macd,bbands,atr,ema:20,ema:50";
+
+/// Indicators the batch command can compute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndicatorKind {
+    Rsi,
+    Macd,
+    Bbands,
+    Atr,
+    Ema,
+    Sma,
+    Stoch,
+    Cci,
+    Willr,
+    Obv,
+    Ichimoku,
+    Supertrend,
+}
+
+impl IndicatorKind {
+    pub const ALL: [IndicatorKind; 12] = [
+        IndicatorKind::Rsi,
+        IndicatorKind::Macd,
+        IndicatorKind::Bbands,
+        IndicatorKind::Atr,
+        IndicatorKind::Ema,
+        IndicatorKind::Sma,
+        IndicatorKind::Stoch,
+        IndicatorKind::Cci,
+        IndicatorKind::Willr,
+        IndicatorKind::Obv,
+        IndicatorKind::Ichimoku,
+        IndicatorKind::Supertrend,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            IndicatorKind::Rsi => "rsi",
+            IndicatorKind::Macd => "macd",
+            IndicatorKind::Bbands => "bbands",
+            IndicatorKind::Atr => "atr",
+            IndicatorKind::Ema => "ema",
+            IndicatorKind::Sma => "sma",
+            IndicatorKind::Stoch => "stoch",
+            IndicatorKind::Cci => "cci",
+            IndicatorKind::Willr => "willr",
+            IndicatorKind::Obv => "obv",
+            IndicatorKind::Ichimoku => "ichimoku",
+            IndicatorKind::Supertrend => "supertrend",
+        }
+    }
+
+    /// Period used when the spec doesn't give one. `None` = not parameterized.
+    pub fn default_period(&self) -> Option<usize> {
+        match self {
+            IndicatorKind::Rsi | IndicatorKind::Atr | IndicatorKind::Willr => Some(14),
+            IndicatorKind::Bbands
+            | IndicatorKind::Ema
+            | IndicatorKind::Sma
+            | IndicatorKind::Cci => Some(20),
+            IndicatorKind::Supertrend => Some(10),
+            IndicatorKind::Macd
+            | IndicatorKind::Stoch
+            | IndicatorKind::Obv
+            | IndicatorKind::Ichimoku => None,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.name() == name)
+    }
+}
+
+/// One requested indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndicatorSpec {
+    pub kind: IndicatorKind,
+    /// Explicit period from the spec (`ema:50`), if any.
+    pub explicit_period: Option<usize>,
+}
+
+impl IndicatorSpec {
+    pub fn new(kind: IndicatorKind, period: Option<usize>) -> Self {
+        Self {
+            kind,
+            explicit_period: period,
+        }
+    }
+
+    /// Effective period (explicit, else the indicator default).
+    pub fn period(&self) -> Option<usize> {
+        self.explicit_period.or(self.kind.default_period())
+    }
+
+    /// Candles needed before the value settles.
+    pub fn lookback(&self) -> usize {
+        match self.kind {
+            IndicatorKind::Macd => 35,
+            IndicatorKind::Stoch => 17,
+            IndicatorKind::Obv => 2,
+            IndicatorKind::Ichimoku => 78,
+            _ => self.period().unwrap_or(1),
+        }
+    }
+}
+
+/// Output key: `rsi`, `ema:50` — the spec as the user wrote it, normalized.
+impl fmt::Display for IndicatorSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.explicit_period {
+            Some(p) => write!(f, "{}:{p}", self.kind.name()),
+            None => f.write_str(self.kind.name()),
+        }
+    }
+}
+
+/// Supported specs, for help and error text: `rsi[:N], macd, ...`.
+pub fn supported_list() -> String {
+    IndicatorKind::ALL
+        .iter()
+        .map(|k| match k.default_period() {
+            Some(_) => format!("{}[:N]", k.name()),
+            None => k.name().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parse a comma-separated spec list. Duplicates are dropped, order kept.
+pub fn parse_indicators(s: &str) -> AtlasResult<Vec<IndicatorSpec>> {
+    let mut specs: Vec<IndicatorSpec> = Vec::new();
+    for raw in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let lower = raw.to_lowercase();
+        let (name, period) = match lower.split_once(':') {
+            Some((n, p)) => (n.trim(), Some(p.trim())),
+            None => (lower.as_str(), None),
+        };
+
+        let kind = IndicatorKind::from_name(name).ok_or_else(|| {
+            AtlasError::InvalidIndicator(format!(
+                "Unknown indicator '{raw}'. Supported: {}",
+                supported_list()
+            ))
+        })?;
+
+        let period = match period {
+            None => None,
+            Some(_) if kind.default_period().is_none() => {
+                return Err(AtlasError::InvalidIndicator(format!(
+                    "'{}' takes no period (got '{raw}')",
+                    kind.name()
+                )));
+            }
+            Some(p) => match p.parse::<usize>() {
+                Ok(n) if n >= 1 => Some(n),
+                _ => {
+                    return Err(AtlasError::InvalidIndicator(format!(
+                        "Invalid period in '{raw}': must be a positive integer"
+                    )));
+                }
+            },
+        };
+
+        let spec = IndicatorSpec::new(kind, period);
+        if !specs.contains(&spec) {
+            specs.push(spec);
+        }
+    }
+
+    if specs.is_empty() {
+        return Err(AtlasError::InvalidIndicator(format!(
+            "No indicators given. Supported: {}",
+            supported_list()
+        )));
+    }
+    Ok(specs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_default_bundle() {
+        let specs = parse_indicators(DEFAULT_BUNDLE).unwrap();
+        let keys: Vec<String> = specs.iter().map(|s| s.to_string()).collect();
+        assert_eq!(keys, ["rsi", "macd", "bbands", "atr", "ema:20", "ema:50"]);
+        assert_eq!(specs[0].period(), Some(14));
+        assert_eq!(specs[5].period(), Some(50));
+    }
+
+    #[test]
+    fn test_parse_parameterized_and_case() {
+        let specs = parse_indicators(" RSI:7 , ema:50,ema:50 ").unwrap();
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[0], IndicatorSpec::new(IndicatorKind::Rsi, Some(7)));
+        assert_eq!(specs[0].to_string(), "rsi:7");
+        assert_eq!(specs[1].lookback(), 50);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown() {
+        let err = parse_indicators("rsi,foo").unwrap_err();
+        assert!(matches!(err, AtlasError::InvalidIndicator(_)));
+        let msg = err.to_string();
+        assert!(msg.contains("'foo'"));
+        assert!(msg.contains("ema[:N]") && msg.contains("macd"));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_period() {
+        assert!(parse_indicators("ema:0").is_err());
+        assert!(parse_indicators("ema:abc").is_err());
+        assert!(parse_indicators("macd:5").is_err());
+        assert!(parse_indicators(" , ").is_err());
+    }
+}
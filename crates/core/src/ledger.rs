@@ -0,0 +1,448 @@
+//! Time-paged Hyperliquid account history for the local cache: deposits,
+//! withdrawals and transfers (`transfers` table), funding payments
+//! (`funding_payments`) and, for `sync --full`, fills older than the
+//! latest 2000.
+//!
+//! `history sync` pulls `userNonFundingLedgerUpdates` and `userFunding`
+//! from the newest cached entry onwards. These endpoints serve a limited
+//! page per call, oldest first from `startTime`, so they are walked forward
+//! until a short page. Ledger entries that move no funds in or out of the
+//! account (liquidations, reward claims, staking) are skipped.
+
+use std::future::Future;
+
+use anyhow::Result;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::db::{AtlasDb, DbFill, DbFunding, DbTransfer};
+
+/// Fewest entries a `userNonFundingLedgerUpdates` page holds when more
+/// follow; anything shorter ends the sync.
+pub const LEDGER_PAGE: usize = 500;
+
+/// Same for `userFunding`.
+pub const FUNDING_PAGE: usize = 500;
+
+/// Same for `userFillsByTime`.
+pub const FILLS_PAGE: usize = 2000;
+
+/// `sync_state` key set to the time of the last `sync --full`, after which
+/// the cache holds the account's whole history.
+pub const FULL_SYNC_STATE: &str = "last_full_sync";
+
+/// Transfer kinds accepted by `history transfers --type`.
+pub const TRANSFER_KINDS: [&str; 3] = ["deposit", "withdraw", "internal"];
+
+/// Cache every ledger entry of `user` from `start_ms` on. `fetch(start_ms)`
+/// returns one `userNonFundingLedgerUpdates` response. Returns the number
+/// of entries not cached before.
+pub async fn sync_transfers<F, Fut>(
+    db: &AtlasDb,
+    user: &str,
+    start_ms: i64,
+    fetch: F,
+) -> Result<usize>
+where
+    F: FnMut(i64) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    walk_pages(start_ms, LEDGER_PAGE, fetch, |page| {
+        db.insert_transfers(&parse_ledger(page, user))
+    })
+    .await
+}
+
+/// Cache every funding payment from `start_ms` on. `fetch(start_ms)`
+/// returns one `userFunding` response. Returns the number of payments not
+/// cached before.
+pub async fn sync_funding<F, Fut>(db: &AtlasDb, start_ms: i64, fetch: F) -> Result<usize>
+where
+    F: FnMut(i64) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    walk_pages(start_ms, FUNDING_PAGE, fetch, |page| {
+        db.insert_funding(&parse_funding(page))
+    })
+    .await
+}
+
+/// Cache every fill from `start_ms` on. `fetch(start_ms)` returns one
+/// `userFillsByTime` response. Hyperliquid only serves the account's most
+/// recent 10000 fills this way; older ones are gone for good.
+pub async fn sync_fills<F, Fut>(db: &AtlasDb, start_ms: i64, fetch: F) -> Result<usize>
+where
+    F: FnMut(i64) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    walk_pages(start_ms, FILLS_PAGE, fetch, |page| {
+        db.insert_fills(&parse_fills(page))
+    })
+    .await
+}
+
+/// Fetch pages forward from `start_ms` until one holds fewer than
+/// `page_size` entries, handing each to `store`. Returns the sum of what
+/// `store` reports.
+async fn walk_pages<F, Fut, S>(
+    start_ms: i64,
+    page_size: usize,
+    mut fetch: F,
+    mut store: S,
+) -> Result<usize>
+where
+    F: FnMut(i64) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+    S: FnMut(&Value) -> Result<usize>,
+{
+    let mut inserted = 0usize;
+    let mut start = start_ms;
+    loop {
+        let page = fetch(start).await?;
+        let entries = page.as_array().map(Vec::as_slice).unwrap_or_default();
+        inserted += store(&page)?;
+
+        // The page ending on `start` again means nothing newer is left
+        let newest = entries
+            .iter()
+            .filter_map(|e| e.get("time").and_then(Value::as_i64))
+            .max();
+        match newest {
+            Some(t) if entries.len() >= page_size && t > start => start = t,
+            _ => break,
+        }
+    }
+    Ok(inserted)
+}
+
+/// The payments in a `userFunding` response:
+/// `{"time", "delta": {"type": "funding", "coin", "usdc", "szi", "fundingRate"}}`.
+pub fn parse_funding(resp: &Value) -> Vec<DbFunding> {
+    resp.as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| {
+            let delta = entry.get("delta")?;
+            let text = |key: &str| delta.get(key).and_then(Value::as_str).map(str::to_string);
+            if delta.get("type").and_then(Value::as_str) != Some("funding") {
+                debug!(entry = %entry, "funding entry skipped");
+                return None;
+            }
+            Some(DbFunding {
+                coin: text("coin")?,
+                time_ms: entry.get("time").and_then(Value::as_i64)?,
+                usdc: text("usdc")?,
+                szi: text("szi").unwrap_or_else(|| "0".into()),
+                funding_rate: text("fundingRate").unwrap_or_else(|| "0".into()),
+            })
+        })
+        .collect()
+}
+
+/// The fills in a `userFills` / `userFillsByTime` response.
+pub fn parse_fills(resp: &Value) -> Vec<DbFill> {
+    resp.as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|f| {
+            let text = |key: &str| f.get(key).and_then(Value::as_str).map(str::to_string);
+            let side = match f.get("side").and_then(Value::as_str)? {
+                "B" => "Buy",
+                "A" => "Sell",
+                _ => return None,
+            };
+            Some(DbFill {
+                protocol: "hyperliquid".to_string(),
+                coin: text("coin")?,
+                px: text("px")?,
+                sz: text("sz")?,
+                side: side.to_string(),
+                time_ms: f.get("time").and_then(Value::as_i64)?,
+                fee: text("fee").unwrap_or_else(|| "0".into()),
+                hash: text("hash")?,
+                oid: f.get("oid").and_then(Value::as_i64).unwrap_or_default(),
+                closed_pnl: text("closedPnl").unwrap_or_else(|| "0".into()),
+            })
+        })
+        .collect()
+}
+
+/// The transfers in a `userNonFundingLedgerUpdates` response for `user`.
+pub fn parse_ledger(resp: &Value, user: &str) -> Vec<DbTransfer> {
+    resp.as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| {
+            let transfer = parse_entry(entry, user);
+            if transfer.is_none() {
+                debug!(entry = %entry, "ledger entry skipped");
+            }
+            transfer
+        })
+        .collect()
+}
+
+/// One `{"time", "hash", "delta": {"type", ...}}` ledger entry, `None` for
+/// entry types that are not transfers.
+pub fn parse_entry(entry: &Value, user: &str) -> Option<DbTransfer> {
+    let delta = entry.get("delta")?;
+    let text = |key: &str| delta.get(key).and_then(Value::as_str).map(str::to_string);
+    let ledger_type = text("type")?;
+    let time_ms = entry.get("time").and_then(Value::as_i64)?;
+    let hash = entry.get("hash").and_then(Value::as_str)?;
+
+    let (kind, direction, counterparty) = match ledger_type.as_str() {
+        "deposit" => ("deposit", "in", None),
+        "withdraw" => ("withdraw", "out", None),
+        "internalTransfer" | "subAccountTransfer" | "spotTransfer" | "send" => {
+            let incoming = text("destination").is_some_and(|d| d.eq_ignore_ascii_case(user));
+            if incoming {
+                ("internal", "in", text("user"))
+            } else {
+                ("internal", "out", text("destination"))
+            }
+        }
+        "accountClassTransfer" => {
+            let to_perp = delta.get("toPerp").and_then(Value::as_bool)?;
+            let path = if to_perp {
+                "spot→perp"
+            } else {
+                "perp→spot"
+            };
+            ("internal", "none", Some(path.to_string()))
+        }
+        "vaultCreate" | "vaultDeposit" => ("internal", "out", text("vault")),
+        "vaultWithdraw" | "vaultDistribution" => ("internal", "in", text("vault")),
+        _ => return None,
+    };
+
+    // Token transfers give the amount in the token plus its USD value;
+    // everything else moves USDC
+    let (token, amount, usd_value) = match text("token") {
+        Some(token) => (token, text("amount")?, text("usdcValue")),
+        None => {
+            let key = if ledger_type == "vaultWithdraw" {
+                "netWithdrawnUsd"
+            } else {
+                "usdc"
+            };
+            let usdc = text(key)?;
+            ("USDC".to_string(), usdc.clone(), Some(usdc))
+        }
+    };
+
+    Some(DbTransfer {
+        hash: ledger_key(hash, time_ms, &ledger_type),
+        time_ms,
+        kind: kind.into(),
+        ledger_type,
+        direction: direction.into(),
+        token,
+        amount,
+        usd_value,
+        fee: text("fee").unwrap_or_else(|| "0".into()),
+        counterparty,
+    })
+}
+
+/// System entries (e.g. vault distributions) carry an all-zero hash; those
+/// are keyed on time and type as well so they don't collapse into one row.
+fn ledger_key(hash: &str, time_ms: i64, ledger_type: &str) -> String {
+    if hash.trim_start_matches("0x").bytes().all(|b| b == b'0') {
+        format!("{hash}:{time_ms}:{ledger_type}")
+    } else {
+        hash.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+    use std::cell::RefCell;
+
+    const ME: &str = "0x1111111111111111111111111111111111111111";
+    const OTHER: &str = "0x2222222222222222222222222222222222222222";
+    const ZERO_HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+    fn entry(time: i64, hash: &str, delta: Value) -> Value {
+        json!({"time": time, "hash": hash, "delta": delta})
+    }
+
+    #[test]
+    fn test_parse_ledger_entry_types() {
+        let resp = json!([
+            entry(1, "0xa1", json!({"type": "deposit", "usdc": "1000.0"})),
+            entry(
+                2,
+                "0xa2",
+                json!({"type": "withdraw", "usdc": "250.0", "nonce": 7, "fee": "1.0"})
+            ),
+            entry(
+                3,
+                "0xa3",
+                json!({"type": "internalTransfer", "usdc": "50.0",
+                "user": OTHER, "destination": ME, "fee": "0.0"})
+            ),
+            entry(
+                4,
+                "0xa4",
+                json!({"type": "accountClassTransfer", "usdc": "300.0", "toPerp": false})
+            ),
+            entry(
+                5,
+                "0xa5",
+                json!({"type": "spotTransfer", "token": "PURR", "amount": "100",
+                "usdcValue": "20.5", "user": ME, "destination": OTHER, "fee": "0.0"})
+            ),
+            entry(
+                6,
+                "0xa6",
+                json!({"type": "vaultDeposit", "vault": OTHER, "usdc": "100.0"})
+            ),
+            entry(
+                7,
+                "0xa7",
+                json!({"type": "vaultWithdraw", "vault": OTHER, "user": ME,
+                "requestedUsd": "60.0", "commission": "1.0", "closingCost": "0.0",
+                "basis": "55.0", "netWithdrawnUsd": "59.0"})
+            ),
+            entry(
+                8,
+                "0xa8",
+                json!({"type": "liquidation", "accountValue": "10.0"})
+            ),
+            entry(9, "0xa9", json!({"type": "rewardsClaim", "amount": "3.0"})),
+        ]);
+        let transfers = parse_ledger(&resp, ME);
+        assert_eq!(transfers.len(), 7);
+
+        let by_hash = |h: &str| transfers.iter().find(|t| t.hash == h).unwrap();
+        let withdraw = by_hash("0xa2");
+        assert_eq!(
+            (withdraw.kind.as_str(), withdraw.direction.as_str()),
+            ("withdraw", "out")
+        );
+        assert_eq!(withdraw.fee, "1.0");
+
+        let received = by_hash("0xa3");
+        assert_eq!(received.kind, "internal");
+        assert_eq!(received.direction, "in");
+        assert_eq!(received.counterparty.as_deref(), Some(OTHER));
+
+        let to_spot = by_hash("0xa4");
+        assert_eq!(to_spot.direction, "none");
+        assert_eq!(to_spot.counterparty.as_deref(), Some("perp→spot"));
+        assert_eq!(to_spot.net_usd(), Decimal::ZERO);
+
+        let sent = by_hash("0xa5");
+        assert_eq!((sent.token.as_str(), sent.amount.as_str()), ("PURR", "100"));
+        assert_eq!(sent.usd_value.as_deref(), Some("20.5"));
+        assert_eq!(sent.counterparty.as_deref(), Some(OTHER));
+
+        assert_eq!(by_hash("0xa7").amount, "59.0");
+
+        // 1000 - 250 + 50 - 20.5 - 100 + 59
+        let net: Decimal = transfers.iter().map(DbTransfer::net_usd).sum();
+        assert_eq!(net, "738.5".parse().unwrap());
+    }
+
+    #[test]
+    fn test_zero_hash_entries_stay_distinct() {
+        let resp = json!([
+            entry(
+                1,
+                ZERO_HASH,
+                json!({"type": "vaultDistribution", "vault": OTHER, "usdc": "1.0"})
+            ),
+            entry(
+                2,
+                ZERO_HASH,
+                json!({"type": "vaultDistribution", "vault": OTHER, "usdc": "2.0"})
+            ),
+        ]);
+        let transfers = parse_ledger(&resp, ME);
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert_eq!(db.insert_transfers(&transfers).unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sync_pages_forward_and_dedups() {
+        // Two full pages, each ending on the time the next one starts at
+        let deposit = |i: i64| {
+            entry(
+                i,
+                &format!("0x{i:x}"),
+                json!({"type": "deposit", "usdc": "1"}),
+            )
+        };
+        let first: Vec<Value> = (0..LEDGER_PAGE as i64).map(deposit).collect();
+        let last = LEDGER_PAGE as i64 - 1;
+        let second: Vec<Value> = (last..last + 10).map(deposit).collect();
+        let pages = RefCell::new(vec![Value::from(second), Value::from(first)]);
+        let calls = RefCell::new(Vec::new());
+
+        let db = AtlasDb::open_in_memory().unwrap();
+        let inserted = sync_transfers(&db, ME, 0, |start| {
+            calls.borrow_mut().push(start);
+            let page = pages.borrow_mut().pop().unwrap_or_else(|| json!([]));
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(inserted, LEDGER_PAGE + 9);
+        assert_eq!(*calls.borrow(), vec![0, last]);
+        assert_eq!(db.last_transfer_time().unwrap(), Some(last + 9));
+    }
+
+    #[test]
+    fn test_parse_funding() {
+        let resp = json!([
+            {"time": 1000, "hash": ZERO_HASH, "delta": {"type": "funding", "coin": "ETH",
+                "usdc": "-0.4213", "szi": "2.5", "fundingRate": "0.0000125", "nSamples": null}},
+            {"time": 1000, "hash": ZERO_HASH, "delta": {"type": "funding", "coin": "BTC",
+                "usdc": "0.08", "szi": "-0.1", "fundingRate": "0.00001"}},
+            {"time": 2000, "hash": ZERO_HASH, "delta": {"type": "deposit", "usdc": "5"}},
+        ]);
+        let payments = parse_funding(&resp);
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments[0].coin, "ETH");
+        assert_eq!(payments[0].usdc, "-0.4213");
+        assert_eq!(payments[1].szi, "-0.1");
+
+        // Same time, different coins: both kept
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert_eq!(db.insert_funding(&payments).unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sync_fills_pages_by_time() {
+        let fill = |i: i64| {
+            json!({"coin": "ETH", "px": "3000.5", "sz": "0.1", "side": if i % 2 == 0 { "B" } else { "A" },
+                "time": i, "fee": "0.01", "hash": format!("0x{i:x}"), "oid": i, "closedPnl": "0.0"})
+        };
+        let first: Vec<Value> = (0..FILLS_PAGE as i64).map(fill).collect();
+        let pages = RefCell::new(vec![json!([fill(FILLS_PAGE as i64)]), Value::from(first)]);
+
+        let db = AtlasDb::open_in_memory().unwrap();
+        let inserted = sync_fills(&db, 0, |_| {
+            let page = pages.borrow_mut().pop().unwrap_or_else(|| json!([]));
+            async move { Ok(page) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(inserted, FILLS_PAGE + 1);
+        assert_eq!(db.first_fill_time(Some("hyperliquid")).unwrap(), Some(0));
+        let fills = parse_fills(&json!([fill(1)]));
+        assert_eq!(
+            (fills[0].side.as_str(), fills[0].px.as_str()),
+            ("Sell", "3000.5")
+        );
+    }
+}
@@ -10,17 +10,57 @@ pub mod output;
 pub mod profile;
 
 // ── From atlas-utils ──
+pub mod assets;
+pub mod backtest;
+pub mod batch;
+pub mod carry;
+pub mod cross;
+pub mod date_range;
+pub mod depth;
+pub mod dex;
 pub mod fmt;
+pub mod indicators;
+pub mod liquidation;
+pub mod market_rank;
+pub mod market_summary;
+pub mod metrics;
+pub mod overview;
 pub mod parse;
+pub mod patterns;
+pub mod pool_screen;
 pub mod prompt;
 pub mod risk;
+pub mod schedule;
+pub mod setup;
+pub mod sizing;
+pub mod statement;
+pub mod stats;
+pub mod status_snapshot;
+pub mod symbols;
+pub mod ta_ext;
+pub mod timeframe;
+pub mod vault_list;
 
 // ── Core modules ──
+pub mod approval;
+pub mod audit;
 pub mod auth;
 pub mod backend;
+pub mod backup;
+pub mod candle_cache;
+pub mod candle_gaps;
+pub mod config_sync;
 pub mod db;
 pub mod engine;
+pub mod ens;
+pub mod ledger;
+pub mod logs;
+pub mod notify;
 pub mod orchestrator;
+pub mod order_sync;
+pub mod recording;
+pub mod spread_profile;
+pub mod trade_import;
 pub mod workspace;
 
 pub use auth::AuthManager;
@@ -0,0 +1,376 @@
+//! Liquidation prices from Hyperliquid's maintenance-margin model
+//! (`atlas hl risk liq`).
+//!
+//! Each asset has maintenance tiers: notional in a tier with max leverage
+//! `L` needs `1 / (2L)` of it as maintenance margin, less a deduction that
+//! keeps the requirement continuous across tier bounds. A cross position is
+//! liquidated when the cross account value falls to the maintenance margin
+//! of every cross position; an isolated one when its own equity falls to
+//! its own maintenance margin. Solving for the target coin's price holds
+//! every other mark where it is. Fees and funding are ignored.
+
+use rust_decimal::Decimal;
+
+use crate::types::{MarginMode, MarginPosition, MarginState, MarginTier};
+
+/// Where a position stands and the price that liquidates it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Liquidation {
+    /// Equity backing the position: the cross account value, or the
+    /// isolated position's own equity.
+    pub equity: Decimal,
+    /// Maintenance margin that equity has to cover at current marks.
+    pub maintenance: Decimal,
+    /// `None` when no positive price liquidates the position.
+    pub price: Option<Decimal>,
+}
+
+/// `(lower_bound, rate, deduction)` per tier, lowest bound first.
+fn schedule(tiers: &[MarginTier]) -> Vec<(Decimal, Decimal, Decimal)> {
+    let mut sorted: Vec<&MarginTier> = tiers.iter().collect();
+    sorted.sort_by(|a, b| a.lower_bound.cmp(&b.lower_bound));
+
+    let mut out: Vec<(Decimal, Decimal, Decimal)> = Vec::with_capacity(sorted.len());
+    for tier in sorted {
+        let rate = Decimal::ONE / Decimal::from(2 * tier.max_leverage.max(1));
+        let deduction = match out.last() {
+            Some(&(_, prev_rate, prev_deduction)) => {
+                prev_deduction + tier.lower_bound * (rate - prev_rate)
+            }
+            None => Decimal::ZERO,
+        };
+        out.push((tier.lower_bound, rate, deduction));
+    }
+    out
+}
+
+/// Maintenance margin for `notional` USD of one asset. No tiers means no
+/// requirement.
+pub fn maintenance_margin(tiers: &[MarginTier], notional: Decimal) -> Decimal {
+    let schedule = schedule(tiers);
+    schedule
+        .iter()
+        .rev()
+        .find(|(lower, _, _)| *lower <= notional)
+        .or(schedule.first())
+        .map(|&(_, rate, deduction)| notional * rate - deduction)
+        .unwrap_or(Decimal::ZERO)
+}
+
+fn tiers_for<'a>(state: &'a MarginState, coin: &str) -> &'a [MarginTier] {
+    state.tiers.get(coin).map(Vec::as_slice).unwrap_or(&[])
+}
+
+fn position_maintenance(state: &MarginState, p: &MarginPosition) -> Decimal {
+    maintenance_margin(tiers_for(state, &p.coin), p.size.abs() * p.mark_price)
+}
+
+/// Liquidation of `coin`'s position, `None` when there is none.
+pub fn liquidation(state: &MarginState, coin: &str) -> Option<Liquidation> {
+    let pos = state
+        .positions
+        .iter()
+        .find(|p| p.coin == coin && !p.size.is_zero())?;
+
+    let (equity, others) = match &pos.mode {
+        MarginMode::Cross => (
+            state.cross_account_value,
+            state
+                .positions
+                .iter()
+                .filter(|p| p.coin != coin && p.mode == MarginMode::Cross)
+                .map(|p| position_maintenance(state, p))
+                .sum(),
+        ),
+        MarginMode::Isolated { equity } => (*equity, Decimal::ZERO),
+    };
+    let own = position_maintenance(state, pos);
+
+    Some(Liquidation {
+        equity,
+        maintenance: own + others,
+        price: solve(tiers_for(state, coin), pos, equity, others),
+    })
+}
+
+/// The price `p` of `pos` where `equity + size·(p − mark)` equals
+/// `others + maintenance(|size|·p)`, tried tier by tier until the solution
+/// lands inside the tier it was solved for.
+fn solve(
+    tiers: &[MarginTier],
+    pos: &MarginPosition,
+    equity: Decimal,
+    others: Decimal,
+) -> Option<Decimal> {
+    let size = pos.size;
+    let base = equity - size * pos.mark_price - others;
+    let mut schedule = schedule(tiers);
+    if schedule.is_empty() {
+        schedule.push((Decimal::ZERO, Decimal::ZERO, Decimal::ZERO));
+    }
+
+    for (i, &(lower, rate, deduction)) in schedule.iter().enumerate() {
+        let denom = size.abs() * rate - size;
+        if denom.is_zero() {
+            continue;
+        }
+        let price = (base + deduction) / denom;
+        if price <= Decimal::ZERO {
+            continue;
+        }
+        let notional = size.abs() * price;
+        let above = i == 0 || notional >= lower;
+        let below = schedule
+            .get(i + 1)
+            .map_or(true, |&(next, _, _)| notional < next);
+        if above && below {
+            return Some(price);
+        }
+    }
+    None
+}
+
+/// `state` after trading `size` (signed, positive buys) of `coin` at
+/// `price`. Cross trades move the cross account value by the fill's
+/// distance from the mark. Isolated trades post `|size|·price / leverage`
+/// of new margin from the cross account and release margin pro rata when
+/// reducing. A coin with no position opens a cross one marked at `price`.
+pub fn with_added(state: &MarginState, coin: &str, size: Decimal, price: Decimal) -> MarginState {
+    let mut next = state.clone();
+    let Some(idx) = next.positions.iter().position(|p| p.coin == coin) else {
+        let leverage = tiers_for(state, coin).first().map_or(1, |t| t.max_leverage);
+        next.positions.push(MarginPosition {
+            coin: coin.to_string(),
+            size,
+            mark_price: price,
+            leverage,
+            mode: MarginMode::Cross,
+        });
+        return next;
+    };
+
+    let pos = &mut next.positions[idx];
+    let new_size = pos.size + size;
+    match &mut pos.mode {
+        MarginMode::Cross => {
+            next.cross_account_value += size * (pos.mark_price - price);
+        }
+        MarginMode::Isolated { equity } => {
+            let leverage = Decimal::from(pos.leverage.max(1));
+            let closed = if size.is_sign_negative() != pos.size.is_sign_negative() {
+                size.abs().min(pos.size.abs())
+            } else {
+                Decimal::ZERO
+            };
+            let opened = size.abs() - closed;
+
+            let at_price = *equity + pos.size * (price - pos.mark_price);
+            let released = if pos.size.is_zero() {
+                Decimal::ZERO
+            } else {
+                at_price * closed / pos.size.abs()
+            };
+            let posted = opened * price / leverage;
+            next.cross_account_value += released - posted;
+            *equity = at_price - released + posted + new_size * (pos.mark_price - price);
+        }
+    }
+    pos.size = new_size;
+    if new_size.is_zero() {
+        next.positions.remove(idx);
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn d(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn tier(lower: &str, max_leverage: u32) -> MarginTier {
+        MarginTier {
+            lower_bound: d(lower),
+            max_leverage,
+        }
+    }
+
+    fn position(coin: &str, size: &str, mark: &str, mode: MarginMode) -> MarginPosition {
+        MarginPosition {
+            coin: coin.into(),
+            size: d(size),
+            mark_price: d(mark),
+            leverage: 10,
+            mode,
+        }
+    }
+
+    fn state(cross_account_value: &str, positions: Vec<MarginPosition>) -> MarginState {
+        MarginState {
+            cross_account_value: d(cross_account_value),
+            positions,
+            tiers: [
+                ("BTC".to_string(), vec![tier("0", 50)]),
+                ("ETH".to_string(), vec![tier("0", 25)]),
+                ("SOL".to_string(), vec![tier("0", 20), tier("100000", 10)]),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    fn liq_price(state: &MarginState, coin: &str) -> Option<Decimal> {
+        liquidation(state, coin)
+            .unwrap()
+            .price
+            .map(|p| p.round_dp(4))
+    }
+
+    #[test]
+    fn test_maintenance_margin_is_continuous_across_tiers() {
+        let tiers = vec![tier("100000", 10), tier("0", 20)];
+        // 2.5% below 100k, 5% above less a 2,500 deduction
+        assert_eq!(maintenance_margin(&tiers, d("40000")), d("1000"));
+        assert_eq!(maintenance_margin(&tiers, d("100000")), d("2500"));
+        assert_eq!(maintenance_margin(&tiers, d("200000")), d("7500"));
+        assert_eq!(maintenance_margin(&[], d("200000")), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_isolated_long() {
+        // 1 ETH at 3000 with 300 equity, 50x tier (1% maintenance):
+        // 300 + (p - 3000) = 0.01p  =>  p = 2700 / 0.99
+        let mut s = state(
+            "10000",
+            vec![position(
+                "ETH",
+                "1",
+                "3000",
+                MarginMode::Isolated { equity: d("300") },
+            )],
+        );
+        s.tiers.insert("ETH".into(), vec![tier("0", 50)]);
+        let liq = liquidation(&s, "ETH").unwrap();
+        assert_eq!(liq.equity, d("300"));
+        assert_eq!(liq.maintenance, d("30"));
+        assert_eq!(liq.price.unwrap().round_dp(4), d("2727.2727"));
+
+        // The cross account does not back it
+        s.cross_account_value = d("1000000");
+        assert_eq!(liq_price(&s, "ETH"), Some(d("2727.2727")));
+    }
+
+    #[test]
+    fn test_cross_positions_share_equity() {
+        // 10k cross; BTC long 1 @ 50k (1% mm = 500), ETH short 10 @ 3k (2% mm = 600)
+        let s = state(
+            "10000",
+            vec![
+                position("BTC", "1", "50000", MarginMode::Cross),
+                position("ETH", "-10", "3000", MarginMode::Cross),
+            ],
+        );
+        let btc = liquidation(&s, "BTC").unwrap();
+        assert_eq!(btc.maintenance, d("1100"));
+        // 10000 + (p - 50000) = 0.01p + 600  =>  p = 40600 / 0.99
+        assert_eq!(btc.price.unwrap().round_dp(4), d("41010.1010"));
+        // 10000 - 10(p - 3000) = 0.2p + 500  =>  p = 39500 / 10.2
+        assert_eq!(liq_price(&s, "ETH"), Some(d("3872.5490")));
+
+        // An isolated position elsewhere neither adds equity nor maintenance
+        let mut with_isolated = s.clone();
+        with_isolated.positions.push(position(
+            "SOL",
+            "100",
+            "150",
+            MarginMode::Isolated { equity: d("1500") },
+        ));
+        assert_eq!(liq_price(&with_isolated, "BTC"), Some(d("41010.1010")));
+    }
+
+    #[test]
+    fn test_cross_liquidation_in_higher_tier() {
+        // 4 SOL at 50k is 200k notional: 5% tier with a 2,500 deduction
+        // 20000 + 4(p - 50000) = 0.2p - 2500  =>  p = 177500 / 3.8
+        let s = state(
+            "20000",
+            vec![position("SOL", "4", "50000", MarginMode::Cross)],
+        );
+        assert_eq!(liq_price(&s, "SOL"), Some(d("46710.5263")));
+    }
+
+    #[test]
+    fn test_overcollateralized_long_has_no_liquidation_price() {
+        let s = state(
+            "100000",
+            vec![position("ETH", "1", "3000", MarginMode::Cross)],
+        );
+        assert_eq!(liq_price(&s, "ETH"), None);
+        assert!(liquidation(&s, "BTC").is_none());
+    }
+
+    #[test]
+    fn test_with_added_cross() {
+        let s = state(
+            "10000",
+            vec![
+                position("BTC", "1", "50000", MarginMode::Cross),
+                position("ETH", "-10", "3000", MarginMode::Cross),
+            ],
+        );
+        // Doubling BTC at the mark: 10000 + 2(p - 50000) = 0.02p + 600
+        let after = with_added(&s, "BTC", d("1"), d("50000"));
+        assert_eq!(after.cross_account_value, d("10000"));
+        assert_eq!(liq_price(&after, "BTC"), Some(d("45757.5758")));
+
+        // Buying above the mark costs the difference up front
+        let after = with_added(&s, "BTC", d("1"), d("50100"));
+        assert_eq!(after.cross_account_value, d("9900"));
+
+        // A new coin opens a cross position at the given price
+        let after = with_added(&s, "SOL", d("-100"), d("150"));
+        assert_eq!(after.positions.len(), 3);
+        assert!(liq_price(&after, "SOL").is_some());
+
+        // Closing removes the position
+        let after = with_added(&s, "ETH", d("10"), d("3000"));
+        assert!(liquidation(&after, "ETH").is_none());
+    }
+
+    #[test]
+    fn test_with_added_isolated() {
+        let mut s = state(
+            "10000",
+            vec![position(
+                "ETH",
+                "1",
+                "3000",
+                MarginMode::Isolated { equity: d("300") },
+            )],
+        );
+        s.tiers.insert("ETH".into(), vec![tier("0", 50)]);
+
+        // Adding 1 @ 2900 at 10x: 200 left at the fill price + 290 posted,
+        // marked back up to 3000 => 690
+        let after = with_added(&s, "ETH", d("1"), d("2900"));
+        assert_eq!(
+            after.positions[0].mode,
+            MarginMode::Isolated { equity: d("690") }
+        );
+        assert_eq!(after.cross_account_value, d("9710"));
+        // 690 + 2(p - 3000) = 0.02p  =>  p = 5310 / 1.98
+        assert_eq!(liq_price(&after, "ETH"), Some(d("2681.8182")));
+
+        // Selling half of a 2 ETH position releases half its equity
+        let two = with_added(&s, "ETH", d("1"), d("3000"));
+        let half = with_added(&two, "ETH", d("-1"), d("3000"));
+        assert_eq!(
+            half.positions[0].mode,
+            MarginMode::Isolated { equity: d("300") }
+        );
+        assert_eq!(half.cross_account_value, d("10000"));
+    }
+}
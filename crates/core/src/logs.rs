@@ -0,0 +1,516 @@
+//! Structured log file — JSON lines under `~/.atlas-os/logs/`.
+//!
+//! The CLI installs a tracing layer that hands every event to [`RotatingLog`]
+//! as one JSON object per line. The active file is always `atlas.log`; on the
+//! first write of a new UTC day it is renamed to `atlas.log.YYYY-MM-DD` and
+//! dated files beyond the configured retention are deleted.
+//!
+//! Secrets never reach disk: fields whose name looks like a credential are
+//! replaced wholesale, and raw private keys or backend API keys embedded in
+//! any other value are masked (see [`redact_value`]).
+
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Name of the active log file inside the logs directory.
+pub const LOG_FILE: &str = "atlas.log";
+
+/// Placeholder written in place of a redacted value.
+pub const REDACTED: &str = "[redacted]";
+
+/// Field-name fragments that mark a value as secret.
+const SECRET_FIELDS: &[&str] = &[
+    "private_key",
+    "privatekey",
+    "api_key",
+    "apikey",
+    "secret",
+    "password",
+    "passphrase",
+    "mnemonic",
+    "seed",
+];
+
+/// `~/.atlas-os/logs/`.
+pub fn logs_dir() -> anyhow::Result<PathBuf> {
+    crate::workspace::resolve("logs")
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  LEVELS
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Log severity, ordered from most to least verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Upper-case name as written to the log (`"INFO"`, `"WARN"`, …).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            other => Err(format!(
+                "unknown log level '{other}' (expected trace, debug, info, warn or error)"
+            )),
+        }
+    }
+}
+
+impl From<&tracing::Level> for LogLevel {
+    fn from(level: &tracing::Level) -> Self {
+        match *level {
+            tracing::Level::TRACE => LogLevel::Trace,
+            tracing::Level::DEBUG => LogLevel::Debug,
+            tracing::Level::INFO => LogLevel::Info,
+            tracing::Level::WARN => LogLevel::Warn,
+            tracing::Level::ERROR => LogLevel::Error,
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  REDACTION
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Whether a field name identifies a credential.
+pub fn is_secret_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SECRET_FIELDS.iter().any(|s| name.contains(s))
+}
+
+/// Mask secrets embedded in free text: 32-byte hex strings (private keys)
+/// and backend API keys (`atl_…`). Addresses, hashes of other lengths and
+/// ordinary words pass through unchanged.
+pub fn redact_value(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut token = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            token.push(c);
+        } else {
+            out.push_str(&redact_token(&token));
+            token.clear();
+            out.push(c);
+        }
+    }
+    out.push_str(&redact_token(&token));
+    out
+}
+
+fn redact_token(token: &str) -> String {
+    let hex = token.strip_prefix("0x").unwrap_or(token);
+    let is_key = hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit());
+    let is_api_key = token.starts_with("atl_") && token.len() > 8;
+    if is_key || is_api_key {
+        REDACTED.to_string()
+    } else {
+        token.to_string()
+    }
+}
+
+/// Collects an event's fields into a JSON map, redacting as it goes.
+///
+/// The `message` field is kept as a string; numbers and booleans keep their
+/// JSON types so `jq` filters work on the log file directly.
+#[derive(Debug, Default)]
+pub struct FieldCollector {
+    pub fields: Map<String, Value>,
+}
+
+impl FieldCollector {
+    fn insert(&mut self, name: &str, value: Value) {
+        let value = if is_secret_field(name) {
+            Value::String(REDACTED.into())
+        } else {
+            match value {
+                Value::String(s) => Value::String(redact_value(&s)),
+                other => other,
+            }
+        };
+        self.fields.insert(name.to_string(), value);
+    }
+}
+
+impl tracing::field::Visit for FieldCollector {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.insert(field.name(), Value::String(value.to_string()));
+    }
+
+    fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
+        self.insert(field.name(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        self.insert(field.name(), Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+        self.insert(field.name(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+        self.insert(field.name(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn fmt::Debug) {
+        self.insert(field.name(), Value::String(format!("{value:?}")));
+    }
+}
+
+/// Serialize one log record. Event fields are flattened next to the fixed
+/// `timestamp`, `level` and `target` keys.
+pub fn format_record(
+    timestamp: DateTime<Utc>,
+    level: LogLevel,
+    target: &str,
+    fields: Map<String, Value>,
+) -> String {
+    let mut record = Map::new();
+    record.insert(
+        "timestamp".into(),
+        Value::String(timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+    );
+    record.insert("level".into(), Value::String(level.as_str().into()));
+    record.insert("target".into(), Value::String(target.into()));
+    for (k, v) in fields {
+        record.entry(k).or_insert(v);
+    }
+    Value::Object(record).to_string()
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  ROTATING WRITER
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Append-only writer for `atlas.log` with daily rotation.
+pub struct RotatingLog {
+    dir: PathBuf,
+    retention: usize,
+    /// UTC day the active file belongs to (`None` when it does not exist yet).
+    day: Option<NaiveDate>,
+    file: Option<File>,
+}
+
+impl RotatingLog {
+    /// Open (or prepare to create) `atlas.log` in `dir`, keeping at most
+    /// `retention` rotated files.
+    pub fn open(dir: &Path, retention: usize) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let day = fs::metadata(dir.join(LOG_FILE))
+            .and_then(|m| m.modified())
+            .ok()
+            .map(|t| DateTime::<Utc>::from(t).date_naive());
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            retention,
+            day,
+            file: None,
+        })
+    }
+
+    /// Append one line, rotating first if `today` is past the active file's day.
+    pub fn write_line(&mut self, line: &str, today: NaiveDate) -> io::Result<()> {
+        if let Some(day) = self.day {
+            if day < today {
+                self.rotate(day)?;
+            }
+        }
+        if self.file.is_none() {
+            self.file = Some(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(self.dir.join(LOG_FILE))?,
+            );
+        }
+        self.day = Some(today);
+        let file = self.file.as_mut().expect("log file opened above");
+        file.write_all(line.as_bytes())?;
+        file.write_all(b"\n")
+    }
+
+    fn rotate(&mut self, day: NaiveDate) -> io::Result<()> {
+        self.file = None;
+        let rotated = self
+            .dir
+            .join(format!("{LOG_FILE}.{}", day.format("%Y-%m-%d")));
+        // Another process may have rotated already; appending to its file is fine.
+        if rotated.exists() {
+            let mut dst = OpenOptions::new().append(true).open(&rotated)?;
+            dst.write_all(&fs::read(self.dir.join(LOG_FILE))?)?;
+            fs::remove_file(self.dir.join(LOG_FILE))?;
+        } else if self.dir.join(LOG_FILE).exists() {
+            fs::rename(self.dir.join(LOG_FILE), rotated)?;
+        }
+        self.day = None;
+        self.prune()
+    }
+
+    /// Delete rotated files beyond the retention count, oldest first.
+    fn prune(&self) -> io::Result<()> {
+        let rotated = rotated_files(&self.dir)?;
+        let excess = rotated.len().saturating_sub(self.retention);
+        for path in &rotated[..excess] {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Rotated files (`atlas.log.YYYY-MM-DD`), oldest first.
+fn rotated_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let prefix = format!("{LOG_FILE}.");
+    let mut files: Vec<(NaiveDate, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let date = NaiveDate::parse_from_str(name.strip_prefix(&prefix)?, "%Y-%m-%d").ok()?;
+            Some((date, e.path()))
+        })
+        .collect();
+    files.sort();
+    Ok(files.into_iter().map(|(_, p)| p).collect())
+}
+
+/// All log files in read order: rotated files oldest first, then `atlas.log`.
+pub fn log_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files = rotated_files(dir)?;
+    let active = dir.join(LOG_FILE);
+    if active.exists() {
+        files.push(active);
+    }
+    Ok(files)
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  READING
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Filter applied by `atlas logs`.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// Minimum level; records below it are skipped.
+    pub level: Option<LogLevel>,
+    /// Case-insensitive substring matched against the raw line.
+    pub grep: Option<String>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, line: &str) -> bool {
+        if let Some(min) = self.level {
+            let level = serde_json::from_str::<Value>(line)
+                .ok()
+                .and_then(|v| v["level"].as_str().and_then(|l| l.parse::<LogLevel>().ok()));
+            match level {
+                Some(level) if level >= min => {}
+                _ => return false,
+            }
+        }
+        if let Some(needle) = &self.grep {
+            if !line.to_lowercase().contains(&needle.to_lowercase()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// The last `n` matching lines across all log files, oldest first.
+pub fn tail(dir: &Path, filter: &LogFilter, n: usize) -> io::Result<Vec<String>> {
+    let mut lines = std::collections::VecDeque::with_capacity(n.min(4096));
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    for path in log_files(dir)? {
+        let reader = BufReader::new(File::open(&path)?);
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() || !filter.matches(&line) {
+                continue;
+            }
+            if lines.len() == n {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+    }
+    Ok(lines.into())
+}
+
+/// Human-readable rendering of one JSON log line:
+/// `2026-01-02T03:04:05.000Z INFO  order placed coin=BTC size=0.1`.
+/// Lines that are not JSON are returned unchanged.
+pub fn format_line(line: &str) -> String {
+    let Ok(Value::Object(record)) = serde_json::from_str::<Value>(line) else {
+        return line.to_string();
+    };
+    let text = |v: &Value| match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let mut out = format!(
+        "{} {:<5} {}",
+        record.get("timestamp").map(text).unwrap_or_default(),
+        record.get("level").map(text).unwrap_or_default(),
+        record.get("message").map(text).unwrap_or_default(),
+    );
+    for (k, v) in &record {
+        if matches!(k.as_str(), "timestamp" | "level" | "target" | "message") {
+            continue;
+        }
+        out.push_str(&format!(" {k}={}", text(v)));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("atlas-logs-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn names(dir: &Path) -> Vec<String> {
+        log_files(dir)
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_rotation_and_retention() {
+        let dir = temp_dir("rotate");
+        let mut log = RotatingLog::open(&dir, 2).unwrap();
+        log.write_line("a", day("2026-01-01")).unwrap();
+        log.write_line("b", day("2026-01-01")).unwrap();
+        log.write_line("c", day("2026-01-02")).unwrap();
+        log.write_line("d", day("2026-01-03")).unwrap();
+        log.write_line("e", day("2026-01-04")).unwrap();
+
+        assert_eq!(
+            names(&dir),
+            vec!["atlas.log.2026-01-02", "atlas.log.2026-01-03", "atlas.log"]
+        );
+        let all = tail(&dir, &LogFilter::default(), 10).unwrap();
+        assert_eq!(all, vec!["c", "d", "e"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_tail_filters_level_and_grep() {
+        let dir = temp_dir("tail");
+        let mut log = RotatingLog::open(&dir, 7).unwrap();
+        let today = day("2026-01-01");
+        let ts = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        for (level, msg) in [
+            (LogLevel::Info, "order placed"),
+            (LogLevel::Warn, "order rejected"),
+            (LogLevel::Info, "fills sync complete"),
+            (LogLevel::Error, "transfer failed"),
+        ] {
+            let mut fields = Map::new();
+            fields.insert("message".into(), msg.into());
+            let line = format_record(ts, level, "atlas_hl::client", fields);
+            log.write_line(&line, today).unwrap();
+        }
+
+        let warn = LogFilter {
+            level: Some(LogLevel::Warn),
+            grep: None,
+        };
+        assert_eq!(tail(&dir, &warn, 10).unwrap().len(), 2);
+
+        let orders = LogFilter {
+            level: None,
+            grep: Some("ORDER".into()),
+        };
+        let hits = tail(&dir, &orders, 1).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].contains("order rejected"));
+        assert_eq!(
+            format_line(&hits[0]),
+            "2026-01-01T00:00:00.000Z WARN  order rejected"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_redaction() {
+        let key = format!("0x{}", "ab".repeat(32));
+        assert_eq!(
+            redact_value(&format!("signer {key} ok")),
+            "signer [redacted] ok"
+        );
+        assert_eq!(redact_value("key=atl_1234abcdef"), "key=[redacted]");
+        // Addresses (20 bytes) are not secrets.
+        let addr = format!("0x{}", "cd".repeat(20));
+        assert_eq!(redact_value(&addr), addr);
+
+        let mut c = FieldCollector::default();
+        c.insert("api_key", Value::String("plain".into()));
+        c.insert("private_key", Value::from(1));
+        c.insert("coin", Value::String("BTC".into()));
+        assert_eq!(c.fields["api_key"], REDACTED);
+        assert_eq!(c.fields["private_key"], REDACTED);
+        assert_eq!(c.fields["coin"], "BTC");
+    }
+
+    #[test]
+    fn test_level_parse_and_order() {
+        assert_eq!("WARN".parse::<LogLevel>().unwrap(), LogLevel::Warn);
+        assert_eq!("warning".parse::<LogLevel>().unwrap(), LogLevel::Warn);
+        assert!("loud".parse::<LogLevel>().is_err());
+        assert!(LogLevel::Error > LogLevel::Info);
+    }
+}
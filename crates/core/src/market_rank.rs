@@ -0,0 +1,226 @@
+//! Perp market ranking for `atlas market hl top`.
+//!
+//! Markets are ranked by one metric from their [`AssetContext`], best first:
+//! highest volume, change, open interest and funding, tightest spread.
+//! `--reverse` flips the metric order only — markets missing the metric stay
+//! at the bottom, and ties always break on symbol A→Z, so the same input
+//! gives the same order either way.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+use crate::output::TopMarketRow;
+use crate::types::AssetContext;
+
+/// Metric `atlas market hl top` sorts by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopSort {
+    /// 24h notional volume.
+    Volume,
+    /// 24h price change, %.
+    Change,
+    /// Open interest in USD.
+    Oi,
+    /// Current funding rate.
+    Funding,
+    /// Impact spread in bps; tightest first.
+    Spread,
+}
+
+impl TopSort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TopSort::Volume => "volume",
+            TopSort::Change => "change",
+            TopSort::Oi => "oi",
+            TopSort::Funding => "funding",
+            TopSort::Spread => "spread",
+        }
+    }
+
+    /// The metric for one market, if it has one.
+    pub fn metric(self, ctx: &AssetContext) -> Option<Decimal> {
+        match self {
+            TopSort::Volume => ctx.volume_24h,
+            TopSort::Change => ctx.change_24h_pct(),
+            TopSort::Oi => ctx.open_interest_usd(),
+            TopSort::Funding => ctx.funding_rate,
+            TopSort::Spread => ctx.spread_bps(),
+        }
+    }
+
+    /// Best first: descending, except spread where smaller is better.
+    fn order(self, a: Decimal, b: Decimal) -> Ordering {
+        match self {
+            TopSort::Spread => a.cmp(&b),
+            _ => b.cmp(&a),
+        }
+    }
+}
+
+impl fmt::Display for TopSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TopSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "volume" | "vol" => Ok(TopSort::Volume),
+            "change" | "chg" | "gainers" => Ok(TopSort::Change),
+            "oi" | "open-interest" => Ok(TopSort::Oi),
+            "funding" => Ok(TopSort::Funding),
+            "spread" => Ok(TopSort::Spread),
+            "losers" => Err("use --sort change --reverse for losers".into()),
+            other => Err(format!(
+                "unknown sort '{other}' (expected volume, change, oi, funding or spread)"
+            )),
+        }
+    }
+}
+
+/// Quoted markets with at least `min_volume` of 24h volume, ranked by
+/// `sort`. Markets without a mid price (delisted) are dropped.
+pub fn rank<'a>(
+    ctxs: &'a [AssetContext],
+    sort: TopSort,
+    reverse: bool,
+    min_volume: Option<Decimal>,
+) -> Vec<&'a AssetContext> {
+    let mut ranked: Vec<&AssetContext> = ctxs
+        .iter()
+        .filter(|c| c.mid_price.is_some())
+        .filter(|c| min_volume.map_or(true, |min| c.volume_24h.is_some_and(|v| v >= min)))
+        .collect();
+    ranked.sort_by(|a, b| {
+        let by_metric = match (sort.metric(a), sort.metric(b)) {
+            (Some(x), Some(y)) if reverse => sort.order(x, y).reverse(),
+            (Some(x), Some(y)) => sort.order(x, y),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        by_metric.then_with(|| a.symbol.cmp(&b.symbol))
+    });
+    ranked
+}
+
+/// Output row with every metric at full precision.
+pub fn top_row(ctx: &AssetContext) -> TopMarketRow {
+    let s = |d: Option<Decimal>| d.map(|d| d.normalize().to_string());
+    TopMarketRow {
+        symbol: ctx.symbol.clone(),
+        mid_price: s(ctx.mid_price),
+        mark_price: s(ctx.mark_price),
+        volume_24h: s(ctx.volume_24h),
+        change_24h_pct: s(ctx.change_24h_pct()),
+        open_interest_usd: s(ctx.open_interest_usd()),
+        funding_rate: s(ctx.funding_rate),
+        funding_apr_pct: s(ctx.funding_apr_pct()),
+        spread_bps: s(ctx.spread_bps()),
+        best_bid: s(ctx.impact_bid),
+        best_ask: s(ctx.impact_ask),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Protocol;
+
+    fn ctx(symbol: &str, volume: i64, prev: i64, oi: i64, funding: Option<&str>) -> AssetContext {
+        AssetContext {
+            symbol: symbol.into(),
+            protocol: Protocol::Hyperliquid,
+            mark_price: Some(Decimal::from(100)),
+            mid_price: Some(Decimal::from(100)),
+            oracle_price: None,
+            funding_rate: funding.map(|f| f.parse().unwrap()),
+            funding_interval_hours: 1,
+            premium: None,
+            open_interest: Some(Decimal::from(oi)),
+            volume_24h: Some(Decimal::from(volume)),
+            volume_24h_base: None,
+            prev_day_price: Some(Decimal::from(prev)),
+            impact_bid: Some(Decimal::from(100) - Decimal::from(volume) / Decimal::from(1000)),
+            impact_ask: Some(Decimal::from(100)),
+        }
+    }
+
+    fn fixture() -> Vec<AssetContext> {
+        let mut dead = ctx("DEAD", 5, 100, 1, None);
+        dead.mid_price = None;
+        vec![
+            ctx("ETH", 3000, 95, 500, Some("0.00002")),
+            ctx("BTC", 9000, 98, 900, Some("0.00001")),
+            ctx("SOL", 1000, 110, 500, Some("-0.00003")),
+            ctx("ARB", 1000, 90, 50, None),
+            dead,
+        ]
+    }
+
+    fn symbols(ranked: &[&AssetContext]) -> Vec<String> {
+        ranked.iter().map(|c| c.symbol.clone()).collect()
+    }
+
+    #[test]
+    fn test_rank_each_key() {
+        let ctxs = fixture();
+        let order = |sort| symbols(&rank(&ctxs, sort, false, None));
+        // Ties on volume (ARB, SOL) break on symbol; DEAD has no mid
+        assert_eq!(order(TopSort::Volume), ["BTC", "ETH", "ARB", "SOL"]);
+        assert_eq!(order(TopSort::Change), ["ARB", "ETH", "BTC", "SOL"]);
+        assert_eq!(order(TopSort::Oi), ["BTC", "ETH", "SOL", "ARB"]);
+        // No funding rate → last
+        assert_eq!(order(TopSort::Funding), ["ETH", "BTC", "SOL", "ARB"]);
+        // Spread grows with volume in the fixture: tightest first
+        assert_eq!(order(TopSort::Spread), ["ARB", "SOL", "ETH", "BTC"]);
+    }
+
+    #[test]
+    fn test_reverse_keeps_missing_last_and_symbol_tiebreak() {
+        let ctxs = fixture();
+        let ranked = symbols(&rank(&ctxs, TopSort::Volume, true, None));
+        assert_eq!(ranked, ["ARB", "SOL", "ETH", "BTC"]);
+        let ranked = symbols(&rank(&ctxs, TopSort::Funding, true, None));
+        assert_eq!(ranked, ["SOL", "BTC", "ETH", "ARB"]);
+    }
+
+    #[test]
+    fn test_min_volume_filter() {
+        let ctxs = fixture();
+        let min = Some(Decimal::from(3000));
+        let ranked = symbols(&rank(&ctxs, TopSort::Change, false, min));
+        assert_eq!(ranked, ["ETH", "BTC"]);
+    }
+
+    #[test]
+    fn test_row_has_every_metric_at_full_precision() {
+        let row = top_row(&ctx("ETH", 3000, 95, 500, Some("0.0000125")));
+        assert_eq!(row.volume_24h.as_deref(), Some("3000"));
+        assert_eq!(row.funding_rate.as_deref(), Some("0.0000125"));
+        assert_eq!(row.funding_apr_pct.as_deref(), Some("10.95"));
+        assert_eq!(row.open_interest_usd.as_deref(), Some("50000"));
+        assert_eq!(row.spread_bps.as_deref(), Some("300"));
+        // Not rounded for display
+        let change = row.change_24h_pct.unwrap();
+        assert!(change.starts_with("5.2631578947368421"), "{change}");
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        assert_eq!("vol".parse::<TopSort>(), Ok(TopSort::Volume));
+        assert_eq!("OI".parse::<TopSort>(), Ok(TopSort::Oi));
+        assert!("losers"
+            .parse::<TopSort>()
+            .unwrap_err()
+            .contains("--reverse"));
+        assert!("price".parse::<TopSort>().is_err());
+    }
+}
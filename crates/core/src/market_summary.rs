@@ -0,0 +1,438 @@
+//! Market dashboard for `atlas market hl summary`.
+//!
+//! Everything comes from one `metaAndAssetCtxs` fetch: the headline
+//! figures, one ranked list per requested section (via
+//! [`crate::market_rank`]) and, with `--watchlist`, a row per watched coin
+//! joined against the account's open positions. Positions in coins that
+//! are not watched are listed separately rather than dropped.
+//!
+//! Hyperliquid only reports current open interest, so the 24h OI change
+//! compares it with the snapshot `atlas market hl oi record` took nearest
+//! to 24h ago; without one the change is `None`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+use crate::db::DbOiSnapshot;
+use crate::market_rank::{rank, TopSort};
+use crate::output::{MarketSummaryOutput, SummaryRow, WatchRow, WatchlistSummary};
+use crate::types::{AssetContext, Position, Side};
+
+/// How far back the OI change looks.
+pub const OI_CHANGE_WINDOW_MS: i64 = 24 * 3_600_000;
+
+/// A snapshot further than this from 24h ago is not used as the reference.
+pub const OI_REFERENCE_TOLERANCE_MS: i64 = 3_600_000;
+
+/// A ranked list on the dashboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummarySection {
+    Gainers,
+    Losers,
+    Volume,
+    Funding,
+    Oi,
+}
+
+/// Shown when `--sections` is not given (and no `--watchlist`).
+pub const DEFAULT_SECTIONS: [SummarySection; 3] = [
+    SummarySection::Gainers,
+    SummarySection::Losers,
+    SummarySection::Volume,
+];
+
+impl SummarySection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SummarySection::Gainers => "gainers",
+            SummarySection::Losers => "losers",
+            SummarySection::Volume => "volume",
+            SummarySection::Funding => "funding",
+            SummarySection::Oi => "oi",
+        }
+    }
+
+    /// The `top` ranking behind the section.
+    fn ranking(self) -> (TopSort, bool) {
+        match self {
+            SummarySection::Gainers => (TopSort::Change, false),
+            SummarySection::Losers => (TopSort::Change, true),
+            SummarySection::Volume => (TopSort::Volume, false),
+            SummarySection::Funding => (TopSort::Funding, false),
+            SummarySection::Oi => (TopSort::Oi, false),
+        }
+    }
+}
+
+impl fmt::Display for SummarySection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SummarySection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "gainers" => Ok(SummarySection::Gainers),
+            "losers" => Ok(SummarySection::Losers),
+            "volume" | "vol" => Ok(SummarySection::Volume),
+            "funding" => Ok(SummarySection::Funding),
+            "oi" | "open-interest" => Ok(SummarySection::Oi),
+            other => Err(format!(
+                "unknown section '{other}' (expected gainers, losers, volume, funding or oi)"
+            )),
+        }
+    }
+}
+
+/// `--sections` values in the order given, without repeats.
+pub fn parse_sections(raw: &[String]) -> Result<Vec<SummarySection>, String> {
+    let mut sections = Vec::new();
+    for name in raw {
+        let section: SummarySection = name.parse()?;
+        if !sections.contains(&section) {
+            sections.push(section);
+        }
+    }
+    Ok(sections)
+}
+
+/// Open interest per coin (base units) from the snapshot of each coin
+/// nearest `at_ms`, ignoring any more than [`OI_REFERENCE_TOLERANCE_MS`]
+/// away.
+pub fn oi_reference(snapshots: &[DbOiSnapshot], at_ms: i64) -> HashMap<String, Decimal> {
+    let mut nearest: HashMap<&str, (i64, &DbOiSnapshot)> = HashMap::new();
+    for s in snapshots {
+        let distance = (s.time_ms - at_ms).abs();
+        if distance > OI_REFERENCE_TOLERANCE_MS {
+            continue;
+        }
+        let entry = nearest.entry(&s.coin).or_insert((distance, s));
+        if distance < entry.0 {
+            *entry = (distance, s);
+        }
+    }
+    nearest
+        .into_iter()
+        .filter_map(|(coin, (_, s))| Some((coin.to_string(), s.open_interest.parse().ok()?)))
+        .collect()
+}
+
+/// Change of `ctx`'s open interest against the reference, in percent.
+pub fn oi_change_pct(ctx: &AssetContext, oi_ref: &HashMap<String, Decimal>) -> Option<Decimal> {
+    let before = oi_ref.get(&ctx.symbol).filter(|oi| !oi.is_zero())?;
+    Some((ctx.open_interest? - before) / before * Decimal::ONE_HUNDRED)
+}
+
+/// The dashboard over `ctxs`: headline figures and `limit` rows for each
+/// of `sections`.
+pub fn summarize(
+    ctxs: &[AssetContext],
+    sections: &[SummarySection],
+    limit: usize,
+    oi_ref: &HashMap<String, Decimal>,
+) -> MarketSummaryOutput {
+    let quoted: Vec<&AssetContext> = ctxs.iter().filter(|c| c.mid_price.is_some()).collect();
+    let changes = || quoted.iter().filter_map(|c| c.change_24h_pct());
+    let mut output = MarketSummaryOutput {
+        markets: quoted.len(),
+        volume_24h: quoted
+            .iter()
+            .filter_map(|c| c.volume_24h)
+            .sum::<Decimal>()
+            .normalize()
+            .to_string(),
+        advancing: changes().filter(|c| *c > Decimal::ZERO).count(),
+        declining: changes().filter(|c| *c < Decimal::ZERO).count(),
+        sections: sections.iter().map(|s| s.to_string()).collect(),
+        gainers: None,
+        losers: None,
+        volume: None,
+        funding: None,
+        oi: None,
+        watchlist: None,
+    };
+    for &section in sections {
+        let (sort, reverse) = section.ranking();
+        let rows: Vec<SummaryRow> = rank(ctxs, sort, reverse, None)
+            .into_iter()
+            .take(limit)
+            .map(|c| summary_row(c, oi_ref))
+            .collect();
+        let slot = match section {
+            SummarySection::Gainers => &mut output.gainers,
+            SummarySection::Losers => &mut output.losers,
+            SummarySection::Volume => &mut output.volume,
+            SummarySection::Funding => &mut output.funding,
+            SummarySection::Oi => &mut output.oi,
+        };
+        *slot = Some(rows);
+    }
+    output
+}
+
+/// Only the contexts of watched coins (case-insensitive).
+pub fn watched(ctxs: &[AssetContext], watchlist: &[String]) -> Vec<AssetContext> {
+    ctxs.iter()
+        .filter(|c| is_watched(&c.symbol, watchlist))
+        .cloned()
+        .collect()
+}
+
+/// One row per watched coin in watchlist order, with the open position
+/// size where there is one, and a row per position in an unwatched coin.
+/// `positions` is `None` when the account could not be read.
+pub fn watchlist_summary(
+    ctxs: &[AssetContext],
+    watchlist: &[String],
+    positions: Option<&[Position]>,
+    oi_ref: &HashMap<String, Decimal>,
+) -> WatchlistSummary {
+    let find = |coin: &str| ctxs.iter().find(|c| c.symbol.eq_ignore_ascii_case(coin));
+    let size_of = |coin: &str| {
+        positions?
+            .iter()
+            .find(|p| p.symbol.eq_ignore_ascii_case(coin) && !p.size.is_zero())
+            .map(signed_size)
+    };
+
+    let mut coins = Vec::new();
+    let mut missing = Vec::new();
+    for coin in watchlist {
+        match find(coin) {
+            Some(ctx) => coins.push(watch_row(&ctx.symbol, Some(ctx), size_of(coin), oi_ref)),
+            None => missing.push(coin.clone()),
+        }
+    }
+
+    let unwatched_positions = positions
+        .unwrap_or_default()
+        .iter()
+        .filter(|p| !p.size.is_zero() && !is_watched(&p.symbol, watchlist))
+        .map(|p| watch_row(&p.symbol, find(&p.symbol), Some(signed_size(p)), oi_ref))
+        .collect();
+
+    WatchlistSummary {
+        coins,
+        missing,
+        unwatched_positions,
+        positions_error: None,
+    }
+}
+
+fn is_watched(symbol: &str, watchlist: &[String]) -> bool {
+    watchlist
+        .iter()
+        .any(|w| w.trim().eq_ignore_ascii_case(symbol))
+}
+
+/// Negative for shorts.
+fn signed_size(p: &Position) -> Decimal {
+    match p.side {
+        Side::Sell => -p.size.abs(),
+        Side::Buy => p.size.abs(),
+    }
+}
+
+fn s(d: Option<Decimal>) -> Option<String> {
+    d.map(|d| d.normalize().to_string())
+}
+
+fn summary_row(ctx: &AssetContext, oi_ref: &HashMap<String, Decimal>) -> SummaryRow {
+    SummaryRow {
+        symbol: ctx.symbol.clone(),
+        price: s(ctx.mid_price.or(ctx.mark_price)),
+        change_24h_pct: s(ctx.change_24h_pct().map(|c| c.round_dp(4))),
+        volume_24h: s(ctx.volume_24h),
+        funding_rate: s(ctx.funding_rate),
+        open_interest_usd: s(ctx.open_interest_usd().map(|v| v.round_dp(2))),
+        oi_change_24h_pct: s(oi_change_pct(ctx, oi_ref).map(|c| c.round_dp(4))),
+    }
+}
+
+fn watch_row(
+    symbol: &str,
+    ctx: Option<&AssetContext>,
+    position_size: Option<Decimal>,
+    oi_ref: &HashMap<String, Decimal>,
+) -> WatchRow {
+    WatchRow {
+        symbol: symbol.to_string(),
+        price: s(ctx.and_then(|c| c.mid_price.or(c.mark_price))),
+        change_24h_pct: s(ctx
+            .and_then(AssetContext::change_24h_pct)
+            .map(|c| c.round_dp(4))),
+        funding_rate: s(ctx.and_then(|c| c.funding_rate)),
+        open_interest_usd: s(ctx
+            .and_then(AssetContext::open_interest_usd)
+            .map(|v| v.round_dp(2))),
+        oi_change_24h_pct: s(ctx
+            .and_then(|c| oi_change_pct(c, oi_ref))
+            .map(|c| c.round_dp(4))),
+        position_size: s(position_size),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Protocol;
+
+    fn ctx(symbol: &str, mid: i64, prev: i64, volume: i64, oi: i64) -> AssetContext {
+        AssetContext {
+            symbol: symbol.into(),
+            protocol: Protocol::Hyperliquid,
+            mark_price: Some(Decimal::from(mid)),
+            mid_price: Some(Decimal::from(mid)),
+            oracle_price: None,
+            funding_rate: Some(Decimal::new(125, 7)),
+            funding_interval_hours: 1,
+            premium: None,
+            open_interest: Some(Decimal::from(oi)),
+            volume_24h: Some(Decimal::from(volume)),
+            volume_24h_base: None,
+            prev_day_price: Some(Decimal::from(prev)),
+            impact_bid: None,
+            impact_ask: None,
+        }
+    }
+
+    fn position(symbol: &str, side: Side, size: i64) -> Position {
+        Position {
+            protocol: Protocol::Hyperliquid,
+            symbol: symbol.into(),
+            side,
+            size: Decimal::from(size),
+            entry_price: None,
+            mark_price: None,
+            unrealized_pnl: None,
+            leverage: None,
+            margin: None,
+            liquidation_price: None,
+            margin_mode: None,
+        }
+    }
+
+    fn snapshot(coin: &str, time_ms: i64, oi: &str) -> DbOiSnapshot {
+        DbOiSnapshot {
+            protocol: "hyperliquid".into(),
+            coin: coin.into(),
+            time_ms,
+            open_interest: oi.into(),
+            mark_px: "1".into(),
+            volume_24h: "0".into(),
+            funding: "0".into(),
+        }
+    }
+
+    fn fixture() -> Vec<AssetContext> {
+        vec![
+            ctx("BTC", 100, 90, 9000, 10),
+            ctx("ETH", 100, 110, 3000, 50),
+            ctx("SOL", 100, 95, 1000, 20),
+            ctx("HYPE", 100, 100, 500, 5),
+        ]
+    }
+
+    #[test]
+    fn test_parse_sections_keeps_order_and_drops_repeats() {
+        let raw: Vec<String> = ["oi", "Gainers", "vol", "oi"].map(String::from).to_vec();
+        assert_eq!(
+            parse_sections(&raw).unwrap(),
+            [
+                SummarySection::Oi,
+                SummarySection::Gainers,
+                SummarySection::Volume
+            ]
+        );
+        let err = parse_sections(&["price".to_string()]).unwrap_err();
+        assert!(err.contains("expected gainers"), "{err}");
+    }
+
+    #[test]
+    fn test_summarize_fills_only_requested_sections() {
+        let out = summarize(
+            &fixture(),
+            &[SummarySection::Losers, SummarySection::Volume],
+            2,
+            &HashMap::new(),
+        );
+        assert_eq!((out.markets, out.advancing, out.declining), (4, 2, 1));
+        assert_eq!(out.volume_24h, "13500");
+        assert_eq!(out.sections, ["losers", "volume"]);
+        assert!(out.gainers.is_none() && out.funding.is_none() && out.oi.is_none());
+        let symbols = |rows: &Option<Vec<SummaryRow>>| {
+            rows.as_ref()
+                .unwrap()
+                .iter()
+                .map(|r| r.symbol.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(symbols(&out.losers), ["ETH", "HYPE"]);
+        assert_eq!(symbols(&out.volume), ["BTC", "ETH"]);
+
+        let json = serde_json::to_value(&out).unwrap();
+        assert_eq!(json["losers"][0]["symbol"], "ETH");
+        assert!(json.get("gainers").is_none());
+    }
+
+    #[test]
+    fn test_oi_reference_uses_nearest_snapshot_in_tolerance() {
+        let day_ago = 10 * OI_CHANGE_WINDOW_MS;
+        let snapshots = [
+            snapshot("BTC", day_ago - 600_000, "8"),
+            snapshot("BTC", day_ago + 60_000, "8"),
+            snapshot("ETH", day_ago - OI_REFERENCE_TOLERANCE_MS - 1, "40"),
+        ];
+        let oi_ref = oi_reference(&snapshots, day_ago);
+        assert_eq!(oi_ref.get("BTC"), Some(&Decimal::from(8)));
+        assert!(!oi_ref.contains_key("ETH"));
+
+        let ctxs = fixture();
+        assert_eq!(oi_change_pct(&ctxs[0], &oi_ref), Some(Decimal::from(25)));
+        assert_eq!(oi_change_pct(&ctxs[1], &oi_ref), None);
+    }
+
+    #[test]
+    fn test_watchlist_joins_positions_and_lists_unwatched() {
+        let ctxs = fixture();
+        let watchlist: Vec<String> = ["eth", "BTC", "DOGE"].map(String::from).to_vec();
+        let positions = [
+            position("ETH", Side::Sell, 2),
+            position("SOL", Side::Buy, 30),
+            position("BTC", Side::Buy, 0),
+        ];
+        let w = watchlist_summary(&ctxs, &watchlist, Some(&positions), &HashMap::new());
+
+        let rows: Vec<_> = w
+            .coins
+            .iter()
+            .map(|r| (r.symbol.as_str(), r.position_size.as_deref()))
+            .collect();
+        assert_eq!(rows, [("ETH", Some("-2")), ("BTC", None)]);
+        assert_eq!(w.missing, ["DOGE"]);
+        assert_eq!(w.unwatched_positions.len(), 1);
+        assert_eq!(w.unwatched_positions[0].symbol, "SOL");
+        assert_eq!(
+            w.unwatched_positions[0].position_size.as_deref(),
+            Some("30")
+        );
+        assert_eq!(
+            w.unwatched_positions[0].change_24h_pct.as_deref(),
+            Some("5.2632")
+        );
+
+        // Account unavailable: no sizes, nothing unwatched
+        let w = watchlist_summary(&ctxs, &watchlist, None, &HashMap::new());
+        assert!(w.coins.iter().all(|r| r.position_size.is_none()));
+        assert!(w.unwatched_positions.is_empty());
+
+        let only = watched(&ctxs, &watchlist);
+        assert_eq!(only.len(), 2);
+    }
+}
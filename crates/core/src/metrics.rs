@@ -0,0 +1,334 @@
+//! Heartbeat metrics for long-running commands (`atlas stream …`).
+//!
+//! A [`StreamMetrics`] registry is shared between the event loop, which
+//! records every message and reconnect, and a tiny HTTP endpoint started by
+//! [`serve`] that exposes it as plain text in the Prometheus format:
+//!
+//! - `GET /metrics` — message counters per channel, last message age,
+//!   reconnects and uptime
+//! - `GET /healthz` — `200 ok`, or `503 stale` once no message has arrived
+//!   within the staleness threshold, so systemd or Kubernetes can restart us
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Default `--stale-after`: no message for this long fails `/healthz`.
+pub const DEFAULT_STALE_AFTER_SECS: u64 = 60;
+
+/// Longest request head read; the endpoint only serves two GET paths.
+const MAX_REQUEST_HEAD: usize = 4096;
+
+/// Counters for one long-running command, updated from its event loop.
+#[derive(Debug)]
+pub struct StreamMetrics {
+    started: Instant,
+    stale_after: Duration,
+    messages: Mutex<BTreeMap<String, u64>>,
+    last_message: Mutex<Option<Instant>>,
+    connected_once: AtomicBool,
+    reconnects: AtomicU64,
+}
+
+impl StreamMetrics {
+    pub fn new(stale_after: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            stale_after,
+            messages: Mutex::new(BTreeMap::new()),
+            last_message: Mutex::new(None),
+            connected_once: AtomicBool::new(false),
+            reconnects: AtomicU64::new(0),
+        }
+    }
+
+    /// Count one message on `channel` and reset the staleness clock.
+    pub fn record_message(&self, channel: &str) {
+        if let Ok(mut messages) = self.messages.lock() {
+            *messages.entry(channel.to_string()).or_default() += 1;
+        }
+        if let Ok(mut last) = self.last_message.lock() {
+            *last = Some(Instant::now());
+        }
+    }
+
+    /// Note an established connection; each one after the first is a
+    /// reconnect.
+    pub fn record_connected(&self) {
+        if self.connected_once.swap(true, Ordering::Relaxed) {
+            self.record_reconnect();
+        }
+    }
+
+    pub fn record_reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn reconnects(&self) -> u64 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+
+    /// Messages received on `channel` so far.
+    pub fn messages(&self, channel: &str) -> u64 {
+        self.messages
+            .lock()
+            .ok()
+            .and_then(|m| m.get(channel).copied())
+            .unwrap_or(0)
+    }
+
+    /// Time since the last message — or since start, before the first one.
+    pub fn last_message_age(&self) -> Duration {
+        let last = self.last_message.lock().ok().and_then(|l| *l);
+        last.unwrap_or(self.started).elapsed()
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// False once no message has arrived within the staleness threshold.
+    /// A fresh process gets the same grace period for its first message.
+    pub fn is_healthy(&self) -> bool {
+        self.last_message_age() <= self.stale_after
+    }
+
+    /// The registry in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let messages: Vec<String> = self
+            .messages
+            .lock()
+            .map(|m| {
+                m.iter()
+                    .map(|(channel, n)| {
+                        format!("atlas_stream_messages_total{{channel=\"{channel}\"}} {n}")
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut out = String::new();
+        write_metric(
+            &mut out,
+            "atlas_stream_messages_total",
+            "counter",
+            "Messages received, by channel.",
+            &messages,
+        );
+        write_metric(
+            &mut out,
+            "atlas_stream_last_message_age_seconds",
+            "gauge",
+            "Seconds since the last message (since start before the first).",
+            &[format!(
+                "atlas_stream_last_message_age_seconds {:.3}",
+                self.last_message_age().as_secs_f64()
+            )],
+        );
+        write_metric(
+            &mut out,
+            "atlas_stream_reconnects_total",
+            "counter",
+            "WebSocket reconnects since start.",
+            &[format!(
+                "atlas_stream_reconnects_total {}",
+                self.reconnects()
+            )],
+        );
+        write_metric(
+            &mut out,
+            "atlas_stream_uptime_seconds",
+            "gauge",
+            "Seconds since the command started.",
+            &[format!(
+                "atlas_stream_uptime_seconds {:.3}",
+                self.uptime().as_secs_f64()
+            )],
+        );
+        write_metric(
+            &mut out,
+            "atlas_stream_healthy",
+            "gauge",
+            "1 while messages arrive within the staleness threshold, else 0.",
+            &[format!(
+                "atlas_stream_healthy {}",
+                u8::from(self.is_healthy())
+            )],
+        );
+        out
+    }
+
+    /// Status and body for a request path.
+    fn respond(&self, path: &str) -> (u16, String) {
+        match path {
+            "/metrics" => (200, self.render()),
+            "/healthz" if self.is_healthy() => (200, "ok\n".to_string()),
+            "/healthz" => (
+                503,
+                format!(
+                    "stale: no message for {}s\n",
+                    self.last_message_age().as_secs()
+                ),
+            ),
+            _ => (404, "not found\n".to_string()),
+        }
+    }
+}
+
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, samples: &[String]) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for sample in samples {
+        let _ = writeln!(out, "{sample}");
+    }
+}
+
+/// `--metrics-port` value: a bare port listens on localhost, `HOST:PORT`
+/// (e.g. `0.0.0.0:9100` for a Kubernetes probe) on that address.
+pub fn parse_listen_addr(s: &str) -> Result<SocketAddr> {
+    if let Ok(port) = s.parse::<u16>() {
+        return Ok(SocketAddr::from(([127, 0, 0, 1], port)));
+    }
+    s.parse()
+        .with_context(|| format!("Invalid metrics address '{s}': expected PORT or HOST:PORT"))
+}
+
+/// Serve `metrics` over HTTP on `addr` in the background. Returns the bound
+/// address (useful with port 0).
+pub async fn serve(metrics: Arc<StreamMetrics>, addr: SocketAddr) -> Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind metrics endpoint on {addr}"))?;
+    let local = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((socket, _)) = listener.accept().await else {
+                continue;
+            };
+            let metrics = metrics.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle(socket, &metrics).await {
+                    tracing::debug!("metrics request failed: {e}");
+                }
+            });
+        }
+    });
+
+    Ok(local)
+}
+
+async fn handle(mut socket: TcpStream, metrics: &StreamMetrics) -> Result<()> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 512];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_HEAD {
+        let n = socket.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let (status, body) = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(target)) => {
+            let path = target.split('?').next().unwrap_or(target);
+            metrics.respond(path)
+        }
+        _ => (405, "method not allowed\n".to_string()),
+    };
+    let reason = match status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Service Unavailable",
+    };
+    let response = format!(
+        "HTTP/1.1 {status} {reason}\r\n\
+         Content-Type: text/plain; version=0.0.4; charset=utf-8\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    );
+    socket.write_all(response.as_bytes()).await?;
+    socket.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_messages_and_reconnects() {
+        let m = StreamMetrics::new(Duration::from_secs(60));
+        m.record_message("trades");
+        m.record_message("trades");
+        m.record_message("l2Book");
+        m.record_connected();
+        m.record_connected();
+        assert_eq!(m.messages("trades"), 2);
+        assert_eq!(m.reconnects(), 1);
+
+        let text = m.render();
+        assert!(text.contains("# TYPE atlas_stream_messages_total counter"));
+        assert!(text.contains("atlas_stream_messages_total{channel=\"trades\"} 2"));
+        assert!(text.contains("atlas_stream_messages_total{channel=\"l2Book\"} 1"));
+        assert!(text.contains("atlas_stream_reconnects_total 1"));
+        assert!(text.contains("atlas_stream_healthy 1"));
+        assert!(text.contains("atlas_stream_uptime_seconds "));
+    }
+
+    #[test]
+    fn test_healthz_goes_stale() {
+        let m = StreamMetrics::new(Duration::from_secs(60));
+        assert_eq!(m.respond("/healthz").0, 200);
+
+        let m = StreamMetrics::new(Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(2));
+        let (status, body) = m.respond("/healthz");
+        assert_eq!(status, 503);
+        assert!(body.starts_with("stale"));
+        assert!(m.render().contains("atlas_stream_healthy 0"));
+
+        assert_eq!(m.respond("/nope").0, 404);
+    }
+
+    #[test]
+    fn test_parse_listen_addr() {
+        assert_eq!(
+            parse_listen_addr("9100").unwrap(),
+            "127.0.0.1:9100".parse().unwrap()
+        );
+        assert_eq!(
+            parse_listen_addr("0.0.0.0:9100").unwrap(),
+            "0.0.0.0:9100".parse().unwrap()
+        );
+        assert!(parse_listen_addr("localhost").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_serve_over_http() {
+        let m = Arc::new(StreamMetrics::new(Duration::from_secs(60)));
+        m.record_message("allMids");
+        let addr = serve(m, "127.0.0.1:0".parse().unwrap()).await.unwrap();
+
+        let mut socket = TcpStream::connect(addr).await.unwrap();
+        socket
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        socket.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("atlas_stream_messages_total{channel=\"allMids\"} 1"));
+    }
+}
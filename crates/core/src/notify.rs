@@ -0,0 +1,401 @@
+//! Event notifications for `atlas notify`: fills, order cancels and
+//! liquidation warnings pushed to a webhook and/or a local command.
+//!
+//! Every event is one JSON [`Notification`]. The webhook receives it as a
+//! POST body, signed with `notify.secret` when one is set; the exec hook
+//! receives it on stdin. Failed deliveries are logged and retried with
+//! exponential backoff (`notify.max_retries`, `notify.backoff_ms`).
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::process::Stdio;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+use tokio::io::AsyncWriteExt;
+
+use crate::config::NotifyConfig;
+
+/// Webhook header carrying `sha256=<hex>`, the HMAC-SHA256 of the raw body
+/// keyed with `notify.secret`.
+pub const SIGNATURE_HEADER: &str = "X-Atlas-Signature";
+
+/// Webhook header carrying the event name, e.g. `fill`.
+pub const EVENT_HEADER: &str = "X-Atlas-Event";
+
+/// Longest wait between two attempts.
+const MAX_BACKOFF_MS: u64 = 60_000;
+
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+const EXEC_TIMEOUT_SECS: u64 = 30;
+
+/// One event, as POSTed to the webhook and written to the exec hook's stdin.
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    /// `fill`, `order_cancelled`, `liquidation_warning` or `test`.
+    pub event: String,
+    pub timestamp_ms: i64,
+    pub network: String,
+    /// Account the event belongs to.
+    pub address: Option<String>,
+    /// Event-specific fields (see `docs/json-schemas.md`).
+    pub data: Value,
+}
+
+impl Notification {
+    pub fn new(event: &str, network: &str, address: Option<String>, data: Value) -> Self {
+        Self {
+            event: event.into(),
+            timestamp_ms: chrono::Utc::now().timestamp_millis(),
+            network: network.into(),
+            address,
+            data,
+        }
+    }
+
+    /// What `atlas notify test` sends: a `test` event whose data is shaped
+    /// like a fill, so receivers can exercise their parsing.
+    pub fn sample(network: &str, address: Option<String>) -> Self {
+        let now = chrono::Utc::now().timestamp_millis();
+        let data = serde_json::json!({
+            "message": "Test notification from atlas notify test",
+            "order_id": 0,
+            "symbol": "BTC",
+            "side": "buy",
+            "size": "0.001",
+            "price": "67000",
+            "fee": "0.0301",
+            "timestamp": now,
+        });
+        Self::new("test", network, address, data)
+    }
+}
+
+/// Data of a `liquidation_warning` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct LiqWarning {
+    pub symbol: String,
+    /// `long` or `short`.
+    pub side: String,
+    pub size: Decimal,
+    pub mark_price: Decimal,
+    pub liquidation_price: Decimal,
+    /// Distance from mark to liquidation, as % of mark.
+    pub distance_pct: Decimal,
+    /// `notify.liq_warn_pct` at the time of the warning.
+    pub warn_pct: Decimal,
+}
+
+/// `sha256=<hex>` HMAC of `body` keyed with `secret`, for [`SIGNATURE_HEADER`].
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// How far the mark is from the liquidation price, as % of the mark.
+pub fn liq_distance_pct(mark: Decimal, liquidation: Decimal) -> Option<Decimal> {
+    if mark <= Decimal::ZERO || liquidation <= Decimal::ZERO {
+        return None;
+    }
+    Some((mark - liquidation).abs() / mark * Decimal::ONE_HUNDRED)
+}
+
+/// Tracks which positions have been warned about, so a position close to
+/// liquidation produces one warning rather than one per poll. A position
+/// is warned again once it has recovered to 1.5× the threshold.
+#[derive(Debug, Default)]
+pub struct LiqWatch {
+    warned: HashSet<String>,
+}
+
+impl LiqWatch {
+    /// Should `symbol`, `distance_pct` from liquidation, be warned about now?
+    pub fn check(&mut self, symbol: &str, distance_pct: Decimal, warn_pct: Decimal) -> bool {
+        if distance_pct <= warn_pct {
+            return self.warned.insert(symbol.to_string());
+        }
+        if distance_pct > warn_pct * Decimal::new(15, 1) {
+            self.warned.remove(symbol);
+        }
+        false
+    }
+
+    /// Forget positions that are no longer open.
+    pub fn retain_open(&mut self, open: &[String]) {
+        self.warned.retain(|s| open.contains(s));
+    }
+}
+
+/// Delay before retry number `attempt` (0-based): `base_ms` doubled per
+/// attempt, capped at a minute.
+pub fn backoff_ms(base_ms: u64, attempt: u32) -> u64 {
+    base_ms
+        .saturating_mul(1u64 << attempt.min(16))
+        .min(MAX_BACKOFF_MS)
+}
+
+/// Outcome of delivering one notification to one target.
+#[derive(Debug, Clone, Serialize)]
+pub struct Delivery {
+    /// `webhook` or `exec`.
+    pub target: String,
+    pub ok: bool,
+    pub attempts: u32,
+    /// Last failure, when `ok` is false.
+    pub error: Option<String>,
+}
+
+/// A failed attempt; `retry` is false when trying again cannot help
+/// (e.g. the webhook answered 404).
+struct Failure {
+    message: String,
+    retry: bool,
+}
+
+impl Failure {
+    fn retry(message: String) -> Self {
+        Self {
+            message,
+            retry: true,
+        }
+    }
+}
+
+/// Send `notification` to every configured target, retrying failures.
+/// Returns one [`Delivery`] per target; empty when none is configured.
+pub async fn deliver(config: &NotifyConfig, notification: &Notification) -> Vec<Delivery> {
+    let body = serde_json::to_vec(notification).expect("Notification serializes");
+    let event = notification.event.as_str();
+    let mut deliveries = Vec::new();
+    if let Some(url) = &config.webhook {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+            .build()
+            .expect("Failed to build HTTP client");
+        let secret = config.secret.as_deref();
+        deliveries.push(
+            with_retry(config, "webhook", event, || {
+                post_webhook(&client, url, secret, event, &body)
+            })
+            .await,
+        );
+    }
+    if let Some(command) = &config.exec {
+        deliveries
+            .push(with_retry(config, "exec", event, || run_exec(command, event, &body)).await);
+    }
+    deliveries
+}
+
+async fn with_retry<F, Fut>(
+    config: &NotifyConfig,
+    target: &str,
+    event: &str,
+    mut attempt: F,
+) -> Delivery
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), Failure>>,
+{
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let failure = match attempt().await {
+            Ok(()) => {
+                return Delivery {
+                    target: target.into(),
+                    ok: true,
+                    attempts,
+                    error: None,
+                }
+            }
+            Err(failure) => failure,
+        };
+        tracing::warn!(
+            "notify: {target} delivery of '{event}' failed (attempt {attempts}): {}",
+            failure.message
+        );
+        if !failure.retry || attempts > config.max_retries {
+            return Delivery {
+                target: target.into(),
+                ok: false,
+                attempts,
+                error: Some(failure.message),
+            };
+        }
+        let delay = backoff_ms(config.backoff_ms, attempts - 1);
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+    }
+}
+
+async fn post_webhook(
+    client: &reqwest::Client,
+    url: &str,
+    secret: Option<&str>,
+    event: &str,
+    body: &[u8],
+) -> Result<(), Failure> {
+    let mut request = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event)
+        .body(body.to_vec());
+    if let Some(secret) = secret {
+        request = request.header(SIGNATURE_HEADER, sign(secret, body));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| Failure::retry(e.to_string()))?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    Err(Failure {
+        message: format!("HTTP {status}"),
+        retry: status.is_server_error()
+            || status == reqwest::StatusCode::REQUEST_TIMEOUT
+            || status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+    })
+}
+
+async fn run_exec(command: &str, event: &str, body: &[u8]) -> Result<(), Failure> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    let mut child = cmd
+        .arg(command)
+        .env("ATLAS_EVENT", event)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| Failure {
+            message: format!("Failed to start exec hook: {e}"),
+            retry: false,
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores its input closes stdin early; that's fine
+        let _ = stdin.write_all(body).await;
+    }
+    let output = tokio::time::timeout(
+        Duration::from_secs(EXEC_TIMEOUT_SECS),
+        child.wait_with_output(),
+    )
+    .await
+    .map_err(|_| Failure::retry(format!("timed out after {EXEC_TIMEOUT_SECS}s")))?
+    .map_err(|e| Failure::retry(e.to_string()))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let detail = stderr.lines().last().unwrap_or("").trim();
+    Err(Failure::retry(match output.status.code() {
+        Some(code) if detail.is_empty() => format!("exit status {code}"),
+        Some(code) => format!("exit status {code}: {detail}"),
+        None => "terminated by signal".into(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_sign_matches_hmac_sha256() {
+        assert_eq!(
+            sign("key", b"The quick brown fox jumps over the lazy dog"),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_liq_distance_and_watch() {
+        assert_eq!(liq_distance_pct(d("100"), d("92")), Some(d("8")));
+        // Short: liquidation above the mark
+        assert_eq!(liq_distance_pct(d("100"), d("105")), Some(d("5")));
+        assert_eq!(liq_distance_pct(d("100"), d("0")), None);
+
+        let mut watch = LiqWatch::default();
+        let warn = d("10");
+        assert!(!watch.check("ETH", d("12"), warn));
+        assert!(watch.check("ETH", d("9"), warn));
+        // Still close: no repeat
+        assert!(!watch.check("ETH", d("7"), warn));
+        // Back above the threshold but not recovered: still quiet
+        assert!(!watch.check("ETH", d("11"), warn));
+        assert!(!watch.check("ETH", d("9.5"), warn));
+        // Recovered, then close again
+        assert!(!watch.check("ETH", d("16"), warn));
+        assert!(watch.check("ETH", d("9"), warn));
+
+        watch.retain_open(&[]);
+        assert!(watch.check("ETH", d("9"), warn));
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        assert_eq!(backoff_ms(1000, 0), 1000);
+        assert_eq!(backoff_ms(1000, 1), 2000);
+        assert_eq!(backoff_ms(1000, 3), 8000);
+        assert_eq!(backoff_ms(1000, 10), MAX_BACKOFF_MS);
+        assert_eq!(backoff_ms(0, 5), 0);
+    }
+
+    #[test]
+    fn test_notification_payload_shape() {
+        let n = Notification::sample("testnet", Some("0xabc".into()));
+        let v = serde_json::to_value(&n).unwrap();
+        assert_eq!(v["event"], "test");
+        assert_eq!(v["network"], "testnet");
+        assert_eq!(v["address"], "0xabc");
+        assert_eq!(v["data"]["symbol"], "BTC");
+        assert!(v["timestamp_ms"].as_i64().unwrap() > 0);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_exec_hook_gets_payload_and_retries() {
+        let dir = std::env::temp_dir().join(format!("atlas-notify-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("payload.json");
+        let n = Notification::sample("mainnet", None);
+
+        let mut config = NotifyConfig {
+            exec: Some(format!("cat > '{}'", out.display())),
+            backoff_ms: 0,
+            ..NotifyConfig::default()
+        };
+        let deliveries = deliver(&config, &n).await;
+        assert_eq!(deliveries.len(), 1);
+        assert!(deliveries[0].ok);
+        let written: Value = serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(written["event"], "test");
+
+        config.exec = Some("echo nope >&2; exit 3".into());
+        config.max_retries = 2;
+        let failed = &deliver(&config, &n).await[0];
+        assert!(!failed.ok);
+        assert_eq!(failed.attempts, 3);
+        assert_eq!(failed.error.as_deref(), Some("exit status 3: nope"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -4,14 +4,21 @@
 //! for the CLI, TUI, and backend to consume.
 
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
-use tracing::info;
+use futures::future::join_all;
+use tracing::{info, warn};
 
+use crate::error::{AtlasError, AtlasResult};
 use crate::traits::{LendingModule, PerpModule, SwapModule};
 use crate::types::*;
 
+/// How long one module gets to answer a fanned-out query.
+pub const MODULE_TIMEOUT: Duration = Duration::from_secs(15);
+
 /// The core orchestrator — holds all protocol modules.
 pub struct Orchestrator {
     /// Perp modules keyed by protocol name.
@@ -109,6 +116,29 @@ impl Orchestrator {
             .ok_or_else(|| anyhow::anyhow!("Unknown swap protocol: {name}"))
     }
 
+    /// Every registered perp module, ordered by protocol name.
+    pub fn all_perps(&self) -> Vec<Arc<dyn PerpModule>> {
+        let mut names: Vec<&String> = self.perp_modules.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| Arc::clone(&self.perp_modules[name]))
+            .collect()
+    }
+
+    /// Run `call` against every perp module concurrently; see [`fan_out`].
+    pub async fn fan_out_perps<T, F, Fut>(
+        &self,
+        timeout: Duration,
+        call: F,
+    ) -> Vec<(Protocol, AtlasResult<T>)>
+    where
+        F: Fn(Arc<dyn PerpModule>) -> Fut,
+        Fut: Future<Output = AtlasResult<T>>,
+    {
+        fan_out(&self.all_perps(), timeout, call).await
+    }
+
     /// List all registered protocols.
     pub fn protocols(&self) -> Vec<ProtocolInfo> {
         let mut protos = Vec::new();
@@ -187,9 +217,284 @@ impl Orchestrator {
     }
 }
 
+/// Run `call` against each module concurrently, giving each `timeout`.
+///
+/// Every module gets an entry, in input order: a module that errors or
+/// times out yields `Err` rather than being dropped.
+pub async fn fan_out<T, F, Fut>(
+    modules: &[Arc<dyn PerpModule>],
+    timeout: Duration,
+    call: F,
+) -> Vec<(Protocol, AtlasResult<T>)>
+where
+    F: Fn(Arc<dyn PerpModule>) -> Fut,
+    Fut: Future<Output = AtlasResult<T>>,
+{
+    join_all(modules.iter().map(|module| {
+        let protocol = module.protocol();
+        let fut = call(Arc::clone(module));
+        async move {
+            let result = match tokio::time::timeout(timeout, fut).await {
+                Ok(result) => result,
+                Err(_) => Err(AtlasError::ProtocolTimeout(format!(
+                    "{protocol} did not respond within {}s",
+                    timeout.as_secs_f64()
+                ))),
+            };
+            (protocol, result)
+        }
+    }))
+    .await
+}
+
+/// Items gathered from a fan-out, plus one warning per failed module.
+#[derive(Debug, Clone)]
+pub struct Gathered<T> {
+    pub items: Vec<T>,
+    /// `"<protocol>: <error>"` for each module that failed.
+    pub warnings: Vec<String>,
+}
+
+impl<T> Gathered<T> {
+    /// Flatten per-module lists, turning failures into warnings.
+    pub fn from_results(results: Vec<(Protocol, AtlasResult<Vec<T>>)>) -> Self {
+        let mut items = Vec::new();
+        let mut warnings = Vec::new();
+        for (protocol, result) in results {
+            match result {
+                Ok(batch) => items.extend(batch),
+                Err(e) => {
+                    warn!(%protocol, error = %e, "module query failed");
+                    warnings.push(format!("{protocol}: {e}"));
+                }
+            }
+        }
+        Self { items, warnings }
+    }
+}
+
 /// Protocol registration info.
 #[derive(Debug, Clone)]
 pub struct ProtocolInfo {
     pub name: String,
     pub module_type: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use rust_decimal::Decimal;
+
+    /// Perp module that answers `positions` after `delay`, or fails.
+    struct MockPerp {
+        protocol: Protocol,
+        positions: Result<Vec<&'static str>, &'static str>,
+        delay: Duration,
+    }
+
+    impl MockPerp {
+        fn new(protocol: Protocol, positions: Result<Vec<&'static str>, &'static str>) -> Self {
+            Self {
+                protocol,
+                positions,
+                delay: Duration::ZERO,
+            }
+        }
+    }
+
+    fn position(protocol: Protocol, symbol: &str) -> Position {
+        Position {
+            protocol,
+            symbol: symbol.into(),
+            side: Side::Buy,
+            size: Decimal::ONE,
+            entry_price: None,
+            mark_price: None,
+            unrealized_pnl: None,
+            leverage: None,
+            margin: None,
+            liquidation_price: None,
+            margin_mode: None,
+        }
+    }
+
+    #[async_trait]
+    impl PerpModule for MockPerp {
+        fn protocol(&self) -> Protocol {
+            self.protocol.clone()
+        }
+
+        async fn positions(&self) -> AtlasResult<Vec<Position>> {
+            tokio::time::sleep(self.delay).await;
+            match &self.positions {
+                Ok(symbols) => Ok(symbols
+                    .iter()
+                    .map(|s| position(self.protocol.clone(), s))
+                    .collect()),
+                Err(e) => Err(AtlasError::Network((*e).into())),
+            }
+        }
+
+        async fn markets(&self) -> AtlasResult<Vec<Market>> {
+            unimplemented!()
+        }
+        async fn ticker(&self, _: &str) -> AtlasResult<Ticker> {
+            unimplemented!()
+        }
+        async fn all_tickers(&self) -> AtlasResult<Vec<Ticker>> {
+            unimplemented!()
+        }
+        async fn candles(&self, _: &str, _: &str, _: usize) -> AtlasResult<Vec<Candle>> {
+            unimplemented!()
+        }
+        async fn funding(&self, _: &str) -> AtlasResult<Vec<FundingRate>> {
+            unimplemented!()
+        }
+        async fn orderbook(&self, _: &str, _: usize) -> AtlasResult<OrderBook> {
+            unimplemented!()
+        }
+        async fn market_order(
+            &self,
+            _: &str,
+            _: Side,
+            _: Decimal,
+            _: Option<f64>,
+        ) -> AtlasResult<OrderResult> {
+            unimplemented!()
+        }
+        async fn limit_order(
+            &self,
+            _: &str,
+            _: Side,
+            _: Decimal,
+            _: Decimal,
+            _: &LimitOptions,
+        ) -> AtlasResult<OrderResult> {
+            unimplemented!()
+        }
+        async fn close_position(
+            &self,
+            _: &str,
+            _: Option<Decimal>,
+            _: Option<f64>,
+        ) -> AtlasResult<OrderResult> {
+            unimplemented!()
+        }
+        async fn cancel_order(&self, _: &str, _: &str) -> AtlasResult<()> {
+            unimplemented!()
+        }
+        async fn cancel_all(&self, _: &str) -> AtlasResult<u32> {
+            unimplemented!()
+        }
+        async fn open_orders(&self) -> AtlasResult<Vec<Order>> {
+            unimplemented!()
+        }
+        async fn fills(&self) -> AtlasResult<Vec<Fill>> {
+            unimplemented!()
+        }
+        async fn balances(&self) -> AtlasResult<Vec<Balance>> {
+            unimplemented!()
+        }
+        async fn set_leverage(&self, _: &str, _: u32, _: bool) -> AtlasResult<()> {
+            unimplemented!()
+        }
+        async fn update_margin(&self, _: &str, _: Decimal) -> AtlasResult<()> {
+            unimplemented!()
+        }
+        async fn transfer(&self, _: Decimal, _: &str) -> AtlasResult<String> {
+            unimplemented!()
+        }
+    }
+
+    fn orchestrator(modules: Vec<MockPerp>) -> Orchestrator {
+        let mut orch = Orchestrator::new();
+        for m in modules {
+            orch.add_perp(Arc::new(m));
+        }
+        orch
+    }
+
+    #[test]
+    fn test_all_perps_is_ordered_by_name() {
+        let orch = orchestrator(vec![
+            MockPerp::new(Protocol::ZeroX, Ok(vec![])),
+            MockPerp::new(Protocol::Hyperliquid, Ok(vec![])),
+        ]);
+        let protocols: Vec<Protocol> = orch.all_perps().iter().map(|m| m.protocol()).collect();
+        assert_eq!(protocols, vec![Protocol::ZeroX, Protocol::Hyperliquid]);
+        assert_eq!(orch.default_perp.as_deref(), Some("0x"));
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_collects_every_module() {
+        let orch = orchestrator(vec![
+            MockPerp::new(Protocol::Hyperliquid, Ok(vec!["BTC", "ETH"])),
+            MockPerp::new(Protocol::ZeroX, Ok(vec!["SOL"])),
+        ]);
+        let results = orch
+            .fan_out_perps(MODULE_TIMEOUT, |m| async move { m.positions().await })
+            .await;
+        assert_eq!(results.len(), 2);
+
+        let gathered = Gathered::from_results(results);
+        assert!(gathered.warnings.is_empty());
+        let mut rows: Vec<(String, String)> = gathered
+            .items
+            .iter()
+            .map(|p| (p.protocol.to_string(), p.symbol.clone()))
+            .collect();
+        rows.sort();
+        assert_eq!(
+            rows,
+            vec![
+                ("0x".to_string(), "SOL".to_string()),
+                ("hyperliquid".to_string(), "BTC".to_string()),
+                ("hyperliquid".to_string(), "ETH".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_keeps_partial_failures_as_warnings() {
+        let orch = orchestrator(vec![
+            MockPerp::new(Protocol::Hyperliquid, Ok(vec!["BTC"])),
+            MockPerp::new(Protocol::ZeroX, Err("connection refused")),
+        ]);
+        let results = orch
+            .fan_out_perps(MODULE_TIMEOUT, |m| async move { m.positions().await })
+            .await;
+        let gathered = Gathered::from_results(results);
+        assert_eq!(gathered.items.len(), 1);
+        assert_eq!(gathered.items[0].symbol, "BTC");
+        assert_eq!(gathered.warnings.len(), 1);
+        assert!(gathered.warnings[0].starts_with("0x: "));
+        assert!(gathered.warnings[0].contains("connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_times_out_slow_modules() {
+        let slow = MockPerp {
+            delay: Duration::from_secs(30),
+            ..MockPerp::new(Protocol::ZeroX, Ok(vec!["SOL"]))
+        };
+        let orch = orchestrator(vec![
+            MockPerp::new(Protocol::Hyperliquid, Ok(vec!["BTC"])),
+            slow,
+        ]);
+        let results = orch
+            .fan_out_perps(
+                Duration::from_millis(50),
+                |m| async move { m.positions().await },
+            )
+            .await;
+
+        let by_protocol: HashMap<Protocol, &AtlasResult<Vec<Position>>> =
+            results.iter().map(|(p, r)| (p.clone(), r)).collect();
+        assert!(by_protocol[&Protocol::Hyperliquid].is_ok());
+        assert!(matches!(
+            by_protocol[&Protocol::ZeroX],
+            Err(AtlasError::ProtocolTimeout(_))
+        ));
+    }
+}
@@ -0,0 +1,310 @@
+//! Order lifecycle tracking for the local order cache.
+//!
+//! `history sync` records every order returned by Hyperliquid's
+//! `historicalOrders` with its current status. That endpoint only covers
+//! the most recent orders, so an order cached while resting can drop out
+//! of it before it fills or is canceled; such orders are looked up one by
+//! one with `orderStatus`. Filled size is the original size minus what is
+//! still resting, the average price comes from the cached fills.
+
+use std::collections::HashSet;
+use std::future::Future;
+
+use anyhow::Result;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use tracing::{debug, info};
+
+use crate::db::{AtlasDb, DbOrder, OrderFilter};
+
+/// What one reconciliation pass did to the cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OrderSyncStats {
+    /// Orders returned by `historicalOrders`.
+    pub fetched: usize,
+    /// Orders not cached before.
+    pub inserted: usize,
+    /// Cached orders whose status or filled size changed.
+    pub updated: usize,
+    /// Open orders the exchange could not confirm; they keep their status.
+    pub unconfirmed: usize,
+}
+
+/// Bring the cached Hyperliquid orders up to date at `now_ms`.
+///
+/// `historical` is the `historicalOrders` response. Every cached order
+/// still open afterwards that it did not cover is passed to
+/// `order_status(oid)`, which returns the `orderStatus` response.
+pub async fn reconcile_orders<F, Fut>(
+    db: &AtlasDb,
+    historical: &Value,
+    now_ms: i64,
+    mut order_status: F,
+) -> Result<OrderSyncStats>
+where
+    F: FnMut(i64) -> Fut,
+    Fut: Future<Output = Result<Value>>,
+{
+    let mut stats = OrderSyncStats::default();
+
+    let entries = historical.as_array().map(Vec::as_slice).unwrap_or_default();
+    let mut orders = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if let Some(order) = parse_order_entry(entry, now_ms) {
+            orders.push(with_avg_px(db, order)?);
+        }
+    }
+    stats.fetched = orders.len();
+    let seen: HashSet<i64> = orders.iter().map(|o| o.oid).collect();
+    let (inserted, updated) = db.update_orders(&orders)?;
+    stats.inserted = inserted;
+    stats.updated = updated;
+
+    // Anything still open and not confirmed just now fell out of the window
+    let pending = db.query_orders(&OrderFilter {
+        protocol: Some("hyperliquid".into()),
+        stale_before_ms: Some(now_ms),
+        ..Default::default()
+    })?;
+    let mut confirmed = Vec::new();
+    for cached in pending.iter().filter(|o| !seen.contains(&o.oid)) {
+        let resp = order_status(cached.oid).await?;
+        match parse_order_status(&resp, now_ms) {
+            Some(order) => confirmed.push(with_avg_px(db, order)?),
+            None => {
+                debug!(oid = cached.oid, "order unknown to the exchange");
+                stats.unconfirmed += 1;
+            }
+        }
+    }
+    let (inserted, updated) = db.update_orders(&confirmed)?;
+    stats.inserted += inserted;
+    stats.updated += updated;
+
+    info!(
+        fetched = stats.fetched,
+        looked_up = confirmed.len() + stats.unconfirmed,
+        updated = stats.updated,
+        "order statuses reconciled"
+    );
+    Ok(stats)
+}
+
+fn with_avg_px(db: &AtlasDb, mut order: DbOrder) -> Result<DbOrder> {
+    order.avg_px = db.avg_fill_px(order.oid)?.map(|px| px.to_string());
+    Ok(order)
+}
+
+/// The order in an `orderStatus` response, `None` for `unknownOid`.
+pub fn parse_order_status(resp: &Value, now_ms: i64) -> Option<DbOrder> {
+    if resp.get("status").and_then(Value::as_str) != Some("order") {
+        return None;
+    }
+    parse_order_entry(resp.get("order")?, now_ms)
+}
+
+/// One `{"order": {...}, "status": ..., "statusTimestamp": ...}` entry, as
+/// returned by `historicalOrders` and inside `orderStatus`, confirmed at
+/// `now_ms`.
+pub fn parse_order_entry(entry: &Value, now_ms: i64) -> Option<DbOrder> {
+    let order = entry.get("order")?;
+    let text = |key: &str| order.get(key).and_then(Value::as_str);
+    let dec = |key: &str| text(key).and_then(|s| s.parse::<Decimal>().ok());
+
+    let remaining = dec("sz")?;
+    let orig = dec("origSz").unwrap_or(remaining);
+    let side = match text("side")? {
+        "B" => "Buy",
+        _ => "Sell",
+    };
+    Some(DbOrder {
+        protocol: "hyperliquid".to_string(),
+        coin: text("coin")?.to_string(),
+        side: side.to_string(),
+        limit_px: text("limitPx")?.to_string(),
+        sz: orig.normalize().to_string(),
+        oid: order.get("oid").and_then(Value::as_i64)?,
+        timestamp_ms: order.get("timestamp").and_then(Value::as_i64)?,
+        status: normalize_status(entry.get("status").and_then(Value::as_str)?),
+        order_type: text("orderType").unwrap_or_default().to_string(),
+        filled_sz: (orig - remaining)
+            .max(Decimal::ZERO)
+            .normalize()
+            .to_string(),
+        avg_px: None,
+        status_updated_at: Some(now_ms),
+    })
+}
+
+/// Collapse Hyperliquid's order statuses into the cached ones: `open`,
+/// `filled`, `triggered`, `canceled` (every `*Canceled` reason and
+/// `scheduledCancel`) and `rejected` (every `*Rejected` reason).
+pub fn normalize_status(status: &str) -> String {
+    let lower = status.to_ascii_lowercase();
+    if lower.ends_with("canceled") || lower.ends_with("cancelled") || lower == "scheduledcancel" {
+        "canceled".to_string()
+    } else if lower.ends_with("rejected") {
+        "rejected".to_string()
+    } else {
+        lower
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DbFill;
+    use serde_json::json;
+    use std::cell::RefCell;
+
+    const NOW: i64 = 1_700_000_100_000;
+
+    fn entry(oid: i64, status: &str, sz: &str, orig_sz: &str) -> Value {
+        json!({
+            "order": {
+                "coin": "ETH",
+                "side": "B",
+                "limitPx": "3500.0",
+                "sz": sz,
+                "oid": oid,
+                "timestamp": 1_700_000_000_000i64 + oid,
+                "orderType": "Limit",
+                "origSz": orig_sz,
+                "tif": "Gtc",
+                "cloid": null
+            },
+            "status": status,
+            "statusTimestamp": 1_700_000_050_000i64
+        })
+    }
+
+    fn fill(oid: i64, hash: &str, px: &str, sz: &str) -> DbFill {
+        DbFill {
+            protocol: "hyperliquid".into(),
+            coin: "ETH".into(),
+            px: px.into(),
+            sz: sz.into(),
+            side: "Buy".into(),
+            time_ms: 1_700_000_060_000,
+            fee: "0".into(),
+            hash: hash.into(),
+            oid,
+            closed_pnl: "0".into(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_status() {
+        assert_eq!(normalize_status("open"), "open");
+        assert_eq!(normalize_status("filled"), "filled");
+        assert_eq!(normalize_status("marginCanceled"), "canceled");
+        assert_eq!(normalize_status("scheduledCancel"), "canceled");
+        assert_eq!(normalize_status("reduceOnlyRejected"), "rejected");
+    }
+
+    #[test]
+    fn test_parse_order_entry() {
+        let order = parse_order_entry(&entry(9, "canceled", "0.25", "1.0"), NOW).unwrap();
+        assert_eq!(order.side, "Buy");
+        assert_eq!(order.sz, "1");
+        assert_eq!(order.filled_sz, "0.75");
+        assert_eq!(order.status, "canceled");
+        assert_eq!(order.status_updated_at, Some(NOW));
+
+        assert!(parse_order_status(&json!({"status": "unknownOid"}), NOW).is_none());
+        let wrapped = json!({"status": "order", "order": entry(9, "filled", "0.0", "1.0")});
+        let order = parse_order_status(&wrapped, NOW).unwrap();
+        assert_eq!(
+            (order.status.as_str(), order.filled_sz.as_str()),
+            ("filled", "1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_updates_open_orders_in_place() {
+        let db = AtlasDb::open_in_memory().unwrap();
+
+        // First sync: both orders resting
+        let first = json!([
+            entry(1, "open", "1.0", "1.0"),
+            entry(2, "open", "2.0", "2.0")
+        ]);
+        let stats = reconcile_orders(&db, &first, NOW - 60_000, |_| async {
+            anyhow::bail!("no lookups expected")
+        })
+        .await
+        .unwrap();
+        assert_eq!((stats.fetched, stats.inserted, stats.updated), (2, 2, 0));
+
+        // Order 1 filled in two fills; order 2 dropped out of the window
+        // and was canceled after filling half
+        db.insert_fills(&[fill(1, "0xa", "3500", "0.4"), fill(1, "0xb", "3490", "0.6")])
+            .unwrap();
+        let second = json!([entry(1, "filled", "0.0", "1.0")]);
+        let looked_up = RefCell::new(Vec::new());
+        let stats = reconcile_orders(&db, &second, NOW, |oid| {
+            looked_up.borrow_mut().push(oid);
+            async move {
+                Ok(json!({"status": "order", "order": entry(oid, "marginCanceled", "1.0", "2.0")}))
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(*looked_up.borrow(), [2]);
+        assert_eq!(
+            stats,
+            OrderSyncStats {
+                fetched: 1,
+                inserted: 0,
+                updated: 2,
+                unconfirmed: 0
+            }
+        );
+
+        let orders = db.query_orders(&OrderFilter::default()).unwrap();
+        assert_eq!(orders.len(), 2);
+        let filled = orders.iter().find(|o| o.oid == 1).unwrap();
+        assert_eq!(filled.status, "filled");
+        assert_eq!(filled.filled_sz, "1");
+        assert_eq!(filled.avg_px.as_deref(), Some("3494"));
+        assert_eq!(filled.status_updated_at, Some(NOW));
+        let canceled = orders.iter().find(|o| o.oid == 2).unwrap();
+        assert_eq!(canceled.status, "canceled");
+        assert_eq!(canceled.filled_sz, "1");
+
+        // Nothing is open any more
+        let open = db
+            .query_orders(&OrderFilter {
+                status: Some("open".into()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(open.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_orders_stay_stale() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let first = json!([entry(3, "open", "1.0", "1.0")]);
+        reconcile_orders(&db, &first, NOW - 60_000, |_| async { Ok(json!(null)) })
+            .await
+            .unwrap();
+
+        let stats = reconcile_orders(&db, &json!([]), NOW, |_| async {
+            Ok(json!({"status": "unknownOid"}))
+        })
+        .await
+        .unwrap();
+        assert_eq!(stats.unconfirmed, 1);
+
+        let stale = db
+            .query_orders(&OrderFilter {
+                stale_before_ms: Some(NOW),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].status, "open");
+        assert_eq!(stale[0].status_updated_at, Some(NOW - 60_000));
+    }
+}
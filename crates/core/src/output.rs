@@ -4,10 +4,14 @@
 // They all derive `Serialize` for JSON output, and implement
 // `TableDisplay` for human-readable table rendering.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::sync::{Mutex, OnceLock};
 
 use serde::Serialize;
 
+use crate::fmt::Align;
+
 // ─── Status ─────────────────────────────────────────────────────────
 
 /// PRD-compliant status output.
@@ -29,6 +33,9 @@ use serde::Serialize;
 pub struct StatusOutput {
     pub profile: String,
     pub address: String,
+    /// Active config environment (`atlas configure env use`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
     pub network: String,
     pub modules: Vec<String>,
     pub balances: Vec<BalanceRow>,
@@ -38,6 +45,64 @@ pub struct StatusOutput {
     pub withdrawable: Option<String>,
     pub positions: Vec<PositionRow>,
     pub open_orders: usize,
+    /// `status --all` only: everything below, summed in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_value_usd: Option<String>,
+    /// `status --all` only: one section per protocol / wallet.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub protocols: Vec<ProtocolSummary>,
+    /// Sources that failed or couldn't be priced; the rest is still shown.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// `status --diff` only: changes since the previous snapshot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<StatusDiff>,
+}
+
+/// Changes since the previous `atlas status` snapshot of the same profile
+/// and network. The default is the first run's: nothing to compare with.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StatusDiff {
+    /// When the previous snapshot was taken; `None` on the first run, when
+    /// there is nothing to compare with.
+    pub baseline_ms: Option<i64>,
+    /// `None` when either snapshot has no account value.
+    pub account_value_change: Option<String>,
+    pub open_orders_change: i64,
+    /// Only positions that changed.
+    pub positions: Vec<PositionDiffRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionDiffRow {
+    pub symbol: String,
+    pub protocol: String,
+    /// `new`, `closed` or `resized`.
+    pub change: String,
+    /// `None` for a new (before) or closed (after) position.
+    pub size_before: Option<String>,
+    pub size_after: Option<String>,
+    pub size_change: String,
+}
+
+/// One protocol's holdings in `atlas status --all`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProtocolSummary {
+    /// e.g. `"hyperliquid"`, `"hyperliquid-spot"`, `"evm-wallet"`.
+    pub protocol: String,
+    pub value_usd: String,
+    pub balances: Vec<ValuedBalanceRow>,
+    pub positions: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ValuedBalanceRow {
+    pub asset: String,
+    pub chain: String,
+    pub amount: String,
+    /// `None` when no price source covers the asset.
+    pub price_usd: Option<String>,
+    pub value_usd: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -57,16 +122,35 @@ pub struct PositionRow {
     pub entry_price: Option<String>,
     pub mark_price: Option<String>,
     pub unrealized_pnl: Option<String>,
+    /// Unsigned value at the mark price (entry price without a mark).
+    pub notional: Option<String>,
+    /// Unrealized PnL as a percentage of the entry value.
+    pub pnl_pct: Option<String>,
     pub liquidation_price: Option<String>,
     pub leverage: Option<u32>,
     pub margin_mode: Option<String>,
     pub protocol: String,
 }
 
+/// `atlas positions` — positions from one or every perp module.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionsOutput {
+    pub positions: Vec<PositionRow>,
+    /// Sum of `notional` over the listed positions.
+    pub total_notional: String,
+    /// Sum of `unrealized_pnl` over the listed positions.
+    pub total_unrealized_pnl: String,
+    /// Modules that failed; their positions are missing from the list.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
 // ─── Orders ─────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize)]
 pub struct OrdersOutput {
+    /// `perp` or `spot` — which market the listing covers.
+    pub market_type: String,
     pub orders: Vec<OrderRow>,
 }
 
@@ -79,6 +163,14 @@ pub struct OrderRow {
     pub price: String,
     #[serde(rename = "order_id")]
     pub oid: u64,
+    /// `limit`, or `trigger` for a stop order resting off the book.
+    pub order_type: String,
+    /// Mark price that releases a `trigger` order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<String>,
+    /// How a `trigger` order executes: `market` or `limit`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_type: Option<String>,
 }
 
 // ─── Fills ──────────────────────────────────────────────────────────
@@ -86,6 +178,13 @@ pub struct OrderRow {
 #[derive(Debug, Clone, Serialize)]
 pub struct FillsOutput {
     pub fills: Vec<FillRow>,
+    /// Sum of `fee` over the listed fills.
+    pub total_fee: String,
+    /// Sum of `closed_pnl` over the listed fills.
+    pub net_closed_pnl: String,
+    /// Modules that failed; their fills are missing from the list.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -97,6 +196,8 @@ pub struct FillRow {
     pub price: String,
     pub closed_pnl: String,
     pub fee: String,
+    pub protocol: String,
+    pub timestamp_ms: u64,
 }
 
 // ─── Order result (place/close) ─────────────────────────────────────
@@ -119,6 +220,111 @@ pub struct OrderResultOutput {
     pub builder_fee_bps: u32,
     pub protocol: String,
     pub timestamp: Option<u64>,
+    /// Time in force of a limit order: "gtc", "ioc" or "alo".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tif: Option<String>,
+    /// Mark price that releases a stop order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<String>,
+    /// How a stop order executes once triggered: "market" or "limit".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_type: Option<String>,
+    /// Client order ID, when one was supplied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloid: Option<String>,
+    /// Adjustments made before sending, e.g. a reduce-only size capped at
+    /// the position.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Market orders sent while the spread was far above its median.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liquidity_warning: Option<crate::spread_profile::LiquidityWarning>,
+    /// Audit log entry of the signed request; absent for paper orders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit_id: Option<i64>,
+}
+
+/// `atlas hl perp buy|sell --dry-run` — the market order as it would be
+/// sent, with where each parameter came from and the book's expected cost.
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketOrderPreview {
+    pub symbol: String,
+    /// "buy" or "sell".
+    pub side: String,
+    pub size: String,
+    pub size_source: crate::config::ParamSource,
+    pub mark: String,
+    pub notional: String,
+    pub leverage: u32,
+    pub leverage_source: crate::config::ParamSource,
+    pub slippage: f64,
+    pub slippage_source: crate::config::ParamSource,
+    /// Worst price the order accepts: mark ± slippage.
+    pub limit_price: String,
+    /// Average fill price from walking the L2 book; `null` when the book
+    /// was unavailable or too thin for the size.
+    pub estimated_avg_px: Option<String>,
+    /// Distance of `estimated_avg_px` from the mid, in bps.
+    pub estimated_impact_bps: Option<String>,
+    /// Share of the size the visible book covers (0–100); `null` without
+    /// a book.
+    pub book_coverage_pct: Option<String>,
+    /// Present when the spread is far above the coin's median.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liquidity_warning: Option<crate::spread_profile::LiquidityWarning>,
+}
+
+/// `atlas hl perp flip` — a position reversed by one order.
+#[derive(Debug, Clone, Serialize)]
+pub struct FlipOutput {
+    pub coin: String,
+    pub before: FlipSide,
+    /// Read back after the fill; `flat` if nothing is left open.
+    pub after: FlipSide,
+    pub order: OrderResultOutput,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FlipSide {
+    /// "long", "short" or "flat".
+    pub side: String,
+    /// Absolute size in coin units.
+    pub size: String,
+}
+
+/// `atlas hl perp close-all` — one row per position, failures included.
+#[derive(Debug, Clone, Serialize)]
+pub struct CloseAllOutput {
+    pub results: Vec<CloseAllRow>,
+    pub summary: CloseAllSummary,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CloseAllRow {
+    #[serde(rename = "symbol")]
+    pub coin: String,
+    /// "long" or "short".
+    pub side: String,
+    /// Absolute position size before the close.
+    pub size: String,
+    /// "closed", "partial" (IOC filled part of it) or "error".
+    pub status: String,
+    #[serde(rename = "order_id", skip_serializing_if = "Option::is_none")]
+    pub oid: Option<u64>,
+    pub filled: Option<String>,
+    pub avg_price: Option<String>,
+    /// Closed PnL from the order's fills.
+    pub realized_pnl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CloseAllSummary {
+    pub closed: usize,
+    pub failed: usize,
+    /// Sum of `realized_pnl` over the closed rows.
+    pub realized_pnl: String,
 }
 
 // ─── Cancel ─────────────────────────────────────────────────────────
@@ -152,6 +358,31 @@ pub struct LeverageOutput {
     pub mode: String,
 }
 
+/// `atlas hl perp leverage-audit` — each open position's leverage against
+/// `modules.hyperliquid.default_leverage`, and what `--apply-default` did.
+#[derive(Debug, Clone, Serialize)]
+pub struct LeverageAuditOutput {
+    pub default_leverage: u32,
+    pub rows: Vec<LeverageAuditRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LeverageAuditRow {
+    #[serde(rename = "symbol")]
+    pub coin: String,
+    /// `None` when the module does not report it.
+    pub leverage: Option<u32>,
+    /// `cross` or `isolated`.
+    pub mode: Option<String>,
+    pub default_leverage: u32,
+    pub mismatch: bool,
+    /// `none` (matches the default), `flagged` (mismatch left alone),
+    /// `set` (aligned to the default) or `failed`.
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 // ─── Margin ─────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize)]
@@ -167,7 +398,25 @@ pub struct MarginOutput {
 #[derive(Debug, Clone, Serialize)]
 pub struct TransferOutput {
     pub amount: String,
+    /// Checksummed address that was paid.
     pub destination: String,
+    /// ENS name the destination was resolved from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Whitelist label of the destination.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+// ─── Paper ──────────────────────────────────────────────────────────
+
+/// `atlas paper reset`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PaperResetOutput {
+    /// USDC the account starts over with.
+    pub balance: String,
+    /// Paper fills removed from the local DB.
+    pub fills_cleared: usize,
 }
 
 // ─── Risk ───────────────────────────────────────────────────────────
@@ -182,7 +431,10 @@ pub struct RiskCalcOutput {
     pub notional: f64,
     pub stop_loss: f64,
     pub take_profit: f64,
+    /// `0` when the cross model finds no price that liquidates the account.
     pub est_liquidation: f64,
+    /// `isolated` (entry ± margin per unit) or `cross` (account-wide model).
+    pub liq_model: String,
     pub risk_usd: f64,
     pub risk_pct: f64,
     pub margin: f64,
@@ -191,6 +443,217 @@ pub struct RiskCalcOutput {
     pub blocked: bool,
 }
 
+/// `atlas hl risk suggest` — a position size from the closed-trade record
+/// (Kelly) or a fixed fraction of the account.
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskSuggestOutput {
+    pub coin: String,
+    pub side: String,
+    pub entry_price: f64,
+    pub stop_loss: f64,
+    /// Method asked for: `kelly` or `fixed-frac`.
+    pub requested: String,
+    /// Method used; `fixed-frac` when Kelly fell back for lack of history.
+    pub method: String,
+    pub stats: SizingStatsOutput,
+    /// Full Kelly fraction of the record, when Kelly was used.
+    pub kelly: Option<f64>,
+    pub kelly_multiplier: f64,
+    pub kelly_cap: f64,
+    pub account_value: f64,
+    /// Fraction of the account lost at the stop.
+    pub risk_pct: f64,
+    pub risk_usd: f64,
+    pub size: f64,
+    pub lots: f64,
+    pub notional: f64,
+    pub margin: f64,
+    pub leverage: u32,
+    pub warnings: Vec<String>,
+}
+
+/// The closed-trade record behind a [`RiskSuggestOutput`].
+#[derive(Debug, Clone, Serialize)]
+pub struct SizingStatsOutput {
+    /// `coin` (this coin's trades) or `all` (every coin's).
+    pub scope: String,
+    pub trades: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub win_rate: f64,
+    pub avg_win: f64,
+    pub avg_loss: f64,
+    /// Average win over average loss; `None` without both.
+    pub payoff_ratio: Option<f64>,
+}
+
+/// `atlas hl risk liq` — liquidation price from the maintenance-margin
+/// model, as the account stands and after a hypothetical trade.
+#[derive(Debug, Clone, Serialize)]
+pub struct LiquidationOutput {
+    pub coin: String,
+    /// `cross` or `isolated`.
+    pub margin_mode: String,
+    /// `long` or `short`; `None` without a position.
+    pub side: Option<String>,
+    /// Signed position size.
+    pub size: String,
+    pub mark_price: Option<String>,
+    /// Equity backing the position: cross account value or isolated equity.
+    pub equity: Option<String>,
+    /// Maintenance margin that equity has to cover.
+    pub maintenance_margin: Option<String>,
+    /// `None` when no positive price liquidates the position.
+    pub liquidation_price: Option<String>,
+    /// Distance from mark to liquidation, % of mark.
+    pub distance_pct: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<LiquidationScenario>,
+}
+
+/// The position after `add_size` more (signed) filled at `at_price`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LiquidationScenario {
+    pub add_size: String,
+    pub at_price: String,
+    pub size: String,
+    pub equity: Option<String>,
+    pub maintenance_margin: Option<String>,
+    pub liquidation_price: Option<String>,
+    pub distance_pct: Option<String>,
+}
+
+// ─── Strategy ───────────────────────────────────────────────────────
+
+/// `atlas strategy carry` — short HL perp / long spot supplied on Morpho.
+/// Percentages are yearly; `fees_usd` and `net_usd` cover the horizon.
+#[derive(Debug, Clone, Serialize)]
+pub struct CarryOutput {
+    pub coin: String,
+    pub size_usd: String,
+    pub horizon_days: u32,
+    pub chain: String,
+    pub market_id: String,
+    pub market: String,
+    /// Predicted rate for the next funding interval.
+    pub funding_rate: String,
+    pub funding_interval_hours: u32,
+    pub funding_apr_pct: String,
+    /// From the 7-day funding history.
+    pub trailing_funding_apr_pct: Option<String>,
+    pub supply_apy_pct: String,
+    pub taker_fee_bps: String,
+    pub builder_fee_bps: String,
+    pub fees_usd: String,
+    pub fees_apr_pct: String,
+    pub net_apr_pct: String,
+    pub net_usd: String,
+    pub breakeven_funding_apr_pct: String,
+    pub breakeven_funding_rate: String,
+    /// Trailing funding is under half the current rate.
+    pub trailing_below_current: bool,
+}
+
+// ─── Schedule ───────────────────────────────────────────────────────
+
+/// `atlas schedule list`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleListOutput {
+    pub schedules: Vec<ScheduleRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleRow {
+    pub id: i64,
+    /// The atlas command line, without `atlas`.
+    pub command: String,
+    /// Interval, e.g. `1d` or `12h`.
+    pub every: String,
+    /// `--at HH:MM` (UTC) the job was anchored to.
+    pub at: Option<String>,
+    pub max_runs: Option<u32>,
+    /// Executions so far (skipped runs don't count).
+    pub runs: u32,
+    /// `active`, or `done` once `max_runs` is reached.
+    pub status: String,
+    /// `None` once done.
+    pub next_run_ms: Option<i64>,
+    pub last_run: Option<ScheduleRunRow>,
+}
+
+/// One execution (or skip) recorded by `atlas schedule run`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleRunRow {
+    pub schedule_id: i64,
+    /// Slot the run was for.
+    pub due_ms: i64,
+    pub started_ms: i64,
+    /// `ok`, `error`, or `skipped` (missed while the executor was down).
+    pub status: String,
+    /// Slots that passed unrun before this one.
+    pub missed: u32,
+    pub order_id: Option<String>,
+    /// Average fill price of the order.
+    pub price: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Result of `atlas schedule add` / `remove`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleChangeOutput {
+    /// `added` or `removed`.
+    pub action: String,
+    pub schedule: ScheduleRow,
+}
+
+// ─── Notifications ──────────────────────────────────────────────────
+
+/// `atlas configure notify set` / `show`.
+#[derive(Debug, Clone, Serialize)]
+pub struct NotifyConfigOutput {
+    pub webhook: Option<String>,
+    pub exec: Option<String>,
+    /// Redacted unless `--reveal`.
+    pub secret: Option<String>,
+    pub liq_warn_pct: f64,
+    pub max_retries: u32,
+    pub backoff_ms: u64,
+}
+
+/// `atlas configure security show` / `totp enroll` / `totp remove`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SecurityConfigOutput {
+    /// USDC above which a transfer needs a second approval; `None` = off.
+    pub transfer_confirm_above: Option<f64>,
+    /// `totp` or `file`.
+    pub method: String,
+    pub approval_timeout_secs: u64,
+    /// File the `file` method waits for.
+    pub approval_file: String,
+    /// Whether a TOTP secret is in the OS keyring.
+    pub totp_enrolled: bool,
+    /// Whether transfers are limited to whitelisted addresses.
+    pub whitelist_enabled: bool,
+    /// Number of whitelisted addresses.
+    pub whitelisted: usize,
+}
+
+/// `atlas configure security whitelist list` (and `add`/`remove`/`enable`/
+/// `disable`, which show the result).
+#[derive(Debug, Clone, Serialize)]
+pub struct WhitelistOutput {
+    /// Whether transfers to addresses outside `entries` are refused.
+    pub enabled: bool,
+    pub entries: Vec<crate::config::WhitelistEntry>,
+}
+
+/// `atlas notify test` — one delivery per configured target.
+#[derive(Debug, Clone, Serialize)]
+pub struct NotifyTestOutput {
+    pub event: String,
+    pub deliveries: Vec<crate::notify::Delivery>,
+}
+
 // ─── Config ─────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize)]
@@ -200,14 +663,109 @@ pub struct ConfigOutput {
     pub leverage: u32,
     pub slippage: f64,
     pub network: String,
+    pub confirm: bool,
     pub lots: HashMap<String, f64>,
+    /// Redacted unless `--reveal`.
+    pub api_key: Option<String>,
+    /// Builder fee attached to perp orders, in bps (0 = disabled).
+    pub builder_fee_bps: u16,
+    /// Address receiving the builder fee; `None` when disabled.
+    pub builder_address: Option<String>,
+    /// Client-side throttle for Hyperliquid `/info` requests.
+    pub rate_limit: crate::config::RateLimitConfig,
+    /// `atlas tui` settings, read at launch.
+    pub tui: crate::config::TuiConfig,
+    /// Number display rules for tables.
+    pub output: crate::config::OutputConfig,
+    /// Per-coin trading presets.
+    pub presets: Vec<PresetRow>,
+}
+
+/// `atlas configure trading preset list` / `set` / `remove`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetsOutput {
+    pub presets: Vec<PresetRow>,
+}
+
+/// One coin's trading preset; `None` fields fall back to the module default.
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetRow {
+    pub coin: String,
+    pub leverage: Option<u32>,
+    pub slippage: Option<f64>,
+    pub default_size: Option<String>,
+}
+
+impl PresetRow {
+    /// `5x, 0.30% slip, size $500` — only the fields that are set.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(lev) = self.leverage {
+            parts.push(format!("{lev}x"));
+        }
+        if let Some(slip) = self.slippage {
+            parts.push(format!(
+                "{} slip",
+                crate::fmt::format_pct(&slip.to_string())
+            ));
+        }
+        if let Some(size) = &self.default_size {
+            parts.push(format!("size {size}"));
+        }
+        parts.join(", ")
+    }
+}
+
+/// `atlas configure env list`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvListOutput {
+    pub active: Option<String>,
+    pub envs: Vec<EnvRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvRow {
+    pub name: String,
+    pub active: bool,
+    pub network: String,
+    pub profile: String,
+}
+
+/// `atlas configure sync push|pull|status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigSyncOutput {
+    /// `push`, `pull` or `status`.
+    pub action: String,
+    /// Remote version after the command; `None` until the first push.
+    pub remote_version: Option<i64>,
+    pub remote_updated_at: Option<String>,
+    /// Shareable settings were edited here since the last sync.
+    pub local_modified: bool,
+    /// Another machine pushed since the last sync.
+    pub remote_changed: bool,
+    /// `status`: fields that differ from the remote. `push`/`pull`: fields
+    /// the command changed.
+    pub changes: Vec<ConfigFieldChange>,
+    /// `pull --merge`: fields edited on both sides; the local value was kept.
+    pub conflicts: Vec<String>,
+    /// Whether anything was written; `false` for `status` or when in sync.
+    pub applied: bool,
+}
+
+/// One differing setting, by dotted key. `None` where a side lacks it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigFieldChange {
+    pub key: String,
+    pub local: Option<serde_json::Value>,
+    pub remote: Option<serde_json::Value>,
 }
 
 // ─── Doctor ─────────────────────────────────────────────────────────
 
 /// PRD-compliant doctor check result.
 ///
-/// Status is "ok" or "fail". On failure, `fix` contains the actionable hint.
+/// Status is "ok", "warn" or "fail". On warning or failure, `fix` contains
+/// the actionable hint; only failures make doctor exit non-zero.
 #[derive(Debug, Clone, Serialize)]
 pub struct DoctorCheck {
     pub name: String,
@@ -220,6 +778,14 @@ pub struct DoctorCheck {
     pub latency_ms: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<String>,
+    /// Local database schema version (`db_schema` check).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+    /// Migrations not applied yet, as `v<N> <name>` (`db_schema` check).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pending_migrations: Vec<String>,
+    /// `--fix` applied a repair for this check.
+    pub fixed: bool,
 }
 
 impl DoctorCheck {
@@ -231,6 +797,9 @@ impl DoctorCheck {
             fix: None,
             latency_ms: None,
             network: None,
+            schema_version: None,
+            pending_migrations: Vec::new(),
+            fixed: false,
         }
     }
 
@@ -242,6 +811,9 @@ impl DoctorCheck {
             fix: None,
             latency_ms: None,
             network: None,
+            schema_version: None,
+            pending_migrations: Vec::new(),
+            fixed: false,
         }
     }
 
@@ -253,8 +825,32 @@ impl DoctorCheck {
             fix: Some(fix.into()),
             latency_ms: None,
             network: None,
+            schema_version: None,
+            pending_migrations: Vec::new(),
+            fixed: false,
+        }
+    }
+
+    /// Working, but worth changing: `value` is kept, `fix` says how.
+    pub fn warn(name: impl Into<String>, value: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: "warn".into(),
+            value: Some(value.into()),
+            fix: Some(fix.into()),
+            latency_ms: None,
+            network: None,
+            schema_version: None,
+            pending_migrations: Vec::new(),
+            fixed: false,
         }
     }
+
+    /// Mark that `--fix` repaired something for this check.
+    pub fn fixed(mut self) -> Self {
+        self.fixed = true;
+        self
+    }
 }
 
 /// PRD-compliant `atlas doctor --output json` output.
@@ -263,6 +859,95 @@ pub struct DoctorOutput {
     pub checks: Vec<DoctorCheck>,
 }
 
+/// `atlas init --non-interactive --from <file>`.
+#[derive(Debug, Clone, Serialize)]
+pub struct InitOutput {
+    /// Active profile after setup.
+    pub profile: String,
+    pub address: Option<String>,
+    pub network: String,
+    /// Steps the answers file set up, in wizard order.
+    pub applied: Vec<String>,
+    /// Steps still open; a later `atlas init` asks about them.
+    pub pending: Vec<String>,
+    pub doctor: DoctorOutput,
+}
+
+// ─── Modules ────────────────────────────────────────────────────────
+
+/// `atlas configure module list` / `atlas configure module doctor`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleListOutput {
+    pub modules: Vec<ModuleRow>,
+    /// Atlas backend `/health`; `None` with `--no-probe`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend: Option<ModuleHealth>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleRow {
+    pub name: String,
+    pub description: String,
+    pub enabled: bool,
+    /// Configured network (Hyperliquid) or default chain (0x, Morpho).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    pub config: String,
+    /// `None` for disabled modules and with `--no-probe`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<ModuleHealth>,
+}
+
+/// Result of one module probe.
+///
+/// Status is "ok" or "unreachable". On failure, `error` has a one-line summary.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleHealth {
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// What the probe saw, e.g. `229 perps`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ModuleHealth {
+    pub fn ok(latency_ms: u64, detail: Option<String>) -> Self {
+        Self {
+            status: "ok".into(),
+            latency_ms: Some(latency_ms),
+            detail,
+            error: None,
+        }
+    }
+
+    pub fn unreachable(error: impl Into<String>) -> Self {
+        Self {
+            status: "unreachable".into(),
+            latency_ms: None,
+            detail: None,
+            error: Some(error.into()),
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status == "ok"
+    }
+}
+
+impl ModuleListOutput {
+    /// Every probe that ran succeeded.
+    pub fn all_healthy(&self) -> bool {
+        self.modules
+            .iter()
+            .filter_map(|m| m.health.as_ref())
+            .chain(self.backend.as_ref())
+            .all(ModuleHealth::is_ok)
+    }
+}
+
 // ─── Market Data: Price ─────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize)]
@@ -277,6 +962,37 @@ pub struct PriceRow {
     #[serde(rename = "price")]
     pub mid_price: String,
     pub protocol: String,
+    /// Spot only: the pair priced, e.g. `HYPE/USDC`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pair: Option<String>,
+    /// Spot only: the exchange's key for the pair (`@107`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Set when this coin could not be priced (e.g. unknown symbol).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// `atlas market hl price --in <quote>` — prices as cross rates.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrossPriceOutput {
+    pub quote: String,
+    /// USD mid of the quote asset.
+    pub quote_usd: String,
+    pub prices: Vec<CrossPriceRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrossPriceRow {
+    pub symbol: String,
+    /// Price of one `symbol` in the quote asset.
+    pub price: Option<String>,
+    /// USD mid of `symbol`; `price` is `base_usd / quote_usd`.
+    pub base_usd: Option<String>,
+    pub quote_usd: String,
+    /// Set when this coin could not be priced (e.g. unknown symbol).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 // ─── Market Data: Markets ───────────────────────────────────────────
@@ -289,16 +1005,61 @@ pub struct MarketsOutput {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct MarketRow {
+    /// Coin for perps, pair (`HYPE/USDC`) for spot.
     pub name: String,
     pub index: usize,
-    pub max_leverage: u64,
+    /// Perps only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_leverage: Option<u64>,
     pub sz_decimals: i64,
+    /// Spot only: the exchange's key for the pair (`@107`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Spot only: on-chain decimals of the base token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wei_decimals: Option<u32>,
+    /// Spot only: current mid, `None` when the pair has no book.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mid_price: Option<String>,
 }
 
-// ─── Market Data: Candles ───────────────────────────────────────────
+// ─── Market Data: Info ──────────────────────────────────────────────
 
+/// One perp market in detail: book, 24h range, premium and funding.
 #[derive(Debug, Clone, Serialize)]
-pub struct CandlesOutput {
+pub struct MarketInfoOutput {
+    pub symbol: String,
+    pub mid_price: String,
+    pub best_bid: Option<String>,
+    pub best_ask: Option<String>,
+    pub spread: Option<String>,
+    pub spread_bps: Option<String>,
+    pub volume_24h: Option<String>,
+    pub volume_24h_base: Option<String>,
+    pub change_24h_pct: Option<String>,
+    pub high_24h: Option<String>,
+    pub low_24h: Option<String>,
+    /// Open interest in base units.
+    pub open_interest: Option<String>,
+    pub open_interest_usd: Option<String>,
+    pub mark_price: Option<String>,
+    pub index_price: Option<String>,
+    pub oracle_price: Option<String>,
+    /// Mark premium over the oracle, as a fraction.
+    pub premium: Option<String>,
+    pub premium_bps: Option<String>,
+    /// Predicted rate for the next funding payment.
+    pub funding_rate: Option<String>,
+    pub funding_apr_pct: Option<String>,
+    /// Seconds until the next funding payment.
+    pub next_funding_secs: Option<u64>,
+    pub max_leverage: Option<u32>,
+}
+
+// ─── Market Data: Candles ───────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CandlesOutput {
     pub coin: String,
     pub interval: String,
     pub candles: Vec<CandleRow>,
@@ -331,6 +1092,724 @@ pub struct FundingRow {
     pub premium: String,
 }
 
+// ─── Market Data: Top Markets ───────────────────────────────────────
+
+/// `atlas market hl top` — perps ranked by one metric.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopMarketsOutput {
+    /// `volume`, `change`, `oi`, `funding` or `spread`.
+    pub sort: String,
+    pub reverse: bool,
+    pub min_volume: Option<String>,
+    pub markets: Vec<TopMarketRow>,
+}
+
+/// Every metric `top` can sort by, unrounded. `None` where the exchange
+/// reports no value.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopMarketRow {
+    pub symbol: String,
+    pub mid_price: Option<String>,
+    pub mark_price: Option<String>,
+    pub volume_24h: Option<String>,
+    pub change_24h_pct: Option<String>,
+    pub open_interest_usd: Option<String>,
+    /// Per funding interval (hourly on Hyperliquid).
+    pub funding_rate: Option<String>,
+    pub funding_apr_pct: Option<String>,
+    /// Impact ask minus impact bid, in bps of the mid.
+    pub spread_bps: Option<String>,
+    pub best_bid: Option<String>,
+    pub best_ask: Option<String>,
+}
+
+// ─── Market Data: Summary ───────────────────────────────────────────
+
+/// `atlas market hl summary` — headline figures plus one list per
+/// requested section, each under its own key.
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketSummaryOutput {
+    /// Quoted markets covered (only watched ones with `--watchlist`).
+    pub markets: usize,
+    pub volume_24h: String,
+    /// Markets up / down on the day.
+    pub advancing: usize,
+    pub declining: usize,
+    /// Section keys present below, in the order requested.
+    pub sections: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gainers: Option<Vec<SummaryRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub losers: Option<Vec<SummaryRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<Vec<SummaryRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding: Option<Vec<SummaryRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oi: Option<Vec<SummaryRow>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watchlist: Option<WatchlistSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryRow {
+    pub symbol: String,
+    pub price: Option<String>,
+    pub change_24h_pct: Option<String>,
+    pub volume_24h: Option<String>,
+    /// Per funding interval (hourly on Hyperliquid).
+    pub funding_rate: Option<String>,
+    pub open_interest_usd: Option<String>,
+    /// Against the `oi record` snapshot nearest 24h ago; `None` without one.
+    pub oi_change_24h_pct: Option<String>,
+}
+
+/// `summary --watchlist`: the `hl.watchlist` coins and the account's
+/// positions.
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchlistSummary {
+    /// Watched coins in watchlist order.
+    pub coins: Vec<WatchRow>,
+    /// Watched coins Hyperliquid does not list.
+    pub missing: Vec<String>,
+    /// Open positions in coins that are not watched.
+    pub unwatched_positions: Vec<WatchRow>,
+    /// Why position sizes are missing, when the account could not be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positions_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchRow {
+    pub symbol: String,
+    pub price: Option<String>,
+    pub change_24h_pct: Option<String>,
+    pub funding_rate: Option<String>,
+    pub open_interest_usd: Option<String>,
+    pub oi_change_24h_pct: Option<String>,
+    /// Signed: negative for a short; `None` without a position.
+    pub position_size: Option<String>,
+}
+
+// ─── Market Data: Funding Screener ──────────────────────────────────
+
+/// `atlas market hl funding-screen` — most extreme current funding rates.
+#[derive(Debug, Clone, Serialize)]
+pub struct FundingScreenOutput {
+    /// `"rate"` or `"apr"`.
+    pub sort: String,
+    pub min_abs_rate: String,
+    pub min_oi_usd: String,
+    /// Markets left after the OI and rate filters, before `--limit`.
+    pub matched: usize,
+    /// Longs pay shorts. Most extreme first unless `--reverse`.
+    pub positive: Vec<FundingScreenRow>,
+    /// Shorts pay longs. Most extreme first unless `--reverse`.
+    pub negative: Vec<FundingScreenRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FundingScreenRow {
+    pub coin: String,
+    /// Predicted rate for the next funding interval.
+    pub rate: String,
+    pub apr_pct: String,
+    pub open_interest_usd: String,
+    pub volume_24h: String,
+    pub mark_price: String,
+}
+
+// ─── Technical Analysis: Bundle ─────────────────────────────────────
+
+/// `atlas market hl ta` — several indicators over one candle fetch,
+/// keyed by spec (`rsi`, `ema:50`).
+#[derive(Debug, Clone, Serialize)]
+pub struct TaBundleOutput {
+    pub ticker: String,
+    pub timeframe: String,
+    pub candles: usize,
+    pub last_price: String,
+    pub indicators: BTreeMap<String, TaReading>,
+    /// Missing candles in the series the values were computed over.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub gaps: Vec<crate::candle_gaps::CandleGap>,
+}
+
+/// Latest and previous-candle values of one indicator. Single-line
+/// indicators use the field `value`; MACD/BBands/Stoch name each line.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaReading {
+    pub latest: BTreeMap<String, String>,
+    /// `None` when only one candle was available.
+    pub previous: Option<BTreeMap<String, String>>,
+}
+
+// ─── Technical Analysis: Multi-timeframe Trend ──────────────────────
+
+/// `atlas market hl trend <ticker> --mtf 15m,1h,4h`
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendMtfOutput {
+    pub ticker: String,
+    /// One entry per requested timeframe, in request order.
+    pub timeframes: Vec<TrendTimeframeRow>,
+    /// Weighted score across the timeframes that could be scored.
+    pub score: i32,
+    pub direction: String,
+    /// e.g. `"bullish 2/3 timeframes"`, or `"mixed"`.
+    pub verdict: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendTimeframeRow {
+    pub timeframe: String,
+    /// Trend label, or `"n/a"` when the timeframe could not be scored.
+    pub direction: String,
+    pub score: Option<i32>,
+    pub indicators: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Missing candles in the series the values were computed over.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub gaps: Vec<crate::candle_gaps::CandleGap>,
+}
+
+// ─── Market Data: Candlestick Patterns ──────────────────────────────
+
+/// `atlas market hl patterns <ticker>`
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternsOutput {
+    pub ticker: String,
+    pub timeframe: String,
+    /// Closed candles searched.
+    pub lookback: usize,
+    /// Newest first.
+    pub patterns: Vec<PatternRow>,
+    /// Missing candles in the series that was searched.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub gaps: Vec<crate::candle_gaps::CandleGap>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternRow {
+    #[serde(flatten)]
+    pub found: crate::patterns::PatternMatch,
+    /// Open time of the completing candle.
+    pub time: String,
+    /// 1 = the most recent closed candle.
+    pub candles_ago: usize,
+    /// Same as `direction`; kept for older scripts.
+    pub signal: String,
+}
+
+/// `atlas market hl patterns-scan`
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternScanOutput {
+    pub timeframe: String,
+    pub patterns: Vec<String>,
+    /// Markets scanned: the top by 24h volume.
+    pub scanned: usize,
+    /// Patterns completed on each market's latest closed candle, strongest
+    /// first.
+    pub hits: Vec<PatternScanRow>,
+    /// Markets whose candles could not be fetched.
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternScanRow {
+    pub coin: String,
+    pub pattern: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub direction: String,
+    pub strength: u8,
+    pub time: String,
+    pub volume_24h: Option<String>,
+}
+
+// ─── Market Data: Open Interest History ─────────────────────────────
+
+/// `atlas market hl oi <ticker>` — OI, price and volume change over a
+/// window of locally recorded snapshots.
+#[derive(Debug, Clone, Serialize)]
+pub struct OiHistoryOutput {
+    pub ticker: String,
+    pub hours: u64,
+    pub samples: usize,
+    pub from: String,
+    pub to: String,
+    pub oi_start: String,
+    pub oi_end: String,
+    pub oi_change_pct: String,
+    /// Latest OI valued at the latest mark price.
+    pub oi_usd_end: String,
+    pub price_start: String,
+    pub price_end: String,
+    pub price_change_pct: String,
+    /// Rolling 24h notional volume at the first and last sample.
+    pub volume_24h_start: String,
+    pub volume_24h_end: String,
+    pub volume_24h_change: String,
+    /// How OI moved relative to price, e.g. `"longs building"`.
+    pub divergence: String,
+    pub series: Vec<OiPoint>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OiPoint {
+    pub time: String,
+    pub open_interest: String,
+    pub mark_price: String,
+    pub volume_24h: String,
+}
+
+/// `atlas market hl spread-profile <coin>` — median impact spread per UTC
+/// hour from the locally recorded samples.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpreadProfileOutput {
+    pub coin: String,
+    pub samples: usize,
+    /// Median over all retained samples.
+    pub median_bps: f64,
+    pub from: String,
+    pub to: String,
+    pub retention_days: i64,
+    /// Hours with samples only, 0 (UTC midnight) first.
+    pub hours: Vec<crate::spread_profile::HourlySpread>,
+}
+
+// ─── Market Data: Correlation ───────────────────────────────────────
+
+/// `atlas market hl correlate` — pairwise log-return correlation.
+#[derive(Debug, Clone, Serialize)]
+pub struct CorrelationOutput {
+    pub symbols: Vec<String>,
+    pub timeframe: String,
+    /// Return observations used, after aligning candles by timestamp.
+    pub samples: usize,
+    /// Every unordered pair once, in symbol order.
+    pub pairs: Vec<CorrelationPair>,
+    /// Beta of each symbol's returns to the first symbol's.
+    pub betas: BTreeMap<String, Option<f64>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CorrelationPair {
+    pub a: String,
+    pub b: String,
+    /// `None` when either series is flat over the window.
+    pub corr: Option<f64>,
+}
+
+impl CorrelationOutput {
+    /// Correlation for a pair in either order; 1.0 on the diagonal.
+    pub fn corr(&self, a: &str, b: &str) -> Option<f64> {
+        if a == b {
+            return Some(1.0);
+        }
+        self.pairs
+            .iter()
+            .find(|p| (p.a == a && p.b == b) || (p.a == b && p.b == a))
+            .and_then(|p| p.corr)
+    }
+}
+
+// ─── Market Data: DEX Pools ─────────────────────────────────────────
+
+/// `atlas market dex ohlcv` — candles of one onchain pool, oldest first.
+#[derive(Debug, Clone, Serialize)]
+pub struct DexOhlcvOutput {
+    pub network: String,
+    pub pool: String,
+    /// `day`, `hour` or `minute`.
+    pub timeframe: String,
+    /// Timeframe units per candle (`hour` × 4 = 4h candles).
+    pub aggregate: u32,
+    pub base: Option<String>,
+    pub quote: Option<String>,
+    /// Candles asked for; fewer come back for young or quiet pools.
+    pub limit: usize,
+    pub candles: Vec<DexCandleRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DexCandleRow {
+    pub timestamp_ms: u64,
+    pub time: String,
+    /// Prices in USD.
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume_usd: String,
+}
+
+/// `atlas market dex trades` — latest swaps in one onchain pool, newest first.
+#[derive(Debug, Clone, Serialize)]
+pub struct DexTradesOutput {
+    pub network: String,
+    pub pool: String,
+    pub min_usd: Option<String>,
+    pub trades: Vec<DexTradeRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DexTradeRow {
+    pub timestamp_ms: u64,
+    pub time: String,
+    /// `buy` or `sell` of the pool's base token.
+    pub side: String,
+    pub base_amount: String,
+    pub quote_amount: String,
+    /// USD price of the base token in this trade.
+    pub price_usd: Option<String>,
+    pub volume_usd: String,
+    pub tx_hash: String,
+}
+
+/// `atlas market dex watch list`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DexWatchListOutput {
+    pub pools: Vec<DexWatchEntryRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DexWatchEntryRow {
+    pub network: String,
+    pub pool: String,
+    pub label: Option<String>,
+    pub added_ms: i64,
+    /// `None` until the first `watch check`.
+    pub last_checked_ms: Option<i64>,
+    /// Set while the API cannot find the pool.
+    pub missing_since_ms: Option<i64>,
+}
+
+/// Result of `atlas market dex watch add` / `remove`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DexWatchChangeOutput {
+    /// `added` or `removed`.
+    pub action: String,
+    pub entry: DexWatchEntryRow,
+}
+
+/// `atlas market dex watch check` — every watched pool now, against its
+/// previous check.
+#[derive(Debug, Clone, Serialize)]
+pub struct DexWatchCheckOutput {
+    pub checked_ms: i64,
+    /// `--threshold`: only pools whose price moved more than this many
+    /// percent are listed.
+    pub threshold_pct: Option<f64>,
+    /// Pools checked, including those filtered out by the threshold.
+    pub checked: usize,
+    pub pools: Vec<DexWatchRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DexWatchRow {
+    pub network: String,
+    pub pool: String,
+    pub label: Option<String>,
+    pub name: Option<String>,
+    /// `ok`, `new` (first check, no changes yet), `missing` (the API no
+    /// longer returns the pool) or `error`.
+    pub status: String,
+    pub price_usd: Option<String>,
+    /// Changes in percent since the previous check.
+    pub price_change_pct: Option<f64>,
+    pub volume_24h_usd: Option<String>,
+    pub volume_change_pct: Option<f64>,
+    pub liquidity_usd: Option<String>,
+    pub liquidity_change_pct: Option<f64>,
+    pub previous_check_ms: Option<i64>,
+    pub missing_since_ms: Option<i64>,
+    pub error: Option<String>,
+}
+
+// ─── Market Data: Overview ──────────────────────────────────────────
+
+/// `atlas market overview` — the `market.watchlist` coins at a glance.
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketOverviewOutput {
+    /// `None` when the global stats request failed.
+    pub global: Option<MarketOverviewGlobal>,
+    pub sort: String,
+    pub coins: Vec<MarketOverviewRow>,
+    /// Watchlist ids CoinGecko returned nothing for.
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketOverviewGlobal {
+    pub total_market_cap_usd: f64,
+    pub market_cap_change_24h_pct: Option<f64>,
+    pub btc_dominance_pct: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketOverviewRow {
+    /// CoinGecko coin id.
+    pub id: String,
+    pub symbol: String,
+    pub name: String,
+    pub price_usd: f64,
+    pub change_1h_pct: Option<f64>,
+    pub change_24h_pct: Option<f64>,
+    pub change_7d_pct: Option<f64>,
+    pub volume_24h_usd: Option<f64>,
+    pub market_cap_usd: Option<f64>,
+    pub market_cap_rank: Option<u32>,
+    /// Distance from the all-time high in percent (≤ 0).
+    pub from_ath_pct: Option<f64>,
+}
+
+// ─── Market Data: Cross-source Compare ──────────────────────────────
+
+/// `atlas market compare` — one coin's USD price from every source.
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceCompareOutput {
+    pub coin: String,
+    /// Chain the 0x quote was taken on.
+    pub chain: String,
+    /// USDC amount quoted on 0x.
+    pub size_usd: String,
+    /// Median of the available prices; `None` if every source failed.
+    pub median: Option<String>,
+    pub threshold_bps: String,
+    pub sources: Vec<PriceSourceRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceSourceRow {
+    /// `hyperliquid`, `coingecko` or `0x`.
+    pub source: String,
+    pub available: bool,
+    pub price: Option<String>,
+    /// When the source last updated the price, in ms.
+    pub timestamp_ms: Option<u64>,
+    /// Signed distance from the median, in bps.
+    pub deviation_bps: Option<String>,
+    /// Further from the median than `threshold_bps`.
+    pub flagged: bool,
+    /// Why the source is unavailable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A USD price from one source, stamped with when the source saw it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcePrice {
+    pub price: rust_decimal::Decimal,
+    pub timestamp_ms: u64,
+}
+
+impl PriceCompareOutput {
+    /// Rows for each source, scored against the median of those that
+    /// answered. Failed sources become unavailable rows.
+    pub fn new(
+        coin: &str,
+        chain: &str,
+        size_usd: rust_decimal::Decimal,
+        threshold_bps: rust_decimal::Decimal,
+        results: Vec<(String, Result<SourcePrice, String>)>,
+    ) -> Self {
+        use rust_decimal::Decimal;
+
+        let mut prices: Vec<Decimal> = results
+            .iter()
+            .filter_map(|(_, r)| r.as_ref().ok().map(|p| p.price))
+            .collect();
+        prices.sort();
+        let median = match prices.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(prices[n / 2]),
+            n => Some((prices[n / 2 - 1] + prices[n / 2]) / Decimal::TWO),
+        }
+        .filter(|m| !m.is_zero());
+
+        let sources = results
+            .into_iter()
+            .map(|(source, result)| match result {
+                Ok(p) => {
+                    let deviation =
+                        median.map(|m| ((p.price - m) / m * Decimal::from(10_000)).round_dp(1));
+                    PriceSourceRow {
+                        source,
+                        available: true,
+                        price: Some(p.price.normalize().to_string()),
+                        timestamp_ms: Some(p.timestamp_ms),
+                        deviation_bps: deviation.map(|d| d.normalize().to_string()),
+                        flagged: deviation.is_some_and(|d| d.abs() > threshold_bps),
+                        error: None,
+                    }
+                }
+                Err(e) => PriceSourceRow {
+                    source,
+                    available: false,
+                    price: None,
+                    timestamp_ms: None,
+                    deviation_bps: None,
+                    flagged: false,
+                    error: Some(e),
+                },
+            })
+            .collect();
+
+        Self {
+            coin: coin.to_string(),
+            chain: chain.to_string(),
+            size_usd: size_usd.normalize().to_string(),
+            median: median.map(|m| m.normalize().to_string()),
+            threshold_bps: threshold_bps.normalize().to_string(),
+            sources,
+        }
+    }
+}
+
+// ─── 0x Swap Quote ──────────────────────────────────────────────────
+
+/// `atlas 0x quote` — a 0x price with amounts in token units, gas in USD
+/// and the route split per liquidity source.
+///
+/// Amounts without a suffix are base units, as 0x returns them; `*_tokens`
+/// fields apply the token's decimals and are `None` when those are unknown.
+/// Fill in the raw fields, then call [`with_derived`](Self::with_derived).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SwapQuoteOutput {
+    pub chain: String,
+    pub sell_token: String,
+    /// Address book name.
+    pub sell_token_name: Option<String>,
+    /// Symbol reported by 0x.
+    pub sell_symbol: Option<String>,
+    pub sell_decimals: Option<u32>,
+    pub buy_token: String,
+    pub buy_token_name: Option<String>,
+    pub buy_symbol: Option<String>,
+    pub buy_decimals: Option<u32>,
+    pub sell_amount: String,
+    pub buy_amount: String,
+    /// Least the swap may return after slippage.
+    pub min_buy_amount: Option<String>,
+    pub sell_amount_tokens: Option<String>,
+    pub buy_amount_tokens: Option<String>,
+    pub min_buy_amount_tokens: Option<String>,
+    /// Buy tokens received per sell token.
+    pub price: Option<String>,
+    pub sell_usd_price: Option<String>,
+    pub buy_usd_price: Option<String>,
+    pub sell_value_usd: Option<String>,
+    pub buy_value_usd: Option<String>,
+    /// Value lost from sell to buy side at USD prices, in percent. Includes
+    /// the swap fees, so it is an upper bound on pure price impact.
+    pub price_impact_pct: Option<String>,
+    pub slippage_bps: Option<u32>,
+    /// Estimated gas units.
+    pub gas: Option<u64>,
+    pub gas_price_wei: Option<String>,
+    pub gas_price_gwei: Option<String>,
+    /// Total network fee in wei when 0x reports one; otherwise gas × price.
+    pub network_fee_wei: Option<String>,
+    pub native_symbol: Option<String>,
+    pub native_usd_price: Option<String>,
+    pub gas_cost_native: Option<String>,
+    pub gas_cost_usd: Option<String>,
+    pub route: Vec<RouteFillRow>,
+    pub allowance_target: Option<String>,
+    pub allowance_required: bool,
+    pub allowance_spender: Option<String>,
+    /// 0x fee breakdown, as returned.
+    pub fees: Option<serde_json::Value>,
+    /// 0x issues (allowance, balance, simulation), as returned.
+    pub issues: Option<serde_json::Value>,
+    /// 0x request id.
+    pub zid: Option<String>,
+    /// Quoted through the gasless API: the relayer pays gas and takes its
+    /// fee in the sell token, so the gas fields are empty.
+    pub gasless: bool,
+}
+
+/// One leg of the route: a share of the sell amount through one source.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteFillRow {
+    pub source: String,
+    /// Symbol, or address when 0x gave none.
+    pub from: String,
+    pub to: String,
+    pub proportion_pct: String,
+}
+
+impl SwapQuoteOutput {
+    /// Compute token amounts, price, USD values, price impact and gas cost
+    /// from the raw fields. Anything missing an input stays `None`.
+    pub fn with_derived(mut self) -> Self {
+        use crate::assets::from_base_units;
+        use rust_decimal::Decimal;
+
+        let dec = |s: &Option<String>| s.as_deref().and_then(|v| v.parse::<Decimal>().ok());
+        let show = |d: Decimal, dp: u32| d.round_dp(dp).normalize().to_string();
+        let tokens = |raw: Option<&str>, decimals: Option<u32>| {
+            raw.zip(decimals).and_then(|(r, d)| from_base_units(r, d))
+        };
+
+        let sell = tokens(Some(&self.sell_amount), self.sell_decimals);
+        let buy = tokens(Some(&self.buy_amount), self.buy_decimals);
+        let min_buy = tokens(self.min_buy_amount.as_deref(), self.buy_decimals);
+        self.sell_amount_tokens = sell.map(|d| d.normalize().to_string());
+        self.buy_amount_tokens = buy.map(|d| d.normalize().to_string());
+        self.min_buy_amount_tokens = min_buy.map(|d| d.normalize().to_string());
+        self.price = sell
+            .zip(buy)
+            .filter(|(s, _)| !s.is_zero())
+            .map(|(s, b)| show(b / s, 10));
+
+        let sell_value = sell.zip(dec(&self.sell_usd_price)).map(|(a, p)| a * p);
+        let buy_value = buy.zip(dec(&self.buy_usd_price)).map(|(a, p)| a * p);
+        self.sell_value_usd = sell_value.map(|v| show(v, 2));
+        self.buy_value_usd = buy_value.map(|v| show(v, 2));
+        self.price_impact_pct = sell_value
+            .zip(buy_value)
+            .filter(|(s, _)| !s.is_zero())
+            .map(|(s, b)| show((s - b) / s * Decimal::ONE_HUNDRED, 2));
+
+        let gas_price = dec(&self.gas_price_wei);
+        self.gas_price_gwei = gas_price.map(|p| show(p / Decimal::from(1_000_000_000), 4));
+        let fee_wei = dec(&self.network_fee_wei)
+            .or_else(|| self.gas.zip(gas_price).map(|(g, p)| Decimal::from(g) * p));
+        let fee_native = fee_wei.map(|w| w / Decimal::from(1_000_000_000_000_000_000u64));
+        self.gas_cost_native = fee_native.map(|f| show(f, 8));
+        self.gas_cost_usd = fee_native
+            .zip(dec(&self.native_usd_price))
+            .map(|(f, p)| show(f * p, 2));
+        self
+    }
+
+    /// Best label for a side: address book name, then symbol, then address.
+    fn label<'a>(
+        name: &'a Option<String>,
+        symbol: &'a Option<String>,
+        address: &'a str,
+    ) -> &'a str {
+        name.as_deref().or(symbol.as_deref()).unwrap_or(address)
+    }
+}
+
+// ─── Technical Analysis: Backtest ───────────────────────────────────
+
+/// `atlas market hl backtest` — simulated trades and summary stats.
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestOutput {
+    pub ticker: String,
+    pub timeframe: String,
+    pub strategy: String,
+    pub candles: usize,
+    pub fee_bps: f64,
+    pub trade_count: usize,
+    #[serde(flatten)]
+    pub report: crate::backtest::BacktestReport,
+}
+
 // ─── Spot Balance ───────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize)]
@@ -352,10 +1831,20 @@ pub struct SpotBalanceRow {
 pub struct SpotOrderOutput {
     pub market: String,
     pub side: String,
+    /// Size sent, in base tokens (after rounding to the token's decimals).
+    pub size: String,
+    /// `size` × price at submission, in USDC.
+    pub notional: String,
+    /// Set when rounding moved the order more than 1% off a requested USDC amount.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
     pub oid: u64,
     pub status: String,
     pub total_sz: Option<String>,
     pub avg_px: Option<String>,
+    /// Audit log entry of the signed request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audit_id: Option<i64>,
 }
 
 // ─── Spot Transfer ──────────────────────────────────────────────
@@ -404,6 +1893,28 @@ pub struct VaultUserStateRow {
     pub lockup_until: Option<String>,
 }
 
+/// `atlas hl vault list` — public vaults, ranked.
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultListOutput {
+    pub sort: String,
+    /// Vaults that passed `--min-tvl` / `--search`, before `--limit`.
+    pub matched: usize,
+    pub vaults: Vec<VaultListRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultListRow {
+    pub rank: usize,
+    pub address: String,
+    pub name: String,
+    pub leader: String,
+    pub tvl_usd: rust_decimal::Decimal,
+    pub apr_pct: Option<rust_decimal::Decimal>,
+    pub age_days: Option<u64>,
+    /// Only looked up for `--sort followers`.
+    pub followers: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct VaultDepositsOutput {
     pub deposits: Vec<VaultDepositRow>,
@@ -417,6 +1928,65 @@ pub struct VaultDepositRow {
     pub locked_until: Option<String>,
 }
 
+// ─── Lending: Morpho ────────────────────────────────────────────────
+
+/// `atlas morpho markets` — Morpho Blue markets on one chain.
+#[derive(Debug, Clone, Serialize)]
+pub struct MorphoMarketsOutput {
+    pub chain: String,
+    pub markets: Vec<MorphoMarketRow>,
+}
+
+/// `atlas morpho market <id>`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MorphoMarketOutput {
+    pub chain: String,
+    pub market: MorphoMarketRow,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MorphoMarketRow {
+    /// Market unique key (bytes32 hex).
+    pub id: String,
+    /// `COLLATERAL/LOAN`.
+    pub name: String,
+    pub collateral_asset: String,
+    pub loan_asset: String,
+    pub supply_apy_pct: String,
+    pub borrow_apy_pct: String,
+    pub utilization_pct: String,
+    /// Liquidation loan-to-value.
+    pub lltv_pct: String,
+    pub total_supply_usd: String,
+    pub total_borrow_usd: String,
+    pub liquidity_usd: String,
+}
+
+/// `atlas morpho position` — an empty `positions` list means the address
+/// has nothing open on this chain.
+#[derive(Debug, Clone, Serialize)]
+pub struct MorphoPositionsOutput {
+    pub address: String,
+    pub chain: String,
+    pub positions: Vec<MorphoPositionRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MorphoPositionRow {
+    pub market_id: String,
+    pub market: String,
+    pub collateral_asset: String,
+    pub loan_asset: String,
+    pub collateral: String,
+    pub collateral_usd: String,
+    pub borrowed: String,
+    pub borrowed_usd: String,
+    pub supplied: String,
+    pub supplied_usd: String,
+    /// `None` when nothing is borrowed.
+    pub health_factor: Option<String>,
+}
+
 // ─── Subaccounts ────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize)]
@@ -460,12 +2030,40 @@ pub struct AuthProfileRow {
     pub active: bool,
 }
 
+/// `atlas profile remove`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileRemoveOutput {
+    pub name: String,
+    pub address: String,
+    /// False when the keyring had no key for the profile.
+    pub key_deleted: bool,
+    /// Profile activated in its place (`--switch-to`).
+    pub switched_to: Option<String>,
+    /// Cached rows deleted with `--purge-history`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purged_rows: Option<usize>,
+}
+
+/// `atlas profile rename`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileRenameOutput {
+    pub old: String,
+    pub new: String,
+    pub address: String,
+    /// The renamed profile is the active one.
+    pub active: bool,
+    pub warnings: Vec<String>,
+}
+
 // ─── History (trade/order/pnl from local DB cache) ──────────────────
 
 #[derive(Debug, Clone, Serialize)]
 pub struct TradeHistoryOutput {
     pub trades: Vec<TradeHistoryRow>,
     pub total: usize,
+    /// Print journal notes inline instead of a marker (`--with-notes`).
+    #[serde(skip)]
+    pub with_notes: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -478,36 +2076,314 @@ pub struct TradeHistoryRow {
     pub pnl: String,
     pub fee: String,
     pub time: String,
+    pub hash: String,
+    /// A journal note is linked to this fill.
+    pub has_note: bool,
+    /// Linked notes; only filled in with `--with-notes`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
 }
 
+/// `atlas history swaps`: 0x swaps and recorded quotes.
 #[derive(Debug, Clone, Serialize)]
-pub struct OrderHistoryOutput {
-    pub orders: Vec<OrderHistoryRow>,
+pub struct SwapHistoryOutput {
+    pub swaps: Vec<SwapHistoryRow>,
     pub total: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
-pub struct OrderHistoryRow {
-    pub coin: String,
-    pub side: String,
-    pub size: String,
-    pub price: String,
-    pub oid: i64,
+pub struct SwapHistoryRow {
+    /// `executed` or `quoted`.
     pub status: String,
-    pub order_type: String,
+    pub chain: String,
+    pub sell_token: String,
+    pub sell_symbol: Option<String>,
+    pub buy_token: String,
+    pub buy_symbol: Option<String>,
+    /// Token units when decimals are known, else base units.
+    pub sell_amount: String,
+    pub buy_amount: String,
+    /// Buy tokens per sell token.
+    pub price: Option<String>,
+    pub sell_value_usd: Option<String>,
+    pub gas_cost_usd: Option<String>,
+    pub tx_hash: Option<String>,
     pub time: String,
 }
 
+/// `atlas history transfers`: cached deposits, withdrawals and transfers.
 #[derive(Debug, Clone, Serialize)]
-pub struct PnlSummaryOutput {
-    pub total_pnl: String,
-    pub total_fees: String,
-    pub net_pnl: String,
-    pub trade_count: usize,
-    pub win_count: usize,
-    pub loss_count: usize,
-    pub win_rate: String,
-    pub by_coin: Vec<PnlByCoinRow>,
+pub struct TransferHistoryOutput {
+    pub transfers: Vec<TransferRow>,
+    pub total: usize,
+    /// USD in minus USD out over the listed transfers.
+    pub net_deposits: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferRow {
+    /// `deposit`, `withdraw` or `internal`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Hyperliquid's ledger entry type, e.g. `vaultDeposit`.
+    pub ledger_type: String,
+    /// `in`, `out`, or `none` for spot↔perp moves.
+    pub direction: String,
+    pub token: String,
+    pub amount: String,
+    pub usd_value: Option<String>,
+    pub fee: String,
+    pub counterparty: Option<String>,
+    pub hash: String,
+    pub time: String,
+}
+
+/// `atlas history import`: trades read from a CSV file into the fills cache.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportOutput {
+    pub file: String,
+    /// Label the fills are stored under, e.g. `custom`.
+    pub protocol: String,
+    /// `generic` or `hyperliquid-ui`.
+    pub format: String,
+    /// Nothing was written; `imported` counts what would have been.
+    pub dry_run: bool,
+    pub imported: usize,
+    /// Valid rows already cached or repeated in the file.
+    pub skipped: usize,
+    pub failed: usize,
+    pub errors: Vec<ImportErrorRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportErrorRow {
+    /// 1-based, counting the header.
+    pub line: usize,
+    pub error: String,
+}
+
+// ─── Journal ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalOutput {
+    pub entries: Vec<JournalRow>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalRow {
+    pub id: i64,
+    pub coin: Option<String>,
+    pub time: String,
+    /// `linked` once attached to a cached fill, `pending` until the next sync.
+    pub status: String,
+    pub fill_hash: Option<String>,
+    pub oid: Option<i64>,
+    pub note: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JournalAddOutput {
+    pub entry: JournalRow,
+}
+
+// ─── Audit Log ──────────────────────────────────────────────────────
+
+/// `atlas audit list`
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditListOutput {
+    pub entries: Vec<AuditRow>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRow {
+    pub id: i64,
+    pub time: String,
+    pub protocol: String,
+    /// `order`, `cancel`, `modify`, `leverage`, `margin`, `transfer`, `agent`,
+    /// `swap`, `approve`, `revoke` or `gasless`.
+    #[serde(rename = "type")]
+    pub action_type: String,
+    pub coin: Option<String>,
+    /// `pending` until the exchange answered, then `ok`, `rejected` or `error`.
+    pub status: String,
+    pub nonce: Option<i64>,
+    pub signer: String,
+    /// keccak256 of the signed action's parameters.
+    pub params_hash: String,
+}
+
+/// `atlas audit show <id>` — one entry with the raw request and response.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEntryOutput {
+    #[serde(flatten)]
+    pub entry: AuditRow,
+    /// The request as sent, secrets redacted.
+    pub request: serde_json::Value,
+    /// The exchange's raw answer (JSON when it parses) or the error text;
+    /// `null` while pending.
+    pub response: Option<serde_json::Value>,
+    pub completed: Option<String>,
+}
+
+// ─── Address Book ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressBookOutput {
+    /// Chain filter, if one was given.
+    pub chain: Option<String>,
+    pub tokens: Vec<AddressBookRow>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressBookRow {
+    pub chain: String,
+    pub name: String,
+    pub address: String,
+    pub decimals: Option<u32>,
+    /// `built-in` or `user`.
+    pub source: String,
+}
+
+/// Result of `atlas address add` / `remove`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressChangeOutput {
+    /// `added` or `removed`.
+    pub action: String,
+    pub entry: AddressBookRow,
+}
+
+// ─── Wallet: ERC-20 Allowances ──────────────────────────────────────
+
+/// `atlas wallet allowance`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AllowanceOutput {
+    pub chain: String,
+    pub token: String,
+    /// Address book name.
+    pub token_name: Option<String>,
+    pub decimals: Option<u32>,
+    pub owner: String,
+    pub spender: String,
+    /// Known contract name, e.g. `0x AllowanceHolder`.
+    pub spender_name: Option<String>,
+    /// Base units.
+    pub allowance: String,
+    /// `allowance` adjusted for decimals; `None` when unlimited or the
+    /// decimals are unknown.
+    pub allowance_tokens: Option<String>,
+    /// At or near the uint256 maximum.
+    pub unlimited: bool,
+}
+
+impl AllowanceOutput {
+    fn token_label(&self) -> &str {
+        self.token_name.as_deref().unwrap_or(&self.token)
+    }
+
+    fn spender_label(&self) -> &str {
+        self.spender_name.as_deref().unwrap_or(&self.spender)
+    }
+
+    fn amount_label(&self) -> String {
+        if self.unlimited {
+            return "unlimited".into();
+        }
+        match &self.allowance_tokens {
+            Some(tokens) => format!("{tokens} {}", self.token_label()),
+            None => format!("{} base units", self.allowance),
+        }
+    }
+}
+
+/// Result of `atlas wallet approve` / `revoke`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApprovalOutput {
+    /// `approved` or `revoked`.
+    pub action: String,
+    pub tx_hash: String,
+    /// Allowance read back once the transaction confirmed.
+    #[serde(flatten)]
+    pub allowance: AllowanceOutput,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderHistoryOutput {
+    pub orders: Vec<OrderHistoryRow>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderHistoryRow {
+    pub coin: String,
+    pub side: String,
+    pub size: String,
+    pub price: String,
+    pub oid: i64,
+    pub status: String,
+    pub order_type: String,
+    pub time: String,
+    /// Size filled so far.
+    pub filled: String,
+    /// Average fill price, once any fill is cached.
+    pub avg_price: Option<String>,
+    /// When the status was last confirmed by `history sync`.
+    pub status_updated: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PnlSummaryOutput {
+    pub total_pnl: String,
+    pub total_fees: String,
+    pub net_pnl: String,
+    pub trade_count: usize,
+    pub win_count: usize,
+    pub loss_count: usize,
+    pub win_rate: String,
+    pub by_coin: Vec<PnlByCoinRow>,
+    /// USD deposited minus withdrawn over the same period, from the synced
+    /// ledger. `net_pnl + net_deposits` should match the change in account
+    /// value. Absent when filtering by coin or a protocol other than
+    /// Hyperliquid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_deposits: Option<String>,
+    /// Tokens bought through 0x swaps, valued at cached prices. Kept apart
+    /// from the realized totals above.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swaps: Option<SwapPnlSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SwapPnlSummary {
+    pub positions: Vec<SwapPnlRow>,
+    /// Sums over priced positions only.
+    pub cost_usd: String,
+    pub value_usd: String,
+    pub unrealized_pnl: String,
+    /// Positions with no cached price, left out of the sums.
+    pub unpriced: usize,
+    /// Executed swaps without a USD cost or token decimals.
+    pub skipped_swaps: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SwapPnlRow {
+    pub chain: String,
+    pub token: String,
+    pub symbol: Option<String>,
+    /// Token units bought.
+    pub amount: String,
+    pub cost_usd: String,
+    pub gas_usd: String,
+    pub price_usd: Option<String>,
+    pub value_usd: Option<String>,
+    pub unrealized_pnl: Option<String>,
+    /// When the price was cached.
+    pub priced_at: Option<String>,
+    pub swaps: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -522,6 +2398,14 @@ pub struct PnlByCoinRow {
 pub struct SyncOutput {
     pub fills_synced: usize,
     pub orders_synced: usize,
+    /// Cached orders whose status or filled size changed.
+    pub orders_updated: usize,
+    /// Ledger entries (deposits, withdrawals, transfers) not cached before.
+    pub transfers_synced: usize,
+    /// Funding payments not cached before.
+    pub funding_synced: usize,
+    /// Whether the whole account history was walked (`--full`).
+    pub full: bool,
     pub status: String,
 }
 
@@ -556,9 +2440,255 @@ pub enum OutputFormat {
 /// Trait for types that can render as a human-readable table.
 ///
 /// Implement this on each structured output type to define
-/// how it looks in table mode.
+/// how it looks in table mode. Glyphs, borders and colors come from
+/// the [`RenderContext`] rather than being hardcoded per impl.
 pub trait TableDisplay {
-    fn print_table(&self);
+    /// Write the table-mode rendering into `out`.
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result;
+
+    /// Output that only confirms a side effect (a set, cancel or
+    /// transfer). `--quiet` suppresses it entirely.
+    fn side_effect_only(&self) -> bool {
+        false
+    }
+
+    /// The table rendering as a string. In plain mode anything an impl
+    /// didn't route through the context is transliterated as a last resort.
+    fn table_string(&self, ctx: &RenderContext) -> String {
+        let mut out = String::new();
+        self.write_table(&mut out, ctx)
+            .expect("writing to a String cannot fail");
+        if ctx.plain {
+            crate::fmt::to_ascii(&out)
+        } else {
+            out
+        }
+    }
+
+    /// Print the table to stdout using the process-wide [`render_context`].
+    fn print_table(&self) {
+        let ctx = render_context();
+        if ctx.quiet && self.side_effect_only() {
+            return;
+        }
+        print!("{}", self.table_string(&ctx));
+    }
+}
+
+// ─── Workspace Backup ───────────────────────────────────────────────
+
+/// Result of `atlas workspace backup`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupOutput {
+    pub path: String,
+    pub bytes: u64,
+    pub files: Vec<String>,
+    /// Profiles whose key is in the archive, encrypted.
+    pub profiles_with_keys: Vec<String>,
+    pub created_at: String,
+}
+
+/// Result of `atlas workspace restore`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreOutput {
+    pub path: String,
+    /// When the backup was taken.
+    pub created_at: String,
+    pub files_restored: Vec<String>,
+    pub profiles_imported: Vec<String>,
+    /// Restored without a key: re-import with `atlas profile import`.
+    pub profiles_without_key: Vec<String>,
+}
+
+// ─── Candle Cache ───────────────────────────────────────────────────
+
+/// Result of `atlas cache candles`: the cached series after any pruning.
+#[derive(Debug, Clone, Serialize)]
+pub struct CandleCacheOutput {
+    pub series: Vec<CandleSeriesRow>,
+    /// Candles deleted by `--prune-before`; `None` when not pruning.
+    pub pruned: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CandleSeriesRow {
+    /// `hyperliquid` or `hyperliquid-testnet`.
+    pub source: String,
+    pub coin: String,
+    pub interval: String,
+    pub candles: usize,
+    pub oldest_ms: u64,
+    pub newest_ms: u64,
+}
+
+// ─── Stream Recording ───────────────────────────────────────────────
+
+/// Result of `atlas stream record`, once it stops. Mirrors the
+/// recording's `manifest.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamRecordingOutput {
+    pub dir: String,
+    pub ticker: String,
+    pub channels: Vec<String>,
+    pub network: String,
+    pub started: String,
+    pub ended: String,
+    /// `interrupted`, `disk_space`, `stream_closed` or `error`.
+    pub stop_reason: String,
+    /// Messages per channel.
+    pub messages: BTreeMap<String, u64>,
+    pub segments: usize,
+    pub bytes: u64,
+    /// Reconnects: nothing was received between `from_ms` and `to_ms`.
+    pub gaps: Vec<crate::recording::Gap>,
+}
+
+// ─── Render context ─────────────────────────────────────────────────
+
+/// How table output is drawn: `--plain` / `--quiet` plus terminal detection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderContext {
+    /// ASCII only: no box drawing, emoji or colors.
+    pub plain: bool,
+    /// Print nothing for side-effect-only commands; errors still show.
+    pub quiet: bool,
+    /// ANSI colors in tables.
+    pub color: bool,
+    /// Decimals and separators for numbers in tables (`output.*`).
+    pub numbers: crate::fmt::NumberFormat,
+}
+
+static RENDER_CONTEXT: OnceLock<RenderContext> = OnceLock::new();
+
+/// Set the process-wide render context. Only the first call takes effect.
+pub fn set_render_context(ctx: RenderContext) {
+    let _ = RENDER_CONTEXT.set(ctx);
+}
+
+/// The process-wide render context, detected from the environment if
+/// [`set_render_context`] was never called.
+pub fn render_context() -> RenderContext {
+    *RENDER_CONTEXT.get_or_init(|| RenderContext::detect(false, false))
+}
+
+impl RenderContext {
+    /// Combine the CLI flags with the environment: `NO_COLOR`,
+    /// `ATLAS_PLAIN` or `TERM=dumb` force plain output, and colors are
+    /// only used when stdout is a terminal.
+    pub fn detect(plain: bool, quiet: bool) -> Self {
+        let set = |key: &str| std::env::var_os(key).is_some_and(|v| !v.is_empty());
+        let plain = plain
+            || set("NO_COLOR")
+            || set("ATLAS_PLAIN")
+            || std::env::var("TERM").is_ok_and(|t| t == "dumb");
+        Self {
+            plain,
+            quiet,
+            color: !plain && crate::fmt::color_enabled(),
+            numbers: crate::fmt::NumberFormat::default(),
+        }
+    }
+
+    /// The same context with the `output.*` number rules.
+    pub fn with_numbers(self, numbers: crate::fmt::NumberFormat) -> Self {
+        Self { numbers, ..self }
+    }
+
+    /// Plain, uncolored rendering — what tests and pipes see.
+    pub fn plain() -> Self {
+        Self {
+            plain: true,
+            ..Self::default()
+        }
+    }
+
+    /// A table styled for this context.
+    pub fn table(&self) -> crate::fmt::Table {
+        crate::fmt::Table::new().ascii(self.plain).color(self.color)
+    }
+
+    /// A label / value panel styled for this context.
+    pub fn panel(&self, title: impl Into<String>) -> crate::fmt::Table {
+        crate::fmt::Table::panel(title)
+            .ascii(self.plain)
+            .color(self.color)
+    }
+
+    /// Success mark.
+    pub fn ok(&self) -> &'static str {
+        if self.plain {
+            "OK"
+        } else {
+            "✓"
+        }
+    }
+
+    /// Failure mark.
+    pub fn fail(&self) -> &'static str {
+        if self.plain {
+            "FAIL"
+        } else {
+            "✗"
+        }
+    }
+
+    /// Warning mark.
+    pub fn warn(&self) -> &'static str {
+        if self.plain {
+            "WARN"
+        } else {
+            "⚠"
+        }
+    }
+
+    /// Placeholder for a missing value.
+    pub fn dash(&self) -> &'static str {
+        if self.plain {
+            "-"
+        } else {
+            "—"
+        }
+    }
+
+    pub fn arrow(&self) -> &'static str {
+        if self.plain {
+            "->"
+        } else {
+            "→"
+        }
+    }
+
+    /// Horizontal rule under a plain column header.
+    pub fn rule(&self, width: usize) -> String {
+        if self.plain { "-" } else { "─" }.repeat(width)
+    }
+
+    /// Section heading, with its emoji outside plain mode.
+    pub fn heading(&self, emoji: &str, text: &str) -> String {
+        if self.plain {
+            text.to_string()
+        } else {
+            format!("{emoji} {text}")
+        }
+    }
+
+    /// Free text (names, warnings) made safe for this context.
+    pub fn text(&self, s: &str) -> String {
+        if self.plain {
+            crate::fmt::to_ascii(s)
+        } else {
+            s.to_string()
+        }
+    }
+}
+
+/// Print a one-line success confirmation for a side-effect command,
+/// e.g. `✓ confirm = on`. Nothing is printed under `--quiet`.
+pub fn print_confirmation(message: &str) {
+    let ctx = render_context();
+    if !ctx.quiet {
+        println!("{} {}", ctx.ok(), ctx.text(message));
+    }
 }
 
 /// A generic API response wrapper for JSON output.
@@ -574,12 +2704,37 @@ pub struct ApiResponse<T> {
     error: Option<serde_json::Value>,
 }
 
+static CAPTURED: Mutex<Option<Vec<serde_json::Value>>> = Mutex::new(None);
+
+/// Start keeping a JSON copy of everything [`render`] prints, until
+/// [`take_captured`]. Used by `atlas schedule run` to record order results.
+pub fn start_capture() {
+    if let Ok(mut captured) = CAPTURED.lock() {
+        *captured = Some(Vec::new());
+    }
+}
+
+/// What was rendered since [`start_capture`]; stops capturing.
+pub fn take_captured() -> Vec<serde_json::Value> {
+    CAPTURED
+        .lock()
+        .ok()
+        .and_then(|mut captured| captured.take())
+        .unwrap_or_default()
+}
+
 /// Render structured output — JSON or table depending on format.
 ///
 /// For JSON formats, uses `serde_json` serialization and wraps the output
 /// in an `ApiResponse` envelope (`{"ok":true,"data":...}` or `{"ok":false,"error":...}`).
-/// For table format, calls `TableDisplay::print_table()`.
+/// For table format, calls `TableDisplay::print_table()`, which honours
+/// the process-wide [`RenderContext`].
 pub fn render<T: Serialize + TableDisplay>(format: OutputFormat, data: &T) -> anyhow::Result<()> {
+    if let Ok(mut captured) = CAPTURED.lock() {
+        if let Some(values) = captured.as_mut() {
+            values.push(serde_json::to_value(data)?);
+        }
+    }
     match format {
         OutputFormat::Table => {
             data.print_table();
@@ -641,614 +2796,3933 @@ pub fn render_json_or<T: Serialize>(format: OutputFormat, data: &T) -> anyhow::R
 // ─── TableDisplay implementations for output types ──────────────────
 
 impl TableDisplay for StatusOutput {
-    fn print_table(&self) {
-        let dash = "—";
-        println!("╔══════════════════════════════════════════════════════════╗");
-        println!("║  ACCOUNT SUMMARY                                       ║");
-        println!("╠══════════════════════════════════════════════════════════╣");
-        println!("║  Profile     : {:<41}║", self.profile);
-        println!("║  Address     : {:<41}║", self.address);
-        println!("║  Network     : {:<41}║", self.network);
-        println!(
-            "║  Modules     : {:<41}║",
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{format_usd, Cell};
+        let dash = ctx.dash();
+        let mut summary = ctx.panel("ACCOUNT SUMMARY");
+        summary.kv("Profile", &self.profile);
+        summary.kv("Address", &self.address);
+        if let Some(env) = &self.env {
+            summary.kv("Environment", env);
+        }
+        summary.kv("Network", &self.network);
+        summary.kv(
+            "Modules",
             if self.modules.is_empty() {
                 "none".to_string()
             } else {
                 self.modules.join(", ")
-            }
+            },
         );
-        println!(
-            "║  Account Val : {:<41}║",
-            self.account_value.as_deref().unwrap_or(dash)
-        );
-        println!(
-            "║  Margin Used : {:<41}║",
-            self.margin_used.as_deref().unwrap_or(dash)
-        );
-        println!(
-            "║  Net Pos     : {:<41}║",
-            self.net_position.as_deref().unwrap_or(dash)
-        );
-        println!(
-            "║  Withdrawable: {:<41}║",
-            self.withdrawable.as_deref().unwrap_or(dash)
-        );
-        println!("║  Open Orders : {:<41}║", self.open_orders);
-        println!("╠══════════════════════════════════════════════════════════╣");
-
+        let usd = |v: &Option<String>| v.as_deref().map_or(dash.into(), |v| ctx.numbers.usd_str(v));
+        summary.kv("Account Val", usd(&self.account_value));
+        summary.kv("Margin Used", usd(&self.margin_used));
+        summary.kv("Net Pos", usd(&self.net_position));
+        summary.kv("Withdrawable", usd(&self.withdrawable));
+        summary.kv("Open Orders", self.open_orders.to_string());
+
+        out.push_str(&summary.render());
         if self.positions.is_empty() {
-            println!("║  No open positions.                                      ║");
+            writeln!(out, "No open positions.")?;
         } else {
-            println!(
-                "║  {:^6} │ {:^10} │ {:^10} │ {:^12} ║",
-                "Coin", "Size", "Entry", "uPnL"
-            );
-            println!("║  ──────┼────────────┼────────────┼────────────── ║");
-            for pos in &self.positions {
-                println!(
-                    "║  {:^6} │ {:>10} │ {:>10} │ {:>12} ║",
-                    pos.coin,
-                    pos.size,
-                    pos.entry_price.as_deref().unwrap_or(dash),
-                    pos.unrealized_pnl.as_deref().unwrap_or(dash),
-                );
+            out.push_str(&positions_table(&self.positions, ctx).render());
+        }
+
+        if let Some(total) = &self.total_value_usd {
+            let heading = format!("PORTFOLIO {} {}", dash, format_usd(total));
+            writeln!(out, "\n{}", ctx.heading("💼", &heading))?;
+            for section in &self.protocols {
+                let mut t = ctx
+                    .table()
+                    .title(format!(
+                        "{} {} {}",
+                        section.protocol,
+                        dash,
+                        format_usd(&section.value_usd)
+                    ))
+                    .column("Asset", Align::Left)
+                    .column("Chain", Align::Left)
+                    .column("Amount", Align::Right)
+                    .column("Value", Align::Right);
+                for b in &section.balances {
+                    t.row([
+                        Cell::new(&b.asset),
+                        Cell::new(&b.chain),
+                        Cell::new(&b.amount),
+                        Cell::new(
+                            b.value_usd
+                                .as_deref()
+                                .map(format_usd)
+                                .unwrap_or_else(|| "unpriced".into()),
+                        ),
+                    ]);
+                }
+                out.push_str(&t.render());
+                if section.positions > 0 {
+                    writeln!(out, "   {} open position(s)", section.positions)?;
+                }
             }
         }
-        println!("╚══════════════════════════════════════════════════════════╝");
+        if let Some(diff) = &self.diff {
+            write_status_diff(out, diff, ctx)?;
+        }
+        Ok(())
+    }
+
+    fn print_table(&self) {
+        let ctx = render_context();
+        print!("{}", self.table_string(&ctx));
+        for w in &self.warnings {
+            eprintln!("{} {}", ctx.warn(), ctx.text(w));
+        }
+    }
+}
+
+/// The `status --diff` section: account value and order count changes,
+/// then changed positions marked `+` (new), `-` (closed) or `~` (resized).
+fn write_status_diff(out: &mut String, diff: &StatusDiff, ctx: &RenderContext) -> std::fmt::Result {
+    use crate::fmt::{format_timestamp_ms, Cell};
+    let Some(baseline_ms) = diff.baseline_ms else {
+        writeln!(
+            out,
+            "\nNo earlier snapshot to compare with; this run is the baseline for the next --diff."
+        )?;
+        return Ok(());
+    };
+    let plus = |s: &str| match crate::fmt::sign_of(s) {
+        crate::fmt::Sign::Positive => format!("+{s}"),
+        _ => s.to_string(),
+    };
+
+    let mut changes = ctx.panel(format!(
+        "CHANGES SINCE {} UTC",
+        format_timestamp_ms(baseline_ms.max(0) as u64)
+    ));
+    changes.kv(
+        "Account Val",
+        match &diff.account_value_change {
+            Some(v) => Cell::signed(plus(v)),
+            None => Cell::new(ctx.dash()),
+        },
+    );
+    changes.kv(
+        "Open Orders",
+        Cell::signed(plus(&diff.open_orders_change.to_string())),
+    );
+    writeln!(out)?;
+    out.push_str(&changes.render());
+
+    if diff.positions.is_empty() {
+        return writeln!(out, "No position changes.");
+    }
+    let multi = spans_protocols(diff.positions.iter().map(|p| &p.protocol));
+    let mut t = ctx.table().column("", Align::Left);
+    if multi {
+        t = t.column("Protocol", Align::Left);
+    }
+    t = t
+        .column("Coin", Align::Left)
+        .column("Before", Align::Right)
+        .column("After", Align::Right)
+        .column("Change", Align::Right);
+    for p in &diff.positions {
+        let mut row = Vec::with_capacity(6);
+        row.push(match p.change.as_str() {
+            "new" => Cell::new("+").color(crate::fmt::Color::Green),
+            "closed" => Cell::new("-").color(crate::fmt::Color::Red),
+            _ => Cell::new("~"),
+        });
+        if multi {
+            row.push(Cell::new(&p.protocol));
+        }
+        row.extend([
+            Cell::new(&p.symbol),
+            Cell::new(p.size_before.as_deref().unwrap_or(ctx.dash())),
+            Cell::new(p.size_after.as_deref().unwrap_or(ctx.dash())),
+            Cell::signed(plus(&p.size_change)),
+        ]);
+        t.row(row);
+    }
+    out.push_str(&t.render());
+    Ok(())
+}
+
+/// Coin / Size / Entry / uPnL table shared by status, positions and
+/// subaccounts. Gains a Protocol column when rows come from several modules.
+fn positions_table(positions: &[PositionRow], ctx: &RenderContext) -> crate::fmt::Table {
+    use crate::fmt::Cell;
+    let nf = &ctx.numbers;
+    let multi = spans_protocols(positions.iter().map(|p| &p.protocol));
+    let mut t = ctx.table();
+    if multi {
+        t = t.column("Protocol", Align::Left);
+    }
+    t = t
+        .column("Coin", Align::Left)
+        .column("Size", Align::Right)
+        .column("Entry", Align::Right)
+        .column("uPnL", Align::Right);
+    for pos in positions {
+        let mut row = Vec::with_capacity(5);
+        if multi {
+            row.push(Cell::new(&pos.protocol));
+        }
+        row.extend([
+            Cell::new(&pos.coin),
+            Cell::new(nf.size_str(&pos.size, None)),
+            Cell::new(nf.price_str(pos.entry_price.as_deref().unwrap_or(ctx.dash()), None)),
+            Cell::signed(nf.usd_str(pos.unrealized_pnl.as_deref().unwrap_or(ctx.dash()))),
+        ]);
+        t.row(row);
+    }
+    t
+}
+
+/// True when rows come from more than one protocol.
+fn spans_protocols<'a>(mut protocols: impl Iterator<Item = &'a String>) -> bool {
+    match protocols.next() {
+        Some(first) => protocols.any(|p| p != first),
+        None => false,
     }
 }
 
 impl TableDisplay for OrdersOutput {
-    fn print_table(&self) {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let spot = self.market_type == "spot";
         if self.orders.is_empty() {
-            println!("No open orders.");
-            return;
+            return writeln!(
+                out,
+                "{}",
+                if spot {
+                    "No open spot orders."
+                } else {
+                    "No open orders."
+                }
+            );
         }
 
-        println!("┌────────┬──────┬────────────┬──────────────┬────────────────┐");
-        println!("│ Coin   │ Side │ Size       │ Price        │ OID            │");
-        println!("├────────┼──────┼────────────┼──────────────┼────────────────┤");
+        // Type and trigger columns only once a stop order is resting
+        let triggers = self.orders.iter().any(|o| o.trigger_price.is_some());
+        let mut t = ctx
+            .table()
+            .column(if spot { "Pair" } else { "Coin" }, Align::Left)
+            .column("Side", Align::Left);
+        if triggers {
+            t = t
+                .column("Type", Align::Left)
+                .column("Trigger", Align::Right);
+        }
+        t = t
+            .column("Size", Align::Right)
+            .column("Price", Align::Right)
+            .column("OID", Align::Right);
         for o in &self.orders {
-            println!(
-                "│ {:<6} │ {:<4} │ {:>10} │ {:>12} │ {:>14} │",
-                o.coin, o.side, o.size, o.price, o.oid,
-            );
+            let mut row = vec![o.coin.clone(), o.side.clone()];
+            if triggers {
+                row.push(match &o.trigger_type {
+                    Some(kind) => format!("{} ({kind})", o.order_type),
+                    None => o.order_type.clone(),
+                });
+                row.push(o.trigger_price.clone().unwrap_or_else(|| ctx.dash().into()));
+            }
+            row.extend([o.size.clone(), o.price.clone(), o.oid.to_string()]);
+            t.row(row);
         }
-        println!("└────────┴──────┴────────────┴──────────────┴────────────────┘");
+        out.push_str(&t.render());
+        Ok(())
     }
 }
 
 impl TableDisplay for FillsOutput {
-    fn print_table(&self) {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::Cell;
         if self.fills.is_empty() {
-            println!("No recent fills.");
-            return;
+            return writeln!(out, "No recent fills.");
         }
+        let nf = &ctx.numbers;
 
-        println!("┌────────┬──────┬────────────┬──────────────┬──────────────┬──────────┐");
-        println!("│ Coin   │ Side │ Size       │ Price        │ Closed PnL   │ Fee      │");
-        println!("├────────┼──────┼────────────┼──────────────┼──────────────┼──────────┤");
+        let multi = spans_protocols(self.fills.iter().map(|f| &f.protocol));
+        let mut t = ctx.table().column("Time (UTC)", Align::Left);
+        if multi {
+            t = t.column("Protocol", Align::Left);
+        }
+        t = t
+            .column("Coin", Align::Left)
+            .column("Side", Align::Left)
+            .column("Size", Align::Right)
+            .column("Price", Align::Right)
+            .column("Closed PnL", Align::Right)
+            .column("Fee", Align::Right);
         for f in &self.fills {
-            println!(
-                "│ {:<6} │ {:<4} │ {:>10} │ {:>12} │ {:>12} │ {:>8} │",
-                f.coin, f.side, f.size, f.price, f.closed_pnl, f.fee,
-            );
+            let mut row = Vec::with_capacity(8);
+            row.push(Cell::new(crate::fmt::format_timestamp_ms(f.timestamp_ms)));
+            if multi {
+                row.push(Cell::new(&f.protocol));
+            }
+            row.extend([
+                Cell::new(&f.coin),
+                Cell::new(&f.side),
+                Cell::new(nf.size_str(&f.size, None)),
+                Cell::new(nf.price_str(&f.price, None)),
+                Cell::signed(nf.usd_str(&f.closed_pnl)),
+                Cell::new(nf.usd_str(&f.fee)),
+            ]);
+            t.row(row);
+        }
+        t.separator();
+        let mut total = vec![Cell::new("Total")];
+        if multi {
+            total.push(Cell::default());
+        }
+        total.extend([
+            Cell::default(),
+            Cell::default(),
+            Cell::default(),
+            Cell::default(),
+            Cell::signed(nf.usd_str(&self.net_closed_pnl)),
+            Cell::new(nf.usd_str(&self.total_fee)),
+        ]);
+        t.row(total);
+        out.push_str(&t.render());
+        Ok(())
+    }
+
+    fn print_table(&self) {
+        let ctx = render_context();
+        print!("{}", self.table_string(&ctx));
+        for w in &self.warnings {
+            eprintln!("{} {}", ctx.warn(), ctx.text(w));
         }
-        println!("└────────┴──────┴────────────┴──────────────┴──────────────┴──────────┘");
     }
 }
 
-impl TableDisplay for OrderResultOutput {
+impl TableDisplay for PositionsOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::Cell;
+        if self.positions.is_empty() {
+            return writeln!(out, "No open positions.");
+        }
+        let dash = ctx.dash();
+        let multi = spans_protocols(self.positions.iter().map(|p| &p.protocol));
+        let mut t = ctx.table();
+        if multi {
+            t = t.column("Protocol", Align::Left);
+        }
+        t = t
+            .column("Coin", Align::Left)
+            .column("Size", Align::Right)
+            .column("Entry", Align::Right)
+            .column("Mark", Align::Right)
+            .column("Notional", Align::Right)
+            .column("uPnL", Align::Right)
+            .column("PnL %", Align::Right);
+        for pos in &self.positions {
+            let mut row = Vec::with_capacity(8);
+            if multi {
+                row.push(Cell::new(&pos.protocol));
+            }
+            row.extend([
+                Cell::new(&pos.coin),
+                Cell::new(&pos.size),
+                Cell::new(pos.entry_price.as_deref().unwrap_or(dash)),
+                Cell::new(pos.mark_price.as_deref().unwrap_or(dash)),
+                Cell::new(pos.notional.as_deref().unwrap_or(dash)),
+                Cell::signed(pos.unrealized_pnl.as_deref().unwrap_or(dash)),
+                Cell::signed(
+                    pos.pnl_pct
+                        .as_deref()
+                        .map_or_else(|| dash.to_string(), |p| format!("{p}%")),
+                ),
+            ]);
+            t.row(row);
+        }
+        t.separator();
+        let mut total = vec![Cell::new("Total")];
+        if multi {
+            total.push(Cell::default());
+        }
+        total.extend([
+            Cell::default(),
+            Cell::default(),
+            Cell::default(),
+            Cell::new(&self.total_notional),
+            Cell::signed(&self.total_unrealized_pnl),
+            Cell::default(),
+        ]);
+        t.row(total);
+        out.push_str(&t.render());
+        Ok(())
+    }
+
     fn print_table(&self) {
+        let ctx = render_context();
+        print!("{}", self.table_string(&ctx));
+        for w in &self.warnings {
+            eprintln!("{} {}", ctx.warn(), ctx.text(w));
+        }
+    }
+}
+
+impl TableDisplay for OrderResultOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let ok = ctx.ok();
         match self.status.as_str() {
             "filled" => {
-                let sz = self.total_sz.as_deref().unwrap_or("—");
-                let px = self.avg_px.as_deref().unwrap_or("—");
-                println!(
-                    "✓ Order FILLED (oid: {}, size: {}, avg_px: {})",
+                let sz = self.total_sz.as_deref().unwrap_or(ctx.dash());
+                let px = self.avg_px.as_deref().unwrap_or(ctx.dash());
+                writeln!(
+                    out,
+                    "{ok} Order FILLED (oid: {}, size: {}, avg_px: {})",
                     self.oid, sz, px
-                );
-            }
-            "resting" => {
-                println!("✓ Order RESTING (oid: {})", self.oid);
-            }
-            _ => {
-                println!("✓ Order accepted (oid: {})", self.oid);
+                )
             }
+            "resting" => match (&self.trigger_price, &self.tif) {
+                (Some(px), _) => writeln!(
+                    out,
+                    "{ok} Trigger order RESTING (oid: {}, {} @ {px})",
+                    self.oid,
+                    self.trigger_type.as_deref().unwrap_or("market")
+                ),
+                (None, Some(tif)) => {
+                    writeln!(out, "{ok} Order RESTING (oid: {}, tif: {tif})", self.oid)
+                }
+                (None, None) => writeln!(out, "{ok} Order RESTING (oid: {})", self.oid),
+            },
+            _ => writeln!(out, "{ok} Order accepted (oid: {})", self.oid),
         }
     }
-}
 
-impl TableDisplay for CancelOutput {
     fn print_table(&self) {
-        println!(
-            "✓ Cancelled {}/{} orders on {}.",
-            self.cancelled, self.total, self.coin
-        );
+        let ctx = render_context();
+        print!("{}", self.table_string(&ctx));
+        for w in &self.warnings {
+            eprintln!("{} {}", ctx.warn(), ctx.text(w));
+        }
+        if let Some(w) = &self.liquidity_warning {
+            eprintln!("{} {}", ctx.warn(), w.describe());
+        }
     }
 }
 
-impl TableDisplay for CancelSingleOutput {
-    fn print_table(&self) {
-        println!("✓ Order {} on {} cancelled.", self.oid, self.coin);
+impl TableDisplay for MarketOrderPreview {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let source = |src: crate::config::ParamSource| match src {
+            crate::config::ParamSource::Flag => String::new(),
+            other => format!(" ({other})"),
+        };
+        let mut p = ctx.panel(format!("MARKET {} — DRY RUN", self.side.to_uppercase()));
+        p.kv("Market", &self.symbol);
+        p.kv("Size", format!("{}{}", self.size, source(self.size_source)));
+        p.kv("Mark", format!("${}", self.mark));
+        p.kv(
+            "Price band",
+            format!(
+                "{} ${} ({:.1}% slip){}",
+                if self.side == "buy" { "≤" } else { "≥" },
+                self.limit_price,
+                self.slippage * 100.0,
+                source(self.slippage_source)
+            ),
+        );
+        let impact = match (&self.estimated_impact_bps, &self.estimated_avg_px) {
+            (Some(bps), Some(px)) => format!("{bps} bps @ {px}"),
+            _ => match &self.book_coverage_pct {
+                Some(pct) => format!("insufficient visible liquidity ({pct}% covered)"),
+                None => "book unavailable".to_string(),
+            },
+        };
+        p.kv("Est. impact", impact);
+        if let Some(w) = &self.liquidity_warning {
+            p.kv(
+                "Liquidity",
+                format!(
+                    "{} spread {:.2} bps, {:.1}x median",
+                    ctx.warn(),
+                    w.spread_bps,
+                    w.ratio
+                ),
+            );
+        }
+        p.kv("Notional", format!("${}", self.notional));
+        p.kv(
+            "Leverage",
+            format!("{}x{}", self.leverage, source(self.leverage_source)),
+        );
+        out.push_str(&p.render());
+        writeln!(out, "Dry run — nothing was sent.")
     }
 }
 
-impl TableDisplay for LeverageOutput {
-    fn print_table(&self) {
-        println!(
-            "✓ {} leverage set to {}x ({})",
-            self.coin, self.leverage, self.mode
-        );
+impl TableDisplay for FlipOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        self.order.write_table(out, ctx)?;
+        writeln!(
+            out,
+            "  {}: {} {} {} {} {}",
+            self.coin,
+            self.before.side.to_uppercase(),
+            self.before.size,
+            ctx.arrow(),
+            self.after.side.to_uppercase(),
+            self.after.size
+        )
     }
 }
 
-impl TableDisplay for MarginOutput {
-    fn print_table(&self) {
-        println!("✓ {} ${} margin on {}", self.action, self.amount, self.coin);
+impl TableDisplay for CloseAllOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.results.is_empty() {
+            return writeln!(out, "No positions to close.");
+        }
+
+        use crate::fmt::{Cell, Color};
+        let dash = || Cell::new(ctx.dash()).color(Color::Dim);
+        let mut t = ctx
+            .table()
+            .title("CLOSE ALL")
+            .column("Coin", Align::Left)
+            .column("Side", Align::Left)
+            .column("Size", Align::Right)
+            .column("Status", Align::Left)
+            .column("Avg Px", Align::Right)
+            .column("PnL", Align::Right);
+        for row in &self.results {
+            let status = match &row.error {
+                Some(e) => Cell::new(format!("{} {e}", ctx.fail())).color(Color::Red),
+                None => Cell::new(&row.status),
+            };
+            t.row([
+                Cell::new(&row.coin),
+                Cell::new(&row.side),
+                Cell::new(&row.size),
+                status,
+                row.avg_price.as_ref().map_or_else(dash, Cell::from),
+                row.realized_pnl.as_ref().map_or_else(dash, Cell::from),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(
+            out,
+            "{} Closed {}, failed {} {} realized PnL ${}",
+            if self.summary.failed == 0 {
+                ctx.ok()
+            } else {
+                ctx.warn()
+            },
+            self.summary.closed,
+            self.summary.failed,
+            ctx.dash(),
+            self.summary.realized_pnl
+        )
     }
 }
 
-impl TableDisplay for TransferOutput {
-    fn print_table(&self) {
-        println!(
-            "✓ Transferred ${} USDC to {}",
-            self.amount, self.destination
-        );
+impl TableDisplay for CancelOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        writeln!(
+            out,
+            "{} Cancelled {}/{} orders on {}.",
+            ctx.ok(),
+            self.cancelled,
+            self.total,
+            self.coin
+        )
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
     }
 }
 
-impl TableDisplay for ConfigOutput {
-    fn print_table(&self) {
-        println!("╔══════════════════════════════════════════════════════════╗");
-        println!("║  ATLAS CONFIGURATION                                   ║");
-        println!("╠══════════════════════════════════════════════════════════╣");
-        println!("║  Mode      : {:<43}║", self.mode);
-        println!("║  Size Mode : {:<43}║", self.size_mode);
-        println!("║  Leverage  : {:<43}║", format!("{}x", self.leverage));
-        println!(
-            "║  Slippage  : {:<43}║",
-            format!("{:.1}%", self.slippage * 100.0)
-        );
-        println!("║  Network   : {:<43}║", self.network);
-        println!("╠══════════════════════════════════════════════════════════╣");
-        if !self.lots.is_empty() {
-            println!("║  Lot Sizes:                                            ║");
-            let mut sorted: Vec<_> = self.lots.iter().collect();
-            sorted.sort_by_key(|(k, _)| (*k).clone());
-            for (coin, size) in &sorted {
-                println!("║    {:<6} : {:<39}║", coin, format!("{} units/lot", size));
-            }
-        }
-        println!("╚══════════════════════════════════════════════════════════╝");
+impl TableDisplay for CancelSingleOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        writeln!(
+            out,
+            "{} Order {} on {} cancelled.",
+            ctx.ok(),
+            self.oid,
+            self.coin
+        )
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
     }
 }
 
-impl TableDisplay for DoctorOutput {
-    fn print_table(&self) {
-        println!("┌─────────────────────────────────────────────┐");
-        println!("│  ATLAS DOCTOR                               │");
-        println!("├─────────────────────────────────────────────┤");
-        for check in &self.checks {
-            let icon = if check.status == "ok" { "✓" } else { "✗" };
-            let label = format!("{:<14}", check.name);
-            if check.status == "ok" {
-                let val = check.value.as_deref().unwrap_or("");
-                let display = if val.is_empty() {
-                    icon.to_string()
-                } else {
-                    format!("{icon} ({val})")
-                };
-                println!("│  {label}: {:<27}│", display);
-            } else {
-                let fix = check
-                    .fix
-                    .as_deref()
-                    .unwrap_or("")
-                    .chars()
-                    .take(26)
-                    .collect::<String>();
-                println!("│  {label}: {icon} → {:<26}│", fix);
-            }
+impl TableDisplay for LeverageAuditOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.rows.is_empty() {
+            return writeln!(out, "No open positions.");
+        }
+
+        use crate::fmt::{Cell, Color};
+        let dash = || Cell::new(ctx.dash()).color(Color::Dim);
+        let mut t = ctx
+            .table()
+            .title(format!(
+                "LEVERAGE AUDIT (default {}x)",
+                self.default_leverage
+            ))
+            .column("Coin", Align::Left)
+            .column("Mode", Align::Left)
+            .column("Leverage", Align::Right)
+            .column("Default", Align::Right)
+            .column("Action", Align::Left);
+        for row in &self.rows {
+            let leverage = match row.leverage {
+                Some(l) if row.mismatch => Cell::new(format!("{l}x")).color(Color::Yellow),
+                Some(l) => Cell::new(format!("{l}x")),
+                None => dash(),
+            };
+            let action = match (row.action.as_str(), &row.error) {
+                (_, Some(e)) => Cell::new(format!("{} {e}", ctx.fail())).color(Color::Red),
+                ("set", None) => {
+                    Cell::new(format!("set to {}x", row.default_leverage)).color(Color::Green)
+                }
+                ("flagged", None) => Cell::new("mismatch").color(Color::Yellow),
+                _ => dash(),
+            };
+            t.row([
+                Cell::new(&row.coin),
+                row.mode.as_ref().map_or_else(dash, Cell::from),
+                leverage,
+                Cell::new(format!("{}x", row.default_leverage)),
+                action,
+            ]);
         }
-        let all_ok = self.checks.iter().all(|c| c.status == "ok");
-        println!("├─────────────────────────────────────────────┤");
-        if all_ok {
-            println!("│  ✓ All systems operational.                 │");
+        out.push_str(&t.render());
+
+        let count = |action: &str| self.rows.iter().filter(|r| r.action == action).count();
+        let (flagged, set, failed) = (count("flagged"), count("set"), count("failed"));
+        if set + failed > 0 {
+            writeln!(
+                out,
+                "{} Set {set}, failed {failed}",
+                if failed == 0 { ctx.ok() } else { ctx.warn() }
+            )?;
+        } else if flagged > 0 {
+            writeln!(
+                out,
+                "{} {flagged} position(s) differ from the default; \
+                 run with --apply-default to align them",
+                ctx.warn()
+            )?;
         } else {
-            println!("│  Issues found. Run with --fix to repair.    │");
+            writeln!(out, "{} Every position uses the default leverage", ctx.ok())?;
         }
-        println!("└─────────────────────────────────────────────┘");
+        Ok(())
     }
 }
 
-impl TableDisplay for RiskCalcOutput {
-    fn print_table(&self) {
-        println!("╔══════════════════════════════════════════════════════════╗");
-        println!("║  RISK CALCULATOR                                       ║");
-        println!("╠══════════════════════════════════════════════════════════╣");
-        println!(
-            "║  Asset        : {:<6} {:<34}║",
+impl TableDisplay for LeverageOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        writeln!(
+            out,
+            "{} {} leverage set to {}x ({})",
+            ctx.ok(),
             self.coin,
-            self.side.to_uppercase()
-        );
-        println!("║  Entry Price  : ${:<43.4}║", self.entry_price);
-        println!(
-            "║  Size         : {:<40}║",
-            format!("{:.6} {}", self.size, self.coin)
-        );
-        if (self.lots - self.size).abs() > 0.0001 {
-            println!("║  Lots         : {:<40.4}║", self.lots);
-        }
-        println!("║  Notional     : ${:<43.2}║", self.notional);
-        println!("╠══════════════════════════════════════════════════════════╣");
-        println!("║  Stop-Loss    : ${:<43.4}║", self.stop_loss);
-        println!("║  Take-Profit  : ${:<43.4}║", self.take_profit);
-        println!("║  Est. Liq     : ${:<43.4}║", self.est_liquidation);
-        println!("╠══════════════════════════════════════════════════════════╣");
-        println!("║  Risk (USDC)  : ${:<43.2}║", self.risk_usd);
-        println!(
-            "║  Risk (%)     : {:<43}║",
-            format!("{:.2}%", self.risk_pct * 100.0)
-        );
-        println!("║  Margin Req.  : ${:<43.2}║", self.margin);
-        println!("║  Leverage     : {:<43}║", format!("{}x", self.leverage));
-        println!("╚══════════════════════════════════════════════════════════╝");
+            self.leverage,
+            self.mode
+        )
+    }
 
-        if !self.warnings.is_empty() {
-            println!();
-            for w in &self.warnings {
-                println!("{w}");
-            }
-            if self.blocked {
-                println!();
-                println!("❌ Trade BLOCKED by risk rules.");
-            }
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for MarginOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        writeln!(
+            out,
+            "{} {} ${} margin on {}",
+            ctx.ok(),
+            self.action,
+            self.amount,
+            self.coin
+        )
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for TransferOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let mut to = match &self.name {
+            Some(name) => format!("{name} ({})", self.destination),
+            None => self.destination.clone(),
+        };
+        if let Some(label) = &self.label {
+            to.push_str(&format!(" [{}]", ctx.text(label)));
         }
+        writeln!(
+            out,
+            "{} Transferred ${} USDC to {to}",
+            ctx.ok(),
+            self.amount
+        )
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
     }
 }
 
-impl TableDisplay for SpotBalanceOutput {
-    fn print_table(&self) {
-        if self.balances.is_empty() {
-            println!("No spot token balances.");
-            return;
+impl TableDisplay for PaperResetOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        writeln!(
+            out,
+            "{} Paper account reset to ${} USDC ({} fills cleared)",
+            ctx.ok(),
+            self.balance,
+            self.fills_cleared
+        )
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for ProfileRemoveOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        writeln!(
+            out,
+            "{} Removed profile '{}' ({})",
+            ctx.ok(),
+            self.name,
+            self.address
+        )?;
+        if !self.key_deleted {
+            writeln!(out, "  No keyring key was stored for it.")?;
         }
+        if let Some(next) = &self.switched_to {
+            writeln!(out, "  Active profile is now '{next}'.")?;
+        }
+        if let Some(rows) = self.purged_rows {
+            writeln!(out, "  Purged {rows} cached rows.")?;
+        }
+        Ok(())
+    }
 
-        println!("┌──────────┬──────────────┬──────────────┬──────────────┐");
-        println!("│ Token    │ Total        │ Held         │ Available    │");
-        println!("├──────────┼──────────────┼──────────────┼──────────────┤");
-        for b in &self.balances {
-            println!(
-                "│ {:<8} │ {:>12} │ {:>12} │ {:>12} │",
-                b.coin, b.total, b.held, b.available,
-            );
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for ProfileRenameOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let active = if self.active { " (active)" } else { "" };
+        writeln!(
+            out,
+            "{} Renamed profile '{}' {} '{}'{active}",
+            ctx.ok(),
+            self.old,
+            ctx.arrow(),
+            self.new
+        )?;
+        for w in &self.warnings {
+            writeln!(out, "{} {}", ctx.warn(), ctx.text(w))?;
         }
-        println!("└──────────┴──────────────┴──────────────┴──────────────┘");
+        Ok(())
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
     }
 }
 
-impl TableDisplay for SpotOrderOutput {
-    fn print_table(&self) {
-        match self.status.as_str() {
-            "filled" => {
-                let sz = self.total_sz.as_deref().unwrap_or("—");
-                let px = self.avg_px.as_deref().unwrap_or("—");
-                println!(
-                    "✓ Spot {} {} FILLED (oid: {}, size: {}, avg_px: {})",
-                    self.side, self.market, self.oid, sz, px
-                );
+impl TableDisplay for ConfigOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let mut p = ctx.panel("ATLAS CONFIGURATION");
+        p.kv("Mode", &self.mode);
+        p.kv("Size Mode", &self.size_mode);
+        p.kv("Leverage", format!("{}x", self.leverage));
+        p.kv("Slippage", format!("{:.1}%", self.slippage * 100.0));
+        p.kv("Network", &self.network);
+        p.kv("Confirm", if self.confirm { "on" } else { "off" });
+        p.kv("API Key", self.api_key.as_deref().unwrap_or("not set"));
+        match &self.builder_address {
+            Some(addr) => p.kv(
+                "Builder Fee",
+                format!(
+                    "{} bps → {}",
+                    self.builder_fee_bps,
+                    crate::fmt::truncate_address(addr)
+                ),
+            ),
+            None => p.kv("Builder Fee", "off"),
+        }
+        let rate = &self.rate_limit;
+        if rate.requests_per_sec > 0.0 {
+            let mut text = format!("{} req/s, burst {}", rate.requests_per_sec, rate.burst);
+            if rate.exceeds_documented_limit() {
+                text.push_str(&format!(
+                    " {} above HL limit ({}/s)",
+                    ctx.warn(),
+                    crate::constants::HL_INFO_REQUESTS_PER_SEC
+                ));
             }
-            "resting" => {
-                println!(
-                    "✓ Spot {} {} RESTING (oid: {})",
-                    self.side, self.market, self.oid
-                );
+            p.kv("Info Rate Limit", text);
+        } else {
+            p.kv("Info Rate Limit", "off");
+        }
+        let tui = &self.tui;
+        p.kv(
+            "TUI",
+            format!(
+                "{} tab, refresh {}s, prices {}",
+                tui.default_tab,
+                tui.refresh_secs,
+                tui.price_decimals
+                    .map_or("as output".to_string(), |dp| format!("to {dp} dp"))
+            ),
+        );
+        let o = &self.output;
+        p.kv(
+            "Numbers",
+            format!(
+                "prices {}, sizes {}, USD {} dp, % {} dp, separators {}",
+                o.price_decimals,
+                o.size_decimals,
+                o.usd_decimals,
+                o.pct_decimals,
+                if o.thousands_separator { "on" } else { "off" }
+            ),
+        );
+        for (action, keys) in &tui.keys {
+            p.kv(&format!("Key {action}"), keys);
+        }
+        if !self.presets.is_empty() {
+            p.separator();
+            for row in &self.presets {
+                p.kv(&format!("Preset {}", row.coin), row.summary());
             }
-            _ => {
-                println!(
-                    "✓ Spot {} {} accepted (oid: {})",
-                    self.side, self.market, self.oid
-                );
+        }
+        if !self.lots.is_empty() {
+            p.separator();
+            let mut sorted: Vec<_> = self.lots.iter().collect();
+            sorted.sort_by_key(|(k, _)| (*k).clone());
+            for (coin, size) in &sorted {
+                p.kv(&format!("Lot {coin}"), format!("{size} units/lot"));
             }
         }
+        out.push_str(&p.render());
+        Ok(())
     }
 }
 
-impl TableDisplay for SpotTransferOutput {
-    fn print_table(&self) {
-        println!(
-            "✓ Transferred {} {} ({})",
-            self.amount, self.token, self.direction
-        );
+impl TableDisplay for EnvListOutput {
+    fn write_table(&self, out: &mut String, _ctx: &RenderContext) -> std::fmt::Result {
+        if self.envs.is_empty() {
+            return writeln!(out, "No environments configured.");
+        }
+        writeln!(
+            out,
+            "  {:<2}{:<12} {:<10} {:<16}",
+            "", "ENV", "NETWORK", "PROFILE"
+        )?;
+        for env in &self.envs {
+            writeln!(
+                out,
+                "  {:<2}{:<12} {:<10} {:<16}",
+                if env.active { "*" } else { "" },
+                env.name,
+                env.network,
+                env.profile
+            )?;
+        }
+        writeln!(out)?;
+        writeln!(out, "Switch with: atlas configure env use <name>")
     }
 }
 
-impl TableDisplay for VaultDetailsOutput {
-    fn print_table(&self) {
-        println!("╔══════════════════════════════════════════════════════════╗");
-        println!("║  VAULT DETAILS                                         ║");
-        println!("╠══════════════════════════════════════════════════════════╣");
-        println!("║  Name         : {:<41}║", self.name);
-        println!("║  Address      : {:<41}║", self.address);
-        println!("║  Leader       : {:<41}║", self.leader);
-        println!("║  APR          : {:<41}║", format!("{}%", self.apr));
-        println!(
-            "║  Leader Frac  : {:<41}║",
-            format!("{}%", self.leader_fraction)
-        );
-        println!(
-            "║  Commission   : {:<41}║",
-            format!("{}%", self.leader_commission)
-        );
-        println!("║  Distributable: ${:<40}║", self.max_distributable);
-        println!("║  Withdrawable : ${:<40}║", self.max_withdrawable);
-        println!("║  Followers    : {:<41}║", self.follower_count);
-        println!(
-            "║  Closed       : {:<41}║",
-            if self.is_closed { "Yes" } else { "No" }
-        );
-        println!(
-            "║  Deposits     : {:<41}║",
-            if self.allow_deposits {
-                "Allowed"
-            } else {
-                "Closed"
+impl TableDisplay for ConfigSyncOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let version = match self.remote_version {
+            Some(v) => format!("v{v}"),
+            None => "nothing pushed yet".into(),
+        };
+        match (self.action.as_str(), self.applied) {
+            ("push", true) => writeln!(out, "{} Pushed config ({version})", ctx.ok())?,
+            ("pull", true) => writeln!(out, "{} Pulled config ({version})", ctx.ok())?,
+            ("status", _) => {
+                let mut p = ctx.panel("CONFIG SYNC");
+                p.kv("Remote", &version);
+                if let Some(at) = &self.remote_updated_at {
+                    p.kv("Updated", at);
+                }
+                let since = |changed: bool| if changed { "since last sync" } else { "none" };
+                p.kv("Local edits", since(self.local_modified));
+                p.kv("Remote edits", since(self.remote_changed));
+                out.push_str(&p.render());
             }
-        );
-        println!("╠══════════════════════════════════════════════════════════╣");
-
-        if !self.description.is_empty() {
-            println!(
-                "║  {:<55}║",
-                self.description.chars().take(55).collect::<String>()
-            );
-            println!("╠══════════════════════════════════════════════════════════╣");
+            _ => return writeln!(out, "{} Config is in sync ({version})", ctx.ok()),
         }
 
-        if let Some(state) = &self.user_state {
-            println!("║  YOUR POSITION                                         ║");
-            println!("║  Equity       : ${:<40}║", state.equity);
-            println!("║  PnL          : ${:<40}║", state.pnl);
-            println!("║  All-time PnL : ${:<40}║", state.all_time_pnl);
-            println!("║  Days         : {:<41}║", state.days_following);
-            if let Some(lockup) = &state.lockup_until {
-                println!("║  Locked Until : {:<41}║", lockup);
+        if !self.changes.is_empty() {
+            let show = |v: &Option<serde_json::Value>| match v {
+                Some(v) => v.to_string(),
+                None => ctx.dash().to_string(),
+            };
+            writeln!(out)?;
+            writeln!(out, "  {:<40} {:<20} {:<20}", "KEY", "LOCAL", "REMOTE")?;
+            for c in &self.changes {
+                writeln!(
+                    out,
+                    "  {:<40} {:<20} {:<20}",
+                    c.key,
+                    show(&c.local),
+                    show(&c.remote)
+                )?;
             }
-            println!("╠══════════════════════════════════════════════════════════╣");
         }
+        for key in &self.conflicts {
+            writeln!(
+                out,
+                "{} {key} changed here and remotely; kept the local value",
+                ctx.warn()
+            )?;
+        }
+        Ok(())
+    }
+}
 
-        if !self.followers.is_empty() {
-            println!("║  TOP FOLLOWERS                                         ║");
-            println!(
-                "║  {:<20} │ {:>12} │ {:>12} │ {:>4} ║",
-                "User", "Equity", "PnL", "Days"
-            );
-            println!("║  ────────────────────┼──────────────┼──────────────┼──────║");
-            for f in self.followers.iter().take(10) {
-                let user_short = if f.user.len() > 20 {
-                    format!("{}…", &f.user[..19])
+impl TableDisplay for DoctorOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{Cell, Color};
+        let mut t = ctx.panel("ATLAS DOCTOR").max_width(48);
+        for check in &self.checks {
+            let line = if check.status == "ok" {
+                let mark = if check.fixed {
+                    format!("{} fixed", ctx.ok())
                 } else {
-                    f.user.clone()
+                    ctx.ok().to_string()
                 };
-                println!(
-                    "║  {:<20} │ {:>12} │ {:>12} │ {:>4} ║",
-                    user_short, f.equity, f.pnl, f.days_following,
-                );
-            }
+                match check.value.as_deref().filter(|v| !v.is_empty()) {
+                    Some(val) => Cell::new(format!("{mark} ({val})")).color(Color::Green),
+                    None => Cell::new(mark).color(Color::Green),
+                }
+            } else if check.status == "warn" {
+                let value = check.value.as_deref().unwrap_or("");
+                Cell::new(format!("{} ({value})", ctx.warn())).color(Color::Yellow)
+            } else {
+                let fix = check.fix.as_deref().unwrap_or("");
+                Cell::new(format!("{} {} {fix}", ctx.fail(), ctx.arrow())).color(Color::Red)
+            };
+            t.kv(&check.name, line);
+        }
+        out.push_str(&t.render());
+        if self.checks.iter().all(|c| c.status != "fail") {
+            writeln!(out, "{} All systems operational.", ctx.ok())?;
+        } else {
+            writeln!(out, "Issues found. Run with --fix to repair.")?;
+        }
+        // The box truncates fix hints; print them in full
+        for check in self.checks.iter().filter(|c| c.status != "ok") {
+            let fix = check.fix.as_deref().unwrap_or("");
+            let mark = if check.status == "warn" {
+                ctx.warn()
+            } else {
+                ctx.fail()
+            };
+            writeln!(out, "  {mark} {}: {}", check.name, ctx.text(fix))?;
         }
-        println!("╚══════════════════════════════════════════════════════════╝");
+        Ok(())
     }
 }
 
-impl TableDisplay for VaultDepositsOutput {
-    fn print_table(&self) {
-        if self.deposits.is_empty() {
-            println!("No vault deposits.");
-            return;
+impl TableDisplay for ModuleListOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{Cell, Color};
+        let probed = self.backend.is_some() || self.modules.iter().any(|m| m.health.is_some());
+        let dash = || Cell::new(ctx.dash()).color(Color::Dim);
+        let health_cells = |h: Option<&ModuleHealth>| match h {
+            None => [dash(), dash()],
+            Some(h) if h.is_ok() => [
+                Cell::new(ctx.ok()).color(Color::Green),
+                Cell::new(format!("{}ms", h.latency_ms.unwrap_or(0))),
+            ],
+            Some(_) => [Cell::new("unreachable").color(Color::Red), dash()],
+        };
+
+        let mut t = ctx
+            .table()
+            .title("ATLAS OS — MODULES")
+            .column("Module", Align::Left)
+            .column("State", Align::Left);
+        if probed {
+            t = t
+                .column("Health", Align::Left)
+                .column("Latency", Align::Right);
+        }
+        t = t
+            .column("Network", Align::Left)
+            .column("Details", Align::Left);
+
+        for m in &self.modules {
+            let state = if m.enabled {
+                Cell::new("on").color(Color::Green)
+            } else {
+                Cell::new("off").color(Color::Dim)
+            };
+            let mut row = vec![Cell::new(&m.name), state];
+            if probed {
+                row.extend(health_cells(m.health.as_ref()));
+            }
+            row.push(m.network.as_deref().map_or_else(dash, Cell::new));
+            let details = match m.health.as_ref().and_then(|h| h.detail.as_deref()) {
+                Some(seen) => format!("{} ({seen})", m.config),
+                None => m.config.clone(),
+            };
+            row.push(Cell::new(details));
+            t.row(row);
+        }
+        if let Some(backend) = &self.backend {
+            let mut row = vec![Cell::new("backend"), dash()];
+            row.extend(health_cells(Some(backend)));
+            row.extend([dash(), Cell::new("Atlas API gateway")]);
+            t.row(row);
         }
+        out.push_str(&t.render());
+
+        // Errors don't fit the table; print them in full
+        let failures = self
+            .modules
+            .iter()
+            .filter_map(|m| Some((m.name.as_str(), m.health.as_ref()?)))
+            .chain(self.backend.as_ref().map(|b| ("backend", b)))
+            .filter(|(_, h)| !h.is_ok());
+        for (name, h) in failures {
+            let error = h.error.as_deref().unwrap_or("");
+            writeln!(out, "  {} {name}: {}", ctx.fail(), ctx.text(error))?;
+        }
+        Ok(())
+    }
+}
 
-        println!(
-            "┌──────────────────────────────────────────────┬──────────────┬──────────────────┐"
+impl TableDisplay for MarketInfoOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::Cell;
+        let dash = || ctx.dash().to_string();
+        let usd = |v: &Option<String>| v.as_ref().map(|v| format!("${v}")).unwrap_or_else(dash);
+
+        let mut p = ctx.panel(format!("{} — Market Info", self.symbol));
+        p.kv("Mid Price", format!("${}", self.mid_price));
+        p.kv("Best Bid", usd(&self.best_bid));
+        p.kv("Best Ask", usd(&self.best_ask));
+        p.kv(
+            "Spread",
+            match (&self.spread, &self.spread_bps) {
+                (Some(s), Some(bps)) => format!("${s} ({bps} bps)"),
+                _ => dash(),
+            },
         );
-        println!(
-            "│ Vault Address                                │ Equity       │ Locked Until      │"
+        p.separator();
+        p.kv("24h Volume", usd(&self.volume_24h));
+        p.kv(
+            "24h Change",
+            match &self.change_24h_pct {
+                Some(c) => Cell::signed(format!("{c}%")),
+                None => Cell::new(dash()),
+            },
         );
-        println!(
-            "├──────────────────────────────────────────────┼──────────────┼──────────────────┤"
+        p.kv("24h High", usd(&self.high_24h));
+        p.kv("24h Low", usd(&self.low_24h));
+        p.separator();
+        p.kv("Mark Price", usd(&self.mark_price));
+        p.kv("Oracle Price", usd(&self.oracle_price));
+        p.kv(
+            "Premium",
+            match &self.premium_bps {
+                Some(bps) => Cell::signed(format!("{bps} bps")),
+                None => Cell::new(dash()),
+            },
         );
-        for d in &self.deposits {
-            let locked = d.locked_until.as_deref().unwrap_or("—");
-            println!(
-                "│ {:<44} │ {:>12} │ {:<16} │",
-                d.vault_address, d.equity, locked,
-            );
-        }
-        println!(
-            "├──────────────────────────────────────────────┼──────────────┼──────────────────┤"
+        p.kv(
+            "Funding (pred.)",
+            match (&self.funding_rate, &self.funding_apr_pct) {
+                (Some(rate), Some(apr)) => Cell::signed(format!("{rate} ({apr}% APR)")),
+                (Some(rate), None) => Cell::signed(rate.clone()),
+                _ => Cell::new(dash()),
+            },
         );
-        println!(
-            "│ {:<44} │ {:>12} │ {:<16} │",
-            "TOTAL", self.total_equity, "",
+        p.kv(
+            "Next Funding",
+            self.next_funding_secs
+                .map(crate::fmt::format_countdown)
+                .unwrap_or_else(dash),
         );
-        println!(
-            "└──────────────────────────────────────────────┴──────────────┴──────────────────┘"
+        p.separator();
+        p.kv(
+            "Open Interest",
+            match (&self.open_interest, &self.open_interest_usd) {
+                (Some(oi), Some(usd)) => format!("{oi} {} (${usd})", self.symbol),
+                (Some(oi), None) => format!("{oi} {}", self.symbol),
+                _ => dash(),
+            },
         );
+        p.kv(
+            "Max Leverage",
+            self.max_leverage
+                .map(|l| format!("{l}x"))
+                .unwrap_or_else(dash),
+        );
+        out.push_str(&p.render());
+        Ok(())
     }
 }
 
-impl TableDisplay for SubAccountsOutput {
-    fn print_table(&self) {
-        if self.subaccounts.is_empty() {
-            println!("No subaccounts.");
-            return;
+impl TableDisplay for RiskCalcOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let mut p = ctx.panel("RISK CALCULATOR");
+        p.kv(
+            "Asset",
+            format!("{} {}", self.coin, self.side.to_uppercase()),
+        );
+        p.kv("Entry Price", format!("${:.4}", self.entry_price));
+        p.kv("Size", format!("{:.6} {}", self.size, self.coin));
+        if (self.lots - self.size).abs() > 0.0001 {
+            p.kv("Lots", format!("{:.4}", self.lots));
         }
+        p.kv("Notional", format!("${:.2}", self.notional));
+        p.separator();
+        p.kv("Stop-Loss", format!("${:.4}", self.stop_loss));
+        p.kv("Take-Profit", format!("${:.4}", self.take_profit));
+        let liq = if self.est_liquidation > 0.0 {
+            format!("${:.4} ({})", self.est_liquidation, self.liq_model)
+        } else {
+            format!("none ({})", self.liq_model)
+        };
+        p.kv("Est. Liq", liq);
+        p.separator();
+        p.kv("Risk (USDC)", format!("${:.2}", self.risk_usd));
+        p.kv("Risk (%)", format!("{:.2}%", self.risk_pct * 100.0));
+        p.kv("Margin Req.", format!("${:.2}", self.margin));
+        p.kv("Leverage", format!("{}x", self.leverage));
+        out.push_str(&p.render());
 
-        for sub in &self.subaccounts {
-            println!("╔══════════════════════════════════════════════════════════╗");
-            println!("║  SUBACCOUNT: {:<43}║", sub.name);
-            println!("╠══════════════════════════════════════════════════════════╣");
-            println!("║  Address      : {:<41}║", sub.address);
-            println!("║  Account Val  : ${:<40}║", sub.account_value);
-            println!("║  Total Pos    : ${:<40}║", sub.total_position);
-            println!("║  Margin Used  : ${:<40}║", sub.margin_used);
-            println!("║  Withdrawable : ${:<40}║", sub.withdrawable);
-            println!("╠══════════════════════════════════════════════════════════╣");
-
-            if sub.positions.is_empty() {
-                println!("║  No open positions.                                    ║");
-            } else {
-                println!(
-                    "║  {:^6} │ {:^10} │ {:^10} │ {:^12} ║",
-                    "Coin", "Size", "Entry", "uPnL"
-                );
-                println!("║  ──────┼────────────┼────────────┼────────────── ║");
-                for pos in &sub.positions {
-                    println!(
-                        "║  {:^6} │ {:>10} │ {:>10} │ {:>12} ║",
-                        pos.coin,
-                        pos.size,
-                        pos.entry_price.as_deref().unwrap_or("—"),
-                        pos.unrealized_pnl.as_deref().unwrap_or("—"),
-                    );
-                }
+        if !self.warnings.is_empty() {
+            writeln!(out)?;
+            for w in &self.warnings {
+                writeln!(out, "{}", ctx.text(w))?;
             }
-
-            if !sub.spot_balances.is_empty() {
-                println!("╠══════════════════════════════════════════════════════════╣");
-                println!("║  SPOT BALANCES                                         ║");
-                for b in &sub.spot_balances {
-                    println!("║    {:<6} : {:<46}║", b.coin, b.total);
-                }
+            if self.blocked {
+                writeln!(out)?;
+                writeln!(out, "{} Trade BLOCKED by risk rules.", ctx.fail())?;
             }
-            println!("╚══════════════════════════════════════════════════════════╝");
-            println!();
         }
-
-        println!("Total subaccounts: {}", self.subaccounts.len());
+        Ok(())
     }
 }
 
-impl TableDisplay for AgentApproveOutput {
-    fn print_table(&self) {
-        let name_display = if self.agent_name.is_empty() {
-            "(unnamed)"
+impl TableDisplay for RiskSuggestOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let mut p = ctx.panel("POSITION SIZING");
+        p.kv(
+            "Asset",
+            format!("{} {}", self.coin, self.side.to_uppercase()),
+        );
+        p.kv("Entry Price", format!("${:.4}", self.entry_price));
+        p.kv("Stop-Loss", format!("${:.4}", self.stop_loss));
+        p.separator();
+        let record = if self.stats.scope == "coin" {
+            self.coin.clone()
         } else {
-            &self.agent_name
+            "all coins".to_string()
         };
-        println!(
-            "✓ Agent {} approved (name: {}, status: {})",
-            self.agent_address, name_display, self.status
+        p.kv(
+            "Record",
+            format!(
+                "{} trades ({record}), {:.1}% won",
+                self.stats.trades,
+                self.stats.win_rate * 100.0
+            ),
         );
-    }
-}
-
-impl TableDisplay for TradeHistoryOutput {
-    fn print_table(&self) {
-        if self.trades.is_empty() {
-            println!("No trade history cached. Run `atlas history sync` first.");
-            return;
+        let payoff = self
+            .stats
+            .payoff_ratio
+            .map_or(String::new(), |b| format!(" ({b:.2}:1)"));
+        p.kv(
+            "Avg Win / Loss",
+            format!(
+                "${:.2} / ${:.2}{payoff}",
+                self.stats.avg_win, self.stats.avg_loss
+            ),
+        );
+        let method = match self.kelly {
+            Some(k) => format!(
+                "kelly ({:.2}% full, {}x, cap {:.2}%)",
+                k * 100.0,
+                self.kelly_multiplier,
+                self.kelly_cap * 100.0
+            ),
+            None => self.method.clone(),
+        };
+        p.kv("Method", method);
+        p.separator();
+        p.kv("Account", format!("${:.2}", self.account_value));
+        p.kv("Risk (%)", format!("{:.2}%", self.risk_pct * 100.0));
+        p.kv("Risk (USDC)", format!("${:.2}", self.risk_usd));
+        p.kv("Size", format!("{:.6} {}", self.size, self.coin));
+        if (self.lots - self.size).abs() > 0.0001 {
+            p.kv("Lots", format!("{:.4}", self.lots));
         }
+        p.kv("Notional", format!("${:.2}", self.notional));
+        p.kv(
+            "Margin",
+            format!("${:.2} at {}x", self.margin, self.leverage),
+        );
+        out.push_str(&p.render());
 
-        println!("┌────────┬──────┬────────────┬──────────────┬──────────────┬──────────┬─────────────────────┐");
-        println!("│ Coin   │ Side │ Size       │ Price        │ PnL          │ Fee      │ Time                │");
-        println!("├────────┼──────┼────────────┼──────────────┼──────────────┼──────────┼─────────────────────┤");
-        for t in &self.trades {
-            println!(
-                "│ {:<6} │ {:<4} │ {:>10} │ {:>12} │ {:>12} │ {:>8} │ {:>19} │",
-                t.coin, t.side, t.size, t.price, t.pnl, t.fee, t.time,
-            );
+        if !self.warnings.is_empty() {
+            writeln!(out)?;
+            for w in &self.warnings {
+                writeln!(out, "{}", ctx.text(w))?;
+            }
         }
-        println!("└────────┴──────┴────────────┴──────────────┴──────────────┴──────────┴─────────────────────┘");
-        println!("Total: {} trades", self.total);
+        Ok(())
     }
 }
 
-impl TableDisplay for OrderHistoryOutput {
-    fn print_table(&self) {
-        if self.orders.is_empty() {
-            println!("No order history cached. Run `atlas history sync` first.");
-            return;
+impl TableDisplay for LiquidationOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let usd = |v: &Option<String>| v.as_ref().map_or(ctx.dash().into(), |v| format!("${v}"));
+        let liq = |v: &Option<String>| v.as_ref().map_or("none".into(), |v| format!("${v}"));
+        let pct = |v: &Option<String>| v.as_ref().map_or(ctx.dash().into(), |v| format!("{v}%"));
+
+        let mut p = ctx.panel("LIQUIDATION");
+        let side = self
+            .side
+            .as_deref()
+            .map(str::to_uppercase)
+            .unwrap_or_default();
+        p.kv(
+            "Asset",
+            format!("{} {side}", self.coin).trim_end().to_string(),
+        );
+        p.kv("Margin", self.margin_mode.clone());
+        p.kv("Size", self.size.clone());
+        p.kv("Mark", usd(&self.mark_price));
+        p.separator();
+        p.kv("Equity", usd(&self.equity));
+        p.kv("Maintenance", usd(&self.maintenance_margin));
+        p.kv("Liq. Price", liq(&self.liquidation_price));
+        p.kv("Distance", pct(&self.distance_pct));
+        if let Some(after) = &self.after {
+            p.separator();
+            p.kv("Add", format!("{} @ ${}", after.add_size, after.at_price));
+            p.kv("New Size", after.size.clone());
+            p.kv("Equity", usd(&after.equity));
+            p.kv("Maintenance", usd(&after.maintenance_margin));
+            p.kv("Liq. Price", liq(&after.liquidation_price));
+            p.kv("Distance", pct(&after.distance_pct));
         }
+        out.push_str(&p.render());
+        Ok(())
+    }
+}
 
-        println!("┌────────┬──────┬────────────┬──────────────┬────────────────┬──────────┬─────────────────────┐");
-        println!("│ Coin   │ Side │ Size       │ Price        │ OID            │ Status   │ Time                │");
-        println!("├────────┼──────┼────────────┼──────────────┼────────────────┼──────────┼─────────────────────┤");
-        for o in &self.orders {
-            println!(
-                "│ {:<6} │ {:<4} │ {:>10} │ {:>12} │ {:>14} │ {:>8} │ {:>19} │",
-                o.coin, o.side, o.size, o.price, o.oid, o.status, o.time,
-            );
+impl TableDisplay for CarryOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::Cell;
+        let interval = format!("{}h", self.funding_interval_hours);
+        let mut p = ctx.panel(format!(
+            "CARRY {} {} short perp / long spot",
+            ctx.dash(),
+            self.coin
+        ));
+        p.kv("Size", format!("${}", self.size_usd));
+        p.kv("Horizon", format!("{} days", self.horizon_days));
+        p.kv("Morpho market", format!("{} ({})", self.market, self.chain));
+        p.separator();
+        p.kv(
+            "Funding (now)",
+            Cell::signed(format!(
+                "{}% APR ({}/{interval})",
+                self.funding_apr_pct, self.funding_rate
+            )),
+        );
+        p.kv(
+            "Funding (7d avg)",
+            match &self.trailing_funding_apr_pct {
+                Some(apr) => Cell::signed(format!("{apr}% APR")),
+                None => Cell::new(ctx.dash()),
+            },
+        );
+        p.kv("Supply APY", format!("{}%", self.supply_apy_pct));
+        p.kv(
+            "Fees",
+            format!(
+                "-{}% APR (${}; taker {} bps, builder {} bps)",
+                self.fees_apr_pct, self.fees_usd, self.taker_fee_bps, self.builder_fee_bps
+            ),
+        );
+        p.separator();
+        p.kv(
+            "Net carry",
+            Cell::signed(format!("{}% APR", self.net_apr_pct)),
+        );
+        p.kv(
+            &format!("Net over {}d (USD)", self.horizon_days),
+            Cell::signed(&self.net_usd),
+        );
+        p.kv(
+            "Breakeven funding",
+            format!(
+                "{}% APR ({}/{interval})",
+                self.breakeven_funding_apr_pct, self.breakeven_funding_rate
+            ),
+        );
+        out.push_str(&p.render());
+
+        if self.trailing_below_current {
+            writeln!(
+                out,
+                "{} 7-day average funding is under half the current rate; \
+                 the current APR may not last",
+                ctx.warn()
+            )?;
         }
-        println!("└────────┴──────┴────────────┴──────────────┴────────────────┴──────────┴─────────────────────┘");
-        println!("Total: {} orders", self.total);
+        Ok(())
     }
 }
 
-impl TableDisplay for PnlSummaryOutput {
-    fn print_table(&self) {
-        println!("╔══════════════════════════════════════════════════════════╗");
-        println!("║  PNL SUMMARY                                           ║");
-        println!("╠══════════════════════════════════════════════════════════╣");
-        println!("║  Total PnL    : ${:<40}║", self.total_pnl);
-        println!("║  Total Fees   : ${:<40}║", self.total_fees);
-        println!("║  Net PnL      : ${:<40}║", self.net_pnl);
-        println!("║  Trades       : {:<41}║", self.trade_count);
-        println!(
-            "║  Win/Loss     : {:<41}║",
-            format!("{} / {}", self.win_count, self.loss_count)
-        );
-        println!("║  Win Rate     : {:<41}║", self.win_rate);
-        println!("╠══════════════════════════════════════════════════════════╣");
+impl TableDisplay for TaBundleOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let heading = format!(
+            "TA for {} [{}] {} {} candles, last ${}",
+            self.ticker,
+            self.timeframe,
+            ctx.dash(),
+            self.candles,
+            self.last_price
+        );
+        writeln!(out, "{}\n", ctx.heading("📊", &heading))?;
+        writeln!(
+            out,
+            "{:<12} {:<10} {:>14} {:>14}",
+            "INDICATOR", "FIELD", "LATEST", "PREVIOUS"
+        )?;
+        writeln!(out, "{}", ctx.rule(53))?;
+        for (name, reading) in &self.indicators {
+            for (i, (field, latest)) in reading.latest.iter().enumerate() {
+                let prev = reading
+                    .previous
+                    .as_ref()
+                    .and_then(|p| p.get(field))
+                    .map(String::as_str)
+                    .unwrap_or(ctx.dash());
+                let label = if i == 0 { name.as_str() } else { "" };
+                writeln!(
+                    out,
+                    "{:<12} {:<10} {:>14} {:>14}",
+                    label, field, latest, prev
+                )?;
+            }
+        }
+        write_gap_warning(out, ctx, &self.gaps)
+    }
+}
 
-        if !self.by_coin.is_empty() {
-            println!("║  BREAKDOWN BY COIN                                     ║");
-            println!(
-                "║  {:<8} │ {:>12} │ {:>10} │ {:>6}      ║",
-                "Coin", "PnL", "Fees", "Trades"
+/// Warning under a TA table computed over a series with missing candles.
+fn write_gap_warning(
+    out: &mut String,
+    ctx: &RenderContext,
+    gaps: &[crate::candle_gaps::CandleGap],
+) -> std::fmt::Result {
+    if gaps.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        out,
+        "\n{} {}; values may be skewed (--strict refuses)",
+        ctx.warn(),
+        crate::candle_gaps::describe(gaps)
+    )
+}
+
+impl TableDisplay for TopMarketsOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{format_decimal, format_usd, Cell, Color};
+        use rust_decimal::Decimal;
+        let title = match (self.sort.as_str(), self.reverse) {
+            ("change", false) => "Top Gainers".to_string(),
+            ("change", true) => "Top Losers".to_string(),
+            (sort, reverse) => format!(
+                "By {}{}",
+                match sort {
+                    "oi" => "Open Interest",
+                    "funding" => "Funding",
+                    "spread" => "Spread",
+                    _ => "Volume",
+                },
+                if reverse { " (reversed)" } else { "" }
+            ),
+        };
+        let title = match &self.min_volume {
+            Some(min) => format!("{title}, 24h volume {} {}", ctx.text("≥"), format_usd(min)),
+            None => title,
+        };
+        if self.markets.is_empty() {
+            return writeln!(out, "No markets match.");
+        }
+
+        // The sort column is marked in the header and colored
+        let columns: [(&str, &str); 6] = [
+            ("", "PRICE"),
+            ("volume", "24h VOLUME"),
+            ("change", "24h CHG"),
+            ("oi", "OI"),
+            ("funding", "FUNDING APR"),
+            ("spread", "SPREAD"),
+        ];
+        let marker = if self.reverse { "^" } else { "v" };
+        let mut t = ctx.table().title(ctx.heading("📊", &title));
+        t = t.column("COIN", Align::Left);
+        for (key, header) in columns {
+            let header = if key == self.sort {
+                format!("{header} {marker}")
+            } else {
+                header.to_string()
+            };
+            t = t.column(header, Align::Right);
+        }
+
+        let num = |s: &Option<String>, f: &dyn Fn(Decimal) -> String| {
+            s.as_deref()
+                .and_then(|v| v.parse::<Decimal>().ok())
+                .map_or_else(|| ctx.dash().to_string(), f)
+        };
+        for r in &self.markets {
+            let cells = [
+                num(&r.mid_price, &|d| d.normalize().to_string()),
+                num(&r.volume_24h, &|d| format_usd(&d.to_string())),
+                num(&r.change_24h_pct, &|d| format!("{d:+.2}%")),
+                num(&r.open_interest_usd, &|d| format_usd(&d.to_string())),
+                num(&r.funding_apr_pct, &|d| {
+                    format!("{}%", format_decimal(d, 2))
+                }),
+                num(&r.spread_bps, &|d| format!("{} bps", format_decimal(d, 2))),
+            ];
+            let mut row = vec![Cell::new(&r.symbol)];
+            for ((key, _), text) in columns.iter().zip(cells) {
+                let cell = if *key == "change" {
+                    Cell::signed(text)
+                } else {
+                    Cell::new(text)
+                };
+                row.push(if *key == self.sort {
+                    cell.color(Color::Yellow)
+                } else {
+                    cell
+                });
+            }
+            t.row(row);
+        }
+        out.push_str(&t.render());
+        Ok(())
+    }
+}
+
+impl TableDisplay for MarketSummaryOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::format_usd;
+        let mut p = ctx.panel(ctx.heading("📊", "MARKET SUMMARY"));
+        p.kv("Markets", self.markets.to_string());
+        p.kv("24h Volume", format_usd(&self.volume_24h));
+        p.kv(
+            "Up / Down",
+            format!("{} / {}", self.advancing, self.declining),
+        );
+        out.push_str(&p.render());
+
+        for key in &self.sections {
+            let (rows, emoji, title) = match key.as_str() {
+                "gainers" => (&self.gainers, "🟢", "Top Gainers"),
+                "losers" => (&self.losers, "🔴", "Top Losers"),
+                "volume" => (&self.volume, "📈", "Top Volume"),
+                "funding" => (&self.funding, "💸", "Highest Funding"),
+                "oi" => (&self.oi, "🏦", "Open Interest"),
+                _ => continue,
+            };
+            let Some(rows) = rows else { continue };
+            out.push_str(&summary_table(ctx, ctx.heading(emoji, title), rows).render());
+        }
+
+        if let Some(w) = &self.watchlist {
+            write_watchlist(out, ctx, w)?;
+        }
+        Ok(())
+    }
+}
+
+fn pct_cell(ctx: &RenderContext, v: &Option<String>) -> crate::fmt::Cell {
+    use crate::fmt::Cell;
+    match v
+        .as_deref()
+        .and_then(|v| v.parse::<rust_decimal::Decimal>().ok())
+    {
+        Some(d) => Cell::signed(format!("{d:+.2}%")),
+        None => Cell::new(ctx.dash()),
+    }
+}
+
+fn funding_text(ctx: &RenderContext, v: &Option<String>) -> String {
+    v.as_deref()
+        .and_then(|v| v.parse::<rust_decimal::Decimal>().ok())
+        .map_or_else(
+            || ctx.dash().to_string(),
+            |d| {
+                format!(
+                    "{}%",
+                    crate::fmt::format_decimal(d * rust_decimal::Decimal::ONE_HUNDRED, 4)
+                )
+            },
+        )
+}
+
+fn summary_table(ctx: &RenderContext, title: String, rows: &[SummaryRow]) -> crate::fmt::Table {
+    use crate::fmt::{format_usd, Cell};
+    let usd = |v: &Option<String>| v.as_deref().map_or(ctx.dash().to_string(), format_usd);
+    let mut t = ctx
+        .table()
+        .title(title)
+        .column("COIN", Align::Left)
+        .column("PRICE", Align::Right)
+        .column("24h CHG", Align::Right)
+        .column("24h VOLUME", Align::Right)
+        .column("FUNDING", Align::Right)
+        .column("OI", Align::Right)
+        .column("OI 24h", Align::Right);
+    for r in rows {
+        t.row([
+            Cell::new(&r.symbol),
+            Cell::new(
+                r.price
+                    .as_deref()
+                    .map_or(ctx.dash().to_string(), |p| ctx.numbers.price_str(p, None)),
+            ),
+            pct_cell(ctx, &r.change_24h_pct),
+            Cell::new(usd(&r.volume_24h)),
+            Cell::new(funding_text(ctx, &r.funding_rate)),
+            Cell::new(usd(&r.open_interest_usd)),
+            pct_cell(ctx, &r.oi_change_24h_pct),
+        ]);
+    }
+    t
+}
+
+fn write_watchlist(
+    out: &mut String,
+    ctx: &RenderContext,
+    w: &WatchlistSummary,
+) -> std::fmt::Result {
+    use crate::fmt::Cell;
+    let table = |title: String, rows: &[WatchRow]| {
+        let mut t = ctx
+            .table()
+            .title(title)
+            .column("COIN", Align::Left)
+            .column("PRICE", Align::Right)
+            .column("24h CHG", Align::Right)
+            .column("FUNDING", Align::Right)
+            .column("OI 24h", Align::Right)
+            .column("POSITION", Align::Right);
+        for r in rows {
+            t.row([
+                Cell::new(&r.symbol),
+                Cell::new(
+                    r.price
+                        .as_deref()
+                        .map_or(ctx.dash().to_string(), |p| ctx.numbers.price_str(p, None)),
+                ),
+                pct_cell(ctx, &r.change_24h_pct),
+                Cell::new(funding_text(ctx, &r.funding_rate)),
+                pct_cell(ctx, &r.oi_change_24h_pct),
+                match &r.position_size {
+                    Some(size) => Cell::signed(size.clone()),
+                    None => Cell::new(ctx.dash()),
+                },
+            ]);
+        }
+        t.render()
+    };
+
+    if w.coins.is_empty() {
+        writeln!(out, "None of the hl.watchlist coins are listed.")?;
+    } else {
+        out.push_str(&table(ctx.heading("👀", "WATCHLIST"), &w.coins));
+    }
+    if !w.missing.is_empty() {
+        writeln!(
+            out,
+            "{} Not listed on Hyperliquid: {}",
+            ctx.warn(),
+            w.missing.join(", ")
+        )?;
+    }
+    if let Some(e) = &w.positions_error {
+        writeln!(out, "{} Positions unavailable: {e}", ctx.warn())?;
+    }
+    if !w.unwatched_positions.is_empty() {
+        out.push_str(&table(
+            ctx.heading("📌", "UNWATCHED POSITIONS"),
+            &w.unwatched_positions,
+        ));
+    }
+    Ok(())
+}
+
+impl TableDisplay for FundingScreenOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let heading = format!(
+            "FUNDING SCREEN {} |rate| {} {}, OI {} {} ({} markets)",
+            ctx.dash(),
+            ctx.text("≥"),
+            self.min_abs_rate,
+            ctx.text("≥"),
+            crate::fmt::format_usd(&self.min_oi_usd),
+            self.matched
+        );
+        writeln!(out, "{}", ctx.heading("💸", &heading))?;
+        for (title, rows) in [
+            ("Positive (longs pay)", &self.positive),
+            ("Negative (shorts pay)", &self.negative),
+        ] {
+            writeln!(out, "\n{title}")?;
+            if rows.is_empty() {
+                writeln!(out, "   none")?;
+                continue;
+            }
+            writeln!(
+                out,
+                "{:<10} {:>12} {:>10} {:>12} {:>12} {:>14}",
+                "COIN", "RATE", "APR", "OI", "24h VOLUME", "MARK"
+            )?;
+            writeln!(out, "{}", ctx.rule(75))?;
+            for r in rows {
+                writeln!(
+                    out,
+                    "{:<10} {:>12} {:>10} {:>12} {:>12} {:>14}",
+                    r.coin,
+                    r.rate,
+                    format!("{}%", r.apr_pct),
+                    crate::fmt::format_usd(&r.open_interest_usd),
+                    crate::fmt::format_usd(&r.volume_24h),
+                    r.mark_price
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for TrendMtfOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let heading = format!("TREND CONFLUENCE: {}", self.ticker);
+        writeln!(out, "{}\n", ctx.heading("📊", &heading))?;
+        writeln!(
+            out,
+            "{:<6} {:<18} {:>6} {:>8} {:>12}",
+            "TF", "DIRECTION", "SCORE", "RSI", "MACD HIST"
+        )?;
+        writeln!(out, "{}", ctx.rule(54))?;
+        for row in &self.timeframes {
+            let get = |k: &str| {
+                row.indicators
+                    .get(k)
+                    .map(String::as_str)
+                    .unwrap_or(ctx.dash())
+            };
+            let score = row
+                .score
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| ctx.dash().into());
+            writeln!(
+                out,
+                "{:<6} {:<18} {:>6} {:>8} {:>12}",
+                row.timeframe,
+                row.direction,
+                score,
+                get("rsi"),
+                get("macd_histogram")
+            )?;
+        }
+        writeln!(out, "{}", ctx.rule(54))?;
+        writeln!(
+            out,
+            "Overall: {} {} score {}/100 ({})",
+            self.verdict,
+            ctx.dash(),
+            self.score,
+            self.direction
+        )?;
+        for row in self.timeframes.iter().filter(|r| !r.gaps.is_empty()) {
+            writeln!(
+                out,
+                "{} {}: {}",
+                ctx.warn(),
+                row.timeframe,
+                crate::candle_gaps::describe(&row.gaps)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for PatternsOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let heading = format!(
+            "CANDLESTICK PATTERNS: {} [{}], last {} closed candles",
+            self.ticker, self.timeframe, self.lookback
+        );
+        writeln!(out, "{}\n", ctx.heading("🕯️", &heading))?;
+        if self.patterns.is_empty() {
+            writeln!(out, "   No patterns detected.")?;
+            return write_gap_warning(out, ctx, &self.gaps);
+        }
+        writeln!(
+            out,
+            "{:<18} {:<17} {:<8} {:>8} {:>4}  TIME",
+            "PATTERN", "TYPE", "SIGNAL", "STRENGTH", "AGO"
+        )?;
+        writeln!(out, "{}", ctx.rule(80))?;
+        for row in &self.patterns {
+            writeln!(
+                out,
+                "{:<18} {:<17} {:<8} {:>8} {:>4}  {}",
+                row.found.pattern,
+                row.found.kind,
+                row.signal,
+                row.found.strength,
+                row.candles_ago,
+                row.time
+            )?;
+        }
+        write_gap_warning(out, ctx, &self.gaps)
+    }
+}
+
+impl TableDisplay for PatternScanOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let heading = format!(
+            "PATTERN SCAN [{}]: {} across top {} markets",
+            self.timeframe,
+            self.patterns.join(", "),
+            self.scanned
+        );
+        writeln!(out, "{}\n", ctx.heading("🕯️", &heading))?;
+        if self.hits.is_empty() {
+            writeln!(out, "   No patterns on the latest closed candle.")?;
+        } else {
+            writeln!(
+                out,
+                "{:<10} {:<18} {:<8} {:>8} {:>16}  CANDLE",
+                "COIN", "PATTERN", "SIGNAL", "STRENGTH", "24H VOLUME"
+            )?;
+            writeln!(out, "{}", ctx.rule(84))?;
+            for hit in &self.hits {
+                let volume = hit
+                    .volume_24h
+                    .as_deref()
+                    .map(crate::fmt::format_usd)
+                    .unwrap_or_else(|| ctx.dash().into());
+                writeln!(
+                    out,
+                    "{:<10} {:<18} {:<8} {:>8} {:>16}  {}",
+                    hit.coin, hit.pattern, hit.direction, hit.strength, volume, hit.time
+                )?;
+            }
+        }
+        for warning in &self.warnings {
+            writeln!(out, "{} {warning}", ctx.warn())?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for OiHistoryOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let arrow = ctx.arrow();
+        let heading = format!(
+            "{} OPEN INTEREST {} last {}h ({} samples)",
+            self.ticker,
+            ctx.dash(),
+            self.hours,
+            self.samples
+        );
+        writeln!(out, "{}", ctx.heading("📈", &heading))?;
+        writeln!(out, "   Window:        {} {arrow} {}", self.from, self.to)?;
+        writeln!(
+            out,
+            "   OI:            {} {arrow} {} ({}%)",
+            self.oi_start, self.oi_end, self.oi_change_pct
+        )?;
+        writeln!(
+            out,
+            "   OI (USD):      {}",
+            crate::fmt::format_usd(&self.oi_usd_end)
+        )?;
+        writeln!(
+            out,
+            "   Price:         {} {arrow} {} ({}%)",
+            self.price_start, self.price_end, self.price_change_pct
+        )?;
+        writeln!(
+            out,
+            "   24h volume:    {} {arrow} {} ({})",
+            crate::fmt::format_usd(&self.volume_24h_start),
+            crate::fmt::format_usd(&self.volume_24h_end),
+            crate::fmt::format_usd(&self.volume_24h_change)
+        )?;
+        writeln!(out, "   Read:          {}", self.divergence)?;
+
+        // Evenly spaced points, always ending on the latest sample
+        const SHOWN: usize = 12;
+        if self.series.is_empty() {
+            return Ok(());
+        }
+        let step = self.series.len().div_ceil(SHOWN).max(1);
+        let last = self.series.len() - 1;
+        writeln!(
+            out,
+            "\n{:<20} {:>16} {:>14} {:>14}",
+            "TIME", "OPEN INTEREST", "MARK", "24h VOLUME"
+        )?;
+        writeln!(out, "{}", ctx.rule(67))?;
+        for (i, p) in self.series.iter().enumerate() {
+            if (last - i) % step != 0 {
+                continue;
+            }
+            writeln!(
+                out,
+                "{:<20} {:>16} {:>14} {:>14}",
+                p.time,
+                p.open_interest,
+                p.mark_price,
+                crate::fmt::format_usd(&p.volume_24h)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for SpreadProfileOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let heading = format!(
+            "{} SPREAD PROFILE {} {} samples, median {:.2} bps",
+            self.coin,
+            ctx.dash(),
+            self.samples,
+            self.median_bps
+        );
+        writeln!(out, "{}", ctx.heading("📊", &heading))?;
+        writeln!(
+            out,
+            "   Window:        {} {} {} (kept {} days)",
+            self.from,
+            ctx.arrow(),
+            self.to,
+            self.retention_days
+        )?;
+        writeln!(
+            out,
+            "\n{:<11} {:>8} {:>13} {:>10}",
+            "HOUR (UTC)", "SAMPLES", "MEDIAN (bps)", "VS MEDIAN"
+        )?;
+        writeln!(out, "{}", ctx.rule(45))?;
+        for h in &self.hours {
+            let ratio = if self.median_bps > 0.0 {
+                format!("{:.2}x", h.median_bps / self.median_bps)
+            } else {
+                ctx.dash().to_string()
+            };
+            writeln!(
+                out,
+                "{:<11} {:>8} {:>13.2} {:>10}",
+                format!("{:02}:00", h.hour_utc),
+                h.samples,
+                h.median_bps,
+                ratio
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for CorrelationOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let heading = format!(
+            "CORRELATION [{}] {} {} log returns",
+            self.timeframe,
+            ctx.dash(),
+            self.samples
+        );
+        writeln!(out, "{}\n", ctx.heading("🔗", &heading))?;
+        write!(out, "{:<8}", "")?;
+        for s in &self.symbols {
+            write!(out, " {:>8}", s)?;
+        }
+        writeln!(out)?;
+        writeln!(out, "{}", ctx.rule(8 + 9 * self.symbols.len()))?;
+        for a in &self.symbols {
+            write!(out, "{:<8}", a)?;
+            for b in &self.symbols {
+                match self.corr(a, b) {
+                    Some(c) => write!(out, " {:>8.2}", c)?,
+                    None => write!(out, " {:>8}", ctx.dash())?,
+                }
+            }
+            writeln!(out)?;
+        }
+        if let Some(base) = self.symbols.first() {
+            writeln!(out, "\nBeta to {base}:")?;
+            for s in &self.symbols[1..] {
+                match self.betas.get(s).copied().flatten() {
+                    Some(b) => writeln!(out, "   {:<8} {:>8.2}", s, b)?,
+                    None => writeln!(out, "   {:<8} {:>8}", s, ctx.dash())?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for CrossPriceOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let mut t = ctx
+            .table()
+            .column("Coin", Align::Left)
+            .column(format!("Price ({})", self.quote), Align::Right)
+            .column("USD Mid", Align::Right)
+            .column("", Align::Left);
+        for p in &self.prices {
+            let dash = || ctx.dash().to_string();
+            t.row([
+                p.symbol.clone(),
+                p.price.clone().unwrap_or_else(dash),
+                p.base_usd.clone().unwrap_or_else(dash),
+                p.error.clone().unwrap_or_default(),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "{} = ${}", self.quote, self.quote_usd)
+    }
+}
+
+impl TableDisplay for DexOhlcvOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let pair = match (&self.base, &self.quote) {
+            (Some(base), Some(quote)) => format!("{base}/{quote} "),
+            _ => String::new(),
+        };
+        // day/hour/minute → 1d, 4h, 15m
+        let unit = self.timeframe.chars().next().unwrap_or('?');
+        let heading = format!(
+            "{pair}{} {} {}{unit} candles",
+            self.network,
+            ctx.dash(),
+            self.aggregate
+        );
+        writeln!(out, "{}\n", ctx.heading("🕯", &heading))?;
+        if self.candles.is_empty() {
+            return writeln!(
+                out,
+                "No candles yet {} the pool has not traded.",
+                ctx.dash()
             );
-            println!("║  ────────┼──────────────┼────────────┼────────────  ║");
-            for row in &self.by_coin {
-                println!(
-                    "║  {:<8} │ {:>12} │ {:>10} │ {:>6}      ║",
-                    row.coin, row.pnl, row.fees, row.trades,
-                );
+        }
+
+        let mut t = ctx
+            .table()
+            .column("Time", Align::Left)
+            .column("Open", Align::Right)
+            .column("High", Align::Right)
+            .column("Low", Align::Right)
+            .column("Close", Align::Right)
+            .column("Volume ($)", Align::Right);
+        for c in &self.candles {
+            t.row([
+                c.time.clone(),
+                c.open.clone(),
+                c.high.clone(),
+                c.low.clone(),
+                c.close.clone(),
+                c.volume_usd.clone(),
+            ]);
+        }
+        out.push_str(&t.render());
+        if self.candles.len() < self.limit {
+            writeln!(
+                out,
+                "{} of {} candles {} history starts {}",
+                self.candles.len(),
+                self.limit,
+                ctx.dash(),
+                self.candles[0].time
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for DexTradesOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{Cell, Color};
+
+        if self.trades.is_empty() {
+            return match &self.min_usd {
+                Some(min) => writeln!(out, "No trades of ${min} or more in this pool."),
+                None => writeln!(out, "No trades in this pool yet."),
+            };
+        }
+        let mut t = ctx
+            .table()
+            .column("Time", Align::Left)
+            .column("Side", Align::Left)
+            .column("Base", Align::Right)
+            .column("Quote", Align::Right)
+            .column("Price ($)", Align::Right)
+            .column("Value ($)", Align::Right)
+            .column("Tx", Align::Left);
+        for tr in &self.trades {
+            let color = if tr.side == "buy" {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            t.row([
+                Cell::new(&tr.time),
+                Cell::new(&tr.side).color(color),
+                Cell::new(&tr.base_amount),
+                Cell::new(&tr.quote_amount),
+                Cell::new(
+                    tr.price_usd
+                        .clone()
+                        .unwrap_or_else(|| ctx.dash().to_string()),
+                ),
+                Cell::new(&tr.volume_usd),
+                Cell::new(crate::fmt::truncate_address(&tr.tx_hash)),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "{} trades", self.trades.len())
+    }
+}
+
+impl TableDisplay for DexWatchListOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{format_timestamp_ms, Cell, Color};
+        if self.pools.is_empty() {
+            return writeln!(
+                out,
+                "No pools watched. Add one with `atlas market dex watch add <NETWORK> <POOL>`."
+            );
+        }
+
+        let mut t = ctx
+            .table()
+            .column("Label", Align::Left)
+            .column("Network", Align::Left)
+            .column("Pool", Align::Left)
+            .column("Last Check", Align::Left);
+        for p in &self.pools {
+            let checked = match (p.missing_since_ms, p.last_checked_ms) {
+                (Some(_), _) => Cell::new("missing").color(Color::Red),
+                (None, Some(ms)) => Cell::new(format_timestamp_ms(ms.max(0) as u64)),
+                (None, None) => Cell::new("never").color(Color::Dim),
+            };
+            t.row([
+                Cell::new(p.label.as_deref().unwrap_or(ctx.dash())),
+                Cell::new(&p.network),
+                Cell::new(&p.pool),
+                checked,
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "Total: {} pools", self.pools.len())
+    }
+}
+
+impl TableDisplay for DexWatchChangeOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let e = &self.entry;
+        let label = e
+            .label
+            .as_ref()
+            .map(|l| format!(" as {l}"))
+            .unwrap_or_default();
+        if self.action == "removed" {
+            writeln!(
+                out,
+                "{} Stopped watching {} on {}",
+                ctx.ok(),
+                e.pool,
+                e.network
+            )
+        } else {
+            writeln!(
+                out,
+                "{} Watching {} on {}{label}",
+                ctx.ok(),
+                e.pool,
+                e.network
+            )
+        }
+    }
+}
+
+impl ScheduleRunRow {
+    /// One-line result, e.g. `ok oid 123 @ 67012.5`.
+    pub fn summary(&self) -> String {
+        let mut text = self.status.clone();
+        if let Some(oid) = &self.order_id {
+            text.push_str(&format!(" oid {oid}"));
+        }
+        if let Some(price) = &self.price {
+            text.push_str(&format!(" @ {price}"));
+        }
+        if self.missed > 0 {
+            text.push_str(&format!(" ({} missed)", self.missed));
+        }
+        if let Some(error) = &self.error {
+            text.push_str(&format!(": {error}"));
+        }
+        text
+    }
+}
+
+impl TableDisplay for ScheduleListOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{format_timestamp_ms, Cell, Color};
+        if self.schedules.is_empty() {
+            return writeln!(
+                out,
+                "No scheduled jobs. Add one with `atlas schedule add \"<COMMAND>\" --every 24h`."
+            );
+        }
+
+        let mut t = ctx
+            .table()
+            .column("ID", Align::Right)
+            .column("Command", Align::Left)
+            .max_width(40)
+            .column("Every", Align::Left)
+            .column("Next Run (UTC)", Align::Left)
+            .column("Runs", Align::Right)
+            .column("Last Result", Align::Left)
+            .max_width(48);
+        for s in &self.schedules {
+            let next = match s.next_run_ms {
+                Some(ms) => Cell::new(format_timestamp_ms(ms.max(0) as u64)),
+                None => Cell::new("done").color(Color::Dim),
+            };
+            let runs = match s.max_runs {
+                Some(max) => format!("{}/{max}", s.runs),
+                None => s.runs.to_string(),
+            };
+            let last = match &s.last_run {
+                Some(r) => {
+                    let color = match r.status.as_str() {
+                        "ok" => Color::Green,
+                        "error" => Color::Red,
+                        _ => Color::Yellow,
+                    };
+                    Cell::new(r.summary()).color(color)
+                }
+                None => Cell::new("never").color(Color::Dim),
+            };
+            let every = match &s.at {
+                Some(at) => format!("{} at {at}", s.every),
+                None => s.every.clone(),
+            };
+            t.row([
+                Cell::new(s.id.to_string()),
+                Cell::new(&s.command),
+                Cell::new(every),
+                next,
+                Cell::new(runs),
+                last,
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "Total: {} jobs", self.schedules.len())
+    }
+}
+
+impl TableDisplay for ScheduleChangeOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let s = &self.schedule;
+        if self.action == "removed" {
+            return writeln!(
+                out,
+                "{} Removed schedule #{}: {}",
+                ctx.ok(),
+                s.id,
+                s.command
+            );
+        }
+        writeln!(
+            out,
+            "{} Scheduled #{}: {} every {}",
+            ctx.ok(),
+            s.id,
+            s.command,
+            s.every
+        )?;
+        if let Some(ms) = s.next_run_ms {
+            writeln!(
+                out,
+                "  First run {} UTC. Start the executor with `atlas schedule run`.",
+                crate::fmt::format_timestamp_ms(ms.max(0) as u64)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for SecurityConfigOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let mut p = ctx.panel("SECURITY");
+        p.kv(
+            "Transfer approval",
+            match self.transfer_confirm_above {
+                Some(above) => format!("above {above} USDC, by {}", self.method),
+                None => "off".to_string(),
+            },
+        );
+        p.kv("Timeout", format!("{}s", self.approval_timeout_secs));
+        p.kv("Approval file", &self.approval_file);
+        p.kv(
+            "TOTP",
+            if self.totp_enrolled {
+                "enrolled (OS keyring)"
+            } else {
+                "not enrolled"
+            },
+        );
+        p.kv(
+            "Whitelist",
+            format!(
+                "{}, {} address{}",
+                if self.whitelist_enabled { "on" } else { "off" },
+                self.whitelisted,
+                if self.whitelisted == 1 { "" } else { "es" }
+            ),
+        );
+        out.push_str(&p.render());
+        if self.transfer_confirm_above.is_some() && self.method == "totp" && !self.totp_enrolled {
+            writeln!(
+                out,
+                "{} Transfers above the threshold will fail. Run: atlas configure security totp enroll",
+                ctx.warn()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for WhitelistOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.entries.is_empty() {
+            writeln!(
+                out,
+                "No whitelisted addresses. Run: atlas configure security whitelist add <0x…> --label <name>"
+            )?;
+        } else {
+            let mut t = ctx
+                .table()
+                .column("Label", Align::Left)
+                .column("Address", Align::Left);
+            for entry in &self.entries {
+                t.row([ctx.text(&entry.label), entry.address.clone()]);
             }
+            out.push_str(&t.render());
+        }
+        if self.enabled {
+            writeln!(
+                out,
+                "Whitelist mode on: transfers to other addresses are refused."
+            )
+        } else {
+            writeln!(
+                out,
+                "Whitelist mode off: labels only. Enable: atlas configure security whitelist enable"
+            )
         }
-        println!("╚══════════════════════════════════════════════════════════╝");
     }
 }
 
-impl TableDisplay for SyncOutput {
-    fn print_table(&self) {
-        println!(
-            "✓ Sync {} — fills: {}, orders: {}",
-            self.status, self.fills_synced, self.orders_synced
+impl TableDisplay for PresetsOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.presets.is_empty() {
+            return writeln!(
+                out,
+                "No presets. Run: atlas configure trading preset set <COIN> --leverage 5"
+            );
+        }
+        let mut t = ctx
+            .table()
+            .column("Coin", Align::Left)
+            .column("Leverage", Align::Right)
+            .column("Slippage", Align::Right)
+            .column("Default size", Align::Right);
+        let dash = || ctx.dash().to_string();
+        for row in &self.presets {
+            t.row([
+                row.coin.clone(),
+                row.leverage.map_or_else(dash, |lev| format!("{lev}x")),
+                row.slippage
+                    .map_or_else(dash, |slip| crate::fmt::format_pct(&slip.to_string())),
+                row.default_size.clone().unwrap_or_else(dash),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(
+            out,
+            "Unset fields use the module defaults; flags override both."
+        )
+    }
+}
+
+impl TableDisplay for NotifyConfigOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let mut p = ctx.panel("NOTIFICATIONS");
+        p.kv("Webhook", self.webhook.as_deref().unwrap_or("not set"));
+        p.kv("Exec hook", self.exec.as_deref().unwrap_or("not set"));
+        p.kv(
+            "Signing secret",
+            self.secret.as_deref().unwrap_or("not set"),
+        );
+        p.kv(
+            "Liquidation warning",
+            format!("mark within {}% of liq price", self.liq_warn_pct),
         );
+        p.kv(
+            "Retries",
+            format!(
+                "{} (backoff from {}ms, doubling)",
+                self.max_retries, self.backoff_ms
+            ),
+        );
+        out.push_str(&p.render());
+        if self.webhook.is_none() && self.exec.is_none() {
+            writeln!(
+                out,
+                "No targets set. Run: atlas configure notify set --webhook <URL>"
+            )?;
+        }
+        Ok(())
     }
 }
 
-impl TableDisplay for ExportOutput {
-    fn print_table(&self) {
-        println!(
-            "✓ Exported {} rows ({}) → {}",
-            self.rows, self.format, self.path
+impl TableDisplay for NotifyTestOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        for d in &self.deliveries {
+            let tries = if d.attempts == 1 {
+                "1 attempt".to_string()
+            } else {
+                format!("{} attempts", d.attempts)
+            };
+            match &d.error {
+                None if d.ok => writeln!(
+                    out,
+                    "{} {} delivered '{}' event ({tries})",
+                    ctx.ok(),
+                    d.target,
+                    self.event
+                )?,
+                error => writeln!(
+                    out,
+                    "{} {} failed after {tries}: {}",
+                    ctx.fail(),
+                    d.target,
+                    error.as_deref().unwrap_or("unknown error")
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for MarketOverviewOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{format_usd, truncate_number, Cell, Color};
+
+        let pct = |v: Option<f64>| match v {
+            Some(p) => Cell::signed(format!("{p:+.2}%")),
+            None => Cell::new(ctx.dash()).color(Color::Dim),
+        };
+        let usd = |v: Option<f64>| {
+            v.map_or_else(|| ctx.dash().to_string(), |v| format_usd(&v.to_string()))
+        };
+
+        if let Some(g) = &self.global {
+            let mcap = if g.total_market_cap_usd >= 1e12 {
+                format!("${:.2}T", g.total_market_cap_usd / 1e12)
+            } else {
+                format!("${:.2}B", g.total_market_cap_usd / 1e9)
+            };
+            let mut heading = format!("MARKET OVERVIEW {} MCap {mcap}", ctx.dash());
+            if let Some(chg) = g.market_cap_change_24h_pct {
+                heading.push_str(&format!(" ({chg:+.2}% 24h)"));
+            }
+            if let Some(btc) = g.btc_dominance_pct {
+                heading.push_str(&format!(", BTC dom {btc:.1}%"));
+            }
+            writeln!(out, "{}\n", ctx.heading("🌍", &heading))?;
+        } else {
+            writeln!(out, "{}\n", ctx.heading("🌍", "MARKET OVERVIEW"))?;
+        }
+
+        if self.coins.is_empty() {
+            writeln!(out, "None of the market.watchlist coins were found.")?;
+        } else {
+            let mut t = ctx
+                .table()
+                .column("#", Align::Right)
+                .column("Coin", Align::Left)
+                .column("Price ($)", Align::Right)
+                .column("1h", Align::Right)
+                .column("24h", Align::Right)
+                .column("7d", Align::Right)
+                .column("24h Vol", Align::Right)
+                .column("MCap", Align::Right)
+                .column("From ATH", Align::Right);
+            for r in &self.coins {
+                t.row([
+                    Cell::new(
+                        r.market_cap_rank
+                            .map_or_else(|| ctx.dash().to_string(), |n| n.to_string()),
+                    ),
+                    Cell::new(&r.symbol),
+                    Cell::new(truncate_number(&r.price_usd.to_string())),
+                    pct(r.change_1h_pct),
+                    pct(r.change_24h_pct),
+                    pct(r.change_7d_pct),
+                    Cell::new(usd(r.volume_24h_usd)),
+                    Cell::new(usd(r.market_cap_usd)),
+                    pct(r.from_ath_pct),
+                ]);
+            }
+            out.push_str(&t.render());
+        }
+        if self.global.is_none() {
+            writeln!(out, "Global market stats unavailable.")?;
+        }
+        if !self.missing.is_empty() {
+            writeln!(
+                out,
+                "Not found on CoinGecko: {} (check the ids in market.watchlist)",
+                self.missing.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for DexWatchCheckOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{format_usd, truncate_address, truncate_number, Cell, Color};
+
+        if self.pools.is_empty() {
+            return match self.threshold_pct {
+                Some(pct) if self.checked > 0 => writeln!(
+                    out,
+                    "No watched pool moved more than {pct}% since the last check ({} checked).",
+                    self.checked
+                ),
+                _ => writeln!(
+                    out,
+                    "No pools watched. Add one with `atlas market dex watch add <NETWORK> <POOL>`."
+                ),
+            };
+        }
+
+        let change = |pct: Option<f64>| match pct {
+            Some(p) if p > 0.0 => Cell::new(format!("+{p:.2}%")).color(Color::Green),
+            Some(p) if p < 0.0 => Cell::new(format!("{p:.2}%")).color(Color::Red),
+            Some(_) => Cell::new("0.00%"),
+            None => Cell::new(ctx.dash()).color(Color::Dim),
+        };
+        let usd = |v: &Option<String>| {
+            v.as_deref()
+                .map_or_else(|| ctx.dash().to_string(), format_usd)
+        };
+        let mut t = ctx
+            .table()
+            .column("Pool", Align::Left)
+            .column("Network", Align::Left)
+            .column("Price ($)", Align::Right)
+            .column("Price Chg", Align::Right)
+            .column("24h Vol", Align::Right)
+            .column("Vol Chg", Align::Right)
+            .column("Liquidity", Align::Right)
+            .column("Liq Chg", Align::Right)
+            .column("", Align::Left)
+            .max_width(48);
+        for r in &self.pools {
+            let pool = r
+                .label
+                .clone()
+                .or_else(|| r.name.clone())
+                .unwrap_or_else(|| truncate_address(&r.pool));
+            let note = match r.status.as_str() {
+                "missing" => Cell::new(ctx.text("⚠ pool gone from the API")).color(Color::Red),
+                "error" => Cell::new(r.error.as_deref().unwrap_or("error")).color(Color::Yellow),
+                "new" => Cell::new("first check").color(Color::Dim),
+                _ => Cell::new(""),
+            };
+            t.row([
+                Cell::new(pool),
+                Cell::new(&r.network),
+                Cell::new(
+                    r.price_usd
+                        .as_deref()
+                        .map_or_else(|| ctx.dash().to_string(), truncate_number),
+                ),
+                change(r.price_change_pct),
+                Cell::new(usd(&r.volume_24h_usd)),
+                change(r.volume_change_pct),
+                Cell::new(usd(&r.liquidity_usd)),
+                change(r.liquidity_change_pct),
+                note,
+            ]);
+        }
+        out.push_str(&t.render());
+        match self.threshold_pct {
+            Some(pct) => writeln!(
+                out,
+                "{} of {} pools moved more than {pct}% or need attention",
+                self.pools.len(),
+                self.checked
+            ),
+            None => writeln!(out, "{} pools checked", self.checked),
+        }
+    }
+}
+
+impl TableDisplay for PriceCompareOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{Cell, Color};
+
+        let heading = format!(
+            "PRICE COMPARE: {} {} 0x quote for ${} on {}",
+            self.coin,
+            ctx.dash(),
+            self.size_usd,
+            self.chain
         );
+        writeln!(out, "{}\n", ctx.heading("🔎", &heading))?;
+        let mut t = ctx
+            .table()
+            .column("Source", Align::Left)
+            .column("Price", Align::Right)
+            .column("vs Median", Align::Right)
+            .column("Updated", Align::Left)
+            .column("", Align::Left)
+            .max_width(60);
+        for r in &self.sources {
+            if !r.available {
+                let why = r.error.as_deref().unwrap_or_default();
+                t.row([
+                    Cell::new(&r.source),
+                    Cell::new(ctx.dash()),
+                    Cell::new(ctx.dash()),
+                    Cell::new(ctx.dash()),
+                    Cell::new(ctx.text(&format!("unavailable: {why}"))).color(Color::Dim),
+                ]);
+                continue;
+            }
+            let deviation = match r.deviation_bps.as_deref() {
+                Some(d) if d.starts_with('-') || d == "0" => format!("{d} bps"),
+                Some(d) => format!("+{d} bps"),
+                None => ctx.dash().to_string(),
+            };
+            let flag = if r.flagged {
+                Cell::new(format!("{} beyond {} bps", ctx.warn(), self.threshold_bps))
+                    .color(Color::Yellow)
+            } else {
+                Cell::new("")
+            };
+            t.row([
+                Cell::new(&r.source),
+                Cell::new(r.price.clone().unwrap_or_default()),
+                Cell::new(deviation),
+                Cell::new(
+                    r.timestamp_ms
+                        .map(crate::fmt::format_timestamp_ms)
+                        .unwrap_or_else(|| ctx.dash().to_string()),
+                ),
+                flag,
+            ]);
+        }
+        out.push_str(&t.render());
+        match &self.median {
+            Some(m) => writeln!(out, "Median: {m}"),
+            None => writeln!(out, "{} No source returned a price", ctx.warn()),
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl TableDisplay for BacktestOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let r = &self.report;
+        let pf = r
+            .profit_factor
+            .map(|pf| format!("{pf:.2}"))
+            .unwrap_or_else(|| ctx.dash().into());
+        let heading = format!(
+            "BACKTEST: {} [{}] {} {}",
+            self.ticker,
+            self.timeframe,
+            ctx.dash(),
+            self.strategy
+        );
+        writeln!(out, "{}", ctx.heading("🧪", &heading))?;
+        writeln!(out, "   Candles:       {}", self.candles)?;
+        writeln!(out, "   Fee:           {} bps/side", self.fee_bps)?;
+        writeln!(out, "   {}", ctx.rule(21))?;
+        writeln!(out, "   Trades:        {}", self.trade_count)?;
+        writeln!(out, "   Win rate:      {:.1}%", r.win_rate_pct)?;
+        writeln!(out, "   Total return:  {:+.2}%", r.total_return_pct)?;
+        writeln!(out, "   Profit factor: {pf}")?;
+        writeln!(out, "   Max drawdown:  {:.2}%", r.max_drawdown_pct)?;
+
+        if r.trades.is_empty() {
+            return Ok(());
+        }
+        let shown = r.trades.len().min(10);
+        writeln!(out, "\nLast {shown} trades:")?;
+        writeln!(
+            out,
+            "{:<6} {:<20} {:>12} {:<20} {:>12} {:>9}",
+            "SIDE", "ENTRY", "PRICE", "EXIT", "PRICE", "RETURN"
+        )?;
+        writeln!(out, "{}", ctx.rule(84))?;
+        for t in &r.trades[r.trades.len() - shown..] {
+            let side = match t.direction {
+                crate::backtest::Direction::Long => "long",
+                crate::backtest::Direction::Short => "short",
+            };
+            writeln!(
+                out,
+                "{:<6} {:<20} {:>12.4} {:<20} {:>12.4} {:>8.2}%",
+                side,
+                crate::fmt::format_timestamp_ms(t.entry_time_ms),
+                t.entry_price,
+                crate::fmt::format_timestamp_ms(t.exit_time_ms),
+                t.exit_price,
+                t.return_pct
+            )?;
+        }
+        Ok(())
+    }
+}
 
-    #[test]
-    fn test_status_output_serializes() {
-        let output = StatusOutput {
-            profile: "default".into(),
-            address: "0x1234".into(),
-            network: "Mainnet".into(),
-            modules: vec!["hyperliquid".into()],
-            balances: vec![BalanceRow {
+impl TableDisplay for SpotBalanceOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.balances.is_empty() {
+            return writeln!(out, "No spot token balances.");
+        }
+
+        let mut t = ctx
+            .table()
+            .column("Token", Align::Left)
+            .column("Total", Align::Right)
+            .column("Held", Align::Right)
+            .column("Available", Align::Right);
+        for b in &self.balances {
+            t.row([&b.coin, &b.total, &b.held, &b.available]);
+        }
+        out.push_str(&t.render());
+        Ok(())
+    }
+}
+
+impl TableDisplay for SpotOrderOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let ok = ctx.ok();
+        match self.status.as_str() {
+            "filled" => {
+                let sz = self.total_sz.as_deref().unwrap_or(ctx.dash());
+                let px = self.avg_px.as_deref().unwrap_or(ctx.dash());
+                writeln!(
+                    out,
+                    "{ok} Spot {} {} FILLED (oid: {}, size: {}, avg_px: {})",
+                    self.side, self.market, self.oid, sz, px
+                )
+            }
+            "resting" => writeln!(
+                out,
+                "{ok} Spot {} {} RESTING (oid: {})",
+                self.side, self.market, self.oid
+            ),
+            _ => writeln!(
+                out,
+                "{ok} Spot {} {} accepted (oid: {})",
+                self.side, self.market, self.oid
+            ),
+        }
+    }
+}
+
+impl TableDisplay for SpotTransferOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        writeln!(
+            out,
+            "{} Transferred {} {} ({})",
+            ctx.ok(),
+            self.amount,
+            self.token,
+            self.direction
+        )
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for VaultDetailsOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::Cell;
+        let mut details = ctx.panel("VAULT DETAILS");
+        details.kv("Name", &self.name);
+        details.kv("Address", &self.address);
+        details.kv("Leader", &self.leader);
+        details.kv("APR", format!("{}%", self.apr));
+        details.kv("Leader Frac", format!("{}%", self.leader_fraction));
+        details.kv("Commission", format!("{}%", self.leader_commission));
+        details.kv("Distributable", format!("${}", self.max_distributable));
+        details.kv("Withdrawable", format!("${}", self.max_withdrawable));
+        details.kv("Followers", self.follower_count.to_string());
+        details.kv("Closed", if self.is_closed { "Yes" } else { "No" });
+        details.kv(
+            "Deposits",
+            if self.allow_deposits {
+                "Allowed"
+            } else {
+                "Closed"
+            },
+        );
+        out.push_str(&details.render());
+
+        if !self.description.is_empty() {
+            let mut desc = ctx.table().column("", Align::Left).max_width(72);
+            desc.row([self.description.as_str()]);
+            out.push_str(&desc.render());
+        }
+
+        if let Some(state) = &self.user_state {
+            let mut mine = ctx.panel("YOUR POSITION");
+            mine.kv("Equity", format!("${}", state.equity));
+            mine.kv("PnL", Cell::signed(format!("${}", state.pnl)));
+            mine.kv(
+                "All-time PnL",
+                Cell::signed(format!("${}", state.all_time_pnl)),
+            );
+            mine.kv("Days", state.days_following.to_string());
+            if let Some(lockup) = &state.lockup_until {
+                mine.kv("Locked Until", lockup);
+            }
+            out.push_str(&mine.render());
+        }
+
+        if !self.followers.is_empty() {
+            let mut t = ctx
+                .table()
+                .title("TOP FOLLOWERS")
+                .column("User", Align::Left)
+                .max_width(20)
+                .column("Equity", Align::Right)
+                .column("PnL", Align::Right)
+                .column("Days", Align::Right);
+            for f in self.followers.iter().take(10) {
+                t.row([
+                    Cell::new(&f.user),
+                    Cell::new(&f.equity),
+                    Cell::signed(&f.pnl),
+                    Cell::new(f.days_following.to_string()),
+                ]);
+            }
+            out.push_str(&t.render());
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for VaultListOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{format_usd, Cell, Color};
+
+        if self.vaults.is_empty() {
+            return writeln!(out, "No vaults match.");
+        }
+        let heading = format!(
+            "VAULTS by {} ({} of {})",
+            self.sort,
+            self.vaults.len(),
+            self.matched
+        );
+        writeln!(out, "{}\n", ctx.heading("🏦", &heading))?;
+
+        let dim = || Cell::new(ctx.dash()).color(Color::Dim);
+        let mut t = ctx
+            .table()
+            .column("#", Align::Right)
+            .column("Vault", Align::Left)
+            .max_width(28)
+            .column("Address", Align::Left)
+            .column("TVL", Align::Right)
+            .column("APR", Align::Right)
+            .column("Age", Align::Right)
+            .column("Followers", Align::Right);
+        for v in &self.vaults {
+            t.row([
+                Cell::new(v.rank.to_string()),
+                Cell::new(&v.name),
+                Cell::new(&v.address),
+                Cell::new(format_usd(&v.tvl_usd.round_dp(0).to_string())),
+                v.apr_pct
+                    .map_or_else(dim, |apr| Cell::signed(format!("{apr:+.2}%"))),
+                v.age_days.map_or_else(dim, |d| Cell::new(format!("{d}d"))),
+                v.followers.map_or_else(dim, |f| Cell::new(f.to_string())),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "Details: atlas hl vault details <address>")
+    }
+}
+
+impl TableDisplay for VaultDepositsOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.deposits.is_empty() {
+            return writeln!(out, "No vault deposits.");
+        }
+
+        let mut t = ctx
+            .table()
+            .column("Vault Address", Align::Left)
+            .column("Equity", Align::Right)
+            .column("Locked Until", Align::Left);
+        for d in &self.deposits {
+            t.row([
+                d.vault_address.as_str(),
+                d.equity.as_str(),
+                d.locked_until.as_deref().unwrap_or(ctx.dash()),
+            ]);
+        }
+        t.separator();
+        t.row(["TOTAL", self.total_equity.as_str(), ""]);
+        out.push_str(&t.render());
+        Ok(())
+    }
+}
+
+impl TableDisplay for MorphoMarketsOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.markets.is_empty() {
+            return writeln!(out, "No Morpho markets on {}.", self.chain);
+        }
+        let heading = format!(
+            "MORPHO MARKETS {} {} ({})",
+            ctx.dash(),
+            self.chain,
+            self.markets.len()
+        );
+        writeln!(out, "{}\n", ctx.heading("🏦", &heading))?;
+        writeln!(
+            out,
+            "{:<18} {:>10} {:>10} {:>8} {:>8} {:>12} {:>12}  ID",
+            "MARKET", "SUPPLY APY", "BORROW APY", "UTIL", "LLTV", "SUPPLY", "BORROW"
+        )?;
+        writeln!(out, "{}", ctx.rule(96))?;
+        for m in &self.markets {
+            writeln!(
+                out,
+                "{:<18} {:>10} {:>10} {:>8} {:>8} {:>12} {:>12}  {}",
+                ctx.text(&m.name),
+                format!("{}%", m.supply_apy_pct),
+                format!("{}%", m.borrow_apy_pct),
+                format!("{}%", m.utilization_pct),
+                format!("{}%", m.lltv_pct),
+                crate::fmt::format_usd(&m.total_supply_usd),
+                crate::fmt::format_usd(&m.total_borrow_usd),
+                m.id
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for MorphoMarketOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let m = &self.market;
+        let heading = format!("MORPHO MARKET {} {} on {}", ctx.dash(), m.name, self.chain);
+        writeln!(out, "{}\n", ctx.heading("🏦", &heading))?;
+        writeln!(out, "  ID           : {}", m.id)?;
+        writeln!(out, "  Collateral   : {}", m.collateral_asset)?;
+        writeln!(out, "  Loan         : {}", m.loan_asset)?;
+        writeln!(out, "  Supply APY   : {}%", m.supply_apy_pct)?;
+        writeln!(out, "  Borrow APY   : {}%", m.borrow_apy_pct)?;
+        writeln!(out, "  Utilization  : {}%", m.utilization_pct)?;
+        writeln!(out, "  LLTV         : {}%", m.lltv_pct)?;
+        writeln!(
+            out,
+            "  Total Supply : {}",
+            crate::fmt::format_usd(&m.total_supply_usd)
+        )?;
+        writeln!(
+            out,
+            "  Total Borrow : {}",
+            crate::fmt::format_usd(&m.total_borrow_usd)
+        )?;
+        writeln!(
+            out,
+            "  Liquidity    : {}",
+            crate::fmt::format_usd(&m.liquidity_usd)
+        )
+    }
+}
+
+impl TableDisplay for MorphoPositionsOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.positions.is_empty() {
+            return writeln!(
+                out,
+                "No Morpho positions for {} on {}.",
+                self.address, self.chain
+            );
+        }
+        let heading = format!(
+            "MORPHO POSITIONS {} {} on {}",
+            ctx.dash(),
+            self.address,
+            self.chain
+        );
+        writeln!(out, "{}\n", ctx.heading("🏦", &heading))?;
+        writeln!(
+            out,
+            "{:<18} {:>16} {:>16} {:>16} {:>8}",
+            "MARKET", "COLLATERAL", "DEBT", "SUPPLIED", "HEALTH"
+        )?;
+        writeln!(out, "{}", ctx.rule(78))?;
+        for p in &self.positions {
+            writeln!(
+                out,
+                "{:<18} {:>16} {:>16} {:>16} {:>8}",
+                ctx.text(&p.market),
+                format!("{} {}", p.collateral, p.collateral_asset),
+                format!("{} {}", p.borrowed, p.loan_asset),
+                format!("{} {}", p.supplied, p.loan_asset),
+                p.health_factor.as_deref().unwrap_or(ctx.dash())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for SubAccountsOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.subaccounts.is_empty() {
+            return writeln!(out, "No subaccounts.");
+        }
+
+        for sub in &self.subaccounts {
+            let mut summary = ctx.panel(format!("SUBACCOUNT: {}", sub.name));
+            summary.kv("Address", &sub.address);
+            summary.kv("Account Val", format!("${}", sub.account_value));
+            summary.kv("Total Pos", format!("${}", sub.total_position));
+            summary.kv("Margin Used", format!("${}", sub.margin_used));
+            summary.kv("Withdrawable", format!("${}", sub.withdrawable));
+            out.push_str(&summary.render());
+
+            if sub.positions.is_empty() {
+                writeln!(out, "No open positions.")?;
+            } else {
+                out.push_str(&positions_table(&sub.positions, ctx).render());
+            }
+
+            if !sub.spot_balances.is_empty() {
+                let mut spot = ctx
+                    .table()
+                    .title("SPOT BALANCES")
+                    .column("Coin", Align::Left)
+                    .column("Total", Align::Right);
+                for b in &sub.spot_balances {
+                    spot.row([&b.coin, &b.total]);
+                }
+                out.push_str(&spot.render());
+            }
+            writeln!(out)?;
+        }
+
+        writeln!(out, "Total subaccounts: {}", self.subaccounts.len())
+    }
+}
+
+impl TableDisplay for AgentApproveOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let name_display = if self.agent_name.is_empty() {
+            "(unnamed)"
+        } else {
+            &self.agent_name
+        };
+        writeln!(
+            out,
+            "{} Agent {} approved (name: {}, status: {})",
+            ctx.ok(),
+            self.agent_address,
+            name_display,
+            self.status
+        )
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for TradeHistoryOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::Cell;
+        if self.trades.is_empty() {
+            return writeln!(
+                out,
+                "No trade history cached. Run `atlas history sync` first."
+            );
+        }
+
+        let show_notes = self.with_notes || self.trades.iter().any(|t| t.has_note);
+        let show_protocol = self
+            .trades
+            .iter()
+            .any(|t| t.protocol != self.trades[0].protocol);
+        let mut t = ctx.table();
+        if show_protocol {
+            t = t.column("Protocol", Align::Left);
+        }
+        t = t
+            .column("Coin", Align::Left)
+            .column("Side", Align::Left)
+            .column("Size", Align::Right)
+            .column("Price", Align::Right)
+            .column("PnL", Align::Right)
+            .column("Fee", Align::Right)
+            .column("Time", Align::Right);
+        if show_notes {
+            t = t.column("Note", Align::Left).max_width(40);
+        }
+        let marker = if ctx.plain { "*" } else { "✎" };
+        let nf = &ctx.numbers;
+        for tr in &self.trades {
+            let mut row = Vec::with_capacity(9);
+            if show_protocol {
+                row.push(Cell::new(&tr.protocol));
+            }
+            row.extend([
+                Cell::new(&tr.coin),
+                Cell::new(&tr.side),
+                Cell::new(nf.size_str(&tr.size, None)),
+                Cell::new(nf.price_str(&tr.price, None)),
+                Cell::signed(nf.usd_str(&tr.pnl)),
+                Cell::new(nf.usd_str(&tr.fee)),
+                Cell::new(&tr.time),
+            ]);
+            if show_notes {
+                let note = match (self.with_notes, tr.has_note) {
+                    (true, _) => tr.notes.join("; "),
+                    (false, true) => marker.to_string(),
+                    (false, false) => String::new(),
+                };
+                row.push(Cell::new(note));
+            }
+            t.row(row);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "Total: {} trades", self.total)
+    }
+}
+
+impl TableDisplay for SwapHistoryOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{Cell, Color};
+        if self.swaps.is_empty() {
+            return writeln!(
+                out,
+                "No 0x swaps recorded. Swaps are recorded by `atlas 0x swap`; quotes with `atlas 0x quote --record`."
+            );
+        }
+
+        let mut t = ctx
+            .table()
+            .column("Time", Align::Left)
+            .column("Status", Align::Left)
+            .column("Chain", Align::Left)
+            .column("Sold", Align::Right)
+            .column("Bought", Align::Right)
+            .column("Price", Align::Right)
+            .column("USD", Align::Right)
+            .column("Gas", Align::Right)
+            .column("Tx", Align::Left);
+        let short = |a: &str| {
+            if a.len() > 12 {
+                format!("{}…{}", &a[..6], &a[a.len() - 4..])
+            } else {
+                a.to_string()
+            }
+        };
+        let dash = || Cell::new(ctx.dash()).color(Color::Dim);
+        for s in &self.swaps {
+            let sell = s
+                .sell_symbol
+                .clone()
+                .unwrap_or_else(|| short(&s.sell_token));
+            let buy = s.buy_symbol.clone().unwrap_or_else(|| short(&s.buy_token));
+            let status = if s.status == "quoted" {
+                Cell::new(&s.status).color(Color::Dim)
+            } else {
+                Cell::new(&s.status)
+            };
+            t.row([
+                Cell::new(&s.time),
+                status,
+                Cell::new(&s.chain),
+                Cell::new(format!("{} {sell}", s.sell_amount)),
+                Cell::new(format!("{} {buy}", s.buy_amount)),
+                s.price.as_deref().map_or_else(dash, Cell::new),
+                s.sell_value_usd
+                    .as_deref()
+                    .map_or_else(dash, |v| Cell::new(format!("${v}"))),
+                s.gas_cost_usd
+                    .as_deref()
+                    .map_or_else(dash, |v| Cell::new(format!("${v}"))),
+                s.tx_hash
+                    .as_deref()
+                    .map_or_else(dash, |h| Cell::new(short(h))),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "Total: {} swaps", self.total)
+    }
+}
+
+impl TableDisplay for TransferHistoryOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{truncate_address, Cell, Color};
+        if self.transfers.is_empty() {
+            return writeln!(
+                out,
+                "No transfers cached. Run `atlas hl sync` to pull deposits, withdrawals and transfers."
+            );
+        }
+
+        let mut t = ctx
+            .table()
+            .column("Time", Align::Left)
+            .column("Type", Align::Left)
+            .column("Entry", Align::Left)
+            .column("Dir", Align::Left)
+            .column("Amount", Align::Right)
+            .column("USD", Align::Right)
+            .column("Fee", Align::Right)
+            .column("Counterparty", Align::Left);
+        let dash = || Cell::new(ctx.dash()).color(Color::Dim);
+        for tr in &self.transfers {
+            let usd = match (tr.direction.as_str(), tr.usd_value.as_deref()) {
+                ("in", Some(v)) => Cell::signed(v),
+                ("out", Some(v)) => Cell::signed(format!("-{v}")),
+                (_, Some(v)) => Cell::new(v),
+                (_, None) => dash(),
+            };
+            t.row([
+                Cell::new(&tr.time),
+                Cell::new(&tr.kind),
+                Cell::new(&tr.ledger_type),
+                Cell::new(&tr.direction),
+                Cell::new(format!("{} {}", tr.amount, tr.token)),
+                usd,
+                Cell::new(&tr.fee),
+                tr.counterparty
+                    .as_deref()
+                    .map_or_else(dash, |c| Cell::new(truncate_address(c))),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(
+            out,
+            "Total: {} transfers, net deposits ${}",
+            self.total, self.net_deposits
+        )
+    }
+}
+
+impl TableDisplay for JournalOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{Cell, Color};
+        if self.entries.is_empty() {
+            return writeln!(
+                out,
+                "No journal notes. Add one with `atlas journal add <fill-hash|oid> --note ...`."
+            );
+        }
+
+        let mut t = ctx
+            .table()
+            .column("#", Align::Right)
+            .column("Time", Align::Left)
+            .column("Coin", Align::Left)
+            .column("Tags", Align::Left)
+            .column("Status", Align::Left)
+            .column("Note", Align::Left)
+            .max_width(60);
+        for e in &self.entries {
+            let status = if e.status == "pending" {
+                Cell::new(&e.status).color(Color::Yellow)
+            } else {
+                Cell::new(&e.status)
+            };
+            t.row([
+                Cell::new(e.id.to_string()),
+                Cell::new(&e.time),
+                Cell::new(e.coin.as_deref().unwrap_or(ctx.dash())),
+                Cell::new(e.tags.join(",")),
+                status,
+                Cell::new(&e.note),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "Total: {} notes", self.total)
+    }
+}
+
+impl TableDisplay for AuditListOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{Cell, Color};
+        if self.entries.is_empty() {
+            return writeln!(out, "No signed actions recorded.");
+        }
+
+        let mut t = ctx
+            .table()
+            .column("#", Align::Right)
+            .column("Time", Align::Left)
+            .column("Type", Align::Left)
+            .column("Coin", Align::Left)
+            .column("Status", Align::Left)
+            .column("Nonce", Align::Right);
+        for e in &self.entries {
+            let status = match e.status.as_str() {
+                "ok" => Cell::new(&e.status),
+                "pending" => Cell::new(&e.status).color(Color::Yellow),
+                _ => Cell::new(&e.status).color(Color::Red),
+            };
+            t.row([
+                Cell::new(e.id.to_string()),
+                Cell::new(&e.time),
+                Cell::new(&e.action_type),
+                Cell::new(e.coin.as_deref().unwrap_or(ctx.dash())),
+                status,
+                Cell::new(
+                    e.nonce
+                        .map_or_else(|| ctx.dash().to_string(), |n| n.to_string()),
+                ),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "Total: {} entries", self.total)
+    }
+}
+
+impl TableDisplay for AuditEntryOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let e = &self.entry;
+        let mut p = ctx.panel(format!("AUDIT #{}", e.id));
+        p.kv("Time", &e.time);
+        p.kv("Type", format!("{} ({})", e.action_type, e.protocol));
+        p.kv("Coin", e.coin.as_deref().unwrap_or(ctx.dash()));
+        p.kv("Status", &e.status);
+        p.kv(
+            "Nonce",
+            e.nonce
+                .map_or_else(|| ctx.dash().to_string(), |n| n.to_string()),
+        );
+        p.kv("Signer", &e.signer);
+        p.kv("Params hash", &e.params_hash);
+        p.kv("Completed", self.completed.as_deref().unwrap_or(ctx.dash()));
+        out.push_str(&p.render());
+
+        let pretty = |v: &serde_json::Value| match v {
+            serde_json::Value::String(s) => s.clone(),
+            other => serde_json::to_string_pretty(other).unwrap_or_default(),
+        };
+        writeln!(out, "Request:\n{}", pretty(&self.request))?;
+        match &self.response {
+            Some(response) => writeln!(out, "Response:\n{}", pretty(response)),
+            None => writeln!(out, "Response: {}", ctx.dash()),
+        }
+    }
+}
+
+impl TableDisplay for JournalAddOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let e = &self.entry;
+        match (&e.fill_hash, e.oid) {
+            (Some(hash), _) => writeln!(
+                out,
+                "{} Note #{} linked to {} fill {}",
+                ctx.ok(),
+                e.id,
+                e.coin.as_deref().unwrap_or_default(),
+                hash
+            ),
+            (None, Some(oid)) => writeln!(
+                out,
+                "{} Note #{} saved; links to order {} once its fill is synced",
+                ctx.ok(),
+                e.id,
+                oid
+            ),
+            (None, None) => writeln!(
+                out,
+                "{} Note #{} saved; links to the {} fill nearest {} at next sync",
+                ctx.ok(),
+                e.id,
+                e.coin.as_deref().unwrap_or_default(),
+                e.time
+            ),
+        }
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for SwapQuoteOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{format_usd_full, Cell, Color};
+        let dash = ctx.dash();
+        let sell = Self::label(&self.sell_token_name, &self.sell_symbol, &self.sell_token);
+        let buy = Self::label(&self.buy_token_name, &self.buy_symbol, &self.buy_token);
+        let amount = |tokens: &Option<String>, raw: &str, symbol: &str, usd: &Option<String>| {
+            let mut s = match tokens {
+                Some(t) => format!("{t} {symbol}"),
+                None => format!("{raw} (base units) {symbol}"),
+            };
+            if let Some(usd) = usd {
+                s.push_str(&format!(" ({})", format_usd_full(usd)));
+            }
+            s
+        };
+
+        let title = if self.gasless {
+            "0x GASLESS QUOTE"
+        } else {
+            "0x QUOTE"
+        };
+        let mut p = ctx.panel(format!("{title} {dash} {}", self.chain));
+        p.kv(
+            "Sell",
+            amount(
+                &self.sell_amount_tokens,
+                &self.sell_amount,
+                sell,
+                &self.sell_value_usd,
+            ),
+        );
+        p.kv(
+            "Buy",
+            amount(
+                &self.buy_amount_tokens,
+                &self.buy_amount,
+                buy,
+                &self.buy_value_usd,
+            ),
+        );
+        if let Some(min) = &self.min_buy_amount {
+            let mut s = amount(&self.min_buy_amount_tokens, min, buy, &None);
+            if let Some(bps) = self.slippage_bps {
+                s.push_str(&format!(" ({bps} bps slippage)"));
+            }
+            p.kv("Min Buy", s);
+        }
+        p.kv(
+            "Price",
+            self.price
+                .as_ref()
+                .map(|px| format!("{px} {buy} per {sell}"))
+                .unwrap_or_else(|| dash.to_string()),
+        );
+        let impact = match &self.price_impact_pct {
+            Some(pct) => {
+                let high = pct.parse::<f64>().is_ok_and(|v| v >= 1.0);
+                let cell = Cell::new(format!("{pct}% (incl. fees)"));
+                if high {
+                    cell.color(Color::Yellow)
+                } else {
+                    cell
+                }
+            }
+            None => Cell::new(dash).color(Color::Dim),
+        };
+        p.kv("Price Impact", impact);
+        p.separator();
+        if self.gasless {
+            p.kv("Gas", format!("paid by relayer, fee in {sell}"));
+        } else {
+            let native = self.native_symbol.as_deref().unwrap_or("ETH");
+            let gas = match (self.gas, &self.gas_price_gwei) {
+                (Some(g), Some(gwei)) => format!("{g} @ {gwei} gwei"),
+                (Some(g), None) => g.to_string(),
+                (None, Some(gwei)) => format!("{gwei} gwei"),
+                (None, None) => dash.to_string(),
+            };
+            p.kv("Gas", gas);
+            let cost = match (&self.gas_cost_native, &self.gas_cost_usd) {
+                (Some(n), Some(usd)) => format!("{n} {native} ({})", format_usd_full(usd)),
+                (Some(n), None) => format!("{n} {native}"),
+                _ => dash.to_string(),
+            };
+            p.kv("Gas Cost", cost);
+        }
+        if self.allowance_required {
+            p.kv(
+                "Allowance",
+                Cell::new(format!(
+                    "approve {}",
+                    self.allowance_spender.as_deref().unwrap_or(dash)
+                ))
+                .color(Color::Yellow),
+            );
+        }
+        out.push_str(&p.render());
+
+        if self.route.is_empty() {
+            return Ok(());
+        }
+        let mut t = ctx
+            .table()
+            .title("ROUTE")
+            .column("Source", Align::Left)
+            .column("From", Align::Left)
+            .column("To", Align::Left)
+            .column("Share", Align::Right);
+        for fill in &self.route {
+            t.row([
+                Cell::new(&fill.source),
+                Cell::new(&fill.from),
+                Cell::new(&fill.to),
+                Cell::new(format!("{}%", fill.proportion_pct)),
+            ]);
+        }
+        out.push_str(&t.render());
+        Ok(())
+    }
+}
+
+impl TableDisplay for AddressBookOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{Cell, Color};
+        if self.tokens.is_empty() {
+            return writeln!(
+                out,
+                "No tokens known on {}. Add one with `atlas address add <NAME> <0x…> --chain {}`.",
+                self.chain.as_deref().unwrap_or_default(),
+                self.chain.as_deref().unwrap_or_default()
+            );
+        }
+
+        let mut t = ctx
+            .table()
+            .column("Chain", Align::Left)
+            .column("Name", Align::Left)
+            .column("Address", Align::Left)
+            .column("Decimals", Align::Right)
+            .column("Source", Align::Left);
+        for row in &self.tokens {
+            let source = if row.source == "user" {
+                Cell::new(&row.source).color(Color::Green)
+            } else {
+                Cell::new(&row.source).color(Color::Dim)
+            };
+            t.row([
+                Cell::new(&row.chain),
+                Cell::new(&row.name),
+                Cell::new(&row.address),
+                Cell::new(
+                    row.decimals
+                        .map_or_else(|| ctx.dash().to_string(), |d| d.to_string()),
+                ),
+                source,
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "Total: {} tokens", self.total)
+    }
+}
+
+impl TableDisplay for AddressChangeOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let e = &self.entry;
+        if self.action == "removed" {
+            return writeln!(
+                out,
+                "{} Removed {} ({}) from the {} address book",
+                ctx.ok(),
+                e.name,
+                e.address,
+                e.chain
+            );
+        }
+        write!(
+            out,
+            "{} Added {} on {} {} {}",
+            ctx.ok(),
+            e.name,
+            e.chain,
+            ctx.arrow(),
+            e.address
+        )?;
+        match e.decimals {
+            Some(d) => writeln!(out, " ({d} decimals)"),
+            None => writeln!(out),
+        }
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for AllowanceOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::{Cell, Color};
+        let mut p = ctx.panel(format!("ALLOWANCE {} {}", ctx.dash(), self.chain));
+        p.kv("Token", format!("{} ({})", self.token_label(), self.token));
+        p.kv("Owner", &self.owner);
+        match &self.spender_name {
+            Some(name) => p.kv("Spender", format!("{name} ({})", self.spender)),
+            None => p.kv("Spender", &self.spender),
+        }
+        let amount = Cell::new(self.amount_label());
+        p.kv(
+            "Allowance",
+            if self.unlimited {
+                amount.color(Color::Yellow)
+            } else if self.allowance == "0" {
+                amount.color(Color::Dim)
+            } else {
+                amount
+            },
+        );
+        out.push_str(&p.render());
+        Ok(())
+    }
+}
+
+impl TableDisplay for ApprovalOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let a = &self.allowance;
+        if self.action == "revoked" {
+            write!(
+                out,
+                "{} Revoked {} allowance for {} on {}",
+                ctx.ok(),
+                a.token_label(),
+                a.spender_label(),
+                a.chain
+            )?;
+        } else {
+            write!(
+                out,
+                "{} Approved {} to spend {} on {}",
+                ctx.ok(),
+                a.spender_label(),
+                a.amount_label(),
+                a.chain
+            )?;
+        }
+        writeln!(out, " {} tx {}", ctx.arrow(), self.tx_hash)
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for BackupOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let mut p = ctx.panel("WORKSPACE BACKUP");
+        p.kv("Archive", &self.path);
+        p.kv("Size", format!("{:.1} KiB", self.bytes as f64 / 1024.0));
+        p.kv("Files", self.files.len().to_string());
+        p.kv(
+            "Keys",
+            if self.profiles_with_keys.is_empty() {
+                "not included".to_string()
+            } else {
+                format!("{} (encrypted)", self.profiles_with_keys.join(", "))
+            },
+        );
+        out.push_str(&p.render());
+        if self.profiles_with_keys.is_empty() {
+            writeln!(
+                out,
+                "Private keys are not in this backup; pass --include-keys to add them encrypted."
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for RestoreOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let mut p = ctx.panel("WORKSPACE RESTORE");
+        p.kv("Archive", &self.path);
+        p.kv("Backup from", &self.created_at);
+        p.kv("Files", self.files_restored.len().to_string());
+        p.kv(
+            "Profiles",
+            if self.profiles_imported.is_empty() {
+                ctx.dash().to_string()
+            } else {
+                self.profiles_imported.join(", ")
+            },
+        );
+        out.push_str(&p.render());
+        for name in &self.profiles_without_key {
+            writeln!(
+                out,
+                "  {} No key restored for '{name}': atlas profile import {name}",
+                ctx.warn()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for CandleCacheOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::format_timestamp_ms;
+        if let Some(pruned) = self.pruned {
+            writeln!(out, "{} Pruned {pruned} cached candles", ctx.ok())?;
+        }
+        if self.series.is_empty() {
+            return writeln!(
+                out,
+                "No cached candles. TA commands fill the cache as they run."
+            );
+        }
+
+        let mut t = ctx
+            .table()
+            .title("CANDLE CACHE")
+            .column("Coin", Align::Left)
+            .column("Interval", Align::Left)
+            .column("Source", Align::Left)
+            .column("Candles", Align::Right)
+            .column("Oldest", Align::Left)
+            .column("Newest", Align::Left);
+        for row in &self.series {
+            t.row([
+                row.coin.clone(),
+                row.interval.clone(),
+                row.source.clone(),
+                row.candles.to_string(),
+                format_timestamp_ms(row.oldest_ms),
+                format_timestamp_ms(row.newest_ms),
+            ]);
+        }
+        out.push_str(&t.render());
+        Ok(())
+    }
+}
+
+impl TableDisplay for StreamRecordingOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::format_timestamp_ms;
+        let mut p = ctx.panel("STREAM RECORDING");
+        p.kv("Directory", &self.dir);
+        p.kv("Ticker", format!("{} ({})", self.ticker, self.network));
+        p.kv("From", &self.started);
+        p.kv("To", &self.ended);
+        let messages: Vec<String> = self
+            .messages
+            .iter()
+            .map(|(channel, n)| format!("{channel} {n}"))
+            .collect();
+        p.kv("Messages", messages.join(", "));
+        p.kv(
+            "Segments",
+            format!(
+                "{} ({})",
+                self.segments,
+                crate::recording::format_bytes(self.bytes)
+            ),
+        );
+        p.kv("Gaps", self.gaps.len().to_string());
+        p.kv("Stopped", self.stop_reason.replace('_', " "));
+        out.push_str(&p.render());
+        for gap in &self.gaps {
+            writeln!(
+                out,
+                "{} No data {} → {} ({:.1}s, reconnect)",
+                ctx.warn(),
+                format_timestamp_ms(gap.from_ms.max(0) as u64),
+                format_timestamp_ms(gap.to_ms.max(0) as u64),
+                (gap.to_ms - gap.from_ms) as f64 / 1000.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for OrderHistoryOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        if self.orders.is_empty() {
+            return writeln!(
+                out,
+                "No order history cached. Run `atlas history sync` first."
+            );
+        }
+
+        let mut t = ctx
+            .table()
+            .column("Coin", Align::Left)
+            .column("Side", Align::Left)
+            .column("Size", Align::Right)
+            .column("Price", Align::Right)
+            .column("Filled", Align::Right)
+            .column("Avg Px", Align::Right)
+            .column("OID", Align::Right)
+            .column("Status", Align::Right)
+            .column("Time", Align::Right);
+        let nf = &ctx.numbers;
+        for o in &self.orders {
+            t.row([
+                o.coin.clone(),
+                o.side.clone(),
+                nf.size_str(&o.size, None),
+                nf.price_str(&o.price, None),
+                nf.size_str(&o.filled, None),
+                o.avg_price
+                    .as_deref()
+                    .map_or_else(|| ctx.dash().to_string(), |px| nf.price_str(px, None)),
+                o.oid.to_string(),
+                o.status.clone(),
+                o.time.clone(),
+            ]);
+        }
+        out.push_str(&t.render());
+        writeln!(out, "Total: {} orders", self.total)
+    }
+}
+
+impl TableDisplay for PnlSummaryOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        use crate::fmt::Cell;
+        let mut summary = ctx.panel("PNL SUMMARY");
+        summary.kv("Total PnL", Cell::signed(format!("${}", self.total_pnl)));
+        summary.kv("Total Fees", format!("${}", self.total_fees));
+        summary.kv("Net PnL", Cell::signed(format!("${}", self.net_pnl)));
+        if let Some(net) = &self.net_deposits {
+            summary.kv("Net Deposits", Cell::signed(format!("${net}")));
+        }
+        summary.kv("Trades", self.trade_count.to_string());
+        summary.kv(
+            "Win/Loss",
+            format!("{} / {}", self.win_count, self.loss_count),
+        );
+        summary.kv("Win Rate", &self.win_rate);
+        out.push_str(&summary.render());
+
+        if !self.by_coin.is_empty() {
+            let mut t = ctx
+                .table()
+                .title("BREAKDOWN BY COIN")
+                .column("Coin", Align::Left)
+                .column("PnL", Align::Right)
+                .column("Fees", Align::Right)
+                .column("Trades", Align::Right);
+            for row in &self.by_coin {
+                t.row([
+                    Cell::new(&row.coin),
+                    Cell::signed(&row.pnl),
+                    Cell::new(&row.fees),
+                    Cell::new(row.trades.to_string()),
+                ]);
+            }
+            out.push_str(&t.render());
+        }
+
+        if let Some(swaps) = self.swaps.as_ref().filter(|s| !s.positions.is_empty()) {
+            use crate::fmt::Color;
+            let dash = || Cell::new(ctx.dash()).color(Color::Dim);
+            let mut t = ctx
+                .table()
+                .title("0x SWAPS — UNREALIZED")
+                .column("Chain", Align::Left)
+                .column("Token", Align::Left)
+                .column("Amount", Align::Right)
+                .column("Cost", Align::Right)
+                .column("Value", Align::Right)
+                .column("uPnL", Align::Right)
+                .column("Priced", Align::Right)
+                .column("Swaps", Align::Right);
+            for row in &swaps.positions {
+                t.row([
+                    Cell::new(&row.chain),
+                    Cell::new(row.symbol.as_deref().unwrap_or(&row.token)),
+                    Cell::new(&row.amount),
+                    Cell::new(format!("${}", row.cost_usd)),
+                    row.value_usd
+                        .as_deref()
+                        .map_or_else(dash, |v| Cell::new(format!("${v}"))),
+                    row.unrealized_pnl
+                        .as_deref()
+                        .map_or_else(dash, Cell::signed),
+                    row.priced_at.as_deref().map_or_else(dash, Cell::new),
+                    Cell::new(row.swaps.to_string()),
+                ]);
+            }
+            out.push_str(&t.render());
+            writeln!(
+                out,
+                "Unrealized: ${} (cost ${}, value ${}) at cached prices; later sales are not netted out",
+                swaps.unrealized_pnl, swaps.cost_usd, swaps.value_usd
+            )?;
+            if swaps.unpriced > 0 {
+                writeln!(
+                    out,
+                    "{} token(s) have no cached price yet; run `atlas 0x quote` on them to refresh",
+                    swaps.unpriced
+                )?;
+            }
+            if swaps.skipped_swaps > 0 {
+                writeln!(
+                    out,
+                    "{} swap(s) left out: no USD value was recorded",
+                    swaps.skipped_swaps
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TableDisplay for InitOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        let list = |steps: &[String]| {
+            if steps.is_empty() {
+                "none".to_string()
+            } else {
+                steps.join(", ")
+            }
+        };
+        writeln!(
+            out,
+            "{} Profile {} on {}{}",
+            ctx.ok(),
+            self.profile,
+            self.network,
+            self.address
+                .as_deref()
+                .map(|a| format!(" ({a})"))
+                .unwrap_or_default()
+        )?;
+        writeln!(out, "  Set up:     {}", list(&self.applied))?;
+        writeln!(out, "  Still open: {}", list(&self.pending))?;
+        self.doctor.write_table(out, ctx)
+    }
+}
+
+impl TableDisplay for SyncOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        writeln!(
+            out,
+            "{} {} {} {} fills: {}, orders: {} ({} updated), transfers: {}, funding: {}",
+            ctx.ok(),
+            if self.full { "Full sync" } else { "Sync" },
+            self.status,
+            ctx.dash(),
+            self.fills_synced,
+            self.orders_synced,
+            self.orders_updated,
+            self.transfers_synced,
+            self.funding_synced
+        )
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+impl TableDisplay for ImportOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        for e in &self.errors {
+            writeln!(out, "{} Line {}: {}", ctx.fail(), e.line, e.error)?;
+        }
+        let glyph = if self.failed == 0 {
+            ctx.ok()
+        } else {
+            ctx.warn()
+        };
+        let verb = if self.dry_run {
+            "Would import"
+        } else {
+            "Imported"
+        };
+        writeln!(
+            out,
+            "{glyph} {verb} {} fills as '{}' ({} skipped, {} failed) {} {}",
+            self.imported,
+            self.protocol,
+            self.skipped,
+            self.failed,
+            ctx.dash(),
+            self.file
+        )
+    }
+}
+
+impl TableDisplay for ExportOutput {
+    fn write_table(&self, out: &mut String, ctx: &RenderContext) -> std::fmt::Result {
+        writeln!(
+            out,
+            "{} Exported {} rows ({}) {} {}",
+            ctx.ok(),
+            self.rows,
+            self.format,
+            ctx.arrow(),
+            self.path
+        )
+    }
+
+    fn side_effect_only(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_output_serializes() {
+        let output = StatusOutput {
+            profile: "default".into(),
+            address: "0x1234".into(),
+            env: None,
+            network: "Mainnet".into(),
+            modules: vec!["hyperliquid".into()],
+            balances: vec![BalanceRow {
                 asset: "USDC".into(),
                 total: "10000.00".into(),
                 available: "9500.00".into(),
@@ -1258,333 +6732,2819 @@ mod tests {
             margin_used: Some("500.00".into()),
             net_position: Some("2500.00".into()),
             withdrawable: Some("9500.00".into()),
-            positions: vec![PositionRow {
+            positions: vec![PositionRow {
+                coin: "ETH".into(),
+                side: "long".into(),
+                size: "0.5".into(),
+                entry_price: Some("3500.00".into()),
+                mark_price: Some("3550.00".into()),
+                unrealized_pnl: Some("25.00".into()),
+                notional: None,
+                pnl_pct: None,
+                liquidation_price: Some("2800.00".into()),
+                leverage: Some(5),
+                margin_mode: Some("isolated".into()),
+                protocol: "hyperliquid".into(),
+            }],
+            open_orders: 2,
+            total_value_usd: None,
+            protocols: vec![],
+            warnings: vec![],
+            diff: None,
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"profile\":\"default\""));
+        assert!(json.contains("\"symbol\":\"ETH\""));
+        assert!(json.contains("\"modules\""));
+        assert!(json.contains("\"open_orders\":2"));
+        assert!(json.contains("\"balances\""));
+    }
+
+    #[test]
+    fn test_status_all_keeps_top_level_fields() {
+        let output = StatusOutput {
+            profile: "default".into(),
+            address: "0x1234".into(),
+            env: None,
+            network: "Mainnet".into(),
+            modules: vec!["hyperliquid".into(), "zero_x".into()],
+            balances: vec![],
+            account_value: Some("1000".into()),
+            margin_used: None,
+            net_position: None,
+            withdrawable: None,
+            positions: vec![],
+            open_orders: 0,
+            total_value_usd: Some("1350.00".into()),
+            protocols: vec![ProtocolSummary {
+                protocol: "evm-wallet".into(),
+                value_usd: "350.00".into(),
+                balances: vec![ValuedBalanceRow {
+                    asset: "ETH".into(),
+                    chain: "base".into(),
+                    amount: "0.1".into(),
+                    price_usd: Some("3500".into()),
+                    value_usd: Some("350.00".into()),
+                }],
+                positions: 0,
+            }],
+            warnings: vec!["hyperliquid-spot: timeout".into()],
+            diff: None,
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        // Pre-existing fields stay at the top level
+        assert_eq!(json["account_value"], "1000");
+        assert_eq!(json["open_orders"], 0);
+        assert_eq!(json["total_value_usd"], "1350.00");
+        assert_eq!(json["protocols"][0]["balances"][0]["chain"], "base");
+        assert_eq!(json["warnings"][0], "hyperliquid-spot: timeout");
+
+        let plain = serde_json::to_value(StatusOutput {
+            total_value_usd: None,
+            protocols: vec![],
+            warnings: vec![],
+            ..output
+        })
+        .unwrap();
+        assert!(plain.get("total_value_usd").is_none());
+        assert!(plain.get("protocols").is_none());
+        assert!(plain.get("warnings").is_none());
+    }
+
+    #[test]
+    fn test_orders_output_serializes() {
+        let output = OrdersOutput {
+            market_type: "perp".into(),
+            orders: vec![OrderRow {
+                coin: "BTC".into(),
+                side: "BUY".into(),
+                size: "0.01".into(),
+                price: "50000.00".into(),
+                oid: 12345,
+                order_type: "limit".into(),
+                trigger_price: None,
+                trigger_type: None,
+            }],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"order_id\":12345"));
+    }
+
+    #[test]
+    fn test_trigger_orders_show_type_and_trigger() {
+        let limit = OrderRow {
+            coin: "ETH".into(),
+            side: "Buy".into(),
+            size: "1".into(),
+            price: "3400".into(),
+            oid: 1,
+            order_type: "limit".into(),
+            trigger_price: None,
+            trigger_type: None,
+        };
+        let stop = OrderRow {
+            price: "3636".into(),
+            oid: 2,
+            order_type: "trigger".into(),
+            trigger_price: Some("3600".into()),
+            trigger_type: Some("market".into()),
+            ..limit.clone()
+        };
+        let output = OrdersOutput {
+            market_type: "perp".into(),
+            orders: vec![limit, stop],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["orders"][0]["order_type"], "limit");
+        assert!(json["orders"][0].get("trigger_price").is_none());
+        assert_eq!(json["orders"][1]["order_type"], "trigger");
+        assert_eq!(json["orders"][1]["trigger_price"], "3600");
+
+        let table = output.table_string(&RenderContext::default());
+        assert!(table.contains("Trigger"), "{table}");
+        assert!(table.contains("trigger (market)"), "{table}");
+        assert!(table.contains("3600"), "{table}");
+    }
+
+    #[test]
+    fn test_spot_orders_table_snapshot() {
+        let output = OrdersOutput {
+            market_type: "spot".into(),
+            orders: vec![OrderRow {
+                coin: "PURR/USDC".into(),
+                side: "Buy".into(),
+                size: "1000".into(),
+                price: "0.185".into(),
+                oid: 4242,
+                order_type: "limit".into(),
+                trigger_price: None,
+                trigger_type: None,
+            }],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["market_type"], "spot");
+        assert_eq!(json["orders"][0]["symbol"], "PURR/USDC");
+        assert_eq!(
+            output.table_string(&RenderContext::default()),
+            "\
+┌───────────┬──────┬──────┬───────┬──────┐
+│ Pair      │ Side │ Size │ Price │  OID │
+├───────────┼──────┼──────┼───────┼──────┤
+│ PURR/USDC │ Buy  │ 1000 │ 0.185 │ 4242 │
+└───────────┴──────┴──────┴───────┴──────┘
+"
+        );
+
+        let empty = OrdersOutput {
+            market_type: "spot".into(),
+            orders: vec![],
+        };
+        assert_eq!(
+            empty.table_string(&RenderContext::default()),
+            "No open spot orders.\n"
+        );
+    }
+
+    #[test]
+    fn test_fills_output_serializes() {
+        let output = FillsOutput {
+            fills: vec![FillRow {
+                coin: "ETH".into(),
+                side: "SELL".into(),
+                size: "1.0".into(),
+                price: "3500.00".into(),
+                closed_pnl: "100.00".into(),
+                fee: "1.50".into(),
+                protocol: "hyperliquid".into(),
+                timestamp_ms: 1_767_254_400_000,
+            }],
+            total_fee: "1.50".into(),
+            net_closed_pnl: "100.00".into(),
+            warnings: vec![],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"closed_pnl\":\"100.00\""));
+        assert!(json.contains("\"timestamp_ms\":1767254400000"));
+        assert!(json.contains("\"net_closed_pnl\":\"100.00\""));
+        assert!(!json.contains("warnings"));
+        // One protocol: no protocol column.
+        let table = output.table_string(&RenderContext::default());
+        assert!(!table.contains("Protocol"));
+        assert!(table.contains("2026-01-01 08:00:00"));
+        assert!(table.contains("Total"));
+
+        // Tables follow output.*; JSON keeps the strings as given
+        let grouped = RenderContext::plain().with_numbers(crate::fmt::NumberFormat {
+            thousands: true,
+            ..Default::default()
+        });
+        let table = output.table_string(&grouped);
+        assert!(table.contains("| 3,500.0 |"), "{table}");
+        assert!(json.contains("\"price\":\"3500.00\""));
+        assert!(!json.contains("3,500"));
+    }
+
+    fn position_row(protocol: &str, coin: &str) -> PositionRow {
+        PositionRow {
+            coin: coin.into(),
+            side: "long".into(),
+            size: "1".into(),
+            entry_price: Some("100".into()),
+            mark_price: None,
+            unrealized_pnl: Some("5".into()),
+            notional: None,
+            pnl_pct: None,
+            liquidation_price: None,
+            leverage: None,
+            margin_mode: None,
+            protocol: protocol.into(),
+        }
+    }
+
+    #[test]
+    fn test_positions_output_shows_protocol_and_warnings() {
+        let output = PositionsOutput {
+            positions: vec![
+                position_row("hyperliquid", "BTC"),
+                position_row("0x", "ETH"),
+            ],
+            total_notional: "200.00".into(),
+            total_unrealized_pnl: "10.00".into(),
+            warnings: vec!["dydx: timed out".into()],
+        };
+        let table = output.table_string(&RenderContext::default());
+        assert!(table.contains("Protocol"));
+        assert!(table.contains("hyperliquid"));
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["positions"][1]["protocol"], "0x");
+        assert_eq!(json["warnings"][0], "dydx: timed out");
+
+        let single = PositionsOutput {
+            positions: vec![position_row("hyperliquid", "BTC")],
+            total_notional: "100.00".into(),
+            total_unrealized_pnl: "5.00".into(),
+            warnings: vec![],
+        };
+        assert!(!single
+            .table_string(&RenderContext::default())
+            .contains("Protocol"));
+    }
+
+    #[test]
+    fn test_order_result_output_serializes() {
+        let output = OrderResultOutput {
+            oid: 999,
+            coin: "BTC".into(),
+            side: "sell".into(),
+            total_sz: Some("0.5".into()),
+            avg_px: Some("3500.00".into()),
+            filled: Some("0.5".into()),
+            status: "filled".into(),
+            fee: Some("0.05".into()),
+            builder_fee_bps: 1,
+            protocol: "hyperliquid".into(),
+            timestamp: None,
+            tif: None,
+            trigger_price: None,
+            trigger_type: None,
+            cloid: None,
+            warnings: vec![],
+            liquidity_warning: None,
+            audit_id: Some(17),
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"status\":\"filled\""));
+        assert!(json.contains("\"audit_id\":17"));
+        assert!(!json.contains("\"tif\""));
+        assert!(!json.contains("\"warnings\""));
+
+        let output = OrderResultOutput {
+            status: "resting".into(),
+            tif: Some("alo".into()),
+            cloid: Some("0x1234567890abcdef1234567890abcdef".into()),
+            warnings: vec!["reduce-only sell 5 ETH capped at the 2 ETH long".into()],
+            ..output
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            json["warnings"][0],
+            "reduce-only sell 5 ETH capped at the 2 ETH long"
+        );
+        assert_eq!(json["tif"], "alo");
+        assert_eq!(json["cloid"], "0x1234567890abcdef1234567890abcdef");
+
+        let stop = OrderResultOutput {
+            tif: None,
+            trigger_price: Some("3600".into()),
+            trigger_type: Some("market".into()),
+            warnings: vec![],
+            ..output
+        };
+        let json = serde_json::to_value(&stop).unwrap();
+        assert_eq!(json["trigger_price"], "3600");
+        assert_eq!(json["trigger_type"], "market");
+        assert_eq!(
+            stop.table_string(&RenderContext::default()),
+            "✓ Trigger order RESTING (oid: 999, market @ 3600)\n"
+        );
+    }
+
+    #[test]
+    fn test_flip_output_renders_before_and_after() {
+        let output = FlipOutput {
+            coin: "ETH".into(),
+            before: FlipSide {
+                side: "long".into(),
+                size: "1".into(),
+            },
+            after: FlipSide {
+                side: "short".into(),
+                size: "1".into(),
+            },
+            order: OrderResultOutput {
+                oid: 42,
+                coin: "ETH".into(),
+                side: "sell".into(),
+                total_sz: Some("2".into()),
+                avg_px: Some("3500".into()),
+                filled: Some("2".into()),
+                status: "filled".into(),
+                fee: None,
+                builder_fee_bps: 1,
+                protocol: "hyperliquid".into(),
+                timestamp: None,
+                tif: None,
+                trigger_price: None,
+                trigger_type: None,
+                cloid: None,
+                warnings: vec![],
+                liquidity_warning: None,
+                audit_id: None,
+            },
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["before"]["side"], "long");
+        assert_eq!(json["after"]["side"], "short");
+        assert_eq!(json["order"]["size"], "2");
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("Order FILLED (oid: 42, size: 2"));
+        assert!(table.contains("ETH: LONG 1 -> SHORT 1"));
+    }
+
+    #[test]
+    fn test_close_all_output_keeps_failed_rows() {
+        let closed = CloseAllRow {
+            coin: "ETH".into(),
+            side: "long".into(),
+            size: "1".into(),
+            status: "closed".into(),
+            oid: Some(7),
+            filled: Some("1".into()),
+            avg_price: Some("3400".into()),
+            realized_pnl: Some("-100".into()),
+            error: None,
+        };
+        let failed = CloseAllRow {
+            coin: "SOL".into(),
+            side: "short".into(),
+            size: "10".into(),
+            status: "error".into(),
+            oid: None,
+            filled: None,
+            avg_price: None,
+            realized_pnl: None,
+            error: Some("Order rejected".into()),
+        };
+        let output = CloseAllOutput {
+            results: vec![closed, failed],
+            summary: CloseAllSummary {
+                closed: 1,
+                failed: 1,
+                realized_pnl: "-100".into(),
+            },
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["results"][0]["order_id"], 7);
+        assert_eq!(json["results"][1]["status"], "error");
+        assert_eq!(json["results"][1]["error"], "Order rejected");
+        assert!(json["results"][0].get("error").is_none());
+        assert_eq!(json["summary"]["failed"], 1);
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("FAIL Order rejected"));
+        assert!(table.contains("WARN Closed 1, failed 1 - realized PnL $-100"));
+    }
+
+    #[test]
+    fn test_leverage_audit_output_reports_each_row() {
+        let row = |coin: &str, leverage: u32, mode: &str, action: &str, error: Option<&str>| {
+            LeverageAuditRow {
+                coin: coin.into(),
+                leverage: Some(leverage),
+                mode: Some(mode.into()),
+                default_leverage: 5,
+                mismatch: leverage != 5,
+                action: action.into(),
+                error: error.map(Into::into),
+            }
+        };
+        let mut output = LeverageAuditOutput {
+            default_leverage: 5,
+            rows: vec![
+                row("BTC", 5, "cross", "none", None),
+                row("ETH", 10, "isolated", "flagged", None),
+            ],
+        };
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("LEVERAGE AUDIT (default 5x)"));
+        assert!(table.contains("mismatch"));
+        assert!(table.contains(
+            "WARN 1 position(s) differ from the default; run with --apply-default to align them"
+        ));
+
+        output.rows[1].action = "set".into();
+        output.rows.push(row(
+            "SOL",
+            20,
+            "isolated",
+            "failed",
+            Some("Insufficient margin"),
+        ));
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("set to 5x"));
+        assert!(table.contains("FAIL Insufficient margin"));
+        assert!(table.contains("WARN Set 1, failed 1"));
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["rows"][1]["symbol"], "ETH");
+        assert_eq!(json["rows"][1]["action"], "set");
+        assert_eq!(json["rows"][2]["error"], "Insufficient margin");
+        assert!(json["rows"][0].get("error").is_none());
+    }
+
+    #[test]
+    fn test_security_config_output() {
+        let mut output = SecurityConfigOutput {
+            transfer_confirm_above: Some(1000.0),
+            method: "totp".into(),
+            approval_timeout_secs: 300,
+            approval_file: "/home/u/.atlas-os/approvals/transfer.approve".into(),
+            totp_enrolled: false,
+            whitelist_enabled: true,
+            whitelisted: 1,
+        };
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("above 1000 USDC, by totp"));
+        assert!(table.contains("totp enroll"));
+
+        output.totp_enrolled = true;
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("enrolled (OS keyring)"));
+        assert!(!table.contains("will fail"));
+        assert!(table.contains("on, 1 address"), "{table}");
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["transfer_confirm_above"], 1000.0);
+        assert_eq!(json["totp_enrolled"], true);
+    }
+
+    #[test]
+    fn test_notify_outputs() {
+        let config = NotifyConfigOutput {
+            webhook: Some("https://example.com/hook".into()),
+            exec: None,
+            secret: Some("whse...1234".into()),
+            liq_warn_pct: 10.0,
+            max_retries: 3,
+            backoff_ms: 1000,
+        };
+        let table = config.table_string(&RenderContext::plain());
+        assert!(table.contains("https://example.com/hook"));
+        assert!(table.contains("within 10% of liq price"));
+        assert!(!table.contains("No targets set"));
+
+        let test = NotifyTestOutput {
+            event: "test".into(),
+            deliveries: vec![
+                crate::notify::Delivery {
+                    target: "webhook".into(),
+                    ok: true,
+                    attempts: 1,
+                    error: None,
+                },
+                crate::notify::Delivery {
+                    target: "exec".into(),
+                    ok: false,
+                    attempts: 4,
+                    error: Some("exit status 1".into()),
+                },
+            ],
+        };
+        let table = test.table_string(&RenderContext::plain());
+        assert!(table.contains("webhook delivered 'test' event (1 attempt)"));
+        assert!(table.contains("FAIL exec failed after 4 attempts: exit status 1"));
+        let json = serde_json::to_value(&test).unwrap();
+        assert_eq!(json["deliveries"][1]["attempts"], 4);
+    }
+
+    #[test]
+    fn test_schedule_list_and_capture() {
+        let row = |id: i64, runs: u32, max_runs: Option<u32>, last_run: Option<ScheduleRunRow>| {
+            ScheduleRow {
+                id,
+                command: "hl perp buy BTC $50".into(),
+                every: "1d".into(),
+                at: Some("09:00".into()),
+                max_runs,
+                runs,
+                status: if max_runs == Some(runs) {
+                    "done"
+                } else {
+                    "active"
+                }
+                .into(),
+                next_run_ms: (max_runs != Some(runs)).then_some(1_767_258_000_000),
+                last_run,
+            }
+        };
+        let last = ScheduleRunRow {
+            schedule_id: 1,
+            due_ms: 1_767_171_600_000,
+            started_ms: 1_767_171_600_200,
+            status: "ok".into(),
+            missed: 0,
+            order_id: Some("123".into()),
+            price: Some("67012.5".into()),
+            error: None,
+        };
+        let output = ScheduleListOutput {
+            schedules: vec![row(1, 3, Some(30), Some(last)), row(2, 5, Some(5), None)],
+        };
+
+        start_capture();
+        render(OutputFormat::Table, &output).unwrap();
+        // Other tests may render meanwhile; look for ours
+        assert!(take_captured()
+            .iter()
+            .any(|v| v["schedules"][0]["last_run"]["order_id"] == "123"));
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("1d at 09:00"));
+        assert!(table.contains("2026-01-01 09:00:00"));
+        assert!(table.contains("3/30"));
+        assert!(table.contains("ok oid 123 @ 67012.5"));
+        assert!(table.contains("done"));
+        assert!(table.contains("never"));
+        assert!(table.contains("Total: 2 jobs"));
+    }
+
+    #[test]
+    fn test_carry_output_breakdown() {
+        let mut output = CarryOutput {
+            coin: "ETH".into(),
+            size_usd: "10000".into(),
+            horizon_days: 30,
+            chain: "ethereum".into(),
+            market_id: "0xabc".into(),
+            market: "wstETH/WETH".into(),
+            funding_rate: "0.0000125".into(),
+            funding_interval_hours: 1,
+            funding_apr_pct: "10.95".into(),
+            trailing_funding_apr_pct: Some("4.38".into()),
+            supply_apy_pct: "2.10".into(),
+            taker_fee_bps: "4.5".into(),
+            builder_fee_bps: "1".into(),
+            fees_usd: "20.00".into(),
+            fees_apr_pct: "2.43".into(),
+            net_apr_pct: "10.62".into(),
+            net_usd: "87.29".into(),
+            breakeven_funding_apr_pct: "0.33".into(),
+            breakeven_funding_rate: "0.00000038".into(),
+            trailing_below_current: true,
+        };
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("CARRY - ETH short perp / long spot"));
+        assert!(table.contains("10.95% APR (0.0000125/1h)"));
+        assert!(table.contains("-2.43% APR ($20.00; taker 4.5 bps, builder 1 bps)"));
+        assert!(table.contains("Net over 30d (USD)"));
+        assert!(table.contains("0.33% APR (0.00000038/1h)"));
+        assert!(table.contains("WARN 7-day average funding is under half the current rate"));
+
+        output.trailing_below_current = false;
+        output.trailing_funding_apr_pct = None;
+        let table = output.table_string(&RenderContext::plain());
+        assert!(!table.contains("WARN"));
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["net_apr_pct"], "10.62");
+        assert!(json["trailing_funding_apr_pct"].is_null());
+    }
+
+    #[test]
+    fn test_cancel_output_serializes() {
+        let output = CancelOutput {
+            coin: "ETH".into(),
+            cancelled: 3,
+            total: 5,
+            oids: vec![1, 2, 3],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"cancelled\":3"));
+    }
+
+    #[test]
+    fn test_risk_calc_output_serializes() {
+        let output = RiskCalcOutput {
+            coin: "ETH".into(),
+            side: "long".into(),
+            entry_price: 3500.0,
+            size: 2.857,
+            lots: 285.7,
+            notional: 10000.0,
+            stop_loss: 3400.0,
+            take_profit: 3700.0,
+            est_liquidation: 3100.0,
+            liq_model: "isolated".into(),
+            risk_usd: 200.0,
+            risk_pct: 0.02,
+            margin: 1000.0,
+            leverage: 10,
+            warnings: vec!["⚠ test warning".into()],
+            blocked: false,
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"risk_usd\":200.0"));
+        assert!(json.contains("\"blocked\":false"));
+    }
+
+    #[test]
+    fn test_risk_suggest_output() {
+        let output = RiskSuggestOutput {
+            coin: "ETH".into(),
+            side: "long".into(),
+            entry_price: 3200.0,
+            stop_loss: 3100.0,
+            requested: "kelly".into(),
+            method: "fixed-frac".into(),
+            stats: SizingStatsOutput {
+                scope: "all".into(),
+                trades: 5,
+                wins: 3,
+                losses: 2,
+                win_rate: 0.6,
+                avg_win: 120.0,
+                avg_loss: 80.0,
+                payoff_ratio: Some(1.5),
+            },
+            kelly: None,
+            kelly_multiplier: 0.5,
+            kelly_cap: 0.05,
+            account_value: 10000.0,
+            risk_pct: 0.02,
+            risk_usd: 200.0,
+            size: 2.0,
+            lots: 2.0,
+            notional: 6400.0,
+            margin: 1280.0,
+            leverage: 5,
+            warnings: vec!["Only 5 closed trades in the record (need 20)".into()],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"method\":\"fixed-frac\""));
+        assert!(json.contains("\"scope\":\"all\""));
+        assert!(json.contains("\"kelly\":null"));
+
+        let text = output.table_string(&RenderContext::plain());
+        assert!(text.contains("5 trades (all coins), 60.0% won"), "{text}");
+        assert!(text.contains("$120.00 / $80.00 (1.50:1)"));
+        assert!(text.contains("$1280.00 at 5x"));
+        assert!(!text.contains("Lots"));
+        assert!(text.contains("Only 5 closed trades"));
+    }
+
+    #[test]
+    fn test_profile_remove_and_rename_outputs() {
+        let removed = ProfileRemoveOutput {
+            name: "bot".into(),
+            address: "0x0000000000000000000000000000000000000002".into(),
+            key_deleted: true,
+            switched_to: Some("main".into()),
+            purged_rows: None,
+        };
+        let json = serde_json::to_value(&removed).unwrap();
+        assert_eq!(json["switched_to"], "main");
+        assert!(json.get("purged_rows").is_none());
+        let table = removed.table_string(&RenderContext::plain());
+        assert!(table.contains("Removed profile 'bot'"), "{table}");
+        assert!(table.contains("Active profile is now 'main'"), "{table}");
+
+        let renamed = ProfileRenameOutput {
+            old: "bot".into(),
+            new: "sniper".into(),
+            address: "0x0000000000000000000000000000000000000002".into(),
+            active: false,
+            warnings: vec!["The old keyring entry 'bot' could not be deleted".into()],
+        };
+        let table = renamed.table_string(&RenderContext::plain());
+        assert!(table.contains("'bot' -> 'sniper'"), "{table}");
+        assert!(table.contains("WARN The old keyring entry"), "{table}");
+    }
+
+    #[test]
+    fn test_top_markets_marks_sort_column() {
+        let row = TopMarketRow {
+            symbol: "BTC".into(),
+            mid_price: Some("97000.5".into()),
+            mark_price: Some("97000".into()),
+            volume_24h: Some("1500000000.25".into()),
+            change_24h_pct: Some("-1.23456".into()),
+            open_interest_usd: None,
+            funding_rate: Some("0.0000125".into()),
+            funding_apr_pct: Some("10.95".into()),
+            spread_bps: Some("0.3093".into()),
+            best_bid: Some("97000".into()),
+            best_ask: Some("97003".into()),
+        };
+        let output = TopMarketsOutput {
+            sort: "funding".into(),
+            reverse: false,
+            min_volume: None,
+            markets: vec![row],
+        };
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("By Funding"), "{table}");
+        assert!(table.contains("FUNDING APR v"), "{table}");
+        assert!(table.contains("-1.23%"), "{table}");
+        assert!(table.contains("10.95%"), "{table}");
+        // JSON keeps full precision
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["markets"][0]["volume_24h"], "1500000000.25");
+        assert_eq!(json["markets"][0]["change_24h_pct"], "-1.23456");
+        assert!(json["markets"][0]["open_interest_usd"].is_null());
+    }
+
+    #[test]
+    fn test_config_sync_output() {
+        let mut output = ConfigSyncOutput {
+            action: "pull".into(),
+            remote_version: Some(3),
+            remote_updated_at: None,
+            local_modified: true,
+            remote_changed: true,
+            changes: vec![ConfigFieldChange {
+                key: "trading.max_notional".into(),
+                local: Some(serde_json::json!(100.0)),
+                remote: None,
+            }],
+            conflicts: vec!["trading.confirm".into()],
+            applied: true,
+        };
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("Pulled config (v3)"), "{table}");
+        assert!(table.contains("trading.max_notional"), "{table}");
+        assert!(
+            table.contains("WARN trading.confirm changed here and remotely"),
+            "{table}"
+        );
+
+        output.applied = false;
+        output.changes.clear();
+        output.conflicts.clear();
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("Config is in sync (v3)"), "{table}");
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["remote_version"], 3);
+        assert_eq!(json["local_modified"], true);
+    }
+
+    #[test]
+    fn test_liquidation_output_serializes() {
+        let mut output = LiquidationOutput {
+            coin: "BTC".into(),
+            margin_mode: "cross".into(),
+            side: Some("long".into()),
+            size: "1".into(),
+            mark_price: Some("50000".into()),
+            equity: Some("10000".into()),
+            maintenance_margin: Some("1100".into()),
+            liquidation_price: Some("41010.10".into()),
+            distance_pct: Some("17.98".into()),
+            after: None,
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["liquidation_price"], "41010.10");
+        assert!(json.get("after").is_none());
+
+        output.after = Some(LiquidationScenario {
+            add_size: "1".into(),
+            at_price: "50000".into(),
+            size: "2".into(),
+            equity: Some("10000".into()),
+            maintenance_margin: Some("1600".into()),
+            liquidation_price: None,
+            distance_pct: None,
+        });
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["after"]["size"], "2");
+        assert!(json["after"]["liquidation_price"].is_null());
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("none"), "{table}");
+    }
+
+    #[test]
+    fn test_market_order_preview() {
+        use crate::config::ParamSource;
+        let preview = MarketOrderPreview {
+            symbol: "ETH".into(),
+            side: "buy".into(),
+            size: "0.5".into(),
+            size_source: ParamSource::Preset,
+            mark: "3500.0000".into(),
+            notional: "1750.00".into(),
+            leverage: 5,
+            leverage_source: ParamSource::Flag,
+            slippage: 0.05,
+            slippage_source: ParamSource::Default,
+            limit_price: "3675.0000".into(),
+            estimated_avg_px: Some("3501.75".into()),
+            estimated_impact_bps: Some("5.00".into()),
+            book_coverage_pct: Some("100".into()),
+            liquidity_warning: None,
+        };
+        let json = serde_json::to_value(&preview).unwrap();
+        assert_eq!(json["estimated_impact_bps"], "5.00");
+        assert_eq!(json["estimated_avg_px"], "3501.75");
+        assert_eq!(json["size_source"], "preset");
+        assert_eq!(json["leverage_source"], "flag");
+
+        let table = preview.table_string(&RenderContext::default());
+        assert!(table.contains("5.00 bps @ 3501.75"), "{table}");
+        assert!(table.contains("0.5 (preset)"), "{table}");
+        assert!(table.contains("5.0% slip) (default)"), "{table}");
+
+        let thin = MarketOrderPreview {
+            estimated_avg_px: None,
+            estimated_impact_bps: None,
+            book_coverage_pct: Some("62.5".into()),
+            ..preview
+        };
+        let json = serde_json::to_value(&thin).unwrap();
+        assert!(json["estimated_impact_bps"].is_null());
+        let table = thin.table_string(&RenderContext::default());
+        assert!(
+            table.contains("insufficient visible liquidity (62.5% covered)"),
+            "{table}"
+        );
+        assert!(!table.contains("Liquidity"));
+
+        let wide = MarketOrderPreview {
+            liquidity_warning: Some(crate::spread_profile::LiquidityWarning {
+                spread_bps: 24.5,
+                median_bps: 4.9,
+                ratio: 5.0,
+                threshold: 3.0,
+                samples: 180,
+            }),
+            ..thin
+        };
+        let json = serde_json::to_value(&wide).unwrap();
+        assert_eq!(json["liquidity_warning"]["ratio"], 5.0);
+        let table = wide.table_string(&RenderContext::plain());
+        assert!(
+            table.contains("WARN spread 24.50 bps, 5.0x median"),
+            "{table}"
+        );
+    }
+
+    #[test]
+    fn test_spread_profile_output() {
+        use crate::spread_profile::HourlySpread;
+        let output = SpreadProfileOutput {
+            coin: "WIF".into(),
+            samples: 40,
+            median_bps: 8.0,
+            from: "2026-10-01 00:00".into(),
+            to: "2026-10-14 23:59".into(),
+            retention_days: 14,
+            hours: vec![
+                HourlySpread {
+                    hour_utc: 3,
+                    samples: 10,
+                    median_bps: 24.0,
+                },
+                HourlySpread {
+                    hour_utc: 14,
+                    samples: 30,
+                    median_bps: 6.0,
+                },
+            ],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["hours"][0]["hour_utc"], 3);
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("03:00"), "{table}");
+        assert!(table.contains("3.00x"), "{table}");
+        assert!(table.contains("0.75x"), "{table}");
+    }
+
+    #[test]
+    fn test_config_output_serializes() {
+        let mut lots = HashMap::new();
+        lots.insert("BTC".into(), 0.001);
+        let output = ConfigOutput {
+            mode: "futures".into(),
+            size_mode: "usdc".into(),
+            leverage: 10,
+            slippage: 0.05,
+            network: "Mainnet".into(),
+            confirm: true,
+            lots,
+            api_key: Some(crate::config::redact_secret("atl_1234abcdef")),
+            builder_fee_bps: 1,
+            builder_address: Some("0x2287e62D1F9715Aa132aFF90cd37cf57A507065c".into()),
+            rate_limit: crate::config::RateLimitConfig::default(),
+            tui: crate::config::TuiConfig::default(),
+            output: crate::config::OutputConfig::default(),
+            presets: vec![PresetRow {
+                coin: "BTC".into(),
+                leverage: Some(5),
+                slippage: Some(0.003),
+                default_size: Some("$500".into()),
+            }],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"leverage\":10"));
+        assert!(json.contains("\"default_size\":\"$500\""));
+        let table = output.table_string(&RenderContext::default());
+        assert!(table.contains("5x, 0.30% slip, size $500"), "{table}");
+        assert!(json.contains("\"refresh_secs\":10"));
+        assert!(json.contains("\"price_decimals\":\"auto\""));
+        assert!(
+            table.contains("prices auto, sizes auto, USD 2 dp"),
+            "{table}"
+        );
+        assert!(json.contains("\"requests_per_sec\":8.0"));
+        assert!(json.contains("\"builder_fee_bps\":1"));
+        assert!(json.contains("atl_1234…redacted"));
+        assert!(!json.contains("abcdef"));
+    }
+
+    #[test]
+    fn test_doctor_output_serializes() {
+        let output = DoctorOutput {
+            checks: vec![
+                DoctorCheck::ok("profile", "main"),
+                DoctorCheck::ok_bare("keyring"),
+                DoctorCheck::fail("api_key", "Run: atlas configure system api-key <key>"),
+                DoctorCheck::ok("database", "indexes rebuilt").fixed(),
+            ],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"checks\""));
+        assert!(json.contains("\"status\":\"ok\""));
+        assert!(json.contains("\"status\":\"fail\""));
+        assert!(json.contains("\"fix\""));
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(value["checks"][0]["fixed"], false);
+        assert_eq!(value["checks"][3]["fixed"], true);
+    }
+
+    #[test]
+    fn test_doctor_warning_is_not_a_failure() {
+        let output = DoctorOutput {
+            checks: vec![
+                DoctorCheck::ok("profile", "main"),
+                DoctorCheck::warn("hl_rate_limit", "20 req/s, burst 120", "Lower it"),
+            ],
+        };
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(value["checks"][1]["status"], "warn");
+        assert_eq!(value["checks"][1]["value"], "20 req/s, burst 120");
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("All systems operational."));
+        assert!(table.contains("WARN hl_rate_limit: Lower it"));
+    }
+
+    #[test]
+    fn test_init_output() {
+        let output = InitOutput {
+            profile: "bot".into(),
+            address: Some("0xabc".into()),
+            network: "testnet".into(),
+            applied: vec!["profile".into(), "network".into(), "trading".into()],
+            pending: vec![],
+            doctor: DoctorOutput {
+                checks: vec![DoctorCheck::ok("profile", "bot")],
+            },
+        };
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("Profile bot on testnet (0xabc)"), "{table}");
+        assert!(table.contains("Set up:     profile, network, trading"));
+        assert!(table.contains("Still open: none"));
+        assert!(table.contains("All systems operational."));
+
+        let value = serde_json::to_value(&output).unwrap();
+        assert_eq!(value["applied"][1], "network");
+        assert_eq!(value["doctor"]["checks"][0]["status"], "ok");
+    }
+
+    #[test]
+    fn test_pattern_outputs() {
+        use crate::patterns::{Direction, PatternFamily, PatternMatch};
+        let output = PatternsOutput {
+            ticker: "ETH".into(),
+            timeframe: "4h".into(),
+            lookback: 20,
+            patterns: vec![PatternRow {
+                found: PatternMatch {
+                    pattern: "Bullish Engulfing",
+                    family: PatternFamily::Engulfing,
+                    kind: "bullish reversal",
+                    direction: Direction::Bullish,
+                    index: 33,
+                    time_ms: 1_700_000_000_000,
+                    strength: 72,
+                },
+                time: "2023-11-14 22:13:20".into(),
+                candles_ago: 2,
+                signal: "bullish".into(),
+            }],
+            gaps: vec![],
+        };
+        let value = serde_json::to_value(&output).unwrap();
+        let row = &value["patterns"][0];
+        assert_eq!(row["pattern"], "Bullish Engulfing");
+        assert_eq!(row["type"], "bullish reversal");
+        assert_eq!(row["family"], "engulfing");
+        assert_eq!(row["direction"], "bullish");
+        assert_eq!(row["signal"], "bullish");
+        assert_eq!(row["strength"], 72);
+        assert_eq!(row["candles_ago"], 2);
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("Bullish Engulfing"), "{table}");
+        assert!(table.contains("2023-11-14 22:13:20"));
+
+        let scan = PatternScanOutput {
+            timeframe: "4h".into(),
+            patterns: vec!["hammer".into()],
+            scanned: 50,
+            hits: vec![PatternScanRow {
+                coin: "SOL".into(),
+                pattern: "Hammer".into(),
+                kind: "bullish reversal".into(),
+                direction: "bullish".into(),
+                strength: 40,
+                time: "2023-11-14 20:00:00".into(),
+                volume_24h: Some("125000000".into()),
+            }],
+            warnings: vec!["XYZ: no closed candles".into()],
+        };
+        let table = scan.table_string(&RenderContext::plain());
+        assert!(table.contains("across top 50 markets"), "{table}");
+        assert!(table.contains("SOL"));
+        assert!(table.contains("XYZ: no closed candles"));
+        assert_eq!(
+            serde_json::to_value(&scan).unwrap()["hits"][0]["type"],
+            "bullish reversal"
+        );
+    }
+
+    #[test]
+    fn test_ta_bundle_output_serializes() {
+        let mut indicators = BTreeMap::new();
+        indicators.insert(
+            "rsi".to_string(),
+            TaReading {
+                latest: BTreeMap::from([("value".to_string(), "61.20".to_string())]),
+                previous: Some(BTreeMap::from([("value".to_string(), "58.90".to_string())])),
+            },
+        );
+        let output = TaBundleOutput {
+            ticker: "ETH".into(),
+            timeframe: "1h".into(),
+            candles: 150,
+            last_price: "3456.78".into(),
+            indicators,
+            gaps: vec![],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["indicators"]["rsi"]["latest"]["value"], "61.20");
+        assert_eq!(json["indicators"]["rsi"]["previous"]["value"], "58.90");
+        assert!(json.get("gaps").is_none());
+        let table = output.table_string(&RenderContext::plain());
+        assert!(!table.contains("missing"), "{table}");
+
+        let gappy = TaBundleOutput {
+            gaps: vec![crate::candle_gaps::CandleGap {
+                from_ms: 3_600_000,
+                to_ms: 7_200_000,
+                missing: 2,
+            }],
+            ..output
+        };
+        let json = serde_json::to_value(&gappy).unwrap();
+        assert_eq!(json["gaps"][0]["from_ms"], 3_600_000);
+        assert_eq!(json["gaps"][0]["missing"], 2);
+        let table = gappy.table_string(&RenderContext::plain());
+        assert!(
+            table.contains("2 missing candles in 1 gap, first at 1970-01-01 01:00 UTC"),
+            "{table}"
+        );
+    }
+
+    #[test]
+    fn test_funding_screen_output_serializes() {
+        let row = |coin: &str, rate: &str, apr: &str| FundingScreenRow {
+            coin: coin.into(),
+            rate: rate.into(),
+            apr_pct: apr.into(),
+            open_interest_usd: "2500000".into(),
+            volume_24h: "18000000".into(),
+            mark_price: "1.234".into(),
+        };
+        let output = FundingScreenOutput {
+            sort: "apr".into(),
+            min_abs_rate: "0.0005".into(),
+            min_oi_usd: "100000".into(),
+            matched: 2,
+            positive: vec![row("WIF", "0.0012", "1051.20")],
+            negative: vec![row("TAO", "-0.0006", "-525.60")],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["matched"], 2);
+        assert_eq!(json["positive"][0]["coin"], "WIF");
+        assert_eq!(json["negative"][0]["apr_pct"], "-525.60");
+    }
+
+    #[test]
+    fn test_oi_history_output_serializes() {
+        let point = |time: &str, oi: &str| OiPoint {
+            time: time.into(),
+            open_interest: oi.into(),
+            mark_price: "3500".into(),
+            volume_24h: "1000000".into(),
+        };
+        let output = OiHistoryOutput {
+            ticker: "ETH".into(),
+            hours: 24,
+            samples: 2,
+            from: "2026-03-01 00:00:00".into(),
+            to: "2026-03-02 00:00:00".into(),
+            oi_start: "1000".into(),
+            oi_end: "1100".into(),
+            oi_change_pct: "10.00".into(),
+            oi_usd_end: "3850000".into(),
+            price_start: "3400".into(),
+            price_end: "3500".into(),
+            price_change_pct: "2.94".into(),
+            volume_24h_start: "900000".into(),
+            volume_24h_end: "1000000".into(),
+            volume_24h_change: "100000".into(),
+            divergence: "longs building".into(),
+            series: vec![
+                point("2026-03-01 00:00:00", "1000"),
+                point("2026-03-02 00:00:00", "1100"),
+            ],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["oi_change_pct"], "10.00");
+        assert_eq!(json["divergence"], "longs building");
+        assert_eq!(json["series"][1]["open_interest"], "1100");
+    }
+
+    #[test]
+    fn test_correlation_output_lookup_and_json() {
+        let output = CorrelationOutput {
+            symbols: vec!["BTC".into(), "ETH".into(), "SOL".into()],
+            timeframe: "1h".into(),
+            samples: 199,
+            pairs: vec![
+                CorrelationPair {
+                    a: "BTC".into(),
+                    b: "ETH".into(),
+                    corr: Some(0.86),
+                },
+                CorrelationPair {
+                    a: "BTC".into(),
+                    b: "SOL".into(),
+                    corr: Some(0.74),
+                },
+                CorrelationPair {
+                    a: "ETH".into(),
+                    b: "SOL".into(),
+                    corr: None,
+                },
+            ],
+            betas: BTreeMap::from([
+                ("BTC".to_string(), Some(1.0)),
+                ("ETH".to_string(), Some(1.21)),
+                ("SOL".to_string(), Some(1.47)),
+            ]),
+        };
+        assert_eq!(output.corr("SOL", "BTC"), Some(0.74));
+        assert_eq!(output.corr("ETH", "ETH"), Some(1.0));
+        assert_eq!(output.corr("SOL", "ETH"), None);
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["pairs"][0]["a"], "BTC");
+        assert_eq!(json["pairs"][0]["corr"], 0.86);
+        assert!(json["pairs"][2]["corr"].is_null());
+        assert_eq!(json["betas"]["ETH"], 1.21);
+    }
+
+    #[test]
+    fn test_price_compare_scores_against_median() {
+        use rust_decimal::Decimal;
+
+        let price = |p: &str| {
+            Ok(SourcePrice {
+                price: p.parse().unwrap(),
+                timestamp_ms: 1_700_000_000_000,
+            })
+        };
+        let output = PriceCompareOutput::new(
+            "ETH",
+            "base",
+            Decimal::from(1000),
+            Decimal::from(50),
+            vec![
+                ("hyperliquid".into(), price("3500")),
+                ("coingecko".into(), price("3501.75")),
+                ("0x".into(), price("3540")),
+            ],
+        );
+        assert_eq!(output.median.as_deref(), Some("3501.75"));
+        let rows = &output.sources;
+        assert_eq!(rows[0].deviation_bps.as_deref(), Some("-5"));
+        assert!(!rows[0].flagged);
+        assert_eq!(rows[1].deviation_bps.as_deref(), Some("0"));
+        assert_eq!(rows[2].deviation_bps.as_deref(), Some("109.2"));
+        assert!(rows[2].flagged);
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("+109.2 bps"), "{table}");
+        assert!(table.contains("WARN beyond 50 bps"), "{table}");
+    }
+
+    #[test]
+    fn test_price_compare_failed_source_is_unavailable_row() {
+        use rust_decimal::Decimal;
+
+        let output = PriceCompareOutput::new(
+            "BTC",
+            "ethereum",
+            Decimal::from(1000),
+            Decimal::from(50),
+            vec![
+                (
+                    "hyperliquid".into(),
+                    Ok(SourcePrice {
+                        price: Decimal::from(65_000),
+                        timestamp_ms: 1,
+                    }),
+                ),
+                ("coingecko".into(), Err("backend not reachable".into())),
+                (
+                    "0x".into(),
+                    Ok(SourcePrice {
+                        price: Decimal::from(65_100),
+                        timestamp_ms: 1,
+                    }),
+                ),
+            ],
+        );
+        // Two prices: the median is their midpoint
+        assert_eq!(output.median.as_deref(), Some("65050"));
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["sources"][1]["available"], false);
+        assert!(json["sources"][1]["price"].is_null());
+        assert_eq!(json["sources"][1]["error"], "backend not reachable");
+        assert!(json["sources"][0].get("error").is_none());
+
+        let none = PriceCompareOutput::new(
+            "BTC",
+            "ethereum",
+            Decimal::from(1000),
+            Decimal::from(50),
+            vec![("coingecko".into(), Err("down".into()))],
+        );
+        assert!(none.median.is_none());
+        assert!(none
+            .table_string(&RenderContext::plain())
+            .contains("No source returned a price"));
+    }
+
+    #[test]
+    fn test_trend_mtf_output_serializes() {
+        let output = TrendMtfOutput {
+            ticker: "ETH".into(),
+            timeframes: vec![
+                TrendTimeframeRow {
+                    timeframe: "1h".into(),
+                    direction: "bullish".into(),
+                    score: Some(72),
+                    indicators: BTreeMap::from([("rsi".to_string(), "64.10".to_string())]),
+                    error: None,
+                    gaps: vec![],
+                },
+                TrendTimeframeRow {
+                    timeframe: "1w".into(),
+                    direction: "n/a".into(),
+                    score: None,
+                    indicators: BTreeMap::new(),
+                    error: Some("insufficient data".into()),
+                    gaps: vec![],
+                },
+            ],
+            score: 72,
+            direction: "bullish".into(),
+            verdict: "bullish 1/2 timeframes".into(),
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        let rows = json["timeframes"].as_array().unwrap();
+        assert_eq!(rows[0]["timeframe"], "1h");
+        assert!(rows[0].get("error").is_none());
+        assert_eq!(rows[1]["direction"], "n/a");
+        assert!(rows[1]["score"].is_null());
+        assert_eq!(json["verdict"], "bullish 1/2 timeframes");
+    }
+
+    #[test]
+    fn test_transfer_output_name_and_label() {
+        let plain = TransferOutput {
+            amount: "25".into(),
+            destination: "0xAb5801a7D398351b8bE11C439e05C5B3259aeC9B".into(),
+            name: None,
+            label: None,
+        };
+        let json = serde_json::to_value(&plain).unwrap();
+        assert!(json.get("name").is_none() && json.get("label").is_none());
+
+        let named = TransferOutput {
+            name: Some("vitalik.eth".into()),
+            label: Some("cold wallet".into()),
+            ..plain
+        };
+        let json = serde_json::to_value(&named).unwrap();
+        assert_eq!(json["name"], "vitalik.eth");
+        assert_eq!(json["label"], "cold wallet");
+        let table = named.table_string(&RenderContext::plain());
+        assert!(
+            table.contains(
+                "to vitalik.eth (0xAb5801a7D398351b8bE11C439e05C5B3259aeC9B) [cold wallet]"
+            ),
+            "{table}"
+        );
+    }
+
+    #[test]
+    fn test_whitelist_output_table() {
+        let output = WhitelistOutput {
+            enabled: true,
+            entries: vec![crate::config::WhitelistEntry {
+                address: "0xAb5801a7D398351b8bE11C439e05C5B3259aeC9B".into(),
+                label: "exchange".into(),
+            }],
+        };
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("exchange"), "{table}");
+        assert!(table.contains("transfers to other addresses are refused"));
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["entries"][0]["label"], "exchange");
+    }
+
+    #[test]
+    fn test_morpho_positions_json_and_empty() {
+        let empty = MorphoPositionsOutput {
+            address: "0xabc".into(),
+            chain: "ethereum".into(),
+            positions: vec![],
+        };
+        let json = serde_json::to_value(&empty).unwrap();
+        assert_eq!(json["positions"].as_array().unwrap().len(), 0);
+
+        let output = MorphoPositionsOutput {
+            positions: vec![MorphoPositionRow {
+                market_id: "0xb323".into(),
+                market: "wstETH/USDC".into(),
+                collateral_asset: "wstETH".into(),
+                loan_asset: "USDC".into(),
+                collateral: "2.5".into(),
+                collateral_usd: "9500".into(),
+                borrowed: "4000".into(),
+                borrowed_usd: "4000".into(),
+                supplied: "0".into(),
+                supplied_usd: "0".into(),
+                health_factor: Some("2.04".into()),
+            }],
+            ..empty
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        let row = &json["positions"][0];
+        assert_eq!(row["market"], "wstETH/USDC");
+        assert_eq!(row["health_factor"], "2.04");
+    }
+
+    #[test]
+    fn test_json_pretty_format() {
+        let output = StatusOutput {
+            profile: "default".into(),
+            address: "0x1234".into(),
+            env: None,
+            network: "Mainnet".into(),
+            modules: vec!["hyperliquid".into()],
+            balances: vec![],
+            account_value: Some("10000.00".into()),
+            margin_used: Some("500.00".into()),
+            net_position: Some("2500.00".into()),
+            withdrawable: Some("9500.00".into()),
+            positions: vec![],
+            open_orders: 0,
+            total_value_usd: None,
+            protocols: vec![],
+            warnings: vec![],
+            diff: None,
+        };
+        let pretty = serde_json::to_string_pretty(&output).unwrap();
+        assert!(pretty.contains('\n'));
+        assert!(pretty.contains("  "));
+    }
+
+    #[test]
+    fn test_price_output_serializes() {
+        let output = PriceOutput {
+            prices: vec![
+                PriceRow {
+                    coin: "BTC".into(),
+                    mid_price: "105234.50".into(),
+                    protocol: "hyperliquid".into(),
+                    pair: None,
+                    key: None,
+                    error: None,
+                },
+                PriceRow {
+                    coin: "ETH".into(),
+                    mid_price: "3521.25".into(),
+                    protocol: "hyperliquid".into(),
+                    pair: None,
+                    key: None,
+                    error: None,
+                },
+            ],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"symbol\":\"BTC\""));
+        assert!(json.contains("\"price\":\"105234.50\""));
+        assert!(!json.contains("\"error\""));
+        assert!(!json.contains("\"pair\""));
+
+        let spot = PriceRow {
+            coin: "HYPE".into(),
+            mid_price: "31.25".into(),
+            protocol: "hyperliquid".into(),
+            pair: Some("HYPE/USDC".into()),
+            key: Some("@107".into()),
+            error: None,
+        };
+        let json = serde_json::to_string(&spot).unwrap();
+        assert!(json.contains("\"pair\":\"HYPE/USDC\",\"key\":\"@107\""));
+    }
+
+    #[test]
+    fn test_dex_tables_handle_empty_and_young_pools() {
+        let candle = DexCandleRow {
+            timestamp_ms: 1_712_534_400_000,
+            time: "2024-04-08 00:00:00".into(),
+            open: "1.2".into(),
+            high: "1.5".into(),
+            low: "1.1".into(),
+            close: "1.4".into(),
+            volume_usd: "52000".into(),
+        };
+        let mut ohlcv = DexOhlcvOutput {
+            network: "base".into(),
+            pool: "0x4c36388be6f416a29c8d8eee81c771ce6be14b18".into(),
+            timeframe: "hour".into(),
+            aggregate: 4,
+            base: Some("VIRTUAL".into()),
+            quote: Some("WETH".into()),
+            limit: 100,
+            candles: vec![candle],
+        };
+        let table = ohlcv.table_string(&RenderContext::plain());
+        assert!(table.starts_with("VIRTUAL/WETH base - 4h candles"));
+        assert!(table.ends_with("1 of 100 candles - history starts 2024-04-08 00:00:00\n"));
+
+        ohlcv.candles.clear();
+        assert!(ohlcv
+            .table_string(&RenderContext::plain())
+            .contains("No candles yet"));
+
+        let trades = DexTradesOutput {
+            network: "base".into(),
+            pool: "0x4c36".into(),
+            min_usd: Some("10000".into()),
+            trades: vec![],
+        };
+        assert_eq!(
+            trades.table_string(&RenderContext::plain()),
+            "No trades of $10000 or more in this pool.\n"
+        );
+    }
+
+    #[test]
+    fn test_dex_watch_check_output() {
+        let row = |label: &str, status: &str, pct: Option<f64>| DexWatchRow {
+            network: "base".into(),
+            pool: "0x6921b130d297cc43754afba22e5eac0fbf8db75b".into(),
+            label: Some(label.into()),
+            name: None,
+            status: status.into(),
+            price_usd: pct.map(|_| "0.009".into()),
+            price_change_pct: pct,
+            volume_24h_usd: pct.map(|_| "1250000".into()),
+            volume_change_pct: pct.map(|_| -25.0),
+            liquidity_usd: None,
+            liquidity_change_pct: None,
+            previous_check_ms: Some(1_000),
+            missing_since_ms: (status == "missing").then_some(2_000),
+            error: None,
+        };
+        let output = DexWatchCheckOutput {
+            checked_ms: 2_000,
+            threshold_pct: Some(5.0),
+            checked: 3,
+            pools: vec![row("toshi", "ok", Some(20.0)), row("rug", "missing", None)],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["pools"][0]["price_change_pct"], 20.0);
+        assert_eq!(json["pools"][0]["volume_change_pct"], -25.0);
+        assert!(json["pools"][1]["price_change_pct"].is_null());
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("+20.00%"), "{table}");
+        assert!(table.contains("-25.00%"), "{table}");
+        assert!(table.contains("pool gone from the API"), "{table}");
+        assert!(table.contains("2 of 3 pools moved more than 5%"), "{table}");
+
+        let quiet = DexWatchCheckOutput {
+            pools: vec![],
+            ..output
+        };
+        assert_eq!(
+            quiet.table_string(&RenderContext::plain()),
+            "No watched pool moved more than 5% since the last check (3 checked).\n"
+        );
+    }
+
+    #[test]
+    fn test_market_overview_output() {
+        let output = MarketOverviewOutput {
+            global: Some(MarketOverviewGlobal {
+                total_market_cap_usd: 3.41e12,
+                market_cap_change_24h_pct: Some(-1.25),
+                btc_dominance_pct: Some(56.6),
+            }),
+            sort: "mcap".into(),
+            coins: vec![MarketOverviewRow {
+                id: "bitcoin".into(),
+                symbol: "BTC".into(),
+                name: "Bitcoin".into(),
+                price_usd: 97541.22,
+                change_1h_pct: Some(0.21),
+                change_24h_pct: Some(1.12),
+                change_7d_pct: None,
+                volume_24h_usd: Some(41.2e9),
+                market_cap_usd: Some(1.931e12),
+                market_cap_rank: Some(1),
+                from_ath_pct: Some(-10.34),
+            }],
+            missing: vec!["nope".into()],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["global"]["btc_dominance_pct"], 56.6);
+        assert_eq!(json["coins"][0]["change_24h_pct"], 1.12);
+        assert!(json["coins"][0]["change_7d_pct"].is_null());
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(
+            table.starts_with("MARKET OVERVIEW - MCap $3.41T (-1.25% 24h), BTC dom 56.6%"),
+            "{table}"
+        );
+        assert!(table.contains("+1.12%"), "{table}");
+        assert!(table.contains("-10.34%"), "{table}");
+        assert!(table.contains("Not found on CoinGecko: nope"), "{table}");
+    }
+
+    #[test]
+    fn test_cross_price_output_carries_usd_mids() {
+        let output = CrossPriceOutput {
+            quote: "BTC".into(),
+            quote_usd: "60000".into(),
+            prices: vec![
+                CrossPriceRow {
+                    symbol: "ETH".into(),
+                    price: Some("0.05".into()),
+                    base_usd: Some("3000".into()),
+                    quote_usd: "60000".into(),
+                    error: None,
+                },
+                CrossPriceRow {
+                    symbol: "NOPE".into(),
+                    price: None,
+                    base_usd: None,
+                    quote_usd: "60000".into(),
+                    error: Some("Asset not found: NOPE".into()),
+                },
+            ],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["prices"][0]["price"], "0.05");
+        assert_eq!(json["prices"][0]["base_usd"], "3000");
+        assert_eq!(json["prices"][0]["quote_usd"], "60000");
+        assert!(json["prices"][1]["price"].is_null());
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("Price (BTC)"));
+        assert!(table.contains("Asset not found: NOPE"));
+        assert!(table.ends_with("BTC = $60000\n"));
+    }
+
+    #[test]
+    fn test_markets_output_serializes() {
+        let output = MarketsOutput {
+            market_type: "perp".into(),
+            markets: vec![MarketRow {
+                name: "ETH".into(),
+                index: 1,
+                max_leverage: Some(50),
+                sz_decimals: 4,
+                key: None,
+                wei_decimals: None,
+                mid_price: None,
+            }],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"max_leverage\":50"));
+        assert!(!json.contains("\"key\""));
+
+        let spot = MarketRow {
+            name: "HYPE/USDC".into(),
+            index: 107,
+            max_leverage: None,
+            sz_decimals: 2,
+            key: Some("@107".into()),
+            wei_decimals: Some(8),
+            mid_price: Some("31.25".into()),
+        };
+        let json = serde_json::to_string(&spot).unwrap();
+        assert!(!json.contains("max_leverage"));
+        assert!(json.contains("\"key\":\"@107\",\"wei_decimals\":8,\"mid_price\":\"31.25\""));
+    }
+
+    #[test]
+    fn test_candles_output_serializes() {
+        let output = CandlesOutput {
+            coin: "BTC".into(),
+            interval: "1h".into(),
+            candles: vec![CandleRow {
+                time: "2026-02-24 08:00:00".into(),
+                open: "105000".into(),
+                high: "105500".into(),
+                low: "104800".into(),
+                close: "105300".into(),
+                volume: "1234.5".into(),
+                trades: 456,
+            }],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"trades\":456"));
+        assert!(json.contains("\"interval\":\"1h\""));
+    }
+
+    #[test]
+    fn test_spot_balance_output_serializes() {
+        let output = SpotBalanceOutput {
+            balances: vec![SpotBalanceRow {
+                coin: "USDC".into(),
+                total: "1000.00".into(),
+                held: "50.00".into(),
+                available: "950.00".into(),
+            }],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"coin\":\"USDC\""));
+        assert!(json.contains("\"available\":\"950.00\""));
+    }
+
+    #[test]
+    fn test_spot_order_output_serializes() {
+        let output = SpotOrderOutput {
+            market: "PURR/USDC".into(),
+            side: "BUY".into(),
+            size: "100".into(),
+            notional: "50.00".into(),
+            warning: None,
+            oid: 42,
+            status: "filled".into(),
+            total_sz: Some("100.0".into()),
+            avg_px: Some("0.50".into()),
+            audit_id: None,
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"market\":\"PURR/USDC\""));
+        assert!(json.contains("\"oid\":42"));
+        assert!(json.contains("\"status\":\"filled\""));
+        assert!(json.contains("\"notional\":\"50.00\""));
+        assert!(!json.contains("warning"));
+    }
+
+    #[test]
+    fn test_spot_transfer_output_serializes() {
+        let output = SpotTransferOutput {
+            direction: "perps → spot".into(),
+            token: "USDC".into(),
+            amount: "500.00".into(),
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"direction\":\"perps → spot\""));
+        assert!(json.contains("\"token\":\"USDC\""));
+    }
+
+    #[test]
+    fn test_funding_output_serializes() {
+        let output = FundingOutput {
+            coin: "ETH".into(),
+            rates: vec![FundingRow {
+                time: "2026-02-24 08:00:00".into(),
                 coin: "ETH".into(),
-                side: "long".into(),
-                size: "0.5".into(),
-                entry_price: Some("3500.00".into()),
-                mark_price: Some("3550.00".into()),
-                unrealized_pnl: Some("25.00".into()),
-                liquidation_price: Some("2800.00".into()),
-                leverage: Some(5),
-                margin_mode: Some("isolated".into()),
-                protocol: "hyperliquid".into(),
+                rate: "0.00012".into(),
+                premium: "0.00005".into(),
+            }],
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"rate\":\"0.00012\""));
+    }
+
+    #[test]
+    fn test_vault_details_output_serializes() {
+        let output = VaultDetailsOutput {
+            name: "Test Vault".into(),
+            address: "0xabc".into(),
+            leader: "0xdef".into(),
+            description: "A test vault".into(),
+            apr: "12.50".into(),
+            leader_fraction: "10.00".into(),
+            leader_commission: "5.00".into(),
+            max_distributable: "50000.00".into(),
+            max_withdrawable: "40000.00".into(),
+            follower_count: 42,
+            is_closed: false,
+            allow_deposits: true,
+            followers: vec![VaultFollowerRow {
+                user: "0x1234".into(),
+                equity: "10000.00".into(),
+                pnl: "500.00".into(),
+                days_following: 30,
+            }],
+            user_state: Some(VaultUserStateRow {
+                equity: "5000.00".into(),
+                pnl: "250.00".into(),
+                all_time_pnl: "1000.00".into(),
+                days_following: 60,
+                lockup_until: Some("2026-03-01".into()),
+            }),
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"name\":\"Test Vault\""));
+        assert!(json.contains("\"follower_count\":42"));
+        assert!(json.contains("\"allow_deposits\":true"));
+        assert!(json.contains("\"all_time_pnl\":\"1000.00\""));
+    }
+
+    #[test]
+    fn test_vault_list_output() {
+        let output = VaultListOutput {
+            sort: "apr".into(),
+            matched: 40,
+            vaults: vec![
+                VaultListRow {
+                    rank: 1,
+                    address: "0x1e37a337ed460039d1b15bd3bc489de789768d5e".into(),
+                    name: "Delta Neutral Basis".into(),
+                    leader: "0x5b5d51203a0f9079f8aeb098a6523a13f298c060".into(),
+                    tvl_usd: rust_decimal::Decimal::new(25431108, 2),
+                    apr_pct: Some(rust_decimal::Decimal::new(4217, 2)),
+                    age_days: Some(120),
+                    followers: None,
+                },
+                VaultListRow {
+                    rank: 2,
+                    address: "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303".into(),
+                    name: "Hyperliquidity Provider (HLP)".into(),
+                    leader: "0x677d831aef5328190852e24f13c46cac05f984e7".into(),
+                    tvl_usd: rust_decimal::Decimal::new(391234567, 0),
+                    apr_pct: Some(rust_decimal::Decimal::new(-152, 2)),
+                    age_days: None,
+                    followers: Some(12000),
+                },
+            ],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["matched"], 40);
+        assert_eq!(json["vaults"][0]["tvl_usd"], "254311.08");
+        assert!(json["vaults"][0]["followers"].is_null());
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.starts_with("VAULTS by apr (2 of 40)"), "{table}");
+        assert!(
+            table.contains("0x1e37a337ed460039d1b15bd3bc489de789768d5e"),
+            "{table}"
+        );
+        assert!(table.contains("+42.17%"), "{table}");
+        assert!(table.contains("-1.52%"), "{table}");
+        assert!(table.contains("120d"), "{table}");
+        assert!(table.contains("12000"), "{table}");
+    }
+
+    #[test]
+    fn test_vault_deposits_output_serializes() {
+        let output = VaultDepositsOutput {
+            deposits: vec![VaultDepositRow {
+                vault_address: "0xabc".into(),
+                equity: "5000.00".into(),
+                locked_until: Some("2026-03-01".into()),
+            }],
+            total_equity: "5000.00".into(),
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"total_equity\":\"5000.00\""));
+        assert!(json.contains("\"vault_address\":\"0xabc\""));
+    }
+
+    #[test]
+    fn test_subaccounts_output_serializes() {
+        let output = SubAccountsOutput {
+            subaccounts: vec![SubAccountRow {
+                name: "bot-1".into(),
+                address: "0x5678".into(),
+                account_value: "10000.00".into(),
+                total_position: "5000.00".into(),
+                margin_used: "1000.00".into(),
+                withdrawable: "9000.00".into(),
+                positions: vec![PositionRow {
+                    coin: "ETH".into(),
+                    side: "long".into(),
+                    size: "1.5".into(),
+                    entry_price: Some("3500.00".into()),
+                    mark_price: None,
+                    unrealized_pnl: Some("100.00".into()),
+                    notional: None,
+                    pnl_pct: None,
+                    liquidation_price: None,
+                    leverage: None,
+                    margin_mode: None,
+                    protocol: "hyperliquid".into(),
+                }],
+                spot_balances: vec![SpotBalanceRow {
+                    coin: "USDC".into(),
+                    total: "500.00".into(),
+                    held: "0.00".into(),
+                    available: "500.00".into(),
+                }],
             }],
-            open_orders: 2,
         };
         let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"profile\":\"default\""));
+        assert!(json.contains("\"name\":\"bot-1\""));
+        assert!(json.contains("\"account_value\":\"10000.00\""));
         assert!(json.contains("\"symbol\":\"ETH\""));
-        assert!(json.contains("\"modules\""));
-        assert!(json.contains("\"open_orders\":2"));
-        assert!(json.contains("\"balances\""));
     }
 
     #[test]
-    fn test_orders_output_serializes() {
-        let output = OrdersOutput {
-            orders: vec![OrderRow {
-                coin: "BTC".into(),
-                side: "BUY".into(),
-                size: "0.01".into(),
-                price: "50000.00".into(),
-                oid: 12345,
-            }],
+    fn test_agent_approve_output_serializes() {
+        let output = AgentApproveOutput {
+            agent_address: "0xagent".into(),
+            agent_name: "my-bot".into(),
+            status: "approved".into(),
         };
         let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"order_id\":12345"));
+        assert!(json.contains("\"agent_address\":\"0xagent\""));
+        assert!(json.contains("\"status\":\"approved\""));
     }
 
     #[test]
-    fn test_fills_output_serializes() {
-        let output = FillsOutput {
-            fills: vec![FillRow {
+    fn test_trade_history_output_serializes() {
+        let output = TradeHistoryOutput {
+            trades: vec![TradeHistoryRow {
+                protocol: "hyperliquid".into(),
                 coin: "ETH".into(),
-                side: "SELL".into(),
-                size: "1.0".into(),
+                side: "Buy".into(),
+                size: "0.5".into(),
                 price: "3500.00".into(),
-                closed_pnl: "100.00".into(),
-                fee: "1.50".into(),
+                pnl: "100.00".into(),
+                fee: "1.75".into(),
+                time: "2026-02-24 08:00:00".into(),
+                hash: "0xabc".into(),
+                has_note: false,
+                notes: vec![],
             }],
+            total: 1,
+            with_notes: false,
         };
         let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"closed_pnl\":\"100.00\""));
+        assert!(json.contains("\"coin\":\"ETH\""));
+        assert!(json.contains("\"total\":1"));
     }
 
     #[test]
-    fn test_order_result_output_serializes() {
-        let output = OrderResultOutput {
-            oid: 999,
-            coin: "BTC".into(),
-            side: "sell".into(),
-            total_sz: Some("0.5".into()),
-            avg_px: Some("3500.00".into()),
-            filled: Some("0.5".into()),
-            status: "filled".into(),
-            fee: Some("0.05".into()),
-            builder_fee_bps: 1,
+    fn test_trade_history_note_column() {
+        let row = |coin: &str, notes: &[&str]| TradeHistoryRow {
             protocol: "hyperliquid".into(),
-            timestamp: None,
+            coin: coin.into(),
+            side: "Buy".into(),
+            size: "1".into(),
+            price: "10".into(),
+            pnl: "0".into(),
+            fee: "0.01".into(),
+            time: "2026-02-24 08:00:00".into(),
+            hash: format!("0x{coin}"),
+            has_note: !notes.is_empty(),
+            notes: notes.iter().map(|n| n.to_string()).collect(),
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"status\":\"filled\""));
+        let mut output = TradeHistoryOutput {
+            trades: vec![
+                row("ETH", &["breakout", "added on retest"]),
+                row("BTC", &[]),
+            ],
+            total: 2,
+            with_notes: false,
+        };
+        let marked = output.table_string(&RenderContext::default());
+        assert!(marked.contains("│ Note │"));
+        assert!(marked.contains("│ ✎    │"));
+        assert!(output
+            .table_string(&RenderContext::plain())
+            .contains("| *    |"));
+
+        output.with_notes = true;
+        assert!(output
+            .table_string(&RenderContext::default())
+            .contains("│ breakout; added on retest │"));
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["trades"][0]["has_note"], true);
+        assert_eq!(json["trades"][0]["notes"][1], "added on retest");
+        assert!(json["trades"][1].get("notes").is_none());
     }
 
     #[test]
-    fn test_cancel_output_serializes() {
-        let output = CancelOutput {
-            coin: "ETH".into(),
-            cancelled: 3,
-            total: 5,
-            oids: vec![1, 2, 3],
+    fn test_restore_output_flags_missing_keys() {
+        let output = RestoreOutput {
+            path: "/tmp/atlas.tar.gz".into(),
+            created_at: "2026-10-01T12:00:00Z".into(),
+            files_restored: vec!["atlas.json".into(), "keystore/wallets.json".into()],
+            profiles_imported: vec!["main".into()],
+            profiles_without_key: vec!["bot".into()],
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"cancelled\":3"));
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("main"));
+        assert!(table.contains("No key restored for 'bot': atlas profile import bot"));
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["files_restored"][1], "keystore/wallets.json");
+        assert_eq!(json["profiles_without_key"][0], "bot");
     }
 
     #[test]
-    fn test_risk_calc_output_serializes() {
-        let output = RiskCalcOutput {
-            coin: "ETH".into(),
-            side: "long".into(),
-            entry_price: 3500.0,
-            size: 2.857,
-            lots: 285.7,
-            notional: 10000.0,
-            stop_loss: 3400.0,
-            take_profit: 3700.0,
-            est_liquidation: 3100.0,
-            risk_usd: 200.0,
-            risk_pct: 0.02,
-            margin: 1000.0,
-            leverage: 10,
-            warnings: vec!["⚠ test warning".into()],
-            blocked: false,
+    fn test_module_list_health() {
+        let row = |name: &str, enabled: bool, health: Option<ModuleHealth>| ModuleRow {
+            name: name.into(),
+            description: String::new(),
+            enabled,
+            network: Some("mainnet".into()),
+            config: "network=mainnet".into(),
+            health,
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"risk_usd\":200.0"));
-        assert!(json.contains("\"blocked\":false"));
+        let mut output = ModuleListOutput {
+            modules: vec![
+                row(
+                    "hyperliquid",
+                    true,
+                    Some(ModuleHealth::ok(84, Some("229 perps".into()))),
+                ),
+                row(
+                    "zero_x",
+                    true,
+                    Some(ModuleHealth::unreachable("timed out after 3s")),
+                ),
+                row("morpho", false, None),
+            ],
+            backend: Some(ModuleHealth::ok(40, None)),
+        };
+        assert!(!output.all_healthy());
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("| Health      | Latency |"));
+        assert!(table.contains("| hyperliquid | on    | OK          |    84ms |"));
+        assert!(table.contains("network=mainnet (229 perps)"));
+        assert!(table.contains("| zero_x      | on    | unreachable |       - |"));
+        assert!(table.contains("FAIL zero_x: timed out after 3s"));
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["modules"][0]["health"]["latency_ms"], 84);
+        assert_eq!(json["modules"][1]["health"]["status"], "unreachable");
+        assert!(json["modules"][2].get("health").is_none());
+        assert_eq!(json["backend"]["status"], "ok");
+
+        // --no-probe: no health columns, no backend
+        output.backend = None;
+        for m in &mut output.modules {
+            m.health = None;
+        }
+        assert!(output.all_healthy());
+        let table = output.table_string(&RenderContext::plain());
+        assert!(!table.contains("Health"));
+        assert!(serde_json::to_value(&output)
+            .unwrap()
+            .get("backend")
+            .is_none());
     }
 
     #[test]
-    fn test_config_output_serializes() {
-        let mut lots = HashMap::new();
-        lots.insert("BTC".into(), 0.001);
-        let output = ConfigOutput {
-            mode: "futures".into(),
-            size_mode: "usdc".into(),
-            leverage: 10,
-            slippage: 0.05,
-            network: "Mainnet".into(),
-            lots,
+    fn test_trade_history_protocol_column_and_swaps() {
+        let row = |protocol: &str, coin: &str| TradeHistoryRow {
+            protocol: protocol.into(),
+            coin: coin.into(),
+            side: "swap".into(),
+            size: "1000".into(),
+            price: "0.00025".into(),
+            pnl: "0".into(),
+            fee: "0.02".into(),
+            time: "2026-02-24 08:00:00".into(),
+            hash: "0xabc".into(),
+            has_note: false,
+            notes: vec![],
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"leverage\":10"));
+        let mixed = TradeHistoryOutput {
+            trades: vec![row("0x", "USDC→WETH"), row("hyperliquid", "ETH")],
+            total: 2,
+            with_notes: false,
+        };
+        let table = mixed.table_string(&RenderContext::plain());
+        assert!(table.contains("| Protocol    | Coin"));
+        assert!(table.contains("| 0x          | USDC->WETH |"));
+
+        let swaps = SwapHistoryOutput {
+            swaps: vec![SwapHistoryRow {
+                status: "executed".into(),
+                chain: "base".into(),
+                sell_token: "0x8335".into(),
+                sell_symbol: Some("USDC".into()),
+                buy_token: "0x4200000000000000000000000000000000000006".into(),
+                buy_symbol: None,
+                sell_amount: "1000".into(),
+                buy_amount: "0.25".into(),
+                price: Some("0.00025".into()),
+                sell_value_usd: Some("1000".into()),
+                gas_cost_usd: None,
+                tx_hash: Some("0xdeadbeefcafe0123".into()),
+                time: "2026-02-24 08:00:00".into(),
+            }],
+            total: 1,
+        };
+        let table = swaps.table_string(&RenderContext::plain());
+        assert!(table.contains("| 1000 USDC |"));
+        assert!(table.contains("| 0.25 0x4200...0006 |"));
+        assert!(table.contains("| $1000 |   - | 0xdead...0123 |"));
+
+        let pnl = PnlSummaryOutput {
+            total_pnl: "0".into(),
+            total_fees: "0".into(),
+            net_pnl: "0".into(),
+            trade_count: 0,
+            win_count: 0,
+            loss_count: 0,
+            win_rate: "0.0%".into(),
+            by_coin: vec![],
+            net_deposits: None,
+            swaps: Some(SwapPnlSummary {
+                positions: vec![SwapPnlRow {
+                    chain: "base".into(),
+                    token: "0x4200".into(),
+                    symbol: Some("WETH".into()),
+                    amount: "0.25".into(),
+                    cost_usd: "1000".into(),
+                    gas_usd: "0.02".into(),
+                    price_usd: Some("4400".into()),
+                    value_usd: Some("1100".into()),
+                    unrealized_pnl: Some("100".into()),
+                    priced_at: Some("2026-02-24 09:00:00".into()),
+                    swaps: 1,
+                }],
+                cost_usd: "1000".into(),
+                value_usd: "1100".into(),
+                unrealized_pnl: "100".into(),
+                unpriced: 0,
+                skipped_swaps: 2,
+            }),
+        };
+        let table = pnl.table_string(&RenderContext::plain());
+        assert!(table.contains("0x SWAPS"));
+        assert!(table.contains("| base  | WETH  |   0.25 | $1000 | $1100 |  100 |"));
+        assert!(table.contains("Unrealized: $100 (cost $1000, value $1100)"));
+        assert!(table.contains("2 swap(s) left out"));
+        let json = serde_json::to_value(&pnl).unwrap();
+        assert_eq!(json["swaps"]["positions"][0]["unrealized_pnl"], "100");
     }
 
     #[test]
-    fn test_doctor_output_serializes() {
-        let output = DoctorOutput {
-            checks: vec![
-                DoctorCheck::ok("profile", "main"),
-                DoctorCheck::ok_bare("keyring"),
-                DoctorCheck::fail("api_key", "Run: atlas configure system api-key <key>"),
-            ],
+    fn test_market_info_table_snapshot() {
+        let output = MarketInfoOutput {
+            symbol: "BTC".into(),
+            mid_price: "97250.5".into(),
+            best_bid: Some("97250".into()),
+            best_ask: Some("97251".into()),
+            spread: Some("1".into()),
+            spread_bps: Some("0.1".into()),
+            volume_24h: Some("1250000000".into()),
+            volume_24h_base: Some("12850.4".into()),
+            change_24h_pct: Some("-1.25".into()),
+            high_24h: Some("99100".into()),
+            low_24h: Some("96400".into()),
+            open_interest: Some("25000.5".into()),
+            open_interest_usd: Some("2431298625".into()),
+            mark_price: Some("97252".into()),
+            index_price: None,
+            oracle_price: Some("97240".into()),
+            premium: Some("0.00012".into()),
+            premium_bps: Some("1.2".into()),
+            funding_rate: Some("0.0000125".into()),
+            funding_apr_pct: Some("10.95".into()),
+            next_funding_secs: Some(2525),
+            max_leverage: None,
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"checks\""));
-        assert!(json.contains("\"status\":\"ok\""));
-        assert!(json.contains("\"status\":\"fail\""));
-        assert!(json.contains("\"fix\""));
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["next_funding_secs"], 2525);
+        assert_eq!(json["high_24h"], "99100");
+        assert!(json["max_leverage"].is_null());
+        assert_eq!(
+            output.table_string(&RenderContext::default()),
+            "\
+┌─────────────────────────────────────────────┐
+│ BTC — Market Info                           │
+├─────────────────┬───────────────────────────┤
+│ Mid Price       │ $97250.5                  │
+│ Best Bid        │ $97250                    │
+│ Best Ask        │ $97251                    │
+│ Spread          │ $1 (0.1 bps)              │
+├─────────────────┼───────────────────────────┤
+│ 24h Volume      │ $1250000000               │
+│ 24h Change      │ -1.25%                    │
+│ 24h High        │ $99100                    │
+│ 24h Low         │ $96400                    │
+├─────────────────┼───────────────────────────┤
+│ Mark Price      │ $97252                    │
+│ Oracle Price    │ $97240                    │
+│ Premium         │ 1.2 bps                   │
+│ Funding (pred.) │ 0.0000125 (10.95% APR)    │
+│ Next Funding    │ 42m 05s                   │
+├─────────────────┼───────────────────────────┤
+│ Open Interest   │ 25000.5 BTC ($2431298625) │
+│ Max Leverage    │ —                         │
+└─────────────────┴───────────────────────────┘
+"
+        );
     }
 
     #[test]
-    fn test_json_pretty_format() {
-        let output = StatusOutput {
-            profile: "default".into(),
-            address: "0x1234".into(),
-            network: "Mainnet".into(),
-            modules: vec!["hyperliquid".into()],
-            balances: vec![],
-            account_value: Some("10000.00".into()),
-            margin_used: Some("500.00".into()),
-            net_position: Some("2500.00".into()),
-            withdrawable: Some("9500.00".into()),
-            positions: vec![],
-            open_orders: 0,
+    fn test_market_summary_output() {
+        let row = |symbol: &str, size: Option<&str>| WatchRow {
+            symbol: symbol.into(),
+            price: Some("3000".into()),
+            change_24h_pct: Some("-2.5".into()),
+            funding_rate: Some("0.0000125".into()),
+            open_interest_usd: Some("1000000".into()),
+            oi_change_24h_pct: None,
+            position_size: size.map(str::to_string),
         };
-        let pretty = serde_json::to_string_pretty(&output).unwrap();
-        assert!(pretty.contains('\n'));
-        assert!(pretty.contains("  "));
+        let output = MarketSummaryOutput {
+            markets: 2,
+            volume_24h: "13500".into(),
+            advancing: 1,
+            declining: 1,
+            sections: vec!["losers".into()],
+            gainers: None,
+            losers: Some(vec![SummaryRow {
+                symbol: "ETH".into(),
+                price: Some("3000".into()),
+                change_24h_pct: Some("-2.5".into()),
+                volume_24h: Some("4500".into()),
+                funding_rate: None,
+                open_interest_usd: None,
+                oi_change_24h_pct: None,
+            }]),
+            volume: None,
+            funding: None,
+            oi: None,
+            watchlist: Some(WatchlistSummary {
+                coins: vec![row("ETH", Some("-0.5"))],
+                missing: vec!["FOO".into()],
+                unwatched_positions: vec![row("DOGE", Some("100"))],
+                positions_error: None,
+            }),
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert!(json.get("gainers").is_none());
+        assert_eq!(json["losers"][0]["symbol"], "ETH");
+        assert_eq!(json["watchlist"]["coins"][0]["position_size"], "-0.5");
+
+        let table = output.table_string(&RenderContext::default());
+        assert!(table.contains("Top Losers"));
+        assert!(!table.contains("Top Gainers"));
+        assert!(table.contains("Not listed on Hyperliquid: FOO"));
+        assert!(table.contains("UNWATCHED POSITIONS"));
+        assert!(table.contains("-2.50%"));
     }
 
     #[test]
-    fn test_price_output_serializes() {
-        let output = PriceOutput {
-            prices: vec![
-                PriceRow {
-                    coin: "BTC".into(),
-                    mid_price: "105234.50".into(),
-                    protocol: "hyperliquid".into(),
-                },
-                PriceRow {
-                    coin: "ETH".into(),
-                    mid_price: "3521.25".into(),
-                    protocol: "hyperliquid".into(),
-                },
-            ],
+    fn test_audit_outputs() {
+        let entry = AuditRow {
+            id: 7,
+            time: "2026-10-16 09:30:00".into(),
+            protocol: "hyperliquid".into(),
+            action_type: "order".into(),
+            coin: Some("ETH".into()),
+            status: "rejected".into(),
+            nonce: Some(1_760_607_000_000),
+            signer: "0x1111111111111111111111111111111111111111".into(),
+            params_hash: "0xabc".into(),
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"symbol\":\"BTC\""));
-        assert!(json.contains("\"price\":\"105234.50\""));
+        let list = AuditListOutput {
+            entries: vec![entry.clone()],
+            total: 1,
+        };
+        let table = list.table_string(&RenderContext::default());
+        assert!(
+            table.contains("│ 7 │ 2026-10-16 09:30:00 │ order │ ETH  │ rejected │ 1760607000000 │")
+        );
+        assert_eq!(
+            serde_json::to_value(&list).unwrap()["entries"][0]["type"],
+            "order"
+        );
+
+        let show = AuditEntryOutput {
+            entry,
+            request: serde_json::json!({"action": {"type": "order"}, "nonce": 1_760_607_000_000u64}),
+            response: Some(serde_json::json!({"status": "err", "response": "bad"})),
+            completed: Some("2026-10-16 09:30:01".into()),
+        };
+        let json = serde_json::to_value(&show).unwrap();
+        assert_eq!(json["id"], 7);
+        assert_eq!(json["request"]["action"]["type"], "order");
+        assert_eq!(json["response"]["status"], "err");
+        let table = show.table_string(&RenderContext::default());
+        assert!(table.contains("AUDIT #7"));
+        assert!(table.contains("Request:\n{\n  \"action\""));
     }
 
     #[test]
-    fn test_markets_output_serializes() {
-        let output = MarketsOutput {
-            market_type: "perp".into(),
-            markets: vec![MarketRow {
-                name: "ETH".into(),
-                index: 1,
-                max_leverage: 50,
-                sz_decimals: 4,
+    fn test_journal_table_snapshot() {
+        let output = JournalOutput {
+            entries: vec![JournalRow {
+                id: 3,
+                coin: Some("BTC".into()),
+                time: "2026-02-24 08:00:00".into(),
+                status: "pending".into(),
+                fill_hash: None,
+                oid: None,
+                note: "fade the FOMC spike".into(),
+                tags: vec!["macro".into(), "fade".into()],
             }],
+            total: 1,
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"max_leverage\":50"));
+        assert_eq!(
+            output.table_string(&RenderContext::default()),
+            "\
+┌───┬─────────────────────┬──────┬────────────┬─────────┬─────────────────────┐
+│ # │ Time                │ Coin │ Tags       │ Status  │ Note                │
+├───┼─────────────────────┼──────┼────────────┼─────────┼─────────────────────┤
+│ 3 │ 2026-02-24 08:00:00 │ BTC  │ macro,fade │ pending │ fade the FOMC spike │
+└───┴─────────────────────┴──────┴────────────┴─────────┴─────────────────────┘
+Total: 1 notes
+"
+        );
+        let added = JournalAddOutput {
+            entry: output.entries[0].clone(),
+        };
+        assert_eq!(
+            added.table_string(&RenderContext::default()),
+            "✓ Note #3 saved; links to the BTC fill nearest 2026-02-24 08:00:00 at next sync\n"
+        );
+        assert!(added.side_effect_only());
     }
 
     #[test]
-    fn test_candles_output_serializes() {
-        let output = CandlesOutput {
-            coin: "BTC".into(),
-            interval: "1h".into(),
-            candles: vec![CandleRow {
-                time: "2026-02-24 08:00:00".into(),
-                open: "105000".into(),
-                high: "105500".into(),
-                low: "104800".into(),
-                close: "105300".into(),
-                volume: "1234.5".into(),
-                trades: 456,
+    fn test_address_book_table() {
+        let row = |name: &str, address: &str, decimals, source: &str| AddressBookRow {
+            chain: "base".into(),
+            name: name.into(),
+            address: address.into(),
+            decimals,
+            source: source.into(),
+        };
+        let output = AddressBookOutput {
+            chain: Some("base".into()),
+            tokens: vec![
+                row("AERO", "0x9401", None, "user"),
+                row("USDC", "0x8335", Some(6), "built-in"),
+            ],
+            total: 2,
+        };
+        assert_eq!(
+            output.table_string(&RenderContext::plain()),
+            "\
++-------+------+---------+----------+----------+
+| Chain | Name | Address | Decimals | Source   |
++-------+------+---------+----------+----------+
+| base  | AERO | 0x9401  |        - | user     |
+| base  | USDC | 0x8335  |        6 | built-in |
++-------+------+---------+----------+----------+
+Total: 2 tokens
+"
+        );
+        let json = serde_json::to_value(&output).unwrap();
+        assert!(json["tokens"][0]["decimals"].is_null());
+
+        let added = AddressChangeOutput {
+            action: "added".into(),
+            entry: output.tokens[1].clone(),
+        };
+        assert_eq!(
+            added.table_string(&RenderContext::plain()),
+            "OK Added USDC on base -> 0x8335 (6 decimals)\n"
+        );
+        assert!(added.side_effect_only());
+    }
+
+    #[test]
+    fn test_allowance_outputs() {
+        let allowance = AllowanceOutput {
+            chain: "base".into(),
+            token: "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913".into(),
+            token_name: Some("USDC".into()),
+            decimals: Some(6),
+            owner: "0xc0a1".into(),
+            spender: "0x0000000000001fF3684f28c67538d4D072C22734".into(),
+            spender_name: Some("0x AllowanceHolder".into()),
+            allowance: "250000000".into(),
+            allowance_tokens: Some("250".into()),
+            unlimited: false,
+        };
+        let table = allowance.table_string(&RenderContext::plain());
+        assert!(table.contains("| Allowance | 250 USDC"));
+        assert!(table.contains("0x AllowanceHolder (0x0000000000001fF3684f28c67538d4D072C22734)"));
+
+        let approved = ApprovalOutput {
+            action: "approved".into(),
+            tx_hash: "0xabc".into(),
+            allowance: AllowanceOutput {
+                allowance:
+                    "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+                        .into(),
+                allowance_tokens: None,
+                unlimited: true,
+                ..allowance.clone()
+            },
+        };
+        assert_eq!(
+            approved.table_string(&RenderContext::plain()),
+            "OK Approved 0x AllowanceHolder to spend unlimited on base -> tx 0xabc\n"
+        );
+        let json = serde_json::to_value(&approved).unwrap();
+        assert_eq!(json["tx_hash"], "0xabc");
+        assert_eq!(json["unlimited"], true);
+        assert_eq!(json["token_name"], "USDC");
+
+        let revoked = ApprovalOutput {
+            action: "revoked".into(),
+            tx_hash: "0xdef".into(),
+            allowance: AllowanceOutput {
+                allowance: "0".into(),
+                allowance_tokens: Some("0".into()),
+                ..allowance
+            },
+        };
+        assert_eq!(
+            revoked.table_string(&RenderContext::plain()),
+            "OK Revoked USDC allowance for 0x AllowanceHolder on base -> tx 0xdef\n"
+        );
+    }
+
+    #[test]
+    fn test_swap_quote_derived_fields_and_table() {
+        let output = SwapQuoteOutput {
+            chain: "base".into(),
+            sell_token: "0x8335".into(),
+            sell_token_name: Some("USDC".into()),
+            sell_decimals: Some(6),
+            buy_token: "0x4200".into(),
+            buy_symbol: Some("WETH".into()),
+            buy_decimals: Some(18),
+            sell_amount: "1000000000".into(),
+            buy_amount: "285000000000000000".into(),
+            min_buy_amount: Some("282150000000000000".into()),
+            sell_usd_price: Some("1".into()),
+            buy_usd_price: Some("3500".into()),
+            slippage_bps: Some(100),
+            gas: Some(150_000),
+            gas_price_wei: Some("20000000".into()),
+            native_symbol: Some("ETH".into()),
+            native_usd_price: Some("3500".into()),
+            route: vec![
+                RouteFillRow {
+                    source: "Uniswap_V3".into(),
+                    from: "USDC".into(),
+                    to: "WETH".into(),
+                    proportion_pct: "70".into(),
+                },
+                RouteFillRow {
+                    source: "Aerodrome".into(),
+                    from: "USDC".into(),
+                    to: "WETH".into(),
+                    proportion_pct: "30".into(),
+                },
+            ],
+            ..Default::default()
+        }
+        .with_derived();
+
+        assert_eq!(output.sell_amount_tokens.as_deref(), Some("1000"));
+        assert_eq!(output.min_buy_amount_tokens.as_deref(), Some("0.28215"));
+        assert_eq!(output.price.as_deref(), Some("0.000285"));
+        assert_eq!(output.buy_value_usd.as_deref(), Some("997.5"));
+        assert_eq!(output.price_impact_pct.as_deref(), Some("0.25"));
+        // 150k gas at 0.02 gwei = 0.000003 ETH
+        assert_eq!(output.gas_price_gwei.as_deref(), Some("0.02"));
+        assert_eq!(output.gas_cost_native.as_deref(), Some("0.000003"));
+        assert_eq!(output.gas_cost_usd.as_deref(), Some("0.01"));
+
+        assert_eq!(
+            output.table_string(&RenderContext::plain()),
+            "\
++------------------------------------------------+
+| 0x QUOTE - base                                |
++--------------+---------------------------------+
+| Sell         | 1000 USDC ($1000.00)            |
+| Buy          | 0.285 WETH ($997.50)            |
+| Min Buy      | 0.28215 WETH (100 bps slippage) |
+| Price        | 0.000285 WETH per USDC          |
+| Price Impact | 0.25% (incl. fees)              |
++--------------+---------------------------------+
+| Gas          | 150000 @ 0.02 gwei              |
+| Gas Cost     | 0.000003 ETH ($0.01)            |
++--------------+---------------------------------+
++----------------------------------+
+| ROUTE                            |
++------------+------+------+-------+
+| Source     | From | To   | Share |
++------------+------+------+-------+
+| Uniswap_V3 | USDC | WETH |   70% |
+| Aerodrome  | USDC | WETH |   30% |
++------------+------+------+-------+
+"
+        );
+
+        // Unknown decimals leave token amounts empty instead of guessing
+        let raw = SwapQuoteOutput {
+            sell_amount: "1000".into(),
+            buy_amount: "5".into(),
+            ..Default::default()
+        }
+        .with_derived();
+        assert!(raw.sell_amount_tokens.is_none() && raw.price.is_none());
+        assert!(raw.price_impact_pct.is_none() && raw.gas_cost_usd.is_none());
+    }
+
+    #[test]
+    fn test_order_history_output_serializes() {
+        let output = OrderHistoryOutput {
+            orders: vec![OrderHistoryRow {
+                coin: "BTC".into(),
+                side: "Sell".into(),
+                size: "0.01".into(),
+                price: "105000.00".into(),
+                oid: 42,
+                status: "filled".into(),
+                order_type: "Limit".into(),
+                time: "2026-02-24 09:00:00".into(),
+                filled: "0.01".into(),
+                avg_price: Some("105010.5".into()),
+                status_updated: Some("2026-02-24 10:00:00".into()),
             }],
+            total: 1,
         };
         let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"trades\":456"));
-        assert!(json.contains("\"interval\":\"1h\""));
+        assert!(json.contains("\"oid\":42"));
+        assert!(json.contains("\"status\":\"filled\""));
+        assert!(json.contains("\"avg_price\":\"105010.5\""));
     }
 
     #[test]
-    fn test_spot_balance_output_serializes() {
-        let output = SpotBalanceOutput {
-            balances: vec![SpotBalanceRow {
-                coin: "USDC".into(),
-                total: "1000.00".into(),
-                held: "50.00".into(),
-                available: "950.00".into(),
+    fn test_pnl_summary_output_serializes() {
+        let output = PnlSummaryOutput {
+            total_pnl: "500.00".into(),
+            total_fees: "25.00".into(),
+            net_pnl: "475.00".into(),
+            trade_count: 10,
+            win_count: 7,
+            loss_count: 3,
+            win_rate: "70.0%".into(),
+            by_coin: vec![PnlByCoinRow {
+                coin: "ETH".into(),
+                pnl: "300.00".into(),
+                fees: "15.00".into(),
+                trades: 6,
             }],
+            net_deposits: Some("1200.00".into()),
+            swaps: None,
         };
         let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"coin\":\"USDC\""));
-        assert!(json.contains("\"available\":\"950.00\""));
+        assert!(json.contains("\"net_pnl\":\"475.00\""));
+        assert!(json.contains("\"net_deposits\":\"1200.00\""));
+        assert!(output
+            .table_string(&RenderContext::plain())
+            .contains("| Net Deposits | $1200.00 |"));
+        assert!(json.contains("\"win_rate\":\"70.0%\""));
+    }
+
+    // ─── Table snapshots ────────────────────────────────────────────
+
+    fn snapshot_position(coin: &str, size: &str, entry: &str, upnl: &str) -> PositionRow {
+        PositionRow {
+            coin: coin.into(),
+            side: "long".into(),
+            size: size.into(),
+            entry_price: Some(entry.into()),
+            mark_price: None,
+            unrealized_pnl: Some(upnl.into()),
+            notional: None,
+            pnl_pct: None,
+            liquidation_price: None,
+            leverage: None,
+            margin_mode: None,
+            protocol: "hyperliquid".into(),
+        }
     }
 
     #[test]
-    fn test_spot_order_output_serializes() {
-        let output = SpotOrderOutput {
-            market: "PURR/USDC".into(),
-            side: "BUY".into(),
-            oid: 42,
-            status: "filled".into(),
-            total_sz: Some("100.0".into()),
-            avg_px: Some("0.50".into()),
+    fn test_status_table_snapshot() {
+        let output = StatusOutput {
+            profile: "main".into(),
+            address: "0xe8Ecb4D59690d1E1748217e1b56B73D51A8Bc94C".into(),
+            env: Some("testnet".into()),
+            network: "Testnet".into(),
+            modules: vec!["hyperliquid".into(), "zero_x".into()],
+            balances: vec![],
+            account_value: Some("10000.00".into()),
+            margin_used: Some("500.00".into()),
+            net_position: None,
+            withdrawable: Some("9500.00".into()),
+            positions: vec![
+                snapshot_position("BTC", "0.1", "104250.0", "12.50"),
+                snapshot_position("kPEPE", "1000000", "0.0123", "-3.2"),
+            ],
+            open_orders: 2,
+            total_value_usd: None,
+            protocols: vec![],
+            warnings: vec!["ignored in the table".into()],
+            diff: None,
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"market\":\"PURR/USDC\""));
-        assert!(json.contains("\"oid\":42"));
-        assert!(json.contains("\"status\":\"filled\""));
+        assert_eq!(
+            output.table_string(&RenderContext::default()),
+            "\
+┌───────────────────────────────────────────────────────────┐
+│ ACCOUNT SUMMARY                                           │
+├──────────────┬────────────────────────────────────────────┤
+│ Profile      │ main                                       │
+│ Address      │ 0xe8Ecb4D59690d1E1748217e1b56B73D51A8Bc94C │
+│ Environment  │ testnet                                    │
+│ Network      │ Testnet                                    │
+│ Modules      │ hyperliquid, zero_x                        │
+│ Account Val  │ 10000.00                                   │
+│ Margin Used  │ 500.00                                     │
+│ Net Pos      │ —                                          │
+│ Withdrawable │ 9500.00                                    │
+│ Open Orders  │ 2                                          │
+└──────────────┴────────────────────────────────────────────┘
+┌───────┬─────────┬──────────┬───────┐
+│ Coin  │    Size │    Entry │  uPnL │
+├───────┼─────────┼──────────┼───────┤
+│ BTC   │     0.1 │   104250 │ 12.50 │
+│ kPEPE │ 1000000 │ 0.012300 │ -3.20 │
+└───────┴─────────┴──────────┴───────┘
+"
+        );
     }
 
     #[test]
-    fn test_spot_transfer_output_serializes() {
-        let output = SpotTransferOutput {
-            direction: "perps → spot".into(),
-            token: "USDC".into(),
-            amount: "500.00".into(),
+    fn test_status_diff_table_and_json() {
+        let mut output = StatusOutput {
+            profile: "main".into(),
+            address: "0x1234".into(),
+            env: None,
+            network: "Mainnet".into(),
+            modules: vec!["hyperliquid".into()],
+            balances: vec![],
+            account_value: Some("5123.45".into()),
+            margin_used: None,
+            net_position: None,
+            withdrawable: None,
+            positions: vec![],
+            open_orders: 1,
+            total_value_usd: None,
+            protocols: vec![],
+            warnings: vec![],
+            diff: Some(StatusDiff {
+                baseline_ms: None,
+                account_value_change: None,
+                open_orders_change: 0,
+                positions: vec![],
+            }),
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"direction\":\"perps → spot\""));
-        assert!(json.contains("\"token\":\"USDC\""));
+        let table = output.table_string(&RenderContext::default());
+        assert!(table.ends_with(
+            "No open positions.\n\nNo earlier snapshot to compare with; \
+             this run is the baseline for the next --diff.\n"
+        ));
+
+        let row =
+            |symbol: &str, change: &str, before: Option<&str>, after: Option<&str>, delta: &str| {
+                PositionDiffRow {
+                    symbol: symbol.into(),
+                    protocol: "hyperliquid".into(),
+                    change: change.into(),
+                    size_before: before.map(Into::into),
+                    size_after: after.map(Into::into),
+                    size_change: delta.into(),
+                }
+            };
+        output.diff = Some(StatusDiff {
+            // 2025-10-14 12:00:00 UTC
+            baseline_ms: Some(1_760_443_200_000),
+            account_value_change: Some("123.45".into()),
+            open_orders_change: -2,
+            positions: vec![
+                row("ETH", "resized", Some("-2"), Some("-3"), "-1"),
+                row("HYPE", "new", None, Some("50"), "50"),
+                row("BTC", "closed", Some("0.1"), None, "-0.1"),
+            ],
+        });
+        let table = output.table_string(&RenderContext::default());
+        let diff = &table[table.find("No open positions.\n").unwrap()..];
+        assert_eq!(
+            diff,
+            "\
+No open positions.
+
+┌───────────────────────────────────────┐
+│ CHANGES SINCE 2025-10-14 12:00:00 UTC │
+├─────────────┬─────────────────────────┤
+│ Account Val │ +123.45                 │
+│ Open Orders │ -2                      │
+└─────────────┴─────────────────────────┘
+┌───┬──────┬────────┬───────┬────────┐
+│   │ Coin │ Before │ After │ Change │
+├───┼──────┼────────┼───────┼────────┤
+│ ~ │ ETH  │     -2 │    -3 │     -1 │
+│ + │ HYPE │      — │    50 │    +50 │
+│ - │ BTC  │    0.1 │     — │   -0.1 │
+└───┴──────┴────────┴───────┴────────┘
+"
+        );
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["diff"]["open_orders_change"], -2);
+        assert_eq!(json["diff"]["positions"][2]["change"], "closed");
+        assert!(json["diff"]["positions"][2]["size_after"].is_null());
+        output.diff = None;
+        assert!(serde_json::to_value(&output).unwrap().get("diff").is_none());
     }
 
     #[test]
-    fn test_funding_output_serializes() {
-        let output = FundingOutput {
-            coin: "ETH".into(),
-            rates: vec![FundingRow {
-                time: "2026-02-24 08:00:00".into(),
-                coin: "ETH".into(),
-                rate: "0.00012".into(),
-                premium: "0.00005".into(),
-            }],
+    fn test_positions_table_snapshot() {
+        let row = |coin: &str, size: &str, mark: &str, notional: &str, upnl: &str, pct: &str| {
+            PositionRow {
+                mark_price: Some(mark.into()),
+                notional: Some(notional.into()),
+                pnl_pct: Some(pct.into()),
+                ..snapshot_position(coin, size, "100", upnl)
+            }
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"rate\":\"0.00012\""));
+        let output = PositionsOutput {
+            positions: vec![
+                row("ETH", "2", "110", "220.00", "20.00", "10.00"),
+                row("SOL", "-5", "104", "520.00", "-20.00", "-4.00"),
+            ],
+            total_notional: "740.00".into(),
+            total_unrealized_pnl: "0.00".into(),
+            warnings: vec![],
+        };
+        assert_eq!(
+            output.table_string(&RenderContext::default()),
+            "\
+┌───────┬──────┬───────┬──────┬──────────┬────────┬────────┐
+│ Coin  │ Size │ Entry │ Mark │ Notional │   uPnL │  PnL % │
+├───────┼──────┼───────┼──────┼──────────┼────────┼────────┤
+│ ETH   │    2 │   100 │  110 │   220.00 │  20.00 │ 10.00% │
+│ SOL   │   -5 │   100 │  104 │   520.00 │ -20.00 │ -4.00% │
+├───────┼──────┼───────┼──────┼──────────┼────────┼────────┤
+│ Total │      │       │      │   740.00 │   0.00 │        │
+└───────┴──────┴───────┴──────┴──────────┴────────┴────────┘
+"
+        );
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["positions"][1]["notional"], "520.00");
+        assert_eq!(json["positions"][1]["pnl_pct"], "-4.00");
+        assert_eq!(json["total_notional"], "740.00");
     }
 
     #[test]
-    fn test_vault_details_output_serializes() {
+    fn test_vault_table_snapshot() {
         let output = VaultDetailsOutput {
-            name: "Test Vault".into(),
-            address: "0xabc".into(),
-            leader: "0xdef".into(),
-            description: "A test vault".into(),
+            name: "Hyperliquidity Provider (HLP)".into(),
+            address: "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303".into(),
+            leader: "0x677d831aef5328190852e24f13c46cac05f984e7".into(),
+            description: "This community-owned vault provides liquidity to Hyperliquid through \
+                          multiple market making strategies, performs liquidations, and accrues \
+                          platform fees."
+                .into(),
             apr: "12.50".into(),
             leader_fraction: "10.00".into(),
-            leader_commission: "5.00".into(),
+            leader_commission: "0.00".into(),
             max_distributable: "50000.00".into(),
             max_withdrawable: "40000.00".into(),
-            follower_count: 42,
+            follower_count: 2,
             is_closed: false,
             allow_deposits: true,
-            followers: vec![VaultFollowerRow {
-                user: "0x1234".into(),
-                equity: "10000.00".into(),
-                pnl: "500.00".into(),
-                days_following: 30,
-            }],
-            user_state: Some(VaultUserStateRow {
-                equity: "5000.00".into(),
-                pnl: "250.00".into(),
-                all_time_pnl: "1000.00".into(),
-                days_following: 60,
-                lockup_until: Some("2026-03-01".into()),
-            }),
-        };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"name\":\"Test Vault\""));
-        assert!(json.contains("\"follower_count\":42"));
-        assert!(json.contains("\"allow_deposits\":true"));
-        assert!(json.contains("\"all_time_pnl\":\"1000.00\""));
-    }
-
-    #[test]
-    fn test_vault_deposits_output_serializes() {
-        let output = VaultDepositsOutput {
-            deposits: vec![VaultDepositRow {
-                vault_address: "0xabc".into(),
-                equity: "5000.00".into(),
-                locked_until: Some("2026-03-01".into()),
-            }],
-            total_equity: "5000.00".into(),
+            followers: vec![
+                VaultFollowerRow {
+                    user: "0x1234567890abcdef1234567890abcdef12345678".into(),
+                    equity: "10000.00".into(),
+                    pnl: "500.00".into(),
+                    days_following: 30,
+                },
+                VaultFollowerRow {
+                    user: "0xabc".into(),
+                    equity: "25.10".into(),
+                    pnl: "-1.20".into(),
+                    days_following: 365,
+                },
+            ],
+            user_state: None,
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"total_equity\":\"5000.00\""));
-        assert!(json.contains("\"vault_address\":\"0xabc\""));
+        assert_eq!(
+            output.table_string(&RenderContext::default()),
+            "\
+┌────────────────────────────────────────────────────────────┐
+│ VAULT DETAILS                                              │
+├───────────────┬────────────────────────────────────────────┤
+│ Name          │ Hyperliquidity Provider (HLP)              │
+│ Address       │ 0xdfc24b077bc1425ad1dea75bcb6f8158e10df303 │
+│ Leader        │ 0x677d831aef5328190852e24f13c46cac05f984e7 │
+│ APR           │ 12.50%                                     │
+│ Leader Frac   │ 10.00%                                     │
+│ Commission    │ 0.00%                                      │
+│ Distributable │ $50000.00                                  │
+│ Withdrawable  │ $40000.00                                  │
+│ Followers     │ 2                                          │
+│ Closed        │ No                                         │
+│ Deposits      │ Allowed                                    │
+└───────────────┴────────────────────────────────────────────┘
+┌──────────────────────────────────────────────────────────────────────────┐
+│ This community-owned vault provides liquidity to Hyperliquid through mu… │
+└──────────────────────────────────────────────────────────────────────────┘
+┌─────────────────────────────────────────────────┐
+│ TOP FOLLOWERS                                   │
+├──────────────────────┬──────────┬────────┬──────┤
+│ User                 │   Equity │    PnL │ Days │
+├──────────────────────┼──────────┼────────┼──────┤
+│ 0x1234567890abcdef1… │ 10000.00 │ 500.00 │   30 │
+│ 0xabc                │    25.10 │  -1.20 │  365 │
+└──────────────────────┴──────────┴────────┴──────┘
+"
+        );
     }
 
     #[test]
-    fn test_subaccounts_output_serializes() {
+    fn test_subaccounts_table_snapshot() {
+        assert_eq!(
+            SubAccountsOutput {
+                subaccounts: vec![]
+            }
+            .table_string(&RenderContext::default()),
+            "No subaccounts.\n"
+        );
         let output = SubAccountsOutput {
             subaccounts: vec![SubAccountRow {
                 name: "bot-1".into(),
@@ -1593,18 +9553,7 @@ mod tests {
                 total_position: "5000.00".into(),
                 margin_used: "1000.00".into(),
                 withdrawable: "9000.00".into(),
-                positions: vec![PositionRow {
-                    coin: "ETH".into(),
-                    side: "long".into(),
-                    size: "1.5".into(),
-                    entry_price: Some("3500.00".into()),
-                    mark_price: None,
-                    unrealized_pnl: Some("100.00".into()),
-                    liquidation_price: None,
-                    leverage: None,
-                    margin_mode: None,
-                    protocol: "hyperliquid".into(),
-                }],
+                positions: vec![snapshot_position("ETH", "1.5", "3500.00", "100.00")],
                 spot_balances: vec![SpotBalanceRow {
                     coin: "USDC".into(),
                     total: "500.00".into(),
@@ -1613,47 +9562,76 @@ mod tests {
                 }],
             }],
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"name\":\"bot-1\""));
-        assert!(json.contains("\"account_value\":\"10000.00\""));
-        assert!(json.contains("\"symbol\":\"ETH\""));
-    }
-
-    #[test]
-    fn test_agent_approve_output_serializes() {
-        let output = AgentApproveOutput {
-            agent_address: "0xagent".into(),
-            agent_name: "my-bot".into(),
-            status: "approved".into(),
-        };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"agent_address\":\"0xagent\""));
-        assert!(json.contains("\"status\":\"approved\""));
+        assert_eq!(
+            output.table_string(&RenderContext::default()),
+            "\
+┌──────────────────────────┐
+│ SUBACCOUNT: bot-1        │
+├──────────────┬───────────┤
+│ Address      │ 0x5678    │
+│ Account Val  │ $10000.00 │
+│ Total Pos    │ $5000.00  │
+│ Margin Used  │ $1000.00  │
+│ Withdrawable │ $9000.00  │
+└──────────────┴───────────┘
+┌──────┬──────┬────────┬────────┐
+│ Coin │ Size │  Entry │   uPnL │
+├──────┼──────┼────────┼────────┤
+│ ETH  │  1.5 │ 3500.0 │ 100.00 │
+└──────┴──────┴────────┴────────┘
+┌───────────────┐
+│ SPOT BALANCES │
+├──────┬────────┤
+│ Coin │  Total │
+├──────┼────────┤
+│ USDC │ 500.00 │
+└──────┴────────┘
+
+Total subaccounts: 1
+"
+        );
     }
 
     #[test]
-    fn test_trade_history_output_serializes() {
-        let output = TradeHistoryOutput {
+    fn test_history_table_snapshots() {
+        let trades = TradeHistoryOutput {
             trades: vec![TradeHistoryRow {
                 protocol: "hyperliquid".into(),
                 coin: "ETH".into(),
                 side: "Buy".into(),
                 size: "0.5".into(),
                 price: "3500.00".into(),
-                pnl: "100.00".into(),
+                pnl: "-100.00".into(),
                 fee: "1.75".into(),
                 time: "2026-02-24 08:00:00".into(),
+                hash: "0xabc".into(),
+                has_note: false,
+                notes: vec![],
             }],
             total: 1,
+            with_notes: false,
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"coin\":\"ETH\""));
-        assert!(json.contains("\"total\":1"));
-    }
+        assert_eq!(
+            trades.table_string(&RenderContext::default()),
+            "\
+┌──────┬──────┬──────┬────────┬─────────┬──────┬─────────────────────┐
+│ Coin │ Side │ Size │  Price │     PnL │  Fee │                Time │
+├──────┼──────┼──────┼────────┼─────────┼──────┼─────────────────────┤
+│ ETH  │ Buy  │  0.5 │ 3500.0 │ -100.00 │ 1.75 │ 2026-02-24 08:00:00 │
+└──────┴──────┴──────┴────────┴─────────┴──────┴─────────────────────┘
+Total: 1 trades
+"
+        );
+        // Colors only wrap the PnL cell; widths are unchanged
+        let colored = RenderContext {
+            color: true,
+            ..RenderContext::default()
+        };
+        assert!(trades
+            .table_string(&colored)
+            .contains("\x1b[31m-100.00\x1b[0m"));
 
-    #[test]
-    fn test_order_history_output_serializes() {
-        let output = OrderHistoryOutput {
+        let orders = OrderHistoryOutput {
             orders: vec![OrderHistoryRow {
                 coin: "BTC".into(),
                 side: "Sell".into(),
@@ -1663,17 +9641,25 @@ mod tests {
                 status: "filled".into(),
                 order_type: "Limit".into(),
                 time: "2026-02-24 09:00:00".into(),
+                filled: "0.01".into(),
+                avg_price: Some("105010.5".into()),
+                status_updated: Some("2026-02-24 10:00:00".into()),
             }],
             total: 1,
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"oid\":42"));
-        assert!(json.contains("\"status\":\"filled\""));
-    }
+        assert_eq!(
+            orders.table_string(&RenderContext::default()),
+            "\
+┌──────┬──────┬──────┬────────┬────────┬────────┬─────┬────────┬─────────────────────┐
+│ Coin │ Side │ Size │  Price │ Filled │ Avg Px │ OID │ Status │                Time │
+├──────┼──────┼──────┼────────┼────────┼────────┼─────┼────────┼─────────────────────┤
+│ BTC  │ Sell │ 0.01 │ 105000 │   0.01 │ 105010 │  42 │ filled │ 2026-02-24 09:00:00 │
+└──────┴──────┴──────┴────────┴────────┴────────┴─────┴────────┴─────────────────────┘
+Total: 1 orders
+"
+        );
 
-    #[test]
-    fn test_pnl_summary_output_serializes() {
-        let output = PnlSummaryOutput {
+        let pnl = PnlSummaryOutput {
             total_pnl: "500.00".into(),
             total_fees: "25.00".into(),
             net_pnl: "475.00".into(),
@@ -1687,10 +9673,163 @@ mod tests {
                 fees: "15.00".into(),
                 trades: 6,
             }],
+            net_deposits: None,
+            swaps: None,
         };
-        let json = serde_json::to_string(&output).unwrap();
-        assert!(json.contains("\"net_pnl\":\"475.00\""));
-        assert!(json.contains("\"win_rate\":\"70.0%\""));
+        assert_eq!(
+            pnl.table_string(&RenderContext::default()),
+            "\
+┌──────────────────────┐
+│ PNL SUMMARY          │
+├────────────┬─────────┤
+│ Total PnL  │ $500.00 │
+│ Total Fees │ $25.00  │
+│ Net PnL    │ $475.00 │
+│ Trades     │ 10      │
+│ Win/Loss   │ 7 / 3   │
+│ Win Rate   │ 70.0%   │
+└────────────┴─────────┘
+┌────────────────────────────────┐
+│ BREAKDOWN BY COIN              │
+├──────┬────────┬───────┬────────┤
+│ Coin │    PnL │  Fees │ Trades │
+├──────┼────────┼───────┼────────┤
+│ ETH  │ 300.00 │ 15.00 │      6 │
+└──────┴────────┴───────┴────────┘
+"
+        );
+    }
+
+    #[test]
+    fn test_plain_rendering_is_ascii() {
+        let ctx = RenderContext::plain();
+        let status = StatusOutput {
+            profile: "main".into(),
+            address: "0x1234".into(),
+            env: None,
+            network: "Mainnet".into(),
+            modules: vec![],
+            balances: vec![],
+            account_value: None,
+            margin_used: None,
+            net_position: None,
+            withdrawable: None,
+            positions: vec![snapshot_position("ETH", "0.5", "3500.00", "-2.00")],
+            open_orders: 0,
+            total_value_usd: Some("1200".into()),
+            protocols: vec![ProtocolSummary {
+                protocol: "evm-wallet".into(),
+                value_usd: "1200".into(),
+                balances: vec![ValuedBalanceRow {
+                    asset: "USDC".into(),
+                    chain: "base".into(),
+                    amount: "1200".into(),
+                    price_usd: None,
+                    value_usd: None,
+                }],
+                positions: 0,
+            }],
+            warnings: vec![],
+            diff: None,
+        };
+        let doctor = DoctorOutput {
+            checks: vec![
+                DoctorCheck::ok("profile", "main"),
+                DoctorCheck::fail("api_key", "Run: atlas configure system api-key <key>"),
+                DoctorCheck::ok("database", "indexes rebuilt").fixed(),
+            ],
+        };
+        let risk = RiskCalcOutput {
+            coin: "ETH".into(),
+            side: "long".into(),
+            entry_price: 3500.0,
+            size: 2.857,
+            lots: 285.7,
+            notional: 10000.0,
+            stop_loss: 3400.0,
+            take_profit: 3700.0,
+            est_liquidation: 3100.0,
+            liq_model: "isolated".into(),
+            risk_usd: 200.0,
+            risk_pct: 0.02,
+            margin: 1000.0,
+            leverage: 10,
+            warnings: vec!["max leverage exceeded".into()],
+            blocked: true,
+        };
+        let row = |coin: &str| FundingScreenRow {
+            coin: coin.into(),
+            rate: "0.0012".into(),
+            apr_pct: "1051.20".into(),
+            open_interest_usd: "2500000".into(),
+            volume_24h: "18000000".into(),
+            mark_price: "1.234".into(),
+        };
+        let funding = FundingScreenOutput {
+            sort: "apr".into(),
+            min_abs_rate: "0.0005".into(),
+            min_oi_usd: "100000".into(),
+            matched: 1,
+            positive: vec![row("WIF")],
+            negative: vec![],
+        };
+        let export = ExportOutput {
+            path: "/tmp/trades.csv".into(),
+            rows: 3,
+            format: "csv".into(),
+        };
+        let outputs: [&dyn TableDisplay; 5] = [&status, &doctor, &risk, &funding, &export];
+        for output in outputs {
+            // Every glyph the impls emit themselves goes through the context
+            let mut raw = String::new();
+            output.write_table(&mut raw, &ctx).unwrap();
+            assert!(raw.is_ascii(), "non-ASCII in plain output:\n{raw}");
+            assert!(!raw.contains('\x1b'));
+        }
+
+        // Free text from elsewhere (names, warnings) is transliterated
+        let risk = RiskCalcOutput {
+            warnings: vec!["⚠ Leverage 25x > max 20x".into()],
+            ..risk
+        };
+        let text = risk.table_string(&ctx);
+        assert!(text.is_ascii(), "{text}");
+        assert!(text.starts_with("+-"));
+        assert!(text.contains("! Leverage 25x > max 20x"));
+        assert!(text.contains("FAIL Trade BLOCKED"));
+    }
+
+    #[test]
+    fn test_quiet_skips_side_effect_confirmations_only() {
+        let cancel = CancelSingleOutput {
+            coin: "ETH".into(),
+            oid: 7,
+            status: "success".into(),
+        };
+        assert!(cancel.side_effect_only());
+        assert!(SyncOutput {
+            fills_synced: 1,
+            orders_synced: 2,
+            orders_updated: 0,
+            transfers_synced: 0,
+            funding_synced: 0,
+            full: false,
+            status: "complete".into(),
+        }
+        .side_effect_only());
+        assert!(!OrdersOutput {
+            market_type: "perp".into(),
+            orders: vec![],
+        }
+        .side_effect_only());
+        assert_eq!(
+            cancel.table_string(&RenderContext::plain()),
+            "OK Order 7 on ETH cancelled.\n"
+        );
+        assert_eq!(
+            cancel.table_string(&RenderContext::default()),
+            "✓ Order 7 on ETH cancelled.\n"
+        );
     }
 
     #[test]
@@ -1698,11 +9837,93 @@ mod tests {
         let output = SyncOutput {
             fills_synced: 50,
             orders_synced: 30,
+            orders_updated: 4,
+            transfers_synced: 2,
+            funding_synced: 120,
+            full: true,
             status: "complete".into(),
         };
         let json = serde_json::to_string(&output).unwrap();
         assert!(json.contains("\"fills_synced\":50"));
         assert!(json.contains("\"orders_synced\":30"));
+        assert!(json.contains("\"orders_updated\":4"));
+        assert!(json.contains("\"transfers_synced\":2"));
+        assert!(json.contains("\"funding_synced\":120"));
+        assert!(output
+            .table_string(&RenderContext::plain())
+            .starts_with("OK Full sync complete"));
+    }
+
+    #[test]
+    fn test_transfer_history_output() {
+        let row =
+            |kind: &str, direction: &str, usd: &str, counterparty: Option<&str>| TransferRow {
+                kind: kind.into(),
+                ledger_type: kind.into(),
+                direction: direction.into(),
+                token: "USDC".into(),
+                amount: usd.into(),
+                usd_value: Some(usd.into()),
+                fee: "0".into(),
+                counterparty: counterparty.map(str::to_string),
+                hash: "0xabc".into(),
+                time: "2026-01-01 08:00:00".into(),
+            };
+        let output = TransferHistoryOutput {
+            transfers: vec![
+                row("withdraw", "out", "200", None),
+                row(
+                    "internal",
+                    "in",
+                    "50",
+                    Some("0x2222222222222222222222222222222222222222"),
+                ),
+            ],
+            total: 2,
+            net_deposits: "-150".into(),
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["transfers"][0]["type"], "withdraw");
+        assert_eq!(json["transfers"][1]["direction"], "in");
+        assert_eq!(json["net_deposits"], "-150");
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("| -200 |"));
+        assert!(table.contains("| 0x2222...2222 |"));
+        assert!(table.contains("Total: 2 transfers, net deposits $-150"));
+
+        let empty = TransferHistoryOutput {
+            transfers: vec![],
+            total: 0,
+            net_deposits: "0".into(),
+        };
+        assert!(empty
+            .table_string(&RenderContext::plain())
+            .starts_with("No transfers cached."));
+    }
+
+    #[test]
+    fn test_import_output() {
+        let output = ImportOutput {
+            file: "trades.csv".into(),
+            protocol: "custom".into(),
+            format: "generic".into(),
+            dry_run: true,
+            imported: 12,
+            skipped: 3,
+            failed: 1,
+            errors: vec![ImportErrorRow {
+                line: 7,
+                error: "invalid side 'hold'".into(),
+            }],
+        };
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["dry_run"], true);
+        assert_eq!(json["errors"][0]["line"], 7);
+
+        let table = output.table_string(&RenderContext::plain());
+        assert!(table.contains("FAIL Line 7: invalid side 'hold'"));
+        assert!(table.contains("WARN Would import 12 fills as 'custom' (3 skipped, 1 failed)"));
     }
 
     #[test]
@@ -1719,33 +9940,48 @@ mod tests {
 
     #[test]
     fn test_render_json() {
-        let data = OrdersOutput { orders: vec![] };
+        let data = OrdersOutput {
+            market_type: "perp".into(),
+            orders: vec![],
+        };
         // Just verify it doesn't panic
         render(OutputFormat::Json, &data).unwrap();
     }
 
     #[test]
     fn test_render_json_pretty() {
-        let data = OrdersOutput { orders: vec![] };
+        let data = OrdersOutput {
+            market_type: "perp".into(),
+            orders: vec![],
+        };
         render(OutputFormat::JsonPretty, &data).unwrap();
     }
 
     #[test]
     fn test_render_table() {
-        let data = OrdersOutput { orders: vec![] };
+        let data = OrdersOutput {
+            market_type: "perp".into(),
+            orders: vec![],
+        };
         render(OutputFormat::Table, &data).unwrap();
     }
 
     #[test]
     fn test_render_json_or_returns_false_for_table() {
-        let data = OrdersOutput { orders: vec![] };
+        let data = OrdersOutput {
+            market_type: "perp".into(),
+            orders: vec![],
+        };
         let was_json = render_json_or(OutputFormat::Table, &data).unwrap();
         assert!(!was_json);
     }
 
     #[test]
     fn test_render_json_or_returns_true_for_json() {
-        let data = OrdersOutput { orders: vec![] };
+        let data = OrdersOutput {
+            market_type: "perp".into(),
+            orders: vec![],
+        };
         let was_json = render_json_or(OutputFormat::Json, &data).unwrap();
         assert!(was_json);
     }
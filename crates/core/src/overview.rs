@@ -0,0 +1,156 @@
+//! Watchlist dashboard (`atlas market overview`) from CoinGecko's
+//! `coins/markets` and `global` responses.
+//!
+//! The coins come from `market.watchlist` in the config, fetched in one
+//! batched `coins/markets` request. Ids CoinGecko does not return are
+//! reported as missing rather than failing the dashboard.
+
+use serde_json::Value;
+
+use crate::output::{MarketOverviewGlobal, MarketOverviewRow};
+
+/// Header figures from a `global` response.
+pub fn parse_global(resp: &Value) -> Option<MarketOverviewGlobal> {
+    let d = resp.get("data")?;
+    Some(MarketOverviewGlobal {
+        total_market_cap_usd: d.pointer("/total_market_cap/usd")?.as_f64()?,
+        market_cap_change_24h_pct: d
+            .get("market_cap_change_percentage_24h_usd")
+            .and_then(Value::as_f64),
+        btc_dominance_pct: d
+            .pointer("/market_cap_percentage/btc")
+            .and_then(Value::as_f64),
+    })
+}
+
+/// Rows of a `coins/markets` response, in response order. Entries without
+/// an id or price are skipped.
+pub fn parse_markets(resp: &Value) -> Vec<MarketOverviewRow> {
+    let list = resp.as_array().map(Vec::as_slice).unwrap_or_default();
+    list.iter()
+        .filter_map(|c| {
+            let f = |key: &str| c.get(key).and_then(Value::as_f64);
+            let text = |key: &str| c.get(key).and_then(Value::as_str).unwrap_or_default();
+            Some(MarketOverviewRow {
+                id: c.get("id")?.as_str()?.to_string(),
+                symbol: text("symbol").to_uppercase(),
+                name: text("name").to_string(),
+                price_usd: f("current_price")?,
+                change_1h_pct: f("price_change_percentage_1h_in_currency"),
+                change_24h_pct: f("price_change_percentage_24h_in_currency")
+                    .or_else(|| f("price_change_percentage_24h")),
+                change_7d_pct: f("price_change_percentage_7d_in_currency"),
+                volume_24h_usd: f("total_volume"),
+                market_cap_usd: f("market_cap"),
+                market_cap_rank: c
+                    .get("market_cap_rank")
+                    .and_then(Value::as_u64)
+                    .map(|r| r as u32),
+                from_ath_pct: f("ath_change_percentage"),
+            })
+        })
+        .collect()
+}
+
+/// Watchlist ids the response did not include.
+pub fn missing_ids(watchlist: &[String], rows: &[MarketOverviewRow]) -> Vec<String> {
+    watchlist
+        .iter()
+        .filter(|id| !rows.iter().any(|r| r.id.eq_ignore_ascii_case(id)))
+        .cloned()
+        .collect()
+}
+
+/// Sort rows by `--sort`: `24h` and `7d` biggest gain first, `mcap` by
+/// market cap rank. Coins missing the figure go last.
+pub fn sort_rows(rows: &mut [MarketOverviewRow], sort: &str) {
+    let desc = |v: Option<f64>| v.map_or(f64::INFINITY, |v| -v);
+    match sort {
+        "24h" => rows.sort_by(|a, b| desc(a.change_24h_pct).total_cmp(&desc(b.change_24h_pct))),
+        "7d" => rows.sort_by(|a, b| desc(a.change_7d_pct).total_cmp(&desc(b.change_7d_pct))),
+        _ => rows.sort_by_key(|r| r.market_cap_rank.unwrap_or(u32::MAX)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn markets() -> Value {
+        json!([
+            {
+                "id": "bitcoin", "symbol": "btc", "name": "Bitcoin",
+                "current_price": 97541.22, "market_cap": 1931000000000.0_f64,
+                "market_cap_rank": 1, "total_volume": 41200000000.0_f64,
+                "price_change_percentage_24h": 1.12,
+                "ath": 108786.0, "ath_change_percentage": -10.34,
+                "price_change_percentage_1h_in_currency": 0.21,
+                "price_change_percentage_24h_in_currency": 1.12,
+                "price_change_percentage_7d_in_currency": -2.4
+            },
+            {
+                "id": "hyperliquid", "symbol": "hype", "name": "Hyperliquid",
+                "current_price": 24.81, "market_cap": 8290000000.0_f64,
+                "market_cap_rank": 21, "total_volume": 312000000.0_f64,
+                "ath_change_percentage": -28.9,
+                "price_change_percentage_1h_in_currency": -0.4,
+                "price_change_percentage_24h_in_currency": 6.3,
+                "price_change_percentage_7d_in_currency": null
+            },
+            {
+                "id": "solana", "symbol": "sol", "name": "Solana",
+                "current_price": 174.32, "market_cap": 84000000000.0_f64,
+                "market_cap_rank": 6, "total_volume": 3100000000.0_f64,
+                "price_change_percentage_24h_in_currency": -3.5,
+                "price_change_percentage_7d_in_currency": 4.2
+            },
+            {"id": "delisted", "symbol": "dls", "current_price": null}
+        ])
+    }
+
+    #[test]
+    fn test_parse_markets() {
+        let rows = parse_markets(&markets());
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].symbol, "BTC");
+        assert_eq!(rows[0].change_7d_pct, Some(-2.4));
+        assert_eq!(rows[0].from_ath_pct, Some(-10.34));
+        assert_eq!(rows[1].change_7d_pct, None);
+        assert_eq!(rows[2].change_1h_pct, None);
+
+        let watchlist: Vec<String> = ["bitcoin", "solana", "hyperliquid", "delisted", "nope"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(missing_ids(&watchlist, &rows), ["delisted", "nope"]);
+    }
+
+    #[test]
+    fn test_sort_rows() {
+        let mut rows = parse_markets(&markets());
+        let order = |rows: &[MarketOverviewRow]| -> Vec<String> {
+            rows.iter().map(|r| r.symbol.clone()).collect()
+        };
+        sort_rows(&mut rows, "mcap");
+        assert_eq!(order(&rows), ["BTC", "SOL", "HYPE"]);
+        sort_rows(&mut rows, "24h");
+        assert_eq!(order(&rows), ["HYPE", "BTC", "SOL"]);
+        // No 7d figure for HYPE: last
+        sort_rows(&mut rows, "7d");
+        assert_eq!(order(&rows), ["SOL", "BTC", "HYPE"]);
+    }
+
+    #[test]
+    fn test_parse_global() {
+        let resp = json!({"data": {
+            "total_market_cap": {"usd": 3.41e12, "eur": 3.1e12},
+            "market_cap_percentage": {"btc": 56.6, "eth": 11.9},
+            "market_cap_change_percentage_24h_usd": -1.25
+        }});
+        let global = parse_global(&resp).unwrap();
+        assert_eq!(global.total_market_cap_usd, 3.41e12);
+        assert_eq!(global.btc_dominance_pct, Some(56.6));
+        assert_eq!(global.market_cap_change_24h_pct, Some(-1.25));
+        assert!(parse_global(&json!({"status": {"error_code": 429}})).is_none());
+    }
+}
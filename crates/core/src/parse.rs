@@ -133,6 +133,19 @@ pub fn parse_size(s: &str) -> Result<SizeInput> {
     Ok(SizeInput::Raw(val))
 }
 
+/// Like [`parse_size`], but also accepts the asset's own ticker as a units
+/// suffix (`"0.5hype"` for HYPE) — spot tokens aren't in the fixed list.
+pub fn parse_size_for(s: &str, symbol: &str) -> Result<SizeInput> {
+    let lower = s.trim().to_lowercase();
+    let symbol = symbol.to_lowercase();
+    if let Some(num) = lower.strip_suffix(symbol.as_str()) {
+        if let Ok(val) = num.trim().parse::<f64>() {
+            return Ok(SizeInput::Units(val));
+        }
+    }
+    parse_size(s)
+}
+
 /// Parse a hex-encoded address, validating basic format.
 pub fn parse_address(s: &str) -> Result<String> {
     let addr = s.trim();
@@ -146,6 +159,90 @@ pub fn parse_address(s: &str) -> Result<String> {
     Ok(addr.to_string())
 }
 
+/// Parse a client order ID: 16 bytes as hex (with or without `0x`) or a
+/// UUID. Returns the `0x`-prefixed lowercase form the exchange expects.
+pub fn parse_cloid(s: &str) -> Result<String> {
+    let trimmed = s.trim();
+    let body = trimmed.strip_prefix("0x").unwrap_or(trimmed);
+    let is_uuid = body.len() == 36 && [8, 13, 18, 23].iter().all(|&i| body.as_bytes()[i] == b'-');
+    let hex = if is_uuid {
+        body.replace('-', "")
+    } else {
+        body.to_string()
+    };
+    if hex.len() != 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("Invalid cloid '{trimmed}'. Use 32 hex digits (0x…) or a UUID.");
+    }
+    Ok(format!("0x{}", hex.to_lowercase()))
+}
+
+/// Parse an age like `90d`, `12h`, `2w` or `30m` into milliseconds.
+pub fn parse_age_ms(s: &str) -> Result<u64> {
+    let trimmed = s.trim();
+    let split = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(trimmed.len());
+    let (digits, unit) = trimmed.split_at(split);
+    let unit_ms: u64 = match unit {
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        "w" => 7 * 86_400_000,
+        _ => bail!("Invalid age '{trimmed}'. Use a number with m, h, d or w (e.g. 90d)."),
+    };
+    let n: u64 = digits
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid age '{trimmed}'. Use e.g. 90d."))?;
+    n.checked_mul(unit_ms)
+        .ok_or_else(|| anyhow::anyhow!("Age '{trimmed}' is too large"))
+}
+
+/// Split a command line into arguments the way a shell would for simple
+/// input: whitespace separates, single and double quotes group, and a
+/// backslash escapes the next character outside single quotes.
+pub fn split_command_line(s: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('\''), c) => current.push(c),
+            (_, '\\') => match chars.next() {
+                Some(next) => {
+                    current.push(next);
+                    in_arg = true;
+                }
+                None => bail!("Command ends with a lone backslash"),
+            },
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        bail!("Unclosed quote in command: {s}");
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,4 +403,86 @@ mod tests {
         assert!(parse_size("abc").is_err());
         assert!(parse_size("$abc").is_err());
     }
+
+    #[test]
+    fn test_parse_size_for_token_suffix() {
+        assert_eq!(
+            parse_size_for("0.5hype", "HYPE").unwrap(),
+            SizeInput::Units(0.5)
+        );
+        assert_eq!(
+            parse_size_for("100 PURR", "purr").unwrap(),
+            SizeInput::Units(100.0)
+        );
+        // Everything else falls through to the shared grammar.
+        assert_eq!(
+            parse_size_for("$250", "HYPE").unwrap(),
+            SizeInput::Usdc(250.0)
+        );
+        assert_eq!(
+            parse_size_for("250", "HYPE").unwrap(),
+            SizeInput::Raw(250.0)
+        );
+        assert_eq!(
+            parse_size_for("0.5eth", "HYPE").unwrap(),
+            SizeInput::Units(0.5)
+        );
+        assert!(parse_size_for("0.5hype", "PURR").is_err());
+    }
+
+    #[test]
+    fn test_parse_cloid() {
+        let want = "0x1234567890abcdef1234567890abcdef";
+        assert_eq!(parse_cloid(want).unwrap(), want);
+        assert_eq!(
+            parse_cloid("1234567890ABCDEF1234567890ABCDEF").unwrap(),
+            want
+        );
+        assert_eq!(
+            parse_cloid("12345678-90ab-cdef-1234-567890abcdef").unwrap(),
+            want
+        );
+        assert!(parse_cloid("0x1234").is_err());
+        assert!(parse_cloid("12345678-90ab-cdef-1234567890abcdef").is_err());
+        assert!(parse_cloid("0xzz34567890abcdef1234567890abcdef").is_err());
+    }
+
+    #[test]
+    fn test_parse_age_ms() {
+        assert_eq!(parse_age_ms("90d").unwrap(), 90 * 86_400_000);
+        assert_eq!(parse_age_ms("12h").unwrap(), 12 * 3_600_000);
+        assert_eq!(parse_age_ms(" 2w ").unwrap(), 14 * 86_400_000);
+        assert_eq!(parse_age_ms("30m").unwrap(), 1_800_000);
+        assert!(parse_age_ms("90").is_err());
+        assert!(parse_age_ms("d").is_err());
+        assert!(parse_age_ms("1.5d").is_err());
+    }
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line("hl perp buy BTC $50").unwrap(),
+            ["hl", "perp", "buy", "BTC", "$50"]
+        );
+        assert_eq!(
+            split_command_line(r#"  journal add "took profit, early" --tag 'a b' x\ y "" "#)
+                .unwrap(),
+            [
+                "journal",
+                "add",
+                "took profit, early",
+                "--tag",
+                "a b",
+                "x y",
+                ""
+            ]
+        );
+        assert_eq!(
+            split_command_line(r#"'it\s' "say \"hi\"""#).unwrap(),
+            [r"it\s", r#"say "hi""#]
+        );
+        assert!(split_command_line("buy 'BTC").is_err());
+        assert!(split_command_line("buy \\").is_err());
+        assert!(split_command_line("   ").unwrap().is_empty());
+    }
 }
@@ -0,0 +1,488 @@
+//! Candlestick pattern recognition over a candle slice.
+//!
+//! Detectors are pure functions of one or two candles, so a pattern is
+//! found the same way on the latest candle as further back. Each match is
+//! scored 0–100 by the size of its decisive part (the engulfing body, the
+//! hammer's lower wick, ...) against the ATR at that candle: a move of
+//! [`FULL_STRENGTH_ATR`] ATRs or more scores 100, one lost in the noise
+//! scores near 0.
+
+use std::str::FromStr;
+
+use rust_decimal::prelude::ToPrimitive;
+use serde::Serialize;
+
+use crate::error::{AtlasError, AtlasResult};
+use crate::types::Candle;
+
+/// Candles in the ATR that strength is measured against.
+pub const ATR_PERIOD: usize = 14;
+
+/// Size, in ATRs, that scores a full 100.
+pub const FULL_STRENGTH_ATR: f64 = 2.0;
+
+/// One candle as plain floats.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ohlc {
+    pub time_ms: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Ohlc {
+    pub fn body(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+
+    pub fn range(&self) -> f64 {
+        self.high - self.low
+    }
+
+    pub fn upper_shadow(&self) -> f64 {
+        self.high - self.open.max(self.close)
+    }
+
+    pub fn lower_shadow(&self) -> f64 {
+        self.open.min(self.close) - self.low
+    }
+
+    pub fn is_bullish(&self) -> bool {
+        self.close > self.open
+    }
+
+    pub fn is_bearish(&self) -> bool {
+        self.close < self.open
+    }
+}
+
+impl From<&Candle> for Ohlc {
+    fn from(c: &Candle) -> Self {
+        Self {
+            time_ms: c.open_time_ms,
+            open: c.open.to_f64().unwrap_or(0.0),
+            high: c.high.to_f64().unwrap_or(0.0),
+            low: c.low.to_f64().unwrap_or(0.0),
+            close: c.close.to_f64().unwrap_or(0.0),
+        }
+    }
+}
+
+/// Pattern families `--patterns` selects; engulfing and harami cover both
+/// directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternFamily {
+    Doji,
+    Hammer,
+    ShootingStar,
+    Engulfing,
+    Harami,
+}
+
+impl PatternFamily {
+    pub const ALL: [PatternFamily; 5] = [
+        PatternFamily::Doji,
+        PatternFamily::Hammer,
+        PatternFamily::ShootingStar,
+        PatternFamily::Engulfing,
+        PatternFamily::Harami,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PatternFamily::Doji => "doji",
+            PatternFamily::Hammer => "hammer",
+            PatternFamily::ShootingStar => "shooting_star",
+            PatternFamily::Engulfing => "engulfing",
+            PatternFamily::Harami => "harami",
+        }
+    }
+}
+
+impl FromStr for PatternFamily {
+    type Err = AtlasError;
+
+    fn from_str(s: &str) -> AtlasResult<Self> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "doji" => Ok(PatternFamily::Doji),
+            "hammer" => Ok(PatternFamily::Hammer),
+            "shooting_star" | "star" => Ok(PatternFamily::ShootingStar),
+            "engulfing" => Ok(PatternFamily::Engulfing),
+            "harami" => Ok(PatternFamily::Harami),
+            _ => Err(AtlasError::InvalidIndicator(format!(
+                "unknown pattern '{s}'. Supported: {}",
+                PatternFamily::ALL.map(PatternFamily::as_str).join(", ")
+            ))),
+        }
+    }
+}
+
+/// Comma-separated families, or `all`. Duplicates are dropped.
+pub fn parse_families(list: &str) -> AtlasResult<Vec<PatternFamily>> {
+    if list.trim().eq_ignore_ascii_case("all") {
+        return Ok(PatternFamily::ALL.to_vec());
+    }
+    let mut families = Vec::new();
+    for raw in list.split(',').filter(|s| !s.trim().is_empty()) {
+        let family: PatternFamily = raw.parse()?;
+        if !families.contains(&family) {
+            families.push(family);
+        }
+    }
+    if families.is_empty() {
+        return Err(AtlasError::InvalidIndicator(
+            "no patterns given, e.g. --patterns engulfing,hammer".into(),
+        ));
+    }
+    Ok(families)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Bullish,
+    Bearish,
+    Neutral,
+}
+
+impl Direction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Direction::Bullish => "bullish",
+            Direction::Bearish => "bearish",
+            Direction::Neutral => "neutral",
+        }
+    }
+}
+
+/// A pattern completed on the candle at `index`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PatternMatch {
+    /// e.g. `"Bullish Engulfing"`.
+    pub pattern: &'static str,
+    pub family: PatternFamily,
+    /// e.g. `"bullish reversal"`, `"indecision"`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub direction: Direction,
+    /// Position in the candle slice the pattern was found in.
+    pub index: usize,
+    /// Open time of the completing candle.
+    pub time_ms: u64,
+    /// 0–100; see the module docs.
+    pub strength: u8,
+}
+
+// ── Detectors ───────────────────────────────────────────────────────
+
+/// Body under a tenth of the range.
+pub fn is_doji(c: &Ohlc) -> bool {
+    c.range() > 0.0 && c.body() / c.range() < 0.1
+}
+
+/// Lower wick over twice the body, upper wick under half of it.
+pub fn is_hammer(c: &Ohlc) -> bool {
+    c.range() > 0.0 && c.lower_shadow() > c.body() * 2.0 && c.upper_shadow() < c.body() * 0.5
+}
+
+/// Upper wick over twice the body, lower wick under half of it.
+pub fn is_shooting_star(c: &Ohlc) -> bool {
+    c.range() > 0.0 && c.upper_shadow() > c.body() * 2.0 && c.lower_shadow() < c.body() * 0.5
+}
+
+/// `last`'s body covers `prev`'s opposite-colored body.
+pub fn engulfing(prev: &Ohlc, last: &Ohlc) -> Option<Direction> {
+    if prev.is_bearish() && last.is_bullish() && last.open <= prev.close && last.close >= prev.open
+    {
+        Some(Direction::Bullish)
+    } else if prev.is_bullish()
+        && last.is_bearish()
+        && last.open >= prev.close
+        && last.close <= prev.open
+    {
+        Some(Direction::Bearish)
+    } else {
+        None
+    }
+}
+
+/// `last`'s body sits strictly inside `prev`'s opposite-colored body.
+pub fn harami(prev: &Ohlc, last: &Ohlc) -> Option<Direction> {
+    if prev.is_bearish() && last.is_bullish() && last.open > prev.close && last.close < prev.open {
+        Some(Direction::Bullish)
+    } else if prev.is_bullish()
+        && last.is_bearish()
+        && last.open < prev.close
+        && last.close > prev.open
+    {
+        Some(Direction::Bearish)
+    } else {
+        None
+    }
+}
+
+/// Average true range over the [`ATR_PERIOD`] candles ending at `index`
+/// (which must be in bounds), or fewer when the slice starts later.
+pub fn atr_at(candles: &[Ohlc], index: usize) -> f64 {
+    let start = (index + 1).saturating_sub(ATR_PERIOD);
+    let ranges: Vec<f64> = (start..=index)
+        .map(|j| {
+            let c = &candles[j];
+            match j.checked_sub(1).map(|p| candles[p].close) {
+                Some(pc) => c.range().max((c.high - pc).abs()).max((c.low - pc).abs()),
+                None => c.range(),
+            }
+        })
+        .collect();
+    ranges.iter().sum::<f64>() / ranges.len() as f64
+}
+
+fn strength(size: f64, atr: f64) -> u8 {
+    if atr <= 0.0 || !size.is_finite() {
+        return 0;
+    }
+    ((size / atr / FULL_STRENGTH_ATR).clamp(0.0, 1.0) * 100.0).round() as u8
+}
+
+/// Patterns of `families` that complete on `candles[index]`, in
+/// [`PatternFamily::ALL`] order. Two-candle patterns need `index >= 1`.
+pub fn detect_at(candles: &[Ohlc], index: usize, families: &[PatternFamily]) -> Vec<PatternMatch> {
+    let Some(last) = candles.get(index) else {
+        return Vec::new();
+    };
+    let prev = index.checked_sub(1).map(|p| &candles[p]);
+    let atr = atr_at(candles, index);
+
+    let mut found = Vec::new();
+    let mut push = |family, pattern, kind, direction, size: f64| {
+        found.push(PatternMatch {
+            pattern,
+            family,
+            kind,
+            direction,
+            index,
+            time_ms: last.time_ms,
+            strength: strength(size, atr),
+        })
+    };
+    for family in PatternFamily::ALL {
+        if !families.contains(&family) {
+            continue;
+        }
+        match family {
+            PatternFamily::Doji if is_doji(last) => push(
+                family,
+                "Doji",
+                "indecision",
+                Direction::Neutral,
+                last.range(),
+            ),
+            PatternFamily::Hammer if is_hammer(last) => push(
+                family,
+                "Hammer",
+                "bullish reversal",
+                Direction::Bullish,
+                last.lower_shadow(),
+            ),
+            PatternFamily::ShootingStar if is_shooting_star(last) => push(
+                family,
+                "Shooting Star",
+                "bearish reversal",
+                Direction::Bearish,
+                last.upper_shadow(),
+            ),
+            PatternFamily::Engulfing => match prev.and_then(|p| engulfing(p, last)) {
+                Some(Direction::Bullish) => push(
+                    family,
+                    "Bullish Engulfing",
+                    "bullish reversal",
+                    Direction::Bullish,
+                    last.body(),
+                ),
+                Some(_) => push(
+                    family,
+                    "Bearish Engulfing",
+                    "bearish reversal",
+                    Direction::Bearish,
+                    last.body(),
+                ),
+                None => {}
+            },
+            // A harami is as telling as the candle it sits inside
+            PatternFamily::Harami => match prev.and_then(|p| harami(p, last).map(|d| (p, d))) {
+                Some((p, Direction::Bullish)) => push(
+                    family,
+                    "Bullish Harami",
+                    "bullish reversal",
+                    Direction::Bullish,
+                    p.body(),
+                ),
+                Some((p, _)) => push(
+                    family,
+                    "Bearish Harami",
+                    "bearish reversal",
+                    Direction::Bearish,
+                    p.body(),
+                ),
+                None => {}
+            },
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Patterns completing on any of the last `lookback` candles, newest first.
+pub fn scan(candles: &[Ohlc], families: &[PatternFamily], lookback: usize) -> Vec<PatternMatch> {
+    let start = candles.len().saturating_sub(lookback);
+    (start..candles.len())
+        .rev()
+        .flat_map(|i| detect_at(candles, i, families))
+        .collect()
+}
+
+/// `candles` without a trailing candle still open at `now_ms`. Exchanges
+/// return the forming candle last, and a pattern on it can still vanish.
+pub fn closed(candles: &[Ohlc], interval_ms: u64, now_ms: u64) -> &[Ohlc] {
+    match candles.split_last() {
+        Some((last, rest)) if last.time_ms + interval_ms > now_ms => rest,
+        _ => candles,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> Ohlc {
+        Ohlc {
+            time_ms: 0,
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+
+    /// Flat 10-point candles around 100, then `tail`; times are minutes.
+    fn series(tail: &[Ohlc]) -> Vec<Ohlc> {
+        let mut candles: Vec<Ohlc> = (0..14).map(|_| bar(100.0, 105.0, 95.0, 100.0)).collect();
+        candles.extend_from_slice(tail);
+        for (i, c) in candles.iter_mut().enumerate() {
+            c.time_ms = i as u64 * 60_000;
+        }
+        candles
+    }
+
+    fn names(found: &[PatternMatch]) -> Vec<&str> {
+        found.iter().map(|m| m.pattern).collect()
+    }
+
+    #[test]
+    fn test_single_candle_shapes() {
+        assert!(is_doji(&bar(100.0, 105.0, 95.0, 100.5)));
+        assert!(!is_doji(&bar(100.0, 105.0, 95.0, 103.0)));
+        assert!(!is_doji(&bar(100.0, 100.0, 100.0, 100.0)));
+
+        let hammer = bar(100.0, 101.2, 94.0, 101.0);
+        assert!(is_hammer(&hammer) && !is_shooting_star(&hammer));
+        let star = bar(100.0, 106.0, 98.8, 99.0);
+        assert!(is_shooting_star(&star) && !is_hammer(&star));
+    }
+
+    #[test]
+    fn test_two_candle_shapes() {
+        let red = bar(102.0, 103.0, 97.0, 98.0);
+        let green = bar(97.0, 104.0, 96.0, 103.0);
+        assert_eq!(engulfing(&red, &green), Some(Direction::Bullish));
+        assert_eq!(engulfing(&green, &red), None);
+        let big_green = bar(100.0, 106.0, 96.0, 105.0);
+        let red_after = bar(106.0, 107.0, 98.0, 99.0);
+        assert_eq!(engulfing(&big_green, &red_after), Some(Direction::Bearish));
+
+        let inside_green = bar(99.0, 101.0, 98.5, 101.0);
+        assert_eq!(harami(&red, &inside_green), Some(Direction::Bullish));
+        let inside_red = bar(104.0, 104.5, 100.0, 101.0);
+        assert_eq!(harami(&big_green, &inside_red), Some(Direction::Bearish));
+        assert_eq!(harami(&red, &green), None);
+    }
+
+    #[test]
+    fn test_atr_handles_gaps_and_short_history() {
+        let candles = series(&[bar(120.0, 122.0, 118.0, 121.0)]);
+        // Range 10 for the flat candles; the gap up makes the last TR 22
+        let expected = (13.0 * 10.0 + 22.0) / 14.0;
+        assert!((atr_at(&candles, 14) - expected).abs() < 1e-9);
+        assert_eq!(atr_at(&candles, 0), 10.0);
+    }
+
+    #[test]
+    fn test_detect_reports_location_and_strength() {
+        // Red then a 30-point green engulfing body, over 2 ATRs
+        let candles = series(&[bar(104.0, 105.0, 94.0, 96.0), bar(95.0, 126.0, 94.0, 125.0)]);
+        let all = PatternFamily::ALL;
+        let found = detect_at(&candles, 15, &all);
+        assert_eq!(names(&found), ["Bullish Engulfing"]);
+        let m = &found[0];
+        assert_eq!(m.direction, Direction::Bullish);
+        assert_eq!((m.index, m.time_ms), (15, 15 * 60_000));
+        assert_eq!(m.strength, 100);
+
+        // A body of half an ATR scores about a quarter
+        let small = series(&[bar(102.0, 103.0, 97.0, 98.0), bar(97.0, 104.0, 96.0, 103.0)]);
+        let m = &detect_at(&small, 15, &all)[0];
+        assert_eq!(m.pattern, "Bullish Engulfing");
+        assert!((25..=35).contains(&m.strength), "{}", m.strength);
+
+        // Filtered out, or not enough candles for a two-candle pattern
+        assert!(detect_at(&candles, 15, &[PatternFamily::Doji]).is_empty());
+        assert!(detect_at(&candles[15..], 0, &all).is_empty());
+        assert!(detect_at(&candles, 99, &all).is_empty());
+    }
+
+    #[test]
+    fn test_scan_lists_newest_first() {
+        let candles = series(&[
+            bar(100.0, 101.2, 94.0, 101.0),
+            bar(101.0, 103.0, 100.0, 102.0),
+            bar(100.0, 106.0, 98.8, 99.0),
+        ]);
+        let found = scan(&candles, &PatternFamily::ALL, 3);
+        assert_eq!(names(&found), ["Shooting Star", "Hammer"]);
+        assert_eq!(found[0].index, 16);
+        // Flat candles are dojis, but sit outside the lookback
+        assert!(scan(&candles, &PatternFamily::ALL, 4)
+            .iter()
+            .any(|m| m.pattern == "Doji" && m.index == 13));
+    }
+
+    #[test]
+    fn test_closed_drops_forming_candle() {
+        let candles = series(&[]);
+        let last_open = candles[13].time_ms;
+        assert_eq!(closed(&candles, 60_000, last_open + 30_000).len(), 13);
+        assert_eq!(closed(&candles, 60_000, last_open + 60_000).len(), 14);
+        assert!(closed(&[], 60_000, 0).is_empty());
+    }
+
+    #[test]
+    fn test_parse_families() {
+        assert_eq!(
+            parse_families("engulfing, Hammer,doji,hammer").unwrap(),
+            [
+                PatternFamily::Engulfing,
+                PatternFamily::Hammer,
+                PatternFamily::Doji
+            ]
+        );
+        assert_eq!(
+            parse_families("shooting-star").unwrap(),
+            [PatternFamily::ShootingStar]
+        );
+        assert_eq!(parse_families("all").unwrap().len(), 5);
+        assert!(parse_families("marubozu").is_err());
+        assert!(parse_families(" , ").is_err());
+    }
+}
@@ -0,0 +1,269 @@
+//! Rug heuristics for fresh DEX pools (`atlas market dex new --screen`).
+//!
+//! Each check that trips adds a flag and takes points off a score of 100;
+//! the score maps to a grade from A (nothing found) to F. The checks are
+//! deliberately simple — thin liquidity, a very young pool, a token
+//! CoinGecko does not list, one-sided trading and an FDV far above the
+//! liquidity backing it. A good grade is not a safety guarantee.
+
+use rust_decimal::prelude::*;
+use serde_json::Value;
+
+use crate::fmt::format_usd;
+
+/// Liquidity below this is a serious flag.
+pub const MIN_LIQUIDITY_USD: u64 = 10_000;
+/// Liquidity below this (but above [`MIN_LIQUIDITY_USD`]) is a warning.
+pub const THIN_LIQUIDITY_USD: u64 = 50_000;
+/// Buys outnumbering sells (or the reverse) by this factor is a warning.
+pub const TX_SKEW: u64 = 5;
+/// FDV at this multiple of liquidity is a warning, ten times it serious.
+pub const FDV_TO_LIQUIDITY: u64 = 100;
+
+const HOUR_MS: i64 = 3_600_000;
+const SEVERE: u32 = 35;
+const WARNING: u32 = 15;
+const NOTICE: u32 = 10;
+
+/// Outcome of screening one pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolRisk {
+    /// `A` (best) to `F`.
+    pub grade: String,
+    /// 0–100; 100 means no flag tripped.
+    pub score: u32,
+    /// Why points were taken off, worst first.
+    pub flags: Vec<String>,
+}
+
+/// Grade a pool from its pool detail response (`data.attributes`) and its
+/// base token's info response, at `now_ms`. `token` is `None` when the
+/// token lookup failed.
+pub fn screen_pool(pool: &Value, token: Option<&Value>, now_ms: i64) -> PoolRisk {
+    let attrs = pool.pointer("/data/attributes").unwrap_or(&Value::Null);
+    let num = |ptr: &str| attrs.pointer(ptr).and_then(decimal);
+    let mut flags: Vec<(u32, String)> = Vec::new();
+
+    // Liquidity
+    let liquidity = num("/reserve_in_usd");
+    match liquidity {
+        None => flags.push((SEVERE, "liquidity unknown".into())),
+        Some(l) if l < Decimal::from(MIN_LIQUIDITY_USD) => flags.push((
+            SEVERE,
+            format!("liquidity {} < {}", usd(l), usd(MIN_LIQUIDITY_USD.into())),
+        )),
+        Some(l) if l < Decimal::from(THIN_LIQUIDITY_USD) => flags.push((
+            WARNING,
+            format!("liquidity {} < {}", usd(l), usd(THIN_LIQUIDITY_USD.into())),
+        )),
+        Some(_) => {}
+    }
+
+    // Age
+    let created_ms = attrs
+        .get("pool_created_at")
+        .and_then(Value::as_str)
+        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.timestamp_millis());
+    match created_ms.map(|c| now_ms - c) {
+        None => flags.push((WARNING, "pool age unknown".into())),
+        Some(age) if age < HOUR_MS => flags.push((SEVERE, "pool < 1h old".into())),
+        Some(age) if age < 24 * HOUR_MS => {
+            flags.push((WARNING, format!("pool {}h old", age / HOUR_MS)))
+        }
+        Some(_) => {}
+    }
+
+    // CoinGecko listing
+    match token {
+        None => flags.push((NOTICE, "token info unavailable".into())),
+        Some(t) => {
+            let listed = t
+                .pointer("/data/attributes/coingecko_coin_id")
+                .and_then(Value::as_str)
+                .is_some_and(|id| !id.is_empty());
+            if !listed {
+                flags.push((NOTICE, "token not on CoinGecko".into()));
+            }
+        }
+    }
+
+    // Buy/sell balance over 24h
+    let count = |side: &str| {
+        attrs
+            .pointer(&format!("/transactions/h24/{side}"))
+            .and_then(Value::as_u64)
+    };
+    match (count("buys"), count("sells")) {
+        (Some(0), Some(0)) => flags.push((WARNING, "no trades in 24h".into())),
+        (Some(buys), Some(0)) if buys >= 10 => {
+            flags.push((SEVERE, format!("{buys} buys, no sells in 24h")))
+        }
+        (Some(buys), Some(sells)) if sells > 0 && buys >= sells * TX_SKEW => flags.push((
+            WARNING,
+            format!("buys outnumber sells {:.1}x", buys as f64 / sells as f64),
+        )),
+        (Some(buys), Some(sells)) if buys > 0 && sells >= buys * TX_SKEW => flags.push((
+            WARNING,
+            format!("sells outnumber buys {:.1}x", sells as f64 / buys as f64),
+        )),
+        (Some(_), Some(_)) => {}
+        _ => flags.push((NOTICE, "24h transactions unknown".into())),
+    }
+
+    // FDV backed by liquidity
+    if let (Some(fdv), Some(l)) = (num("/fdv_usd"), liquidity) {
+        if l > Decimal::ZERO {
+            let ratio = (fdv / l).round();
+            let threshold = Decimal::from(FDV_TO_LIQUIDITY);
+            if ratio >= threshold * Decimal::TEN {
+                flags.push((SEVERE, format!("FDV {ratio}x liquidity")));
+            } else if ratio >= threshold {
+                flags.push((WARNING, format!("FDV {ratio}x liquidity")));
+            }
+        }
+    }
+
+    // Stable sort keeps check order within a severity
+    flags.sort_by_key(|(points, _)| std::cmp::Reverse(*points));
+    let penalty: u32 = flags.iter().map(|(points, _)| points).sum();
+    let score = 100u32.saturating_sub(penalty);
+    PoolRisk {
+        grade: grade(score).to_string(),
+        score,
+        flags: flags.into_iter().map(|(_, flag)| flag).collect(),
+    }
+}
+
+/// Letter grade for a score.
+pub fn grade(score: u32) -> &'static str {
+    match score {
+        90.. => "A",
+        75..=89 => "B",
+        60..=74 => "C",
+        40..=59 => "D",
+        _ => "F",
+    }
+}
+
+fn usd(d: Decimal) -> String {
+    format_usd(&d.to_string())
+}
+
+fn decimal(v: &Value) -> Option<Decimal> {
+    match v {
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Number(n) => n.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 2025-10-14T12:00:00Z
+    const NOW: i64 = 1_760_443_200_000;
+
+    fn pool(reserve: &str, fdv: &str, created: &str, buys: u64, sells: u64) -> Value {
+        json!({
+            "data": {
+                "id": "base_0x6921b130d297cc43754afba22e5eac0fbf8db75b",
+                "type": "pool",
+                "attributes": {
+                    "name": "TOSHI / WETH",
+                    "address": "0x6921b130d297cc43754afba22e5eac0fbf8db75b",
+                    "base_token_price_usd": "0.008241",
+                    "fdv_usd": fdv,
+                    "reserve_in_usd": reserve,
+                    "pool_created_at": created,
+                    "transactions": {
+                        "h24": {"buys": buys, "sells": sells, "buyers": 10, "sellers": 10}
+                    },
+                    "volume_usd": {"h24": "14841044.9"}
+                }
+            }
+        })
+    }
+
+    fn token(coingecko_id: Option<&str>) -> Value {
+        json!({"data": {"attributes": {
+            "address": "0xac1bd2486aaf3b5c0fc3fd868558b082a531b2b4",
+            "symbol": "TOSHI",
+            "coingecko_coin_id": coingecko_id
+        }}})
+    }
+
+    #[test]
+    fn test_established_pool_grades_a() {
+        let risk = screen_pool(
+            &pool("3182041.44", "82410000", "2024-01-15T08:00:00Z", 4821, 5912),
+            Some(&token(Some("toshi"))),
+            NOW,
+        );
+        assert_eq!(risk.grade, "A");
+        assert_eq!(risk.score, 100);
+        assert!(risk.flags.is_empty());
+    }
+
+    #[test]
+    fn test_fresh_thin_pool_grades_f() {
+        // 20 minutes old, $4.2K liquidity, $21M FDV, only buys, unlisted
+        let risk = screen_pool(
+            &pool("4200", "21000000", "2025-10-14T11:40:00Z", 120, 0),
+            Some(&token(None)),
+            NOW,
+        );
+        assert_eq!(risk.grade, "F");
+        assert_eq!(risk.score, 0);
+        assert_eq!(
+            risk.flags,
+            [
+                "liquidity $4.20K < $10.00K",
+                "pool < 1h old",
+                "120 buys, no sells in 24h",
+                "FDV 5000x liquidity",
+                "token not on CoinGecko",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_warnings_grade_in_between() {
+        // 6h old, $30K liquidity, buys 6x sells, FDV 200x liquidity
+        let risk = screen_pool(
+            &pool("30000", "6000000", "2025-10-14T06:00:00Z", 600, 100),
+            Some(&token(Some("some-coin"))),
+            NOW,
+        );
+        assert_eq!(
+            risk.flags,
+            [
+                "liquidity $30.00K < $50.00K",
+                "pool 6h old",
+                "buys outnumber sells 6.0x",
+                "FDV 200x liquidity",
+            ]
+        );
+        assert_eq!((risk.score, risk.grade.as_str()), (40, "D"));
+    }
+
+    #[test]
+    fn test_missing_data_is_flagged_not_fatal() {
+        let risk = screen_pool(&json!({"data": {"attributes": {}}}), None, NOW);
+        assert_eq!(
+            risk.flags,
+            [
+                "liquidity unknown",
+                "pool age unknown",
+                "token info unavailable",
+                "24h transactions unknown",
+            ]
+        );
+        assert_eq!(risk.grade, "F");
+        assert_eq!(grade(90), "A");
+        assert_eq!(grade(89), "B");
+        assert_eq!(grade(60), "C");
+    }
+}
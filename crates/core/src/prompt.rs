@@ -1,7 +1,11 @@
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::Result;
 
+use crate::error::AtlasError;
+use crate::output::OutputFormat;
+
 /// Prompt the user for input on stdin. Prints `label: ` and reads one line.
 pub fn prompt(label: &str) -> Result<String> {
     print!("{label}: ");
@@ -11,6 +15,56 @@ pub fn prompt(label: &str) -> Result<String> {
     Ok(input)
 }
 
+/// Prompt for a secret (private key, passphrase) without echoing it.
+///
+/// On a terminal the input is read in raw mode: Enter finishes, Backspace
+/// deletes, Ctrl-C or Esc cancels. Piped input is read as a plain line.
+pub fn prompt_secret(label: &str) -> Result<String> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use crossterm::terminal;
+
+    if !io::stdin().is_terminal() {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        return Ok(input.trim().to_string());
+    }
+
+    print!("{label} (hidden): ");
+    io::stdout().flush()?;
+    terminal::enable_raw_mode()?;
+    let read = || -> Result<Option<String>> {
+        let mut input = String::new();
+        loop {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Enter => return Ok(Some(input)),
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(None)
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+        }
+    };
+    let result = read();
+    // Restore the terminal whatever happened while reading
+    terminal::disable_raw_mode()?;
+    println!();
+    match result? {
+        Some(input) => Ok(input.trim().to_string()),
+        None => anyhow::bail!("Cancelled."),
+    }
+}
+
 /// Prompt with a default value shown in brackets.
 pub fn prompt_default(label: &str, default: &str) -> Result<String> {
     let input = prompt(&format!("{label} [{default}]"))?;
@@ -34,3 +88,97 @@ pub fn confirm(label: &str, default_yes: bool) -> Result<bool> {
         _ => default_yes,
     })
 }
+
+static UNATTENDED: AtomicBool = AtomicBool::new(false);
+
+/// Treat every [`confirm_action`] as confirmed for the rest of the process.
+/// Set by `atlas schedule run`: its jobs were confirmed when added.
+/// [`confirm_attended`] prompts fail instead.
+pub fn set_unattended(unattended: bool) {
+    UNATTENDED.store(unattended, Ordering::Relaxed);
+}
+
+/// Show a summary of a destructive action and ask before proceeding.
+///
+/// Returns `Ok(true)` to proceed and `Ok(false)` if the user declined.
+/// `skip` is `--yes` or `trading.confirm = false`. Without a TTY, or when
+/// output is JSON, this never blocks on stdin: it fails with
+/// `AtlasError::ConfirmationRequired` unless `skip` is set. Never asks
+/// once [`set_unattended`] is on.
+pub fn confirm_action(
+    title: &str,
+    summary: &[(&str, String)],
+    skip: bool,
+    fmt: OutputFormat,
+) -> Result<bool> {
+    if skip || UNATTENDED.load(Ordering::Relaxed) {
+        return Ok(true);
+    }
+
+    let interactive =
+        fmt == OutputFormat::Table && io::stdin().is_terminal() && io::stdout().is_terminal();
+    if !interactive {
+        return Err(AtlasError::ConfirmationRequired(format!(
+            "{title} needs confirmation but no interactive terminal is available"
+        ))
+        .into());
+    }
+
+    println!("┌────────────────────────────────────────────────────────────┐");
+    println!("│  {:<58}│", title);
+    println!("├────────────────────────────────────────────────────────────┤");
+    for (label, value) in summary {
+        println!("│  {:<12}: {:<44}│", label, value);
+    }
+    println!("└────────────────────────────────────────────────────────────┘");
+
+    let ok = confirm("Proceed?", false)?;
+    if !ok {
+        println!("Cancelled.");
+    }
+    Ok(ok)
+}
+
+/// [`confirm_action`] for prompts that a scheduled run must never answer:
+/// key material, transfers off the account, close-all. Once
+/// [`set_unattended`] is on these fail with
+/// `AtlasError::ConfirmationRequired`, even when `skip` is set.
+pub fn confirm_attended(
+    title: &str,
+    summary: &[(&str, String)],
+    skip: bool,
+    fmt: OutputFormat,
+) -> Result<bool> {
+    if UNATTENDED.load(Ordering::Relaxed) {
+        return Err(AtlasError::ConfirmationRequired(format!(
+            "{title} can't run unattended from a schedule"
+        ))
+        .into());
+    }
+    confirm_action(title, summary, skip, fmt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unattended_skips_only_plain_prompts() {
+        set_unattended(true);
+        let plain = confirm_action("MARKET BUY — CONFIRM", &[], false, OutputFormat::Json);
+        let attended = confirm_attended(
+            "EXPORT PRIVATE KEY — CONFIRM",
+            &[],
+            true,
+            OutputFormat::Json,
+        );
+        set_unattended(false);
+
+        assert!(plain.unwrap());
+        let err = attended.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<AtlasError>(),
+            Some(AtlasError::ConfirmationRequired(_))
+        ));
+    }
+}
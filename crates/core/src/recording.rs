@@ -0,0 +1,676 @@
+//! Stream recordings — `atlas stream record` / `atlas stream replay`.
+//!
+//! A recording is a directory:
+//!
+//! ```text
+//! manifest.json        ticker, channels, start/end, counts, segments, gaps
+//! part-00001.ndjson    one Record per line
+//! part-00002.ndjson    started once the previous one reached --rotate
+//! ```
+//!
+//! Each record holds the receive time, the channel and the event exactly
+//! as the live `stream` command prints it with `--output json`, so replay
+//! re-emits what the live command would have. The manifest is rewritten
+//! (write-then-rename) at every checkpoint, so after a crash it still
+//! describes everything up to the last one; replay refuses a recording
+//! whose segments no longer match it.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::AtlasError;
+
+/// Manifest layout version; replay refuses anything else.
+pub const RECORDING_FORMAT: u32 = 1;
+
+pub const MANIFEST: &str = "manifest.json";
+
+/// Channels `stream record` can subscribe to.
+pub const CHANNELS: &[&str] = &["book", "trades"];
+
+/// Buffered records are flushed and the manifest rewritten after this many
+/// messages or this long, whichever comes first.
+const CHECKPOINT_MESSAGES: u64 = 1_000;
+const CHECKPOINT_MS: i64 = 5_000;
+
+/// One recorded message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// Local receive time, ms since epoch. Replay paces on this.
+    pub recv_ms: i64,
+    pub channel: String,
+    /// The live stream's NDJSON event.
+    pub event: Value,
+}
+
+/// One `part-NNNNN.ndjson` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    pub file: String,
+    pub messages: u64,
+    pub bytes: u64,
+    pub first_ms: Option<i64>,
+    pub last_ms: Option<i64>,
+}
+
+/// Time the feed was down: last message before a disconnect to the
+/// reconnect. Nothing was recorded in between.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Gap {
+    pub from_ms: i64,
+    pub to_ms: i64,
+}
+
+/// Why a recording ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// Ctrl+C.
+    Interrupted,
+    /// Free space fell below `--min-free`.
+    DiskSpace,
+    /// The WebSocket stream ended.
+    StreamClosed,
+    /// A write failed.
+    Error,
+}
+
+impl StopReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StopReason::Interrupted => "interrupted",
+            StopReason::DiskSpace => "disk_space",
+            StopReason::StreamClosed => "stream_closed",
+            StopReason::Error => "error",
+        }
+    }
+}
+
+/// `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: u32,
+    pub ticker: String,
+    pub channels: Vec<String>,
+    /// `mainnet` or `testnet`.
+    pub network: String,
+    pub started_ms: i64,
+    /// `None` while recording, or when the recorder never shut down.
+    pub ended_ms: Option<i64>,
+    pub stop_reason: Option<StopReason>,
+    /// Messages per channel.
+    pub messages: BTreeMap<String, u64>,
+    pub segments: Vec<Segment>,
+    pub gaps: Vec<Gap>,
+}
+
+impl Manifest {
+    pub fn total_messages(&self) -> u64 {
+        self.messages.values().sum()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.segments.iter().map(|s| s.bytes).sum()
+    }
+}
+
+fn segment_name(n: usize) -> String {
+    format!("part-{n:05}.ndjson")
+}
+
+/// Free bytes on the filesystem holding `path`, if it can be told.
+pub type FreeSpace = fn(&Path) -> Option<u64>;
+
+/// What to record and how.
+#[derive(Debug, Clone)]
+pub struct RecordingSpec {
+    pub ticker: String,
+    pub channels: Vec<String>,
+    pub network: String,
+    /// Segment size that starts a new file.
+    pub rotate_bytes: u64,
+    /// Stop once the disk has less than this free.
+    pub min_free_bytes: u64,
+}
+
+/// Appends records to a recording directory, rotating segments and
+/// keeping the manifest current.
+pub struct Recorder {
+    dir: PathBuf,
+    manifest: Manifest,
+    writer: BufWriter<File>,
+    rotate_bytes: u64,
+    min_free_bytes: u64,
+    free_space: FreeSpace,
+    unflushed: u64,
+    checkpoint_ms: i64,
+}
+
+impl Recorder {
+    /// Start a recording in `dir`, created if missing. Refuses a directory
+    /// that already holds one, and fails with `DISK_SPACE_LOW` when there
+    /// is less than `min_free_bytes` to start with.
+    pub fn create(
+        dir: &Path,
+        spec: &RecordingSpec,
+        free_space: FreeSpace,
+        now_ms: i64,
+    ) -> Result<Self> {
+        if dir.join(MANIFEST).exists() {
+            anyhow::bail!(
+                "{} already holds a recording. Pick another --out directory.",
+                dir.display()
+            );
+        }
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        check_free(dir, spec.min_free_bytes, free_space)?;
+
+        let manifest = Manifest {
+            format: RECORDING_FORMAT,
+            ticker: spec.ticker.clone(),
+            channels: spec.channels.clone(),
+            network: spec.network.clone(),
+            started_ms: now_ms,
+            ended_ms: None,
+            stop_reason: None,
+            messages: spec.channels.iter().map(|c| (c.clone(), 0)).collect(),
+            segments: Vec::new(),
+            gaps: Vec::new(),
+        };
+        let writer = open_segment(dir, 1)?;
+        let mut recorder = Self {
+            dir: dir.to_path_buf(),
+            manifest,
+            writer,
+            rotate_bytes: spec.rotate_bytes.max(1),
+            min_free_bytes: spec.min_free_bytes,
+            free_space,
+            unflushed: 0,
+            checkpoint_ms: now_ms,
+        };
+        recorder.manifest.segments.push(Segment {
+            file: segment_name(1),
+            messages: 0,
+            bytes: 0,
+            first_ms: None,
+            last_ms: None,
+        });
+        recorder.save_manifest()?;
+        Ok(recorder)
+    }
+
+    /// Append `record`, starting a new segment first if it would push the
+    /// current one past the rotation size. Fails with `DISK_SPACE_LOW` at a
+    /// checkpoint that finds too little space left; the caller should then
+    /// [`finish`](Self::finish).
+    pub fn write(&mut self, record: &Record) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        let len = line.len() as u64;
+
+        let current = self.current();
+        if current.bytes > 0 && current.bytes + len > self.rotate_bytes {
+            self.rotate()?;
+        }
+        self.writer
+            .write_all(&line)
+            .context("Failed to write to the recording")?;
+
+        let segment = self.current_mut();
+        segment.messages += 1;
+        segment.bytes += len;
+        segment.first_ms.get_or_insert(record.recv_ms);
+        segment.last_ms = Some(record.recv_ms);
+        *self
+            .manifest
+            .messages
+            .entry(record.channel.clone())
+            .or_default() += 1;
+        self.unflushed += 1;
+
+        if self.unflushed >= CHECKPOINT_MESSAGES
+            || record.recv_ms - self.checkpoint_ms >= CHECKPOINT_MS
+        {
+            self.checkpoint(record.recv_ms)?;
+        }
+        Ok(())
+    }
+
+    /// Note that nothing was received between `from_ms` and `to_ms`.
+    pub fn gap(&mut self, from_ms: i64, to_ms: i64) -> Result<()> {
+        self.manifest.gaps.push(Gap { from_ms, to_ms });
+        self.checkpoint(to_ms)
+    }
+
+    /// Flush what is buffered and close the manifest with `reason`.
+    pub fn finish(mut self, reason: StopReason, now_ms: i64) -> Result<Manifest> {
+        self.writer
+            .flush()
+            .context("Failed to flush the recording")?;
+        self.manifest.ended_ms = Some(now_ms);
+        self.manifest.stop_reason = Some(reason);
+        self.save_manifest()?;
+        Ok(self.manifest)
+    }
+
+    fn checkpoint(&mut self, now_ms: i64) -> Result<()> {
+        self.writer
+            .flush()
+            .context("Failed to flush the recording")?;
+        self.save_manifest()?;
+        self.unflushed = 0;
+        self.checkpoint_ms = now_ms;
+        check_free(&self.dir, self.min_free_bytes, self.free_space)
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.writer
+            .flush()
+            .context("Failed to flush the recording")?;
+        let next = self.manifest.segments.len() + 1;
+        self.writer = open_segment(&self.dir, next)?;
+        self.manifest.segments.push(Segment {
+            file: segment_name(next),
+            messages: 0,
+            bytes: 0,
+            first_ms: None,
+            last_ms: None,
+        });
+        self.save_manifest()
+    }
+
+    fn current(&self) -> &Segment {
+        self.manifest
+            .segments
+            .last()
+            .expect("recording has a segment")
+    }
+
+    fn current_mut(&mut self) -> &mut Segment {
+        self.manifest
+            .segments
+            .last_mut()
+            .expect("recording has a segment")
+    }
+
+    fn save_manifest(&self) -> Result<()> {
+        let tmp = self.dir.join(format!("{MANIFEST}.tmp"));
+        fs::write(&tmp, serde_json::to_vec_pretty(&self.manifest)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, self.dir.join(MANIFEST)).context("Failed to update the manifest")
+    }
+}
+
+fn open_segment(dir: &Path, n: usize) -> Result<BufWriter<File>> {
+    let path = dir.join(segment_name(n));
+    let file = File::options()
+        .create_new(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+fn check_free(dir: &Path, min_free_bytes: u64, free_space: FreeSpace) -> Result<()> {
+    match free_space(dir) {
+        Some(free) if free < min_free_bytes => Err(AtlasError::DiskSpaceLow(format!(
+            "{} free on the disk holding {}, below the {} minimum",
+            format_bytes(free),
+            dir.display(),
+            format_bytes(min_free_bytes)
+        ))
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// Free space from `df -Pk`. `None` where `df` is unavailable, which turns
+/// the guard off.
+pub fn disk_free(path: &Path) -> Option<u64> {
+    let output = std::process::Command::new("df")
+        .arg("-Pk")
+        .arg(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_df(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Available bytes from POSIX `df -Pk` output: the fourth column of the
+/// second line, in KiB.
+fn parse_df(stdout: &str) -> Option<u64> {
+    let line = stdout.lines().nth(1)?;
+    let kib: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Read and check a recording: every segment in the manifest must exist
+/// with the recorded size and message count, parse line by line, and be in
+/// time order, and the per-channel counts must add up. Fails with
+/// `RECORDING_INVALID` naming each problem.
+pub fn verify(dir: &Path) -> Result<Manifest> {
+    let path = dir.join(MANIFEST);
+    let raw = fs::read(&path).map_err(|e| {
+        AtlasError::RecordingInvalid(format!("cannot read {}: {e}", path.display()))
+    })?;
+    let manifest: Manifest = serde_json::from_slice(&raw)
+        .map_err(|e| AtlasError::RecordingInvalid(format!("{MANIFEST} is malformed: {e}")))?;
+    if manifest.format != RECORDING_FORMAT {
+        return Err(AtlasError::RecordingInvalid(format!(
+            "format {} is not supported (expected {RECORDING_FORMAT})",
+            manifest.format
+        ))
+        .into());
+    }
+
+    let mut problems = Vec::new();
+    let mut counted: BTreeMap<String, u64> = BTreeMap::new();
+    let mut last_ms = i64::MIN;
+    for segment in &manifest.segments {
+        let path = dir.join(&segment.file);
+        let Ok(meta) = fs::metadata(&path) else {
+            problems.push(format!("{} is missing", segment.file));
+            continue;
+        };
+        if meta.len() != segment.bytes {
+            problems.push(format!(
+                "{} is {} bytes, manifest says {}",
+                segment.file,
+                meta.len(),
+                segment.bytes
+            ));
+            continue;
+        }
+        let mut messages = 0u64;
+        for (n, line) in BufReader::new(File::open(&path)?).lines().enumerate() {
+            let record: Record = match line
+                .map_err(anyhow::Error::from)
+                .and_then(|l| serde_json::from_str(&l).map_err(anyhow::Error::from))
+            {
+                Ok(r) => r,
+                Err(e) => {
+                    problems.push(format!("{} line {}: {e}", segment.file, n + 1));
+                    break;
+                }
+            };
+            if record.recv_ms < last_ms {
+                problems.push(format!(
+                    "{} line {} is out of time order",
+                    segment.file,
+                    n + 1
+                ));
+                break;
+            }
+            last_ms = record.recv_ms;
+            *counted.entry(record.channel).or_default() += 1;
+            messages += 1;
+        }
+        if messages != segment.messages {
+            problems.push(format!(
+                "{} holds {messages} messages, manifest says {}",
+                segment.file, segment.messages
+            ));
+        }
+    }
+    let expected: BTreeMap<_, _> = manifest
+        .messages
+        .iter()
+        .filter(|(_, n)| **n > 0)
+        .map(|(c, n)| (c.clone(), *n))
+        .collect();
+    if problems.is_empty() && counted != expected {
+        problems.push(format!(
+            "per-channel counts {counted:?} do not match the manifest's {expected:?}"
+        ));
+    }
+
+    if problems.is_empty() {
+        Ok(manifest)
+    } else {
+        Err(AtlasError::RecordingInvalid(problems.join("; ")).into())
+    }
+}
+
+/// Records of a verified recording in order, segment after segment.
+pub fn records(dir: &Path, manifest: &Manifest) -> impl Iterator<Item = Result<Record>> {
+    let paths: Vec<PathBuf> = manifest
+        .segments
+        .iter()
+        .map(|s| dir.join(&s.file))
+        .collect();
+    paths.into_iter().flat_map(|path| {
+        let lines: Box<dyn Iterator<Item = Result<Record>>> = match File::open(&path) {
+            Ok(file) => Box::new(
+                BufReader::new(file)
+                    .lines()
+                    .map(|line| -> Result<Record> { Ok(serde_json::from_str(&line?)?) }),
+            ),
+            Err(e) => Box::new(std::iter::once(Err(
+                anyhow::Error::from(e).context(format!("Failed to open {}", path.display()))
+            ))),
+        };
+        lines
+    })
+}
+
+/// `100mb`, `512kb`, `2gb` or a plain byte count. Units are powers of 1024.
+pub fn parse_byte_size(s: &str) -> Result<u64, String> {
+    let lower = s.trim().to_ascii_lowercase();
+    let split = lower
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(lower.len());
+    let (number, unit) = lower.split_at(split);
+    let multiplier: u64 = match unit.trim() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        other => return Err(format!("unknown size unit '{other}' (use b, kb, mb or gb)")),
+    };
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("'{s}' is not a size like 100mb"))?;
+    if !number.is_finite() || number <= 0.0 {
+        return Err(format!("'{s}' must be greater than zero"));
+    }
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// Replay speed: `10x`, `0.5`, or `max` for no pacing (returned as
+/// `None`).
+pub fn parse_speed(s: &str) -> Result<Option<f64>, String> {
+    let lower = s.trim().to_ascii_lowercase();
+    if lower == "max" {
+        return Ok(None);
+    }
+    let speed: f64 = lower
+        .strip_suffix('x')
+        .unwrap_or(&lower)
+        .parse()
+        .map_err(|_| format!("'{s}' is not a speed like 10x or max"))?;
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err(format!("speed '{s}' must be greater than zero"));
+    }
+    Ok(Some(speed))
+}
+
+/// `104857600` → `100.0 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("atlas-recording-{name}-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn plenty(_: &Path) -> Option<u64> {
+        Some(u64::MAX)
+    }
+
+    fn full(_: &Path) -> Option<u64> {
+        Some(10 << 20)
+    }
+
+    fn trade(recv_ms: i64) -> Record {
+        Record {
+            recv_ms,
+            channel: "trades".into(),
+            event: json!({"event": "trade", "symbol": "ETH", "price": "3500.1", "size": "0.25",
+                "side": "buy", "timestamp": recv_ms - 3}),
+        }
+    }
+
+    fn spec(channels: &[&str], rotate_bytes: u64, min_free_bytes: u64) -> RecordingSpec {
+        RecordingSpec {
+            ticker: "ETH".into(),
+            channels: channels.iter().map(|c| c.to_string()).collect(),
+            network: "mainnet".into(),
+            rotate_bytes,
+            min_free_bytes,
+        }
+    }
+
+    fn recorder(dir: &Path, rotate_bytes: u64, free_space: FreeSpace) -> Recorder {
+        let spec = spec(&["book", "trades"], rotate_bytes, 1 << 30);
+        Recorder::create(dir, &spec, free_space, 1_000).unwrap()
+    }
+
+    #[test]
+    fn test_rotates_and_replays_in_order() {
+        let dir = temp_dir("rotate");
+        let line_len = serde_json::to_vec(&trade(20_000)).unwrap().len() as u64 + 1;
+        let mut rec = recorder(&dir, line_len * 2, plenty);
+        for i in 0..5 {
+            rec.write(&trade(20_000 + i)).unwrap();
+        }
+        rec.gap(20_004, 29_000).unwrap();
+        let manifest = rec.finish(StopReason::Interrupted, 29_500).unwrap();
+
+        assert_eq!(manifest.segments.len(), 3);
+        assert_eq!(
+            manifest
+                .segments
+                .iter()
+                .map(|s| s.messages)
+                .collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(manifest.messages["trades"], 5);
+        assert_eq!(manifest.messages["book"], 0);
+        assert_eq!(manifest.stop_reason, Some(StopReason::Interrupted));
+        assert_eq!(
+            manifest.gaps,
+            vec![Gap {
+                from_ms: 20_004,
+                to_ms: 29_000
+            }]
+        );
+
+        let verified = verify(&dir).unwrap();
+        assert_eq!(verified, manifest);
+        let replayed: Vec<Record> = records(&dir, &verified).map(Result::unwrap).collect();
+        assert_eq!(replayed.len(), 5);
+        assert_eq!(replayed[4], trade(20_004));
+
+        // A second recording in the same place would mix the two
+        assert!(Recorder::create(&dir, &spec(&["trades"], 1, 0), plenty, 0).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_segments() {
+        let dir = temp_dir("tamper");
+        let mut rec = recorder(&dir, 1 << 20, plenty);
+        for i in 0..3 {
+            rec.write(&trade(2_000 + i)).unwrap();
+        }
+        rec.finish(StopReason::StreamClosed, 3_000).unwrap();
+
+        let segment = dir.join(segment_name(1));
+        let body = fs::read_to_string(&segment).unwrap();
+        let dropped: String = body.lines().skip(1).map(|l| format!("{l}\n")).collect();
+        fs::write(&segment, dropped).unwrap();
+        let err = verify(&dir).unwrap_err();
+        let atlas = err.downcast_ref::<AtlasError>().unwrap();
+        assert_eq!(atlas.detail().code, "RECORDING_INVALID");
+        assert!(err.to_string().contains("part-00001.ndjson is"), "{err}");
+
+        fs::remove_file(&segment).unwrap();
+        assert!(verify(&dir).unwrap_err().to_string().contains("missing"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_disk_guard_stops_at_checkpoint() {
+        let dir = temp_dir("disk");
+        let err = Recorder::create(&dir, &spec(&["trades"], 1 << 20, 1 << 30), full, 0)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.downcast_ref::<AtlasError>().unwrap().detail().code,
+            "DISK_SPACE_LOW"
+        );
+
+        // Space runs out mid-recording: the write that checkpoints fails,
+        // and what was written is still a valid recording
+        let mut rec =
+            Recorder::create(&dir, &spec(&["trades"], 1 << 20, 1 << 20), plenty, 0).unwrap();
+        rec.write(&trade(10)).unwrap();
+        rec.free_space = full;
+        rec.min_free_bytes = 1 << 30;
+        assert!(rec.write(&trade(CHECKPOINT_MS + 10)).is_err());
+        let manifest = rec
+            .finish(StopReason::DiskSpace, CHECKPOINT_MS + 20)
+            .unwrap();
+        assert_eq!(manifest.total_messages(), 2);
+        assert_eq!(
+            verify(&dir).unwrap().stop_reason,
+            Some(StopReason::DiskSpace)
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_sizes_and_speeds() {
+        assert_eq!(parse_byte_size("100mb"), Ok(100 << 20));
+        assert_eq!(parse_byte_size("1.5GB"), Ok(3 << 29));
+        assert_eq!(parse_byte_size("4096"), Ok(4096));
+        assert!(parse_byte_size("10tb").is_err());
+        assert!(parse_byte_size("0mb").is_err());
+
+        assert_eq!(parse_speed("10x"), Ok(Some(10.0)));
+        assert_eq!(parse_speed("0.5"), Ok(Some(0.5)));
+        assert_eq!(parse_speed("MAX"), Ok(None));
+        assert!(parse_speed("0x").is_err());
+        assert!(parse_speed("fast").is_err());
+
+        let df = "Filesystem 1024-blocks Used Available Capacity Mounted on\n\
+                  /dev/sda1 102400 2048 100352 2% /\n";
+        assert_eq!(parse_df(df), Some(100_352 * 1024));
+        assert_eq!(format_bytes(100 << 20), "100.0 MiB");
+    }
+}
@@ -1,4 +1,12 @@
-use crate::config::AppConfig;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::config::{AppConfig, TradingConfig};
+use crate::db::AtlasDb;
+use crate::depth::ImpactEstimate;
+use crate::error::{AtlasError, AtlasResult, ExchangeRejectionKind};
+use crate::traits::PerpModule;
+use crate::types::{Position, Side};
 
 /// Input for calculating a risk-managed position.
 #[derive(Debug, Clone)]
@@ -283,10 +291,406 @@ pub fn format_risk_summary(config: &AppConfig, input: &RiskInput, output: &RiskO
     )
 }
 
+// ═══════════════════════════════════════════════════════════════════════
+//  PRE-TRADE GUARD — enforces `trading` limits at order time
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Account state the pre-trade guard evaluates an order against.
+#[derive(Debug, Clone, Default)]
+pub struct AccountSnapshot {
+    /// Total account value in USDC.
+    pub account_value: f64,
+    /// Sum of `|size| × mark` over open positions.
+    pub open_notional: f64,
+    /// Realized PnL (net of fees) since 00:00 UTC, from the local DB.
+    pub realized_pnl_today: f64,
+}
+
+/// Check an order that opens or adds exposure against the `trading` limits.
+///
+/// Checks run in order: daily loss, order notional, resulting leverage.
+/// The first breached limit is returned as `AtlasError::RiskBlocked`.
+/// Without account value the leverage is unbounded, so any order breaches
+/// `max_leverage`. Closing orders should not be passed through here — they
+/// only reduce risk (see [`reduces_position`]).
+pub fn check_trade_limits(
+    limits: &TradingConfig,
+    order_notional: f64,
+    account: &AccountSnapshot,
+) -> AtlasResult<()> {
+    if let Some(max_loss) = limits.daily_loss_limit {
+        let loss = -account.realized_pnl_today;
+        if loss >= max_loss {
+            return Err(AtlasError::RiskBlocked {
+                limit: "daily_loss_limit".into(),
+                value: loss,
+                max: max_loss,
+            });
+        }
+    }
+
+    if let Some(max_notional) = limits.max_notional {
+        if order_notional > max_notional {
+            return Err(AtlasError::RiskBlocked {
+                limit: "max_notional".into(),
+                value: order_notional,
+                max: max_notional,
+            });
+        }
+    }
+
+    if let Some(max_leverage) = limits.max_leverage {
+        let leverage = if account.account_value > 0.0 {
+            (account.open_notional + order_notional) / account.account_value
+        } else {
+            f64::INFINITY
+        };
+        if leverage > max_leverage {
+            return Err(AtlasError::RiskBlocked {
+                limit: "max_leverage".into(),
+                value: leverage,
+                max: max_leverage,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether an order on `side` for `size` only shrinks `position`: the
+/// closing side, and no larger than the position, so it can't open or
+/// flip one. Such orders skip [`check_trade_limits`].
+pub fn reduces_position(position: Option<&Position>, side: &Side, size: Decimal) -> bool {
+    position.is_some_and(|p| !p.size.is_zero() && *side == p.closing_side() && size <= p.size.abs())
+}
+
+/// What an order adds to the account, for [`check_trade_limits`]:
+/// `(order_notional, open_notional)` at `price`. `None` when it only
+/// reduces the open position. An order that crosses through the position
+/// (a flip) adds only the part beyond it, and the position it replaces
+/// leaves the open notional.
+pub fn order_exposure(
+    positions: &[Position],
+    coin: &str,
+    side: &Side,
+    size: Decimal,
+    price: f64,
+) -> Option<(f64, f64)> {
+    let position = positions
+        .iter()
+        .find(|p| p.symbol.eq_ignore_ascii_case(coin) && !p.size.is_zero());
+    if reduces_position(position, side, size) {
+        return None;
+    }
+    let replaced = position.filter(|p| *side == p.closing_side());
+    let added = size - replaced.map_or(Decimal::ZERO, |p| p.size.abs());
+    let open_notional = positions
+        .iter()
+        .filter(|p| !replaced.is_some_and(|r| r.symbol == p.symbol))
+        .map(|p| {
+            let px = p.mark_price.or(p.entry_price).unwrap_or_default();
+            (p.size.abs() * px).to_f64().unwrap_or(0.0)
+        })
+        .sum();
+    Some((added.to_f64().unwrap_or(0.0) * price, open_notional))
+}
+
+/// Run the `trading` risk limits against an order on `perp`, with its
+/// positions and balances and today's realized PnL from the local DB.
+/// No-op without limits, and for an order that only reduces a position.
+pub async fn check_order_risk<P: PerpModule + ?Sized>(
+    perp: &P,
+    limits: &TradingConfig,
+    coin: &str,
+    side: &Side,
+    size: Decimal,
+    price: f64,
+) -> AtlasResult<()> {
+    if !limits.has_risk_limits() {
+        return Ok(());
+    }
+    let positions = perp.positions().await?;
+    let Some((order_notional, open_notional)) = order_exposure(&positions, coin, side, size, price)
+    else {
+        return Ok(());
+    };
+    let account_value = perp
+        .balances()
+        .await?
+        .iter()
+        .map(|b| b.total.to_f64().unwrap_or(0.0))
+        .sum();
+    let realized_pnl_today = match limits.daily_loss_limit {
+        Some(_) => {
+            let midnight_ms = chrono::Utc::now()
+                .date_naive()
+                .and_hms_opt(0, 0, 0)
+                .map(|t| t.and_utc().timestamp_millis())
+                .unwrap_or(0);
+            AtlasDb::open()
+                .and_then(|db| db.realized_pnl_since(midnight_ms))
+                .map_err(|e| AtlasError::Other(format!("Cannot read today's PnL: {e}")))?
+        }
+        None => 0.0,
+    };
+    let account = AccountSnapshot {
+        account_value,
+        open_notional,
+        realized_pnl_today,
+    };
+    check_trade_limits(limits, order_notional, &account)
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  ORDER PROTECTIONS — sanity checks on market order prices
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Refuse a market order whose slippage exceeds `trading.max_slippage`.
+pub fn check_slippage(limits: &TradingConfig, slippage: f64) -> AtlasResult<()> {
+    match limits.max_slippage {
+        Some(max) if slippage > max => Err(AtlasError::SlippageTooHigh { slippage, max }),
+        _ => Ok(()),
+    }
+}
+
+/// Limit price of a market order: the mid moved `slippage` against the taker.
+pub fn market_limit_price(mid: f64, is_buy: bool, slippage: f64) -> f64 {
+    if is_buy {
+        mid * (1.0 + slippage)
+    } else {
+        mid * (1.0 - slippage)
+    }
+}
+
+/// Refuse a limit price further than `trading.price_band` from
+/// `reference` — the last 1m candle close — so an order priced off a bad
+/// mid never reaches the exchange. Skipped without a reference price.
+pub fn check_price_band(
+    limits: &TradingConfig,
+    coin: &str,
+    limit_price: f64,
+    reference: f64,
+) -> AtlasResult<()> {
+    let Some(max) = limits.price_band else {
+        return Ok(());
+    };
+    if reference <= 0.0 {
+        return Ok(());
+    }
+    let deviation = (limit_price - reference).abs() / reference;
+    if deviation > max {
+        return Err(AtlasError::PriceBandExceeded {
+            coin: coin.to_string(),
+            limit_price,
+            reference,
+            deviation,
+            max,
+        });
+    }
+    Ok(())
+}
+
+/// The order protections a module applies to every market order and close
+/// just before signing it: `max_slippage`, then the limit price (mid moved
+/// `slippage` against the taker) against `price_band` around `reference`,
+/// the last 1m close (0 when unknown: the band is skipped).
+pub fn check_market_order(
+    limits: &TradingConfig,
+    coin: &str,
+    side: &Side,
+    mid: f64,
+    slippage: f64,
+    reference: f64,
+) -> AtlasResult<()> {
+    check_slippage(limits, slippage)?;
+    if mid <= 0.0 {
+        return Ok(());
+    }
+    let limit_price = market_limit_price(mid, *side == Side::Buy, slippage);
+    check_price_band(limits, coin, limit_price, reference)
+}
+
+/// Refuse a market order whose estimated book impact exceeds `max_bps`
+/// (`--max-impact-bps`). An order the visible book can't fill is refused
+/// too: its impact can't be bounded.
+pub fn check_impact(coin: &str, estimate: &ImpactEstimate, max_bps: f64) -> AtlasResult<()> {
+    let Some(impact) = estimate.impact_bps else {
+        return Err(AtlasError::NoLiquidity(format!(
+            "{coin}: visible book covers {}% of {} — can't bound the impact under {max_bps} bps",
+            estimate.coverage_pct.round_dp(1).normalize(),
+            estimate.size.normalize()
+        )));
+    };
+    let impact_bps = impact.to_f64().unwrap_or(f64::INFINITY);
+    if impact_bps > max_bps {
+        return Err(AtlasError::ImpactTooHigh {
+            coin: coin.to_string(),
+            impact_bps,
+            max_bps,
+        });
+    }
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  REDUCE-ONLY CHECK — catches orders the exchange would reject
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Size a reduce-only order may be sent with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReduceOnly {
+    pub size: Decimal,
+    /// Set when the size was capped at the position.
+    pub warning: Option<String>,
+}
+
+/// Check a reduce-only order against the open `position` in `coin`.
+///
+/// The order must face the other way to an open position. A size above
+/// the position is capped at it, or refused when `strict`. Refusals are
+/// `ReduceOnlyViolation` rejections, so they read like the exchange's own.
+pub fn check_reduce_only(
+    coin: &str,
+    position: Option<&Position>,
+    side: Side,
+    size: Decimal,
+    strict: bool,
+) -> AtlasResult<ReduceOnly> {
+    let reject = |message: String| AtlasError::OrderRejected {
+        kind: ExchangeRejectionKind::ReduceOnlyViolation,
+        message,
+    };
+    let order = format!(
+        "reduce-only {} {size} {coin}",
+        side.to_string().to_lowercase()
+    );
+
+    let Some(pos) = position.filter(|p| !p.size.is_zero()) else {
+        return Err(reject(format!("{order}: no open {coin} position")));
+    };
+    let held = pos.size.abs();
+    let direction = if pos.side == Side::Buy {
+        "long"
+    } else {
+        "short"
+    };
+    if pos.side == side {
+        return Err(reject(format!(
+            "{order} would add to the {held} {coin} {direction}"
+        )));
+    }
+    if size <= held {
+        return Ok(ReduceOnly {
+            size,
+            warning: None,
+        });
+    }
+    if strict {
+        return Err(reject(format!(
+            "{order} exceeds the {held} {coin} {direction}"
+        )));
+    }
+    Ok(ReduceOnly {
+        size: held,
+        warning: Some(format!("{order} capped at the {held} {coin} {direction}")),
+    })
+}
+
+/// The one IOC order that reverses a position (`atlas hl perp flip`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlipOrder {
+    /// Opposite to the position.
+    pub side: Side,
+    /// `|current| + target`, so there is no flat window.
+    pub size: Decimal,
+    /// Size of the position after the flip.
+    pub target: Decimal,
+}
+
+/// Plan a flip of `position` into a `target`-sized one the other way
+/// (default: the current size), both rounded down to `sz_decimals` the way
+/// the exchange will.
+pub fn flip_order(
+    position: &Position,
+    target: Option<Decimal>,
+    sz_decimals: u32,
+) -> AtlasResult<FlipOrder> {
+    let round = |d: Decimal| d.round_dp_with_strategy(sz_decimals, RoundingStrategy::ToZero);
+    let current = position.size.abs();
+    let target = round(target.unwrap_or(current));
+    let size = round(current + target);
+    if target.is_zero() || size.is_zero() {
+        return Err(AtlasError::InvalidSize(format!(
+            "Flip size rounds to zero for {}",
+            position.symbol
+        )));
+    }
+    Ok(FlipOrder {
+        side: position.closing_side(),
+        size,
+        target,
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  TRIGGER CHECK — which way a stop order fires
+// ═══════════════════════════════════════════════════════════════════════
+
+/// What a trigger order does once the mark crosses its trigger price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerRole {
+    /// Buy above / sell below the market: a stop entry, or a stop-loss
+    /// when reduce-only.
+    Stop,
+    /// Buy below / sell above the market: only as a reduce-only
+    /// take-profit.
+    TakeProfit,
+}
+
+/// Check a trigger price against the current `mid` in `coin`.
+///
+/// A stop entry must trigger through the market in the order's direction:
+/// a buy above the mid, a sell below it. A reduce-only trigger may sit on
+/// either side — through the market it is a stop-loss, the other way a
+/// take-profit. A trigger at the mid would fire at once and is refused.
+pub fn check_trigger(
+    coin: &str,
+    side: Side,
+    trigger: Decimal,
+    mid: Decimal,
+    reduce_only: bool,
+) -> AtlasResult<TriggerRole> {
+    let stop_side = match side {
+        Side::Buy => trigger > mid,
+        Side::Sell => trigger < mid,
+    };
+    if stop_side {
+        return Ok(TriggerRole::Stop);
+    }
+    let order = format!("{} stop on {coin}", side.to_string().to_lowercase());
+    if trigger == mid {
+        return Err(AtlasError::InvalidTrigger(format!(
+            "{order}: trigger {trigger} is at the mid and would fire immediately"
+        )));
+    }
+    if reduce_only {
+        return Ok(TriggerRole::TakeProfit);
+    }
+    let (want, got) = match side {
+        Side::Buy => ("above", "below"),
+        Side::Sell => ("below", "above"),
+    };
+    Err(AtlasError::InvalidTrigger(format!(
+        "{order}: trigger {trigger} is {got} the mid {mid}; it must be {want} it \
+         (use --reduce-only for a take-profit)"
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::AppConfig;
+    use crate::types::Protocol;
 
     fn default_input() -> RiskInput {
         RiskInput {
@@ -475,6 +879,402 @@ mod tests {
         let output = calculate_position(&config, &RiskConfig::default(), &input);
         assert_eq!(output.size, 0.0);
     }
+
+    fn limits() -> TradingConfig {
+        TradingConfig {
+            max_notional: Some(50_000.0),
+            max_leverage: Some(10.0),
+            daily_loss_limit: Some(500.0),
+            ..TradingConfig::default()
+        }
+    }
+
+    fn account() -> AccountSnapshot {
+        AccountSnapshot {
+            account_value: 10_000.0,
+            open_notional: 20_000.0,
+            realized_pnl_today: -100.0,
+        }
+    }
+
+    fn blocked_limit(result: AtlasResult<()>) -> String {
+        match result {
+            Err(AtlasError::RiskBlocked { limit, .. }) => limit,
+            other => panic!("expected RiskBlocked, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_guard_allows_within_limits() {
+        assert!(check_trade_limits(&limits(), 10_000.0, &account()).is_ok());
+    }
+
+    #[test]
+    fn test_guard_no_limits_configured() {
+        let cfg = TradingConfig::default();
+        assert!(!cfg.has_risk_limits());
+        let mut acct = account();
+        acct.realized_pnl_today = -1_000_000.0;
+        assert!(check_trade_limits(&cfg, 1e12, &acct).is_ok());
+    }
+
+    #[test]
+    fn test_guard_blocks_max_notional() {
+        let err = check_trade_limits(&limits(), 60_000.0, &account());
+        assert_eq!(blocked_limit(err), "max_notional");
+    }
+
+    #[test]
+    fn test_guard_blocks_resulting_leverage() {
+        // (20k open + 45k new) / 10k = 6.5x with a 5x cap
+        let cfg = TradingConfig {
+            max_leverage: Some(5.0),
+            ..limits()
+        };
+        match check_trade_limits(&cfg, 45_000.0, &account()) {
+            Err(AtlasError::RiskBlocked { limit, value, max }) => {
+                assert_eq!(limit, "max_leverage");
+                assert!((value - 6.5).abs() < 1e-9);
+                assert_eq!(max, 5.0);
+            }
+            other => panic!("expected RiskBlocked, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_guard_blocks_daily_loss() {
+        let mut acct = account();
+        acct.realized_pnl_today = -500.0;
+        let err = check_trade_limits(&limits(), 100.0, &acct);
+        assert_eq!(blocked_limit(err), "daily_loss_limit");
+    }
+
+    #[test]
+    fn test_guard_blocks_leverage_without_equity() {
+        let mut acct = account();
+        acct.account_value = 0.0;
+        let err = check_trade_limits(&limits(), 1_000.0, &acct);
+        assert_eq!(blocked_limit(err), "max_leverage");
+
+        acct.account_value = -50.0;
+        let err = check_trade_limits(&limits(), 1_000.0, &acct);
+        assert_eq!(blocked_limit(err), "max_leverage");
+
+        let no_leverage_cap = TradingConfig {
+            max_leverage: None,
+            ..limits()
+        };
+        assert!(check_trade_limits(&no_leverage_cap, 1_000.0, &acct).is_ok());
+    }
+
+    #[test]
+    fn test_slippage_cap() {
+        let cfg = TradingConfig::default();
+        assert!(check_slippage(&cfg, 0.05).is_ok());
+        assert!(check_slippage(&cfg, 0.10).is_ok());
+        match check_slippage(&cfg, 0.25) {
+            Err(AtlasError::SlippageTooHigh { slippage, max }) => {
+                assert_eq!(slippage, 0.25);
+                assert_eq!(max, 0.10);
+            }
+            other => panic!("expected SlippageTooHigh, got {other:?}"),
+        }
+
+        let off = TradingConfig {
+            max_slippage: None,
+            ..TradingConfig::default()
+        };
+        assert!(check_slippage(&off, 0.9).is_ok());
+    }
+
+    #[test]
+    fn test_market_limit_price() {
+        assert!((market_limit_price(100.0, true, 0.05) - 105.0).abs() < 1e-9);
+        assert!((market_limit_price(100.0, false, 0.05) - 95.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_price_band_allows_healthy_mid() {
+        let cfg = TradingConfig::default();
+        // Mid in line with the last close, 5% slippage: 5% away
+        let px = market_limit_price(3000.0, true, 0.05);
+        assert!(check_price_band(&cfg, "ETH", px, 3001.0).is_ok());
+        let px = market_limit_price(3000.0, false, 0.10);
+        assert!(check_price_band(&cfg, "ETH", px, 2990.0).is_ok());
+    }
+
+    #[test]
+    fn test_price_band_blocks_bad_mid() {
+        let cfg = TradingConfig::default();
+        // A degraded feed reports 3600 while the market last closed at 3000
+        let px = market_limit_price(3600.0, true, 0.05);
+        match check_price_band(&cfg, "ETH", px, 3000.0) {
+            Err(AtlasError::PriceBandExceeded {
+                coin,
+                limit_price,
+                reference,
+                deviation,
+                max,
+            }) => {
+                assert_eq!(coin, "ETH");
+                assert_eq!(limit_price, px);
+                assert_eq!(reference, 3000.0);
+                assert!((deviation - 0.26).abs() < 1e-9);
+                assert_eq!(max, 0.15);
+            }
+            other => panic!("expected PriceBandExceeded, got {other:?}"),
+        }
+
+        // Same on the sell side, with a mid that collapsed
+        let px = market_limit_price(2400.0, false, 0.01);
+        assert!(check_price_band(&cfg, "ETH", px, 3000.0).is_err());
+    }
+
+    #[test]
+    fn test_impact_guard() {
+        use crate::depth::walk_book;
+        use crate::types::{BookLevel, OrderBook};
+
+        let level = |price: i64, size: i64| BookLevel {
+            price: Decimal::from(price),
+            size: Decimal::from(size),
+            count: None,
+        };
+        // Mid 1000; asks 1001 × 1, 1010 × 1
+        let book = OrderBook {
+            symbol: "ETH".into(),
+            protocol: Protocol::Hyperliquid,
+            bids: vec![level(999, 5)],
+            asks: vec![level(1001, 1), level(1010, 1)],
+            timestamp_ms: None,
+        };
+        let small = walk_book(&book, Side::Buy, Decimal::ONE); // 10 bps
+        assert!(check_impact("ETH", &small, 10.0).is_ok());
+        let large = walk_book(&book, Side::Buy, Decimal::TWO); // 55 bps
+        match check_impact("ETH", &large, 20.0) {
+            Err(AtlasError::ImpactTooHigh {
+                impact_bps,
+                max_bps,
+                ..
+            }) => {
+                assert!((impact_bps - 55.0).abs() < 1e-9);
+                assert_eq!(max_bps, 20.0);
+            }
+            other => panic!("expected ImpactTooHigh, got {other:?}"),
+        }
+
+        let thin = walk_book(&book, Side::Buy, Decimal::from(4));
+        let err = check_impact("ETH", &thin, 1000.0).unwrap_err();
+        assert!(matches!(err, AtlasError::NoLiquidity(_)), "{err:?}");
+        assert!(err.to_string().contains("covers 50%"), "{err}");
+    }
+
+    #[test]
+    fn test_price_band_disabled_or_without_reference() {
+        let off = TradingConfig {
+            price_band: None,
+            ..TradingConfig::default()
+        };
+        assert!(check_price_band(&off, "ETH", 9000.0, 3000.0).is_ok());
+        assert!(check_price_band(&TradingConfig::default(), "ETH", 9000.0, 0.0).is_ok());
+    }
+
+    /// A position as the module reports it from the clearinghouse state:
+    /// unsigned size, direction in `side`.
+    fn position(side: Side, size: i64) -> Position {
+        Position {
+            protocol: Protocol::Hyperliquid,
+            symbol: "ETH".into(),
+            side,
+            size: Decimal::from(size),
+            entry_price: Some(Decimal::from(3000)),
+            mark_price: None,
+            unrealized_pnl: None,
+            leverage: Some(5),
+            margin: None,
+            liquidation_price: None,
+            margin_mode: None,
+        }
+    }
+
+    #[test]
+    fn test_reduce_only_long() {
+        let long = position(Side::Buy, 2);
+        let ok = check_reduce_only("ETH", Some(&long), Side::Sell, Decimal::ONE, false).unwrap();
+        assert_eq!(ok.size, Decimal::ONE);
+        assert!(ok.warning.is_none());
+
+        let capped =
+            check_reduce_only("ETH", Some(&long), Side::Sell, Decimal::from(5), false).unwrap();
+        assert_eq!(capped.size, Decimal::from(2));
+        assert_eq!(
+            capped.warning.as_deref(),
+            Some("reduce-only sell 5 ETH capped at the 2 ETH long")
+        );
+
+        let err =
+            check_reduce_only("ETH", Some(&long), Side::Buy, Decimal::ONE, false).unwrap_err();
+        assert!(
+            err.to_string().contains("would add to the 2 ETH long"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_reduce_only_short_strict() {
+        let short = position(Side::Sell, 3);
+        let ok = check_reduce_only("ETH", Some(&short), Side::Buy, Decimal::from(3), true).unwrap();
+        assert_eq!(ok.size, Decimal::from(3));
+
+        let err =
+            check_reduce_only("ETH", Some(&short), Side::Buy, Decimal::from(4), true).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                AtlasError::OrderRejected {
+                    kind: ExchangeRejectionKind::ReduceOnlyViolation,
+                    ..
+                }
+            ),
+            "{err:?}"
+        );
+        assert!(err.to_string().contains("exceeds the 3 ETH short"), "{err}");
+    }
+
+    #[test]
+    fn test_flip_short_reported_unsigned() {
+        // Hyperliquid reports a 2 ETH short as size 2, side Sell
+        let short = position(Side::Sell, 2);
+        assert!(!short.is_long());
+        let flip = flip_order(&short, None, 4).unwrap();
+        assert_eq!(flip.side, Side::Buy);
+        assert_eq!(flip.size, Decimal::from(4));
+        assert_eq!(flip.target, Decimal::from(2));
+
+        let long = position(Side::Buy, 2);
+        let flip = flip_order(&long, Some(Decimal::new(15, 1)), 4).unwrap();
+        assert_eq!(flip.side, Side::Sell);
+        assert_eq!(flip.size, Decimal::new(35, 1));
+
+        let err = flip_order(&short, Some(Decimal::new(1, 3)), 2).unwrap_err();
+        assert!(matches!(err, AtlasError::InvalidSize(_)), "{err:?}");
+    }
+
+    #[test]
+    fn test_reduces_position_only_on_the_closing_side() {
+        let short = position(Side::Sell, 2);
+        assert!(reduces_position(Some(&short), &Side::Buy, Decimal::from(2)));
+        assert!(reduces_position(Some(&short), &Side::Buy, Decimal::ONE));
+        assert!(
+            !reduces_position(Some(&short), &Side::Buy, Decimal::from(3)),
+            "flips it"
+        );
+        assert!(!reduces_position(Some(&short), &Side::Sell, Decimal::ONE));
+        assert!(!reduces_position(None, &Side::Buy, Decimal::ONE));
+    }
+
+    #[test]
+    fn test_order_exposure_counts_only_what_a_flip_adds() {
+        let mut btc = position(Side::Buy, 1);
+        btc.symbol = "BTC".into();
+        btc.entry_price = Some(Decimal::from(60_000));
+        let positions = [position(Side::Sell, 2), btc];
+
+        // Reducing the short adds nothing
+        assert_eq!(
+            order_exposure(&positions, "eth", &Side::Buy, Decimal::ONE, 3000.0),
+            None
+        );
+        // Adding to it counts the whole order on top of both positions
+        assert_eq!(
+            order_exposure(&positions, "ETH", &Side::Sell, Decimal::ONE, 3000.0),
+            Some((3000.0, 66_000.0))
+        );
+        // Buying 5 flips the short to a 3 long, which replaces it
+        assert_eq!(
+            order_exposure(&positions, "ETH", &Side::Buy, Decimal::from(5), 3000.0),
+            Some((9000.0, 60_000.0))
+        );
+        assert_eq!(
+            order_exposure(&positions, "SOL", &Side::Buy, Decimal::from(10), 150.0),
+            Some((1500.0, 66_000.0))
+        );
+    }
+
+    #[test]
+    fn test_market_order_protections_use_the_closing_side() {
+        let cfg = TradingConfig::default();
+        let short = position(Side::Sell, 2);
+        // Closing a short buys: priced 10% above a mid that sits 6% over the
+        // last close, 16.6% off — outside the 15% band. Priced as a sell it
+        // would have been 4.6% off and passed.
+        let side = short.closing_side();
+        let err = check_market_order(&cfg, "ETH", &side, 3180.0, 0.10, 3000.0).unwrap_err();
+        assert!(
+            matches!(err, AtlasError::PriceBandExceeded { .. }),
+            "{err:?}"
+        );
+        assert!(check_market_order(&cfg, "ETH", &Side::Sell, 3180.0, 0.10, 3000.0).is_ok());
+
+        let err = check_market_order(&cfg, "ETH", &side, 3000.0, 0.2, 3000.0).unwrap_err();
+        assert!(matches!(err, AtlasError::SlippageTooHigh { .. }), "{err:?}");
+        // No reference close: only the slippage cap applies
+        assert!(check_market_order(&cfg, "ETH", &side, 9000.0, 0.05, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_reduce_only_without_position() {
+        let err = check_reduce_only("ETH", None, Side::Sell, Decimal::ONE, false).unwrap_err();
+        assert!(err.to_string().contains("no open ETH position"), "{err}");
+
+        let flat = position(Side::Buy, 0);
+        assert!(check_reduce_only("ETH", Some(&flat), Side::Sell, Decimal::ONE, false).is_err());
+    }
+
+    #[test]
+    fn test_trigger_side_matrix() {
+        let mid = Decimal::from(3500);
+        let above = Decimal::from(3600);
+        let below = Decimal::from(3400);
+        // (side, trigger, reduce_only) → role; None = refused
+        let cases = [
+            (Side::Buy, above, false, Some(TriggerRole::Stop)),
+            (Side::Buy, below, false, None),
+            (Side::Sell, below, false, Some(TriggerRole::Stop)),
+            (Side::Sell, above, false, None),
+            (Side::Buy, above, true, Some(TriggerRole::Stop)),
+            (Side::Buy, below, true, Some(TriggerRole::TakeProfit)),
+            (Side::Sell, below, true, Some(TriggerRole::Stop)),
+            (Side::Sell, above, true, Some(TriggerRole::TakeProfit)),
+            (Side::Buy, mid, false, None),
+            (Side::Sell, mid, true, None),
+        ];
+        for (side, trigger, reduce_only, want) in cases {
+            let got = check_trigger("ETH", side.clone(), trigger, mid, reduce_only).ok();
+            assert_eq!(
+                got, want,
+                "{side} trigger {trigger} reduce_only={reduce_only}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrong_side_trigger_error() {
+        let err = check_trigger(
+            "ETH",
+            Side::Buy,
+            Decimal::from(3400),
+            Decimal::from(3500),
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(err.detail().code, "INVALID_TRIGGER");
+        assert!(
+            err.to_string()
+                .contains("trigger 3400 is below the mid 3500; it must be above it"),
+            "{err}"
+        );
+    }
 }
 use std::collections::HashMap;
 
@@ -508,6 +1308,27 @@ pub struct RiskConfig {
     /// Per-asset risk overrides.
     #[serde(default)]
     pub asset_overrides: HashMap<String, AssetRiskOverride>,
+    /// Share of full Kelly that `risk suggest` bets (0.5 = half Kelly).
+    #[serde(default = "default_kelly_multiplier")]
+    pub kelly_multiplier: f64,
+    /// Highest account fraction `risk suggest` puts at risk under Kelly.
+    #[serde(default = "default_kelly_cap")]
+    pub kelly_cap: f64,
+    /// Closed trades needed before Kelly is used instead of a fixed fraction.
+    #[serde(default = "default_kelly_min_trades")]
+    pub kelly_min_trades: usize,
+}
+
+fn default_kelly_multiplier() -> f64 {
+    0.5
+}
+
+fn default_kelly_cap() -> f64 {
+    0.05
+}
+
+fn default_kelly_min_trades() -> usize {
+    20
 }
 
 /// Per-asset risk override.
@@ -529,6 +1350,9 @@ impl Default for RiskConfig {
             max_exposure_multiplier: 3.0,
             default_stop_pct: 0.02, // 2% stop-loss distance
             asset_overrides: HashMap::new(),
+            kelly_multiplier: default_kelly_multiplier(),
+            kelly_cap: default_kelly_cap(),
+            kelly_min_trades: default_kelly_min_trades(),
         }
     }
 }
@@ -0,0 +1,240 @@
+//! Recurring commands for `atlas schedule` (e.g. a daily DCA buy).
+//!
+//! Jobs live in the `schedules` table; `atlas schedule run` sleeps until the
+//! next one is due, runs it in-process and records the result in
+//! `schedule_runs`. This module holds the timing rules: when a job first
+//! runs, when it is due again, and what happens to runs missed while the
+//! executor was down.
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+/// A slot this long before the executor started counts as missed.
+pub const MISSED_GRACE_MS: i64 = 60_000;
+
+const DAY_MS: i64 = 86_400_000;
+
+/// What `schedule run` does with runs missed while it was not running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatchUp {
+    /// Drop the missed runs and wait for the next slot.
+    Skip,
+    /// Run once now for all the missed slots, then carry on.
+    RunOnce,
+}
+
+impl CatchUp {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "skip" => Ok(Self::Skip),
+            "run-once" => Ok(Self::RunOnce),
+            _ => bail!("Invalid catch-up policy '{s}'. Use skip or run-once."),
+        }
+    }
+}
+
+/// What the executor should do with a job right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Due {
+    /// Not due yet; due in this many ms.
+    Wait(i64),
+    /// Run it. `missed` counts the slots that passed unrun (0 when on time).
+    Run { missed: u32 },
+    /// Record the missed slots and move on without running.
+    Skip { missed: u32 },
+}
+
+/// Parse `--at HH:MM` (UTC) into minutes after midnight.
+pub fn parse_at(s: &str) -> Result<u32> {
+    let invalid = || anyhow::anyhow!("Invalid --at '{s}'. Use HH:MM in UTC (e.g. 09:00).");
+    let (h, m) = s.trim().split_once(':').ok_or_else(invalid)?;
+    let (h, m): (u32, u32) = (
+        h.parse().map_err(|_| invalid())?,
+        m.parse().map_err(|_| invalid())?,
+    );
+    if h > 23 || m > 59 || s.trim().len() != 5 {
+        return Err(invalid());
+    }
+    Ok(h * 60 + m)
+}
+
+/// When a new job first runs: the next `at` (minutes after midnight UTC)
+/// from `now_ms`, or one interval from now without one.
+pub fn first_run_ms(now_ms: i64, every_ms: i64, at_minutes: Option<u32>) -> i64 {
+    match at_minutes {
+        Some(at) => {
+            let slot = now_ms - now_ms.rem_euclid(DAY_MS) + at as i64 * 60_000;
+            if slot < now_ms {
+                slot + DAY_MS
+            } else {
+                slot
+            }
+        }
+        None => now_ms + every_ms,
+    }
+}
+
+/// The first slot of the job's cadence after `now_ms`.
+pub fn next_slot_after(next_run_ms: i64, every_ms: i64, now_ms: i64) -> i64 {
+    if next_run_ms > now_ms {
+        return next_run_ms;
+    }
+    let every = every_ms.max(1);
+    next_run_ms + ((now_ms - next_run_ms) / every + 1) * every
+}
+
+/// Whether a job due at `next_run_ms` should run, be skipped or wait.
+/// Only slots that passed before the executor started (`up_since_ms`) are
+/// missed; one that comes due while another job runs is just late.
+pub fn due(
+    next_run_ms: i64,
+    every_ms: i64,
+    now_ms: i64,
+    up_since_ms: i64,
+    catch_up: CatchUp,
+) -> Due {
+    if now_ms < next_run_ms {
+        return Due::Wait(next_run_ms - now_ms);
+    }
+    if next_run_ms + MISSED_GRACE_MS >= up_since_ms {
+        return Due::Run { missed: 0 };
+    }
+    let missed = ((now_ms - next_run_ms) / every_ms.max(1) + 1).min(u32::MAX as i64) as u32;
+    match catch_up {
+        CatchUp::Skip => Due::Skip { missed },
+        CatchUp::RunOnce => Due::Run { missed },
+    }
+}
+
+/// An interval as the largest whole unit, e.g. `1d`, `12h`, `90m`.
+pub fn format_every(every_ms: i64) -> String {
+    const UNITS: [(i64, &str); 4] = [
+        (7 * DAY_MS, "w"),
+        (DAY_MS, "d"),
+        (3_600_000, "h"),
+        (60_000, "m"),
+    ];
+    UNITS
+        .iter()
+        .find(|(ms, _)| every_ms >= *ms && every_ms % ms == 0)
+        .map(|(ms, unit)| format!("{}{unit}", every_ms / ms))
+        .unwrap_or_else(|| format!("{}s", every_ms / 1000))
+}
+
+/// Order id and fill price from what a scheduled command rendered: the
+/// first output with an `order_id` (an order result).
+pub fn order_fill(rendered: &[Value]) -> (Option<String>, Option<String>) {
+    let text = |v: &Value| match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+    rendered
+        .iter()
+        .find_map(|v| {
+            let oid = text(v.get("order_id")?)?;
+            Some((Some(oid), v.get("price").and_then(text)))
+        })
+        .unwrap_or((None, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = 3_600_000;
+
+    #[test]
+    fn test_parse_at() {
+        assert_eq!(parse_at("09:00").unwrap(), 540);
+        assert_eq!(parse_at("23:59").unwrap(), 1439);
+        assert!(parse_at("24:00").is_err());
+        assert!(parse_at("9:00").is_err());
+        assert!(parse_at("09:60").is_err());
+        assert!(parse_at("noon").is_err());
+    }
+
+    #[test]
+    fn test_first_run() {
+        // 2026-01-01 08:00 UTC
+        let now = 1_767_254_400_000;
+        assert_eq!(first_run_ms(now, 24 * HOUR, None), now + 24 * HOUR);
+        // 09:00 is later today
+        assert_eq!(first_run_ms(now, 24 * HOUR, Some(540)), now + HOUR);
+        // 07:00 has passed: tomorrow
+        assert_eq!(first_run_ms(now, 24 * HOUR, Some(420)), now + 23 * HOUR);
+        // Exactly now
+        assert_eq!(first_run_ms(now, 24 * HOUR, Some(480)), now);
+    }
+
+    #[test]
+    fn test_due_and_catch_up() {
+        let every = 24 * HOUR;
+        assert_eq!(due(1_000, every, 400, 0, CatchUp::Skip), Due::Wait(600));
+        // Due while the executor was up, however late
+        assert_eq!(
+            due(1_000, every, 1_000 + HOUR, 0, CatchUp::Skip),
+            Due::Run { missed: 0 }
+        );
+        // Started just after the slot
+        let started = 1_000 + MISSED_GRACE_MS;
+        assert_eq!(
+            due(1_000, every, started, started, CatchUp::Skip),
+            Due::Run { missed: 0 }
+        );
+        // Down for two and a half days: three slots passed
+        let now = 1_000 + 5 * every / 2;
+        assert_eq!(
+            due(1_000, every, now, now, CatchUp::Skip),
+            Due::Skip { missed: 3 }
+        );
+        assert_eq!(
+            due(1_000, every, now, now, CatchUp::RunOnce),
+            Due::Run { missed: 3 }
+        );
+        // Started an hour after the slot: still missed
+        let now = 1_000 + HOUR;
+        assert_eq!(
+            due(1_000, every, now, now, CatchUp::Skip),
+            Due::Skip { missed: 1 }
+        );
+        assert!(CatchUp::parse("later").is_err());
+        assert_eq!(CatchUp::parse("run-once").unwrap(), CatchUp::RunOnce);
+    }
+
+    #[test]
+    fn test_next_slot_keeps_cadence() {
+        let every = 24 * HOUR;
+        assert_eq!(next_slot_after(1_000, every, 500), 1_000);
+        assert_eq!(next_slot_after(1_000, every, 1_000), 1_000 + every);
+        // A run that took a few seconds
+        assert_eq!(next_slot_after(1_000, every, 6_000), 1_000 + every);
+        assert_eq!(
+            next_slot_after(1_000, every, 1_000 + 5 * every / 2),
+            1_000 + 3 * every
+        );
+    }
+
+    #[test]
+    fn test_format_every() {
+        assert_eq!(format_every(24 * HOUR), "1d");
+        assert_eq!(format_every(12 * HOUR), "12h");
+        assert_eq!(format_every(14 * 24 * HOUR), "2w");
+        assert_eq!(format_every(90 * 60_000), "90m");
+    }
+
+    #[test]
+    fn test_order_fill() {
+        let rendered = vec![
+            serde_json::json!({"symbol": "BTC", "mid": "67000"}),
+            serde_json::json!({"order_id": 123, "price": "67012.5", "status": "filled"}),
+        ];
+        assert_eq!(
+            order_fill(&rendered),
+            (Some("123".into()), Some("67012.5".into()))
+        );
+        let resting = vec![serde_json::json!({"order_id": 9, "price": null})];
+        assert_eq!(order_fill(&resting), (Some("9".into()), None));
+        assert_eq!(order_fill(&[]), (None, None));
+    }
+}
@@ -0,0 +1,482 @@
+//! `atlas init`: the first-run setup steps, which of them a config still
+//! lacks, and the answers file `--non-interactive --from` applies.
+//!
+//! A step counts as done once answered (recorded in `system.setup_steps`)
+//! or when the config already differs from the defaults there, so a re-run,
+//! or a wizard interrupted half way, only asks about the gaps. Skipped
+//! steps are not recorded and come up again next time.
+//!
+//! Private keys never go in an answers file: `profile.key_env` or
+//! `profile.key_file` say where to read one from.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::config::{AppConfig, SizeMode};
+use crate::error::{AtlasError, AtlasResult};
+
+/// Highest leverage Hyperliquid offers on any market.
+pub const MAX_LEVERAGE: u32 = 50;
+
+/// Modules `atlas init` can switch on or off.
+pub const MODULES: [&str; 4] = ["hyperliquid", "zero_x", "morpho", "paper"];
+
+/// One wizard step, in the order asked. The network comes first: it picks
+/// the environment, and the later answers are stored in that environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupStep {
+    Network,
+    Profile,
+    Modules,
+    Trading,
+    ApiKey,
+}
+
+impl SetupStep {
+    pub const ALL: [SetupStep; 5] = [
+        SetupStep::Network,
+        SetupStep::Profile,
+        SetupStep::Modules,
+        SetupStep::Trading,
+        SetupStep::ApiKey,
+    ];
+
+    /// Name recorded in `system.setup_steps`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SetupStep::Network => "network",
+            SetupStep::Profile => "profile",
+            SetupStep::Modules => "modules",
+            SetupStep::Trading => "trading",
+            SetupStep::ApiKey => "api_key",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            SetupStep::Network => "Network",
+            SetupStep::Profile => "Wallet profile",
+            SetupStep::Modules => "Modules",
+            SetupStep::Trading => "Trading defaults",
+            SetupStep::ApiKey => "Backend API key",
+        }
+    }
+}
+
+/// Steps still open, in wizard order. `profile_ready`: the active profile
+/// exists and its key is in the keyring, which only the caller can check.
+pub fn pending_steps(config: &AppConfig, profile_ready: bool) -> Vec<SetupStep> {
+    SetupStep::ALL
+        .into_iter()
+        .filter(|step| !is_done(config, *step, profile_ready))
+        .collect()
+}
+
+fn is_done(config: &AppConfig, step: SetupStep, profile_ready: bool) -> bool {
+    let recorded = config.system.setup_steps.iter().any(|s| s == step.as_str());
+    let defaults = AppConfig::default();
+    let (hl, default_hl) = (
+        &config.modules.hyperliquid.config,
+        &defaults.modules.hyperliquid.config,
+    );
+    match step {
+        // A profile whose key has left the keyring needs setting up again
+        SetupStep::Profile => profile_ready,
+        SetupStep::Network => recorded || hl.network != default_hl.network,
+        SetupStep::Modules => recorded || module_states(config) != module_states(&defaults),
+        SetupStep::Trading => {
+            recorded
+                || hl.default_leverage != default_hl.default_leverage
+                || hl.default_slippage != default_hl.default_slippage
+                || hl.default_size_mode != default_hl.default_size_mode
+        }
+        SetupStep::ApiKey => recorded || config.system.api_key.is_some(),
+    }
+}
+
+/// Record `step` as answered.
+pub fn mark_done(config: &mut AppConfig, step: SetupStep) {
+    let steps = &mut config.system.setup_steps;
+    if !steps.iter().any(|s| s == step.as_str()) {
+        steps.push(step.as_str().to_string());
+    }
+}
+
+/// Whether each of [`MODULES`] is enabled.
+pub fn module_states(config: &AppConfig) -> [(&'static str, bool); 4] {
+    let m = &config.modules;
+    [
+        ("hyperliquid", m.hyperliquid.enabled),
+        ("zero_x", m.zero_x.enabled),
+        ("morpho", m.morpho.enabled),
+        ("paper", m.paper.enabled),
+    ]
+}
+
+/// Switch a module (any alias `atlas configure module` takes) on or off.
+pub fn set_module(config: &mut AppConfig, name: &str, enabled: bool) -> AtlasResult<()> {
+    let m = &mut config.modules;
+    match module_name(name)? {
+        "hyperliquid" => m.hyperliquid.enabled = enabled,
+        "zero_x" => m.zero_x.enabled = enabled,
+        "morpho" => m.morpho.enabled = enabled,
+        _ => m.paper.enabled = enabled,
+    }
+    Ok(())
+}
+
+fn module_name(name: &str) -> AtlasResult<&'static str> {
+    match name.trim().to_lowercase().as_str() {
+        "hyperliquid" | "hl" | "perp" => Ok("hyperliquid"),
+        "zero_x" | "0x" | "swap" => Ok("zero_x"),
+        "morpho" | "lending" => Ok("morpho"),
+        "paper" | "sim" => Ok("paper"),
+        _ => Err(invalid(format!(
+            "unknown module '{name}'. Available: {}",
+            MODULES.join(", ")
+        ))),
+    }
+}
+
+// ── Validation ──────────────────────────────────────────────────────
+
+/// `mainnet` or `testnet`.
+pub fn parse_network(s: &str) -> AtlasResult<&'static str> {
+    match s.trim().to_lowercase().as_str() {
+        "mainnet" | "main" => Ok("mainnet"),
+        "testnet" | "test" => Ok("testnet"),
+        _ => Err(invalid(format!("network '{s}' must be mainnet or testnet"))),
+    }
+}
+
+/// 1 to [`MAX_LEVERAGE`], and within `trading.max_leverage` when set.
+pub fn check_leverage(leverage: u32, config: &AppConfig) -> AtlasResult<u32> {
+    if !(1..=MAX_LEVERAGE).contains(&leverage) {
+        return Err(invalid(format!(
+            "leverage {leverage} must be between 1 and {MAX_LEVERAGE}"
+        )));
+    }
+    if let Some(max) = config.trading.max_leverage {
+        if f64::from(leverage) > max {
+            return Err(invalid(format!(
+                "leverage {leverage} is above trading.max_leverage ({max})"
+            )));
+        }
+    }
+    Ok(leverage)
+}
+
+pub fn parse_leverage(s: &str, config: &AppConfig) -> AtlasResult<u32> {
+    let leverage = s
+        .trim()
+        .trim_end_matches(['x', 'X'])
+        .parse()
+        .map_err(|_| invalid(format!("leverage '{s}' is not a whole number")))?;
+    check_leverage(leverage, config)
+}
+
+/// A fraction above 0 and below 1, and within `trading.max_slippage` when
+/// set, or orders would be refused with `SLIPPAGE_TOO_HIGH`.
+pub fn check_slippage(slippage: f64, config: &AppConfig) -> AtlasResult<f64> {
+    if !(slippage > 0.0 && slippage < 1.0) {
+        return Err(invalid(format!(
+            "slippage {slippage} must be a fraction above 0 and below 1 (0.05 = 5%)"
+        )));
+    }
+    if let Some(max) = config.trading.max_slippage {
+        if slippage > max {
+            return Err(invalid(format!(
+                "slippage {slippage} is above trading.max_slippage ({max})"
+            )));
+        }
+    }
+    Ok(slippage)
+}
+
+/// `0.05` or `5%`.
+pub fn parse_slippage(s: &str, config: &AppConfig) -> AtlasResult<f64> {
+    let s = s.trim();
+    let parsed = match s.strip_suffix('%') {
+        Some(pct) => pct.trim().parse::<f64>().map(|p| p / 100.0),
+        None => s.parse::<f64>(),
+    };
+    let slippage = parsed.map_err(|_| invalid(format!("slippage '{s}' is not a number")))?;
+    check_slippage(slippage, config)
+}
+
+pub fn parse_size_mode(s: &str) -> AtlasResult<SizeMode> {
+    s.trim()
+        .parse()
+        .map_err(|_| invalid(format!("size mode '{s}' must be usdc, units or lots")))
+}
+
+fn invalid(message: String) -> AtlasError {
+    AtlasError::InvalidConfig(message)
+}
+
+// ── Answers file ────────────────────────────────────────────────────
+
+/// Pre-baked answers for `atlas init --non-interactive --from <file>`.
+/// Anything left out is not touched.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetupAnswers {
+    #[serde(default)]
+    pub profile: Option<ProfileAnswer>,
+    /// `mainnet` or `testnet`.
+    #[serde(default)]
+    pub network: Option<String>,
+    /// Module name → enabled. Modules left out keep their state.
+    #[serde(default)]
+    pub modules: BTreeMap<String, bool>,
+    #[serde(default)]
+    pub leverage: Option<u32>,
+    /// Fraction, 0.05 = 5%.
+    #[serde(default)]
+    pub slippage: Option<f64>,
+    /// `usdc`, `units` or `lots`.
+    #[serde(default)]
+    pub size_mode: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileAnswer {
+    pub name: String,
+    #[serde(default)]
+    pub action: ProfileAction,
+    /// Environment variable holding the key to import.
+    #[serde(default)]
+    pub key_env: Option<String>,
+    /// File holding the key to import.
+    #[serde(default)]
+    pub key_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileAction {
+    /// New random wallet; an existing profile of that name is kept.
+    #[default]
+    Generate,
+    /// Key from `key_env` or `key_file`.
+    Import,
+    /// Switch to a profile that already exists.
+    Use,
+}
+
+impl SetupAnswers {
+    /// Parse and check an answers file without applying anything.
+    pub fn parse(raw: &str, config: &AppConfig) -> AtlasResult<Self> {
+        let value: serde_json::Value = serde_json::from_str(raw)
+            .map_err(|e| invalid(format!("answers file is not valid JSON: {e}")))?;
+        if value.pointer("/profile/private_key").is_some() {
+            return Err(invalid(
+                "answers files must not hold private keys; use profile.key_env or profile.key_file"
+                    .into(),
+            ));
+        }
+        let answers: Self =
+            serde_json::from_value(value).map_err(|e| invalid(format!("answers file: {e}")))?;
+        answers.check(config)?;
+        Ok(answers)
+    }
+
+    fn check(&self, config: &AppConfig) -> AtlasResult<()> {
+        if let Some(profile) = &self.profile {
+            if profile.name.trim().is_empty() {
+                return Err(invalid("profile.name is empty".into()));
+            }
+            let has_source = profile.key_env.is_some() || profile.key_file.is_some();
+            match profile.action {
+                ProfileAction::Import if !has_source => {
+                    return Err(invalid(
+                        "profile.action import needs profile.key_env or profile.key_file".into(),
+                    ))
+                }
+                ProfileAction::Generate | ProfileAction::Use if has_source => {
+                    return Err(invalid(
+                        "profile.key_env / profile.key_file only apply to action import".into(),
+                    ))
+                }
+                _ => {}
+            }
+        }
+        if let Some(network) = &self.network {
+            parse_network(network)?;
+        }
+        for name in self.modules.keys() {
+            module_name(name)?;
+        }
+        if let Some(leverage) = self.leverage {
+            check_leverage(leverage, config)?;
+        }
+        if let Some(slippage) = self.slippage {
+            check_slippage(slippage, config)?;
+        }
+        if let Some(mode) = &self.size_mode {
+            parse_size_mode(mode)?;
+        }
+        if self.api_key.as_deref().is_some_and(|k| k.trim().is_empty()) {
+            return Err(invalid("api_key is empty".into()));
+        }
+        Ok(())
+    }
+
+    /// Apply the module, trading and API key answers to `config` and record
+    /// those steps. The profile and network need the keyring and the
+    /// environment list, so the caller handles them.
+    pub fn apply(&self, config: &mut AppConfig) -> AtlasResult<Vec<SetupStep>> {
+        self.check(config)?;
+        let mut applied = Vec::new();
+        if !self.modules.is_empty() {
+            for (name, enabled) in &self.modules {
+                set_module(config, name, *enabled)?;
+            }
+            applied.push(SetupStep::Modules);
+        }
+        let hl = &mut config.modules.hyperliquid.config;
+        if let Some(leverage) = self.leverage {
+            hl.default_leverage = leverage;
+        }
+        if let Some(slippage) = self.slippage {
+            hl.default_slippage = slippage;
+        }
+        if let Some(mode) = &self.size_mode {
+            hl.default_size_mode = parse_size_mode(mode)?;
+        }
+        if self.leverage.is_some() || self.slippage.is_some() || self.size_mode.is_some() {
+            applied.push(SetupStep::Trading);
+        }
+        if let Some(key) = &self.api_key {
+            config.system.api_key = Some(key.trim().to_string());
+            applied.push(SetupStep::ApiKey);
+        }
+        for step in &applied {
+            mark_done(config, *step);
+        }
+        Ok(applied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_steps_follow_the_config() {
+        let mut config = AppConfig::default();
+        assert_eq!(pending_steps(&config, false), SetupStep::ALL);
+
+        // Settings changed by hand count as answered
+        config.modules.hyperliquid.config.default_leverage = 3;
+        config.system.api_key = Some("atlas_123".into());
+        assert_eq!(
+            pending_steps(&config, true),
+            [SetupStep::Network, SetupStep::Modules]
+        );
+
+        // So does a step answered with the defaults
+        mark_done(&mut config, SetupStep::Network);
+        mark_done(&mut config, SetupStep::Network);
+        assert_eq!(config.system.setup_steps, ["network"]);
+        assert_eq!(pending_steps(&config, true), [SetupStep::Modules]);
+
+        // A recorded profile whose key is gone is asked again
+        mark_done(&mut config, SetupStep::Profile);
+        assert_eq!(
+            pending_steps(&config, false),
+            [SetupStep::Profile, SetupStep::Modules]
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut config = AppConfig::default();
+        assert_eq!(parse_network(" Testnet ").unwrap(), "testnet");
+        assert!(parse_network("devnet").is_err());
+
+        assert_eq!(parse_leverage("5x", &config).unwrap(), 5);
+        assert!(parse_leverage("0", &config).is_err());
+        assert!(parse_leverage("51", &config).is_err());
+        assert!(parse_leverage("2.5", &config).is_err());
+        config.trading.max_leverage = Some(10.0);
+        let err = parse_leverage("20", &config).unwrap_err();
+        assert!(err.to_string().contains("trading.max_leverage"));
+
+        assert_eq!(parse_slippage("0.01", &config).unwrap(), 0.01);
+        assert_eq!(parse_slippage("2%", &config).unwrap(), 0.02);
+        assert!(parse_slippage("1", &config).is_err());
+        assert!(parse_slippage("-0.1", &config).is_err());
+        config.trading.max_slippage = Some(0.03);
+        assert!(parse_slippage("5%", &config).is_err());
+
+        assert_eq!(parse_size_mode("Units").unwrap(), SizeMode::Units);
+        assert!(parse_size_mode("shares").is_err());
+    }
+
+    #[test]
+    fn test_answers_apply() {
+        let mut config = AppConfig::default();
+        let answers = SetupAnswers::parse(
+            r#"{
+                "profile": {"name": "bot", "action": "import", "key_env": "BOT_KEY"},
+                "network": "testnet",
+                "modules": {"0x": true, "paper": true, "morpho": false},
+                "leverage": 3,
+                "slippage": 0.02,
+                "size_mode": "units",
+                "api_key": "atlas_abc"
+            }"#,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(
+            answers.profile.as_ref().unwrap().action,
+            ProfileAction::Import
+        );
+
+        let applied = answers.apply(&mut config).unwrap();
+        assert_eq!(
+            applied,
+            [SetupStep::Modules, SetupStep::Trading, SetupStep::ApiKey]
+        );
+        assert!(config.modules.zero_x.enabled && config.modules.paper.enabled);
+        let hl = &config.modules.hyperliquid.config;
+        assert_eq!((hl.default_leverage, hl.default_slippage), (3, 0.02));
+        assert_eq!(hl.default_size_mode, SizeMode::Units);
+        assert_eq!(config.system.api_key.as_deref(), Some("atlas_abc"));
+        assert_eq!(config.system.setup_steps, ["modules", "trading", "api_key"]);
+
+        // Nothing given, nothing changed
+        let before = config.clone();
+        assert!(SetupAnswers::default()
+            .apply(&mut config)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::to_value(&before).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_answers_rejected() {
+        let config = AppConfig::default();
+        let parse = |raw: &str| SetupAnswers::parse(raw, &config).unwrap_err().to_string();
+
+        assert!(
+            parse(r#"{"profile": {"name": "a", "private_key": "0xabc"}}"#)
+                .contains("must not hold private keys")
+        );
+        assert!(parse(r#"{"profile": {"name": "a", "action": "import"}}"#).contains("key_env"));
+        assert!(parse(r#"{"leverage": 100}"#).contains("between 1 and 50"));
+        assert!(parse(r#"{"modules": {"dex": true}}"#).contains("unknown module"));
+        assert!(parse(r#"{"levrage": 3}"#).contains("unknown field"));
+        assert!(parse("not json").contains("not valid JSON"));
+    }
+}
@@ -0,0 +1,473 @@
+//! Position size suggestions from the trader's own record
+//! (`atlas hl risk suggest`).
+//!
+//! A trade is one closing order: the closing fills of an order id summed,
+//! net of their fees. Kelly's `f* = p - (1 - p) / b` (win rate `p`, payoff
+//! ratio `b` = average win / average loss) is read as the fraction of the
+//! account lost if the stop is hit, so it sizes through the same
+//! `risk / stop distance` as a fixed fraction. Full Kelly assumes the
+//! record is the true edge; the configured multiplier (half by default) and
+//! cap keep an estimate from a short record from over-betting.
+
+use std::collections::BTreeMap;
+
+use crate::db::DbFill;
+use crate::error::{AtlasError, AtlasResult};
+
+/// How the risk fraction is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizingMethod {
+    Kelly,
+    FixedFraction,
+}
+
+impl SizingMethod {
+    /// `kelly` or `fixed-frac`.
+    pub fn parse(s: &str) -> AtlasResult<Self> {
+        match s.to_lowercase().as_str() {
+            "kelly" => Ok(Self::Kelly),
+            "fixed-frac" | "fixed" => Ok(Self::FixedFraction),
+            other => Err(AtlasError::Other(format!(
+                "unknown sizing method '{other}' (expected kelly or fixed-frac)"
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Kelly => "kelly",
+            Self::FixedFraction => "fixed-frac",
+        }
+    }
+}
+
+/// Win/loss record over closed trades.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TradeStats {
+    pub trades: usize,
+    pub wins: usize,
+    pub losses: usize,
+    /// Mean winning trade, in USDC.
+    pub avg_win: f64,
+    /// Mean losing trade as a positive amount, in USDC.
+    pub avg_loss: f64,
+}
+
+impl TradeStats {
+    /// Stats over net trade results; break-even trades count toward
+    /// `trades` but are neither wins nor losses.
+    pub fn from_outcomes(pnls: &[f64]) -> Self {
+        let wins: Vec<f64> = pnls.iter().copied().filter(|p| *p > 0.0).collect();
+        let losses: Vec<f64> = pnls.iter().copied().filter(|p| *p < 0.0).collect();
+        let avg = |xs: &[f64]| {
+            if xs.is_empty() {
+                0.0
+            } else {
+                xs.iter().sum::<f64>().abs() / xs.len() as f64
+            }
+        };
+        Self {
+            trades: pnls.len(),
+            wins: wins.len(),
+            losses: losses.len(),
+            avg_win: avg(&wins),
+            avg_loss: avg(&losses),
+        }
+    }
+
+    /// Share of trades that won, `0` without trades.
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.trades as f64
+        }
+    }
+
+    /// Average win over average loss; `None` without both a win and a loss.
+    pub fn payoff_ratio(&self) -> Option<f64> {
+        (self.wins > 0 && self.losses > 0 && self.avg_loss > 0.0)
+            .then(|| self.avg_win / self.avg_loss)
+    }
+
+    /// Full Kelly fraction of this record, see [`kelly_fraction`]. A record
+    /// without losses has no payoff ratio and bets everything — the cap
+    /// is what bounds it.
+    pub fn kelly(&self) -> f64 {
+        match self.payoff_ratio() {
+            Some(b) => kelly_fraction(self.win_rate(), b),
+            None if self.wins > 0 && self.losses == 0 => 1.0,
+            None => 0.0,
+        }
+    }
+}
+
+/// Net result per closed trade, oldest first: closing fills (non-zero
+/// `closed_pnl`) grouped by coin and order id, minus their fees. Opening
+/// fills carry no result and are skipped.
+pub fn trade_outcomes(fills: &[DbFill]) -> Vec<f64> {
+    let mut trades: BTreeMap<(String, i64), (i64, f64)> = BTreeMap::new();
+    for f in fills {
+        let pnl: f64 = f.closed_pnl.parse().unwrap_or(0.0);
+        if pnl == 0.0 {
+            continue;
+        }
+        let fee: f64 = f.fee.parse().unwrap_or(0.0);
+        let entry = trades
+            .entry((f.coin.clone(), f.oid))
+            .or_insert((f.time_ms, 0.0));
+        entry.0 = entry.0.min(f.time_ms);
+        entry.1 += pnl - fee;
+    }
+    let mut outcomes: Vec<(i64, f64)> = trades.into_values().collect();
+    outcomes.sort_by_key(|(time, _)| *time);
+    outcomes.into_iter().map(|(_, pnl)| pnl).collect()
+}
+
+/// Kelly fraction `p - (1 - p) / b` for win rate `p` and payoff ratio `b`,
+/// floored at `0` (no edge means no bet).
+pub fn kelly_fraction(win_rate: f64, payoff_ratio: f64) -> f64 {
+    if payoff_ratio <= 0.0 || !payoff_ratio.is_finite() {
+        return 0.0;
+    }
+    (win_rate - (1.0 - win_rate) / payoff_ratio).max(0.0)
+}
+
+/// Which record to size from: the coin's own once it has `min_trades`,
+/// else the whole account's if that is longer.
+pub fn select_stats(
+    coin: TradeStats,
+    overall: TradeStats,
+    min_trades: usize,
+) -> (&'static str, TradeStats) {
+    if coin.trades >= min_trades || overall.trades <= coin.trades {
+        ("coin", coin)
+    } else {
+        ("all", overall)
+    }
+}
+
+/// Knobs for [`suggest`], from `RiskConfig` and the command line.
+#[derive(Debug, Clone, Copy)]
+pub struct SizingParams {
+    /// Share of full Kelly to bet (0.5 = half Kelly).
+    pub kelly_multiplier: f64,
+    /// Highest Kelly risk fraction ever suggested.
+    pub kelly_cap: f64,
+    /// Closed trades needed before Kelly is trusted.
+    pub min_trades: usize,
+    /// Risk fraction for fixed-fractional sizing and the fallback.
+    pub fraction: f64,
+    /// Hard cap on size in asset units.
+    pub max_size: Option<f64>,
+}
+
+/// The trade being sized.
+#[derive(Debug, Clone, Copy)]
+pub struct SizingInput {
+    pub account_value: f64,
+    pub entry: f64,
+    pub stop: f64,
+    pub is_buy: bool,
+    pub leverage: u32,
+}
+
+/// A suggested size and how it was reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    /// The method actually used, after any fallback.
+    pub method: SizingMethod,
+    /// Full Kelly fraction of the record, when Kelly was used.
+    pub kelly: Option<f64>,
+    /// Fraction of the account at risk to the stop.
+    pub risk_fraction: f64,
+    pub risk_usd: f64,
+    /// Size in asset units.
+    pub size: f64,
+    pub notional: f64,
+    /// Margin at `leverage`.
+    pub margin: f64,
+    pub warnings: Vec<String>,
+}
+
+/// Size `input` by `method`. Kelly falls back to fixed-fractional with a
+/// warning when `stats` has fewer than `params.min_trades` trades.
+pub fn suggest(
+    method: SizingMethod,
+    stats: &TradeStats,
+    params: &SizingParams,
+    input: &SizingInput,
+) -> AtlasResult<Suggestion> {
+    if input.entry <= 0.0 || input.stop <= 0.0 {
+        return Err(AtlasError::Other(
+            "entry and stop must be positive prices".into(),
+        ));
+    }
+    let stop_ok = if input.is_buy {
+        input.stop < input.entry
+    } else {
+        input.stop > input.entry
+    };
+    if !stop_ok {
+        let (side, dir) = if input.is_buy {
+            ("long", "below")
+        } else {
+            ("short", "above")
+        };
+        return Err(AtlasError::Other(format!(
+            "stop {} must be {dir} the entry {} for a {side}",
+            input.stop, input.entry
+        )));
+    }
+
+    let mut warnings = Vec::new();
+    let mut used = method;
+    let mut kelly = None;
+    let mut risk_fraction = params.fraction;
+    if method == SizingMethod::Kelly {
+        if stats.trades < params.min_trades {
+            used = SizingMethod::FixedFraction;
+            warnings.push(format!(
+                "Only {} closed trade{} in the record (need {}): fell back to \
+                 fixed-fractional at {:.2}%.",
+                stats.trades,
+                if stats.trades == 1 { "" } else { "s" },
+                params.min_trades,
+                params.fraction * 100.0
+            ));
+        } else {
+            let full = stats.kelly();
+            let scaled = full * params.kelly_multiplier;
+            risk_fraction = scaled.min(params.kelly_cap);
+            kelly = Some(full);
+            if full == 0.0 {
+                warnings.push("No edge in the record (Kelly ≤ 0): no position suggested.".into());
+            } else if scaled > params.kelly_cap {
+                warnings.push(format!(
+                    "Kelly risk {:.2}% capped at {:.2}%.",
+                    scaled * 100.0,
+                    params.kelly_cap * 100.0
+                ));
+            }
+        }
+    }
+
+    let distance = (input.entry - input.stop).abs();
+    let mut size = input.account_value.max(0.0) * risk_fraction / distance;
+    if let Some(max) = params.max_size {
+        if size > max {
+            warnings.push(format!("Size {size:.6} capped at the asset max of {max}."));
+            size = max;
+        }
+    }
+    let notional = size * input.entry;
+    let margin = if input.leverage > 0 {
+        notional / input.leverage as f64
+    } else {
+        notional
+    };
+    if margin > input.account_value {
+        warnings.push(format!(
+            "Margin ${margin:.2} exceeds the account value ${:.2} at {}x.",
+            input.account_value, input.leverage
+        ));
+    }
+
+    Ok(Suggestion {
+        method: used,
+        kelly,
+        risk_fraction,
+        risk_usd: size * distance,
+        size,
+        notional,
+        margin,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close_to(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn params() -> SizingParams {
+        SizingParams {
+            kelly_multiplier: 0.5,
+            kelly_cap: 0.05,
+            min_trades: 20,
+            fraction: 0.02,
+            max_size: None,
+        }
+    }
+
+    fn long(entry: f64, stop: f64) -> SizingInput {
+        SizingInput {
+            account_value: 10_000.0,
+            entry,
+            stop,
+            is_buy: true,
+            leverage: 5,
+        }
+    }
+
+    /// `wins` trades of +`win` and `losses` of -`loss`.
+    fn record(wins: usize, win: f64, losses: usize, loss: f64) -> TradeStats {
+        let mut pnls = vec![win; wins];
+        pnls.extend(vec![-loss; losses]);
+        TradeStats::from_outcomes(&pnls)
+    }
+
+    #[test]
+    fn test_kelly_fraction() {
+        // 60% at 1:1 → 0.2; 50% at 2:1 → 0.25; losing edge floors at 0
+        assert!(close_to(kelly_fraction(0.6, 1.0), 0.2));
+        assert!(close_to(kelly_fraction(0.5, 2.0), 0.25));
+        assert_eq!(kelly_fraction(0.4, 1.0), 0.0);
+        assert_eq!(kelly_fraction(0.9, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_trade_stats() {
+        let s = TradeStats::from_outcomes(&[100.0, 50.0, -25.0, 0.0]);
+        assert_eq!((s.trades, s.wins, s.losses), (4, 2, 1));
+        assert!(close_to(s.avg_win, 75.0));
+        assert!(close_to(s.avg_loss, 25.0));
+        assert!(close_to(s.win_rate(), 0.5));
+        assert!(close_to(s.payoff_ratio().unwrap(), 3.0));
+        assert!(close_to(s.kelly(), 0.5 - 0.5 / 3.0));
+
+        assert_eq!(record(3, 10.0, 0, 0.0).kelly(), 1.0);
+        assert_eq!(record(0, 0.0, 3, 10.0).kelly(), 0.0);
+        assert_eq!(TradeStats::from_outcomes(&[]).win_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_trade_outcomes_groups_closing_fills() {
+        let fill = |coin: &str, oid: i64, time_ms: i64, pnl: &str, fee: &str| DbFill {
+            protocol: "hyperliquid".into(),
+            coin: coin.into(),
+            px: "100".into(),
+            sz: "1".into(),
+            side: "sell".into(),
+            time_ms,
+            fee: fee.into(),
+            hash: String::new(),
+            oid,
+            closed_pnl: pnl.into(),
+        };
+        let fills = [
+            fill("ETH", 1, 10, "0", "0.5"), // opening
+            fill("ETH", 2, 30, "12", "1"),
+            fill("ETH", 2, 31, "8", "1"),
+            fill("BTC", 2, 20, "-5", "0.25"),
+        ];
+        assert_eq!(trade_outcomes(&fills), [-5.25, 18.0]);
+    }
+
+    #[test]
+    fn test_select_stats() {
+        let coin = record(5, 10.0, 5, 10.0);
+        let all = record(20, 10.0, 20, 10.0);
+        assert_eq!(select_stats(coin, all, 20), ("all", all));
+        assert_eq!(select_stats(coin, all, 10), ("coin", coin));
+        assert_eq!(select_stats(coin, coin, 20), ("coin", coin));
+    }
+
+    #[test]
+    fn test_half_kelly_sizing() {
+        // 55% at 1.5:1 → full 0.25, half 0.125, capped at 5%
+        let stats = record(22, 150.0, 18, 100.0);
+        let s = suggest(SizingMethod::Kelly, &stats, &params(), &long(100.0, 95.0)).unwrap();
+        assert_eq!(s.method, SizingMethod::Kelly);
+        assert!(close_to(s.kelly.unwrap(), 0.25));
+        assert!(close_to(s.risk_fraction, 0.05));
+        assert!(close_to(s.risk_usd, 500.0));
+        assert!(close_to(s.size, 100.0));
+        assert!(close_to(s.margin, 2_000.0));
+        assert!(
+            s.warnings[0].contains("capped at 5.00%"),
+            "{:?}",
+            s.warnings
+        );
+
+        // Under the cap the multiplier alone applies
+        let p = SizingParams {
+            kelly_cap: 0.5,
+            ..params()
+        };
+        let s = suggest(SizingMethod::Kelly, &stats, &p, &long(100.0, 95.0)).unwrap();
+        assert!(close_to(s.risk_fraction, 0.125));
+        assert!(s.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_kelly_without_edge_suggests_nothing() {
+        let stats = record(8, 100.0, 12, 100.0);
+        let s = suggest(SizingMethod::Kelly, &stats, &params(), &long(100.0, 95.0)).unwrap();
+        assert_eq!(s.size, 0.0);
+        assert!(s.warnings[0].contains("No edge"));
+    }
+
+    #[test]
+    fn test_short_history_falls_back_to_fixed_fraction() {
+        let stats = record(4, 100.0, 1, 100.0);
+        let s = suggest(SizingMethod::Kelly, &stats, &params(), &long(100.0, 98.0)).unwrap();
+        assert_eq!(s.method, SizingMethod::FixedFraction);
+        assert_eq!(s.kelly, None);
+        assert!(close_to(s.risk_usd, 200.0));
+        assert!(close_to(s.size, 100.0));
+        assert!(s.warnings[0].contains("Only 5 closed trades in the record (need 20)"));
+    }
+
+    #[test]
+    fn test_fixed_fraction_short_and_max_size() {
+        let short = SizingInput {
+            is_buy: false,
+            leverage: 0,
+            ..long(50.0, 52.0)
+        };
+        let p = SizingParams {
+            max_size: Some(40.0),
+            ..params()
+        };
+        let s = suggest(
+            SizingMethod::FixedFraction,
+            &TradeStats::default(),
+            &p,
+            &short,
+        )
+        .unwrap();
+        // 200 USDC over a 2.0 stop is 100 units, capped at 40
+        assert!(close_to(s.size, 40.0));
+        assert!(close_to(s.risk_usd, 80.0));
+        assert!(close_to(s.margin, s.notional));
+        assert!(s
+            .warnings
+            .iter()
+            .any(|w| w.contains("capped at the asset max")));
+    }
+
+    #[test]
+    fn test_stop_on_wrong_side_rejected() {
+        let stats = TradeStats::default();
+        assert!(suggest(
+            SizingMethod::FixedFraction,
+            &stats,
+            &params(),
+            &long(100.0, 101.0)
+        )
+        .is_err());
+        assert!(suggest(
+            SizingMethod::FixedFraction,
+            &stats,
+            &params(),
+            &long(100.0, 100.0)
+        )
+        .is_err());
+        assert_eq!(SizingMethod::parse("Kelly").unwrap(), SizingMethod::Kelly);
+        assert!(SizingMethod::parse("martingale").is_err());
+    }
+}
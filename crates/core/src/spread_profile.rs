@@ -0,0 +1,297 @@
+//! Spread profiling from opportunistic samples, for low-liquidity warnings
+//! on market orders and `atlas market hl spread-profile`.
+//!
+//! There is no sampler to run: commands that already fetch tickers
+//! (`price`, `info`, `summary`, market orders) record each coin's impact
+//! spread into `spread_samples`, at most one sample per coin per minute,
+//! and prune anything older than [`RETENTION_MS`] on the way out. A market
+//! order compares the current spread with the coin's median over what is
+//! kept; hours are UTC so the profile lines up across machines.
+
+use chrono::Timelike;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::db::{AtlasDb, DbSpreadSample};
+use crate::types::Ticker;
+
+/// Samples are bucketed to the minute; a coin keeps the first of each.
+pub const SAMPLE_BUCKET_MS: i64 = 60_000;
+
+/// How long samples are kept, and so the window of the trailing median.
+pub const RETENTION_MS: i64 = 14 * 86_400_000;
+
+/// Samples needed before a median is trusted enough to warn against.
+pub const MIN_SAMPLES: usize = 20;
+
+/// Ask minus bid in bps of the mid; `None` without both sides, a
+/// positive mid or an uncrossed book. On Hyperliquid the ticker's bid and
+/// ask are the impact prices from the asset context.
+pub fn ticker_spread_bps(ticker: &Ticker) -> Option<f64> {
+    let (bid, ask) = (ticker.best_bid?, ticker.best_ask?);
+    if ticker.mid_price <= Decimal::ZERO || ask < bid {
+        return None;
+    }
+    ((ask - bid) / ticker.mid_price * Decimal::from(10_000)).to_f64()
+}
+
+/// One sample per ticker with a spread, in the minute bucket of `now_ms`.
+pub fn samples(protocol: &str, tickers: &[Ticker], now_ms: i64) -> Vec<DbSpreadSample> {
+    let time_ms = now_ms - now_ms.rem_euclid(SAMPLE_BUCKET_MS);
+    tickers
+        .iter()
+        .filter_map(|t| {
+            Some(DbSpreadSample {
+                protocol: protocol.to_string(),
+                coin: t.symbol.clone(),
+                time_ms,
+                spread_bps: format!("{:.4}", ticker_spread_bps(t)?),
+            })
+        })
+        .collect()
+}
+
+/// Record `tickers` and prune samples past retention. Returns rows inserted.
+pub fn record(
+    db: &AtlasDb,
+    protocol: &str,
+    tickers: &[Ticker],
+    now_ms: i64,
+) -> anyhow::Result<usize> {
+    let inserted = db.insert_spread_samples(&samples(protocol, tickers, now_ms))?;
+    db.prune_spread_samples(now_ms - RETENTION_MS)?;
+    Ok(inserted)
+}
+
+/// Median of `values`; `None` when empty.
+pub fn median(values: &[f64]) -> Option<f64> {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    Some(if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    })
+}
+
+/// Median spread over `samples`, in bps.
+pub fn median_bps(samples: &[DbSpreadSample]) -> Option<f64> {
+    median(&values(samples)).map(round2)
+}
+
+fn values(samples: &[DbSpreadSample]) -> Vec<f64> {
+    samples
+        .iter()
+        .filter_map(|s| s.spread_bps.parse().ok())
+        .collect()
+}
+
+/// Median spread of one UTC hour of the day.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HourlySpread {
+    /// 0–23, UTC.
+    pub hour_utc: u32,
+    pub samples: usize,
+    pub median_bps: f64,
+}
+
+/// Median spread per UTC hour, for hours that have samples.
+pub fn hourly_profile(samples: &[DbSpreadSample]) -> Vec<HourlySpread> {
+    let mut by_hour: [Vec<f64>; 24] = Default::default();
+    for s in samples {
+        let (Some(t), Ok(bps)) = (
+            chrono::DateTime::from_timestamp_millis(s.time_ms),
+            s.spread_bps.parse::<f64>(),
+        ) else {
+            continue;
+        };
+        by_hour[t.hour() as usize].push(bps);
+    }
+    by_hour
+        .iter()
+        .enumerate()
+        .filter_map(|(hour, values)| {
+            Some(HourlySpread {
+                hour_utc: hour as u32,
+                samples: values.len(),
+                median_bps: round2(median(values)?),
+            })
+        })
+        .collect()
+}
+
+/// A market order's spread well above the coin's usual.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LiquidityWarning {
+    pub spread_bps: f64,
+    /// Trailing median over the retained samples.
+    pub median_bps: f64,
+    /// `spread_bps / median_bps`.
+    pub ratio: f64,
+    /// Configured `trading.spread_warn_multiple` that `ratio` exceeded.
+    pub threshold: f64,
+    pub samples: usize,
+}
+
+impl LiquidityWarning {
+    pub fn describe(&self) -> String {
+        format!(
+            "Spread {:.2} bps is {:.1}x the median {:.2} bps ({} samples): thin liquidity",
+            self.spread_bps, self.ratio, self.median_bps, self.samples
+        )
+    }
+}
+
+/// Warning when `current_bps` exceeds the median of `history` by more than
+/// `multiple`. Quiet with fewer than [`MIN_SAMPLES`] samples or a zero
+/// median.
+pub fn check(
+    current_bps: f64,
+    history: &[DbSpreadSample],
+    multiple: f64,
+) -> Option<LiquidityWarning> {
+    let values = values(history);
+    if values.len() < MIN_SAMPLES {
+        return None;
+    }
+    let median_bps = median(&values).filter(|m| *m > 0.0)?;
+    let ratio = current_bps / median_bps;
+    (ratio > multiple).then(|| LiquidityWarning {
+        spread_bps: round2(current_bps),
+        median_bps: round2(median_bps),
+        ratio: round2(ratio),
+        threshold: multiple,
+        samples: values.len(),
+    })
+}
+
+fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Protocol;
+    use std::str::FromStr;
+
+    const HOUR: i64 = 3_600_000;
+
+    fn ticker(symbol: &str, bid: &str, ask: &str, mid: &str) -> Ticker {
+        Ticker {
+            symbol: symbol.into(),
+            protocol: Protocol::Hyperliquid,
+            mid_price: Decimal::from_str(mid).unwrap(),
+            best_bid: Some(Decimal::from_str(bid).unwrap()),
+            best_ask: Some(Decimal::from_str(ask).unwrap()),
+            volume_24h: None,
+            change_24h_pct: None,
+        }
+    }
+
+    fn sample(time_ms: i64, bps: f64) -> DbSpreadSample {
+        DbSpreadSample {
+            protocol: "hyperliquid".into(),
+            coin: "ETH".into(),
+            time_ms,
+            spread_bps: bps.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_samples_bucket_to_minute_and_skip_missing_sides() {
+        let mut no_book = ticker("DOGE", "0.1", "0.1", "0.1");
+        no_book.best_ask = None;
+        let got = samples(
+            "hyperliquid",
+            &[ticker("ETH", "3499", "3501", "3500"), no_book],
+            125_000,
+        );
+        assert_eq!(got.len(), 1);
+        assert_eq!(got[0].time_ms, 120_000);
+        assert_eq!(got[0].spread_bps, "5.7143");
+        assert!(ticker_spread_bps(&ticker("X", "2", "1", "1.5")).is_none());
+    }
+
+    #[test]
+    fn test_record_writes_once_per_minute_and_prunes() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let eth = [ticker("ETH", "3499", "3501", "3500")];
+        assert_eq!(record(&db, "hyperliquid", &eth, 60_000).unwrap(), 1);
+        assert_eq!(record(&db, "hyperliquid", &eth, 90_000).unwrap(), 0);
+        assert_eq!(record(&db, "hyperliquid", &eth, 120_000).unwrap(), 1);
+        assert_eq!(
+            db.query_spread_samples("hyperliquid", "ETH", 0)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // A call past retention drops the old samples
+        let later = 120_000 + RETENTION_MS;
+        record(&db, "hyperliquid", &eth, later).unwrap();
+        let kept = db.query_spread_samples("hyperliquid", "ETH", 0).unwrap();
+        assert_eq!(
+            kept.iter().map(|s| s.time_ms).collect::<Vec<_>>(),
+            [120_000, later]
+        );
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+    }
+
+    #[test]
+    fn test_hourly_profile_from_db() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let day = 20_000 * 24 * HOUR;
+        db.insert_spread_samples(&[
+            sample(day + 60_000, 2.0),
+            sample(day + 120_000, 4.0),
+            sample(day + 180_000, 3.0),
+            sample(day + 23 * HOUR, 10.0),
+            sample(day + 24 * HOUR, 1.0), // next day's 00:00
+        ])
+        .unwrap();
+        let rows = db.query_spread_samples("hyperliquid", "ETH", 0).unwrap();
+        assert_eq!(
+            hourly_profile(&rows),
+            [
+                HourlySpread {
+                    hour_utc: 0,
+                    samples: 4,
+                    median_bps: 2.5
+                },
+                HourlySpread {
+                    hour_utc: 23,
+                    samples: 1,
+                    median_bps: 10.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_against_trailing_median() {
+        let history: Vec<_> = (0..MIN_SAMPLES as i64)
+            .map(|i| sample(i * 60_000, 2.0))
+            .collect();
+        assert_eq!(check(5.0, &history, 3.0), None);
+        let warning = check(7.0, &history, 3.0).unwrap();
+        assert_eq!(warning.ratio, 3.5);
+        assert_eq!(warning.median_bps, 2.0);
+        assert_eq!(warning.samples, MIN_SAMPLES);
+        assert!(warning.describe().contains("3.5x the median 2.00 bps"));
+
+        // Too little history to judge
+        assert_eq!(check(100.0, &history[1..], 3.0), None);
+    }
+}
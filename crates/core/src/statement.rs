@@ -0,0 +1,710 @@
+//! Monthly account statements for `atlas export statement`.
+//!
+//! A statement walks one calendar month of the local cache in time order:
+//! every fill (realized PnL less its fee), every funding payment and every
+//! transfer in or out, each with the equity it leaves behind. Opening
+//! equity is the newest `atlas status` snapshot taken before the month,
+//! carried forward over the flows since, or failing that the sum of every
+//! cached flow before the month. Closing equity is opening plus the month's
+//! flows; moves in unrealized PnL are not part of it.
+//!
+//! Amounts are summed as decimals and printed without rounding. Snapshots,
+//! transfers and funding describe the Hyperliquid account, so they only
+//! count when the statement covers Hyperliquid (`all` or `hyperliquid`).
+
+use std::io::Write;
+
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::db::{AtlasDb, DbFill, DbFunding, DbTransfer, FillFilter, TransferFilter};
+use crate::error::AtlasError;
+use crate::ledger::FULL_SYNC_STATE;
+use crate::status_snapshot::StatusSnapshot;
+
+const HYPERLIQUID: &str = "hyperliquid";
+
+/// CSV header. Changing the columns breaks spreadsheets built on them:
+/// append new ones at the end.
+pub const CSV_HEADER: &str = "row_type,time,kind,protocol,asset,side,size,price,\
+                              realized_pnl,fee,funding_rate,amount,running_equity,reference";
+
+/// What to put on a statement.
+#[derive(Debug, Clone)]
+pub struct StatementRequest<'a> {
+    /// `2026-01`, as given.
+    pub month: &'a str,
+    /// First millisecond of the month, inclusive.
+    pub from_ms: i64,
+    /// Last millisecond of the month, inclusive.
+    pub to_ms: i64,
+    /// Normalized protocol; `None` for all of them.
+    pub protocol: Option<&'a str>,
+    /// Profile and network the opening snapshot is looked up for.
+    pub profile: &'a str,
+    pub network: &'a str,
+    pub now_ms: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Statement {
+    pub month: String,
+    pub protocol: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub opening: StatementBalance,
+    /// Oldest first.
+    pub entries: Vec<StatementEntry>,
+    pub closing: StatementBalance,
+    pub summary: StatementSummary,
+}
+
+/// Equity at the start or end of the period.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatementBalance {
+    pub time: String,
+    pub equity: String,
+    /// `snapshot` (carried forward from a status snapshot) or `computed`
+    /// (summed from cached flows).
+    pub source: String,
+    /// When the snapshot behind a `snapshot` balance was taken.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_time: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatementEntry {
+    pub time: String,
+    /// `fill`, `funding`, `deposit`, `withdraw` or `internal`.
+    pub kind: String,
+    pub protocol: String,
+    /// Coin for fills and funding, token for transfers.
+    pub asset: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill: Option<FillDetail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funding: Option<FundingDetail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer: Option<TransferDetail>,
+    /// Change in equity, USD.
+    pub amount: String,
+    pub running_equity: String,
+    /// Fill or transfer hash; `coin@time_ms` for funding.
+    pub reference: String,
+    #[serde(skip)]
+    time_ms: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FillDetail {
+    pub side: String,
+    pub size: String,
+    pub price: String,
+    pub realized_pnl: String,
+    pub fee: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FundingDetail {
+    /// Signed position size the payment was charged on.
+    pub position_size: String,
+    pub rate: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransferDetail {
+    /// `in`, `out` or `none` (spot↔perp).
+    pub direction: String,
+    pub token: String,
+    /// Token units.
+    pub amount: String,
+    pub fee: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counterparty: Option<String>,
+}
+
+/// Totals over the period, USD.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatementSummary {
+    pub fills: usize,
+    pub realized_pnl: String,
+    pub trading_fees: String,
+    /// Net funding received (negative: paid).
+    pub funding: String,
+    pub transfers_in: String,
+    pub transfers_out: String,
+    /// Closing less opening equity.
+    pub net_change: String,
+}
+
+/// Build the statement for `req` from the local cache.
+///
+/// Fails with [`AtlasError::InvalidDate`] for a month that has not started
+/// and with [`AtlasError::HistoryNotSynced`] when the cached Hyperliquid
+/// history may not reach back to the month's start.
+pub fn build(db: &AtlasDb, req: &StatementRequest) -> Result<Statement> {
+    if req.from_ms > req.now_ms {
+        return Err(
+            AtlasError::InvalidDate(format!("--month {} has not started yet", req.month)).into(),
+        );
+    }
+    let with_account = req.protocol.map_or(true, |p| p == HYPERLIQUID);
+    if with_account {
+        ensure_synced(db, req)?;
+    }
+
+    // Opening: a snapshot before the month plus what happened since, or
+    // every cached flow before the month
+    let snapshot = if with_account {
+        db.status_snapshot_before(req.profile, req.network, req.from_ms - 1)?
+            .and_then(|s| {
+                let parsed: StatusSnapshot = serde_json::from_str(&s.snapshot).ok()?;
+                Some((s.taken_ms, parsed.account_value?.parse::<Decimal>().ok()?))
+            })
+    } else {
+        None
+    };
+    let (base, flows_from, source) = match snapshot {
+        Some((taken_ms, value)) => (value, Some(taken_ms + 1), "snapshot"),
+        None => (Decimal::ZERO, None, "computed"),
+    };
+    let before: Decimal = entries(db, req, with_account, flows_from, Some(req.from_ms - 1))?
+        .iter()
+        .map(|(amount, _)| *amount)
+        .sum();
+    let opening = base + before;
+
+    let mut running = opening;
+    let mut summary = Totals::default();
+    let mut rows = Vec::new();
+    for (amount, mut entry) in entries(db, req, with_account, Some(req.from_ms), Some(req.to_ms))? {
+        running += amount;
+        summary.add(&entry, amount);
+        entry.amount = dec(amount);
+        entry.running_equity = dec(running);
+        rows.push(entry);
+    }
+
+    Ok(Statement {
+        month: req.month.to_string(),
+        protocol: req.protocol.unwrap_or("all").to_string(),
+        period_start: rfc3339(req.from_ms),
+        period_end: rfc3339(req.to_ms),
+        opening: StatementBalance {
+            time: rfc3339(req.from_ms),
+            equity: dec(opening),
+            source: source.into(),
+            snapshot_time: snapshot.map(|(taken_ms, _)| rfc3339(taken_ms)),
+        },
+        entries: rows,
+        closing: StatementBalance {
+            time: rfc3339(req.to_ms),
+            equity: dec(running),
+            source: "computed".into(),
+            snapshot_time: None,
+        },
+        summary: StatementSummary {
+            fills: summary.fills,
+            realized_pnl: dec(summary.realized_pnl),
+            trading_fees: dec(summary.trading_fees),
+            funding: dec(summary.funding),
+            transfers_in: dec(summary.transfers_in),
+            transfers_out: dec(summary.transfers_out),
+            net_change: dec(running - opening),
+        },
+    })
+}
+
+/// Without a full sync only the latest 2000 fills were fetched, so a
+/// month before the oldest cached fill may be missing trades.
+fn ensure_synced(db: &AtlasDb, req: &StatementRequest) -> Result<()> {
+    if db.get_sync_state(FULL_SYNC_STATE)?.is_some() {
+        return Ok(());
+    }
+    let message = match db.first_fill_time(Some(HYPERLIQUID))? {
+        Some(first) if first <= req.from_ms => return Ok(()),
+        Some(first) => format!(
+            "--month {} starts before the oldest cached fill ({}); older history has not been synced",
+            req.month,
+            rfc3339(first)
+        ),
+        None => format!(
+            "no Hyperliquid history is cached for --month {}",
+            req.month
+        ),
+    };
+    Err(AtlasError::HistoryNotSynced(message).into())
+}
+
+/// Every flow in `[from_ms, to_ms]` with its USD amount, oldest first.
+fn entries(
+    db: &AtlasDb,
+    req: &StatementRequest,
+    with_account: bool,
+    from_ms: Option<i64>,
+    to_ms: Option<i64>,
+) -> Result<Vec<(Decimal, StatementEntry)>> {
+    let mut out: Vec<(Decimal, StatementEntry)> = db
+        .query_fills(&FillFilter {
+            protocol: req.protocol.map(str::to_string),
+            coin: None,
+            from_ms,
+            to_ms,
+            limit: None,
+        })?
+        .iter()
+        .map(fill_entry)
+        .collect();
+    if with_account {
+        out.extend(db.query_funding(from_ms, to_ms)?.iter().map(funding_entry));
+        let transfers = db.query_transfers(&TransferFilter {
+            from_ms,
+            to_ms,
+            ..Default::default()
+        })?;
+        out.extend(transfers.iter().map(transfer_entry));
+    }
+    out.sort_by(|a, b| {
+        (a.1.time_ms, rank(&a.1.kind), &a.1.reference).cmp(&(
+            b.1.time_ms,
+            rank(&b.1.kind),
+            &b.1.reference,
+        ))
+    });
+    Ok(out)
+}
+
+/// Within one millisecond: transfers in, then trades, then funding.
+fn rank(kind: &str) -> u8 {
+    match kind {
+        "fill" => 1,
+        "funding" => 2,
+        _ => 0,
+    }
+}
+
+fn fill_entry(f: &DbFill) -> (Decimal, StatementEntry) {
+    let pnl = parse(&f.closed_pnl);
+    let fee = parse(&f.fee);
+    let entry = StatementEntry {
+        time: rfc3339(f.time_ms),
+        kind: "fill".into(),
+        protocol: f.protocol.clone(),
+        asset: f.coin.clone(),
+        fill: Some(FillDetail {
+            side: f.side.clone(),
+            size: f.sz.clone(),
+            price: f.px.clone(),
+            realized_pnl: dec(pnl),
+            fee: dec(fee),
+        }),
+        funding: None,
+        transfer: None,
+        amount: String::new(),
+        running_equity: String::new(),
+        reference: f.hash.clone(),
+        time_ms: f.time_ms,
+    };
+    (pnl - fee, entry)
+}
+
+fn funding_entry(f: &DbFunding) -> (Decimal, StatementEntry) {
+    let entry = StatementEntry {
+        time: rfc3339(f.time_ms),
+        kind: "funding".into(),
+        protocol: HYPERLIQUID.into(),
+        asset: f.coin.clone(),
+        fill: None,
+        funding: Some(FundingDetail {
+            position_size: f.szi.clone(),
+            rate: f.funding_rate.clone(),
+        }),
+        transfer: None,
+        amount: String::new(),
+        running_equity: String::new(),
+        reference: format!("{}@{}", f.coin, f.time_ms),
+        time_ms: f.time_ms,
+    };
+    (parse(&f.usdc), entry)
+}
+
+fn transfer_entry(t: &DbTransfer) -> (Decimal, StatementEntry) {
+    let entry = StatementEntry {
+        time: rfc3339(t.time_ms),
+        kind: t.kind.clone(),
+        protocol: HYPERLIQUID.into(),
+        asset: t.token.clone(),
+        fill: None,
+        funding: None,
+        transfer: Some(TransferDetail {
+            direction: t.direction.clone(),
+            token: t.token.clone(),
+            amount: t.amount.clone(),
+            fee: t.fee.clone(),
+            counterparty: t.counterparty.clone(),
+        }),
+        amount: String::new(),
+        running_equity: String::new(),
+        reference: t.hash.clone(),
+        time_ms: t.time_ms,
+    };
+    (t.net_usd(), entry)
+}
+
+#[derive(Default)]
+struct Totals {
+    fills: usize,
+    realized_pnl: Decimal,
+    trading_fees: Decimal,
+    funding: Decimal,
+    transfers_in: Decimal,
+    transfers_out: Decimal,
+}
+
+impl Totals {
+    fn add(&mut self, entry: &StatementEntry, amount: Decimal) {
+        if let Some(fill) = &entry.fill {
+            self.fills += 1;
+            self.realized_pnl += parse(&fill.realized_pnl);
+            self.trading_fees += parse(&fill.fee);
+        } else if entry.funding.is_some() {
+            self.funding += amount;
+        } else if amount > Decimal::ZERO {
+            self.transfers_in += amount;
+        } else {
+            self.transfers_out -= amount;
+        }
+    }
+}
+
+impl Statement {
+    /// Write the statement as CSV: one `opening` row, the `entry` rows
+    /// oldest first, one `closing` row, then `summary` rows with the total
+    /// in `amount`. See [`CSV_HEADER`] for the columns. Returns the number
+    /// of rows written, header excluded.
+    pub fn write_csv(&self, mut w: impl Write) -> std::io::Result<usize> {
+        writeln!(w, "{CSV_HEADER}")?;
+        let mut rows = 0usize;
+        let mut row = |cols: [&str; 14]| -> std::io::Result<()> {
+            let line: Vec<String> = cols.iter().map(|c| csv_field(c)).collect();
+            rows += 1;
+            writeln!(w, "{}", line.join(","))
+        };
+
+        row(self.balance_row("opening", &self.opening))?;
+        for e in &self.entries {
+            let (side, size, price, pnl, fee, rate) = match (&e.fill, &e.funding, &e.transfer) {
+                (Some(f), _, _) => (
+                    f.side.as_str(),
+                    f.size.as_str(),
+                    f.price.as_str(),
+                    f.realized_pnl.as_str(),
+                    f.fee.as_str(),
+                    "",
+                ),
+                (_, Some(f), _) => ("", f.position_size.as_str(), "", "", "", f.rate.as_str()),
+                (_, _, Some(t)) => (
+                    t.direction.as_str(),
+                    t.amount.as_str(),
+                    "",
+                    "",
+                    t.fee.as_str(),
+                    "",
+                ),
+                _ => ("", "", "", "", "", ""),
+            };
+            row([
+                "entry",
+                &e.time,
+                &e.kind,
+                &e.protocol,
+                &e.asset,
+                side,
+                size,
+                price,
+                pnl,
+                fee,
+                rate,
+                &e.amount,
+                &e.running_equity,
+                &e.reference,
+            ])?;
+        }
+        row(self.balance_row("closing", &self.closing))?;
+
+        let s = &self.summary;
+        let fills = s.fills.to_string();
+        for (name, value) in [
+            ("fills", fills.as_str()),
+            ("realized_pnl", &s.realized_pnl),
+            ("trading_fees", &s.trading_fees),
+            ("funding", &s.funding),
+            ("transfers_in", &s.transfers_in),
+            ("transfers_out", &s.transfers_out),
+            ("net_change", &s.net_change),
+        ] {
+            row([
+                "summary",
+                &self.period_end,
+                name,
+                &self.protocol,
+                "",
+                "",
+                "",
+                "",
+                "",
+                "",
+                "",
+                value,
+                "",
+                "",
+            ])?;
+        }
+        Ok(rows)
+    }
+
+    fn balance_row<'a>(&'a self, row_type: &'a str, b: &'a StatementBalance) -> [&'a str; 14] {
+        let reference = b.snapshot_time.as_deref().unwrap_or_default();
+        [
+            row_type,
+            &b.time,
+            &b.source,
+            &self.protocol,
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+            &b.equity,
+            reference,
+        ]
+    }
+}
+
+fn parse(s: &str) -> Decimal {
+    s.parse().unwrap_or_default()
+}
+
+/// Full precision, no trailing zeros.
+fn dec(d: Decimal) -> String {
+    d.normalize().to_string()
+}
+
+/// UTC with milliseconds: `2026-01-31T23:59:59.999Z`.
+fn rfc3339(ms: i64) -> String {
+    DateTime::from_timestamp_millis(ms)
+        .map(|d| d.to_rfc3339_opts(SecondsFormat::Millis, true))
+        .unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2026-01-01 00:00:00 UTC
+    const JAN: i64 = 1_767_225_600_000;
+    const DAY: i64 = 86_400_000;
+
+    fn request(protocol: Option<&str>) -> StatementRequest<'_> {
+        StatementRequest {
+            month: "2026-01",
+            from_ms: JAN,
+            to_ms: JAN + 31 * DAY - 1,
+            protocol,
+            profile: "main",
+            network: "Mainnet",
+            now_ms: JAN + 40 * DAY,
+        }
+    }
+
+    fn fill(hash: &str, time_ms: i64, pnl: &str, fee: &str) -> DbFill {
+        DbFill {
+            protocol: HYPERLIQUID.into(),
+            coin: "ETH".into(),
+            px: "3000.5".into(),
+            sz: "0.1".into(),
+            side: "Sell".into(),
+            time_ms,
+            fee: fee.into(),
+            hash: hash.into(),
+            oid: 1,
+            closed_pnl: pnl.into(),
+        }
+    }
+
+    fn deposit(hash: &str, time_ms: i64, usd: &str) -> DbTransfer {
+        DbTransfer {
+            hash: hash.into(),
+            time_ms,
+            kind: "deposit".into(),
+            ledger_type: "deposit".into(),
+            direction: "in".into(),
+            token: "USDC".into(),
+            amount: usd.into(),
+            usd_value: Some(usd.into()),
+            fee: "0".into(),
+            counterparty: None,
+        }
+    }
+
+    fn funding(time_ms: i64, usdc: &str) -> DbFunding {
+        DbFunding {
+            coin: "ETH".into(),
+            time_ms,
+            usdc: usdc.into(),
+            szi: "-0.1".into(),
+            funding_rate: "0.0000125".into(),
+        }
+    }
+
+    /// 1000 deposited in December, one trade and a funding payment before
+    /// January, then a trade, funding and a withdrawal in it.
+    fn seeded() -> AtlasDb {
+        let db = AtlasDb::open_in_memory().unwrap();
+        db.insert_transfers(&[deposit("d1", JAN - 10 * DAY, "1000")])
+            .unwrap();
+        let mut withdraw = deposit("w1", JAN + 20 * DAY, "200");
+        withdraw.kind = "withdraw".into();
+        withdraw.direction = "out".into();
+        withdraw.fee = "1".into();
+        db.insert_transfers(&[withdraw]).unwrap();
+        db.insert_fills(&[
+            fill("f0", JAN - 5 * DAY, "0", "0.3"),
+            fill("f1", JAN + DAY, "25.123456789", "0.15"),
+        ])
+        .unwrap();
+        db.insert_funding(&[funding(JAN - DAY, "0.05"), funding(JAN + DAY, "-0.0123")])
+            .unwrap();
+        db.set_sync_state(FULL_SYNC_STATE, "1").unwrap();
+        db
+    }
+
+    #[test]
+    fn test_computed_opening_and_running_equity() {
+        let db = seeded();
+        let st = build(&db, &request(None)).unwrap();
+
+        // 1000 - 0.3 + 0.05
+        assert_eq!(st.opening.equity, "999.75");
+        assert_eq!(st.opening.source, "computed");
+        assert_eq!(st.period_start, "2026-01-01T00:00:00.000Z");
+        assert_eq!(st.period_end, "2026-01-31T23:59:59.999Z");
+
+        let kinds: Vec<_> = st.entries.iter().map(|e| e.kind.as_str()).collect();
+        assert_eq!(kinds, ["fill", "funding", "withdraw"]);
+        assert_eq!(st.entries[0].amount, "24.973456789");
+        assert_eq!(st.entries[0].running_equity, "1024.723456789");
+        assert_eq!(st.entries[1].running_equity, "1024.711156789");
+        assert_eq!(st.entries[2].amount, "-200");
+
+        assert_eq!(st.closing.equity, "824.711156789");
+        assert_eq!(st.summary.fills, 1);
+        assert_eq!(st.summary.realized_pnl, "25.123456789");
+        assert_eq!(st.summary.trading_fees, "0.15");
+        assert_eq!(st.summary.funding, "-0.0123");
+        assert_eq!(st.summary.transfers_out, "200");
+        assert_eq!(st.summary.net_change, "-175.038843211");
+    }
+
+    #[test]
+    fn test_snapshot_opening_carried_forward() {
+        let db = seeded();
+        let snapshot = StatusSnapshot {
+            account_value: Some("1500".into()),
+            open_orders: 0,
+            positions: vec![],
+            balances: vec![],
+        };
+        let json = serde_json::to_string(&snapshot).unwrap();
+        db.save_status_snapshot("main", "Mainnet", &json, JAN - 3 * DAY, 5)
+            .unwrap();
+
+        let st = build(&db, &request(None)).unwrap();
+        // Only the funding after the snapshot is carried forward
+        assert_eq!(st.opening.equity, "1500.05");
+        assert_eq!(st.opening.source, "snapshot");
+        assert_eq!(
+            st.opening.snapshot_time.as_deref(),
+            Some("2025-12-29T00:00:00.000Z")
+        );
+
+        // A paper-only statement ignores the account snapshot and transfers
+        let st = build(&db, &request(Some("paper"))).unwrap();
+        assert_eq!(st.opening.equity, "0");
+        assert!(st.entries.is_empty());
+    }
+
+    #[test]
+    fn test_csv_layout() {
+        let st = build(&seeded(), &request(None)).unwrap();
+        let mut buf = Vec::new();
+        let rows = st.write_csv(&mut buf).unwrap();
+        let csv = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows, 1 + 3 + 1 + 7);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            "opening,2026-01-01T00:00:00.000Z,computed,all,,,,,,,,,999.75,"
+        );
+        assert_eq!(
+            lines[2],
+            "entry,2026-01-02T00:00:00.000Z,fill,hyperliquid,ETH,Sell,0.1,3000.5,\
+             25.123456789,0.15,,24.973456789,1024.723456789,f1"
+        );
+        assert!(lines[3].starts_with(
+            "entry,2026-01-02T00:00:00.000Z,funding,hyperliquid,ETH,,-0.1,,,,0.0000125,-0.0123,"
+        ));
+        assert!(lines[4].contains(",withdraw,hyperliquid,USDC,out,200,,,1,,-200,"));
+        assert!(lines[5].starts_with("closing,"));
+        assert_eq!(
+            lines[12],
+            "summary,2026-01-31T23:59:59.999Z,net_change,all,,,,,,,,-175.038843211,,"
+        );
+        assert!(lines.iter().all(|l| l.split(',').count() == 14));
+    }
+
+    #[test]
+    fn test_refuses_unsynced_or_future_months() {
+        let db = AtlasDb::open_in_memory().unwrap();
+        let err = build(&db, &request(None)).unwrap_err();
+        let err = err.downcast_ref::<AtlasError>().unwrap();
+        assert_eq!(err.detail().code, "HISTORY_NOT_SYNCED");
+        assert_eq!(err.detail().hints, ["Run: atlas hl sync --full"]);
+
+        // Only the latest fills cached: January predates them
+        db.insert_fills(&[fill("f1", JAN + 5 * DAY, "0", "0")])
+            .unwrap();
+        let err = build(&db, &request(None)).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("starts before the oldest cached fill"));
+        // Paper history is local and always complete
+        assert!(build(&db, &request(Some("paper"))).is_ok());
+
+        db.set_sync_state(FULL_SYNC_STATE, "1").unwrap();
+        assert!(build(&db, &request(None)).is_ok());
+
+        let mut future = request(None);
+        future.now_ms = JAN - DAY;
+        let err = build(&db, &future).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<AtlasError>(),
+            Some(AtlasError::InvalidDate(_))
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+//! Return-series statistics for cross-market analysis
+//! (`atlas market hl correlate`).
+//!
+//! All functions take plain `f64` slices and return `None` when the input
+//! is too short or degenerate (zero variance) rather than NaN.
+
+use std::collections::BTreeMap;
+
+/// Log returns `ln(p[i] / p[i-1])`, one shorter than `prices`.
+/// `None` if any price is non-positive — dropping single steps would
+/// silently misalign series that are compared pairwise.
+pub fn log_returns(prices: &[f64]) -> Option<Vec<f64>> {
+    if prices.iter().any(|p| *p <= 0.0 || !p.is_finite()) {
+        return None;
+    }
+    Some(prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect())
+}
+
+pub fn mean(xs: &[f64]) -> Option<f64> {
+    if xs.is_empty() {
+        return None;
+    }
+    Some(xs.iter().sum::<f64>() / xs.len() as f64)
+}
+
+/// Sample covariance (n - 1 denominator). Slices must be the same length.
+pub fn covariance(a: &[f64], b: &[f64]) -> Option<f64> {
+    if a.len() != b.len() || a.len() < 2 {
+        return None;
+    }
+    let (ma, mb) = (mean(a)?, mean(b)?);
+    let sum: f64 = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum();
+    Some(sum / (a.len() - 1) as f64)
+}
+
+/// Sample variance.
+pub fn variance(xs: &[f64]) -> Option<f64> {
+    covariance(xs, xs)
+}
+
+/// Pearson correlation in [-1, 1]. `None` if either series is flat.
+pub fn correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let cov = covariance(a, b)?;
+    let (va, vb) = (variance(a)?, variance(b)?);
+    if va <= 0.0 || vb <= 0.0 {
+        return None;
+    }
+    Some((cov / (va.sqrt() * vb.sqrt())).clamp(-1.0, 1.0))
+}
+
+/// Beta of `asset` to `benchmark`: cov(asset, benchmark) / var(benchmark).
+pub fn beta(asset: &[f64], benchmark: &[f64]) -> Option<f64> {
+    let var = variance(benchmark)?;
+    if var <= 0.0 {
+        return None;
+    }
+    Some(covariance(asset, benchmark)? / var)
+}
+
+/// Align several `(timestamp, value)` series on the timestamps present in
+/// all of them. Returns the shared timestamps (ascending) and one value
+/// column per input series, in input order.
+pub fn align_by_time(series: &[Vec<(u64, f64)>]) -> (Vec<u64>, Vec<Vec<f64>>) {
+    let maps: Vec<BTreeMap<u64, f64>> =
+        series.iter().map(|s| s.iter().copied().collect()).collect();
+    let Some((first, rest)) = maps.split_first() else {
+        return (Vec::new(), Vec::new());
+    };
+    let times: Vec<u64> = first
+        .keys()
+        .copied()
+        .filter(|t| rest.iter().all(|m| m.contains_key(t)))
+        .collect();
+    let columns = maps
+        .iter()
+        .map(|m| times.iter().map(|t| m[t]).collect())
+        .collect();
+    (times, columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn test_log_returns_fixture() {
+        let r = log_returns(&[100.0, 110.0, 99.0]).unwrap();
+        assert_eq!(r.len(), 2);
+        assert!(close(r[0], 0.095_310_179_804_3));
+        assert!(close(r[1], -0.105_360_515_657_8));
+        assert!(log_returns(&[100.0]).unwrap().is_empty());
+        assert_eq!(log_returns(&[100.0, 0.0, 50.0]), None);
+    }
+
+    #[test]
+    fn test_covariance_correlation_beta_fixture() {
+        // Deviations a: -2,-1,0,1,2  b: -1,-2,1,0,2 → Σab = 8, Σa² = Σb² = 10
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [2.0, 1.0, 4.0, 3.0, 5.0];
+        assert!(close(covariance(&a, &b).unwrap(), 2.0));
+        assert!(close(variance(&a).unwrap(), 2.5));
+        assert!(close(correlation(&a, &b).unwrap(), 0.8));
+        assert!(close(beta(&b, &a).unwrap(), 0.8));
+
+        let doubled: Vec<f64> = a.iter().map(|x| x * 2.0).collect();
+        assert!(close(correlation(&a, &doubled).unwrap(), 1.0));
+        assert!(close(beta(&doubled, &a).unwrap(), 2.0));
+        let inverted: Vec<f64> = a.iter().rev().copied().collect();
+        assert!(close(correlation(&a, &inverted).unwrap(), -1.0));
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert_eq!(correlation(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]), None);
+        assert_eq!(beta(&[1.0, 2.0], &[3.0, 3.0]), None);
+        assert_eq!(covariance(&[1.0, 2.0], &[1.0]), None);
+        assert_eq!(mean(&[]), None);
+    }
+
+    #[test]
+    fn test_align_by_time_intersects() {
+        let btc = vec![(1, 10.0), (2, 11.0), (3, 12.0), (4, 13.0)];
+        // Newer listing: missing t=1, and a gap at t=3
+        let hype = vec![(2, 20.0), (4, 22.0), (5, 23.0)];
+        let (times, cols) = align_by_time(&[btc, hype]);
+        assert_eq!(times, vec![2, 4]);
+        assert_eq!(cols[0], vec![11.0, 13.0]);
+        assert_eq!(cols[1], vec![20.0, 22.0]);
+
+        let (times, cols) = align_by_time(&[]);
+        assert!(times.is_empty() && cols.is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+//! Account snapshots for `atlas status --diff`.
+//!
+//! Every `atlas status` run saves what it saw (account value, open order
+//! count, positions and balances) to the local DB, keyed by profile and
+//! network. `--diff` compares the current run with the previous snapshot.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::output::{PositionDiffRow, StatusDiff, StatusOutput};
+
+/// What a status run saw, as stored in `status_snapshots`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusSnapshot {
+    pub account_value: Option<String>,
+    pub open_orders: usize,
+    pub positions: Vec<SnapshotPosition>,
+    pub balances: Vec<SnapshotBalance>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotPosition {
+    pub protocol: String,
+    pub symbol: String,
+    /// Size as `PositionRow::size` shows it.
+    pub size: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotBalance {
+    pub protocol: String,
+    pub asset: String,
+    pub total: String,
+}
+
+impl From<&StatusOutput> for StatusSnapshot {
+    fn from(s: &StatusOutput) -> Self {
+        Self {
+            account_value: s.account_value.clone(),
+            open_orders: s.open_orders,
+            positions: s
+                .positions
+                .iter()
+                .map(|p| SnapshotPosition {
+                    protocol: p.protocol.clone(),
+                    symbol: p.coin.clone(),
+                    size: p.size.clone(),
+                })
+                .collect(),
+            balances: s
+                .balances
+                .iter()
+                .map(|b| SnapshotBalance {
+                    protocol: b.protocol.clone(),
+                    asset: b.asset.clone(),
+                    total: b.total.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Changes from `prev` (taken at `baseline_ms`) to `now`. Positions are
+/// matched by protocol and symbol: current ones first (`new` or
+/// `resized`), then `closed` ones; unchanged positions are left out.
+pub fn diff(prev: &StatusSnapshot, baseline_ms: i64, now: &StatusSnapshot) -> StatusDiff {
+    let dec = |s: Option<&str>| s.and_then(|s| s.parse::<Decimal>().ok());
+    let account_value_change = match (
+        dec(prev.account_value.as_deref()),
+        dec(now.account_value.as_deref()),
+    ) {
+        (Some(before), Some(after)) => Some((after - before).normalize().to_string()),
+        _ => None,
+    };
+
+    let same = |a: &SnapshotPosition, b: &SnapshotPosition| {
+        a.protocol == b.protocol && a.symbol.eq_ignore_ascii_case(&b.symbol)
+    };
+    let row = |p: &SnapshotPosition, change: &str, before: Option<&str>, after: Option<&str>| {
+        let delta = dec(after).unwrap_or_default() - dec(before).unwrap_or_default();
+        PositionDiffRow {
+            symbol: p.symbol.clone(),
+            protocol: p.protocol.clone(),
+            change: change.into(),
+            size_before: before.map(str::to_string),
+            size_after: after.map(str::to_string),
+            size_change: delta.normalize().to_string(),
+        }
+    };
+
+    let mut positions = Vec::new();
+    for p in &now.positions {
+        match prev.positions.iter().find(|q| same(q, p)) {
+            None => positions.push(row(p, "new", None, Some(&p.size))),
+            Some(q) if dec(Some(&q.size)) != dec(Some(&p.size)) => {
+                positions.push(row(p, "resized", Some(&q.size), Some(&p.size)))
+            }
+            Some(_) => {}
+        }
+    }
+    for q in &prev.positions {
+        if !now.positions.iter().any(|p| same(q, p)) {
+            positions.push(row(q, "closed", Some(&q.size), None));
+        }
+    }
+
+    StatusDiff {
+        baseline_ms: Some(baseline_ms),
+        account_value_change,
+        open_orders_change: now.open_orders as i64 - prev.open_orders as i64,
+        positions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(value: &str, orders: usize, positions: &[(&str, &str)]) -> StatusSnapshot {
+        StatusSnapshot {
+            account_value: Some(value.into()),
+            open_orders: orders,
+            positions: positions
+                .iter()
+                .map(|(symbol, size)| SnapshotPosition {
+                    protocol: "hyperliquid".into(),
+                    symbol: symbol.to_string(),
+                    size: size.to_string(),
+                })
+                .collect(),
+            balances: vec![],
+        }
+    }
+
+    #[test]
+    fn test_diff_positions_value_and_orders() {
+        let prev = snapshot(
+            "5000.00",
+            3,
+            &[("BTC", "0.1"), ("ETH", "-2"), ("SOL", "10")],
+        );
+        let now = snapshot(
+            "5123.45",
+            1,
+            &[("ETH", "-3.0"), ("SOL", "10.0"), ("HYPE", "50")],
+        );
+        let d = diff(&prev, 1_000, &now);
+
+        assert_eq!(d.baseline_ms, Some(1_000));
+        assert_eq!(d.account_value_change.as_deref(), Some("123.45"));
+        assert_eq!(d.open_orders_change, -2);
+        let summary: Vec<_> = d
+            .positions
+            .iter()
+            .map(|p| (p.symbol.as_str(), p.change.as_str(), p.size_change.as_str()))
+            .collect();
+        // SOL "10" vs "10.0" is unchanged
+        assert_eq!(
+            summary,
+            [
+                ("ETH", "resized", "-1"),
+                ("HYPE", "new", "50"),
+                ("BTC", "closed", "-0.1"),
+            ]
+        );
+        assert_eq!(d.positions[2].size_after, None);
+    }
+
+    #[test]
+    fn test_diff_without_account_value() {
+        let mut prev = snapshot("0", 0, &[]);
+        prev.account_value = None;
+        let d = diff(&prev, 1_000, &snapshot("100", 0, &[]));
+        assert_eq!(d.account_value_change, None);
+        assert!(d.positions.is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+//! Coin symbol resolution: exact match, aliases, then fuzzy suggestions.
+//!
+//! Every command that takes a coin goes through [`SymbolResolver`], so `btc`,
+//! `XBT` and `1000PEPE` all land on the exchange's own name (`BTC`, `kPEPE`)
+//! and a typo fails with "did you mean" hints instead of a bare not-found.
+
+use std::collections::HashMap;
+
+use crate::error::AtlasError;
+use crate::types::SpotMarket;
+
+/// Common names traders use that the exchange lists differently.
+pub const BUILTIN_ALIASES: &[(&str, &str)] = &[
+    ("XBT", "BTC"),
+    ("WBTC", "BTC"),
+    ("BITCOIN", "BTC"),
+    ("WETH", "ETH"),
+    ("ETHEREUM", "ETH"),
+    ("SOLANA", "SOL"),
+    ("WSOL", "SOL"),
+    ("MATIC", "POL"),
+];
+
+/// Suggestions attached to a not-found error.
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// Maps user input to a listed market name.
+#[derive(Debug, Clone)]
+pub struct SymbolResolver {
+    /// Uppercased alias → target name.
+    aliases: HashMap<String, String>,
+}
+
+impl Default for SymbolResolver {
+    fn default() -> Self {
+        Self {
+            aliases: BUILTIN_ALIASES
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl SymbolResolver {
+    /// Built-in aliases plus user-defined ones; a user alias wins on conflict.
+    pub fn with_aliases(mut self, user: &HashMap<String, String>) -> Self {
+        for (from, to) in user {
+            self.aliases.insert(from.to_uppercase(), to.clone());
+        }
+        self
+    }
+
+    /// Alias target for `input`, if one is defined.
+    pub fn alias(&self, input: &str) -> Option<&str> {
+        self.aliases
+            .get(&input.trim().to_uppercase())
+            .map(String::as_str)
+    }
+
+    /// Resolve `input` to one of `names`.
+    ///
+    /// Order: case-insensitive exact match, alias, then the `1000X` → `kX`
+    /// convention other venues use for low-priced coins. No match returns
+    /// [`AtlasError::AssetNotFound`] with the nearest names as suggestions.
+    pub fn resolve<'a, I>(&self, input: &str, names: I) -> Result<&'a str, AtlasError>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let names: Vec<&'a str> = names.into_iter().collect();
+        let input = input.trim();
+        let find = |want: &str| names.iter().copied().find(|n| n.eq_ignore_ascii_case(want));
+
+        if let Some(name) = find(input) {
+            return Ok(name);
+        }
+        if let Some(name) = self.alias(input).and_then(find) {
+            return Ok(name);
+        }
+        if let Some(rest) = input.strip_prefix("1000") {
+            if let Some(name) = find(&format!("k{rest}")) {
+                return Ok(name);
+            }
+        }
+
+        Err(AtlasError::AssetNotFound {
+            symbol: input.to_string(),
+            suggestions: suggest(input, &names, MAX_SUGGESTIONS),
+        })
+    }
+}
+
+/// Resolve spot input to a listed pair: the exchange key (`@107`), the
+/// pair name (`HYPE/USDC`, any case), or a base token (`HYPE`), which
+/// prefers the USDC-quoted pair. No match suggests the nearest pairs.
+pub fn resolve_spot<'a>(
+    markets: &'a [SpotMarket],
+    input: &str,
+) -> Result<&'a SpotMarket, AtlasError> {
+    let input = input.trim();
+    let by_key = markets.iter().find(|m| m.key.eq_ignore_ascii_case(input));
+    let by_pair = || markets.iter().find(|m| m.pair.eq_ignore_ascii_case(input));
+    let by_base = || {
+        let has_base = |m: &&SpotMarket| m.base.eq_ignore_ascii_case(input);
+        let mut bases = markets.iter().filter(has_base);
+        markets
+            .iter()
+            .filter(has_base)
+            .find(|m| m.quote == "USDC")
+            .or_else(|| bases.next())
+    };
+    if let Some(market) = by_key.or_else(by_pair).or_else(by_base) {
+        return Ok(market);
+    }
+
+    let pairs: Vec<&str> = markets.iter().map(|m| m.pair.as_str()).collect();
+    Err(AtlasError::AssetNotFound {
+        symbol: format!("Spot: {input}"),
+        suggestions: suggest(input, &pairs, MAX_SUGGESTIONS),
+    })
+}
+
+/// Edit distance between two strings, compared case-insensitively.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_uppercase().chars().collect();
+    let b: Vec<char> = b.to_uppercase().chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// How well `name` matches `query`; lower is better, `None` is no match.
+///
+/// 0 exact, 1 prefix, 2 substring, then `2 + edit distance` for typos of
+/// at most a third of the query length, rounded up.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<usize> {
+    let q = query.trim().to_uppercase();
+    let n = name.to_uppercase();
+    if q.is_empty() {
+        return None;
+    }
+    if n == q {
+        return Some(0);
+    }
+    if n.starts_with(&q) {
+        return Some(1);
+    }
+    if n.contains(&q) {
+        return Some(2);
+    }
+    let max_edits = q.chars().count().div_ceil(3);
+    let d = levenshtein(&q, &n);
+    (d <= max_edits).then_some(2 + d)
+}
+
+/// Up to `n` names closest to `query`, best first (ties by name).
+pub fn suggest(query: &str, names: &[&str], n: usize) -> Vec<String> {
+    let mut scored: Vec<(usize, &str)> = names
+        .iter()
+        .filter_map(|name| fuzzy_score(query, name).map(|s| (s, *name)))
+        .collect();
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    scored
+        .into_iter()
+        .take(n)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKETS: &[&str] = &[
+        "BTC", "ETH", "SOL", "kPEPE", "kBONK", "ETHFI", "POL", "HYPE",
+    ];
+
+    fn resolve(r: &SymbolResolver, input: &str) -> Result<String, AtlasError> {
+        r.resolve(input, MARKETS.iter().copied())
+            .map(str::to_string)
+    }
+
+    #[test]
+    fn test_exact_match_ignores_case() {
+        let r = SymbolResolver::default();
+        assert_eq!(resolve(&r, "btc").unwrap(), "BTC");
+        assert_eq!(resolve(&r, "KPEPE").unwrap(), "kPEPE");
+        assert_eq!(resolve(&r, " hype ").unwrap(), "HYPE");
+    }
+
+    #[test]
+    fn test_builtin_and_thousand_aliases() {
+        let r = SymbolResolver::default();
+        assert_eq!(resolve(&r, "XBT").unwrap(), "BTC");
+        assert_eq!(resolve(&r, "weth").unwrap(), "ETH");
+        assert_eq!(resolve(&r, "MATIC").unwrap(), "POL");
+        assert_eq!(resolve(&r, "1000PEPE").unwrap(), "kPEPE");
+        assert_eq!(resolve(&r, "1000bonk").unwrap(), "kBONK");
+        // `1000X` only resolves when `kX` is listed.
+        assert!(resolve(&r, "1000SHIB").is_err());
+    }
+
+    #[test]
+    fn test_user_aliases_override_builtins() {
+        let user = HashMap::from([
+            ("xbt".to_string(), "ETH".to_string()),
+            ("PURRFECT".to_string(), "HYPE".to_string()),
+        ]);
+        let r = SymbolResolver::default().with_aliases(&user);
+        assert_eq!(resolve(&r, "XBT").unwrap(), "ETH");
+        assert_eq!(resolve(&r, "purrfect").unwrap(), "HYPE");
+        assert_eq!(r.alias("WBTC"), Some("BTC"));
+    }
+
+    #[test]
+    fn test_not_found_suggests_nearest() {
+        let r = SymbolResolver::default();
+        let err = resolve(&r, "ETHH").unwrap_err();
+        match &err {
+            AtlasError::AssetNotFound {
+                symbol,
+                suggestions,
+            } => {
+                assert_eq!(symbol, "ETHH");
+                assert_eq!(suggestions, &["ETH", "ETHFI"]);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(err.detail().hints[0].contains("Did you mean ETH, ETHFI?"));
+
+        let err = resolve(&r, "PEPE").unwrap_err();
+        assert!(matches!(
+            err,
+            AtlasError::AssetNotFound { ref suggestions, .. } if suggestions[0] == "kPEPE"
+        ));
+
+        // Nothing close: no suggestions, only the generic hint.
+        let err = resolve(&r, "ZZZZZZ").unwrap_err();
+        assert!(matches!(
+            err,
+            AtlasError::AssetNotFound { ref suggestions, .. } if suggestions.is_empty()
+        ));
+        assert!(err.detail().hints[0].contains("atlas market hyperliquid list"));
+    }
+
+    fn spot(pair: &str, index: usize, key: &str) -> SpotMarket {
+        let (base, quote) = pair.split_once('/').unwrap();
+        SpotMarket {
+            pair: pair.into(),
+            base: base.into(),
+            quote: quote.into(),
+            index,
+            key: key.into(),
+            sz_decimals: 2,
+            wei_decimals: 8,
+            mid_price: None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_spot_by_key_pair_or_base() {
+        let markets = vec![
+            spot("PURR/USDC", 0, "PURR/USDC"),
+            spot("HYPE/USDT", 140, "@140"),
+            spot("HYPE/USDC", 107, "@107"),
+            spot("UBTC/USDC", 142, "@142"),
+        ];
+        let key = |input: &str| resolve_spot(&markets, input).map(|m| m.key.as_str());
+
+        assert_eq!(key("@107").unwrap(), "@107");
+        assert_eq!(key("hype/usdt").unwrap(), "@140");
+        assert_eq!(key("purr/usdc").unwrap(), "PURR/USDC");
+        // A bare base prefers the USDC quote over listing order
+        assert_eq!(key("HYPE").unwrap(), "@107");
+        assert_eq!(key(" purr ").unwrap(), "PURR/USDC");
+
+        let err = resolve_spot(&markets, "UBT").unwrap_err();
+        assert!(matches!(
+            err,
+            AtlasError::AssetNotFound { ref suggestions, .. } if suggestions[0] == "UBTC/USDC"
+        ));
+        assert!(resolve_spot(&markets, "@999").is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_score_ordering() {
+        assert_eq!(fuzzy_score("eth", "ETH"), Some(0));
+        assert_eq!(fuzzy_score("eth", "ETHFI"), Some(1));
+        assert_eq!(fuzzy_score("pepe", "kPEPE"), Some(2));
+        assert_eq!(fuzzy_score("SOLL", "SOL"), Some(3));
+        assert_eq!(fuzzy_score("BTC", "ETH"), None);
+        assert_eq!(fuzzy_score("", "ETH"), None);
+        assert_eq!(suggest("et", MARKETS, 3), vec!["ETH", "ETHFI"]);
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("ETH", "eth"), 0);
+        assert_eq!(levenshtein("", "SOL"), 3);
+    }
+}
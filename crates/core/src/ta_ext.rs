@@ -0,0 +1,314 @@
+//! Indicators the `ta` crate doesn't ship: Ichimoku Cloud and SuperTrend.
+//!
+//! Both are streaming like the `ta` indicators — feed one candle at a time
+//! through [`Next`] and read the latest output.
+
+use std::collections::VecDeque;
+
+use ta::errors::{Result as TaResult, TaError};
+use ta::indicators::AverageTrueRange;
+use ta::{Close, High, Low, Next};
+
+// ─── Ichimoku Cloud ─────────────────────────────────────────────────
+
+/// Ichimoku line values at the latest candle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IchimokuOutput {
+    /// Conversion line: midpoint of the last `tenkan` candles.
+    pub tenkan: f64,
+    /// Base line: midpoint of the last `kijun` candles.
+    pub kijun: f64,
+    /// Leading span A of the cloud under the current candle
+    /// (computed `kijun` candles ago).
+    pub senkou_a: f64,
+    /// Leading span B of the cloud under the current candle.
+    pub senkou_b: f64,
+    /// Lagging span: the latest close, plotted `kijun` candles back.
+    pub chikou: f64,
+    /// Close `kijun` candles ago — what the lagging span is compared to.
+    pub chikou_ref: f64,
+}
+
+impl IchimokuOutput {
+    pub fn cloud_top(&self) -> f64 {
+        self.senkou_a.max(self.senkou_b)
+    }
+
+    pub fn cloud_bottom(&self) -> f64 {
+        self.senkou_a.min(self.senkou_b)
+    }
+
+    /// `"above cloud"`, `"below cloud"` or `"in cloud"`.
+    pub fn cloud_position(&self, price: f64) -> &'static str {
+        if price > self.cloud_top() {
+            "above cloud"
+        } else if price < self.cloud_bottom() {
+            "below cloud"
+        } else {
+            "in cloud"
+        }
+    }
+}
+
+/// Ichimoku Kinko Hyo. Standard periods are 9/26/52; the cloud is
+/// displaced forward by the `kijun` period.
+#[derive(Debug, Clone)]
+pub struct Ichimoku {
+    tenkan: usize,
+    kijun: usize,
+    senkou_b: usize,
+    highs: VecDeque<f64>,
+    lows: VecDeque<f64>,
+    closes: VecDeque<f64>,
+    /// Leading spans (A, B) as computed on each of the last `kijun + 1` candles.
+    spans: VecDeque<(f64, f64)>,
+}
+
+impl Ichimoku {
+    pub fn new(tenkan: usize, kijun: usize, senkou_b: usize) -> TaResult<Self> {
+        if tenkan == 0 || kijun == 0 || senkou_b == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        let window = tenkan.max(kijun).max(senkou_b);
+        Ok(Self {
+            tenkan,
+            kijun,
+            senkou_b,
+            highs: VecDeque::with_capacity(window + 1),
+            lows: VecDeque::with_capacity(window + 1),
+            closes: VecDeque::with_capacity(kijun + 2),
+            spans: VecDeque::with_capacity(kijun + 2),
+        })
+    }
+
+    /// Candles needed before the first output.
+    pub fn warmup(&self) -> usize {
+        self.senkou_b.max(self.tenkan).max(self.kijun) + self.kijun
+    }
+
+    /// Midpoint of the highest high and lowest low of the last `period` candles.
+    fn midpoint(&self, period: usize) -> f64 {
+        let start = self.highs.len().saturating_sub(period);
+        let high = self.highs.range(start..).copied().fold(f64::MIN, f64::max);
+        let low = self.lows.range(start..).copied().fold(f64::MAX, f64::min);
+        (high + low) / 2.0
+    }
+}
+
+impl Default for Ichimoku {
+    fn default() -> Self {
+        Self::new(9, 26, 52).expect("standard periods are valid")
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for Ichimoku {
+    /// `None` until [`warmup`](Ichimoku::warmup) candles have been seen.
+    type Output = Option<IchimokuOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let window = self.tenkan.max(self.kijun).max(self.senkou_b);
+        self.highs.push_back(input.high());
+        self.lows.push_back(input.low());
+        if self.highs.len() > window {
+            self.highs.pop_front();
+            self.lows.pop_front();
+        }
+        self.closes.push_back(input.close());
+        if self.closes.len() > self.kijun + 1 {
+            self.closes.pop_front();
+        }
+
+        let tenkan = self.midpoint(self.tenkan);
+        let kijun = self.midpoint(self.kijun);
+        if self.highs.len() >= window {
+            let lead_a = (tenkan + kijun) / 2.0;
+            let lead_b = self.midpoint(self.senkou_b);
+            self.spans.push_back((lead_a, lead_b));
+            if self.spans.len() > self.kijun + 1 {
+                self.spans.pop_front();
+            }
+        }
+
+        if self.spans.len() < self.kijun + 1 {
+            return None;
+        }
+        let (senkou_a, senkou_b) = *self.spans.front()?;
+        Some(IchimokuOutput {
+            tenkan,
+            kijun,
+            senkou_a,
+            senkou_b,
+            chikou: input.close(),
+            chikou_ref: *self.closes.front()?,
+        })
+    }
+}
+
+// ─── SuperTrend ─────────────────────────────────────────────────────
+
+/// SuperTrend line at the latest candle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuperTrendOutput {
+    /// The active band: the lower band in an uptrend, upper in a downtrend.
+    pub value: f64,
+    pub upper: f64,
+    pub lower: f64,
+    pub uptrend: bool,
+}
+
+/// SuperTrend: ATR bands around the candle midpoint that only tighten
+/// while the trend holds and flip when price closes through them.
+#[derive(Debug, Clone)]
+pub struct SuperTrend {
+    atr: AverageTrueRange,
+    multiplier: f64,
+    prev: Option<(SuperTrendOutput, f64)>,
+}
+
+impl SuperTrend {
+    pub fn new(period: usize, multiplier: f64) -> TaResult<Self> {
+        if multiplier <= 0.0 || !multiplier.is_finite() {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            atr: AverageTrueRange::new(period)?,
+            multiplier,
+            prev: None,
+        })
+    }
+}
+
+impl Default for SuperTrend {
+    fn default() -> Self {
+        Self::new(10, 3.0).expect("standard parameters are valid")
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for SuperTrend {
+    type Output = SuperTrendOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let atr = self.atr.next(input);
+        let mid = (input.high() + input.low()) / 2.0;
+        let basic_upper = mid + self.multiplier * atr;
+        let basic_lower = mid - self.multiplier * atr;
+        let close = input.close();
+
+        let out = match self.prev {
+            None => SuperTrendOutput {
+                value: basic_lower,
+                upper: basic_upper,
+                lower: basic_lower,
+                uptrend: true,
+            },
+            Some((prev, prev_close)) => {
+                let upper = if basic_upper < prev.upper || prev_close > prev.upper {
+                    basic_upper
+                } else {
+                    prev.upper
+                };
+                let lower = if basic_lower > prev.lower || prev_close < prev.lower {
+                    basic_lower
+                } else {
+                    prev.lower
+                };
+                let uptrend = if prev.uptrend {
+                    close >= lower
+                } else {
+                    close > upper
+                };
+                SuperTrendOutput {
+                    value: if uptrend { lower } else { upper },
+                    upper,
+                    lower,
+                    uptrend,
+                }
+            }
+        };
+        self.prev = Some((out, close));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ta::DataItem;
+
+    /// Steady ramp: candle `i` spans [i, i+1] and closes at i+0.5.
+    fn ramp(n: usize) -> Vec<DataItem> {
+        (0..n)
+            .map(|i| candle(i as f64 + 1.0, i as f64, i as f64 + 0.5))
+            .collect()
+    }
+
+    fn candle(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .open(close)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_ichimoku_reference_values() {
+        // On the ramp every window midpoint is `n - period/2` at the last
+        // candle, so the lines have closed-form values for n = 100.
+        let mut ichi = Ichimoku::default();
+        let mut out = None;
+        for item in &ramp(100) {
+            out = ichi.next(item);
+        }
+        let out = out.unwrap();
+        assert_eq!(out.tenkan, 95.5);
+        assert_eq!(out.kijun, 87.0);
+        // Cloud under the current candle was computed 26 candles ago (n = 74)
+        assert_eq!(out.senkou_a, 65.25);
+        assert_eq!(out.senkou_b, 48.0);
+        assert_eq!(out.chikou, 99.5);
+        assert_eq!(out.chikou_ref, 73.5);
+        assert_eq!(out.cloud_position(99.5), "above cloud");
+        assert_eq!(out.cloud_position(50.0), "in cloud");
+        assert_eq!(out.cloud_position(10.0), "below cloud");
+    }
+
+    #[test]
+    fn test_ichimoku_warmup() {
+        let mut ichi = Ichimoku::default();
+        assert_eq!(ichi.warmup(), 78);
+        let outputs: Vec<_> = ramp(78).iter().map(|i| ichi.next(i)).collect();
+        assert!(outputs[..77].iter().all(Option::is_none));
+        assert!(outputs[77].is_some());
+        assert!(Ichimoku::new(9, 0, 52).is_err());
+    }
+
+    #[test]
+    fn test_supertrend_reference_values() {
+        // period 3 → ATR is an EMA with k = 0.5 seeded by the first TR:
+        // TR = 1, 1.5, 1.5 → ATR = 1, 1.25, 1.375
+        let mut st = SuperTrend::new(3, 1.0).unwrap();
+        let values: Vec<f64> = ramp(3).iter().map(|i| st.next(i).value).collect();
+        assert_eq!(values, vec![-0.5, 0.25, 1.125]);
+    }
+
+    #[test]
+    fn test_supertrend_flips_on_breakdown() {
+        let mut st = SuperTrend::new(3, 1.0).unwrap();
+        let mut out = None;
+        for item in &ramp(20) {
+            out = Some(st.next(item));
+        }
+        let up = out.unwrap();
+        assert!(up.uptrend);
+        assert!(up.value < 19.5);
+
+        let down = st.next(&candle(19.0, 10.0, 10.5));
+        assert!(!down.uptrend);
+        assert_eq!(down.value, down.upper);
+        assert!(down.value > 10.5);
+        assert!(SuperTrend::new(10, 0.0).is_err());
+    }
+}
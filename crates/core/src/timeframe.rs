@@ -0,0 +1,255 @@
+//! Candle timeframes (`--timeframe`, stream intervals).
+//!
+//! Parsed once at the CLI boundary so a typo fails before any network call.
+//! Accepts aliases — `60m` → `1h`, `D` → `1d`, `1H` → `1h` — and always
+//! prints the canonical Hyperliquid spelling. `m` is minutes and `M` is
+//! months, so that single letter stays case-sensitive.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::AtlasError;
+
+const MINUTE_MS: u64 = 60_000;
+
+/// A supported candle interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Timeframe {
+    M1,
+    M3,
+    M5,
+    M15,
+    M30,
+    H1,
+    H2,
+    H4,
+    H8,
+    H12,
+    D1,
+    D3,
+    W1,
+    Month1,
+}
+
+impl Timeframe {
+    /// Shortest to longest.
+    pub const ALL: [Timeframe; 14] = [
+        Timeframe::M1,
+        Timeframe::M3,
+        Timeframe::M5,
+        Timeframe::M15,
+        Timeframe::M30,
+        Timeframe::H1,
+        Timeframe::H2,
+        Timeframe::H4,
+        Timeframe::H8,
+        Timeframe::H12,
+        Timeframe::D1,
+        Timeframe::D3,
+        Timeframe::W1,
+        Timeframe::Month1,
+    ];
+
+    /// Canonical spelling, as the exchange expects it.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Timeframe::M1 => "1m",
+            Timeframe::M3 => "3m",
+            Timeframe::M5 => "5m",
+            Timeframe::M15 => "15m",
+            Timeframe::M30 => "30m",
+            Timeframe::H1 => "1h",
+            Timeframe::H2 => "2h",
+            Timeframe::H4 => "4h",
+            Timeframe::H8 => "8h",
+            Timeframe::H12 => "12h",
+            Timeframe::D1 => "1d",
+            Timeframe::D3 => "3d",
+            Timeframe::W1 => "1w",
+            Timeframe::Month1 => "1M",
+        }
+    }
+
+    /// Length in minutes (a month counts as 30 days).
+    pub fn minutes(self) -> u64 {
+        match self {
+            Timeframe::M1 => 1,
+            Timeframe::M3 => 3,
+            Timeframe::M5 => 5,
+            Timeframe::M15 => 15,
+            Timeframe::M30 => 30,
+            Timeframe::H1 => 60,
+            Timeframe::H2 => 120,
+            Timeframe::H4 => 240,
+            Timeframe::H8 => 480,
+            Timeframe::H12 => 720,
+            Timeframe::D1 => 1_440,
+            Timeframe::D3 => 4_320,
+            Timeframe::W1 => 10_080,
+            Timeframe::Month1 => 43_200,
+        }
+    }
+
+    /// Length in milliseconds.
+    pub fn duration_ms(self) -> u64 {
+        self.minutes() * MINUTE_MS
+    }
+
+    fn from_minutes(minutes: u64) -> Option<Self> {
+        // A month is only ever spelled as one (`1M`), never `30d`.
+        Self::ALL
+            .into_iter()
+            .find(|tf| *tf != Timeframe::Month1 && tf.minutes() == minutes)
+    }
+}
+
+/// Valid timeframes, for help and error text: `1m, 3m, ..., 1M`.
+pub fn supported_list() -> String {
+    Timeframe::ALL
+        .iter()
+        .map(|tf| tf.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for Timeframe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Timeframe {
+    type Err = AtlasError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || AtlasError::InvalidTimeframe(format!("'{s}' (valid: {})", supported_list()));
+        let trimmed = s.trim();
+        let split = trimmed
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(trimmed.len());
+        let (num, unit) = trimmed.split_at(split);
+        let n: u64 = match num {
+            "" => 1, // bare unit: `D`, `h`, `W`
+            _ => num.parse().map_err(|_| invalid())?,
+        };
+
+        // Months first: `M` is case-sensitive, longer spellings are not.
+        let unit_lower = unit.to_ascii_lowercase();
+        if unit == "M" || matches!(unit_lower.as_str(), "mo" | "mon" | "month" | "months") {
+            return if n == 1 {
+                Ok(Timeframe::Month1)
+            } else {
+                Err(invalid())
+            };
+        }
+        let unit_minutes = match unit_lower.as_str() {
+            "m" | "min" | "mins" | "minute" | "minutes" => 1,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 60,
+            "d" | "day" | "days" => 1_440,
+            "w" | "wk" | "week" | "weeks" => 10_080,
+            _ => return Err(invalid()),
+        };
+        n.checked_mul(unit_minutes)
+            .and_then(Timeframe::from_minutes)
+            .ok_or_else(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tf(s: &str) -> Timeframe {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    #[test]
+    fn test_canonical_round_trip() {
+        for t in Timeframe::ALL {
+            assert_eq!(tf(t.as_str()), t);
+            assert_eq!(t.to_string(), t.as_str());
+        }
+        assert!(Timeframe::ALL
+            .windows(2)
+            .all(|w| w[0].minutes() < w[1].minutes()));
+    }
+
+    #[test]
+    fn test_alias_table() {
+        let cases: &[(&str, Timeframe)] = &[
+            ("1min", Timeframe::M1),
+            ("3MIN", Timeframe::M3),
+            ("5mins", Timeframe::M5),
+            ("15minutes", Timeframe::M15),
+            ("30m", Timeframe::M30),
+            ("60m", Timeframe::H1),
+            ("1H", Timeframe::H1),
+            ("h", Timeframe::H1),
+            ("1hr", Timeframe::H1),
+            ("1hour", Timeframe::H1),
+            ("120m", Timeframe::H2),
+            ("2H", Timeframe::H2),
+            ("240m", Timeframe::H4),
+            ("4hrs", Timeframe::H4),
+            ("480m", Timeframe::H8),
+            ("8hours", Timeframe::H8),
+            ("720m", Timeframe::H12),
+            ("12H", Timeframe::H12),
+            ("D", Timeframe::D1),
+            ("1D", Timeframe::D1),
+            ("24h", Timeframe::D1),
+            ("1440m", Timeframe::D1),
+            ("1day", Timeframe::D1),
+            ("3D", Timeframe::D3),
+            ("72h", Timeframe::D3),
+            ("3days", Timeframe::D3),
+            ("W", Timeframe::W1),
+            ("1W", Timeframe::W1),
+            ("7d", Timeframe::W1),
+            ("168h", Timeframe::W1),
+            ("1wk", Timeframe::W1),
+            ("1week", Timeframe::W1),
+            ("M", Timeframe::Month1),
+            ("1M", Timeframe::Month1),
+            ("1mo", Timeframe::Month1),
+            ("1MON", Timeframe::Month1),
+            ("1month", Timeframe::Month1),
+            (" 1h ", Timeframe::H1),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(tf(input), *expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_minutes_and_months_stay_distinct() {
+        assert_eq!(tf("1m"), Timeframe::M1);
+        assert_eq!(tf("1M"), Timeframe::Month1);
+        assert_eq!(tf("m"), Timeframe::M1);
+        // 30 days is not a month, and there is no multi-month candle.
+        assert!("30d".parse::<Timeframe>().is_err());
+        assert!("2M".parse::<Timeframe>().is_err());
+    }
+
+    #[test]
+    fn test_invalid_timeframes() {
+        for bad in [
+            "", "0m", "7m", "45m", "90m", "6h", "2d", "2w", "60x", "h1", "1.5h", "-1h",
+        ] {
+            let err = bad.parse::<Timeframe>().unwrap_err();
+            assert!(
+                matches!(&err, AtlasError::InvalidTimeframe(msg) if msg.contains("1m, 3m")),
+                "{bad}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_duration_ms() {
+        assert_eq!(Timeframe::M1.duration_ms(), 60_000);
+        assert_eq!(Timeframe::H4.duration_ms(), 14_400_000);
+        assert_eq!(Timeframe::W1.duration_ms(), 604_800_000);
+        assert_eq!(Timeframe::Month1.duration_ms(), 2_592_000_000);
+    }
+}
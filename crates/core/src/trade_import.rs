@@ -0,0 +1,391 @@
+//! CSV import of trades made outside Atlas into the local `fills` table.
+//!
+//! Two layouts, both with a header row:
+//! - `generic`: columns `timestamp, coin, side, size, price, fee, pnl` and
+//!   optionally `tx_id`, in any order. `timestamp` takes anything `--from`
+//!   accepts (ISO date/time, unix s or ms); `side` is buy/sell (or b/s,
+//!   long/short); `fee`, `pnl` and `tx_id` may be empty.
+//! - `hyperliquid-ui`: the trade history CSV from the Hyperliquid web app,
+//!   `time, coin, dir, px, sz, ntl, fee, closedPnl`, with times like
+//!   `31/01/2026 - 14:30:05`.
+//!
+//! Every imported fill is keyed on the protocol label plus the tx id, or
+//! plus time, coin and size when there is none, so importing a file twice
+//! adds nothing.
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+
+use crate::date_range::{self, TimeZone};
+use crate::db::DbFill;
+
+/// Layouts accepted by `history import --format`.
+pub const IMPORT_FORMATS: [&str; 2] = ["generic", "hyperliquid-ui"];
+
+/// Required columns, in [`RawRow`] order: time, coin, side, size, price,
+/// fee, pnl.
+const GENERIC_COLUMNS: [&str; 7] = ["timestamp", "coin", "side", "size", "price", "fee", "pnl"];
+const HL_UI_COLUMNS: [&str; 7] = ["time", "coin", "dir", "sz", "px", "fee", "closedPnl"];
+
+/// A row that could not be imported. `line` is 1-based and counts the
+/// header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// The outcome of parsing one file.
+#[derive(Debug, Default)]
+pub struct ParsedImport {
+    /// Valid rows, in file order, first occurrence of each key only.
+    pub fills: Vec<DbFill>,
+    /// Valid rows repeating an earlier row's key.
+    pub duplicates: usize,
+    pub errors: Vec<RowError>,
+}
+
+/// Parse `text` in `format` (one of [`IMPORT_FORMATS`]) into fills labelled
+/// `protocol`. Times without an offset are read in `tz`. Fails only when
+/// the header is unusable; bad rows end up in [`ParsedImport::errors`].
+pub fn parse_trades(
+    text: &str,
+    format: &str,
+    protocol: &str,
+    tz: TimeZone,
+    now_ms: i64,
+) -> Result<ParsedImport> {
+    let hl_ui = match format {
+        "generic" => false,
+        "hyperliquid-ui" => true,
+        other => bail!(
+            "Invalid import format '{other}'. Use one of: {}",
+            IMPORT_FORMATS.join(", ")
+        ),
+    };
+    let expected: &[&str] = if hl_ui {
+        &HL_UI_COLUMNS
+    } else {
+        &GENERIC_COLUMNS
+    };
+
+    let mut lines = text
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l))
+        .filter(|(_, l)| !l.trim().is_empty());
+    let Some((header_line, header)) = lines.next() else {
+        bail!("Invalid CSV: the file is empty");
+    };
+    let header = split_record(header).map_err(|e| anyhow::anyhow!("Invalid CSV header: {e}"))?;
+    let position = |name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let mut columns = Vec::with_capacity(expected.len());
+    for name in expected {
+        match position(name) {
+            Some(i) => columns.push(i),
+            None => bail!(
+                "Invalid CSV header on line {header_line}: no '{name}' column. \
+                 The {format} format needs: {}",
+                expected.join(", ")
+            ),
+        }
+    }
+    let tx_column = if hl_ui { None } else { position("tx_id") };
+
+    let mut parsed = ParsedImport::default();
+    let mut seen = HashSet::new();
+    for (line, record) in lines {
+        let fill = split_record(record).and_then(|fields| {
+            let cell = |i: usize| fields.get(columns[i]).map_or("", |s| s.trim());
+            let row = RawRow {
+                time: cell(0),
+                coin: cell(1),
+                side: cell(2),
+                size: cell(3),
+                price: cell(4),
+                fee: cell(5),
+                pnl: cell(6),
+                tx_id: tx_column
+                    .and_then(|i| fields.get(i))
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty()),
+            };
+            row.to_fill(hl_ui, protocol, tz, now_ms)
+        });
+        match fill {
+            Ok(fill) if seen.insert(fill.hash.clone()) => parsed.fills.push(fill),
+            Ok(_) => parsed.duplicates += 1,
+            Err(message) => parsed.errors.push(RowError { line, message }),
+        }
+    }
+    Ok(parsed)
+}
+
+/// One data row's cells, in layout-independent order.
+struct RawRow<'a> {
+    time: &'a str,
+    coin: &'a str,
+    side: &'a str,
+    size: &'a str,
+    price: &'a str,
+    fee: &'a str,
+    pnl: &'a str,
+    tx_id: Option<&'a str>,
+}
+
+impl RawRow<'_> {
+    fn to_fill(
+        &self,
+        hl_ui: bool,
+        protocol: &str,
+        tz: TimeZone,
+        now_ms: i64,
+    ) -> Result<DbFill, String> {
+        let time_ms = if hl_ui {
+            parse_hl_ui_time(self.time, tz, now_ms)
+        } else {
+            date_range::parse_point(self.time, tz, now_ms).ok()
+        }
+        .ok_or_else(|| format!("invalid time '{}'", self.time))?;
+        if self.coin.is_empty() {
+            return Err("coin is empty".into());
+        }
+        let side = if hl_ui {
+            hl_ui_side(self.side)
+        } else {
+            generic_side(self.side)
+        }
+        .ok_or_else(|| format!("invalid side '{}'", self.side))?;
+
+        let size = positive("size", self.size)?;
+        let price = positive("price", self.price)?;
+        let fee = optional_decimal("fee", self.fee)?;
+        let pnl = optional_decimal("pnl", self.pnl)?;
+
+        let key = match self.tx_id {
+            Some(tx) => format!("{protocol}:{tx}"),
+            None => format!("{protocol}:{time_ms}:{}:{size}", self.coin),
+        };
+        Ok(DbFill {
+            protocol: protocol.to_string(),
+            coin: self.coin.to_string(),
+            px: price.to_string(),
+            sz: size.to_string(),
+            side: side.to_string(),
+            time_ms,
+            fee: fee.to_string(),
+            hash: key,
+            oid: 0,
+            closed_pnl: pnl.to_string(),
+        })
+    }
+}
+
+fn generic_side(s: &str) -> Option<&'static str> {
+    match s.to_ascii_lowercase().as_str() {
+        "buy" | "b" | "long" => Some("Buy"),
+        "sell" | "s" | "short" => Some("Sell"),
+        _ => None,
+    }
+}
+
+/// The web app's `dir` column: the side is the direction of the trade
+/// itself, so closing a short is a buy.
+fn hl_ui_side(dir: &str) -> Option<&'static str> {
+    match dir {
+        "Open Long" | "Close Short" | "Short > Long" | "Buy" => Some("Buy"),
+        "Open Short" | "Close Long" | "Long > Short" | "Sell" => Some("Sell"),
+        _ => None,
+    }
+}
+
+/// `31/01/2026 - 14:30:05` in `tz`, or anything the generic layout takes.
+fn parse_hl_ui_time(s: &str, tz: TimeZone, now_ms: i64) -> Option<i64> {
+    match NaiveDateTime::parse_from_str(s, "%d/%m/%Y - %H:%M:%S") {
+        Ok(naive) => {
+            let iso = naive.format("%Y-%m-%dT%H:%M:%S").to_string();
+            date_range::parse_point(&iso, tz, now_ms).ok()
+        }
+        Err(_) => date_range::parse_point(s, tz, now_ms).ok(),
+    }
+}
+
+fn positive(name: &str, s: &str) -> Result<Decimal, String> {
+    match s.parse::<Decimal>() {
+        Ok(d) if d > Decimal::ZERO => Ok(d.normalize()),
+        Ok(_) => Err(format!("{name} must be positive, got '{s}'")),
+        Err(_) => Err(format!("invalid {name} '{s}'")),
+    }
+}
+
+/// An empty cell is zero.
+fn optional_decimal(name: &str, s: &str) -> Result<Decimal, String> {
+    if s.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    s.parse::<Decimal>()
+        .map(|d| d.normalize())
+        .map_err(|_| format!("invalid {name} '{s}'"))
+}
+
+/// Split one CSV line. Fields may be quoted, with `""` for a literal
+/// quote; quoted fields can't span lines.
+fn split_record(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".into());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::AtlasDb;
+
+    /// 2026-01-15 10:30:00 UTC
+    const NOW: i64 = 1_768_473_000_000;
+    /// 2026-01-02 09:00:00 UTC
+    const JAN_2: i64 = 1_767_344_400_000;
+
+    fn parse(text: &str, format: &str) -> ParsedImport {
+        parse_trades(text, format, "custom", TimeZone::UTC, NOW).unwrap()
+    }
+
+    #[test]
+    fn test_split_record() {
+        assert_eq!(split_record("a,b,,c").unwrap(), ["a", "b", "", "c"]);
+        assert_eq!(
+            split_record(r#"1,"1,950.05","say ""hi""""#).unwrap(),
+            ["1", "1,950.05", r#"say "hi""#]
+        );
+        assert!(split_record(r#"a,"b"#).is_err());
+    }
+
+    #[test]
+    fn test_generic_layout() {
+        let csv = "\u{feff}Side,Timestamp,Coin,Size,Price,Fee,PnL,tx_id\n\
+                   buy,2026-01-02T09:00:00,BTC,0.10,95000,4.75,,0xaa\n\
+                   \n\
+                   SELL,1767348000,BTC,0.1,96000,-0.5,100,\n";
+        let parsed = parse(csv, "generic");
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(parsed.fills.len(), 2);
+
+        let buy = &parsed.fills[0];
+        assert_eq!((buy.side.as_str(), buy.time_ms), ("Buy", JAN_2));
+        assert_eq!((buy.sz.as_str(), buy.px.as_str()), ("0.1", "95000"));
+        assert_eq!((buy.fee.as_str(), buy.closed_pnl.as_str()), ("4.75", "0"));
+        assert_eq!(buy.hash, "custom:0xaa");
+        assert_eq!(buy.protocol, "custom");
+
+        // No tx id: keyed on time, coin and size
+        let sell = &parsed.fills[1];
+        assert_eq!(sell.hash, "custom:1767348000000:BTC:0.1");
+        assert_eq!(sell.fee, "-0.5");
+        assert_eq!(sell.closed_pnl, "100");
+    }
+
+    #[test]
+    fn test_row_errors_carry_line_numbers() {
+        let csv = "timestamp,coin,side,size,price,fee,pnl\n\
+                   2026-01-02,ETH,buy,1,3000,0,0\n\
+                   yesterday-ish,ETH,buy,1,3000,0,0\n\
+                   2026-01-02,ETH,hold,1,3000,0,0\n\
+                   2026-01-02,ETH,buy,-1,3000,0,0\n\
+                   2026-01-02,ETH,buy,1,abc,0,0\n\
+                   2026-01-02,,buy,1,3000,0,0\n\
+                   2026-01-02,ETH,buy,1,3000,\"0,0\n";
+        let parsed = parse(csv, "generic");
+        assert_eq!(parsed.fills.len(), 1);
+        let lines: Vec<usize> = parsed.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [3, 4, 5, 6, 7, 8]);
+        assert_eq!(parsed.errors[0].message, "invalid time 'yesterday-ish'");
+        assert_eq!(parsed.errors[1].message, "invalid side 'hold'");
+        assert_eq!(parsed.errors[2].message, "size must be positive, got '-1'");
+        assert_eq!(parsed.errors[3].message, "invalid price 'abc'");
+        assert_eq!(parsed.errors[4].message, "coin is empty");
+        assert_eq!(parsed.errors[5].message, "unterminated quoted field");
+    }
+
+    #[test]
+    fn test_bad_header_fails_the_file() {
+        let err = parse_trades("time,coin\n", "generic", "custom", TimeZone::UTC, NOW)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no 'timestamp' column"), "{err}");
+        assert!(parse_trades("", "generic", "custom", TimeZone::UTC, NOW).is_err());
+        assert!(parse_trades("a\n", "binance", "custom", TimeZone::UTC, NOW).is_err());
+    }
+
+    #[test]
+    fn test_hyperliquid_ui_layout() {
+        let csv = "time,coin,dir,px,sz,ntl,fee,closedPnl\n\
+                   02/01/2026 - 09:00:00,ETH,Open Long,3000.5,0.5,1500.25,0.675,-0.675\n\
+                   02/01/2026 - 11:00:00,ETH,Close Long,3100,0.5,1550,0.6975,49.75\n\
+                   02/01/2026 - 12:00:00,ETH,Close Short,3100,0.5,1550,0.6975,0\n\
+                   02/01/2026 - 13:00:00,ETH,Liquidated,3100,0.5,1550,0,0\n";
+        let parsed = parse_trades(csv, "hyperliquid-ui", "hl-import", TimeZone::UTC, NOW).unwrap();
+        assert_eq!(parsed.fills.len(), 3);
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].line, 5);
+
+        let sides: Vec<&str> = parsed.fills.iter().map(|f| f.side.as_str()).collect();
+        assert_eq!(sides, ["Buy", "Sell", "Buy"]);
+        assert_eq!(parsed.fills[0].time_ms, JAN_2);
+        assert_eq!(parsed.fills[1].closed_pnl, "49.75");
+        assert_eq!(parsed.fills[0].hash, format!("hl-import:{JAN_2}:ETH:0.5"));
+
+        // Web app times are wall-clock in --tz
+        let plus_two = TimeZone::parse("+02:00").unwrap();
+        let parsed = parse_trades(csv, "hyperliquid-ui", "hl-import", plus_two, NOW).unwrap();
+        assert_eq!(parsed.fills[0].time_ms, JAN_2 - 2 * 3_600_000);
+    }
+
+    #[test]
+    fn test_reimport_dedups() {
+        let csv = "timestamp,coin,side,size,price,fee,pnl\n\
+                   2026-01-02T09:00:00,SOL,buy,10,200,0.1,0\n\
+                   2026-01-02T09:00:00,SOL,buy,10.0,200,0.1,0\n\
+                   2026-01-02T09:00:01,SOL,sell,10,201,0.1,10\n";
+        let parsed = parse(csv, "generic");
+        assert_eq!((parsed.fills.len(), parsed.duplicates), (2, 1));
+
+        let db = AtlasDb::open_in_memory().unwrap();
+        assert_eq!(db.insert_fills(&parsed.fills).unwrap(), 2);
+        let hashes: Vec<String> = parsed.fills.iter().map(|f| f.hash.clone()).collect();
+        assert_eq!(db.existing_fill_hashes(&hashes).unwrap().len(), 2);
+        assert_eq!(db.insert_fills(&parse(csv, "generic").fills).unwrap(), 0);
+
+        // The same trade under another label is a different fill
+        let other = parse_trades(csv, "generic", "cex", TimeZone::UTC, NOW).unwrap();
+        assert_eq!(db.insert_fills(&other.fills).unwrap(), 2);
+    }
+}
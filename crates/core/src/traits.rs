@@ -16,6 +16,12 @@ pub trait PerpModule: Send + Sync {
     /// Protocol identifier.
     fn protocol(&self) -> Protocol;
 
+    /// Resolve user input (any case, alias, typo) to a listed market name.
+    /// Unknown input fails with `AssetNotFound` carrying suggestions.
+    fn resolve_symbol(&self, input: &str) -> AtlasResult<String> {
+        Ok(input.to_uppercase())
+    }
+
     /// List available perp markets.
     async fn markets(&self) -> AtlasResult<Vec<Market>>;
 
@@ -51,7 +57,7 @@ pub trait PerpModule: Send + Sync {
         side: Side,
         size: Decimal,
         price: Decimal,
-        reduce_only: bool,
+        opts: &LimitOptions,
     ) -> AtlasResult<OrderResult>;
 
     /// Close a position.
@@ -68,7 +74,7 @@ pub trait PerpModule: Send + Sync {
     /// Cancel all orders on a symbol.
     async fn cancel_all(&self, symbol: &str) -> AtlasResult<u32>;
 
-    /// Get open orders.
+    /// Get open orders across perp and spot markets (see `Order::market_type`).
     async fn open_orders(&self) -> AtlasResult<Vec<Order>>;
 
     /// Get positions.
@@ -77,6 +83,13 @@ pub trait PerpModule: Send + Sync {
     /// Get recent fills.
     async fn fills(&self) -> AtlasResult<Vec<Fill>>;
 
+    /// Fills matching `query`, newest first. The default filters
+    /// [`fills`](Self::fills); modules with a fill history endpoint page
+    /// through it for time ranges.
+    async fn fills_query(&self, query: &FillQuery) -> AtlasResult<Vec<Fill>> {
+        Ok(query.apply(self.fills().await?))
+    }
+
     /// Get account balances.
     async fn balances(&self) -> AtlasResult<Vec<Balance>>;
 
@@ -89,12 +102,42 @@ pub trait PerpModule: Send + Sync {
     /// Transfer USDC.
     async fn transfer(&self, amount: Decimal, destination: &str) -> AtlasResult<String>;
 
+    /// Amend a resting order's price and/or size in place. `order_id` is an
+    /// exchange oid or a client order ID; the result carries the order's
+    /// (possibly new) ID.
+    async fn modify_order(
+        &self,
+        _symbol: &str,
+        _order_id: &str,
+        _price: Option<Decimal>,
+        _size: Option<Decimal>,
+    ) -> AtlasResult<OrderResult> {
+        Err(crate::error::AtlasError::Other(
+            "Order modification not supported on this protocol".into(),
+        ))
+    }
+
     /// Cancel an order by client order ID.
     async fn cancel_by_cloid(&self, symbol: &str, cloid: &str) -> AtlasResult<()> {
         // Default: fall back to cancel_order if not supported
         self.cancel_order(symbol, cloid).await
     }
 
+    /// Funding, OI and prices for every market in one call.
+    async fn asset_contexts(&self) -> AtlasResult<Vec<AssetContext>> {
+        Err(crate::error::AtlasError::Other(
+            "Asset contexts not supported on this protocol".into(),
+        ))
+    }
+
+    /// Cross account value, open positions with their margin mode and the
+    /// maintenance tiers of every market, for the liquidation model.
+    async fn margin_state(&self) -> AtlasResult<MarginState> {
+        Err(crate::error::AtlasError::Other(
+            "Margin state not supported on this protocol".into(),
+        ))
+    }
+
     // ── Spot operations (optional — not all perp protocols have spot) ──
 
     /// Get spot token balances. Returns empty vec if not supported.
@@ -107,6 +150,20 @@ pub trait PerpModule: Send + Sync {
         Ok(std::collections::HashMap::new())
     }
 
+    /// Every listed spot pair with its current mid.
+    async fn spot_markets(&self) -> AtlasResult<Vec<SpotMarket>> {
+        Err(crate::error::AtlasError::Other(
+            "Spot markets not supported on this protocol".into(),
+        ))
+    }
+
+    /// Mid price and size precision for `base`'s spot market.
+    async fn spot_market_info(&self, _base: &str) -> AtlasResult<SpotMarketInfo> {
+        Err(crate::error::AtlasError::Other(
+            "Spot trading not supported on this protocol".into(),
+        ))
+    }
+
     /// Place a spot market order. Returns error if not supported.
     async fn spot_market_order(
         &self,
@@ -120,6 +177,33 @@ pub trait PerpModule: Send + Sync {
         ))
     }
 
+    /// Place a resting (GTC) spot limit order. Returns error if not supported.
+    async fn spot_limit_order(
+        &self,
+        _base: &str,
+        _side: Side,
+        _size: Decimal,
+        _price: Decimal,
+    ) -> AtlasResult<OrderResult> {
+        Err(crate::error::AtlasError::Other(
+            "Spot trading not supported on this protocol".into(),
+        ))
+    }
+
+    /// Cancel one spot order by ID.
+    async fn spot_cancel_order(&self, _base: &str, _order_id: &str) -> AtlasResult<()> {
+        Err(crate::error::AtlasError::Other(
+            "Spot trading not supported on this protocol".into(),
+        ))
+    }
+
+    /// Cancel every open order on a spot market. Returns the count.
+    async fn spot_cancel_all(&self, _base: &str) -> AtlasResult<u32> {
+        Err(crate::error::AtlasError::Other(
+            "Spot trading not supported on this protocol".into(),
+        ))
+    }
+
     /// Internal transfer between sub-wallets (perps↔spot↔evm).
     async fn internal_transfer(
         &self,
@@ -141,6 +225,14 @@ pub trait PerpModule: Send + Sync {
         ))
     }
 
+    /// Public vaults, open for deposits. `refresh` skips any cached
+    /// listing.
+    async fn vault_list(&self, _refresh: bool) -> AtlasResult<Vec<VaultSummary>> {
+        Err(crate::error::AtlasError::Other(
+            "Vaults not supported on this protocol".into(),
+        ))
+    }
+
     /// Get user's vault deposits.
     async fn vault_deposits(&self) -> AtlasResult<Vec<VaultDeposit>> {
         Ok(vec![])
@@ -245,6 +337,12 @@ pub trait SwapModule: Send + Sync {
     /// Execute a swap.
     async fn swap(&self, quote: &SwapQuote) -> AtlasResult<String>;
 
+    /// Wallet balances on the chains this module trades on.
+    /// Returns empty vec if not supported.
+    async fn wallet_balances(&self) -> AtlasResult<Vec<Balance>> {
+        Ok(vec![])
+    }
+
     /// Downcast to concrete type for protocol-specific features.
     fn as_any(&self) -> &dyn std::any::Any;
 }
@@ -12,6 +12,8 @@ use serde::{Deserialize, Serialize};
 pub enum Protocol {
     Hyperliquid,
     ZeroX,
+    /// Simulated account on live Hyperliquid prices (`atlas-paper`).
+    Paper,
     // Future: Dydx, Gmx, Vertex, Jupiter, Drift, ...
 }
 
@@ -20,6 +22,7 @@ impl std::fmt::Display for Protocol {
         match self {
             Protocol::Hyperliquid => write!(f, "hyperliquid"),
             Protocol::ZeroX => write!(f, "0x"),
+            Protocol::Paper => write!(f, "paper"),
         }
     }
 }
@@ -140,6 +143,71 @@ pub struct Ticker {
     pub change_24h_pct: Option<Decimal>,
 }
 
+/// Universal per-market context: live funding, open interest and prices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetContext {
+    pub symbol: String,
+    pub protocol: Protocol,
+    pub mark_price: Option<Decimal>,
+    pub mid_price: Option<Decimal>,
+    pub oracle_price: Option<Decimal>,
+    /// Current predicted funding rate for one funding interval.
+    pub funding_rate: Option<Decimal>,
+    /// Hours between funding payments (1 on Hyperliquid).
+    pub funding_interval_hours: u32,
+    pub premium: Option<Decimal>,
+    /// Open interest in base units.
+    pub open_interest: Option<Decimal>,
+    /// 24h notional volume (USD).
+    pub volume_24h: Option<Decimal>,
+    /// 24h volume in base units.
+    pub volume_24h_base: Option<Decimal>,
+    pub prev_day_price: Option<Decimal>,
+    /// Price a standard-size market sell would fill at.
+    #[serde(default)]
+    pub impact_bid: Option<Decimal>,
+    /// Price a standard-size market buy would fill at.
+    #[serde(default)]
+    pub impact_ask: Option<Decimal>,
+}
+
+impl AssetContext {
+    /// Open interest in USD at the mark price.
+    pub fn open_interest_usd(&self) -> Option<Decimal> {
+        Some(self.open_interest? * self.mark_price?)
+    }
+
+    /// Price change since the previous day's close, in percent.
+    pub fn change_24h_pct(&self) -> Option<Decimal> {
+        let prev = self.prev_day_price.filter(|p| !p.is_zero())?;
+        let px = self.mid_price.or(self.mark_price)?;
+        Some((px - prev) / prev * Decimal::ONE_HUNDRED)
+    }
+
+    /// Impact ask minus impact bid, in basis points of the mid.
+    pub fn spread_bps(&self) -> Option<Decimal> {
+        let mid = self
+            .mid_price
+            .or(self.mark_price)
+            .filter(|p| !p.is_zero())?;
+        Some((self.impact_ask? - self.impact_bid?) / mid * Decimal::from(10_000))
+    }
+
+    /// Funding rate annualized to a percentage (simple, not compounded).
+    pub fn funding_apr_pct(&self) -> Option<Decimal> {
+        let periods_per_year = Decimal::from(24 * 365 / self.funding_interval_hours.max(1));
+        Some(self.funding_rate? * periods_per_year * Decimal::ONE_HUNDRED)
+    }
+
+    /// Seconds from `now_secs` (unix) until the next funding payment.
+    /// Payments fall on whole multiples of the interval since the epoch —
+    /// the top of every hour on Hyperliquid.
+    pub fn next_funding_secs(&self, now_secs: u64) -> u64 {
+        let interval = u64::from(self.funding_interval_hours.max(1)) * 3600;
+        interval - now_secs % interval
+    }
+}
+
 /// Trade side.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -173,15 +241,93 @@ pub struct Position {
     pub margin_mode: Option<String>,
 }
 
+impl Position {
+    /// Long or short by `side`: Hyperliquid reports `size` unsigned, so its
+    /// sign says nothing about the direction.
+    pub fn is_long(&self) -> bool {
+        self.side == Side::Buy
+    }
+
+    /// The side of an order that reduces this position.
+    pub fn closing_side(&self) -> Side {
+        if self.is_long() {
+            Side::Sell
+        } else {
+            Side::Buy
+        }
+    }
+
+    /// Unsigned position value at the mark price, or the entry price when
+    /// the module reports no mark.
+    pub fn notional(&self) -> Option<Decimal> {
+        Some(self.size.abs() * self.mark_price.or(self.entry_price)?)
+    }
+
+    /// Unrealized PnL as a percentage of the position's entry value.
+    pub fn pnl_pct(&self) -> Option<Decimal> {
+        let cost = self.size.abs() * self.entry_price?;
+        if cost.is_zero() {
+            return None;
+        }
+        Some(self.unrealized_pnl? / cost * Decimal::ONE_HUNDRED)
+    }
+}
+
+/// One maintenance-margin tier: positions with notional (USD) from
+/// `lower_bound` up to the next tier's bound may use at most `max_leverage`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginTier {
+    pub lower_bound: Decimal,
+    pub max_leverage: u32,
+}
+
+/// How a position is margined.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "mode")]
+pub enum MarginMode {
+    /// Shares the account's cross equity with every other cross position.
+    Cross,
+    /// Backed only by its own margin; `equity` is that margin plus the
+    /// position's unrealized PnL at the mark.
+    Isolated { equity: Decimal },
+}
+
+/// An open perp position as the margin model sees it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginPosition {
+    pub coin: String,
+    /// Signed size: positive long, negative short.
+    pub size: Decimal,
+    pub mark_price: Decimal,
+    pub leverage: u32,
+    pub mode: MarginMode,
+}
+
+/// Everything needed to reproduce the exchange's liquidation maths.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarginState {
+    /// Cross account value: collateral plus unrealized PnL of the cross
+    /// positions, excluding isolated margin.
+    pub cross_account_value: Decimal,
+    pub positions: Vec<MarginPosition>,
+    /// Maintenance tiers per listed coin, lowest bound first.
+    pub tiers: std::collections::HashMap<String, Vec<MarginTier>>,
+}
+
 /// Universal order.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
     pub protocol: Protocol,
+    /// Coin for perps, pair name (`PURR/USDC`) for spot.
     pub symbol: String,
+    pub market_type: MarketType,
     pub side: Side,
     pub order_type: OrderType,
     pub size: Decimal,
     pub price: Option<Decimal>,
+    /// Mark price that releases a resting stop order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<Decimal>,
     pub filled_size: Option<Decimal>,
     pub status: OrderStatus,
     pub order_id: String,
@@ -197,6 +343,94 @@ pub enum OrderType {
     StopLimit,
 }
 
+/// How long a limit order may rest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeInForce {
+    /// Good 'til cancelled.
+    #[default]
+    Gtc,
+    /// Immediate or cancel: fill what crosses now, cancel the rest.
+    Ioc,
+    /// Add liquidity only (post-only): rejected if it would cross.
+    Alo,
+}
+
+impl std::fmt::Display for TimeInForce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TimeInForce::Gtc => "gtc",
+            TimeInForce::Ioc => "ioc",
+            TimeInForce::Alo => "alo",
+        })
+    }
+}
+
+impl std::str::FromStr for TimeInForce {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "gtc" => Ok(TimeInForce::Gtc),
+            "ioc" => Ok(TimeInForce::Ioc),
+            "alo" | "post-only" | "postonly" => Ok(TimeInForce::Alo),
+            _ => Err(format!("Invalid time in force '{s}'. Use: gtc, ioc, alo")),
+        }
+    }
+}
+
+/// How a trigger order executes once its trigger price is hit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerKind {
+    /// Stop-market: fills at market, bounded by the order's limit price.
+    #[default]
+    Market,
+    /// Stop-limit: rests at the order's limit price.
+    Limit,
+}
+
+impl std::fmt::Display for TriggerKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TriggerKind::Market => "market",
+            TriggerKind::Limit => "limit",
+        })
+    }
+}
+
+impl std::str::FromStr for TriggerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "market" => Ok(TriggerKind::Market),
+            "limit" => Ok(TriggerKind::Limit),
+            _ => Err(format!("Invalid trigger type '{s}'. Use: market, limit")),
+        }
+    }
+}
+
+/// Trigger on a limit order: it stays off the book until the mark price
+/// crosses `price`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerOptions {
+    pub price: Decimal,
+    pub kind: TriggerKind,
+}
+
+/// Execution options for a limit order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LimitOptions {
+    /// Close-only: never opens or grows a position.
+    pub reduce_only: bool,
+    pub tif: TimeInForce,
+    /// Caller-supplied client order ID (`0x` + 32 hex); random when `None`.
+    pub cloid: Option<String>,
+    /// Makes this a stop order; `tif` does not apply.
+    pub trigger: Option<TriggerOptions>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum OrderStatus {
@@ -208,7 +442,7 @@ pub enum OrderStatus {
 }
 
 /// Universal fill (executed trade).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Fill {
     pub protocol: Protocol,
     pub symbol: String,
@@ -222,6 +456,40 @@ pub struct Fill {
     pub timestamp_ms: u64,
 }
 
+/// Which fills to fetch for `atlas hl perp fills`. Times are ms, inclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FillQuery {
+    /// Exchange symbol, already resolved.
+    pub coin: Option<String>,
+    pub from_ms: Option<u64>,
+    pub to_ms: Option<u64>,
+    pub limit: usize,
+}
+
+impl FillQuery {
+    /// Is a time range given? Modules then page through their history
+    /// instead of listing recent fills.
+    pub fn has_range(&self) -> bool {
+        self.from_ms.is_some() || self.to_ms.is_some()
+    }
+
+    pub fn matches(&self, fill: &Fill) -> bool {
+        self.coin
+            .as_ref()
+            .map_or(true, |c| fill.symbol.eq_ignore_ascii_case(c))
+            && self.from_ms.map_or(true, |from| fill.timestamp_ms >= from)
+            && self.to_ms.map_or(true, |to| fill.timestamp_ms <= to)
+    }
+
+    /// Matching fills, newest first, at most `limit`.
+    pub fn apply(&self, mut fills: Vec<Fill>) -> Vec<Fill> {
+        fills.retain(|f| self.matches(f));
+        fills.sort_by(|a, b| b.timestamp_ms.cmp(&a.timestamp_ms));
+        fills.truncate(self.limit);
+        fills
+    }
+}
+
 /// Universal balance.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Balance {
@@ -245,7 +513,13 @@ pub struct OrderResult {
     pub avg_price: Option<Decimal>,
     pub fee: Option<Decimal>,
     pub timestamp: Option<u64>,
+    /// Builder fee attached to this order, in bps; `None` when none was.
+    #[serde(default)]
+    pub builder_fee_bps: Option<u16>,
     pub message: Option<String>,
+    /// Audit log entry of the signed request (`atlas audit show <id>`).
+    #[serde(default)]
+    pub audit_id: Option<i64>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -262,6 +536,35 @@ pub struct SpotBalance {
     pub held: Decimal,
 }
 
+/// Pricing and lot precision for a spot pair, used to size orders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotMarketInfo {
+    /// Pair name, e.g. `HYPE/USDC`.
+    pub pair: String,
+    pub mid_price: Decimal,
+    /// Decimal places the base token's size is rounded to.
+    pub sz_decimals: u32,
+}
+
+/// One spot pair as listed, with its current mid when known.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpotMarket {
+    /// Pair name, e.g. `HYPE/USDC`.
+    pub pair: String,
+    pub base: String,
+    pub quote: String,
+    /// Universe index.
+    pub index: usize,
+    /// Key the exchange uses for the pair in mids, orders and fills
+    /// (`@107`, or `PURR/USDC` for the oldest pairs).
+    pub key: String,
+    /// Decimal places the base token's size is rounded to.
+    pub sz_decimals: u32,
+    /// Decimal places of the base token's on-chain amount.
+    pub wei_decimals: u32,
+    pub mid_price: Option<Decimal>,
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 //  VAULTS & SUBACCOUNTS
 // ═══════════════════════════════════════════════════════════════════════
@@ -279,6 +582,21 @@ pub struct VaultDetails {
     pub pnl_all_time: Option<Decimal>,
 }
 
+/// One public vault from the protocol's vault listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultSummary {
+    pub protocol: Protocol,
+    pub address: String,
+    pub name: String,
+    pub leader: String,
+    pub tvl: Decimal,
+    /// As a fraction (0.12 = 12%).
+    pub apr: Option<Decimal>,
+    pub created_ms: u64,
+    /// Not in the listing; filled in from the vault's details when needed.
+    pub followers: Option<u32>,
+}
+
 /// User's vault deposit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VaultDeposit {
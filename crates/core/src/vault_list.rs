@@ -0,0 +1,123 @@
+//! Filtering and ranking for `atlas hl vault list`.
+//!
+//! Vault listings carry TVL, APR and age but not follower counts; those
+//! come from each vault's details, so ranking by followers is done over a
+//! shortlist (the largest vaults by TVL) that the caller has filled in.
+
+use rust_decimal::Decimal;
+
+use crate::output::VaultListRow;
+use crate::types::VaultSummary;
+
+/// Accepted `--sort` values.
+pub const VAULT_SORTS: [&str; 3] = ["apr", "tvl", "followers"];
+
+const DAY_MS: u64 = 86_400_000;
+
+/// Vaults with at least `min_tvl` whose name contains `search`
+/// (case-insensitive).
+pub fn filter_vaults(
+    vaults: Vec<VaultSummary>,
+    min_tvl: Option<Decimal>,
+    search: Option<&str>,
+) -> Vec<VaultSummary> {
+    let search = search.map(str::to_lowercase);
+    vaults
+        .into_iter()
+        .filter(|v| min_tvl.map_or(true, |min| v.tvl >= min))
+        .filter(|v| {
+            search
+                .as_deref()
+                .map_or(true, |s| v.name.to_lowercase().contains(s))
+        })
+        .collect()
+}
+
+/// Best first by `sort`; vaults missing the figure go last, ties by TVL.
+pub fn sort_vaults(vaults: &mut [VaultSummary], sort: &str) {
+    match sort {
+        "apr" => vaults.sort_by(|a, b| b.apr.cmp(&a.apr).then(b.tvl.cmp(&a.tvl))),
+        "followers" => vaults.sort_by(|a, b| b.followers.cmp(&a.followers).then(b.tvl.cmp(&a.tvl))),
+        _ => vaults.sort_by_key(|v| std::cmp::Reverse(v.tvl)),
+    }
+}
+
+/// Numbered rows, with the age in whole days at `now_ms`.
+pub fn to_rows(vaults: &[VaultSummary], now_ms: u64) -> Vec<VaultListRow> {
+    vaults
+        .iter()
+        .enumerate()
+        .map(|(i, v)| VaultListRow {
+            rank: i + 1,
+            address: v.address.clone(),
+            name: v.name.clone(),
+            leader: v.leader.clone(),
+            tvl_usd: v.tvl,
+            apr_pct: v.apr.map(|a| (a * Decimal::ONE_HUNDRED).round_dp(2)),
+            age_days: (v.created_ms > 0).then(|| now_ms.saturating_sub(v.created_ms) / DAY_MS),
+            followers: v.followers,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Protocol;
+
+    fn vault(name: &str, tvl: i64, apr: Option<&str>, followers: Option<u32>) -> VaultSummary {
+        VaultSummary {
+            protocol: Protocol::Hyperliquid,
+            address: format!("0x{}", name.to_lowercase()),
+            name: name.into(),
+            leader: "0xleader".into(),
+            tvl: Decimal::from(tvl),
+            apr: apr.map(|a| a.parse().unwrap()),
+            created_ms: 1_700_000_000_000,
+            followers,
+        }
+    }
+
+    fn names(vaults: &[VaultSummary]) -> Vec<&str> {
+        vaults.iter().map(|v| v.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filter_by_tvl_and_name() {
+        let vaults = vec![
+            vault("Alpha Basis", 500_000, Some("0.1"), None),
+            vault("Beta", 50_000, Some("0.3"), None),
+            vault("alpha small", 20_000, None, None),
+        ];
+        let big = filter_vaults(vaults.clone(), Some(Decimal::from(100_000)), None);
+        assert_eq!(names(&big), ["Alpha Basis"]);
+        let alpha = filter_vaults(vaults.clone(), None, Some("ALPHA"));
+        assert_eq!(names(&alpha), ["Alpha Basis", "alpha small"]);
+        assert_eq!(filter_vaults(vaults, None, None).len(), 3);
+    }
+
+    #[test]
+    fn test_sort_puts_missing_figures_last() {
+        let mut vaults = vec![
+            vault("NoApr", 900, None, Some(3)),
+            vault("Low", 100, Some("-0.05"), None),
+            vault("High", 200, Some("0.42"), Some(12)),
+        ];
+        sort_vaults(&mut vaults, "apr");
+        assert_eq!(names(&vaults), ["High", "Low", "NoApr"]);
+        sort_vaults(&mut vaults, "followers");
+        assert_eq!(names(&vaults), ["High", "NoApr", "Low"]);
+        sort_vaults(&mut vaults, "tvl");
+        assert_eq!(names(&vaults), ["NoApr", "High", "Low"]);
+    }
+
+    #[test]
+    fn test_rows_number_and_convert() {
+        let vaults = vec![vault("A", 1_000, Some("0.123456"), Some(4))];
+        let rows = to_rows(&vaults, 1_700_000_000_000 + 3 * DAY_MS + 5);
+        assert_eq!(rows[0].rank, 1);
+        assert_eq!(rows[0].apr_pct, Some("12.35".parse().unwrap()));
+        assert_eq!(rows[0].age_days, Some(3));
+        assert_eq!(rows[0].followers, Some(4));
+    }
+}
@@ -1,7 +1,7 @@
 use std::fs;
 use std::path::PathBuf;
 
-use crate::config::AppConfig;
+use crate::config::{self, AppConfig};
 use anyhow::{Context, Result};
 use tracing::info;
 
@@ -80,16 +80,32 @@ pub fn init_workspace() -> Result<()> {
     Ok(())
 }
 
-/// Load the config from disk. If the config is outdated (missing fields),
-/// regenerate with defaults while preserving `active_profile`.
+/// Load the config from disk, with the active environment (`env.<name>`)
+/// merged over the base settings. Flat configs from before environments
+/// are wrapped into `env.mainnet` / `env.testnet` and written back. If the
+/// config is outdated (missing fields), regenerate with defaults while
+/// preserving `active_profile`.
 pub fn load_config() -> Result<AppConfig> {
     let config_path = root_dir()?.join("atlas.json");
     let raw = fs::read_to_string(&config_path)
         .with_context(|| format!("Failed to read {}", config_path.display()))?;
 
-    match AppConfig::from_json_str(&raw) {
-        Ok(config) => Ok(config),
-        Err(_) => {
+    let resolved = serde_json::from_str::<serde_json::Value>(&raw)
+        .ok()
+        .and_then(|mut value| {
+            let migrated = config::migrate_envs(&mut value);
+            let config = config::resolve_env(&value, None).ok()?;
+            Some((value, config, migrated))
+        });
+    match resolved {
+        Some((value, config, migrated)) => {
+            if migrated {
+                write_raw(&value)?;
+                info!("config wrapped into mainnet/testnet environments");
+            }
+            Ok(config)
+        }
+        None => {
             // Config schema changed — try to preserve active_profile
             info!("atlas.json outdated, migrating to new schema");
             let mut new_config = AppConfig::default();
@@ -118,20 +134,81 @@ pub fn load_config() -> Result<AppConfig> {
                 }
             }
 
-            // Write the migrated config
-            save_config(&new_config)?;
+            // Write the migrated config as a fresh flat file; environments
+            // are seeded from it on the next load
+            write_raw(&serde_json::to_value(&new_config).context("Failed to serialize config")?)?;
             info!("config migrated successfully");
             Ok(new_config)
         }
     }
 }
 
-/// Write the config back to disk.
+/// Write the config back to disk. Changes land in the environment the
+/// config was loaded from; see [`config::store_env`].
 pub fn save_config(config: &AppConfig) -> Result<()> {
+    let mut raw = read_raw().unwrap_or(serde_json::Value::Null);
+    config::store_env(&mut raw, config);
+    write_raw(&raw)
+}
+
+/// Every configured environment with its effective settings, sorted by name.
+pub fn load_envs() -> Result<Vec<(String, AppConfig)>> {
+    let mut raw = read_raw()?;
+    config::migrate_envs(&mut raw);
+    config::env_names(&raw)
+        .into_iter()
+        .map(|name| {
+            let resolved = config::resolve_env(&raw, Some(&name))
+                .with_context(|| format!("Invalid settings in env.{name}"))?;
+            Ok((name, resolved))
+        })
+        .collect()
+}
+
+/// Ways `atlas.json` on disk is behind the current schema (`atlas doctor`).
+pub fn config_schema_issues() -> Result<Vec<String>> {
+    let config_path = root_dir()?.join("atlas.json");
+    let raw = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    Ok(match serde_json::from_str(&raw) {
+        Ok(value) => config::schema_issues(&value),
+        Err(_) => vec!["not valid JSON".into()],
+    })
+}
+
+/// Bring `atlas.json` up to the current schema: the usual load-time
+/// migrations, then the base rewritten with every field present.
+pub fn repair_config() -> Result<()> {
+    load_config()?;
+    let mut raw = read_raw()?;
+    config::normalize_base(&mut raw).context("Config still invalid after migration")?;
+    write_raw(&raw)
+}
+
+/// Workspace directories that can't be written to (or don't exist).
+pub fn unwritable_dirs() -> Result<Vec<PathBuf>> {
+    let root = root_dir()?;
+    let dirs = std::iter::once(root.clone()).chain(SUBDIRS.iter().map(|sub| root.join(sub)));
+    Ok(dirs
+        .filter(|dir| {
+            let probe = dir.join(".atlas-write-test");
+            let writable = fs::write(&probe, b"").is_ok();
+            let _ = fs::remove_file(&probe);
+            !writable
+        })
+        .collect())
+}
+
+fn read_raw() -> Result<serde_json::Value> {
+    let config_path = root_dir()?.join("atlas.json");
+    let raw = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("Invalid JSON in {}", config_path.display()))
+}
+
+fn write_raw(value: &serde_json::Value) -> Result<()> {
     let config_path = root_dir()?.join("atlas.json");
-    let json_str = config
-        .to_json_string()
-        .context("Failed to serialize config")?;
+    let json_str = serde_json::to_string_pretty(value).context("Failed to serialize config")?;
     fs::write(&config_path, &json_str)
         .with_context(|| format!("Failed to write {}", config_path.display()))?;
     Ok(())
@@ -0,0 +1,334 @@
+//! The Hyperliquid API as seen by [`HyperliquidModule`](crate::client::HyperliquidModule).
+//!
+//! Every `/info` read and `/exchange` write the module's trading paths make
+//! goes through [`HlApi`]: [`HttpApi`] sends them to Hyperliquid, while
+//! [`MockApi`] answers from canned responses and records each request, so
+//! order building and response parsing can be tested down to the JSON sent.
+//! The typed reads are provided methods over [`HlApi::info`], so a transport
+//! only has to implement the two POSTs.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use alloy::primitives::Address;
+use async_trait::async_trait;
+use atlas_core::constants::{HL_MAINNET_RPC, HL_TESTNET_RPC};
+use atlas_core::error::AtlasError;
+use atlas_core::timeframe::Timeframe;
+use atlas_core::types::{Candle, Fill, FundingRate, MarginTier};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::http::{ExchangeHttp, HttpFailure};
+use crate::meta::MarketMeta;
+use crate::spot::{parse_spot_pairs, SpotPair};
+
+/// Account state from `clearinghouseState` (perps only).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearinghouseState {
+    pub asset_positions: Vec<AssetPosition>,
+    pub margin_summary: MarginSummary,
+    /// The cross-margined part of the account; isolated margin is excluded.
+    #[serde(default)]
+    pub cross_margin_summary: Option<MarginSummary>,
+    pub withdrawable: Decimal,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssetPosition {
+    pub position: PositionState,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionState {
+    pub coin: String,
+    /// Signed size: positive long, negative short.
+    pub szi: Decimal,
+    pub entry_px: Option<Decimal>,
+    pub unrealized_pnl: Decimal,
+    pub leverage: PositionLeverage,
+    pub liquidation_px: Option<Decimal>,
+    /// `|szi|` at the mark price.
+    #[serde(default)]
+    pub position_value: Option<Decimal>,
+    #[serde(default)]
+    pub margin_used: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PositionLeverage {
+    /// `cross` or `isolated`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub value: u32,
+    /// Isolated only: the position's equity is `raw_usd + szi × mark`.
+    #[serde(default)]
+    pub raw_usd: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginSummary {
+    pub account_value: Decimal,
+    pub total_margin_used: Decimal,
+}
+
+/// A resting order from `openOrders`, with just what cancelling needs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RestingOrder {
+    pub coin: String,
+    pub oid: u64,
+}
+
+fn parse<T: DeserializeOwned>(request: &str, resp: Value) -> Result<T, AtlasError> {
+    serde_json::from_value(resp)
+        .map_err(|e| AtlasError::Network(format!("unexpected {request} shape: {e}")))
+}
+
+/// Transport for Hyperliquid's `/info` and `/exchange` endpoints.
+#[async_trait]
+pub trait HlApi: Send + Sync {
+    /// POST an `/info` request and return the JSON body.
+    async fn info(&self, body: &Value) -> Result<Value, AtlasError>;
+
+    /// POST a signed request to `/exchange` once and return the raw body.
+    /// Never retried here: an order must be looked up by cloid before it
+    /// may be resent.
+    async fn exchange(&self, body: &Value) -> Result<String, HttpFailure>;
+
+    /// Mid price per coin; spot markets are keyed `@N` (or `PURR/USDC`).
+    async fn all_mids(&self) -> Result<HashMap<String, Decimal>, AtlasError> {
+        parse("allMids", self.info(&json!({"type": "allMids"})).await?)
+    }
+
+    async fn clearinghouse_state(&self, user: Address) -> Result<ClearinghouseState, AtlasError> {
+        let body = json!({"type": "clearinghouseState", "user": format!("{user:?}")});
+        parse("clearinghouseState", self.info(&body).await?)
+    }
+
+    async fn open_orders(&self, user: Address) -> Result<Vec<RestingOrder>, AtlasError> {
+        let body = json!({"type": "openOrders", "user": format!("{user:?}")});
+        parse("openOrders", self.info(&body).await?)
+    }
+
+    /// The user's most recent fills, newest first.
+    async fn user_fills(&self, user: Address) -> Result<Vec<Fill>, AtlasError> {
+        let body = json!({"type": "userFills", "user": format!("{user:?}")});
+        crate::fills::parse_fills(self.info(&body).await?)
+    }
+
+    /// Candles opened in `[start, end]`.
+    async fn candle_snapshot(
+        &self,
+        coin: &str,
+        tf: Timeframe,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<Candle>, AtlasError> {
+        let body = json!({
+            "type": "candleSnapshot",
+            "req": {"coin": coin, "interval": tf.as_str(), "startTime": start, "endTime": end},
+        });
+        crate::proxy::parse_candles(self.info(&body).await?)
+    }
+
+    async fn funding_history(
+        &self,
+        coin: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<FundingRate>, AtlasError> {
+        let body =
+            json!({"type": "fundingHistory", "coin": coin, "startTime": start, "endTime": end});
+        crate::proxy::parse_funding_history(self.info(&body).await?)
+    }
+
+    /// Perp markets from `meta`.
+    async fn perps(&self) -> Result<Vec<MarketMeta>, AtlasError> {
+        crate::proxy::parse_universe(&self.info(&json!({"type": "meta"})).await?)
+    }
+
+    /// Maintenance tiers per perp from `meta`.
+    async fn margin_tiers(&self) -> Result<HashMap<String, Vec<MarginTier>>, AtlasError> {
+        crate::proxy::parse_margin_tiers(&self.info(&json!({"type": "meta"})).await?)
+    }
+
+    /// Spot pairs from `spotMeta`.
+    async fn spot(&self) -> Result<Vec<SpotPair>, AtlasError> {
+        parse_spot_pairs(&self.info(&json!({"type": "spotMeta"})).await?)
+    }
+}
+
+/// [`HlApi`] over HTTP, sharing the module's retry policy and throttle.
+pub struct HttpApi {
+    http: ExchangeHttp,
+    base_url: &'static str,
+}
+
+impl HttpApi {
+    pub fn new(http: ExchangeHttp, testnet: bool) -> Self {
+        Self {
+            http,
+            base_url: if testnet {
+                HL_TESTNET_RPC
+            } else {
+                HL_MAINNET_RPC
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl HlApi for HttpApi {
+    async fn info(&self, body: &Value) -> Result<Value, AtlasError> {
+        self.http
+            .post_info(&format!("{}/info", self.base_url), body)
+            .await
+    }
+
+    async fn exchange(&self, body: &Value) -> Result<String, HttpFailure> {
+        self.http
+            .post_once(&format!("{}/exchange", self.base_url), body)
+            .await
+    }
+}
+
+/// Programmable [`HlApi`] for tests.
+///
+/// `/info` requests are answered by their `type`, the same response every
+/// time; `/exchange` responses are queued and used once each, in order.
+/// Anything unprogrammed fails. Every request is recorded.
+#[derive(Default)]
+pub struct MockApi {
+    info: Mutex<HashMap<String, Value>>,
+    exchange: Mutex<VecDeque<Result<String, HttpFailure>>>,
+    info_sent: Mutex<Vec<Value>>,
+    exchange_sent: Mutex<Vec<Value>>,
+}
+
+impl MockApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer every `/info` request of type `request` with `resp`.
+    pub fn with_info(self, request: &str, resp: Value) -> Self {
+        self.info.lock().unwrap().insert(request.into(), resp);
+        self
+    }
+
+    /// Queue the body of the next `/exchange` response.
+    pub fn with_exchange(self, resp: Value) -> Self {
+        self.exchange
+            .lock()
+            .unwrap()
+            .push_back(Ok(resp.to_string()));
+        self
+    }
+
+    /// Queue a failed `/exchange` attempt.
+    pub fn with_exchange_failure(self, failure: HttpFailure) -> Self {
+        self.exchange.lock().unwrap().push_back(Err(failure));
+        self
+    }
+
+    /// `/info` bodies received so far, oldest first.
+    pub fn info_requests(&self) -> Vec<Value> {
+        self.info_sent.lock().unwrap().clone()
+    }
+
+    /// `/exchange` bodies received so far, oldest first.
+    pub fn exchange_requests(&self) -> Vec<Value> {
+        self.exchange_sent.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl HlApi for MockApi {
+    async fn info(&self, body: &Value) -> Result<Value, AtlasError> {
+        self.info_sent.lock().unwrap().push(body.clone());
+        let request = body.get("type").and_then(Value::as_str).unwrap_or("");
+        self.info
+            .lock()
+            .unwrap()
+            .get(request)
+            .cloned()
+            .ok_or_else(|| AtlasError::Network(format!("mock: no response for {request}")))
+    }
+
+    async fn exchange(&self, body: &Value) -> Result<String, HttpFailure> {
+        self.exchange_sent.lock().unwrap().push(body.clone());
+        self.exchange
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| Err(HttpFailure::NotSent("mock: no exchange response".into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_typed_reads_parse_info_bodies() {
+        let api = MockApi::new()
+            .with_info("allMids", json!({"BTC": "97000.5", "@107": "31.2"}))
+            .with_info(
+                "clearinghouseState",
+                json!({
+                    "assetPositions": [{"type": "oneWay", "position": {
+                        "coin": "ETH", "szi": "-1.5", "entryPx": "3000.0",
+                        "unrealizedPnl": "12.5", "liquidationPx": null,
+                        "leverage": {"type": "isolated", "value": 5, "rawUsd": "900.0"},
+                    }}],
+                    "marginSummary": {"accountValue": "1000.0", "totalMarginUsed": "900.0",
+                        "totalNtlPos": "4500.0", "totalRawUsd": "5500.0"},
+                    "withdrawable": "100.0",
+                }),
+            );
+
+        let mids = api.all_mids().await.unwrap();
+        assert_eq!(mids["BTC"], Decimal::new(970005, 1));
+        assert_eq!(mids.len(), 2);
+
+        let state = api.clearinghouse_state(Address::ZERO).await.unwrap();
+        let position = &state.asset_positions[0].position;
+        assert_eq!(position.szi, Decimal::new(-15, 1));
+        assert_eq!(position.leverage.kind, "isolated");
+        assert_eq!(position.leverage.value, 5);
+        assert_eq!(position.liquidation_px, None);
+        assert_eq!(position.leverage.raw_usd, Some(Decimal::from(900)));
+        assert!(state.cross_margin_summary.is_none());
+        assert_eq!(state.withdrawable, Decimal::from(100));
+
+        let sent = api.info_requests();
+        assert_eq!(sent[1]["type"], "clearinghouseState");
+        assert_eq!(
+            sent[1]["user"],
+            "0x0000000000000000000000000000000000000000"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_fails_when_unprogrammed() {
+        let api = MockApi::new().with_exchange(json!({"status": "ok"}));
+        assert!(matches!(
+            api.open_orders(Address::ZERO).await,
+            Err(AtlasError::Network(_))
+        ));
+        assert_eq!(
+            api.exchange(&json!({})).await.unwrap(),
+            r#"{"status":"ok"}"#
+        );
+        assert!(matches!(
+            api.exchange(&json!({})).await,
+            Err(HttpFailure::NotSent(_))
+        ));
+        assert_eq!(api.exchange_requests().len(), 2);
+    }
+}
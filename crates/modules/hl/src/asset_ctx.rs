@@ -0,0 +1,173 @@
+//! Typed `metaAndAssetCtxs` response.
+//!
+//! The endpoint returns `[meta, [ctx, ...]]` where `ctx[i]` belongs to
+//! `meta.universe[i]`. Numbers arrive as decimal strings; a field that is
+//! missing, null or unparseable becomes `None` instead of failing the batch.
+
+use std::str::FromStr;
+
+use atlas_core::error::AtlasError;
+use atlas_core::types::{AssetContext, Protocol};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+/// Hyperliquid pays funding every hour.
+pub const FUNDING_INTERVAL_HOURS: u32 = 1;
+
+/// One perp market's live context, paired with its name from the universe.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetCtx {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default, deserialize_with = "lenient_decimal")]
+    pub mid_px: Option<Decimal>,
+    #[serde(default, deserialize_with = "lenient_decimal")]
+    pub mark_px: Option<Decimal>,
+    #[serde(default, deserialize_with = "lenient_decimal")]
+    pub oracle_px: Option<Decimal>,
+    /// `[bid, ask]` impact prices.
+    #[serde(default)]
+    pub impact_pxs: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "lenient_decimal")]
+    pub day_ntl_vlm: Option<Decimal>,
+    #[serde(default, deserialize_with = "lenient_decimal")]
+    pub day_base_vlm: Option<Decimal>,
+    #[serde(default, deserialize_with = "lenient_decimal")]
+    pub prev_day_px: Option<Decimal>,
+    /// Open interest in base units.
+    #[serde(default, deserialize_with = "lenient_decimal")]
+    pub open_interest: Option<Decimal>,
+    /// Predicted hourly funding rate.
+    #[serde(default, deserialize_with = "lenient_decimal")]
+    pub funding: Option<Decimal>,
+    #[serde(default, deserialize_with = "lenient_decimal")]
+    pub premium: Option<Decimal>,
+}
+
+#[derive(Deserialize)]
+struct Universe {
+    universe: Vec<UniverseEntry>,
+}
+
+#[derive(Deserialize)]
+struct UniverseEntry {
+    name: String,
+}
+
+impl AssetCtx {
+    pub fn impact_bid(&self) -> Option<Decimal> {
+        self.impact_px(0)
+    }
+
+    pub fn impact_ask(&self) -> Option<Decimal> {
+        self.impact_px(1)
+    }
+
+    fn impact_px(&self, i: usize) -> Option<Decimal> {
+        let s = self.impact_pxs.as_ref()?.get(i)?;
+        Decimal::from_str(s).ok()
+    }
+
+    pub fn to_universal(&self) -> AssetContext {
+        AssetContext {
+            symbol: self.name.clone(),
+            protocol: Protocol::Hyperliquid,
+            mark_price: self.mark_px,
+            mid_price: self.mid_px,
+            oracle_price: self.oracle_px,
+            funding_rate: self.funding,
+            funding_interval_hours: FUNDING_INTERVAL_HOURS,
+            premium: self.premium,
+            open_interest: self.open_interest,
+            volume_24h: self.day_ntl_vlm,
+            volume_24h_base: self.day_base_vlm,
+            prev_day_price: self.prev_day_px,
+            impact_bid: self.impact_bid(),
+            impact_ask: self.impact_ask(),
+        }
+    }
+}
+
+/// Parse a `metaAndAssetCtxs` response into named contexts, in universe order.
+pub fn parse_meta_and_asset_ctxs(resp: Value) -> Result<Vec<AssetCtx>, AtlasError> {
+    let (meta, mut ctxs): (Universe, Vec<AssetCtx>) = serde_json::from_value(resp)
+        .map_err(|e| AtlasError::Network(format!("unexpected metaAndAssetCtxs shape: {e}")))?;
+    for (ctx, entry) in ctxs.iter_mut().zip(meta.universe) {
+        ctx.name = entry.name;
+    }
+    Ok(ctxs)
+}
+
+fn lenient_decimal<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Decimal>, D::Error> {
+    Ok(match Option::<Value>::deserialize(d)? {
+        Some(Value::String(s)) => Decimal::from_str(&s).ok(),
+        Some(Value::Number(n)) => Decimal::from_str(&n.to_string()).ok(),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_names_and_fields() {
+        let resp = serde_json::json!([
+            {"universe": [{"name": "BTC", "szDecimals": 5}, {"name": "ETH", "szDecimals": 4}]},
+            [
+                {
+                    "funding": "0.0000125", "openInterest": "12000.5", "markPx": "97000.0",
+                    "midPx": "97001.5", "oraclePx": "96990.0", "premium": "0.0001",
+                    "dayNtlVlm": "1500000000.0", "dayBaseVlm": "15500.25", "prevDayPx": "95000.0",
+                    "impactPxs": ["97000.0", "97003.0"]
+                },
+                {"funding": "-0.00005", "openInterest": "bad", "markPx": "3400.0", "midPx": null}
+            ]
+        ]);
+        let ctxs = parse_meta_and_asset_ctxs(resp).unwrap();
+        assert_eq!(ctxs.len(), 2);
+
+        let btc = &ctxs[0];
+        assert_eq!(btc.name, "BTC");
+        assert_eq!(btc.funding, Some(Decimal::from_str("0.0000125").unwrap()));
+        assert_eq!(btc.impact_ask(), Some(Decimal::from(97003)));
+        let u = btc.to_universal();
+        assert_eq!(
+            u.funding_apr_pct(),
+            Some(Decimal::from_str("10.95").unwrap())
+        );
+        assert_eq!(
+            u.open_interest_usd(),
+            Some(Decimal::from_str("1164048500").unwrap())
+        );
+        assert_eq!(
+            u.volume_24h_base,
+            Some(Decimal::from_str("15500.25").unwrap())
+        );
+        assert_eq!(u.oracle_price, Some(Decimal::from(96990)));
+        assert_eq!(u.premium, Some(Decimal::from_str("0.0001").unwrap()));
+        assert_eq!(u.impact_bid, Some(Decimal::from(97000)));
+        assert_eq!(
+            u.spread_bps().map(|b| b.round_dp(4)),
+            Some(Decimal::from_str("0.3093").unwrap())
+        );
+        // xx:59:30 → next payment in 30s; exactly on the hour → a full hour
+        assert_eq!(u.next_funding_secs(3600 * 10 + 3570), 30);
+        assert_eq!(u.next_funding_secs(7200), 3600);
+
+        let eth = &ctxs[1];
+        assert_eq!(eth.name, "ETH");
+        assert!(eth.funding.unwrap().is_sign_negative());
+        assert_eq!(eth.open_interest, None);
+        assert_eq!(eth.mid_px, None);
+        assert_eq!(eth.impact_bid(), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_wrong_shape() {
+        let err = parse_meta_and_asset_ctxs(serde_json::json!({"universe": []})).unwrap_err();
+        assert!(matches!(err, AtlasError::Network(_)));
+    }
+}
@@ -0,0 +1,234 @@
+//! Paginated candle fetching.
+//!
+//! `candleSnapshot` returns at most a few hundred candles per call, so deep
+//! lookbacks walk backwards from now one window at a time until enough
+//! candles are collected or the exchange runs out of history.
+
+use std::future::Future;
+
+use atlas_core::error::AtlasError;
+use atlas_core::types::Candle;
+use tracing::warn;
+
+/// Candles requested per `candleSnapshot` call (Hyperliquid caps a response at 500).
+pub const CANDLES_PER_REQUEST: u64 = 500;
+
+/// Collect the `limit` most recent candles ending at `now_ms`.
+///
+/// `fetch(start_ms, end_ms)` returns one page. Each next window ends at the
+/// oldest candle seen so far, so a page the exchange truncates still leaves
+/// no gap; overlapping candles are deduped on open time. Returns candles in
+/// chronological order, and warns when history runs out before `limit`.
+pub async fn fetch_paginated<F, Fut>(
+    symbol: &str,
+    interval_ms: u64,
+    limit: usize,
+    now_ms: u64,
+    mut fetch: F,
+) -> Result<Vec<Candle>, AtlasError>
+where
+    F: FnMut(u64, u64) -> Fut,
+    Fut: Future<Output = Result<Vec<Candle>, AtlasError>>,
+{
+    let mut candles: Vec<Candle> = Vec::with_capacity(limit);
+    let mut end = now_ms;
+    while candles.len() < limit && end > 0 {
+        let start = end.saturating_sub(interval_ms * CANDLES_PER_REQUEST);
+        let page = fetch(start, end).await?;
+        let Some(oldest) = page.iter().map(|c| c.open_time_ms).min() else {
+            break; // earliest available data reached
+        };
+        candles.extend(page);
+        candles.sort_by_key(|c| c.open_time_ms);
+        candles.dedup_by_key(|c| c.open_time_ms);
+        if oldest >= end {
+            break; // no progress — never loop on a misbehaving page
+        }
+        end = oldest;
+    }
+
+    if candles.len() > limit {
+        candles.drain(..candles.len() - limit);
+    }
+    if candles.len() < limit {
+        warn!(
+            symbol,
+            requested = limit,
+            returned = candles.len(),
+            "fewer candles available than requested"
+        );
+    }
+    Ok(candles)
+}
+
+/// Every candle opened in `[start_ms, end_ms]`, in chronological order —
+/// the range fetch behind the candle cache.
+///
+/// Walks backwards one window at a time like [`fetch_paginated`], so pages
+/// the exchange truncates leave no gap. Stops early at the earliest history.
+pub async fn fetch_range<F, Fut>(
+    interval_ms: u64,
+    start_ms: u64,
+    end_ms: u64,
+    mut fetch: F,
+) -> Result<Vec<Candle>, AtlasError>
+where
+    F: FnMut(u64, u64) -> Fut,
+    Fut: Future<Output = Result<Vec<Candle>, AtlasError>>,
+{
+    let mut candles: Vec<Candle> = Vec::new();
+    let mut end = end_ms;
+    while end >= start_ms {
+        let start = end
+            .saturating_sub(interval_ms * (CANDLES_PER_REQUEST - 1))
+            .max(start_ms);
+        let page = fetch(start, end).await?;
+        let Some(oldest) = page.iter().map(|c| c.open_time_ms).min() else {
+            break; // earliest available data reached
+        };
+        candles.extend(
+            page.into_iter()
+                .filter(|c| (start_ms..=end_ms).contains(&c.open_time_ms)),
+        );
+        if oldest <= start_ms || oldest > end {
+            break;
+        }
+        end = oldest - 1;
+    }
+
+    candles.sort_by_key(|c| c.open_time_ms);
+    candles.dedup_by_key(|c| c.open_time_ms);
+    Ok(candles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::cell::RefCell;
+
+    const MINUTE: u64 = 60_000;
+
+    fn candle(open_time_ms: u64) -> Candle {
+        Candle {
+            open_time_ms,
+            open: Decimal::ONE,
+            high: Decimal::ONE,
+            low: Decimal::ONE,
+            close: Decimal::ONE,
+            volume: Decimal::ZERO,
+            trades: None,
+        }
+    }
+
+    /// Mock exchange: 1m candles from `first` to `now`, serving at most
+    /// `cap` candles per call (the most recent ones in the window).
+    struct MockExchange {
+        first: u64,
+        cap: usize,
+        calls: RefCell<Vec<(u64, u64)>>,
+    }
+
+    impl MockExchange {
+        async fn snapshot(&self, start: u64, end: u64) -> Result<Vec<Candle>, AtlasError> {
+            self.calls.borrow_mut().push((start, end));
+            let from = start.max(self.first).div_ceil(MINUTE) * MINUTE;
+            let all: Vec<Candle> = (from..=end).step_by(MINUTE as usize).map(candle).collect();
+            Ok(all[all.len().saturating_sub(self.cap)..].to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paginates_to_requested_depth() {
+        let now = 10_000 * MINUTE;
+        let mock = MockExchange {
+            first: 0,
+            cap: 500,
+            calls: RefCell::new(Vec::new()),
+        };
+        let candles = fetch_paginated("BTC", MINUTE, 2000, now, |s, e| mock.snapshot(s, e))
+            .await
+            .unwrap();
+
+        assert_eq!(candles.len(), 2000);
+        assert_eq!(candles.last().unwrap().open_time_ms, now);
+        assert_eq!(candles[0].open_time_ms, now - 1999 * MINUTE);
+        assert!(candles
+            .windows(2)
+            .all(|w| w[1].open_time_ms - w[0].open_time_ms == MINUTE));
+        assert!(mock.calls.borrow().len() >= 4);
+    }
+
+    #[tokio::test]
+    async fn test_truncated_pages_leave_no_gaps() {
+        let now = 10_000 * MINUTE;
+        // The exchange serves fewer candles than the window asks for.
+        let mock = MockExchange {
+            first: 0,
+            cap: 120,
+            calls: RefCell::new(Vec::new()),
+        };
+        let candles = fetch_paginated("BTC", MINUTE, 700, now, |s, e| mock.snapshot(s, e))
+            .await
+            .unwrap();
+
+        assert_eq!(candles.len(), 700);
+        assert!(candles
+            .windows(2)
+            .all(|w| w[1].open_time_ms - w[0].open_time_ms == MINUTE));
+    }
+
+    #[tokio::test]
+    async fn test_stops_at_earliest_history() {
+        let now = 10_000 * MINUTE;
+        // Listed 300 minutes ago — less history than requested.
+        let mock = MockExchange {
+            first: now - 299 * MINUTE,
+            cap: 500,
+            calls: RefCell::new(Vec::new()),
+        };
+        let candles = fetch_paginated("NEW", MINUTE, 1000, now, |s, e| mock.snapshot(s, e))
+            .await
+            .unwrap();
+
+        assert_eq!(candles.len(), 300);
+        assert_eq!(candles[0].open_time_ms, now - 299 * MINUTE);
+        // One page with data, one that only repeats the oldest candle, then stop.
+        assert_eq!(mock.calls.borrow().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_range_is_exact_and_gapless() {
+        let now = 10_000 * MINUTE;
+        let mock = MockExchange {
+            first: 0,
+            cap: 120,
+            calls: RefCell::new(Vec::new()),
+        };
+        let start = now - 999 * MINUTE;
+        let candles = fetch_range(MINUTE, start, now - 300 * MINUTE, |s, e| {
+            mock.snapshot(s, e)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(candles.len(), 700);
+        assert_eq!(candles[0].open_time_ms, start);
+        assert_eq!(candles.last().unwrap().open_time_ms, now - 300 * MINUTE);
+        assert!(candles
+            .windows(2)
+            .all(|w| w[1].open_time_ms - w[0].open_time_ms == MINUTE));
+
+        // Listed after the range: one call, no candles
+        let mock = MockExchange {
+            first: 20 * MINUTE,
+            cap: 500,
+            calls: RefCell::new(Vec::new()),
+        };
+        let none = fetch_range(MINUTE, 0, 10 * MINUTE, |s, e| mock.snapshot(s, e))
+            .await
+            .unwrap();
+        assert!(none.is_empty());
+        assert_eq!(mock.calls.borrow().len(), 1);
+    }
+}
@@ -1,5 +1,8 @@
 //! Hyperliquid protocol module — implements PerpModule trait.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use alloy::primitives::Address;
 use alloy::signers::local::PrivateKeySigner;
 use alloy::signers::SignerSync;
@@ -9,56 +12,46 @@ use hypersdk::hypercore::{
     self as hypercore,
     types::{
         api::{Action, UpdateIsolatedMargin},
-        BatchCancel, BatchCancelCloid, BatchOrder, Cancel, CancelByCloid, CandleInterval,
-        OrderGrouping, OrderRequest, OrderResponseStatus, OrderTypePlacement, TimeInForce,
+        BatchCancel, BatchCancelCloid, BatchOrder, Cancel, CancelByCloid, OrderGrouping,
+        OrderRequest, OrderResponseStatus, OrderTypePlacement, TimeInForce, TpSl,
     },
-    Cloid, HttpClient, NonceHandler, PerpMarket,
+    Cloid, HttpClient,
 };
 use rust_decimal::prelude::*;
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use serde_json::Value;
-use tracing::info;
+use tracing::{debug, info, warn};
 
+use atlas_core::audit::{AuditOutcome, AuditTrail, SignedAction};
+use atlas_core::backend::BackendClient;
+use atlas_core::candle_cache::CandleCache;
+use atlas_core::config::{RateLimitConfig, TradingConfig};
 use atlas_core::constants::*;
+use atlas_core::date_range::now_ms;
+use atlas_core::engine::BuilderFee;
 use atlas_core::error::*;
+use atlas_core::risk::{check_market_order, check_order_risk, check_trigger, TriggerRole};
+use atlas_core::symbols::{resolve_spot, SymbolResolver};
+use atlas_core::timeframe::Timeframe;
 use atlas_core::traits::PerpModule;
 use atlas_core::types::*;
 
+use crate::api::{HlApi, HttpApi};
+use crate::asset_ctx::{parse_meta_and_asset_ctxs, AssetCtx};
+use crate::candles::{fetch_paginated, fetch_range};
 use crate::convert::*;
+use crate::http::{ExchangeHttp, HttpFailure, RetryPolicy};
+use crate::json_cache::JsonCache;
+use crate::meta::{round_spot_price, MarketMeta, MetaCache, MetaOptions};
+use crate::modify::{BatchModify, ModifySpec, ModifyTarget};
+use crate::nonce::{is_nonce_rejection, NonceSource};
+use crate::orders::parse_open_orders;
+use crate::proxy::{self, PROXY_ONLY_MESSAGE};
 use crate::signing::compute_agent_signing_hash;
-
-/// Raw asset context from metaAndAssetCtxs endpoint.
-struct AssetCtxRaw {
-    name: String,
-    mid_px: Option<Decimal>,
-    #[allow(dead_code)]
-    mark_px: Option<Decimal>,
-    impact_bid: Option<Decimal>,
-    impact_ask: Option<Decimal>,
-    volume: Option<Decimal>,
-    prev_day_px: Option<Decimal>,
-    #[allow(dead_code)]
-    oi: Option<Decimal>,
-    #[allow(dead_code)]
-    funding: Option<Decimal>,
-}
-
-/// Builder fee payload injected into order JSON.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct BuilderFee {
-    b: String,
-    f: u16,
-}
-
-impl Default for BuilderFee {
-    fn default() -> Self {
-        Self {
-            b: BUILDER_ADDRESS_EVM.to_string(),
-            f: BUILDER_FEE_BPS,
-        }
-    }
-}
+use crate::spot::{find_usdc_pair, is_spot_key, SpotPair};
+use crate::throttle::InfoThrottle;
+use crate::vaults::{self, VaultCache};
 
 /// Generate a random client order ID.
 fn random_cloid() -> Cloid {
@@ -68,207 +61,404 @@ fn random_cloid() -> Cloid {
     alloy::primitives::B128::from(bytes)
 }
 
-/// Parse candle interval string to SDK enum.
-fn parse_interval(s: &str) -> Result<CandleInterval, AtlasError> {
-    match s {
-        "1m" => Ok(CandleInterval::OneMinute),
-        "3m" => Ok(CandleInterval::ThreeMinutes),
-        "5m" => Ok(CandleInterval::FiveMinutes),
-        "15m" => Ok(CandleInterval::FifteenMinutes),
-        "30m" => Ok(CandleInterval::ThirtyMinutes),
-        "1h" => Ok(CandleInterval::OneHour),
-        "2h" => Ok(CandleInterval::TwoHours),
-        "4h" => Ok(CandleInterval::FourHours),
-        "8h" => Ok(CandleInterval::EightHours),
-        "12h" => Ok(CandleInterval::TwelveHours),
-        "1d" => Ok(CandleInterval::OneDay),
-        "3d" => Ok(CandleInterval::ThreeDays),
-        "1w" => Ok(CandleInterval::OneWeek),
-        "1M" => Ok(CandleInterval::OneMonth),
-        _ => Err(AtlasError::Other(format!("Invalid interval: {s}"))),
+/// The caller's client order ID, or a fresh random one.
+fn order_cloid(requested: Option<&str>) -> Result<Cloid, AtlasError> {
+    match requested {
+        Some(raw) => raw
+            .parse::<Cloid>()
+            .map_err(|_| AtlasError::Other(format!("Invalid cloid '{raw}'"))),
+        None => Ok(random_cloid()),
+    }
+}
+
+fn sdk_tif(tif: atlas_core::types::TimeInForce) -> TimeInForce {
+    match tif {
+        atlas_core::types::TimeInForce::Gtc => TimeInForce::Gtc,
+        atlas_core::types::TimeInForce::Ioc => TimeInForce::Ioc,
+        atlas_core::types::TimeInForce::Alo => TimeInForce::Alo,
     }
 }
 
-fn interval_to_ms(i: &CandleInterval) -> u64 {
-    match i {
-        CandleInterval::OneMinute => 60_000,
-        CandleInterval::ThreeMinutes => 180_000,
-        CandleInterval::FiveMinutes => 300_000,
-        CandleInterval::FifteenMinutes => 900_000,
-        CandleInterval::ThirtyMinutes => 1_800_000,
-        CandleInterval::OneHour => 3_600_000,
-        CandleInterval::TwoHours => 7_200_000,
-        CandleInterval::FourHours => 14_400_000,
-        CandleInterval::EightHours => 28_800_000,
-        CandleInterval::TwelveHours => 43_200_000,
-        CandleInterval::OneDay => 86_400_000,
-        CandleInterval::ThreeDays => 259_200_000,
-        CandleInterval::OneWeek => 604_800_000,
-        CandleInterval::OneMonth => 2_592_000_000,
+/// Record the outcome of an order submission as a structured event, so
+/// `atlas logs --grep order` can reconstruct what was sent and what came back.
+fn log_order(
+    kind: &str,
+    symbol: &str,
+    side: &Side,
+    sz: Decimal,
+    px: Decimal,
+    cloid: &Cloid,
+    result: &AtlasResult<OrderResult>,
+) {
+    match result {
+        Ok(r) => info!(
+            event = "order", kind, coin = symbol, side = %side, size = %sz, px = %px,
+            oid = %r.order_id, cloid = %cloid, status = ?r.status,
+            filled = r.filled_size.and_then(|d| d.to_f64()),
+            avg_px = r.avg_price.and_then(|d| d.to_f64()),
+            "order placed"
+        ),
+        Err(e) => warn!(
+            event = "order", kind, coin = symbol, side = %side, size = %sz, px = %px,
+            cloid = %cloid, status = "rejected", error = %e,
+            "order rejected"
+        ),
     }
 }
 
+/// Per-order statuses from an `/exchange` order or modify response.
+fn parse_order_statuses(body: &str) -> Result<Vec<OrderResponseStatus>, AtlasError> {
+    let parsed: Value = serde_json::from_str(body).map_err(|_| AtlasError::Protocol {
+        protocol: "hyperliquid".into(),
+        message: format!("Bad response: {body}"),
+    })?;
+
+    if parsed.get("status").and_then(|v| v.as_str()) == Some("err") {
+        let msg = parsed
+            .get("response")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown error");
+        return Err(AtlasError::order_rejected(msg));
+    }
+
+    let statuses_val =
+        parsed
+            .pointer("/response/data/statuses")
+            .ok_or_else(|| AtlasError::Protocol {
+                protocol: "hyperliquid".into(),
+                message: format!("No statuses: {body}"),
+            })?;
+
+    serde_json::from_value(statuses_val.clone())
+        .map_err(|e| AtlasError::Other(format!("Parse statuses: {e}")))
+}
+
+/// Exchange-side state of an order looked up by its client order ID.
+#[derive(Debug, Clone, Serialize)]
+pub struct CloidOrderStatus {
+    pub oid: u64,
+    /// Raw HL status: `open`, `filled`, `canceled`, `rejected`, `marginCanceled`, ...
+    pub status: String,
+    pub orig_size: Decimal,
+    /// Unfilled remainder.
+    pub size: Decimal,
+    pub limit_price: Decimal,
+}
+
 /// The Hyperliquid module — wraps the SDK and implements PerpModule.
 pub struct HyperliquidModule {
     pub client: HttpClient,
+    pub http: ExchangeHttp,
+    /// `/info` and `/exchange` transport for market data, account reads and
+    /// order submission; [`HttpApi`] unless swapped in with [`Self::with_api`].
+    pub api: Arc<dyn HlApi>,
     pub signer: Option<PrivateKeySigner>,
-    pub nonce: NonceHandler,
-    pub perps: Vec<PerpMarket>,
+    /// Nonces persisted per wallet, shared with other atlas processes.
+    pub nonce: NonceSource,
+    pub perps: Vec<MarketMeta>,
+    /// Coin aliases and fuzzy suggestions for perp names.
+    pub resolver: SymbolResolver,
+    /// Builder fee attached to perp orders; `None` when disabled.
+    pub builder: Option<BuilderFee>,
+    /// How long cached metadata (perps, spot tokens) stays valid.
+    pub meta_ttl: std::time::Duration,
     pub address: Option<Address>,
     pub testnet: bool,
+    /// Atlas backend that serves market data instead of `/info`
+    /// (`data_via_backend`); signing and account queries are refused.
+    pub backend: Option<BackendClient>,
+    /// Local cache of closed candles; `None` always fetches (`--no-cache`).
+    pub candle_cache: Option<Arc<CandleCache>>,
+    /// Every signed action is recorded here before it is sent.
+    pub audit: AuditTrail,
+    /// `trading.max_slippage` / `trading.price_band`, checked before every
+    /// market order and close; `None` when overridden (`--force`).
+    pub protections: Option<TradingConfig>,
+    /// `trading.max_notional` / `max_leverage` / `daily_loss_limit`, checked
+    /// before every order that opens or adds exposure; `None` when
+    /// overridden (`--override-risk`).
+    pub risk_limits: Option<TradingConfig>,
 }
 
 impl HyperliquidModule {
     /// Create from signer and network config.
     pub async fn new(signer: PrivateKeySigner, testnet: bool) -> Result<Self, AtlasError> {
-        let address = signer.address();
+        Self::with_options(Some(signer), testnet, MetaOptions::default()).await
+    }
+
+    /// Create a read-only client (no signer = market data only, no trading).
+    pub async fn new_readonly(testnet: bool) -> Result<Self, AtlasError> {
+        Self::with_options(None, testnet, MetaOptions::default()).await
+    }
+
+    /// Create with explicit metadata cache options. No signer = read-only.
+    pub async fn with_options(
+        signer: Option<PrivateKeySigner>,
+        testnet: bool,
+        meta: MetaOptions,
+    ) -> Result<Self, AtlasError> {
+        let address = signer.as_ref().map(|s| s.address());
         let client = if testnet {
             hypercore::testnet()
         } else {
             hypercore::mainnet()
         };
 
-        let perps = client
-            .perps()
-            .await
-            .map_err(|e| AtlasError::Network(format!("Failed to fetch markets: {e}")))?;
-
-        let nonce = NonceHandler::default();
+        let http = ExchangeHttp::new(RetryPolicy::default())?;
+        let api: Arc<dyn HlApi> = Arc::new(HttpApi::new(http.clone(), testnet));
+        let perps = Self::load_perps(api.as_ref(), testnet, &meta).await?;
+        let nonce = address.map(NonceSource::persistent).unwrap_or_default();
 
-        info!(%address, testnet, markets = perps.len(), "Hyperliquid module ready");
+        info!(
+            ?address,
+            testnet,
+            markets = perps.len(),
+            "Hyperliquid module ready"
+        );
 
         Ok(Self {
             client,
-            signer: Some(signer),
+            http,
+            api,
+            signer,
             nonce,
             perps,
-            address: Some(address),
+            resolver: SymbolResolver::default(),
+            builder: Some(BuilderFee::default()),
+            meta_ttl: meta.ttl,
+            address,
             testnet,
+            backend: None,
+            candle_cache: None,
+            audit: AuditTrail::workspace(),
+            protections: None,
+            risk_limits: None,
         })
     }
 
-    /// Create a read-only client (no signer = market data only, no trading).
-    pub async fn new_readonly(testnet: bool) -> Result<Self, AtlasError> {
-        let client = if testnet {
-            hypercore::testnet()
+    /// Create a read-only client whose market data comes through the Atlas
+    /// backend's Hyperliquid proxy. Mainnet only; the proxy has no testnet.
+    pub async fn readonly_via_backend(
+        backend: BackendClient,
+        testnet: bool,
+        meta: MetaOptions,
+    ) -> Result<Self, AtlasError> {
+        if testnet {
+            return Err(AtlasError::ProxyOnly(
+                "the backend proxy serves mainnet only; set network to mainnet or turn off data_via_backend".into(),
+            ));
+        }
+
+        let path = MetaCache::path(false);
+        let cached = if meta.refresh {
+            None
         } else {
-            hypercore::mainnet()
+            path.as_deref()
+                .and_then(MetaCache::load)
+                .filter(|c| c.is_fresh(meta.ttl) && !c.perps.is_empty())
+        };
+        let perps = match cached {
+            Some(cache) => cache.perps,
+            None => {
+                let resp = backend
+                    .get(&format!("{}/meta-and-asset-ctxs", proxy::PROXY_PATH), &[])
+                    .await?;
+                let perps = proxy::parse_universe(&resp)?;
+                if let Some(path) = path {
+                    if let Err(e) = MetaCache::new(false, perps.clone()).save(&path) {
+                        warn!(path = %path.display(), "Failed to write HL metadata cache: {e}");
+                    }
+                }
+                perps
+            }
         };
-
-        let perps = client
-            .perps()
-            .await
-            .map_err(|e| AtlasError::Network(format!("Failed to fetch markets: {e}")))?;
-
-        let nonce = NonceHandler::default();
 
         info!(
-            testnet,
             markets = perps.len(),
-            "Hyperliquid module ready (read-only)"
+            "Hyperliquid module ready (market data via Atlas backend)"
         );
 
+        let http = ExchangeHttp::new(RetryPolicy::default())?;
         Ok(Self {
-            client,
+            client: hypercore::mainnet(),
+            api: Arc::new(HttpApi::new(http.clone(), false)),
+            http,
             signer: None,
-            nonce,
+            nonce: NonceSource::default(),
             perps,
+            resolver: SymbolResolver::default(),
+            builder: None,
+            meta_ttl: meta.ttl,
             address: None,
+            testnet: false,
+            backend: Some(backend),
+            candle_cache: None,
+            audit: AuditTrail::workspace(),
+            protections: None,
+            risk_limits: None,
+        })
+    }
+
+    /// Create over an explicit transport with known markets: no metadata
+    /// fetch and no persisted nonces. Used to drive the module against
+    /// [`MockApi`](crate::api::MockApi).
+    pub fn with_api(
+        api: Arc<dyn HlApi>,
+        signer: Option<PrivateKeySigner>,
+        testnet: bool,
+        perps: Vec<MarketMeta>,
+    ) -> Result<Self, AtlasError> {
+        Ok(Self {
+            client: if testnet {
+                hypercore::testnet()
+            } else {
+                hypercore::mainnet()
+            },
+            http: ExchangeHttp::new(RetryPolicy::default())?,
+            api,
+            address: signer.as_ref().map(|s| s.address()),
+            signer,
+            nonce: NonceSource::default(),
+            perps,
+            resolver: SymbolResolver::default(),
+            builder: Some(BuilderFee::default()),
+            meta_ttl: MetaOptions::default().ttl,
             testnet,
+            backend: None,
+            candle_cache: None,
+            audit: AuditTrail::in_memory(),
+            protections: None,
+            risk_limits: None,
         })
     }
 
-    /// Fetch asset contexts (funding, OI, impact prices, volume, etc.) via metaAndAssetCtxs.
-    async fn fetch_asset_ctxs(&self) -> Result<Vec<AssetCtxRaw>, AtlasError> {
-        let url = if self.testnet {
-            "https://api.hyperliquid-testnet.xyz/info"
-        } else {
-            "https://api.hyperliquid.xyz/info"
-        };
-        let http = reqwest::Client::new();
-        let resp: Value = http
-            .post(url)
-            .json(&serde_json::json!({"type": "metaAndAssetCtxs"}))
-            .send()
-            .await
-            .map_err(|e| AtlasError::Network(format!("metaAndAssetCtxs: {e}")))?
-            .json()
+    /// Perp metadata from the cache while fresh, otherwise from the API.
+    ///
+    /// A refetch rewrites the cache; failing to write it is not an error.
+    async fn load_perps(
+        api: &dyn HlApi,
+        testnet: bool,
+        opts: &MetaOptions,
+    ) -> Result<Vec<MarketMeta>, AtlasError> {
+        let path = MetaCache::path(testnet);
+        if !opts.refresh {
+            if let Some(cache) = path.as_deref().and_then(MetaCache::load) {
+                if cache.is_fresh(opts.ttl) && !cache.perps.is_empty() {
+                    debug!(age_secs = cache.age().as_secs(), "Using cached HL metadata");
+                    return Ok(cache.perps);
+                }
+            }
+        }
+
+        let perps = api
+            .perps()
             .await
-            .map_err(|e| AtlasError::Network(format!("metaAndAssetCtxs parse: {e}")))?;
+            .map_err(|e| AtlasError::Network(format!("Failed to fetch markets: {e}")))?;
 
-        // Response is [meta, [ctx, ctx, ...]]
-        let ctxs = resp
-            .get(1)
-            .and_then(|v| v.as_array())
-            .ok_or_else(|| AtlasError::Network("unexpected metaAndAssetCtxs shape".into()))?;
+        if let Some(path) = path {
+            if let Err(e) = MetaCache::new(testnet, perps.clone()).save(&path) {
+                warn!(path = %path.display(), "Failed to write HL metadata cache: {e}");
+            }
+        }
+        Ok(perps)
+    }
 
-        let universe = resp
-            .get(0)
-            .and_then(|v| v.get("universe"))
-            .and_then(|v| v.as_array())
-            .ok_or_else(|| AtlasError::Network("missing universe in meta".into()))?;
-
-        let mut result = Vec::with_capacity(ctxs.len());
-        for (i, ctx) in ctxs.iter().enumerate() {
-            let name = universe
-                .get(i)
-                .and_then(|u| u.get("name"))
-                .and_then(|n| n.as_str())
-                .unwrap_or("")
-                .to_string();
-
-            let impact_bid = ctx
-                .get("impactPxs")
-                .and_then(|v| v.get(0))
-                .and_then(|v| v.as_str())
-                .and_then(|s| Decimal::from_str(s).ok());
-            let impact_ask = ctx
-                .get("impactPxs")
-                .and_then(|v| v.get(1))
-                .and_then(|v| v.as_str())
-                .and_then(|s| Decimal::from_str(s).ok());
-            let mid_px = ctx
-                .get("midPx")
-                .and_then(|v| v.as_str())
-                .and_then(|s| Decimal::from_str(s).ok());
-            let mark_px = ctx
-                .get("markPx")
-                .and_then(|v| v.as_str())
-                .and_then(|s| Decimal::from_str(s).ok());
-            let volume = ctx
-                .get("dayNtlVlm")
-                .and_then(|v| v.as_str())
-                .and_then(|s| Decimal::from_str(s).ok());
-            let prev_day_px = ctx
-                .get("prevDayPx")
-                .and_then(|v| v.as_str())
-                .and_then(|s| Decimal::from_str(s).ok());
-            let oi = ctx
-                .get("openInterest")
-                .and_then(|v| v.as_str())
-                .and_then(|s| Decimal::from_str(s).ok());
-            let funding = ctx
-                .get("funding")
-                .and_then(|v| v.as_str())
-                .and_then(|s| Decimal::from_str(s).ok());
+    /// Replace the default HTTP retry / timeout policy.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Result<Self, AtlasError> {
+        self.http = ExchangeHttp::new(policy)?.with_throttle(self.http.throttle());
+        self.api = Arc::new(HttpApi::new(self.http.clone(), self.testnet));
+        Ok(self)
+    }
 
-            result.push(AssetCtxRaw {
-                name,
-                mid_px,
-                mark_px,
-                impact_bid,
-                impact_ask,
-                volume,
-                prev_day_px,
-                oi,
-                funding,
-            });
+    /// Throttle `/info` requests as configured (see [`InfoThrottle`]).
+    pub fn with_rate_limit(mut self, cfg: &RateLimitConfig) -> Self {
+        self.http = self.http.with_throttle(Arc::new(InfoThrottle::new(cfg)));
+        self.api = Arc::new(HttpApi::new(self.http.clone(), self.testnet));
+        self
+    }
+
+    /// Use the configured builder fee (see [`BuilderFee::from_config`]).
+    pub fn with_builder_fee(mut self, builder: Option<BuilderFee>) -> Self {
+        self.builder = builder;
+        self
+    }
+
+    /// Refuse market orders and closes that break the order protections
+    /// (see [`check_market_order`]); `None` turns them off.
+    pub fn with_order_protections(mut self, limits: Option<TradingConfig>) -> Self {
+        self.protections = limits;
+        self
+    }
+
+    /// Refuse orders that break the `trading` risk limits (see
+    /// [`check_order_risk`]); `None` turns them off.
+    pub fn with_risk_limits(mut self, limits: Option<TradingConfig>) -> Self {
+        self.risk_limits = limits;
+        self
+    }
+
+    /// Serve closed candles from the local cache (see [`CandleCache`]).
+    pub fn with_candle_cache(mut self, cache: Option<Arc<CandleCache>>) -> Self {
+        self.candle_cache = cache;
+        self
+    }
+
+    /// Add user-defined coin aliases (`XBT` → `BTC`) on top of the built-ins.
+    pub fn with_aliases(mut self, aliases: &HashMap<String, String>) -> Self {
+        self.resolver = self.resolver.with_aliases(aliases);
+        self
+    }
+
+    /// One `candleSnapshot` page: candles opened in `[start, end]`.
+    async fn candle_page(
+        &self,
+        symbol: &str,
+        tf: Timeframe,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<Candle>, AtlasError> {
+        if self.backend.is_some() {
+            let resp = self
+                .market_info(serde_json::json!({
+                    "type": "candleSnapshot",
+                    "req": {
+                        "coin": symbol,
+                        "interval": tf.as_str(),
+                        "startTime": start,
+                        "endTime": end,
+                    },
+                }))
+                .await?;
+            return proxy::parse_candles(resp);
         }
-        Ok(result)
+        self.api.candle_snapshot(symbol, tf, start, end).await
+    }
+
+    /// POST a market-data `/info` request, through the backend proxy when
+    /// one is configured.
+    async fn market_info(&self, body: Value) -> Result<Value, AtlasError> {
+        let Some(backend) = &self.backend else {
+            return self.api.info(&body).await;
+        };
+        let (route, query) = proxy::proxy_route(&body)
+            .ok_or_else(|| AtlasError::ProxyOnly(PROXY_ONLY_MESSAGE.into()))?;
+        let query: Vec<(&str, &str)> = query
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        backend.get(&route, &query).await
+    }
+
+    /// Fetch asset contexts (funding, OI, impact prices, volume, etc.) via metaAndAssetCtxs.
+    async fn fetch_asset_ctxs(&self) -> Result<Vec<AssetCtx>, AtlasError> {
+        let resp = self
+            .market_info(serde_json::json!({"type": "metaAndAssetCtxs"}))
+            .await?;
+        parse_meta_and_asset_ctxs(resp)
     }
 
     /// Build a rich Ticker from asset context data.
-    fn ctx_to_ticker(ctx: &AssetCtxRaw) -> Ticker {
+    fn ctx_to_ticker(ctx: &AssetCtx) -> Ticker {
         let mid = ctx.mid_px.unwrap_or(Decimal::ZERO);
         let change_pct = ctx.prev_day_px.and_then(|prev| {
             if prev.is_zero() {
@@ -281,15 +471,18 @@ impl HyperliquidModule {
             symbol: ctx.name.clone(),
             protocol: Protocol::Hyperliquid,
             mid_price: mid,
-            best_bid: ctx.impact_bid,
-            best_ask: ctx.impact_ask,
-            volume_24h: ctx.volume,
+            best_bid: ctx.impact_bid(),
+            best_ask: ctx.impact_ask(),
+            volume_24h: ctx.day_ntl_vlm,
             change_24h_pct: change_pct,
         }
     }
 
     /// Get signer, or error if read-only.
     fn require_signer(&self) -> Result<&PrivateKeySigner, AtlasError> {
+        if self.backend.is_some() {
+            return Err(AtlasError::ProxyOnly(PROXY_ONLY_MESSAGE.into()));
+        }
         self.signer.as_ref().ok_or_else(|| AtlasError::Auth(
             "No signer available — this command requires authentication. Run: atlas profile generate <name>".into()
         ))
@@ -297,6 +490,9 @@ impl HyperliquidModule {
 
     /// Get address, or error if read-only.
     fn require_address(&self) -> Result<Address, AtlasError> {
+        if self.backend.is_some() {
+            return Err(AtlasError::ProxyOnly(PROXY_ONLY_MESSAGE.into()));
+        }
         self.address.ok_or_else(|| {
             AtlasError::Auth(
                 "No wallet address — authenticate first with: atlas profile generate <name>".into(),
@@ -304,21 +500,28 @@ impl HyperliquidModule {
         })
     }
 
+    /// Resolve user input (any case, alias, `1000X`) to the listed perp name.
+    /// Spot keys (`@107`, `PURR/USDC`) pass through unchanged.
+    fn resolve_name<'a>(&'a self, coin: &'a str) -> Result<&'a str, AtlasError> {
+        if is_spot_key(coin) {
+            return Ok(coin);
+        }
+        self.resolver
+            .resolve(coin, self.perps.iter().map(|m| m.name.as_str()))
+    }
+
     /// Resolve coin name to market index.
     fn resolve_asset(&self, coin: &str) -> Result<usize, AtlasError> {
-        self.perps
-            .iter()
-            .find(|m| m.name.eq_ignore_ascii_case(coin))
-            .map(|m| m.index)
-            .ok_or_else(|| AtlasError::AssetNotFound(coin.to_string()))
+        self.get_market(coin).map(|m| m.index)
     }
 
-    /// Get PerpMarket for a coin.
-    fn get_market(&self, coin: &str) -> Result<&PerpMarket, AtlasError> {
+    /// Get market metadata for a coin.
+    fn get_market(&self, coin: &str) -> Result<&MarketMeta, AtlasError> {
+        let name = self.resolve_name(coin)?;
         self.perps
             .iter()
-            .find(|m| m.name.eq_ignore_ascii_case(coin))
-            .ok_or_else(|| AtlasError::AssetNotFound(coin.to_string()))
+            .find(|m| m.name == name)
+            .ok_or_else(|| AtlasError::asset_not_found(coin))
     }
 
     /// Round price to valid tick.
@@ -329,11 +532,56 @@ impl HyperliquidModule {
             .ok_or_else(|| AtlasError::Other(format!("Invalid price {price} for {coin}")))
     }
 
+    /// The order protections for a market order or close priced off `mid`.
+    /// The price band is measured against the last 1m close; without one
+    /// (a fresh listing) only the slippage cap applies.
+    async fn check_protections(
+        &self,
+        symbol: &str,
+        side: &Side,
+        mid: Decimal,
+        slippage: f64,
+    ) -> AtlasResult<()> {
+        let Some(limits) = self
+            .protections
+            .as_ref()
+            .filter(|l| l.has_order_protections())
+        else {
+            return Ok(());
+        };
+        let reference = if limits.price_band.is_some() {
+            self.candles(symbol, "1m", 1)
+                .await?
+                .last()
+                .and_then(|c| c.close.to_f64())
+                .unwrap_or(0.0)
+        } else {
+            0.0
+        };
+        let mid = mid.to_f64().unwrap_or(0.0);
+        check_market_order(limits, symbol, side, mid, slippage, reference)
+    }
+
+    /// The risk limits for an order of `size` at `price`. Orders that only
+    /// reduce the open position pass.
+    async fn check_risk_limits(
+        &self,
+        symbol: &str,
+        side: &Side,
+        size: Decimal,
+        price: Decimal,
+    ) -> AtlasResult<()> {
+        let Some(limits) = &self.risk_limits else {
+            return Ok(());
+        };
+        let price = price.to_f64().unwrap_or(0.0);
+        check_order_risk(self, limits, symbol, side, size, price).await
+    }
+
     /// Round size to valid lot step.
     fn round_size(&self, coin: &str, size: Decimal) -> Result<Decimal, AtlasError> {
         let market = self.get_market(coin)?;
-        let dp = market.sz_decimals.max(0) as u32;
-        Ok(size.round_dp(dp))
+        Ok(size.round_dp(market.sz_decimals))
     }
 
     /// Chain identifier for signing.
@@ -354,14 +602,185 @@ impl HyperliquidModule {
         }
     }
 
+    /// Spot pairs from `spotMeta`, for mapping `@N` keys to pair names.
+    async fn spot_pairs(&self) -> Result<Vec<SpotPair>, AtlasError> {
+        self.api.spot().await
+    }
+
+    /// Resolve spot input (`HYPE`, `HYPE/USDC`, `@107`) to its listing.
+    async fn resolve_spot_market(&self, input: &str) -> Result<SpotMarket, AtlasError> {
+        let markets: Vec<SpotMarket> = self
+            .spot_pairs()
+            .await?
+            .iter()
+            .map(|p| p.to_market(None))
+            .collect();
+        resolve_spot(&markets, input).cloned()
+    }
+
+    /// Asset index used to place and cancel orders on `base`'s spot market.
+    async fn spot_asset(&self, base: &str) -> Result<usize, AtlasError> {
+        let market = self.resolve_spot_market(base).await?;
+        let spot_markets = self
+            .http
+            .info("spotMeta", || self.client.spot())
+            .await
+            .map_err(|e| AtlasError::Network(format!("Fetch spot markets: {e}")))?;
+        spot_markets
+            .iter()
+            .find(|m| {
+                m.tokens.first().is_some_and(|t| t.name == market.base)
+                    && m.tokens.get(1).is_some_and(|t| t.name == market.quote)
+            })
+            .map(|m| m.index)
+            .ok_or_else(|| AtlasError::asset_not_found(format!("Spot: {base}")))
+    }
+
+    /// Current mid for a spot market, read under its exchange key.
+    async fn spot_mid(&self, market: &SpotMarket) -> Result<Decimal, AtlasError> {
+        self.api
+            .all_mids()
+            .await?
+            .get(&market.key)
+            .copied()
+            .ok_or_else(|| AtlasError::Other(format!("No mid price for spot {}", market.pair)))
+    }
+
+    /// Place a spot order on `base`. With a limit price the order rests
+    /// (GTC); without one it crosses the book as an IOC at mid ± slippage.
+    /// Spot orders carry no builder fee.
+    async fn place_spot_order(
+        &self,
+        base: &str,
+        side: Side,
+        size: Decimal,
+        limit_px: Option<Decimal>,
+        slippage: Option<f64>,
+    ) -> AtlasResult<OrderResult> {
+        let listing = self.resolve_spot_market(base).await?;
+        let spot_markets = self
+            .http
+            .info("spotMeta", || self.client.spot())
+            .await
+            .map_err(|e| AtlasError::Network(format!("Fetch spot markets: {e}")))?;
+        let market = spot_markets
+            .iter()
+            .find(|m| {
+                m.tokens.first().is_some_and(|t| t.name == listing.base)
+                    && m.tokens.get(1).is_some_and(|t| t.name == listing.quote)
+            })
+            .ok_or_else(|| AtlasError::asset_not_found(format!("Spot: {base}")))?;
+
+        let is_buy = side_to_is_buy(&side);
+        let (raw_px, tif, kind) = match limit_px {
+            Some(px) => (px, TimeInForce::Gtc, "spot_limit"),
+            None => {
+                let slip = slippage.unwrap_or(0.05);
+                let mid = self.spot_mid(&listing).await?;
+                let slip_dec = Decimal::from_f64(slip)
+                    .ok_or_else(|| AtlasError::Other("Invalid slippage".into()))?;
+                let mult = if is_buy {
+                    Decimal::ONE + slip_dec
+                } else {
+                    Decimal::ONE - slip_dec
+                };
+                (mid * mult, TimeInForce::Ioc, "spot_market")
+            }
+        };
+        let px = round_spot_price(raw_px, listing.sz_decimals)
+            .ok_or_else(|| AtlasError::Other("Cannot round spot price".to_string()))?;
+
+        let sz = size.round_dp(listing.sz_decimals);
+        if sz.is_zero() {
+            return Err(AtlasError::Other("Spot order size rounds to zero".into()));
+        }
+
+        let cloid = random_cloid();
+        let order = OrderRequest {
+            asset: market.index,
+            is_buy,
+            reduce_only: false,
+            limit_px: px,
+            sz,
+            cloid,
+            order_type: OrderTypePlacement::Limit { tif },
+        };
+
+        let batch = BatchOrder {
+            orders: vec![order],
+            grouping: OrderGrouping::Na,
+        };
+        let signer = self.require_signer()?;
+        let nonce = self.nonce.next();
+        let request = serde_json::json!({
+            "action": {
+                "type": "order",
+                "orders": [{
+                    "a": market.index, "b": is_buy, "p": px.to_string(), "s": sz.to_string(),
+                    "r": false, "t": {"limit": {"tif": format!("{tif:?}")}},
+                    "c": format!("{cloid:?}"),
+                }],
+                "grouping": "na",
+            },
+            "nonce": nonce,
+        });
+        let audit_id = self.audit_begin("order", Some(base), &request)?;
+        let result = self
+            .client
+            .place(signer, batch, nonce, None, None)
+            .await
+            .map_err(|e| AtlasError::Protocol {
+                protocol: "hyperliquid".into(),
+                message: format!("Spot order failed: {}", e.message()),
+            })
+            .and_then(|statuses| {
+                self.parse_response(&statuses, base, side.clone(), None, Some(audit_id))
+            });
+        self.audit_sdk(audit_id, &result);
+        log_order(kind, base, &side, sz, px, &cloid, &result);
+        result
+    }
+
     /// Place a batch order with builder fee injection.
+    ///
+    /// If the exchange rejects the nonce (another process got there first),
+    /// the order is re-signed once with a fresh one; a rejected action never
+    /// executed, so this can't double an order.
+    ///
+    /// Returns the audit entry of the request that was answered with the
+    /// statuses.
     async fn place_with_builder(
         &self,
         batch: BatchOrder,
-    ) -> Result<Vec<OrderResponseStatus>, AtlasError> {
-        let nonce = self.nonce.next();
+        coin: &str,
+    ) -> Result<(i64, Vec<OrderResponseStatus>), AtlasError> {
+        let cloids: Vec<Cloid> = batch.orders.iter().map(|o| o.cloid).collect();
         let action: Action = batch.into();
+        match self.submit_order_action(&action, &cloids, coin).await {
+            Err(AtlasError::OrderRejected { message, .. }) if is_nonce_rejection(&message) => {
+                warn!(error = %message, "Exchange rejected the nonce, re-signing once");
+                self.submit_order_action(&action, &cloids, coin).await
+            }
+            result => result,
+        }
+    }
+
+    /// Sign `action` with the next nonce and submit it.
+    ///
+    /// The action is signed once, so every retry resends the same nonce and
+    /// cloids — the exchange can never execute it twice. When an attempt ends
+    /// ambiguously (timeout after send, 5xx), the cloids are looked up first
+    /// and a landed order is returned instead of resubmitting. One audit
+    /// entry covers every attempt.
+    async fn submit_order_action(
+        &self,
+        action: &Action,
+        cloids: &[Cloid],
+        coin: &str,
+    ) -> Result<(i64, Vec<OrderResponseStatus>), AtlasError> {
+        let nonce = self.nonce.next();
         let signed = action
+            .clone()
             .sign_sync(self.require_signer()?, nonce, None, None, self.chain())
             .map_err(|e| AtlasError::Protocol {
                 protocol: "hyperliquid".into(),
@@ -372,68 +791,307 @@ impl HyperliquidModule {
             .map_err(|e| AtlasError::Other(format!("Serialize failed: {e}")))?;
 
         // Inject builder fee
-        let builder = BuilderFee::default();
-        if let Some(action_obj) = json_val.get_mut("action") {
-            action_obj["builder"] =
-                serde_json::to_value(&builder).map_err(|e| AtlasError::Other(e.to_string()))?;
-        }
+        BuilderFee::inject(self.builder.as_ref(), &mut json_val)
+            .map_err(|e| AtlasError::Other(e.to_string()))?;
+        let audit_id = self.audit_begin("order", Some(coin), &json_val)?;
+
+        let mut attempt = 0;
+        let body = loop {
+            match self.api.exchange(&json_val).await {
+                Ok(body) => break body,
+                Err(failure) => {
+                    if matches!(failure, HttpFailure::Ambiguous(_)) {
+                        let recovered = match self.recover_by_cloid(cloids).await {
+                            Ok(recovered) => recovered,
+                            Err(err) => {
+                                self.audit
+                                    .complete(audit_id, &AuditOutcome::Error(err.to_string()));
+                                return Err(err);
+                            }
+                        };
+                        if let Some(statuses) = recovered {
+                            info!(attempt, "Order landed despite failed response");
+                            let recovered = serde_json::json!({
+                                "status": "ok",
+                                "recovered_by_cloid": format!("{statuses:?}"),
+                            });
+                            self.audit
+                                .complete(audit_id, &AuditOutcome::Response(recovered.to_string()));
+                            return Ok((audit_id, statuses));
+                        }
+                    }
+                    if !failure.is_retryable() || attempt >= self.http.max_retries() {
+                        let err = failure.into_error("Exchange request failed");
+                        self.audit
+                            .complete(audit_id, &AuditOutcome::Error(err.to_string()));
+                        return Err(err);
+                    }
+                    attempt += 1;
+                    warn!(attempt, ?failure, "Order submission failed, resubmitting");
+                    tokio::time::sleep(self.http.backoff(attempt)).await;
+                }
+            }
+        };
+        self.audit
+            .complete(audit_id, &AuditOutcome::Response(body.clone()));
 
-        let http = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .map_err(|e| AtlasError::Network(e.to_string()))?;
+        parse_order_statuses(&body).map(|statuses| (audit_id, statuses))
+    }
 
-        let resp = http
-            .post(format!("{}/exchange", self.base_url()))
-            .json(&json_val)
-            .send()
-            .await
-            .map_err(|e| AtlasError::Network(format!("Exchange request failed: {e}")))?;
+    /// Sign `action` with the next nonce and POST it to `/exchange` once,
+    /// without a builder fee, recorded in the audit log as `action_type`.
+    /// Errors (prefixed with `context`) if the exchange answers `status: err`.
+    async fn send_action(
+        &self,
+        action: Action,
+        action_type: &str,
+        coin: Option<&str>,
+        context: &str,
+    ) -> Result<Value, AtlasError> {
+        let signed = action
+            .sign_sync(
+                self.require_signer()?,
+                self.nonce.next(),
+                None,
+                None,
+                self.chain(),
+            )
+            .map_err(|e| AtlasError::Auth(format!("Sign failed: {e}")))?;
+        let request = serde_json::to_value(&signed)
+            .map_err(|e| AtlasError::Other(format!("Serialize failed: {e}")))?;
+        let audit_id = self.audit_begin(action_type, coin, &request)?;
 
-        let status = resp.status();
-        let body = resp
-            .text()
+        let body = self
+            .api
+            .exchange(&request)
             .await
-            .map_err(|e| AtlasError::Network(e.to_string()))?;
-
-        if !status.is_success() {
-            return Err(AtlasError::Protocol {
-                protocol: "hyperliquid".into(),
-                message: format!("HTTP {status}: {body}"),
-            });
-        }
-
+            .map_err(|failure| failure.into_error(context));
+        self.audit_complete(audit_id, &body);
+        let body = body?;
         let parsed: Value = serde_json::from_str(&body).map_err(|_| AtlasError::Protocol {
             protocol: "hyperliquid".into(),
             message: format!("Bad response: {body}"),
         })?;
-
         if parsed.get("status").and_then(|v| v.as_str()) == Some("err") {
             let msg = parsed
                 .get("response")
                 .and_then(|v| v.as_str())
                 .unwrap_or("Unknown error");
-            return Err(AtlasError::OrderRejected(msg.to_string()));
+            return Err(AtlasError::Protocol {
+                protocol: "hyperliquid".into(),
+                message: format!("{context}: {msg}"),
+            });
         }
+        Ok(parsed)
+    }
 
-        let statuses_val =
-            parsed
-                .pointer("/response/data/statuses")
-                .ok_or_else(|| AtlasError::Protocol {
-                    protocol: "hyperliquid".into(),
-                    message: format!("No statuses: {body}"),
-                })?;
+    /// Sign an action hypersdk has no type for (msgpack hash + Agent
+    /// signature) and wrap it in an `/exchange` request body.
+    fn sign_l1_action<T: Serialize>(&self, action: &T) -> Result<Value, AtlasError> {
+        let nonce = self.nonce.next();
+        let mut rmp_bytes = rmp_serde::to_vec_named(action)
+            .map_err(|e| AtlasError::Other(format!("RMP serialize: {e}")))?;
+        rmp_bytes.extend(nonce.to_be_bytes());
+        rmp_bytes.push(0u8);
 
-        serde_json::from_value(statuses_val.clone())
-            .map_err(|e| AtlasError::Other(format!("Parse statuses: {e}")))
+        let connection_id = alloy::primitives::keccak256(&rmp_bytes);
+        let source = if self.testnet { "b" } else { "a" };
+        let agent_hash = compute_agent_signing_hash(source, connection_id);
+
+        let sig = self
+            .require_signer()?
+            .sign_hash_sync(&agent_hash)
+            .map_err(|e| AtlasError::Auth(format!("Sign failed: {e}")))?;
+
+        let r_hex = hex::encode(sig.r().to_be_bytes::<32>());
+        let s_hex = hex::encode(sig.s().to_be_bytes::<32>());
+        let v = if sig.v() { 28u8 } else { 27u8 };
+
+        Ok(serde_json::json!({
+            "action": action,
+            "nonce": nonce,
+            "signature": { "r": format!("0x{r_hex}"), "s": format!("0x{s_hex}"), "v": v },
+            "vaultAddress": null
+        }))
+    }
+
+    /// Write the audit entry for a signed `request` before it is sent.
+    /// Fails closed: on error the request must not go out.
+    fn audit_begin(
+        &self,
+        action_type: &str,
+        coin: Option<&str>,
+        request: &Value,
+    ) -> Result<i64, AtlasError> {
+        self.audit.begin(&SignedAction {
+            protocol: "hyperliquid",
+            action_type,
+            coin,
+            nonce: request.get("nonce").and_then(Value::as_u64),
+            signer: format!("{:?}", self.require_signer()?.address()),
+            request,
+        })
+    }
+
+    /// Record the raw answer (or failure) of a POST audited as `audit_id`.
+    fn audit_complete(&self, audit_id: i64, body: &Result<String, AtlasError>) {
+        let outcome = match body {
+            Ok(body) => AuditOutcome::Response(body.clone()),
+            Err(e) => AuditOutcome::Error(e.to_string()),
+        };
+        self.audit.complete(audit_id, &outcome);
+    }
+
+    /// Record the result of a call hypersdk signed and sent itself; only
+    /// its parsed result or error text is visible.
+    fn audit_sdk<T: std::fmt::Debug>(&self, audit_id: i64, result: &Result<T, AtlasError>) {
+        let outcome = match result {
+            Ok(value) => AuditOutcome::Response(
+                serde_json::json!({"status": "ok", "response": format!("{value:?}")}).to_string(),
+            ),
+            Err(e @ AtlasError::OrderRejected { .. }) => AuditOutcome::Response(
+                serde_json::json!({"status": "err", "response": e.to_string()}).to_string(),
+            ),
+            Err(e) => AuditOutcome::Error(e.to_string()),
+        };
+        self.audit.complete(audit_id, &outcome);
+    }
+
+    /// Raw `orderStatus` response for an oid (number) or cloid (hex string).
+    async fn order_status_raw(&self, oid: Value) -> AtlasResult<Value> {
+        let user = self.require_address()?;
+        self.api
+            .info(&serde_json::json!({
+                "type": "orderStatus",
+                "user": format!("{user:?}"),
+                "oid": oid,
+            }))
+            .await
+    }
+
+    /// Look up an order by client order ID (`None` = the exchange never saw it).
+    pub async fn order_status_by_cloid(
+        &self,
+        cloid: &Cloid,
+    ) -> AtlasResult<Option<CloidOrderStatus>> {
+        let resp = self
+            .order_status_raw(serde_json::json!(format!("{cloid}")))
+            .await?;
+
+        if resp.get("status").and_then(|v| v.as_str()) != Some("order") {
+            return Ok(None);
+        }
+        let dec = |v: Option<&Value>| {
+            v.and_then(|v| v.as_str())
+                .and_then(|s| Decimal::from_str(s).ok())
+                .unwrap_or_default()
+        };
+        let order = resp.pointer("/order/order");
+        Ok(Some(CloidOrderStatus {
+            oid: order
+                .and_then(|o| o.get("oid"))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0),
+            status: resp
+                .pointer("/order/status")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            orig_size: dec(order.and_then(|o| o.get("origSz"))),
+            size: dec(order.and_then(|o| o.get("sz"))),
+            limit_price: dec(order.and_then(|o| o.get("limitPx"))),
+        }))
+    }
+
+    /// After an ambiguous submit, rebuild the response from cloid lookups.
+    ///
+    /// `None` if none of the orders reached the exchange (safe to resend).
+    async fn recover_by_cloid(
+        &self,
+        cloids: &[Cloid],
+    ) -> AtlasResult<Option<Vec<OrderResponseStatus>>> {
+        let mut found = Vec::with_capacity(cloids.len());
+        for cloid in cloids {
+            found.push(self.order_status_by_cloid(cloid).await?);
+        }
+        if found.iter().all(Option::is_none) {
+            return Ok(None);
+        }
+
+        let mut statuses = Vec::with_capacity(found.len());
+        for status in found {
+            let raw = match status {
+                Some(s) if s.status == "filled" => {
+                    let filled = s.orig_size - s.size;
+                    let avg_px = self
+                        .fill_vwap(s.oid)
+                        .await
+                        .ok()
+                        .flatten()
+                        .unwrap_or(s.limit_price);
+                    serde_json::json!({"filled": {
+                        "totalSz": filled.to_string(),
+                        "avgPx": avg_px.to_string(),
+                        "oid": s.oid,
+                    }})
+                }
+                Some(s) if s.status == "open" => serde_json::json!({"resting": {"oid": s.oid}}),
+                Some(s) => serde_json::json!({"error": format!("Order {}: {}", s.oid, s.status)}),
+                None => serde_json::json!({"error": "Order not found after ambiguous submit"}),
+            };
+            statuses.push(
+                serde_json::from_value(raw)
+                    .map_err(|e| AtlasError::Other(format!("Parse statuses: {e}")))?,
+            );
+        }
+        Ok(Some(statuses))
+    }
+
+    /// Volume-weighted fill price for an order, from recent user fills.
+    async fn fill_vwap(&self, oid: u64) -> AtlasResult<Option<Decimal>> {
+        let user = self.require_address()?;
+        let fills = self
+            .api
+            .info(&serde_json::json!({"type": "userFills", "user": format!("{user:?}")}))
+            .await?;
+
+        let (mut notional, mut size) = (Decimal::ZERO, Decimal::ZERO);
+        for f in fills.as_array().into_iter().flatten() {
+            if f.get("oid").and_then(|v| v.as_u64()) != Some(oid) {
+                continue;
+            }
+            let px = f
+                .get("px")
+                .and_then(|v| v.as_str())
+                .and_then(|s| Decimal::from_str(s).ok());
+            let sz = f
+                .get("sz")
+                .and_then(|v| v.as_str())
+                .and_then(|s| Decimal::from_str(s).ok());
+            if let (Some(px), Some(sz)) = (px, sz) {
+                notional += px * sz;
+                size += sz;
+            }
+        }
+        Ok((!size.is_zero()).then(|| notional / size))
+    }
+
+    /// Builder fee in bps attached to perp orders; `None` when disabled.
+    fn builder_fee_bps(&self) -> Option<u16> {
+        self.builder.as_ref().map(|b| b.f)
     }
 
     /// Parse SDK order response to universal OrderResult.
+    ///
+    /// `builder_fee_bps` is the fee injected into this order, if any;
+    /// `audit_id` the audit entry of the request.
     fn parse_response(
         &self,
         statuses: &[OrderResponseStatus],
         symbol: &str,
         side: Side,
+        builder_fee_bps: Option<u16>,
+        audit_id: Option<i64>,
     ) -> AtlasResult<OrderResult> {
         if statuses.is_empty() {
             return Err(AtlasError::Other("Empty response".into()));
@@ -459,7 +1117,9 @@ impl HyperliquidModule {
                 avg_price: Some(*avg_px),
                 fee: None,
                 timestamp: now_ms,
+                builder_fee_bps,
                 message: None,
+                audit_id,
             }),
             OrderResponseStatus::Resting { oid, .. } => Ok(OrderResult {
                 protocol: Protocol::Hyperliquid,
@@ -471,7 +1131,9 @@ impl HyperliquidModule {
                 avg_price: None,
                 fee: None,
                 timestamp: now_ms,
+                builder_fee_bps,
                 message: None,
+                audit_id,
             }),
             OrderResponseStatus::Success => Ok(OrderResult {
                 protocol: Protocol::Hyperliquid,
@@ -483,9 +1145,11 @@ impl HyperliquidModule {
                 avg_price: None,
                 fee: None,
                 timestamp: now_ms,
+                builder_fee_bps,
                 message: Some("accepted".into()),
+                audit_id,
             }),
-            OrderResponseStatus::Error(msg) => Err(AtlasError::OrderRejected(msg.clone())),
+            OrderResponseStatus::Error(msg) => Err(AtlasError::order_rejected(msg.clone())),
         }
     }
 }
@@ -496,16 +1160,21 @@ impl PerpModule for HyperliquidModule {
         Protocol::Hyperliquid
     }
 
+    fn resolve_symbol(&self, input: &str) -> AtlasResult<String> {
+        self.resolve_name(input).map(str::to_string)
+    }
+
     async fn markets(&self) -> AtlasResult<Vec<Market>> {
         Ok(self.perps.iter().map(perp_market_to_universal).collect())
     }
 
     async fn ticker(&self, symbol: &str) -> AtlasResult<Ticker> {
+        let symbol = self.resolve_name(symbol)?;
         let ctxs = self.fetch_asset_ctxs().await?;
         let ctx = ctxs
             .iter()
             .find(|c| c.name.eq_ignore_ascii_case(symbol))
-            .ok_or_else(|| AtlasError::AssetNotFound(symbol.to_string()))?;
+            .ok_or_else(|| AtlasError::asset_not_found(symbol))?;
         Ok(Self::ctx_to_ticker(ctx))
     }
 
@@ -520,70 +1189,69 @@ impl PerpModule for HyperliquidModule {
         Ok(tickers)
     }
 
+    async fn asset_contexts(&self) -> AtlasResult<Vec<AssetContext>> {
+        let ctxs = self.fetch_asset_ctxs().await?;
+        Ok(ctxs
+            .iter()
+            .filter(|c| !c.name.is_empty())
+            .map(AssetCtx::to_universal)
+            .collect())
+    }
+
     async fn candles(
         &self,
         symbol: &str,
         interval: &str,
         limit: usize,
     ) -> AtlasResult<Vec<Candle>> {
-        let ci = parse_interval(interval)?;
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64;
-        let start = now_ms.saturating_sub(interval_to_ms(&ci) * limit as u64);
-
-        let raw = self
-            .client
-            .candle_snapshot(symbol, ci, start, now_ms)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch candles: {e}")))?;
-
-        Ok(raw
-            .iter()
-            .map(|c| Candle {
-                open_time_ms: c.open_time,
-                open: c.open,
-                high: c.high,
-                low: c.low,
-                close: c.close,
-                volume: c.volume,
-                trades: Some(c.num_trades),
-            })
-            .collect())
+        let symbol = self.resolve_name(symbol)?;
+        let tf: Timeframe = interval.parse()?;
+        let now_ms = now_ms() as u64;
+        let page = |start, end| self.candle_page(symbol, tf, start, end);
+
+        match &self.candle_cache {
+            Some(cache) if CandleCache::is_cacheable(tf) => {
+                let source = if self.testnet {
+                    "hyperliquid-testnet"
+                } else {
+                    "hyperliquid"
+                };
+                cache
+                    .candles(source, symbol, tf, limit, now_ms, |start, end| {
+                        fetch_range(tf.duration_ms(), start, end, page)
+                    })
+                    .await
+            }
+            _ => fetch_paginated(symbol, tf.duration_ms(), limit, now_ms, page).await,
+        }
     }
 
     async fn funding(&self, symbol: &str) -> AtlasResult<Vec<FundingRate>> {
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64;
+        let symbol = self.resolve_name(symbol)?;
+        let now_ms = now_ms() as u64;
         let start = now_ms.saturating_sub(7 * 86_400_000);
 
-        let rates = self
-            .client
-            .funding_history(symbol, start, Some(now_ms))
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch funding: {e}")))?;
+        if self.backend.is_some() {
+            let resp = self
+                .market_info(serde_json::json!({
+                    "type": "fundingHistory",
+                    "coin": symbol,
+                    "startTime": start,
+                    "endTime": now_ms,
+                }))
+                .await?;
+            return proxy::parse_funding_history(resp);
+        }
 
-        Ok(rates
-            .iter()
-            .map(|r| FundingRate {
-                symbol: r.coin.clone(),
-                protocol: Protocol::Hyperliquid,
-                rate: r.funding_rate,
-                premium: Some(r.premium),
-                timestamp_ms: r.time,
-                next_funding_ms: None,
-            })
-            .collect())
+        self.api.funding_history(symbol, start, now_ms).await
     }
 
-    async fn orderbook(&self, _symbol: &str, _depth: usize) -> AtlasResult<OrderBook> {
-        // L2Book is WebSocket-only on Hyperliquid
-        Err(AtlasError::Other(
-            "Orderbook is WebSocket-only. Use `atlas stream book`.".into(),
-        ))
+    async fn orderbook(&self, symbol: &str, depth: usize) -> AtlasResult<OrderBook> {
+        let symbol = self.resolve_name(symbol)?;
+        let resp = self
+            .market_info(serde_json::json!({"type": "l2Book", "coin": symbol}))
+            .await?;
+        proxy::parse_l2_book(resp, depth)
     }
 
     async fn market_order(
@@ -593,18 +1261,17 @@ impl PerpModule for HyperliquidModule {
         size: Decimal,
         slippage: Option<f64>,
     ) -> AtlasResult<OrderResult> {
+        let symbol = self.resolve_name(symbol)?;
         let asset = self.resolve_asset(symbol)?;
         let is_buy = side_to_is_buy(&side);
         let slip = slippage.unwrap_or(0.05);
 
-        let mids = self
-            .client
-            .all_mids(None)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch mids: {e}")))?;
+        let mids = self.api.all_mids().await?;
         let mid = mids
             .get(symbol)
-            .ok_or_else(|| AtlasError::AssetNotFound(symbol.to_string()))?;
+            .ok_or_else(|| AtlasError::asset_not_found(symbol))?;
+        self.check_protections(symbol, &side, *mid, slip).await?;
+        self.check_risk_limits(symbol, &side, size, *mid).await?;
 
         let slip_dec =
             Decimal::from_f64(slip).ok_or_else(|| AtlasError::Other("Invalid slippage".into()))?;
@@ -624,17 +1291,18 @@ impl PerpModule for HyperliquidModule {
 
         info!(
             symbol, side = %side, %sz, %px, slippage = slip,
-            builder = BUILDER_ADDRESS_EVM, fee_bps = BUILDER_FEE_BPS,
+            builder = ?self.builder.as_ref().map(|b| &b.b), fee_bps = ?self.builder_fee_bps(),
             "HL market order with builder fee"
         );
 
+        let cloid = random_cloid();
         let order = OrderRequest {
             asset,
             is_buy,
             reduce_only: false,
             limit_px: px,
             sz,
-            cloid: random_cloid(),
+            cloid,
             order_type: OrderTypePlacement::Limit {
                 tif: TimeInForce::Ioc,
             },
@@ -644,8 +1312,20 @@ impl PerpModule for HyperliquidModule {
             orders: vec![order],
             grouping: OrderGrouping::Na,
         };
-        let statuses = self.place_with_builder(batch).await?;
-        self.parse_response(&statuses, symbol, side)
+        let result =
+            self.place_with_builder(batch, symbol)
+                .await
+                .and_then(|(audit_id, statuses)| {
+                    self.parse_response(
+                        &statuses,
+                        symbol,
+                        side.clone(),
+                        self.builder_fee_bps(),
+                        Some(audit_id),
+                    )
+                });
+        log_order("market", symbol, &side, sz, px, &cloid, &result);
+        result
     }
 
     async fn limit_order(
@@ -654,8 +1334,9 @@ impl PerpModule for HyperliquidModule {
         side: Side,
         size: Decimal,
         price: Decimal,
-        reduce_only: bool,
+        opts: &LimitOptions,
     ) -> AtlasResult<OrderResult> {
+        let symbol = self.resolve_name(symbol)?;
         let asset = self.resolve_asset(symbol)?;
         let is_buy = side_to_is_buy(&side);
         let px = self.round_price(symbol, price)?;
@@ -667,30 +1348,73 @@ impl PerpModule for HyperliquidModule {
             )));
         }
 
+        let reduce_only = opts.reduce_only;
+        if !reduce_only {
+            self.check_risk_limits(symbol, &side, sz, px).await?;
+        }
+        let order_type = match &opts.trigger {
+            None => OrderTypePlacement::Limit {
+                tif: sdk_tif(opts.tif),
+            },
+            Some(trigger) => {
+                let mids = self.api.all_mids().await?;
+                let mid = mids
+                    .get(symbol)
+                    .ok_or_else(|| AtlasError::asset_not_found(symbol))?;
+                // HL fires `sl` through the market in the order's direction, `tp` the other way
+                let tpsl =
+                    match check_trigger(symbol, side.clone(), trigger.price, *mid, reduce_only)? {
+                        TriggerRole::Stop => TpSl::Sl,
+                        TriggerRole::TakeProfit => TpSl::Tp,
+                    };
+                OrderTypePlacement::Trigger {
+                    is_market: trigger.kind == TriggerKind::Market,
+                    trigger_px: self.round_price(symbol, trigger.price)?,
+                    tpsl,
+                }
+            }
+        };
         info!(
-            symbol, side = %side, %sz, %px, reduce_only,
-            builder = BUILDER_ADDRESS_EVM, fee_bps = BUILDER_FEE_BPS,
+            symbol, side = %side, %sz, %px, reduce_only, tif = %opts.tif,
+            trigger = ?opts.trigger.as_ref().map(|t| (t.price, t.kind)),
+            builder = ?self.builder.as_ref().map(|b| &b.b), fee_bps = ?self.builder_fee_bps(),
             "HL limit order with builder fee"
         );
 
+        let cloid = order_cloid(opts.cloid.as_deref())?;
         let order = OrderRequest {
             asset,
             is_buy,
             reduce_only,
             limit_px: px,
             sz,
-            cloid: random_cloid(),
-            order_type: OrderTypePlacement::Limit {
-                tif: TimeInForce::Gtc,
-            },
+            cloid,
+            order_type,
         };
 
         let batch = BatchOrder {
             orders: vec![order],
             grouping: OrderGrouping::Na,
         };
-        let statuses = self.place_with_builder(batch).await?;
-        self.parse_response(&statuses, symbol, side)
+        let result =
+            self.place_with_builder(batch, symbol)
+                .await
+                .and_then(|(audit_id, statuses)| {
+                    self.parse_response(
+                        &statuses,
+                        symbol,
+                        side.clone(),
+                        self.builder_fee_bps(),
+                        Some(audit_id),
+                    )
+                });
+        let kind = if opts.trigger.is_some() {
+            "trigger"
+        } else {
+            "limit"
+        };
+        log_order(kind, symbol, &side, sz, px, &cloid, &result);
+        result
     }
 
     async fn close_position(
@@ -699,14 +1423,12 @@ impl PerpModule for HyperliquidModule {
         size: Option<Decimal>,
         slippage: Option<f64>,
     ) -> AtlasResult<OrderResult> {
+        let symbol = self.resolve_name(symbol)?;
         let asset = self.resolve_asset(symbol)?;
         let slip = slippage.unwrap_or(0.05);
 
-        let state = self
-            .client
-            .clearinghouse_state(self.require_address()?, None)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch state: {e}")))?;
+        let user = self.require_address()?;
+        let state = self.api.clearinghouse_state(user).await?;
 
         let position = state
             .asset_positions
@@ -729,14 +1451,13 @@ impl PerpModule for HyperliquidModule {
             None => self.round_size(symbol, pos_size.abs())?,
         };
 
-        let mids = self
-            .client
-            .all_mids(None)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch mids: {e}")))?;
+        let mids = self.api.all_mids().await?;
         let mid = mids
             .get(symbol)
-            .ok_or_else(|| AtlasError::AssetNotFound(symbol.to_string()))?;
+            .ok_or_else(|| AtlasError::asset_not_found(symbol))?;
+        let close_side = if is_buy { Side::Buy } else { Side::Sell };
+        self.check_protections(symbol, &close_side, *mid, slip)
+            .await?;
 
         let slip_dec =
             Decimal::from_f64(slip).ok_or_else(|| AtlasError::Other("Invalid slippage".into()))?;
@@ -747,13 +1468,14 @@ impl PerpModule for HyperliquidModule {
         };
         let px = self.round_price(symbol, *mid * mult)?;
 
+        let cloid = random_cloid();
         let order = OrderRequest {
             asset,
             is_buy,
             reduce_only: true,
             limit_px: px,
             sz: close_size,
-            cloid: random_cloid(),
+            cloid,
             order_type: OrderTypePlacement::Limit {
                 tif: TimeInForce::Ioc,
             },
@@ -763,14 +1485,32 @@ impl PerpModule for HyperliquidModule {
             orders: vec![order],
             grouping: OrderGrouping::Na,
         };
-        let statuses = self.place_with_builder(batch).await?;
-        {
-            let close_side = if is_buy { Side::Buy } else { Side::Sell };
-            self.parse_response(&statuses, symbol, close_side)
-        }
+        let result =
+            self.place_with_builder(batch, symbol)
+                .await
+                .and_then(|(audit_id, statuses)| {
+                    self.parse_response(
+                        &statuses,
+                        symbol,
+                        close_side.clone(),
+                        self.builder_fee_bps(),
+                        Some(audit_id),
+                    )
+                });
+        log_order(
+            "close",
+            symbol,
+            &close_side,
+            close_size,
+            px,
+            &cloid,
+            &result,
+        );
+        result
     }
 
     async fn cancel_order(&self, symbol: &str, order_id: &str) -> AtlasResult<()> {
+        let symbol = self.resolve_name(symbol)?;
         let asset = self.resolve_asset(symbol)?;
         let oid: u64 = order_id
             .parse()
@@ -779,23 +1519,23 @@ impl PerpModule for HyperliquidModule {
         let batch = BatchCancel {
             cancels: vec![Cancel { asset, oid }],
         };
-        self.client
-            .cancel(self.require_signer()?, batch, self.nonce.next(), None, None)
-            .await
-            .map_err(|e| AtlasError::Protocol {
-                protocol: "hyperliquid".into(),
-                message: format!("Cancel failed: {}", e.message()),
-            })?;
+        self.send_action(batch.into(), "cancel", Some(symbol), "Cancel failed")
+            .await?;
+        info!(
+            event = "cancel",
+            coin = symbol,
+            oid,
+            status = "cancelled",
+            "order cancelled"
+        );
         Ok(())
     }
 
     async fn cancel_all(&self, symbol: &str) -> AtlasResult<u32> {
+        let symbol = self.resolve_name(symbol)?;
         let asset = self.resolve_asset(symbol)?;
-        let orders = self
-            .client
-            .open_orders(self.require_address()?, None)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch orders: {e}")))?;
+        let user = self.require_address()?;
+        let orders = self.api.open_orders(user).await?;
 
         let matching: Vec<_> = orders
             .iter()
@@ -813,44 +1553,55 @@ impl PerpModule for HyperliquidModule {
         let total = cancels.len() as u32;
 
         let batch = BatchCancel { cancels };
-        let _ = self
-            .client
-            .cancel(self.require_signer()?, batch, self.nonce.next(), None, None)
+        let outcome = self
+            .send_action(batch.into(), "cancel", Some(symbol), "Cancel failed")
             .await;
+        match outcome {
+            // Never sent: not a partial success
+            Err(e @ AtlasError::AuditLog(_)) => return Err(e),
+            Ok(_) => info!(
+                event = "cancel",
+                coin = symbol,
+                count = total,
+                status = "cancelled",
+                "cancel all"
+            ),
+            Err(e) => warn!(
+                event = "cancel", coin = symbol, count = total, status = "failed",
+                error = %e, "cancel all failed"
+            ),
+        }
 
         Ok(total)
     }
 
     async fn open_orders(&self) -> AtlasResult<Vec<Order>> {
-        let orders = self
-            .client
-            .open_orders(self.require_address()?, None)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch orders: {e}")))?;
-
-        Ok(orders
-            .iter()
-            .map(|o| Order {
-                protocol: Protocol::Hyperliquid,
-                symbol: o.coin.clone(),
-                side: convert_side(&o.side),
-                order_type: OrderType::Limit,
-                size: o.sz,
-                price: Some(o.limit_px),
-                filled_size: None,
-                status: OrderStatus::Open,
-                order_id: o.oid.to_string(),
-                timestamp_ms: o.timestamp,
-            })
-            .collect())
+        let user = self.require_address()?;
+        // The frontend variant carries trigger prices and order types
+        let resp = self
+            .api
+            .info(&serde_json::json!({
+                "type": "frontendOpenOrders",
+                "user": format!("{user:?}"),
+            }))
+            .await?;
+        let mut orders = parse_open_orders(&resp)?;
+
+        // Spot orders come back keyed `@N`; name them only when there are any.
+        if orders.iter().any(|o| o.market_type == MarketType::Spot) {
+            let pairs = self.spot_pairs().await.unwrap_or_default();
+            for order in &mut orders {
+                if let Some(pair) = pairs.iter().find(|p| p.key == order.symbol) {
+                    order.symbol = pair.display();
+                }
+            }
+        }
+        Ok(orders)
     }
 
     async fn positions(&self) -> AtlasResult<Vec<Position>> {
-        let state = self
-            .client
-            .clearinghouse_state(self.require_address()?, None)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch state: {e}")))?;
+        let user = self.require_address()?;
+        let state = self.api.clearinghouse_state(user).await?;
 
         Ok(state
             .asset_positions
@@ -866,46 +1617,105 @@ impl PerpModule for HyperliquidModule {
                     entry_price: p.entry_px,
                     mark_price: None,
                     unrealized_pnl: Some(p.unrealized_pnl),
-                    leverage: Some(p.leverage.value.to_u32().unwrap_or(1)),
+                    leverage: Some(p.leverage.value),
                     margin: None,
                     liquidation_price: p.liquidation_px,
-                    margin_mode: Some(format!("{:?}", p.leverage.leverage_type).to_lowercase()),
+                    margin_mode: Some(p.leverage.kind.clone()),
                 }
             })
             .collect())
     }
 
-    async fn fills(&self) -> AtlasResult<Vec<Fill>> {
-        let fills = self
-            .client
-            .user_fills(self.require_address()?)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch fills: {e}")))?;
+    async fn margin_state(&self) -> AtlasResult<MarginState> {
+        let user = self.require_address()?;
+        let (state, tiers) =
+            tokio::try_join!(self.api.clearinghouse_state(user), self.api.margin_tiers())?;
 
-        Ok(fills
+        let positions = state
+            .asset_positions
             .iter()
-            .take(50)
-            .map(|f| Fill {
-                protocol: Protocol::Hyperliquid,
-                symbol: f.coin.clone(),
-                side: convert_side(&f.side),
-                price: f.px,
-                size: f.sz,
-                fee: f.fee,
-                realized_pnl: Some(f.closed_pnl),
-                order_id: f.oid.to_string(),
-                tx_hash: Some(f.hash.clone()),
-                timestamp_ms: f.time,
+            .filter(|ap| !ap.position.szi.is_zero())
+            .map(|ap| {
+                let p = &ap.position;
+                let mark_price = match p.position_value {
+                    Some(value) => value / p.szi.abs(),
+                    None => p.entry_px.unwrap_or_default(),
+                };
+                let mode = if p.leverage.kind == "isolated" {
+                    let equity = match p.leverage.raw_usd {
+                        Some(raw) => raw + p.szi * mark_price,
+                        None => p.margin_used.unwrap_or_default(),
+                    };
+                    MarginMode::Isolated { equity }
+                } else {
+                    MarginMode::Cross
+                };
+                MarginPosition {
+                    coin: p.coin.clone(),
+                    size: p.szi,
+                    mark_price,
+                    leverage: p.leverage.value,
+                    mode,
+                }
             })
-            .collect())
+            .collect();
+
+        Ok(MarginState {
+            cross_account_value: state
+                .cross_margin_summary
+                .unwrap_or(state.margin_summary)
+                .account_value,
+            positions,
+            tiers,
+        })
+    }
+
+    async fn fills(&self) -> AtlasResult<Vec<Fill>> {
+        let user = self.require_address()?;
+        let mut fills = self.api.user_fills(user).await?;
+        fills.truncate(50);
+        Ok(fills)
+    }
+
+    async fn fills_query(&self, query: &FillQuery) -> AtlasResult<Vec<Fill>> {
+        let user = format!("{:?}", self.require_address()?);
+        // No coin parameter on either endpoint: the coin is filtered here
+        if !query.has_range() {
+            let resp = self
+                .api
+                .info(&serde_json::json!({"type": "userFills", "user": user}))
+                .await?;
+            return Ok(query.apply(crate::fills::parse_fills(resp)?));
+        }
+
+        let end_ms = query.to_ms;
+        let page = |start_ms: u64| {
+            let mut body = serde_json::json!({
+                "type": "userFillsByTime",
+                "user": user,
+                "startTime": start_ms,
+            });
+            if let Some(end) = end_ms {
+                body["endTime"] = end.into();
+            }
+            let api = &self.api;
+            async move { crate::fills::parse_fills(api.info(&body).await?) }
+        };
+        let mut fills = crate::fills::fetch_range(
+            query.from_ms.unwrap_or(0),
+            end_ms,
+            query.limit,
+            |f| query.matches(f),
+            page,
+        )
+        .await?;
+        fills.reverse();
+        Ok(fills)
     }
 
     async fn balances(&self) -> AtlasResult<Vec<Balance>> {
-        let state = self
-            .client
-            .clearinghouse_state(self.require_address()?, None)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch state: {e}")))?;
+        let user = self.require_address()?;
+        let state = self.api.clearinghouse_state(user).await?;
 
         Ok(vec![Balance {
             protocol: Protocol::Hyperliquid,
@@ -918,6 +1728,7 @@ impl PerpModule for HyperliquidModule {
     }
 
     async fn set_leverage(&self, symbol: &str, leverage: u32, is_cross: bool) -> AtlasResult<()> {
+        let symbol = self.resolve_name(symbol)?;
         let asset = self.resolve_asset(symbol)?;
 
         let action_json = serde_json::json!({
@@ -927,48 +1738,16 @@ impl PerpModule for HyperliquidModule {
             "leverage": leverage
         });
 
-        let nonce = self.nonce.next();
-        let mut rmp_bytes = rmp_serde::to_vec_named(&action_json)
-            .map_err(|e| AtlasError::Other(format!("RMP serialize: {e}")))?;
-        rmp_bytes.extend(nonce.to_be_bytes());
-        rmp_bytes.push(0u8);
-
-        let connection_id = alloy::primitives::keccak256(&rmp_bytes);
-        let source = if self.testnet { "b" } else { "a" };
-        let agent_hash = compute_agent_signing_hash(source, connection_id);
-
-        let sig = self
-            .require_signer()?
-            .sign_hash_sync(&agent_hash)
-            .map_err(|e| AtlasError::Auth(format!("Sign failed: {e}")))?;
-
-        let r_hex = hex::encode(sig.r().to_be_bytes::<32>());
-        let s_hex = hex::encode(sig.s().to_be_bytes::<32>());
-        let v = if sig.v() { 28u8 } else { 27u8 };
-
-        let request_body = serde_json::json!({
-            "action": action_json,
-            "nonce": nonce,
-            "signature": { "r": format!("0x{r_hex}"), "s": format!("0x{s_hex}"), "v": v },
-            "vaultAddress": null
-        });
-
-        let http = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .map_err(|e| AtlasError::Network(e.to_string()))?;
+        let request_body = self.sign_l1_action(&action_json)?;
+        let audit_id = self.audit_begin("leverage", Some(symbol), &request_body)?;
 
-        let resp = http
-            .post(format!("{}/exchange", self.base_url()))
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| AtlasError::Network(e.to_string()))?;
-
-        let body = resp
-            .text()
-            .await
-            .map_err(|e| AtlasError::Network(e.to_string()))?;
+        // Setting leverage is idempotent, so plain retries are safe
+        let body = self
+            .http
+            .post_idempotent(&format!("{}/exchange", self.base_url()), &request_body)
+            .await;
+        self.audit_complete(audit_id, &body);
+        let body = body?;
 
         let parsed: Value = serde_json::from_str(&body).map_err(|_| AtlasError::Protocol {
             protocol: "hyperliquid".into(),
@@ -994,33 +1773,47 @@ impl PerpModule for HyperliquidModule {
             .parse()
             .map_err(|_| AtlasError::Other(format!("Invalid address: {destination}")))?;
 
+        let signer = self.require_signer()?;
         let nonce = self.nonce.next();
         let send = hypersdk::hypercore::types::UsdSend {
             destination: dest,
             amount,
             time: nonce,
         };
+        let request = serde_json::json!({
+            "action": {
+                "type": "usdSend", "destination": format!("{dest:?}"),
+                "amount": amount.to_string(), "time": nonce,
+            },
+            "nonce": nonce,
+        });
+        let audit_id = self.audit_begin("transfer", Some("USDC"), &request)?;
 
-        self.client
-            .send_usdc(self.require_signer()?, send, nonce)
+        let result = self
+            .client
+            .send_usdc(signer, send, nonce)
             .await
             .map_err(|e| AtlasError::Protocol {
                 protocol: "hyperliquid".into(),
                 message: format!("Transfer failed: {e}"),
-            })?;
+            });
+        self.audit_sdk(audit_id, &result);
+        result?;
+        info!(
+            event = "transfer", kind = "usd_send", amount = %amount, token = "USDC",
+            destination, status = "ok", "usdc sent"
+        );
 
         Ok(format!("Transferred {} USDC to {}", amount, destination))
     }
 
     async fn update_margin(&self, symbol: &str, amount: Decimal) -> AtlasResult<()> {
+        let symbol = self.resolve_name(symbol)?;
         let asset = self.resolve_asset(symbol)?;
 
         // Determine is_buy from position side
-        let state = self
-            .client
-            .clearinghouse_state(self.require_address()?, None)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Fetch state: {e}")))?;
+        let user = self.require_address()?;
+        let state = self.api.clearinghouse_state(user).await?;
 
         let is_buy = state
             .asset_positions
@@ -1038,29 +1831,19 @@ impl PerpModule for HyperliquidModule {
             is_buy,
             ntli,
         };
-        let chain = if self.testnet {
-            hypercore::Chain::Testnet
-        } else {
-            hypercore::Chain::Mainnet
-        };
-
-        let action: hypercore::types::api::Action = update.into();
-        let signed = action
-            .sign_sync(self.require_signer()?, self.nonce.next(), None, None, chain)
-            .map_err(|e| AtlasError::Auth(format!("Sign failed: {e}")))?;
-
-        self.client
-            .send(signed)
-            .await
-            .map_err(|e| AtlasError::Protocol {
-                protocol: "hyperliquid".into(),
-                message: format!("Margin update failed: {e}"),
-            })?;
+        self.send_action(
+            update.into(),
+            "margin",
+            Some(symbol),
+            "Margin update failed",
+        )
+        .await?;
 
         Ok(())
     }
 
     async fn cancel_by_cloid(&self, symbol: &str, cloid: &str) -> AtlasResult<()> {
+        let symbol = self.resolve_name(symbol)?;
         let asset = self.resolve_asset(symbol)? as u32;
         let cloid_bytes: [u8; 16] = hex::decode(cloid.replace('-', ""))
             .map_err(|_| AtlasError::Other(format!("Invalid CLOID: {cloid}")))?
@@ -1075,20 +1858,154 @@ impl PerpModule for HyperliquidModule {
         let batch = BatchCancelCloid {
             cancels: vec![cancel],
         };
-        self.client
-            .cancel_by_cloid(self.require_signer()?, batch, self.nonce.next(), None, None)
-            .await
-            .map_err(|e| AtlasError::Protocol {
+        self.send_action(
+            batch.into(),
+            "cancel",
+            Some(symbol),
+            "Cancel by CLOID failed",
+        )
+        .await?;
+        info!(
+            event = "cancel",
+            coin = symbol,
+            cloid,
+            status = "cancelled",
+            "order cancelled by cloid"
+        );
+        Ok(())
+    }
+
+    async fn modify_order(
+        &self,
+        symbol: &str,
+        order_id: &str,
+        price: Option<Decimal>,
+        size: Option<Decimal>,
+    ) -> AtlasResult<OrderResult> {
+        let symbol = self.resolve_name(symbol)?;
+        let asset = self.resolve_asset(symbol)?;
+        if price.is_none() && size.is_none() {
+            return Err(AtlasError::Other(
+                "Nothing to modify — give a new price and/or size".into(),
+            ));
+        }
+        let target = ModifyTarget::parse(order_id)?;
+
+        // The modify replaces the whole order, so carry over what isn't changing.
+        let lookup = serde_json::to_value(&target)
+            .map_err(|e| AtlasError::Other(format!("Serialize failed: {e}")))?;
+        let resp = self.order_status_raw(lookup).await?;
+        if resp.get("status").and_then(|v| v.as_str()) != Some("order") {
+            return Err(AtlasError::Other(format!("Order {order_id} not found")));
+        }
+        let status = resp
+            .pointer("/order/status")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        if status != "open" {
+            return Err(AtlasError::order_rejected(format!(
+                "Cannot modify canceled or filled order {order_id} (status: {status})"
+            )));
+        }
+        let order = resp
+            .pointer("/order/order")
+            .ok_or_else(|| AtlasError::Protocol {
                 protocol: "hyperliquid".into(),
-                message: format!("Cancel by CLOID failed: {}", e.message()),
+                message: format!("Order status without order: {resp}"),
             })?;
-        Ok(())
+        let field = |key: &str| order.get(key).and_then(|v| v.as_str());
+        let coin = field("coin").unwrap_or_default();
+        if !coin.eq_ignore_ascii_case(symbol) {
+            return Err(AtlasError::Other(format!(
+                "Order {order_id} is on {coin}, not {symbol}"
+            )));
+        }
+        if order.get("isTrigger").and_then(|v| v.as_bool()) == Some(true) {
+            return Err(AtlasError::Other(
+                "Trigger orders can't be modified — cancel and place a new one".into(),
+            ));
+        }
+        let current = |key: &str| {
+            field(key)
+                .and_then(|s| Decimal::from_str(s).ok())
+                .ok_or_else(|| AtlasError::Protocol {
+                    protocol: "hyperliquid".into(),
+                    message: format!("Order {order_id} has no {key}"),
+                })
+        };
+        let px = self.round_price(symbol, price.map_or_else(|| current("limitPx"), Ok)?)?;
+        let sz = self.round_size(symbol, size.map_or_else(|| current("sz"), Ok)?)?;
+        if sz.is_zero() {
+            return Err(AtlasError::Other(format!(
+                "Size rounds to zero for {symbol}"
+            )));
+        }
+        let is_buy = field("side") == Some("B");
+        let side = if is_buy { Side::Buy } else { Side::Sell };
+        let reduce_only = order
+            .get("reduceOnly")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if !reduce_only {
+            self.check_risk_limits(symbol, &side, sz, px).await?;
+        }
+
+        let action = BatchModify::single(ModifySpec {
+            target,
+            asset,
+            is_buy,
+            price: px,
+            size: sz,
+            reduce_only,
+            tif: field("tif").unwrap_or("Gtc").to_string(),
+            cloid: field("cloid").map(str::to_string),
+        });
+        let request_body = self.sign_l1_action(&action)?;
+        let audit_id = self.audit_begin("modify", Some(symbol), &request_body)?;
+
+        // Signed once with a fixed nonce, so a resend can't apply it twice.
+        let body = self
+            .http
+            .post_idempotent(&format!("{}/exchange", self.base_url()), &request_body)
+            .await;
+        self.audit_complete(audit_id, &body);
+        let body = body?;
+        let result = parse_order_statuses(&body)
+            .and_then(|statuses| {
+                self.parse_response(&statuses, symbol, side.clone(), None, Some(audit_id))
+            })
+            .map(|mut r| {
+                // A bare "success" keeps the order resting under its old oid.
+                if r.order_id == "0" {
+                    r.order_id = order
+                        .get("oid")
+                        .and_then(|v| v.as_u64())
+                        .map(|oid| oid.to_string())
+                        .unwrap_or_else(|| order_id.to_string());
+                    r.status = OrderStatus::Open;
+                    r.message = Some("modified".into());
+                }
+                r
+            });
+        match &result {
+            Ok(r) => info!(
+                event = "modify", coin = symbol, side = %side, %sz, %px,
+                order_id, new_oid = %r.order_id, status = "ok",
+                "order modified"
+            ),
+            Err(e) => warn!(
+                event = "modify", coin = symbol, order_id, status = "rejected", error = %e,
+                "order modify rejected"
+            ),
+        }
+        result
     }
 
     async fn spot_balances(&self) -> AtlasResult<Vec<SpotBalance>> {
+        let user = self.require_address()?;
         let balances = self
-            .client
-            .user_balances(self.require_address()?)
+            .http
+            .info("spotClearinghouseState", || self.client.user_balances(user))
             .await
             .map_err(|e| AtlasError::Network(format!("Fetch spot balances: {e}")))?;
 
@@ -1105,21 +2022,18 @@ impl PerpModule for HyperliquidModule {
     }
 
     async fn spot_tokens_map(&self) -> AtlasResult<std::collections::HashMap<usize, String>> {
-        let url = if self.testnet {
-            "https://api.hyperliquid-testnet.xyz/info"
-        } else {
-            "https://api.hyperliquid.xyz/info"
-        };
-        let http = reqwest::Client::new();
-        let resp: Value = http
-            .post(url)
-            .json(&serde_json::json!({"type": "spotMetaAndAssetCtxs"}))
-            .send()
-            .await
-            .map_err(|e| AtlasError::Network(format!("spotMetaAndAssetCtxs request: {e}")))?
-            .json()
-            .await
-            .map_err(|e| AtlasError::Network(format!("spotMetaAndAssetCtxs parse: {e}")))?;
+        let path = MetaCache::path(self.testnet);
+        let cached = path.as_deref().and_then(MetaCache::load);
+        if let Some(cache) = &cached {
+            if cache.is_fresh(self.meta_ttl) && !cache.spot_tokens.is_empty() {
+                return Ok(cache.spot_tokens.clone());
+            }
+        }
+
+        let resp = self
+            .api
+            .info(&serde_json::json!({"type": "spotMetaAndAssetCtxs"}))
+            .await?;
 
         let tokens = resp
             .get(0)
@@ -1136,9 +2050,36 @@ impl PerpModule for HyperliquidModule {
                 map.insert(index as usize, name.to_string());
             }
         }
+
+        // Only piggyback on a fresh perps cache — never extend a stale one
+        if let (Some(path), Some(mut cache)) = (path, cached) {
+            if cache.is_fresh(self.meta_ttl) {
+                cache.spot_tokens = map.clone();
+                if let Err(e) = cache.save(&path) {
+                    warn!("Failed to update HL metadata cache: {e}");
+                }
+            }
+        }
         Ok(map)
     }
 
+    async fn spot_market_info(&self, base: &str) -> AtlasResult<SpotMarketInfo> {
+        let market = self.resolve_spot_market(base).await?;
+        Ok(SpotMarketInfo {
+            mid_price: self.spot_mid(&market).await?,
+            sz_decimals: market.sz_decimals,
+            pair: market.pair,
+        })
+    }
+
+    async fn spot_markets(&self) -> AtlasResult<Vec<SpotMarket>> {
+        let (pairs, mids) = tokio::try_join!(self.spot_pairs(), self.api.all_mids())?;
+        Ok(pairs
+            .iter()
+            .map(|p| p.to_market(mids.get(&p.key).copied()))
+            .collect())
+    }
+
     async fn spot_market_order(
         &self,
         base: &str,
@@ -1146,81 +2087,73 @@ impl PerpModule for HyperliquidModule {
         size: Decimal,
         slippage: Option<f64>,
     ) -> AtlasResult<OrderResult> {
-        let spot_markets = self
-            .client
-            .spot()
+        self.place_spot_order(base, side, size, None, slippage)
             .await
-            .map_err(|e| AtlasError::Network(format!("Fetch spot markets: {e}")))?;
-
-        let market = spot_markets
-            .iter()
-            .find(|m| {
-                m.tokens
-                    .first()
-                    .map(|t| t.name.eq_ignore_ascii_case(base))
-                    .unwrap_or(false)
-            })
-            .ok_or_else(|| AtlasError::AssetNotFound(format!("Spot: {base}")))?;
-
-        let is_buy = side_to_is_buy(&side);
-        let slip = slippage.unwrap_or(0.05);
+    }
 
-        let mids = self
-            .client
-            .all_mids(None)
+    async fn spot_limit_order(
+        &self,
+        base: &str,
+        side: Side,
+        size: Decimal,
+        price: Decimal,
+    ) -> AtlasResult<OrderResult> {
+        self.place_spot_order(base, side, size, Some(price), None)
             .await
-            .map_err(|e| AtlasError::Network(format!("Fetch mids: {e}")))?;
+    }
 
-        let mid_key = format!("@{}", market.index);
-        let mid = mids
-            .get(base)
-            .or_else(|| mids.get(&mid_key))
-            .ok_or_else(|| AtlasError::Other(format!("No mid price for spot {base}")))?;
+    async fn spot_cancel_order(&self, base: &str, order_id: &str) -> AtlasResult<()> {
+        let asset = self.spot_asset(base).await?;
+        let oid: u64 = order_id
+            .parse()
+            .map_err(|_| AtlasError::Other(format!("Invalid OID: {order_id}")))?;
 
-        let slip_dec =
-            Decimal::from_f64(slip).ok_or_else(|| AtlasError::Other("Invalid slippage".into()))?;
-        let mult = if is_buy {
-            Decimal::ONE + slip_dec
-        } else {
-            Decimal::ONE - slip_dec
+        let batch = BatchCancel {
+            cancels: vec![Cancel { asset, oid }],
         };
-        let px = market
-            .round_price(*mid * mult)
-            .ok_or_else(|| AtlasError::Other("Cannot round spot price".to_string()))?;
+        self.send_action(batch.into(), "cancel", Some(base), "Spot cancel failed")
+            .await?;
+        info!(
+            event = "cancel",
+            coin = base,
+            market = "spot",
+            oid,
+            status = "cancelled",
+            "order cancelled"
+        );
+        Ok(())
+    }
 
-        let sz_dp = market.tokens[0].sz_decimals.max(0) as u32;
-        let sz = size.round_dp(sz_dp);
-        if sz.is_zero() {
-            return Err(AtlasError::Other("Spot order size rounds to zero".into()));
-        }
+    async fn spot_cancel_all(&self, base: &str) -> AtlasResult<u32> {
+        let asset = self.spot_asset(base).await?;
+        let pairs = self.spot_pairs().await?;
+        let pair = find_usdc_pair(&pairs, base)
+            .ok_or_else(|| AtlasError::asset_not_found(format!("Spot: {base}")))?;
+        let user = self.require_address()?;
+        let orders = self.api.open_orders(user).await?;
 
-        let order = OrderRequest {
-            asset: market.index,
-            is_buy,
-            reduce_only: false,
-            limit_px: px,
-            sz,
-            cloid: random_cloid(),
-            order_type: OrderTypePlacement::Limit {
-                tif: TimeInForce::Ioc,
-            },
-        };
-
-        let batch = BatchOrder {
-            orders: vec![order],
-            grouping: OrderGrouping::Na,
-        };
-        // Spot: no builder fee
-        let statuses = self
-            .client
-            .place(self.require_signer()?, batch, self.nonce.next(), None, None)
-            .await
-            .map_err(|e| AtlasError::Protocol {
-                protocol: "hyperliquid".into(),
-                message: format!("Spot order failed: {}", e.message()),
-            })?;
+        let cancels: Vec<Cancel> = orders
+            .iter()
+            .filter(|o| o.coin == pair.key)
+            .map(|o| Cancel { asset, oid: o.oid })
+            .collect();
+        if cancels.is_empty() {
+            return Ok(0);
+        }
+        let total = cancels.len() as u32;
 
-        self.parse_response(&statuses, base, side)
+        let batch = BatchCancel { cancels };
+        self.send_action(batch.into(), "cancel", Some(base), "Spot cancel failed")
+            .await?;
+        info!(
+            event = "cancel",
+            coin = base,
+            market = "spot",
+            count = total,
+            status = "cancelled",
+            "cancel all"
+        );
+        Ok(total)
     }
 
     async fn internal_transfer(
@@ -1233,72 +2166,76 @@ impl PerpModule for HyperliquidModule {
 
         // Find spot token
         let tokens = self
-            .client
-            .spot_tokens()
+            .http
+            .info("spotMeta", || self.client.spot_tokens())
             .await
             .map_err(|e| AtlasError::Network(format!("Fetch spot tokens: {e}")))?;
 
         let spot_token = tokens
             .into_iter()
             .find(|t| t.name.eq_ignore_ascii_case(token_name))
-            .ok_or_else(|| AtlasError::AssetNotFound(format!("Spot token: {token_name}")))?;
+            .ok_or_else(|| AtlasError::asset_not_found(format!("Spot token: {token_name}")))?;
+
+        let route = match direction {
+            "to-spot" | "perps-to-spot" => "perps-to-spot",
+            "to-perps" | "spot-to-perps" => "spot-to-perps",
+            "to-evm" | "spot-to-evm" => "spot-to-evm",
+            _ => {
+                return Err(AtlasError::Other(format!(
+                    "Unknown transfer direction: {direction}"
+                )))
+            }
+        };
+        let signer = self.require_signer()?;
+        let nonce = self.nonce.next();
+        let request = serde_json::json!({
+            "action": {
+                "type": "internalTransfer", "route": route, "token": token_name,
+                "amount": amount.to_string(),
+            },
+            "nonce": nonce,
+        });
+        let audit_id = self.audit_begin("transfer", Some(token_name), &request)?;
 
-        match direction {
-            "to-spot" | "perps-to-spot" => {
+        let (result, message) = match route {
+            "perps-to-spot" => (
                 self.client
-                    .transfer_to_spot(
-                        self.require_signer()?,
-                        spot_token,
-                        amount,
-                        self.nonce.next(),
-                    )
+                    .transfer_to_spot(signer, spot_token, amount, nonce)
                     .await
                     .map_err(|e| AtlasError::Protocol {
                         protocol: "hyperliquid".into(),
                         message: format!("Transfer to spot failed: {e}"),
-                    })?;
-                Ok(format!(
-                    "Transferred {} {} perps → spot",
-                    amount, token_name
-                ))
-            }
-            "to-perps" | "spot-to-perps" => {
+                    }),
+                format!("Transferred {} {} perps → spot", amount, token_name),
+            ),
+            "spot-to-perps" => (
                 self.client
-                    .transfer_to_perps(
-                        self.require_signer()?,
-                        spot_token,
-                        amount,
-                        self.nonce.next(),
-                    )
+                    .transfer_to_perps(signer, spot_token, amount, nonce)
                     .await
                     .map_err(|e| AtlasError::Protocol {
                         protocol: "hyperliquid".into(),
                         message: format!("Transfer to perps failed: {e}"),
-                    })?;
-                Ok(format!(
-                    "Transferred {} {} spot → perps",
-                    amount, token_name
-                ))
-            }
-            "to-evm" | "spot-to-evm" => {
+                    }),
+                format!("Transferred {} {} spot → perps", amount, token_name),
+            ),
+            _ => (
                 self.client
-                    .transfer_to_evm(
-                        self.require_signer()?,
-                        spot_token,
-                        amount,
-                        self.nonce.next(),
-                    )
+                    .transfer_to_evm(signer, spot_token, amount, nonce)
                     .await
                     .map_err(|e| AtlasError::Protocol {
                         protocol: "hyperliquid".into(),
                         message: format!("Transfer to EVM failed: {e}"),
-                    })?;
-                Ok(format!("Transferred {} {} spot → EVM", amount, token_name))
-            }
-            _ => Err(AtlasError::Other(format!(
-                "Unknown transfer direction: {direction}"
-            ))),
-        }
+                    }),
+                format!("Transferred {} {} spot → EVM", amount, token_name),
+            ),
+        };
+        self.audit_sdk(audit_id, &result);
+        result?;
+        info!(
+            event = "transfer", kind = direction, amount = %amount, token = token_name,
+            status = "ok", "internal transfer"
+        );
+        Ok(message)
     }
 
     async fn vault_details(&self, vault_address: &str) -> AtlasResult<VaultDetails> {
@@ -1306,9 +2243,12 @@ impl PerpModule for HyperliquidModule {
             .parse()
             .map_err(|_| AtlasError::Other(format!("Invalid vault address: {vault_address}")))?;
 
+        let user = self.require_address()?;
         let details = self
-            .client
-            .vault_details(vault_addr, Some(self.require_address()?))
+            .http
+            .info("vaultDetails", || {
+                self.client.vault_details(vault_addr, Some(user))
+            })
             .await
             .map_err(|e| AtlasError::Network(format!("Fetch vault details: {e}")))?;
 
@@ -1334,10 +2274,37 @@ impl PerpModule for HyperliquidModule {
         })
     }
 
+    async fn vault_list(&self, refresh: bool) -> AtlasResult<Vec<VaultSummary>> {
+        let path = VaultCache::path(self.testnet);
+        if !refresh {
+            if let Some(cache) = path.as_deref().and_then(VaultCache::load) {
+                if cache.is_fresh(vaults::CACHE_TTL) {
+                    debug!(vaults = cache.vaults.len(), "HL vault listing from cache");
+                    return Ok(cache.vaults);
+                }
+            }
+        }
+
+        let resp = self
+            .http
+            .get_json(vaults::listing_url(self.testnet), "vaults")
+            .await?;
+        let list = vaults::parse_vaults(resp)?;
+        if let Some(path) = path {
+            if let Err(e) = VaultCache::new(self.testnet, list.clone()).save(&path) {
+                warn!("Failed to write HL vault cache: {e}");
+            }
+        }
+        Ok(list)
+    }
+
     async fn vault_deposits(&self) -> AtlasResult<Vec<VaultDeposit>> {
+        let user = self.require_address()?;
         let equities = self
-            .client
-            .user_vault_equities(self.require_address()?)
+            .http
+            .info("userVaultEquities", || {
+                self.client.user_vault_equities(user)
+            })
             .await
             .map_err(|e| AtlasError::Network(format!("Fetch vault deposits: {e}")))?;
 
@@ -1353,9 +2320,10 @@ impl PerpModule for HyperliquidModule {
     }
 
     async fn subaccounts(&self) -> AtlasResult<Vec<SubAccount>> {
+        let user = self.require_address()?;
         let subs = self
-            .client
-            .subaccounts(self.require_address()?)
+            .http
+            .info("subAccounts", || self.client.subaccounts(user))
             .await
             .map_err(|e| AtlasError::Network(format!("Fetch subaccounts: {e}")))?;
 
@@ -1376,19 +2344,27 @@ impl PerpModule for HyperliquidModule {
             .map_err(|_| AtlasError::Other(format!("Invalid agent address: {agent_address}")))?;
 
         let agent_name = name.unwrap_or("").to_string();
+        let signer = self.require_signer()?;
+        let nonce = self.nonce.next();
+        let request = serde_json::json!({
+            "action": {
+                "type": "approveAgent", "agentAddress": format!("{agent_addr:?}"),
+                "agentName": agent_name, "nonce": nonce,
+            },
+            "nonce": nonce,
+        });
+        let audit_id = self.audit_begin("agent", None, &request)?;
 
-        self.client
-            .approve_agent(
-                self.require_signer()?,
-                agent_addr,
-                agent_name.clone(),
-                self.nonce.next(),
-            )
+        let result = self
+            .client
+            .approve_agent(signer, agent_addr, agent_name.clone(), nonce)
             .await
             .map_err(|e| AtlasError::Protocol {
                 protocol: "hyperliquid".into(),
                 message: format!("Agent approval failed: {e}"),
-            })?;
+            });
+        self.audit_sdk(audit_id, &result);
+        result?;
 
         Ok(format!(
             "Agent {} approved{}",
@@ -1401,3 +2377,649 @@ impl PerpModule for HyperliquidModule {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::MockApi;
+    use serde_json::json;
+
+    fn perps() -> Vec<MarketMeta> {
+        vec![
+            MarketMeta {
+                name: "BTC".into(),
+                index: 0,
+                sz_decimals: 5,
+                max_leverage: 40,
+            },
+            MarketMeta {
+                name: "ETH".into(),
+                index: 1,
+                sz_decimals: 4,
+                max_leverage: 25,
+            },
+        ]
+    }
+
+    fn module(api: MockApi) -> (HyperliquidModule, Arc<MockApi>) {
+        let signer: PrivateKeySigner =
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let api = Arc::new(api);
+        let module =
+            HyperliquidModule::with_api(api.clone(), Some(signer), false, perps()).unwrap();
+        (module, api)
+    }
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn order_statuses(statuses: Value) -> Value {
+        json!({"status": "ok", "response": {"type": "order", "data": {"statuses": statuses}}})
+    }
+
+    /// The one `/exchange` request sent, checked for a nonce and signature.
+    fn sent_action(api: &MockApi) -> Value {
+        let sent = api.exchange_requests();
+        assert_eq!(sent.len(), 1, "expected one /exchange request: {sent:?}");
+        assert!(sent[0]["nonce"].as_u64().is_some());
+        assert!(sent[0]["signature"]["r"].as_str().is_some());
+        sent[0]["action"].clone()
+    }
+
+    fn short_eth_state() -> Value {
+        json!({
+            "assetPositions": [{"type": "oneWay", "position": {
+                "coin": "ETH", "szi": "-1.5", "entryPx": "3100.0", "unrealizedPnl": "-20.0",
+                "liquidationPx": "3900.0", "leverage": {"type": "isolated", "value": 10},
+            }}],
+            "marginSummary": {"accountValue": "1000.0", "totalMarginUsed": "465.0"},
+            "withdrawable": "535.0",
+        })
+    }
+
+    #[tokio::test]
+    async fn test_market_open_sends_rounded_ioc_with_builder_fee() {
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("allMids", json!({"BTC": "97000.5", "ETH": "3000.25"}))
+                .with_exchange(order_statuses(json!([
+                    {"filled": {"totalSz": "0.12346", "avgPx": "97012.0", "oid": 77}}
+                ]))),
+        );
+
+        let result = hl
+            .market_order("btc", Side::Buy, dec("0.123456"), None)
+            .await
+            .unwrap();
+
+        let action = sent_action(&api);
+        assert_eq!(action["type"], "order");
+        assert_eq!(action["grouping"], "na");
+        assert_eq!(action["builder"], json!(BuilderFee::default()));
+        let order = &action["orders"][0];
+        assert_eq!(order["a"], 0);
+        assert_eq!(order["b"], true);
+        // 97000.5 × 1.05, cut to 5 significant figures; size to 5 decimals
+        assert_eq!(order["p"], "101851");
+        assert_eq!(order["s"], "0.12346");
+        assert_eq!(order["r"], false);
+        assert_eq!(order["t"], json!({"limit": {"tif": "Ioc"}}));
+        assert!(order["c"].as_str().is_some_and(|c| c.starts_with("0x")));
+
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.order_id, "77");
+        assert_eq!(result.filled_size, Some(dec("0.12346")));
+        assert_eq!(result.avg_price, Some(dec("97012.0")));
+        assert_eq!(result.builder_fee_bps, Some(BuilderFee::default().f));
+    }
+
+    #[tokio::test]
+    async fn test_market_close_buys_back_short_reduce_only() {
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("clearinghouseState", short_eth_state())
+                .with_info("allMids", json!({"ETH": "3000.25"}))
+                .with_exchange(order_statuses(json!([
+                    {"filled": {"totalSz": "1.5", "avgPx": "3001.1", "oid": 90}}
+                ]))),
+        );
+
+        let result = hl.close_position("ETH", None, Some(0.01)).await.unwrap();
+
+        let order = &sent_action(&api)["orders"][0];
+        assert_eq!(order["a"], 1);
+        assert_eq!(order["b"], true);
+        assert_eq!(order["r"], true);
+        assert_eq!(order["s"], "1.5");
+        // 3000.25 × 1.01 = 3030.2525 → 5 significant figures
+        assert_eq!(order["p"], "3030.3");
+        assert_eq!(result.side, Some(Side::Buy));
+        assert_eq!(result.filled_size, Some(dec("1.5")));
+
+        // A partial close never exceeds the position
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("clearinghouseState", short_eth_state())
+                .with_info("allMids", json!({"ETH": "3000.25"}))
+                .with_exchange(order_statuses(json!([
+                    {"filled": {"totalSz": "1.5", "avgPx": "3001.1", "oid": 91}}
+                ]))),
+        );
+        hl.close_position("ETH", Some(dec("4")), None)
+            .await
+            .unwrap();
+        assert_eq!(sent_action(&api)["orders"][0]["s"], "1.5");
+    }
+
+    #[tokio::test]
+    async fn test_close_checks_order_protections_on_the_buy_side() {
+        let candle =
+            json!([{"t": 1, "o": "3000", "h": "3000", "l": "3000", "c": "3000", "v": "1"}]);
+        // Closing the short buys at 3180 × 1.10: 16.6% over the last close
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("clearinghouseState", short_eth_state())
+                .with_info("allMids", json!({"ETH": "3180"}))
+                .with_info("candleSnapshot", candle.clone()),
+        );
+        let hl = hl.with_order_protections(Some(TradingConfig::default()));
+        let err = hl
+            .close_position("ETH", None, Some(0.10))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, AtlasError::PriceBandExceeded { .. }),
+            "{err:?}"
+        );
+        let err = hl.market_order("ETH", Side::Buy, dec("1"), Some(0.2)).await;
+        assert!(
+            matches!(err, Err(AtlasError::SlippageTooHigh { .. })),
+            "{err:?}"
+        );
+        assert!(api.exchange_requests().is_empty());
+
+        // Overridden (`--force`): sent as asked
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("clearinghouseState", short_eth_state())
+                .with_info("allMids", json!({"ETH": "3180"}))
+                .with_info("candleSnapshot", candle)
+                .with_exchange(order_statuses(json!([
+                    {"filled": {"totalSz": "1.5", "avgPx": "3181", "oid": 92}}
+                ]))),
+        );
+        let hl = hl.with_order_protections(None);
+        hl.close_position("ETH", None, Some(0.10)).await.unwrap();
+        assert_eq!(sent_action(&api)["orders"][0]["b"], true);
+    }
+
+    #[tokio::test]
+    async fn test_tui_limit_order_over_max_notional_is_blocked() {
+        let limits = TradingConfig {
+            max_notional: Some(2000.0),
+            ..TradingConfig::default()
+        };
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("clearinghouseState", short_eth_state())
+                .with_exchange(order_statuses(json!([{"resting": {"oid": 93}}]))),
+        );
+        let hl = hl.with_risk_limits(Some(limits));
+        // What the TUI trade popup sends: a plain GTC limit
+        let opts = LimitOptions::default();
+
+        // Adding 1 ETH to the short: $3000 over the $2000 cap
+        let err = hl
+            .limit_order("ETH", Side::Sell, dec("1"), dec("3000"), &opts)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, AtlasError::RiskBlocked { limit, .. } if limit == "max_notional"),
+            "{err:?}"
+        );
+        // Flipping the 1.5 short into a 1.5 long: $4500 of new exposure
+        let err = hl
+            .limit_order("ETH", Side::Buy, dec("3"), dec("3000"), &opts)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AtlasError::RiskBlocked { .. }), "{err:?}");
+        assert!(api.exchange_requests().is_empty());
+
+        // Reducing the short is never blocked
+        hl.limit_order("ETH", Side::Buy, dec("1"), dec("3000"), &opts)
+            .await
+            .unwrap();
+        assert_eq!(sent_action(&api)["orders"][0]["s"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_limit_order_rests_with_requested_cloid() {
+        let cloid = "0x0000000000000000000000000000002a";
+        let (hl, api) = module(MockApi::new().with_exchange(order_statuses(json!([
+            {"resting": {"oid": 88}}
+        ]))));
+        let opts = LimitOptions {
+            tif: atlas_core::types::TimeInForce::Alo,
+            cloid: Some(cloid.into()),
+            ..LimitOptions::default()
+        };
+
+        let result = hl
+            .limit_order("BTC", Side::Sell, dec("0.5"), dec("97123.456"), &opts)
+            .await
+            .unwrap();
+
+        let action = sent_action(&api);
+        assert_eq!(action["builder"], json!(BuilderFee::default()));
+        let order = &action["orders"][0];
+        assert_eq!(order["b"], false);
+        assert_eq!(order["p"], "97123");
+        assert_eq!(order["s"], "0.5");
+        assert_eq!(order["t"], json!({"limit": {"tif": "Alo"}}));
+        assert_eq!(order["c"], cloid);
+        // No trigger: the mid is never read
+        assert!(api.info_requests().is_empty());
+
+        assert_eq!(result.status, OrderStatus::Open);
+        assert_eq!(result.order_id, "88");
+        assert_eq!(result.filled_size, None);
+    }
+
+    #[tokio::test]
+    async fn test_order_errors_are_rejections() {
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("allMids", json!({"BTC": "97000.5"}))
+                .with_exchange(order_statuses(json!([
+                    {"error": "Order must have minimum value of $10."}
+                ]))),
+        );
+        let err = hl
+            .market_order("BTC", Side::Sell, dec("0.0001"), None)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, AtlasError::OrderRejected { message, .. } if message.contains("minimum value")),
+            "{err:?}"
+        );
+        assert_eq!(api.exchange_requests().len(), 1);
+
+        let (hl, _) = module(MockApi::new().with_exchange(json!({
+            "status": "err", "response": "Insufficient margin to place order."
+        })));
+        let err = hl
+            .limit_order(
+                "ETH",
+                Side::Buy,
+                dec("1"),
+                dec("2900"),
+                &LimitOptions::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AtlasError::OrderRejected { .. }), "{err:?}");
+    }
+
+    #[tokio::test]
+    async fn test_signed_actions_are_audited_before_sending() {
+        use atlas_core::db::AuditFilter;
+
+        let (hl, api) = module(MockApi::new().with_exchange(order_statuses(json!([
+            {"error": "Insufficient margin to place order."}
+        ]))));
+        hl.limit_order(
+            "ETH",
+            Side::Buy,
+            dec("1"),
+            dec("2900"),
+            &LimitOptions::default(),
+        )
+        .await
+        .unwrap_err();
+        let (hl_ok, _) = module(MockApi::new().with_exchange(order_statuses(json!([
+            {"resting": {"oid": 88}}
+        ]))));
+        let result = hl_ok
+            .limit_order(
+                "ETH",
+                Side::Buy,
+                dec("1"),
+                dec("2900"),
+                &LimitOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        let entries = hl.audit.entries(&AuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.action_type, "order");
+        assert_eq!(entry.coin.as_deref(), Some("ETH"));
+        assert_eq!(entry.status, "rejected");
+        assert_eq!(
+            entry.nonce,
+            api.exchange_requests()[0]["nonce"].as_i64(),
+            "the entry records the nonce that was sent"
+        );
+        assert_eq!(entry.signer, format!("{:?}", hl.require_address().unwrap()));
+        assert!(entry
+            .response
+            .as_deref()
+            .is_some_and(|r| r.contains("Insufficient margin")));
+
+        let ok = hl_ok.audit.entries(&AuditFilter::default()).unwrap();
+        assert_eq!(result.audit_id, Some(ok[0].id));
+        assert_eq!(ok[0].status, "ok");
+    }
+
+    #[tokio::test]
+    async fn test_ambiguous_submit_recovers_landed_order() {
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("allMids", json!({"BTC": "97000.5"}))
+                .with_exchange_failure(HttpFailure::Ambiguous("timed out".into()))
+                .with_info(
+                    "orderStatus",
+                    json!({"status": "order", "order": {"status": "filled", "order": {
+                        "oid": 5, "origSz": "0.1", "sz": "0.0", "limitPx": "101851"
+                    }}}),
+                ),
+        );
+
+        let result = hl
+            .market_order("BTC", Side::Buy, dec("0.1"), None)
+            .await
+            .unwrap();
+
+        // Found by cloid, so never resubmitted
+        assert_eq!(api.exchange_requests().len(), 1);
+        assert_eq!(result.order_id, "5");
+        assert_eq!(result.filled_size, Some(dec("0.1")));
+        // No userFills programmed: falls back to the limit price
+        assert_eq!(result.avg_price, Some(dec("101851")));
+    }
+
+    /// Retries without sleeping, so a test can walk the resubmit loop.
+    fn fast_retries(max_retries: u32) -> ExchangeHttp {
+        ExchangeHttp::new(RetryPolicy {
+            max_retries,
+            timeout: std::time::Duration::from_secs(1),
+            backoff: std::time::Duration::ZERO,
+        })
+        .unwrap()
+    }
+
+    fn order_status_requests(api: &MockApi) -> usize {
+        api.info_requests()
+            .iter()
+            .filter(|r| r["type"] == "orderStatus")
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_ambiguous_submit_recovers_fill_price() {
+        use atlas_core::db::AuditFilter;
+
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("allMids", json!({"BTC": "97000.5"}))
+                .with_exchange_failure(HttpFailure::Ambiguous("timed out".into()))
+                .with_info(
+                    "orderStatus",
+                    json!({"status": "order", "order": {"status": "filled", "order": {
+                        "oid": 5, "origSz": "0.1", "sz": "0.0", "limitPx": "101851"
+                    }}}),
+                )
+                .with_info(
+                    "userFills",
+                    json!([
+                        {"oid": 5, "px": "97000", "sz": "0.04"},
+                        {"oid": 4, "px": "90000", "sz": "1.0"},
+                        {"oid": 5, "px": "97100", "sz": "0.06"},
+                    ]),
+                ),
+        );
+
+        let result = hl
+            .market_order("BTC", Side::Buy, dec("0.1"), None)
+            .await
+            .unwrap();
+
+        assert_eq!(api.exchange_requests().len(), 1);
+        assert_eq!(result.filled_size, Some(dec("0.1")));
+        // VWAP of oid 5's fills, not the limit price
+        assert_eq!(result.avg_price, Some(dec("97060")));
+
+        let entries = hl.audit.entries(&AuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1, "one entry covers every attempt");
+        assert_eq!(entries[0].status, "ok");
+        assert!(entries[0]
+            .response
+            .as_deref()
+            .is_some_and(|r| r.contains("recovered_by_cloid")));
+    }
+
+    #[tokio::test]
+    async fn test_failed_cloid_lookup_completes_audit_entry() {
+        use atlas_core::db::AuditFilter;
+
+        // No orderStatus response programmed: the lookup fails
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("allMids", json!({"BTC": "97000.5"}))
+                .with_exchange_failure(HttpFailure::Ambiguous("timed out".into())),
+        );
+
+        assert!(hl
+            .market_order("BTC", Side::Buy, dec("0.1"), None)
+            .await
+            .is_err());
+        assert_eq!(api.exchange_requests().len(), 1);
+
+        let entries = hl.audit.entries(&AuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, "error");
+        assert!(entries[0]
+            .response
+            .as_deref()
+            .is_some_and(|r| r.contains("orderStatus")));
+    }
+
+    #[tokio::test]
+    async fn test_ambiguous_submit_not_found_resends_same_action() {
+        let (mut hl, api) = module(
+            MockApi::new()
+                .with_info("allMids", json!({"BTC": "97000.5"}))
+                .with_info("orderStatus", json!({"status": "unknownOid"}))
+                .with_exchange_failure(HttpFailure::Ambiguous("timed out".into()))
+                .with_exchange(order_statuses(json!([
+                    {"filled": {"totalSz": "0.1", "avgPx": "97010", "oid": 7}}
+                ]))),
+        );
+        hl.http = fast_retries(2);
+
+        let result = hl
+            .market_order("BTC", Side::Buy, dec("0.1"), None)
+            .await
+            .unwrap();
+
+        assert_eq!(result.order_id, "7");
+        assert_eq!(order_status_requests(&api), 1);
+        let sent = api.exchange_requests();
+        assert_eq!(sent.len(), 2);
+        // Same nonce, cloid and signature: the exchange can't run it twice
+        assert_eq!(sent[0], sent[1]);
+    }
+
+    #[tokio::test]
+    async fn test_double_timeout_fails_with_protocol_timeout() {
+        use atlas_core::db::AuditFilter;
+
+        let (mut hl, api) = module(
+            MockApi::new()
+                .with_info("allMids", json!({"BTC": "97000.5"}))
+                .with_info("orderStatus", json!({"status": "unknownOid"}))
+                .with_exchange_failure(HttpFailure::Ambiguous("timed out".into()))
+                .with_exchange_failure(HttpFailure::Ambiguous("timed out".into())),
+        );
+        hl.http = fast_retries(1);
+
+        let err = hl
+            .market_order("BTC", Side::Buy, dec("0.1"), None)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, AtlasError::ProtocolTimeout(_)), "{err:?}");
+        // Looked up by cloid after each timeout, resent once in between
+        assert_eq!(order_status_requests(&api), 2);
+        let sent = api.exchange_requests();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0], sent[1]);
+
+        let entries = hl.audit.entries(&AuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, "error");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_cancels_only_that_coin() {
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info(
+                    "openOrders",
+                    json!([
+                        {"coin": "BTC", "oid": 1, "side": "B", "limitPx": "90000", "sz": "0.1"},
+                        {"coin": "ETH", "oid": 2, "side": "A", "limitPx": "3500", "sz": "1"},
+                        {"coin": "BTC", "oid": 3, "side": "A", "limitPx": "99000", "sz": "0.1"},
+                    ]),
+                )
+                .with_exchange(json!({"status": "ok", "response": {"type": "cancel",
+                    "data": {"statuses": ["success", "success"]}}})),
+        );
+
+        assert_eq!(hl.cancel_all("BTC").await.unwrap(), 2);
+        let action = sent_action(&api);
+        assert_eq!(action["type"], "cancel");
+        assert_eq!(
+            action["cancels"],
+            json!([{"a": 0, "o": 1}, {"a": 0, "o": 3}])
+        );
+        assert!(action.get("builder").is_none());
+
+        let (hl, api) = module(MockApi::new().with_info("openOrders", json!([])));
+        assert_eq!(hl.cancel_all("ETH").await.unwrap(), 0);
+        assert!(api.exchange_requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_margin_sends_isolated_delta() {
+        let (hl, api) = module(
+            MockApi::new()
+                .with_info("clearinghouseState", short_eth_state())
+                .with_exchange(json!({"status": "ok", "response": {"type": "default"}})),
+        );
+
+        hl.update_margin("ETH", dec("25.5")).await.unwrap();
+        assert_eq!(
+            sent_action(&api),
+            json!({"type": "updateIsolatedMargin", "asset": 1, "isBuy": false, "ntli": 25_500_000})
+        );
+
+        let (hl, _) = module(
+            MockApi::new()
+                .with_info("clearinghouseState", short_eth_state())
+                .with_exchange(json!({"status": "err", "response": "Insufficient margin"})),
+        );
+        let err = hl.update_margin("ETH", dec("5000")).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Margin update failed: Insufficient margin"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_margin_state_reads_cross_value_and_isolated_equity() {
+        let (hl, _) = module(
+            MockApi::new()
+                .with_info(
+                    "clearinghouseState",
+                    json!({
+                        "assetPositions": [
+                            {"type": "oneWay", "position": {
+                                "coin": "BTC", "szi": "0.5", "entryPx": "60000.0",
+                                "positionValue": "31000.0", "unrealizedPnl": "1000.0",
+                                "liquidationPx": "45000.0", "marginUsed": "1550.0",
+                                "leverage": {"type": "cross", "value": 20},
+                            }},
+                            {"type": "oneWay", "position": {
+                                "coin": "ETH", "szi": "-1.5", "entryPx": "3100.0",
+                                "positionValue": "4500.0", "unrealizedPnl": "150.0",
+                                "liquidationPx": "3400.0", "marginUsed": "615.0",
+                                "leverage": {"type": "isolated", "value": 10, "rawUsd": "5115.0"},
+                            }},
+                        ],
+                        "marginSummary": {"accountValue": "12615.0", "totalMarginUsed": "2165.0"},
+                        "crossMarginSummary": {"accountValue": "12000.0", "totalMarginUsed": "1550.0"},
+                        "withdrawable": "10450.0",
+                    }),
+                )
+                .with_info(
+                    "meta",
+                    json!({"universe": [
+                        {"name": "BTC", "szDecimals": 5, "maxLeverage": 40},
+                        {"name": "ETH", "szDecimals": 4, "maxLeverage": 25},
+                    ]}),
+                ),
+        );
+
+        let state = hl.margin_state().await.unwrap();
+        assert_eq!(state.cross_account_value, dec("12000"));
+        assert_eq!(state.positions[0].mark_price, dec("62000"));
+        assert_eq!(state.positions[0].mode, MarginMode::Cross);
+        // 5115 - 1.5 × 3000
+        assert_eq!(
+            state.positions[1].mode,
+            MarginMode::Isolated { equity: dec("615") }
+        );
+        assert_eq!(state.tiers["ETH"][0].max_leverage, 25);
+    }
+
+    #[tokio::test]
+    async fn test_spot_markets_resolve_mids_by_exchange_key() {
+        let (hl, _) = module(
+            MockApi::new()
+                .with_info(
+                    "spotMeta",
+                    json!({
+                        "tokens": [
+                            {"name": "USDC", "index": 0, "szDecimals": 8, "weiDecimals": 8},
+                            {"name": "PURR", "index": 1, "szDecimals": 0, "weiDecimals": 5},
+                            {"name": "HYPE", "index": 150, "szDecimals": 2, "weiDecimals": 8},
+                        ],
+                        "universe": [
+                            {"name": "PURR/USDC", "tokens": [1, 0], "index": 0},
+                            {"name": "@107", "tokens": [150, 0], "index": 107},
+                        ],
+                    }),
+                )
+                .with_info("allMids", json!({"PURR/USDC": "0.21", "@107": "31.25"})),
+        );
+
+        let markets = hl.spot_markets().await.unwrap();
+        assert_eq!(markets.len(), 2);
+        assert_eq!(markets[1].pair, "HYPE/USDC");
+        assert_eq!(markets[1].key, "@107");
+        assert_eq!(markets[1].sz_decimals, 2);
+        assert_eq!(markets[1].wei_decimals, 8);
+        assert_eq!(markets[1].mid_price, Some(dec("31.25")));
+        assert_eq!(markets[0].mid_price, Some(dec("0.21")));
+
+        let info = hl.spot_market_info("@107").await.unwrap();
+        assert_eq!(info.pair, "HYPE/USDC");
+        assert_eq!(info.mid_price, dec("31.25"));
+    }
+}
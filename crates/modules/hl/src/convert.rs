@@ -39,8 +39,8 @@ pub fn format_timestamp_ms(ms: u64) -> String {
     format!("{y:04}-{m:02}-{d:02} {hours:02}:{minutes:02}:{seconds:02}")
 }
 
-/// Convert HL PerpMarket to universal Market.
-pub fn perp_market_to_universal(m: &hypersdk::hypercore::PerpMarket) -> Market {
+/// Convert HL perp market metadata to universal Market.
+pub fn perp_market_to_universal(m: &crate::meta::MarketMeta) -> Market {
     Market {
         symbol: format!("{}-PERP", m.name),
         base: m.name.clone(),
@@ -53,7 +53,7 @@ pub fn perp_market_to_universal(m: &hypersdk::hypercore::PerpMarket) -> Market {
         volume_24h: None,
         open_interest: None,
         funding_rate: None,
-        max_leverage: Some(m.max_leverage as u32),
+        max_leverage: Some(m.max_leverage),
         min_size: None,
         tick_size: None,
         sz_decimals: Some(m.sz_decimals as i32),
@@ -0,0 +1,224 @@
+//! Fill history: `userFills` / `userFillsByTime` parsing and pagination.
+//!
+//! `userFills` only returns the most recent fills. A time range goes through
+//! `userFillsByTime` instead, which serves at most 2000 fills per call,
+//! oldest first from `startTime`; the range is walked forward one page at a
+//! time until it is covered or enough fills are collected.
+
+use std::collections::HashSet;
+use std::future::Future;
+
+use atlas_core::error::AtlasError;
+use atlas_core::types::{Fill, Protocol, Side};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Most fills Hyperliquid returns for one `userFillsByTime` call.
+pub const FILLS_PER_REQUEST: usize = 2000;
+
+/// Fills from a `userFills` or `userFillsByTime` response.
+pub fn parse_fills(resp: Value) -> Result<Vec<Fill>, AtlasError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Raw {
+        coin: String,
+        px: Decimal,
+        sz: Decimal,
+        side: String,
+        time: u64,
+        fee: Decimal,
+        closed_pnl: Option<Decimal>,
+        oid: u64,
+        hash: Option<String>,
+    }
+
+    let raw: Vec<Raw> = serde_json::from_value(resp)
+        .map_err(|e| AtlasError::Network(format!("unexpected userFills shape: {e}")))?;
+    Ok(raw
+        .into_iter()
+        .map(|r| Fill {
+            protocol: Protocol::Hyperliquid,
+            symbol: r.coin,
+            side: if r.side == "B" { Side::Buy } else { Side::Sell },
+            price: r.px,
+            size: r.sz,
+            fee: r.fee,
+            realized_pnl: r.closed_pnl,
+            order_id: r.oid.to_string(),
+            tx_hash: r.hash,
+            timestamp_ms: r.time,
+        })
+        .collect())
+}
+
+/// The first `limit` fills in `[start_ms, end_ms]` that `keep` accepts, in
+/// chronological order.
+///
+/// `fetch(start_ms)` returns one page. Each next page starts at the newest
+/// fill seen so far, so fills sharing that millisecond are not lost; the
+/// overlap is deduped. Stops at a short page (the range is covered), at
+/// `end_ms`, or once `limit` fills are kept.
+pub async fn fetch_range<F, Fut>(
+    start_ms: u64,
+    end_ms: Option<u64>,
+    limit: usize,
+    keep: impl Fn(&Fill) -> bool,
+    mut fetch: F,
+) -> Result<Vec<Fill>, AtlasError>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<Vec<Fill>, AtlasError>>,
+{
+    let in_range =
+        |f: &Fill| f.timestamp_ms >= start_ms && end_ms.map_or(true, |e| f.timestamp_ms <= e);
+    let mut fills = Vec::new();
+    let mut seen = HashSet::new();
+    let mut start = start_ms;
+    while fills.len() < limit {
+        let page = fetch(start).await?;
+        let full = page.len() >= FILLS_PER_REQUEST;
+        let Some(newest) = page.iter().map(|f| f.timestamp_ms).max() else {
+            break;
+        };
+        for f in page {
+            let key = (
+                f.tx_hash.clone(),
+                f.order_id.clone(),
+                f.timestamp_ms,
+                f.size,
+                f.price,
+            );
+            if in_range(&f) && keep(&f) && seen.insert(key) {
+                fills.push(f);
+            }
+        }
+        if !full || end_ms.is_some_and(|e| newest >= e) {
+            break;
+        }
+        // A full page within a single millisecond would repeat forever
+        start = if newest > start { newest } else { start + 1 };
+    }
+
+    fills.sort_by_key(|f| f.timestamp_ms);
+    fills.truncate(limit);
+    Ok(fills)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn fill(time: u64, coin: &str, tid: u64) -> Fill {
+        Fill {
+            protocol: Protocol::Hyperliquid,
+            symbol: coin.into(),
+            side: Side::Buy,
+            price: Decimal::ONE,
+            size: Decimal::ONE,
+            fee: Decimal::ZERO,
+            realized_pnl: None,
+            order_id: tid.to_string(),
+            tx_hash: Some(format!("0x{tid:x}")),
+            timestamp_ms: time,
+        }
+    }
+
+    /// Mock exchange: `n` fills one second apart, alternating BTC/ETH,
+    /// serving at most `FILLS_PER_REQUEST` per call from `start`.
+    struct MockExchange {
+        fills: Vec<Fill>,
+        calls: RefCell<Vec<u64>>,
+    }
+
+    impl MockExchange {
+        fn new(n: u64) -> Self {
+            let coin = |i: u64| if i % 2 == 0 { "BTC" } else { "ETH" };
+            Self {
+                fills: (0..n).map(|i| fill(i * 1000, coin(i), i)).collect(),
+                calls: RefCell::new(Vec::new()),
+            }
+        }
+
+        async fn by_time(&self, start: u64) -> Result<Vec<Fill>, AtlasError> {
+            self.calls.borrow_mut().push(start);
+            Ok(self
+                .fills
+                .iter()
+                .filter(|f| f.timestamp_ms >= start)
+                .take(FILLS_PER_REQUEST)
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_parse_fills() {
+        let resp = serde_json::json!([{
+            "coin": "ETH", "px": "3500.5", "sz": "0.1", "side": "A", "time": 1767254400000u64,
+            "startPosition": "0.1", "dir": "Close Long", "closedPnl": "12.5",
+            "hash": "0xabc", "oid": 42, "crossed": true, "fee": "0.157", "tid": 7,
+            "feeToken": "USDC"
+        }]);
+        let fills = parse_fills(resp).unwrap();
+        assert_eq!(fills.len(), 1);
+        let f = &fills[0];
+        assert_eq!(f.side, Side::Sell);
+        assert_eq!(f.price, "3500.5".parse::<Decimal>().unwrap());
+        assert_eq!(f.realized_pnl, Some("12.5".parse().unwrap()));
+        assert_eq!(f.order_id, "42");
+        assert_eq!(f.timestamp_ms, 1_767_254_400_000);
+        assert!(parse_fills(serde_json::json!({"error": "bad"})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pages_until_range_covered() {
+        let mock = MockExchange::new(4500);
+        let fills = fetch_range(0, None, 10_000, |_| true, |s| mock.by_time(s))
+            .await
+            .unwrap();
+        assert_eq!(fills.len(), 4500);
+        assert!(fills
+            .windows(2)
+            .all(|w| w[1].timestamp_ms > w[0].timestamp_ms));
+        // Two full pages, then a short one
+        assert_eq!(mock.calls.borrow().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stops_at_limit_and_range_end() {
+        let mock = MockExchange::new(4500);
+        let fills = fetch_range(0, None, 2500, |_| true, |s| mock.by_time(s))
+            .await
+            .unwrap();
+        assert_eq!(fills.len(), 2500);
+        assert_eq!(fills.last().unwrap().timestamp_ms, 2499 * 1000);
+        assert_eq!(mock.calls.borrow().len(), 2);
+
+        let mock = MockExchange::new(4500);
+        let fills = fetch_range(
+            100_000,
+            Some(199_000),
+            10_000,
+            |_| true,
+            |s| mock.by_time(s),
+        )
+        .await
+        .unwrap();
+        assert_eq!(fills.len(), 100);
+        assert_eq!(fills[0].timestamp_ms, 100_000);
+        assert_eq!(mock.calls.borrow().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_coin_filter_keeps_paging() {
+        let mock = MockExchange::new(4500);
+        let fills = fetch_range(0, None, 1500, |f| f.symbol == "ETH", |s| mock.by_time(s))
+            .await
+            .unwrap();
+        assert_eq!(fills.len(), 1500);
+        assert!(fills.iter().all(|f| f.symbol == "ETH"));
+        assert_eq!(mock.calls.borrow().len(), 2);
+    }
+}
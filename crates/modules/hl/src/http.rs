@@ -0,0 +1,486 @@
+//! Shared HTTP layer for Hyperliquid `/info` and `/exchange` calls.
+//!
+//! One pooled `reqwest::Client` per module with a configurable timeout,
+//! failure classification, and exponential backoff with jitter.
+//! Reads are retried here; order submission drives its own loop in
+//! `client.rs` so it can check by cloid before resending. `/info` requests
+//! — raw and through hypersdk — also pass the module's [`InfoThrottle`].
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use atlas_core::config::HttpRetryConfig;
+use atlas_core::error::AtlasError;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::throttle::InfoThrottle;
+
+/// Upper bound for a single backoff sleep.
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Retry / timeout policy.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Extra attempts after the first one.
+    pub max_retries: u32,
+    /// Per-request timeout.
+    pub timeout: Duration,
+    /// Base backoff, doubled per attempt.
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from(&HttpRetryConfig::default())
+    }
+}
+
+impl From<&HttpRetryConfig> for RetryPolicy {
+    fn from(cfg: &HttpRetryConfig) -> Self {
+        Self {
+            max_retries: cfg.max_retries,
+            timeout: Duration::from_secs(cfg.timeout_secs.max(1)),
+            backoff: Duration::from_millis(cfg.backoff_ms),
+        }
+    }
+}
+
+/// Why a single attempt failed.
+#[derive(Debug)]
+pub enum HttpFailure {
+    /// Never reached the server (connect error) — safe to resend.
+    NotSent(String),
+    /// May have been processed (timeout after send, dropped connection, 5xx).
+    Ambiguous(String),
+    /// Definitive 4xx — never retried, except 429 (rate limited, not
+    /// processed).
+    Rejected { status: u16, body: String },
+}
+
+impl HttpFailure {
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            HttpFailure::Rejected { status, .. } => *status == 429,
+            _ => true,
+        }
+    }
+
+    pub fn into_error(self, context: &str) -> AtlasError {
+        match self {
+            HttpFailure::NotSent(msg) => AtlasError::Network(format!("{context}: {msg}")),
+            HttpFailure::Ambiguous(msg) => AtlasError::ProtocolTimeout(format!("{context}: {msg}")),
+            HttpFailure::Rejected { status: 429, body } => {
+                AtlasError::RateLimited(format!("{context}: {body}"))
+            }
+            HttpFailure::Rejected { status, body } => AtlasError::Protocol {
+                protocol: "hyperliquid".into(),
+                message: format!("HTTP {status}: {body}"),
+            },
+        }
+    }
+}
+
+/// Whether an error message from hypersdk reports HTTP 429.
+pub fn is_rate_limited(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("429")
+        || message.contains("too many requests")
+        || message.contains("rate limit")
+}
+
+/// Pooled HTTP client with a retry policy and the `/info` throttle.
+#[derive(Debug, Clone)]
+pub struct ExchangeHttp {
+    client: reqwest::Client,
+    policy: RetryPolicy,
+    throttle: Arc<InfoThrottle>,
+}
+
+impl ExchangeHttp {
+    /// Fails if the HTTP client can't be built (e.g. no TLS backend).
+    pub fn new(policy: RetryPolicy) -> Result<Self, AtlasError> {
+        let client = reqwest::Client::builder()
+            .timeout(policy.timeout)
+            .build()
+            .map_err(|e| AtlasError::Other(format!("Failed to build HTTP client: {e}")))?;
+        Ok(Self {
+            client,
+            policy,
+            throttle: Arc::default(),
+        })
+    }
+
+    /// Share `throttle` instead of the default one.
+    pub fn with_throttle(mut self, throttle: Arc<InfoThrottle>) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    pub fn throttle(&self) -> Arc<InfoThrottle> {
+        self.throttle.clone()
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.policy.max_retries
+    }
+
+    /// Sleep before retry `attempt` (1-based): `backoff × 2^(attempt-1)` plus
+    /// up to one `backoff` of jitter, capped at [`MAX_BACKOFF`].
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        use rand::Rng;
+        let base = self.policy.backoff.as_millis() as u64;
+        let exp = base.saturating_mul(1u64 << attempt.saturating_sub(1).min(16));
+        let jitter = if base > 0 {
+            rand::thread_rng().gen_range(0..base)
+        } else {
+            0
+        };
+        Duration::from_millis(exp.saturating_add(jitter)).min(MAX_BACKOFF)
+    }
+
+    /// One POST attempt, with the failure classified for the caller.
+    pub async fn post_once(&self, url: &str, body: &Value) -> Result<String, HttpFailure> {
+        self.send_once(self.client.post(url).json(body)).await
+    }
+
+    async fn send_once(&self, request: reqwest::RequestBuilder) -> Result<String, HttpFailure> {
+        let resp = request.send().await.map_err(|e| {
+            if e.is_connect() {
+                HttpFailure::NotSent(e.to_string())
+            } else {
+                HttpFailure::Ambiguous(e.to_string())
+            }
+        })?;
+
+        let status = resp.status();
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| HttpFailure::Ambiguous(e.to_string()))?;
+
+        if status.is_server_error() {
+            return Err(HttpFailure::Ambiguous(format!("HTTP {status}: {text}")));
+        }
+        if !status.is_success() {
+            return Err(HttpFailure::Rejected {
+                status: status.as_u16(),
+                body: text,
+            });
+        }
+        Ok(text)
+    }
+
+    /// POST a side-effect-free request, retrying any retryable failure.
+    pub async fn post_idempotent(&self, url: &str, body: &Value) -> Result<String, AtlasError> {
+        self.post_retrying(url, body, false).await
+    }
+
+    async fn post_retrying(
+        &self,
+        url: &str,
+        body: &Value,
+        throttled: bool,
+    ) -> Result<String, AtlasError> {
+        let context = body
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or("request");
+        self.retrying(context, throttled, || self.post_once(url, body))
+            .await
+    }
+
+    /// Run `send` until it succeeds or fails for good. `context` names the
+    /// request in logs and errors, and is its throttle cost when `throttled`.
+    async fn retrying<F, Fut>(
+        &self,
+        context: &str,
+        throttled: bool,
+        mut send: F,
+    ) -> Result<String, AtlasError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<String, HttpFailure>>,
+    {
+        let mut attempt = 0;
+        loop {
+            if throttled {
+                self.throttle.acquire(context).await;
+            }
+            match send().await {
+                Ok(text) => return Ok(text),
+                Err(failure) if failure.is_retryable() && attempt < self.policy.max_retries => {
+                    attempt += 1;
+                    warn!(%context, attempt, ?failure, "HL request failed, retrying");
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                }
+                Err(failure) => return Err(failure.into_error(context)),
+            }
+        }
+    }
+
+    /// [`post_idempotent`](Self::post_idempotent) and parse the body as JSON.
+    pub async fn post_json(&self, url: &str, body: &Value) -> Result<Value, AtlasError> {
+        let text = self.post_idempotent(url, body).await?;
+        parse_json(text)
+    }
+
+    /// [`post_json`](Self::post_json) for an `/info` request: every attempt
+    /// waits for the throttle first.
+    pub async fn post_info(&self, url: &str, body: &Value) -> Result<Value, AtlasError> {
+        let text = self.post_retrying(url, body, true).await?;
+        parse_json(text)
+    }
+
+    /// GET a JSON document outside `/info` (e.g. the stats endpoints the
+    /// web UI reads), retried and throttled like `request`.
+    pub async fn get_json(&self, url: &str, request: &str) -> Result<Value, AtlasError> {
+        let text = self
+            .retrying(request, true, || self.send_once(self.client.get(url)))
+            .await?;
+        parse_json(text)
+    }
+
+    /// Run a hypersdk `/info` call (`request` is its `type`) behind the
+    /// throttle, retrying with backoff while Hyperliquid answers 429.
+    pub async fn info<T, E, F, Fut>(&self, request: &str, mut call: F) -> Result<T, E>
+    where
+        E: std::fmt::Display,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempt = 0;
+        loop {
+            self.throttle.acquire(request).await;
+            match call().await {
+                Err(e) if attempt < self.policy.max_retries && is_rate_limited(&e.to_string()) => {
+                    attempt += 1;
+                    warn!(request, attempt, "HL rate limited, retrying");
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Local clock minus the server's, in ms, read from the `Date` header
+    /// of one POST. Local time is taken at the midpoint of the round trip;
+    /// `Date` has whole-second resolution, so ±1s is noise.
+    pub async fn clock_skew_ms(&self, url: &str, body: &Value) -> Result<i64, AtlasError> {
+        let sent = chrono::Utc::now().timestamp_millis();
+        let resp = self
+            .client
+            .post(url)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| AtlasError::Network(format!("{url}: {e}")))?;
+        let received = chrono::Utc::now().timestamp_millis();
+        let date = resp
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| AtlasError::Network(format!("{url}: response has no Date header")))?;
+        skew_from_date(date, sent + (received - sent) / 2)
+            .ok_or_else(|| AtlasError::Network(format!("{url}: unparseable Date header '{date}'")))
+    }
+}
+
+fn parse_json(text: String) -> Result<Value, AtlasError> {
+    serde_json::from_str(&text).map_err(|_| AtlasError::Protocol {
+        protocol: "hyperliquid".into(),
+        message: format!("Bad response: {text}"),
+    })
+}
+
+/// `local_ms` minus the time in an HTTP `Date` header
+/// (`Sun, 06 Nov 1994 08:49:37 GMT`).
+pub fn skew_from_date(date: &str, local_ms: i64) -> Option<i64> {
+    let server = chrono::DateTime::parse_from_rfc2822(date).ok()?;
+    Some(local_ms - server.timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Minimal HTTP server: connection `n` is answered by `respond(n)`;
+    /// `None` drops the connection after reading the request.
+    async fn mock_server(
+        respond: fn(usize) -> Option<(u16, &'static str)>,
+    ) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut sock, _)) = listener.accept().await else {
+                    return;
+                };
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let mut buf = vec![0u8; 4096];
+                let _ = sock.read(&mut buf).await;
+                if let Some((status, body)) = respond(n) {
+                    let resp = format!(
+                        "HTTP/1.1 {status} X\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = sock.write_all(resp.as_bytes()).await;
+                }
+            }
+        });
+        (format!("http://{addr}/info"), hits)
+    }
+
+    fn fast_policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            timeout: Duration::from_secs(2),
+            backoff: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_after_dropped_request() {
+        let (url, hits) = mock_server(|n| (n > 0).then_some((200, r#"{"ok":1}"#))).await;
+        let http = ExchangeHttp::new(fast_policy(2)).unwrap();
+        let v = http
+            .post_json(&url, &serde_json::json!({"type": "meta"}))
+            .await
+            .unwrap();
+        assert_eq!(v["ok"], 1);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_request_is_ambiguous() {
+        let (url, _) = mock_server(|_| None).await;
+        let http = ExchangeHttp::new(fast_policy(0)).unwrap();
+        let err = http
+            .post_once(&url, &serde_json::json!({"type": "order"}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HttpFailure::Ambiguous(_)));
+    }
+
+    #[tokio::test]
+    async fn test_retries_5xx_not_4xx() {
+        let (url, hits) =
+            mock_server(|n| Some(if n == 0 { (502, "bad") } else { (200, "{}") })).await;
+        let http = ExchangeHttp::new(fast_policy(2)).unwrap();
+        http.post_json(&url, &serde_json::json!({"type": "meta"}))
+            .await
+            .unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        let (url, hits) = mock_server(|_| Some((422, "bad request"))).await;
+        let err = http
+            .post_json(&url, &serde_json::json!({"type": "meta"}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AtlasError::Protocol { .. }));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_429() {
+        let (url, hits) = mock_server(|n| {
+            Some(if n == 0 {
+                (429, "slow down")
+            } else {
+                (200, "{}")
+            })
+        })
+        .await;
+        let http = ExchangeHttp::new(fast_policy(2)).unwrap();
+        http.post_info(&url, &serde_json::json!({"type": "allMids"}))
+            .await
+            .unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        let (url, _) = mock_server(|_| Some((429, "slow down"))).await;
+        let err = http
+            .post_info(&url, &serde_json::json!({"type": "allMids"}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AtlasError::RateLimited(_)));
+    }
+
+    #[tokio::test]
+    async fn test_info_call_retries_rate_limit_only() {
+        let http = ExchangeHttp::new(fast_policy(2)).unwrap();
+        let calls = AtomicUsize::new(0);
+        let result: Result<u32, String> = http
+            .info("allMids", || async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err("HTTP status client error (429 Too Many Requests)".to_string()),
+                    _ => Ok(7),
+                }
+            })
+            .await;
+        assert_eq!(result, Ok(7));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<u32, String> = http
+            .info("allMids", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err("unknown coin".to_string())
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_json_retries() {
+        let (url, hits) =
+            mock_server(|n| Some(if n == 0 { (502, "bad") } else { (200, "[1,2]") })).await;
+        let http = ExchangeHttp::new(fast_policy(2)).unwrap();
+        let v = http.get_json(&url, "vaults").await.unwrap();
+        assert_eq!(v, serde_json::json!([1, 2]));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let (url, hits) = mock_server(|_| Some((503, "down"))).await;
+        let http = ExchangeHttp::new(fast_policy(3)).unwrap();
+        let err = http
+            .post_json(&url, &serde_json::json!({"type": "meta"}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AtlasError::ProtocolTimeout(_)));
+        assert_eq!(hits.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let http = ExchangeHttp::new(RetryPolicy {
+            max_retries: 10,
+            timeout: Duration::from_secs(1),
+            backoff: Duration::from_millis(100),
+        })
+        .unwrap();
+        let first = http.backoff(1);
+        assert!(first >= Duration::from_millis(100) && first < Duration::from_millis(200));
+        let third = http.backoff(3);
+        assert!(third >= Duration::from_millis(400) && third < Duration::from_millis(500));
+        assert_eq!(http.backoff(12), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_skew_from_date() {
+        let date = "Sun, 06 Nov 1994 08:49:37 GMT";
+        assert_eq!(skew_from_date(date, 784_111_777_000), Some(0));
+        assert_eq!(skew_from_date(date, 784_111_778_500), Some(1_500));
+        assert_eq!(skew_from_date(date, 784_111_770_000), Some(-7_000));
+        assert_eq!(skew_from_date("yesterday", 0), None);
+    }
+}
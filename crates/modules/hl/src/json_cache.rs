@@ -0,0 +1,49 @@
+//! JSON snapshots in the workspace `data/` dir, reused until they are
+//! older than a TTL: the market metadata ([`MetaCache`](crate::meta::MetaCache))
+//! and the vault listing ([`VaultCache`](crate::vaults::VaultCache)).
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use atlas_core::date_range::now_ms;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// A snapshot that knows when it was fetched. Loading never fails: a
+/// missing or unreadable file is just no cache.
+pub trait JsonCache: Serialize + DeserializeOwned {
+    /// Unix ms of the fetch the snapshot holds.
+    fn fetched_at_ms(&self) -> u64;
+
+    /// Read a cache file. `None` if missing or unreadable.
+    fn load(path: &Path) -> Option<Self> {
+        let raw = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&raw).ok()
+    }
+
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string(self)?;
+        std::fs::write(path, json)
+    }
+
+    fn age(&self) -> Duration {
+        Duration::from_millis((now_ms() as u64).saturating_sub(self.fetched_at_ms()))
+    }
+
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        self.age() < ttl
+    }
+}
+
+/// `data/hl-<kind>-<network>.json` in the workspace.
+pub fn cache_path(kind: &str, testnet: bool) -> Option<PathBuf> {
+    atlas_core::workspace::resolve(&format!("data/hl-{kind}-{}.json", network_name(testnet))).ok()
+}
+
+pub(crate) fn network_name(testnet: bool) -> &'static str {
+    if testnet {
+        "testnet"
+    } else {
+        "mainnet"
+    }
+}
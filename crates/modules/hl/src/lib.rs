@@ -1,3 +1,17 @@
+pub mod api;
+pub mod asset_ctx;
+pub mod candles;
 pub mod client;
 pub mod convert;
+pub mod fills;
+pub mod http;
+pub mod json_cache;
+pub mod meta;
+pub mod modify;
+pub mod nonce;
+pub mod orders;
+pub mod proxy;
 mod signing;
+pub mod spot;
+pub mod throttle;
+pub mod vaults;
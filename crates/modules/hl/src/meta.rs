@@ -0,0 +1,200 @@
+//! Perp / spot metadata cache.
+//!
+//! The `meta` round-trip adds 300–600ms to every CLI call, so the perps list
+//! and spot token names are kept in `data/hl-meta-<network>.json` for
+//! `meta_ttl_secs`. A cache older than the TTL is never used for size or
+//! price rounding — it is refetched synchronously instead.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use atlas_core::date_range::now_ms;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::json_cache::{cache_path, network_name, JsonCache};
+
+/// Max price decimals for perps before subtracting `sz_decimals`.
+const PERP_MAX_DECIMALS: u32 = 6;
+
+/// Max price decimals for spot before subtracting `sz_decimals`.
+const SPOT_MAX_DECIMALS: u32 = 8;
+
+/// Max significant figures in a Hyperliquid price.
+const MAX_SIG_FIGS: u32 = 5;
+
+/// The fields of a perp market that trading needs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketMeta {
+    pub name: String,
+    pub index: usize,
+    pub sz_decimals: u32,
+    pub max_leverage: u32,
+}
+
+impl MarketMeta {
+    pub fn from_sdk(m: &hypersdk::hypercore::PerpMarket) -> Self {
+        Self {
+            name: m.name.clone(),
+            index: m.index,
+            sz_decimals: m.sz_decimals.max(0) as u32,
+            max_leverage: m.max_leverage as u32,
+        }
+    }
+
+    /// Round to a valid perp price: at most 5 significant figures and
+    /// `6 - sz_decimals` decimals. Integer prices are always valid.
+    pub fn round_price(&self, price: Decimal) -> Option<Decimal> {
+        round_to_tick(price, PERP_MAX_DECIMALS.saturating_sub(self.sz_decimals))
+    }
+}
+
+/// Round to a valid spot price: like perps, but `8 - sz_decimals` decimals.
+pub fn round_spot_price(price: Decimal, sz_decimals: u32) -> Option<Decimal> {
+    round_to_tick(price, SPOT_MAX_DECIMALS.saturating_sub(sz_decimals))
+}
+
+/// At most [`MAX_SIG_FIGS`] significant figures and `max_decimals` places.
+fn round_to_tick(price: Decimal, max_decimals: u32) -> Option<Decimal> {
+    if price <= Decimal::ZERO {
+        return None;
+    }
+    // Decimal places that keep MAX_SIG_FIGS significant figures
+    let mut sig_dp = MAX_SIG_FIGS as i64;
+    let mut p = price;
+    while p >= Decimal::ONE {
+        p /= Decimal::TEN;
+        sig_dp -= 1;
+    }
+    while p < Decimal::new(1, 1) {
+        p *= Decimal::TEN;
+        sig_dp += 1;
+    }
+    let dp = sig_dp.min(max_decimals as i64).max(0) as u32;
+    let rounded = price.round_dp(dp);
+    (!rounded.is_zero()).then(|| rounded.normalize())
+}
+
+/// On-disk metadata snapshot for one network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetaCache {
+    pub network: String,
+    pub fetched_at_ms: u64,
+    pub perps: Vec<MarketMeta>,
+    #[serde(default)]
+    pub spot_tokens: HashMap<usize, String>,
+}
+
+/// How the module should treat the cache on startup.
+#[derive(Debug, Clone)]
+pub struct MetaOptions {
+    pub ttl: Duration,
+    /// Ignore the cache and refetch (`--refresh-meta`).
+    pub refresh: bool,
+}
+
+impl Default for MetaOptions {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(600),
+            refresh: false,
+        }
+    }
+}
+
+impl MetaCache {
+    pub fn new(testnet: bool, perps: Vec<MarketMeta>) -> Self {
+        Self {
+            network: network_name(testnet).into(),
+            fetched_at_ms: now_ms() as u64,
+            perps,
+            spot_tokens: HashMap::new(),
+        }
+    }
+
+    /// Cache file for a network, inside the workspace `data/` dir.
+    pub fn path(testnet: bool) -> Option<PathBuf> {
+        cache_path("meta", testnet)
+    }
+}
+
+impl JsonCache for MetaCache {
+    fn fetched_at_ms(&self) -> u64 {
+        self.fetched_at_ms
+    }
+}
+
+/// True when a cache file exists but cannot be parsed.
+pub fn is_corrupt(path: &Path) -> bool {
+    path.exists() && MetaCache::load(path).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn market(sz_decimals: u32) -> MarketMeta {
+        MarketMeta {
+            name: "X".into(),
+            index: 0,
+            sz_decimals,
+            max_leverage: 10,
+        }
+    }
+
+    fn px(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_round_price_sig_figs() {
+        // BTC-like: 5 sig figs wins over decimals
+        assert_eq!(market(5).round_price(px("97123.456")), Some(px("97123")));
+        assert_eq!(market(4).round_price(px("3456.789")), Some(px("3456.8")));
+        // Above 5 integer digits the integer is kept as-is
+        assert_eq!(market(5).round_price(px("123456.7")), Some(px("123457")));
+    }
+
+    #[test]
+    fn test_round_price_max_decimals() {
+        // Small prices are capped by 6 - sz_decimals
+        assert_eq!(
+            market(0).round_price(px("0.000123456")),
+            Some(px("0.000123"))
+        );
+        assert_eq!(market(2).round_price(px("0.123456")), Some(px("0.1235")));
+        assert_eq!(market(0).round_price(px("0")), None);
+    }
+
+    #[test]
+    fn test_round_spot_price() {
+        // Spot allows two more decimals than perps
+        assert_eq!(
+            round_spot_price(px("0.000123456"), 0),
+            Some(px("0.00012346"))
+        );
+        assert_eq!(round_spot_price(px("0.000123456"), 2), Some(px("0.000123")));
+        assert_eq!(round_spot_price(px("31.23456"), 2), Some(px("31.235")));
+        assert_eq!(round_spot_price(px("-1"), 2), None);
+    }
+
+    #[test]
+    fn test_cache_freshness_and_corruption() {
+        let mut cache = MetaCache::new(false, vec![market(5)]);
+        assert!(cache.is_fresh(Duration::from_secs(600)));
+        cache.fetched_at_ms -= 601_000;
+        assert!(!cache.is_fresh(Duration::from_secs(600)));
+
+        let dir = std::env::temp_dir().join(format!("atlas-meta-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("hl-meta-mainnet.json");
+        cache.save(&path).unwrap();
+        assert_eq!(MetaCache::load(&path).unwrap().perps.len(), 1);
+        assert!(!is_corrupt(&path));
+        std::fs::write(&path, "{not json").unwrap();
+        assert!(is_corrupt(&path));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
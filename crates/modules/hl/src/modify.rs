@@ -0,0 +1,165 @@
+//! `batchModify` wire types.
+//!
+//! hypersdk has no modify action, so the action is built here and signed
+//! through the manual msgpack + Agent path (see `signing`). Field order is
+//! part of the signed hash, so these are structs, not `json!` maps.
+
+use atlas_core::error::AtlasError;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// Which order to amend: exchange oid or `0x…` client order ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum ModifyTarget {
+    Oid(u64),
+    Cloid(String),
+}
+
+impl ModifyTarget {
+    /// Parse a numeric oid or a 16-byte hex cloid.
+    pub fn parse(raw: &str) -> Result<Self, AtlasError> {
+        let raw = raw.trim();
+        if let Ok(oid) = raw.parse::<u64>() {
+            return Ok(Self::Oid(oid));
+        }
+        let hex = raw.strip_prefix("0x").unwrap_or(raw).replace('-', "");
+        if hex.len() == 32 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(Self::Cloid(format!("0x{}", hex.to_lowercase())));
+        }
+        Err(AtlasError::Other(format!(
+            "Invalid order reference '{raw}': expected an oid or a 0x… cloid"
+        )))
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchModify {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub modifies: Vec<ModifyWire>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModifyWire {
+    pub oid: ModifyTarget,
+    pub order: OrderWire,
+}
+
+/// One order in HL's compact wire format.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderWire {
+    /// Asset index.
+    pub a: usize,
+    /// Is buy.
+    pub b: bool,
+    /// Limit price.
+    pub p: String,
+    /// Size.
+    pub s: String,
+    /// Reduce only.
+    pub r: bool,
+    pub t: OrderTypeWire,
+    /// Client order ID, kept across the modify.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub c: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderTypeWire {
+    pub limit: LimitWire,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LimitWire {
+    /// `Gtc`, `Alo` or `Ioc`.
+    pub tif: String,
+}
+
+/// The replacement order for a modify: everything but price and size is
+/// carried over from the resting order.
+#[derive(Debug, Clone)]
+pub struct ModifySpec {
+    pub target: ModifyTarget,
+    pub asset: usize,
+    pub is_buy: bool,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub reduce_only: bool,
+    pub tif: String,
+    pub cloid: Option<String>,
+}
+
+impl BatchModify {
+    pub fn single(spec: ModifySpec) -> Self {
+        Self {
+            kind: "batchModify",
+            modifies: vec![ModifyWire {
+                oid: spec.target,
+                order: OrderWire {
+                    a: spec.asset,
+                    b: spec.is_buy,
+                    p: wire_decimal(spec.price),
+                    s: wire_decimal(spec.size),
+                    r: spec.reduce_only,
+                    t: OrderTypeWire {
+                        limit: LimitWire { tif: spec.tif },
+                    },
+                    c: spec.cloid,
+                },
+            }],
+        }
+    }
+}
+
+/// Decimal as HL signs it: no trailing zeros, no exponent.
+fn wire_decimal(d: Decimal) -> String {
+    d.normalize().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(target: ModifyTarget, cloid: Option<&str>) -> ModifySpec {
+        ModifySpec {
+            target,
+            asset: 4,
+            is_buy: true,
+            price: Decimal::new(3_150_500, 3),
+            size: Decimal::new(2500, 4),
+            reduce_only: false,
+            tif: "Gtc".into(),
+            cloid: cloid.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(
+            ModifyTarget::parse("123456").unwrap(),
+            ModifyTarget::Oid(123456)
+        );
+        assert_eq!(
+            ModifyTarget::parse("0x1234567890ABCDEF1234567890abcdef").unwrap(),
+            ModifyTarget::Cloid("0x1234567890abcdef1234567890abcdef".into())
+        );
+        assert!(ModifyTarget::parse("0x1234").is_err());
+        assert!(ModifyTarget::parse("abc").is_err());
+    }
+
+    #[test]
+    fn test_wire_shape_and_field_order() {
+        let action = BatchModify::single(spec(ModifyTarget::Oid(77), None));
+        assert_eq!(
+            serde_json::to_string(&action).unwrap(),
+            r#"{"type":"batchModify","modifies":[{"oid":77,"order":{"a":4,"b":true,"p":"3150.5","s":"0.25","r":false,"t":{"limit":{"tif":"Gtc"}}}}]}"#
+        );
+
+        let cloid = "0x1234567890abcdef1234567890abcdef";
+        let action = BatchModify::single(spec(ModifyTarget::Cloid(cloid.into()), Some(cloid)));
+        let json = serde_json::to_value(&action).unwrap();
+        assert_eq!(json["modifies"][0]["oid"], cloid);
+        assert_eq!(json["modifies"][0]["order"]["c"], cloid);
+    }
+}
@@ -0,0 +1,180 @@
+//! Exchange nonces shared by every atlas process signing for a wallet.
+//!
+//! Hyperliquid nonces are millisecond timestamps and the exchange refuses
+//! one it has already seen for the signer. hypersdk's `NonceHandler` only
+//! orders nonces within a process, so two commands started in the same
+//! millisecond — an agent running trades in parallel — could collide.
+//! [`NonceSource`] reserves each nonce in the workspace database instead:
+//! `max(now_ms, last + 1)` under SQLite's write lock.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use alloy::primitives::Address;
+use atlas_core::db::AtlasDb;
+use tracing::{debug, warn};
+
+/// Nonce sequence for one wallet.
+#[derive(Default)]
+pub struct NonceSource {
+    /// Wallet the persisted sequence belongs to; `None` stays in memory.
+    address: Option<String>,
+    db: OnceLock<Option<Mutex<AtlasDb>>>,
+    /// Last nonce handed out by this process, the floor for the next one.
+    last: AtomicU64,
+}
+
+impl NonceSource {
+    /// Sequence for `address` on `~/.atlas-os/data/atlas.db`, opened on
+    /// first use.
+    pub fn persistent(address: Address) -> Self {
+        Self {
+            address: Some(format!("{address:?}")),
+            ..Self::default()
+        }
+    }
+
+    /// Sequence for `address` on an already-open database.
+    pub fn with_db(db: AtlasDb, address: &str) -> Self {
+        Self {
+            address: Some(address.to_string()),
+            db: OnceLock::from(Some(Mutex::new(db))),
+            last: AtomicU64::new(0),
+        }
+    }
+
+    /// The next nonce: strictly above every nonce issued for the wallet so
+    /// far, by this or any other atlas process. If the database fails the
+    /// sequence continues in memory.
+    pub fn next(&self) -> u64 {
+        let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        if let Some(address) = &self.address {
+            match self.reserve(address, now_ms) {
+                Ok(nonce) => {
+                    self.last.fetch_max(nonce, Ordering::SeqCst);
+                    return nonce;
+                }
+                Err(e) => debug!("Nonce kept in memory: {e}"),
+            }
+        }
+        let prev = self
+            .last
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+                Some(now_ms.max(last + 1))
+            })
+            .unwrap_or_default();
+        now_ms.max(prev + 1)
+    }
+
+    fn reserve(&self, address: &str, now_ms: u64) -> anyhow::Result<u64> {
+        let db = self.db.get_or_init(|| match AtlasDb::open() {
+            Ok(db) => Some(Mutex::new(db)),
+            Err(e) => {
+                warn!("Nonce store unavailable: {e}");
+                None
+            }
+        });
+        let db = db
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("database unavailable"))?
+            .lock()
+            .map_err(|_| anyhow::anyhow!("nonce store lock poisoned"))?;
+        let floor = now_ms.max(self.last.load(Ordering::SeqCst) + 1);
+        db.next_nonce(address, floor)
+    }
+}
+
+/// Hyperliquid only accepts nonces up to a day ahead of its clock.
+const MAX_AHEAD_MS: i64 = 86_400_000;
+
+/// Whether a stored last nonce is unusable: not a timestamp, or so far
+/// ahead of the clock that every nonce after it would be refused.
+pub fn is_corrupt(last_nonce: i64, now_ms: i64) -> bool {
+    last_nonce <= 0 || last_nonce > now_ms + MAX_AHEAD_MS
+}
+
+/// Whether an exchange rejection is about the nonce (already used, or
+/// older than the ones the exchange still accepts) — worth one re-sign.
+pub fn is_nonce_rejection(message: &str) -> bool {
+    message.to_ascii_lowercase().contains("nonce")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_in_memory_nonces_strictly_increase() {
+        let source = NonceSource::default();
+        let mut prev = 0;
+        for _ in 0..1_000 {
+            let nonce = source.next();
+            assert!(nonce > prev);
+            prev = nonce;
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    async fn test_concurrent_sources_never_share_a_nonce() {
+        // Each source stands in for a separate atlas process on the same
+        // workspace database, each running many tasks at once.
+        let path = std::env::temp_dir().join(format!("atlas-nonces-{}.db", std::process::id()));
+        let remove_db = || {
+            for ext in ["", "-wal", "-shm"] {
+                let _ = std::fs::remove_file(format!("{}{ext}", path.display()));
+            }
+        };
+        // A file left by an earlier run with this pid would skew the nonces
+        remove_db();
+        AtlasDb::open_at(&path).unwrap();
+        let sources: Vec<Arc<NonceSource>> = (0..4)
+            .map(|_| {
+                Arc::new(NonceSource::with_db(
+                    AtlasDb::open_at(&path).unwrap(),
+                    "0xabc",
+                ))
+            })
+            .collect();
+
+        let mut tasks = Vec::new();
+        for source in &sources {
+            for _ in 0..16 {
+                let source = Arc::clone(source);
+                tasks.push(tokio::task::spawn_blocking(move || {
+                    (0..25).map(|_| source.next()).collect::<Vec<u64>>()
+                }));
+            }
+        }
+        let mut all = Vec::new();
+        for task in tasks {
+            let batch = task.await.unwrap();
+            assert!(batch.windows(2).all(|w| w[0] < w[1]));
+            all.extend(batch);
+        }
+
+        let issued = all.len();
+        all.sort_unstable();
+        all.dedup();
+        assert_eq!(all.len(), issued, "a nonce was handed out twice");
+
+        drop(sources);
+        remove_db();
+    }
+
+    #[test]
+    fn test_corrupt_nonce() {
+        let now = 1_700_000_000_000;
+        assert!(!is_corrupt(now - 5_000, now));
+        assert!(!is_corrupt(now + 60_000, now));
+        assert!(is_corrupt(now + 2 * MAX_AHEAD_MS, now));
+        assert!(is_corrupt(-1, now));
+    }
+
+    #[test]
+    fn test_nonce_rejection() {
+        assert!(is_nonce_rejection("Invalid nonce: duplicate nonce"));
+        assert!(is_nonce_rejection("Nonce too low"));
+        assert!(!is_nonce_rejection("Insufficient margin to place order."));
+    }
+}
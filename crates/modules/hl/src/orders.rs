@@ -0,0 +1,126 @@
+//! Open orders from `frontendOpenOrders`.
+//!
+//! Unlike `openOrders`, the frontend variant says which orders are
+//! triggers and at what price, so resting stops can be told apart from
+//! plain limits.
+
+use std::str::FromStr;
+
+use atlas_core::error::AtlasError;
+use atlas_core::types::{MarketType, Order, OrderStatus, OrderType, Protocol, Side};
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::spot::is_spot_key;
+
+/// Parse a `frontendOpenOrders` response. `symbol` is the API coin key
+/// (`@N` for most spot pairs); the caller names spot pairs.
+pub fn parse_open_orders(resp: &Value) -> Result<Vec<Order>, AtlasError> {
+    let rows = resp.as_array().ok_or_else(|| AtlasError::Protocol {
+        protocol: "hyperliquid".into(),
+        message: format!("frontendOpenOrders is not a list: {resp}"),
+    })?;
+    rows.iter().map(parse_order).collect()
+}
+
+fn parse_order(o: &Value) -> Result<Order, AtlasError> {
+    let missing = |key: &str| AtlasError::Protocol {
+        protocol: "hyperliquid".into(),
+        message: format!("open order without {key}: {o}"),
+    };
+    let decimal = |key: &str| {
+        o.get(key)
+            .and_then(Value::as_str)
+            .and_then(|s| Decimal::from_str(s).ok())
+    };
+    let coin = o
+        .get("coin")
+        .and_then(Value::as_str)
+        .ok_or_else(|| missing("coin"))?;
+    let is_trigger = o.get("isTrigger").and_then(Value::as_bool) == Some(true);
+    // "Stop Market", "Take Profit Limit", ...
+    let order_type = match o.get("orderType").and_then(Value::as_str) {
+        _ if !is_trigger => OrderType::Limit,
+        Some(kind) if kind.ends_with("Limit") => OrderType::StopLimit,
+        _ => OrderType::StopMarket,
+    };
+
+    Ok(Order {
+        protocol: Protocol::Hyperliquid,
+        symbol: coin.to_string(),
+        market_type: if is_spot_key(coin) {
+            MarketType::Spot
+        } else {
+            MarketType::Perp
+        },
+        side: if o.get("side").and_then(Value::as_str) == Some("B") {
+            Side::Buy
+        } else {
+            Side::Sell
+        },
+        order_type,
+        size: decimal("sz").ok_or_else(|| missing("sz"))?,
+        price: decimal("limitPx"),
+        trigger_price: if is_trigger {
+            decimal("triggerPx")
+        } else {
+            None
+        },
+        filled_size: None,
+        status: OrderStatus::Open,
+        order_id: o
+            .get("oid")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| missing("oid"))?
+            .to_string(),
+        timestamp_ms: o.get("timestamp").and_then(Value::as_u64).unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_limit_and_trigger_orders() {
+        let resp = serde_json::json!([
+            {"coin": "ETH", "side": "B", "limitPx": "3400.0", "sz": "0.5", "oid": 11,
+             "timestamp": 1700000000000u64, "isTrigger": false, "triggerPx": "0.0",
+             "orderType": "Limit", "reduceOnly": false, "origSz": "0.5"},
+            {"coin": "ETH", "side": "B", "limitPx": "3636.0", "sz": "0.5", "oid": 12,
+             "timestamp": 1700000000001u64, "isTrigger": true, "triggerPx": "3600.0",
+             "orderType": "Stop Market", "reduceOnly": false, "origSz": "0.5"},
+            {"coin": "BTC", "side": "A", "limitPx": "59000.0", "sz": "0.01", "oid": 13,
+             "timestamp": 1700000000002u64, "isTrigger": true, "triggerPx": "59500.0",
+             "orderType": "Stop Limit", "reduceOnly": true, "origSz": "0.01"},
+            {"coin": "@107", "side": "A", "limitPx": "30.0", "sz": "2", "oid": 14,
+             "timestamp": 1700000000003u64}
+        ]);
+        let orders = parse_open_orders(&resp).unwrap();
+        assert_eq!(orders.len(), 4);
+
+        assert_eq!(orders[0].order_type, OrderType::Limit);
+        assert_eq!(orders[0].trigger_price, None);
+        assert_eq!(orders[0].side, Side::Buy);
+
+        assert_eq!(orders[1].order_type, OrderType::StopMarket);
+        assert_eq!(orders[1].trigger_price, Some(Decimal::from(3600)));
+        assert_eq!(orders[1].price, Some(Decimal::from(3636)));
+
+        assert_eq!(orders[2].order_type, OrderType::StopLimit);
+        assert_eq!(orders[2].side, Side::Sell);
+        assert_eq!(orders[2].order_id, "13");
+
+        assert_eq!(orders[3].market_type, MarketType::Spot);
+        assert_eq!(orders[3].order_type, OrderType::Limit);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        assert!(parse_open_orders(&serde_json::json!({"error": "x"})).is_err());
+        assert!(parse_open_orders(&serde_json::json!([{"coin": "ETH", "sz": "1"}])).is_err());
+        assert!(parse_open_orders(&serde_json::json!([]))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,383 @@
+//! Market data through the Atlas backend (`data_via_backend`).
+//!
+//! The backend's `/atlas-os/hl` routes forward a whitelisted set of `/info`
+//! requests and return Hyperliquid's bodies unchanged, so the parsers here
+//! read direct `/info` responses just the same. Anything that signs or reads
+//! an account is not proxied and stays direct-only.
+
+use std::collections::HashMap;
+
+use atlas_core::error::AtlasError;
+use atlas_core::types::{BookLevel, Candle, FundingRate, MarginTier, OrderBook, Protocol};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::meta::MarketMeta;
+
+/// Mount point of the proxy routes on the backend.
+pub const PROXY_PATH: &str = "/atlas-os/hl";
+
+/// Why signing and account queries are refused in proxy-only mode.
+pub const PROXY_ONLY_MESSAGE: &str = "Hyperliquid data is routed through the Atlas backend \
+     (data_via_backend), which only serves market data; orders and account queries need direct \
+     access to api.hyperliquid.xyz";
+
+/// Backend path and query string for an `/info` request body. `None` for
+/// request types the proxy does not serve.
+pub fn proxy_route(body: &Value) -> Option<(String, Vec<(String, String)>)> {
+    let field = |v: &Value, key: &str| -> Option<String> {
+        match v.get(key)? {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    };
+    let route = |path: &str, query: Vec<(&str, Option<String>)>| {
+        let query = query
+            .into_iter()
+            .filter_map(|(k, v)| Some((k.to_string(), v?)))
+            .collect();
+        Some((format!("{PROXY_PATH}{path}"), query))
+    };
+
+    match body.get("type")?.as_str()? {
+        "allMids" => route("/all-mids", vec![]),
+        "metaAndAssetCtxs" => route("/meta-and-asset-ctxs", vec![]),
+        "l2Book" => route("/l2-book", vec![("coin", Some(field(body, "coin")?))]),
+        "candleSnapshot" => {
+            let req = body.get("req")?;
+            route(
+                "/candles",
+                vec![
+                    ("coin", Some(field(req, "coin")?)),
+                    ("interval", Some(field(req, "interval")?)),
+                    ("startTime", Some(field(req, "startTime")?)),
+                    ("endTime", field(req, "endTime")),
+                ],
+            )
+        }
+        "fundingHistory" => route(
+            "/funding-history",
+            vec![
+                ("coin", Some(field(body, "coin")?)),
+                ("startTime", Some(field(body, "startTime")?)),
+                ("endTime", field(body, "endTime")),
+            ],
+        ),
+        _ => None,
+    }
+}
+
+/// Perp markets from a `meta` response or the first element of
+/// `metaAndAssetCtxs`. The index is the position in the universe.
+pub fn parse_universe(resp: &Value) -> Result<Vec<MarketMeta>, AtlasError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Entry {
+        name: String,
+        sz_decimals: u32,
+        #[serde(default)]
+        max_leverage: u32,
+    }
+
+    let meta = if resp.is_array() { &resp[0] } else { resp };
+    let entries: Vec<Entry> = meta
+        .get("universe")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| AtlasError::Network(format!("unexpected meta shape: {e}")))?
+        .ok_or_else(|| AtlasError::Network("missing universe in meta".into()))?;
+    Ok(entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, e)| MarketMeta {
+            name: e.name,
+            index,
+            sz_decimals: e.sz_decimals,
+            max_leverage: e.max_leverage,
+        })
+        .collect())
+}
+
+/// Maintenance tiers per perp from `meta`. Markets whose `marginTableId`
+/// is not among `marginTables` get one tier at their max leverage.
+pub fn parse_margin_tiers(resp: &Value) -> Result<HashMap<String, Vec<MarginTier>>, AtlasError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Entry {
+        name: String,
+        #[serde(default)]
+        max_leverage: u32,
+        #[serde(default)]
+        margin_table_id: Option<u64>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Table {
+        margin_tiers: Vec<Tier>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Tier {
+        lower_bound: Decimal,
+        max_leverage: u32,
+    }
+
+    let shape = |e: serde_json::Error| AtlasError::Network(format!("unexpected meta shape: {e}"));
+    let meta = if resp.is_array() { &resp[0] } else { resp };
+    let entries: Vec<Entry> = meta
+        .get("universe")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(shape)?
+        .ok_or_else(|| AtlasError::Network("missing universe in meta".into()))?;
+    let tables: Vec<(u64, Table)> = meta
+        .get("marginTables")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(shape)?
+        .unwrap_or_default();
+    let tables: HashMap<u64, Table> = tables.into_iter().collect();
+
+    Ok(entries
+        .into_iter()
+        .map(|e| {
+            let tiers = match e.margin_table_id.and_then(|id| tables.get(&id)) {
+                Some(table) => table
+                    .margin_tiers
+                    .iter()
+                    .map(|t| MarginTier {
+                        lower_bound: t.lower_bound,
+                        max_leverage: t.max_leverage,
+                    })
+                    .collect(),
+                None => vec![MarginTier {
+                    lower_bound: Decimal::ZERO,
+                    max_leverage: e.max_leverage,
+                }],
+            };
+            (e.name, tiers)
+        })
+        .collect())
+}
+
+/// Candles from a `candleSnapshot` response.
+pub fn parse_candles(resp: Value) -> Result<Vec<Candle>, AtlasError> {
+    #[derive(Deserialize)]
+    struct Raw {
+        t: u64,
+        o: Decimal,
+        h: Decimal,
+        l: Decimal,
+        c: Decimal,
+        v: Decimal,
+        #[serde(default)]
+        n: Option<u64>,
+    }
+
+    let raw: Vec<Raw> = serde_json::from_value(resp)
+        .map_err(|e| AtlasError::Network(format!("unexpected candleSnapshot shape: {e}")))?;
+    Ok(raw
+        .into_iter()
+        .map(|c| Candle {
+            open_time_ms: c.t,
+            open: c.o,
+            high: c.h,
+            low: c.l,
+            close: c.c,
+            volume: c.v,
+            trades: c.n,
+        })
+        .collect())
+}
+
+/// Funding rates from a `fundingHistory` response.
+pub fn parse_funding_history(resp: Value) -> Result<Vec<FundingRate>, AtlasError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Raw {
+        coin: String,
+        funding_rate: Decimal,
+        premium: Option<Decimal>,
+        time: u64,
+    }
+
+    let raw: Vec<Raw> = serde_json::from_value(resp)
+        .map_err(|e| AtlasError::Network(format!("unexpected fundingHistory shape: {e}")))?;
+    Ok(raw
+        .into_iter()
+        .map(|r| FundingRate {
+            symbol: r.coin,
+            protocol: Protocol::Hyperliquid,
+            rate: r.funding_rate,
+            premium: r.premium,
+            timestamp_ms: r.time,
+            next_funding_ms: None,
+        })
+        .collect())
+}
+
+/// The top `depth` levels per side of an `l2Book` response.
+pub fn parse_l2_book(resp: Value, depth: usize) -> Result<OrderBook, AtlasError> {
+    #[derive(Deserialize)]
+    struct Level {
+        px: Decimal,
+        sz: Decimal,
+        n: Option<u32>,
+    }
+    #[derive(Deserialize)]
+    struct Raw {
+        coin: String,
+        time: Option<u64>,
+        levels: (Vec<Level>, Vec<Level>),
+    }
+
+    let raw: Raw = serde_json::from_value(resp)
+        .map_err(|e| AtlasError::Network(format!("unexpected l2Book shape: {e}")))?;
+    let side = |levels: Vec<Level>| {
+        levels
+            .into_iter()
+            .take(depth)
+            .map(|l| BookLevel {
+                price: l.px,
+                size: l.sz,
+                count: l.n,
+            })
+            .collect()
+    };
+    Ok(OrderBook {
+        symbol: raw.coin,
+        protocol: Protocol::Hyperliquid,
+        bids: side(raw.levels.0),
+        asks: side(raw.levels.1),
+        timestamp_ms: raw.time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn query(route: &(String, Vec<(String, String)>)) -> Vec<(&str, &str)> {
+        route
+            .1
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_proxy_routes() {
+        let r = proxy_route(&json!({"type": "metaAndAssetCtxs"})).unwrap();
+        assert_eq!(r.0, "/atlas-os/hl/meta-and-asset-ctxs");
+        assert!(r.1.is_empty());
+
+        let r = proxy_route(&json!({
+            "type": "candleSnapshot",
+            "req": {"coin": "BTC", "interval": "1h", "startTime": 1000, "endTime": 2000}
+        }))
+        .unwrap();
+        assert_eq!(r.0, "/atlas-os/hl/candles");
+        assert_eq!(
+            query(&r),
+            [
+                ("coin", "BTC"),
+                ("interval", "1h"),
+                ("startTime", "1000"),
+                ("endTime", "2000")
+            ]
+        );
+
+        let r =
+            proxy_route(&json!({"type": "fundingHistory", "coin": "ETH", "startTime": 5})).unwrap();
+        assert_eq!(query(&r), [("coin", "ETH"), ("startTime", "5")]);
+
+        // Account and unknown types are never proxied
+        assert!(proxy_route(&json!({"type": "clearinghouseState", "user": "0x1"})).is_none());
+        assert!(proxy_route(&json!({"type": "l2Book"})).is_none());
+    }
+
+    #[test]
+    fn test_parse_universe() {
+        let resp = json!([
+            {"universe": [
+                {"name": "BTC", "szDecimals": 5, "maxLeverage": 40},
+                {"name": "ETH", "szDecimals": 4, "maxLeverage": 25}
+            ]},
+            []
+        ]);
+        let perps = parse_universe(&resp).unwrap();
+        assert_eq!(perps.len(), 2);
+        assert_eq!(perps[1].name, "ETH");
+        assert_eq!(perps[1].index, 1);
+        assert_eq!(perps[1].sz_decimals, 4);
+        assert!(parse_universe(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_parse_margin_tiers() {
+        let resp = json!({
+            "universe": [
+                {"name": "BTC", "szDecimals": 5, "maxLeverage": 40, "marginTableId": 56},
+                {"name": "DOGE", "szDecimals": 0, "maxLeverage": 10, "marginTableId": 10}
+            ],
+            "marginTables": [[56, {"description": "tiered 40x", "marginTiers": [
+                {"lowerBound": "0.0", "maxLeverage": 40},
+                {"lowerBound": "150000000.0", "maxLeverage": 20}
+            ]}]]
+        });
+        let tiers = parse_margin_tiers(&resp).unwrap();
+        assert_eq!(tiers["BTC"].len(), 2);
+        assert_eq!(tiers["BTC"][1].lower_bound, Decimal::from(150_000_000));
+        assert_eq!(tiers["BTC"][1].max_leverage, 20);
+        assert_eq!(
+            tiers["DOGE"],
+            vec![MarginTier {
+                lower_bound: Decimal::ZERO,
+                max_leverage: 10
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_candles_and_funding() {
+        let candles = parse_candles(json!([{
+            "t": 1700000000000u64, "T": 1700003599999u64, "s": "BTC", "i": "1h",
+            "o": "97000.0", "c": "97100.5", "h": "97200", "l": "96900", "v": "12.5", "n": 420
+        }]))
+        .unwrap();
+        assert_eq!(candles[0].close, Decimal::new(971005, 1));
+        assert_eq!(candles[0].trades, Some(420));
+
+        let rates = parse_funding_history(json!([
+            {"coin": "ETH", "fundingRate": "0.0000125", "premium": "-0.0002", "time": 1700000000000u64}
+        ]))
+        .unwrap();
+        assert_eq!(rates[0].rate, Decimal::new(125, 7));
+        assert_eq!(rates[0].premium, Some(Decimal::new(-2, 4)));
+    }
+
+    #[test]
+    fn test_parse_l2_book_depth() {
+        let book = parse_l2_book(
+            json!({
+                "coin": "BTC", "time": 1700000000000u64,
+                "levels": [
+                    [{"px": "97000", "sz": "1.5", "n": 3}, {"px": "96999", "sz": "2", "n": 1}],
+                    [{"px": "97001", "sz": "0.5", "n": 2}]
+                ]
+            }),
+            1,
+        )
+        .unwrap();
+        assert_eq!(book.bids.len(), 1);
+        assert_eq!(book.bids[0].size, Decimal::new(15, 1));
+        assert_eq!(book.asks[0].count, Some(2));
+        assert_eq!(book.timestamp_ms, Some(1700000000000));
+    }
+}
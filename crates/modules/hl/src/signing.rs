@@ -1,7 +1,7 @@
 //! EIP-712 Agent signing for Hyperliquid exchange actions.
 //!
 //! Used for action types not exposed in hypersdk's Action enum
-//! (e.g. updateLeverage).
+//! (e.g. updateLeverage, batchModify).
 
 use alloy::primitives::{keccak256, B256};
 
@@ -0,0 +1,150 @@
+//! Spot pair naming from `spotMeta`.
+//!
+//! Hyperliquid keys spot markets by their universe name: `PURR/USDC` for the
+//! original pair and `@N` for everything listed since. Open orders, fills and
+//! mids all use that key, so it has to be mapped back to a readable pair.
+
+use atlas_core::error::AtlasError;
+use atlas_core::types::SpotMarket;
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+/// One spot market from the `spotMeta` universe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpotPair {
+    /// Universe name used as the coin key by the API (`@107`, `PURR/USDC`).
+    pub key: String,
+    /// Universe index.
+    pub index: usize,
+    pub base: String,
+    pub quote: String,
+    /// Size decimals of the base token.
+    pub sz_decimals: u32,
+    /// On-chain decimals of the base token.
+    pub wei_decimals: u32,
+}
+
+impl SpotPair {
+    /// Human-readable pair, e.g. `HYPE/USDC`.
+    pub fn display(&self) -> String {
+        format!("{}/{}", self.base, self.quote)
+    }
+
+    /// The universal listing, priced at `mid`.
+    pub fn to_market(&self, mid: Option<Decimal>) -> SpotMarket {
+        SpotMarket {
+            pair: self.display(),
+            base: self.base.clone(),
+            quote: self.quote.clone(),
+            index: self.index,
+            key: self.key.clone(),
+            sz_decimals: self.sz_decimals,
+            wei_decimals: self.wei_decimals,
+            mid_price: mid,
+        }
+    }
+}
+
+/// True when an API coin key names a spot market rather than a perp.
+pub fn is_spot_key(coin: &str) -> bool {
+    coin.starts_with('@') || coin.contains('/')
+}
+
+/// Parse the pairs out of a `spotMeta` response. Also accepts the
+/// `spotMetaAndAssetCtxs` array, whose first element is the meta.
+pub fn parse_spot_pairs(resp: &Value) -> Result<Vec<SpotPair>, AtlasError> {
+    let meta = if resp.is_array() { &resp[0] } else { resp };
+    let tokens = meta
+        .get("tokens")
+        .and_then(Value::as_array)
+        .ok_or_else(|| AtlasError::Network("missing tokens in spot meta".into()))?;
+    let universe = meta
+        .get("universe")
+        .and_then(Value::as_array)
+        .ok_or_else(|| AtlasError::Network("missing universe in spot meta".into()))?;
+
+    let token = |idx: u64| {
+        tokens
+            .iter()
+            .find(|t| t.get("index").and_then(Value::as_u64) == Some(idx))
+    };
+    let name = |t: &Value| t.get("name").and_then(Value::as_str).map(str::to_string);
+    let decimals = |t: &Value, key: &str| t.get(key).and_then(Value::as_u64).unwrap_or(0) as u32;
+
+    Ok(universe
+        .iter()
+        .filter_map(|u| {
+            let pair = u.get("tokens").and_then(Value::as_array)?;
+            let base = token(pair.first()?.as_u64()?)?;
+            let quote = token(pair.get(1)?.as_u64()?)?;
+            Some(SpotPair {
+                key: u.get("name")?.as_str()?.to_string(),
+                index: u.get("index")?.as_u64()? as usize,
+                base: name(base)?,
+                quote: name(quote)?,
+                sz_decimals: decimals(base, "szDecimals"),
+                wei_decimals: decimals(base, "weiDecimals"),
+            })
+        })
+        .collect())
+}
+
+/// The USDC-quoted pair for a base token (case-insensitive).
+pub fn find_usdc_pair<'a>(pairs: &'a [SpotPair], base: &str) -> Option<&'a SpotPair> {
+    pairs
+        .iter()
+        .find(|p| p.base.eq_ignore_ascii_case(base) && p.quote == "USDC")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta() -> Value {
+        serde_json::json!({
+            "tokens": [
+                {"name": "USDC", "index": 0, "szDecimals": 8},
+                {"name": "PURR", "index": 1, "szDecimals": 0},
+                {"name": "HYPE", "index": 150, "szDecimals": 2, "weiDecimals": 8}
+            ],
+            "universe": [
+                {"name": "PURR/USDC", "tokens": [1, 0], "index": 0},
+                {"name": "@107", "tokens": [150, 0], "index": 107},
+                {"name": "@999", "tokens": [404, 0], "index": 999}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_parse_spot_pairs() {
+        let pairs = parse_spot_pairs(&meta()).unwrap();
+        // The pair with an unknown token is skipped.
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[1].key, "@107");
+        assert_eq!(pairs[1].display(), "HYPE/USDC");
+        assert_eq!(pairs[0].display(), "PURR/USDC");
+        assert_eq!((pairs[1].sz_decimals, pairs[1].wei_decimals), (2, 8));
+
+        let market = pairs[1].to_market(Some(Decimal::new(3125, 2)));
+        assert_eq!(market.pair, "HYPE/USDC");
+        assert_eq!(market.key, "@107");
+        assert_eq!(market.index, 107);
+        assert_eq!(market.mid_price, Some(Decimal::new(3125, 2)));
+
+        let wrapped = serde_json::json!([meta(), []]);
+        assert_eq!(parse_spot_pairs(&wrapped).unwrap(), pairs);
+        assert!(parse_spot_pairs(&serde_json::json!({})).is_err());
+    }
+
+    #[test]
+    fn test_find_usdc_pair_and_spot_keys() {
+        let pairs = parse_spot_pairs(&meta()).unwrap();
+        assert_eq!(find_usdc_pair(&pairs, "hype").unwrap().key, "@107");
+        assert!(find_usdc_pair(&pairs, "BTC").is_none());
+
+        assert!(is_spot_key("@107"));
+        assert!(is_spot_key("PURR/USDC"));
+        assert!(!is_spot_key("BTC"));
+        assert!(!is_spot_key("kPEPE"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Client-side rate limiting for Hyperliquid `/info` requests.
+//!
+//! Hyperliquid allows 1200 request weight per minute per IP and answers
+//! 429 beyond that. One [`InfoThrottle`] token bucket is shared by every
+//! info call of a module — including the fan-outs behind `market summary`,
+//! `market top` and the screeners — so a burst queues here instead of
+//! being rejected by the exchange.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use atlas_core::config::RateLimitConfig;
+use tracing::debug;
+
+/// Token bucket in light-request units (see [`request_cost`]).
+#[derive(Debug)]
+pub struct InfoThrottle {
+    /// Tokens added per second; `0` disables throttling.
+    rate: f64,
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Negative while callers are queued for tokens not yet refilled.
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Default for InfoThrottle {
+    fn default() -> Self {
+        Self::new(&RateLimitConfig::default())
+    }
+}
+
+impl InfoThrottle {
+    /// A full bucket for `cfg`.
+    pub fn new(cfg: &RateLimitConfig) -> Self {
+        let burst = f64::from(cfg.burst.max(1));
+        Self {
+            rate: cfg.requests_per_sec.max(0.0),
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until `request` (an `/info` `type`) may be sent.
+    pub async fn acquire(&self, request: &str) {
+        let wait = self.reserve(request_cost(request), Instant::now());
+        if !wait.is_zero() {
+            debug!(
+                request,
+                delay_ms = wait.as_millis() as u64,
+                "HL info request delayed by rate limit"
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take `cost` tokens at `now` and return how long to wait until they
+    /// have been refilled. Tokens are taken immediately, so concurrent
+    /// callers queue behind each other instead of racing for the refill.
+    fn reserve(&self, cost: f64, now: Instant) -> Duration {
+        if self.rate <= 0.0 {
+            return Duration::ZERO;
+        }
+        let Ok(mut bucket) = self.bucket.lock() else {
+            return Duration::ZERO;
+        };
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.updated = bucket.updated.max(now);
+        bucket.tokens -= cost;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        }
+    }
+}
+
+/// Cost of an `/info` request in light requests: its documented weight
+/// divided by two. Prices, book and account state weigh 2, `userRole` 60,
+/// everything else (candles, metadata, fills, funding, vaults) 20.
+pub fn request_cost(request: &str) -> f64 {
+    match request {
+        "allMids"
+        | "l2Book"
+        | "clearinghouseState"
+        | "spotClearinghouseState"
+        | "orderStatus"
+        | "exchangeStatus" => 1.0,
+        "userRole" => 30.0,
+        _ => 10.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttle(requests_per_sec: f64, burst: u32) -> InfoThrottle {
+        InfoThrottle::new(&RateLimitConfig {
+            requests_per_sec,
+            burst,
+        })
+    }
+
+    #[test]
+    fn test_burst_then_waits_for_refill() {
+        let t = throttle(10.0, 3);
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert_eq!(t.reserve(1.0, now), Duration::ZERO);
+        }
+        // Fourth and fifth queue behind each other: 100ms, then 200ms
+        assert_eq!(t.reserve(1.0, now), Duration::from_millis(100));
+        assert_eq!(t.reserve(1.0, now), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_refills_up_to_burst() {
+        let t = throttle(10.0, 5);
+        let now = Instant::now();
+        assert_eq!(t.reserve(5.0, now), Duration::ZERO);
+        // Idle long enough to refill many times over: capped at the burst
+        let later = now + Duration::from_secs(60);
+        assert_eq!(t.reserve(5.0, later), Duration::ZERO);
+        assert_eq!(t.reserve(1.0, later), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_heavy_requests_cost_more() {
+        assert_eq!(request_cost("allMids"), 1.0);
+        assert_eq!(request_cost("candleSnapshot"), 10.0);
+        assert_eq!(request_cost("metaAndAssetCtxs"), 10.0);
+
+        let t = throttle(10.0, 10);
+        let now = Instant::now();
+        assert_eq!(
+            t.reserve(request_cost("candleSnapshot"), now),
+            Duration::ZERO
+        );
+        assert_eq!(
+            t.reserve(request_cost("candleSnapshot"), now),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_zero_rate_disables() {
+        let t = throttle(0.0, 1);
+        let now = Instant::now();
+        for _ in 0..100 {
+            assert_eq!(t.reserve(10.0, now), Duration::ZERO);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Public vault listing for `atlas hl vault list`.
+//!
+//! `/info` has no call that lists every vault, so this reads the stats
+//! document the web UI's vault page uses. It covers a few thousand vaults
+//! and weighs several MB, so it is kept in `data/hl-vaults-<network>.json`
+//! for [`CACHE_TTL`]. Closed vaults and HLP's child vaults, which take no
+//! deposits, are dropped.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use atlas_core::date_range::now_ms;
+use atlas_core::error::AtlasError;
+use atlas_core::types::{Protocol, VaultSummary};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::json_cache::{cache_path, network_name, JsonCache};
+
+/// How long a fetched listing is reused.
+pub const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Vault listing for a network.
+pub fn listing_url(testnet: bool) -> &'static str {
+    if testnet {
+        "https://stats-data.hyperliquid-testnet.xyz/Testnet/vaults"
+    } else {
+        "https://stats-data.hyperliquid.xyz/Mainnet/vaults"
+    }
+}
+
+/// Open vaults from a listing response.
+pub fn parse_vaults(resp: Value) -> Result<Vec<VaultSummary>, AtlasError> {
+    #[derive(Deserialize)]
+    struct Entry {
+        apr: Option<Decimal>,
+        summary: Summary,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Summary {
+        name: String,
+        vault_address: String,
+        leader: String,
+        tvl: Decimal,
+        #[serde(default)]
+        is_closed: bool,
+        relationship: Option<Relationship>,
+        #[serde(default)]
+        create_time_millis: u64,
+    }
+
+    #[derive(Deserialize)]
+    struct Relationship {
+        #[serde(rename = "type")]
+        kind: String,
+    }
+
+    let entries: Vec<Entry> = serde_json::from_value(resp)
+        .map_err(|e| AtlasError::Network(format!("unexpected vault listing shape: {e}")))?;
+    Ok(entries
+        .into_iter()
+        .filter(|e| {
+            !e.summary.is_closed
+                && e.summary
+                    .relationship
+                    .as_ref()
+                    .map_or(true, |r| r.kind != "child")
+        })
+        .map(|e| VaultSummary {
+            protocol: Protocol::Hyperliquid,
+            address: e.summary.vault_address,
+            name: e.summary.name,
+            leader: e.summary.leader,
+            tvl: e.summary.tvl,
+            apr: e.apr,
+            created_ms: e.summary.create_time_millis,
+            followers: None,
+        })
+        .collect())
+}
+
+/// On-disk vault listing for one network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultCache {
+    pub network: String,
+    pub fetched_at_ms: u64,
+    pub vaults: Vec<VaultSummary>,
+}
+
+impl VaultCache {
+    pub fn new(testnet: bool, vaults: Vec<VaultSummary>) -> Self {
+        Self {
+            network: network_name(testnet).into(),
+            fetched_at_ms: now_ms() as u64,
+            vaults,
+        }
+    }
+
+    /// Cache file for a network, inside the workspace `data/` dir.
+    pub fn path(testnet: bool) -> Option<PathBuf> {
+        cache_path("vaults", testnet)
+    }
+}
+
+impl JsonCache for VaultCache {
+    fn fetched_at_ms(&self) -> u64 {
+        self.fetched_at_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    /// Trimmed from a captured mainnet response: the HLP parent, one of its
+    /// children, a user vault and a closed one.
+    const LISTING: &str = r#"[
+        {
+            "apr": 0.0931,
+            "pnls": [["day", ["0.0", "41234.5"]], ["allTime", ["0.0", "71234567.8"]]],
+            "summary": {
+                "name": "Hyperliquidity Provider (HLP)",
+                "vaultAddress": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303",
+                "leader": "0x677d831aef5328190852e24f13c46cac05f984e7",
+                "tvl": "391234567.123456",
+                "isClosed": false,
+                "relationship": {
+                    "type": "parent",
+                    "data": {"childAddresses": ["0x010461c14e146ac35fe42271bdc1134ee31c703a"]}
+                },
+                "createTimeMillis": 1683244800000
+            }
+        },
+        {
+            "apr": 0.0874,
+            "pnls": [],
+            "summary": {
+                "name": "HLP Strategy A",
+                "vaultAddress": "0x010461c14e146ac35fe42271bdc1134ee31c703a",
+                "leader": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303",
+                "tvl": "120000000.0",
+                "isClosed": false,
+                "relationship": {"type": "child"},
+                "createTimeMillis": 1683244800000
+            }
+        },
+        {
+            "apr": -0.1523,
+            "pnls": [["day", ["0.0", "-12.3"]]],
+            "summary": {
+                "name": "Delta Neutral Basis",
+                "vaultAddress": "0x1e37a337ed460039d1b15bd3bc489de789768d5e",
+                "leader": "0x5b5d51203a0f9079f8aeb098a6523a13f298c060",
+                "tvl": "254311.08",
+                "isClosed": false,
+                "relationship": {"type": "normal"},
+                "createTimeMillis": 1718236800000
+            }
+        },
+        {
+            "apr": 0.0,
+            "pnls": [],
+            "summary": {
+                "name": "Retired",
+                "vaultAddress": "0x2b804617c6f63c040377e95bb276811747006f4b",
+                "leader": "0x5b5d51203a0f9079f8aeb098a6523a13f298c060",
+                "tvl": "0.0",
+                "isClosed": true,
+                "relationship": {"type": "normal"},
+                "createTimeMillis": 1700000000000
+            }
+        }
+    ]"#;
+
+    #[test]
+    fn test_parse_listing_drops_closed_and_children() {
+        let vaults = parse_vaults(serde_json::from_str(LISTING).unwrap()).unwrap();
+        assert_eq!(vaults.len(), 2);
+
+        let hlp = &vaults[0];
+        assert_eq!(hlp.name, "Hyperliquidity Provider (HLP)");
+        assert_eq!(hlp.address, "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303");
+        assert_eq!(hlp.tvl, Decimal::from_str("391234567.123456").unwrap());
+        assert_eq!(hlp.apr, Some(Decimal::from_str("0.0931").unwrap()));
+        assert_eq!(hlp.created_ms, 1683244800000);
+        assert_eq!(hlp.followers, None);
+
+        assert_eq!(vaults[1].name, "Delta Neutral Basis");
+        assert_eq!(vaults[1].apr, Some(Decimal::from_str("-0.1523").unwrap()));
+    }
+
+    #[test]
+    fn test_parse_listing_tolerates_missing_optional_fields() {
+        let resp = serde_json::json!([{
+            "apr": null,
+            "summary": {
+                "name": "New",
+                "vaultAddress": "0xabc",
+                "leader": "0xdef",
+                "tvl": "100"
+            }
+        }]);
+        let vaults = parse_vaults(resp).unwrap();
+        assert_eq!(vaults.len(), 1);
+        assert_eq!(vaults[0].apr, None);
+        assert_eq!(vaults[0].created_ms, 0);
+    }
+
+    #[test]
+    fn test_parse_listing_rejects_other_shapes() {
+        let err = parse_vaults(serde_json::json!({"error": "not found"})).unwrap_err();
+        assert!(err.to_string().contains("vault listing"));
+    }
+
+    #[test]
+    fn test_cache_roundtrip_and_expiry() {
+        let vaults = parse_vaults(serde_json::from_str(LISTING).unwrap()).unwrap();
+        let mut cache = VaultCache::new(false, vaults);
+        assert!(cache.is_fresh(CACHE_TTL));
+
+        let dir = std::env::temp_dir().join(format!("atlas-vaults-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("hl-vaults-mainnet.json");
+        cache.save(&path).unwrap();
+        let loaded = VaultCache::load(&path).unwrap();
+        assert_eq!(loaded.vaults.len(), 2);
+        assert_eq!(
+            loaded.vaults[1].tvl,
+            Decimal::from_str("254311.08").unwrap()
+        );
+        std::fs::remove_dir_all(&dir).ok();
+
+        cache.fetched_at_ms -= CACHE_TTL.as_millis() as u64;
+        assert!(!cache.is_fresh(CACHE_TTL));
+    }
+}
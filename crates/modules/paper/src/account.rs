@@ -0,0 +1,800 @@
+//! Simulated margin account: cash, positions, resting orders and fills.
+//!
+//! Pure bookkeeping — prices are passed in and nothing here does I/O, so
+//! every rule can be tested without a network. The module stores the whole
+//! account as JSON in the local DB between commands (see `client`).
+//!
+//! Margin model: a position posts `|size| × entry / leverage` when opened
+//! and is liquidated once its unrealized loss at the mark uses that up.
+//! PnL is funding-free.
+
+use std::collections::{BTreeMap, HashMap};
+
+use atlas_core::config::PaperConfig;
+use atlas_core::error::{AtlasError, AtlasResult};
+use atlas_core::types::{Fill, Protocol, Side};
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Fills kept on the account; older ones remain in the local DB.
+pub const MAX_FILLS: usize = 500;
+
+const BPS: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+
+/// Cost and sizing rules the simulation runs under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaperSettings {
+    /// USDC a new account starts with.
+    pub starting_balance: Decimal,
+    /// Fee on every fill, in bps of notional.
+    pub fee_bps: Decimal,
+    /// Market orders fill this many bps worse than mid.
+    pub slippage_bps: Decimal,
+    /// Leverage for coins without one set via `set_leverage`.
+    pub default_leverage: u32,
+}
+
+impl PaperSettings {
+    /// Settings from the `paper` module config; leverage follows the
+    /// perp default so sizing matches live trading.
+    pub fn from_config(cfg: &PaperConfig, default_leverage: u32) -> Self {
+        Self {
+            starting_balance: Decimal::from_f64(cfg.starting_balance).unwrap_or_default(),
+            fee_bps: Decimal::from_f64(cfg.fee_bps).unwrap_or_default(),
+            slippage_bps: Decimal::from_f64(cfg.slippage_bps).unwrap_or_default(),
+            default_leverage: default_leverage.max(1),
+        }
+    }
+
+    /// Market fill price: `mid` moved against the taker by the slippage.
+    pub fn market_price(&self, mid: Decimal, side: &Side) -> Decimal {
+        let slip = mid * self.slippage_bps / BPS;
+        match side {
+            Side::Buy => mid + slip,
+            Side::Sell => mid - slip,
+        }
+    }
+
+    fn fee(&self, size: Decimal, price: Decimal) -> Decimal {
+        size * price * self.fee_bps / BPS
+    }
+}
+
+/// One open position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaperPosition {
+    /// Signed: positive long, negative short.
+    pub size: Decimal,
+    pub entry_price: Decimal,
+    pub leverage: u32,
+}
+
+impl PaperPosition {
+    pub fn side(&self) -> Side {
+        if self.size > Decimal::ZERO {
+            Side::Buy
+        } else {
+            Side::Sell
+        }
+    }
+
+    pub fn unrealized_pnl(&self, mark: Decimal) -> Decimal {
+        (mark - self.entry_price) * self.size
+    }
+
+    /// Margin posted at entry.
+    pub fn margin(&self) -> Decimal {
+        self.size.abs() * self.entry_price / Decimal::from(self.leverage.max(1))
+    }
+
+    /// Mark at which the posted margin is gone; `None` when that is zero
+    /// (an unlevered long).
+    pub fn liquidation_price(&self) -> Option<Decimal> {
+        let lev = Decimal::from(self.leverage.max(1));
+        let px = if self.size > Decimal::ZERO {
+            self.entry_price * (lev - Decimal::ONE) / lev
+        } else {
+            self.entry_price * (lev + Decimal::ONE) / lev
+        };
+        (px > Decimal::ZERO).then_some(px)
+    }
+
+    pub fn is_liquidatable(&self, mark: Decimal) -> bool {
+        self.margin() + self.unrealized_pnl(mark) <= Decimal::ZERO
+    }
+}
+
+/// A resting limit order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaperOrder {
+    pub oid: u64,
+    pub symbol: String,
+    pub side: Side,
+    pub size: Decimal,
+    pub price: Decimal,
+    #[serde(default)]
+    pub reduce_only: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloid: Option<String>,
+    pub timestamp_ms: u64,
+}
+
+impl PaperOrder {
+    /// The order would fill against this mark.
+    pub fn crosses(&self, mark: Decimal) -> bool {
+        match self.side {
+            Side::Buy => mark <= self.price,
+            Side::Sell => mark >= self.price,
+        }
+    }
+
+    /// This order executed in full at `price`.
+    pub fn execution(&self, price: Decimal) -> Execution {
+        Execution {
+            symbol: self.symbol.clone(),
+            side: self.side.clone(),
+            size: self.size,
+            price,
+            reduce_only: self.reduce_only,
+            oid: self.oid,
+        }
+    }
+}
+
+/// One fill to book against the account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Execution {
+    pub symbol: String,
+    pub side: Side,
+    pub size: Decimal,
+    pub price: Decimal,
+    pub reduce_only: bool,
+    pub oid: u64,
+}
+
+/// The whole simulated account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaperAccount {
+    /// Cash: the starting balance plus realized PnL, minus fees.
+    pub balance: Decimal,
+    #[serde(default)]
+    pub positions: BTreeMap<String, PaperPosition>,
+    #[serde(default)]
+    pub orders: Vec<PaperOrder>,
+    /// Most recent fills, oldest first (at most [`MAX_FILLS`]).
+    #[serde(default)]
+    pub fills: Vec<Fill>,
+    /// Leverage chosen per coin with `set_leverage`.
+    #[serde(default)]
+    pub leverage: BTreeMap<String, u32>,
+    /// Last order ID handed out.
+    #[serde(default)]
+    pub last_oid: u64,
+    /// Fills booked since the account was created.
+    #[serde(default)]
+    pub fill_count: u64,
+}
+
+impl PaperAccount {
+    pub fn new(balance: Decimal) -> Self {
+        Self {
+            balance,
+            positions: BTreeMap::new(),
+            orders: Vec::new(),
+            fills: Vec::new(),
+            leverage: BTreeMap::new(),
+            last_oid: 0,
+            fill_count: 0,
+        }
+    }
+
+    pub fn next_oid(&mut self) -> u64 {
+        self.last_oid += 1;
+        self.last_oid
+    }
+
+    /// Leverage a new fill on `symbol` is booked at.
+    pub fn leverage_for(&self, symbol: &str, default: u32) -> u32 {
+        self.leverage.get(symbol).copied().unwrap_or(default).max(1)
+    }
+
+    /// Set leverage for `symbol`; an open position is re-margined at it.
+    pub fn set_leverage(&mut self, symbol: &str, leverage: u32) {
+        let leverage = leverage.max(1);
+        self.leverage.insert(symbol.to_string(), leverage);
+        if let Some(pos) = self.positions.get_mut(symbol) {
+            pos.leverage = leverage;
+        }
+    }
+
+    /// Mark for `symbol`, falling back to the entry when no price is known.
+    pub fn mark(&self, symbol: &str, marks: &HashMap<String, Decimal>) -> Option<Decimal> {
+        marks
+            .get(symbol)
+            .copied()
+            .or_else(|| self.positions.get(symbol).map(|p| p.entry_price))
+    }
+
+    pub fn unrealized_pnl(&self, marks: &HashMap<String, Decimal>) -> Decimal {
+        self.positions
+            .iter()
+            .map(|(symbol, p)| p.unrealized_pnl(self.mark(symbol, marks).unwrap_or(p.entry_price)))
+            .sum()
+    }
+
+    /// Account value: cash plus unrealized PnL.
+    pub fn equity(&self, marks: &HashMap<String, Decimal>) -> Decimal {
+        self.balance + self.unrealized_pnl(marks)
+    }
+
+    /// Margin held by open positions at the current marks.
+    pub fn margin_used(&self, marks: &HashMap<String, Decimal>) -> Decimal {
+        self.positions
+            .iter()
+            .map(|(symbol, p)| {
+                let mark = self.mark(symbol, marks).unwrap_or(p.entry_price);
+                p.size.abs() * mark / Decimal::from(p.leverage.max(1))
+            })
+            .sum()
+    }
+
+    /// Equity not tied up as margin.
+    pub fn available(&self, marks: &HashMap<String, Decimal>) -> Decimal {
+        (self.equity(marks) - self.margin_used(marks)).max(Decimal::ZERO)
+    }
+
+    /// Whether a `side` order on `symbol` would shrink the open position.
+    pub fn reduces(&self, symbol: &str, side: &Side) -> bool {
+        self.positions
+            .get(symbol)
+            .is_some_and(|p| p.side() != *side)
+    }
+
+    /// Book one fill. Validates reduce-only and margin first; on error the
+    /// account is left untouched.
+    pub fn execute(
+        &mut self,
+        exec: &Execution,
+        settings: &PaperSettings,
+        marks: &HashMap<String, Decimal>,
+        now_ms: u64,
+    ) -> AtlasResult<Fill> {
+        if exec.size <= Decimal::ZERO {
+            return Err(AtlasError::InvalidSize(format!(
+                "order size must be positive, got {}",
+                exec.size
+            )));
+        }
+        let current = self
+            .positions
+            .get(&exec.symbol)
+            .map(|p| p.size.abs())
+            .unwrap_or_default();
+        let reducing = self.reduces(&exec.symbol, &exec.side);
+
+        let size = if exec.reduce_only {
+            if !reducing {
+                return Err(AtlasError::order_rejected(
+                    "Reduce only order would increase position",
+                ));
+            }
+            exec.size.min(current)
+        } else {
+            exec.size
+        };
+
+        // Exposure this fill adds: all of it, or whatever flips past flat.
+        let opening = if reducing {
+            (size - current).max(Decimal::ZERO)
+        } else {
+            size
+        };
+        let fee = settings.fee(size, exec.price);
+        let leverage = self.leverage_for(&exec.symbol, settings.default_leverage);
+        if opening > Decimal::ZERO {
+            let required = opening * exec.price / Decimal::from(leverage) + fee;
+            let available = self.available(marks);
+            if required > available {
+                return Err(AtlasError::InsufficientMargin(format!(
+                    "paper order needs ${:.2} of margin, ${:.2} available",
+                    required, available
+                )));
+            }
+        }
+
+        let signed = match exec.side {
+            Side::Buy => size,
+            Side::Sell => -size,
+        };
+        let realized = self.apply(&exec.symbol, signed, exec.price, leverage);
+        self.balance += realized - fee;
+        Ok(self.record(exec, size, fee, realized, now_ms))
+    }
+
+    /// Move the position by `signed` at `price`. Returns the realized PnL.
+    fn apply(&mut self, symbol: &str, signed: Decimal, price: Decimal, leverage: u32) -> Decimal {
+        let Some(pos) = self.positions.get_mut(symbol) else {
+            self.positions.insert(
+                symbol.to_string(),
+                PaperPosition {
+                    size: signed,
+                    entry_price: price,
+                    leverage,
+                },
+            );
+            return Decimal::ZERO;
+        };
+
+        if pos.size.is_sign_positive() == signed.is_sign_positive() {
+            let size = pos.size + signed;
+            pos.entry_price =
+                (pos.size.abs() * pos.entry_price + signed.abs() * price) / size.abs();
+            pos.size = size;
+            return Decimal::ZERO;
+        }
+
+        let closing = signed.abs().min(pos.size.abs());
+        let realized = if pos.size > Decimal::ZERO {
+            closing * (price - pos.entry_price)
+        } else {
+            closing * (pos.entry_price - price)
+        };
+        let remaining = pos.size + signed;
+        if remaining.is_zero() {
+            self.positions.remove(symbol);
+        } else {
+            if remaining.is_sign_positive() != pos.size.is_sign_positive() {
+                // Flipped through flat: the new side starts at this price.
+                pos.entry_price = price;
+                pos.leverage = leverage;
+            }
+            pos.size = remaining;
+        }
+        realized
+    }
+
+    fn record(
+        &mut self,
+        exec: &Execution,
+        size: Decimal,
+        fee: Decimal,
+        realized: Decimal,
+        now_ms: u64,
+    ) -> Fill {
+        self.fill_count += 1;
+        let fill = Fill {
+            protocol: Protocol::Paper,
+            symbol: exec.symbol.clone(),
+            side: exec.side.clone(),
+            price: exec.price,
+            size,
+            fee,
+            realized_pnl: Some(realized),
+            order_id: exec.oid.to_string(),
+            tx_hash: Some(format!("paper-{}", self.fill_count)),
+            timestamp_ms: now_ms,
+        };
+        self.fills.push(fill.clone());
+        if self.fills.len() > MAX_FILLS {
+            let excess = self.fills.len() - MAX_FILLS;
+            self.fills.drain(..excess);
+        }
+        fill
+    }
+
+    /// Fill resting orders the mark has crossed, at their limit price.
+    /// An order that can no longer fill (margin, reduce-only) is dropped.
+    pub fn match_orders(
+        &mut self,
+        settings: &PaperSettings,
+        marks: &HashMap<String, Decimal>,
+        now_ms: u64,
+    ) -> Vec<Fill> {
+        let mut fills = Vec::new();
+        for order in std::mem::take(&mut self.orders) {
+            match marks.get(&order.symbol) {
+                Some(&mark) if order.crosses(mark) => {
+                    match self.execute(&order.execution(order.price), settings, marks, now_ms) {
+                        Ok(fill) => fills.push(fill),
+                        Err(e) => {
+                            warn!(oid = order.oid, coin = %order.symbol, "paper order dropped: {e}")
+                        }
+                    }
+                }
+                _ => self.orders.push(order),
+            }
+        }
+        fills
+    }
+
+    /// Close, at the mark and without a fee, every position whose margin
+    /// is used up. The loss is realized against the balance.
+    pub fn liquidate(&mut self, marks: &HashMap<String, Decimal>, now_ms: u64) -> Vec<Fill> {
+        let doomed: Vec<(String, Decimal)> = self
+            .positions
+            .iter()
+            .filter_map(|(symbol, p)| {
+                let mark = *marks.get(symbol)?;
+                p.is_liquidatable(mark).then(|| (symbol.clone(), mark))
+            })
+            .collect();
+
+        let mut fills = Vec::new();
+        for (symbol, mark) in doomed {
+            let Some(pos) = self.positions.get(&symbol).cloned() else {
+                continue;
+            };
+            let exec = Execution {
+                symbol: symbol.clone(),
+                side: match pos.side() {
+                    Side::Buy => Side::Sell,
+                    Side::Sell => Side::Buy,
+                },
+                size: pos.size.abs(),
+                price: mark,
+                reduce_only: true,
+                oid: self.next_oid(),
+            };
+            let realized = self.apply(&symbol, -pos.size, mark, pos.leverage);
+            self.balance += realized;
+            warn!(coin = %symbol, mark = %mark, pnl = %realized, "paper position liquidated");
+            fills.push(self.record(&exec, exec.size, Decimal::ZERO, realized, now_ms));
+        }
+        fills
+    }
+
+    /// Bring the account up to date with the marks: crossed orders fill,
+    /// then under-margined positions are liquidated.
+    pub fn settle(
+        &mut self,
+        settings: &PaperSettings,
+        marks: &HashMap<String, Decimal>,
+        now_ms: u64,
+    ) -> Vec<Fill> {
+        let mut fills = self.match_orders(settings, marks, now_ms);
+        fills.extend(self.liquidate(marks, now_ms));
+        fills
+    }
+
+    /// Remove a resting order by oid or client order ID.
+    pub fn cancel(&mut self, order_id: &str) -> Option<PaperOrder> {
+        let idx = self.orders.iter().position(|o| {
+            o.oid.to_string() == order_id
+                || o.cloid
+                    .as_deref()
+                    .is_some_and(|c| c.eq_ignore_ascii_case(order_id))
+        })?;
+        Some(self.orders.remove(idx))
+    }
+
+    /// Remove every resting order on `symbol`. Returns how many.
+    pub fn cancel_all(&mut self, symbol: &str) -> u32 {
+        let before = self.orders.len();
+        self.orders.retain(|o| o.symbol != symbol);
+        (before - self.orders.len()) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn settings() -> PaperSettings {
+        PaperSettings {
+            starting_balance: d("10000"),
+            fee_bps: d("5"),
+            slippage_bps: d("10"),
+            default_leverage: 1,
+        }
+    }
+
+    fn marks(pairs: &[(&str, &str)]) -> HashMap<String, Decimal> {
+        pairs.iter().map(|(s, p)| (s.to_string(), d(p))).collect()
+    }
+
+    fn exec(symbol: &str, side: Side, size: &str, price: &str) -> Execution {
+        Execution {
+            symbol: symbol.into(),
+            side,
+            size: d(size),
+            price: d(price),
+            reduce_only: false,
+            oid: 1,
+        }
+    }
+
+    #[test]
+    fn test_long_round_trip_realizes_pnl_minus_fees() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("10000"));
+        let m = marks(&[("ETH", "2000")]);
+
+        let open = acct
+            .execute(&exec("ETH", Side::Buy, "1", "2000"), &s, &m, 1)
+            .unwrap();
+        assert_eq!(open.fee, d("1")); // 2000 × 5 bps
+        assert_eq!(open.realized_pnl, Some(Decimal::ZERO));
+        assert_eq!(acct.positions["ETH"].size, d("1"));
+
+        let m = marks(&[("ETH", "2200")]);
+        assert_eq!(acct.equity(&m), d("10199"));
+        let close = acct
+            .execute(&exec("ETH", Side::Sell, "1", "2200"), &s, &m, 2)
+            .unwrap();
+        assert_eq!(close.realized_pnl, Some(d("200")));
+        assert_eq!(close.fee, d("1.1"));
+        assert!(acct.positions.is_empty());
+        assert_eq!(acct.balance, d("10197.9"));
+    }
+
+    #[test]
+    fn test_short_profits_when_price_falls() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("10000"));
+        let m = marks(&[("BTC", "50000")]);
+        acct.execute(&exec("BTC", Side::Sell, "0.1", "50000"), &s, &m, 1)
+            .unwrap();
+        let pos = &acct.positions["BTC"];
+        assert_eq!(pos.size, d("-0.1"));
+        assert_eq!(pos.side(), Side::Sell);
+        assert_eq!(pos.unrealized_pnl(d("48000")), d("200"));
+
+        let m = marks(&[("BTC", "48000")]);
+        let close = acct
+            .execute(&exec("BTC", Side::Buy, "0.1", "48000"), &s, &m, 2)
+            .unwrap();
+        assert_eq!(close.realized_pnl, Some(d("200")));
+        // 200 profit − 2.5 − 2.4 fees
+        assert_eq!(acct.balance, d("10195.1"));
+    }
+
+    #[test]
+    fn test_partial_close_keeps_entry_and_realizes_pro_rata() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("10000"));
+        let m = marks(&[("ETH", "2000")]);
+        acct.execute(&exec("ETH", Side::Buy, "2", "2000"), &s, &m, 1)
+            .unwrap();
+        let fill = acct
+            .execute(&exec("ETH", Side::Sell, "0.5", "2100"), &s, &m, 2)
+            .unwrap();
+        assert_eq!(fill.realized_pnl, Some(d("50")));
+        let pos = &acct.positions["ETH"];
+        assert_eq!(pos.size, d("1.5"));
+        assert_eq!(pos.entry_price, d("2000"));
+    }
+
+    #[test]
+    fn test_adding_averages_entry() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("10000"));
+        let m = marks(&[("ETH", "2000")]);
+        acct.execute(&exec("ETH", Side::Buy, "1", "2000"), &s, &m, 1)
+            .unwrap();
+        acct.execute(&exec("ETH", Side::Buy, "1", "2200"), &s, &m, 2)
+            .unwrap();
+        assert_eq!(acct.positions["ETH"].entry_price, d("2100"));
+        assert_eq!(acct.positions["ETH"].size, d("2"));
+    }
+
+    #[test]
+    fn test_flip_realizes_closed_part_and_reenters() {
+        let s = PaperSettings {
+            fee_bps: Decimal::ZERO,
+            ..settings()
+        };
+        let mut acct = PaperAccount::new(d("10000"));
+        let m = marks(&[("ETH", "2000")]);
+        acct.execute(&exec("ETH", Side::Buy, "1", "2000"), &s, &m, 1)
+            .unwrap();
+        let fill = acct
+            .execute(&exec("ETH", Side::Sell, "3", "1900"), &s, &m, 2)
+            .unwrap();
+        assert_eq!(fill.realized_pnl, Some(d("-100")));
+        let pos = &acct.positions["ETH"];
+        assert_eq!(pos.size, d("-2"));
+        assert_eq!(pos.entry_price, d("1900"));
+        assert_eq!(acct.balance, d("9900"));
+    }
+
+    #[test]
+    fn test_reduce_only() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("10000"));
+        let m = marks(&[("ETH", "2000")]);
+        let mut sell = exec("ETH", Side::Sell, "1", "2000");
+        sell.reduce_only = true;
+        let err = acct.execute(&sell, &s, &m, 1).unwrap_err();
+        assert!(err.to_string().contains("Reduce only"), "{err}");
+
+        acct.execute(&exec("ETH", Side::Buy, "0.5", "2000"), &s, &m, 1)
+            .unwrap();
+        // Larger than the position: clamped, never flips
+        let fill = acct.execute(&sell, &s, &m, 2).unwrap();
+        assert_eq!(fill.size, d("0.5"));
+        assert!(acct.positions.is_empty());
+    }
+
+    #[test]
+    fn test_insufficient_margin_leaves_account_untouched() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("1000"));
+        let m = marks(&[("ETH", "2000")]);
+        let before = acct.clone();
+        let err = acct
+            .execute(&exec("ETH", Side::Buy, "1", "2000"), &s, &m, 1)
+            .unwrap_err();
+        assert!(matches!(err, AtlasError::InsufficientMargin(_)));
+        assert_eq!(acct, before);
+
+        // Same order at 5x fits: 400 margin + 1 fee
+        acct.set_leverage("ETH", 5);
+        acct.execute(&exec("ETH", Side::Buy, "1", "2000"), &s, &m, 1)
+            .unwrap();
+        assert_eq!(acct.margin_used(&m), d("400"));
+        assert_eq!(acct.available(&m), d("599"));
+    }
+
+    #[test]
+    fn test_closing_needs_no_margin() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("2001"));
+        let m = marks(&[("ETH", "2000")]);
+        acct.execute(&exec("ETH", Side::Buy, "1", "2000"), &s, &m, 1)
+            .unwrap();
+        assert_eq!(acct.available(&m), d("0"));
+        acct.execute(&exec("ETH", Side::Sell, "1", "2000"), &s, &m, 2)
+            .unwrap();
+        assert_eq!(acct.balance, d("1999"));
+    }
+
+    #[test]
+    fn test_liquidation_when_position_margin_is_gone() {
+        let s = PaperSettings {
+            fee_bps: Decimal::ZERO,
+            ..settings()
+        };
+        let mut acct = PaperAccount::new(d("10000"));
+        acct.set_leverage("ETH", 10);
+        let m = marks(&[("ETH", "2000")]);
+        acct.execute(&exec("ETH", Side::Buy, "5", "2000"), &s, &m, 1)
+            .unwrap();
+        let pos = acct.positions["ETH"].clone();
+        assert_eq!(pos.margin(), d("1000"));
+        assert_eq!(pos.liquidation_price(), Some(d("1800")));
+
+        assert!(acct.liquidate(&marks(&[("ETH", "1801")]), 2).is_empty());
+        let fills = acct.liquidate(&marks(&[("ETH", "1790")]), 3);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, Side::Sell);
+        assert_eq!(fills[0].realized_pnl, Some(d("-1050")));
+        assert!(acct.positions.is_empty());
+        assert_eq!(acct.balance, d("8950"));
+    }
+
+    #[test]
+    fn test_short_liquidation_price() {
+        let pos = PaperPosition {
+            size: d("-1"),
+            entry_price: d("100"),
+            leverage: 4,
+        };
+        assert_eq!(pos.liquidation_price(), Some(d("125")));
+        assert!(pos.is_liquidatable(d("125")));
+        assert!(!pos.is_liquidatable(d("124")));
+        let unlevered = PaperPosition {
+            size: d("1"),
+            entry_price: d("100"),
+            leverage: 1,
+        };
+        assert_eq!(unlevered.liquidation_price(), None);
+    }
+
+    #[test]
+    fn test_resting_orders_fill_at_limit_once_crossed() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("10000"));
+        let oid = acct.next_oid();
+        acct.orders.push(PaperOrder {
+            oid,
+            symbol: "ETH".into(),
+            side: Side::Buy,
+            size: d("1"),
+            price: d("1950"),
+            reduce_only: false,
+            cloid: None,
+            timestamp_ms: 0,
+        });
+
+        assert!(acct.settle(&s, &marks(&[("ETH", "2000")]), 1).is_empty());
+        assert_eq!(acct.orders.len(), 1);
+
+        let fills = acct.settle(&s, &marks(&[("ETH", "1940")]), 2);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, d("1950"));
+        assert_eq!(fills[0].order_id, oid.to_string());
+        assert!(acct.orders.is_empty());
+        assert_eq!(acct.positions["ETH"].entry_price, d("1950"));
+    }
+
+    #[test]
+    fn test_unfillable_resting_order_is_dropped() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("100"));
+        acct.orders.push(PaperOrder {
+            oid: 1,
+            symbol: "ETH".into(),
+            side: Side::Buy,
+            size: d("1"),
+            price: d("2000"),
+            reduce_only: false,
+            cloid: None,
+            timestamp_ms: 0,
+        });
+        assert!(acct.settle(&s, &marks(&[("ETH", "1990")]), 1).is_empty());
+        assert!(acct.orders.is_empty());
+        assert!(acct.positions.is_empty());
+    }
+
+    #[test]
+    fn test_cancel_by_oid_or_cloid() {
+        let mut acct = PaperAccount::new(d("100"));
+        for (oid, cloid) in [(1, None), (2, Some("0xabc")), (3, None)] {
+            acct.orders.push(PaperOrder {
+                oid,
+                symbol: if oid == 3 { "BTC" } else { "ETH" }.into(),
+                side: Side::Buy,
+                size: d("1"),
+                price: d("1"),
+                reduce_only: false,
+                cloid: cloid.map(str::to_string),
+                timestamp_ms: 0,
+            });
+        }
+        assert_eq!(acct.cancel("0xABC").map(|o| o.oid), Some(2));
+        assert!(acct.cancel("9").is_none());
+        assert_eq!(acct.cancel_all("ETH"), 1);
+        assert_eq!(acct.orders.len(), 1);
+    }
+
+    #[test]
+    fn test_fill_history_is_capped() {
+        let s = PaperSettings {
+            fee_bps: Decimal::ZERO,
+            ..settings()
+        };
+        let mut acct = PaperAccount::new(d("1000000"));
+        let m = marks(&[("ETH", "1")]);
+        for i in 0..(MAX_FILLS + 5) {
+            acct.execute(&exec("ETH", Side::Buy, "1", "1"), &s, &m, i as u64)
+                .unwrap();
+        }
+        assert_eq!(acct.fills.len(), MAX_FILLS);
+        assert_eq!(acct.fill_count, (MAX_FILLS + 5) as u64);
+        assert_eq!(acct.fills[0].timestamp_ms, 5);
+    }
+
+    #[test]
+    fn test_market_price_slips_against_taker() {
+        let s = settings();
+        assert_eq!(s.market_price(d("1000"), &Side::Buy), d("1001"));
+        assert_eq!(s.market_price(d("1000"), &Side::Sell), d("999"));
+    }
+
+    #[test]
+    fn test_account_json_roundtrip() {
+        let s = settings();
+        let mut acct = PaperAccount::new(d("10000"));
+        let m = marks(&[("ETH", "2000")]);
+        acct.execute(&exec("ETH", Side::Buy, "1", "2000"), &s, &m, 1)
+            .unwrap();
+        let json = serde_json::to_string(&acct).unwrap();
+        let back: PaperAccount = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, acct);
+    }
+}
@@ -0,0 +1,828 @@
+//! `PaperModule` — a `PerpModule` that trades a simulated account.
+//!
+//! Market data is proxied from a live, read-only perp module; orders are
+//! matched locally against its mids. Every call first settles the account
+//! at current prices (crossed limit orders fill, under-margined positions
+//! are liquidated), so state is always as of the latest quote.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use atlas_core::date_range::now_ms;
+use atlas_core::db::{AtlasDb, DbFill};
+use atlas_core::error::{AtlasError, AtlasResult};
+use atlas_core::traits::PerpModule;
+use atlas_core::types::*;
+use rust_decimal::Decimal;
+use tracing::debug;
+
+use crate::account::{Execution, PaperAccount, PaperOrder, PaperSettings};
+
+/// Paper trading module.
+pub struct PaperModule {
+    /// Live module prices and market data come from.
+    data: Arc<dyn PerpModule>,
+    settings: PaperSettings,
+    store: Mutex<Store>,
+}
+
+/// Where the account lives between calls.
+enum Store {
+    /// The local DB, so the account carries over between commands.
+    Db,
+    /// Memory only (tests).
+    Memory(PaperAccount),
+}
+
+impl Store {
+    fn load(&self, settings: &PaperSettings) -> AtlasResult<PaperAccount> {
+        match self {
+            Store::Memory(account) => Ok(account.clone()),
+            Store::Db => match open_db()?.paper_account().map_err(db_error)? {
+                Some(state) => serde_json::from_str(&state).map_err(|e| {
+                    AtlasError::Database(format!(
+                        "Unreadable paper account ({e}). Start over with: atlas paper reset"
+                    ))
+                }),
+                None => Ok(PaperAccount::new(settings.starting_balance)),
+            },
+        }
+    }
+
+    /// Save `account`; fills booked after `seen` also go to the fills table.
+    fn save(&mut self, account: &PaperAccount, seen: u64) -> AtlasResult<()> {
+        match self {
+            Store::Memory(stored) => *stored = account.clone(),
+            Store::Db => {
+                let new =
+                    (account.fill_count.saturating_sub(seen) as usize).min(account.fills.len());
+                let fills: Vec<DbFill> = account.fills[account.fills.len() - new..]
+                    .iter()
+                    .map(db_fill)
+                    .collect();
+                let state = serde_json::to_string(account)
+                    .map_err(|e| AtlasError::Internal(format!("paper account: {e}")))?;
+                open_db()?
+                    .save_paper_account(&state, &fills)
+                    .map_err(db_error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PaperModule {
+    /// Paper account stored in the local DB.
+    pub fn new(data: Arc<dyn PerpModule>, settings: PaperSettings) -> Self {
+        Self {
+            data,
+            settings,
+            store: Mutex::new(Store::Db),
+        }
+    }
+
+    /// Fresh account held in memory only.
+    pub fn in_memory(data: Arc<dyn PerpModule>, settings: PaperSettings) -> Self {
+        let account = PaperAccount::new(settings.starting_balance);
+        Self {
+            data,
+            settings,
+            store: Mutex::new(Store::Memory(account)),
+        }
+    }
+
+    /// Current mids for every market, from the data module.
+    async fn marks(&self) -> AtlasResult<HashMap<String, Decimal>> {
+        Ok(self
+            .data
+            .all_tickers()
+            .await?
+            .into_iter()
+            .map(|t| (t.symbol, t.mid_price))
+            .collect())
+    }
+
+    /// Load the account, settle it at `marks`, run `op` and save. The
+    /// settlement is kept even when `op` fails.
+    fn with_account<R>(
+        &self,
+        marks: &HashMap<String, Decimal>,
+        op: impl FnOnce(&mut PaperAccount, u64) -> AtlasResult<R>,
+    ) -> AtlasResult<R> {
+        let mut store = self
+            .store
+            .lock()
+            .map_err(|_| AtlasError::Internal("paper account lock poisoned".into()))?;
+        let mut account = store.load(&self.settings)?;
+        let seen = account.fill_count;
+        let now = now_ms() as u64;
+
+        let settled = account.settle(&self.settings, marks, now);
+        if !settled.is_empty() {
+            debug!(fills = settled.len(), "paper account settled");
+        }
+        let result = op(&mut account, now);
+        store.save(&account, seen)?;
+        result
+    }
+}
+
+/// Start the stored paper account over with `balance` USDC. Positions,
+/// orders and paper fill history are cleared. Returns the fills removed.
+pub fn reset(balance: Decimal) -> AtlasResult<usize> {
+    let db = open_db()?;
+    let removed = db
+        .delete_fills(&Protocol::Paper.to_string())
+        .map_err(db_error)?;
+    let state = serde_json::to_string(&PaperAccount::new(balance))
+        .map_err(|e| AtlasError::Internal(format!("paper account: {e}")))?;
+    db.save_paper_account(&state, &[]).map_err(db_error)?;
+    Ok(removed)
+}
+
+fn open_db() -> AtlasResult<AtlasDb> {
+    AtlasDb::open().map_err(db_error)
+}
+
+fn db_error(e: anyhow::Error) -> AtlasError {
+    AtlasError::Database(format!("{e:#}"))
+}
+
+fn mid(marks: &HashMap<String, Decimal>, symbol: &str) -> AtlasResult<Decimal> {
+    marks
+        .get(symbol)
+        .copied()
+        .ok_or_else(|| AtlasError::asset_not_found(symbol))
+}
+
+fn db_fill(f: &Fill) -> DbFill {
+    DbFill {
+        protocol: Protocol::Paper.to_string(),
+        coin: f.symbol.clone(),
+        px: f.price.to_string(),
+        sz: f.size.to_string(),
+        side: match f.side {
+            Side::Buy => "Buy",
+            Side::Sell => "Sell",
+        }
+        .to_string(),
+        time_ms: f.timestamp_ms as i64,
+        fee: f.fee.to_string(),
+        hash: f.tx_hash.clone().unwrap_or_default(),
+        oid: f.order_id.parse().unwrap_or_default(),
+        closed_pnl: f.realized_pnl.unwrap_or_default().to_string(),
+    }
+}
+
+fn filled(fill: &Fill) -> OrderResult {
+    OrderResult {
+        protocol: Protocol::Paper,
+        order_id: fill.order_id.clone(),
+        coin: Some(fill.symbol.clone()),
+        side: Some(fill.side.clone()),
+        status: OrderStatus::Filled,
+        filled_size: Some(fill.size),
+        avg_price: Some(fill.price),
+        fee: Some(fill.fee),
+        timestamp: Some(fill.timestamp_ms / 1000),
+        builder_fee_bps: None,
+        message: None,
+        audit_id: None,
+    }
+}
+
+fn resting(order: &PaperOrder) -> OrderResult {
+    OrderResult {
+        protocol: Protocol::Paper,
+        order_id: order.oid.to_string(),
+        coin: Some(order.symbol.clone()),
+        side: Some(order.side.clone()),
+        status: OrderStatus::Open,
+        filled_size: None,
+        avg_price: None,
+        fee: None,
+        timestamp: Some(order.timestamp_ms / 1000),
+        builder_fee_bps: None,
+        message: None,
+        audit_id: None,
+    }
+}
+
+/// Place `order`: a marketable one takes at the mid (never worse than its
+/// limit), anything else rests unless the time in force forbids it.
+fn place(
+    account: &mut PaperAccount,
+    order: PaperOrder,
+    tif: TimeInForce,
+    mid: Decimal,
+    settings: &PaperSettings,
+    marks: &HashMap<String, Decimal>,
+) -> AtlasResult<OrderResult> {
+    if order.crosses(mid) {
+        if tif == TimeInForce::Alo {
+            return Err(AtlasError::order_rejected(format!(
+                "Post only order would have immediately matched, bbo was {mid}"
+            )));
+        }
+        let fill = account.execute(&order.execution(mid), settings, marks, order.timestamp_ms)?;
+        return Ok(filled(&fill));
+    }
+    if tif == TimeInForce::Ioc {
+        return Err(AtlasError::order_rejected(
+            "Order could not immediately match against any resting orders",
+        ));
+    }
+    if order.reduce_only && !account.reduces(&order.symbol, &order.side) {
+        return Err(AtlasError::order_rejected(
+            "Reduce only order would increase position",
+        ));
+    }
+    let result = resting(&order);
+    account.orders.push(order);
+    Ok(result)
+}
+
+#[async_trait]
+impl PerpModule for PaperModule {
+    fn protocol(&self) -> Protocol {
+        Protocol::Paper
+    }
+
+    fn resolve_symbol(&self, input: &str) -> AtlasResult<String> {
+        self.data.resolve_symbol(input)
+    }
+
+    // ── Market data: straight from the live module ──────────────────
+
+    async fn markets(&self) -> AtlasResult<Vec<Market>> {
+        self.data.markets().await
+    }
+
+    async fn ticker(&self, symbol: &str) -> AtlasResult<Ticker> {
+        self.data.ticker(symbol).await
+    }
+
+    async fn all_tickers(&self) -> AtlasResult<Vec<Ticker>> {
+        self.data.all_tickers().await
+    }
+
+    async fn candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: usize,
+    ) -> AtlasResult<Vec<Candle>> {
+        self.data.candles(symbol, interval, limit).await
+    }
+
+    async fn funding(&self, symbol: &str) -> AtlasResult<Vec<FundingRate>> {
+        self.data.funding(symbol).await
+    }
+
+    async fn orderbook(&self, symbol: &str, depth: usize) -> AtlasResult<OrderBook> {
+        self.data.orderbook(symbol, depth).await
+    }
+
+    async fn asset_contexts(&self) -> AtlasResult<Vec<AssetContext>> {
+        self.data.asset_contexts().await
+    }
+
+    // ── Trading: simulated ──────────────────────────────────────────
+
+    /// Fills in full at the mid moved by the configured slippage; the
+    /// caller's slippage tolerance is not needed.
+    async fn market_order(
+        &self,
+        symbol: &str,
+        side: Side,
+        size: Decimal,
+        _slippage: Option<f64>,
+    ) -> AtlasResult<OrderResult> {
+        let marks = self.marks().await?;
+        let price = self.settings.market_price(mid(&marks, symbol)?, &side);
+        self.with_account(&marks, |account, now| {
+            let exec = Execution {
+                symbol: symbol.to_string(),
+                side,
+                size,
+                price,
+                reduce_only: false,
+                oid: account.next_oid(),
+            };
+            Ok(filled(&account.execute(
+                &exec,
+                &self.settings,
+                &marks,
+                now,
+            )?))
+        })
+    }
+
+    async fn limit_order(
+        &self,
+        symbol: &str,
+        side: Side,
+        size: Decimal,
+        price: Decimal,
+        opts: &LimitOptions,
+    ) -> AtlasResult<OrderResult> {
+        if opts.trigger.is_some() {
+            return Err(AtlasError::Other(
+                "Trigger orders aren't simulated by the paper account".into(),
+            ));
+        }
+        let marks = self.marks().await?;
+        let mid = mid(&marks, symbol)?;
+        self.with_account(&marks, |account, now| {
+            let order = PaperOrder {
+                oid: account.next_oid(),
+                symbol: symbol.to_string(),
+                side,
+                size,
+                price,
+                reduce_only: opts.reduce_only,
+                cloid: opts.cloid.clone(),
+                timestamp_ms: now,
+            };
+            place(account, order, opts.tif, mid, &self.settings, &marks)
+        })
+    }
+
+    async fn close_position(
+        &self,
+        symbol: &str,
+        size: Option<Decimal>,
+        _slippage: Option<f64>,
+    ) -> AtlasResult<OrderResult> {
+        let marks = self.marks().await?;
+        let mid = mid(&marks, symbol)?;
+        self.with_account(&marks, |account, now| {
+            let open = account
+                .positions
+                .get(symbol)
+                .map(|p| (p.side(), p.size.abs()))
+                .ok_or_else(|| AtlasError::PositionNotFound(symbol.to_string()))?;
+            let side = match open.0 {
+                Side::Buy => Side::Sell,
+                Side::Sell => Side::Buy,
+            };
+            let exec = Execution {
+                symbol: symbol.to_string(),
+                price: self.settings.market_price(mid, &side),
+                side,
+                size: size.map_or(open.1, |s| s.min(open.1)),
+                reduce_only: true,
+                oid: account.next_oid(),
+            };
+            Ok(filled(&account.execute(
+                &exec,
+                &self.settings,
+                &marks,
+                now,
+            )?))
+        })
+    }
+
+    async fn modify_order(
+        &self,
+        symbol: &str,
+        order_id: &str,
+        price: Option<Decimal>,
+        size: Option<Decimal>,
+    ) -> AtlasResult<OrderResult> {
+        let marks = self.marks().await?;
+        let mid = mid(&marks, symbol)?;
+        self.with_account(&marks, |account, _| {
+            let mut order = account.cancel(order_id).ok_or_else(|| {
+                AtlasError::order_rejected("Cannot modify canceled or filled order")
+            })?;
+            order.price = price.unwrap_or(order.price);
+            order.size = size.unwrap_or(order.size);
+            place(
+                account,
+                order,
+                TimeInForce::Gtc,
+                mid,
+                &self.settings,
+                &marks,
+            )
+        })
+    }
+
+    async fn cancel_order(&self, _symbol: &str, order_id: &str) -> AtlasResult<()> {
+        let marks = self.marks().await?;
+        self.with_account(&marks, |account, _| {
+            account.cancel(order_id).map(|_| ()).ok_or_else(|| {
+                AtlasError::order_rejected("Order was never placed, already canceled, or filled")
+            })
+        })
+    }
+
+    async fn cancel_all(&self, symbol: &str) -> AtlasResult<u32> {
+        let marks = self.marks().await?;
+        self.with_account(&marks, |account, _| Ok(account.cancel_all(symbol)))
+    }
+
+    async fn open_orders(&self) -> AtlasResult<Vec<Order>> {
+        let marks = self.marks().await?;
+        self.with_account(&marks, |account, _| {
+            Ok(account
+                .orders
+                .iter()
+                .map(|o| Order {
+                    protocol: Protocol::Paper,
+                    symbol: o.symbol.clone(),
+                    market_type: MarketType::Perp,
+                    side: o.side.clone(),
+                    order_type: OrderType::Limit,
+                    size: o.size,
+                    price: Some(o.price),
+                    trigger_price: None,
+                    filled_size: Some(Decimal::ZERO),
+                    status: OrderStatus::Open,
+                    order_id: o.oid.to_string(),
+                    timestamp_ms: o.timestamp_ms,
+                })
+                .collect())
+        })
+    }
+
+    async fn positions(&self) -> AtlasResult<Vec<Position>> {
+        let marks = self.marks().await?;
+        self.with_account(&marks, |account, _| {
+            Ok(account
+                .positions
+                .iter()
+                .map(|(symbol, p)| {
+                    let mark = account.mark(symbol, &marks).unwrap_or(p.entry_price);
+                    Position {
+                        protocol: Protocol::Paper,
+                        symbol: symbol.clone(),
+                        side: p.side(),
+                        size: p.size,
+                        entry_price: Some(p.entry_price),
+                        mark_price: Some(mark),
+                        unrealized_pnl: Some(p.unrealized_pnl(mark)),
+                        leverage: Some(p.leverage),
+                        margin: Some(p.margin()),
+                        liquidation_price: p.liquidation_price(),
+                        margin_mode: Some("isolated".into()),
+                    }
+                })
+                .collect())
+        })
+    }
+
+    /// Newest first.
+    async fn fills(&self) -> AtlasResult<Vec<Fill>> {
+        let marks = self.marks().await?;
+        self.with_account(&marks, |account, _| {
+            Ok(account.fills.iter().rev().cloned().collect())
+        })
+    }
+
+    async fn balances(&self) -> AtlasResult<Vec<Balance>> {
+        let marks = self.marks().await?;
+        self.with_account(&marks, |account, _| {
+            Ok(vec![Balance {
+                protocol: Protocol::Paper,
+                chain: Chain::HyperliquidL1,
+                asset: "USDC".into(),
+                total: account.equity(&marks),
+                available: account.available(&marks),
+                locked: account.margin_used(&marks),
+            }])
+        })
+    }
+
+    async fn set_leverage(&self, symbol: &str, leverage: u32, _is_cross: bool) -> AtlasResult<()> {
+        let marks = self.marks().await?;
+        self.with_account(&marks, |account, _| {
+            account.set_leverage(symbol, leverage);
+            Ok(())
+        })
+    }
+
+    async fn update_margin(&self, _symbol: &str, _amount: Decimal) -> AtlasResult<()> {
+        Err(AtlasError::Other(
+            "Paper positions are margined at their leverage; adding margin is not simulated".into(),
+        ))
+    }
+
+    async fn transfer(&self, _amount: Decimal, _destination: &str) -> AtlasResult<String> {
+        Err(AtlasError::Other(
+            "Transfers are not available on the paper account".into(),
+        ))
+    }
+
+    async fn cancel_by_cloid(&self, symbol: &str, cloid: &str) -> AtlasResult<()> {
+        self.cancel_order(symbol, cloid).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atlas_core::error::ExchangeRejectionKind;
+
+    /// Price feed standing in for the live module; only mids are served.
+    struct Feed(Mutex<HashMap<String, Decimal>>);
+
+    impl Feed {
+        fn set(&self, symbol: &str, px: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(symbol.into(), px.parse().unwrap());
+        }
+    }
+
+    #[async_trait]
+    impl PerpModule for Feed {
+        fn protocol(&self) -> Protocol {
+            Protocol::Hyperliquid
+        }
+        async fn markets(&self) -> AtlasResult<Vec<Market>> {
+            unimplemented!()
+        }
+        async fn ticker(&self, _: &str) -> AtlasResult<Ticker> {
+            unimplemented!()
+        }
+        async fn all_tickers(&self) -> AtlasResult<Vec<Ticker>> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(symbol, mid)| Ticker {
+                    symbol: symbol.clone(),
+                    protocol: Protocol::Hyperliquid,
+                    mid_price: *mid,
+                    best_bid: None,
+                    best_ask: None,
+                    volume_24h: None,
+                    change_24h_pct: None,
+                })
+                .collect())
+        }
+        async fn candles(&self, _: &str, _: &str, _: usize) -> AtlasResult<Vec<Candle>> {
+            unimplemented!()
+        }
+        async fn funding(&self, _: &str) -> AtlasResult<Vec<FundingRate>> {
+            unimplemented!()
+        }
+        async fn orderbook(&self, _: &str, _: usize) -> AtlasResult<OrderBook> {
+            unimplemented!()
+        }
+        async fn market_order(
+            &self,
+            _: &str,
+            _: Side,
+            _: Decimal,
+            _: Option<f64>,
+        ) -> AtlasResult<OrderResult> {
+            unimplemented!()
+        }
+        async fn limit_order(
+            &self,
+            _: &str,
+            _: Side,
+            _: Decimal,
+            _: Decimal,
+            _: &LimitOptions,
+        ) -> AtlasResult<OrderResult> {
+            unimplemented!()
+        }
+        async fn close_position(
+            &self,
+            _: &str,
+            _: Option<Decimal>,
+            _: Option<f64>,
+        ) -> AtlasResult<OrderResult> {
+            unimplemented!()
+        }
+        async fn cancel_order(&self, _: &str, _: &str) -> AtlasResult<()> {
+            unimplemented!()
+        }
+        async fn cancel_all(&self, _: &str) -> AtlasResult<u32> {
+            unimplemented!()
+        }
+        async fn open_orders(&self) -> AtlasResult<Vec<Order>> {
+            unimplemented!()
+        }
+        async fn positions(&self) -> AtlasResult<Vec<Position>> {
+            unimplemented!()
+        }
+        async fn fills(&self) -> AtlasResult<Vec<Fill>> {
+            unimplemented!()
+        }
+        async fn balances(&self) -> AtlasResult<Vec<Balance>> {
+            unimplemented!()
+        }
+        async fn set_leverage(&self, _: &str, _: u32, _: bool) -> AtlasResult<()> {
+            unimplemented!()
+        }
+        async fn update_margin(&self, _: &str, _: Decimal) -> AtlasResult<()> {
+            unimplemented!()
+        }
+        async fn transfer(&self, _: Decimal, _: &str) -> AtlasResult<String> {
+            unimplemented!()
+        }
+    }
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn paper() -> (Arc<Feed>, PaperModule) {
+        let feed = Arc::new(Feed(Mutex::new(HashMap::new())));
+        feed.set("ETH", "2000");
+        let settings = PaperSettings {
+            starting_balance: d("10000"),
+            fee_bps: d("5"),
+            slippage_bps: d("10"),
+            default_leverage: 1,
+        };
+        (feed.clone(), PaperModule::in_memory(feed, settings))
+    }
+
+    #[tokio::test]
+    async fn test_market_order_opens_position_at_slipped_mid() {
+        let (_, paper) = paper();
+        let result = paper
+            .market_order("ETH", Side::Buy, d("1"), Some(0.05))
+            .await
+            .unwrap();
+        assert_eq!(result.protocol, Protocol::Paper);
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.avg_price, Some(d("2002")));
+
+        let positions = paper.positions().await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].unrealized_pnl, Some(d("-2")));
+
+        let balance = &paper.balances().await.unwrap()[0];
+        // 10000 − 1.001 fee − 2 unrealized
+        assert_eq!(balance.total, d("9996.999"));
+        assert_eq!(balance.locked, d("2000"));
+    }
+
+    #[tokio::test]
+    async fn test_limit_order_rests_then_fills_when_crossed() {
+        let (feed, paper) = paper();
+        let result = paper
+            .limit_order(
+                "ETH",
+                Side::Buy,
+                d("1"),
+                d("1900"),
+                &LimitOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.status, OrderStatus::Open);
+        assert_eq!(paper.open_orders().await.unwrap().len(), 1);
+
+        feed.set("ETH", "1890");
+        assert!(paper.open_orders().await.unwrap().is_empty());
+        let fills = paper.fills().await.unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, d("1900"));
+        assert_eq!(fills[0].order_id, result.order_id);
+    }
+
+    #[tokio::test]
+    async fn test_marketable_limit_fills_at_mid_unless_post_only() {
+        let (_, paper) = paper();
+        let alo = LimitOptions {
+            tif: TimeInForce::Alo,
+            ..Default::default()
+        };
+        let err = paper
+            .limit_order("ETH", Side::Buy, d("1"), d("2100"), &alo)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AtlasError::OrderRejected {
+                kind: ExchangeRejectionKind::PostOnlyWouldCross,
+                ..
+            }
+        ));
+        assert_eq!(err.to_json()["error"]["bbo"], "2000");
+
+        let result = paper
+            .limit_order(
+                "ETH",
+                Side::Buy,
+                d("1"),
+                d("2100"),
+                &LimitOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.avg_price, Some(d("2000")));
+    }
+
+    #[tokio::test]
+    async fn test_close_and_cancel() {
+        let (_, paper) = paper();
+        let err = paper.close_position("ETH", None, None).await.unwrap_err();
+        assert!(matches!(err, AtlasError::PositionNotFound(_)));
+
+        paper
+            .market_order("ETH", Side::Sell, d("2"), None)
+            .await
+            .unwrap();
+        let result = paper
+            .close_position("ETH", Some(d("0.5")), None)
+            .await
+            .unwrap();
+        assert_eq!(result.side, Some(Side::Buy));
+        assert_eq!(result.filled_size, Some(d("0.5")));
+        assert_eq!(paper.positions().await.unwrap()[0].size, d("-1.5"));
+
+        let err = paper.cancel_order("ETH", "42").await.unwrap_err();
+        assert!(matches!(
+            err,
+            AtlasError::OrderRejected {
+                kind: ExchangeRejectionKind::AlreadyFilled,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_positions_are_liquidated_on_read() {
+        let (feed, paper) = paper();
+        paper.set_leverage("ETH", 10, true).await.unwrap();
+        paper
+            .market_order("ETH", Side::Buy, d("10"), None)
+            .await
+            .unwrap();
+        let liq = paper.positions().await.unwrap()[0]
+            .liquidation_price
+            .unwrap();
+
+        feed.set("ETH", &(liq - Decimal::ONE).to_string());
+        assert!(paper.positions().await.unwrap().is_empty());
+        let fills = paper.fills().await.unwrap();
+        assert_eq!(fills.len(), 2);
+        assert!(fills[0].realized_pnl.unwrap() < Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_modify_moves_resting_order() {
+        let (_, paper) = paper();
+        let placed = paper
+            .limit_order(
+                "ETH",
+                Side::Sell,
+                d("1"),
+                d("2100"),
+                &LimitOptions::default(),
+            )
+            .await
+            .unwrap();
+        let err = paper
+            .limit_order(
+                "ETH",
+                Side::Sell,
+                d("1"),
+                d("2100"),
+                &LimitOptions {
+                    reduce_only: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Reduce only"));
+
+        let modified = paper
+            .modify_order("ETH", &placed.order_id, Some(d("2050")), None)
+            .await
+            .unwrap();
+        assert_eq!(modified.status, OrderStatus::Open);
+        assert_eq!(paper.open_orders().await.unwrap()[0].price, Some(d("2050")));
+    }
+
+    #[tokio::test]
+    async fn test_trigger_orders_refused() {
+        let (_, paper) = paper();
+        let stop = LimitOptions {
+            trigger: Some(TriggerOptions {
+                price: d("2100"),
+                kind: TriggerKind::Market,
+            }),
+            ..Default::default()
+        };
+        let err = paper
+            .limit_order("ETH", Side::Buy, d("1"), d("2110"), &stop)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Trigger orders"), "{err}");
+        assert!(paper.open_orders().await.unwrap().is_empty());
+    }
+}
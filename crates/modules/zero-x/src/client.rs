@@ -4,10 +4,12 @@
 //! Supports 19+ EVM chains via chainId parameter.
 
 use async_trait::async_trait;
+use atlas_core::audit::{AuditOutcome, AuditTrail, SignedAction};
+use atlas_core::batch::map_concurrent;
 use atlas_core::constants::{ATLAS_FEE_WALLET, BUILDER_FEE_BPS};
 use atlas_core::error::{AtlasError, AtlasResult};
 use atlas_core::traits::SwapModule;
-use atlas_core::types::{Chain, Protocol, SwapQuote};
+use atlas_core::types::{Balance, Chain, Protocol, SwapQuote};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use tracing::info;
@@ -20,7 +22,7 @@ use alloy::signers::local::PrivateKeySigner;
 
 /// Atlas backend API sub-route for 0x proxy.
 /// Backend mounts at /atlas-os/0x (see apps/backend index).
-const ZEROX_API_BASE: &str = "/atlas-os/0x";
+pub(crate) const ZEROX_API_BASE: &str = "/atlas-os/0x";
 
 /// AllowanceHolder address (Ethereum, Arbitrum, Base, etc.)
 pub const ALLOWANCE_HOLDER: &str = "0x0000000000001fF3684f28c67538d4D072C22734";
@@ -41,11 +43,32 @@ pub fn chain_id(chain: &Chain) -> u64 {
     }
 }
 
+/// Chains whose native balance is reported by `wallet_balances`.
+const WALLET_CHAINS: [Chain; 3] = [Chain::Ethereum, Chain::Arbitrum, Chain::Base];
+
 /// Check if a chain is supported by 0x.
 pub fn is_supported(chain: &Chain) -> bool {
     !matches!(chain, Chain::Solana | Chain::HyperliquidL1)
 }
 
+/// Decode a price/quote body, mapping `liquidityAvailable: false` to
+/// [`AtlasError::NoLiquidity`]. 0x omits every other field in that case.
+pub(crate) fn decode_quote<T: serde::de::DeserializeOwned>(
+    val: serde_json::Value,
+    what: &str,
+    chain: &Chain,
+    sell_token: &str,
+    buy_token: &str,
+) -> AtlasResult<T> {
+    if val.get("liquidityAvailable") == Some(&serde_json::Value::Bool(false)) {
+        return Err(AtlasError::NoLiquidity(format!(
+            "0x has no route for {sell_token} -> {buy_token} on {chain}"
+        )));
+    }
+    serde_json::from_value(val)
+        .map_err(|e| AtlasError::Other(format!("Failed to deserialize 0x {what}: {e}")))
+}
+
 // ── Response Types ──────────────────────────────────────────────────
 
 /// 0x price/quote response (AllowanceHolder or Permit2).
@@ -79,6 +102,10 @@ pub struct ZeroXQuoteResponse {
     #[serde(default)]
     pub min_buy_amount: Option<String>,
 
+    /// Estimated gas units for the swap.
+    #[serde(default)]
+    pub gas: Option<String>,
+
     /// Gas price in wei.
     #[serde(default)]
     pub gas_price: Option<String>,
@@ -114,12 +141,42 @@ pub struct ZeroXQuoteResponse {
     /// Unique 0x request identifier.
     #[serde(default)]
     pub zid: Option<String>,
+
+    /// Decimals and USD prices added by the Atlas backend.
+    #[serde(default)]
+    pub atlas: Option<ZeroXEnrichment>,
+}
+
+/// Token metadata the Atlas backend attaches to 0x price/quote responses.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ZeroXEnrichment {
+    #[serde(default)]
+    pub native_symbol: Option<String>,
+    #[serde(default)]
+    pub native_usd_price: Option<String>,
+    pub sell_token: ZeroXTokenInfo,
+    pub buy_token: ZeroXTokenInfo,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ZeroXTokenInfo {
+    pub address: String,
+    #[serde(default)]
+    pub symbol: Option<String>,
+    #[serde(default)]
+    pub decimals: Option<u32>,
+    #[serde(default)]
+    pub usd_price: Option<String>,
 }
 
 /// Route: how the swap is split across liquidity sources.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ZeroXRoute {
+    #[serde(default)]
     pub fills: Vec<ZeroXRouteFill>,
+    #[serde(default)]
     pub tokens: Vec<ZeroXRouteToken>,
 }
 
@@ -161,6 +218,7 @@ pub struct ZeroXFeeItem {
 pub struct ZeroXIssues {
     pub allowance: Option<ZeroXAllowanceIssue>,
     pub balance: Option<ZeroXBalanceIssue>,
+    #[serde(default)]
     pub simulation_incomplete: bool,
     #[serde(default)]
     pub invalid_sources_passed: Vec<String>,
@@ -227,7 +285,10 @@ pub struct ZeroXModule {
     /// Atlas builder fee in bps (default: 1 bps = 0.01%).
     pub fee_bps: u16,
     /// EVM signer for on-chain execution (None = quote-only mode).
-    signer: Option<PrivateKeySigner>,
+    pub(crate) signer: Option<PrivateKeySigner>,
+    /// Every transaction and gasless payload is recorded here before it is
+    /// signed.
+    pub audit: AuditTrail,
 }
 
 /// Parse chain name (e.g. from config) to Chain enum. Falls back to Ethereum if unknown.
@@ -259,6 +320,7 @@ impl ZeroXModule {
             fee_recipient: Some(ATLAS_FEE_WALLET.to_string()),
             fee_bps: BUILDER_FEE_BPS,
             signer: None,
+            audit: AuditTrail::workspace(),
         }
     }
 
@@ -281,6 +343,12 @@ impl ZeroXModule {
         self
     }
 
+    /// Record signed actions in `audit` instead of the workspace database.
+    pub fn with_audit(mut self, audit: AuditTrail) -> Self {
+        self.audit = audit;
+        self
+    }
+
     /// Get the taker address (signer's address) if available.
     pub fn taker_address(&self) -> Option<String> {
         self.signer.as_ref().map(|s| format!("{:?}", s.address()))
@@ -322,6 +390,36 @@ impl ZeroXModule {
         Ok(provider)
     }
 
+    /// Read-only provider on the backend RPC proxy (no signer needed).
+    fn read_provider(&self, chain: &Chain) -> AtlasResult<impl Provider> {
+        let rpc_url: alloy::transports::http::reqwest::Url = self
+            .rpc_url(chain)
+            .parse()
+            .map_err(|e| AtlasError::Other(format!("Invalid RPC URL: {e}")))?;
+        Ok(ProviderBuilder::new().connect_http(rpc_url))
+    }
+
+    /// Native ETH balance of the signer's wallet on `chain`, via a read-only
+    /// provider on the backend RPC proxy.
+    pub async fn native_balance(&self, chain: &Chain) -> AtlasResult<Decimal> {
+        let owner = self
+            .signer
+            .as_ref()
+            .map(|s| s.address())
+            .ok_or_else(|| AtlasError::Auth("No wallet loaded".into()))?;
+
+        let wei = self
+            .read_provider(chain)?
+            .get_balance(owner)
+            .await
+            .map_err(|e| AtlasError::Network(format!("{chain} balance: {e}")))?;
+        u128::try_from(wei)
+            .ok()
+            .and_then(|w| i128::try_from(w).ok())
+            .and_then(|w| Decimal::try_from_i128_with_scale(w, 18).ok())
+            .ok_or_else(|| AtlasError::Other(format!("{chain} balance out of range: {wei}")))
+    }
+
     /// Approve a spender (AllowanceHolder) to spend an ERC20 token.
     /// Approves the exact sell amount from the quote (not unlimited).
     async fn approve_token(
@@ -331,6 +429,102 @@ impl ZeroXModule {
         spender: &str,
         amount: &SwapQuote,
     ) -> AtlasResult<()> {
+        let approve_amount =
+            U256::from_str_radix(&amount.sell_amount.to_string(), 10).unwrap_or(U256::MAX);
+        self.approve(chain, token, spender, approve_amount).await?;
+        Ok(())
+    }
+
+    // ── ERC20 Allowances ────────────────────────────────────────
+
+    /// `eth_call` against `to` with raw calldata.
+    async fn eth_call(&self, chain: &Chain, to: &str, calldata: Vec<u8>) -> AtlasResult<Bytes> {
+        let to: Address = to
+            .parse()
+            .map_err(|e| AtlasError::Other(format!("Invalid contract address: {e}")))?;
+        let tx_req = TransactionRequest::default()
+            .to(to)
+            .input(Bytes::from(calldata).into());
+        self.read_provider(chain)?
+            .call(tx_req)
+            .await
+            .map_err(|e| AtlasError::Network(format!("{chain} eth_call: {e}")))
+    }
+
+    /// ERC20 `allowance(owner, spender)` in base units. `owner` defaults to
+    /// the loaded wallet.
+    pub async fn allowance(
+        &self,
+        chain: &Chain,
+        token: &str,
+        owner: Option<&str>,
+        spender: &str,
+    ) -> AtlasResult<U256> {
+        let owner: Address = match owner {
+            Some(o) => o
+                .parse()
+                .map_err(|e| AtlasError::Other(format!("Invalid owner address: {e}")))?,
+            None => self
+                .signer
+                .as_ref()
+                .map(|s| s.address())
+                .ok_or_else(|| AtlasError::Auth("No wallet loaded".into()))?,
+        };
+        let spender_addr: Address = spender
+            .parse()
+            .map_err(|e| AtlasError::Other(format!("Invalid spender address: {e}")))?;
+
+        // selector: 0xdd62ed3e
+        let mut calldata = Vec::with_capacity(68);
+        calldata.extend_from_slice(&hex::decode("dd62ed3e").unwrap());
+        calldata.extend_from_slice(&[0u8; 12]);
+        calldata.extend_from_slice(owner.as_slice());
+        calldata.extend_from_slice(&[0u8; 12]);
+        calldata.extend_from_slice(spender_addr.as_slice());
+
+        let out = self.eth_call(chain, token, calldata).await?;
+        if out.len() < 32 {
+            return Err(AtlasError::Protocol {
+                protocol: "erc20".into(),
+                message: format!("{token} on {chain} did not answer allowance(); is it an ERC20?"),
+            });
+        }
+        Ok(U256::from_be_slice(&out[..32]))
+    }
+
+    /// ERC20 `decimals()`; 18 for the native token placeholder.
+    pub async fn token_decimals(&self, chain: &Chain, token: &str) -> AtlasResult<u32> {
+        if token.eq_ignore_ascii_case(NATIVE_TOKEN) {
+            return Ok(18);
+        }
+        // selector: 0x313ce567
+        let out = self
+            .eth_call(chain, token, hex::decode("313ce567").unwrap())
+            .await?;
+        if out.len() < 32 {
+            return Err(AtlasError::Protocol {
+                protocol: "erc20".into(),
+                message: format!("{token} on {chain} did not answer decimals(); is it an ERC20?"),
+            });
+        }
+        u32::try_from(U256::from_be_slice(&out[..32]))
+            .ok()
+            .filter(|d| *d <= 77)
+            .ok_or_else(|| AtlasError::Protocol {
+                protocol: "erc20".into(),
+                message: format!("{token} on {chain} reported implausible decimals"),
+            })
+    }
+
+    /// Send ERC20 `approve(spender, amount)` from the loaded wallet and wait
+    /// for the receipt. Returns the transaction hash.
+    pub async fn approve(
+        &self,
+        chain: &Chain,
+        token: &str,
+        spender: &str,
+        amount: U256,
+    ) -> AtlasResult<String> {
         let provider = self.build_provider(chain).await?;
 
         let token_addr: Address = token
@@ -343,49 +537,129 @@ impl ZeroXModule {
 
         // ERC20 approve(address spender, uint256 amount)
         // selector: 0x095ea7b3
-        // Approve exact sell amount (not unlimited — safer)
-        let approve_amount =
-            U256::from_str_radix(&amount.sell_amount.to_string(), 10).unwrap_or(U256::MAX);
-
         let mut calldata = Vec::with_capacity(68);
         calldata.extend_from_slice(&hex::decode("095ea7b3").unwrap());
         // spender address (padded to 32 bytes)
         calldata.extend_from_slice(&[0u8; 12]);
         calldata.extend_from_slice(spender_addr.as_slice());
         // amount (32 bytes, big-endian)
-        calldata.extend_from_slice(&approve_amount.to_be_bytes::<32>());
+        calldata.extend_from_slice(&amount.to_be_bytes::<32>());
 
         let tx_req = TransactionRequest::default()
             .to(token_addr)
             .input(Bytes::from(calldata).into());
 
+        let action_type = if amount.is_zero() {
+            "revoke"
+        } else {
+            "approve"
+        };
+        let audit_id = self.audit_begin(
+            action_type,
+            &serde_json::json!({
+                "chainId": chain_id(chain),
+                "token": token,
+                "spender": spender,
+                "amount": amount.to_string(),
+            }),
+        )?;
+
         info!("Sending ERC20 approve tx for {} → {}", token, spender);
 
+        let result = self.send_and_confirm(&provider, tx_req, "approve").await;
+        self.audit_complete(audit_id, &result);
+        let (tx_hash, confirmed) = result?;
+        if !confirmed {
+            return Err(AtlasError::Protocol {
+                protocol: "0x".into(),
+                message: format!("Token approval reverted for {}", token),
+            });
+        }
+
+        info!("Token approval confirmed for {}", token);
+        Ok(tx_hash)
+    }
+
+    /// Send `tx_req` and wait for its receipt. Returns the transaction hash
+    /// and whether it succeeded; `what` names the transaction in errors.
+    async fn send_and_confirm(
+        &self,
+        provider: &impl Provider,
+        tx_req: TransactionRequest,
+        what: &str,
+    ) -> AtlasResult<(String, bool)> {
         let pending = provider
             .send_transaction(tx_req)
             .await
-            .map_err(|e| AtlasError::Network(format!("Failed to send approve tx: {e}")))?;
+            .map_err(|e| AtlasError::Network(format!("Failed to send {what} tx: {e}")))?;
+        let tx_hash = format!("{:?}", pending.tx_hash());
 
         let receipt = pending
             .get_receipt()
             .await
-            .map_err(|e| AtlasError::Network(format!("Failed to get approve receipt: {e}")))?;
+            .map_err(|e| AtlasError::Network(format!("Failed to get {what} receipt: {e}")))?;
+        Ok((tx_hash, receipt.status()))
+    }
 
-        if !receipt.status() {
-            return Err(AtlasError::Protocol {
-                protocol: "0x".into(),
-                message: format!("Token approval reverted for {}", token),
-            });
-        }
+    /// Write the audit entry for a transaction or payload before it is
+    /// signed. Fails closed: on error nothing may be signed.
+    pub(crate) fn audit_begin(
+        &self,
+        action_type: &str,
+        request: &serde_json::Value,
+    ) -> AtlasResult<i64> {
+        let signer = self.signer.as_ref().ok_or_else(|| {
+            AtlasError::Auth(
+                "No signer available. Import a wallet first: `atlas profile import`".into(),
+            )
+        })?;
+        self.audit.begin(&SignedAction {
+            protocol: "0x",
+            action_type,
+            coin: None,
+            nonce: None,
+            signer: format!("{:?}", signer.address()),
+            request,
+        })
+    }
 
-        info!("Token approval confirmed for {}", token);
-        Ok(())
+    /// Record how a transaction audited as `audit_id` ended: `ok` when its
+    /// receipt succeeded, `rejected` when it reverted.
+    fn audit_complete(&self, audit_id: i64, result: &AtlasResult<(String, bool)>) {
+        let outcome = match result {
+            Ok((tx_hash, confirmed)) => AuditOutcome::Response(
+                serde_json::json!({
+                    "status": if *confirmed { "ok" } else { "err" },
+                    "tx_hash": tx_hash,
+                })
+                .to_string(),
+            ),
+            Err(e) => AuditOutcome::Error(e.to_string()),
+        };
+        self.audit.complete(audit_id, &outcome);
     }
 
     /// GET request to Atlas backend. Sends Authorization when api_key is set.
-    async fn get(&self, path: &str, query: &[(&str, &str)]) -> AtlasResult<serde_json::Value> {
+    pub(crate) async fn get(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> AtlasResult<serde_json::Value> {
+        let url = format!("{}{}", self.backend_url, path);
+        self.send(self.http.get(&url).query(query)).await
+    }
+
+    /// JSON POST to Atlas backend, same auth and error mapping as [`Self::get`].
+    pub(crate) async fn post(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> AtlasResult<serde_json::Value> {
         let url = format!("{}{}", self.backend_url, path);
-        let mut req = self.http.get(&url).query(query);
+        self.send(self.http.post(&url).json(body)).await
+    }
+
+    async fn send(&self, mut req: reqwest::RequestBuilder) -> AtlasResult<serde_json::Value> {
         if let Some(ref key) = self.api_key {
             req = req.header("Authorization", format!("Bearer {}", key));
         }
@@ -455,8 +729,7 @@ impl ZeroXModule {
         }
 
         let val = self.get(&path, &query).await?;
-        serde_json::from_value(val)
-            .map_err(|e| AtlasError::Other(format!("Failed to deserialize 0x price: {e}")))
+        decode_quote(val, "price", chain, sell_token, buy_token)
     }
 
     // ── Quote (firm, commits liquidity) ─────────────────────────
@@ -504,8 +777,7 @@ impl ZeroXModule {
         }
 
         let val = self.get(&path, &query).await?;
-        serde_json::from_value(val)
-            .map_err(|e| AtlasError::Other(format!("Failed to deserialize 0x quote: {e}")))
+        decode_quote(val, "quote", chain, sell_token, buy_token)
     }
 
     // ── Supported Chains ────────────────────────────────────────
@@ -558,13 +830,6 @@ impl SwapModule for ZeroXModule {
             )
             .await?;
 
-        if !resp.liquidity_available {
-            return Err(AtlasError::Protocol {
-                protocol: "0x".into(),
-                message: "No liquidity available for this pair".into(),
-            });
-        }
-
         let buy_amount = resp
             .buy_amount
             .as_deref()
@@ -604,6 +869,35 @@ impl SwapModule for ZeroXModule {
         })
     }
 
+    async fn wallet_balances(&self) -> AtlasResult<Vec<Balance>> {
+        if self.signer.is_none() {
+            return Ok(vec![]);
+        }
+        let results = map_concurrent(
+            WALLET_CHAINS.iter(),
+            WALLET_CHAINS.len(),
+            |chain| async move { (chain, self.native_balance(chain).await) },
+        )
+        .await;
+
+        let mut balances = Vec::new();
+        for (chain, result) in results {
+            let amount = result?;
+            if amount.is_zero() {
+                continue;
+            }
+            balances.push(Balance {
+                protocol: Protocol::ZeroX,
+                chain: chain.clone(),
+                asset: "ETH".into(),
+                total: amount,
+                available: amount,
+                locked: Decimal::ZERO,
+            });
+        }
+        Ok(balances)
+    }
+
     async fn swap(&self, quote: &SwapQuote) -> AtlasResult<String> {
         let signer = self.signer.as_ref().ok_or_else(|| {
             AtlasError::Auth(
@@ -624,13 +918,6 @@ impl SwapModule for ZeroXModule {
             )
             .await?;
 
-        if !firm.liquidity_available {
-            return Err(AtlasError::Protocol {
-                protocol: "0x".into(),
-                message: "No liquidity available for this swap".into(),
-            });
-        }
-
         let tx_data = firm
             .transaction
             .as_ref()
@@ -685,23 +972,26 @@ impl SwapModule for ZeroXModule {
             .input(Bytes::from(data_bytes).into())
             .value(value);
 
-        info!("Sending swap transaction to {}", tx_data.to);
+        let audit_id = self.audit_begin(
+            "swap",
+            &serde_json::json!({
+                "chainId": chain_id(&quote.chain),
+                "sellToken": quote.sell_token,
+                "buyToken": quote.buy_token,
+                "to": tx_data.to,
+                "data": tx_data.data,
+                "value": value.to_string(),
+            }),
+        )?;
 
-        let pending = provider
-            .send_transaction(tx_req)
-            .await
-            .map_err(|e| AtlasError::Network(format!("Failed to send swap tx: {e}")))?;
-
-        let tx_hash = format!("{:?}", pending.tx_hash());
-        info!("Swap tx sent: {}", tx_hash);
+        info!("Sending swap transaction to {}", tx_data.to);
 
-        // 4. Wait for confirmation
-        let receipt = pending
-            .get_receipt()
-            .await
-            .map_err(|e| AtlasError::Network(format!("Failed to get tx receipt: {e}")))?;
+        // 4. Send and wait for confirmation
+        let result = self.send_and_confirm(&provider, tx_req, "swap").await;
+        self.audit_complete(audit_id, &result);
+        let (tx_hash, confirmed) = result?;
 
-        if !receipt.status() {
+        if !confirmed {
             return Err(AtlasError::Protocol {
                 protocol: "0x".into(),
                 message: format!("Swap transaction reverted: {tx_hash}"),
@@ -0,0 +1,663 @@
+//! 0x Gasless API — the taker signs EIP-712 payloads and 0x's relayer
+//! submits the trade, paying gas and taking its fee in the sell token.
+//!
+//! Flow: [`ZeroXModule::gasless_quote`] → [`ZeroXModule::sign_gasless`] →
+//! [`ZeroXModule::gasless_submit`] → poll [`ZeroXModule::gasless_status`].
+//! Only a subset of the swap chains is served; [`ZeroXModule::ensure_gasless`]
+//! checks the chain against `/gasless/chains` first.
+
+use alloy::primitives::{Address, B256, U256};
+use atlas_core::audit::AuditOutcome;
+use atlas_core::error::{AtlasError, AtlasResult};
+use atlas_core::types::Chain;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::client::{
+    chain_id, decode_quote, is_supported, ZeroXChainsResponse, ZeroXModule, ZeroXQuoteResponse,
+    ZEROX_API_BASE,
+};
+use crate::signing::{sign_typed_data, typed_data_hash, ZeroXSignature};
+
+/// `approve(address,uint256)`, the call inside meta-transaction approvals.
+const APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+// ── Response Types ──────────────────────────────────────────────────
+
+/// Gasless price/quote response: the usual quote fields plus, on /quote,
+/// the payloads to sign.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GaslessQuoteResponse {
+    #[serde(flatten)]
+    pub quote: ZeroXQuoteResponse,
+
+    /// Gasless approval (permit / meta-transaction). `None` when the
+    /// allowance is already set, or when the token has no gasless approval —
+    /// then `quote.issues.allowance` is set and an on-chain approve is needed.
+    #[serde(default)]
+    pub approval: Option<GaslessStep>,
+
+    /// The trade itself (only in /quote responses).
+    #[serde(default)]
+    pub trade: Option<GaslessStep>,
+}
+
+/// One payload to sign: its 0x type, hash and EIP-712 typed data.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GaslessStep {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub hash: Option<String>,
+    pub eip712: serde_json::Value,
+}
+
+/// A [`GaslessStep`] with the taker's signature, as `/gasless/submit` takes it.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SignedGaslessStep {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub eip712: serde_json::Value,
+    pub signature: ZeroXSignature,
+}
+
+/// Signed approval (if any) and trade, ready to submit.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GaslessSubmission {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval: Option<SignedGaslessStep>,
+    pub trade: SignedGaslessStep,
+    /// Audit entry of the signatures, completed by
+    /// [`ZeroXModule::gasless_submit`].
+    #[serde(skip)]
+    pub audit_id: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GaslessSubmitResponse {
+    /// Handle for `/gasless/status`; not an on-chain transaction hash.
+    pub trade_hash: String,
+    #[serde(default)]
+    pub zid: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GaslessStatus {
+    /// `pending`, `submitted`, `succeeded`, `confirmed` or `failed`.
+    pub status: String,
+    /// On-chain transactions the relayer sent for the trade.
+    #[serde(default)]
+    pub transactions: Vec<GaslessTransaction>,
+    /// Why the trade failed, when it did.
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub zid: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GaslessTransaction {
+    pub hash: String,
+    #[serde(default)]
+    pub timestamp: Option<u64>,
+}
+
+impl GaslessStatus {
+    /// `confirmed` and `failed` are final; `succeeded` is mined but may
+    /// still be reorged out.
+    pub fn is_final(&self) -> bool {
+        matches!(self.status.as_str(), "confirmed" | "failed")
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self.status.as_str(), "succeeded" | "confirmed")
+    }
+
+    /// Hash of the relayer's latest on-chain transaction, if any.
+    pub fn tx_hash(&self) -> Option<&str> {
+        self.transactions.last().map(|t| t.hash.as_str())
+    }
+}
+
+// ── Module ──────────────────────────────────────────────────────────
+
+impl ZeroXModule {
+    /// Chains served by the gasless API.
+    pub async fn gasless_chains(&self) -> AtlasResult<ZeroXChainsResponse> {
+        let path = format!("{ZEROX_API_BASE}/gasless/chains");
+        let val = self.get(&path, &[]).await?;
+        serde_json::from_value(val)
+            .map_err(|e| AtlasError::Other(format!("Failed to deserialize 0x gasless chains: {e}")))
+    }
+
+    /// Fail with [`AtlasError::UnsupportedChain`] unless 0x relays gasless
+    /// trades on `chain`.
+    pub async fn ensure_gasless(&self, chain: &Chain) -> AtlasResult<()> {
+        let cid = chain_id(chain);
+        let available = is_supported(chain)
+            && self
+                .gasless_chains()
+                .await?
+                .chains
+                .iter()
+                .any(|c| c.chain_id == cid);
+        if available {
+            return Ok(());
+        }
+        Err(AtlasError::UnsupportedChain(format!(
+            "gasless swaps are not available on {chain}; drop --gasless to pay gas yourself"
+        )))
+    }
+
+    /// Indicative gasless price (`taker` optional).
+    pub async fn gasless_price(
+        &self,
+        chain: &Chain,
+        sell_token: &str,
+        buy_token: &str,
+        sell_amount: &str,
+        taker: Option<&str>,
+        slippage_bps: Option<u32>,
+    ) -> AtlasResult<GaslessQuoteResponse> {
+        let query = self.gasless_query(
+            chain,
+            sell_token,
+            buy_token,
+            sell_amount,
+            taker,
+            slippage_bps,
+        );
+        let query: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        let val = self
+            .get(&format!("{ZEROX_API_BASE}/gasless/price"), &query)
+            .await?;
+        decode_quote(val, "gasless price", chain, sell_token, buy_token)
+    }
+
+    /// Firm gasless quote with the approval/trade payloads to sign.
+    pub async fn gasless_quote(
+        &self,
+        chain: &Chain,
+        sell_token: &str,
+        buy_token: &str,
+        sell_amount: &str,
+        taker: &str,
+        slippage_bps: Option<u32>,
+    ) -> AtlasResult<GaslessQuoteResponse> {
+        let query = self.gasless_query(
+            chain,
+            sell_token,
+            buy_token,
+            sell_amount,
+            Some(taker),
+            slippage_bps,
+        );
+        let query: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        let val = self
+            .get(&format!("{ZEROX_API_BASE}/gasless/quote"), &query)
+            .await?;
+        decode_quote(val, "gasless quote", chain, sell_token, buy_token)
+    }
+
+    fn gasless_query(
+        &self,
+        chain: &Chain,
+        sell_token: &str,
+        buy_token: &str,
+        sell_amount: &str,
+        taker: Option<&str>,
+        slippage_bps: Option<u32>,
+    ) -> Vec<(&'static str, String)> {
+        let mut query = vec![
+            ("chainId", chain_id(chain).to_string()),
+            ("sellToken", sell_token.to_string()),
+            ("buyToken", buy_token.to_string()),
+            ("sellAmount", sell_amount.to_string()),
+        ];
+        if let Some(t) = taker {
+            query.push(("taker", t.to_string()));
+        }
+        if let Some(s) = slippage_bps {
+            query.push(("slippageBps", s.to_string()));
+        }
+        // Inject Atlas builder fee
+        if let Some(ref recipient) = self.fee_recipient {
+            query.push(("swapFeeRecipient", recipient.clone()));
+            query.push(("swapFeeBps", self.fee_bps.to_string()));
+        }
+        query
+    }
+
+    /// Sign a firm gasless quote's approval (when present) and trade with
+    /// the loaded wallet.
+    ///
+    /// Nothing is signed unless every payload is for `chain` and matches
+    /// its `hash`, and the approval only lets the quote's allowance spender
+    /// move up to `sell_amount`. A quote that needs an allowance but offers
+    /// no gasless approval is refused: the token has to be approved
+    /// on-chain once first. The payloads are written to the audit log before
+    /// they are signed.
+    pub fn sign_gasless(
+        &self,
+        chain: &Chain,
+        sell_amount: &str,
+        quote: &GaslessQuoteResponse,
+    ) -> AtlasResult<GaslessSubmission> {
+        let signer = self.signer.as_ref().ok_or_else(|| {
+            AtlasError::Auth(
+                "No signer available. Import a wallet first: `atlas profile import`".into(),
+            )
+        })?;
+        let trade = quote
+            .trade
+            .as_ref()
+            .ok_or_else(|| refuse("0x gasless quote did not include a trade to sign".into()))?;
+        let allowance = quote
+            .quote
+            .issues
+            .as_ref()
+            .and_then(|i| i.allowance.as_ref());
+        match (allowance, &quote.approval) {
+            (Some(allowance), None) => {
+                let token = quote
+                    .quote
+                    .sell_token
+                    .as_deref()
+                    .unwrap_or("the sell token");
+                return Err(refuse(format!(
+                    "{token} has no gasless approval; approve {} on-chain once \
+                     (`atlas wallet approve`), then retry",
+                    allowance.spender
+                )));
+            }
+            (Some(allowance), Some(approval)) => {
+                check_step(approval, chain)?;
+                check_approval(approval, &allowance.spender, sell_amount)?;
+            }
+            (None, Some(_)) => {
+                return Err(refuse(
+                    "0x gasless quote includes an approval but no allowance to set".into(),
+                ));
+            }
+            (None, None) => {}
+        }
+        check_step(trade, chain)?;
+
+        let audit_id = self.audit_begin(
+            "gasless",
+            &serde_json::json!({
+                "chainId": chain_id(chain),
+                "approval": quote.approval.as_ref().map(|a| &a.eip712),
+                "trade": trade.eip712,
+            }),
+        )?;
+        let sign = |step: &GaslessStep| -> AtlasResult<SignedGaslessStep> {
+            Ok(SignedGaslessStep {
+                kind: step.kind.clone(),
+                eip712: step.eip712.clone(),
+                signature: sign_typed_data(signer, &step.eip712)?,
+            })
+        };
+        let signed = quote
+            .approval
+            .as_ref()
+            .map(sign)
+            .transpose()
+            .and_then(|approval| Ok((approval, sign(trade)?)));
+        match signed {
+            Ok((approval, trade)) => Ok(GaslessSubmission {
+                approval,
+                trade,
+                audit_id: Some(audit_id),
+            }),
+            Err(e) => {
+                self.audit
+                    .complete(audit_id, &AuditOutcome::Error(e.to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    /// Hand a signed trade to the relayer.
+    pub async fn gasless_submit(
+        &self,
+        chain: &Chain,
+        submission: &GaslessSubmission,
+    ) -> AtlasResult<GaslessSubmitResponse> {
+        let mut body = serde_json::to_value(submission)
+            .map_err(|e| AtlasError::Other(format!("Failed to encode gasless submit: {e}")))?;
+        body["chainId"] = chain_id(chain).into();
+        let result = self
+            .post(&format!("{ZEROX_API_BASE}/gasless/submit"), &body)
+            .await;
+        if let Some(audit_id) = submission.audit_id {
+            let outcome = match &result {
+                Ok(val) => AuditOutcome::Response(val.to_string()),
+                Err(e) => AuditOutcome::Error(e.to_string()),
+            };
+            self.audit.complete(audit_id, &outcome);
+        }
+        serde_json::from_value(result?)
+            .map_err(|e| AtlasError::Other(format!("Failed to deserialize 0x gasless submit: {e}")))
+    }
+
+    /// Relay status of a submitted trade.
+    pub async fn gasless_status(
+        &self,
+        chain: &Chain,
+        trade_hash: &str,
+    ) -> AtlasResult<GaslessStatus> {
+        let cid = chain_id(chain).to_string();
+        let path = format!("{ZEROX_API_BASE}/gasless/status/{trade_hash}");
+        let val = self.get(&path, &[("chainId", &cid)]).await?;
+        serde_json::from_value(val)
+            .map_err(|e| AtlasError::Other(format!("Failed to deserialize 0x gasless status: {e}")))
+    }
+}
+
+// ── Payload checks ──────────────────────────────────────────────────
+
+fn refuse(message: String) -> AtlasError {
+    AtlasError::Protocol {
+        protocol: "0x".into(),
+        message,
+    }
+}
+
+/// A uint sent as a JSON number, decimal string or hex string.
+fn uint(value: &Value) -> Option<U256> {
+    match value {
+        Value::Number(n) => n.as_u64().map(U256::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// The payload must be for `chain`, and match the `hash` 0x sent with it.
+fn check_step(step: &GaslessStep, chain: &Chain) -> AtlasResult<()> {
+    let expected = U256::from(chain_id(chain));
+    let signed_for = step.eip712.pointer("/domain/chainId").and_then(uint);
+    if signed_for != Some(expected) {
+        return Err(refuse(format!(
+            "0x {} payload is for chain {}, not {chain} ({expected}); nothing was signed",
+            step.kind,
+            signed_for.map_or("(none)".into(), |c| c.to_string()),
+        )));
+    }
+    if let Some(hash) = &step.hash {
+        let computed = typed_data_hash(&step.eip712)?;
+        if hash.parse::<B256>().ok() != Some(computed) {
+            return Err(refuse(format!(
+                "0x {} payload does not match its hash {hash}; nothing was signed",
+                step.kind
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The approval may only let `spender` (the quote's allowance spender) move
+/// up to `sell_amount`. Permits carry `spender`/`value` in the message;
+/// meta-transaction approvals carry an encoded `approve` call.
+fn check_approval(step: &GaslessStep, spender: &str, sell_amount: &str) -> AtlasResult<()> {
+    let message = step.eip712.get("message").unwrap_or(&Value::Null);
+    let (approved, amount) = match message.get("functionSignature").and_then(Value::as_str) {
+        Some(call) => {
+            let call = hex::decode(call.trim_start_matches("0x")).unwrap_or_default();
+            if call.len() < 68 || call[..4] != APPROVE_SELECTOR {
+                return Err(refuse(format!(
+                    "0x {} payload is not an approve call; nothing was signed",
+                    step.kind
+                )));
+            }
+            (
+                Some(Address::from_slice(&call[16..36])),
+                Some(U256::from_be_slice(&call[36..68])),
+            )
+        }
+        None => (
+            message
+                .get("spender")
+                .and_then(Value::as_str)
+                .and_then(|a| a.parse().ok()),
+            message.get("value").and_then(uint),
+        ),
+    };
+
+    let expected: Option<Address> = spender.parse().ok();
+    if approved.is_none() || approved != expected {
+        return Err(refuse(format!(
+            "0x {} payload approves {}, not the quote's spender {spender}; nothing was signed",
+            step.kind,
+            approved.map_or("an unknown spender".into(), |a| a.to_string()),
+        )));
+    }
+    let limit = sell_amount
+        .parse::<U256>()
+        .map_err(|_| refuse(format!("Invalid sell amount {sell_amount}")))?;
+    if let Some(amount) = amount.filter(|a| *a > limit) {
+        return Err(refuse(format!(
+            "0x {} payload approves {amount}, more than the {limit} being sold; nothing was signed",
+            step.kind
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+    use atlas_core::audit::AuditTrail;
+    use atlas_core::db::AuditFilter;
+
+    const SPENDER: &str = "0x0000000000001ff3684f28c67538d4d072c22734";
+
+    /// EIP-2612 permit of `value` to `spender` on Base, with its hash.
+    fn permit(spender: &str, value: &str) -> GaslessStep {
+        let eip712 = serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Permit": [
+                    {"name": "owner", "type": "address"},
+                    {"name": "spender", "type": "address"},
+                    {"name": "value", "type": "uint256"},
+                    {"name": "nonce", "type": "uint256"},
+                    {"name": "deadline", "type": "uint256"}
+                ]
+            },
+            "primaryType": "Permit",
+            "domain": {
+                "name": "USD Coin",
+                "chainId": 8453,
+                "verifyingContract": "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"
+            },
+            "message": {
+                "owner": "0x0000000000000000000000000000000000000002",
+                "spender": spender,
+                "value": value,
+                "nonce": "0",
+                "deadline": "1900000000"
+            }
+        });
+        step("permit", eip712)
+    }
+
+    fn step(kind: &str, eip712: Value) -> GaslessStep {
+        GaslessStep {
+            kind: kind.into(),
+            hash: Some(typed_data_hash(&eip712).unwrap().to_string()),
+            eip712,
+        }
+    }
+
+    fn trade(chain_id: u64) -> GaslessStep {
+        let mut eip712 = permit(SPENDER, "1000").eip712;
+        eip712["domain"]["chainId"] = chain_id.into();
+        step("settler_metatransaction", eip712)
+    }
+
+    fn quote(approval: GaslessStep, trade: GaslessStep) -> GaslessQuoteResponse {
+        let mut quote: GaslessQuoteResponse = serde_json::from_value(serde_json::json!({
+            "liquidityAvailable": true,
+            "sellAmount": "1000",
+            "issues": {"allowance": {"actual": "0", "spender": SPENDER}, "balance": null}
+        }))
+        .unwrap();
+        quote.approval = Some(approval);
+        quote.trade = Some(trade);
+        quote
+    }
+
+    fn module() -> ZeroXModule {
+        ZeroXModule::new("http://localhost".into())
+            .with_signer(PrivateKeySigner::random())
+            .with_audit(AuditTrail::in_memory())
+    }
+
+    fn refused(result: AtlasResult<GaslessSubmission>) -> String {
+        match result {
+            Err(AtlasError::Protocol { message, .. }) => message,
+            other => panic!("expected a protocol error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_signs_matching_payloads() {
+        let signed = module()
+            .sign_gasless(
+                &Chain::Base,
+                "1000",
+                &quote(permit(SPENDER, "1000"), trade(8453)),
+            )
+            .unwrap();
+        assert_eq!(signed.approval.unwrap().kind, "permit");
+        assert_eq!(signed.trade.kind, "settler_metatransaction");
+    }
+
+    #[test]
+    fn test_audits_signed_payloads_but_not_refused_ones() {
+        let zerox = module();
+        let refused = quote(permit(SPENDER, "1000"), trade(1));
+        assert!(zerox.sign_gasless(&Chain::Base, "1000", &refused).is_err());
+        let entries = zerox.audit.entries(&AuditFilter::default()).unwrap();
+        assert!(entries.is_empty());
+
+        let signed = zerox
+            .sign_gasless(
+                &Chain::Base,
+                "1000",
+                &quote(permit(SPENDER, "1000"), trade(8453)),
+            )
+            .unwrap();
+        let entries = zerox.audit.entries(&AuditFilter::default()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(Some(entries[0].id), signed.audit_id);
+        assert_eq!(entries[0].protocol, "0x");
+        assert_eq!(entries[0].action_type, "gasless");
+        assert_eq!(entries[0].status, "pending");
+    }
+
+    #[test]
+    fn test_refuses_payload_for_another_chain() {
+        let q = quote(permit(SPENDER, "1000"), trade(1));
+        let message = refused(module().sign_gasless(&Chain::Base, "1000", &q));
+        assert!(message.contains("chain 1"), "{message}");
+
+        // The approval is checked too
+        let q = quote(permit(SPENDER, "1000"), trade(8453));
+        assert!(module().sign_gasless(&Chain::Arbitrum, "1000", &q).is_err());
+    }
+
+    #[test]
+    fn test_refuses_permit_to_another_spender_or_for_more() {
+        let other = "0x00000000000000000000000000000000000000ff";
+        let message = refused(module().sign_gasless(
+            &Chain::Base,
+            "1000",
+            &quote(permit(other, "1000"), trade(8453)),
+        ));
+        assert!(message.contains("not the quote's spender"), "{message}");
+
+        let max = U256::MAX.to_string();
+        let message = refused(module().sign_gasless(
+            &Chain::Base,
+            "1000",
+            &quote(permit(SPENDER, &max), trade(8453)),
+        ));
+        assert!(message.contains("more than the 1000"), "{message}");
+    }
+
+    #[test]
+    fn test_refuses_payload_that_does_not_match_its_hash() {
+        let mut approval = permit(SPENDER, "1000");
+        // Payload swapped after the hash was computed
+        approval.eip712["message"]["deadline"] = "1900000001".into();
+        let message =
+            refused(module().sign_gasless(&Chain::Base, "1000", &quote(approval, trade(8453))));
+        assert!(message.contains("does not match its hash"), "{message}");
+    }
+
+    #[test]
+    fn test_checks_meta_transaction_approve_call() {
+        let call = |spender: &str, amount: u64| {
+            format!(
+                "0x095ea7b3{:0>64}{:064x}",
+                spender.trim_start_matches("0x"),
+                amount
+            )
+        };
+        let meta = |call: String| {
+            let mut eip712 = permit(SPENDER, "0").eip712;
+            eip712["message"] = serde_json::json!({"functionSignature": call});
+            GaslessStep {
+                kind: "executeMetaTransaction::approve".into(),
+                hash: None,
+                eip712,
+            }
+        };
+        let ok = quote(meta(call(SPENDER, 1000)), trade(8453));
+        assert!(module().sign_gasless(&Chain::Base, "1000", &ok).is_ok());
+        let too_much = quote(meta(call(SPENDER, 1001)), trade(8453));
+        assert!(module()
+            .sign_gasless(&Chain::Base, "1000", &too_much)
+            .is_err());
+    }
+
+    #[test]
+    fn test_quote_parses_with_payloads() {
+        let quote: GaslessQuoteResponse = serde_json::from_value(serde_json::json!({
+            "liquidityAvailable": true,
+            "buyAmount": "1000",
+            "sellAmount": "2000",
+            "issues": {"allowance": {"actual": "0", "spender": "0xabc"}, "balance": null},
+            "approval": {"type": "permit", "hash": "0x01", "eip712": {"primaryType": "Permit"}},
+            "trade": {"type": "settler_metatransaction", "hash": "0x02", "eip712": {}}
+        }))
+        .unwrap();
+        assert_eq!(quote.quote.buy_amount.as_deref(), Some("1000"));
+        assert_eq!(quote.approval.unwrap().kind, "permit");
+        assert_eq!(quote.trade.unwrap().kind, "settler_metatransaction");
+    }
+
+    #[test]
+    fn test_status_states() {
+        let status = |s: &str| GaslessStatus {
+            status: s.into(),
+            transactions: vec![],
+            reason: None,
+            zid: None,
+        };
+        assert!(!status("pending").is_final());
+        assert!(status("succeeded").is_success() && !status("succeeded").is_final());
+        assert!(status("confirmed").is_final() && status("confirmed").is_success());
+        assert!(status("failed").is_final() && !status("failed").is_success());
+    }
+}
@@ -1,2 +1,4 @@
 pub mod client;
+pub mod gasless;
+pub mod signing;
 pub use client::*;
@@ -0,0 +1,113 @@
+//! EIP-712 typed-data signing for 0x gasless payloads.
+//!
+//! Unlike Hyperliquid's fixed Agent struct (see `atlas_hl::signing`), 0x
+//! sends the full typed data (types, domain, message) with each quote, so
+//! the payload is hashed as given rather than rebuilt from known fields.
+
+use alloy::dyn_abi::TypedData;
+use alloy::primitives::{Signature, B256};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::SignerSync;
+use atlas_core::error::{AtlasError, AtlasResult};
+use serde::{Deserialize, Serialize};
+
+/// 0x's `signatureType` for an EIP-712 signature.
+const SIGNATURE_TYPE_EIP712: u8 = 2;
+
+/// A signature in the shape 0x's gasless submit endpoint expects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZeroXSignature {
+    pub signature_type: u8,
+    /// 27 or 28.
+    pub v: u8,
+    pub r: String,
+    pub s: String,
+}
+
+impl From<Signature> for ZeroXSignature {
+    fn from(sig: Signature) -> Self {
+        Self {
+            signature_type: SIGNATURE_TYPE_EIP712,
+            v: 27 + u8::from(sig.v()),
+            r: B256::from(sig.r()).to_string(),
+            s: B256::from(sig.s()).to_string(),
+        }
+    }
+}
+
+/// EIP-712 signing hash of a `eth_signTypedData_v4`-style JSON payload.
+pub fn typed_data_hash(eip712: &serde_json::Value) -> AtlasResult<B256> {
+    let typed: TypedData = serde_json::from_value(eip712.clone())
+        .map_err(|e| AtlasError::Other(format!("Invalid EIP-712 payload from 0x: {e}")))?;
+    typed
+        .eip712_signing_hash()
+        .map_err(|e| AtlasError::Other(format!("Cannot hash EIP-712 payload: {e}")))
+}
+
+/// Sign a typed-data payload with the profile key.
+pub fn sign_typed_data(
+    signer: &PrivateKeySigner,
+    eip712: &serde_json::Value,
+) -> AtlasResult<ZeroXSignature> {
+    let hash = typed_data_hash(eip712)?;
+    let sig = signer
+        .sign_hash_sync(&hash)
+        .map_err(|e| AtlasError::Auth(format!("Failed to sign 0x payload: {e}")))?;
+    Ok(sig.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload() -> serde_json::Value {
+        serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Trade": [
+                    {"name": "taker", "type": "address"},
+                    {"name": "amount", "type": "uint256"}
+                ]
+            },
+            "primaryType": "Trade",
+            "domain": {
+                "name": "Settler",
+                "chainId": 8453,
+                "verifyingContract": "0x0000000000000000000000000000000000000001"
+            },
+            "message": {
+                "taker": "0x0000000000000000000000000000000000000002",
+                "amount": "1000000"
+            }
+        })
+    }
+
+    #[test]
+    fn test_signature_recovers_signer() {
+        let signer = PrivateKeySigner::random();
+        let sig = sign_typed_data(&signer, &payload()).unwrap();
+        assert_eq!(sig.signature_type, 2);
+        assert!(sig.v == 27 || sig.v == 28);
+        assert_eq!(sig.r.len(), 66);
+
+        let hash = typed_data_hash(&payload()).unwrap();
+        let r: B256 = sig.r.parse().unwrap();
+        let s: B256 = sig.s.parse().unwrap();
+        let rebuilt = Signature::from_scalars_and_parity(r, s, sig.v == 28);
+        assert_eq!(
+            rebuilt.recover_address_from_prehash(&hash).unwrap(),
+            signer.address()
+        );
+    }
+
+    #[test]
+    fn test_bad_payload_is_an_error() {
+        let signer = PrivateKeySigner::random();
+        assert!(sign_typed_data(&signer, &serde_json::json!({"types": 1})).is_err());
+    }
+}